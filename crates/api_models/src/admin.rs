@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use common_utils::{
     crypto::{Encryptable, OptionalEncryptableName},
     pii,
@@ -95,6 +97,17 @@ pub struct MerchantAccountCreate {
 
     /// The id of the organization to which the merchant belongs to
     pub organization_id: Option<String>,
+
+    /// Default currency of the payments of the merchant, used when the payment request does not pass one. The currency must be accepted by at least one of the connector accounts of the merchant
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments of the merchant, used when the payment request does not pass one
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the API responses to the API keys marked as `pii_restricted` and in the outgoing webhooks
+    pub pii_policy: Option<PiiPolicy>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
@@ -184,6 +197,17 @@ pub struct MerchantAccountUpdate {
     /// To unset this field, pass an empty string
     #[schema(max_length = 64)]
     pub default_profile: Option<String>,
+
+    /// Default currency of the payments of the merchant, used when the payment request does not pass one. The currency must be accepted by at least one of the connector accounts of the merchant
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments of the merchant, used when the payment request does not pass one
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the API responses to the API keys marked as `pii_restricted` and in the outgoing webhooks
+    pub pii_policy: Option<PiiPolicy>,
 }
 
 #[derive(Clone, Debug, ToSchema, Serialize)]
@@ -277,6 +301,17 @@ pub struct MerchantAccountResponse {
     /// A enum value to indicate the status of recon service. By default it is not_requested.
     #[schema(value_type = ReconStatus, example = "not_requested")]
     pub recon_status: enums::ReconStatus,
+
+    /// Default currency of the payments of the merchant, used when the payment request does not pass one. The currency must be accepted by at least one of the connector accounts of the merchant
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments of the merchant, used when the payment request does not pass one
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the API responses to the API keys marked as `pii_restricted` and in the outgoing webhooks
+    pub pii_policy: Option<PiiPolicy>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -436,6 +471,46 @@ pub mod payout_routing_algorithm {
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum RoutingAlgorithm {
     Single(api_enums::RoutableConnectors),
+    /// Routes the payments of a payment method type to the connector chosen for it, such as the
+    /// wallets to one connector and the cards to another
+    PaymentMethodType(PaymentMethodTypeRoutingAlgorithm),
+}
+
+impl RoutingAlgorithm {
+    /// The connector to route a payment of the payment method type to
+    pub fn get_connector(
+        &self,
+        payment_method_type: Option<api_enums::PaymentMethodType>,
+    ) -> api_enums::RoutableConnectors {
+        match self {
+            Self::Single(connector) => *connector,
+            Self::PaymentMethodType(algorithm) => payment_method_type
+                .and_then(|payment_method_type| {
+                    algorithm
+                        .connectors
+                        .iter()
+                        .find(|choice| choice.payment_method_type == payment_method_type)
+                })
+                .map_or(algorithm.default_connector, |choice| choice.connector),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentMethodTypeRoutingAlgorithm {
+    /// The connectors chosen for payment method types, a payment is routed to the first connector
+    /// chosen for its payment method type
+    pub connectors: Vec<PaymentMethodTypeConnectorChoice>,
+    /// The connector of the payments whose payment method type has no connector chosen for it
+    pub default_connector: api_enums::RoutableConnectors,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentMethodTypeConnectorChoice {
+    pub payment_method_type: api_enums::PaymentMethodType,
+    pub connector: api_enums::RoutableConnectors,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -527,6 +602,200 @@ pub struct WebhookDetails {
     /// If this property is true, a webhook message is posted whenever a payment fails
     #[schema(example = true)]
     pub payment_failed_enabled: Option<bool>,
+
+    /// Custom headers sent along with every webhook, such as a static API key expected by the
+    /// webhook endpoint. The header values are stored encrypted and are not returned in responses
+    #[schema(value_type = Option<Object>, example = json!({"x-webhook-key": "9b3c27d0f1"}))]
+    pub custom_headers: Option<HashMap<String, Secret<String>>>,
+
+    /// Authentication used by the webhook endpoint, stored encrypted and not returned in responses
+    pub authentication: Option<WebhookAuthentication>,
+
+    /// PEM encoded client certificate presented to the webhook endpoint over mutual TLS, stored
+    /// encrypted and not returned in responses
+    #[schema(value_type = Option<String>)]
+    pub client_certificate: Option<Secret<String>>,
+
+    /// PEM encoded PKCS#8 private key of the client certificate, stored encrypted and not returned
+    /// in responses
+    #[schema(value_type = Option<String>)]
+    pub client_certificate_key: Option<Secret<String>>,
+
+    /// The subscriptions of the webhook events, each delivering the events matching its event type
+    /// to its own endpoint. When configured, the events are delivered only to the subscriptions
+    /// instead of the webhook url
+    pub subscriptions: Option<Vec<WebhookSubscription>>,
+
+    /// The version of the payloads of the webhooks sent to the webhook url, and to the
+    /// subscriptions without a version of their own. Set to the latest version when the webhook
+    /// details are first configured, and kept on later updates which do not pass it
+    #[schema(value_type = Option<WebhookPayloadVersion>, example = "v2")]
+    pub payload_version: Option<api_enums::WebhookPayloadVersion>,
+}
+
+/// A subscription of the merchant to the webhook events of the given event type
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSubscription {
+    /// The identifier of the subscription, unique among the subscriptions of the merchant
+    #[schema(max_length = 64, example = "refunds_ledger")]
+    pub subscription_id: String,
+
+    /// The event type of the events delivered to the subscription. A pattern ending with `*`
+    /// matches all the event types starting with the rest of the pattern
+    #[schema(example = "refund_*")]
+    pub event_type: String,
+
+    /// The HTTPS url of the endpoint the events are delivered to
+    #[schema(value_type = String, example = "https://ekart.com/webhooks/refunds")]
+    pub url: Secret<String>,
+
+    /// The key used to sign the events delivered to the subscription, stored encrypted and not
+    /// returned in responses. Required when the subscription is created, the key configured
+    /// earlier is kept on later updates which do not pass it
+    #[schema(value_type = Option<String>, example = "whsec_2b5e9c0f1d")]
+    #[serde(default, skip_serializing)]
+    pub secret: Option<Secret<String>>,
+
+    /// The version of the payloads of the events delivered to the subscription. Set to the latest
+    /// version when the subscription is created, and kept on later updates which do not pass it
+    #[schema(value_type = Option<WebhookPayloadVersion>, example = "v2")]
+    pub payload_version: Option<api_enums::WebhookPayloadVersion>,
+}
+
+impl WebhookSubscription {
+    /// Checks whether the event type matches the event type pattern of the subscription
+    pub fn matches(&self, event_type: api_enums::EventType) -> bool {
+        let event_type = event_type.to_string();
+        match self.event_type.strip_suffix('*') {
+            Some(prefix) => event_type.starts_with(prefix),
+            None => self.event_type == event_type,
+        }
+    }
+}
+
+/// Authentication used by the webhook endpoint of the merchant
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum WebhookAuthentication {
+    /// Basic authentication with the given username and password
+    Basic {
+        username: String,
+        #[schema(value_type = String)]
+        password: Secret<String>,
+    },
+    /// Bearer authentication with the given token
+    Bearer {
+        #[schema(value_type = String)]
+        token: Secret<String>,
+    },
+}
+
+/// The parts of the webhook details of a merchant used to authenticate to the webhook endpoint,
+/// which are stored encrypted apart from the rest of the webhook details
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WebhookAuthDetails {
+    pub custom_headers: Option<HashMap<String, Secret<String>>>,
+    pub authentication: Option<WebhookAuthentication>,
+    pub client_certificate: Option<Secret<String>>,
+    pub client_certificate_key: Option<Secret<String>>,
+    /// The keys signing the events delivered to the subscriptions, by the id of the subscription
+    pub subscription_secrets: Option<HashMap<String, Secret<String>>>,
+}
+
+impl WebhookDetails {
+    /// Takes the authentication details out of the webhook details, returning them if any of them
+    /// were passed
+    pub fn take_auth_details(&mut self) -> Option<WebhookAuthDetails> {
+        let subscription_secrets = self
+            .subscriptions
+            .iter_mut()
+            .flatten()
+            .filter_map(|subscription| {
+                subscription
+                    .secret
+                    .take()
+                    .map(|secret| (subscription.subscription_id.clone(), secret))
+            })
+            .collect::<HashMap<_, _>>();
+        let auth_details = WebhookAuthDetails {
+            custom_headers: self.custom_headers.take(),
+            authentication: self.authentication.take(),
+            client_certificate: self.client_certificate.take(),
+            client_certificate_key: self.client_certificate_key.take(),
+            subscription_secrets: (!subscription_secrets.is_empty())
+                .then_some(subscription_secrets),
+        };
+        let is_empty = auth_details.custom_headers.is_none()
+            && auth_details.authentication.is_none()
+            && auth_details.client_certificate.is_none()
+            && auth_details.client_certificate_key.is_none()
+            && auth_details.subscription_secrets.is_none();
+        (!is_empty).then_some(auth_details)
+    }
+
+    /// Pins the versions of the payloads, keeping the versions configured earlier and setting the
+    /// latest version on the webhook details and the subscriptions configured for the first time,
+    /// so that the payloads sent to an endpoint never change without the merchant asking for it
+    pub fn pin_payload_versions(&mut self, previous_webhook_details: Option<&Self>) {
+        let previous_subscriptions = previous_webhook_details
+            .and_then(|webhook_details| webhook_details.subscriptions.as_ref());
+        for subscription in self.subscriptions.iter_mut().flatten() {
+            let previous_payload_version = previous_subscriptions
+                .into_iter()
+                .flatten()
+                .find(|previous| previous.subscription_id == subscription.subscription_id)
+                .map(|previous| previous.payload_version);
+            subscription.payload_version = subscription
+                .payload_version
+                .or(Some(get_pinned_payload_version(previous_payload_version)));
+        }
+
+        let previous_payload_version =
+            previous_webhook_details.map(|webhook_details| webhook_details.payload_version);
+        self.payload_version = self
+            .payload_version
+            .or(Some(get_pinned_payload_version(previous_payload_version)));
+    }
+}
+
+/// The version of the payloads of a webhook configured earlier, the webhooks configured before the
+/// payloads were versioned being on the first version, or the latest version for a webhook
+/// configured for the first time
+fn get_pinned_payload_version(
+    previous_payload_version: Option<Option<api_enums::WebhookPayloadVersion>>,
+) -> api_enums::WebhookPayloadVersion {
+    match previous_payload_version {
+        Some(payload_version) => payload_version.unwrap_or(api_enums::WebhookPayloadVersion::V1),
+        None => api_enums::WebhookPayloadVersion::LATEST,
+    }
+}
+
+/// The fields holding the personal data of the customers to be redacted from the API responses and
+/// the outgoing webhooks. The fields are located by [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901)
+/// into the bodies, a `*` segment matching every element of an array or every member of an object.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PiiPolicy {
+    /// The fields redacted from the responses to the requests made with the API keys marked as `pii_restricted`
+    pub api_response: Option<PiiRedaction>,
+
+    /// The fields redacted from the payloads of the outgoing webhooks
+    pub outgoing_webhook: Option<PiiRedaction>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PiiRedaction {
+    /// The fields whose values are replaced by a mask
+    #[schema(example = json!(["/email", "/customer/email"]))]
+    #[serde(default)]
+    pub masked_fields: Vec<String>,
+
+    /// The fields removed from the bodies
+    #[schema(example = json!(["/phone", "/billing/phone"]))]
+    #[serde(default)]
+    pub removed_fields: Vec<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -550,6 +819,352 @@ pub struct MerchantConnectorId {
     pub merchant_connector_id: String,
 }
 
+/// Request to run a synthetic test payment against a Merchant Connector, to verify its configuration
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorVerifyRequest {
+    /// The test payment is run only against connectors in test mode, unless this flag is set
+    #[serde(default)]
+    pub allow_live: bool,
+
+    /// The currency to be used for the test payment, defaults to the currency from the connector's test data
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<api_enums::Currency>,
+
+    /// The authentication type to be used for the test payment, can be used to verify the 3DS settings of the connector
+    #[schema(value_type = Option<AuthenticationType>, example = "no_three_ds")]
+    pub authentication_type: Option<api_enums::AuthenticationType>,
+
+    /// Store the result of the test payment in the metadata of the Merchant Connector
+    #[serde(default)]
+    pub store_result_in_metadata: bool,
+}
+
+/// Result of a synthetic test payment run against a Merchant Connector
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantConnectorVerifyResponse {
+    /// Unique ID of the Merchant Connector
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: String,
+
+    /// Name of the Connector
+    #[schema(example = "stripe")]
+    pub connector_name: String,
+
+    /// Whether the connector is in test mode
+    #[schema(example = true)]
+    pub test_mode: Option<bool>,
+
+    /// Whether all the steps of the test payment succeeded
+    #[schema(example = true)]
+    pub verified: bool,
+
+    /// Result of the authorization step
+    pub authorization: ConnectorTestStepResult,
+
+    /// Result of the void step, not present if the authorization failed or the connector does not support voids
+    pub void: Option<ConnectorTestStepResult>,
+
+    /// Time at which the test payment was run
+    #[schema(value_type = PrimitiveDateTime, example = "2023-09-28T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub verified_at: time::PrimitiveDateTime,
+}
+
+/// Result of a single step of a synthetic test payment
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectorTestStepResult {
+    /// Whether the step succeeded
+    #[schema(example = true)]
+    pub success: bool,
+
+    /// Status of the payment attempt after the step
+    #[schema(value_type = AttemptStatus, example = "authorized")]
+    pub status: api_enums::AttemptStatus,
+
+    /// Time taken by the connector to respond, in milliseconds
+    #[schema(example = 512)]
+    pub latency_ms: u64,
+
+    /// The HTTP status code returned by the connector
+    #[schema(example = 200)]
+    pub connector_http_status_code: Option<u16>,
+
+    /// The transaction ID returned by the connector
+    pub connector_transaction_id: Option<String>,
+
+    /// The error code returned by the connector
+    pub error_code: Option<String>,
+
+    /// The error message returned by the connector
+    pub error_message: Option<String>,
+}
+
+/// State of the circuit breaker tracking the availability of a connector
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorCircuitState {
+    /// Requests are sent to the connector
+    #[default]
+    Closed,
+    /// Requests are short-circuited without calling the connector
+    Open,
+    /// A limited number of probe requests are sent to the connector to check if it has recovered
+    HalfOpen,
+}
+
+/// Circuit breaker status of a connector, for a particular flow
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorCircuitBreakerStatus {
+    /// Name of the Connector
+    #[schema(example = "stripe")]
+    pub connector_name: String,
+
+    /// The flow for which the circuit is tracked
+    #[schema(example = "Authorize")]
+    pub flow: String,
+
+    /// Current state of the circuit
+    #[schema(example = "closed")]
+    pub state: ConnectorCircuitState,
+
+    /// Number of connector calls in the current window
+    #[schema(example = 20)]
+    pub request_count: u32,
+
+    /// Number of connector calls in the current window which failed due to the connector being unavailable
+    #[schema(example = 2)]
+    pub failure_count: u32,
+
+    /// Unix timestamp (in seconds) at which the circuit was last opened
+    #[schema(example = 1696000000)]
+    pub opened_at: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorCircuitBreakerStatusResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// Circuit breaker status of the connectors configured for the merchant
+    pub circuit_breakers: Vec<ConnectorCircuitBreakerStatus>,
+}
+
+/// Flow of a connector which is affected by a maintenance window of the connector. The syncs of
+/// the payments and refunds, and the webhooks of the connector, are never affected
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorMaintenanceFlow {
+    /// New payments, which are routed to the fallback connectors of the merchant during the window
+    Payment,
+    Capture,
+    Void,
+    Refund,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorMaintenanceWindowRequest {
+    /// Start of the maintenance window, in ISO 8601 format. A timestamp with an offset is converted to UTC
+    #[schema(value_type = PrimitiveDateTime, example = "2023-11-20T02:00:00+05:30")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: time::PrimitiveDateTime,
+
+    /// End of the maintenance window, in ISO 8601 format. A timestamp with an offset is converted to UTC
+    #[schema(value_type = PrimitiveDateTime, example = "2023-11-20T06:00:00+05:30")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: time::PrimitiveDateTime,
+
+    /// The flows of the connector affected by the maintenance
+    #[schema(value_type = Vec<ConnectorMaintenanceFlow>, example = json!(["payment", "refund"]))]
+    pub flows: Vec<ConnectorMaintenanceFlow>,
+
+    /// Message of the connector about the maintenance
+    #[schema(example = "Scheduled maintenance of the acquiring platform")]
+    pub message: Option<String>,
+}
+
+/// Maintenance window of a connector, during which the affected flows are not sent to the connector
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ConnectorMaintenanceWindow {
+    /// The identifier of the maintenance window
+    #[schema(example = "mw_0J2Lz7nBkx7ZpQyF6s2j")]
+    pub window_id: String,
+
+    /// Start of the maintenance window, in UTC
+    #[schema(value_type = PrimitiveDateTime, example = "2023-11-19T20:30:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: time::PrimitiveDateTime,
+
+    /// End of the maintenance window, in UTC
+    #[schema(value_type = PrimitiveDateTime, example = "2023-11-20T00:30:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: time::PrimitiveDateTime,
+
+    /// The flows of the connector affected by the maintenance
+    #[schema(value_type = Vec<ConnectorMaintenanceFlow>, example = json!(["payment", "refund"]))]
+    pub flows: Vec<ConnectorMaintenanceFlow>,
+
+    /// Message of the connector about the maintenance
+    #[schema(example = "Scheduled maintenance of the acquiring platform")]
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorMaintenanceWindowsResponse {
+    /// The name of the connector
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector: api_enums::Connector,
+
+    /// The maintenance windows of the connector which have not ended, in order of their start.
+    /// Windows can overlap, the connector is under maintenance for a flow while any of the windows
+    /// affecting the flow is ongoing
+    pub maintenance_windows: Vec<ConnectorMaintenanceWindow>,
+}
+
+/// The type of the value expected for a field of the connector metadata
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorMetadataFieldType {
+    String,
+    Number,
+    Boolean,
+    Object,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorMetadataField {
+    /// The name of the field in the metadata of the merchant connector account
+    #[schema(example = "merchant_account_id")]
+    pub name: String,
+
+    /// The type of the value expected for the field
+    #[schema(example = "string")]
+    pub field_type: ConnectorMetadataFieldType,
+
+    /// Whether the connector needs the field to process payments
+    #[schema(example = true)]
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorMetadataSchemaResponse {
+    /// The name of the connector
+    #[schema(value_type = Connector, example = "braintree")]
+    pub connector: api_enums::Connector,
+
+    /// The fields expected in the metadata of the merchant connector account. This is empty if the connector does not declare a metadata schema
+    pub fields: Vec<ConnectorMetadataField>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorCapabilitiesResponse {
+    /// The name of the connector
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector: api_enums::Connector,
+
+    /// The payment methods which the connector can process
+    pub payment_methods: Vec<ConnectorSupportedPaymentMethod>,
+
+    /// The currencies which the connector can process. This is not present if the connector can process any currency
+    #[schema(value_type = Option<Vec<Currency>>, example = json!(["USD", "CAD"]))]
+    pub currencies: Option<Vec<api_enums::Currency>>,
+
+    /// The countries of the customers which the connector can process payments for. This is not present if the connector can process payments for any country
+    #[schema(value_type = Option<Vec<CountryAlpha2>>, example = json!(["US", "CA"]))]
+    pub countries: Option<Vec<api_enums::CountryAlpha2>>,
+
+    /// The capture methods which the connector supports
+    #[schema(value_type = Vec<CaptureMethod>, example = json!(["automatic", "manual"]))]
+    pub capture_methods: Vec<api_enums::CaptureMethod>,
+
+    /// Whether the connector supports refunds
+    pub refunds_supported: bool,
+
+    /// Whether the connector supports mandates for recurring payments
+    pub mandates_supported: bool,
+
+    /// Whether the connector can split a payment between the platform and a sub-account
+    pub split_payments_supported: bool,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorSupportedPaymentMethod {
+    /// The payment method
+    #[schema(value_type = PaymentMethod, example = "card")]
+    pub payment_method: api_enums::PaymentMethod,
+
+    /// The payment method types of the payment method which the connector can process
+    #[schema(value_type = Vec<PaymentMethodType>, example = json!(["credit", "debit"]))]
+    pub payment_method_types: Vec<api_enums::PaymentMethodType>,
+}
+
+/// Conditions of a 3DS decision rule, the rule matches a payment only if all the configured conditions are satisfied
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ThreeDsDecisionConditions {
+    /// The rule matches payments with an amount (in the lowest denomination of the currency) greater than this value
+    #[schema(example = 15000)]
+    pub amount_greater_than: Option<i64>,
+
+    /// The rule matches payments with an amount (in the lowest denomination of the currency) less than this value
+    #[schema(example = 3000)]
+    pub amount_less_than: Option<i64>,
+
+    /// The rule matches payments made in one of these currencies
+    #[schema(value_type = Option<Vec<Currency>>, example = json!(["EUR"]))]
+    pub currencies: Option<Vec<api_enums::Currency>>,
+
+    /// The rule matches payments made with a card issued outside the billing country when true, and inside the billing country when false
+    #[schema(example = true)]
+    pub card_issuer_outside_billing_country: Option<bool>,
+
+    /// The rule matches payments made with a saved card when true, and with a new card when false
+    #[schema(example = false)]
+    pub saved_card: Option<bool>,
+}
+
+/// A merchant configured rule deciding whether a card payment is authenticated with 3DS
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ThreeDsDecisionRule {
+    /// Name of the rule, unique among the rules of the merchant
+    #[schema(example = "force_3ds_above_150_eur")]
+    pub name: String,
+
+    /// Conditions to be satisfied by the payment for the rule to match
+    #[serde(default)]
+    pub conditions: ThreeDsDecisionConditions,
+
+    /// The authentication decision taken when the rule matches
+    #[schema(value_type = ThreeDsDecision, example = "force")]
+    pub decision: api_enums::ThreeDsDecision,
+
+    /// The SCA exemption requested from the issuer, allowed only with the `skip` decision
+    #[schema(value_type = Option<ScaExemptionType>, example = "low_value")]
+    pub sca_exemption: Option<api_enums::ScaExemptionType>,
+}
+
+/// The 3DS decision rules of the merchant, evaluated in order on confirm. The first matching rule decides the authentication type of the payment.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ThreeDsDecisionRulesRequest {
+    /// The rules to be evaluated, in order of priority
+    pub rules: Vec<ThreeDsDecisionRule>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ThreeDsDecisionRulesResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The rules evaluated on confirm, in order of priority
+    pub rules: Vec<ThreeDsDecisionRule>,
+}
+
 /// Create a new Merchant Connector for the merchant account. The connector could be a payment processor / facilitator / acquirer or specialized services like Fraud / Accounting etc."
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(deny_unknown_fields)]
@@ -560,10 +1175,9 @@ pub struct MerchantConnectorCreate {
     /// Name of the Connector
     #[schema(value_type = Connector, example = "stripe")]
     pub connector_name: api_enums::Connector,
-    // /// Connector label for specific country and Business
-    #[serde(skip_deserializing)]
-    #[schema(example = "stripe_US_travel")]
-    pub connector_label: String,
+    /// Label of the connector account, unique among the connector accounts of the merchant. Made of letters, digits, `_` and `-`, up to 64 characters. When not provided, the label is generated from the connector name and the business details, with a numeric suffix if the generated label is already used, like `stripe_US_default_2`
+    #[schema(example = "stripe_US_travel", max_length = 64)]
+    pub connector_label: Option<String>,
 
     /// Unique ID of the connector
     #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
@@ -633,6 +1247,14 @@ pub struct MerchantConnectorCreate {
     pub connector_webhook_details: Option<MerchantConnectorWebhookDetails>,
     /// Identifier for the business profile, if not provided default will be chosen from merchant account
     pub profile_id: Option<String>,
+
+    /// Flows which are rejected for the payments processed through this connector account, while new payments continue to be processed. Incoming webhooks for existing payments and refunds are still processed
+    #[schema(value_type = Option<Vec<ConnectorFlow>>, example = json!(["refund"]))]
+    pub disabled_flows: Option<Vec<api_enums::ConnectorFlow>>,
+
+    /// Register the webhook endpoint of this merchant connector with the connector, storing the id and the secret of the created endpoint in the webhook details. Supported only for the connectors which allow managing webhook endpoints through their API
+    #[schema(example = true)]
+    pub register_webhook_endpoint: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -642,6 +1264,37 @@ pub struct MerchantConnectorWebhookDetails {
     pub merchant_secret: Secret<String>,
     #[schema(value_type = String, example = "12345678900987654321")]
     pub additional_secret: Option<Secret<String>>,
+    /// Identifier of the webhook endpoint registered with the connector, set when the endpoint is registered by us
+    #[schema(example = "we_1NnmKq2eZvKYlo2C0jVbwqsv")]
+    pub connector_webhook_endpoint_id: Option<String>,
+}
+
+/// Status of the webhook endpoint registered with the connector for a merchant connector
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEndpointRegistrationStatus {
+    Registered,
+    Updated,
+    Deleted,
+    /// The connector could not be reached or returned an error, the operation will be retried in the background
+    RetryScheduled,
+    /// The operation failed and could not be scheduled for a retry
+    Failed,
+}
+
+/// Result of registering, updating or deleting the webhook endpoint with the connector
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEndpointRegistration {
+    /// Status of the webhook endpoint
+    #[schema(example = "registered")]
+    pub status: WebhookEndpointRegistrationStatus,
+
+    /// Identifier of the webhook endpoint registered with the connector
+    #[schema(example = "we_1NnmKq2eZvKYlo2C0jVbwqsv")]
+    pub connector_webhook_endpoint_id: Option<String>,
+
+    /// The error returned by the connector, if the operation failed and is being retried
+    pub error_message: Option<String>,
 }
 
 /// Response of creating a new Merchant Connector for the merchant account."
@@ -654,7 +1307,7 @@ pub struct MerchantConnectorResponse {
     /// Name of the Connector
     #[schema(example = "stripe")]
     pub connector_name: String,
-    // /// Connector label for specific country and Business
+    /// Label of the connector account, as provided in the request or generated at creation
     #[serde(skip_deserializing)]
     #[schema(example = "stripe_US_travel")]
     pub connector_label: Option<String>,
@@ -736,6 +1389,19 @@ pub struct MerchantConnectorResponse {
     pub profile_id: Option<String>,
     /// identifier for the verified domains of a particular connector account
     pub applepay_verified_domains: Option<Vec<String>>,
+
+    /// Flows which are rejected for the payments processed through this connector account
+    #[schema(value_type = Option<Vec<ConnectorFlow>>, example = json!(["refund"]))]
+    pub disabled_flows: Option<Vec<api_enums::ConnectorFlow>>,
+
+    /// Time until which the webhooks signed with the credentials replaced in the last rotation are accepted, present only while the rotation is not finalized
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub previous_credentials_expire_at: Option<time::PrimitiveDateTime>,
+
+    /// Result of the registration of the webhook endpoint with the connector, present only if the endpoint was registered, updated or deleted by this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_endpoint_registration: Option<WebhookEndpointRegistration>,
 }
 
 /// Create a new Merchant Connector for the merchant account. The connector could be a payment processor / facilitator / acquirer or specialized services like Fraud / Accounting etc."
@@ -750,6 +1416,10 @@ pub struct MerchantConnectorUpdate {
     #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretApiKey" }))]
     pub connector_account_details: Option<pii::SecretSerdeValue>,
 
+    /// New account details of the Connector, which replace the current account details for the requests to the connector immediately. The current account details and webhook details are kept to verify the webhooks signed with them, until the rotation is finalized or the configured grace period ends. Cannot be passed along with `connector_account_details`
+    #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyNewSecretApiKey" }))]
+    pub connector_account_details_new: Option<pii::SecretSerdeValue>,
+
     /// A boolean value to indicate if the connector is in Test mode. By default, its value is false.
     #[schema(default = false, example = false)]
     pub test_mode: Option<bool>,
@@ -805,6 +1475,136 @@ pub struct MerchantConnectorUpdate {
         }
     }))]
     pub connector_webhook_details: Option<MerchantConnectorWebhookDetails>,
+
+    /// Flows to be rejected for the payments processed through this connector account, while new payments continue to be processed. Pass an empty list to enable all the flows again
+    #[schema(value_type = Option<Vec<ConnectorFlow>>, example = json!(["refund"]))]
+    pub disabled_flows: Option<Vec<api_enums::ConnectorFlow>>,
+
+    /// Register the webhook endpoint with the connector when true, delete the registered endpoint when false. When not passed, an endpoint registered earlier is updated
+    #[schema(example = true)]
+    pub register_webhook_endpoint: Option<bool>,
+}
+
+/// Configuration of the connector accounts of a merchant, without their credentials, which can be
+/// applied to other merchants
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorTemplate {
+    /// The connector accounts of the template, identified by their labels
+    pub connectors: Vec<MerchantConnectorTemplateEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorTemplateEntry {
+    /// Type of the Connector for the financial use case. Could range from Payments to Accounting to Banking.
+    #[schema(value_type = ConnectorType, example = "payment_processor")]
+    pub connector_type: api_enums::ConnectorType,
+
+    /// Name of the Connector
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector_name: api_enums::Connector,
+
+    /// Label of the connector account. Importing the template updates the connector account of the merchant with this label, or creates it if the merchant has none
+    #[schema(example = "stripe_US_travel", max_length = 64)]
+    pub connector_label: String,
+
+    /// Account details of the Connector, in which every `{{variable}}` placeholder is substituted with the value of the variable on import. In an exported template, every credential is a placeholder
+    #[schema(value_type = Object, example = json!({ "auth_type": "HeaderKey", "api_key": "{{stripe_US_travel_api_key}}" }))]
+    pub connector_account_details: pii::SecretSerdeValue,
+
+    /// A boolean value to indicate if the connector is in Test mode
+    #[schema(example = false)]
+    pub test_mode: Option<bool>,
+
+    /// A boolean value to indicate if the connector is disabled
+    #[schema(example = false)]
+    pub disabled: Option<bool>,
+
+    /// Details of all the payment methods enabled for the connector
+    pub payment_methods_enabled: Option<Vec<PaymentMethodsEnabled>>,
+
+    /// Metadata of the connector account
+    #[schema(value_type = Option<Object>, example = json!({ "city": "NY", "unit": "245" }))]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The frm configs of the connector account
+    pub frm_configs: Option<Vec<FrmConfigs>>,
+
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub business_country: Option<api_enums::CountryAlpha2>,
+
+    pub business_label: Option<String>,
+
+    /// Business Sub label of the merchant
+    #[schema(example = "chase")]
+    pub business_sub_label: Option<String>,
+
+    /// Flows which are rejected for the payments processed through the connector account
+    #[schema(value_type = Option<Vec<ConnectorFlow>>, example = json!(["refund"]))]
+    pub disabled_flows: Option<Vec<api_enums::ConnectorFlow>>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorTemplateImportRequest {
+    /// The template to apply to the merchant
+    pub template: MerchantConnectorTemplate,
+
+    /// Values of the variables of the placeholders in the account details of the template
+    #[schema(value_type = HashMap<String, String>, example = json!({ "stripe_US_travel_api_key": "sk_test_MyVerySecretApiKey" }))]
+    #[serde(default)]
+    pub variables: HashMap<String, Secret<String>>,
+
+    /// Report the connector accounts which would be created or updated, without creating or updating them
+    #[schema(default = false, example = false)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Outcome of the import of an entry of a template
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MerchantConnectorTemplateImportStatus {
+    /// The connector account was created, or would be created on a dry run
+    Created,
+    /// The connector account was updated, or would be updated on a dry run
+    Updated,
+    /// The connector account already matches the entry
+    Unchanged,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct MerchantConnectorTemplateImportResult {
+    /// Label of the connector account of the entry
+    #[schema(example = "stripe_US_travel")]
+    pub connector_label: String,
+
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector_name: api_enums::Connector,
+
+    pub status: MerchantConnectorTemplateImportStatus,
+
+    /// Unique ID of the connector account, not present when the connector account is not created
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+
+    /// Reason the entry could not be imported
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct MerchantConnectorTemplateImportResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// Whether the import was a dry run, which did not create or update any connector account
+    pub dry_run: bool,
+
+    /// Outcome of the import of each entry of the template, in the order of the template
+    pub results: Vec<MerchantConnectorTemplateImportResult>,
 }
 
 ///Details of FrmConfigs are mentioned here... it should be passed in payment connector create api call, and stored in merchant_connector_table
@@ -900,6 +1700,9 @@ pub struct MerchantConnectorDeleteResponse {
     /// If the connector is deleted or not
     #[schema(example = false)]
     pub deleted: bool,
+    /// Result of the deletion of the webhook endpoint registered with the connector, present only if an endpoint was registered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_endpoint_registration: Option<WebhookEndpointRegistration>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -1016,6 +1819,52 @@ pub struct BusinessProfileCreate {
 
     /// Verified applepay domains for a particular profile
     pub applepay_verified_domains: Option<Vec<String>>,
+
+    /// Default statement descriptor name used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Hyperswitch Router")]
+    pub statement_descriptor_name: Option<String>,
+
+    /// Default statement descriptor suffix used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub statement_descriptor_suffix: Option<String>,
+
+    /// Default time in seconds after which an unconfirmed payment created under this profile is cancelled, when not passed in the payment request
+    #[schema(minimum = 60, maximum = 604800, example = 86400)]
+    pub session_expiry: Option<u32>,
+
+    /// Default locale of the shoppers in BCP-47 format, used by connectors to localize their hosted and 3DS challenge pages, when neither the payment request nor the shopper's browser specify one
+    #[schema(max_length = 35, example = "fr-FR")]
+    pub default_locale: Option<String>,
+
+    /// Default description used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub default_payment_description: Option<String>,
+
+    /// Format of the reference of the payment sent to the connectors. The placeholders `{merchant_order_reference}`, `{payment_id}` and `{attempt}` are replaced with the merchant order reference id passed in the payment request, the payment id and the ordinal of the attempt. The attempt ordinal is appended when the format does not include it, and the reference is truncated to the length accepted by the connector. The webhooks of connectors which identify the payment only by this reference are not matched to the payment when a format is configured
+    #[schema(max_length = 255, example = "{merchant_order_reference}")]
+    pub connector_request_reference_format: Option<String>,
+
+    /// What is done when a payment is created with the merchant order reference id of a payment created earlier within the duplicate order reference window, which has not failed or been cancelled. Payments are not checked for duplicates when it is not configured
+    #[schema(value_type = Option<DuplicateOrderReferenceBehaviour>, example = "reject")]
+    pub duplicate_order_reference_behaviour: Option<api_enums::DuplicateOrderReferenceBehaviour>,
+
+    /// Time in seconds before the creation of a payment within which the payments created with the same merchant order reference id are considered duplicates of it, 86400 seconds if not configured
+    #[schema(minimum = 1, maximum = 2592000, example = 86400)]
+    pub duplicate_order_reference_window: Option<u32>,
+    /// Payments for which a chargeback prevention alert is received are refunded automatically when they have succeeded and their amount, in the lowest denomination of the currency of the payment, is at most this amount. Payments are not refunded automatically on alerts when it is not configured
+    #[schema(minimum = 1, example = 5000)]
+    pub alert_auto_refund_max_amount: Option<i64>,
+
+    /// Default currency of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account. The currency must be accepted by at least one of the connector accounts of the profile
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the outgoing webhooks of the payments made under this profile. Takes precedence over the policy of the merchant account
+    pub pii_policy: Option<PiiPolicy>,
 }
 
 #[derive(Clone, Debug, ToSchema, Serialize)]
@@ -1080,6 +1929,52 @@ pub struct BusinessProfileResponse {
 
     /// Verified applepay domains for a particular profile
     pub applepay_verified_domains: Option<Vec<String>>,
+
+    /// Default statement descriptor name used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Hyperswitch Router")]
+    pub statement_descriptor_name: Option<String>,
+
+    /// Default statement descriptor suffix used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub statement_descriptor_suffix: Option<String>,
+
+    /// Default time in seconds after which an unconfirmed payment created under this profile is cancelled, when not passed in the payment request
+    #[schema(minimum = 60, maximum = 604800, example = 86400)]
+    pub session_expiry: Option<i64>,
+
+    /// Default locale of the shoppers in BCP-47 format, used by connectors to localize their hosted and 3DS challenge pages, when neither the payment request nor the shopper's browser specify one
+    #[schema(max_length = 35, example = "fr-FR")]
+    pub default_locale: Option<String>,
+
+    /// Default description used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub default_payment_description: Option<String>,
+
+    /// Format of the reference of the payment sent to the connectors. The placeholders `{merchant_order_reference}`, `{payment_id}` and `{attempt}` are replaced with the merchant order reference id passed in the payment request, the payment id and the ordinal of the attempt. The attempt ordinal is appended when the format does not include it, and the reference is truncated to the length accepted by the connector. The webhooks of connectors which identify the payment only by this reference are not matched to the payment when a format is configured
+    #[schema(max_length = 255, example = "{merchant_order_reference}")]
+    pub connector_request_reference_format: Option<String>,
+
+    /// What is done when a payment is created with the merchant order reference id of a payment created earlier within the duplicate order reference window, which has not failed or been cancelled. Payments are not checked for duplicates when it is not configured
+    #[schema(value_type = Option<DuplicateOrderReferenceBehaviour>, example = "reject")]
+    pub duplicate_order_reference_behaviour: Option<api_enums::DuplicateOrderReferenceBehaviour>,
+
+    /// Time in seconds before the creation of a payment within which the payments created with the same merchant order reference id are considered duplicates of it, 86400 seconds if not configured
+    #[schema(minimum = 1, maximum = 2592000, example = 86400)]
+    pub duplicate_order_reference_window: Option<i64>,
+    /// Payments for which a chargeback prevention alert is received are refunded automatically when they have succeeded and their amount, in the lowest denomination of the currency of the payment, is at most this amount. Payments are not refunded automatically on alerts when it is not configured
+    #[schema(minimum = 1, example = 5000)]
+    pub alert_auto_refund_max_amount: Option<i64>,
+
+    /// Default currency of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account. The currency must be accepted by at least one of the connector accounts of the profile
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the outgoing webhooks of the payments made under this profile. Takes precedence over the policy of the merchant account
+    pub pii_policy: Option<PiiPolicy>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema)]
@@ -1137,4 +2032,50 @@ pub struct BusinessProfileUpdate {
 
     /// Verified applepay domains for a particular profile
     pub applepay_verified_domains: Option<Vec<String>>,
+
+    /// Default statement descriptor name used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Hyperswitch Router")]
+    pub statement_descriptor_name: Option<String>,
+
+    /// Default statement descriptor suffix used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub statement_descriptor_suffix: Option<String>,
+
+    /// Default time in seconds after which an unconfirmed payment created under this profile is cancelled, when not passed in the payment request
+    #[schema(minimum = 60, maximum = 604800, example = 86400)]
+    pub session_expiry: Option<u32>,
+
+    /// Default locale of the shoppers in BCP-47 format, used by connectors to localize their hosted and 3DS challenge pages, when neither the payment request nor the shopper's browser specify one
+    #[schema(max_length = 35, example = "fr-FR")]
+    pub default_locale: Option<String>,
+
+    /// Default description used for payments made under this profile, when not passed in the payment request
+    #[schema(max_length = 255, example = "Payment for shoes purchase")]
+    pub default_payment_description: Option<String>,
+
+    /// Format of the reference of the payment sent to the connectors. The placeholders `{merchant_order_reference}`, `{payment_id}` and `{attempt}` are replaced with the merchant order reference id passed in the payment request, the payment id and the ordinal of the attempt. The attempt ordinal is appended when the format does not include it, and the reference is truncated to the length accepted by the connector. The webhooks of connectors which identify the payment only by this reference are not matched to the payment when a format is configured
+    #[schema(max_length = 255, example = "{merchant_order_reference}")]
+    pub connector_request_reference_format: Option<String>,
+
+    /// What is done when a payment is created with the merchant order reference id of a payment created earlier within the duplicate order reference window, which has not failed or been cancelled. Payments are not checked for duplicates when it is not configured
+    #[schema(value_type = Option<DuplicateOrderReferenceBehaviour>, example = "reject")]
+    pub duplicate_order_reference_behaviour: Option<api_enums::DuplicateOrderReferenceBehaviour>,
+
+    /// Time in seconds before the creation of a payment within which the payments created with the same merchant order reference id are considered duplicates of it, 86400 seconds if not configured
+    #[schema(minimum = 1, maximum = 2592000, example = 86400)]
+    pub duplicate_order_reference_window: Option<u32>,
+    /// Payments for which a chargeback prevention alert is received are refunded automatically when they have succeeded and their amount, in the lowest denomination of the currency of the payment, is at most this amount. Payments are not refunded automatically on alerts when it is not configured
+    #[schema(minimum = 1, example = 5000)]
+    pub alert_auto_refund_max_amount: Option<i64>,
+
+    /// Default currency of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account. The currency must be accepted by at least one of the connector accounts of the profile
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub default_currency: Option<api_enums::Currency>,
+
+    /// Default country of the billing address of the payments made under this profile, used when the payment request does not pass one. Takes precedence over the default of the merchant account
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub default_country: Option<api_enums::CountryAlpha2>,
+
+    /// The policy for the personal data of the customers in the outgoing webhooks of the payments made under this profile. Takes precedence over the policy of the merchant account
+    pub pii_policy: Option<PiiPolicy>,
 }
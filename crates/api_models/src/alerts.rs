@@ -0,0 +1,62 @@
+use common_enums::{AlertAction, AlertStatus, AlertType};
+use serde::Serialize;
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// A chargeback prevention alert raised about a payment, which is sent to the merchant before a
+/// chargeback is raised for it
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AlertResponse {
+    /// The identifier of the alert
+    #[schema(max_length = 64, example = "alert_7YbFdx3CAxMzv1LQqh5D")]
+    pub alert_id: String,
+
+    /// The identifier of the merchant account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier of the payment the alert was raised about
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The identifier of the payment attempt the alert was raised about
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4_1")]
+    pub attempt_id: String,
+
+    /// The connector which sent the alert
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// The identifier of the alert at the connector
+    #[schema(example = "issfr_1NnrwHBw2dPENLoi9lnhV3RQ")]
+    pub connector_alert_id: String,
+
+    /// The kind of the alert
+    #[schema(value_type = AlertType, example = "fraud")]
+    pub alert_type: AlertType,
+
+    /// The party which raised the alert, such as the issuer or the alert network
+    #[schema(example = "issuer")]
+    pub initiated_by: Option<String>,
+
+    /// The reason of the alert sent by the connector
+    #[schema(example = "made_with_stolen_card")]
+    pub connector_reason: Option<String>,
+
+    /// The status of the alert
+    #[schema(value_type = AlertStatus, example = "actioned")]
+    pub status: AlertStatus,
+
+    /// The action taken automatically on the alert
+    #[schema(value_type = Option<AlertAction>, example = "refund")]
+    pub action: Option<AlertAction>,
+
+    /// The identifier of the refund created by the action of the alert
+    #[schema(max_length = 64, example = "ref_mbabizu24mvu3mela5njyhpit4")]
+    pub refund_id: Option<String>,
+
+    /// The time at which the alert was received
+    #[schema(example = "2023-10-09T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
@@ -1,3 +1,4 @@
+use common_enums::ApiKeyPermission;
 use common_utils::custom_serde;
 use masking::StrongSecret;
 use serde::{Deserialize, Serialize};
@@ -23,6 +24,28 @@ pub struct CreateApiKeyRequest {
     /// rotating your keys once every 6 months.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
+
+    /// The permissions granted to the API Key. If not provided, the API Key is granted all the
+    /// permissions.
+    #[schema(value_type = Option<Vec<ApiKeyPermission>>, example = json!(["payments_read"]))]
+    pub permissions: Option<Vec<ApiKeyPermission>>,
+
+    /// Whether the API Key is a live key. The payments created with a test key are only processed
+    /// by the merchant connector accounts in test mode. If not provided, a live key is created in
+    /// the production environment and a test key otherwise.
+    #[schema(example = true)]
+    pub livemode: Option<bool>,
+
+    /// Whether the requests authenticated with the API Key are rejected when they contain fields
+    /// which are not part of the request. If not provided, the unknown fields are ignored.
+    #[schema(example = false)]
+    pub strict_request_validation: Option<bool>,
+
+    /// Whether the personal data of the customers is redacted from the responses to the requests
+    /// authenticated with the API Key, as configured in the PII policy of the merchant. If not
+    /// provided, the responses are not redacted.
+    #[schema(example = false)]
+    pub pii_restricted: Option<bool>,
 }
 
 /// The response body for creating an API Key.
@@ -60,6 +83,24 @@ pub struct CreateApiKeyResponse {
     /// The expiration date for the API Key.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
+
+    /// The permissions granted to the API Key.
+    #[schema(value_type = Vec<ApiKeyPermission>, example = json!(["payments_read"]))]
+    pub permissions: Vec<ApiKeyPermission>,
+
+    /// Whether the API Key is a live key.
+    #[schema(example = true)]
+    pub livemode: bool,
+
+    /// Whether the requests authenticated with the API Key are rejected when they contain unknown
+    /// fields.
+    #[schema(example = false)]
+    pub strict_request_validation: bool,
+
+    /// Whether the personal data of the customers is redacted from the responses to the requests
+    /// authenticated with the API Key.
+    #[schema(example = false)]
+    pub pii_restricted: bool,
     /*
     /// The date and time indicating when the API Key was last used.
     #[schema(example = "2022-09-10T10:11:12Z")]
@@ -102,6 +143,24 @@ pub struct RetrieveApiKeyResponse {
     /// The expiration date for the API Key.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
+
+    /// The permissions granted to the API Key.
+    #[schema(value_type = Vec<ApiKeyPermission>, example = json!(["payments_read"]))]
+    pub permissions: Vec<ApiKeyPermission>,
+
+    /// Whether the API Key is a live key.
+    #[schema(example = true)]
+    pub livemode: bool,
+
+    /// Whether the requests authenticated with the API Key are rejected when they contain unknown
+    /// fields.
+    #[schema(example = false)]
+    pub strict_request_validation: bool,
+
+    /// Whether the personal data of the customers is redacted from the responses to the requests
+    /// authenticated with the API Key.
+    #[schema(example = false)]
+    pub pii_restricted: bool,
     /*
     /// The date and time indicating when the API Key was last used.
     #[schema(example = "2022-09-10T10:11:12Z")]
@@ -129,6 +188,10 @@ pub struct UpdateApiKeyRequest {
     /// rotating your keys once every 6 months.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: Option<ApiKeyExpiration>,
+
+    /// The permissions to be granted to the API Key, replacing the existing permissions.
+    #[schema(value_type = Option<Vec<ApiKeyPermission>>, example = json!(["payments_read"]))]
+    pub permissions: Option<Vec<ApiKeyPermission>>,
 }
 
 /// The response body for revoking an API Key.
@@ -0,0 +1,87 @@
+use common_enums::{AuditAction, AuditResourceType};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// A change made to a resource of a merchant through the admin API
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    /// The identifier of the audit event
+    #[schema(max_length = 64, example = "audit_7YbFdx3CAxMzv1LQqh5D")]
+    pub audit_event_id: String,
+
+    /// The identifier of the merchant account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier of the key which authenticated the request that made the change
+    #[schema(max_length = 64, example = "admin_api_key")]
+    pub actor_key_id: String,
+
+    /// The kind of resource that was changed
+    #[schema(value_type = AuditResourceType, example = "merchant_connector_account")]
+    pub resource_type: AuditResourceType,
+
+    /// The identifier of the resource that was changed
+    #[schema(max_length = 64, example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub resource_id: String,
+
+    /// The change made to the resource
+    #[schema(value_type = AuditAction, example = "updated")]
+    pub action: AuditAction,
+
+    /// The changed fields of the resource, each with its value before and after the change. The
+    /// values of the secret fields are masked
+    #[schema(value_type = Object, example = json!({ "disabled": { "before": false, "after": true } }))]
+    pub diff: serde_json::Value,
+
+    /// The identifier of the request that made the change
+    #[schema(max_length = 64, example = "018b6f3d-1f6e-7b9a-a4c4-4ce1b3b3e4e1")]
+    pub request_id: Option<String>,
+
+    /// The time at which the change was made
+    #[schema(example = "2023-10-09T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// A page of the audit events of a merchant, the latest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditEventListResponse {
+    /// The number of audit events included in the list
+    pub count: usize,
+
+    /// The total number of audit events matching the constraints
+    pub total_count: i64,
+
+    /// The audit events of the page
+    pub data: Vec<AuditEventResponse>,
+}
+
+/// The constraints that are applicable when listing the audit events of a merchant
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListAuditEventsConstraints {
+    /// Only list the events of this kind of resource
+    #[schema(value_type = Option<AuditResourceType>)]
+    pub resource_type: Option<AuditResourceType>,
+
+    /// Only list the events of the resource with this identifier
+    pub resource_id: Option<String>,
+
+    /// Only list the events recorded at or after this time
+    #[schema(example = "2023-10-01T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_after: Option<PrimitiveDateTime>,
+
+    /// Only list the events recorded before this time
+    #[schema(example = "2023-11-01T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_before: Option<PrimitiveDateTime>,
+
+    /// The maximum number of events to include in the response, between 1 and 100. Defaults to 10
+    pub limit: Option<i64>,
+
+    /// The number of events to skip when retrieving the list of events
+    pub skip: Option<i64>,
+}
@@ -0,0 +1,119 @@
+use common_enums::BlocklistDataKind;
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// The request body for adding an entry to the blocklist of a merchant
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateBlocklistRequest {
+    /// The kind of data that is blocked
+    #[schema(value_type = BlocklistDataKind, example = "email")]
+    pub data_kind: BlocklistDataKind,
+
+    /// The data that is blocked. This is the card number for `card_fingerprint` entries, of which
+    /// only the fingerprint is stored, the first 6 or 8 digits of the card number for `card_bin`
+    /// entries, the email for `email` entries and the IP address for `ip` entries
+    #[schema(value_type = String, example = "fraudster@example.com")]
+    pub data: Secret<String>,
+
+    /// The reason for blocking the data
+    #[schema(max_length = 255, example = "Repeated chargebacks")]
+    pub reason: Option<String>,
+
+    /// The time after which the entry no longer blocks payments. If not provided, the entry
+    /// blocks payments until it is deleted
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+}
+
+/// The request body for updating an entry of the blocklist of a merchant
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateBlocklistRequest {
+    /// The reason for blocking the data
+    #[schema(max_length = 255, example = "Repeated chargebacks")]
+    pub reason: Option<String>,
+
+    /// The time after which the entry no longer blocks payments
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+}
+
+/// An entry of the blocklist of a merchant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlocklistResponse {
+    /// The identifier of the blocklist entry
+    #[schema(max_length = 64, example = "blocklist_7YbFdx3CAxMzv1LQqh5D")]
+    pub blocklist_id: String,
+
+    /// The identifier of the merchant account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The kind of data that is blocked
+    #[schema(value_type = BlocklistDataKind, example = "email")]
+    pub data_kind: BlocklistDataKind,
+
+    /// The value that payments are matched against. This is the card BIN for `card_bin` entries
+    /// and a fingerprint of the blocked data for all other entries
+    #[schema(max_length = 128)]
+    pub fingerprint: String,
+
+    /// The reason for blocking the data
+    #[schema(max_length = 255, example = "Repeated chargebacks")]
+    pub reason: Option<String>,
+
+    /// The time after which the entry no longer blocks payments
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+
+    /// The number of payments blocked by the entry
+    #[schema(example = 2)]
+    pub hit_count: i64,
+
+    /// The time at which the entry last blocked a payment
+    #[schema(example = "2023-10-09T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_hit_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the entry was created
+    #[schema(example = "2023-10-09T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The response body for deleting an entry of the blocklist of a merchant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteBlocklistResponse {
+    /// The identifier of the merchant account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier of the blocklist entry
+    #[schema(max_length = 64, example = "blocklist_7YbFdx3CAxMzv1LQqh5D")]
+    pub blocklist_id: String,
+
+    /// Indicates whether the blocklist entry was deleted
+    #[schema(example = true)]
+    pub deleted: bool,
+}
+
+/// The constraints that are applicable when listing the entries of the blocklist of a merchant
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListBlocklistConstraints {
+    /// Only list the entries which block this kind of data
+    #[schema(value_type = Option<BlocklistDataKind>)]
+    pub data_kind: Option<BlocklistDataKind>,
+
+    /// The maximum number of entries to include in the response
+    pub limit: Option<i64>,
+
+    /// The number of entries to skip when retrieving the list of entries
+    pub skip: Option<i64>,
+}
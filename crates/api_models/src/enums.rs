@@ -490,6 +490,140 @@ pub enum FrmPreferredFlowTypes {
     Pre,
     Post,
 }
+
+/// What is done with a bank transfer for which only a part of the amount is received by the time
+/// the payment session expires
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PartialBankTransferHandling {
+    /// The payment is left for the merchant to accept the received amount
+    Accept,
+    /// The payment is cancelled and the received amount is returned to the customer
+    #[default]
+    Refund,
+}
+
+/// The result of the address verification performed by the issuer, normalized across connectors
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AvsResult {
+    /// Both the street address and the postal code match
+    FullMatch,
+    /// Only the postal code matches
+    PartialZip,
+    /// Only the street address matches
+    PartialAddress,
+    /// Neither the street address nor the postal code match
+    NoMatch,
+    /// The address could not be verified by the issuer
+    Unavailable,
+}
+
+/// The result of the card verification code check performed by the issuer, normalized across
+/// connectors
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CvcResult {
+    /// The card verification code matches
+    Match,
+    /// The card verification code does not match
+    NoMatch,
+    /// The card verification code was not checked by the issuer
+    NotProcessed,
+    /// The card verification code was not provided
+    NotPresent,
+}
+
+/// The authentication decision taken for a payment when a merchant configured 3DS decision rule matches
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ThreeDsDecision {
+    /// Authenticate the payment with 3DS, irrespective of the requested authentication type
+    Force,
+    /// Authenticate the payment with 3DS when the customer is present, merchant initiated payments keep the requested authentication type
+    Prefer,
+    /// Do not authenticate the payment with 3DS, irrespective of the requested authentication type
+    Skip,
+}
+
+/// The Strong Customer Authentication exemption requested from the issuer when 3DS is skipped
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ScaExemptionType {
+    /// Low value transaction exemption
+    LowValue,
+    /// Transaction risk analysis performed by the acquirer
+    TransactionRiskAnalysis,
+    /// The merchant is whitelisted by the customer as a trusted beneficiary
+    TrustedListing,
+    /// Subsequent payment initiated by the merchant without the customer being present
+    MerchantInitiatedTransaction,
+}
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UnresolvedResponseReason {
     pub code: String,
@@ -532,6 +666,7 @@ pub enum FieldType {
     UserAddressCountry { options: Vec<String> },
     UserBlikCode,
     UserBank,
+    DeviceSessionId, //collected by the SDK with the device data collection of the connector
     Text,
     DropDown { options: Vec<String> },
 }
@@ -555,3 +690,27 @@ pub enum RetryAction {
     /// Denotes that the payment is requeued
     Requeue,
 }
+
+/// A field of the payment which was not passed in the request and was filled from the defaults configured on the business profile or the merchant account
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentDefaultField {
+    ReturnUrl,
+    Description,
+    StatementDescriptorName,
+    StatementDescriptorSuffix,
+    Currency,
+    BillingCountry,
+}
@@ -1,6 +1,15 @@
 use serde;
 use utoipa::ToSchema;
 
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq, ToSchema)]
+pub struct EphemeralKeyCreateRequest {
+    /// customer_id for which the ephemeral key is created
+    pub customer_id: String,
+    /// Delete the ephemeral key last created for the customer, so that only the new key is usable
+    #[serde(default)]
+    pub invalidate_existing_keys: bool,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq, ToSchema)]
 pub struct EphemeralKeyCreateResponse {
     /// customer_id to which this ephemeral key belongs to
@@ -8,7 +8,7 @@ impl actix_web::ResponseError for ApiErrorResponse {
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::ForbiddenCommonResource(_) => StatusCode::FORBIDDEN,
             Self::ForbiddenPrivateResource(_) => StatusCode::NOT_FOUND,
-            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Conflict(_) | Self::RequestInProgress(_, _) => StatusCode::CONFLICT,
             Self::Gone(_) => StatusCode::GONE,
             Self::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -17,14 +17,20 @@ impl actix_web::ResponseError for ApiErrorResponse {
             Self::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
             Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::TooManyRequests(_, _) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
         use actix_web::http::header;
 
-        actix_web::HttpResponseBuilder::new(self.status_code())
-            .insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON))
-            .body(self.to_string())
+        let mut response_builder = actix_web::HttpResponseBuilder::new(self.status_code());
+        response_builder.insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON));
+        if let Self::TooManyRequests(_, retry_after_secs)
+        | Self::RequestInProgress(_, retry_after_secs) = self
+        {
+            response_builder.insert_header((header::RETRY_AFTER, retry_after_secs.to_string()));
+        }
+        response_builder.body(self.to_string())
     }
 }
@@ -76,6 +76,10 @@ pub struct Extra {
     pub connector: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
+    /// Message which the merchant can show to the shopper, it never contains the technical
+    /// details of the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_message: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +96,11 @@ pub enum ApiErrorResponse {
     NotFound(ApiError),
     MethodNotAllowed(ApiError),
     BadRequest(ApiError),
+    /// The request was rate limited, with the number of seconds after which it can be retried
+    TooManyRequests(ApiError, u64),
+    /// The request conflicts with a request in progress, with the number of seconds after which
+    /// it can be retried
+    RequestInProgress(ApiError, u64),
 }
 
 impl ::core::fmt::Display for ApiErrorResponse {
@@ -120,7 +129,9 @@ impl ApiErrorResponse {
             | Self::NotFound(i)
             | Self::MethodNotAllowed(i)
             | Self::BadRequest(i)
-            | Self::ConnectorError(i, _) => i,
+            | Self::ConnectorError(i, _)
+            | Self::TooManyRequests(i, _)
+            | Self::RequestInProgress(i, _) => i,
         }
     }
 
@@ -137,7 +148,9 @@ impl ApiErrorResponse {
             | Self::NotFound(i)
             | Self::MethodNotAllowed(i)
             | Self::BadRequest(i)
-            | Self::ConnectorError(i, _) => i,
+            | Self::ConnectorError(i, _)
+            | Self::TooManyRequests(i, _)
+            | Self::RequestInProgress(i, _) => i,
         }
     }
 
@@ -152,7 +165,9 @@ impl ApiErrorResponse {
             | Self::NotImplemented(_)
             | Self::MethodNotAllowed(_)
             | Self::NotFound(_)
-            | Self::BadRequest(_) => "invalid_request",
+            | Self::BadRequest(_)
+            | Self::TooManyRequests(_, _)
+            | Self::RequestInProgress(_, _) => "invalid_request",
             Self::InternalServerError(_) => "api",
             Self::ConnectorError(_, _) => "connector",
         }
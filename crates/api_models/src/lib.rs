@@ -1,7 +1,10 @@
 #![forbid(unsafe_code)]
 pub mod admin;
+pub mod alerts;
 pub mod api_keys;
+pub mod audit_events;
 pub mod bank_accounts;
+pub mod blocklist;
 pub mod cards_info;
 pub mod customers;
 pub mod disputes;
@@ -16,5 +19,6 @@ pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
+pub mod reports;
 pub mod verifications;
 pub mod webhooks;
@@ -35,6 +35,36 @@ pub struct MandateResponse {
     /// Details about the customer’s acceptance
     #[schema(value_type = Option<CustomerAcceptance>)]
     pub customer_acceptance: Option<payments::CustomerAcceptance>,
+    /// The charges made against the mandate
+    pub usage: MandateUsage,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, ToSchema)]
+pub struct MandateUsage {
+    /// The number of successful charges made against the mandate
+    #[schema(example = 3)]
+    pub charge_count: i32,
+    /// The total amount charged against the mandate
+    #[schema(example = 3000)]
+    pub amount_charged: i64,
+    /// The calendar period in which the number of charges against the mandate is limited
+    #[schema(value_type = Option<MandateFrequency>, example = "monthly")]
+    pub frequency: Option<api_enums::MandateFrequency>,
+    /// The maximum number of charges allowed against the mandate in every period
+    #[schema(example = 1)]
+    pub max_charges_per_period: Option<i32>,
+    /// The number of charges made against the mandate in the current period
+    #[schema(example = 1)]
+    pub period_charge_count: i32,
+    /// The start of the current period
+    #[schema(example = "2022-09-01T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub period_started_at: Option<PrimitiveDateTime>,
+    /// The time from which the mandate can be charged again, if the maximum number of charges of
+    /// the current period has been reached
+    #[schema(example = "2022-10-01T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub next_charge_allowed_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, ToSchema)]
@@ -91,3 +121,71 @@ pub struct MandateListConstraints {
     #[serde(rename = "created_time.gte")]
     pub created_time_gte: Option<PrimitiveDateTime>,
 }
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MandateMigrationRequest {
+    /// The identifier for the merchant connector account the mandates are migrated from
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub source_merchant_connector_id: String,
+    /// The identifier for the merchant connector account the mandates are migrated to
+    #[schema(example = "mca_9x2bQeP1tNts4rg7U1kL")]
+    pub target_merchant_connector_id: String,
+    /// Verify the new reference of every mandate with a charge of 0 at the new connector. A mandate whose verification fails is left on the old connector
+    #[serde(default)]
+    pub verify: bool,
+    /// The mapping of the references of the mandates at the old connector to their references at the new connector, as produced by the token migration program of the connectors
+    pub mandates: Vec<MandateMigrationItemRequest>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MandateMigrationItemRequest {
+    /// The connector mandate id at the old connector
+    #[schema(example = "pm_1NgZrWLkdIwHu7ix5kU0nJfJ")]
+    pub connector_mandate_id: String,
+    /// The connector mandate id at the new connector
+    #[schema(example = "8835511112385671")]
+    pub new_connector_mandate_id: Option<String>,
+    /// The network transaction id with which the mandate is charged at the new connector, used when the new connector does not provide a connector mandate id
+    pub network_transaction_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MandateMigrationResponse {
+    /// The identifier for the mandate migration, which can be used to retrieve its progress
+    #[schema(example = "mig_mbabizu24mvu3mela5njyhpit4")]
+    pub migration_id: String,
+    /// The identifier for the merchant connector account the mandates are migrated from
+    pub source_merchant_connector_id: String,
+    /// The identifier for the merchant connector account the mandates are migrated to
+    pub target_merchant_connector_id: String,
+    /// Whether the new reference of every mandate is verified with a charge of 0
+    pub verify: bool,
+    /// The status of the mandate migration
+    #[schema(value_type = MandateMigrationStatus)]
+    pub status: api_enums::MandateMigrationStatus,
+    /// The outcome of the migration of each mandate, in the order of the request
+    pub mandates: Vec<MandateMigrationItemResponse>,
+    /// The timestamp at which the mandate migration is created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    /// The timestamp at which the mandate migration was last updated
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MandateMigrationItemResponse {
+    /// The connector mandate id at the old connector
+    pub connector_mandate_id: String,
+    /// The identifier for the mandate, present if a mandate was found for the connector mandate id
+    pub mandate_id: Option<String>,
+    /// Whether the mandate is pending migration, has been migrated or has failed to migrate
+    #[schema(value_type = MandateMigrationItemStatus)]
+    pub status: api_enums::MandateMigrationItemStatus,
+    /// The code for the error, present if the mandate has failed to migrate
+    pub error_code: Option<String>,
+    /// The error message, present if the mandate has failed to migrate
+    pub error_message: Option<String>,
+}
@@ -50,6 +50,11 @@ pub struct PaymentMethodCreate {
     /// The card network
     #[schema(example = "Visa")]
     pub card_network: Option<String>,
+
+    /// What is done if the customer has already saved the card, the payment method already saved
+    /// is returned if not passed
+    #[schema(value_type = Option<DuplicatePaymentMethodBehaviour>, example = "return_existing")]
+    pub duplicate_behaviour: Option<api_enums::DuplicatePaymentMethodBehaviour>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -283,6 +288,76 @@ pub struct RequiredFieldInfo {
     pub value: Option<String>,
 }
 
+/// The request for listing the fields required to pay with a payment method type
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RequiredFieldsRequest {
+    /// The client secret of the payment, which limits the connectors to the ones eligible for the
+    /// payment and prefills the values of the fields already present in the payment
+    #[schema(max_length = 30, min_length = 30, example = "secret_k2uj3he2893ein2d")]
+    pub client_secret: Option<String>,
+
+    /// The payment method for which the required fields are listed
+    #[schema(value_type = PaymentMethod, example = "pay_later")]
+    pub payment_method: api_enums::PaymentMethod,
+
+    /// The payment method type for which the required fields are listed
+    #[schema(value_type = PaymentMethodType, example = "klarna")]
+    pub payment_method_type: api_enums::PaymentMethodType,
+}
+
+/// The fields required to pay with a payment method type through any of the eligible connectors
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct RequiredFieldsResponse {
+    /// The payment method for which the required fields are listed
+    #[schema(value_type = PaymentMethod, example = "pay_later")]
+    pub payment_method: api_enums::PaymentMethod,
+
+    /// The payment method type for which the required fields are listed
+    #[schema(value_type = PaymentMethodType, example = "klarna")]
+    pub payment_method_type: api_enums::PaymentMethodType,
+
+    /// The connectors eligible for the payment which support the payment method type
+    #[schema(example = json!(["stripe", "adyen"]))]
+    pub eligible_connectors: Vec<String>,
+
+    /// The fields required by any of the eligible connectors, keyed by the path of the field in
+    /// the payments request
+    pub required_fields: HashMap<String, RequiredFieldInfo>,
+}
+
+/// The request for listing the banks of a bank redirect payment method type
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BankListRequest {
+    /// The client secret of the payment, which limits the connectors to the ones eligible for the
+    /// payment
+    #[schema(max_length = 30, min_length = 30, example = "secret_k2uj3he2893ein2d")]
+    pub client_secret: Option<String>,
+}
+
+/// The banks which can be selected to pay with a bank redirect payment method type
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct BankListResponse {
+    /// The payment method type for which the banks are listed
+    #[schema(value_type = PaymentMethodType, example = "ideal")]
+    pub payment_method_type: api_enums::PaymentMethodType,
+
+    /// The banks supported by each of the connectors eligible for the payment
+    pub connectors: Vec<ConnectorBanks>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct ConnectorBanks {
+    /// The connector which supports the banks
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// The banks which the connector can process the payment method type with
+    #[schema(value_type = Vec<BankNames>, example = json!(["abn_amro", "ing"]))]
+    pub banks: Vec<api_enums::BankNames>,
+}
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct ResponsePaymentMethodsEnabled {
     /// The payment method enabled
@@ -300,7 +375,7 @@ pub struct BankTransferTypes {
     pub eligible_connectors: Vec<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ResponsePaymentMethodIntermediate {
     pub payment_method_type: api_enums::PaymentMethodType,
     pub payment_experience: Option<api_enums::PaymentExperience>,
@@ -357,6 +432,10 @@ pub struct RequestPaymentMethodTypes {
     #[schema(example = 1313)]
     pub maximum_amount: Option<i32>,
 
+    /// The currency in which `minimum_amount` and `maximum_amount` are represented. The amount of a payment in another currency is converted to this currency with the current exchange rates before it is compared against the amounts. If not set, the amounts are represented in the lowest denomination of the currency of the payment
+    #[schema(example = "EUR")]
+    pub amount_range_currency: Option<api_enums::Currency>,
+
     /// Boolean to enable recurring payments / mandates. Default is true.
     #[schema(default = true, example = false)]
     pub recurring_enabled: bool,
@@ -118,9 +118,8 @@ pub struct PaymentsRequest {
     #[schema(value_type = Option<Vec<Connector>>, max_length = 255, example = json!(["stripe", "adyen"]))]
     pub connector: Option<Vec<api_enums::Connector>>,
 
-    /// The currency of the payment request can be specified here
+    /// The currency of the payment request can be specified here. The default currency of the business profile or the merchant account is used when it is not passed
     #[schema(value_type = Option<Currency>, example = "USD")]
-    #[mandatory_in(PaymentsCreateRequest)]
     pub currency: Option<api_enums::Currency>,
 
     /// This is the instruction for capture/ debit the money from the users' card. On the other hand authorization refers to blocking the amount on the users' payment method.
@@ -203,8 +202,10 @@ pub struct PaymentsRequest {
     #[schema(example = "187282ab-40ef-47a9-9206-5099ba31e432")]
     pub payment_token: Option<String>,
 
-    /// This is used when payment is to be confirmed and the card is not saved
-    #[schema(value_type = Option<String>)]
+    /// The card verification code collected from the customer when paying with a saved card,
+    /// either through the `payment_token` or a mandate. It is passed to the connector and is not
+    /// stored with the payment.
+    #[schema(value_type = Option<String>, example = "737")]
     pub card_cvc: Option<Secret<String>>,
 
     /// The shipping address for the payment
@@ -254,6 +255,11 @@ pub struct PaymentsRequest {
     }"#)]
     pub browser_info: Option<serde_json::Value>,
 
+    /// The results of a 3DS authentication performed outside Hyperswitch, using an external 3DS server (MPI).
+    /// When provided, the payment is flagged as authenticated at the connector and the connector's own 3DS challenge is skipped
+    /// It is stored encrypted on the payment attempt until the payment is confirmed
+    pub authentication_data: Option<ExternalAuthenticationData>,
+
     /// Payment Experience for the current payment
     #[schema(value_type = Option<PaymentExperience>, example = "redirect_to_url")]
     pub payment_experience: Option<api_enums::PaymentExperience>,
@@ -298,6 +304,207 @@ pub struct PaymentsRequest {
     /// The business profile to use for this payment, if not passed the default business profile
     /// associated with the merchant account will be used.
     pub profile_id: Option<String>,
+
+    /// Time in seconds after which the payment is cancelled if it is not confirmed. If not passed,
+    /// the session expiry configured on the business profile is used. The payment does not expire
+    /// when neither of them is set
+    #[schema(minimum = 60, maximum = 604800, example = 900)]
+    pub session_expiry: Option<u32>,
+
+    /// Locale of the shopper in BCP-47 format, used by connectors to localize their hosted and 3DS
+    /// challenge pages. If not passed, the language of the shopper's browser is used, followed by
+    /// the default locale configured on the business profile and `en`
+    #[schema(max_length = 35, example = "fr-FR")]
+    pub locale: Option<String>,
+
+    /// Descriptor of the merchant on whose behalf the payment is made, shown on the customer's
+    /// statement by connectors supporting payment facilitators
+    #[schema(max_length = 255, example = "ACME*SUB SHOP")]
+    pub merchant_descriptor: Option<String>,
+
+    /// Details of the sub-merchant on whose behalf the payment is made, passed to connectors
+    /// supporting payment facilitators
+    pub sub_merchant_data: Option<SubMerchantData>,
+
+    /// Category of the goods or services purchased in the order, passed to connectors which use
+    /// it for risk assessment (e.g. buy now pay later connectors)
+    #[schema(max_length = 255, example = "apparel")]
+    pub order_category: Option<String>,
+
+    /// The method by which the order is delivered to the customer. Orders delivered digitally do
+    /// not require a shipping address
+    #[schema(value_type = Option<ShippingMethod>, example = "standard")]
+    pub shipping_method: Option<api_enums::ShippingMethod>,
+
+    /// The date at which the order is expected to be delivered to the customer
+    #[schema(example = "2022-09-15T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub estimated_delivery_date: Option<PrimitiveDateTime>,
+
+    /// Reference of the order in the merchant's system, used to build the reference of the
+    /// payment sent to the connectors when configured in the connector request reference format
+    /// of the business profile
+    #[schema(max_length = 255, example = "order_2023_1045")]
+    pub merchant_order_reference_id: Option<String>,
+
+    /// Currency in which the amount of the payment is displayed to the customer, the payment
+    /// creation response then contains the amount converted at an indicative exchange rate. The
+    /// payment is still charged in `currency`
+    #[schema(value_type = Option<Currency>, example = "EUR")]
+    pub display_currency: Option<api_enums::Currency>,
+
+    /// Split of a marketplace payment between the platform, which retains a fee, and a
+    /// sub-account at the connector, to which the rest of the amount is transferred. Only
+    /// connectors supporting split payments can process the payment
+    pub split_payment: Option<SplitPaymentRequest>,
+
+    /// Breakdown of the amount of the payment into the tax, shipping, duty and discount of the
+    /// order, passed as Level 2 and Level 3 data to connectors supporting it. Payments routed to
+    /// other connectors are processed without it
+    pub amount_details: Option<AmountDetails>,
+
+    /// Whether the merchant accepts an authorization of less than the amount of the payment, as
+    /// some debit networks approve only the balance available on the card. A partially authorized
+    /// payment can be captured up to the authorized amount or voided. When not requested, a partial
+    /// authorization is voided and the payment is cancelled
+    #[schema(example = true)]
+    pub request_partial_authorization: Option<bool>,
+
+    /// The channel through which the customer gave their payment details. Mail order and
+    /// telephone order payments do not require the browser info of the customer and are not
+    /// authenticated with 3DS
+    #[schema(value_type = Option<PaymentChannel>, example = "ecommerce")]
+    pub payment_channel: Option<api_enums::PaymentChannel>,
+
+    /// Session of the device data collection run by the SDK before the authorization, as returned
+    /// by the device data endpoint. Some connectors reject the payments without it, it is taken
+    /// from the payment when the device data endpoint was called for it
+    #[schema(max_length = 128, example = "dds_5bDkHXt9vpKgMrLqw2yN")]
+    pub device_session_id: Option<String>,
+}
+
+/// Split of a marketplace payment between the platform and a sub-account at the connector
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SplitPaymentRequest {
+    /// The fee retained by the platform, it must be less than the amount of the payment
+    pub platform_fee: PlatformFee,
+
+    /// Reference of the sub-account at the connector to which the amount is transferred, such as
+    /// the connected account ID on Stripe
+    #[schema(max_length = 255, example = "acct_1032D82eZvKYlo2C")]
+    pub destination_account: String,
+
+    /// How the platform fee is reversed when the payment is refunded, proportionally to the
+    /// refunded amount if not passed
+    #[schema(value_type = Option<PlatformFeeRefundBehaviour>, example = "proportional")]
+    pub fee_refund_behaviour: Option<api_enums::PlatformFeeRefundBehaviour>,
+}
+
+/// The fee retained by the platform on a split payment
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum PlatformFee {
+    /// A fixed fee, in the lowest denomination of the currency of the payment
+    Amount { amount: i64 },
+    /// A fee in basis points of the amount of the payment, `250` being 2.5%
+    Percentage { basis_points: u16 },
+}
+
+/// Split of a payment between the platform and a sub-account, with the fee resolved from the
+/// amount of the payment
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct SplitPaymentDetails {
+    /// The fee retained by the platform, in the lowest denomination of the currency of the payment
+    #[schema(example = 250)]
+    pub platform_fee_amount: i64,
+
+    /// Reference of the sub-account at the connector to which the amount is transferred
+    #[schema(example = "acct_1032D82eZvKYlo2C")]
+    pub destination_account: String,
+
+    /// How the platform fee is reversed when the payment is refunded
+    #[schema(value_type = PlatformFeeRefundBehaviour, example = "proportional")]
+    pub fee_refund_behaviour: api_enums::PlatformFeeRefundBehaviour,
+}
+
+/// Breakdown of the amount of a payment, all the amounts are in the lowest denomination of the
+/// currency of the payment. The amounts of the line items in `order_details`, with the tax,
+/// shipping cost and duty, less the discount, must add up to the amount of the payment
+#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AmountDetails {
+    /// The tax charged on the order
+    #[schema(example = 850)]
+    pub tax_amount: Option<i64>,
+
+    /// The cost of shipping the order
+    #[schema(example = 500)]
+    pub shipping_cost: Option<i64>,
+
+    /// The customs duty charged on the order
+    #[schema(example = 0)]
+    pub duty_amount: Option<i64>,
+
+    /// The discount applied to the order
+    #[schema(example = 100)]
+    pub discount_amount: Option<i64>,
+
+    /// Whether the breakdown may differ from the amount of the payment by the rounding of the
+    /// taxes of the line items, of at most one unit of the lowest denomination per line item
+    #[serde(default)]
+    #[schema(example = false)]
+    pub allow_rounding_difference: bool,
+}
+
+/// Details of the sub-merchant of a payment facilitator
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubMerchantData {
+    /// The identifier of the sub-merchant assigned by the payment facilitator
+    #[schema(max_length = 64, example = "sub_merchant_123")]
+    pub id: String,
+
+    /// The name of the sub-merchant
+    #[schema(max_length = 255, example = "Sub Shop")]
+    pub name: String,
+
+    /// The Merchant Category Code (MCC) of the sub-merchant, as defined by ISO 18245
+    #[schema(min_length = 4, max_length = 4, example = "5411")]
+    pub mcc: String,
+
+    /// The country of the sub-merchant
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub country: Option<api_enums::CountryAlpha2>,
+
+    /// The postal code of the sub-merchant
+    #[schema(value_type = Option<String>, example = "94122")]
+    pub postal_code: Option<Secret<String>>,
+}
+
+/// Details of a 3DS authentication performed by the merchant using an external 3DS server
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalAuthenticationData {
+    /// The Cardholder Authentication Verification Value (CAVV) obtained from the 3DS authentication, base64 encoded
+    #[schema(value_type = String, example = "AAABBIIFmAAAAAAAAAAAAAAAAAA=")]
+    pub cavv: Secret<String>,
+
+    /// The Electronic Commerce Indicator (ECI) obtained from the 3DS authentication
+    #[schema(example = "05")]
+    pub eci: String,
+
+    /// The transaction identifier assigned by the 3DS Directory Server
+    #[schema(example = "c4e59ceb-a382-4d6a-bc87-385d591fa09d")]
+    pub ds_transaction_id: Option<String>,
+
+    /// The version of the 3DS protocol used for the authentication
+    #[schema(example = "2.2.0")]
+    pub message_version: String,
+
+    /// The transaction identifier assigned by the 3DS Server
+    #[schema(example = "4bb8ac1e-6a5f-4f38-8c1d-3a0fd4e4f68a")]
+    pub threeds_server_transaction_id: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -323,6 +530,8 @@ pub struct PaymentAttemptResponse {
     pub connector: Option<String>,
     /// If there was an error while calling the connector the error message is received here
     pub error_message: Option<String>,
+    /// The raw error reason received from the connector, if any
+    pub error_reason: Option<String>,
     /// The payment method that is to be used
     #[schema(value_type = Option<PaymentMethod>, example = "bank_transfer")]
     pub payment_method: Option<enums::PaymentMethod>,
@@ -336,6 +545,9 @@ pub struct PaymentAttemptResponse {
     pub authentication_type: Option<enums::AuthenticationType>,
     /// If the payment was cancelled the reason provided here
     pub cancellation_reason: Option<String>,
+    /// If the payment was cancelled the reason code provided here
+    #[schema(value_type = Option<CancellationReason>, example = "requested_by_customer")]
+    pub cancellation_reason_code: Option<enums::CancellationReason>,
     /// A unique identifier to link the payment to a mandate, can be use instead of payment_method_data
     pub mandate_id: Option<String>,
     /// If there was an error while calling the connectors the code is received here
@@ -353,6 +565,26 @@ pub struct PaymentAttemptResponse {
     /// reference to the payment at connector side
     #[schema(value_type = Option<String>, example = "993672945374576J")]
     pub reference_id: Option<String>,
+    /// The amount which is to be captured for the attempt
+    pub amount_to_capture: Option<i64>,
+    /// The amount which can still be captured for the attempt
+    pub amount_capturable: i64,
+    /// Time at which the attempt was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    /// Time at which the attempt was last modified
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub modified_at: Option<PrimitiveDateTime>,
+}
+
+#[derive(Default, Debug, serde::Serialize, Clone, PartialEq, ToSchema)]
+pub struct PaymentAttemptListResponse {
+    /// The identifier for the payment
+    pub payment_id: String,
+    /// The attempts made for the payment, ordered by the time at which they were created
+    pub attempts: Vec<PaymentAttemptResponse>,
 }
 
 #[derive(
@@ -466,6 +698,49 @@ impl From<i64> for Amount {
     }
 }
 
+/// Request to charge a customer off session against a mandate which was set up earlier
+#[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RecurringPaymentsRequest {
+    /// The identifier of the mandate to be charged, required if `payment_method_id` is not passed
+    #[schema(max_length = 255, example = "mandate_iwer89rnjef349dni3")]
+    pub mandate_id: Option<String>,
+
+    /// The identifier of the customer who set up the mandate, required if `mandate_id` is not passed
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+
+    /// The identifier of the payment method saved along with the mandate, the active mandate of
+    /// the customer for this payment method is charged
+    #[schema(example = "pm_01926c58bc6e77c09e809964e72af8c8")]
+    pub payment_method_id: Option<String>,
+
+    /// The amount to be charged in the lowest denomination of the currency
+    #[schema(minimum = 1, example = 6540)]
+    pub amount: i64,
+
+    /// The currency of the amount to be charged
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// Whether the amount is captured automatically or manually after the authorization
+    #[schema(value_type = Option<CaptureMethod>, example = "automatic")]
+    pub capture_method: Option<api_enums::CaptureMethod>,
+
+    /// A description of the payment
+    #[schema(example = "Monthly subscription")]
+    pub description: Option<String>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "udf1": "some-value", "udf2": "some-value" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// Charge the mandate even if the charge exceeds the amount or the frequency limits of the
+    /// mandate. Requires an API key with the `mandate_limit_override` permission
+    #[schema(default = false, example = false)]
+    pub override_mandate_limits: Option<bool>,
+}
+
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct PaymentsRedirectRequest {
@@ -568,6 +843,12 @@ pub struct MandateAmountData {
         "frequency": "DAILY"
     }"#)]
     pub metadata: Option<pii::SecretSerdeValue>,
+    /// The calendar period in which the number of charges against a multi use mandate is limited, to be passed along with `max_charges_per_period`
+    #[schema(value_type = Option<MandateFrequency>, example = "monthly")]
+    pub frequency: Option<api_enums::MandateFrequency>,
+    /// The maximum number of charges allowed against the mandate in every period. When set, every charge is limited to the mandate amount.
+    #[schema(example = 1)]
+    pub max_charges_per_period: Option<i32>,
 }
 
 #[derive(Eq, PartialEq, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -830,7 +1111,9 @@ pub enum AdditionalPaymentData {
     BankRedirect {
         bank_name: Option<api_enums::BankNames>,
     },
-    Wallet {},
+    Wallet {
+        wallet_type: Option<api_enums::PaymentMethodType>,
+    },
     PayLater {},
     BankTransfer {},
     Crypto {},
@@ -1144,6 +1427,20 @@ pub enum BankTransferData {
     },
     Pix {},
     Pse {},
+    VirtualAccountBankTransfer {
+        /// The billing details of the customer for whom the virtual account is issued
+        billing_details: VirtualAccountBillingDetails,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct VirtualAccountBillingDetails {
+    /// The Email ID to which the bank transfer instructions are sent
+    #[schema(value_type = String, example = "example@me.com")]
+    pub email: Email,
+    /// The name of the customer for whom the virtual account is issued
+    #[schema(value_type = String, example = "Jane Doe")]
+    pub name: Secret<String>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, ToSchema, Eq, PartialEq)]
@@ -1214,6 +1511,38 @@ pub enum WalletData {
     SwishQr(SwishQrData),
 }
 
+impl WalletData {
+    pub fn get_payment_method_type(&self) -> api_enums::PaymentMethodType {
+        match self {
+            Self::AliPayQr(_) | Self::AliPayRedirect(_) => api_enums::PaymentMethodType::AliPay,
+            Self::AliPayHkRedirect(_) => api_enums::PaymentMethodType::AliPayHk,
+            Self::MomoRedirect(_) => api_enums::PaymentMethodType::Momo,
+            Self::KakaoPayRedirect(_) => api_enums::PaymentMethodType::KakaoPay,
+            Self::GoPayRedirect(_) => api_enums::PaymentMethodType::GoPay,
+            Self::GcashRedirect(_) => api_enums::PaymentMethodType::Gcash,
+            Self::ApplePay(_) | Self::ApplePayRedirect(_) | Self::ApplePayThirdPartySdk(_) => {
+                api_enums::PaymentMethodType::ApplePay
+            }
+            Self::DanaRedirect {} => api_enums::PaymentMethodType::Dana,
+            Self::GooglePay(_) | Self::GooglePayRedirect(_) | Self::GooglePayThirdPartySdk(_) => {
+                api_enums::PaymentMethodType::GooglePay
+            }
+            Self::MbWayRedirect(_) => api_enums::PaymentMethodType::MbWay,
+            Self::MobilePayRedirect(_) => api_enums::PaymentMethodType::MobilePay,
+            Self::PaypalRedirect(_) | Self::PaypalSdk(_) => api_enums::PaymentMethodType::Paypal,
+            Self::SamsungPay(_) => api_enums::PaymentMethodType::SamsungPay,
+            Self::TwintRedirect {} => api_enums::PaymentMethodType::Twint,
+            Self::VippsRedirect {} => api_enums::PaymentMethodType::Vipps,
+            Self::TouchNGoRedirect(_) => api_enums::PaymentMethodType::TouchNGo,
+            Self::WeChatPayRedirect(_) | Self::WeChatPayQr(_) => {
+                api_enums::PaymentMethodType::WeChatPay
+            }
+            Self::CashappQr(_) => api_enums::PaymentMethodType::Cashapp,
+            Self::SwishQr(_) => api_enums::PaymentMethodType::Swish,
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct SamsungPayWalletData {
@@ -1391,16 +1720,28 @@ pub enum VoucherData {
     PayEasy(Box<JCSVoucherData>),
 }
 
+#[derive(Eq, PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WalletResponse {
+    pub wallet_type: Option<api_enums::PaymentMethodType>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BankRedirectResponse {
+    pub bank_name: Option<api_enums::BankNames>,
+}
+
+/// Details of the payment method used for the payment, limited to the ones which do not reveal the
+/// payment method such as the last 4 digits of the card
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PaymentMethodDataResponse {
     #[serde(rename = "card")]
     Card(CardResponse),
     BankTransfer,
-    Wallet,
+    Wallet(WalletResponse),
     PayLater,
     Paypal,
-    BankRedirect,
+    BankRedirect(BankRedirectResponse),
     Crypto,
     BankDebit,
     MandatePayment,
@@ -1564,6 +1905,9 @@ pub enum NextActionData {
     QrCodeInformation {
         #[schema(value_type = String)]
         image_data_url: Url,
+        /// The raw data encoded in the Qr code, for sdks rendering the Qr code by themselves
+        qr_code_data: Option<String>,
+        /// The unix timestamp (in seconds) after which the Qr code should not be displayed
         display_to_timestamp: Option<i64>,
     },
     /// Contains the download url and the reference number for transaction
@@ -1575,9 +1919,79 @@ pub enum NextActionData {
     WaitScreenInformation {
         display_from_timestamp: i128,
         display_to_timestamp: Option<i128>,
+        /// The interval (in seconds) at which the sdk should poll for the status of the payment while the wait screen is displayed
+        poll_interval_in_secs: Option<u16>,
+    },
+    /// Informs that a one time password sent to the customer has to be collected and submitted
+    CollectOtp {
+        /// The number of digits in the one time password
+        otp_length: Option<u8>,
+        /// The unix timestamp (in seconds) after which the one time password expires
+        display_to_timestamp: Option<i64>,
     },
 }
 
+/// The next action required from the customer, as received from the connector.
+/// This is stored in the connector metadata of the payment attempt and is mapped to [`NextActionData`] in the payments response
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorNextActionData {
+    DisplayBankTransferInformation {
+        bank_transfer_steps_and_charges_details: BankTransferNextStepsData,
+    },
+    QrCodeInformation {
+        image_data_url: Url,
+        qr_code_data: Option<String>,
+        display_to_timestamp: Option<i64>,
+    },
+    WaitScreenInformation {
+        display_from_timestamp: i128,
+        display_to_timestamp: Option<i128>,
+        poll_interval_in_secs: Option<u16>,
+    },
+    CollectOtp {
+        otp_length: Option<u8>,
+        display_to_timestamp: Option<i64>,
+    },
+}
+
+impl From<ConnectorNextActionData> for NextActionData {
+    fn from(next_action: ConnectorNextActionData) -> Self {
+        match next_action {
+            ConnectorNextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details,
+            } => Self::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details,
+            },
+            ConnectorNextActionData::QrCodeInformation {
+                image_data_url,
+                qr_code_data,
+                display_to_timestamp,
+            } => Self::QrCodeInformation {
+                image_data_url,
+                qr_code_data,
+                display_to_timestamp,
+            },
+            ConnectorNextActionData::WaitScreenInformation {
+                display_from_timestamp,
+                display_to_timestamp,
+                poll_interval_in_secs,
+            } => Self::WaitScreenInformation {
+                display_from_timestamp,
+                display_to_timestamp,
+                poll_interval_in_secs,
+            },
+            ConnectorNextActionData::CollectOtp {
+                otp_length,
+                display_to_timestamp,
+            } => Self::CollectOtp {
+                otp_length,
+                display_to_timestamp,
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct BankTransferNextStepsData {
     /// The instructions for performing a bank transfer
@@ -1602,6 +2016,7 @@ pub struct VoucherNextStepData {
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct QrCodeNextStepsInstruction {
     pub image_data_url: Url,
+    pub qr_code_data: Option<String>,
     pub display_to_timestamp: Option<i64>,
 }
 
@@ -1624,6 +2039,8 @@ pub enum BankTransferInstructions {
     BacsBankInstructions(Box<BacsBankTransferInstructions>),
     /// The instructions for Multibanco bank transactions
     Multibanco(Box<MultibancoTransferInstructions>),
+    /// The virtual account to which the customer has to transfer the amount
+    VirtualAccountInstructions(Box<VirtualAccountTransferInstructions>),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -1655,6 +2072,32 @@ pub struct MultibancoTransferInstructions {
     pub entity: String,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct VirtualAccountTransferInstructions {
+    /// The name of the bank in which the virtual account is held
+    #[schema(example = "Test Bank")]
+    pub bank_name: Option<String>,
+    /// The account number of the virtual account
+    #[schema(value_type = Option<String>, example = "122385736258")]
+    pub account_number: Option<Secret<String>>,
+    /// The IBAN of the virtual account, for banks identifying accounts by IBAN
+    #[schema(value_type = Option<String>, example = "DE89370400440532013000")]
+    pub iban: Option<Secret<String>>,
+    /// The routing code of the bank (ex: ABA routing number, CLABE, branch code)
+    #[schema(value_type = Option<String>, example = "110000000")]
+    pub routing_number: Option<Secret<String>>,
+    /// The SWIFT / BIC code of the bank
+    #[schema(value_type = Option<String>, example = "TESTUS99")]
+    pub swift_code: Option<Secret<String>>,
+    /// The reference which the customer has to mention while making the transfer
+    #[schema(value_type = Option<String>, example = "PAYREF1234")]
+    pub reference: Option<Secret<String>>,
+    /// The time after which the virtual account no longer accepts transfers for the payment
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct DokuBankTransferInstructions {
     #[schema(value_type = String, example = "2023-07-26T17:33:00-07-21")]
@@ -1679,11 +2122,11 @@ pub struct AchTransfer {
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct ReceiverDetails {
     /// The amount received by receiver
-    amount_received: i64,
+    pub amount_received: i64,
     /// The amount charged by ACH
-    amount_charged: Option<i64>,
+    pub amount_charged: Option<i64>,
     /// The amount remaining to be sent via ACH
-    amount_remaining: Option<i64>,
+    pub amount_remaining: Option<i64>,
 }
 
 #[derive(Setter, Clone, Default, Debug, PartialEq, serde::Serialize, ToSchema)]
@@ -1718,6 +2161,19 @@ pub struct PaymentsResponse {
     #[schema(minimum = 100, example = 6540)]
     pub amount_received: Option<i64>,
 
+    /// The amount captured from the payment, including the amount of captures which are pending
+    /// with the connector
+    #[schema(example = 6540)]
+    pub amount_captured: Option<i64>,
+
+    /// The amount refunded from the payment, including the amount of refunds which are pending
+    #[schema(example = 1000)]
+    pub amount_refunded: Option<i64>,
+
+    /// The maximum amount that could still be refunded from the payment
+    #[schema(example = 5540)]
+    pub amount_refundable: Option<i64>,
+
     /// The connector used for the payment
     #[schema(example = "stripe")]
     pub connector: Option<String>,
@@ -1849,6 +2305,10 @@ pub struct PaymentsResponse {
     /// If the payment was cancelled the reason provided here
     pub cancellation_reason: Option<String>,
 
+    /// If the payment was cancelled the reason code provided here
+    #[schema(value_type = Option<CancellationReason>, example = "requested_by_customer")]
+    pub cancellation_reason_code: Option<enums::CancellationReason>,
+
     /// If there was an error while calling the connectors the code is received here
     #[schema(example = "E0001")]
     pub error_code: Option<String>,
@@ -1920,6 +2380,254 @@ pub struct PaymentsResponse {
 
     /// Denotes the action(approve or reject) taken by merchant in case of manual review. Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
+
+    /// A timestamp (ISO 8601 code) after which the payment is cancelled if it is not confirmed
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_on: Option<PrimitiveDateTime>,
+
+    /// The results of the address and card verification code checks performed by the issuer
+    pub card_verification_results: Option<CardVerificationResults>,
+
+    /// Locale of the shopper in BCP-47 format, which is passed to the connector
+    #[schema(max_length = 35, example = "fr-FR")]
+    pub locale: Option<String>,
+
+    /// The fee charged by the connector for processing the payment, once the connector reports it
+    pub connector_fee: Option<ConnectorFeeDetails>,
+
+    /// Authorization code returned by the issuer when the payment was authorized
+    #[schema(example = "770687")]
+    pub auth_code: Option<String>,
+
+    /// Acquirer reference number (ARN) of the payment, used to trace the payment across the card
+    /// network, for instance in chargeback representment. It is usually assigned by the acquirer
+    /// only once the payment is captured
+    #[schema(example = "24492153326100000008311")]
+    pub acquirer_reference_number: Option<String>,
+
+    /// A timestamp (ISO 8601 code) at which the authorization of a manually captured payment
+    /// expires at the connector, the payment can no longer be captured after it
+    #[schema(example = "2022-09-17T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub authorization_expires_at: Option<PrimitiveDateTime>,
+
+    /// The fields which were not passed in the payment request and were filled from the defaults configured on the business profile or the merchant account
+    #[schema(example = json!(["return_url"]))]
+    pub applied_defaults: Option<Vec<api_enums::PaymentDefaultField>>,
+
+    /// Reference of the order in the merchant's system, passed in the payment request
+    #[schema(max_length = 255, example = "order_2023_1045")]
+    pub merchant_order_reference_id: Option<String>,
+
+    /// Identifier of the payment created earlier with the same merchant order reference, when the
+    /// payment is suspected to be a duplicate of it
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub suspected_duplicate_of: Option<String>,
+
+    /// The amount of the payment converted to the `display_currency` passed in the payment
+    /// creation request. It is not returned when no recent exchange rate is available
+    pub display_amount: Option<DisplayAmount>,
+
+    /// Split of the payment between the platform and a sub-account at the connector
+    pub split_payment: Option<SplitPaymentDetails>,
+
+    /// Breakdown of the amount of the payment passed in the payment creation request
+    pub amount_details: Option<AmountDetails>,
+
+    /// Whether the merchant accepts an authorization of less than the amount of the payment
+    #[schema(example = true)]
+    pub request_partial_authorization: Option<bool>,
+
+    /// The amount authorized by the connector, when it is less than the amount of the payment
+    #[schema(example = 6000)]
+    pub authorized_amount: Option<i64>,
+
+    /// The connector which the payment was routed around because of its maintenance window. This
+    /// is only returned in the response of the request in which the connector was chosen
+    pub maintenance_fallback: Option<MaintenanceFallback>,
+
+    /// Whether the payment was created with a live API key. Payments created with a test API key
+    /// are only processed by the merchant connector accounts in test mode
+    #[schema(example = true)]
+    pub livemode: bool,
+
+    /// The raw response of the connector to the latest request made for the payment, only returned
+    /// by retrieve with `expand=connector_response`
+    pub connector_response: Option<ConnectorResponseDetails>,
+
+    /// The steps of the processing of the attempts of the payment, in the order they happened. Only
+    /// returned by retrieve with `expand=timeline`
+    pub timeline: Option<Vec<PaymentTimelineEvent>>,
+
+    /// The decisions taken in routing the attempts of the payment to the connectors, in the order
+    /// they were taken. Only returned by retrieve with `expand=routing_decisions`
+    pub routing_decisions: Option<Vec<RoutingDecision>>,
+}
+
+/// A decision taken in routing an attempt of the payment: the connector chosen by the routing, a
+/// connector skipped in favour of a fallback connector, or a request sent to the connector
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct RoutingDecision {
+    #[schema(value_type = RoutingDecisionType, example = "connector_routed")]
+    pub decision: api_enums::RoutingDecisionType,
+    /// The attempt of the payment which the decision is for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4_1")]
+    pub attempt_id: String,
+    /// The connector which the decision is about
+    #[schema(example = "stripe")]
+    pub connector: String,
+    /// The routing rule which chose the connector, for the `connector_routed` decisions
+    #[schema(value_type = Option<RoutingRule>, example = "merchant_routing_algorithm")]
+    pub routing_rule: Option<api_enums::RoutingRule>,
+    /// Why the connector was skipped, for the `connector_skipped` decisions, or why the payment was
+    /// routed to a fallback connector, for the `connector_routed` decisions
+    #[schema(value_type = Option<RoutingFallbackReason>, example = "circuit_open")]
+    pub fallback_reason: Option<api_enums::RoutingFallbackReason>,
+    /// The class of the outcome of the request, for the `connector_attempted` decisions
+    #[schema(value_type = Option<ConnectorAttemptOutcome>, example = "timeout")]
+    pub outcome: Option<api_enums::ConnectorAttemptOutcome>,
+    /// The time taken by the connector to respond to the request in milliseconds, for the
+    /// `connector_attempted` decisions of the requests which were sent
+    #[schema(example = 1250)]
+    pub latency_ms: Option<i64>,
+    /// The time at which the decision was taken
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// A step of the processing of an attempt of the payment. The steps report the progress of the
+/// payment, like the customer being redirected for authentication, and are not authoritative: the
+/// status of the payment is the one to act on
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct PaymentTimelineEvent {
+    #[schema(value_type = PaymentAttemptStep, example = "redirect_issued")]
+    pub step: api_enums::PaymentAttemptStep,
+    /// The attempt of the payment which the step is for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4_1")]
+    pub attempt_id: String,
+    /// The connector which processes the attempt
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+    /// The time at which the step happened
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The connector chosen by the routing of the merchant which was skipped because it was under
+/// maintenance, the payment being routed to a fallback connector of the merchant instead
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct MaintenanceFallback {
+    /// The connector which was under maintenance
+    #[schema(example = "stripe")]
+    pub skipped_connector: String,
+    /// The identifier of the maintenance window of the connector
+    #[schema(example = "mw_0J2Lz7nBkx7ZpQyF6s2j")]
+    pub window_id: String,
+    /// End of the maintenance window, in UTC
+    #[schema(value_type = PrimitiveDateTime, example = "2023-11-20T00:30:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+    /// Message of the connector about the maintenance
+    #[schema(example = "Scheduled maintenance of the acquiring platform")]
+    pub message: Option<String>,
+}
+
+/// The amount of a payment converted to the currency in which it is displayed to the customer, at
+/// an indicative exchange rate which may differ from the rate applied by the customer's bank
+#[derive(Clone, Debug, PartialEq, serde::Serialize, ToSchema)]
+pub struct DisplayAmount {
+    /// The currency in which the amount is displayed
+    #[schema(value_type = Currency, example = "EUR")]
+    pub currency: api_enums::Currency,
+    /// The converted amount, in the lowest denomination of `currency`
+    #[schema(example = 6180)]
+    pub amount: i64,
+    /// The number of units of `currency` for one unit of the currency of the payment
+    #[schema(example = 0.9155)]
+    pub exchange_rate: f64,
+    /// A timestamp (ISO 8601 code) at which the exchange rate was published by the rates provider
+    #[schema(example = "2022-09-10T10:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub rates_as_of: PrimitiveDateTime,
+}
+
+/// The fee charged by the connector for processing a payment. The fee is reported in the
+/// currency in which the connector charged it, which can differ from the currency of the payment
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ConnectorFeeDetails {
+    /// The fee, in the lowest denomination of `fee_currency`
+    #[schema(example = 59)]
+    pub fee_amount: i64,
+    /// The currency in which the connector charged the fee
+    #[schema(value_type = Currency, example = "USD")]
+    pub fee_currency: api_enums::Currency,
+    /// The amount settled to the merchant after deducting the fee, in the lowest denomination of `fee_currency`
+    #[schema(example = 941)]
+    pub net_amount: Option<i64>,
+}
+
+/// The address and card verification code check results returned by the connector, along with
+/// their values normalized across connectors
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct CardVerificationResults {
+    /// The normalized result of the address verification
+    #[schema(value_type = Option<AvsResult>, example = "full_match")]
+    pub avs_result: Option<api_enums::AvsResult>,
+    /// The address verification result code as returned by the connector
+    #[schema(example = "Y")]
+    pub raw_avs_result: Option<String>,
+    /// The normalized result of the card verification code check
+    #[schema(value_type = Option<CvcResult>, example = "match")]
+    pub cvc_result: Option<api_enums::CvcResult>,
+    /// The card verification code result as returned by the connector
+    #[schema(example = "M")]
+    pub raw_cvc_result: Option<String>,
+}
+
+/// The day of the routing decisions of the merchant to summarize
+#[derive(Clone, Debug, Default, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingDecisionsSummaryRequest {
+    /// The day to summarize in UTC, in the `YYYY-MM-DD` format. Defaults to the current day
+    #[schema(example = "2023-11-14")]
+    pub date: Option<String>,
+}
+
+/// The routing decisions of the payments of the merchant taken during a day, counted for each
+/// connector
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct RoutingDecisionsSummaryResponse {
+    /// The day which is summarized in UTC
+    #[schema(example = "2023-11-14")]
+    pub date: String,
+    /// The counts of the decisions of each connector, ordered by connector
+    pub connectors: Vec<ConnectorRoutingSummary>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct ConnectorRoutingSummary {
+    #[schema(example = "stripe")]
+    pub connector: String,
+    /// The attempts which were routed to the connector
+    #[schema(example = 120)]
+    pub routed: u32,
+    /// The attempts which were routed to the connector as a fallback for another connector
+    #[schema(example = 4)]
+    pub fallbacks: u32,
+    /// The attempts in which the connector was skipped in favour of a fallback connector
+    #[schema(example = 2)]
+    pub skipped: u32,
+    /// The requests sent to the connector, including the ones blocked before being sent
+    #[schema(example = 118)]
+    pub attempts: u32,
+    /// The attempts of the payments which were retried after an earlier attempt of the payment
+    #[schema(example = 6)]
+    pub retries: u32,
+    /// The requests which the connector did not process, as it failed, timed out or was
+    /// unavailable
+    #[schema(example = 3)]
+    pub failed_attempts: u32,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema)]
@@ -1979,6 +2687,10 @@ pub struct PaymentListConstraints {
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     #[serde(rename = "created.gte")]
     pub created_gte: Option<PrimitiveDateTime>,
+
+    /// Only the payments created with live API keys when true, or with test API keys when false
+    #[schema(example = true)]
+    pub livemode: Option<bool>,
 }
 
 #[derive(Clone, Debug, serde::Serialize, ToSchema)]
@@ -2019,6 +2731,8 @@ pub struct PaymentListFilterConstraints {
     pub status: Option<Vec<enums::IntentStatus>>,
     /// The list of payment methods to filter payments list
     pub payment_methods: Option<Vec<enums::PaymentMethod>>,
+    /// Only the payments created with live API keys when true, or with test API keys when false
+    pub livemode: Option<bool>,
 }
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct PaymentListFilters {
@@ -2032,6 +2746,53 @@ pub struct PaymentListFilters {
     pub payment_method: Vec<enums::PaymentMethod>,
 }
 
+/// The criterion of the payment which a connector is not configured to accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityCriterion {
+    /// No enabled merchant connector account was found for the connector
+    ConnectorAccount,
+    PaymentMethod,
+    PaymentMethodType,
+    Currency,
+    Country,
+    /// The amount of the payment is outside the amount range configured for the payment method type
+    Amount,
+}
+
+/// Eligibility of a connector to process a payment, based on the payment methods enabled for its merchant connector account
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, ToSchema)]
+pub struct ConnectorEligibility {
+    /// Name of the Connector
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// Unique ID of the Merchant Connector Account which was validated
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+
+    /// Whether the connector can process the payment
+    pub eligible: bool,
+
+    /// The criterion which the connector is not configured to accept, present only if the connector is not eligible
+    #[schema(value_type = Option<EligibilityCriterion>, example = "currency")]
+    pub failed_criterion: Option<EligibilityCriterion>,
+
+    /// Reason for the connector not being eligible
+    #[schema(example = "currency EUR is not enabled for payment method type credit")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct EligibleConnectorsResponse {
+    /// The identifier for the payment
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// Eligibility of each of the connectors configured for the business profile of the payment
+    pub connectors: Vec<ConnectorEligibility>,
+}
+
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
 pub struct TimeRange {
     /// The start time to filter payments list or to get list of filters. To get list of filters start time is needed to be passed
@@ -2153,8 +2914,12 @@ impl From<AdditionalPaymentData> for PaymentMethodDataResponse {
         match payment_method_data {
             AdditionalPaymentData::Card(card) => Self::Card(CardResponse::from(*card)),
             AdditionalPaymentData::PayLater {} => Self::PayLater,
-            AdditionalPaymentData::Wallet {} => Self::Wallet,
-            AdditionalPaymentData::BankRedirect { .. } => Self::BankRedirect,
+            AdditionalPaymentData::Wallet { wallet_type } => {
+                Self::Wallet(WalletResponse { wallet_type })
+            }
+            AdditionalPaymentData::BankRedirect { bank_name } => {
+                Self::BankRedirect(BankRedirectResponse { bank_name })
+            }
             AdditionalPaymentData::Crypto {} => Self::Crypto,
             AdditionalPaymentData::BankDebit {} => Self::BankDebit,
             AdditionalPaymentData::MandatePayment {} => Self::MandatePayment,
@@ -2227,6 +2992,12 @@ pub struct OrderDetailsWithAmount {
     pub quantity: u16,
     /// the amount per quantity of product
     pub amount: i64,
+    /// The type of the product, used by connectors for risk assessment
+    #[schema(value_type = Option<ProductType>, example = "physical")]
+    pub product_type: Option<api_enums::ProductType>,
+    /// The commodity code of the product, passed as Level 3 data to connectors supporting it
+    #[schema(max_length = 12, example = "44121903")]
+    pub commodity_code: Option<String>,
 }
 
 #[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2237,6 +3008,9 @@ pub struct OrderDetails {
     /// The quantity of the product to be purchased
     #[schema(example = 1)]
     pub quantity: u16,
+    /// The type of the product, used by connectors for risk assessment
+    #[schema(value_type = Option<ProductType>, example = "physical")]
+    pub product_type: Option<api_enums::ProductType>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2634,6 +3408,42 @@ pub struct PaymentsSessionResponse {
     pub session_token: Vec<SessionToken>,
 }
 
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentsDeviceDataRequest {
+    /// This is a token which expires after 15 minutes, used from the client to authenticate and create sessions from the SDK
+    pub client_secret: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentsDeviceDataResponse {
+    /// The identifier for the payment
+    pub payment_id: String,
+    /// Session of the device data collection, which is sent along with the authorization to the
+    /// connectors requiring it. The same session is returned on each call for the payment
+    #[schema(example = "dds_5bDkHXt9vpKgMrLqw2yN")]
+    pub device_session_id: String,
+    /// The device data collection which the SDK runs for each of the connectors of the merchant
+    /// requiring it, empty if none of the connectors requires it
+    pub device_data_collection: Vec<DeviceDataCollectionParams>,
+}
+
+/// Parameters with which the SDK runs the device data collection script of a connector
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct DeviceDataCollectionParams {
+    /// The connector which requires the device data collection
+    #[schema(example = "cybersource")]
+    pub connector: String,
+    /// The organization of the connector under which the device data is collected
+    #[schema(example = "1snn5n9w")]
+    pub org_id: String,
+    /// The session identifier passed to the script
+    #[schema(example = "dds_5bDkHXt9vpKgMrLqw2yN")]
+    pub session_id: String,
+    /// The script which collects the device data
+    #[schema(example = "https://h.online-metrix.net/fp/tags.js")]
+    pub script_url: String,
+}
+
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
 pub struct PaymentRetrieveBody {
     /// The identifier for the Merchant Account.
@@ -2646,6 +3456,79 @@ pub struct PaymentRetrieveBody {
     pub expand_captures: Option<bool>,
     /// If enabled provides list of attempts linked to payment intent
     pub expand_attempts: Option<bool>,
+    /// Field of the payment which is only returned when expanded
+    #[schema(value_type = Option<RetrieveExpandField>, example = "connector_response")]
+    pub expand: Option<RetrieveExpandField>,
+}
+
+/// The fields of the payments and refunds which are only returned by retrieve when expanded
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetrieveExpandField {
+    /// The raw response of the connector to the latest request made for the payment or refund,
+    /// which requires an API key with the `connector_response_read` permission
+    ConnectorResponse,
+    /// The steps of the processing of the attempts of the payment, for payments only
+    Timeline,
+    /// The decisions taken in routing the attempts of the payment, for payments only
+    RoutingDecisions,
+}
+
+/// Whether a response of the connector is returned in `connector_response`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorResponseCaptureStatus {
+    /// The response of the connector was captured
+    Captured,
+    /// The capture of the connector responses is disabled for the merchant
+    NotCaptured,
+    /// No response of the connector was captured for the payment or refund, as it was not sent to
+    /// the connector since the capture was enabled
+    NoResponse,
+}
+
+/// The raw response of the connector to the latest request made for the payment or refund. The
+/// sensitive fields of the response and the card numbers in it are masked, and the body is
+/// truncated when it is too long
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct ConnectorResponseDetails {
+    #[schema(value_type = ConnectorResponseCaptureStatus, example = "captured")]
+    pub status: ConnectorResponseCaptureStatus,
+    /// The connector which sent the response
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+    /// The flow of the request which the connector responded to
+    #[schema(example = "Authorize")]
+    pub flow: Option<String>,
+    /// The attempt of the payment which the request was made for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4_1")]
+    pub attempt_id: Option<String>,
+    /// The HTTP status code of the response
+    #[schema(example = 402)]
+    pub status_code: Option<u16>,
+    /// The body of the response, with its sensitive fields masked
+    pub body: Option<String>,
+    /// Whether the body was truncated to the maximum length of a captured response
+    pub is_truncated: Option<bool>,
+    /// The time at which the response was received
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub captured_at: Option<PrimitiveDateTime>,
+}
+
+impl ConnectorResponseDetails {
+    /// Marker returned in place of the response of the connector when none was captured
+    pub fn without_response(status: ConnectorResponseCaptureStatus) -> Self {
+        Self {
+            status,
+            connector: None,
+            flow: None,
+            attempt_id: None,
+            status_code: None,
+            body: None,
+            is_truncated: None,
+            captured_at: None,
+        }
+    }
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2667,6 +3550,9 @@ pub struct PaymentsCancelRequest {
     pub payment_id: String,
     /// The reason for the payment cancel
     pub cancellation_reason: Option<String>,
+    /// The reason code for the payment cancel, this is mapped to the reasons accepted by the connector
+    #[schema(value_type = Option<CancellationReason>, example = "requested_by_customer")]
+    pub cancellation_reason_code: Option<enums::CancellationReason>,
     /// Merchant connector details used to make payments.
     #[schema(value_type = MerchantConnectorDetailsWrap)]
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
@@ -2875,6 +3761,37 @@ mod tests {
     #![allow(clippy::unwrap_used)]
     use super::*;
 
+    #[test]
+    fn test_shipping_method_and_product_type_are_validated() {
+        let request: PaymentsRequest = serde_json::from_str(
+            r#"{
+                "order_category": "electronics",
+                "shipping_method": "digital",
+                "estimated_delivery_date": "2023-10-20T00:00:00Z",
+                "order_details": [
+                    { "product_name": "ebook", "quantity": 1, "amount": 500, "product_type": "digital" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.shipping_method,
+            Some(api_enums::ShippingMethod::Digital)
+        );
+        assert_eq!(
+            request.order_details.unwrap()[0].product_type,
+            Some(api_enums::ProductType::Digital)
+        );
+
+        assert!(
+            serde_json::from_str::<PaymentsRequest>(r#"{ "shipping_method": "drone" }"#).is_err()
+        );
+        assert!(serde_json::from_str::<OrderDetailsWithAmount>(
+            r#"{ "product_name": "ebook", "quantity": 1, "amount": 500, "product_type": "nft" }"#
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_mandate_type() {
         let mandate_type = MandateType::default();
@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
-use crate::{admin, enums};
+use crate::{admin, enums, payments};
 
-#[derive(Default, Debug, ToSchema, Clone, Deserialize)]
+#[derive(Default, Debug, ToSchema, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RefundRequest {
     /// Unique Identifier for the Refund. This is to ensure idempotency for multiple partial refund initiated against the same payment. If the identifiers is not defined by the merchant, this filed shall be auto generated and provide in the API response. It is recommended to generate uuid(v4) as the refund_id.
@@ -32,10 +32,18 @@ pub struct RefundRequest {
     #[schema(minimum = 100, example = 6540)]
     pub amount: Option<i64>,
 
+    /// The three-letter ISO currency code of the refund, which must be the currency of the payment. If not provided, this will default to the currency of the payment
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
     /// An arbitrary string attached to the object. Often useful for displaying to users and your customer support executive
     #[schema(max_length = 255, example = "Customer returned the product")]
     pub reason: Option<String>,
 
+    /// The reason for the refund, which is mapped to the reasons accepted by the connector. Connectors which accept free text receive the `reason` if it is provided
+    #[schema(value_type = Option<RefundReason>, example = "requested_by_customer")]
+    pub reason_code: Option<enums::RefundReason>,
+
     /// The type of refund based on waiting time for processing: Scheduled or Instant Refund
     #[schema(default = "Instant", example = "Instant")]
     pub refund_type: Option<RefundType>,
@@ -52,6 +60,8 @@ pub struct RefundRequest {
 #[derive(Default, Debug, Clone, Deserialize)]
 pub struct RefundsRetrieveBody {
     pub force_sync: Option<bool>,
+    /// Field of the refund which is only returned when expanded
+    pub expand: Option<payments::RetrieveExpandField>,
 }
 
 #[derive(Default, Debug, ToSchema, Clone, Deserialize)]
@@ -106,6 +116,9 @@ pub struct RefundResponse {
     pub currency: String,
     /// An arbitrary string attached to the object. Often useful for displaying to users and your customer support executive
     pub reason: Option<String>,
+    /// The reason for the refund
+    #[schema(value_type = Option<RefundReason>, example = "requested_by_customer")]
+    pub reason_code: Option<enums::RefundReason>,
     /// The status for refund
     pub status: RefundStatus,
     /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object
@@ -124,6 +137,17 @@ pub struct RefundResponse {
     /// The connector used for the refund and the corresponding payment
     #[schema(example = "stripe")]
     pub connector: String,
+    /// The identifier of the merchant connector account through which the refund is made, which is
+    /// the account of the payment attempt being refunded
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+    /// Acquirer reference number (ARN) of the refund, which the customer can use to trace the
+    /// refund with their bank
+    #[schema(example = "74537603289200123456789")]
+    pub acquirer_reference_number: Option<String>,
+    /// The raw response of the connector to the latest request made for the refund, only returned
+    /// by retrieve with `expand=connector_response`
+    pub connector_response: Option<payments::ConnectorResponseDetails>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
@@ -181,6 +205,105 @@ pub struct RefundListMetaData {
     pub status: Vec<enums::RefundStatus>,
 }
 
+#[derive(Debug, ToSchema, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkRefundRequest {
+    /// The refunds to be initiated. Each refund is validated independently, the refunds which fail validation are rejected without affecting the others
+    pub refunds: Vec<BulkRefundItemRequest>,
+}
+
+#[derive(Default, Debug, ToSchema, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkRefundItemRequest {
+    /// Unique Identifier for the Refund. If the identifier is not defined by the merchant, it shall be auto generated and provided in the API response
+    #[schema(
+        max_length = 30,
+        min_length = 30,
+        example = "ref_mbabizu24mvu3mela5njyhpit4"
+    )]
+    pub refund_id: Option<String>,
+
+    /// The identifier for the payment to be refunded
+    #[schema(
+        max_length = 30,
+        min_length = 30,
+        example = "pay_mbabizu24mvu3mela5njyhpit4"
+    )]
+    pub payment_id: String,
+
+    /// Total amount for which the refund is to be initiated. Amount for the payment in lowest denomination of the currency. If not provided, this will default to the full payment amount
+    #[schema(minimum = 100, example = 6540)]
+    pub amount: Option<i64>,
+
+    /// The three-letter ISO currency code of the refund, which must be the currency of the payment. If not provided, this will default to the currency of the payment
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
+    /// An arbitrary string attached to the object. Often useful for displaying to users and your customer support executive
+    #[schema(max_length = 255, example = "Customer returned the product")]
+    pub reason: Option<String>,
+
+    /// The reason for the refund, which is mapped to the reasons accepted by the connector
+    #[schema(value_type = Option<RefundReason>, example = "requested_by_customer")]
+    pub reason_code: Option<enums::RefundReason>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type  = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+impl From<BulkRefundItemRequest> for RefundRequest {
+    fn from(item: BulkRefundItemRequest) -> Self {
+        Self {
+            refund_id: item.refund_id,
+            payment_id: item.payment_id,
+            merchant_id: None,
+            amount: item.amount,
+            currency: item.currency,
+            reason: item.reason,
+            reason_code: item.reason_code,
+            refund_type: Some(RefundType::Scheduled),
+            metadata: item.metadata,
+            merchant_connector_details: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct BulkRefundResponse {
+    /// The identifier for the bulk refund, which can be used to retrieve the status of its refunds
+    #[schema(example = "bulk_ref_mbabizu24mvu3mela5njyhpit4")]
+    pub bulk_refund_id: String,
+    /// The number of refunds which were accepted for processing
+    pub accepted_count: usize,
+    /// The number of refunds which were rejected
+    pub rejected_count: usize,
+    /// The outcome of each refund of the request, in the order of the request
+    pub refunds: Vec<BulkRefundItemResponse>,
+    /// The timestamp at which the bulk refund is created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct BulkRefundItemResponse {
+    /// The identifier for payment
+    pub payment_id: String,
+    /// The identifier for the refund, present if the refund was accepted
+    pub refund_id: Option<String>,
+    /// The refund amount, present if the refund was accepted
+    pub amount: Option<i64>,
+    /// Whether the refund was accepted for processing or rejected
+    #[schema(value_type = BulkRefundItemStatus)]
+    pub status: enums::BulkRefundItemStatus,
+    /// The current status of the refund, present if the refund was accepted
+    pub refund_status: Option<RefundStatus>,
+    /// The code for the error, present if the refund was rejected or has failed
+    pub error_code: Option<String>,
+    /// The error message, present if the refund was rejected or has failed
+    pub error_message: Option<String>,
+}
+
 /// The status for refunds
 #[derive(
     Debug,
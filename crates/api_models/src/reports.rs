@@ -0,0 +1,41 @@
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReconciliationReportRequest {
+    /// Payments and refunds created at or after this time are reported
+    #[schema(example = "2023-10-05T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// Payments and refunds created at or before this time are reported. If not passed, the current time is used
+    #[schema(example = "2023-10-05T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReconciliationReportScheduleRequest {
+    /// Payments and refunds created at or after this time are reported
+    #[schema(example = "2023-10-05T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// Payments and refunds created at or before this time are reported
+    #[schema(example = "2023-10-05T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// Once this report is generated, generate the report for the same time range on the following day, so that a report is generated every day
+    #[serde(default)]
+    pub daily: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct ReconciliationReportScheduleResponse {
+    /// The identifier of the file to which the report is written, which can be retrieved once the report is generated
+    #[schema(example = "file_4huXTr6nPyL5m5U1aF9Z")]
+    pub file_id: String,
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
-use crate::{disputes, enums as api_enums, payments, refunds};
+use crate::{alerts, disputes, enums as api_enums, payments, refunds};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -28,6 +28,8 @@ pub enum IncomingWebhookEvent {
     // dispute has been unsuccessfully challenged
     DisputeLost,
     EndpointVerification,
+    // chargeback prevention alert received ahead of a chargeback
+    AlertReceived,
 }
 
 pub enum WebhookFlow {
@@ -37,6 +39,7 @@ pub enum WebhookFlow {
     Subscription,
     ReturnResponse,
     BankTransfer,
+    Alert,
 }
 
 impl From<IncomingWebhookEvent> for WebhookFlow {
@@ -61,6 +64,7 @@ impl From<IncomingWebhookEvent> for WebhookFlow {
             IncomingWebhookEvent::EndpointVerification => Self::ReturnResponse,
             IncomingWebhookEvent::SourceChargeable
             | IncomingWebhookEvent::SourceTransactionCreated => Self::BankTransfer,
+            IncomingWebhookEvent::AlertReceived => Self::Alert,
         }
     }
 }
@@ -102,6 +106,62 @@ pub struct OutgoingWebhook {
 
     /// The time at which webhook was sent
     pub timestamp: PrimitiveDateTime,
+
+    /// The version of the payload, the fields below it being sent only from the version `v2`
+    #[schema(value_type = Option<WebhookPayloadVersion>, example = "v2")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_version: Option<api_enums::WebhookPayloadVersion>,
+
+    /// The error of the payment or the refund, classified by its unified code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<OutgoingWebhookError>,
+
+    /// The attempts made for the payment, the latest first
+    #[schema(value_type = Option<Vec<PaymentAttemptResponse>>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<Vec<payments::PaymentAttemptResponse>>,
+
+    /// The steps of the processing of the attempts of the payment, sent to the merchants which
+    /// opted in to the timeline of the payments
+    #[schema(value_type = Option<Vec<PaymentTimelineEvent>>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<Vec<payments::PaymentTimelineEvent>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OutgoingWebhookError {
+    /// The error code received from the connector
+    #[schema(example = "card_declined")]
+    pub code: Option<String>,
+
+    /// The error message received from the connector
+    #[schema(example = "Your card was declined")]
+    pub message: Option<String>,
+
+    /// The unified code classifying the error, the same across the connectors
+    #[schema(example = "payment_declined")]
+    pub unified_code: String,
+
+    /// The message of the unified code, which can be shown to the customer
+    #[schema(
+        example = "Your payment was declined. Please use a different payment method or contact your bank."
+    )]
+    pub unified_message: String,
+}
+
+/// The event as sent in each version of the payloads of the webhooks
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookEventPreviewResponse {
+    /// The unique event id for each webhook
+    pub event_id: String,
+
+    /// The type of event this webhook corresponds to.
+    #[schema(value_type = EventType)]
+    pub event_type: api_enums::EventType,
+
+    /// The payloads of the event, one for each version, rendered from the current state of the
+    /// object of the event
+    pub payloads: Vec<OutgoingWebhook>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -113,6 +173,8 @@ pub enum OutgoingWebhookContent {
     RefundDetails(refunds::RefundResponse),
     #[schema(value_type = DisputeResponse)]
     DisputeDetails(Box<disputes::DisputeResponse>),
+    #[schema(value_type = AlertResponse)]
+    AlertDetails(Box<alerts::AlertResponse>),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -57,6 +57,8 @@ pub enum AttemptStatus {
     PaymentMethodAwaited,
     ConfirmationAwaited,
     DeviceDataCollectionPending,
+    AuthorizationExpired,
+    PartiallyAuthorized,
 }
 
 impl AttemptStatus {
@@ -68,6 +70,7 @@ impl AttemptStatus {
             | Self::Voided
             | Self::VoidFailed
             | Self::CaptureFailed
+            | Self::AuthorizationExpired
             | Self::Failure => true,
             Self::Started
             | Self::AuthenticationFailed
@@ -84,7 +87,8 @@ impl AttemptStatus {
             | Self::Pending
             | Self::PaymentMethodAwaited
             | Self::ConfirmationAwaited
-            | Self::DeviceDataCollectionPending => false,
+            | Self::DeviceDataCollectionPending
+            | Self::PartiallyAuthorized => false,
         }
     }
 }
@@ -372,6 +376,51 @@ impl Currency {
         Ok(amount)
     }
 
+    /// Convert the amount to its base denomination with as many decimal digits as the currency
+    /// has, using integer arithmetic so that the result is exact for every amount
+    pub fn to_currency_base_unit_exact(&self, amount: i64) -> String {
+        let decimal_digits = self.number_of_digits_after_decimal_point();
+        if decimal_digits == 0 {
+            return amount.to_string();
+        }
+        let minor_units_per_unit = 10_u64.pow(decimal_digits.into());
+        let sign = if amount < 0 { "-" } else { "" };
+        let absolute_amount = amount.unsigned_abs();
+        format!(
+            "{sign}{}.{:0width$}",
+            absolute_amount / minor_units_per_unit,
+            absolute_amount % minor_units_per_unit,
+            width = usize::from(decimal_digits)
+        )
+    }
+
+    /// Convert an amount in its base denomination, like `"12.50"`, to the lowest denomination of the
+    /// currency using integer arithmetic. Returns `None` if the amount is not a valid decimal or has
+    /// more decimal digits than the currency
+    pub fn to_currency_lower_unit_exact(&self, amount: &str) -> Option<i64> {
+        let decimal_digits = usize::from(self.number_of_digits_after_decimal_point());
+        let (sign, absolute_amount) = match amount.trim().strip_prefix('-') {
+            Some(absolute_amount) => (-1, absolute_amount),
+            None => (1, amount.trim()),
+        };
+        let (units, fraction) = absolute_amount
+            .split_once('.')
+            .unwrap_or((absolute_amount, ""));
+        let is_valid = !units.is_empty()
+            && fraction.len() <= decimal_digits
+            && units
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit());
+        if !is_valid {
+            return None;
+        }
+        format!("{units}{fraction:0<decimal_digits$}")
+            .parse::<i64>()
+            .ok()
+            .map(|amount| sign * amount)
+    }
+
     ///Convert the higher decimal amount to its base absolute units
     pub fn to_currency_lower_unit(&self, amount: String) -> Result<String, ParseFloatError> {
         let amount_f64 = amount.parse::<f64>()?;
@@ -525,6 +574,16 @@ impl Currency {
         }
     }
 
+    pub fn number_of_digits_after_decimal_point(self) -> u8 {
+        if self.is_zero_decimal_currency() {
+            0
+        } else if self.is_three_decimal_currency() {
+            3
+        } else {
+            2
+        }
+    }
+
     pub fn is_zero_decimal_currency(self) -> bool {
         match self {
             Self::BIF
@@ -782,6 +841,7 @@ impl Currency {
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
+    strum::EnumIter,
     strum::EnumString,
     ToSchema,
 )]
@@ -792,6 +852,7 @@ pub enum EventType {
     PaymentSucceeded,
     PaymentFailed,
     PaymentProcessing,
+    PaymentCancelled,
     ActionRequired,
     RefundSucceeded,
     RefundFailed,
@@ -802,6 +863,9 @@ pub enum EventType {
     DisputeChallenged,
     DisputeWon,
     DisputeLost,
+    PaymentCaptureDeadlineApproaching,
+    AlertReceived,
+    PaymentPartiallyAuthorized,
 }
 
 #[derive(
@@ -1018,6 +1082,7 @@ pub enum PaymentMethodType {
     Twint,
     UpiCollect,
     Vipps,
+    VirtualAccount,
     Walley,
     WeChatPay,
     SevenEleven,
@@ -1087,33 +1152,111 @@ pub enum RefundStatus {
     TransactionFailure,
 }
 
-/// The status of the mandate, which indicates whether it can be used to initiate a payment
+/// The reason for which the refund is initiated, this is mapped to the reasons accepted by the connector
 #[derive(
     Clone,
     Copy,
     Debug,
     Eq,
+    Hash,
     PartialEq,
-    Default,
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
+    strum::EnumIter,
     strum::EnumString,
     ToSchema,
 )]
-#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[router_derive::diesel_enum(storage_type = "text")]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
-pub enum MandateStatus {
-    #[default]
-    Active,
-    Inactive,
+pub enum RefundReason {
+    /// The payment was made more than once
+    Duplicate,
+    /// The payment was not authorized by the card holder
+    Fraudulent,
+    /// The customer requested the refund, for instance after returning the product
+    RequestedByCustomer,
+    /// Any other reason, which can be described in the free text reason of the refund
+    Other,
+}
+
+/// The outcome of the validation of a refund of a bulk refund
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum BulkRefundItemStatus {
+    /// The refund was created and is processed asynchronously
+    Accepted,
+    /// The refund failed validation and was not created
+    Rejected,
+}
+
+/// The status of a migration of mandates between merchant connector accounts
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MandateMigrationStatus {
+    /// The mandates are yet to be migrated by the scheduler
     Pending,
-    Revoked,
+    /// Every mandate of the migration has been processed
+    Completed,
 }
 
+/// The outcome of the migration of a mandate
 #[derive(
     Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MandateMigrationItemStatus {
+    /// The mandate is yet to be migrated
+    Pending,
+    /// The mandate now points at the new merchant connector account
+    Migrated,
+    /// The mandate could not be migrated and still points at the old merchant connector account
+    Failed,
+}
+
+/// The reason for which the payment is cancelled, this is mapped to the reasons accepted by the connector
+#[derive(
+    Clone,
+    Copy,
     Debug,
     Eq,
     Hash,
@@ -1121,30 +1264,33 @@ pub enum MandateStatus {
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
-    strum::EnumVariantNames,
     strum::EnumIter,
     strum::EnumString,
     ToSchema,
 )]
 #[router_derive::diesel_enum(storage_type = "text")]
-pub enum CardNetwork {
-    Visa,
-    Mastercard,
-    AmericanExpress,
-    JCB,
-    DinersClub,
-    Discover,
-    CartesBancaires,
-    UnionPay,
-    Interac,
-    RuPay,
-    Maestro,
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CancellationReason {
+    /// The customer requested the cancellation of the payment
+    RequestedByCustomer,
+    /// The payment is suspected to be fraudulent
+    FraudSuspected,
+    /// The payment was made more than once
+    Duplicate,
+    /// The customer abandoned the payment, for instance by cancelling it on the page of the connector
+    Abandoned,
+    /// The payment or its authorization expired before it was completed
+    Expired,
+    /// The connector authorized less than the amount of the payment, and the merchant did not
+    /// request partial authorizations
+    PartiallyAuthorized,
 }
 
+/// The method by which the goods of the order are delivered to the customer
 #[derive(
     Clone,
     Copy,
-    Default,
     Debug,
     Eq,
     Hash,
@@ -1155,20 +1301,25 @@ pub enum CardNetwork {
     strum::EnumString,
     ToSchema,
 )]
-#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[router_derive::diesel_enum(storage_type = "text")]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
-pub enum DisputeStage {
-    PreDispute,
-    #[default]
-    Dispute,
-    PreArbitration,
+pub enum ShippingMethod {
+    /// Delivered by the regular shipping service
+    Standard,
+    /// Delivered by an expedited shipping service
+    Express,
+    /// Collected by the customer from a store or pickup point
+    Pickup,
+    /// Delivered electronically, no shipping address is involved
+    Digital,
 }
 
+/// The channel through which the customer gave their payment details
 #[derive(
     Clone,
-    Debug,
     Copy,
+    Debug,
     Default,
     Eq,
     Hash,
@@ -1179,125 +1330,396 @@ pub enum DisputeStage {
     strum::EnumString,
     ToSchema,
 )]
-#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[router_derive::diesel_enum(storage_type = "text")]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
-pub enum DisputeStatus {
+pub enum PaymentChannel {
+    /// Entered by the customer on the website or app of the merchant
     #[default]
-    DisputeOpened,
-    DisputeExpired,
-    DisputeAccepted,
-    DisputeCancelled,
-    DisputeChallenged,
-    // dispute has been successfully challenged by the merchant
-    DisputeWon,
-    // dispute has been unsuccessfully challenged
-    DisputeLost,
+    Ecommerce,
+    /// Sent to the merchant by mail, the customer is not present to authenticate the payment
+    MailOrder,
+    /// Given to the merchant over the phone, the customer is not present to authenticate the
+    /// payment
+    TelephoneOrder,
+}
+
+impl PaymentChannel {
+    /// Mail order and telephone order (MOTO) payments are made without a browser and are out of
+    /// the scope of 3DS
+    pub fn is_moto(self) -> bool {
+        matches!(self, Self::MailOrder | Self::TelephoneOrder)
+    }
 }
 
+/// Operations on existing payments and refunds which can be disabled for a merchant connector
+/// account, while the payments continue to be processed through it
 #[derive(
     Clone,
+    Copy,
     Debug,
     Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorFlow {
+    Capture,
+    Void,
+    Refund,
+}
+
+/// How the fee retained by the platform on a split payment is reversed when the payment is
+/// refunded
+#[derive(
+    Clone,
+    Copy,
+    Debug,
     Default,
+    Eq,
     Hash,
     PartialEq,
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
     strum::EnumString,
-    utoipa::ToSchema,
-    Copy
+    ToSchema,
 )]
-#[router_derive::diesel_enum(storage_type = "pg_enum")]
-#[rustfmt::skip]
-pub enum CountryAlpha2 {
-    AF, AX, AL, DZ, AS, AD, AO, AI, AQ, AG, AR, AM, AW, AU, AT,
-    AZ, BS, BH, BD, BB, BY, BE, BZ, BJ, BM, BT, BO, BQ, BA, BW,
-    BV, BR, IO, BN, BG, BF, BI, KH, CM, CA, CV, KY, CF, TD, CL,
-    CN, CX, CC, CO, KM, CG, CD, CK, CR, CI, HR, CU, CW, CY, CZ,
-    DK, DJ, DM, DO, EC, EG, SV, GQ, ER, EE, ET, FK, FO, FJ, FI,
-    FR, GF, PF, TF, GA, GM, GE, DE, GH, GI, GR, GL, GD, GP, GU,
-    GT, GG, GN, GW, GY, HT, HM, VA, HN, HK, HU, IS, IN, ID, IR,
-    IQ, IE, IM, IL, IT, JM, JP, JE, JO, KZ, KE, KI, KP, KR, KW,
-    KG, LA, LV, LB, LS, LR, LY, LI, LT, LU, MO, MK, MG, MW, MY,
-    MV, ML, MT, MH, MQ, MR, MU, YT, MX, FM, MD, MC, MN, ME, MS,
-    MA, MZ, MM, NA, NR, NP, NL, NC, NZ, NI, NE, NG, NU, NF, MP,
-    NO, OM, PK, PW, PS, PA, PG, PY, PE, PH, PN, PL, PT, PR, QA,
-    RE, RO, RU, RW, BL, SH, KN, LC, MF, PM, VC, WS, SM, ST, SA,
-    SN, RS, SC, SL, SG, SX, SK, SI, SB, SO, ZA, GS, SS, ES, LK,
-    SD, SR, SJ, SZ, SE, CH, SY, TW, TJ, TZ, TH, TL, TG, TK, TO,
-    TT, TN, TR, TM, TC, TV, UG, UA, AE, GB, UM, UY, UZ, VU,
-    VE, VN, VG, VI, WF, EH, YE, ZM, ZW,
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PlatformFeeRefundBehaviour {
+    /// The fee is reversed in proportion to the refunded amount, a full refund reverses the whole
+    /// fee
     #[default]
-    US
+    Proportional,
+    /// The platform keeps the whole fee, the refund is borne by the sub-account
+    Retain,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-#[rustfmt::skip]
-pub enum CountryAlpha3 {
-    AFG, ALA, ALB, DZA, ASM, AND, AGO, AIA, ATA, ATG, ARG, ARM, ABW, AUS, AUT,
-    AZE, BHS, BHR, BGD, BRB, BLR, BEL, BLZ, BEN, BMU, BTN, BOL, BES, BIH, BWA,
-    BVT, BRA, IOT, BRN, BGR, BFA, BDI, CPV, KHM, CMR, CAN, CYM, CAF, TCD, CHL,
-    CHN, CXR, CCK, COL, COM, COG, COD, COK, CRI, CIV, HRV, CUB, CUW, CYP, CZE,
-    DNK, DJI, DMA, DOM, ECU, EGY, SLV, GNQ, ERI, EST, ETH, FLK, FRO, FJI, FIN,
-    FRA, GUF, PYF, ATF, GAB, GMB, GEO, DEU, GHA, GIB, GRC, GRL, GRD, GLP, GUM,
-    GTM, GGY, GIN, GNB, GUY, HTI, HMD, VAT, HND, HKG, HUN, ISL, IND, IDN, IRN,
-    IRQ, IRL, IMN, ISR, ITA, JAM, JPN, JEY, JOR, KAZ, KEN, KIR, PRK, KOR, KWT,
-    KGZ, LAO, LVA, LBN, LSO, LBR, LBY, LIE, LTU, LUX, MAC, MKD, MDG, MWI, MYS,
-    MDV, MLI, MLT, MHL, MTQ, MRT, MUS, MYT, MEX, FSM, MDA, MCO, MNG, MNE, MSR,
-    MAR, MOZ, MMR, NAM, NRU, NPL, NLD, NCL, NZL, NIC, NER, NGA, NIU, NFK, MNP,
-    NOR, OMN, PAK, PLW, PSE, PAN, PNG, PRY, PER, PHL, PCN, POL, PRT, PRI, QAT,
-    REU, ROU, RUS, RWA, BLM, SHN, KNA, LCA, MAF, SPM, VCT, WSM, SMR, STP, SAU,
-    SEN, SRB, SYC, SLE, SGP, SXM, SVK, SVN, SLB, SOM, ZAF, SGS, SSD, ESP, LKA,
-    SDN, SUR, SJM, SWZ, SWE, CHE, SYR, TWN, TJK, TZA, THA, TLS, TGO, TKL, TON,
-    TTO, TUN, TUR, TKM, TCA, TUV, UGA, UKR, ARE, GBR, USA, UMI, URY, UZB, VUT,
-    VEN, VNM, VGB, VIR, WLF, ESH, YEM, ZMB, ZWE
+/// What is done when a customer saves a card which they have already saved
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DuplicatePaymentMethodBehaviour {
+    /// The payment method already saved with the card is returned, its expiry is updated if the
+    /// card being saved expires later
+    #[default]
+    ReturnExisting,
+    /// A new payment method is created, linked to the saved one by the fingerprint of the card.
+    /// Only the most recently saved of the linked payment methods is listed for the customer
+    CreateNew,
 }
 
+/// What is done when a payment is created with the merchant order reference id of a payment which
+/// was created earlier and has not failed
 #[derive(
     Clone,
     Copy,
     Debug,
+    Eq,
+    Hash,
     PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DuplicateOrderReferenceBehaviour {
+    /// The payment is not created, the request is rejected with the id of the existing payment
+    Reject,
+    /// The payment is not created, the existing payment is returned instead
+    ReturnExisting,
+    /// The payment is created and flagged as a suspected duplicate of the existing payment
+    Flag,
+}
+
+/// The type of the product purchased in a line item of the order
+#[derive(
+    Clone,
+    Copy,
+    Debug,
     Eq,
     Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
     strum::Display,
-    strum::EnumVariantNames,
-    strum::EnumIter,
     strum::EnumString,
-    Deserialize,
-    Serialize,
+    ToSchema,
 )]
-pub enum Country {
-    Afghanistan,
-    AlandIslands,
-    Albania,
-    Algeria,
-    AmericanSamoa,
-    Andorra,
-    Angola,
-    Anguilla,
-    Antarctica,
-    AntiguaAndBarbuda,
-    Argentina,
-    Armenia,
-    Aruba,
-    Australia,
-    Austria,
-    Azerbaijan,
-    Bahamas,
-    Bahrain,
-    Bangladesh,
-    Barbados,
-    Belarus,
-    Belgium,
-    Belize,
-    Benin,
-    Bermuda,
-    Bhutan,
-    BoliviaPlurinationalState,
-    BonaireSintEustatiusAndSaba,
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProductType {
+    /// Goods which are shipped to the customer
+    Physical,
+    /// Goods delivered electronically, such as software, media or vouchers
+    Digital,
+    /// A service rendered to the customer
+    Service,
+    /// A gift card or store credit
+    GiftCard,
+}
+
+/// The status of the mandate, which indicates whether it can be used to initiate a payment
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Default,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MandateStatus {
+    #[default]
+    Active,
+    Inactive,
+    Pending,
+    Revoked,
+    RequiresAttention,
+}
+
+/// The calendar period, in UTC, in which the number of charges against a mandate is limited
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MandateFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumVariantNames,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+pub enum CardNetwork {
+    Visa,
+    Mastercard,
+    AmericanExpress,
+    JCB,
+    DinersClub,
+    Discover,
+    CartesBancaires,
+    UnionPay,
+    Interac,
+    RuPay,
+    Maestro,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DisputeStage {
+    PreDispute,
+    #[default]
+    Dispute,
+    PreArbitration,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DisputeStatus {
+    #[default]
+    DisputeOpened,
+    DisputeExpired,
+    DisputeAccepted,
+    DisputeCancelled,
+    DisputeChallenged,
+    // dispute has been successfully challenged by the merchant
+    DisputeWon,
+    // dispute has been unsuccessfully challenged
+    DisputeLost,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    Default,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+    Copy
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[rustfmt::skip]
+pub enum CountryAlpha2 {
+    AF, AX, AL, DZ, AS, AD, AO, AI, AQ, AG, AR, AM, AW, AU, AT,
+    AZ, BS, BH, BD, BB, BY, BE, BZ, BJ, BM, BT, BO, BQ, BA, BW,
+    BV, BR, IO, BN, BG, BF, BI, KH, CM, CA, CV, KY, CF, TD, CL,
+    CN, CX, CC, CO, KM, CG, CD, CK, CR, CI, HR, CU, CW, CY, CZ,
+    DK, DJ, DM, DO, EC, EG, SV, GQ, ER, EE, ET, FK, FO, FJ, FI,
+    FR, GF, PF, TF, GA, GM, GE, DE, GH, GI, GR, GL, GD, GP, GU,
+    GT, GG, GN, GW, GY, HT, HM, VA, HN, HK, HU, IS, IN, ID, IR,
+    IQ, IE, IM, IL, IT, JM, JP, JE, JO, KZ, KE, KI, KP, KR, KW,
+    KG, LA, LV, LB, LS, LR, LY, LI, LT, LU, MO, MK, MG, MW, MY,
+    MV, ML, MT, MH, MQ, MR, MU, YT, MX, FM, MD, MC, MN, ME, MS,
+    MA, MZ, MM, NA, NR, NP, NL, NC, NZ, NI, NE, NG, NU, NF, MP,
+    NO, OM, PK, PW, PS, PA, PG, PY, PE, PH, PN, PL, PT, PR, QA,
+    RE, RO, RU, RW, BL, SH, KN, LC, MF, PM, VC, WS, SM, ST, SA,
+    SN, RS, SC, SL, SG, SX, SK, SI, SB, SO, ZA, GS, SS, ES, LK,
+    SD, SR, SJ, SZ, SE, CH, SY, TW, TJ, TZ, TH, TL, TG, TK, TO,
+    TT, TN, TR, TM, TC, TV, UG, UA, AE, GB, UM, UY, UZ, VU,
+    VE, VN, VG, VI, WF, EH, YE, ZM, ZW,
+    #[default]
+    US
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[rustfmt::skip]
+pub enum CountryAlpha3 {
+    AFG, ALA, ALB, DZA, ASM, AND, AGO, AIA, ATA, ATG, ARG, ARM, ABW, AUS, AUT,
+    AZE, BHS, BHR, BGD, BRB, BLR, BEL, BLZ, BEN, BMU, BTN, BOL, BES, BIH, BWA,
+    BVT, BRA, IOT, BRN, BGR, BFA, BDI, CPV, KHM, CMR, CAN, CYM, CAF, TCD, CHL,
+    CHN, CXR, CCK, COL, COM, COG, COD, COK, CRI, CIV, HRV, CUB, CUW, CYP, CZE,
+    DNK, DJI, DMA, DOM, ECU, EGY, SLV, GNQ, ERI, EST, ETH, FLK, FRO, FJI, FIN,
+    FRA, GUF, PYF, ATF, GAB, GMB, GEO, DEU, GHA, GIB, GRC, GRL, GRD, GLP, GUM,
+    GTM, GGY, GIN, GNB, GUY, HTI, HMD, VAT, HND, HKG, HUN, ISL, IND, IDN, IRN,
+    IRQ, IRL, IMN, ISR, ITA, JAM, JPN, JEY, JOR, KAZ, KEN, KIR, PRK, KOR, KWT,
+    KGZ, LAO, LVA, LBN, LSO, LBR, LBY, LIE, LTU, LUX, MAC, MKD, MDG, MWI, MYS,
+    MDV, MLI, MLT, MHL, MTQ, MRT, MUS, MYT, MEX, FSM, MDA, MCO, MNG, MNE, MSR,
+    MAR, MOZ, MMR, NAM, NRU, NPL, NLD, NCL, NZL, NIC, NER, NGA, NIU, NFK, MNP,
+    NOR, OMN, PAK, PLW, PSE, PAN, PNG, PRY, PER, PHL, PCN, POL, PRT, PRI, QAT,
+    REU, ROU, RUS, RWA, BLM, SHN, KNA, LCA, MAF, SPM, VCT, WSM, SMR, STP, SAU,
+    SEN, SRB, SYC, SLE, SGP, SXM, SVK, SVN, SLB, SOM, ZAF, SGS, SSD, ESP, LKA,
+    SDN, SUR, SJM, SWZ, SWE, CHE, SYR, TWN, TJK, TZA, THA, TLS, TGO, TKL, TON,
+    TTO, TUN, TUR, TKM, TCA, TUV, UGA, UKR, ARE, GBR, USA, UMI, URY, UZB, VUT,
+    VEN, VNM, VGB, VIR, WLF, ESH, YEM, ZMB, ZWE
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumVariantNames,
+    strum::EnumIter,
+    strum::EnumString,
+    Deserialize,
+    Serialize,
+)]
+pub enum Country {
+    Afghanistan,
+    AlandIslands,
+    Albania,
+    Algeria,
+    AmericanSamoa,
+    Andorra,
+    Angola,
+    Anguilla,
+    Antarctica,
+    AntiguaAndBarbuda,
+    Argentina,
+    Armenia,
+    Aruba,
+    Australia,
+    Austria,
+    Azerbaijan,
+    Bahamas,
+    Bahrain,
+    Bangladesh,
+    Barbados,
+    Belarus,
+    Belgium,
+    Belize,
+    Benin,
+    Bermuda,
+    Bhutan,
+    BoliviaPlurinationalState,
+    BonaireSintEustatiusAndSaba,
     BosniaAndHerzegovina,
     Botswana,
     BouvetIsland,
@@ -1796,8 +2218,398 @@ pub enum ReconStatus {
     Active,
     Disabled,
 }
+/// The permissions that can be granted to an API key
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiKeyPermission {
+    /// Retrieve and list payments, refunds, customers, mandates and disputes
+    PaymentsRead,
+    /// Create, update, confirm, capture and cancel payments
+    PaymentsWrite,
+    /// Create and update refunds
+    RefundsWrite,
+    /// Create, update and delete customers and their payment methods
+    CustomersWrite,
+    /// Full access, including merchant account level operations
+    Admin,
+    /// Charge mandates beyond their amount and frequency limits
+    MandateLimitOverride,
+    /// Read the raw responses of the connectors to the payments and refunds
+    ConnectorResponseRead,
+}
+
+/// The kind of data a blocklist entry is matched against
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum BlocklistDataKind {
+    /// The first 6 or 8 digits of the card number
+    CardBin,
+    /// The fingerprint of the card number
+    CardFingerprint,
+    /// The email of the customer
+    Email,
+    /// The IP address from which the payment is made
+    Ip,
+}
+
+/// The kind of resource changed by an administrative operation recorded in the audit log
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditResourceType {
+    MerchantAccount,
+    MerchantConnectorAccount,
+    ApiKey,
+}
+
+/// The administrative operation recorded in the audit log
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// The kind of a chargeback prevention alert, which is raised about a payment before a
+/// chargeback is
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AlertType {
+    /// The payment was reported as fraudulent by the issuer, such as with an early fraud warning
+    Fraud,
+    /// The payment was disputed by the cardholder with the issuer
+    Dispute,
+}
+
+/// The status of a chargeback prevention alert
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AlertStatus {
+    /// The alert was recorded, no action was taken on it
+    Received,
+    /// The action of the alert was taken
+    Actioned,
+    /// The action of the alert was attempted and failed
+    ActionFailed,
+}
+
+/// The action taken automatically on a chargeback prevention alert
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AlertAction {
+    /// The payment of the alert was refunded in full
+    Refund,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ApplePayFlow {
     Simplified,
     Manual,
 }
+
+/// The outcome of the delivery of a webhook event to the endpoint of a subscription
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// The endpoint acknowledged the event
+    Delivered,
+    /// The endpoint could not be reached or did not acknowledge the event
+    Failed,
+    /// The event matched none of the subscriptions, it was not delivered
+    Skipped,
+}
+
+/// The version of the payloads of the outgoing webhooks. The webhooks are configured with the
+/// latest version and stay on it until the merchant changes it, as the payloads of a newer version
+/// may break the existing consumers
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookPayloadVersion {
+    /// The event type and the object of the event
+    V1,
+    /// The payload of `v1`, along with the unified error code of the failures and the attempts of
+    /// the payments
+    V2,
+}
+
+impl WebhookPayloadVersion {
+    /// The version new webhooks are configured with
+    pub const LATEST: Self = Self::V2;
+}
+
+/// A step of the processing of a payment attempt, reported for the progress of the payment. The
+/// steps are informational only, the status of the payment is not derived from them.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentAttemptStep {
+    /// The request of the payment was sent to the connector
+    ConnectorCallStarted,
+    /// The customer was redirected to authenticate the payment
+    RedirectIssued,
+    /// The customer returned from the redirection
+    RedirectReturned,
+    /// The payment was synced with the connector and is still being processed by it
+    SyncPending,
+}
+
+/// A decision taken in routing a payment attempt to a connector
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RoutingDecisionType {
+    /// The connector was skipped in favour of a fallback connector
+    ConnectorSkipped,
+    /// The attempt was routed to the connector
+    ConnectorRouted,
+    /// The request of the attempt was sent to the connector, or was blocked before it
+    ConnectorAttempted,
+}
+
+/// The routing rule which chose the connector of a payment attempt
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RoutingRule {
+    /// The straight through routing algorithm passed in the request
+    RequestStraightThrough,
+    /// The straight through routing algorithm stored on the payment by an earlier request
+    PaymentStraightThrough,
+    /// The routing algorithm of the merchant account
+    MerchantRoutingAlgorithm,
+}
+
+/// Why a connector chosen by the routing was skipped in favour of a fallback connector
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RoutingFallbackReason {
+    /// The merchant connector account of the connector is not configured to accept the payment
+    Ineligible,
+    /// The circuit breaker of the connector is open
+    CircuitOpen,
+    /// The connector is in a maintenance window
+    UnderMaintenance,
+}
+
+/// The class of the outcome of a request sent to a connector for a payment attempt
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorAttemptOutcome {
+    /// The connector processed the request successfully
+    Success,
+    /// The connector processed the request and returned an error, like a decline
+    BusinessDecline,
+    /// The connector failed to process the request, or its response could not be handled
+    ConnectorError,
+    /// The connector did not respond in time
+    Timeout,
+    /// The request was not sent as the circuit breaker of the connector is open
+    CircuitOpen,
+    /// The request was not sent as the connector is in a maintenance window
+    UnderMaintenance,
+}
+
+impl ConnectorAttemptOutcome {
+    /// Whether the request was not processed by the connector
+    pub fn is_failure(self) -> bool {
+        !matches!(self, Self::Success | Self::BusinessDecline)
+    }
+}
@@ -0,0 +1,227 @@
+//! Canonical JSON serialization, for the connectors which sign the exact bytes of the JSON request
+//! body. The same value always serializes to the same bytes, whatever the order of the fields of
+//! the structs or of the entries of the maps it is made of:
+//!
+//! - the keys of the objects are sorted recursively, in the order of their UTF-16 code units
+//! - there is no whitespace between the tokens
+//! - integral numbers are written without a fraction or an exponent, other numbers in their
+//!   shortest form without an exponent, and negative zero as `0`
+//! - strings are escaped as by `serde_json`: quotes, backslashes and control characters are
+//!   escaped, other characters including the non-ASCII ones are written as they are
+
+use error_stack::{IntoReport, ResultExt};
+use serde::Serialize;
+
+use crate::{
+    crypto::SignMessage,
+    errors::{self, CustomResult},
+};
+
+/// Serializes the value to canonical JSON
+pub fn to_canonical_json<T>(value: &T) -> CustomResult<String, errors::ParsingError>
+where
+    T: Serialize + ?Sized,
+{
+    let value = serde_json::to_value(value)
+        .into_report()
+        .change_context(errors::ParsingError::EncodeError("canonical json"))?;
+    let mut canonical_json = String::new();
+    write_value(&mut canonical_json, &value)?;
+    Ok(canonical_json)
+}
+
+/// Signs the canonical JSON of the value with the secret, using the signing algorithm
+pub fn sign_canonical_json<T>(
+    value: &T,
+    secret: &[u8],
+    algorithm: &impl SignMessage,
+) -> CustomResult<Vec<u8>, errors::CryptoError>
+where
+    T: Serialize + ?Sized,
+{
+    let canonical_json =
+        to_canonical_json(value).change_context(errors::CryptoError::EncodingFailed)?;
+    algorithm.sign_message(secret, canonical_json.as_bytes())
+}
+
+fn write_value(
+    output: &mut String,
+    value: &serde_json::Value,
+) -> CustomResult<(), errors::ParsingError> {
+    match value {
+        serde_json::Value::Null => output.push_str("null"),
+        serde_json::Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        serde_json::Value::Number(number) => write_number(output, number)?,
+        serde_json::Value::String(value) => write_string(output, value)?,
+        serde_json::Value::Array(values) => {
+            output.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_value(output, value)?;
+            }
+            output.push(']');
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|(key, _), (other_key, _)| {
+                key.encode_utf16().cmp(other_key.encode_utf16())
+            });
+
+            output.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_string(output, key)?;
+                output.push(':');
+                write_value(output, value)?;
+            }
+            output.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_number(
+    output: &mut String,
+    number: &serde_json::Number,
+) -> CustomResult<(), errors::ParsingError> {
+    if number.is_i64() || number.is_u64() {
+        output.push_str(&number.to_string());
+        return Ok(());
+    }
+
+    let number = number
+        .as_f64()
+        .filter(|number| number.is_finite())
+        .ok_or(errors::ParsingError::EncodeError("canonical json number"))
+        .into_report()?;
+    // The `Display` implementation of floats writes the shortest representation which reads back
+    // as the same number, without an exponent and without a fraction for integral numbers
+    if number == 0.0 {
+        output.push('0');
+    } else {
+        output.push_str(&number.to_string());
+    }
+    Ok(())
+}
+
+fn write_string(output: &mut String, value: &str) -> CustomResult<(), errors::ParsingError> {
+    let escaped = serde_json::to_string(value)
+        .into_report()
+        .change_context(errors::ParsingError::EncodeError("canonical json string"))?;
+    output.push_str(&escaped);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::crypto::HmacSha256;
+
+    #[derive(Serialize)]
+    struct Address {
+        line_1: &'static str,
+        city: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct Customer {
+        name: &'static str,
+        addresses: Vec<Address>,
+    }
+
+    #[derive(Serialize)]
+    struct PaymentMethod {
+        #[serde(rename = "type")]
+        payment_method_type: &'static str,
+        fields: HashMap<&'static str, &'static str>,
+    }
+
+    #[derive(Serialize)]
+    struct PaymentRequest {
+        payment_method: PaymentMethod,
+        currency: &'static str,
+        amount: i64,
+        metadata: HashMap<&'static str, &'static str>,
+        customer: Customer,
+    }
+
+    const PAYMENT_REQUEST_JSON: &str = r#"{"amount":100,"customer":{"addresses":[{"city":"Zürich","line_1":"Bahnhofstrasse 1"},{"city":"東京","line_1":"丸の内 1-1"}],"name":"Jöhn \"JJ\" Doe"},"currency":"EUR","metadata":{"emoji":"😀","notes":"line 1\nline 2\u001f"},"payment_method":{"fields":{"cvv":"123","number":"4111111111111111"},"type":"de_visa_card"}}"#;
+
+    fn get_payment_request() -> PaymentRequest {
+        PaymentRequest {
+            payment_method: PaymentMethod {
+                payment_method_type: "de_visa_card",
+                fields: HashMap::from([("number", "4111111111111111"), ("cvv", "123")]),
+            },
+            currency: "EUR",
+            amount: 100,
+            metadata: HashMap::from([("notes", "line 1\nline 2\u{1f}"), ("emoji", "😀")]),
+            customer: Customer {
+                name: "Jöhn \"JJ\" Doe",
+                addresses: vec![
+                    Address {
+                        line_1: "Bahnhofstrasse 1",
+                        city: "Zürich",
+                    },
+                    Address {
+                        line_1: "丸の内 1-1",
+                        city: "東京",
+                    },
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn test_nested_objects_and_arrays_are_serialized_canonically() {
+        assert_eq!(
+            to_canonical_json(&get_payment_request()).unwrap(),
+            PAYMENT_REQUEST_JSON
+        );
+
+        // The same value built in a different order serializes to the same bytes
+        let value: serde_json::Value = serde_json::from_str(PAYMENT_REQUEST_JSON).unwrap();
+        assert_eq!(to_canonical_json(&value).unwrap(), PAYMENT_REQUEST_JSON);
+    }
+
+    #[test]
+    fn test_numbers_are_formatted_consistently() {
+        let value = serde_json::json!([100.0, 1.50, -0.0, 0.0000001, 12.345, -7, u64::MAX]);
+
+        assert_eq!(
+            to_canonical_json(&value).unwrap(),
+            "[100,1.5,0,0.0000001,12.345,-7,18446744073709551615]"
+        );
+    }
+
+    #[test]
+    fn test_keys_are_sorted_by_utf16_code_units() {
+        // U+1F600 is encoded with a surrogate pair, which sorts before U+E000 in UTF-16 whereas
+        // it sorts after U+E000 in UTF-8
+        let value = serde_json::json!({ "\u{e000}": 1, "😀": 2, "a": 3, "B": 4 });
+
+        assert_eq!(
+            to_canonical_json(&value).unwrap(),
+            "{\"B\":4,\"a\":3,\"😀\":2,\"\u{e000}\":1}"
+        );
+    }
+
+    #[test]
+    fn test_canonical_json_signature() {
+        let signature =
+            sign_canonical_json(&get_payment_request(), b"rapyd_secret_key", &HmacSha256).unwrap();
+
+        assert_eq!(
+            hex::encode(signature),
+            "6ca25fd30b3be8a9963bb7621ae98075fce63605639ef9bfbc61b5f494ef4bea"
+        );
+    }
+}
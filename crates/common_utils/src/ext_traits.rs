@@ -62,6 +62,16 @@ where
     where
         Self: Serialize;
 
+    ///
+    /// Functionality, for specifically encoding `Self` into `String`
+    /// after serialization by using `serde::Serialize`
+    /// specifically, to convert into canonical JSON `String`,
+    /// for the requests whose body is signed.
+    ///
+    fn encode_to_canonical_json(&'e self) -> CustomResult<String, errors::ParsingError>
+    where
+        Self: Serialize;
+
     ///
     /// Functionality, for specifically encoding `Self` into `String`
     /// after serialization by using `serde::Serialize`
@@ -141,6 +151,14 @@ where
             .attach_printable_lazy(|| format!("Unable to convert {self:?} to a request"))
     }
 
+    fn encode_to_canonical_json(&'e self) -> CustomResult<String, errors::ParsingError>
+    where
+        Self: Serialize,
+    {
+        crate::canonical_json::to_canonical_json(self)
+            .attach_printable_lazy(|| format!("Unable to convert {self:?} to a request"))
+    }
+
     fn encode_to_string_of_xml(&'e self) -> CustomResult<String, errors::ParsingError>
     where
         Self: Serialize,
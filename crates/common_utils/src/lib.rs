@@ -2,6 +2,7 @@
 #![warn(missing_docs, missing_debug_implementations)]
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR" ), "/", "README.md"))]
 
+pub mod canonical_json;
 pub mod consts;
 pub mod crypto;
 pub mod custom_serde;
@@ -19,6 +20,7 @@ pub mod validation;
 pub mod date_time {
     use std::{marker::PhantomData, num::NonZeroU8};
 
+    use error_stack::{IntoReport, ResultExt};
     use masking::{Deserialize, Serialize};
     #[cfg(feature = "async_ext")]
     use time::Instant;
@@ -27,8 +29,10 @@ pub mod date_time {
             well_known::iso8601::{Config, EncodedConfig, Iso8601, TimePrecision},
             FormatItem,
         },
-        OffsetDateTime, PrimitiveDateTime,
+        OffsetDateTime, PrimitiveDateTime, UtcOffset,
     };
+
+    use crate::errors::{CustomResult, ParsingError};
     /// Struct to represent milliseconds in time sensitive data fields
     #[derive(Debug)]
     pub struct Milliseconds(i32);
@@ -42,6 +46,31 @@ pub mod date_time {
         YYYYMMDD,
     }
 
+    /// Enum to represent the date and time formats expected by the connectors, always rendered in UTC
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ConnectorDateFormat {
+        /// Format the date in 2023-02-15T13:33:18.898Z format
+        Iso8601WithMillis,
+        /// Format the date in 20230215133318 format
+        YYYYMMDDHHmmss,
+        /// Format the date in Wed, 15 Feb 2023 13:33:18 GMT format, used in the signed headers
+        Rfc1123,
+        /// Format the date as the seconds elapsed since the UNIX epoch Eg: 1676467998
+        EpochSeconds,
+        /// Format the date as the milliseconds elapsed since the UNIX epoch Eg: 1676467998898
+        EpochMillis,
+    }
+
+    const ISO8601_WITH_MILLIS_CONFIG: EncodedConfig = Config::DEFAULT
+        .set_time_precision(TimePrecision::Second {
+            decimal_digits: NonZeroU8::new(3),
+        })
+        .encode();
+
+    const RFC1123_FORMAT: &[FormatItem<'static>] = time::macros::format_description!(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+    );
+
     /// Create a new [`PrimitiveDateTime`] with the current date and time in UTC.
     pub fn now() -> PrimitiveDateTime {
         let utc_date_time = OffsetDateTime::now_utc();
@@ -79,12 +108,96 @@ pub mod date_time {
 
     /// Return the current date and time in UTC with the format [year]-[month]-[day]T[hour]:[minute]:[second].mmmZ Eg: 2023-02-15T13:33:18.898Z
     pub fn date_as_yyyymmddthhmmssmmmz() -> Result<String, time::error::Format> {
-        const ISO_CONFIG: EncodedConfig = Config::DEFAULT
-            .set_time_precision(TimePrecision::Second {
-                decimal_digits: NonZeroU8::new(3),
-            })
-            .encode();
-        now().assume_utc().format(&Iso8601::<ISO_CONFIG>)
+        now_utc_formatted(ConnectorDateFormat::Iso8601WithMillis)
+    }
+
+    /// Return the given date and time, taken to be in UTC, in the given connector format Eg: format: Rfc1123 Eg: Wed, 15 Feb 2023 13:33:18 GMT
+    pub fn format_datetime(
+        date: PrimitiveDateTime,
+        format: ConnectorDateFormat,
+    ) -> Result<String, time::error::Format> {
+        let utc_date_time = date.assume_utc();
+        match format {
+            ConnectorDateFormat::Iso8601WithMillis => {
+                utc_date_time.format(&Iso8601::<ISO8601_WITH_MILLIS_CONFIG>)
+            }
+            ConnectorDateFormat::YYYYMMDDHHmmss => {
+                let format = <&[FormatItem<'_>]>::from(DateFormat::YYYYMMDDHHmmss);
+                utc_date_time.format(&format)
+            }
+            ConnectorDateFormat::Rfc1123 => utc_date_time.format(&RFC1123_FORMAT),
+            ConnectorDateFormat::EpochSeconds => Ok(unix_timestamp_seconds(date).to_string()),
+            ConnectorDateFormat::EpochMillis => Ok(unix_timestamp_millis(date).to_string()),
+        }
+    }
+
+    /// Return the current date and time in UTC in the given connector format
+    pub fn now_utc_formatted(format: ConnectorDateFormat) -> Result<String, time::error::Format> {
+        format_datetime(now(), format)
+    }
+
+    /// Return the UNIX timestamp in seconds of the given date and time, taken to be in UTC
+    pub fn unix_timestamp_seconds(date: PrimitiveDateTime) -> i64 {
+        date.assume_utc().unix_timestamp()
+    }
+
+    /// Return the UNIX timestamp in milliseconds of the given date and time, taken to be in UTC
+    pub fn unix_timestamp_millis(date: PrimitiveDateTime) -> i128 {
+        date.assume_utc().unix_timestamp_nanos() / 1_000_000
+    }
+
+    /// Return the UNIX timestamp in milliseconds of the current date and time in UTC
+    pub fn now_unix_timestamp_millis() -> i128 {
+        unix_timestamp_millis(now())
+    }
+
+    /// Parse a date and time sent by a connector in the given format, normalizing it to UTC
+    ///
+    /// Offsets present in ISO 8601 values are applied; the other formats carry no offset and are taken to be in UTC.
+    pub fn parse_datetime(
+        value: &str,
+        format: ConnectorDateFormat,
+    ) -> CustomResult<PrimitiveDateTime, ParsingError> {
+        let date_time = match format {
+            ConnectorDateFormat::Iso8601WithMillis => {
+                OffsetDateTime::parse(value, &Iso8601::DEFAULT)
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?
+            }
+            ConnectorDateFormat::YYYYMMDDHHmmss => {
+                let format = <&[FormatItem<'_>]>::from(DateFormat::YYYYMMDDHHmmss);
+                PrimitiveDateTime::parse(value, &format)
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?
+                    .assume_utc()
+            }
+            ConnectorDateFormat::Rfc1123 => PrimitiveDateTime::parse(value, &RFC1123_FORMAT)
+                .into_report()
+                .change_context(ParsingError::DateTimeParsingError)?
+                .assume_utc(),
+            ConnectorDateFormat::EpochSeconds => {
+                let seconds = value
+                    .parse::<i64>()
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?;
+                OffsetDateTime::from_unix_timestamp(seconds)
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?
+            }
+            ConnectorDateFormat::EpochMillis => {
+                let nanoseconds = value
+                    .parse::<i128>()
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?
+                    .checked_mul(1_000_000)
+                    .ok_or(ParsingError::DateTimeParsingError)
+                    .into_report()?;
+                OffsetDateTime::from_unix_timestamp_nanos(nanoseconds)
+                    .into_report()
+                    .change_context(ParsingError::DateTimeParsingError)?
+            }
+        };
+        Ok(convert_to_pdt(date_time.to_offset(UtcOffset::UTC)))
     }
 
     impl From<DateFormat> for &[FormatItem<'_>] {
@@ -178,6 +291,74 @@ pub mod date_time {
             f.write_str(&output)
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        #![allow(clippy::unwrap_used)]
+        use time::macros::datetime;
+
+        use super::*;
+
+        const FIXED_INSTANT: PrimitiveDateTime = datetime!(2023-02-15 13:33:18.898);
+
+        #[test]
+        fn test_format_datetime_pins_each_connector_format() {
+            let cases = [
+                (
+                    ConnectorDateFormat::Iso8601WithMillis,
+                    "2023-02-15T13:33:18.898Z",
+                ),
+                (ConnectorDateFormat::YYYYMMDDHHmmss, "20230215133318"),
+                (
+                    ConnectorDateFormat::Rfc1123,
+                    "Wed, 15 Feb 2023 13:33:18 GMT",
+                ),
+                (ConnectorDateFormat::EpochSeconds, "1676467998"),
+                (ConnectorDateFormat::EpochMillis, "1676467998898"),
+            ];
+            for (format, expected) in cases {
+                assert_eq!(format_datetime(FIXED_INSTANT, format).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn test_epoch_helpers() {
+            assert_eq!(unix_timestamp_seconds(FIXED_INSTANT), 1676467998);
+            assert_eq!(unix_timestamp_millis(FIXED_INSTANT), 1676467998898);
+        }
+
+        #[test]
+        fn test_parse_datetime_round_trips_each_connector_format() {
+            let without_millis = datetime!(2023-02-15 13:33:18);
+            let cases = [
+                (ConnectorDateFormat::Iso8601WithMillis, FIXED_INSTANT),
+                (ConnectorDateFormat::YYYYMMDDHHmmss, without_millis),
+                (ConnectorDateFormat::Rfc1123, without_millis),
+                (ConnectorDateFormat::EpochSeconds, without_millis),
+                (ConnectorDateFormat::EpochMillis, FIXED_INSTANT),
+            ];
+            for (format, expected) in cases {
+                let formatted = format_datetime(FIXED_INSTANT, format).unwrap();
+                assert_eq!(parse_datetime(&formatted, format).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn test_parse_datetime_normalizes_offsets_to_utc() {
+            let parsed = parse_datetime(
+                "2023-02-15T19:03:18.898+05:30",
+                ConnectorDateFormat::Iso8601WithMillis,
+            )
+            .unwrap();
+            assert_eq!(parsed, FIXED_INSTANT);
+        }
+
+        #[test]
+        fn test_parse_datetime_rejects_values_in_another_format() {
+            assert!(parse_datetime("20230215133318", ConnectorDateFormat::Rfc1123).is_err());
+            assert!(parse_datetime("not a timestamp", ConnectorDateFormat::EpochMillis).is_err());
+        }
+    }
 }
 
 /// Generate a nanoid with the given prefix and length
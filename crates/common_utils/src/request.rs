@@ -1,6 +1,4 @@
 use masking::{Maskable, Secret};
-#[cfg(feature = "logs")]
-use router_env::logger;
 use serde::{Deserialize, Serialize};
 
 use crate::errors;
@@ -32,6 +30,15 @@ fn default_request_headers() -> [(String, Maskable<String>); 1] {
     [(header::VIA.to_string(), "HyperSwitch".to_string().into())]
 }
 
+/// Proxy through which a request is sent, instead of the proxy configured for all the outgoing
+/// requests
+#[derive(Clone, Debug, Deserialize)]
+pub struct OutboundProxy {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<Secret<String>>,
+}
+
 #[derive(Debug)]
 pub struct Request {
     pub url: String,
@@ -42,6 +49,7 @@ pub struct Request {
     pub certificate: Option<String>,
     pub certificate_key: Option<String>,
     pub form_data: Option<reqwest::multipart::Form>,
+    pub proxy: Option<OutboundProxy>,
 }
 
 impl Request {
@@ -55,6 +63,7 @@ impl Request {
             certificate: None,
             certificate_key: None,
             form_data: None,
+            proxy: None,
         }
     }
 
@@ -85,6 +94,10 @@ impl Request {
     pub fn set_form_data(&mut self, form_data: reqwest::multipart::Form) {
         self.form_data = Some(form_data);
     }
+
+    pub fn add_proxy(&mut self, proxy: Option<OutboundProxy>) {
+        self.proxy = proxy;
+    }
 }
 
 #[derive(Debug)]
@@ -174,6 +187,7 @@ impl RequestBuilder {
             certificate: self.certificate,
             certificate_key: self.certificate_key,
             form_data: self.form_data,
+            proxy: None,
         }
     }
 }
@@ -188,6 +202,9 @@ impl Default for RequestBuilder {
 pub struct RequestBody(Secret<String>);
 
 impl RequestBody {
+    /// Encodes the connector request body. The body is not logged here, as its `Debug`
+    /// implementation may expose sensitive fields, it is logged with the sensitive fields masked
+    /// when the request is sent to the connector.
     pub fn log_and_get_request_body<T, F>(
         body: T,
         encoder: F,
@@ -196,8 +213,6 @@ impl RequestBody {
         F: FnOnce(T) -> errors::CustomResult<String, errors::ParsingError>,
         T: std::fmt::Debug,
     {
-        #[cfg(feature = "logs")]
-        logger::info!(connector_request_body=?body);
         Ok(Self(Secret::new(encoder(body)?)))
     }
     pub fn get_inner_value(request_body: Self) -> Secret<String> {
@@ -1,5 +1,7 @@
 use api_models::enums::Connector;
 use common_enums as storage_enums;
+use common_utils::pii::EncryptionStratergy;
+use masking::Secret;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
@@ -43,6 +45,13 @@ pub trait PaymentAttemptInterface {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError>;
 
+    async fn find_payment_attempt_by_merchant_id_acquirer_reference_number(
+        &self,
+        merchant_id: &str,
+        acquirer_reference_number: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError>;
+
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
         payment_id: &str,
@@ -87,6 +96,15 @@ pub trait PaymentAttemptInterface {
         payment_methods: Option<Vec<storage_enums::PaymentMethod>>,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<i64, errors::StorageError>;
+
+    /// Redacts the browser info and the payment method data of up to `limit` of the oldest attempts
+    /// created before `created_before`, which are not redacted yet. Returns the number of redacted
+    /// attempts.
+    async fn redact_payment_attempts_created_before(
+        &self,
+        created_before: PrimitiveDateTime,
+        limit: i64,
+    ) -> error_stack::Result<usize, errors::StorageError>;
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -139,6 +157,29 @@ pub struct PaymentAttempt {
     pub connector_response_reference_id: Option<String>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub external_authentication_data: Option<Secret<Vec<u8>, EncryptionStratergy>>,
+    pub card_verification_results: Option<serde_json::Value>,
+    pub three_ds_decision_data: Option<serde_json::Value>,
+    pub routing_metadata: Option<serde_json::Value>,
+    /// Merchant connector account through which the attempt was made
+    pub merchant_connector_id: Option<String>,
+    pub locale: Option<String>,
+    pub connector_fee_amount: Option<i64>,
+    pub fee_currency: Option<storage_enums::Currency>,
+    pub net_amount: Option<i64>,
+    pub merchant_descriptor: Option<String>,
+    pub sub_merchant_data: Option<serde_json::Value>,
+    pub auth_code: Option<String>,
+    pub acquirer_reference_number: Option<String>,
+    /// Amount authorized by the connector, when it authorized less than the amount of the attempt
+    pub authorized_amount: Option<i64>,
+    pub authorized_at: Option<PrimitiveDateTime>,
+    pub authorization_expires_at: Option<PrimitiveDateTime>,
+    pub cancellation_reason_code: Option<storage_enums::CancellationReason>,
+    pub livemode: bool,
+    /// Time at which the browser info and the payment method data of the attempt were redacted by
+    /// the data retention job
+    pub redacted_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -196,6 +237,29 @@ pub struct PaymentAttemptNew {
     pub multiple_capture_count: Option<i16>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub external_authentication_data: Option<Secret<Vec<u8>, EncryptionStratergy>>,
+    pub card_verification_results: Option<serde_json::Value>,
+    pub three_ds_decision_data: Option<serde_json::Value>,
+    pub routing_metadata: Option<serde_json::Value>,
+    /// Merchant connector account through which the attempt was made
+    pub merchant_connector_id: Option<String>,
+    pub locale: Option<String>,
+    pub connector_fee_amount: Option<i64>,
+    pub fee_currency: Option<storage_enums::Currency>,
+    pub net_amount: Option<i64>,
+    pub merchant_descriptor: Option<String>,
+    pub sub_merchant_data: Option<serde_json::Value>,
+    pub auth_code: Option<String>,
+    pub acquirer_reference_number: Option<String>,
+    /// Amount authorized by the connector, when it authorized less than the amount of the attempt
+    pub authorized_amount: Option<i64>,
+    pub authorized_at: Option<PrimitiveDateTime>,
+    pub authorization_expires_at: Option<PrimitiveDateTime>,
+    pub cancellation_reason_code: Option<storage_enums::CancellationReason>,
+    pub livemode: bool,
+    /// Time at which the browser info and the payment method data of the attempt were redacted by
+    /// the data retention job
+    pub redacted_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -213,12 +277,16 @@ pub enum PaymentAttemptUpdate {
         business_sub_label: Option<String>,
         amount_to_capture: Option<i64>,
         capture_method: Option<storage_enums::CaptureMethod>,
+        external_authentication_data: Option<Secret<Vec<u8>, EncryptionStratergy>>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
         connector: Option<String>,
         straight_through_algorithm: Option<serde_json::Value>,
         amount_capturable: Option<i64>,
+        locale: Option<String>,
+        routing_metadata: Option<serde_json::Value>,
+        merchant_connector_id: Option<String>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -240,6 +308,13 @@ pub enum PaymentAttemptUpdate {
         error_code: Option<Option<String>>,
         error_message: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        external_authentication_data: Option<Secret<Vec<u8>, EncryptionStratergy>>,
+        three_ds_decision_data: Option<serde_json::Value>,
+        routing_metadata: Option<serde_json::Value>,
+        locale: Option<String>,
+        merchant_descriptor: Option<String>,
+        sub_merchant_data: Option<serde_json::Value>,
+        merchant_connector_id: Option<String>,
     },
     RejectUpdate {
         status: storage_enums::AttemptStatus,
@@ -249,6 +324,7 @@ pub enum PaymentAttemptUpdate {
     VoidUpdate {
         status: storage_enums::AttemptStatus,
         cancellation_reason: Option<String>,
+        cancellation_reason_code: Option<storage_enums::CancellationReason>,
     },
     ResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -264,6 +340,13 @@ pub enum PaymentAttemptUpdate {
         error_reason: Option<Option<String>>,
         connector_response_reference_id: Option<String>,
         amount_capturable: Option<i64>,
+        card_verification_results: Option<serde_json::Value>,
+        connector_fee_amount: Option<i64>,
+        fee_currency: Option<storage_enums::Currency>,
+        net_amount: Option<i64>,
+        auth_code: Option<String>,
+        acquirer_reference_number: Option<String>,
+        authorized_amount: Option<i64>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -304,4 +387,16 @@ pub enum PaymentAttemptUpdate {
     SurchargeMetadataUpdate {
         surcharge_metadata: Option<serde_json::Value>,
     },
+    ConnectorFeeUpdate {
+        connector_fee_amount: i64,
+        fee_currency: storage_enums::Currency,
+        net_amount: Option<i64>,
+    },
+    AcquirerReferenceNumberUpdate {
+        acquirer_reference_number: String,
+    },
+    AuthorizationExpiryUpdate {
+        authorized_at: PrimitiveDateTime,
+        authorization_expires_at: PrimitiveDateTime,
+    },
 }
@@ -29,6 +29,16 @@ pub trait PaymentIntentInterface {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, errors::StorageError>;
 
+    /// The earliest payment created with the merchant order reference since `created_after`,
+    /// which has not failed or been cancelled
+    async fn find_payment_intent_by_merchant_order_reference_id(
+        &self,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+        created_after: PrimitiveDateTime,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Option<PaymentIntent>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -63,6 +73,15 @@ pub trait PaymentIntentInterface {
         constraints: &PaymentIntentFetchConstraints,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<Vec<String>, errors::StorageError>;
+
+    /// Archives up to `limit` of the oldest payments in one of `statuses` created before
+    /// `created_before`, which are not archived yet. Returns the number of archived payments.
+    async fn archive_payment_intents_created_before(
+        &self,
+        created_before: PrimitiveDateTime,
+        statuses: Vec<storage_enums::IntentStatus>,
+        limit: i64,
+    ) -> error_stack::Result<usize, errors::StorageError>;
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -105,6 +124,28 @@ pub struct PaymentIntent {
     // Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub applied_defaults: Option<serde_json::Value>,
+    pub order_category: Option<String>,
+    pub shipping_method: Option<storage_enums::ShippingMethod>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub estimated_delivery_date: Option<PrimitiveDateTime>,
+    pub merchant_order_reference_id: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub split_payment: Option<serde_json::Value>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub suspected_duplicate_of: Option<String>,
+    pub livemode: bool,
+    pub amount_details: Option<serde_json::Value>,
+    pub request_partial_authorization: Option<bool>,
+    /// Time at which the payment was archived by the data retention job, archived payments are
+    /// not listed unless the listing falls back to them
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub archived_at: Option<PrimitiveDateTime>,
+    /// Session of the device data collection run by the SDK before the authorization, sent to
+    /// the connectors which reject the payments without it
+    pub device_session_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -144,6 +185,28 @@ pub struct PaymentIntentNew {
     pub profile_id: Option<String>,
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub applied_defaults: Option<serde_json::Value>,
+    pub order_category: Option<String>,
+    pub shipping_method: Option<storage_enums::ShippingMethod>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub estimated_delivery_date: Option<PrimitiveDateTime>,
+    pub merchant_order_reference_id: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub split_payment: Option<serde_json::Value>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub suspected_duplicate_of: Option<String>,
+    pub livemode: bool,
+    pub amount_details: Option<serde_json::Value>,
+    pub request_partial_authorization: Option<bool>,
+    /// Time at which the payment was archived by the data retention job, archived payments are
+    /// not listed unless the listing falls back to them
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub archived_at: Option<PrimitiveDateTime>,
+    /// Session of the device data collection run by the SDK before the authorization, sent to
+    /// the connectors which reject the payments without it
+    pub device_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -188,6 +251,7 @@ pub enum PaymentIntentUpdate {
         order_details: Option<Vec<pii::SecretSerdeValue>>,
         metadata: Option<pii::SecretSerdeValue>,
         payment_confirm_source: Option<storage_enums::PaymentSource>,
+        device_session_id: Option<String>,
     },
     PaymentAttemptAndAttemptCountUpdate {
         active_attempt_id: String,
@@ -205,6 +269,9 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         merchant_decision: Option<String>,
     },
+    DeviceSessionUpdate {
+        device_session_id: String,
+    },
 }
 
 #[derive(Clone, Debug, Default)]
@@ -233,6 +300,7 @@ pub struct PaymentIntentUpdateInternal {
     // Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    pub device_session_id: Option<String>,
 }
 
 impl PaymentIntentUpdate {
@@ -258,6 +326,9 @@ impl PaymentIntentUpdate {
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
             order_details: internal_update.order_details.or(source.order_details),
+            device_session_id: internal_update
+                .device_session_id
+                .or(source.device_session_id),
             ..source
         }
     }
@@ -283,6 +354,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -301,6 +373,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
                 ..Default::default()
             },
             PaymentIntentUpdate::MetadataUpdate { metadata } => Self {
@@ -386,6 +459,11 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 merchant_decision,
                 ..Default::default()
             },
+            PaymentIntentUpdate::DeviceSessionUpdate { device_session_id } => Self {
+                device_session_id: Some(device_session_id),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -406,9 +484,40 @@ pub enum PaymentIntentFetchConstraints {
         starting_after_id: Option<String>,
         ending_before_id: Option<String>,
         limit: Option<u32>,
+        livemode: Option<bool>,
+        /// Whether the payments archived by the data retention job are listed
+        include_archived: bool,
     },
 }
 
+impl PaymentIntentFetchConstraints {
+    /// Whether the listing of the payments which are not archived returned fewer payments than the
+    /// limit, in which case the listing falls back to the archived payments
+    pub fn should_fall_back_to_archived(&self, count: usize) -> bool {
+        match self {
+            Self::Single { .. } => false,
+            Self::List {
+                include_archived: true,
+                ..
+            } => false,
+            Self::List { limit, .. } => limit
+                .and_then(|limit| usize::try_from(limit).ok())
+                .map_or(true, |limit| count < limit),
+        }
+    }
+
+    /// The same constraints, extended to the archived payments
+    pub fn with_archived(mut self) -> Self {
+        if let Self::List {
+            include_archived, ..
+        } = &mut self
+        {
+            *include_archived = true;
+        }
+        self
+    }
+}
+
 impl From<api_models::payments::PaymentListConstraints> for PaymentIntentFetchConstraints {
     fn from(value: api_models::payments::PaymentListConstraints) -> Self {
         Self::List {
@@ -423,6 +532,8 @@ impl From<api_models::payments::PaymentListConstraints> for PaymentIntentFetchCo
             starting_after_id: value.starting_after,
             ending_before_id: value.ending_before,
             limit: Some(std::cmp::min(value.limit, PAYMENTS_LIST_MAX_LIMIT_V1)),
+            livemode: value.livemode,
+            include_archived: false,
         }
     }
 }
@@ -441,6 +552,8 @@ impl From<api_models::payments::TimeRange> for PaymentIntentFetchConstraints {
             starting_after_id: None,
             ending_before_id: None,
             limit: None,
+            livemode: None,
+            include_archived: true,
         }
     }
 }
@@ -462,6 +575,8 @@ impl From<api_models::payments::PaymentListFilterConstraints> for PaymentIntentF
                 starting_after_id: None,
                 ending_before_id: None,
                 limit: Some(std::cmp::min(value.limit, PAYMENTS_LIST_MAX_LIMIT_V2)),
+                livemode: value.livemode,
+                include_archived: false,
             }
         }
     }
@@ -0,0 +1,95 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use masking::Secret;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::alert};
+
+#[derive(Clone, Debug, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = alert)]
+pub struct AlertNew {
+    pub alert_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub connector_alert_id: String,
+    pub alert_type: storage_enums::AlertType,
+    pub initiated_by: Option<String>,
+    pub connector_reason: Option<String>,
+    pub status: storage_enums::AlertStatus,
+    pub action: Option<storage_enums::AlertAction>,
+    pub refund_id: Option<String>,
+    /// The alert as it was sent by the connector
+    pub raw_payload: Secret<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = alert, primary_key(alert_id))]
+pub struct Alert {
+    pub alert_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub connector_alert_id: String,
+    pub alert_type: storage_enums::AlertType,
+    pub initiated_by: Option<String>,
+    pub connector_reason: Option<String>,
+    pub status: storage_enums::AlertStatus,
+    pub action: Option<storage_enums::AlertAction>,
+    pub refund_id: Option<String>,
+    pub raw_payload: Secret<serde_json::Value>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum AlertUpdate {
+    ActionUpdate {
+        status: storage_enums::AlertStatus,
+        action: storage_enums::AlertAction,
+        refund_id: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = alert)]
+pub struct AlertUpdateInternal {
+    status: storage_enums::AlertStatus,
+    action: Option<storage_enums::AlertAction>,
+    refund_id: Option<String>,
+    modified_at: PrimitiveDateTime,
+}
+
+impl From<AlertUpdate> for AlertUpdateInternal {
+    fn from(alert_update: AlertUpdate) -> Self {
+        match alert_update {
+            AlertUpdate::ActionUpdate {
+                status,
+                action,
+                refund_id,
+            } => Self {
+                status,
+                action: Some(action),
+                refund_id,
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
+
+impl AlertUpdateInternal {
+    pub fn apply_changeset(self, source: Alert) -> Alert {
+        Alert {
+            status: self.status,
+            action: self.action.or(source.action),
+            refund_id: self.refund_id.or(source.refund_id),
+            modified_at: self.modified_at,
+            ..source
+        }
+    }
+}
@@ -2,7 +2,7 @@ use diesel::{AsChangeset, AsExpression, Identifiable, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
-use crate::schema::api_keys;
+use crate::{enums::ApiKeyPermission, schema::api_keys};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Identifiable, Queryable)]
 #[diesel(table_name = api_keys, primary_key(key_id))]
@@ -16,6 +16,14 @@ pub struct ApiKey {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    #[diesel(deserialize_as = super::DieselArray<ApiKeyPermission>)]
+    pub permissions: Vec<ApiKeyPermission>,
+    #[serde(default = "crate::default_livemode")]
+    pub livemode: bool,
+    #[serde(default)]
+    pub strict_request_validation: bool,
+    #[serde(default)]
+    pub pii_restricted: bool,
 }
 
 #[derive(Debug, Insertable)]
@@ -30,6 +38,10 @@ pub struct ApiKeyNew {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub permissions: Vec<ApiKeyPermission>,
+    pub livemode: bool,
+    pub strict_request_validation: bool,
+    pub pii_restricted: bool,
 }
 
 #[derive(Debug)]
@@ -39,6 +51,7 @@ pub enum ApiKeyUpdate {
         description: Option<String>,
         expires_at: Option<Option<PrimitiveDateTime>>,
         last_used: Option<PrimitiveDateTime>,
+        permissions: Option<Vec<ApiKeyPermission>>,
     },
     LastUsedUpdate {
         last_used: PrimitiveDateTime,
@@ -52,6 +65,7 @@ pub(crate) struct ApiKeyUpdateInternal {
     pub description: Option<String>,
     pub expires_at: Option<Option<PrimitiveDateTime>>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub permissions: Option<Vec<ApiKeyPermission>>,
 }
 
 impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
@@ -62,17 +76,20 @@ impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
                 description,
                 expires_at,
                 last_used,
+                permissions,
             } => Self {
                 name,
                 description,
                 expires_at,
                 last_used,
+                permissions,
             },
             ApiKeyUpdate::LastUsedUpdate { last_used } => Self {
                 last_used: Some(last_used),
                 name: None,
                 description: None,
                 expires_at: None,
+                permissions: None,
             },
         }
     }
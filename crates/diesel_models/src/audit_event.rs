@@ -0,0 +1,39 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{
+    enums::{AuditAction, AuditResourceType},
+    schema::audit_event,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = audit_event, primary_key(audit_event_id))]
+pub struct AuditEvent {
+    pub audit_event_id: String,
+    pub merchant_id: String,
+    /// The identifier of the key which authenticated the request that made the change
+    pub actor_key_id: String,
+    pub resource_type: AuditResourceType,
+    pub resource_id: String,
+    pub action: AuditAction,
+    /// The changed fields of the resource with their values before and after the change, the
+    /// values of the secret fields are masked
+    pub diff: serde_json::Value,
+    pub request_id: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = audit_event)]
+pub struct AuditEventNew {
+    pub audit_event_id: String,
+    pub merchant_id: String,
+    pub actor_key_id: String,
+    pub resource_type: AuditResourceType,
+    pub resource_id: String,
+    pub action: AuditAction,
+    pub diff: serde_json::Value,
+    pub request_id: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
@@ -0,0 +1,103 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums::BlocklistDataKind, schema::blocklist};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = blocklist, primary_key(blocklist_id))]
+pub struct Blocklist {
+    pub blocklist_id: String,
+    pub merchant_id: String,
+    pub data_kind: BlocklistDataKind,
+    /// The value that is matched against, which is the card BIN itself for card BIN entries and
+    /// a keyed hash of the value for all other kinds of entries
+    pub fingerprint: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<PrimitiveDateTime>,
+    pub hit_count: i64,
+    pub last_hit_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+}
+
+impl Blocklist {
+    /// Whether the entry has expired and no longer blocks payments
+    pub fn is_expired(&self, current_time: PrimitiveDateTime) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= current_time)
+    }
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = blocklist)]
+pub struct BlocklistNew {
+    pub blocklist_id: String,
+    pub merchant_id: String,
+    pub data_kind: BlocklistDataKind,
+    pub fingerprint: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum BlocklistUpdate {
+    Update {
+        reason: Option<Option<String>>,
+        expires_at: Option<Option<PrimitiveDateTime>>,
+    },
+    HitUpdate {
+        hit_count: i64,
+        last_hit_at: PrimitiveDateTime,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = blocklist)]
+pub struct BlocklistUpdateInternal {
+    pub reason: Option<Option<String>>,
+    pub expires_at: Option<Option<PrimitiveDateTime>>,
+    pub hit_count: Option<i64>,
+    pub last_hit_at: Option<PrimitiveDateTime>,
+    pub modified_at: PrimitiveDateTime,
+}
+
+impl BlocklistUpdateInternal {
+    pub fn apply_changeset(self, source: Blocklist) -> Blocklist {
+        Blocklist {
+            reason: self.reason.unwrap_or(source.reason),
+            expires_at: self.expires_at.unwrap_or(source.expires_at),
+            hit_count: self.hit_count.unwrap_or(source.hit_count),
+            last_hit_at: self.last_hit_at.or(source.last_hit_at),
+            modified_at: self.modified_at,
+            ..source
+        }
+    }
+}
+
+impl From<BlocklistUpdate> for BlocklistUpdateInternal {
+    fn from(blocklist_update: BlocklistUpdate) -> Self {
+        let modified_at = common_utils::date_time::now();
+        match blocklist_update {
+            BlocklistUpdate::Update { reason, expires_at } => Self {
+                reason,
+                expires_at,
+                hit_count: None,
+                last_hit_at: None,
+                modified_at,
+            },
+            BlocklistUpdate::HitUpdate {
+                hit_count,
+                last_hit_at,
+            } => Self {
+                reason: None,
+                expires_at: None,
+                hit_count: Some(hit_count),
+                last_hit_at: Some(last_hit_at),
+                modified_at,
+            },
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums::BulkRefundItemStatus, schema::bulk_refund};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = bulk_refund, primary_key(bulk_refund_id))]
+pub struct BulkRefund {
+    pub bulk_refund_id: String,
+    pub merchant_id: String,
+    /// The outcome of every refund of the request, as a list of [`BulkRefundItem`]
+    pub items: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = bulk_refund)]
+pub struct BulkRefundNew {
+    pub bulk_refund_id: String,
+    pub merchant_id: String,
+    pub items: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The outcome of the validation of a refund of a bulk refund. The status of an accepted refund
+/// is tracked by the refund itself.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BulkRefundItem {
+    pub payment_id: String,
+    /// The identifier of the refund, passed in the request or generated for accepted refunds
+    pub refund_id: Option<String>,
+    pub amount: Option<i64>,
+    pub status: BulkRefundItemStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
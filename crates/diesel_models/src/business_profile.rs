@@ -1,7 +1,7 @@
 use common_utils::pii;
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 
-use crate::schema::business_profile;
+use crate::{encryption::Encryption, enums as storage_enums, schema::business_profile};
 
 #[derive(
     Clone,
@@ -32,6 +32,20 @@ pub struct BusinessProfile {
     pub is_recon_enabled: bool,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub statement_descriptor_name: Option<String>,
+    pub statement_descriptor_suffix: Option<String>,
+    pub session_expiry: Option<i64>,
+    pub default_locale: Option<String>,
+    pub default_payment_description: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub duplicate_order_reference_behaviour:
+        Option<storage_enums::DuplicateOrderReferenceBehaviour>,
+    pub duplicate_order_reference_window: Option<i64>,
+    pub alert_auto_refund_max_amount: Option<i64>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
+    pub webhook_auth_details: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -55,6 +69,20 @@ pub struct BusinessProfileNew {
     pub is_recon_enabled: bool,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub statement_descriptor_name: Option<String>,
+    pub statement_descriptor_suffix: Option<String>,
+    pub session_expiry: Option<i64>,
+    pub default_locale: Option<String>,
+    pub default_payment_description: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub duplicate_order_reference_behaviour:
+        Option<storage_enums::DuplicateOrderReferenceBehaviour>,
+    pub duplicate_order_reference_window: Option<i64>,
+    pub alert_auto_refund_max_amount: Option<i64>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
+    pub webhook_auth_details: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -75,6 +103,20 @@ pub struct BusinessProfileUpdateInternal {
     pub is_recon_enabled: Option<bool>,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub statement_descriptor_name: Option<String>,
+    pub statement_descriptor_suffix: Option<String>,
+    pub session_expiry: Option<i64>,
+    pub default_locale: Option<String>,
+    pub default_payment_description: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub duplicate_order_reference_behaviour:
+        Option<storage_enums::DuplicateOrderReferenceBehaviour>,
+    pub duplicate_order_reference_window: Option<i64>,
+    pub alert_auto_refund_max_amount: Option<i64>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
+    pub webhook_auth_details: Option<Encryption>,
 }
 
 impl From<BusinessProfileNew> for BusinessProfile {
@@ -97,6 +139,19 @@ impl From<BusinessProfileNew> for BusinessProfile {
             payout_routing_algorithm: new.payout_routing_algorithm,
             is_recon_enabled: new.is_recon_enabled,
             applepay_verified_domains: new.applepay_verified_domains,
+            statement_descriptor_name: new.statement_descriptor_name,
+            statement_descriptor_suffix: new.statement_descriptor_suffix,
+            session_expiry: new.session_expiry,
+            default_locale: new.default_locale,
+            default_payment_description: new.default_payment_description,
+            connector_request_reference_format: new.connector_request_reference_format,
+            duplicate_order_reference_behaviour: new.duplicate_order_reference_behaviour,
+            duplicate_order_reference_window: new.duplicate_order_reference_window,
+            alert_auto_refund_max_amount: new.alert_auto_refund_max_amount,
+            default_currency: new.default_currency,
+            default_country: new.default_country,
+            pii_policy: new.pii_policy,
+            webhook_auth_details: new.webhook_auth_details,
         }
     }
 }
@@ -122,6 +177,33 @@ impl BusinessProfileUpdateInternal {
             payout_routing_algorithm: self.payout_routing_algorithm,
             is_recon_enabled: self.is_recon_enabled.unwrap_or(source.is_recon_enabled),
             applepay_verified_domains: self.applepay_verified_domains,
+            statement_descriptor_name: self
+                .statement_descriptor_name
+                .or(source.statement_descriptor_name),
+            statement_descriptor_suffix: self
+                .statement_descriptor_suffix
+                .or(source.statement_descriptor_suffix),
+            session_expiry: self.session_expiry.or(source.session_expiry),
+            default_locale: self.default_locale.or(source.default_locale),
+            default_payment_description: self
+                .default_payment_description
+                .or(source.default_payment_description),
+            connector_request_reference_format: self
+                .connector_request_reference_format
+                .or(source.connector_request_reference_format),
+            duplicate_order_reference_behaviour: self
+                .duplicate_order_reference_behaviour
+                .or(source.duplicate_order_reference_behaviour),
+            duplicate_order_reference_window: self
+                .duplicate_order_reference_window
+                .or(source.duplicate_order_reference_window),
+            alert_auto_refund_max_amount: self
+                .alert_auto_refund_max_amount
+                .or(source.alert_auto_refund_max_amount),
+            default_currency: self.default_currency.or(source.default_currency),
+            default_country: self.default_country.or(source.default_country),
+            pii_policy: self.pii_policy.or(source.pii_policy),
+            webhook_auth_details: self.webhook_auth_details.or(source.webhook_auth_details),
             ..source
         }
     }
@@ -0,0 +1,40 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::schema::connector_response_capture;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = connector_response_capture, primary_key(capture_id))]
+pub struct ConnectorResponseCapture {
+    pub capture_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    /// The refund which the response is for, `None` for the responses of the payment flows
+    pub refund_id: Option<String>,
+    pub connector: String,
+    pub flow: String,
+    pub status_code: i32,
+    /// The body of the response with its sensitive fields masked
+    pub response_body: String,
+    /// Whether the body was truncated to the maximum length of a captured response
+    pub is_truncated: bool,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = connector_response_capture)]
+pub struct ConnectorResponseCaptureNew {
+    pub capture_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub refund_id: Option<String>,
+    pub connector: String,
+    pub flow: String,
+    pub status_code: i32,
+    pub response_body: String,
+    pub is_truncated: bool,
+    pub created_at: PrimitiveDateTime,
+}
@@ -39,6 +39,7 @@ pub enum EventClass {
     Payments,
     Refunds,
     Disputes,
+    Alerts,
 }
 
 #[derive(
@@ -59,6 +60,7 @@ pub enum EventObjectType {
     PaymentDetails,
     RefundDetails,
     DisputeDetails,
+    AlertDetails,
 }
 
 #[derive(
@@ -199,6 +201,8 @@ pub struct MandateAmountData {
     pub start_date: Option<PrimitiveDateTime>,
     pub end_date: Option<PrimitiveDateTime>,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub frequency: Option<MandateFrequency>,
+    pub max_charges_per_period: Option<i32>,
 }
 
 #[derive(
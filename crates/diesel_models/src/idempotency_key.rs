@@ -0,0 +1,87 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::schema::idempotency_key;
+
+#[derive(Clone, Debug, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = idempotency_key)]
+pub struct IdempotencyKeyNew {
+    pub merchant_id: String,
+    pub idempotency_key: String,
+    pub request_hash: String,
+    pub expires_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Identifiable, Queryable)]
+#[diesel(table_name = idempotency_key, primary_key(merchant_id, idempotency_key))]
+pub struct IdempotencyKey {
+    pub merchant_id: String,
+    pub idempotency_key: String,
+    /// Hash of the body of the request made with the key
+    pub request_hash: String,
+    /// Status code of the response, `None` while the request is in progress
+    pub response_status_code: Option<i32>,
+    pub response_headers: Option<serde_json::Value>,
+    pub response_body: Option<Vec<u8>>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum IdempotencyKeyUpdate {
+    ResponseUpdate {
+        response_status_code: i32,
+        response_headers: serde_json::Value,
+        response_body: Vec<u8>,
+        expires_at: PrimitiveDateTime,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = idempotency_key)]
+pub struct IdempotencyKeyUpdateInternal {
+    response_status_code: Option<i32>,
+    response_headers: Option<serde_json::Value>,
+    response_body: Option<Vec<u8>>,
+    expires_at: Option<PrimitiveDateTime>,
+}
+
+impl From<IdempotencyKeyUpdate> for IdempotencyKeyUpdateInternal {
+    fn from(idempotency_key_update: IdempotencyKeyUpdate) -> Self {
+        match idempotency_key_update {
+            IdempotencyKeyUpdate::ResponseUpdate {
+                response_status_code,
+                response_headers,
+                response_body,
+                expires_at,
+            } => Self {
+                response_status_code: Some(response_status_code),
+                response_headers: Some(response_headers),
+                response_body: Some(response_body),
+                expires_at: Some(expires_at),
+            },
+        }
+    }
+}
+
+impl IdempotencyKeyUpdate {
+    pub fn apply_changeset(self, source: IdempotencyKey) -> IdempotencyKey {
+        let IdempotencyKeyUpdateInternal {
+            response_status_code,
+            response_headers,
+            response_body,
+            expires_at,
+        } = self.into();
+        IdempotencyKey {
+            response_status_code: response_status_code.or(source.response_status_code),
+            response_headers: response_headers.or(source.response_headers),
+            response_body: response_body.or(source.response_body),
+            expires_at: expires_at.unwrap_or(source.expires_at),
+            ..source
+        }
+    }
+}
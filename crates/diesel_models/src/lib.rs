@@ -1,10 +1,15 @@
 pub mod address;
+pub mod alert;
 pub mod api_keys;
+pub mod audit_event;
+pub mod blocklist;
+pub mod bulk_refund;
 pub mod business_profile;
 pub mod capture;
 pub mod cards_info;
 pub mod configs;
 pub mod connector_response;
+pub mod connector_response_capture;
 pub mod customers;
 pub mod dispute;
 pub mod encryption;
@@ -15,17 +20,21 @@ pub mod events;
 pub mod file;
 #[allow(unused)]
 pub mod fraud_check;
+pub mod idempotency_key;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
 pub mod macros;
 pub mod mandate;
+pub mod mandate_migration;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
+pub mod payment_attempt_step_event;
 pub mod payment_intent;
 pub mod payment_method;
+pub mod payment_routing_decision;
 pub mod payout_attempt;
 pub mod payouts;
 pub mod process_tracker;
@@ -34,18 +43,27 @@ pub mod refund;
 pub mod reverse_lookup;
 #[allow(unused_qualifications)]
 pub mod schema;
+pub mod webhook_delivery;
 
 use diesel_impl::{DieselArray, OptionalDieselArray};
 
 pub type StorageResult<T> = error_stack::Result<T, errors::DatabaseError>;
 pub type PgPooledConn = async_bb8_diesel::Connection<diesel::PgConnection>;
 pub use self::{
-    address::*, api_keys::*, cards_info::*, configs::*, connector_response::*, customers::*,
-    dispute::*, ephemeral_key::*, events::*, file::*, locker_mock_up::*, mandate::*,
-    merchant_account::*, merchant_connector_account::*, payment_attempt::*, payment_intent::*,
-    payment_method::*, process_tracker::*, refund::*, reverse_lookup::*,
+    address::*, alert::*, api_keys::*, audit_event::*, blocklist::*, bulk_refund::*, cards_info::*,
+    configs::*, connector_response::*, connector_response_capture::*, customers::*, dispute::*,
+    ephemeral_key::*, events::*, file::*, idempotency_key::*, locker_mock_up::*, mandate::*,
+    mandate_migration::*, merchant_account::*, merchant_connector_account::*, payment_attempt::*,
+    payment_attempt_step_event::*, payment_intent::*, payment_method::*,
+    payment_routing_decision::*, process_tracker::*, refund::*, reverse_lookup::*,
+    webhook_delivery::*,
 };
 
+/// The API keys and the payments stored before their livemode was recorded are live
+pub(crate) fn default_livemode() -> bool {
+    true
+}
+
 /// The types and implementations provided by this module are required for the schema generated by
 /// `diesel_cli` 2.0 to work with the types defined in Rust code. This is because
 /// [`diesel`][diesel] 2.0 [changed the nullability of array elements][diesel-2.0-array-nullability],
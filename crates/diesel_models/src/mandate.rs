@@ -30,6 +30,13 @@ pub struct Mandate {
     pub end_date: Option<PrimitiveDateTime>,
     pub metadata: Option<pii::SecretSerdeValue>,
     pub connector_mandate_ids: Option<pii::SecretSerdeValue>,
+    pub consecutive_failure_count: i32,
+    pub frequency: Option<storage_enums::MandateFrequency>,
+    pub max_charges_per_period: Option<i32>,
+    pub charge_count: i32,
+    pub period_charge_count: i32,
+    pub period_started_at: Option<PrimitiveDateTime>,
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(
@@ -58,6 +65,9 @@ pub struct MandateNew {
     pub end_date: Option<PrimitiveDateTime>,
     pub metadata: Option<pii::SecretSerdeValue>,
     pub connector_mandate_ids: Option<pii::SecretSerdeValue>,
+    pub frequency: Option<storage_enums::MandateFrequency>,
+    pub max_charges_per_period: Option<i32>,
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(Debug)]
@@ -68,9 +78,25 @@ pub enum MandateUpdate {
     CaptureAmountUpdate {
         amount_captured: Option<i64>,
     },
+    CaptureAmountAndStatusUpdate {
+        amount_captured: Option<i64>,
+        mandate_status: storage_enums::MandateStatus,
+    },
     ConnectorReferenceUpdate {
         connector_mandate_ids: Option<pii::SecretSerdeValue>,
     },
+    FailureCountUpdate {
+        consecutive_failure_count: i32,
+        mandate_status: Option<storage_enums::MandateStatus>,
+    },
+    /// Moves the mandate to another merchant connector account, with its reference at that
+    /// connector. The fields which are `None` are cleared.
+    ConnectorMigrationUpdate {
+        connector: String,
+        merchant_connector_id: Option<String>,
+        connector_mandate_ids: Option<pii::SecretSerdeValue>,
+        network_transaction_id: Option<String>,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -84,7 +110,11 @@ pub struct SingleUseMandate {
 pub struct MandateUpdateInternal {
     mandate_status: Option<storage_enums::MandateStatus>,
     amount_captured: Option<i64>,
-    connector_mandate_ids: Option<pii::SecretSerdeValue>,
+    connector_mandate_ids: Option<Option<pii::SecretSerdeValue>>,
+    consecutive_failure_count: Option<i32>,
+    connector: Option<String>,
+    merchant_connector_id: Option<Option<String>>,
+    network_transaction_id: Option<Option<String>>,
 }
 
 impl From<MandateUpdate> for MandateUpdateInternal {
@@ -92,18 +122,46 @@ impl From<MandateUpdate> for MandateUpdateInternal {
         match mandate_update {
             MandateUpdate::StatusUpdate { mandate_status } => Self {
                 mandate_status: Some(mandate_status),
-                connector_mandate_ids: None,
-                amount_captured: None,
+                ..Default::default()
             },
             MandateUpdate::CaptureAmountUpdate { amount_captured } => Self {
-                mandate_status: None,
                 amount_captured,
-                connector_mandate_ids: None,
+                consecutive_failure_count: Some(0),
+                ..Default::default()
+            },
+            MandateUpdate::CaptureAmountAndStatusUpdate {
+                amount_captured,
+                mandate_status,
+            } => Self {
+                mandate_status: Some(mandate_status),
+                amount_captured,
+                consecutive_failure_count: Some(0),
+                ..Default::default()
             },
             MandateUpdate::ConnectorReferenceUpdate {
                 connector_mandate_ids: connector_mandate_id,
             } => Self {
-                connector_mandate_ids: connector_mandate_id,
+                connector_mandate_ids: connector_mandate_id.map(Some),
+                ..Default::default()
+            },
+            MandateUpdate::FailureCountUpdate {
+                consecutive_failure_count,
+                mandate_status,
+            } => Self {
+                mandate_status,
+                consecutive_failure_count: Some(consecutive_failure_count),
+                ..Default::default()
+            },
+            MandateUpdate::ConnectorMigrationUpdate {
+                connector,
+                merchant_connector_id,
+                connector_mandate_ids,
+                network_transaction_id,
+            } => Self {
+                connector: Some(connector),
+                merchant_connector_id: Some(merchant_connector_id),
+                connector_mandate_ids: Some(connector_mandate_ids),
+                network_transaction_id: Some(network_transaction_id),
                 ..Default::default()
             },
         }
@@ -0,0 +1,93 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::mandate_migration};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = mandate_migration, primary_key(migration_id))]
+pub struct MandateMigration {
+    pub migration_id: String,
+    pub merchant_id: String,
+    /// The merchant connector account the mandates are migrated from
+    pub source_merchant_connector_id: String,
+    /// The merchant connector account the mandates are migrated to
+    pub target_merchant_connector_id: String,
+    /// Verify the new reference of every mandate with a charge of 0
+    pub verify: bool,
+    pub status: storage_enums::MandateMigrationStatus,
+    /// The outcome of the migration of every mandate, as a list of [`MandateMigrationItem`]
+    pub items: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = mandate_migration)]
+pub struct MandateMigrationNew {
+    pub migration_id: String,
+    pub merchant_id: String,
+    pub source_merchant_connector_id: String,
+    pub target_merchant_connector_id: String,
+    pub verify: bool,
+    pub status: storage_enums::MandateMigrationStatus,
+    pub items: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+}
+
+/// A row of the mapping produced by the token migration program of the connectors, from the
+/// reference of the mandate at the old connector to its reference at the new connector
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct MandateMigrationItem {
+    /// The connector mandate id at the old connector
+    pub connector_mandate_id: String,
+    /// The connector mandate id at the new connector
+    pub new_connector_mandate_id: Option<String>,
+    /// The network transaction id with which the mandate is charged at the new connector
+    pub network_transaction_id: Option<String>,
+    /// The mandate found for the connector mandate id at the old connector
+    pub mandate_id: Option<String>,
+    pub status: storage_enums::MandateMigrationItemStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum MandateMigrationUpdate {
+    ItemsUpdate {
+        status: storage_enums::MandateMigrationStatus,
+        items: serde_json::Value,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = mandate_migration)]
+pub struct MandateMigrationUpdateInternal {
+    status: storage_enums::MandateMigrationStatus,
+    items: serde_json::Value,
+    modified_at: PrimitiveDateTime,
+}
+
+impl From<MandateMigrationUpdate> for MandateMigrationUpdateInternal {
+    fn from(mandate_migration_update: MandateMigrationUpdate) -> Self {
+        match mandate_migration_update {
+            MandateMigrationUpdate::ItemsUpdate { status, items } => Self {
+                status,
+                items,
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
+
+impl MandateMigrationUpdateInternal {
+    pub fn apply_changeset(self, source: MandateMigration) -> MandateMigration {
+        MandateMigration {
+            status: self.status,
+            items: self.items,
+            modified_at: self.modified_at,
+            ..source
+        }
+    }
+}
@@ -40,6 +40,10 @@ pub struct MerchantAccount {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
+    pub webhook_auth_details: Option<Encryption>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -69,6 +73,10 @@ pub struct MerchantAccountNew {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
+    pub webhook_auth_details: Option<Encryption>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -97,4 +105,8 @@ pub struct MerchantAccountUpdateInternal {
     pub is_recon_enabled: bool,
     pub default_profile: Option<Option<String>>,
     pub recon_status: storage_enums::ReconStatus,
+    pub webhook_auth_details: Option<Encryption>,
+    pub default_currency: Option<storage_enums::Currency>,
+    pub default_country: Option<storage_enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
 }
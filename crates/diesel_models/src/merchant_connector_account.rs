@@ -41,6 +41,11 @@ pub struct MerchantConnectorAccount {
     pub profile_id: Option<String>,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub disabled_flows: Option<Vec<String>>,
+    pub previous_connector_account_details: Option<Encryption>,
+    pub previous_connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub previous_credentials_expire_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -68,6 +73,11 @@ pub struct MerchantConnectorAccountNew {
     pub profile_id: Option<String>,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub disabled_flows: Option<Vec<String>>,
+    pub previous_connector_account_details: Option<Encryption>,
+    pub previous_connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub previous_credentials_expire_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
@@ -89,6 +99,11 @@ pub struct MerchantConnectorAccountUpdateInternal {
     pub frm_config: Option<Vec<Secret<serde_json::Value>>>,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub disabled_flows: Option<Vec<String>>,
+    pub previous_connector_account_details: Option<Option<Encryption>>,
+    pub previous_connector_webhook_details: Option<Option<pii::SecretSerdeValue>>,
+    pub previous_credentials_expire_at: Option<Option<time::PrimitiveDateTime>>,
 }
 
 impl MerchantConnectorAccountUpdateInternal {
@@ -109,7 +124,20 @@ impl MerchantConnectorAccountUpdateInternal {
                 .unwrap_or(source.merchant_connector_id),
             payment_methods_enabled: self.payment_methods_enabled,
             frm_config: self.frm_config,
+            connector_webhook_details: self
+                .connector_webhook_details
+                .or(source.connector_webhook_details),
             modified_at: self.modified_at.unwrap_or(source.modified_at),
+            disabled_flows: self.disabled_flows.or(source.disabled_flows),
+            previous_connector_account_details: self
+                .previous_connector_account_details
+                .unwrap_or(source.previous_connector_account_details),
+            previous_connector_webhook_details: self
+                .previous_connector_webhook_details
+                .unwrap_or(source.previous_connector_webhook_details),
+            previous_credentials_expire_at: self
+                .previous_credentials_expire_at
+                .unwrap_or(source.previous_credentials_expire_at),
 
             ..source
         }
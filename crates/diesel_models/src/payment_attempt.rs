@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
 use crate::{
+    encryption::Encryption,
     enums::{self as storage_enums},
     schema::payment_attempt,
 };
@@ -58,6 +59,26 @@ pub struct PaymentAttempt {
     pub connector_response_reference_id: Option<String>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub external_authentication_data: Option<Encryption>,
+    pub card_verification_results: Option<serde_json::Value>,
+    pub three_ds_decision_data: Option<serde_json::Value>,
+    pub locale: Option<String>,
+    pub connector_fee_amount: Option<i64>,
+    pub fee_currency: Option<storage_enums::Currency>,
+    pub net_amount: Option<i64>,
+    pub merchant_descriptor: Option<String>,
+    pub sub_merchant_data: Option<serde_json::Value>,
+    pub auth_code: Option<String>,
+    pub acquirer_reference_number: Option<String>,
+    pub authorized_at: Option<PrimitiveDateTime>,
+    pub authorization_expires_at: Option<PrimitiveDateTime>,
+    pub cancellation_reason_code: Option<storage_enums::CancellationReason>,
+    #[serde(default = "crate::default_livemode")]
+    pub livemode: bool,
+    pub routing_metadata: Option<serde_json::Value>,
+    pub merchant_connector_id: Option<String>,
+    pub authorized_amount: Option<i64>,
+    pub redacted_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Queryable, Serialize, Deserialize)]
@@ -117,6 +138,26 @@ pub struct PaymentAttemptNew {
     pub multiple_capture_count: Option<i16>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub external_authentication_data: Option<Encryption>,
+    pub card_verification_results: Option<serde_json::Value>,
+    pub three_ds_decision_data: Option<serde_json::Value>,
+    pub locale: Option<String>,
+    pub connector_fee_amount: Option<i64>,
+    pub fee_currency: Option<storage_enums::Currency>,
+    pub net_amount: Option<i64>,
+    pub merchant_descriptor: Option<String>,
+    pub sub_merchant_data: Option<serde_json::Value>,
+    pub auth_code: Option<String>,
+    pub acquirer_reference_number: Option<String>,
+    pub authorized_at: Option<PrimitiveDateTime>,
+    pub authorization_expires_at: Option<PrimitiveDateTime>,
+    pub cancellation_reason_code: Option<storage_enums::CancellationReason>,
+    #[serde(default = "crate::default_livemode")]
+    pub livemode: bool,
+    pub routing_metadata: Option<serde_json::Value>,
+    pub merchant_connector_id: Option<String>,
+    pub authorized_amount: Option<i64>,
+    pub redacted_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,12 +175,16 @@ pub enum PaymentAttemptUpdate {
         business_sub_label: Option<String>,
         amount_to_capture: Option<i64>,
         capture_method: Option<storage_enums::CaptureMethod>,
+        external_authentication_data: Option<Encryption>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
         connector: Option<String>,
         straight_through_algorithm: Option<serde_json::Value>,
         amount_capturable: Option<i64>,
+        locale: Option<String>,
+        routing_metadata: Option<serde_json::Value>,
+        merchant_connector_id: Option<String>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -161,10 +206,18 @@ pub enum PaymentAttemptUpdate {
         error_code: Option<Option<String>>,
         error_message: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        external_authentication_data: Option<Encryption>,
+        three_ds_decision_data: Option<serde_json::Value>,
+        routing_metadata: Option<serde_json::Value>,
+        locale: Option<String>,
+        merchant_descriptor: Option<String>,
+        sub_merchant_data: Option<serde_json::Value>,
+        merchant_connector_id: Option<String>,
     },
     VoidUpdate {
         status: storage_enums::AttemptStatus,
         cancellation_reason: Option<String>,
+        cancellation_reason_code: Option<storage_enums::CancellationReason>,
     },
     RejectUpdate {
         status: storage_enums::AttemptStatus,
@@ -185,6 +238,13 @@ pub enum PaymentAttemptUpdate {
         error_reason: Option<Option<String>>,
         connector_response_reference_id: Option<String>,
         amount_capturable: Option<i64>,
+        card_verification_results: Option<serde_json::Value>,
+        connector_fee_amount: Option<i64>,
+        fee_currency: Option<storage_enums::Currency>,
+        net_amount: Option<i64>,
+        auth_code: Option<String>,
+        acquirer_reference_number: Option<String>,
+        authorized_amount: Option<i64>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -225,6 +285,18 @@ pub enum PaymentAttemptUpdate {
     SurchargeMetadataUpdate {
         surcharge_metadata: Option<serde_json::Value>,
     },
+    ConnectorFeeUpdate {
+        connector_fee_amount: i64,
+        fee_currency: storage_enums::Currency,
+        net_amount: Option<i64>,
+    },
+    AcquirerReferenceNumberUpdate {
+        acquirer_reference_number: String,
+    },
+    AuthorizationExpiryUpdate {
+        authorized_at: PrimitiveDateTime,
+        authorization_expires_at: PrimitiveDateTime,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -259,6 +331,23 @@ pub struct PaymentAttemptUpdateInternal {
     multiple_capture_count: Option<i16>,
     amount_capturable: Option<i64>,
     surcharge_metadata: Option<serde_json::Value>,
+    external_authentication_data: Option<Encryption>,
+    three_ds_decision_data: Option<serde_json::Value>,
+    routing_metadata: Option<serde_json::Value>,
+    locale: Option<String>,
+    card_verification_results: Option<serde_json::Value>,
+    connector_fee_amount: Option<i64>,
+    fee_currency: Option<storage_enums::Currency>,
+    net_amount: Option<i64>,
+    merchant_descriptor: Option<String>,
+    sub_merchant_data: Option<serde_json::Value>,
+    auth_code: Option<String>,
+    acquirer_reference_number: Option<String>,
+    authorized_at: Option<PrimitiveDateTime>,
+    authorization_expires_at: Option<PrimitiveDateTime>,
+    cancellation_reason_code: Option<storage_enums::CancellationReason>,
+    merchant_connector_id: Option<String>,
+    authorized_amount: Option<i64>,
 }
 
 impl PaymentAttemptUpdate {
@@ -286,6 +375,40 @@ impl PaymentAttemptUpdate {
                 .preprocessing_step_id
                 .or(source.preprocessing_step_id),
             surcharge_metadata: pa_update.surcharge_metadata.or(source.surcharge_metadata),
+            external_authentication_data: pa_update
+                .external_authentication_data
+                .or(source.external_authentication_data),
+            card_verification_results: pa_update
+                .card_verification_results
+                .or(source.card_verification_results),
+            three_ds_decision_data: pa_update
+                .three_ds_decision_data
+                .or(source.three_ds_decision_data),
+            routing_metadata: pa_update.routing_metadata.or(source.routing_metadata),
+            locale: pa_update.locale.or(source.locale),
+            connector_fee_amount: pa_update
+                .connector_fee_amount
+                .or(source.connector_fee_amount),
+            fee_currency: pa_update.fee_currency.or(source.fee_currency),
+            net_amount: pa_update.net_amount.or(source.net_amount),
+            merchant_descriptor: pa_update.merchant_descriptor.or(source.merchant_descriptor),
+            sub_merchant_data: pa_update.sub_merchant_data.or(source.sub_merchant_data),
+            auth_code: pa_update.auth_code.or(source.auth_code),
+            acquirer_reference_number: pa_update
+                .acquirer_reference_number
+                .or(source.acquirer_reference_number),
+            authorized_at: pa_update.authorized_at.or(source.authorized_at),
+            authorization_expires_at: pa_update
+                .authorization_expires_at
+                .or(source.authorization_expires_at),
+            cancellation_reason: pa_update.cancellation_reason.or(source.cancellation_reason),
+            cancellation_reason_code: pa_update
+                .cancellation_reason_code
+                .or(source.cancellation_reason_code),
+            merchant_connector_id: pa_update
+                .merchant_connector_id
+                .or(source.merchant_connector_id),
+            authorized_amount: pa_update.authorized_amount.or(source.authorized_amount),
             ..source
         }
     }
@@ -308,6 +431,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -323,6 +447,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data,
                 ..Default::default()
             },
             PaymentAttemptUpdate::AuthenticationTypeUpdate {
@@ -349,6 +474,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data,
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -367,14 +499,23 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data,
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
                 ..Default::default()
             },
             PaymentAttemptUpdate::VoidUpdate {
                 status,
                 cancellation_reason,
+                cancellation_reason_code,
             } => Self {
                 status: Some(status),
                 cancellation_reason,
+                cancellation_reason_code,
                 ..Default::default()
             },
             PaymentAttemptUpdate::RejectUpdate {
@@ -401,6 +542,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
             } => Self {
                 status: Some(status),
                 connector,
@@ -416,6 +564,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
                 ..Default::default()
             },
             PaymentAttemptUpdate::ErrorUpdate {
@@ -444,11 +599,17 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
             } => Self {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
                 ..Default::default()
             },
             PaymentAttemptUpdate::UnresolvedResponseUpdate {
@@ -507,6 +668,33 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 surcharge_metadata,
                 ..Default::default()
             },
+            PaymentAttemptUpdate::ConnectorFeeUpdate {
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+            } => Self {
+                connector_fee_amount: Some(connector_fee_amount),
+                fee_currency: Some(fee_currency),
+                net_amount,
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            PaymentAttemptUpdate::AcquirerReferenceNumberUpdate {
+                acquirer_reference_number,
+            } => Self {
+                acquirer_reference_number: Some(acquirer_reference_number),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            PaymentAttemptUpdate::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            } => Self {
+                authorized_at: Some(authorized_at),
+                authorization_expires_at: Some(authorization_expires_at),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
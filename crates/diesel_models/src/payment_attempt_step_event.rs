@@ -0,0 +1,30 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::payment_attempt_step_event};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = payment_attempt_step_event)]
+pub struct PaymentAttemptStepEvent {
+    /// Orders the events of a payment in the order they were recorded
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub step: storage_enums::PaymentAttemptStep,
+    pub connector: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_attempt_step_event)]
+pub struct PaymentAttemptStepEventNew {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub step: storage_enums::PaymentAttemptStep,
+    pub connector: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
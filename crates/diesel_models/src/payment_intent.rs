@@ -47,6 +47,25 @@ pub struct PaymentIntent {
     // Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub applied_defaults: Option<serde_json::Value>,
+    pub order_category: Option<String>,
+    pub shipping_method: Option<storage_enums::ShippingMethod>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub estimated_delivery_date: Option<PrimitiveDateTime>,
+    pub merchant_order_reference_id: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub split_payment: Option<serde_json::Value>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub suspected_duplicate_of: Option<String>,
+    #[serde(default = "crate::default_livemode")]
+    pub livemode: bool,
+    pub amount_details: Option<serde_json::Value>,
+    pub request_partial_authorization: Option<bool>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub archived_at: Option<PrimitiveDateTime>,
+    pub device_session_id: Option<String>,
 }
 
 #[derive(
@@ -98,6 +117,25 @@ pub struct PaymentIntentNew {
     pub profile_id: Option<String>,
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub applied_defaults: Option<serde_json::Value>,
+    pub order_category: Option<String>,
+    pub shipping_method: Option<storage_enums::ShippingMethod>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub estimated_delivery_date: Option<PrimitiveDateTime>,
+    pub merchant_order_reference_id: Option<String>,
+    pub connector_request_reference_format: Option<String>,
+    pub split_payment: Option<serde_json::Value>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub suspected_duplicate_of: Option<String>,
+    #[serde(default = "crate::default_livemode")]
+    pub livemode: bool,
+    pub amount_details: Option<serde_json::Value>,
+    pub request_partial_authorization: Option<bool>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub archived_at: Option<PrimitiveDateTime>,
+    pub device_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +180,7 @@ pub enum PaymentIntentUpdate {
         order_details: Option<Vec<pii::SecretSerdeValue>>,
         metadata: Option<pii::SecretSerdeValue>,
         payment_confirm_source: Option<storage_enums::PaymentSource>,
+        device_session_id: Option<String>,
     },
     PaymentAttemptAndAttemptCountUpdate {
         active_attempt_id: String,
@@ -159,6 +198,9 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         merchant_decision: Option<String>,
     },
+    DeviceSessionUpdate {
+        device_session_id: String,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -189,6 +231,7 @@ pub struct PaymentIntentUpdateInternal {
     pub profile_id: Option<String>,
     merchant_decision: Option<String>,
     payment_confirm_source: Option<storage_enums::PaymentSource>,
+    pub device_session_id: Option<String>,
 }
 
 impl PaymentIntentUpdate {
@@ -214,6 +257,9 @@ impl PaymentIntentUpdate {
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
             order_details: internal_update.order_details.or(source.order_details),
+            device_session_id: internal_update
+                .device_session_id
+                .or(source.device_session_id),
             ..source
         }
     }
@@ -239,6 +285,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -257,6 +304,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
                 ..Default::default()
             },
             PaymentIntentUpdate::MetadataUpdate { metadata } => Self {
@@ -342,6 +390,11 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 merchant_decision,
                 ..Default::default()
             },
+            PaymentIntentUpdate::DeviceSessionUpdate { device_session_id } => Self {
+                device_session_id: Some(device_session_id),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
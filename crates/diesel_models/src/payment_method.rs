@@ -33,6 +33,8 @@ pub struct PaymentMethod {
     pub payment_method_issuer_code: Option<storage_enums::PaymentMethodIssuerCode>,
     pub metadata: Option<pii::SecretSerdeValue>,
     pub payment_method_data: Option<Encryption>,
+    pub card_fingerprint: Option<String>,
+    pub locker_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Insertable, Queryable, router_derive::DebugAsDisplay)]
@@ -59,6 +61,8 @@ pub struct PaymentMethodNew {
     pub last_modified: PrimitiveDateTime,
     pub metadata: Option<pii::SecretSerdeValue>,
     pub payment_method_data: Option<Encryption>,
+    pub card_fingerprint: Option<String>,
+    pub locker_id: Option<String>,
 }
 
 impl Default for PaymentMethodNew {
@@ -87,6 +91,8 @@ impl Default for PaymentMethodNew {
             last_modified: now,
             metadata: Option::default(),
             payment_method_data: Option::default(),
+            card_fingerprint: Option::default(),
+            locker_id: Option::default(),
         }
     }
 }
@@ -99,27 +105,69 @@ pub struct TokenizeCoreWorkflow {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PaymentMethodUpdate {
-    MetadataUpdate { metadata: Option<serde_json::Value> },
+    MetadataUpdate {
+        metadata: Option<serde_json::Value>,
+    },
+    PaymentMethodDataUpdate {
+        payment_method_data: Option<Encryption>,
+    },
+    CardFingerprintUpdate {
+        card_fingerprint: Option<String>,
+    },
+    LockerCardUpdate {
+        locker_id: Option<String>,
+        payment_method_data: Option<Encryption>,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethodUpdateInternal {
     metadata: Option<serde_json::Value>,
+    payment_method_data: Option<Encryption>,
+    card_fingerprint: Option<String>,
+    locker_id: Option<String>,
 }
 
 impl PaymentMethodUpdateInternal {
     pub fn create_payment_method(self, source: PaymentMethod) -> PaymentMethod {
         let metadata = self.metadata.map(Secret::new);
 
-        PaymentMethod { metadata, ..source }
+        PaymentMethod {
+            metadata: metadata.or(source.metadata),
+            payment_method_data: self.payment_method_data.or(source.payment_method_data),
+            card_fingerprint: self.card_fingerprint.or(source.card_fingerprint),
+            locker_id: self.locker_id.or(source.locker_id),
+            ..source
+        }
     }
 }
 
 impl From<PaymentMethodUpdate> for PaymentMethodUpdateInternal {
     fn from(payment_method_update: PaymentMethodUpdate) -> Self {
         match payment_method_update {
-            PaymentMethodUpdate::MetadataUpdate { metadata } => Self { metadata },
+            PaymentMethodUpdate::MetadataUpdate { metadata } => Self {
+                metadata,
+                ..Default::default()
+            },
+            PaymentMethodUpdate::PaymentMethodDataUpdate {
+                payment_method_data,
+            } => Self {
+                payment_method_data,
+                ..Default::default()
+            },
+            PaymentMethodUpdate::CardFingerprintUpdate { card_fingerprint } => Self {
+                card_fingerprint,
+                ..Default::default()
+            },
+            PaymentMethodUpdate::LockerCardUpdate {
+                locker_id,
+                payment_method_data,
+            } => Self {
+                locker_id,
+                payment_method_data,
+                ..Default::default()
+            },
         }
     }
 }
@@ -0,0 +1,38 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::payment_routing_decision};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = payment_routing_decision)]
+pub struct PaymentRoutingDecision {
+    /// Orders the decisions of a payment in the order they were taken
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub decision: storage_enums::RoutingDecisionType,
+    pub connector: String,
+    pub routing_rule: Option<storage_enums::RoutingRule>,
+    pub fallback_reason: Option<storage_enums::RoutingFallbackReason>,
+    pub outcome: Option<storage_enums::ConnectorAttemptOutcome>,
+    pub latency_ms: Option<i64>,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_routing_decision)]
+pub struct PaymentRoutingDecisionNew {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub decision: storage_enums::RoutingDecisionType,
+    pub connector: String,
+    pub routing_rule: Option<storage_enums::RoutingRule>,
+    pub fallback_reason: Option<storage_enums::RoutingFallbackReason>,
+    pub outcome: Option<storage_enums::ConnectorAttemptOutcome>,
+    pub latency_ms: Option<i64>,
+    pub created_at: PrimitiveDateTime,
+}
@@ -1,26 +1,36 @@
 pub mod address;
+pub mod alert;
 pub mod api_keys;
+pub mod audit_event;
+pub mod blocklist;
+pub mod bulk_refund;
 pub mod business_profile;
 mod capture;
 pub mod cards_info;
 pub mod configs;
 pub mod connector_response;
+pub mod connector_response_capture;
 pub mod customers;
 pub mod dispute;
 pub mod events;
 pub mod file;
 pub mod fraud_check;
 pub mod generics;
+pub mod idempotency_key;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_migration;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
+pub mod payment_attempt_step_event;
 pub mod payment_intent;
 pub mod payment_method;
+pub mod payment_routing_decision;
 pub mod payout_attempt;
 pub mod payouts;
 pub mod process_tracker;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod webhook_delivery;
@@ -0,0 +1,50 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    alert::{Alert, AlertNew, AlertUpdate, AlertUpdateInternal},
+    schema::alert::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl AlertNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Alert> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Alert {
+    #[instrument(skip(conn))]
+    pub async fn find_optional_by_merchant_id_connector_connector_alert_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        connector: &str,
+        connector_alert_id: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_find_one_optional::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::connector.eq(connector.to_owned()))
+                .and(dsl::connector_alert_id.eq(connector_alert_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update(self, conn: &PgPooledConn, alert: AlertUpdate) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::alert_id.eq(self.alert_id.to_owned()),
+            AlertUpdateInternal::from(alert),
+        )
+        .await
+    }
+}
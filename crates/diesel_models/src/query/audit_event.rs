@@ -0,0 +1,112 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, debug_query, pg::Pg, ExpressionMethods, QueryDsl};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
+
+use super::generics;
+use crate::{
+    audit_event::{AuditEvent, AuditEventNew},
+    enums::AuditResourceType,
+    errors,
+    query::generics::db_metrics,
+    schema::audit_event::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl AuditEventNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<AuditEvent> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl AuditEvent {
+    /// Lists the audit events of the merchant, the latest first
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        resource_type: Option<AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        let mut query = <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .order(dsl::created_at.desc())
+            .into_boxed();
+
+        if let Some(resource_type) = resource_type {
+            query = query.filter(dsl::resource_type.eq(resource_type));
+        }
+        if let Some(resource_id) = resource_id {
+            query = query.filter(dsl::resource_id.eq(resource_id.to_owned()));
+        }
+        if let Some(created_after) = created_after {
+            query = query.filter(dsl::created_at.ge(created_after));
+        }
+        if let Some(created_before) = created_before {
+            query = query.filter(dsl::created_at.lt(created_before));
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async::<Self>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error filtering audit events")
+    }
+
+    /// Counts the audit events of the merchant matching the same filters as
+    /// [`Self::list_by_merchant_id`]
+    #[instrument(skip(conn))]
+    pub async fn count_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        resource_type: Option<AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+    ) -> StorageResult<i64> {
+        let mut query = <Self as HasTable>::table()
+            .count()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .into_boxed();
+
+        if let Some(resource_type) = resource_type {
+            query = query.filter(dsl::resource_type.eq(resource_type));
+        }
+        if let Some(resource_id) = resource_id {
+            query = query.filter(dsl::resource_id.eq(resource_id.to_owned()));
+        }
+        if let Some(created_after) = created_after {
+            query = query.filter(dsl::created_at.ge(created_after));
+        }
+        if let Some(created_before) = created_before {
+            query = query.filter(dsl::created_at.lt(created_before));
+        }
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_result_async::<i64>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error counting audit events")
+    }
+}
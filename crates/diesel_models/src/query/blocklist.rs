@@ -0,0 +1,132 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    blocklist::{Blocklist, BlocklistNew, BlocklistUpdate, BlocklistUpdateInternal},
+    enums::BlocklistDataKind,
+    errors,
+    schema::blocklist::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl BlocklistNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Blocklist> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Blocklist {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_blocklist_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::blocklist_id.eq(blocklist_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_optional_by_merchant_id_data_kind_fingerprint(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        data_kind: BlocklistDataKind,
+        fingerprint: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_find_one_optional::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::data_kind.eq(data_kind))
+                .and(dsl::fingerprint.eq(fingerprint.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        data_kind: Option<BlocklistDataKind>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        match data_kind {
+            Some(data_kind) => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    dsl::merchant_id
+                        .eq(merchant_id.to_owned())
+                        .and(dsl::data_kind.eq(data_kind)),
+                    limit,
+                    offset,
+                    Some(dsl::created_at.asc()),
+                )
+                .await
+            }
+            None => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    dsl::merchant_id.eq(merchant_id.to_owned()),
+                    limit,
+                    offset,
+                    Some(dsl::created_at.asc()),
+                )
+                .await
+            }
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_blocklist_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        blocklist_id: &str,
+        blocklist_update: BlocklistUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::blocklist_id.eq(blocklist_id.to_owned())),
+            BlocklistUpdateInternal::from(blocklist_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Blocklist entry with the given blocklist ID does not exist",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_merchant_id_blocklist_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_delete_one_with_result::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::blocklist_id.eq(blocklist_id.to_owned())),
+        )
+        .await
+    }
+}
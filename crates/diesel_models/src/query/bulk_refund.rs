@@ -0,0 +1,33 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    bulk_refund::{BulkRefund, BulkRefundNew},
+    schema::bulk_refund::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl BulkRefundNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<BulkRefund> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl BulkRefund {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_bulk_refund_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        bulk_refund_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::bulk_refund_id.eq(bulk_refund_id.to_owned())),
+        )
+        .await
+    }
+}
@@ -0,0 +1,59 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    connector_response_capture::{ConnectorResponseCapture, ConnectorResponseCaptureNew},
+    schema::connector_response_capture::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl ConnectorResponseCaptureNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<ConnectorResponseCapture> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl ConnectorResponseCapture {
+    /// Finds the latest response captured for the payment flows of the payment
+    #[instrument(skip(conn))]
+    pub async fn find_latest_by_merchant_id_payment_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq(payment_id.to_owned()))
+                .and(dsl::refund_id.is_null()),
+            Some(1),
+            None,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+        .map(|captures| captures.into_iter().next())
+    }
+
+    /// Finds the latest response captured for the refund
+    #[instrument(skip(conn))]
+    pub async fn find_latest_by_merchant_id_refund_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        refund_id: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::refund_id.eq(refund_id.to_owned())),
+            Some(1),
+            None,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+        .map(|captures| captures.into_iter().next())
+    }
+}
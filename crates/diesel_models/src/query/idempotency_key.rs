@@ -0,0 +1,72 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    idempotency_key::{
+        IdempotencyKey, IdempotencyKeyNew, IdempotencyKeyUpdate, IdempotencyKeyUpdateInternal,
+    },
+    schema::idempotency_key::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl IdempotencyKeyNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<IdempotencyKey> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl IdempotencyKey {
+    #[instrument(skip(conn))]
+    pub async fn find_optional_by_merchant_id_idempotency_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_find_one_optional::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::idempotency_key.eq(idempotency_key.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_idempotency_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        idempotency_key: &str,
+        idempotency_key_update: IdempotencyKeyUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::idempotency_key.eq(idempotency_key.to_owned())),
+            IdempotencyKeyUpdateInternal::from(idempotency_key_update),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_merchant_id_idempotency_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::idempotency_key.eq(idempotency_key.to_owned())),
+        )
+        .await
+    }
+}
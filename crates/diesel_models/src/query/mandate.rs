@@ -1,6 +1,10 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
+use diesel::{
+    associations::HasTable, sql_types, BoolExpressionMethods, ExpressionMethods, IntoSql,
+    NullableExpressionMethods, Table,
+};
 use error_stack::report;
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use super::generics;
 use crate::{errors, mandate::*, schema::mandate::dsl, PgPooledConn, StorageResult};
@@ -70,4 +74,127 @@ impl Mandate {
                 .attach_printable("Error while updating mandate")
         })
     }
+
+    /// Counts a charge against the mandate in the period starting at `period_started_at`, before
+    /// the charge is made. Every update checks the limit of the period in its own condition, so
+    /// that concurrent charges can not exceed it. Returns `false` when the mandate was already
+    /// charged the maximum number of times in the period and `enforce_period_limit` is set.
+    #[instrument(skip(conn))]
+    pub async fn reserve_charge_by_merchant_id_mandate_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+        enforce_period_limit: bool,
+    ) -> StorageResult<bool> {
+        let Some(period_started_at) = period_started_at else {
+            let updated_rows = generics::generic_update::<<Self as HasTable>::Table, _, _>(
+                conn,
+                dsl::merchant_id
+                    .eq(merchant_id.to_owned())
+                    .and(dsl::mandate_id.eq(mandate_id.to_owned())),
+                dsl::charge_count.eq(dsl::charge_count + 1),
+            )
+            .await?;
+            return Ok(updated_rows > 0);
+        };
+
+        let charge_in_current_period = || {
+            generics::generic_update::<<Self as HasTable>::Table, _, _>(
+                conn,
+                dsl::merchant_id
+                    .eq(merchant_id.to_owned())
+                    .and(dsl::mandate_id.eq(mandate_id.to_owned()))
+                    .and(
+                        dsl::period_started_at
+                            .assume_not_null()
+                            .eq(period_started_at),
+                    )
+                    .and(
+                        (!enforce_period_limit)
+                            .into_sql::<sql_types::Bool>()
+                            .or(dsl::max_charges_per_period.is_null())
+                            .or(dsl::period_charge_count
+                                .lt(dsl::max_charges_per_period.assume_not_null())),
+                    ),
+                (
+                    dsl::charge_count.eq(dsl::charge_count + 1),
+                    dsl::period_charge_count.eq(dsl::period_charge_count + 1),
+                ),
+            )
+        };
+
+        if charge_in_current_period().await? > 0 {
+            return Ok(true);
+        }
+
+        // The first charge of the period resets the count of the previous period
+        let updated_rows = generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::mandate_id.eq(mandate_id.to_owned()))
+                .and(
+                    dsl::period_started_at.is_null().or(dsl::period_started_at
+                        .assume_not_null()
+                        .lt(period_started_at)),
+                ),
+            (
+                dsl::charge_count.eq(dsl::charge_count + 1),
+                dsl::period_charge_count.eq(1),
+                dsl::period_started_at.eq(period_started_at),
+            ),
+        )
+        .await?;
+        if updated_rows > 0 {
+            return Ok(true);
+        }
+
+        // The period was started by a concurrent charge in the meantime
+        Ok(charge_in_current_period().await? > 0)
+    }
+
+    /// Releases a charge counted by [`Self::reserve_charge_by_merchant_id_mandate_id`] which was
+    /// not made. The count of a period which is over is left as is.
+    #[instrument(skip(conn))]
+    pub async fn release_charge_by_merchant_id_mandate_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+    ) -> StorageResult<()> {
+        if let Some(period_started_at) = period_started_at {
+            let updated_rows = generics::generic_update::<<Self as HasTable>::Table, _, _>(
+                conn,
+                dsl::merchant_id
+                    .eq(merchant_id.to_owned())
+                    .and(dsl::mandate_id.eq(mandate_id.to_owned()))
+                    .and(
+                        dsl::period_started_at
+                            .assume_not_null()
+                            .eq(period_started_at),
+                    )
+                    .and(dsl::period_charge_count.gt(0)),
+                (
+                    dsl::charge_count.eq(dsl::charge_count - 1),
+                    dsl::period_charge_count.eq(dsl::period_charge_count - 1),
+                ),
+            )
+            .await?;
+            if updated_rows > 0 {
+                return Ok(());
+            }
+        }
+
+        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::mandate_id.eq(mandate_id.to_owned()))
+                .and(dsl::charge_count.gt(0)),
+            dsl::charge_count.eq(dsl::charge_count - 1),
+        )
+        .await
+        .map(|_| ())
+    }
 }
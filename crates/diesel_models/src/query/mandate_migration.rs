@@ -0,0 +1,55 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    mandate_migration::{
+        MandateMigration, MandateMigrationNew, MandateMigrationUpdate,
+        MandateMigrationUpdateInternal,
+    },
+    schema::mandate_migration::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl MandateMigrationNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<MandateMigration> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl MandateMigration {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_migration_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        migration_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::migration_id.eq(migration_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update(
+        self,
+        conn: &PgPooledConn,
+        mandate_migration: MandateMigrationUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::migration_id.eq(self.migration_id.to_owned()),
+            MandateMigrationUpdateInternal::from(mandate_migration),
+        )
+        .await
+    }
+}
@@ -85,6 +85,29 @@ impl MerchantConnectorAccount {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn filter_by_profile_id_connector_name(
+        conn: &PgPooledConn,
+        profile_id: &str,
+        connector_name: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::profile_id
+                .eq(profile_id.to_owned())
+                .and(dsl::connector_name.eq(connector_name.to_owned())),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id_connector_name(
         conn: &PgPooledConn,
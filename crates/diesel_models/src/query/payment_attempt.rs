@@ -136,6 +136,21 @@ impl PaymentAttempt {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_acquirer_reference_number(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        acquirer_reference_number: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::acquirer_reference_number.eq(acquirer_reference_number.to_owned())),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id_attempt_id(
         conn: &PgPooledConn,
@@ -190,19 +205,14 @@ impl PaymentAttempt {
         merchant_id: &str,
         payment_id: &str,
     ) -> StorageResult<Vec<Self>> {
-        generics::generic_filter::<
-            <Self as HasTable>::Table,
-            _,
-            <<Self as HasTable>::Table as Table>::PrimaryKey,
-            _,
-        >(
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
             conn,
             dsl::merchant_id
                 .eq(merchant_id.to_owned())
                 .and(dsl::payment_id.eq(payment_id.to_owned())),
             None,
             None,
-            None,
+            Some(dsl::created_at.asc()),
         )
         .await
     }
@@ -310,4 +320,44 @@ impl PaymentAttempt {
         .change_context(errors::DatabaseError::Others)
         .attach_printable("Error filtering count of payments")
     }
+
+    /// Redacts the browser info and the payment method data of up to `limit` of the oldest attempts
+    /// created before `created_before`, which are not redacted yet. Returns the number of redacted
+    /// attempts.
+    #[instrument(skip(conn))]
+    pub async fn redact_by_created_before(
+        conn: &PgPooledConn,
+        created_before: time::PrimitiveDateTime,
+        limit: i64,
+    ) -> StorageResult<usize> {
+        let ids = <Self as HasTable>::table()
+            .select(dsl::id)
+            .filter(
+                dsl::created_at
+                    .lt(created_before)
+                    .and(dsl::redacted_at.is_null()),
+            )
+            .order(dsl::created_at.asc())
+            .limit(limit)
+            .get_results_async::<i32>(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error filtering the attempts to redact")?;
+
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::id.eq_any(ids),
+            (
+                dsl::browser_info.eq(None::<serde_json::Value>),
+                dsl::payment_method_data.eq(None::<serde_json::Value>),
+                dsl::redacted_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
 }
@@ -1,9 +1,12 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use super::generics;
 use crate::{
-    errors,
+    enums, errors,
     payment_intent::{
         PaymentIntent, PaymentIntentNew, PaymentIntentUpdate, PaymentIntentUpdateInternal,
     },
@@ -73,4 +76,68 @@ impl PaymentIntent {
         )
         .await
     }
+
+    /// The earliest payment of the merchant created with the merchant order reference since
+    /// `created_after`, which has not failed or been cancelled
+    #[instrument(skip(conn))]
+    pub async fn find_optional_by_merchant_id_merchant_order_reference_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+        created_after: PrimitiveDateTime,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::merchant_order_reference_id.eq(merchant_order_reference_id.to_owned()))
+                .and(dsl::created_at.ge(created_after))
+                .and(dsl::status.ne_all(vec![
+                    enums::IntentStatus::Failed,
+                    enums::IntentStatus::Cancelled,
+                ])),
+            Some(1),
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+        .map(|mut payment_intents| payment_intents.pop())
+    }
+
+    /// Archives up to `limit` of the oldest payments in one of `statuses` created before
+    /// `created_before`, which are not archived yet. Returns the number of archived payments.
+    #[instrument(skip(conn))]
+    pub async fn archive_by_created_before(
+        conn: &PgPooledConn,
+        created_before: PrimitiveDateTime,
+        statuses: Vec<enums::IntentStatus>,
+        limit: i64,
+    ) -> StorageResult<usize> {
+        let ids = <Self as HasTable>::table()
+            .select(dsl::id)
+            .filter(
+                dsl::created_at
+                    .lt(created_before)
+                    .and(dsl::archived_at.is_null())
+                    .and(dsl::status.eq_any(statuses)),
+            )
+            .order(dsl::created_at.asc())
+            .limit(limit)
+            .get_results_async::<i32>(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error filtering the payments to archive")?;
+
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::id.eq_any(ids),
+            dsl::archived_at.eq(common_utils::date_time::now()),
+        )
+        .await
+    }
 }
@@ -3,7 +3,7 @@ use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    errors,
+    enums as storage_enums, errors,
     payment_method::{self, PaymentMethod, PaymentMethodNew},
     schema::payment_methods::dsl,
     PgPooledConn, StorageResult,
@@ -99,6 +99,52 @@ impl PaymentMethod {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_customer_id_merchant_id_card_fingerprint(
+        conn: &PgPooledConn,
+        customer_id: &str,
+        merchant_id: &str,
+        card_fingerprint: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::customer_id
+                .eq(customer_id.to_owned())
+                .and(dsl::merchant_id.eq(merchant_id.to_owned()))
+                .and(dsl::card_fingerprint.eq(card_fingerprint.to_owned())),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Card payment methods saved before the cards were fingerprinted, with an id greater than
+    /// `after_id`, in the order of their ids
+    #[instrument(skip(conn))]
+    pub async fn find_cards_without_fingerprint(
+        conn: &PgPooledConn,
+        after_id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id
+                .gt(after_id)
+                .and(dsl::payment_method.eq(storage_enums::PaymentMethod::Card))
+                .and(dsl::card_fingerprint.is_null()),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+
     pub async fn update_with_payment_method_id(
         self,
         conn: &PgPooledConn,
@@ -0,0 +1,60 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
+
+use super::generics;
+use crate::{
+    payment_routing_decision::{PaymentRoutingDecision, PaymentRoutingDecisionNew},
+    schema::payment_routing_decision::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl PaymentRoutingDecisionNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentRoutingDecision> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl PaymentRoutingDecision {
+    /// Finds the decisions of the attempts of the payment, in the order they were taken
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq(payment_id.to_owned())),
+            None,
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+
+    /// Finds the decisions of the merchant taken in the time range, including its start and
+    /// excluding its end, in the order they were taken
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_created_at_range(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        created_before: PrimitiveDateTime,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::created_at.ge(created_after))
+                .and(dsl::created_at.lt(created_before)),
+            None,
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+}
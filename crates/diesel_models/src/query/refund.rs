@@ -134,4 +134,27 @@ impl Refund {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_ids(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_ids: &[String],
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq_any(payment_ids.to_owned())),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
 }
@@ -0,0 +1,36 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    schema::webhook_delivery::dsl,
+    webhook_delivery::{WebhookDelivery, WebhookDeliveryNew},
+    PgPooledConn, StorageResult,
+};
+
+impl WebhookDeliveryNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<WebhookDelivery> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl WebhookDelivery {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_event_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::event_id.eq(event_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+}
@@ -37,6 +37,8 @@ pub struct Refund {
     pub refund_reason: Option<String>,
     pub refund_error_code: Option<String>,
     pub profile_id: Option<String>,
+    pub refund_reason_code: Option<storage_enums::RefundReason>,
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(
@@ -77,6 +79,8 @@ pub struct RefundNew {
     pub attempt_id: String,
     pub refund_reason: Option<String>,
     pub profile_id: Option<String>,
+    pub refund_reason_code: Option<storage_enums::RefundReason>,
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -86,7 +90,7 @@ pub enum RefundUpdate {
         refund_status: storage_enums::RefundStatus,
         sent_to_gateway: bool,
         refund_error_message: Option<String>,
-        refund_arn: String,
+        refund_arn: Option<String>,
     },
     MetadataAndReasonUpdate {
         metadata: Option<pii::SecretSerdeValue>,
@@ -124,7 +128,7 @@ impl RefundUpdateInternal {
             refund_status: self.refund_status.unwrap_or_default(),
             sent_to_gateway: self.sent_to_gateway.unwrap_or_default(),
             refund_error_message: self.refund_error_message,
-            refund_arn: self.refund_arn,
+            refund_arn: self.refund_arn.or(source.refund_arn),
             metadata: self.metadata,
             refund_reason: self.refund_reason,
             refund_error_code: self.refund_error_code,
@@ -147,7 +151,7 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 refund_status: Some(refund_status),
                 sent_to_gateway: Some(sent_to_gateway),
                 refund_error_message,
-                refund_arn: Some(refund_arn),
+                refund_arn,
                 ..Default::default()
             },
             RefundUpdate::MetadataAndReasonUpdate { metadata, reason } => Self {
@@ -32,6 +32,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    alert (alert_id) {
+        #[max_length = 64]
+        alert_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        #[max_length = 64]
+        connector -> Varchar,
+        #[max_length = 255]
+        connector_alert_id -> Varchar,
+        #[max_length = 32]
+        alert_type -> Varchar,
+        #[max_length = 64]
+        initiated_by -> Nullable<Varchar>,
+        #[max_length = 255]
+        connector_reason -> Nullable<Varchar>,
+        #[max_length = 32]
+        status -> Varchar,
+        #[max_length = 32]
+        action -> Nullable<Varchar>,
+        #[max_length = 64]
+        refund_id -> Nullable<Varchar>,
+        raw_payload -> Jsonb,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -52,6 +87,71 @@ diesel::table! {
         created_at -> Timestamp,
         expires_at -> Nullable<Timestamp>,
         last_used -> Nullable<Timestamp>,
+        permissions -> Array<Nullable<Text>>,
+        livemode -> Bool,
+        strict_request_validation -> Bool,
+        pii_restricted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    audit_event (audit_event_id) {
+        #[max_length = 64]
+        audit_event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        actor_key_id -> Varchar,
+        #[max_length = 32]
+        resource_type -> Varchar,
+        #[max_length = 64]
+        resource_id -> Varchar,
+        #[max_length = 32]
+        action -> Varchar,
+        diff -> Jsonb,
+        #[max_length = 64]
+        request_id -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    blocklist (blocklist_id) {
+        #[max_length = 64]
+        blocklist_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 32]
+        data_kind -> Varchar,
+        #[max_length = 128]
+        fingerprint -> Varchar,
+        #[max_length = 255]
+        reason -> Nullable<Varchar>,
+        expires_at -> Nullable<Timestamp>,
+        hit_count -> Int8,
+        last_hit_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    bulk_refund (bulk_refund_id) {
+        #[max_length = 64]
+        bulk_refund_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        items -> Jsonb,
+        created_at -> Timestamp,
     }
 }
 
@@ -81,6 +181,25 @@ diesel::table! {
         payout_routing_algorithm -> Nullable<Jsonb>,
         is_recon_enabled -> Bool,
         applepay_verified_domains -> Nullable<Array<Nullable<Text>>>,
+        #[max_length = 255]
+        statement_descriptor_name -> Nullable<Varchar>,
+        #[max_length = 255]
+        statement_descriptor_suffix -> Nullable<Varchar>,
+        session_expiry -> Nullable<Int8>,
+        #[max_length = 35]
+        default_locale -> Nullable<Varchar>,
+        #[max_length = 255]
+        default_payment_description -> Nullable<Varchar>,
+        #[max_length = 255]
+        connector_request_reference_format -> Nullable<Varchar>,
+        #[max_length = 32]
+        duplicate_order_reference_behaviour -> Nullable<Varchar>,
+        duplicate_order_reference_window -> Nullable<Int8>,
+        alert_auto_refund_max_amount -> Nullable<Int8>,
+        default_currency -> Nullable<Currency>,
+        default_country -> Nullable<CountryAlpha2>,
+        pii_policy -> Nullable<Jsonb>,
+        webhook_auth_details -> Nullable<Bytea>,
     }
 }
 
@@ -178,6 +297,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    connector_response_capture (capture_id) {
+        #[max_length = 64]
+        capture_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        #[max_length = 64]
+        refund_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        connector -> Varchar,
+        #[max_length = 64]
+        flow -> Varchar,
+        status_code -> Int4,
+        response_body -> Text,
+        is_truncated -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -324,6 +469,25 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    idempotency_key (merchant_id, idempotency_key) {
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 255]
+        idempotency_key -> Varchar,
+        #[max_length = 64]
+        request_hash -> Varchar,
+        response_status_code -> Nullable<Int4>,
+        response_headers -> Nullable<Jsonb>,
+        response_body -> Nullable<Bytea>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -398,6 +562,37 @@ diesel::table! {
         end_date -> Nullable<Timestamp>,
         metadata -> Nullable<Jsonb>,
         connector_mandate_ids -> Nullable<Jsonb>,
+        consecutive_failure_count -> Int4,
+        #[max_length = 16]
+        frequency -> Nullable<Varchar>,
+        max_charges_per_period -> Nullable<Int4>,
+        charge_count -> Int4,
+        period_charge_count -> Int4,
+        period_started_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        merchant_connector_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    mandate_migration (migration_id) {
+        #[max_length = 64]
+        migration_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        source_merchant_connector_id -> Varchar,
+        #[max_length = 64]
+        target_merchant_connector_id -> Varchar,
+        verify -> Bool,
+        #[max_length = 32]
+        status -> Varchar,
+        items -> Jsonb,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
     }
 }
 
@@ -440,6 +635,10 @@ diesel::table! {
         #[max_length = 64]
         default_profile -> Nullable<Varchar>,
         recon_status -> ReconStatus,
+        webhook_auth_details -> Nullable<Bytea>,
+        default_currency -> Nullable<Currency>,
+        default_country -> Nullable<CountryAlpha2>,
+        pii_policy -> Nullable<Jsonb>,
     }
 }
 
@@ -476,6 +675,10 @@ diesel::table! {
         #[max_length = 64]
         profile_id -> Nullable<Varchar>,
         applepay_verified_domains -> Nullable<Array<Nullable<Text>>>,
+        disabled_flows -> Nullable<Array<Nullable<Text>>>,
+        previous_connector_account_details -> Nullable<Bytea>,
+        previous_connector_webhook_details -> Nullable<Jsonb>,
+        previous_credentials_expire_at -> Nullable<Timestamp>,
     }
 }
 
@@ -552,6 +755,51 @@ diesel::table! {
         connector_response_reference_id -> Nullable<Varchar>,
         amount_capturable -> Int8,
         surcharge_metadata -> Nullable<Jsonb>,
+        external_authentication_data -> Nullable<Bytea>,
+        card_verification_results -> Nullable<Jsonb>,
+        three_ds_decision_data -> Nullable<Jsonb>,
+        #[max_length = 35]
+        locale -> Nullable<Varchar>,
+        connector_fee_amount -> Nullable<Int8>,
+        fee_currency -> Nullable<Currency>,
+        net_amount -> Nullable<Int8>,
+        #[max_length = 255]
+        merchant_descriptor -> Nullable<Varchar>,
+        sub_merchant_data -> Nullable<Jsonb>,
+        #[max_length = 64]
+        auth_code -> Nullable<Varchar>,
+        #[max_length = 128]
+        acquirer_reference_number -> Nullable<Varchar>,
+        authorized_at -> Nullable<Timestamp>,
+        authorization_expires_at -> Nullable<Timestamp>,
+        #[max_length = 32]
+        cancellation_reason_code -> Nullable<Varchar>,
+        livemode -> Bool,
+        routing_metadata -> Nullable<Jsonb>,
+        #[max_length = 64]
+        merchant_connector_id -> Nullable<Varchar>,
+        authorized_amount -> Nullable<Int8>,
+        redacted_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_attempt_step_event (id) {
+        id -> Int4,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        #[max_length = 32]
+        step -> Varchar,
+        #[max_length = 64]
+        connector -> Nullable<Varchar>,
+        created_at -> Timestamp,
     }
 }
 
@@ -608,6 +856,28 @@ diesel::table! {
         #[max_length = 64]
         merchant_decision -> Nullable<Varchar>,
         payment_confirm_source -> Nullable<PaymentSource>,
+        session_expiry -> Nullable<Timestamp>,
+        applied_defaults -> Nullable<Json>,
+        #[max_length = 255]
+        order_category -> Nullable<Varchar>,
+        #[max_length = 32]
+        shipping_method -> Nullable<Varchar>,
+        estimated_delivery_date -> Nullable<Timestamp>,
+        #[max_length = 255]
+        merchant_order_reference_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        connector_request_reference_format -> Nullable<Varchar>,
+        split_payment -> Nullable<Json>,
+        #[max_length = 32]
+        payment_channel -> Nullable<Varchar>,
+        #[max_length = 64]
+        suspected_duplicate_of -> Nullable<Varchar>,
+        livemode -> Bool,
+        amount_details -> Nullable<Json>,
+        request_partial_authorization -> Nullable<Bool>,
+        archived_at -> Nullable<Timestamp>,
+        #[max_length = 128]
+        device_session_id -> Nullable<Varchar>,
     }
 }
 
@@ -650,6 +920,37 @@ diesel::table! {
         payment_method_issuer_code -> Nullable<PaymentMethodIssuerCode>,
         metadata -> Nullable<Json>,
         payment_method_data -> Nullable<Bytea>,
+        #[max_length = 128]
+        card_fingerprint -> Nullable<Varchar>,
+        #[max_length = 64]
+        locker_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_routing_decision (id) {
+        id -> Int4,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        #[max_length = 32]
+        decision -> Varchar,
+        #[max_length = 64]
+        connector -> Varchar,
+        #[max_length = 32]
+        routing_rule -> Nullable<Varchar>,
+        #[max_length = 32]
+        fallback_reason -> Nullable<Varchar>,
+        #[max_length = 32]
+        outcome -> Nullable<Varchar>,
+        latency_ms -> Nullable<Int8>,
+        created_at -> Timestamp,
     }
 }
 
@@ -791,6 +1092,10 @@ diesel::table! {
         refund_error_code -> Nullable<Text>,
         #[max_length = 64]
         profile_id -> Nullable<Varchar>,
+        #[max_length = 32]
+        refund_reason_code -> Nullable<Varchar>,
+        #[max_length = 64]
+        merchant_connector_id -> Nullable<Varchar>,
     }
 }
 
@@ -810,30 +1115,62 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    webhook_delivery (delivery_id) {
+        #[max_length = 64]
+        delivery_id -> Varchar,
+        #[max_length = 64]
+        event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        subscription_id -> Nullable<Varchar>,
+        #[max_length = 32]
+        status -> Varchar,
+        response_status_code -> Nullable<Int4>,
+        created_at -> Timestamp,
+        #[max_length = 16]
+        payload_version -> Nullable<Varchar>,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     address,
+    alert,
     api_keys,
+    audit_event,
+    blocklist,
+    bulk_refund,
     business_profile,
     captures,
     cards_info,
     configs,
     connector_response,
+    connector_response_capture,
     customers,
     dispute,
     events,
     file_metadata,
     fraud_check,
+    idempotency_key,
     locker_mock_up,
     mandate,
+    mandate_migration,
     merchant_account,
     merchant_connector_account,
     merchant_key_store,
     payment_attempt,
+    payment_attempt_step_event,
     payment_intent,
     payment_methods,
+    payment_routing_decision,
     payout_attempt,
     payouts,
     process_tracker,
     refund,
     reverse_lookup,
+    webhook_delivery,
 );
@@ -0,0 +1,35 @@
+use common_utils::custom_serde;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::webhook_delivery};
+
+#[derive(Clone, Debug, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_delivery)]
+pub struct WebhookDeliveryNew {
+    pub delivery_id: String,
+    pub event_id: String,
+    pub merchant_id: String,
+    /// The subscription the event was delivered to, `None` when the event matched no subscription
+    pub subscription_id: Option<String>,
+    pub status: storage_enums::WebhookDeliveryStatus,
+    pub response_status_code: Option<i32>,
+    /// The version of the payload delivered, for the redeliveries of the event to use the same
+    /// version, `None` when the event was not delivered
+    pub payload_version: Option<storage_enums::WebhookPayloadVersion>,
+}
+
+#[derive(Clone, Debug, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = webhook_delivery, primary_key(delivery_id))]
+pub struct WebhookDelivery {
+    pub delivery_id: String,
+    pub event_id: String,
+    pub merchant_id: String,
+    pub subscription_id: Option<String>,
+    pub status: storage_enums::WebhookDeliveryStatus,
+    pub response_status_code: Option<i32>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    pub payload_version: Option<storage_enums::WebhookPayloadVersion>,
+}
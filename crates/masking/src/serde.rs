@@ -2,10 +2,50 @@
 //! Serde-related.
 //!
 
+use std::cell::Cell;
+
 pub use serde::{de, ser, Deserialize, Serialize};
 
 use crate::{PeekInterface, Secret, Strategy, StrongSecret, ZeroizableSecret};
 
+/// Value which the secrets are serialized as by [`masked_serialize`]
+pub const MASKED_VALUE: &str = "***";
+
+std::thread_local! {
+    static IS_MASKED_SERIALIZATION: Cell<bool> = Cell::new(false);
+}
+
+/// Restores the serialization mode of the thread once the masked serialization is over, even if
+/// it panicked
+struct MaskedSerializationGuard {
+    was_masked: bool,
+}
+
+impl Drop for MaskedSerializationGuard {
+    fn drop(&mut self) {
+        IS_MASKED_SERIALIZATION.with(|is_masked| is_masked.set(self.was_masked));
+    }
+}
+
+///
+/// Serializes the value into JSON with each of its [`Secret`] and [`StrongSecret`] serialized as
+/// [`MASKED_VALUE`], so that the value can be recorded without exposing its secrets. The fields
+/// which are not wrapped in a secret are serialized as they are.
+///
+pub fn masked_serialize<T>(value: &T) -> Result<serde_json::Value, serde_json::Error>
+where
+    T: Serialize + ?Sized,
+{
+    let _guard = MaskedSerializationGuard {
+        was_masked: IS_MASKED_SERIALIZATION.with(|is_masked| is_masked.replace(true)),
+    };
+    serde_json::to_value(value)
+}
+
+fn is_masked_serialization() -> bool {
+    IS_MASKED_SERIALIZATION.with(Cell::get)
+}
+
 /// Marker trait for secret types which can be [`Serialize`]-d by [`serde`].
 ///
 /// When the `serde` feature of this crate is enabled and types are marked with
@@ -48,7 +88,11 @@ where
     where
         S: ser::Serializer,
     {
-        self.peek().serialize(serializer)
+        if is_masked_serialization() {
+            MASKED_VALUE.serialize(serializer)
+        } else {
+            self.peek().serialize(serializer)
+        }
     }
 }
 
@@ -74,6 +118,10 @@ where
     where
         S: serde::Serializer,
     {
-        self.peek().serialize(serializer)
+        if is_masked_serialization() {
+            MASKED_VALUE.serialize(serializer)
+        } else {
+            self.peek().serialize(serializer)
+        }
     }
 }
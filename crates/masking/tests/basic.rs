@@ -60,6 +60,20 @@ fn basic() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         assert_eq!(got, exp);
     }
 
+    // masked serialize
+
+    #[cfg(feature = "serde")]
+    {
+        let got = masking::masked_serialize(&composite).unwrap();
+        let exp = serde_json::json!({ "secret_number": "***", "not_secret": "not secret" });
+        assert_eq!(got, exp);
+
+        // The secrets are serialized again once the masked serialization is over
+        let got = serde_json::to_string(&composite).unwrap();
+        let exp = r#"{"secret_number":"abc","not_secret":"not secret"}"#;
+        assert_eq!(got, exp);
+    }
+
     // end
 
     Ok(())
@@ -6,7 +6,10 @@ use diesel_models::process_tracker as storage;
 use error_stack::ResultExt;
 use router::{
     configs::settings::{CmdLineConf, Settings},
-    core::errors::{self, CustomResult},
+    core::{
+        self,
+        errors::{self, CustomResult},
+    },
     logger, routes, services,
     types::storage::ProcessTrackerExt,
     workflows,
@@ -74,8 +77,18 @@ async fn main() -> CustomResult<(), ProcessTrackerError> {
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum PTRunner {
     PaymentsSyncWorkflow,
+    PaymentsExpiryWorkflow,
+    PaymentsAuthorizationExpiryWorkflow,
+    PaymentsPartialAuthorizationWorkflow,
     RefundWorkflowRouter,
     DeleteTokenizeDataWorkflow,
+    WebhookEndpointWorkflow,
+    AuditEventWorkflow,
+    MandateMigrationWorkflow,
+    #[cfg(feature = "olap")]
+    ReconciliationReportWorkflow,
+    DataRetentionWorkflow,
+    CardFingerprintBackfillWorkflow,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -94,12 +107,40 @@ impl ProcessTrackerWorkflows<routes::AppState> for WorkflowRunner {
             Some(PTRunner::PaymentsSyncWorkflow) => {
                 Box::new(workflows::payment_sync::PaymentsSyncWorkflow)
             }
+            Some(PTRunner::PaymentsExpiryWorkflow) => {
+                Box::new(workflows::payment_expiry::PaymentsExpiryWorkflow)
+            }
+            Some(PTRunner::PaymentsAuthorizationExpiryWorkflow) => {
+                Box::new(workflows::authorization_expiry::PaymentsAuthorizationExpiryWorkflow)
+            }
+            Some(PTRunner::PaymentsPartialAuthorizationWorkflow) => {
+                Box::new(workflows::partial_authorization::PaymentsPartialAuthorizationWorkflow)
+            }
             Some(PTRunner::RefundWorkflowRouter) => {
                 Box::new(workflows::refund_router::RefundWorkflowRouter)
             }
             Some(PTRunner::DeleteTokenizeDataWorkflow) => {
                 Box::new(workflows::tokenized_data::DeleteTokenizeDataWorkflow)
             }
+            Some(PTRunner::WebhookEndpointWorkflow) => {
+                Box::new(workflows::webhook_endpoint::WebhookEndpointWorkflow)
+            }
+            Some(PTRunner::AuditEventWorkflow) => {
+                Box::new(workflows::audit_event::AuditEventWorkflow)
+            }
+            Some(PTRunner::MandateMigrationWorkflow) => {
+                Box::new(workflows::mandate_migration::MandateMigrationWorkflow)
+            }
+            #[cfg(feature = "olap")]
+            Some(PTRunner::ReconciliationReportWorkflow) => {
+                Box::new(workflows::reconciliation_report::ReconciliationReportWorkflow)
+            }
+            Some(PTRunner::DataRetentionWorkflow) => {
+                Box::new(workflows::data_retention::DataRetentionWorkflow)
+            }
+            Some(PTRunner::CardFingerprintBackfillWorkflow) => {
+                Box::new(workflows::card_fingerprint_backfill::CardFingerprintBackfillWorkflow)
+            }
             _ => Err(ProcessTrackerError::UnexpectedFlow)?,
         };
         let app_state = &state.clone();
@@ -139,6 +180,25 @@ async fn start_scheduler(
         .scheduler
         .clone()
         .ok_or(errors::ProcessTrackerError::ConfigurationError)?;
+    if let scheduler::SchedulerFlow::Producer = scheduler_flow {
+        if let Err(error) =
+            core::data_retention::schedule_data_retention(&*state.store, &state.conf.data_retention)
+                .await
+        {
+            logger::error!(?error, "Failed to schedule the data retention job");
+        }
+        if let Err(error) =
+            core::payment_methods::card_fingerprint_backfill::schedule_card_fingerprint_backfill(
+                state,
+            )
+            .await
+        {
+            logger::error!(
+                ?error,
+                "Failed to schedule the card fingerprint backfill job"
+            );
+        }
+    }
     scheduler::start_process_tracker(
         state,
         scheduler_flow,
@@ -132,6 +132,9 @@ pub enum StripeErrorCode {
         connector_name: String,
     },
 
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "token_already_used", message = "The merchant connector account with the specified connector_label '{connector_label}' already exists in our records")]
+    DuplicateConnectorLabel { connector_label: String },
+
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "token_already_used", message = "duplicate payment method")]
     DuplicatePaymentMethod,
 
@@ -234,6 +237,14 @@ pub enum StripeErrorCode {
     CurrencyNotSupported { message: String },
     #[error(error_type = StripeErrorType::HyperswitchError, code = "", message = "Resource Busy. Please try again later")]
     LockTimeout,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "rate_limit", message = "Too many requests hit the API too quickly. Retry after {retry_after_secs} seconds.")]
+    RateLimit { retry_after_secs: u64 },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "There is currently another in-progress request using this idempotency key. Retry after {retry_after_secs} seconds.")]
+    IdempotencyKeyInUse { retry_after_secs: u64 },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "permission_error", message = "The API key used does not have the '{permission}' permission required to access this resource.")]
+    MissingApiKeyPermission { permission: String },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_error", message = "Keys for idempotent requests can only be used with the same parameters they were first used with.")]
+    IdempotencyKeyReused,
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -342,7 +353,6 @@ pub enum StripeErrorCode {
         PostalCodeInvalid,
         ProcessingError,
         ProductInactive,
-        RateLimit,
         ReferToCustomer,
         RefundDisputedPayment,
         ResourceAlreadyExists,
@@ -399,7 +409,8 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             | errors::ApiErrorResponse::InvalidJwtToken
             | errors::ApiErrorResponse::GenericUnauthorized { .. }
             | errors::ApiErrorResponse::AccessForbidden { .. }
-            | errors::ApiErrorResponse::InvalidEphemeralKey => Self::Unauthorized,
+            | errors::ApiErrorResponse::InvalidEphemeralKey
+            | errors::ApiErrorResponse::EphemeralKeyExpired => Self::Unauthorized,
             errors::ApiErrorResponse::InvalidRequestUrl
             | errors::ApiErrorResponse::InvalidHttpMethod
             | errors::ApiErrorResponse::InvalidCardIin
@@ -459,7 +470,8 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::MandateUpdateFailed
             | errors::ApiErrorResponse::MandateSerializationFailed
             | errors::ApiErrorResponse::MandateDeserializationFailed
-            | errors::ApiErrorResponse::InternalServerError => Self::InternalServerError, // not a stripe code
+            | errors::ApiErrorResponse::InternalServerError
+            | errors::ApiErrorResponse::CardVaultRetrievalFailed => Self::InternalServerError, // not a stripe code
             errors::ApiErrorResponse::ExternalConnectorError {
                 code,
                 message,
@@ -509,6 +521,9 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 profile_id,
                 connector_name,
             },
+            errors::ApiErrorResponse::DuplicateConnectorLabel { connector_label } => {
+                Self::DuplicateConnectorLabel { connector_label }
+            }
             errors::ApiErrorResponse::DuplicatePaymentMethod => Self::DuplicatePaymentMethod,
             errors::ApiErrorResponse::ClientSecretInvalid => Self::PaymentIntentInvalidParameter {
                 param: "client_secret".to_owned(),
@@ -519,6 +534,29 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::PreconditionFailed { message } => {
                 Self::PreconditionFailed { message }
             }
+            errors::ApiErrorResponse::PaymentSessionExpired { payment_id } => {
+                Self::PreconditionFailed {
+                    message: format!("The session of payment {payment_id} has expired"),
+                }
+            }
+            errors::ApiErrorResponse::PaymentBlocked { payment_id } => Self::PreconditionFailed {
+                message: format!("The payment {payment_id} was blocked by the blocklist"),
+            },
+            errors::ApiErrorResponse::RefundCurrencyMismatch {
+                refund_currency,
+                payment_currency,
+            } => Self::InvalidRequestData {
+                message: format!(
+                    "The refund currency {refund_currency} does not match the currency {payment_currency} of the payment"
+                ),
+            },
+            errors::ApiErrorResponse::PaymentAuthorizationExpired { payment_id } => {
+                Self::PreconditionFailed {
+                    message: format!(
+                        "The authorization of the payment {payment_id} has expired and it can no longer be captured"
+                    ),
+                }
+            }
             errors::ApiErrorResponse::InvalidDataValue { field_name } => Self::ParameterMissing {
                 field_name: field_name.to_string(),
                 param: field_name.to_string(),
@@ -552,6 +590,13 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 object: "business_profile".to_owned(),
                 id,
             },
+            errors::ApiErrorResponse::BlocklistEntryNotFound { id } => Self::ResourceMissing {
+                object: "blocklist".to_owned(),
+                id,
+            },
+            errors::ApiErrorResponse::DuplicateBlocklistEntry => Self::GenericDuplicateError {
+                message: "The blocklist entry already exists".to_owned(),
+            },
             errors::ApiErrorResponse::DisputeStatusValidationFailed { reason } => {
                 Self::InternalServerError
             }
@@ -577,10 +622,76 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             | errors::ApiErrorResponse::WebhookProcessingFailure
             | errors::ApiErrorResponse::WebhookAuthenticationFailed
             | errors::ApiErrorResponse::WebhookUnprocessableEntity => Self::WebhookProcessingError,
-            errors::ApiErrorResponse::IncorrectPaymentMethodConfiguration => {
+            errors::ApiErrorResponse::IncorrectPaymentMethodConfiguration
+            | errors::ApiErrorResponse::NoEligibleConnector { .. } => {
                 Self::PaymentMethodUnactivated
             }
             errors::ApiErrorResponse::ResourceBusy => Self::PaymentMethodUnactivated,
+            errors::ApiErrorResponse::TooManyRequests { retry_after_secs } => {
+                Self::RateLimit { retry_after_secs }
+            }
+            errors::ApiErrorResponse::InvalidWalletConfiguration {
+                wallet,
+                key,
+                merchant_connector_account,
+            } => Self::InvalidRequestData {
+                message: format!(
+                    "The {wallet} configuration of the merchant connector account {merchant_connector_account} is missing or has an invalid `{key}`"
+                ),
+            },
+            errors::ApiErrorResponse::MandateChargeLimitExceeded {
+                mandate_id, reason, ..
+            } => Self::PaymentIntentMandateInvalid {
+                message: format!("The mandate {mandate_id} cannot be charged: {reason}"),
+            },
+            errors::ApiErrorResponse::PaymentConfirmInProgress { .. } => Self::LockTimeout,
+            errors::ApiErrorResponse::FlowDisabledForConnector {
+                flow, connector, ..
+            } => Self::PreconditionFailed {
+                message: format!("The {flow} flow is disabled for the {connector} account"),
+            },
+            errors::ApiErrorResponse::DuplicateOrderReference { payment_id, .. } => {
+                Self::DuplicatePayment { payment_id }
+            }
+            errors::ApiErrorResponse::ConnectorAccountModeMismatch {
+                payment_mode,
+                connector_mode,
+                ..
+            } => Self::PreconditionFailed {
+                message: format!(
+                    "The {payment_mode} payment cannot be processed by an account in {connector_mode} mode"
+                ),
+            },
+            errors::ApiErrorResponse::PaymentMethodNotAvailableForAmount { reason } => {
+                Self::InvalidRequestData {
+                    message: format!(
+                        "The payment method is not available for the amount of the payment: {reason}"
+                    ),
+                }
+            }
+            errors::ApiErrorResponse::WalletTokenExpired => Self::InvalidRequestData {
+                message: "The wallet token has expired, refresh the wallet session and retry the payment".to_string(),
+            },
+            errors::ApiErrorResponse::UnknownFields { fields } => Self::InvalidRequestData {
+                message: format!("Received unknown parameters: {}", fields.join(", ")),
+            },
+            errors::ApiErrorResponse::AmountOutsideLimits { reason } => Self::InvalidRequestData {
+                message: format!("The amount is outside the limits of its currency: {reason}"),
+            },
+            errors::ApiErrorResponse::ValidationFailed { errors } => Self::InvalidRequestData {
+                message: errors
+                    .iter()
+                    .map(|error| error.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            },
+            errors::ApiErrorResponse::IdempotencyKeyMismatch { .. } => Self::IdempotencyKeyReused,
+            errors::ApiErrorResponse::IdempotencyKeyInUse {
+                retry_after_secs, ..
+            } => Self::IdempotencyKeyInUse { retry_after_secs },
+            errors::ApiErrorResponse::MissingApiKeyPermission { permission } => {
+                Self::MissingApiKeyPermission { permission }
+            }
         }
     }
 }
@@ -618,6 +729,7 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::PayoutNotFound
             | Self::DuplicateMerchantAccount
             | Self::DuplicateMerchantConnectorAccount { .. }
+            | Self::DuplicateConnectorLabel { .. }
             | Self::DuplicatePaymentMethod
             | Self::PaymentFailed
             | Self::VerificationFailed { .. }
@@ -646,6 +758,7 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::FileNotAvailable
             | Self::FileProviderNotSupported
             | Self::CurrencyNotSupported { .. }
+            | Self::IdempotencyKeyReused
             | Self::PaymentMethodUnactivated => StatusCode::BAD_REQUEST,
             Self::RefundFailed
             | Self::PayoutFailed
@@ -658,15 +771,23 @@ impl actix_web::ResponseError for StripeErrorCode {
                 StatusCode::from_u16(*status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
             Self::LockTimeout => StatusCode::LOCKED,
+            Self::RateLimit { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::IdempotencyKeyInUse { .. } => StatusCode::CONFLICT,
+            Self::MissingApiKeyPermission { .. } => StatusCode::FORBIDDEN,
         }
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
         use actix_web::http::header;
 
-        actix_web::HttpResponseBuilder::new(self.status_code())
-            .insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON))
-            .body(self.to_string())
+        let mut response_builder = actix_web::HttpResponseBuilder::new(self.status_code());
+        response_builder.insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON));
+        if let Self::RateLimit { retry_after_secs }
+        | Self::IdempotencyKeyInUse { retry_after_secs } = self
+        {
+            response_builder.insert_header((header::RETRY_AFTER, retry_after_secs.to_string()));
+        }
+        response_builder.body(self.to_string())
     }
 }
 
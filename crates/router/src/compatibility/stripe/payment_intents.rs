@@ -6,7 +6,7 @@ use router_env::{instrument, tracing, Flow};
 
 use crate::{
     compatibility::{stripe::errors, wrap},
-    core::{api_locking::GetLockingInput, payments},
+    core::{api_locking::GetLockingInput, payments, rate_limiter},
     routes,
     services::{api, authentication as auth},
     types::api::{self as api_types},
@@ -49,17 +49,42 @@ pub async fn payment_intents_create(
         state.into_inner(),
         &req,
         create_payment_req,
-        |state, auth, req| {
-            payments::payments_core::<api_types::Authorize, api_types::PaymentsResponse, _, _, _>(
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payments::PaymentCreate,
+        |state, auth, req| async move {
+            rate_limiter::check_payment_rate_limit(
+                &state,
+                &auth.merchant_account.merchant_id,
+                None,
+            )
+            .await?;
+            payments::duplicate_order_reference::payments_create_with_duplicate_detection(
+                &state,
+                &auth.merchant_account,
+                &auth.key_store,
                 req,
-                api::AuthFlow::Merchant,
-                payments::CallConnectorAction::Trigger,
-                api_types::HeaderPayload::default(),
+                |req, suspected_duplicate_of| {
+                    payments::payments_core::<
+                        api_types::Authorize,
+                        api_types::PaymentsResponse,
+                        _,
+                        _,
+                        _,
+                    >(
+                        state.clone(),
+                        auth.merchant_account.clone(),
+                        auth.key_store.clone(),
+                        payments::PaymentCreate {
+                            suspected_duplicate_of,
+                            livemode: auth.api_key_livemode,
+                            ..Default::default()
+                        },
+                        req,
+                        api::AuthFlow::Merchant,
+                        payments::CallConnectorAction::Trigger,
+                        api_types::HeaderPayload::default(),
+                    )
+                },
             )
+            .await
         },
         &auth::ApiKeyAuth,
         locking_action,
@@ -286,6 +311,7 @@ pub async fn payment_intents_confirm(
 
     let flow = Flow::PaymentsConfirm;
     let locking_action = payload.get_locking_input(flow.clone());
+    let client_ip = routes::payments::helpers::get_rate_limited_client_ip(&req, auth_flow);
     Box::pin(wrap::compatibility_api_wrap::<
         _,
         _,
@@ -302,16 +328,26 @@ pub async fn payment_intents_confirm(
         &req,
         payload,
         |state, auth, req| {
-            payments::payments_core::<api_types::Authorize, api_types::PaymentsResponse, _, _, _>(
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payments::PaymentConfirm,
-                req,
-                auth_flow,
-                payments::CallConnectorAction::Trigger,
-                api_types::HeaderPayload::default(),
-            )
+            let client_ip = client_ip.clone();
+            async move {
+                rate_limiter::check_payment_rate_limit(
+                    &state,
+                    &auth.merchant_account.merchant_id,
+                    client_ip.as_deref(),
+                )
+                .await?;
+                payments::payments_core::<api_types::Authorize, api_types::PaymentsResponse, _, _, _>(
+                    state,
+                    auth.merchant_account,
+                    auth.key_store,
+                    payments::PaymentConfirm,
+                    req,
+                    auth_flow,
+                    payments::CallConnectorAction::Trigger,
+                    api_types::HeaderPayload::default(),
+                )
+                .await
+            }
         },
         &*auth_type,
         locking_action,
@@ -268,6 +268,7 @@ pub struct StripePaymentIntentRequest {
     pub error_on_requires_action: Option<String>,            // not used
     pub radar_options: Option<SecretSerdeValue>,             // not used
     pub connector_metadata: Option<payments::ConnectorMetadata>,
+    pub merchant_order_reference_id: Option<String>,
 }
 
 impl TryFrom<StripePaymentIntentRequest> for payments::PaymentsRequest {
@@ -371,6 +372,7 @@ impl TryFrom<StripePaymentIntentRequest> for payments::PaymentsRequest {
                 .attach_printable("convert to browser info failed")?,
             ),
             connector_metadata: item.connector_metadata,
+            merchant_order_reference_id: item.merchant_order_reference_id,
             ..Self::default()
         });
         request
@@ -432,10 +434,22 @@ pub struct StripePaymentCancelRequest {
     cancellation_reason: Option<CancellationReason>,
 }
 
+impl From<CancellationReason> for api_enums::CancellationReason {
+    fn from(item: CancellationReason) -> Self {
+        match item {
+            CancellationReason::Duplicate => Self::Duplicate,
+            CancellationReason::Fraudulent => Self::FraudSuspected,
+            CancellationReason::RequestedByCustomer => Self::RequestedByCustomer,
+            CancellationReason::Abandoned => Self::Abandoned,
+        }
+    }
+}
+
 impl From<StripePaymentCancelRequest> for payments::PaymentsCancelRequest {
     fn from(item: StripePaymentCancelRequest) -> Self {
         Self {
             cancellation_reason: item.cancellation_reason.map(|c| c.to_string()),
+            cancellation_reason_code: item.cancellation_reason.map(Into::into),
             ..Self::default()
         }
     }
@@ -547,7 +561,7 @@ impl From<payments::PaymentsResponse> for StripePaymentIntentResponse {
                     created: u64::try_from(date_time::now().assume_utc().unix_timestamp())
                         .unwrap_or_default(),
                     method_type: "card".to_string(),
-                    livemode: false,
+                    livemode: resp.livemode,
                 },
                 error_type: code,
             }),
@@ -624,6 +638,7 @@ impl TryFrom<StripePaymentListConstraints> for payments::PaymentListConstraints
             created_gt: from_timestamp_to_datetime(item.created_gt)?,
             created_lte: from_timestamp_to_datetime(item.created_lte)?,
             created_gte: from_timestamp_to_datetime(item.created_gte)?,
+            livemode: None,
         })
     }
 }
@@ -720,6 +735,8 @@ impl ForeignTryFrom<(Option<MandateData>, Option<String>)> for Option<payments::
                             start_date: mandate.start_date,
                             end_date: mandate.end_date,
                             metadata: None,
+                            frequency: None,
+                            max_charges_per_period: None,
                         },
                     )),
                     StripeMandateType::MultiUse => Some(payments::MandateType::MultiUse(None)),
@@ -779,6 +796,7 @@ pub enum StripeNextAction {
     },
     QrCodeInformation {
         image_data_url: url::Url,
+        qr_code_data: Option<String>,
         display_to_timestamp: Option<i64>,
     },
     DisplayVoucherInformation {
@@ -787,6 +805,11 @@ pub enum StripeNextAction {
     WaitScreenInformation {
         display_from_timestamp: i128,
         display_to_timestamp: Option<i128>,
+        poll_interval_in_secs: Option<u16>,
+    },
+    CollectOtp {
+        otp_length: Option<u8>,
+        display_to_timestamp: Option<i64>,
     },
 }
 
@@ -813,9 +836,11 @@ pub(crate) fn into_stripe_next_action(
         }
         payments::NextActionData::QrCodeInformation {
             image_data_url,
+            qr_code_data,
             display_to_timestamp,
         } => StripeNextAction::QrCodeInformation {
             image_data_url,
+            qr_code_data,
             display_to_timestamp,
         },
         payments::NextActionData::DisplayVoucherInformation { voucher_details } => {
@@ -824,9 +849,18 @@ pub(crate) fn into_stripe_next_action(
         payments::NextActionData::WaitScreenInformation {
             display_from_timestamp,
             display_to_timestamp,
+            poll_interval_in_secs,
         } => StripeNextAction::WaitScreenInformation {
             display_from_timestamp,
             display_to_timestamp,
+            poll_interval_in_secs,
+        },
+        payments::NextActionData::CollectOtp {
+            otp_length,
+            display_to_timestamp,
+        } => StripeNextAction::CollectOtp {
+            otp_length,
+            display_to_timestamp,
         },
     })
 }
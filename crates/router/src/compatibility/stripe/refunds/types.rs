@@ -1,9 +1,9 @@
-use std::{convert::From, default::Default};
+use std::{convert::From, default::Default, str::FromStr};
 
 use common_utils::pii;
 use serde::{Deserialize, Serialize};
 
-use crate::types::api::{admin, refunds};
+use crate::types::api::{admin, enums as api_enums, refunds};
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct StripeCreateRefundRequest {
@@ -46,6 +46,11 @@ impl From<StripeCreateRefundRequest> for refunds::RefundRequest {
             refund_id: req.refund_id,
             amount: req.amount,
             payment_id: req.payment_intent,
+            // The reason of a stripe refund is one of the reasons which are mapped to a reason code
+            reason_code: req
+                .reason
+                .as_deref()
+                .and_then(|reason| api_enums::RefundReason::from_str(reason).ok()),
             reason: req.reason,
             refund_type: Some(refunds::RefundType::Instant),
             metadata: req.metadata,
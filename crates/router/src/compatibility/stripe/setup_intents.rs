@@ -58,7 +58,10 @@ pub async fn setup_intents_create(
                 state,
                 auth.merchant_account,
                 auth.key_store,
-                payments::PaymentCreate,
+                payments::PaymentCreate {
+                    livemode: auth.api_key_livemode,
+                    ..Default::default()
+                },
                 req,
                 api::AuthFlow::Merchant,
                 payments::CallConnectorAction::Trigger,
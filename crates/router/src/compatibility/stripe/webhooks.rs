@@ -2,7 +2,7 @@ use api_models::{
     enums::DisputeStatus,
     webhooks::{self as api},
 };
-use common_utils::{crypto::SignMessage, date_time, ext_traits};
+use common_utils::{crypto::SignMessage, date_time};
 use error_stack::{IntoReport, ResultExt};
 use router_env::logger;
 use serde::Serialize;
@@ -30,6 +30,7 @@ pub struct StripeOutgoingWebhook {
 impl OutgoingWebhookType for StripeOutgoingWebhook {
     fn get_outgoing_webhooks_signature(
         &self,
+        outgoing_webhook_string: &str,
         payment_response_hash_key: Option<String>,
     ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError> {
         let timestamp = self.created;
@@ -39,12 +40,7 @@ impl OutgoingWebhookType for StripeOutgoingWebhook {
             .into_report()
             .attach_printable("For stripe compatibility payment_response_hash_key is mandatory")?;
 
-        let webhook_signature_payload =
-            ext_traits::Encode::<serde_json::Value>::encode_to_string_of_json(self)
-                .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
-                .attach_printable("failed encoding outgoing webhook payload")?;
-
-        let new_signature_payload = format!("{timestamp}.{webhook_signature_payload}");
+        let new_signature_payload = format!("{timestamp}.{outgoing_webhook_string}");
         let v1 = hex::encode(
             common_utils::crypto::HmacSha256::sign_message(
                 &common_utils::crypto::HmacSha256,
@@ -73,6 +69,7 @@ pub enum StripeWebhookObject {
     PaymentIntent(StripePaymentIntentResponse),
     Refund(StripeRefundResponse),
     Dispute(StripeDisputeResponse),
+    Alert(Box<api_models::alerts::AlertResponse>),
 }
 
 #[derive(Serialize, Debug)]
@@ -130,6 +127,7 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
         api_models::enums::EventType::PaymentSucceeded => "payment_intent.succeeded",
         api_models::enums::EventType::PaymentFailed => "payment_intent.payment_failed",
         api_models::enums::EventType::PaymentProcessing => "payment_intent.processing",
+        api_models::enums::EventType::PaymentCancelled => "payment_intent.canceled",
 
         // the below are not really stripe compatible because stripe doesn't provide this
         api_models::enums::EventType::ActionRequired => "action.required",
@@ -142,6 +140,13 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
         api_models::enums::EventType::DisputeChallenged => "dispute.challenged",
         api_models::enums::EventType::DisputeWon => "dispute.won",
         api_models::enums::EventType::DisputeLost => "dispute.lost",
+        api_models::enums::EventType::PaymentCaptureDeadlineApproaching => {
+            "payment_intent.capture_deadline_approaching"
+        }
+        api_models::enums::EventType::AlertReceived => "alert.received",
+        api_models::enums::EventType::PaymentPartiallyAuthorized => {
+            "payment_intent.partially_authorized"
+        }
     }
 }
 
@@ -179,6 +184,7 @@ impl From<api::OutgoingWebhookContent> for StripeWebhookObject {
             api::OutgoingWebhookContent::DisputeDetails(dispute) => {
                 Self::Dispute((*dispute).into())
             }
+            api::OutgoingWebhookContent::AlertDetails(alert) => Self::Alert(alert),
         }
     }
 }
@@ -42,6 +42,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut response_pii_redaction = None;
     let res = match metrics::request::record_request_time_metric(
         api::server_wrap_util(
             &flow,
@@ -51,6 +52,8 @@ where
             func,
             api_authentication,
             lock_action,
+            None,
+            &mut response_pii_redaction,
         ),
         &flow,
     )
@@ -62,7 +65,10 @@ where
         Ok(api::ApplicationResponse::Json(response)) => {
             let response = S::try_from(response);
             match response {
-                Ok(response) => match serde_json::to_string(&response) {
+                Ok(response) => match api::pii_redaction::redact_and_encode(
+                    &response,
+                    response_pii_redaction.as_ref(),
+                ) {
                     Ok(res) => api::http_response_json(res),
                     Err(_) => api::http_response_err(
                         r#"{
@@ -84,7 +90,10 @@ where
         Ok(api::ApplicationResponse::JsonWithHeaders((response, headers))) => {
             let response = S::try_from(response);
             match response {
-                Ok(response) => match serde_json::to_string(&response) {
+                Ok(response) => match api::pii_redaction::redact_and_encode(
+                    &response,
+                    response_pii_redaction.as_ref(),
+                ) {
                     Ok(res) => api::http_response_json_with_headers(res, headers),
                     Err(_) => api::http_response_err(
                         r#"{
@@ -108,6 +117,9 @@ where
         Ok(api::ApplicationResponse::FileData((file_data, content_type))) => {
             api::http_response_file_data(file_data, content_type)
         }
+        Ok(api::ApplicationResponse::FileStream(file_stream)) => {
+            api::http_response_file_stream(file_stream)
+        }
         Ok(api::ApplicationResponse::JsonForRedirection(response)) => {
             match serde_json::to_string(&response) {
                 Ok(res) => api::http_redirect_response(res, response),
@@ -132,6 +144,10 @@ where
             .respond_to(request)
             .map_into_boxed_body()
         }
+        Ok(api::ApplicationResponse::NotModified(headers)) => {
+            api::http_response_not_modified(headers)
+        }
+        Ok(api::ApplicationResponse::IdempotentReplay(response)) => response.into_http_response(),
         Err(error) => api::log_and_return_error_response(error),
     };
 
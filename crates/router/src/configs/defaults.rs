@@ -83,6 +83,174 @@ impl Default for super::settings::Refund {
     }
 }
 
+impl Default for super::settings::CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_duration_in_secs: 60,
+            minimum_requests: 20,
+            failure_rate_threshold: 50,
+            open_duration_in_secs: 30,
+            half_open_max_probes: 1,
+        }
+    }
+}
+
+impl Default for super::settings::AuthorizationValidityConfig {
+    fn default() -> Self {
+        Self {
+            // 7 days
+            default_validity_in_secs: 604800,
+            // 1 day
+            lead_time_in_secs: 86400,
+            void_on_expiry: false,
+            connector_validity_in_secs: HashMap::new(),
+        }
+    }
+}
+
+impl Default for super::settings::PaymentConfirmLockConfig {
+    fn default() -> Self {
+        Self {
+            lock_expiry_in_secs: 120,
+            wait_time_in_millis: 3000,
+            delay_between_retries_in_millis: 200,
+        }
+    }
+}
+
+impl Default for super::settings::IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl_in_secs: 86400,
+            in_progress_ttl_in_secs: 120,
+            wait_time_in_millis: 3000,
+            delay_between_retries_in_millis: 200,
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorProxyConfig {
+    fn default() -> Self {
+        Self {
+            connectors: HashMap::new(),
+            connect_timeout_in_secs: 5,
+            allowed_proxy_urls: Vec::new(),
+        }
+    }
+}
+
+impl Default for super::settings::DataRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pii_retention_days: 90,
+            // 13 months, which covers the dispute windows of the card networks
+            archival_days: 395,
+            batch_size: 500,
+            batch_interval_in_millis: 1000,
+            max_batches_per_task: 100,
+            run_interval_in_hours: 24,
+        }
+    }
+}
+
+impl Default for super::settings::ForexConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://openexchangerates.org/api/latest.json".into(),
+            api_key: String::new().into(),
+            cache_ttl_in_secs: 3600,
+            max_rates_age_in_secs: 86400,
+        }
+    }
+}
+
+impl Default for super::settings::MerchantMetadataConfig {
+    fn default() -> Self {
+        Self {
+            max_size_in_bytes: 8192,
+            max_keys: 50,
+            max_key_length: 64,
+            max_depth: 5,
+            connector_pass_through_keys: HashMap::new(),
+        }
+    }
+}
+
+impl Default for super::settings::CredentialRotationConfig {
+    fn default() -> Self {
+        Self {
+            grace_period_in_secs: 86400,
+        }
+    }
+}
+
+impl Default for super::settings::PaymentRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            merchant: super::settings::RateLimit {
+                requests: 1000,
+                window_in_secs: 60,
+            },
+            client_ip: super::settings::RateLimit {
+                requests: 20,
+                window_in_secs: 60,
+            },
+        }
+    }
+}
+
+impl Default for super::settings::AmountLimitsConfig {
+    fn default() -> Self {
+        // The lowest amounts accepted by the card networks, in the minor unit of each currency. The
+        // amounts are not capped by default, as a single maximum would not fit every currency, the
+        // maximum amount of a currency is set in the configs
+        let minimum_amounts = [
+            (enums::Currency::AED, 200),
+            (enums::Currency::AUD, 50),
+            (enums::Currency::BRL, 50),
+            (enums::Currency::CAD, 50),
+            (enums::Currency::CHF, 50),
+            (enums::Currency::CZK, 1500),
+            (enums::Currency::DKK, 250),
+            (enums::Currency::EUR, 50),
+            (enums::Currency::GBP, 30),
+            (enums::Currency::HKD, 400),
+            (enums::Currency::HUF, 17500),
+            (enums::Currency::INR, 50),
+            (enums::Currency::JPY, 50),
+            (enums::Currency::MXN, 1000),
+            (enums::Currency::MYR, 200),
+            (enums::Currency::NOK, 300),
+            (enums::Currency::NZD, 50),
+            (enums::Currency::PLN, 200),
+            (enums::Currency::SEK, 300),
+            (enums::Currency::SGD, 50),
+            (enums::Currency::THB, 1000),
+            (enums::Currency::USD, 50),
+        ];
+
+        Self {
+            currencies: minimum_amounts
+                .into_iter()
+                .map(|(currency, minimum_amount)| {
+                    (
+                        currency,
+                        super::settings::AmountLimit {
+                            minimum_amount: Some(minimum_amount),
+                            maximum_amount: None,
+                            minimum_capture_amount: Some(minimum_amount),
+                            minimum_refund_amount: None,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
 impl Default for super::settings::EphemeralConfig {
     fn default() -> Self {
         Self { validity: 1 }
@@ -711,7 +879,19 @@ impl Default for super::settings::RequiredFields {
                                             )
                                         ]
                                     ),
-                                    common:HashMap::new(),
+                                    common: HashMap::from(
+                                        [
+                                            (
+                                                "device_session_id".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "device_session_id".to_string(),
+                                                    display_name: "device_session_id".to_string(),
+                                                    field_type: enums::FieldType::DeviceSessionId,
+                                                    value: None,
+                                                }
+                                            )
+                                        ]
+                                    ),
                                 }
                             ),
                             (
@@ -2487,7 +2667,19 @@ impl Default for super::settings::RequiredFields {
                                             )
                                         ]
                                     ),
-                                    common:HashMap::new(),
+                                    common: HashMap::from(
+                                        [
+                                            (
+                                                "device_session_id".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "device_session_id".to_string(),
+                                                    display_name: "device_session_id".to_string(),
+                                                    field_type: enums::FieldType::DeviceSessionId,
+                                                    value: None,
+                                                }
+                                            )
+                                        ]
+                                    ),
                                 }
                             ),
                             (
@@ -3971,6 +4163,140 @@ impl Default for super::settings::RequiredFields {
                                         common : HashMap::new(),
                                     }
                                 ),
+                                (
+                                    enums::Connector::Adyen,
+                                    RequiredFieldFinal {
+                                        mandate: HashMap::new(),
+                                        non_mandate: HashMap::new(),
+                                        common: HashMap::from([
+                                            (
+                                                "billing.address.first_name".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.first_name".to_string(),
+                                                    display_name: "billing_first_name".to_string(),
+                                                    field_type: enums::FieldType::UserBillingName,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.last_name".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.last_name".to_string(),
+                                                    display_name: "billing_last_name".to_string(),
+                                                    field_type: enums::FieldType::UserBillingName,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.country".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.country".to_string(),
+                                                    display_name: "country".to_string(),
+                                                    field_type: enums::FieldType::UserAddressCountry{
+                                                        options: vec![
+                                                            "ALL".to_string(),
+                                                        ]
+                                                    },
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "email".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "email".to_string(),
+                                                    display_name: "email".to_string(),
+                                                    field_type: enums::FieldType::UserEmailAddress,
+                                                    value: None,
+                                                }
+                                            ),
+                                        ]),
+                                    }
+                                ),
+                                (
+                                    enums::Connector::Multisafepay,
+                                    RequiredFieldFinal {
+                                        mandate: HashMap::new(),
+                                        non_mandate: HashMap::new(),
+                                        common: HashMap::from([
+                                            (
+                                                "billing.address.first_name".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.first_name".to_string(),
+                                                    display_name: "billing_first_name".to_string(),
+                                                    field_type: enums::FieldType::UserBillingName,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.last_name".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.last_name".to_string(),
+                                                    display_name: "billing_last_name".to_string(),
+                                                    field_type: enums::FieldType::UserBillingName,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.line1".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.line1".to_string(),
+                                                    display_name: "line1".to_string(),
+                                                    field_type: enums::FieldType::UserAddressline1,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.line2".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.line2".to_string(),
+                                                    display_name: "line2".to_string(),
+                                                    field_type: enums::FieldType::UserAddressline2,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.city".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.city".to_string(),
+                                                    display_name: "city".to_string(),
+                                                    field_type: enums::FieldType::UserAddressCity,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.zip".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.zip".to_string(),
+                                                    display_name: "zip".to_string(),
+                                                    field_type: enums::FieldType::UserAddressPincode,
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "billing.address.country".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "billing.address.country".to_string(),
+                                                    display_name: "country".to_string(),
+                                                    field_type: enums::FieldType::UserAddressCountry{
+                                                        options: vec![
+                                                            "ALL".to_string(),
+                                                        ]
+                                                    },
+                                                    value: None,
+                                                }
+                                            ),
+                                            (
+                                                "email".to_string(),
+                                                RequiredFieldInfo {
+                                                    required_field: "email".to_string(),
+                                                    display_name: "email".to_string(),
+                                                    field_type: enums::FieldType::UserEmailAddress,
+                                                    value: None,
+                                                }
+                                            ),
+                                        ]),
+                                    }
+                                ),
                                 ]),
                         },
                     ),
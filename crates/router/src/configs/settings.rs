@@ -5,7 +5,7 @@ use std::{
 };
 
 use api_models::{enums, payment_methods::RequiredFieldInfo};
-use common_utils::ext_traits::ConfigExt;
+use common_utils::{ext_traits::ConfigExt, request::OutboundProxy};
 use config::{Environment, File};
 #[cfg(feature = "email")]
 use external_services::email::EmailSettings;
@@ -92,14 +92,33 @@ pub struct Settings {
     pub required_fields: RequiredFields,
     pub delayed_session_response: DelayedSessionConfig,
     pub webhook_source_verification_call: WebhookSourceVerificationCall,
+    pub webhook_endpoint_management: WebhookEndpointManagement,
     pub connector_request_reference_id_config: ConnectorRequestReferenceIdConfig,
     #[cfg(feature = "payouts")]
     pub payouts: Payouts,
     pub applepay_decrypt_keys: ApplePayDecryptConifg,
+    pub googlepay_decrypt_keys: GooglePayDecryptConfig,
     pub multiple_api_version_supported_connectors: MultipleApiVersionSupportedConnectors,
     pub applepay_merchant_configs: ApplepayMerchantConfigs,
     pub lock_settings: LockSettings,
     pub temp_locker_disable_config: TempLockerDisableConfig,
+    pub circuit_breaker: CircuitBreakerConfig,
+    pub sub_merchant: SubMerchantConfig,
+    pub authorization_validity: AuthorizationValidityConfig,
+    pub payment_rate_limit: PaymentRateLimitConfig,
+    pub amount_limits: AmountLimitsConfig,
+    pub connector_capabilities: ConnectorCapabilitiesConfig,
+    pub payment_confirm_lock: PaymentConfirmLockConfig,
+    pub forex: ForexConfig,
+    pub merchant_metadata: MerchantMetadataConfig,
+    pub bin_database: BinDatabaseConfig,
+    pub credential_rotation: CredentialRotationConfig,
+    pub livemode: LivemodeConfig,
+    pub redirect_response: RedirectResponseConfig,
+    pub data_retention: DataRetentionConfig,
+    pub connector_proxies: ConnectorProxyConfig,
+    pub idempotency: IdempotencyConfig,
+    pub device_data_collection: DeviceDataCollectionConfig,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -443,6 +462,91 @@ pub struct Proxy {
     pub idle_pool_connection_timeout: Option<u64>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct IdempotencyConfig {
+    /// Duration for which the response of a request is replayed to the requests made with its
+    /// idempotency key
+    pub ttl_in_secs: u32,
+    /// Duration after which a request still in progress, such as one whose instance stopped while
+    /// processing it, no longer holds its idempotency key
+    pub in_progress_ttl_in_secs: u32,
+    /// Duration for which a request waits for the request in progress with its idempotency key
+    /// before it is rejected
+    pub wait_time_in_millis: u32,
+    pub delay_between_retries_in_millis: u32,
+}
+
+/// Device data collection of the connectors which require it before the authorization, by the name
+/// of the connector
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct DeviceDataCollectionConfig(pub HashMap<String, ConnectorDeviceDataCollection>);
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConnectorDeviceDataCollection {
+    /// Organization of the connector under which the device data is collected for the merchant
+    /// connector accounts in test mode
+    pub test_org_id: String,
+    /// Organization of the connector under which the device data is collected for the live
+    /// merchant connector accounts
+    pub live_org_id: String,
+    /// Script which the SDK runs to collect the device data
+    pub script_url: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorProxyConfig {
+    /// Proxies through which the requests to the connectors are sent, by the name of the
+    /// connector. The requests to the other connectors, and the webhooks sent to the merchants,
+    /// are sent as configured in `proxy`
+    pub connectors: HashMap<String, ConnectorProxy>,
+    /// Timeout of the connection to each proxy at startup, a proxy which cannot be connected to
+    /// fails the startup of the application
+    pub connect_timeout_in_secs: u64,
+    /// URLs of the proxies which a merchant connector account is allowed to configure, the
+    /// proxies of the accounts which are not listed are not used
+    pub allowed_proxy_urls: Vec<String>,
+}
+
+/// Proxy through which the requests to a connector are sent, which can be overridden for a
+/// merchant connector account in the `outbound_proxy` field of its connector account details
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConnectorProxy {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<masking::Secret<String>>,
+    /// Hosts of the connector which are connected to directly, a host starting with a `.` also
+    /// matches its subdomains
+    #[serde(default)]
+    pub bypass_hosts: Vec<String>,
+}
+
+impl ConnectorProxy {
+    /// The proxy of the request to the URL, none if the host of the URL is bypassed
+    pub fn get_outbound_proxy(&self, url: &str) -> Option<OutboundProxy> {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase));
+        let is_bypassed = host.map_or(false, |host| {
+            self.bypass_hosts.iter().any(|bypass_host| {
+                let bypass_host = bypass_host.to_lowercase();
+                match bypass_host.strip_prefix('.') {
+                    Some(domain) => host == domain || host.ends_with(&bypass_host),
+                    None => host == bypass_host,
+                }
+            })
+        });
+
+        (!is_bypassed).then(|| OutboundProxy {
+            url: self.url.clone(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Server {
@@ -638,6 +742,12 @@ pub struct WebhookSourceVerificationCall {
     pub connectors_with_webhook_source_verification_call: HashSet<api_models::enums::Connector>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct WebhookEndpointManagement {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connectors_with_webhook_endpoint_management: HashSet<api_models::enums::Connector>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ApplePayDecryptConifg {
     pub apple_pay_ppc: String,
@@ -646,6 +756,254 @@ pub struct ApplePayDecryptConifg {
     pub apple_pay_merchant_cert_key: String,
 }
 
+/// Keys decrypting the Google Pay tokens of the payments routed to the connectors which accept the
+/// wallet payments only as cards
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GooglePayDecryptConfig {
+    /// Root signing keys published by Google, as the JSON document which Google publishes them in
+    pub google_pay_root_signing_keys: String,
+    /// Recipient ID which the tokens are encrypted for, `gateway:<gateway ID>`
+    pub google_pay_recipient_id: String,
+    /// Private key in PEM format, of the key pair whose public key is registered with Google Pay
+    pub google_pay_private_key: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Duration of the window over which the connector call failures are counted
+    pub window_duration_in_secs: u32,
+    /// Minimum number of connector calls in the window before the failure rate is evaluated
+    pub minimum_requests: u32,
+    /// Failure rate (in percentage) at or above which the circuit is opened
+    pub failure_rate_threshold: u8,
+    /// Duration for which the circuit stays open before probe requests are allowed
+    pub open_duration_in_secs: u32,
+    /// Number of probe requests allowed while the circuit is half open
+    pub half_open_max_probes: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SubMerchantConfig {
+    /// Connectors to which the merchant descriptor and sub-merchant details of payment
+    /// facilitators are passed
+    #[serde(deserialize_with = "connector_deser")]
+    pub supported_connectors: HashSet<api_models::enums::Connector>,
+    /// Behaviour for payments carrying sub-merchant details routed to other connectors
+    pub unsupported_connector_behaviour: UnsupportedSubMerchantBehaviour,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsupportedSubMerchantBehaviour {
+    /// The payment is processed without the sub-merchant details
+    #[default]
+    Ignore,
+    /// The payment is failed
+    Reject,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorCapabilitiesConfig {
+    /// Behaviour for merchant connector accounts which enable payment methods, currencies or
+    /// countries which the connector does not declare support for
+    pub unsupported_configuration_behaviour: UnsupportedConfigurationBehaviour,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsupportedConfigurationBehaviour {
+    /// The merchant connector account is saved and a warning is logged
+    #[default]
+    Warn,
+    /// The merchant connector account is rejected
+    Reject,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuthorizationValidityConfig {
+    /// Duration for which a manual capture authorization stays valid at the connector
+    pub default_validity_in_secs: u32,
+    /// Duration before the expiry of an authorization at which the merchant is warned, or at
+    /// which the payment is captured if the merchant has opted in to automatic capture
+    pub lead_time_in_secs: u32,
+    /// Whether authorizations which expire without being captured are voided at the connector
+    pub void_on_expiry: bool,
+    /// Validity of the authorizations of connectors which differ from the default validity
+    pub connector_validity_in_secs: HashMap<String, u32>,
+}
+
+impl AuthorizationValidityConfig {
+    pub fn get_validity_in_secs(&self, connector: &str) -> u32 {
+        self.connector_validity_in_secs
+            .get(connector)
+            .copied()
+            .unwrap_or(self.default_validity_in_secs)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentRateLimitConfig {
+    pub enabled: bool,
+    /// Limit on the payment create and confirm requests of each merchant, which can be overridden
+    /// for a merchant in the configs
+    pub merchant: RateLimit,
+    /// Limit on the payment confirm requests made with the publishable key of a merchant from
+    /// each client IP
+    pub client_ip: RateLimit,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests which can be made in a burst, the limit is restored at this rate over
+    /// the window
+    pub requests: u32,
+    pub window_in_secs: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AmountLimitsConfig {
+    /// Limits on the amounts in each currency, which can be overridden for a merchant in the
+    /// configs. The amounts in the currencies which are not listed are not limited
+    pub currencies: HashMap<enums::Currency, AmountLimit>,
+}
+
+/// Limits on the amounts in a currency, in the minor unit of the currency
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct AmountLimit {
+    pub minimum_amount: Option<i64>,
+    pub maximum_amount: Option<i64>,
+    pub minimum_capture_amount: Option<i64>,
+    pub minimum_refund_amount: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentConfirmLockConfig {
+    /// Duration after which the lock held by a confirm request is released, it must be longer
+    /// than the connector call
+    pub lock_expiry_in_secs: u32,
+    /// Duration for which a concurrent confirm request waits for the lock before it is rejected
+    pub wait_time_in_millis: u32,
+    pub delay_between_retries_in_millis: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ForexConfig {
+    /// Endpoint of the latest exchange rates of the Open Exchange Rates API
+    pub base_url: String,
+    pub api_key: masking::Secret<String>,
+    /// Duration after which the cached rates are fetched again from the provider
+    pub cache_ttl_in_secs: u32,
+    /// Age of the rates beyond which they are not used, the rates are served from the cache up to
+    /// this age when the provider cannot be reached
+    pub max_rates_age_in_secs: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CredentialRotationConfig {
+    /// Duration for which the webhooks signed with the credentials replaced in a rotation are
+    /// accepted, unless the rotation is finalized earlier
+    pub grace_period_in_secs: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LivemodeConfig {
+    /// Whether the payments created with live API keys are rejected when they are routed to a
+    /// merchant connector account in test mode. The payments created with test API keys are
+    /// always rejected when they are routed to a merchant connector account which is not in test
+    /// mode.
+    pub restrict_live_payments_to_live_connectors: bool,
+    /// Behaviour for merchant connector accounts in test mode whose credentials look like live
+    /// credentials of the connector
+    pub live_credentials_in_test_mode_behaviour: UnsupportedConfigurationBehaviour,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RedirectResponseConfig {
+    /// Page to which the customer is redirected with the error when the payment cannot be
+    /// identified from the redirect response of the connector, and the merchant has no return URL
+    pub failure_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DataRetentionConfig {
+    /// Whether the data retention job is scheduled, by the producer of the scheduler
+    pub enabled: bool,
+    /// Age in days after which the browser info and the payment method data of the payment
+    /// attempts are redacted
+    pub pii_retention_days: u32,
+    /// Age in days after which the payments in a terminal status are archived, the archived
+    /// payments are only listed when the payments which are not archived do not fill the page
+    pub archival_days: u32,
+    /// Rows redacted or archived by a batch of the job
+    pub batch_size: u32,
+    /// Pause between two batches of a run, which bounds the load of the job on the database
+    pub batch_interval_in_millis: u64,
+    /// Batches of a run processed by a task, before the task is rescheduled with its progress
+    pub max_batches_per_task: u32,
+    /// Interval between two runs of the job
+    pub run_interval_in_hours: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BinDatabaseConfig {
+    /// CSV file of the card BINs with their network, card type, issuing country and bank, card
+    /// BIN metadata is not available when the file is not configured
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct MerchantMetadataConfig {
+    /// Size of the metadata of a payment or a merchant connector account, serialized as JSON
+    pub max_size_in_bytes: usize,
+    /// Number of keys at the top level of the metadata
+    pub max_keys: usize,
+    /// Length of the keys at any level of the metadata
+    pub max_key_length: usize,
+    /// Nesting of the objects and arrays in the metadata, the metadata object itself is a level
+    pub max_depth: usize,
+    /// Keys of the payment metadata forwarded to each connector, the metadata is not forwarded to
+    /// the connectors which are not listed
+    #[serde(deserialize_with = "connector_keys_deser")]
+    pub connector_pass_through_keys: HashMap<String, HashSet<String>>,
+}
+
+fn connector_keys_deser<'a, D>(
+    deserializer: D,
+) -> Result<HashMap<String, HashSet<String>>, D::Error>
+where
+    D: Deserializer<'a>,
+{
+    let value = <HashMap<String, String>>::deserialize(deserializer)?;
+    Ok(value
+        .into_iter()
+        .map(|(connector, keys)| {
+            let keys = keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(String::from)
+                .collect();
+            (connector, keys)
+        })
+        .collect())
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorRequestReferenceIdConfig {
     pub merchant_ids_send_payment_id_as_connector_request_id: HashSet<String>,
@@ -741,6 +1099,18 @@ impl Settings {
         #[cfg(feature = "kv_store")]
         self.drainer.validate()?;
         self.api_keys.validate()?;
+        self.circuit_breaker.validate()?;
+        self.authorization_validity.validate()?;
+        self.payment_rate_limit.validate()?;
+        self.amount_limits.validate()?;
+        self.forex.validate()?;
+        self.merchant_metadata.validate()?;
+        self.credential_rotation.validate()?;
+        self.redirect_response.validate()?;
+        self.data_retention.validate()?;
+        self.connector_proxies.validate()?;
+        self.idempotency.validate()?;
+        self.device_data_collection.validate()?;
         #[cfg(feature = "kms")]
         self.kms
             .validate()
@@ -748,6 +1118,7 @@ impl Settings {
         #[cfg(feature = "s3")]
         self.file_upload_config.validate()?;
         self.lock_settings.validate()?;
+        self.required_fields.validate()?;
         Ok(())
     }
 }
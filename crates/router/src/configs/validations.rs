@@ -193,3 +193,364 @@ impl super::settings::LockSettings {
         })
     }
 }
+
+impl super::settings::CircuitBreakerConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(!(1..=100).contains(&self.failure_rate_threshold), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker failure_rate_threshold must be between 1 and 100".into(),
+            ))
+        })?;
+
+        when(self.window_duration_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker window_duration_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.half_open_max_probes.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker half_open_max_probes must not be empty or 0".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::AuthorizationValidityConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.default_validity_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "authorization validity default_validity_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(
+            self.connector_validity_in_secs
+                .values()
+                .chain([&self.default_validity_in_secs])
+                .any(|validity| *validity <= self.lead_time_in_secs),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "authorization validity must be greater than lead_time_in_secs".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::PaymentRateLimitConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        self.merchant.validate("merchant")?;
+        self.client_ip.validate("client_ip")
+    }
+}
+
+impl super::settings::AmountLimitsConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        self.currencies
+            .iter()
+            .try_for_each(|(currency, limit)| limit.validate(*currency))
+    }
+}
+
+impl super::settings::AmountLimit {
+    pub fn validate(&self, currency: api_models::enums::Currency) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        let is_negative = [
+            self.minimum_amount,
+            self.maximum_amount,
+            self.minimum_capture_amount,
+            self.minimum_refund_amount,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|amount| amount < 0);
+        when(is_negative, || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "amount limits of {currency} must not be negative"
+            )))
+        })?;
+
+        when(
+            matches!(
+                (self.minimum_amount, self.maximum_amount),
+                (Some(minimum_amount), Some(maximum_amount)) if minimum_amount > maximum_amount
+            ),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "minimum_amount of {currency} must not be greater than its maximum_amount"
+                )))
+            },
+        )
+    }
+}
+
+impl super::settings::ForexConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.cache_ttl_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "forex cache_ttl_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.max_rates_age_in_secs < self.cache_ttl_in_secs, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "forex max_rates_age_in_secs must not be less than cache_ttl_in_secs".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::MerchantMetadataConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        [
+            ("max_size_in_bytes", self.max_size_in_bytes),
+            ("max_keys", self.max_keys),
+            ("max_key_length", self.max_key_length),
+            ("max_depth", self.max_depth),
+        ]
+        .into_iter()
+        .try_for_each(|(name, limit)| {
+            when(limit == 0, || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "merchant_metadata {name} must not be 0"
+                )))
+            })
+        })
+    }
+}
+
+impl super::settings::CredentialRotationConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.grace_period_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "credential_rotation grace_period_in_secs must not be empty or 0".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::RedirectResponseConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        let Some(failure_url) = self.failure_url.as_ref() else {
+            return Ok(());
+        };
+
+        url::Url::parse(failure_url).map(|_| ()).map_err(|error| {
+            ApplicationError::InvalidConfigurationValueError(format!(
+                "redirect_response failure_url is not a valid URL: {error}"
+            ))
+        })
+    }
+}
+
+impl super::settings::DataRetentionConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        [
+            ("pii_retention_days", self.pii_retention_days),
+            ("archival_days", self.archival_days),
+            ("batch_size", self.batch_size),
+            ("max_batches_per_task", self.max_batches_per_task),
+            ("run_interval_in_hours", self.run_interval_in_hours),
+        ]
+        .into_iter()
+        .try_for_each(|(name, value)| {
+            when(value.is_default_or_empty(), || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "data retention {name} must not be empty or 0"
+                )))
+            })
+        })?;
+
+        // The attempts of a payment are redacted before the payment is archived
+        when(self.archival_days < self.pii_retention_days, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "data retention archival_days must not be less than pii_retention_days".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::IdempotencyConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.ttl_in_secs == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "idempotency ttl_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(
+            self.in_progress_ttl_in_secs == 0 || self.in_progress_ttl_in_secs > self.ttl_in_secs,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "idempotency in_progress_ttl_in_secs must be between 1 and ttl_in_secs".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::DeviceDataCollectionConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        self.0.iter().try_for_each(|(connector, collection)| {
+            when(
+                collection.test_org_id.is_default_or_empty()
+                    || collection.live_org_id.is_default_or_empty(),
+                || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "device data collection org ids of connector {connector} must not be empty"
+                    )))
+                },
+            )?;
+
+            url::Url::parse(&collection.script_url)
+                .map(|_| ())
+                .map_err(|error| {
+                    ApplicationError::InvalidConfigurationValueError(format!(
+                        "device data collection script_url of connector {connector} is not a valid URL: {error}"
+                    ))
+                })
+        })
+    }
+}
+
+impl super::settings::ConnectorProxyConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.connect_timeout_in_secs == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "connector proxies connect_timeout_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        self.allowed_proxy_urls.iter().try_for_each(|allowed_url| {
+            url::Url::parse(allowed_url).map(|_| ()).map_err(|error| {
+                ApplicationError::InvalidConfigurationValueError(format!(
+                    "allowed proxy url {allowed_url} is not a valid URL: {error}"
+                ))
+            })
+        })?;
+
+        self.connectors
+            .iter()
+            .try_for_each(|(connector, proxy)| proxy.validate(&format!("connector {connector}")))
+    }
+}
+
+impl super::settings::ConnectorProxy {
+    /// Validates the proxy, the owner of the proxy is named in the reasons of the rejection
+    pub fn validate(&self, owner: &str) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        let url = url::Url::parse(&self.url).map_err(|error| {
+            ApplicationError::InvalidConfigurationValueError(format!(
+                "proxy url of {owner} is not a valid URL: {error}"
+            ))
+        })?;
+        when(
+            !matches!(url.scheme(), "http" | "https") || url.host_str().is_none(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "proxy url of {owner} must be an HTTP or HTTPS URL with a host"
+                )))
+            },
+        )?;
+
+        when(self.password.is_some() && self.username.is_none(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "proxy password of {owner} is configured without a username"
+            )))
+        })
+    }
+}
+
+impl super::settings::RateLimit {
+    pub fn validate(&self, limit_name: &str) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.requests.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "payment rate limit {limit_name}.requests must not be empty or 0"
+            )))
+        })?;
+
+        when(self.window_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "payment rate limit {limit_name}.window_in_secs must not be empty or 0"
+            )))
+        })
+    }
+}
+
+impl super::settings::RequiredFields {
+    /// Fields of the payments request under which a required field may be declared.
+    const REQUEST_FIELD_ROOTS: [&'static str; 9] = [
+        "payment_method_data",
+        "billing",
+        "shipping",
+        "email",
+        "name",
+        "phone",
+        "phone_country_code",
+        "customer_id",
+        "device_session_id",
+    ];
+
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        let declared_fields = self
+            .0
+            .values()
+            .flat_map(|payment_method_types| payment_method_types.0.values())
+            .flat_map(|connector_fields| connector_fields.fields.values())
+            .flat_map(|required_fields| {
+                required_fields
+                    .mandate
+                    .iter()
+                    .chain(required_fields.non_mandate.iter())
+                    .chain(required_fields.common.iter())
+            });
+
+        for (key, field_info) in declared_fields {
+            when(key != &field_info.required_field, || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "required field `{key}` is declared with a different path `{}`",
+                    field_info.required_field
+                )))
+            })?;
+
+            let root = field_info
+                .required_field
+                .split('.')
+                .next()
+                .unwrap_or_default();
+            when(!Self::REQUEST_FIELD_ROOTS.contains(&root), || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "required field `{}` does not refer to a field of the payments request",
+                    field_info.required_field
+                )))
+            })?;
+        }
+
+        Ok(())
+    }
+}
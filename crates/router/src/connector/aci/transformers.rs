@@ -643,23 +643,28 @@ impl<F, T>
     fn try_from(
         item: types::ResponseRouterData<F, AciPaymentsResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
-        let redirection_data = item.response.redirect.map(|data| {
-            let form_fields = std::collections::HashMap::<_, _>::from_iter(
-                data.parameters
-                    .iter()
-                    .map(|parameter| (parameter.name.clone(), parameter.value.clone())),
-            );
-
-            // If method is Get, parameters are appended to URL
-            // If method is post, we http Post the method to URL
-            services::RedirectForm::Form {
-                endpoint: data.url.to_string(),
-                // Handles method for Bank redirects currently.
-                // 3DS response have method within preconditions. That would require replacing below line with a function.
-                method: data.method.unwrap_or(services::Method::Post),
-                form_fields,
-            }
-        });
+        let redirection_data = item
+            .response
+            .redirect
+            .map(|data| {
+                // If method is Get, parameters are appended to URL
+                // If method is post, we http Post the method to URL
+                services::RedirectFormBuilder::new(
+                    data.url.as_str(),
+                    // Handles method for Bank redirects currently.
+                    // 3DS response have method within preconditions. That would require replacing below line with a function.
+                    data.method.unwrap_or(services::Method::Post),
+                )
+                .map(|form| {
+                    form.fields(
+                        data.parameters
+                            .into_iter()
+                            .map(|parameter| (parameter.name, parameter.value)),
+                    )
+                    .build()
+                })
+            })
+            .transpose()?;
 
         let mandate_reference = item
             .response
@@ -686,6 +691,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -779,6 +788,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AciRefundResponse>>
                 refund_status: enums::RefundStatus::from(AciRefundStatus::from_str(
                     &item.response.result.code,
                 )?),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -18,7 +18,7 @@ use crate::{
         self,
         errors::{self, CustomResult},
     },
-    headers, logger, routes,
+    headers, routes,
     services::{
         self,
         request::{self, Mask},
@@ -36,6 +36,117 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Adyen;
 
+impl connector_utils::capabilities::ConnectorCapabilitiesDeclaration for Adyen {
+    const CAPABILITIES: connector_utils::capabilities::ConnectorCapabilities =
+        connector_utils::capabilities::ConnectorCapabilities {
+            payment_methods: &[
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Card,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Credit,
+                        enums::PaymentMethodType::Debit,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Wallet,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::ApplePay,
+                        enums::PaymentMethodType::GooglePay,
+                        enums::PaymentMethodType::Paypal,
+                        enums::PaymentMethodType::AliPay,
+                        enums::PaymentMethodType::AliPayHk,
+                        enums::PaymentMethodType::MbWay,
+                        enums::PaymentMethodType::MobilePay,
+                        enums::PaymentMethodType::WeChatPay,
+                        enums::PaymentMethodType::SamsungPay,
+                        enums::PaymentMethodType::Twint,
+                        enums::PaymentMethodType::Vipps,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::PayLater,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Klarna,
+                        enums::PaymentMethodType::Affirm,
+                        enums::PaymentMethodType::AfterpayClearpay,
+                        enums::PaymentMethodType::PayBright,
+                        enums::PaymentMethodType::Walley,
+                        enums::PaymentMethodType::Alma,
+                        enums::PaymentMethodType::Atome,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankRedirect,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ideal,
+                        enums::PaymentMethodType::Giropay,
+                        enums::PaymentMethodType::Sofort,
+                        enums::PaymentMethodType::Eps,
+                        enums::PaymentMethodType::Blik,
+                        enums::PaymentMethodType::Trustly,
+                        enums::PaymentMethodType::BancontactCard,
+                        enums::PaymentMethodType::OnlineBankingFinland,
+                        enums::PaymentMethodType::OnlineBankingPoland,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankDebit,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ach,
+                        enums::PaymentMethodType::Sepa,
+                        enums::PaymentMethodType::Bacs,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankTransfer,
+                    payment_method_types: &[enums::PaymentMethodType::Pix],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Voucher,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Boleto,
+                        enums::PaymentMethodType::Oxxo,
+                        enums::PaymentMethodType::SevenEleven,
+                        enums::PaymentMethodType::Lawson,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::GiftCard,
+                    payment_method_types: &[enums::PaymentMethodType::Givex],
+                },
+            ],
+            currencies: None,
+            countries: None,
+            capture_methods: &[
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+                enums::CaptureMethod::ManualMultiple,
+            ],
+            refunds_supported: true,
+            mandates_supported: true,
+            split_payments_supported: false,
+            wallet_support: connector_utils::capabilities::WalletSupport::Native,
+            banks: &[connector_utils::capabilities::SupportedBanks {
+                payment_method_type: enums::PaymentMethodType::Ideal,
+                bank_codes: &[
+                    (api_models::enums::BankNames::AbnAmro, "1121"),
+                    (api_models::enums::BankNames::AsnBank, "1151"),
+                    (api_models::enums::BankNames::Bunq, "1152"),
+                    (api_models::enums::BankNames::Handelsbanken, "1153"),
+                    (api_models::enums::BankNames::Ing, "1154"),
+                    (api_models::enums::BankNames::Knab, "1155"),
+                    (api_models::enums::BankNames::Moneyou, "1156"),
+                    (api_models::enums::BankNames::Rabobank, "1157"),
+                    (api_models::enums::BankNames::Regiobank, "1158"),
+                    (api_models::enums::BankNames::Revolut, "1159"),
+                    (api_models::enums::BankNames::SnsBank, "1159"),
+                    (api_models::enums::BankNames::TriodosBank, "1159"),
+                    (api_models::enums::BankNames::VanLanschot, "1159"),
+                ],
+            }],
+        };
+}
+
 impl ConnectorCommon for Adyen {
     fn id(&self) -> &'static str {
         "adyen"
@@ -497,7 +608,6 @@ impl
         data: &types::RouterData<api::PSync, types::PaymentsSyncData, types::PaymentsResponseData>,
         res: types::Response,
     ) -> CustomResult<types::PaymentsSyncRouterData, errors::ConnectorError> {
-        logger::debug!(payment_sync_response=?res);
         let response: adyen::AdyenPaymentResponse = res
             .response
             .parse_struct("AdyenPaymentResponse")
@@ -14,8 +14,8 @@ use crate::connector::utils::AddressDetailsData;
 use crate::types::api::payouts;
 use crate::{
     connector::utils::{
-        self, BrowserInformationData, CardData, MandateReferenceData, PaymentsAuthorizeRequestData,
-        RouterData,
+        self, capabilities::ConnectorCapabilitiesDeclaration, BrowserInformationData, CardData,
+        MandateReferenceData, PaymentsAuthorizeRequestData, RefundsRequestData, RouterData,
     },
     consts,
     core::errors,
@@ -28,7 +28,6 @@ use crate::{
         transformers::ForeignFrom,
         PaymentsAuthorizeData,
     },
-    utils as crate_utils,
 };
 
 type Error = error_stack::Report<errors::ConnectorError>;
@@ -103,6 +102,45 @@ pub struct AdditionalData {
     #[cfg(feature = "payouts")]
     payout_eligible: Option<PayoutEligibility>,
     funds_availability: Option<String>,
+    #[serde(flatten)]
+    sub_merchant: Option<AdyenSubMerchant>,
+    /// Authorization code of the issuer, only returned in responses
+    #[serde(skip_serializing)]
+    auth_code: Option<String>,
+}
+
+/// Details of the sub-merchant of a payment facilitator, passed as the only sub-seller of the
+/// payment, https://docs.adyen.com/marketplaces-and-platforms/payment-facilitators
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct AdyenSubMerchant {
+    #[serde(rename = "subMerchant.numberOfSubSellers")]
+    number_of_sub_sellers: String,
+    #[serde(rename = "subMerchant.subSeller1.id")]
+    id: String,
+    #[serde(rename = "subMerchant.subSeller1.name")]
+    name: String,
+    #[serde(rename = "subMerchant.subSeller1.mcc")]
+    mcc: String,
+    #[serde(rename = "subMerchant.subSeller1.country")]
+    country: Option<api_enums::CountryAlpha3>,
+    #[serde(rename = "subMerchant.subSeller1.postalCode")]
+    postal_code: Option<Secret<String>>,
+}
+
+impl From<&payments::SubMerchantData> for AdyenSubMerchant {
+    fn from(sub_merchant_data: &payments::SubMerchantData) -> Self {
+        Self {
+            number_of_sub_sellers: "1".to_string(),
+            id: sub_merchant_data.id.clone(),
+            name: sub_merchant_data.name.clone(),
+            mcc: sub_merchant_data.mcc.clone(),
+            country: sub_merchant_data
+                .country
+                .map(|country| api_enums::Country::from_alpha2(country).to_alpha3()),
+            postal_code: sub_merchant_data.postal_code.clone(),
+        }
+    }
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -151,6 +189,7 @@ pub struct AdyenPaymentRequest<'a> {
     store_payment_method: Option<bool>,
     shopper_name: Option<ShopperName>,
     shopper_locale: Option<String>,
+    shopper_statement: Option<String>,
     shopper_email: Option<Email>,
     social_security_number: Option<Secret<String>>,
     telephone_number: Option<Secret<String>>,
@@ -356,6 +395,8 @@ pub struct AdyenQrCodeAction {
     #[serde(rename = "url")]
     mobile_redirection_url: Option<Url>,
     qr_code_data: String,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    expires_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -916,6 +957,9 @@ pub struct AdyenMandate {
     #[serde(rename = "type")]
     payment_type: PaymentType,
     stored_payment_method_id: String,
+    // Collected from the customer when paying with the stored card
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cvc: Option<Secret<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1432,6 +1476,7 @@ impl From<&types::PaymentsAuthorizeRouterData> for AdyenShopperInteraction {
     fn from(item: &types::PaymentsAuthorizeRouterData) -> Self {
         match item.request.off_session {
             Some(true) => Self::ContinuedAuthentication,
+            _ if item.request.is_moto() => Self::Moto,
             _ => Self::Ecommerce,
         }
     }
@@ -1464,6 +1509,10 @@ fn get_recurring_processing_model(
 fn get_browser_info(
     item: &types::PaymentsAuthorizeRouterData,
 ) -> Result<Option<AdyenBrowserInfo>, Error> {
+    // The shopper is not present in mail order and telephone order payments, no browser is involved
+    if item.request.is_moto() {
+        return Ok(None);
+    }
     if item.auth_type == storage_enums::AuthenticationType::ThreeDs
         || item.payment_method == storage_enums::PaymentMethod::BankRedirect
         || item.request.payment_method_type == Some(storage_enums::PaymentMethodType::GoPay)
@@ -1485,6 +1534,10 @@ fn get_browser_info(
 }
 
 fn get_additional_data(item: &types::PaymentsAuthorizeRouterData) -> Option<AdditionalData> {
+    let sub_merchant = item
+        .request
+        .get_sub_merchant_data()
+        .map(AdyenSubMerchant::from);
     match item.request.capture_method {
         Some(diesel_models::enums::CaptureMethod::Manual)
         | Some(diesel_models::enums::CaptureMethod::ManualMultiple) => Some(AdditionalData {
@@ -1494,9 +1547,13 @@ fn get_additional_data(item: &types::PaymentsAuthorizeRouterData) -> Option<Addi
             recurring_detail_reference: None,
             recurring_shopper_reference: None,
             recurring_processing_model: Some(AdyenRecurringModel::UnscheduledCardOnFile),
+            sub_merchant,
+            ..AdditionalData::default()
+        }),
+        _ => sub_merchant.map(|sub_merchant| AdditionalData {
+            sub_merchant: Some(sub_merchant),
             ..AdditionalData::default()
         }),
-        _ => None,
     }
 }
 
@@ -2055,9 +2112,11 @@ impl<'a> TryFrom<&api_models::payments::BankRedirectData> for AdyenPaymentMethod
                 AdyenPaymentMethod::Ideal(Box::new(BankRedirectionWithIssuer {
                     payment_type: PaymentType::Ideal,
                     issuer: bank_name
-                        .map(|bank_name| AdyenTestBankNames::try_from(&bank_name))
-                        .transpose()?
-                        .map(|adyen_bank_name| adyen_bank_name.0),
+                        .map(|bank_name| {
+                            <super::Adyen as ConnectorCapabilitiesDeclaration>::CAPABILITIES
+                                .get_bank_code(storage_enums::PaymentMethodType::Ideal, bank_name)
+                        })
+                        .transpose()?,
                 })),
             ),
             api_models::payments::BankRedirectData::OnlineBankingCzechRepublic { issuer } => {
@@ -2180,6 +2239,7 @@ impl<'a> TryFrom<&api_models::payments::BankTransferData> for AdyenPaymentMethod
             | api_models::payments::BankTransferData::SepaBankTransfer { .. }
             | api_models::payments::BankTransferData::BacsBankTransfer { .. }
             | api_models::payments::BankTransferData::MultibancoBankTransfer { .. }
+            | api_models::payments::BankTransferData::VirtualAccountBankTransfer { .. }
             | payments::BankTransferData::Pse {} => Err(errors::ConnectorError::NotImplemented(
                 utils::get_unimplemented_payment_method_error_message("Adyen"),
             )
@@ -2231,9 +2291,23 @@ impl<'a>
             .ok_or(errors::ConnectorError::MissingPaymentMethodType)?;
         let payment_method = match mandate_ref_id {
             payments::MandateReferenceId::ConnectorMandateId(connector_mandate_ids) => {
+                // The code is only passed when the customer is present to enter it
+                let saved_card_flow = match shopper_interaction {
+                    AdyenShopperInteraction::ContinuedAuthentication => {
+                        utils::SavedCardFlow::Mandate
+                    }
+                    _ => utils::SavedCardFlow::CustomerInitiated,
+                };
                 let adyen_mandate = AdyenMandate {
                     payment_type: PaymentType::try_from(payment_method_type)?,
                     stored_payment_method_id: connector_mandate_ids.get_connector_mandate_id()?,
+                    cvc: item.router_data.request.card_cvc.clone().filter(|_| {
+                        utils::get_saved_card_cvc_support(
+                            api_models::enums::Connector::Adyen,
+                            *payment_method_type,
+                            saved_card_flow,
+                        ) != utils::SavedCardCvcSupport::NotSupported
+                    }),
                 };
                 Ok::<AdyenPaymentMethod<'_>, Self::Error>(AdyenPaymentMethod::Mandate(Box::new(
                     adyen_mandate,
@@ -2288,7 +2362,8 @@ impl<'a>
             telephone_number: None,
             shopper_name: None,
             shopper_email: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             billing_address: None,
             delivery_address: None,
@@ -2336,7 +2411,8 @@ impl<'a>
             telephone_number: None,
             shopper_name: None,
             shopper_email: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             billing_address: None,
             delivery_address: None,
@@ -2384,8 +2460,9 @@ impl<'a>
             recurring_processing_model,
             additional_data,
             shopper_name: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
             shopper_email: item.router_data.request.email.clone(),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             telephone_number: None,
             billing_address: None,
@@ -2435,8 +2512,9 @@ impl<'a>
             recurring_processing_model,
             additional_data,
             shopper_name: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
             shopper_email: item.router_data.request.email.clone(),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number,
             telephone_number: None,
             billing_address: None,
@@ -2482,8 +2560,9 @@ impl<'a>
             recurring_processing_model: None,
             additional_data: None,
             shopper_name: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
             shopper_email: item.router_data.request.email.clone(),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             telephone_number: None,
             billing_address: None,
@@ -2529,7 +2608,7 @@ impl<'a>
             recurring_processing_model: None,
             additional_data: None,
             shopper_name: None,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
             shopper_email: item.router_data.request.email.clone(),
             telephone_number: None,
             billing_address: None,
@@ -2539,6 +2618,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
         };
         Ok(request)
@@ -2584,7 +2664,9 @@ impl<'a>
             telephone_number: None,
             shopper_name: None,
             shopper_email: item.router_data.request.email.clone(),
-            shopper_locale,
+            shopper_locale: shopper_locale
+                .or_else(|| Some(item.router_data.get_locale_or_default())),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             billing_address: None,
             delivery_address: None,
@@ -2678,7 +2760,8 @@ impl<'a>
             telephone_number: None,
             shopper_name: None,
             shopper_email,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             billing_address: None,
             delivery_address: None,
@@ -2735,7 +2818,8 @@ impl<'a>
             telephone_number,
             shopper_name,
             shopper_email,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
             billing_address,
             delivery_address,
@@ -2790,7 +2874,7 @@ impl<'a>
             telephone_number,
             shopper_name,
             shopper_email,
-            shopper_locale: None,
+            shopper_locale: Some(item.router_data.get_locale_or_default()),
             billing_address: None,
             delivery_address: None,
             country_code: None,
@@ -2798,6 +2882,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            shopper_statement: item.router_data.request.merchant_descriptor.clone(),
             social_security_number: None,
         })
     }
@@ -2839,6 +2924,10 @@ impl TryFrom<types::PaymentsCancelResponseRouterData<AdyenCancelResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -2860,6 +2949,10 @@ impl TryFrom<types::PaymentsBalanceResponseRouterData<AdyenBalanceResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             payment_method_balance: Some(types::PaymentMethodBalance {
                 amount: item.response.balance.value,
@@ -2908,9 +3001,15 @@ pub fn get_adyen_response(
             connector_mandate_id: Some(mandate_id),
             payment_method_id: None,
         });
-    let network_txn_id = response
+    let (network_txn_id, auth_code) = response
         .additional_data
-        .and_then(|additional_data| additional_data.network_tx_reference);
+        .map(|additional_data| {
+            (
+                additional_data.network_tx_reference,
+                additional_data.auth_code,
+            )
+        })
+        .unwrap_or_default();
 
     let payments_response_data = types::PaymentsResponseData::TransactionResponse {
         resource_id: types::ResponseId::ConnectorTransactionId(response.psp_reference),
@@ -2919,6 +3018,10 @@ pub fn get_adyen_response(
         connector_metadata: None,
         network_txn_id,
         connector_response_reference_id: Some(response.merchant_reference),
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -3016,6 +3119,10 @@ pub fn get_redirection_response(
         connector_metadata,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -3065,6 +3172,10 @@ pub fn get_present_to_shopper_response(
         connector_metadata,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -3111,6 +3222,10 @@ pub fn get_qr_code_response(
         connector_metadata,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -3143,6 +3258,10 @@ pub fn get_redirection_error_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
 
     Ok((status, error, payments_response_data))
@@ -3151,29 +3270,15 @@ pub fn get_redirection_error_response(
 pub fn get_qr_metadata(
     response: &QrCodeResponseResponse,
 ) -> errors::CustomResult<Option<serde_json::Value>, errors::ConnectorError> {
-    let image_data = crate_utils::QrImage::new_from_data(response.action.qr_code_data.to_owned())
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
-
-    let image_data_url = Url::parse(image_data.data.as_str())
-        .ok()
-        .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
-
-    let qr_code_instructions = payments::QrCodeNextStepsInstruction {
-        image_data_url,
-        display_to_timestamp: None,
-    };
-
-    Some(common_utils::ext_traits::Encode::<
-        payments::QrCodeNextStepsInstruction,
-    >::encode_to_value(&qr_code_instructions))
-    .transpose()
-    .change_context(errors::ConnectorError::ResponseHandlingFailed)
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WaitScreenData {
-    display_from_timestamp: i128,
-    display_to_timestamp: Option<i128>,
+    let display_to_timestamp = response
+        .action
+        .expires_at
+        .map(|expires_at| expires_at.assume_utc().unix_timestamp());
+
+    utils::get_qr_code_next_action_metadata(
+        response.action.qr_code_data.to_owned(),
+        display_to_timestamp,
+    )
 }
 
 pub fn get_wait_screen_metadata(
@@ -3182,17 +3287,25 @@ pub fn get_wait_screen_metadata(
     match next_action.action.payment_method_type {
         PaymentType::Blik => {
             let current_time = OffsetDateTime::now_utc().unix_timestamp_nanos();
-            Ok(Some(serde_json::json!(WaitScreenData {
-                display_from_timestamp: current_time,
-                display_to_timestamp: Some(current_time + Duration::minutes(1).whole_nanoseconds())
-            })))
+            utils::get_next_action_metadata(
+                payments::ConnectorNextActionData::WaitScreenInformation {
+                    display_from_timestamp: current_time,
+                    display_to_timestamp: Some(
+                        current_time + Duration::minutes(1).whole_nanoseconds(),
+                    ),
+                    poll_interval_in_secs: Some(consts::DEFAULT_WAIT_SCREEN_POLL_INTERVAL_IN_SECS),
+                },
+            )
         }
         PaymentType::Mbway => {
             let current_time = OffsetDateTime::now_utc().unix_timestamp_nanos();
-            Ok(Some(serde_json::json!(WaitScreenData {
-                display_from_timestamp: current_time,
-                display_to_timestamp: None
-            })))
+            utils::get_next_action_metadata(
+                payments::ConnectorNextActionData::WaitScreenInformation {
+                    display_from_timestamp: current_time,
+                    display_to_timestamp: None,
+                    poll_interval_in_secs: Some(consts::DEFAULT_WAIT_SCREEN_POLL_INTERVAL_IN_SECS),
+                },
+            )
         }
         PaymentType::Affirm
         | PaymentType::Oxxo
@@ -3477,6 +3590,10 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<AdyenCaptureResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             amount_captured: Some(item.response.amount.value),
             ..item.data
@@ -3528,7 +3645,7 @@ impl<F> TryFrom<&AdyenRouterData<&types::RefundsRouterData<F>>> for AdyenRefundR
                 currency: item.router_data.request.currency.to_string(),
                 value: item.router_data.request.refund_amount,
             },
-            merchant_refund_reason: item.router_data.request.reason.clone(),
+            merchant_refund_reason: item.router_data.request.get_refund_reason(),
             reference: item.router_data.request.refund_id.clone(),
         })
     }
@@ -3549,6 +3666,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AdyenRefundResponse>>
                 // through refund notification webhook
                 // For more info: https://docs.adyen.com/online-payments/refund
                 refund_status: storage_enums::RefundStatus::Pending,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -3565,6 +3683,184 @@ pub struct ErrorResponse {
     pub psp_reference: Option<String>,
 }
 
+#[cfg(test)]
+mod test_next_action {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn should_emit_wait_screen_next_action_for_blik() {
+        let response: RedirectionResponse = serde_json::from_value(serde_json::json!({
+            "resultCode": "Pending",
+            "action": {
+                "paymentMethodType": "blik",
+                "type": "await"
+            }
+        }))
+        .unwrap();
+
+        let (_, error, payments_response_data) =
+            get_redirection_response(response, false, 200).unwrap();
+
+        assert!(error.is_none());
+        match payments_response_data {
+            types::PaymentsResponseData::TransactionResponse {
+                redirection_data,
+                connector_metadata,
+                ..
+            } => {
+                assert!(redirection_data.is_none());
+                let next_action: payments::ConnectorNextActionData =
+                    serde_json::from_value(connector_metadata.unwrap()).unwrap();
+                match next_action {
+                    payments::ConnectorNextActionData::WaitScreenInformation {
+                        display_from_timestamp,
+                        display_to_timestamp,
+                        poll_interval_in_secs,
+                    } => {
+                        assert_eq!(
+                            display_to_timestamp,
+                            Some(display_from_timestamp + Duration::minutes(1).whole_nanoseconds())
+                        );
+                        assert_eq!(
+                            poll_interval_in_secs,
+                            Some(consts::DEFAULT_WAIT_SCREEN_POLL_INTERVAL_IN_SECS)
+                        );
+                    }
+                    _ => panic!("expected wait screen information"),
+                }
+            }
+            _ => panic!("expected transaction response"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_sub_merchant_data {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn should_pass_sub_merchant_data_as_sub_seller() {
+        let sub_merchant_data = payments::SubMerchantData {
+            id: "sub_merchant_123".to_string(),
+            name: "Sub Shop".to_string(),
+            mcc: "5411".to_string(),
+            country: Some(api_enums::CountryAlpha2::US),
+            postal_code: Some(Secret::new("94122".to_string())),
+        };
+        let additional_data = AdditionalData {
+            sub_merchant: Some(AdyenSubMerchant::from(&sub_merchant_data)),
+            ..AdditionalData::default()
+        };
+
+        let payload = serde_json::to_value(additional_data).unwrap();
+        assert_eq!(payload["subMerchant.numberOfSubSellers"], "1");
+        assert_eq!(payload["subMerchant.subSeller1.id"], "sub_merchant_123");
+        assert_eq!(payload["subMerchant.subSeller1.name"], "Sub Shop");
+        assert_eq!(payload["subMerchant.subSeller1.mcc"], "5411");
+        assert_eq!(payload["subMerchant.subSeller1.country"], "USA");
+        assert_eq!(payload["subMerchant.subSeller1.postalCode"], "94122");
+    }
+
+    #[test]
+    fn should_not_pass_sub_seller_without_sub_merchant_data() {
+        let payload = serde_json::to_value(AdditionalData::default()).unwrap();
+        assert!(payload.get("subMerchant.numberOfSubSellers").is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_stored_card_cvc {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn should_pass_collected_cvc_with_stored_payment_method() {
+        let mandate = AdyenMandate {
+            payment_type: PaymentType::Scheme,
+            stored_payment_method_id: "8315974620351234".to_string(),
+            cvc: Some(Secret::new("737".to_string())),
+        };
+        let payment_method = AdyenPaymentMethod::Mandate(Box::new(mandate));
+
+        let payload = serde_json::to_value(&payment_method).unwrap();
+        assert_eq!(payload["storedPaymentMethodId"], "8315974620351234");
+        assert_eq!(payload["cvc"], "737");
+        assert!(!format!("{payment_method:?}").contains("737"));
+    }
+
+    #[test]
+    fn should_not_pass_cvc_when_not_collected() {
+        let mandate = AdyenMandate {
+            payment_type: PaymentType::Scheme,
+            stored_payment_method_id: "8315974620351234".to_string(),
+            cvc: None,
+        };
+
+        let payload = serde_json::to_value(mandate).unwrap();
+        assert!(payload.get("cvc").is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_moto {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::types::test_utils::get_authorize_router_data;
+
+    fn get_adyen_router_data(
+        payment_channel: Option<api_enums::PaymentChannel>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.connector = "adyen".to_string();
+        router_data.connector_auth_type = types::ConnectorAuthType::BodyKey {
+            api_key: Secret::new("api_key".to_string()),
+            key1: Secret::new("merchant_account".to_string()),
+        };
+        router_data.request.router_return_url = Some("https://example.com/return".to_string());
+        router_data.request.browser_info = None;
+        router_data.request.payment_channel = payment_channel;
+        router_data
+    }
+
+    fn get_payment_request(
+        router_data: &types::PaymentsAuthorizeRouterData,
+    ) -> Result<serde_json::Value, Error> {
+        let adyen_router_data = AdyenRouterData::try_from((
+            &types::api::CurrencyUnit::Minor,
+            router_data.request.currency,
+            router_data.request.amount,
+            router_data,
+        ))?;
+        let card = router_data.request.get_card()?;
+        let request = AdyenPaymentRequest::try_from((&adyen_router_data, &card))?;
+        Ok(serde_json::to_value(request).unwrap())
+    }
+
+    #[test]
+    fn should_pass_moto_indicator_without_browser_info() {
+        let mut router_data =
+            get_adyen_router_data(Some(api_enums::PaymentChannel::TelephoneOrder));
+        // 3DS is skipped for MOTO payments before the connector is called
+        router_data.auth_type = storage_enums::AuthenticationType::NoThreeDs;
+
+        let payload = get_payment_request(&router_data).unwrap();
+
+        assert_eq!(payload["shopperInteraction"], "Moto");
+        assert!(payload.get("browserInfo").is_none());
+    }
+
+    #[test]
+    fn should_require_browser_info_for_ecommerce_three_ds_payment() {
+        let router_data = get_adyen_router_data(Some(api_enums::PaymentChannel::Ecommerce));
+        assert!(get_payment_request(&router_data).is_err());
+
+        let router_data = get_adyen_router_data(None);
+        assert!(get_payment_request(&router_data).is_err());
+    }
+}
+
 // #[cfg(test)]
 // mod test_adyen_transformers {
 //     use super::*;
@@ -16,7 +16,7 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
-    headers, logger, routes,
+    headers, routes,
     services::{
         self,
         request::{self, Mask},
@@ -78,7 +78,6 @@ impl ConnectorCommon for Airwallex {
         &self,
         res: Response,
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
-        logger::debug!(payu_error_response=?res);
         let response: airwallex::AirwallexErrorResponse = res
             .response
             .parse_struct("Airwallex ErrorResponse")
@@ -178,7 +177,6 @@ impl ConnectorIntegration<api::AccessTokenAuth, types::AccessTokenRequestData, t
                 .body(types::RefreshTokenType::get_request_body(self, req)?)
                 .build(),
         );
-        logger::debug!(payu_access_token_request=?req);
         Ok(req)
     }
     fn handle_response(
@@ -186,7 +184,6 @@ impl ConnectorIntegration<api::AccessTokenAuth, types::AccessTokenRequestData, t
         data: &types::RefreshTokenRouterData,
         res: Response,
     ) -> CustomResult<types::RefreshTokenRouterData, errors::ConnectorError> {
-        logger::debug!(access_token_response=?res);
         let response: airwallex::AirwallexAuthUpdateResponse = res
             .response
             .parse_struct("airwallex AirwallexAuthUpdateResponse")
@@ -205,7 +202,6 @@ impl ConnectorIntegration<api::AccessTokenAuth, types::AccessTokenRequestData, t
         &self,
         res: Response,
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
-        logger::debug!(access_token_error_response=?res);
         self.build_error_response(res)
     }
 }
@@ -279,7 +275,6 @@ impl
             .response
             .parse_struct("airwallex AirwallexPaymentsResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::debug!(nuvei_session_response=?response);
         types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -403,7 +398,6 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
             .response
             .parse_struct("AirwallexPaymentsResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::debug!(airwallexpayments_create_response=?response);
         types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -475,7 +469,6 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         data: &types::PaymentsSyncRouterData,
         res: Response,
     ) -> CustomResult<types::PaymentsSyncRouterData, errors::ConnectorError> {
-        logger::debug!(payment_sync_response=?res);
         let response: airwallex::AirwallexPaymentsSyncResponse = res
             .response
             .parse_struct("airwallex AirwallexPaymentsSyncResponse")
@@ -659,7 +652,6 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
             .response
             .parse_struct("Airwallex PaymentsResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::debug!(airwallexpayments_create_response=?response);
         types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -737,7 +729,6 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
             .response
             .parse_struct("Airwallex PaymentsResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::debug!(airwallexpayments_create_response=?response);
         types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -837,7 +828,6 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
         data: &types::RefundsRouterData<api::Execute>,
         res: Response,
     ) -> CustomResult<types::RefundsRouterData<api::Execute>, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::airwallex", response=?res);
         let response: airwallex::RefundResponse = res
             .response
             .parse_struct("airwallex RefundResponse")
@@ -908,7 +898,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
         data: &types::RefundSyncRouterData,
         res: Response,
     ) -> CustomResult<types::RefundSyncRouterData, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::airwallex", response=?res);
         let response: airwallex::RefundResponse = res
             .response
             .parse_struct("airwallex RefundResponse")
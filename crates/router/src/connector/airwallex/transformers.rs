@@ -481,6 +481,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -522,6 +526,10 @@ impl
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -597,6 +605,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: item.response.acquirer_reference_number,
             }),
             ..item.data
         })
@@ -615,6 +624,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: item.response.acquirer_reference_number,
             }),
             ..item.data
         })
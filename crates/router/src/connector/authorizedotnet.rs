@@ -27,6 +27,53 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Authorizedotnet;
 
+impl connector_utils::capabilities::ConnectorCapabilitiesDeclaration for Authorizedotnet {
+    const CAPABILITIES: connector_utils::capabilities::ConnectorCapabilities =
+        connector_utils::capabilities::ConnectorCapabilities {
+            payment_methods: &[
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Card,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Credit,
+                        enums::PaymentMethodType::Debit,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Wallet,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::ApplePay,
+                        enums::PaymentMethodType::GooglePay,
+                        enums::PaymentMethodType::Paypal,
+                    ],
+                },
+            ],
+            currencies: Some(&[
+                enums::Currency::USD,
+                enums::Currency::CAD,
+                enums::Currency::GBP,
+                enums::Currency::EUR,
+                enums::Currency::AUD,
+                enums::Currency::NZD,
+            ]),
+            countries: Some(&[
+                enums::CountryAlpha2::US,
+                enums::CountryAlpha2::CA,
+                enums::CountryAlpha2::GB,
+                enums::CountryAlpha2::AU,
+                enums::CountryAlpha2::NZ,
+            ]),
+            capture_methods: &[
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+            ],
+            refunds_supported: true,
+            mandates_supported: false,
+            split_payments_supported: false,
+            wallet_support: connector_utils::capabilities::WalletSupport::Native,
+            banks: &[],
+        };
+}
+
 impl<Flow, Request, Response> ConnectorCommonExt<Flow, Request, Response> for Authorizedotnet
 where
     Self: ConnectorIntegration<Flow, Request, Response>,
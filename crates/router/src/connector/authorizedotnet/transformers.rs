@@ -7,7 +7,10 @@ use masking::{PeekInterface, Secret, StrongSecret};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{self, CardData, PaymentsSyncRequestData, RefundsRequestData, WalletData},
+    connector::utils::{
+        self, CardData, CardVerificationResultCodes, PaymentsSyncRequestData, RefundsRequestData,
+        WalletData,
+    },
     core::errors,
     services,
     types::{self, api, storage::enums},
@@ -413,6 +416,33 @@ pub struct TransactionResponse {
     pub(super) account_number: Option<String>,
     pub(super) errors: Option<Vec<ErrorMessage>>,
     secure_acceptance: Option<SecureAcceptance>,
+    avs_result_code: Option<String>,
+    cvv_result_code: Option<String>,
+}
+
+impl CardVerificationResultCodes for TransactionResponse {
+    const AVS_RESULT_CODES: &'static [(&'static str, api::enums::AvsResult)] = &[
+        ("A", api::enums::AvsResult::PartialAddress),
+        ("B", api::enums::AvsResult::Unavailable),
+        ("E", api::enums::AvsResult::Unavailable),
+        ("G", api::enums::AvsResult::Unavailable),
+        ("N", api::enums::AvsResult::NoMatch),
+        ("P", api::enums::AvsResult::Unavailable),
+        ("R", api::enums::AvsResult::Unavailable),
+        ("S", api::enums::AvsResult::Unavailable),
+        ("U", api::enums::AvsResult::Unavailable),
+        ("W", api::enums::AvsResult::PartialZip),
+        ("X", api::enums::AvsResult::FullMatch),
+        ("Y", api::enums::AvsResult::FullMatch),
+        ("Z", api::enums::AvsResult::PartialZip),
+    ];
+    const CVC_RESULT_CODES: &'static [(&'static str, api::enums::CvcResult)] = &[
+        ("M", api::enums::CvcResult::Match),
+        ("N", api::enums::CvcResult::NoMatch),
+        ("P", api::enums::CvcResult::NotProcessed),
+        ("S", api::enums::CvcResult::NotPresent),
+        ("U", api::enums::CvcResult::NotProcessed),
+    ];
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -541,6 +571,14 @@ impl<F, T>
                             connector_metadata: metadata,
                             network_txn_id: transaction_response.network_trans_id.clone(),
                             connector_response_reference_id: None,
+                            card_verification_results:
+                                TransactionResponse::get_card_verification_results(
+                                    transaction_response.avs_result_code.clone(),
+                                    transaction_response.cvv_result_code.clone(),
+                                ),
+                            connector_fee: None,
+                            auth_code: None,
+                            acquirer_reference_number: None,
                         }),
                     },
                     ..item.data
@@ -605,6 +643,10 @@ impl<F, T>
                             connector_metadata: metadata,
                             network_txn_id: transaction_response.network_trans_id.clone(),
                             connector_response_reference_id: None,
+                            card_verification_results: None,
+                            connector_fee: None,
+                            auth_code: None,
+                            acquirer_reference_number: None,
                         }),
                     },
                     ..item.data
@@ -726,6 +768,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AuthorizedotnetRefundRespons
                 None => Ok(types::RefundsResponseData {
                     connector_refund_id: transaction_response.transaction_id.clone(),
                     refund_status,
+                    acquirer_reference_number: None,
                 }),
             },
             ..item.data
@@ -854,6 +897,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, AuthorizedotnetSyncRes
                     response: Ok(types::RefundsResponseData {
                         connector_refund_id: transaction.transaction_id,
                         refund_status,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -894,6 +938,10 @@ impl<F, Req>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     status: payment_status,
                     ..item.data
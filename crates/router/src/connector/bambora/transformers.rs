@@ -5,7 +5,7 @@ use masking::{PeekInterface, Secret};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
-    connector::utils::{BrowserInformationData, PaymentsAuthorizeRequestData},
+    connector::utils::{self, BrowserInformationData, PaymentsAuthorizeRequestData},
     consts,
     core::errors,
     services,
@@ -124,7 +124,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for BamboraPaymentsRequest {
                     three_d_secure: three_ds,
                     complete: item.request.is_auto_capture()?,
                 };
-                let browser_info = item.request.get_browser_info()?;
+                let browser_info = item.request.get_browser_info_or_default()?;
                 Ok(Self {
                     amount: item.request.amount,
                     payment_method: PaymentMethod::Card,
@@ -191,18 +191,22 @@ impl<F, T>
         let item = data.0;
         match item.response {
             BamboraResponse::NormalTransaction(pg_response) => Ok(Self {
-                status: match pg_response.approved.as_str() {
-                    "0" => match flow {
-                        PaymentFlow::Authorize => enums::AttemptStatus::AuthorizationFailed,
-                        PaymentFlow::Capture => enums::AttemptStatus::Failure,
+                status: {
+                    let is_approved = match pg_response.approved.as_str() {
+                        "0" => false,
+                        "1" => true,
+                        &_ => Err(errors::ConnectorError::ResponseDeserializationFailed)?,
+                    };
+                    match flow {
+                        PaymentFlow::Authorize | PaymentFlow::Capture => {
+                            utils::map_capture_aware_status(
+                                is_approved,
+                                matches!(flow, PaymentFlow::Capture),
+                            )
+                        }
+                        PaymentFlow::Void if is_approved => enums::AttemptStatus::Voided,
                         PaymentFlow::Void => enums::AttemptStatus::VoidFailed,
-                    },
-                    "1" => match flow {
-                        PaymentFlow::Authorize => enums::AttemptStatus::Authorized,
-                        PaymentFlow::Capture => enums::AttemptStatus::Charged,
-                        PaymentFlow::Void => enums::AttemptStatus::Voided,
-                    },
-                    &_ => Err(errors::ConnectorError::ResponseDeserializationFailed)?,
+                    }
                 },
                 response: Ok(types::PaymentsResponseData::TransactionResponse {
                     resource_id: types::ResponseId::ConnectorTransactionId(
@@ -213,6 +217,12 @@ impl<F, T>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    // Bambora returns an empty authorization code for declined transactions
+                    auth_code: Some(pg_response.auth_code.clone())
+                        .filter(|auth_code| !auth_code.is_empty()),
+                    acquirer_reference_number: None,
                 }),
                 ..item.data
             }),
@@ -237,6 +247,10 @@ impl<F, T>
                         ),
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -504,6 +518,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -526,6 +541,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -139,6 +139,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -201,6 +205,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -218,6 +223,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
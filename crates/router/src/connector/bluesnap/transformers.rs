@@ -191,6 +191,15 @@ pub struct BluesnapConnectorMetaData {
     pub merchant_id: String,
 }
 
+impl utils::metadata_schema::ConnectorMetadataSchema for BluesnapConnectorMetaData {
+    const FIELDS: &'static [utils::metadata_schema::MetadataField] =
+        &[utils::metadata_schema::MetadataField {
+            name: "merchant_id",
+            field_type: api_models::admin::ConnectorMetadataFieldType::String,
+            required: true,
+        }];
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BluesnapPaymentsTokenRequest {
@@ -821,6 +830,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: Some(item.response.transaction_id),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -872,6 +885,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, BluesnapPaymentsRespon
                 refund_status: enums::RefundStatus::from(
                     item.response.processing_info.processing_status,
                 ),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -889,6 +903,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refund_transaction_id.to_string(),
                 refund_status: enums::RefundStatus::from(item.response.refund_status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -252,6 +252,10 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, BokuResponse, T, types::Payments
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -358,6 +362,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.charge_id,
                 refund_status: get_refund_status(item.response.refund_status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -419,6 +424,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, BokuRsyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refunds.refund.refund_id,
                 refund_status: get_refund_status(item.response.refunds.refund.refund_status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
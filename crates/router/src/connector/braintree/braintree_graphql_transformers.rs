@@ -236,6 +236,10 @@ impl<F>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -254,6 +258,10 @@ impl<F>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
                 ..item.data
             }),
@@ -415,6 +423,10 @@ impl<F>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -433,6 +445,10 @@ impl<F>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
                 ..item.data
             }),
@@ -476,6 +492,10 @@ impl<F>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -520,6 +540,10 @@ impl<F>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -678,6 +702,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, BraintreeRefundRespo
                     Ok(types::RefundsResponseData {
                         connector_refund_id: refund_data.id.clone(),
                         refund_status: enums::RefundStatus::from(refund_data.status),
+                        acquirer_reference_number: None,
                     })
                 }
             },
@@ -766,6 +791,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, BraintreeRSyncResponse
                 let response = Ok(types::RefundsResponseData {
                     connector_refund_id: connector_refund_id.to_string(),
                     refund_status: enums::RefundStatus::from(edge_data.node.status.clone()),
+                    acquirer_reference_number: None,
                 });
                 Ok(Self {
                     response,
@@ -1039,6 +1065,10 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<BraintreeCaptureResponse>>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -1136,6 +1166,10 @@ impl<F, T>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -1233,6 +1267,10 @@ impl<F, T>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..item.data
                 })
@@ -24,6 +24,21 @@ pub struct BraintreeMeta {
     merchant_config_currency: Option<types::storage::enums::Currency>,
 }
 
+impl utils::metadata_schema::ConnectorMetadataSchema for BraintreeMeta {
+    const FIELDS: &'static [utils::metadata_schema::MetadataField] = &[
+        utils::metadata_schema::MetadataField {
+            name: "merchant_account_id",
+            field_type: api_models::admin::ConnectorMetadataFieldType::String,
+            required: false,
+        },
+        utils::metadata_schema::MetadataField {
+            name: "merchant_config_currency",
+            field_type: api_models::admin::ConnectorMetadataFieldType::String,
+            required: false,
+        },
+    ];
+}
+
 #[derive(Debug, Serialize, Eq, PartialEq)]
 pub struct BraintreePaymentsRequest {
     transaction: TransactionBody,
@@ -239,6 +254,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -413,6 +432,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -430,6 +450,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -236,6 +236,10 @@ impl<F, T>
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                 )
             }
@@ -279,6 +283,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             amount_captured: Some(item.response.amount),
             ..item.data
@@ -35,6 +35,40 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Checkout;
 
+impl connector_utils::capabilities::ConnectorCapabilitiesDeclaration for Checkout {
+    const CAPABILITIES: connector_utils::capabilities::ConnectorCapabilities =
+        connector_utils::capabilities::ConnectorCapabilities {
+            payment_methods: &[
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Card,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Credit,
+                        enums::PaymentMethodType::Debit,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Wallet,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::ApplePay,
+                        enums::PaymentMethodType::GooglePay,
+                    ],
+                },
+            ],
+            currencies: None,
+            countries: None,
+            capture_methods: &[
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+                enums::CaptureMethod::ManualMultiple,
+            ],
+            refunds_supported: true,
+            mandates_supported: false,
+            split_payments_supported: false,
+            wallet_support: connector_utils::capabilities::WalletSupport::Native,
+            banks: &[],
+        };
+}
+
 impl<Flow, Request, Response> ConnectorCommonExt<Flow, Request, Response> for Checkout
 where
     Self: ConnectorIntegration<Flow, Request, Response>,
@@ -109,7 +143,6 @@ impl ConnectorCommon for Checkout {
                 .change_context(errors::ConnectorError::ResponseDeserializationFailed)?
         };
 
-        router_env::logger::info!(error_response=?response);
         let errors_list = response.error_codes.clone().unwrap_or(vec![]);
         let option_error_code_message = conn_utils::get_error_code_error_message_based_on_priority(
             self.clone(),
@@ -150,6 +183,10 @@ impl ConnectorValidation for Checkout {
             ),
         }
     }
+
+    fn get_connector_transaction_id_pattern(&self) -> Option<&'static str> {
+        Some(r"^pay_[a-z0-9]+$")
+    }
 }
 
 impl api::Payment for Checkout {}
@@ -245,7 +282,6 @@ impl
             .response
             .parse_struct("CheckoutTokenResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
 
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -351,7 +387,6 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
             .response
             .parse_struct("CaptureResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
 
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -433,7 +468,6 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
                     .response
                     .parse_struct("checkout::PaymentsResponseEnum")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-                router_env::logger::info!(connector_response=?response);
                 types::RouterData::try_from(types::ResponseRouterData {
                     response,
                     data: data.clone(),
@@ -446,7 +480,6 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
                     .response
                     .parse_struct("PaymentsResponse")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-                router_env::logger::info!(connector_response=?response);
                 types::RouterData::try_from(types::ResponseRouterData {
                     response,
                     data: data.clone(),
@@ -541,7 +574,6 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
             .response
             .parse_struct("PaymentIntentResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -618,7 +650,6 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
             .response
             .parse_struct("PaymentVoidResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
         response.status = res.status_code;
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -713,7 +744,6 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
             .response
             .parse_struct("checkout::RefundResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
         let response = checkout::CheckoutRefundResponse {
             response,
             status: res.status_code,
@@ -784,7 +814,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
             .response
             .parse_struct("checkout::CheckoutRefundResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
 
         let response = response
             .iter()
@@ -981,7 +1010,6 @@ impl ConnectorIntegration<api::Upload, types::UploadFileRequestData, types::Uplo
             .response
             .parse_struct("Checkout FileUploadResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(connector_response=?response);
         Ok(types::UploadFileRouterData {
             response: Ok(types::UploadFileResponse {
                 provider_file_id: response.file_id,
@@ -1256,6 +1284,28 @@ impl api::IncomingWebhook for Checkout {
         Ok(resource_object)
     }
 
+    fn get_webhook_acquirer_reference_number(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let details: checkout::CheckoutWebhookBody = request
+            .body
+            .parse_struct("CheckoutWebhookBody")
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        // Chargeback and refund events carry the reference number of the chargeback or refund
+        if checkout::is_chargeback_event(&details.transaction_type)
+            || checkout::is_refund_event(&details.transaction_type)
+        {
+            return Ok(None);
+        }
+
+        Ok(details
+            .data
+            .processing
+            .and_then(|processing| processing.acquirer_reference_number))
+    }
+
     fn get_dispute_details(
         &self,
         request: &api::IncomingWebhookRequestDetails<'_>,
@@ -6,7 +6,10 @@ use time::PrimitiveDateTime;
 use url::Url;
 
 use crate::{
-    connector::utils::{self, PaymentsCaptureRequestData, RouterData, WalletData},
+    connector::utils::{
+        self, CardVerificationResultCodes, PaymentsAuthorizeRequestData,
+        PaymentsCaptureRequestData, RouterData, WalletData,
+    },
     consts,
     core::errors,
     services,
@@ -238,12 +241,124 @@ pub struct PaymentsRequest {
     pub return_url: ReturnUrl,
     pub capture: bool,
     pub reference: String,
+    #[serde(flatten)]
+    pub l2_l3_data: Option<CheckoutL2L3Data>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_authorization: Option<CheckoutPartialAuthorization>,
 }
 
-#[derive(Debug, Serialize)]
+/// Allows the issuer to approve less than the amount of the payment
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CheckoutPartialAuthorization {
+    enabled: bool,
+}
+
+/// Level 2 and Level 3 data of the payment, with the commodity codes of the line items
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CheckoutL2L3Data {
+    processing: CheckoutL2L3Processing,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    items: Vec<CheckoutLineItem>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CheckoutL2L3Processing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shipping_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duty_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discount_amount: Option<i64>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CheckoutLineItem {
+    name: String,
+    quantity: u16,
+    unit_price: i64,
+    total_amount: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commodity_code: Option<String>,
+}
+
+impl From<utils::L2L3Data> for CheckoutL2L3Data {
+    fn from(l2_l3_data: utils::L2L3Data) -> Self {
+        Self {
+            processing: CheckoutL2L3Processing {
+                tax_amount: l2_l3_data.tax_amount,
+                shipping_amount: l2_l3_data.shipping_cost,
+                duty_amount: l2_l3_data.duty_amount,
+                discount_amount: l2_l3_data.discount_amount,
+            },
+            items: l2_l3_data
+                .line_items
+                .into_iter()
+                .map(|line_item| CheckoutLineItem {
+                    total_amount: line_item
+                        .amount
+                        .saturating_mul(i64::from(line_item.quantity)),
+                    name: line_item.product_name,
+                    quantity: line_item.quantity,
+                    unit_price: line_item.amount,
+                    commodity_code: line_item.commodity_code,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
 pub struct CheckoutThreeDS {
     enabled: bool,
     force_3ds: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exemption: Option<CheckoutThreeDsExemption>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckoutThreeDsExemption {
+    LowValue,
+    TransactionRiskAssessment,
+    TrustedListing,
+    RecurringOperation,
+}
+
+impl From<api_models::enums::ScaExemptionType> for CheckoutThreeDsExemption {
+    fn from(sca_exemption: api_models::enums::ScaExemptionType) -> Self {
+        match sca_exemption {
+            api_models::enums::ScaExemptionType::LowValue => Self::LowValue,
+            api_models::enums::ScaExemptionType::TransactionRiskAnalysis => {
+                Self::TransactionRiskAssessment
+            }
+            api_models::enums::ScaExemptionType::TrustedListing => Self::TrustedListing,
+            api_models::enums::ScaExemptionType::MerchantInitiatedTransaction => {
+                Self::RecurringOperation
+            }
+        }
+    }
+}
+
+impl CheckoutThreeDS {
+    fn new(
+        auth_type: enums::AuthenticationType,
+        sca_exemption: Option<api_models::enums::ScaExemptionType>,
+    ) -> Self {
+        match auth_type {
+            enums::AuthenticationType::ThreeDs => Self {
+                enabled: true,
+                force_3ds: true,
+                exemption: None,
+            },
+            enums::AuthenticationType::NoThreeDs => Self {
+                enabled: false,
+                force_3ds: false,
+                exemption: sca_exemption.map(CheckoutThreeDsExemption::from),
+            },
+        }
+    }
 }
 
 impl TryFrom<&types::ConnectorAuthType> for CheckoutAuthType {
@@ -341,16 +456,10 @@ impl TryFrom<&CheckoutRouterData<&types::PaymentsAuthorizeRouterData>> for Payme
             }
         }?;
 
-        let three_ds = match item.router_data.auth_type {
-            enums::AuthenticationType::ThreeDs => CheckoutThreeDS {
-                enabled: true,
-                force_3ds: true,
-            },
-            enums::AuthenticationType::NoThreeDs => CheckoutThreeDS {
-                enabled: false,
-                force_3ds: false,
-            },
-        };
+        let three_ds = CheckoutThreeDS::new(
+            item.router_data.auth_type,
+            item.router_data.request.get_sca_exemption(),
+        );
 
         let return_url = ReturnUrl {
             success_url: item
@@ -358,13 +467,13 @@ impl TryFrom<&CheckoutRouterData<&types::PaymentsAuthorizeRouterData>> for Payme
                 .request
                 .router_return_url
                 .as_ref()
-                .map(|return_url| format!("{return_url}?status=success")),
+                .map(|return_url| utils::append_query_param(return_url, "status", "success")),
             failure_url: item
                 .router_data
                 .request
                 .router_return_url
                 .as_ref()
-                .map(|return_url| format!("{return_url}?status=failure")),
+                .map(|return_url| utils::append_query_param(return_url, "status", "failure")),
         };
 
         let capture = matches!(
@@ -384,6 +493,16 @@ impl TryFrom<&CheckoutRouterData<&types::PaymentsAuthorizeRouterData>> for Payme
             return_url,
             capture,
             reference: item.router_data.connector_request_reference_id.clone(),
+            l2_l3_data: item
+                .router_data
+                .request
+                .get_l2_l3_data()
+                .map(CheckoutL2L3Data::from),
+            // A partial approval can only be captured for the approved amount, which is not
+            // possible when the payment is captured with the authorization
+            partial_authorization: (item.router_data.request.request_partial_authorization
+                && !capture)
+                .then_some(CheckoutPartialAuthorization { enabled: true }),
         })
     }
 }
@@ -494,6 +613,62 @@ pub struct PaymentsResponse {
     reference: Option<String>,
     response_code: Option<String>,
     response_summary: Option<String>,
+    source: Option<CheckoutSourceResponse>,
+    auth_code: Option<String>,
+    processing: Option<CheckoutProcessing>,
+}
+
+impl PaymentsResponse {
+    /// The amount approved by the issuer, when it approved less than the amount of the payment
+    fn get_partially_authorized_amount(&self, amount: i64) -> Option<i64> {
+        if self.status != CheckoutPaymentStatus::Authorized {
+            return None;
+        }
+        self.amount
+            .map(i64::from)
+            .filter(|authorized_amount| *authorized_amount < amount)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct CheckoutSourceResponse {
+    avs_check: Option<String>,
+    cvv_check: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct CheckoutProcessing {
+    pub acquirer_reference_number: Option<String>,
+}
+
+impl CardVerificationResultCodes for PaymentsResponse {
+    const AVS_RESULT_CODES: &'static [(&'static str, api::enums::AvsResult)] = &[
+        ("A", api::enums::AvsResult::PartialAddress),
+        ("B", api::enums::AvsResult::PartialAddress),
+        ("C", api::enums::AvsResult::NoMatch),
+        ("D", api::enums::AvsResult::FullMatch),
+        ("E", api::enums::AvsResult::Unavailable),
+        ("F", api::enums::AvsResult::FullMatch),
+        ("G", api::enums::AvsResult::Unavailable),
+        ("I", api::enums::AvsResult::Unavailable),
+        ("M", api::enums::AvsResult::FullMatch),
+        ("N", api::enums::AvsResult::NoMatch),
+        ("P", api::enums::AvsResult::PartialZip),
+        ("R", api::enums::AvsResult::Unavailable),
+        ("S", api::enums::AvsResult::Unavailable),
+        ("U", api::enums::AvsResult::Unavailable),
+        ("W", api::enums::AvsResult::PartialZip),
+        ("X", api::enums::AvsResult::FullMatch),
+        ("Y", api::enums::AvsResult::FullMatch),
+        ("Z", api::enums::AvsResult::PartialZip),
+    ];
+    const CVC_RESULT_CODES: &'static [(&'static str, api::enums::CvcResult)] = &[
+        ("Y", api::enums::CvcResult::Match),
+        ("N", api::enums::CvcResult::NoMatch),
+        ("P", api::enums::CvcResult::NotProcessed),
+        ("U", api::enums::CvcResult::NotProcessed),
+        ("X", api::enums::CvcResult::NotPresent),
+    ];
 }
 
 #[derive(Debug, Deserialize)]
@@ -518,6 +693,12 @@ impl TryFrom<types::PaymentsResponseRouterData<PaymentsResponse>>
         let redirection_data = item.response.links.redirect.map(|href| {
             services::RedirectForm::from((href.redirection_url, services::Method::Get))
         });
+        let card_verification_results = item.response.source.and_then(|source| {
+            PaymentsResponse::get_card_verification_results(source.avs_check, source.cvv_check)
+        });
+        let authorized_amount = item
+            .response
+            .get_partially_authorized_amount(item.data.request.amount);
         let status = enums::AttemptStatus::foreign_from((
             item.response.status,
             item.data.request.capture_method,
@@ -548,10 +729,18 @@ impl TryFrom<types::PaymentsResponseRouterData<PaymentsResponse>>
             connector_response_reference_id: Some(
                 item.response.reference.unwrap_or(item.response.id),
             ),
+            card_verification_results,
+            connector_fee: None,
+            auth_code: item.response.auth_code,
+            acquirer_reference_number: item
+                .response
+                .processing
+                .and_then(|processing| processing.acquirer_reference_number),
         };
         Ok(Self {
             status,
             response: error_response.map_or_else(|| Ok(payments_response_data), Err),
+            authorized_amount: authorized_amount.or(item.data.authorized_amount),
             ..item.data
         })
     }
@@ -567,6 +756,9 @@ impl TryFrom<types::PaymentsSyncResponseRouterData<PaymentsResponse>>
         let redirection_data = item.response.links.redirect.map(|href| {
             services::RedirectForm::from((href.redirection_url, services::Method::Get))
         });
+        let card_verification_results = item.response.source.and_then(|source| {
+            PaymentsResponse::get_card_verification_results(source.avs_check, source.cvv_check)
+        });
         let status =
             enums::AttemptStatus::foreign_from((item.response.status, item.response.balances));
         let error_response = if status == enums::AttemptStatus::Failure {
@@ -595,6 +787,13 @@ impl TryFrom<types::PaymentsSyncResponseRouterData<PaymentsResponse>>
             connector_response_reference_id: Some(
                 item.response.reference.unwrap_or(item.response.id),
             ),
+            card_verification_results,
+            connector_fee: None,
+            auth_code: item.response.auth_code,
+            acquirer_reference_number: item
+                .response
+                .processing
+                .and_then(|processing| processing.acquirer_reference_number),
         };
         Ok(Self {
             status,
@@ -669,6 +868,10 @@ impl TryFrom<types::PaymentsCancelResponseRouterData<PaymentVoidResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             status: response.into(),
             ..item.data
@@ -765,6 +968,10 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<PaymentCaptureResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: item.response.reference,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             status,
             amount_captured,
@@ -826,6 +1033,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, CheckoutRefundRespon
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.response.action_id.clone(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -844,6 +1052,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, CheckoutRefundResponse
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.response.action_id.clone(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -966,6 +1175,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, &ActionResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.action_id.clone(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -984,6 +1194,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, &ActionResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.action_id.clone(),
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1065,6 +1276,8 @@ pub struct CheckoutWebhookData {
     pub response_code: Option<String>,
     pub response_summary: Option<String>,
     pub currency: String,
+    pub auth_code: Option<String>,
+    pub processing: Option<CheckoutProcessing>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1224,6 +1437,9 @@ impl TryFrom<&api::IncomingWebhookRequestDetails<'_>> for PaymentsResponse {
             response_code: data.response_code,
             response_summary: data.response_summary,
             action_id: data.action_id,
+            source: None,
+            auth_code: data.auth_code,
+            processing: data.processing,
         };
 
         Ok(psync_struct)
@@ -1260,3 +1476,161 @@ impl From<String> for utils::ErrorCodeAndMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_three_ds_request() {
+        assert_eq!(
+            CheckoutThreeDS::new(
+                enums::AuthenticationType::ThreeDs,
+                Some(api_models::enums::ScaExemptionType::LowValue),
+            ),
+            CheckoutThreeDS {
+                enabled: true,
+                force_3ds: true,
+                exemption: None,
+            }
+        );
+        assert_eq!(
+            CheckoutThreeDS::new(
+                enums::AuthenticationType::NoThreeDs,
+                Some(api_models::enums::ScaExemptionType::LowValue),
+            ),
+            CheckoutThreeDS {
+                enabled: false,
+                force_3ds: false,
+                exemption: Some(CheckoutThreeDsExemption::LowValue),
+            }
+        );
+    }
+
+    #[test]
+    fn test_l2_l3_data_request() {
+        let l2_l3_data = utils::L2L3Data {
+            tax_amount: Some(160),
+            shipping_cost: Some(500),
+            duty_amount: None,
+            discount_amount: Some(100),
+            line_items: vec![api_models::payments::OrderDetailsWithAmount {
+                product_name: "printer paper".to_string(),
+                quantity: 2,
+                amount: 1000,
+                product_type: Some(api_models::enums::ProductType::Physical),
+                commodity_code: Some("44121903".to_string()),
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_value(CheckoutL2L3Data::from(l2_l3_data.clone())).unwrap(),
+            serde_json::json!({
+                "processing": {
+                    "tax_amount": 160,
+                    "shipping_amount": 500,
+                    "discount_amount": 100
+                },
+                "items": [{
+                    "name": "printer paper",
+                    "quantity": 2,
+                    "unit_price": 1000,
+                    "total_amount": 2000,
+                    "commodity_code": "44121903"
+                }]
+            })
+        );
+
+        // Without line items only the breakdown of the amount is sent
+        assert_eq!(
+            serde_json::to_value(CheckoutL2L3Data::from(utils::L2L3Data {
+                line_items: vec![],
+                ..l2_l3_data
+            }))
+            .unwrap(),
+            serde_json::json!({
+                "processing": {
+                    "tax_amount": 160,
+                    "shipping_amount": 500,
+                    "discount_amount": 100
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_partial_approval_response() {
+        let payments_response: PaymentsResponse = serde_json::from_value(serde_json::json!({
+            "id": "pay_mbabizu24mvu3mela5njyhpit4",
+            "action_id": "act_y3oqhf46pyzuxjbcn2giaqnb44",
+            "amount": 6000,
+            "currency": "USD",
+            "status": "Authorized",
+            "response_code": "10010",
+            "response_summary": "Partial Value Approved",
+            "_links": {}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            payments_response.get_partially_authorized_amount(10000),
+            Some(6000)
+        );
+        // An approval of the full amount is not partial
+        assert_eq!(
+            payments_response.get_partially_authorized_amount(6000),
+            None
+        );
+        assert_eq!(
+            PaymentsResponse {
+                status: CheckoutPaymentStatus::Declined,
+                ..payments_response
+            }
+            .get_partially_authorized_amount(10000),
+            None
+        );
+    }
+
+    #[test]
+    fn test_references_from_payment_captured_webhook() {
+        let body = serde_json::json!({
+            "id": "evt_az5sblvku4ge3dwpztvyizgcau",
+            "type": "payment_captured",
+            "data": {
+                "id": "pay_mbabizu24mvu3mela5njyhpit4",
+                "action_id": "act_y3oqhf46pyzuxjbcn2giaqnb44",
+                "reference": "ORD-5023-4E89",
+                "amount": 1000,
+                "currency": "USD",
+                "response_code": "10000",
+                "response_summary": "Approved",
+                "auth_code": "770687",
+                "processing": {
+                    "acquirer_transaction_id": "440189478",
+                    "acquirer_reference_number": "24492153326100000008311"
+                }
+            },
+            "_links": {}
+        })
+        .to_string();
+        let headers = actix_web::http::header::HeaderMap::new();
+        let request = api::IncomingWebhookRequestDetails {
+            method: actix_web::http::Method::POST,
+            uri: actix_web::http::Uri::from_static("/webhooks"),
+            headers: &headers,
+            body: body.as_bytes(),
+            query_params: String::new(),
+        };
+
+        let payments_response = PaymentsResponse::try_from(&request).unwrap();
+
+        assert_eq!(payments_response.auth_code.as_deref(), Some("770687"));
+        assert_eq!(
+            payments_response.processing,
+            Some(CheckoutProcessing {
+                acquirer_reference_number: Some("24492153326100000008311".to_string()),
+            })
+        );
+    }
+}
@@ -158,6 +158,10 @@ impl<F, T>
                     .data
                     .custom_id
                     .or(Some(item.response.data.id)),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
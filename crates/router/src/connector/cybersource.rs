@@ -3,11 +3,11 @@ pub mod transformers;
 use std::fmt::Debug;
 
 use base64::Engine;
+use common_utils::date_time;
 use diesel_models::enums;
 use error_stack::{IntoReport, ResultExt};
 use masking::{ExposeInterface, PeekInterface};
 use ring::{digest, hmac};
-use time::OffsetDateTime;
 use transformers as cybersource;
 use url::Url;
 
@@ -44,7 +44,7 @@ impl Cybersource {
         host: String,
         resource: &str,
         payload: &String,
-        date: OffsetDateTime,
+        date: &str,
         http_method: services::Method,
     ) -> CustomResult<String, errors::ConnectorError> {
         let cybersource::CybersourceAuthType {
@@ -161,7 +161,9 @@ where
         connectors: &settings::Connectors,
     ) -> CustomResult<Vec<(String, services::request::Maskable<String>)>, errors::ConnectorError>
     {
-        let date = OffsetDateTime::now_utc();
+        let date = date_time::now_utc_formatted(date_time::ConnectorDateFormat::Rfc1123)
+            .into_report()
+            .change_context(errors::ConnectorError::InvalidDateFormat)?;
         let cybersource_req = self.get_request_body(req)?;
         let auth = cybersource::CybersourceAuthType::try_from(&req.connector_auth_type)?;
         let merchant_account = auth.merchant_account.clone();
@@ -190,7 +192,7 @@ where
             host.to_string(),
             path.as_str(),
             &sha256,
-            date,
+            &date,
             http_method,
         )?;
 
@@ -207,7 +209,7 @@ where
                 "v-c-merchant-id".to_string(),
                 merchant_account.into_masked(),
             ),
-            ("Date".to_string(), date.to_string().into()),
+            ("Date".to_string(), date.into()),
             ("Host".to_string(), host.to_string().into()),
             ("Signature".to_string(), signature.into_masked()),
         ];
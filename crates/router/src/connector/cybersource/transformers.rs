@@ -4,7 +4,10 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{self, AddressDetailsData, PhoneDetailsData, RouterData},
+    connector::utils::{
+        self, AddressDetailsData, CardVerificationResultCodes, PaymentsAuthorizeRequestData,
+        PhoneDetailsData, RouterData,
+    },
     consts,
     core::errors,
     pii::PeekInterface,
@@ -21,6 +24,14 @@ pub struct CybersourcePaymentsRequest {
     processing_information: ProcessingInformation,
     payment_information: PaymentInformation,
     order_information: OrderInformationWithBill,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_information: Option<DeviceInformation>,
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInformation {
+    fingerprint_session_id: String,
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -113,6 +124,16 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CybersourcePaymentsRequest
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
         match item.request.payment_method_data.clone() {
             api::PaymentMethodData::Card(ccard) => {
+                // Cybersource declines the payments without the device fingerprint, which is not
+                // collected for mail order and telephone order payments
+                let device_information = if item.request.is_moto() {
+                    None
+                } else {
+                    Some(DeviceInformation {
+                        fingerprint_session_id: item.request.get_device_session_id()?,
+                    })
+                };
+
                 let phone = item.get_billing_phone()?;
                 let phone_number = phone.get_number()?;
                 let country_code = phone.get_country_code()?;
@@ -154,6 +175,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CybersourcePaymentsRequest
                     processing_information,
                     payment_information,
                     order_information,
+                    device_information,
                 })
             }
             _ => Err(errors::ConnectorError::NotImplemented("Payment methods".to_string()).into()),
@@ -275,6 +297,70 @@ pub struct CybersourcePaymentsResponse {
     id: String,
     status: CybersourcePaymentStatus,
     error_information: Option<CybersourceErrorInformation>,
+    processor_information: Option<CybersourceProcessorInformation>,
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CybersourceProcessorInformation {
+    avs: Option<CybersourceAvsInformation>,
+    card_verification: Option<CybersourceCardVerification>,
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
+pub struct CybersourceAvsInformation {
+    code: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CybersourceCardVerification {
+    result_code: Option<String>,
+}
+
+impl CardVerificationResultCodes for CybersourcePaymentsResponse {
+    const AVS_RESULT_CODES: &'static [(&'static str, api_enums::AvsResult)] = &[
+        ("A", api_enums::AvsResult::PartialAddress),
+        ("B", api_enums::AvsResult::PartialAddress),
+        ("C", api_enums::AvsResult::NoMatch),
+        ("D", api_enums::AvsResult::FullMatch),
+        ("E", api_enums::AvsResult::Unavailable),
+        ("F", api_enums::AvsResult::FullMatch),
+        ("G", api_enums::AvsResult::Unavailable),
+        ("I", api_enums::AvsResult::Unavailable),
+        ("M", api_enums::AvsResult::FullMatch),
+        ("N", api_enums::AvsResult::NoMatch),
+        ("P", api_enums::AvsResult::PartialZip),
+        ("R", api_enums::AvsResult::Unavailable),
+        ("S", api_enums::AvsResult::Unavailable),
+        ("U", api_enums::AvsResult::Unavailable),
+        ("W", api_enums::AvsResult::PartialZip),
+        ("X", api_enums::AvsResult::FullMatch),
+        ("Y", api_enums::AvsResult::FullMatch),
+        ("Z", api_enums::AvsResult::PartialZip),
+    ];
+    const CVC_RESULT_CODES: &'static [(&'static str, api_enums::CvcResult)] = &[
+        ("M", api_enums::CvcResult::Match),
+        ("N", api_enums::CvcResult::NoMatch),
+        ("P", api_enums::CvcResult::NotProcessed),
+        ("S", api_enums::CvcResult::NotPresent),
+        ("U", api_enums::CvcResult::NotProcessed),
+        ("X", api_enums::CvcResult::NotProcessed),
+    ];
+}
+
+impl CybersourcePaymentsResponse {
+    fn get_raw_card_verification_results(&self) -> (Option<String>, Option<String>) {
+        let processor_information = self.processor_information.as_ref();
+        (
+            processor_information
+                .and_then(|info| info.avs.as_ref())
+                .and_then(|avs| avs.code.clone()),
+            processor_information
+                .and_then(|info| info.card_verification.as_ref())
+                .and_then(|card_verification| card_verification.result_code.clone()),
+        )
+    }
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
@@ -303,6 +389,7 @@ impl<F, T>
     ) -> Result<Self, Self::Error> {
         let item = data.0;
         let is_capture = data.1;
+        let (raw_avs_result, raw_cvc_result) = item.response.get_raw_card_verification_results();
         Ok(Self {
             status: get_payment_status(is_capture, item.response.status.into()),
             response: match item.response.error_information {
@@ -319,6 +406,14 @@ impl<F, T>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results:
+                        CybersourcePaymentsResponse::get_card_verification_results(
+                            raw_avs_result,
+                            raw_cvc_result,
+                        ),
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
             },
             ..item.data
@@ -384,6 +479,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -465,6 +564,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, CybersourcePaymentsR
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -484,8 +584,81 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, CybersourceTransaction
                 refund_status: enums::RefundStatus::from(
                     item.response.application_information.status,
                 ),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
     }
 }
+
+#[cfg(test)]
+mod test_device_information {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::types::test_utils::get_authorize_router_data;
+
+    fn get_cybersource_router_data(
+        device_session_id: Option<String>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.connector = "cybersource".to_string();
+        router_data.request.email =
+            Some(pii::Email::try_from("john.doe@example.com".to_string()).unwrap());
+        router_data.request.device_session_id = device_session_id;
+        router_data.address = types::PaymentAddress {
+            shipping: None,
+            billing: Some(payments::Address {
+                address: Some(payments::AddressDetails {
+                    city: Some("London".to_string()),
+                    country: Some(api_enums::CountryAlpha2::GB),
+                    line1: Some(Secret::new("1 Main Street".to_string())),
+                    zip: Some(Secret::new("EC1A 1BB".to_string())),
+                    state: Some(Secret::new("London".to_string())),
+                    first_name: Some(Secret::new("John".to_string())),
+                    last_name: Some(Secret::new("Doe".to_string())),
+                    ..Default::default()
+                }),
+                phone: Some(payments::PhoneDetails {
+                    number: Some(Secret::new("7700900000".to_string())),
+                    country_code: Some("+44".to_string()),
+                }),
+            }),
+        };
+        router_data
+    }
+
+    #[test]
+    fn should_pass_device_session_as_fingerprint_session() {
+        let router_data = get_cybersource_router_data(Some("dds_1".to_string()));
+        let request = CybersourcePaymentsRequest::try_from(&router_data).unwrap();
+        let payload = serde_json::to_value(request).unwrap();
+
+        assert_eq!(
+            payload["deviceInformation"]["fingerprintSessionId"],
+            "dds_1"
+        );
+    }
+
+    #[test]
+    fn should_reject_payment_without_device_session() {
+        let router_data = get_cybersource_router_data(None);
+        let error = CybersourcePaymentsRequest::try_from(&router_data).unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "device_session_id"
+            }
+        ));
+    }
+
+    #[test]
+    fn should_not_require_device_session_for_moto_payment() {
+        let mut router_data = get_cybersource_router_data(None);
+        router_data.request.payment_channel = Some(api_enums::PaymentChannel::MailOrder);
+        let request = CybersourcePaymentsRequest::try_from(&router_data).unwrap();
+        let payload = serde_json::to_value(request).unwrap();
+
+        assert!(payload.get("deviceInformation").is_none());
+    }
+}
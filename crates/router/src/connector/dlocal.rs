@@ -14,9 +14,9 @@ use transformers as dlocal;
 
 use crate::{
     configs::settings,
-    connector::utils as connector_utils,
+    connector::utils::{self as connector_utils, response_parsing},
     core::errors::{self, CustomResult},
-    headers, logger,
+    headers,
     services::{
         self,
         request::{self, Mask},
@@ -206,7 +206,7 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
         let connector_request = dlocal::DlocalPaymentsRequest::try_from(req)?;
         let dlocal_payments_request = types::RequestBody::log_and_get_request_body(
             &connector_request,
-            utils::Encode::<dlocal::DlocalPaymentsRequest>::encode_to_string_of_json,
+            utils::Encode::<dlocal::DlocalPaymentsRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(dlocal_payments_request))
@@ -237,18 +237,11 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
         data: &types::PaymentsAuthorizeRouterData,
         res: Response,
     ) -> CustomResult<types::PaymentsAuthorizeRouterData, errors::ConnectorError> {
-        logger::debug!(dlocal_payments_authorize_response=?res);
-        let response: dlocal::DlocalPaymentsResponse = res
-            .response
-            .parse_struct("Dlocal PaymentsAuthorizeResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        }
-        .try_into()
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            dlocal::DlocalPaymentsSyncResponse,
+            dlocal::DlocalPaymentsResponse,
+        >(self.id(), &res, "Dlocal PaymentsAuthorizeResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_error_response(
@@ -314,17 +307,11 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         data: &types::PaymentsSyncRouterData,
         res: Response,
     ) -> CustomResult<types::PaymentsSyncRouterData, errors::ConnectorError> {
-        logger::debug!(dlocal_payment_sync_response=?res);
-        let response: dlocal::DlocalPaymentsResponse = res
-            .response
-            .parse_struct("Dlocal PaymentsSyncResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::RouterData::try_from(types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        })
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            dlocal::DlocalPaymentsSyncResponse,
+            dlocal::DlocalPaymentsResponse,
+        >(self.id(), &res, "Dlocal PaymentsSyncResponse")?
+        .into_router_data(data, res.status_code)
     }
 }
 
@@ -358,7 +345,7 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
         let connector_request = dlocal::DlocalPaymentsCaptureRequest::try_from(req)?;
         let dlocal_payments_capture_request = types::RequestBody::log_and_get_request_body(
             &connector_request,
-            utils::Encode::<dlocal::DlocalPaymentsCaptureRequest>::encode_to_string_of_json,
+            utils::Encode::<dlocal::DlocalPaymentsCaptureRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(dlocal_payments_capture_request))
@@ -387,18 +374,11 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
         data: &types::PaymentsCaptureRouterData,
         res: Response,
     ) -> CustomResult<types::PaymentsCaptureRouterData, errors::ConnectorError> {
-        logger::debug!(dlocal_payments_capture_response=?res);
-        let response: dlocal::DlocalPaymentsResponse = res
-            .response
-            .parse_struct("Dlocal PaymentsCaptureResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        }
-        .try_into()
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            dlocal::DlocalPaymentsSyncResponse,
+            dlocal::DlocalPaymentsResponse,
+        >(self.id(), &res, "Dlocal PaymentsCaptureResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_error_response(
@@ -457,18 +437,11 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
         data: &types::PaymentsCancelRouterData,
         res: Response,
     ) -> CustomResult<types::PaymentsCancelRouterData, errors::ConnectorError> {
-        logger::debug!(dlocal_payments_cancel_response=?res);
-        let response: dlocal::DlocalPaymentsResponse = res
-            .response
-            .parse_struct("Dlocal PaymentsCancelResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        }
-        .try_into()
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            dlocal::DlocalPaymentsSyncResponse,
+            dlocal::DlocalPaymentsResponse,
+        >(self.id(), &res, "Dlocal PaymentsCancelResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_error_response(
@@ -507,7 +480,7 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
         let connector_request = dlocal::RefundRequest::try_from(req)?;
         let dlocal_refund_request = types::RequestBody::log_and_get_request_body(
             &connector_request,
-            utils::Encode::<dlocal::RefundRequest>::encode_to_string_of_json,
+            utils::Encode::<dlocal::RefundRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(dlocal_refund_request))
@@ -535,7 +508,6 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
         data: &types::RefundsRouterData<api::Execute>,
         res: Response,
     ) -> CustomResult<types::RefundsRouterData<api::Execute>, errors::ConnectorError> {
-        logger::debug!(dlocal_refund_response=?res);
         let response: dlocal::RefundResponse =
             res.response
                 .parse_struct("Dlocal RefundResponse")
@@ -603,7 +575,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
         data: &types::RefundSyncRouterData,
         res: Response,
     ) -> CustomResult<types::RefundSyncRouterData, errors::ConnectorError> {
-        logger::debug!(dlocal_refund_sync_response=?res);
         let response: dlocal::RefundResponse = res
             .response
             .parse_struct("Dlocal RefundSyncResponse")
@@ -275,6 +275,10 @@ impl<F, T>
             connector_metadata: None,
             network_txn_id: None,
             connector_response_reference_id: None,
+            card_verification_results: None,
+            connector_fee: None,
+            auth_code: None,
+            acquirer_reference_number: None,
         };
         Ok(Self {
             status: enums::AttemptStatus::from(item.response.status),
@@ -284,6 +288,8 @@ impl<F, T>
     }
 }
 
+/// Fields of the payment which decide its status, the payment responses are parsed into it before
+/// they are parsed in full
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DlocalPaymentsSyncResponse {
     status: DlocalPaymentStatus,
@@ -313,6 +319,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -348,6 +358,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -382,6 +396,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -450,6 +468,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -486,6 +505,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1,10 +1,11 @@
 use diesel_models::enums::Currency;
+use error_stack::{IntoReport, ResultExt};
 use masking::Secret;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
-    core::errors,
+    core::errors::{self, CustomResult},
     services,
     types::{self, api, storage::enums},
 };
@@ -64,6 +65,7 @@ pub struct DummyConnectorPaymentsRequest<const T: u8> {
     payment_method_data: PaymentMethodData,
     return_url: Option<String>,
     connector: DummyConnectors,
+    webhook_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -167,6 +169,7 @@ impl<const T: u8> TryFrom<&types::PaymentsAuthorizeRouterData>
             payment_method_data: payment_method_data?,
             return_url: item.request.router_return_url.clone(),
             connector: Into::<DummyConnectors>::into(T),
+            webhook_url: item.request.webhook_url.clone(),
         })
     }
 }
@@ -250,6 +253,10 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, PaymentsResponse, T, types::Paym
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -330,6 +337,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -347,12 +355,58 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
     }
 }
 
+// WEBHOOKS :
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "event_type", content = "data", rename_all = "snake_case")]
+pub enum DummyConnectorWebhookBody {
+    PaymentSucceeded(PaymentsResponse),
+    PaymentFailed(PaymentsResponse),
+    RefundSucceeded(RefundResponse),
+}
+
+impl DummyConnectorWebhookBody {
+    pub fn get_object_reference_id(&self) -> api::webhooks::ObjectReferenceId {
+        match self {
+            Self::PaymentSucceeded(payment) | Self::PaymentFailed(payment) => {
+                api::webhooks::ObjectReferenceId::PaymentId(
+                    api::PaymentIdType::ConnectorTransactionId(payment.id.clone()),
+                )
+            }
+            Self::RefundSucceeded(refund) => api::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(refund.id.clone()),
+            ),
+        }
+    }
+
+    pub fn get_resource_object(self) -> CustomResult<serde_json::Value, errors::ConnectorError> {
+        match self {
+            Self::PaymentSucceeded(payment) | Self::PaymentFailed(payment) => {
+                serde_json::to_value(payment)
+            }
+            Self::RefundSucceeded(refund) => serde_json::to_value(refund),
+        }
+        .into_report()
+        .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
+    }
+}
+
+impl From<&DummyConnectorWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &DummyConnectorWebhookBody) -> Self {
+        match item {
+            DummyConnectorWebhookBody::PaymentSucceeded(_) => Self::PaymentIntentSuccess,
+            DummyConnectorWebhookBody::PaymentFailed(_) => Self::PaymentIntentFailure,
+            DummyConnectorWebhookBody::RefundSucceeded(_) => Self::RefundSuccess,
+        }
+    }
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DummyConnectorErrorResponse {
     pub error: ErrorData,
@@ -364,3 +418,61 @@ pub struct ErrorData {
     pub message: String,
     pub reason: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_payment_and_refund_webhooks_are_parsed() {
+        let payment_webhook: DummyConnectorWebhookBody =
+            serde_json::from_value(serde_json::json!({
+                "event_type": "payment_succeeded",
+                "data": {
+                    "status": "succeeded",
+                    "id": "dummy_pay_1",
+                    "amount": 100,
+                    "currency": "USD",
+                    "created": "2023-10-16T10:00:00.000Z",
+                    "payment_method_type": "card",
+                    "next_action": null
+                }
+            }))
+            .unwrap();
+        assert_eq!(
+            api::IncomingWebhookEvent::from(&payment_webhook),
+            api::IncomingWebhookEvent::PaymentIntentSuccess
+        );
+        assert!(matches!(
+            payment_webhook.get_object_reference_id(),
+            api::webhooks::ObjectReferenceId::PaymentId(
+                api::PaymentIdType::ConnectorTransactionId(id)
+            ) if id == "dummy_pay_1"
+        ));
+
+        let refund_webhook: DummyConnectorWebhookBody = serde_json::from_value(serde_json::json!({
+            "event_type": "refund_succeeded",
+            "data": {
+                "status": "succeeded",
+                "id": "dummy_ref_1",
+                "payment_id": "dummy_pay_1",
+                "currency": "USD",
+                "created": "2023-10-16T10:05:00.000Z",
+                "payment_amount": 100,
+                "refund_amount": 50
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            api::IncomingWebhookEvent::from(&refund_webhook),
+            api::IncomingWebhookEvent::RefundSuccess
+        );
+        assert!(matches!(
+            refund_webhook.get_object_reference_id(),
+            api::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(id)
+            ) if id == "dummy_ref_1"
+        ));
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
     connector::utils as connector_utils,
     consts,
     core::errors::{self, CustomResult},
-    headers, logger,
+    headers,
     services::{
         self,
         api::ConnectorIntegration,
@@ -618,7 +618,6 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
         data: &types::RefundsRouterData<api::Execute>,
         res: types::Response,
     ) -> CustomResult<types::RefundsRouterData<api::Execute>, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::fiserv", response=?res);
         let response: fiserv::RefundResponse =
             res.response
                 .parse_struct("fiserv RefundResponse")
@@ -699,8 +698,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
         data: &types::RefundSyncRouterData,
         res: types::Response,
     ) -> CustomResult<types::RefundSyncRouterData, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::fiserv", response=?res);
-
         let response: fiserv::FiservSyncResponse = res
             .response
             .parse_struct("Fiserv Refund Response")
@@ -316,6 +316,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -351,6 +355,10 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, FiservSyncResponse, T, types::Pa
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -518,6 +526,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
                 refund_status: enums::RefundStatus::from(
                     item.response.gateway_response.transaction_state,
                 ),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -546,6 +555,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, FiservSyncResponse>>
                 refund_status: enums::RefundStatus::from(
                     gateway_resp.gateway_response.transaction_state.clone(),
                 ),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -27,6 +27,15 @@ pub struct GlobalPayMeta {
     account_name: Secret<String>,
 }
 
+impl utils::metadata_schema::ConnectorMetadataSchema for GlobalPayMeta {
+    const FIELDS: &'static [utils::metadata_schema::MetadataField] =
+        &[utils::metadata_schema::MetadataField {
+            name: "account_name",
+            field_type: api_models::admin::ConnectorMetadataFieldType::String,
+            required: true,
+        }];
+}
+
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for GlobalpayPaymentsRequest {
     type Error = Error;
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
@@ -39,7 +48,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for GlobalpayPaymentsRequest {
             account_name,
             amount: Some(item.request.amount.to_string()),
             currency: item.request.currency.to_string(),
-            reference: item.attempt_id.to_string(),
+            reference: item.get_connector_request_reference_id(),
             country: item.get_billing_country()?,
             capture_mode: Some(requests::CaptureMode::from(item.request.capture_method)),
             payment_method: requests::PaymentMethod {
@@ -234,6 +243,10 @@ fn get_payment_response(
             connector_metadata: None,
             network_txn_id: None,
             connector_response_reference_id: response.reference,
+            card_verification_results: None,
+            connector_fee: None,
+            auth_code: None,
+            acquirer_reference_number: None,
         }),
     }
 }
@@ -346,6 +359,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, GlobalpayPaymentsRes
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -363,6 +377,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, GlobalpayPaymentsRespo
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
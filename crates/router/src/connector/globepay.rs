@@ -235,6 +235,10 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        globepay::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsResponseData>
@@ -303,6 +307,10 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        globepay::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::PaymentsResponseData>
@@ -409,6 +417,10 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        globepay::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponseData> for Globepay {
@@ -476,6 +488,10 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        globepay::get_error_from_success_body(res)
+    }
 }
 
 #[async_trait::async_trait]
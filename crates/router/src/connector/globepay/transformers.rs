@@ -7,6 +7,7 @@ use crate::{
     consts,
     core::errors,
     types::{self, api, storage::enums},
+    utils::BytesExt,
 };
 type Error = error_stack::Report<errors::ConnectorError>;
 
@@ -93,8 +94,6 @@ pub struct GlobepayPaymentsResponse {
     result_code: Option<GlobepayPaymentStatus>,
     order_id: Option<String>,
     qrcode_img: Option<url::Url>,
-    return_code: GlobepayReturnCode, //Execution result
-    return_msg: Option<String>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, strum::Display)]
@@ -127,50 +126,44 @@ impl<F, T>
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
-        if item.response.return_code == GlobepayReturnCode::Success {
-            let globepay_metadata = GlobepayConnectorMetadata {
-                image_data_url: item
-                    .response
-                    .qrcode_img
-                    .ok_or(errors::ConnectorError::ResponseHandlingFailed)?,
-            };
-            let connector_metadata = Some(common_utils::ext_traits::Encode::<
-                GlobepayConnectorMetadata,
-            >::encode_to_value(&globepay_metadata))
-            .transpose()
-            .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
-            let globepay_status = item
+        let globepay_metadata = GlobepayConnectorMetadata {
+            image_data_url: item
                 .response
-                .result_code
-                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
+                .qrcode_img
+                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?,
+        };
+        let connector_metadata = Some(
+            common_utils::ext_traits::Encode::<GlobepayConnectorMetadata>::encode_to_value(
+                &globepay_metadata,
+            ),
+        )
+        .transpose()
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
+        let globepay_status = item
+            .response
+            .result_code
+            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
 
-            Ok(Self {
-                status: enums::AttemptStatus::from(globepay_status),
-                response: Ok(types::PaymentsResponseData::TransactionResponse {
-                    resource_id: types::ResponseId::ConnectorTransactionId(
-                        item.response
-                            .order_id
-                            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?,
-                    ),
-                    redirection_data: None,
-                    mandate_reference: None,
-                    connector_metadata,
-                    network_txn_id: None,
-                    connector_response_reference_id: None,
-                }),
-                ..item.data
-            })
-        } else {
-            Ok(Self {
-                status: enums::AttemptStatus::Failure, //As this connector gives 200 in failed scenarios . if return_code is not success status is mapped to failure. ref = "https://pay.globepay.co/docs/en/#api-QRCode-NewQRCode"
-                response: Err(get_error_response(
-                    item.response.return_code,
-                    item.response.return_msg,
-                    item.http_code,
-                )),
-                ..item.data
-            })
-        }
+        Ok(Self {
+            status: enums::AttemptStatus::from(globepay_status),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.response
+                        .order_id
+                        .ok_or(errors::ConnectorError::ResponseHandlingFailed)?,
+                ),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
+            }),
+            ..item.data
+        })
     }
 }
 
@@ -178,8 +171,6 @@ impl<F, T>
 pub struct GlobepaySyncResponse {
     pub result_code: Option<GlobepayPaymentPsyncStatus>,
     pub order_id: Option<String>,
-    pub return_code: GlobepayReturnCode,
-    pub return_msg: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -212,52 +203,51 @@ impl<F, T>
     fn try_from(
         item: types::ResponseRouterData<F, GlobepaySyncResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
-        if item.response.return_code == GlobepayReturnCode::Success {
-            let globepay_status = item
-                .response
-                .result_code
-                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
-            let globepay_id = item
-                .response
-                .order_id
-                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
-            Ok(Self {
-                status: enums::AttemptStatus::from(globepay_status),
-                response: Ok(types::PaymentsResponseData::TransactionResponse {
-                    resource_id: types::ResponseId::ConnectorTransactionId(globepay_id),
-                    redirection_data: None,
-                    mandate_reference: None,
-                    connector_metadata: None,
-                    network_txn_id: None,
-                    connector_response_reference_id: None,
-                }),
-                ..item.data
-            })
-        } else {
-            Ok(Self {
-                status: enums::AttemptStatus::Failure, //As this connector gives 200 in failed scenarios . if return_code is not success status is mapped to failure. ref = "https://pay.globepay.co/docs/en/#api-QRCode-NewQRCode"
-                response: Err(get_error_response(
-                    item.response.return_code,
-                    item.response.return_msg,
-                    item.http_code,
-                )),
-                ..item.data
-            })
-        }
+        let globepay_status = item
+            .response
+            .result_code
+            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
+        let globepay_id = item
+            .response
+            .order_id
+            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
+        Ok(Self {
+            status: enums::AttemptStatus::from(globepay_status),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(globepay_id),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
+            }),
+            ..item.data
+        })
     }
 }
 
-fn get_error_response(
-    return_code: GlobepayReturnCode,
-    return_msg: Option<String>,
-    status_code: u16,
-) -> types::ErrorResponse {
-    types::ErrorResponse {
-        code: return_code.to_string(),
+/// Result of the request, present in all the responses of the connector. The connector responds
+/// to failed requests with a 200 status code and a return code other than `SUCCESS`.
+/// ref = "https://pay.globepay.co/docs/en/#api-QRCode-NewQRCode"
+#[derive(Debug, Deserialize)]
+pub struct GlobepayReturnStatus {
+    pub return_code: String,
+    pub return_msg: Option<String>,
+}
+
+pub fn get_error_from_success_body(res: &types::Response) -> Option<types::ErrorResponse> {
+    let return_status: GlobepayReturnStatus =
+        res.response.parse_struct("GlobepayReturnStatus").ok()?;
+    (return_status.return_code != "SUCCESS").then(|| types::ErrorResponse {
+        code: return_status.return_code,
         message: consts::NO_ERROR_MESSAGE.to_string(),
-        reason: return_msg,
-        status_code,
-    }
+        reason: return_status.return_msg,
+        status_code: res.status_code,
+    })
 }
 
 #[derive(Debug, Serialize)]
@@ -300,8 +290,6 @@ impl From<GlobepayRefundStatus> for enums::RefundStatus {
 pub struct GlobepayRefundResponse {
     pub result_code: Option<GlobepayRefundStatus>,
     pub refund_id: Option<String>,
-    pub return_code: GlobepayReturnCode,
-    pub return_msg: Option<String>,
 }
 
 impl<T> TryFrom<types::RefundsResponseRouterData<T, GlobepayRefundResponse>>
@@ -311,32 +299,22 @@ impl<T> TryFrom<types::RefundsResponseRouterData<T, GlobepayRefundResponse>>
     fn try_from(
         item: types::RefundsResponseRouterData<T, GlobepayRefundResponse>,
     ) -> Result<Self, Self::Error> {
-        if item.response.return_code == GlobepayReturnCode::Success {
-            let globepay_refund_id = item
-                .response
-                .refund_id
-                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
-            let globepay_refund_status = item
-                .response
-                .result_code
-                .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
-            Ok(Self {
-                response: Ok(types::RefundsResponseData {
-                    connector_refund_id: globepay_refund_id,
-                    refund_status: enums::RefundStatus::from(globepay_refund_status),
-                }),
-                ..item.data
-            })
-        } else {
-            Ok(Self {
-                response: Err(get_error_response(
-                    item.response.return_code,
-                    item.response.return_msg,
-                    item.http_code,
-                )),
-                ..item.data
-            })
-        }
+        let globepay_refund_id = item
+            .response
+            .refund_id
+            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
+        let globepay_refund_status = item
+            .response
+            .result_code
+            .ok_or(errors::ConnectorError::ResponseHandlingFailed)?;
+        Ok(Self {
+            response: Ok(types::RefundsResponseData {
+                connector_refund_id: globepay_refund_id,
+                refund_status: enums::RefundStatus::from(globepay_refund_status),
+                acquirer_reference_number: None,
+            }),
+            ..item.data
+        })
     }
 }
 
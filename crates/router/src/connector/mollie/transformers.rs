@@ -10,7 +10,7 @@ use url::Url;
 use crate::{
     connector::utils::{
         self, AddressDetailsData, BrowserInformationData, CardData, PaymentsAuthorizeRequestData,
-        RouterData,
+        RefundsRequestData, RouterData,
     },
     core::errors,
     services, types,
@@ -513,6 +513,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -535,7 +539,7 @@ impl<F> TryFrom<&types::RefundsRouterData<F>> for MollieRefundRequest {
         };
         Ok(Self {
             amount,
-            description: item.request.reason.to_owned(),
+            description: item.request.get_refund_reason(),
         })
     }
 }
@@ -591,6 +595,7 @@ impl<T> TryFrom<types::RefundsResponseRouterData<T, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -544,6 +544,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -626,6 +630,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.data.refund_id.to_string(),
                 refund_status: enums::RefundStatus::from(refund_stat),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -649,6 +654,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.data.refund_id.to_string(),
                 refund_status: enums::RefundStatus::from(refund_status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
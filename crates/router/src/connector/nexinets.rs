@@ -269,8 +269,7 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         req: &types::PaymentsSyncRouterData,
         connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        let meta: nexinets::NexinetsPaymentsMetadata =
-            to_connector_meta(req.request.connector_meta.clone())?;
+        let meta: nexinets::NexinetsPaymentsMetadata = req.request.get_connector_metadata_as()?;
         let order_id = nexinets::get_order_id(&meta)?;
         let transaction_id = match meta.psync_flow {
             transformers::NexinetsTransactionType::Debit
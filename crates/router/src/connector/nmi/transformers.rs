@@ -249,6 +249,10 @@ impl
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
                 enums::AttemptStatus::CaptureInitiated,
             ),
@@ -337,6 +341,10 @@ impl<T>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
                 enums::AttemptStatus::Charged,
             ),
@@ -390,6 +398,10 @@ impl TryFrom<types::PaymentsResponseRouterData<StandardResponse>>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: item.response.authcode,
+                    acquirer_reference_number: None,
                 }),
                 if let Some(diesel_models::enums::CaptureMethod::Automatic) =
                     item.data.request.capture_method
@@ -439,6 +451,10 @@ impl<T>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
                 enums::AttemptStatus::VoidInitiated,
             ),
@@ -490,6 +506,10 @@ impl TryFrom<types::PaymentsSyncResponseRouterData<types::Response>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -560,6 +580,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, StandardResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.transactionid,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -578,6 +599,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Capture, StandardResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.transactionid,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -624,6 +646,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, types::Response>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: response.transaction.transaction_id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
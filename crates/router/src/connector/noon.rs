@@ -241,6 +241,10 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsResponseData>
@@ -307,6 +311,10 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::PaymentsResponseData>
@@ -385,6 +393,10 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsResponseData>
@@ -460,6 +472,10 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsResponseData> for Noon {
@@ -535,6 +551,10 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponseData> for Noon {
@@ -602,6 +622,10 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn get_error_from_success_body(&self, res: &Response) -> Option<ErrorResponse> {
+        noon::get_error_from_success_body(res)
+    }
 }
 
 impl services::ConnectorRedirectResponse for Noon {
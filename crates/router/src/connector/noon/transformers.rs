@@ -7,6 +7,7 @@ use crate::{
         self as conn_utils, CardData, PaymentsAuthorizeRequestData, RefundsRequestData, RouterData,
         WalletData,
     },
+    consts,
     core::errors,
     services,
     types::{self, api, storage::enums, ErrorResponse},
@@ -408,6 +409,10 @@ impl<F, T>
                     connector_metadata: None,
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
                 }),
             },
             ..item.data
@@ -545,6 +550,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.result.transaction.id,
                 refund_status: enums::RefundStatus::from(item.response.result.transaction.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -587,6 +593,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: noon_transaction.id.to_owned(),
                 refund_status: enums::RefundStatus::from(noon_transaction.status.to_owned()),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -666,3 +673,26 @@ pub struct NoonErrorResponse {
     pub message: String,
     pub class_description: String,
 }
+
+/// Result of the request, present in all the responses of the connector. Some of the requests
+/// which fail are responded to with a 200 status code and a result code other than 0.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoonResultStatus {
+    pub result_code: u32,
+    pub message: Option<String>,
+    pub class_description: Option<String>,
+}
+
+pub fn get_error_from_success_body(res: &types::Response) -> Option<ErrorResponse> {
+    let result_status: NoonResultStatus =
+        utils::BytesExt::parse_struct(&res.response, "NoonResultStatus").ok()?;
+    (result_status.result_code != 0).then(|| ErrorResponse {
+        code: result_status.result_code.to_string(),
+        message: result_status
+            .message
+            .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+        reason: result_status.class_description,
+        status_code: res.status_code,
+    })
+}
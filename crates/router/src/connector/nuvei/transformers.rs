@@ -1278,11 +1278,11 @@ where
                 .and_then(|o| o.card.clone())
                 .and_then(|card| card.three_d)
                 .and_then(|three_ds| three_ds.acs_url.zip(three_ds.c_req))
-                .map(|(base_url, creq)| services::RedirectForm::Form {
-                    endpoint: base_url,
-                    method: services::Method::Post,
-                    form_fields: std::collections::HashMap::from([("creq".to_string(), creq)]),
-                }),
+                .map(|(base_url, creq)| {
+                    services::RedirectFormBuilder::new(&base_url, services::Method::Post)
+                        .map(|form| form.field("creq", creq).build())
+                })
+                .transpose()?,
         };
 
         let response = item.response;
@@ -1319,6 +1319,10 @@ where
                     },
                     network_txn_id: None,
                     connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: response.auth_code,
+                    acquirer_reference_number: None,
                 })
             },
             ..item.data
@@ -1426,6 +1430,7 @@ fn get_refund_response(
             _ => Ok(types::RefundsResponseData {
                 connector_refund_id: txn_id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
         },
     }
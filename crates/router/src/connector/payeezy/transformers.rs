@@ -5,7 +5,7 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{self, CardData},
+    connector::utils::{self, CardData, RouterData},
     core::errors,
     types::{self, api, storage::enums, transformers::ForeignFrom},
 };
@@ -104,7 +104,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PayeezyPaymentsRequest {
 fn get_card_specific_payment_data(
     item: &types::PaymentsAuthorizeRouterData,
 ) -> Result<PayeezyPaymentsRequest, error_stack::Report<errors::ConnectorError>> {
-    let merchant_ref = item.attempt_id.to_string();
+    let merchant_ref = item.get_connector_request_reference_id();
     let method = PayeezyPaymentMethodType::CreditCard;
     let amount = item.request.amount;
     let currency_code = item.request.currency.to_string();
@@ -361,6 +361,10 @@ impl<F, T>
                 connector_metadata: metadata,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -465,6 +469,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.transaction_id,
                 refund_status: enums::RefundStatus::from(item.response.transaction_status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
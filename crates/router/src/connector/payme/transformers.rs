@@ -260,6 +260,10 @@ impl TryFrom<&PaymePaySaleResponse> for types::PaymentsResponseData {
             ),
             network_txn_id: None,
             connector_response_reference_id: None,
+            card_verification_results: None,
+            connector_fee: None,
+            auth_code: None,
+            acquirer_reference_number: None,
         })
     }
 }
@@ -322,6 +326,10 @@ impl From<&SaleQuery> for types::PaymentsResponseData {
             connector_metadata: None,
             network_txn_id: None,
             connector_response_reference_id: None,
+            card_verification_results: None,
+            connector_fee: None,
+            auth_code: None,
+            acquirer_reference_number: None,
         }
     }
 }
@@ -530,6 +538,10 @@ impl<F>
                             connector_metadata: None,
                             network_txn_id: None,
                             connector_response_reference_id: None,
+                            card_verification_results: None,
+                            connector_fee: None,
+                            auth_code: None,
+                            acquirer_reference_number: None,
                         }),
                         ..item.data
                     }),
@@ -964,6 +976,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, PaymeRefundResponse>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.payme_transaction_id,
                 refund_status: enums::RefundStatus::try_from(item.response.sale_status)?,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1006,6 +1019,7 @@ impl<F, T>
                     pay_sale_response.sale_status.clone(),
                 )?,
                 connector_refund_id: pay_sale_response.payme_transaction_id.clone(),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
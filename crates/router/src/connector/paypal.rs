@@ -37,6 +37,69 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Paypal;
 
+impl connector_utils::capabilities::ConnectorCapabilitiesDeclaration for Paypal {
+    const CAPABILITIES: connector_utils::capabilities::ConnectorCapabilities =
+        connector_utils::capabilities::ConnectorCapabilities {
+            payment_methods: &[
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Card,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Credit,
+                        enums::PaymentMethodType::Debit,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Wallet,
+                    payment_method_types: &[enums::PaymentMethodType::Paypal],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankRedirect,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ideal,
+                        enums::PaymentMethodType::Giropay,
+                        enums::PaymentMethodType::Sofort,
+                        enums::PaymentMethodType::Eps,
+                    ],
+                },
+            ],
+            currencies: Some(&[
+                enums::Currency::AUD,
+                enums::Currency::BRL,
+                enums::Currency::CAD,
+                enums::Currency::CHF,
+                enums::Currency::CZK,
+                enums::Currency::DKK,
+                enums::Currency::EUR,
+                enums::Currency::GBP,
+                enums::Currency::HKD,
+                enums::Currency::HUF,
+                enums::Currency::ILS,
+                enums::Currency::JPY,
+                enums::Currency::MXN,
+                enums::Currency::MYR,
+                enums::Currency::NOK,
+                enums::Currency::NZD,
+                enums::Currency::PHP,
+                enums::Currency::PLN,
+                enums::Currency::SEK,
+                enums::Currency::SGD,
+                enums::Currency::THB,
+                enums::Currency::TWD,
+                enums::Currency::USD,
+            ]),
+            countries: None,
+            capture_methods: &[
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+            ],
+            refunds_supported: true,
+            mandates_supported: false,
+            split_payments_supported: false,
+            wallet_support: connector_utils::capabilities::WalletSupport::Native,
+            banks: &[],
+        };
+}
+
 impl api::Payment for Paypal {}
 impl api::PaymentSession for Paypal {}
 impl api::PaymentToken for Paypal {}
@@ -1108,6 +1171,26 @@ impl api::IncomingWebhook for Paypal {
         };
         Ok(sync_payload)
     }
+
+    fn get_webhook_connector_fee(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<types::ConnectorFee>, errors::ConnectorError> {
+        let details: paypal::PaypalWebhooksBody =
+            request
+                .body
+                .parse_struct("PaypalWebhooksBody")
+                .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        match details.resource {
+            paypal::PaypalResource::PaypalCardWebhooks(resource) => resource
+                .seller_receivable_breakdown
+                .map(|breakdown| breakdown.get_connector_fee())
+                .transpose()
+                .map(Option::flatten),
+            paypal::PaypalResource::PaypalRedirectsWebhooks(_)
+            | paypal::PaypalResource::PaypalRefundWebhooks(_) => Ok(None),
+        }
+    }
 }
 
 impl services::ConnectorRedirectResponse for Paypal {
@@ -1119,4 +1202,15 @@ impl services::ConnectorRedirectResponse for Paypal {
     ) -> CustomResult<payments::CallConnectorAction, errors::ConnectorError> {
         Ok(payments::CallConnectorAction::Trigger)
     }
+
+    fn is_cancelled_by_customer(
+        &self,
+        query_params: &str,
+        _json_payload: Option<&serde_json::Value>,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        let query = serde_urlencoded::from_str::<paypal::PaypalRedirectResponse>(query_params)
+            .into_report()
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(query.is_cancelled_by_customer())
+    }
 }
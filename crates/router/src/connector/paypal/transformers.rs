@@ -70,6 +70,46 @@ pub struct OrderAmount {
     value: String,
 }
 
+impl OrderAmount {
+    fn get_amount_in_lower_unit(&self) -> Result<i64, error_stack::Report<errors::ConnectorError>> {
+        self.currency_code
+            .to_currency_lower_unit_exact(&self.value)
+            .ok_or(errors::ConnectorError::ResponseDeserializationFailed)
+            .into_report()
+            .attach_printable_lazy(|| format!("Invalid amount {} from paypal", self.value))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaypalSellerReceivableBreakdown {
+    paypal_fee: Option<OrderAmount>,
+    net_amount: Option<OrderAmount>,
+}
+
+impl PaypalSellerReceivableBreakdown {
+    pub fn get_connector_fee(
+        &self,
+    ) -> Result<Option<types::ConnectorFee>, error_stack::Report<errors::ConnectorError>> {
+        self.paypal_fee
+            .as_ref()
+            .map(|paypal_fee| {
+                // Net amount is reported only when it is in the currency of the fee
+                let net_amount = self
+                    .net_amount
+                    .as_ref()
+                    .filter(|net_amount| net_amount.currency_code == paypal_fee.currency_code)
+                    .map(OrderAmount::get_amount_in_lower_unit)
+                    .transpose()?;
+                Ok(types::ConnectorFee {
+                    fee_amount: paypal_fee.get_amount_in_lower_unit()?,
+                    fee_currency: paypal_fee.currency_code,
+                    net_amount,
+                })
+            })
+            .transpose()
+    }
+}
+
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
 pub struct PurchaseUnitRequest {
     reference_id: String,
@@ -463,7 +503,8 @@ impl TryFrom<&api_models::payments::BankTransferData> for PaypalPaymentsRequest
             | api_models::payments::BankTransferData::DanamonVaBankTransfer { .. }
             | api_models::payments::BankTransferData::MandiriVaBankTransfer { .. }
             | api_models::payments::BankTransferData::Pix {}
-            | api_models::payments::BankTransferData::Pse {} => {
+            | api_models::payments::BankTransferData::Pse {}
+            | api_models::payments::BankTransferData::VirtualAccountBankTransfer { .. } => {
                 Err(errors::ConnectorError::NotSupported {
                     message: utils::SELECTED_PAYMENT_METHOD.to_string(),
                     connector: "Paypal",
@@ -667,6 +708,8 @@ pub struct PaypalPaymentsSyncResponse {
     status: PaypalPaymentStatus,
     amount: OrderAmount,
     supplementary_data: PaypalSupplementaryData,
+    #[serde(default)]
+    seller_receivable_breakdown: Option<PaypalSellerReceivableBreakdown>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -768,6 +811,10 @@ impl<F, T>
                 connector_metadata: Some(connector_meta),
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -851,6 +898,10 @@ impl<F, T>
                 connector_metadata: Some(connector_meta),
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -871,6 +922,13 @@ impl<F, T>
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
+        let connector_fee = item
+            .response
+            .seller_receivable_breakdown
+            .as_ref()
+            .map(PaypalSellerReceivableBreakdown::get_connector_fee)
+            .transpose()?
+            .flatten();
         Ok(Self {
             status: storage_enums::AttemptStatus::from(item.response.status),
             response: Ok(types::PaymentsResponseData::TransactionResponse {
@@ -882,6 +940,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -934,6 +996,7 @@ pub struct PaypalCaptureResponse {
     status: PaypalPaymentStatus,
     amount: Option<OrderAmount>,
     final_capture: bool,
+    seller_receivable_breakdown: Option<PaypalSellerReceivableBreakdown>,
 }
 
 impl From<PaypalPaymentStatus> for storage_enums::AttemptStatus {
@@ -964,6 +1027,13 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<PaypalCaptureResponse>>
         let status = storage_enums::AttemptStatus::from(item.response.status);
         let connector_payment_id: PaypalMeta =
             to_connector_meta(item.data.request.connector_meta.clone())?;
+        let connector_fee = item
+            .response
+            .seller_receivable_breakdown
+            .as_ref()
+            .map(PaypalSellerReceivableBreakdown::get_connector_fee)
+            .transpose()?
+            .flatten();
         Ok(Self {
             status,
             response: Ok(types::PaymentsResponseData::TransactionResponse {
@@ -979,6 +1049,10 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<PaypalCaptureResponse>>
                 })),
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             amount_captured: Some(amount_captured),
             ..item.data
@@ -1026,6 +1100,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1089,6 +1167,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: storage_enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1112,6 +1191,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: storage_enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1208,6 +1288,7 @@ pub struct PaypalSellerPayableBreakdown {
 pub struct PaypalCardWebhooks {
     pub supplementary_data: PaypalSupplementaryData,
     pub amount: OrderAmount,
+    pub seller_receivable_breakdown: Option<PaypalSellerReceivableBreakdown>,
 }
 
 #[derive(Deserialize, Debug, Serialize)]
@@ -1328,6 +1409,7 @@ impl TryFrom<(PaypalCardWebhooks, PaypalWebhookEventType)> for PaypalPaymentsSyn
             status: PaypalPaymentStatus::try_from(webhook_event)?,
             amount: webhook_body.amount,
             supplementary_data: webhook_body.supplementary_data,
+            seller_receivable_breakdown: webhook_body.seller_receivable_breakdown,
         })
     }
 }
@@ -1463,3 +1545,45 @@ fn get_headers(
         .to_owned();
     Ok(header_value)
 }
+
+/// Query parameters with which PayPal redirects the customer back. PayPal adds the `PayerID` once
+/// the customer approves the order, and only the `token` of the order when the customer cancels it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaypalRedirectResponse {
+    pub token: Option<String>,
+    #[serde(rename = "PayerID")]
+    pub payer_id: Option<String>,
+    pub liability_shift: Option<String>,
+}
+
+impl PaypalRedirectResponse {
+    pub fn is_cancelled_by_customer(&self) -> bool {
+        // The return from a 3DS challenge of a card payment does not have a `PayerID` either
+        self.token.is_some() && self.payer_id.is_none() && self.liability_shift.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancelled_redirect_is_detected() {
+        let is_cancelled = |query: &str| {
+            serde_urlencoded::from_str::<PaypalRedirectResponse>(query)
+                .ok()
+                .map(|response| response.is_cancelled_by_customer())
+        };
+
+        assert_eq!(is_cancelled("token=5O190127TN364715T"), Some(true));
+        assert_eq!(
+            is_cancelled("token=5O190127TN364715T&PayerID=QYR5Z8XDVJNXQ"),
+            Some(false)
+        );
+        assert_eq!(
+            is_cancelled("token=5O190127TN364715T&liability_shift=POSSIBLE"),
+            Some(false)
+        );
+        assert_eq!(is_cancelled(""), Some(false));
+    }
+}
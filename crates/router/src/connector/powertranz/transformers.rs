@@ -312,6 +312,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             Err,
         );
@@ -396,6 +400,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, PowertranzBaseRespon
                     true => RefundStatus::Success,
                     false => RefundStatus::Failure,
                 },
+                acquirer_reference_number: None,
             }),
             Err,
         );
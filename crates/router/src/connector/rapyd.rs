@@ -182,7 +182,7 @@ impl
         let req_obj = rapyd::RapydPaymentsRequest::try_from(req)?;
         let rapyd_req = types::RequestBody::log_and_get_request_body(
             &req_obj,
-            utils::Encode::<rapyd::RapydPaymentsRequest>::encode_to_string_of_json,
+            utils::Encode::<rapyd::RapydPaymentsRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(rapyd_req))
@@ -486,7 +486,7 @@ impl
         let req_obj = rapyd::CaptureRequest::try_from(req)?;
         let rapyd_req = types::RequestBody::log_and_get_request_body(
             &req_obj,
-            utils::Encode::<rapyd::CaptureRequest>::encode_to_string_of_json,
+            utils::Encode::<rapyd::CaptureRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(rapyd_req))
@@ -618,7 +618,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
         let req_obj = rapyd::RapydRefundRequest::try_from(req)?;
         let rapyd_req = types::RequestBody::log_and_get_request_body(
             &req_obj,
-            utils::Encode::<rapyd::RapydRefundRequest>::encode_to_string_of_json,
+            utils::Encode::<rapyd::RapydRefundRequest>::encode_to_canonical_json,
         )
         .change_context(errors::ConnectorError::RequestEncodingFailed)?;
 
@@ -919,3 +919,50 @@ impl api::IncomingWebhook for Rapyd {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use common_utils::canonical_json::to_canonical_json;
+    use masking::Secret;
+
+    use super::*;
+
+    #[test]
+    fn test_signature_of_canonical_request_body() {
+        let auth = rapyd::RapydAuthType {
+            access_key: Secret::new("rak_1".to_string()),
+            secret_key: Secret::new("rsk_1".to_string()),
+        };
+        let body = to_canonical_json(&serde_json::json!({
+            "payment_method": {
+                "type": "de_visa_card",
+                "fields": { "number": "4111111111111111", "cvv": "123" },
+            },
+            "metadata": { "notes": "line 1\nline 2\u{1f}", "emoji": "😀" },
+            "currency": "EUR",
+            "customer": {
+                "name": "Jöhn \"JJ\" Doe",
+                "addresses": [
+                    { "line_1": "Bahnhofstrasse 1", "city": "Zürich" },
+                    { "line_1": "丸の内 1-1", "city": "東京" },
+                ],
+            },
+            "amount": 100,
+        }))
+        .unwrap();
+
+        assert_eq!(
+            body,
+            r#"{"amount":100,"customer":{"addresses":[{"city":"Zürich","line_1":"Bahnhofstrasse 1"},{"city":"東京","line_1":"丸の内 1-1"}],"name":"Jöhn \"JJ\" Doe"},"currency":"EUR","metadata":{"emoji":"😀","notes":"line 1\nline 2\u001f"},"payment_method":{"fields":{"cvv":"123","number":"4111111111111111"},"type":"de_visa_card"}}"#
+        );
+        // Computed independently over the expected canonical JSON above
+        assert_eq!(
+            Rapyd
+                .generate_signature(&auth, "post", "/v1/payments", &body, &17000000, "salt12345678")
+                .unwrap(),
+            "ZWMyNGFiZGI4YjIwNjM5NTg0M2VhY2RiODg0ZGJlM2I1OWEwMGNmZWJhNTllZDEwYjQwMjU3OGZjZDdjZTA3Ng=="
+        );
+    }
+}
@@ -343,6 +343,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -367,6 +368,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -444,6 +446,10 @@ impl<F, T>
                                 connector_metadata: None,
                                 network_txn_id: None,
                                 connector_response_reference_id: None,
+                                card_verification_results: None,
+                                connector_fee: None,
+                                auth_code: None,
+                                acquirer_reference_number: None,
                             }),
                         )
                     }
@@ -7,7 +7,7 @@ use diesel_models::enums;
 use error_stack::{IntoReport, ResultExt};
 use transformers as shift4;
 
-use super::utils::{self as connector_utils, RefundsRequestData};
+use super::utils::{self as connector_utils, response_parsing, RefundsRequestData};
 use crate::{
     configs::settings,
     consts,
@@ -253,16 +253,11 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
         data: &types::PaymentsAuthorizeRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsAuthorizeRouterData, errors::ConnectorError> {
-        let response: shift4::Shift4NonThreeDsResponse = res
-            .response
-            .parse_struct("Shift4NonThreeDsResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::RouterData::try_from(types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        })
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            shift4::Shift4PaymentStatusResponse,
+            shift4::Shift4NonThreeDsResponse,
+        >(self.id(), &res, "Shift4NonThreeDsResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_error_response(
@@ -337,16 +332,11 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         data: &types::PaymentsSyncRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsSyncRouterData, errors::ConnectorError> {
-        let response: shift4::Shift4NonThreeDsResponse = res
-            .response
-            .parse_struct("Shift4NonThreeDsResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::RouterData::try_from(types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        })
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            shift4::Shift4PaymentStatusResponse,
+            shift4::Shift4NonThreeDsResponse,
+        >(self.id(), &res, "Shift4NonThreeDsResponse")?
+        .into_router_data(data, res.status_code)
     }
 }
 
@@ -387,17 +377,11 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
         data: &types::PaymentsCaptureRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsCaptureRouterData, errors::ConnectorError> {
-        let response: shift4::Shift4NonThreeDsResponse = res
-            .response
-            .parse_struct("Shift4NonThreeDsResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        }
-        .try_into()
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            shift4::Shift4PaymentStatusResponse,
+            shift4::Shift4NonThreeDsResponse,
+        >(self.id(), &res, "Shift4NonThreeDsResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_url(
@@ -587,16 +571,11 @@ impl
         data: &types::PaymentsCompleteAuthorizeRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsCompleteAuthorizeRouterData, errors::ConnectorError> {
-        let response: shift4::Shift4NonThreeDsResponse = res
-            .response
-            .parse_struct("Shift4NonThreeDsResponse")
-            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        types::RouterData::try_from(types::ResponseRouterData {
-            response,
-            data: data.clone(),
-            http_code: res.status_code,
-        })
-        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        response_parsing::parse_response_in_two_phases::<
+            shift4::Shift4PaymentStatusResponse,
+            shift4::Shift4NonThreeDsResponse,
+        >(self.id(), &res, "Shift4NonThreeDsResponse")?
+        .into_router_data(data, res.status_code)
     }
 
     fn get_error_response(
@@ -806,3 +785,50 @@ impl api::IncomingWebhook for Shift4 {
         Ok(details.data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::types::test_utils::get_authorize_router_data;
+
+    #[test]
+    fn test_captured_charge_is_charged_when_enrichment_fields_fail_to_parse() {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.connector = "shift4".to_string();
+        // The amount is not a number and the flow is not an object, the full response fails to
+        // parse whereas the status fields are intact
+        let response = types::Response {
+            headers: None,
+            response: serde_json::json!({
+                "id": "char_mK5jS3T6cBs2Ubhx1PXUbaUS",
+                "currency": "USD",
+                "amount": "10.00",
+                "status": "successful",
+                "captured": true,
+                "refunded": false,
+                "flow": "unexpected",
+            })
+            .to_string()
+            .into(),
+            status_code: 200,
+        };
+
+        let router_data = ConnectorIntegration::<
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        >::handle_response(&Shift4, &router_data, response)
+        .unwrap();
+
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+        assert!(matches!(
+            router_data.response,
+            Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(ref id),
+                ..
+            }) if id == "char_mK5jS3T6cBs2Ubhx1PXUbaUS"
+        ));
+    }
+}
@@ -241,11 +241,14 @@ impl TryFrom<&api_models::payments::BankTransferData> for Shift4PaymentMethod {
             | payments::BankTransferData::DanamonVaBankTransfer { .. }
             | payments::BankTransferData::MandiriVaBankTransfer { .. }
             | payments::BankTransferData::Pix {}
-            | payments::BankTransferData::Pse {} => Err(errors::ConnectorError::NotSupported {
-                message: utils::SELECTED_PAYMENT_METHOD.to_string(),
-                connector: "Shift4",
+            | payments::BankTransferData::Pse {}
+            | payments::BankTransferData::VirtualAccountBankTransfer { .. } => {
+                Err(errors::ConnectorError::NotSupported {
+                    message: utils::SELECTED_PAYMENT_METHOD.to_string(),
+                    connector: "Shift4",
+                }
+                .into())
             }
-            .into()),
         }
     }
 }
@@ -518,13 +521,7 @@ impl ForeignFrom<(bool, Option<&NextAction>, Shift4PaymentStatus)> for enums::At
     fn foreign_from(item: (bool, Option<&NextAction>, Shift4PaymentStatus)) -> Self {
         let (captured, next_action, payment_status) = item;
         match payment_status {
-            Shift4PaymentStatus::Successful => {
-                if captured {
-                    Self::Charged
-                } else {
-                    Self::Authorized
-                }
-            }
+            Shift4PaymentStatus::Successful => utils::map_capture_aware_status(true, captured),
             Shift4PaymentStatus::Failed => Self::Failure,
             Shift4PaymentStatus::Pending => match next_action {
                 Some(NextAction::Redirect) => Self::AuthenticationPending,
@@ -586,6 +583,14 @@ pub struct Shift4NonThreeDsResponse {
     pub flow: Option<FlowResponse>,
 }
 
+/// Fields of the charge which decide the status of the payment, parsed before the full response
+#[derive(Debug, Deserialize)]
+pub struct Shift4PaymentStatusResponse {
+    pub id: String,
+    pub status: Shift4PaymentStatus,
+    pub captured: bool,
+}
+
 #[derive(Default, Debug, Deserialize)]
 pub struct Shift4ThreeDsResponse {
     pub enrolled: bool,
@@ -698,6 +703,10 @@ impl<F>
                 ),
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -738,6 +747,48 @@ impl<T, F>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+impl<T, F>
+    TryFrom<
+        types::ResponseRouterData<F, Shift4PaymentStatusResponse, T, types::PaymentsResponseData>,
+    > for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = Error;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            Shift4PaymentStatusResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            // Without the flow of the charge, a pending charge is not known to require a redirection
+            status: enums::AttemptStatus::foreign_from((
+                item.response.captured,
+                None,
+                item.response.status,
+            )),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -803,6 +854,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -821,6 +873,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -920,4 +920,21 @@ impl api::IncomingWebhook for Square {
         };
         Ok(reference_object)
     }
+
+    fn get_webhook_connector_fee(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<types::ConnectorFee>, errors::ConnectorError> {
+        let details: square::SquareWebhookBody =
+            request
+                .body
+                .parse_struct("SquareWebhookObject")
+                .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        match details.data.object {
+            square::SquareWebhookObject::Payment(square_payments_response_details) => {
+                square_payments_response_details.get_connector_fee()
+            }
+            square::SquareWebhookObject::Refund(_) => Ok(None),
+        }
+    }
 }
@@ -322,12 +322,57 @@ impl From<SquarePaymentStatus> for enums::AttemptStatus {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SquareProcessingFee {
+    amount_money: SquarePaymentsAmountData,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SquarePaymentsResponseDetails {
     status: SquarePaymentStatus,
     id: String,
     amount_money: SquarePaymentsAmountData,
+    processing_fee: Option<Vec<SquareProcessingFee>>,
+}
+
+impl SquarePaymentsResponseDetails {
+    // Square adds the processing fee to the payment some time after it is completed, and it is
+    // reported in the currency of the account of the merchant
+    pub fn get_connector_fee(
+        &self,
+    ) -> Result<Option<types::ConnectorFee>, error_stack::Report<errors::ConnectorError>> {
+        let processing_fee = self.processing_fee.as_deref().unwrap_or_default();
+        processing_fee
+            .first()
+            .map(|first_fee| {
+                let fee_currency = first_fee.amount_money.currency;
+                let fee_amount = processing_fee
+                    .iter()
+                    .try_fold(0_i64, |fee_amount, fee| {
+                        if fee.amount_money.currency == fee_currency {
+                            fee_amount.checked_add(fee.amount_money.amount)
+                        } else {
+                            None
+                        }
+                    })
+                    .ok_or(errors::ConnectorError::ResponseHandlingFailed)
+                    .into_report()
+                    .attach_printable("Processing fees from square are in different currencies")?;
+                let net_amount = if self.amount_money.currency == fee_currency {
+                    self.amount_money.amount.checked_sub(fee_amount)
+                } else {
+                    None
+                };
+                Ok(types::ConnectorFee {
+                    fee_amount,
+                    fee_currency,
+                    net_amount,
+                })
+            })
+            .transpose()
+    }
 }
+
 #[derive(Debug, Deserialize)]
 pub struct SquarePaymentsResponse {
     payment: SquarePaymentsResponseDetails,
@@ -347,6 +392,7 @@ impl<F, T>
         if status == enums::AttemptStatus::Charged {
             amount_captured = Some(item.response.payment.amount_money.amount)
         };
+        let connector_fee = item.response.payment.get_connector_fee()?;
         Ok(Self {
             status,
             response: Ok(types::PaymentsResponseData::TransactionResponse {
@@ -356,6 +402,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             amount_captured,
             ..item.data
@@ -426,6 +476,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refund.id,
                 refund_status: enums::RefundStatus::from(item.response.refund.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -443,6 +494,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refund.id,
                 refund_status: enums::RefundStatus::from(item.response.refund.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -499,3 +551,93 @@ impl From<SquareWebhookObject> for api::IncomingWebhookEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_connector_fee_from_payment_updated_webhook() {
+        let completed_webhook = serde_json::json!({
+            "type": "payment.updated",
+            "data": {
+                "id": "GQTFp1ZlXdpoW4o6eGiZhbjosiDFf",
+                "object": {
+                    "payment": {
+                        "id": "GQTFp1ZlXdpoW4o6eGiZhbjosiDFf",
+                        "status": "COMPLETED",
+                        "amount_money": { "amount": 10000, "currency": "USD" }
+                    }
+                }
+            }
+        });
+        let body: SquareWebhookBody = serde_json::from_value(completed_webhook).unwrap();
+        match body.data.object {
+            SquareWebhookObject::Payment(payment) => {
+                assert_eq!(payment.get_connector_fee().unwrap(), None)
+            }
+            SquareWebhookObject::Refund(_) => panic!("expected a payment webhook"),
+        }
+
+        let fee_webhook = serde_json::json!({
+            "type": "payment.updated",
+            "data": {
+                "id": "GQTFp1ZlXdpoW4o6eGiZhbjosiDFf",
+                "object": {
+                    "payment": {
+                        "id": "GQTFp1ZlXdpoW4o6eGiZhbjosiDFf",
+                        "status": "COMPLETED",
+                        "amount_money": { "amount": 10000, "currency": "USD" },
+                        "processing_fee": [
+                            {
+                                "effective_at": "2023-10-06T09:00:00.000Z",
+                                "type": "INITIAL",
+                                "amount_money": { "amount": 265, "currency": "CAD" }
+                            },
+                            {
+                                "effective_at": "2023-10-06T09:00:00.000Z",
+                                "type": "ADJUSTMENT",
+                                "amount_money": { "amount": 15, "currency": "CAD" }
+                            }
+                        ]
+                    }
+                }
+            }
+        });
+        let body: SquareWebhookBody = serde_json::from_value(fee_webhook).unwrap();
+        match body.data.object {
+            SquareWebhookObject::Payment(payment) => assert_eq!(
+                payment.get_connector_fee().unwrap(),
+                Some(types::ConnectorFee {
+                    fee_amount: 280,
+                    fee_currency: enums::Currency::CAD,
+                    // Not computed since the fee is not in the currency of the payment
+                    net_amount: None,
+                })
+            ),
+            SquareWebhookObject::Refund(_) => panic!("expected a payment webhook"),
+        }
+    }
+
+    #[test]
+    fn test_connector_fee_in_payment_currency() {
+        let payment: SquarePaymentsResponseDetails = serde_json::from_value(serde_json::json!({
+            "id": "GQTFp1ZlXdpoW4o6eGiZhbjosiDFf",
+            "status": "COMPLETED",
+            "amount_money": { "amount": 10000, "currency": "USD" },
+            "processing_fee": [
+                { "amount_money": { "amount": 320, "currency": "USD" } }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            payment.get_connector_fee().unwrap(),
+            Some(types::ConnectorFee {
+                fee_amount: 320,
+                fee_currency: enums::Currency::USD,
+                net_amount: Some(9680),
+            })
+        );
+    }
+}
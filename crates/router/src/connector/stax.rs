@@ -570,8 +570,9 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
         req: &types::PaymentsCancelRouterData,
         connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
+        // Only an authorization which is not captured can be voided, it has no child capture
         Ok(format!(
-            "{}/transaction/{}/void-or-refund",
+            "{}/transaction/{}/void",
             self.base_url(connectors),
             req.request.connector_transaction_id,
         ))
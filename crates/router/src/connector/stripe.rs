@@ -8,7 +8,9 @@ use masking::PeekInterface;
 use router_env::{instrument, tracing};
 
 use self::transformers as stripe;
-use super::utils::{self as connector_utils, RefundsRequestData};
+use super::utils::{
+    self as connector_utils, request_validation::ValidateConnectorRequest, RefundsRequestData,
+};
 use crate::{
     configs::settings,
     consts,
@@ -16,7 +18,7 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
-    headers, logger,
+    headers,
     services::{
         self,
         request::{self, Mask},
@@ -32,6 +34,96 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Stripe;
 
+impl connector_utils::capabilities::ConnectorCapabilitiesDeclaration for Stripe {
+    const CAPABILITIES: connector_utils::capabilities::ConnectorCapabilities =
+        connector_utils::capabilities::ConnectorCapabilities {
+            payment_methods: &[
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Card,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Credit,
+                        enums::PaymentMethodType::Debit,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::Wallet,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::ApplePay,
+                        enums::PaymentMethodType::GooglePay,
+                        enums::PaymentMethodType::WeChatPay,
+                        enums::PaymentMethodType::AliPay,
+                        enums::PaymentMethodType::Cashapp,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::PayLater,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Klarna,
+                        enums::PaymentMethodType::Affirm,
+                        enums::PaymentMethodType::AfterpayClearpay,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankRedirect,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ideal,
+                        enums::PaymentMethodType::Giropay,
+                        enums::PaymentMethodType::Sofort,
+                        enums::PaymentMethodType::Eps,
+                        enums::PaymentMethodType::BancontactCard,
+                        enums::PaymentMethodType::Przelewy,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankDebit,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ach,
+                        enums::PaymentMethodType::Sepa,
+                        enums::PaymentMethodType::Becs,
+                        enums::PaymentMethodType::Bacs,
+                    ],
+                },
+                connector_utils::capabilities::SupportedPaymentMethod {
+                    payment_method: enums::PaymentMethod::BankTransfer,
+                    payment_method_types: &[
+                        enums::PaymentMethodType::Ach,
+                        enums::PaymentMethodType::Sepa,
+                        enums::PaymentMethodType::Bacs,
+                        enums::PaymentMethodType::Multibanco,
+                    ],
+                },
+            ],
+            currencies: None,
+            countries: None,
+            capture_methods: &[
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+            ],
+            refunds_supported: true,
+            mandates_supported: true,
+            split_payments_supported: true,
+            wallet_support: connector_utils::capabilities::WalletSupport::Native,
+            banks: &[connector_utils::capabilities::SupportedBanks {
+                payment_method_type: enums::PaymentMethodType::Ideal,
+                bank_codes: &[
+                    (api_models::enums::BankNames::AbnAmro, "abn_amro"),
+                    (api_models::enums::BankNames::AsnBank, "asn_bank"),
+                    (api_models::enums::BankNames::Bunq, "bunq"),
+                    (api_models::enums::BankNames::Handelsbanken, "handelsbanken"),
+                    (api_models::enums::BankNames::Ing, "ing"),
+                    (api_models::enums::BankNames::Knab, "knab"),
+                    (api_models::enums::BankNames::Moneyou, "moneyou"),
+                    (api_models::enums::BankNames::Rabobank, "rabobank"),
+                    (api_models::enums::BankNames::Regiobank, "regiobank"),
+                    (api_models::enums::BankNames::Revolut, "revolut"),
+                    (api_models::enums::BankNames::SnsBank, "sns_bank"),
+                    (api_models::enums::BankNames::TriodosBank, "triodos_bank"),
+                    (api_models::enums::BankNames::VanLanschot, "van_lanschot"),
+                ],
+            }],
+        };
+}
+
 impl ConnectorCommon for Stripe {
     fn id(&self) -> &'static str {
         "stripe"
@@ -73,6 +165,11 @@ impl ConnectorValidation for Stripe {
             ),
         }
     }
+
+    fn get_connector_transaction_id_pattern(&self) -> Option<&'static str> {
+        // Payment intents, charges and the sources of the bank redirects and transfers
+        Some(r"^(pi|ch|py|src)_[A-Za-z0-9]+$")
+    }
 }
 
 impl api::Payment for Stripe {}
@@ -186,7 +283,6 @@ impl
             .response
             .parse_struct("StripeSourceResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
 
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -204,7 +300,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -303,7 +398,6 @@ impl
             .response
             .parse_struct("StripeCustomerResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
 
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -321,7 +415,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
 
         Ok(types::ErrorResponse {
             status_code: res.status_code,
@@ -417,7 +510,6 @@ impl
             .response
             .parse_struct("StripeTokenResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
 
         types::RouterData::try_from(types::ResponseRouterData {
             response,
@@ -435,7 +527,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
 
         Ok(types::ErrorResponse {
             status_code: res.status_code,
@@ -540,7 +631,6 @@ impl
             .response
             .parse_struct("PaymentIntentResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -557,7 +647,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
 
         Ok(types::ErrorResponse {
             status_code: res.status_code,
@@ -656,7 +745,6 @@ impl
                     .response
                     .parse_struct("SetupIntentSyncResponse")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-                logger::info!(connector_response=?response);
                 types::RouterData::try_from(types::ResponseRouterData {
                     response,
                     data: data.clone(),
@@ -668,7 +756,6 @@ impl
                     .response
                     .parse_struct("PaymentIntentSyncResponse")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-                logger::info!(connector_response=?response);
                 types::RouterData::try_from(types::ResponseRouterData {
                     response,
                     data: data.clone(),
@@ -689,7 +776,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
 
         Ok(types::ErrorResponse {
             status_code: res.status_code,
@@ -772,6 +858,7 @@ impl
             }
             _ => {
                 let req = stripe::PaymentIntentRequest::try_from(req)?;
+                req.validate_request()?;
                 let request = types::RequestBody::log_and_get_request_body(
                     &req,
                     utils::Encode::<stripe::PaymentIntentRequest>::url_encode,
@@ -816,7 +903,6 @@ impl
                     .response
                     .parse_struct("PaymentIntentResponse")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-                logger::info!(connector_response=?response);
 
                 types::RouterData::try_from(types::ResponseRouterData {
                     response,
@@ -836,7 +922,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -930,7 +1015,6 @@ impl
             .response
             .parse_struct("PaymentIntentResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -947,7 +1031,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1060,7 +1143,6 @@ impl
             .response
             .parse_struct("SetupIntentResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -1077,7 +1159,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1169,7 +1250,6 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
             res.response
                 .parse_struct("Stripe RefundResponse")
                 .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -1186,7 +1266,6 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1264,7 +1343,6 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
             res.response
                 .parse_struct("Stripe RefundResponse")
                 .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -1281,7 +1359,6 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1400,7 +1477,6 @@ impl
             .response
             .parse_struct("Stripe FileUploadResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         Ok(types::UploadFileRouterData {
             response: Ok(types::UploadFileResponse {
                 provider_file_id: response.file_id,
@@ -1417,7 +1493,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1511,7 +1586,6 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1610,7 +1684,6 @@ impl
             .response
             .parse_struct("Stripe DisputeObj")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::info!(connector_response=?response);
         Ok(types::SubmitEvidenceRouterData {
             response: Ok(types::SubmitEvidenceResponse {
                 dispute_status: api_models::enums::DisputeStatus::DisputeChallenged,
@@ -1628,7 +1701,359 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        router_env::logger::info!(error_response=?response);
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error
+                .code
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .error
+                .code
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: response.error.message,
+        })
+    }
+}
+
+impl api::ConnectorWebhookEndpoint for Stripe {}
+
+impl
+    services::ConnectorIntegration<
+        api::CreateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for Stripe
+{
+    fn get_headers(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::CreateWebhookEndpointType::get_content_type(self)
+                .to_string()
+                .into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn get_url(
+        &self,
+        _req: &types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        Ok(format!(
+            "{}{}",
+            self.base_url(connectors),
+            "v1/webhook_endpoints"
+        ))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+    ) -> CustomResult<Option<types::RequestBody>, errors::ConnectorError> {
+        let connector_request = stripe::StripeWebhookEndpointRequest::from(&req.request);
+        let stripe_req = types::RequestBody::log_and_get_request_body(
+            &connector_request,
+            utils::Encode::<stripe::StripeWebhookEndpointRequest>::url_encode,
+        )
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(stripe_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::CreateWebhookEndpointType::get_url(
+                    self, req, connectors,
+                )?)
+                .attach_default_headers()
+                .headers(types::CreateWebhookEndpointType::get_headers(
+                    self, req, connectors,
+                )?)
+                .body(types::CreateWebhookEndpointType::get_request_body(
+                    self, req,
+                )?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        res: types::Response,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let response: stripe::StripeWebhookEndpointResponse = res
+            .response
+            .parse_struct("StripeWebhookEndpointResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: stripe::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error
+                .code
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .error
+                .code
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: response.error.message,
+        })
+    }
+}
+
+impl
+    services::ConnectorIntegration<
+        api::UpdateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for Stripe
+{
+    fn get_headers(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::UpdateWebhookEndpointType::get_content_type(self)
+                .to_string()
+                .into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn get_url(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        let id = req.request.connector_webhook_endpoint_id.as_ref().ok_or(
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "connector_webhook_endpoint_id",
+            },
+        )?;
+        Ok(format!(
+            "{}v1/webhook_endpoints/{}",
+            self.base_url(connectors),
+            id
+        ))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+    ) -> CustomResult<Option<types::RequestBody>, errors::ConnectorError> {
+        let connector_request = stripe::StripeWebhookEndpointRequest::from(&req.request);
+        let stripe_req = types::RequestBody::log_and_get_request_body(
+            &connector_request,
+            utils::Encode::<stripe::StripeWebhookEndpointRequest>::url_encode,
+        )
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(stripe_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::UpdateWebhookEndpointType::get_url(
+                    self, req, connectors,
+                )?)
+                .attach_default_headers()
+                .headers(types::UpdateWebhookEndpointType::get_headers(
+                    self, req, connectors,
+                )?)
+                .body(types::UpdateWebhookEndpointType::get_request_body(
+                    self, req,
+                )?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        res: types::Response,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let response: stripe::StripeWebhookEndpointResponse = res
+            .response
+            .parse_struct("StripeWebhookEndpointResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: stripe::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error
+                .code
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .error
+                .code
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: response.error.message,
+        })
+    }
+}
+
+impl
+    services::ConnectorIntegration<
+        api::DeleteWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for Stripe
+{
+    fn get_headers(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::DeleteWebhookEndpointType::get_content_type(self)
+                .to_string()
+                .into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn get_url(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        let id = req.request.connector_webhook_endpoint_id.as_ref().ok_or(
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "connector_webhook_endpoint_id",
+            },
+        )?;
+        Ok(format!(
+            "{}v1/webhook_endpoints/{}",
+            self.base_url(connectors),
+            id
+        ))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Delete)
+                .url(&types::DeleteWebhookEndpointType::get_url(
+                    self, req, connectors,
+                )?)
+                .attach_default_headers()
+                .headers(types::DeleteWebhookEndpointType::get_headers(
+                    self, req, connectors,
+                )?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        res: types::Response,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let response: stripe::StripeWebhookEndpointResponse = res
+            .response
+            .parse_struct("StripeWebhookEndpointResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: stripe::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -1874,7 +2299,11 @@ impl api::IncomingWebhook for Stripe {
             stripe::WebhookEventType::ChargeDisputeFundsReinstated => {
                 api::IncomingWebhookEvent::DisputeWon
             }
+            stripe::WebhookEventType::EarlyFraudWarningCreated => {
+                api::IncomingWebhookEvent::AlertReceived
+            }
             stripe::WebhookEventType::Unknown
+            | stripe::WebhookEventType::EarlyFraudWarningUpdated
             | stripe::WebhookEventType::ChargeCaptured
             | stripe::WebhookEventType::ChargeExpired
             | stripe::WebhookEventType::ChargeFailed
@@ -1940,6 +2369,17 @@ impl api::IncomingWebhook for Stripe {
             updated_at: None,
         })
     }
+
+    fn get_alert_details(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<api::AlertPayload, errors::ConnectorError> {
+        let details: stripe::StripeEarlyFraudWarningWebhook = request
+            .body
+            .parse_struct("StripeEarlyFraudWarningWebhook")
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+        Ok(api::AlertPayload::from(details.data.object))
+    }
 }
 
 impl services::ConnectorRedirectResponse for Stripe {
@@ -1974,4 +2414,146 @@ impl services::ConnectorRedirectResponse for Stripe {
                 },
             ))
     }
+
+    fn get_payment_identifier_from_redirect(
+        &self,
+        query_params: &str,
+        _json_payload: Option<&serde_json::Value>,
+    ) -> CustomResult<Option<api_models::payments::PaymentIdType>, errors::ConnectorError> {
+        let query =
+            serde_urlencoded::from_str::<transformers::StripeRedirectResponse>(query_params)
+                .into_report()
+                .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+
+        Ok(query
+            .payment_intent
+            .map(api_models::payments::PaymentIdType::ConnectorTransactionId))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use api::IncomingWebhook;
+    use services::ConnectorIntegration;
+
+    use super::*;
+    use crate::{core::payments::helpers, types::test_utils::get_authorize_router_data};
+
+    fn get_payment_intent_webhook_body(
+        event_type: &str,
+        status: &str,
+        amount_remaining: i64,
+    ) -> Vec<u8> {
+        let next_action = (amount_remaining > 0).then(|| {
+            serde_json::json!({
+                "type": "display_bank_transfer_instructions",
+                "display_bank_transfer_instructions": {
+                    "amount_remaining": amount_remaining,
+                    "currency": "usd",
+                    "financial_addresses": [{
+                        "aba": {
+                            "account_number": "1234567890",
+                            "bank_name": "Test Bank",
+                            "routing_number": "110000000"
+                        },
+                        "supported_networks": ["ach", "domestic_wire_us"],
+                        "type": "aba"
+                    }],
+                    "hosted_instructions_url": null,
+                    "reference": "PAYREF1234",
+                    "type": "us_bank_transfer"
+                }
+            })
+        });
+        serde_json::json!({
+            "id": "evt_1",
+            "type": event_type,
+            "data": {
+                "object": {
+                    "id": "pi_1",
+                    "object": "payment_intent",
+                    "amount": 1000,
+                    "amount_received": 1000 - amount_remaining,
+                    "currency": "usd",
+                    "status": status,
+                    "created": 1_696_000_000,
+                    "metadata": {
+                        "order_id": "pay_1"
+                    },
+                    "next_action": next_action
+                }
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    /// Handles the payment intent of the webhook as the response of a payment sync, the way the
+    /// incoming webhook flow does for verified webhooks
+    fn handle_webhook(
+        body: &[u8],
+        status: enums::AttemptStatus,
+    ) -> (api::IncomingWebhookEvent, enums::AttemptStatus) {
+        let headers = actix_web::http::header::HeaderMap::new();
+        let request = api::IncomingWebhookRequestDetails {
+            method: actix_web::http::Method::POST,
+            uri: actix_web::http::Uri::from_static("/webhooks/merchant_1/stripe"),
+            headers: &headers,
+            body,
+            query_params: String::new(),
+        };
+        let event_type = Stripe.get_webhook_event_type(&request).unwrap();
+        let resource_object = Stripe.get_webhook_resource_object(&request).unwrap();
+
+        let mut router_data: types::PaymentsSyncRouterData = helpers::router_data_type_conversion(
+            get_authorize_router_data(None),
+            types::PaymentsSyncData {
+                connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                    "pi_1".to_string(),
+                ),
+                ..Default::default()
+            },
+            Err(types::ErrorResponse::default()),
+        );
+        router_data.status = status;
+        let response = types::Response {
+            headers: None,
+            response: serde_json::to_vec(&resource_object).unwrap().into(),
+            status_code: 200,
+        };
+        let router_data = ConnectorIntegration::<
+            api::PSync,
+            types::PaymentsSyncData,
+            types::PaymentsResponseData,
+        >::handle_response(&Stripe, &router_data, response)
+        .unwrap();
+
+        (event_type, router_data.status)
+    }
+
+    #[test]
+    fn test_bank_transfer_webhooks_move_payment_from_pending_to_succeeded() {
+        let (event_type, status) = handle_webhook(
+            &get_payment_intent_webhook_body(
+                "payment_intent.partially_funded",
+                "requires_action",
+                400,
+            ),
+            enums::AttemptStatus::AuthenticationPending,
+        );
+        assert_eq!(
+            event_type,
+            api::IncomingWebhookEvent::PaymentIntentPartiallyFunded
+        );
+        assert_eq!(status, enums::AttemptStatus::AuthenticationPending);
+
+        let (event_type, status) = handle_webhook(
+            &get_payment_intent_webhook_body("payment_intent.succeeded", "succeeded", 0),
+            status,
+        );
+        assert_eq!(event_type, api::IncomingWebhookEvent::PaymentIntentSuccess);
+        assert_eq!(status, enums::AttemptStatus::Charged);
+    }
 }
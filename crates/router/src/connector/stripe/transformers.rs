@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{collections::BTreeMap, ops::Deref};
 
 use api_models::{self, enums as api_enums, payments};
 use common_utils::{
@@ -9,6 +9,8 @@ use common_utils::{
 use data_models::mandates::AcceptanceType;
 use error_stack::{IntoReport, ResultExt};
 use masking::{ExposeInterface, ExposeOptionInterface, PeekInterface, Secret};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use url::Url;
@@ -16,7 +18,13 @@ use uuid::Uuid;
 
 use crate::{
     collect_missing_value_keys,
-    connector::utils::{self as connector_util, ApplePay, PaymentsPreProcessingData, RouterData},
+    connector::utils::{
+        self as connector_util,
+        capabilities::ConnectorCapabilitiesDeclaration,
+        request_validation::{self, ValidateConnectorRequest},
+        ApplePay, PaymentsAuthorizeRequestData, PaymentsPreProcessingData, RefundsRequestData,
+        RouterData,
+    },
     core::errors,
     services,
     types::{
@@ -123,6 +131,164 @@ pub struct PaymentIntentRequest {
     pub off_session: Option<bool>,
     #[serde(rename = "payment_method_types[0]")]
     pub payment_method_types: Option<StripePaymentMethodType>,
+    #[serde(flatten)]
+    pub order_risk_data: StripeOrderRiskData,
+    #[serde(flatten)]
+    pub split_payment: StripeSplitPaymentRequest,
+    #[serde(flatten)]
+    pub merchant_metadata: BTreeMap<String, Secret<String>>,
+}
+
+// Stripe rejects statement descriptors which are longer than 22 characters or which contain any of
+// the characters `<`, `>`, `\`, `'`, `"` and `*`
+const STATEMENT_DESCRIPTOR_MAX_LENGTH: usize = 22;
+
+#[deny(clippy::invalid_regex)]
+static STATEMENT_DESCRIPTOR_REGEX: Lazy<Option<Regex>> =
+    Lazy::new(|| Regex::new(r#"^[^<>\\'"*]*$"#).ok());
+
+impl ValidateConnectorRequest for PaymentIntentRequest {
+    fn validate_request(&self) -> Result<(), error_stack::Report<errors::ConnectorError>> {
+        let statement_descriptor_regex = STATEMENT_DESCRIPTOR_REGEX
+            .as_ref()
+            .ok_or(errors::ConnectorError::RequestEncodingFailed)
+            .into_report()
+            .attach_printable("Invalid statement descriptor regex")?;
+        for (field, value) in [
+            ("statement_descriptor", &self.statement_descriptor),
+            (
+                "statement_descriptor_suffix",
+                &self.statement_descriptor_suffix,
+            ),
+        ] {
+            request_validation::max_len(field, value.as_deref(), STATEMENT_DESCRIPTOR_MAX_LENGTH)?;
+            request_validation::matches_regex(
+                field,
+                value.as_deref(),
+                statement_descriptor_regex,
+                r#"not contain any of the characters < > \ ' " *"#,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Metadata keys of the payment intent which are set by us, they are not overridden by the
+// metadata of the merchant
+const STRIPE_RESERVED_METADATA_KEYS: [&str; 6] = [
+    "order_id",
+    "is_refund_id_as_reference",
+    "order_category",
+    "shipping_method",
+    "estimated_delivery_date",
+    "product_types",
+];
+
+// Payment metadata of the merchant passed as metadata of the payment intent, it holds only the
+// keys allowed for Stripe, and the values which are not strings are passed as JSON
+fn get_merchant_metadata(
+    request: &types::PaymentsAuthorizeData,
+) -> BTreeMap<String, Secret<String>> {
+    request
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.peek().as_object())
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| !STRIPE_RESERVED_METADATA_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            (format!("metadata[{key}]"), Secret::new(value))
+        })
+        .collect()
+}
+
+// Details of the order passed as metadata of the payment intent, so that they can be used in the
+// Radar rules of the merchant
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct StripeOrderRiskData {
+    #[serde(rename = "metadata[order_category]")]
+    pub order_category: Option<String>,
+    #[serde(rename = "metadata[shipping_method]")]
+    pub shipping_method: Option<api_enums::ShippingMethod>,
+    #[serde(rename = "metadata[estimated_delivery_date]")]
+    pub estimated_delivery_date: Option<String>,
+    #[serde(rename = "metadata[product_types]")]
+    pub product_types: Option<String>,
+}
+
+impl From<&types::PaymentsAuthorizeData> for StripeOrderRiskData {
+    fn from(request: &types::PaymentsAuthorizeData) -> Self {
+        let mut product_types = request
+            .order_details
+            .iter()
+            .flatten()
+            .filter_map(|order| {
+                order
+                    .product_type
+                    .map(|product_type| product_type.to_string())
+            })
+            .collect::<Vec<_>>();
+        product_types.sort();
+        product_types.dedup();
+
+        Self {
+            order_category: request.order_category.clone(),
+            shipping_method: request.shipping_method,
+            estimated_delivery_date: request
+                .estimated_delivery_date
+                .map(|delivery_date| delivery_date.date().to_string()),
+            product_types: (!product_types.is_empty()).then(|| product_types.join(",")),
+        }
+    }
+}
+
+// Split payment made as a destination charge, the platform fee is retained as the application fee
+// and the rest of the amount is transferred to the connected account
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct StripeSplitPaymentRequest {
+    pub application_fee_amount: Option<i64>,
+    #[serde(rename = "transfer_data[destination]")]
+    pub transfer_destination: Option<String>,
+}
+
+impl From<&types::PaymentsAuthorizeData> for StripeSplitPaymentRequest {
+    fn from(request: &types::PaymentsAuthorizeData) -> Self {
+        request
+            .get_split_payment_data()
+            .map(|split_payment| Self {
+                application_fee_amount: Some(split_payment.platform_fee_amount),
+                transfer_destination: Some(split_payment.destination_account.clone()),
+            })
+            .unwrap_or_default()
+    }
+}
+
+// Refund of a destination charge, the transfer to the connected account is always reversed, and
+// the application fee is reversed proportionally to the refunded amount unless it is retained
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct StripeSplitRefundRequest {
+    pub refund_application_fee: Option<bool>,
+    pub reverse_transfer: Option<bool>,
+}
+
+impl From<&types::RefundsData> for StripeSplitRefundRequest {
+    fn from(request: &types::RefundsData) -> Self {
+        request
+            .split_refund
+            .as_ref()
+            .map(|split_refund| Self {
+                refund_application_fee: Some(
+                    split_refund.fee_refund_behaviour
+                        == api_enums::PlatformFeeRefundBehaviour::Proportional,
+                ),
+                reverse_transfer: Some(true),
+            })
+            .unwrap_or_default()
+    }
 }
 
 // Field rename is required only in case of serialization as it is passed in the request to the connector.
@@ -172,6 +338,26 @@ pub struct StripeCardData {
     pub payment_method_data_card_cvc: Secret<String>,
     #[serde(rename = "payment_method_options[card][request_three_d_secure]")]
     pub payment_method_auth_type: Auth3ds,
+    #[serde(
+        rename = "payment_method_options[card][three_d_secure][cryptogram]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub three_d_secure_cryptogram: Option<Secret<String>>,
+    #[serde(
+        rename = "payment_method_options[card][three_d_secure][electronic_commerce_indicator]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub three_d_secure_electronic_commerce_indicator: Option<String>,
+    #[serde(
+        rename = "payment_method_options[card][three_d_secure][transaction_id]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub three_d_secure_transaction_id: Option<String>,
+    #[serde(
+        rename = "payment_method_options[card][three_d_secure][version]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub three_d_secure_version: Option<String>,
 }
 #[derive(Debug, Eq, PartialEq, Serialize)]
 pub struct StripePayLaterData {
@@ -279,7 +465,7 @@ pub struct StripeIdeal {
     #[serde(rename = "payment_method_data[type]")]
     pub payment_method_data_type: StripePaymentMethodType,
     #[serde(rename = "payment_method_data[ideal][bank]")]
-    ideal_bank_name: Option<StripeBankNames>,
+    ideal_bank_name: Option<&'static str>,
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize)]
@@ -368,6 +554,18 @@ pub struct SepaBankTransferData {
     pub country: api_models::enums::CountryAlpha2,
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct VirtualAccountBankTransferData {
+    #[serde(rename = "payment_method_data[type]")]
+    pub payment_method_data_type: StripePaymentMethodType,
+    #[serde(rename = "payment_method_options[customer_balance][bank_transfer][type]")]
+    pub bank_transfer_type: BankTransferType,
+    #[serde(rename = "payment_method_options[customer_balance][funding_type]")]
+    pub balance_funding_type: BankTransferType,
+    #[serde(rename = "payment_method_types[0]")]
+    pub payment_method_type: StripePaymentMethodType,
+}
+
 #[derive(Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum StripeCreditTransferSourceRequest {
@@ -459,6 +657,7 @@ pub enum StripeBankTransferData {
     SepaBankTransfer(Box<SepaBankTransferData>),
     BacsBankTransfers(Box<BacsBankTransferData>),
     MultibancoBankTransfers(Box<MultibancoTransferData>),
+    VirtualAccountBankTransfer(Box<VirtualAccountBankTransferData>),
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize)]
@@ -613,6 +812,7 @@ impl TryFrom<enums::PaymentMethodType> for StripePaymentMethodType {
             enums::PaymentMethodType::Blik => Ok(Self::Blik),
             enums::PaymentMethodType::AliPay => Ok(Self::Alipay),
             enums::PaymentMethodType::Przelewy24 => Ok(Self::Przelewy24),
+            enums::PaymentMethodType::VirtualAccount => Ok(Self::CustomerBalance),
             enums::PaymentMethodType::Boleto
             | enums::PaymentMethodType::CryptoCurrency
             | enums::PaymentMethodType::GooglePay
@@ -692,6 +892,9 @@ impl TryFrom<enums::PaymentMethodType> for StripePaymentMethodType {
 pub enum BankTransferType {
     GbBankTransfer,
     EuBankTransfer,
+    UsBankTransfer,
+    MxBankTransfer,
+    JpBankTransfer,
     #[serde(rename = "bank_transfer")]
     BankTransfers,
 }
@@ -875,10 +1078,16 @@ impl TryFrom<&api_models::enums::BankNames> for StripeBankNames {
     }
 }
 
+// Orders delivered digitally are not shipped, so the shipping address is not required for them
 fn validate_shipping_address_against_payment_method(
     shipping_address: &StripeShippingAddress,
     payment_method: Option<&StripePaymentMethodType>,
+    is_digital_goods_order: bool,
 ) -> Result<(), error_stack::Report<errors::ConnectorError>> {
+    if is_digital_goods_order {
+        return Ok(());
+    }
+
     if let Some(StripePaymentMethodType::AfterpayClearpay) = payment_method {
         let missing_fields = collect_missing_value_keys!(
             ("shipping.address.first_name", shipping_address.name),
@@ -1222,10 +1431,28 @@ fn get_bank_debit_data(
     }
 }
 
+/// Stripe issues virtual accounts only in the local banking network of the currency,
+/// EUR and GBP virtual accounts are created through the SEPA and BACS bank transfers
+fn get_virtual_account_bank_transfer_type(
+    currency: enums::Currency,
+) -> Result<BankTransferType, error_stack::Report<errors::ConnectorError>> {
+    match currency {
+        enums::Currency::USD => Ok(BankTransferType::UsBankTransfer),
+        enums::Currency::MXN => Ok(BankTransferType::MxBankTransfer),
+        enums::Currency::JPY => Ok(BankTransferType::JpBankTransfer),
+        _ => Err(errors::ConnectorError::NotSupported {
+            message: format!("{currency} for virtual account bank transfers"),
+            connector: "stripe",
+        }
+        .into()),
+    }
+}
+
 fn create_stripe_payment_method(
     payment_method_data: &api_models::payments::PaymentMethodData,
     auth_type: enums::AuthenticationType,
     payment_method_token: Option<types::PaymentMethodToken>,
+    currency: enums::Currency,
 ) -> Result<
     (
         StripePaymentMethodData,
@@ -1353,6 +1580,30 @@ fn create_stripe_payment_method(
                         billing_details,
                     ))
                 }
+                payments::BankTransferData::VirtualAccountBankTransfer { billing_details } => {
+                    let billing_details = StripeBillingAddress {
+                        email: Some(billing_details.email.clone()),
+                        name: Some(billing_details.name.clone()),
+                        ..Default::default()
+                    };
+                    Ok((
+                        StripePaymentMethodData::BankTransfer(
+                            StripeBankTransferData::VirtualAccountBankTransfer(Box::new(
+                                VirtualAccountBankTransferData {
+                                    payment_method_data_type:
+                                        StripePaymentMethodType::CustomerBalance,
+                                    bank_transfer_type: get_virtual_account_bank_transfer_type(
+                                        currency,
+                                    )?,
+                                    balance_funding_type: BankTransferType::BankTransfers,
+                                    payment_method_type: StripePaymentMethodType::CustomerBalance,
+                                },
+                            )),
+                        ),
+                        Some(StripePaymentMethodType::CustomerBalance),
+                        billing_details,
+                    ))
+                }
                 payments::BankTransferData::Pix {} => Err(errors::ConnectorError::NotImplemented(
                     connector_util::get_unimplemented_payment_method_error_message("stripe"),
                 )
@@ -1449,6 +1700,10 @@ impl TryFrom<(&payments::Card, Auth3ds)> for StripePaymentMethodData {
             payment_method_data_card_exp_year: card.card_exp_year.clone(),
             payment_method_data_card_cvc: card.card_cvc.clone(),
             payment_method_auth_type,
+            three_d_secure_cryptogram: None,
+            three_d_secure_electronic_commerce_indicator: None,
+            three_d_secure_transaction_id: None,
+            three_d_secure_version: None,
         }))
     }
 }
@@ -1609,7 +1864,10 @@ impl TryFrom<&payments::BankRedirectData> for StripePaymentMethodData {
             )),
             payments::BankRedirectData::Ideal { bank_name, .. } => {
                 let bank_name = bank_name
-                    .map(|bank_name| StripeBankNames::try_from(&bank_name))
+                    .map(|bank_name| {
+                        <super::Stripe as ConnectorCapabilitiesDeclaration>::CAPABILITIES
+                            .get_bank_code(enums::PaymentMethodType::Ideal, bank_name)
+                    })
                     .transpose()?;
                 Ok(Self::BankRedirect(StripeBankRedirectData::StripeIdeal(
                     Box::new(StripeIdeal {
@@ -1756,11 +2014,13 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
                             &item.request.payment_method_data,
                             item.auth_type,
                             item.payment_method_token.clone(),
+                            item.request.currency,
                         )?;
 
                     validate_shipping_address_against_payment_method(
                         &shipping_address,
                         payment_method_type.as_ref(),
+                        item.request.is_digital_goods_order(),
                     )?;
 
                     (
@@ -1797,6 +2057,20 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
             _ => payment_data,
         };
 
+        // 3DS was performed by the merchant outside of the router, the authentication result is
+        // passed to stripe so that it does not challenge the customer again
+        if let (Some(StripePaymentMethodData::Card(card)), Some(authentication_data)) = (
+            payment_data.as_mut(),
+            item.request.get_external_authentication_data(),
+        ) {
+            card.payment_method_auth_type = Auth3ds::Automatic;
+            card.three_d_secure_cryptogram = Some(authentication_data.cavv.clone());
+            card.three_d_secure_electronic_commerce_indicator =
+                Some(authentication_data.eci.clone());
+            card.three_d_secure_transaction_id = authentication_data.ds_transaction_id.clone();
+            card.three_d_secure_version = Some(authentication_data.message_version.clone());
+        }
+
         let setup_mandate_details = item
             .request
             .setup_mandate_details
@@ -1888,6 +2162,9 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
             off_session: item.request.off_session,
             setup_future_usage: item.request.setup_future_usage,
             payment_method_types,
+            order_risk_data: StripeOrderRiskData::from(&item.request),
+            split_payment: StripeSplitPaymentRequest::from(&item.request),
+            merchant_metadata: get_merchant_metadata(&item.request),
         })
     }
 }
@@ -1962,6 +2239,7 @@ impl TryFrom<&types::TokenizationRouterData> for TokenRequest {
             &item.request.payment_method_data,
             item.auth_type,
             item.payment_method_token.clone(),
+            item.request.currency,
         )?;
         Ok(Self {
             token_data: payment_data.0,
@@ -2082,13 +2360,6 @@ pub struct SepaAndBacsBankTransferInstructions {
     pub receiver: SepaAndBacsReceiver,
 }
 
-#[serde_with::skip_serializing_none]
-#[derive(Clone, Debug, Serialize)]
-pub struct QrCodeNextInstructions {
-    pub image_data_url: Url,
-    pub display_to_timestamp: Option<i64>,
-}
-
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct SepaAndBacsReceiver {
     pub amount_received: i64,
@@ -2318,6 +2589,10 @@ impl<F, T>
                 connector_metadata,
                 network_txn_id,
                 connector_response_reference_id: Some(item.response.id),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             amount_captured: item.response.amount_received,
             ..item.data
@@ -2331,6 +2606,17 @@ pub fn get_connector_metadata(
 ) -> CustomResult<Option<serde_json::Value>, errors::ConnectorError> {
     let next_action_response = next_action
         .and_then(|next_action_response| match next_action_response {
+            StripeNextActionResponse::DisplayBankTransferInstructions(response)
+                if response.is_virtual_account_transfer() =>
+            {
+                response.get_virtual_account_next_action(amount).map(
+                    |virtual_account_next_action| {
+                        common_utils::ext_traits::Encode::<
+                            payments::ConnectorNextActionData,
+                        >::encode_to_value(&virtual_account_next_action)
+                    },
+                )
+            }
             StripeNextActionResponse::DisplayBankTransferInstructions(response) => {
                 let bank_instructions = response.financial_addresses.get(0);
                 let (sepa_bank_instructions, bacs_bank_instructions) =
@@ -2357,27 +2643,28 @@ pub fn get_connector_metadata(
                 ))
             }
             StripeNextActionResponse::WechatPayDisplayQrCode(response) => {
-                let wechat_pay_instructions = QrCodeNextInstructions {
-                    image_data_url: response.image_data_url.to_owned(),
-                    display_to_timestamp: None,
-                };
+                let wechat_pay_instructions =
+                    payments::ConnectorNextActionData::QrCodeInformation {
+                        image_data_url: response.image_data_url.to_owned(),
+                        qr_code_data: Some(response.data.to_string()),
+                        display_to_timestamp: None,
+                    };
 
-                Some(
-                    common_utils::ext_traits::Encode::<QrCodeNextInstructions>::encode_to_value(
-                        &wechat_pay_instructions,
-                    ),
-                )
+                Some(common_utils::ext_traits::Encode::<
+                    payments::ConnectorNextActionData,
+                >::encode_to_value(&wechat_pay_instructions))
             }
             StripeNextActionResponse::CashappHandleRedirectOrDisplayQrCode(response) => {
-                let cashapp_qr_instructions: QrCodeNextInstructions = QrCodeNextInstructions {
-                    image_data_url: response.qr_code.image_url_png.to_owned(),
-                    display_to_timestamp: response.qr_code.expires_at.to_owned(),
-                };
-                Some(
-                    common_utils::ext_traits::Encode::<QrCodeNextInstructions>::encode_to_value(
-                        &cashapp_qr_instructions,
-                    ),
-                )
+                let cashapp_qr_instructions =
+                    payments::ConnectorNextActionData::QrCodeInformation {
+                        image_data_url: response.qr_code.image_url_png.to_owned(),
+                        qr_code_data: None,
+                        display_to_timestamp: response.qr_code.expires_at.to_owned(),
+                    };
+
+                Some(common_utils::ext_traits::Encode::<
+                    payments::ConnectorNextActionData,
+                >::encode_to_value(&cashapp_qr_instructions))
             }
             _ => None,
         })
@@ -2448,6 +2735,10 @@ impl<F, T>
                 connector_metadata,
                 network_txn_id: None,
                 connector_response_reference_id: Some(item.response.id.clone()),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             Err,
         );
@@ -2489,6 +2780,10 @@ impl<F, T>
                 connector_metadata: None,
                 network_txn_id: Option::foreign_from(item.response.latest_attempt),
                 connector_response_reference_id: Some(item.response.id),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -2602,6 +2897,44 @@ pub struct StripeBankTransferDetails {
     pub bank_transfer_type: Option<String>,
 }
 
+impl StripeBankTransferDetails {
+    fn is_virtual_account_transfer(&self) -> bool {
+        matches!(
+            self.bank_transfer_type.as_deref(),
+            Some("us_bank_transfer" | "mx_bank_transfer" | "jp_bank_transfer")
+        )
+    }
+
+    fn get_virtual_account_next_action(
+        &self,
+        amount: i64,
+    ) -> Option<payments::ConnectorNextActionData> {
+        let virtual_account = self
+            .financial_addresses
+            .iter()
+            .find_map(StripeFinanicalInformation::get_virtual_account_instructions)?;
+        let bank_transfer_instructions =
+            payments::BankTransferInstructions::VirtualAccountInstructions(Box::new(
+                payments::VirtualAccountTransferInstructions {
+                    reference: self.reference.clone().map(Secret::new),
+                    ..virtual_account
+                },
+            ));
+        Some(
+            payments::ConnectorNextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details: payments::BankTransferNextStepsData {
+                    bank_transfer_instructions,
+                    receiver: Some(payments::ReceiverDetails {
+                        amount_received: amount - self.amount_remaining,
+                        amount_charged: None,
+                        amount_remaining: Some(self.amount_remaining),
+                    }),
+                },
+            },
+        )
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct StripeCashappQrResponse {
     pub mobile_auth_url: Url,
@@ -2620,11 +2953,68 @@ pub struct QrCodeResponse {
 pub struct StripeFinanicalInformation {
     pub iban: Option<SepaFinancialDetails>,
     pub sort_code: Option<BacsFinancialDetails>,
+    pub aba: Option<AbaFinancialDetails>,
+    pub spei: Option<SpeiFinancialDetails>,
+    pub zengin: Option<ZenginFinancialDetails>,
     pub supported_networks: Vec<String>,
     #[serde(rename = "type")]
     pub financial_info_type: String,
 }
 
+impl StripeFinanicalInformation {
+    fn get_virtual_account_instructions(
+        &self,
+    ) -> Option<payments::VirtualAccountTransferInstructions> {
+        let virtual_account =
+            |bank_name: &String, account_number: &String, routing_number: &String| {
+                payments::VirtualAccountTransferInstructions {
+                    bank_name: Some(bank_name.to_owned()),
+                    account_number: Some(Secret::new(account_number.to_owned())),
+                    iban: None,
+                    routing_number: Some(Secret::new(routing_number.to_owned())),
+                    swift_code: None,
+                    reference: None,
+                    expires_at: None,
+                }
+            };
+        self.aba
+            .as_ref()
+            .map(|aba| virtual_account(&aba.bank_name, &aba.account_number, &aba.routing_number))
+            .or(self
+                .spei
+                .as_ref()
+                .map(|spei| virtual_account(&spei.bank_name, &spei.clabe, &spei.bank_code)))
+            .or(self.zengin.as_ref().map(|zengin| {
+                virtual_account(
+                    &zengin.bank_name,
+                    &zengin.account_number,
+                    &zengin.branch_code,
+                )
+            }))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AbaFinancialDetails {
+    pub account_number: String,
+    pub bank_name: String,
+    pub routing_number: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SpeiFinancialDetails {
+    pub bank_code: String,
+    pub bank_name: String,
+    pub clabe: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ZenginFinancialDetails {
+    pub account_number: String,
+    pub bank_name: String,
+    pub branch_code: String,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct SepaFinancialDetails {
     pub account_holder_name: String,
@@ -2647,8 +3037,12 @@ pub struct BacsFinancialDetails {
 pub struct RefundRequest {
     pub amount: Option<i64>, //amount in cents, hence passed as integer
     pub payment_intent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
     #[serde(flatten)]
     pub meta_data: StripeMetadata,
+    #[serde(flatten)]
+    pub split_refund: StripeSplitRefundRequest,
 }
 
 impl<F> TryFrom<&types::RefundsRouterData<F>> for RefundRequest {
@@ -2659,10 +3053,12 @@ impl<F> TryFrom<&types::RefundsRouterData<F>> for RefundRequest {
         Ok(Self {
             amount: Some(amount),
             payment_intent,
+            reason: item.request.get_refund_reason(),
             meta_data: StripeMetadata {
                 order_id: item.request.refund_id.clone(),
                 is_refund_id_as_reference: Some("true".to_string()),
             },
+            split_refund: StripeSplitRefundRequest::from(&item.request),
         })
     }
 }
@@ -2712,6 +3108,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -2729,6 +3126,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -2920,7 +3318,8 @@ impl TryFrom<&types::PaymentsPreProcessingRouterData> for StripeCreditTransferSo
                     | payments::BankTransferData::DanamonVaBankTransfer { .. }
                     | payments::BankTransferData::MandiriVaBankTransfer { .. }
                     | payments::BankTransferData::Pix { .. }
-                    | payments::BankTransferData::Pse { .. } => {
+                    | payments::BankTransferData::Pse { .. }
+                    | payments::BankTransferData::VirtualAccountBankTransfer { .. } => {
                         Err(errors::ConnectorError::NotImplemented(
                             connector_util::get_unimplemented_payment_method_error_message(
                                 "stripe",
@@ -3022,6 +3421,10 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, ChargesResponse, T, types::Payme
                 connector_metadata: Some(connector_metadata),
                 network_txn_id: None,
                 connector_response_reference_id: Some(item.response.id),
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -3062,6 +3465,69 @@ impl<F, T>
     }
 }
 
+/// Events sent to the registered webhook endpoint, all the events are sent and the ones which are
+/// not handled are acknowledged by the webhooks flow
+const WEBHOOK_ENDPOINT_ENABLED_EVENTS: &str = "*";
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct StripeWebhookEndpointRequest {
+    pub url: String,
+    #[serde(rename = "enabled_events[0]")]
+    pub enabled_events: String,
+}
+
+impl From<&types::WebhookEndpointRequestData> for StripeWebhookEndpointRequest {
+    fn from(item: &types::WebhookEndpointRequestData) -> Self {
+        Self {
+            url: item.webhook_url.clone(),
+            enabled_events: WEBHOOK_ENDPOINT_ENABLED_EVENTS.to_string(),
+        }
+    }
+}
+
+/// Response of creating, updating and deleting a webhook endpoint, the secret is returned only
+/// when the endpoint is created
+#[derive(Debug, Deserialize)]
+pub struct StripeWebhookEndpointResponse {
+    pub id: String,
+    pub secret: Option<Secret<String>>,
+}
+
+impl From<StripeWebhookEndpointResponse> for types::WebhookEndpointResponseData {
+    fn from(item: StripeWebhookEndpointResponse) -> Self {
+        Self {
+            connector_webhook_endpoint_id: item.id,
+            webhook_secret: item.secret,
+        }
+    }
+}
+
+impl<F, T>
+    TryFrom<
+        types::ResponseRouterData<
+            F,
+            StripeWebhookEndpointResponse,
+            T,
+            types::WebhookEndpointResponseData,
+        >,
+    > for types::RouterData<F, T, types::WebhookEndpointResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            StripeWebhookEndpointResponse,
+            T,
+            types::WebhookEndpointResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(item.response.into()),
+            ..item.data
+        })
+    }
+}
+
 // #[cfg(test)]
 // mod test_stripe_transformers {
 //     use super::*;
@@ -3231,6 +3697,10 @@ pub enum WebhookEventType {
     SourceTransactionCreated,
     #[serde(rename = "payment_intent.partially_funded")]
     PaymentIntentPartiallyFunded,
+    #[serde(rename = "radar.early_fraud_warning.created")]
+    EarlyFraudWarningCreated,
+    #[serde(rename = "radar.early_fraud_warning.updated")]
+    EarlyFraudWarningUpdated,
     #[serde(other)]
     Unknown,
 }
@@ -3265,6 +3735,38 @@ pub struct EvidenceDetails {
     pub due_by: PrimitiveDateTime,
 }
 
+/// Early fraud warning, sent when the issuer reports a charge as fraudulent to the card network
+/// ahead of a dispute
+#[derive(Debug, Deserialize)]
+pub struct StripeEarlyFraudWarning {
+    pub id: String,
+    pub fraud_type: String,
+    pub payment_intent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeEarlyFraudWarningData {
+    pub object: StripeEarlyFraudWarning,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeEarlyFraudWarningWebhook {
+    pub data: StripeEarlyFraudWarningData,
+}
+
+impl From<StripeEarlyFraudWarning> for api::AlertPayload {
+    fn from(early_fraud_warning: StripeEarlyFraudWarning) -> Self {
+        Self {
+            connector_alert_id: early_fraud_warning.id,
+            alert_type: api_enums::AlertType::Fraud,
+            initiated_by: Some("issuer".to_string()),
+            connector_reason: Some(early_fraud_warning.fraud_type),
+            connector_transaction_id: early_fraud_warning.payment_intent,
+            acquirer_reference_number: None,
+        }
+    }
+}
+
 impl
     TryFrom<(
         api::PaymentMethodData,
@@ -3347,7 +3849,8 @@ impl
                     | payments::BankTransferData::BriVaBankTransfer { .. }
                     | payments::BankTransferData::CimbVaBankTransfer { .. }
                     | payments::BankTransferData::DanamonVaBankTransfer { .. }
-                    | payments::BankTransferData::MandiriVaBankTransfer { .. } => {
+                    | payments::BankTransferData::MandiriVaBankTransfer { .. }
+                    | payments::BankTransferData::VirtualAccountBankTransfer { .. } => {
                         Err(errors::ConnectorError::NotImplemented(
                             connector_util::get_unimplemented_payment_method_error_message(
                                 "stripe",
@@ -3585,6 +4088,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3607,6 +4111,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3632,6 +4137,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3657,6 +4163,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3681,6 +4188,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3707,6 +4215,7 @@ mod test_validate_shipping_address_against_payment_method {
         let result = validate_shipping_address_against_payment_method(
             &stripe_shipping_address,
             Some(payment_method),
+            false,
         );
 
         // Assert
@@ -3717,6 +4226,23 @@ mod test_validate_shipping_address_against_payment_method {
         }
     }
 
+    #[test]
+    fn should_return_ok_for_digital_goods_without_shipping_address() {
+        // Arrange
+        let stripe_shipping_address = create_stripe_shipping_address(None, None, None, None);
+        let payment_method = &StripePaymentMethodType::AfterpayClearpay;
+
+        //Act
+        let result = validate_shipping_address_against_payment_method(
+            &stripe_shipping_address,
+            Some(payment_method),
+            true,
+        );
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
     fn get_missing_fields(connector_error: &errors::ConnectorError) -> Vec<&'static str> {
         if let errors::ConnectorError::MissingRequiredFields { field_names } = connector_error {
             return field_names.to_vec();
@@ -3743,3 +4269,553 @@ mod test_validate_shipping_address_against_payment_method {
         }
     }
 }
+
+#[cfg(test)]
+mod test_external_authentication_data {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+
+    use super::{Auth3ds, PaymentIntentRequest, StripePaymentMethodData};
+    use crate::types::test_utils::get_authorize_router_data;
+
+    #[test]
+    fn should_request_three_ds_without_external_authentication_data() {
+        let router_data = get_authorize_router_data(None);
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        match request.payment_data {
+            Some(StripePaymentMethodData::Card(card)) => {
+                assert_eq!(card.payment_method_auth_type, Auth3ds::Any);
+                assert_eq!(card.three_d_secure_cryptogram, None);
+            }
+            _ => panic!("expected card payment method data"),
+        }
+    }
+
+    #[test]
+    fn should_pass_external_authentication_data_and_skip_challenge() {
+        let router_data =
+            get_authorize_router_data(Some(api_models::payments::ExternalAuthenticationData {
+                cavv: Secret::new("AAABBEg0VhI0VniQEjRWAAAAAAA=".to_string()),
+                eci: "05".to_string(),
+                ds_transaction_id: Some("f25084f0-5b16-4c0a-ae5d-b24808a95e4b".to_string()),
+                message_version: "2.2.0".to_string(),
+                threeds_server_transaction_id: None,
+            }));
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        match request.payment_data {
+            Some(StripePaymentMethodData::Card(card)) => {
+                assert_eq!(card.payment_method_auth_type, Auth3ds::Automatic);
+                assert_eq!(
+                    card.three_d_secure_cryptogram,
+                    Some(Secret::new("AAABBEg0VhI0VniQEjRWAAAAAAA=".to_string()))
+                );
+                assert_eq!(
+                    card.three_d_secure_electronic_commerce_indicator,
+                    Some("05".to_string())
+                );
+                assert_eq!(
+                    card.three_d_secure_transaction_id,
+                    Some("f25084f0-5b16-4c0a-ae5d-b24808a95e4b".to_string())
+                );
+                assert_eq!(card.three_d_secure_version, Some("2.2.0".to_string()));
+            }
+            _ => panic!("expected card payment method data"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_order_risk_data {
+    #![allow(clippy::unwrap_used)]
+    use api_models::enums::{ProductType, ShippingMethod};
+    use common_utils::pii::Email;
+    use masking::Secret;
+
+    use super::{PaymentIntentRequest, StripeOrderRiskData};
+    use crate::types::{self, api, test_utils::get_authorize_router_data};
+
+    fn get_afterpay_router_data(
+        shipping_method: Option<ShippingMethod>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.request.payment_method_data = api::PaymentMethodData::PayLater(
+            api_models::payments::PayLaterData::AfterpayClearpayRedirect {
+                billing_email: Email::try_from("john.doe@example.com".to_string()).unwrap(),
+                billing_name: Secret::new("John Doe".to_string()),
+            },
+        );
+        router_data.request.shipping_method = shipping_method;
+        router_data.request.order_details =
+            Some(vec![api_models::payments::OrderDetailsWithAmount {
+                product_name: "ebook".to_string(),
+                quantity: 1,
+                amount: 1000,
+                product_type: Some(ProductType::Digital),
+                commodity_code: None,
+            }]);
+        router_data
+    }
+
+    #[test]
+    fn should_not_require_shipping_address_for_digital_goods() {
+        let router_data = get_afterpay_router_data(Some(ShippingMethod::Digital));
+        assert!(PaymentIntentRequest::try_from(&router_data).is_ok());
+
+        // Line items which are all digital are delivered digitally when no shipping method is passed
+        let router_data = get_afterpay_router_data(None);
+        assert!(PaymentIntentRequest::try_from(&router_data).is_ok());
+    }
+
+    #[test]
+    fn should_require_shipping_address_for_shipped_goods() {
+        let router_data = get_afterpay_router_data(Some(ShippingMethod::Standard));
+        assert!(PaymentIntentRequest::try_from(&router_data).is_err());
+    }
+
+    #[test]
+    fn should_pass_order_details_as_metadata() {
+        let mut request = get_afterpay_router_data(Some(ShippingMethod::Express)).request;
+        request.order_category = Some("books".to_string());
+        request.estimated_delivery_date = Some(time::macros::datetime!(2023-10-20 12:00));
+        request.order_details.as_mut().unwrap().extend([
+            api_models::payments::OrderDetailsWithAmount {
+                product_name: "paperback".to_string(),
+                quantity: 1,
+                amount: 1500,
+                product_type: Some(ProductType::Physical),
+                commodity_code: None,
+            },
+            api_models::payments::OrderDetailsWithAmount {
+                product_name: "audiobook".to_string(),
+                quantity: 1,
+                amount: 500,
+                product_type: Some(ProductType::Digital),
+                commodity_code: None,
+            },
+        ]);
+
+        assert_eq!(
+            StripeOrderRiskData::from(&request),
+            StripeOrderRiskData {
+                order_category: Some("books".to_string()),
+                shipping_method: Some(ShippingMethod::Express),
+                estimated_delivery_date: Some("2023-10-20".to_string()),
+                product_types: Some("digital,physical".to_string()),
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_split_payment {
+    #![allow(clippy::unwrap_used)]
+    use super::{
+        api_enums, PaymentIntentRequest, StripeSplitPaymentRequest, StripeSplitRefundRequest,
+    };
+    use crate::types::{self, test_utils::get_authorize_router_data};
+
+    fn get_split_refund_data(
+        fee_refund_behaviour: api_enums::PlatformFeeRefundBehaviour,
+        platform_fee_refund_amount: i64,
+    ) -> types::RefundsData {
+        types::RefundsData {
+            refund_id: "ref_1".to_string(),
+            connector_transaction_id: "pi_1".to_string(),
+            connector_refund_id: None,
+            currency: api_enums::Currency::USD,
+            payment_amount: 1000,
+            reason: None,
+            webhook_url: None,
+            refund_amount: 1000,
+            connector_metadata: None,
+            split_refund: Some(types::SplitRefundData {
+                platform_fee_refund_amount,
+                fee_refund_behaviour,
+            }),
+        }
+    }
+
+    #[test]
+    fn should_pass_platform_fee_as_application_fee() {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.request.split_payment = Some(api_models::payments::SplitPaymentDetails {
+            platform_fee_amount: 25,
+            destination_account: "acct_1032D82eZvKYlo2C".to_string(),
+            fee_refund_behaviour: api_enums::PlatformFeeRefundBehaviour::Proportional,
+        });
+
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        assert_eq!(request.amount, 1000);
+        assert_eq!(
+            request.split_payment,
+            StripeSplitPaymentRequest {
+                application_fee_amount: Some(25),
+                transfer_destination: Some("acct_1032D82eZvKYlo2C".to_string()),
+            }
+        );
+
+        router_data.request.split_payment = None;
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        assert_eq!(request.split_payment, StripeSplitPaymentRequest::default());
+    }
+
+    #[test]
+    fn should_reverse_transfer_and_application_fee_on_refund() {
+        // Full refund of the payment, reversing the whole platform fee
+        let refund_data =
+            get_split_refund_data(api_enums::PlatformFeeRefundBehaviour::Proportional, 25);
+        assert_eq!(
+            StripeSplitRefundRequest::from(&refund_data),
+            StripeSplitRefundRequest {
+                refund_application_fee: Some(true),
+                reverse_transfer: Some(true),
+            }
+        );
+
+        let refund_data = get_split_refund_data(api_enums::PlatformFeeRefundBehaviour::Retain, 0);
+        assert_eq!(
+            StripeSplitRefundRequest::from(&refund_data),
+            StripeSplitRefundRequest {
+                refund_application_fee: Some(false),
+                reverse_transfer: Some(true),
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_next_action {
+    #![allow(clippy::unwrap_used)]
+    use super::{
+        enums, get_connector_metadata, payments, StripeNextActionResponse, StripePaymentStatus,
+    };
+
+    #[test]
+    fn should_emit_qr_code_next_action_for_wechat_pay() {
+        let next_action: StripeNextActionResponse = serde_json::from_value(serde_json::json!({
+            "type": "wechat_pay_display_qr_code",
+            "wechat_pay_display_qr_code": {
+                "data": "weixin://wxpay/bizpayurl?pr=abc123",
+                "image_data_url": "https://example.com/qr.png"
+            }
+        }))
+        .unwrap();
+
+        let connector_metadata = get_connector_metadata(Some(&next_action), 1000)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            connector_metadata,
+            serde_json::json!({
+                "qr_code_information": {
+                    "image_data_url": "https://example.com/qr.png",
+                    "qr_code_data": "weixin://wxpay/bizpayurl?pr=abc123",
+                    "display_to_timestamp": null
+                }
+            })
+        );
+        assert!(next_action.get_url().is_none());
+    }
+
+    fn get_us_bank_transfer_next_action(amount_remaining: i64) -> StripeNextActionResponse {
+        serde_json::from_value(serde_json::json!({
+            "type": "display_bank_transfer_instructions",
+            "display_bank_transfer_instructions": {
+                "amount_remaining": amount_remaining,
+                "currency": "usd",
+                "financial_addresses": [{
+                    "aba": {
+                        "account_number": "1234567890",
+                        "bank_name": "Test Bank",
+                        "routing_number": "110000000"
+                    },
+                    "supported_networks": ["ach", "domestic_wire_us"],
+                    "type": "aba"
+                }],
+                "hosted_instructions_url": null,
+                "reference": "PAYREF1234",
+                "type": "us_bank_transfer"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn should_emit_virtual_account_next_action_for_us_bank_transfer() {
+        let next_action = get_us_bank_transfer_next_action(1000);
+
+        let connector_metadata = get_connector_metadata(Some(&next_action), 1000)
+            .unwrap()
+            .unwrap();
+        let next_action: payments::ConnectorNextActionData =
+            serde_json::from_value(connector_metadata).unwrap();
+
+        assert!(matches!(
+            next_action,
+            payments::ConnectorNextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details: payments::BankTransferNextStepsData {
+                    bank_transfer_instructions:
+                        payments::BankTransferInstructions::VirtualAccountInstructions(ref instructions),
+                    receiver: Some(payments::ReceiverDetails {
+                        amount_received: 0,
+                        ..
+                    }),
+                },
+            } if instructions.bank_name.as_deref() == Some("Test Bank")
+        ));
+    }
+
+    #[test]
+    fn should_track_amount_received_for_partially_funded_transfer() {
+        let next_action = get_us_bank_transfer_next_action(400);
+
+        let connector_metadata = get_connector_metadata(Some(&next_action), 1000)
+            .unwrap()
+            .unwrap();
+        let next_action: payments::ConnectorNextActionData =
+            serde_json::from_value(connector_metadata).unwrap();
+
+        assert!(matches!(
+            next_action,
+            payments::ConnectorNextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details: payments::BankTransferNextStepsData {
+                    receiver: Some(payments::ReceiverDetails {
+                        amount_received: 600,
+                        amount_remaining: Some(400),
+                        ..
+                    }),
+                    ..
+                },
+            }
+        ));
+    }
+
+    #[test]
+    fn should_move_funded_transfer_from_pending_to_charged() {
+        let pending: StripePaymentStatus =
+            serde_json::from_value(serde_json::json!("requires_action")).unwrap();
+        let funded: StripePaymentStatus =
+            serde_json::from_value(serde_json::json!("succeeded")).unwrap();
+
+        assert_eq!(
+            enums::AttemptStatus::from(pending),
+            enums::AttemptStatus::AuthenticationPending
+        );
+        assert_eq!(
+            enums::AttemptStatus::from(funded),
+            enums::AttemptStatus::Charged
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_webhook_endpoint {
+    #![allow(clippy::unwrap_used)]
+    use masking::PeekInterface;
+
+    use super::{StripeWebhookEndpointRequest, StripeWebhookEndpointResponse};
+    use crate::{types, utils};
+
+    #[test]
+    fn should_encode_webhook_endpoint_request() {
+        let request = StripeWebhookEndpointRequest::from(&types::WebhookEndpointRequestData {
+            webhook_url: "https://sandbox.hyperswitch.io/webhooks/merchant_1/stripe".to_string(),
+            connector_webhook_endpoint_id: None,
+        });
+
+        let encoded = utils::Encode::<StripeWebhookEndpointRequest>::url_encode(&request).unwrap();
+
+        assert_eq!(
+            encoded,
+            "url=https%3A%2F%2Fsandbox.hyperswitch.io%2Fwebhooks%2Fmerchant_1%2Fstripe&enabled_events%5B0%5D=*"
+        );
+    }
+
+    #[test]
+    fn should_take_secret_of_created_webhook_endpoint() {
+        let response: StripeWebhookEndpointResponse = serde_json::from_value(serde_json::json!({
+            "id": "we_1NnmKq2eZvKYlo2C0jVbwqsv",
+            "object": "webhook_endpoint",
+            "enabled_events": ["*"],
+            "secret": "whsec_test_secret",
+            "status": "enabled",
+            "url": "https://sandbox.hyperswitch.io/webhooks/merchant_1/stripe"
+        }))
+        .unwrap();
+
+        let response = types::WebhookEndpointResponseData::from(response);
+
+        assert_eq!(
+            response.connector_webhook_endpoint_id,
+            "we_1NnmKq2eZvKYlo2C0jVbwqsv"
+        );
+        assert_eq!(response.webhook_secret.unwrap().peek(), "whsec_test_secret");
+    }
+
+    #[test]
+    fn should_parse_deleted_webhook_endpoint() {
+        let response: StripeWebhookEndpointResponse = serde_json::from_value(serde_json::json!({
+            "id": "we_1NnmKq2eZvKYlo2C0jVbwqsv",
+            "object": "webhook_endpoint",
+            "deleted": true
+        }))
+        .unwrap();
+
+        let response = types::WebhookEndpointResponseData::from(response);
+
+        assert_eq!(
+            response.connector_webhook_endpoint_id,
+            "we_1NnmKq2eZvKYlo2C0jVbwqsv"
+        );
+        assert!(response.webhook_secret.is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_merchant_metadata {
+    #![allow(clippy::unwrap_used)]
+    use std::collections::{HashMap, HashSet};
+
+    use masking::Secret;
+
+    use super::PaymentIntentRequest;
+    use crate::{
+        configs::settings::MerchantMetadataConfig, core::metadata,
+        types::test_utils::get_authorize_router_data, utils,
+    };
+
+    #[test]
+    fn should_pass_only_allowed_metadata_keys() {
+        let config = MerchantMetadataConfig {
+            connector_pass_through_keys: HashMap::from([(
+                "stripe".to_string(),
+                HashSet::from([
+                    "order_reference".to_string(),
+                    "cart".to_string(),
+                    "order_id".to_string(),
+                ]),
+            )]),
+            ..Default::default()
+        };
+        let payment_metadata = Secret::new(serde_json::json!({
+            "order_reference": "ord_123",
+            "cart": { "items": 2 },
+            "order_id": "overridden",
+            "internal_notes": "not for the connector",
+        }));
+        let mut router_data = get_authorize_router_data(None);
+        router_data.request.metadata = metadata::get_connector_pass_through_metadata(
+            &config,
+            "stripe",
+            Some(&payment_metadata),
+        );
+
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        let encoded = utils::Encode::<PaymentIntentRequest>::url_encode(&request).unwrap();
+
+        assert!(encoded.contains("metadata%5Border_reference%5D=ord_123"));
+        assert!(encoded.contains("metadata%5Bcart%5D=%7B%22items%22%3A2%7D"));
+        assert!(encoded.contains("metadata%5Border_id%5D=pay_1_1"));
+        assert!(!encoded.contains("overridden"));
+        assert!(!encoded.contains("internal_notes"));
+    }
+}
+
+#[cfg(test)]
+mod test_early_fraud_warning {
+    #![allow(clippy::unwrap_used)]
+
+    use super::{StripeEarlyFraudWarningWebhook, WebhookEventType, WebhookEventTypeBody};
+    use crate::types::api;
+
+    #[test]
+    fn should_parse_early_fraud_warning_as_alert() {
+        let body = serde_json::json!({
+            "id": "evt_1NnrwIBw2dPENLoiDf8AvC4u",
+            "object": "event",
+            "type": "radar.early_fraud_warning.created",
+            "data": {
+                "object": {
+                    "id": "issfr_1NnrwHBw2dPENLoi9lnhV3RQ",
+                    "object": "radar.early_fraud_warning",
+                    "actionable": true,
+                    "charge": "ch_3NnrvyBw2dPENLoi0UJZ3Nqf",
+                    "created": 1694181525,
+                    "fraud_type": "made_with_stolen_card",
+                    "livemode": false,
+                    "payment_intent": "pi_3NnrvyBw2dPENLoi0xsH3vKf"
+                }
+            }
+        });
+
+        let event_type: WebhookEventTypeBody = serde_json::from_value(body.clone()).unwrap();
+        assert!(matches!(
+            event_type.event_type,
+            WebhookEventType::EarlyFraudWarningCreated
+        ));
+
+        let webhook: StripeEarlyFraudWarningWebhook = serde_json::from_value(body).unwrap();
+        let alert = api::AlertPayload::from(webhook.data.object);
+
+        assert_eq!(alert.connector_alert_id, "issfr_1NnrwHBw2dPENLoi9lnhV3RQ");
+        assert_eq!(alert.alert_type, api_models::enums::AlertType::Fraud);
+        assert_eq!(
+            alert.connector_reason.as_deref(),
+            Some("made_with_stolen_card")
+        );
+        assert_eq!(
+            alert.connector_transaction_id.as_deref(),
+            Some("pi_3NnrvyBw2dPENLoi0xsH3vKf")
+        );
+        assert!(alert.acquirer_reference_number.is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_request_validation {
+    #![allow(clippy::unwrap_used)]
+    use crate::{
+        connector::Stripe,
+        core::errors,
+        services::ConnectorIntegration,
+        types::{api, test_utils::get_authorize_router_data},
+    };
+
+    #[test]
+    fn should_reject_long_statement_descriptor_before_sending() {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.request.statement_descriptor = Some("HYPERSWITCH ONLINE STORE".to_string());
+
+        let error =
+            ConnectorIntegration::<api::Authorize, _, _>::get_request_body(&Stripe, &router_data)
+                .unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::RequestValidationFailed {
+                field: "statement_descriptor",
+                constraint: "must be at most 22 characters long".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_statement_descriptor_suffix_with_reserved_characters() {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.request.statement_descriptor = Some("HYPERSWITCH".to_string());
+        router_data.request.statement_descriptor_suffix = Some("ORDER *42".to_string());
+
+        let error =
+            ConnectorIntegration::<api::Authorize, _, _>::get_request_body(&Stripe, &router_data)
+                .unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::RequestValidationFailed {
+                field: "statement_descriptor_suffix",
+                ..
+            }
+        ));
+    }
+}
@@ -10,7 +10,8 @@ use transformers as trustpay;
 
 use super::utils::{
     collect_and_sort_values_by_removing_signature, get_error_code_error_message_based_on_priority,
-    ConnectorErrorType, ConnectorErrorTypeMapping, PaymentsPreProcessingData,
+    request_validation::ValidateConnectorRequest, ConnectorErrorType, ConnectorErrorTypeMapping,
+    PaymentsPreProcessingData,
 };
 use crate::{
     configs::settings,
@@ -545,6 +546,7 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
             req,
         ))?;
         let connector_req = trustpay::TrustpayPaymentsRequest::try_from(&connector_router_data)?;
+        connector_req.validate_request()?;
         let trustpay_req_string = match req.payment_method {
             diesel_models::enums::PaymentMethod::BankRedirect => {
                 types::RequestBody::log_and_get_request_body(
@@ -12,7 +12,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     connector::utils::{
-        self, AddressDetailsData, BrowserInformationData, CardData, PaymentsAuthorizeRequestData,
+        self,
+        request_validation::{self, ValidateConnectorRequest},
+        AddressDetailsData, BrowserInformationData, CardData, PaymentsAuthorizeRequestData,
         PaymentsPreProcessingData, RouterData,
     },
     consts,
@@ -220,6 +222,31 @@ pub enum TrustpayPaymentsRequest {
     BankRedirectPaymentRequest(Box<PaymentRequestBankRedirect>),
 }
 
+// Trustpay rejects card payments with a descriptor longer than 127 characters and accepts only
+// plain and preauthorized card payments
+const CARDS_DESCRIPTOR_MAX_LENGTH: usize = 127;
+const CARDS_PAYMENT_TYPES: &[&str] = &["Plain", "Preauth"];
+
+impl ValidateConnectorRequest for TrustpayPaymentsRequest {
+    fn validate_request(&self) -> Result<(), Error> {
+        match self {
+            Self::CardsPaymentRequest(request) => {
+                request_validation::max_len(
+                    "descriptor",
+                    request.descriptor.as_deref(),
+                    CARDS_DESCRIPTOR_MAX_LENGTH,
+                )?;
+                request_validation::one_of(
+                    "payment_type",
+                    Some(request.payment_type.as_str()),
+                    CARDS_PAYMENT_TYPES,
+                )
+            }
+            Self::BankRedirectPaymentRequest(_) => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Eq, PartialEq)]
 pub struct TrustpayMandatoryParams {
     pub billing_city: String,
@@ -366,7 +393,7 @@ fn get_bank_redirection_request_data(
                 debtor: get_debtor_info(item, pm, params)?,
             },
             callback_urls: CallbackURLs {
-                success: format!("{return_url}?status=SuccessOk"),
+                success: utils::append_query_param(&return_url, "status", "SuccessOk"),
                 cancel: return_url.clone(),
                 error: return_url,
             },
@@ -697,6 +724,10 @@ fn handle_cards_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payment_response_data))
 }
@@ -725,6 +756,10 @@ fn handle_bank_redirects_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payment_response_data))
 }
@@ -755,6 +790,10 @@ fn handle_bank_redirects_error_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payment_response_data))
 }
@@ -795,6 +834,10 @@ fn handle_bank_redirects_sync_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, error, payment_response_data))
 }
@@ -817,6 +860,10 @@ pub fn handle_webhook_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     };
     Ok((status, None, payment_response_data))
 }
@@ -1386,6 +1433,7 @@ fn handle_cards_refund_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.instance_id,
         refund_status,
+        acquirer_reference_number: None,
     };
     Ok((error, refund_response_data))
 }
@@ -1401,6 +1449,7 @@ fn handle_webhooks_refund_response(
             .payment_request_id
             .ok_or(errors::ConnectorError::MissingConnectorRefundID)?,
         refund_status,
+        acquirer_reference_number: None,
     };
     Ok((None, refund_response_data))
 }
@@ -1424,6 +1473,7 @@ fn handle_bank_redirects_refund_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.payment_request_id.to_string(),
         refund_status,
+        acquirer_reference_number: None,
     };
     (error, refund_response_data)
 }
@@ -1451,6 +1501,7 @@ fn handle_bank_redirects_refund_sync_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.payment_information.references.payment_request_id,
         refund_status,
+        acquirer_reference_number: None,
     };
     (error, refund_response_data)
 }
@@ -1470,6 +1521,7 @@ fn handle_bank_redirects_refund_sync_error_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: "".to_string(),
         refund_status: enums::RefundStatus::Failure,
+        acquirer_reference_number: None,
     };
     (error, refund_response_data)
 }
@@ -3,7 +3,7 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{CardData, PaymentsAuthorizeRequestData, RefundsRequestData},
+    connector::utils::{self, CardData, PaymentsAuthorizeRequestData, RefundsRequestData},
     core::errors,
     types::{
         self, api,
@@ -198,6 +198,10 @@ fn get_payments_response(connector_response: TsysResponse) -> types::PaymentsRes
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     }
 }
 
@@ -216,6 +220,10 @@ fn get_payments_sync_response(
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     }
 }
 
@@ -231,21 +239,21 @@ impl<F, T>
             TsysPaymentsResponse::AuthResponse(resp) => match resp {
                 TsysResponseTypes::SuccessResponse(auth_response) => (
                     Ok(get_payments_response(auth_response)),
-                    enums::AttemptStatus::Authorized,
+                    utils::map_capture_aware_status(true, false),
                 ),
                 TsysResponseTypes::ErrorResponse(connector_error_response) => (
                     Err(get_error_response(connector_error_response, item.http_code)),
-                    enums::AttemptStatus::AuthorizationFailed,
+                    utils::map_capture_aware_status(false, false),
                 ),
             },
             TsysPaymentsResponse::SaleResponse(resp) => match resp {
                 TsysResponseTypes::SuccessResponse(sale_response) => (
                     Ok(get_payments_response(sale_response)),
-                    enums::AttemptStatus::Charged,
+                    utils::map_capture_aware_status(true, true),
                 ),
                 TsysResponseTypes::ErrorResponse(connector_error_response) => (
                     Err(get_error_response(connector_error_response, item.http_code)),
-                    enums::AttemptStatus::Failure,
+                    utils::map_capture_aware_status(false, true),
                 ),
             },
             TsysPaymentsResponse::CaptureResponse(resp) => match resp {
@@ -489,6 +497,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             TsysResponseTypes::SuccessResponse(return_response) => Ok(types::RefundsResponseData {
                 connector_refund_id: return_response.transaction_id,
                 refund_status: enums::RefundStatus::from(return_response.status),
+                acquirer_reference_number: None,
             }),
             TsysResponseTypes::ErrorResponse(connector_error_response) => {
                 Err(get_error_response(connector_error_response, item.http_code))
@@ -528,6 +537,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, TsysSyncResponse>>
                 Ok(types::RefundsResponseData {
                     connector_refund_id: search_response.transaction_details.transaction_id.clone(),
                     refund_status: enums::RefundStatus::from(search_response.transaction_details),
+                    acquirer_reference_number: None,
                 })
             }
             SearchResponseTypes::ErrorResponse(connector_error_response) => {
@@ -1,3 +1,14 @@
+pub mod cancellation_reason;
+pub mod capabilities;
+pub mod connector_reference;
+pub mod crypto;
+pub mod device_data_collection;
+pub mod metadata_schema;
+pub mod refund_reason;
+pub mod request_validation;
+pub mod response_parsing;
+pub mod wallet_session_config;
+
 use std::collections::HashMap;
 
 use api_models::{
@@ -65,10 +76,14 @@ pub trait RouterData {
     where
         T: serde::de::DeserializeOwned;
     fn is_three_ds(&self) -> bool;
+    fn get_locale_or_default(&self) -> String;
     fn get_payment_method_token(&self) -> Result<types::PaymentMethodToken, Error>;
     fn get_customer_id(&self) -> Result<String, Error>;
     fn get_connector_customer_id(&self) -> Result<String, Error>;
     fn get_preprocessing_id(&self) -> Result<String, Error>;
+    /// Reference of the attempt to be sent to the connector, connectors must use it instead of
+    /// choosing between the payment id and the attempt id
+    fn get_connector_request_reference_id(&self) -> String;
     #[cfg(feature = "payouts")]
     fn get_payout_method_data(&self) -> Result<api::PayoutMethodData, Error>;
     #[cfg(feature = "payouts")]
@@ -137,9 +152,7 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
     where
         T: serde::de::DeserializeOwned,
     {
-        self.get_connector_meta()?
-            .parse_value(std::any::type_name::<T>())
-            .change_context(errors::ConnectorError::NoConnectorMetaData)
+        parse_connector_meta(self.get_connector_meta()?.expose())
     }
 
     fn is_three_ds(&self) -> bool {
@@ -149,6 +162,12 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
         )
     }
 
+    fn get_locale_or_default(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| consts::DEFAULT_LOCALE.to_string())
+    }
+
     fn get_shipping_address(&self) -> Result<&api::AddressDetails, Error> {
         self.address
             .shipping
@@ -176,6 +195,9 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
             .to_owned()
             .ok_or_else(missing_field_err("preprocessing_id"))
     }
+    fn get_connector_request_reference_id(&self) -> String {
+        self.connector_request_reference_id.clone()
+    }
     #[cfg(feature = "payouts")]
     fn get_payout_method_data(&self) -> Result<api::PayoutMethodData, Error> {
         self.payout_method_data
@@ -260,8 +282,11 @@ pub trait PaymentsAuthorizeRequestData {
     fn is_auto_capture(&self) -> Result<bool, Error>;
     fn get_email(&self) -> Result<Email, Error>;
     fn get_browser_info(&self) -> Result<types::BrowserInformation, Error>;
+    fn get_browser_info_or_default(&self) -> Result<types::BrowserInformation, Error>;
+    fn is_moto(&self) -> bool;
     fn get_order_details(&self) -> Result<Vec<OrderDetailsWithAmount>, Error>;
     fn get_card(&self) -> Result<api::Card, Error>;
+    fn get_decrypted_wallet_card(&self) -> Result<&types::DecryptedWalletCard, Error>;
     fn get_return_url(&self) -> Result<String, Error>;
     fn connector_mandate_id(&self) -> Option<String>;
     fn is_mandate_payment(&self) -> bool;
@@ -272,6 +297,31 @@ pub trait PaymentsAuthorizeRequestData {
     fn get_payment_method_type(&self) -> Result<diesel_models::enums::PaymentMethodType, Error>;
     fn get_connector_mandate_id(&self) -> Result<String, Error>;
     fn get_complete_authorize_url(&self) -> Result<String, Error>;
+    fn get_external_authentication_data(
+        &self,
+    ) -> Option<&api_models::payments::ExternalAuthenticationData>;
+    fn get_sca_exemption(&self) -> Option<api_models::enums::ScaExemptionType>;
+    fn get_sub_merchant_data(&self) -> Option<&api_models::payments::SubMerchantData>;
+    fn get_order_category(&self) -> Result<String, Error>;
+    fn get_shipping_method(&self) -> Result<api_models::enums::ShippingMethod, Error>;
+    fn is_digital_goods_order(&self) -> bool;
+    fn get_card_cvc(&self) -> Result<Secret<String>, Error>;
+    fn get_device_session_id(&self) -> Result<String, Error>;
+    fn get_split_payment_data(&self) -> Option<&api_models::payments::SplitPaymentDetails>;
+    fn get_tax_amount(&self) -> Option<i64>;
+    fn get_shipping_cost(&self) -> Option<i64>;
+    fn get_l2_l3_data(&self) -> Option<L2L3Data>;
+}
+
+/// Level 2 and Level 3 data of a card payment, made of the breakdown of its amount and the line
+/// items of the order. All the amounts are in the lowest denomination of the currency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct L2L3Data {
+    pub tax_amount: Option<i64>,
+    pub shipping_cost: Option<i64>,
+    pub duty_amount: Option<i64>,
+    pub discount_amount: Option<i64>,
+    pub line_items: Vec<OrderDetailsWithAmount>,
 }
 
 impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
@@ -290,18 +340,38 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
             .clone()
             .ok_or_else(missing_field_err("browser_info"))
     }
+    /// The browser info is not collected for mail order and telephone order payments, empty
+    /// browser info is used for them in place of failing on the missing field
+    fn get_browser_info_or_default(&self) -> Result<types::BrowserInformation, Error> {
+        match &self.browser_info {
+            Some(browser_info) => Ok(browser_info.clone()),
+            None if self.is_moto() => Ok(types::BrowserInformation::default()),
+            None => Err(missing_field_err("browser_info")()),
+        }
+    }
+    fn is_moto(&self) -> bool {
+        self.payment_channel
+            .map_or(false, |payment_channel| payment_channel.is_moto())
+    }
     fn get_order_details(&self) -> Result<Vec<OrderDetailsWithAmount>, Error> {
         self.order_details
             .clone()
             .ok_or_else(missing_field_err("order_details"))
     }
 
+    /// The card of a wallet payment decrypted for a connector which accepts only cards is a network
+    /// token, which is read with its cryptogram from `get_decrypted_wallet_card` instead
     fn get_card(&self) -> Result<api::Card, Error> {
         match self.payment_method_data.clone() {
-            api::PaymentMethodData::Card(card) => Ok(card),
+            api::PaymentMethodData::Card(card) if self.decrypted_wallet_card.is_none() => Ok(card),
             _ => Err(missing_field_err("card")()),
         }
     }
+    fn get_decrypted_wallet_card(&self) -> Result<&types::DecryptedWalletCard, Error> {
+        self.decrypted_wallet_card
+            .as_ref()
+            .ok_or_else(missing_field_err("decrypted_wallet_card"))
+    }
     fn get_return_url(&self) -> Result<String, Error> {
         self.router_return_url
             .clone()
@@ -314,6 +384,86 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
             .ok_or_else(missing_field_err("complete_authorize_url"))
     }
 
+    fn get_external_authentication_data(
+        &self,
+    ) -> Option<&api_models::payments::ExternalAuthenticationData> {
+        self.external_authentication_data.as_ref()
+    }
+
+    fn get_sca_exemption(&self) -> Option<api_models::enums::ScaExemptionType> {
+        self.sca_exemption
+    }
+
+    fn get_sub_merchant_data(&self) -> Option<&api_models::payments::SubMerchantData> {
+        self.sub_merchant_data.as_ref()
+    }
+
+    fn get_order_category(&self) -> Result<String, Error> {
+        self.order_category
+            .clone()
+            .ok_or_else(missing_field_err("order_category"))
+    }
+
+    fn get_shipping_method(&self) -> Result<api_models::enums::ShippingMethod, Error> {
+        self.shipping_method
+            .ok_or_else(missing_field_err("shipping_method"))
+    }
+
+    /// An order is delivered digitally if the shipping method says so, or if no shipping method is
+    /// passed and every line item of the order is a digital product
+    fn is_digital_goods_order(&self) -> bool {
+        match self.shipping_method {
+            Some(shipping_method) => shipping_method == api_models::enums::ShippingMethod::Digital,
+            None => self.order_details.as_ref().map_or(false, |order_details| {
+                !order_details.is_empty()
+                    && order_details.iter().all(|order| {
+                        order.product_type == Some(api_models::enums::ProductType::Digital)
+                    })
+            }),
+        }
+    }
+    fn get_card_cvc(&self) -> Result<Secret<String>, Error> {
+        self.card_cvc
+            .clone()
+            .ok_or_else(missing_field_err("card_cvc"))
+    }
+
+    /// The session of the device data collection, which is missing if the merchant skipped the
+    /// device data collection for the payment
+    fn get_device_session_id(&self) -> Result<String, Error> {
+        self.device_session_id
+            .clone()
+            .ok_or_else(missing_field_err("device_session_id"))
+    }
+
+    fn get_split_payment_data(&self) -> Option<&api_models::payments::SplitPaymentDetails> {
+        self.split_payment.as_ref()
+    }
+
+    fn get_tax_amount(&self) -> Option<i64> {
+        self.amount_details
+            .as_ref()
+            .and_then(|amount_details| amount_details.tax_amount)
+    }
+
+    fn get_shipping_cost(&self) -> Option<i64> {
+        self.amount_details
+            .as_ref()
+            .and_then(|amount_details| amount_details.shipping_cost)
+    }
+
+    /// The data is only present when the merchant passed the breakdown of the amount, without it
+    /// the payment is processed with its amount alone
+    fn get_l2_l3_data(&self) -> Option<L2L3Data> {
+        self.amount_details.as_ref().map(|amount_details| L2L3Data {
+            tax_amount: amount_details.tax_amount,
+            shipping_cost: amount_details.shipping_cost,
+            duty_amount: amount_details.duty_amount,
+            discount_amount: amount_details.discount_amount,
+            line_items: self.order_details.clone().unwrap_or_default(),
+        })
+    }
+
     fn connector_mandate_id(&self) -> Option<String> {
         self.mandate_id
             .as_ref()
@@ -465,6 +615,12 @@ impl PaymentsCompleteAuthorizeRequestData for types::CompleteAuthorizeData {
 pub trait PaymentsSyncRequestData {
     fn is_auto_capture(&self) -> Result<bool, Error>;
     fn get_connector_transaction_id(&self) -> CustomResult<String, errors::ConnectorError>;
+    fn get_encoded_data(&self) -> Result<String, Error>;
+    fn get_connector_metadata_as<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned;
+    fn get_capture_id(&self) -> Result<String, Error>;
+    fn get_pending_connector_capture_ids(&self) -> Vec<String>;
 }
 
 impl PaymentsSyncRequestData for types::PaymentsSyncData {
@@ -486,12 +642,40 @@ impl PaymentsSyncRequestData for types::PaymentsSyncData {
             .change_context(errors::ConnectorError::MissingConnectorTransactionID)?,
         }
     }
+    fn get_encoded_data(&self) -> Result<String, Error> {
+        self.encoded_data
+            .clone()
+            .ok_or_else(missing_field_err("encoded_data"))
+    }
+    fn get_connector_metadata_as<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        to_connector_meta(self.connector_meta.clone())
+    }
+    fn get_capture_id(&self) -> Result<String, Error> {
+        self.capture_id
+            .clone()
+            .ok_or_else(missing_field_err("capture_id"))
+    }
+    fn get_pending_connector_capture_ids(&self) -> Vec<String> {
+        match &self.sync_type {
+            types::SyncRequestType::MultipleCaptureSync(pending_capture_ids) => {
+                pending_capture_ids.clone()
+            }
+            types::SyncRequestType::SinglePaymentSync => vec![],
+        }
+    }
 }
 
 pub trait PaymentsCancelRequestData {
     fn get_amount(&self) -> Result<i64, Error>;
     fn get_currency(&self) -> Result<diesel_models::enums::Currency, Error>;
     fn get_cancellation_reason(&self) -> Result<String, Error>;
+    fn get_connector_metadata_as<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned;
+    fn get_webhook_url(&self) -> Result<String, Error>;
 }
 
 impl PaymentsCancelRequestData for PaymentsCancelData {
@@ -506,11 +690,31 @@ impl PaymentsCancelRequestData for PaymentsCancelData {
             .clone()
             .ok_or_else(missing_field_err("cancellation_reason"))
     }
+    fn get_connector_metadata_as<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        to_connector_meta(self.connector_meta.clone())
+    }
+    fn get_webhook_url(&self) -> Result<String, Error> {
+        self.webhook_url
+            .clone()
+            .ok_or_else(missing_field_err("webhook_url"))
+    }
 }
 
 pub trait RefundsRequestData {
     fn get_connector_refund_id(&self) -> Result<String, Error>;
     fn get_webhook_url(&self) -> Result<String, Error>;
+    fn get_original_payment_amount_in_base_unit(
+        &self,
+        currency: diesel_models::enums::Currency,
+    ) -> Result<String, Error>;
+    fn get_refund_amount_in_base_unit(
+        &self,
+        currency: diesel_models::enums::Currency,
+    ) -> Result<String, Error>;
+    fn get_refund_reason(&self) -> Option<String>;
 }
 
 impl RefundsRequestData for types::RefundsData {
@@ -526,6 +730,24 @@ impl RefundsRequestData for types::RefundsData {
             .clone()
             .ok_or_else(missing_field_err("webhook_url"))
     }
+    /// Amount of the payment against which the refund is issued, for connectors which need it
+    /// along with the refund amount
+    fn get_original_payment_amount_in_base_unit(
+        &self,
+        currency: diesel_models::enums::Currency,
+    ) -> Result<String, Error> {
+        to_currency_base_unit(self.payment_amount, currency)
+    }
+    fn get_refund_amount_in_base_unit(
+        &self,
+        currency: diesel_models::enums::Currency,
+    ) -> Result<String, Error> {
+        to_currency_base_unit(self.refund_amount, currency)
+    }
+    /// The reason is already mapped to the value accepted by the connector of the refund
+    fn get_refund_reason(&self) -> Option<String> {
+        self.reason.clone()
+    }
 }
 
 #[derive(Clone, Debug, serde::Serialize)]
@@ -603,6 +825,20 @@ pub enum CardIssuer {
     JCB,
 }
 
+impl From<CardIssuer> for api_models::enums::CardNetwork {
+    fn from(card_issuer: CardIssuer) -> Self {
+        match card_issuer {
+            CardIssuer::AmericanExpress => Self::AmericanExpress,
+            CardIssuer::Master => Self::Mastercard,
+            CardIssuer::Maestro => Self::Maestro,
+            CardIssuer::Visa => Self::Visa,
+            CardIssuer::Discover => Self::Discover,
+            CardIssuer::DinersClub => Self::DinersClub,
+            CardIssuer::JCB => Self::JCB,
+        }
+    }
+}
+
 pub trait CardData {
     fn get_card_expiry_year_2_digit(&self) -> Secret<String>;
     fn get_card_issuer(&self) -> Result<CardIssuer, Error>;
@@ -879,6 +1115,147 @@ impl BankRedirectBillingData for payments::BankRedirectBilling {
     }
 }
 
+pub trait BankRedirectData {
+    fn get_bank_name(&self) -> Result<api_models::enums::BankNames, Error>;
+    fn get_country(&self) -> Option<api_models::enums::CountryAlpha2>;
+    fn get_preferred_language(&self) -> String;
+    fn get_billing_details_for_redirect(
+        &self,
+        billing_address: Option<&api::AddressDetails>,
+        email: Option<&Email>,
+    ) -> payments::BankRedirectBilling;
+}
+
+impl BankRedirectData for payments::BankRedirectData {
+    fn get_bank_name(&self) -> Result<api_models::enums::BankNames, Error> {
+        match self {
+            Self::Eps { bank_name, .. }
+            | Self::Ideal { bank_name, .. }
+            | Self::Przelewy24 { bank_name, .. } => {
+                bank_name.ok_or_else(missing_field_err("bank_name"))
+            }
+            Self::OnlineBankingCzechRepublic { issuer }
+            | Self::OnlineBankingPoland { issuer }
+            | Self::OnlineBankingSlovakia { issuer }
+            | Self::OpenBankingUk { issuer, .. }
+            | Self::OnlineBankingFpx { issuer }
+            | Self::OnlineBankingThailand { issuer } => Ok(*issuer),
+            Self::BancontactCard { .. }
+            | Self::Bizum {}
+            | Self::Blik { .. }
+            | Self::Giropay { .. }
+            | Self::Interac { .. }
+            | Self::OnlineBankingFinland { .. }
+            | Self::Sofort { .. }
+            | Self::Trustly { .. } => Err(errors::ConnectorError::MismatchedPaymentData.into()),
+        }
+    }
+
+    fn get_country(&self) -> Option<api_models::enums::CountryAlpha2> {
+        match self {
+            Self::Eps { country, .. }
+            | Self::Giropay { country, .. }
+            | Self::Ideal { country, .. } => *country,
+            Self::Interac { country, .. }
+            | Self::OpenBankingUk { country, .. }
+            | Self::Sofort { country, .. }
+            | Self::Trustly { country } => Some(*country),
+            Self::BancontactCard { .. }
+            | Self::Bizum {}
+            | Self::Blik { .. }
+            | Self::OnlineBankingCzechRepublic { .. }
+            | Self::OnlineBankingFinland { .. }
+            | Self::OnlineBankingPoland { .. }
+            | Self::OnlineBankingSlovakia { .. }
+            | Self::Przelewy24 { .. }
+            | Self::OnlineBankingFpx { .. }
+            | Self::OnlineBankingThailand { .. } => None,
+        }
+    }
+
+    /// The language of the pages of the bank: the preferred language of the customer if it was
+    /// given, else the language of the country of the payment, else English
+    fn get_preferred_language(&self) -> String {
+        if let Self::Sofort {
+            preferred_language, ..
+        } = self
+        {
+            let preferred_language = preferred_language.trim();
+            if !preferred_language.is_empty() {
+                return preferred_language.to_lowercase();
+            }
+        }
+
+        let language = match self.get_country() {
+            Some(
+                api_models::enums::CountryAlpha2::DE
+                | api_models::enums::CountryAlpha2::AT
+                | api_models::enums::CountryAlpha2::CH,
+            ) => "de",
+            Some(api_models::enums::CountryAlpha2::NL | api_models::enums::CountryAlpha2::BE) => {
+                "nl"
+            }
+            Some(api_models::enums::CountryAlpha2::FR) => "fr",
+            Some(api_models::enums::CountryAlpha2::ES) => "es",
+            Some(api_models::enums::CountryAlpha2::IT) => "it",
+            Some(api_models::enums::CountryAlpha2::PL) => "pl",
+            _ => "en",
+        };
+        language.to_string()
+    }
+
+    /// The billing details of the payment method data, with the name and the email which are
+    /// missing taken from the billing address and the email of the payment
+    fn get_billing_details_for_redirect(
+        &self,
+        billing_address: Option<&api::AddressDetails>,
+        email: Option<&Email>,
+    ) -> payments::BankRedirectBilling {
+        let (billing_name, billing_email) = match self {
+            Self::BancontactCard {
+                billing_details, ..
+            } => billing_details.as_ref().map_or((None, None), |billing| {
+                (billing.billing_name.clone(), billing.email.clone())
+            }),
+            Self::Eps {
+                billing_details, ..
+            }
+            | Self::Giropay {
+                billing_details, ..
+            }
+            | Self::Ideal {
+                billing_details, ..
+            }
+            | Self::Przelewy24 {
+                billing_details, ..
+            }
+            | Self::Sofort {
+                billing_details, ..
+            } => (
+                billing_details.billing_name.clone(),
+                billing_details.email.clone(),
+            ),
+            Self::Interac { email, .. } => (None, Some(email.clone())),
+            Self::OnlineBankingFinland { email } => (None, email.clone()),
+            Self::Bizum {}
+            | Self::Blik { .. }
+            | Self::OnlineBankingCzechRepublic { .. }
+            | Self::OnlineBankingPoland { .. }
+            | Self::OnlineBankingSlovakia { .. }
+            | Self::OpenBankingUk { .. }
+            | Self::Trustly { .. }
+            | Self::OnlineBankingFpx { .. }
+            | Self::OnlineBankingThailand { .. } => (None, None),
+        };
+
+        payments::BankRedirectBilling {
+            billing_name: billing_name
+                .or_else(|| billing_address.and_then(|address| address.get_full_name().ok())),
+            email: billing_email.or_else(|| email.cloned()),
+        }
+    }
+}
+
 pub trait BankDirectDebitBillingData {
     fn get_billing_country(&self) -> Result<api_models::enums::CountryAlpha2, Error>;
 }
@@ -925,6 +1302,45 @@ impl MandateReferenceData for api_models::payments::ConnectorMandateReferenceId
     }
 }
 
+/// The address verification and card verification code result codes of a connector, along with
+/// the values they are normalized to
+pub trait CardVerificationResultCodes {
+    const AVS_RESULT_CODES: &'static [(&'static str, api_models::enums::AvsResult)];
+    const CVC_RESULT_CODES: &'static [(&'static str, api_models::enums::CvcResult)];
+
+    fn get_card_verification_results(
+        raw_avs_result: Option<String>,
+        raw_cvc_result: Option<String>,
+    ) -> Option<api_models::payments::CardVerificationResults> {
+        if raw_avs_result.is_none() && raw_cvc_result.is_none() {
+            return None;
+        }
+        // Codes which are not known are treated as the check not being performed by the issuer
+        let avs_result = raw_avs_result.as_deref().map(|raw_avs_result| {
+            Self::AVS_RESULT_CODES
+                .iter()
+                .find(|(code, _)| *code == raw_avs_result)
+                .map_or(api_models::enums::AvsResult::Unavailable, |(_, result)| {
+                    *result
+                })
+        });
+        let cvc_result = raw_cvc_result.as_deref().map(|raw_cvc_result| {
+            Self::CVC_RESULT_CODES
+                .iter()
+                .find(|(code, _)| *code == raw_cvc_result)
+                .map_or(api_models::enums::CvcResult::NotProcessed, |(_, result)| {
+                    *result
+                })
+        });
+        Some(api_models::payments::CardVerificationResults {
+            avs_result,
+            raw_avs_result,
+            cvc_result,
+            raw_cvc_result,
+        })
+    }
+}
+
 pub fn get_header_key_value<'a>(
     key: &str,
     headers: &'a actix_web::http::header::HeaderMap,
@@ -1004,8 +1420,22 @@ where
 {
     let connector_meta_secret =
         connector_meta.ok_or_else(missing_field_err("connector_meta_data"))?;
-    let json = connector_meta_secret.peek().clone();
-    json.parse_value(std::any::type_name::<T>()).switch()
+    parse_connector_meta(connector_meta_secret.expose())
+}
+
+/// Deserializes the metadata of the merchant connector account, the error contains the reason of
+/// the failure, like the field which is missing or has a value of the wrong type
+pub fn parse_connector_meta<T>(connector_meta: serde_json::Value) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_value(connector_meta)
+        .map_err(|error| {
+            report!(errors::ConnectorError::InvalidConnectorMetaData {
+                message: error.to_string(),
+            })
+        })
+        .attach_printable_lazy(|| format!("Failed to parse {}", std::any::type_name::<T>()))
 }
 
 impl common_utils::errors::ErrorSwitch<errors::ConnectorError> for errors::ParsingError {
@@ -1173,6 +1603,13 @@ pub fn get_webhook_merchant_secret_key(connector_label: &str, merchant_id: &str)
     format!("whsec_verification_{connector_label}_{merchant_id}")
 }
 
+/// Appends the query parameter to the URL, after the query parameters which the URL already has,
+/// such as the identifiers appended by the router to its return URLs
+pub fn append_query_param(url: &str, key: &str, value: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}{key}={value}")
+}
+
 impl ForeignTryFrom<String> for UsStatesAbbreviation {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn foreign_try_from(value: String) -> Result<Self, Self::Error> {
@@ -1444,6 +1881,91 @@ pub fn is_manual_capture(capture_method: Option<enums::CaptureMethod>) -> bool {
         || capture_method == Some(enums::CaptureMethod::ManualMultiple)
 }
 
+/// Maps the outcome of an authorization or a sale to the attempt status, so that an
+/// authorization-only response is never marked as `Charged`
+pub fn map_capture_aware_status(
+    connector_indicated_success: bool,
+    is_auto_capture: bool,
+) -> enums::AttemptStatus {
+    match (connector_indicated_success, is_auto_capture) {
+        (true, true) => enums::AttemptStatus::Charged,
+        (true, false) => enums::AttemptStatus::Authorized,
+        (false, true) => enums::AttemptStatus::Failure,
+        (false, false) => enums::AttemptStatus::AuthorizationFailed,
+    }
+}
+
+/// Maps a response which is yet to reach a final state to the attempt status
+pub fn map_capture_aware_pending_status(
+    requires_redirection: bool,
+    is_auto_capture: bool,
+) -> enums::AttemptStatus {
+    if requires_redirection {
+        enums::AttemptStatus::AuthenticationPending
+    } else if is_auto_capture {
+        enums::AttemptStatus::Pending
+    } else {
+        enums::AttemptStatus::Authorizing
+    }
+}
+
+fn is_pre_capture_status(status: enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        enums::AttemptStatus::Started
+            | enums::AttemptStatus::AuthenticationPending
+            | enums::AttemptStatus::AuthenticationSuccessful
+            | enums::AttemptStatus::Authorizing
+            | enums::AttemptStatus::Authorized
+            | enums::AttemptStatus::PartiallyAuthorized
+            | enums::AttemptStatus::Pending
+            | enums::AttemptStatus::PaymentMethodAwaited
+            | enums::AttemptStatus::ConfirmationAwaited
+            | enums::AttemptStatus::DeviceDataCollectionPending
+    )
+}
+
+/// Checks whether a connector response can move the attempt from `current_status` to
+/// `connector_status`, e.g. a sync response must not downgrade a `Charged` attempt to `Authorized`
+pub fn is_impossible_status_transition(
+    current_status: enums::AttemptStatus,
+    connector_status: enums::AttemptStatus,
+) -> bool {
+    match current_status {
+        enums::AttemptStatus::Charged
+        | enums::AttemptStatus::PartialCharged
+        | enums::AttemptStatus::AutoRefunded => is_pre_capture_status(connector_status),
+        enums::AttemptStatus::Voided => {
+            is_pre_capture_status(connector_status)
+                || matches!(
+                    connector_status,
+                    enums::AttemptStatus::Charged | enums::AttemptStatus::PartialCharged
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Flags an impossible status transition returned by a connector before it is persisted.
+/// This is only done in debug builds to catch faulty status mappings during development
+#[cfg(debug_assertions)]
+pub fn flag_impossible_status_transition(
+    connector: &str,
+    current_status: enums::AttemptStatus,
+    connector_status: enums::AttemptStatus,
+) {
+    use router_env::{logger, tracing};
+
+    if is_impossible_status_transition(current_status, connector_status) {
+        logger::error!(
+            connector,
+            ?current_status,
+            ?connector_status,
+            "Impossible attempt status transition returned by the connector"
+        );
+    }
+}
+
 pub fn validate_currency(
     request_currency: types::storage::enums::Currency,
     merchant_config_currency: Option<types::storage::enums::Currency>,
@@ -1461,3 +1983,545 @@ pub fn validate_currency(
     }
     Ok(())
 }
+
+/// Whether the connector accepts the card verification code collected from the customer along
+/// with the token of a card saved at the connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavedCardCvcSupport {
+    NotSupported,
+    /// The code is passed to the connector when it is collected
+    Optional,
+    /// Payments with a saved card are declined by the connector without the code
+    Required,
+}
+
+/// How a payment with a card saved at the connector is made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavedCardFlow {
+    /// The customer is present and pays with the saved card
+    CustomerInitiated,
+    /// The payment sets up a mandate, or is made off session with the mandate
+    Mandate,
+    /// The card is paid with its network token, which is authenticated by its cryptogram
+    NetworkToken,
+}
+
+/// Whether the connector accepts the card verification code for the payment method type in the
+/// flow. The code is never collected for the payments of mandates and network tokens.
+pub fn get_saved_card_cvc_support(
+    connector: api_models::enums::Connector,
+    payment_method_type: api_models::enums::PaymentMethodType,
+    flow: SavedCardFlow,
+) -> SavedCardCvcSupport {
+    match (connector, payment_method_type, flow) {
+        (_, _, SavedCardFlow::Mandate | SavedCardFlow::NetworkToken) => {
+            SavedCardCvcSupport::NotSupported
+        }
+        (
+            api_models::enums::Connector::Adyen,
+            api_models::enums::PaymentMethodType::Credit
+            | api_models::enums::PaymentMethodType::Debit,
+            SavedCardFlow::CustomerInitiated,
+        ) => SavedCardCvcSupport::Optional,
+        _ => SavedCardCvcSupport::NotSupported,
+    }
+}
+
+/// Encodes the payload into a Qr code and returns it as a base64 png data url of `size` x `size` pixels
+pub fn generate_qr_code_data_url(payload: &str, size: u32) -> Result<String, Error> {
+    if payload.is_empty() || payload.len() > consts::MAX_QR_CODE_PAYLOAD_LENGTH {
+        Err(errors::ConnectorError::InvalidDataFormat {
+            field_name: "qr_code_data",
+        })?
+    }
+    let qr_image = crate::utils::QrImage::new_from_data_with_size(payload.to_owned(), Some(size))
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
+    Ok(qr_image.data)
+}
+
+/// Builds the connector metadata for a next action that has to be performed by the customer,
+/// this is mapped to the `next_action` of the payments response
+pub fn get_next_action_metadata(
+    next_action: payments::ConnectorNextActionData,
+) -> Result<Option<serde_json::Value>, Error> {
+    Some(common_utils::ext_traits::Encode::<
+        payments::ConnectorNextActionData,
+    >::encode_to_value(&next_action))
+    .transpose()
+    .change_context(errors::ConnectorError::ResponseHandlingFailed)
+}
+
+/// Builds the connector metadata for the `qr_code_information` next action from the raw Qr payload
+pub fn get_qr_code_next_action_metadata(
+    payload: String,
+    display_to_timestamp: Option<i64>,
+) -> Result<Option<serde_json::Value>, Error> {
+    let image_data_url = url::Url::parse(&generate_qr_code_data_url(
+        &payload,
+        consts::DEFAULT_QR_CODE_IMAGE_SIZE,
+    )?)
+    .into_report()
+    .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
+
+    get_next_action_metadata(payments::ConnectorNextActionData::QrCodeInformation {
+        image_data_url,
+        qr_code_data: Some(payload),
+        display_to_timestamp,
+    })
+}
+
+#[cfg(test)]
+mod qr_code_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_qr_code_data_url_is_deterministic() {
+        let first = generate_qr_code_data_url("upi://pay?pa=merchant@bank", 128).unwrap();
+        let second = generate_qr_code_data_url("upi://pay?pa=merchant@bank", 128).unwrap();
+        assert!(first.starts_with(consts::QR_IMAGE_DATA_SOURCE_STRING));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_qr_code_payload_length_limits() {
+        assert!(generate_qr_code_data_url("", 128).is_err());
+        let oversized_payload = "a".repeat(consts::MAX_QR_CODE_PAYLOAD_LENGTH + 1);
+        assert!(generate_qr_code_data_url(&oversized_payload, 128).is_err());
+    }
+
+    #[test]
+    fn test_qr_code_next_action_metadata_is_typed() {
+        let metadata =
+            get_qr_code_next_action_metadata("upi://pay?pa=merchant@bank".to_string(), Some(10))
+                .unwrap()
+                .unwrap();
+        let next_action: payments::ConnectorNextActionData =
+            serde_json::from_value(metadata).unwrap();
+        match next_action {
+            payments::ConnectorNextActionData::QrCodeInformation {
+                image_data_url,
+                qr_code_data,
+                display_to_timestamp,
+            } => {
+                assert!(image_data_url
+                    .as_str()
+                    .starts_with(consts::QR_IMAGE_DATA_SOURCE_STRING));
+                assert_eq!(qr_code_data.as_deref(), Some("upi://pay?pa=merchant@bank"));
+                assert_eq!(display_to_timestamp, Some(10));
+            }
+            _ => panic!("expected qr code information"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod bank_redirect_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_bank_name_and_preferred_language() {
+        let ideal = payments::BankRedirectData::Ideal {
+            billing_details: payments::BankRedirectBilling {
+                billing_name: None,
+                email: None,
+            },
+            bank_name: Some(api_models::enums::BankNames::Ing),
+            country: Some(api_models::enums::CountryAlpha2::NL),
+        };
+        assert_eq!(
+            ideal.get_bank_name().unwrap(),
+            api_models::enums::BankNames::Ing
+        );
+        assert_eq!(ideal.get_preferred_language(), "nl");
+
+        let sofort = payments::BankRedirectData::Sofort {
+            billing_details: payments::BankRedirectBilling {
+                billing_name: None,
+                email: None,
+            },
+            country: api_models::enums::CountryAlpha2::AT,
+            preferred_language: "".to_string(),
+        };
+        assert!(sofort.get_bank_name().is_err());
+        assert_eq!(sofort.get_preferred_language(), "de");
+
+        let trustly = payments::BankRedirectData::Trustly {
+            country: api_models::enums::CountryAlpha2::SE,
+        };
+        assert_eq!(trustly.get_preferred_language(), "en");
+    }
+
+    #[test]
+    fn test_billing_details_fall_back_to_the_billing_address() {
+        let giropay = payments::BankRedirectData::Giropay {
+            billing_details: payments::BankRedirectBilling {
+                billing_name: None,
+                email: Some(Email::from_str("giropay@example.com").unwrap()),
+            },
+            bank_account_bic: None,
+            bank_account_iban: None,
+            country: Some(api_models::enums::CountryAlpha2::DE),
+        };
+        let billing_address = api::AddressDetails {
+            first_name: Some(Secret::new("John".to_string())),
+            last_name: Some(Secret::new("Doe".to_string())),
+            ..Default::default()
+        };
+        let payment_email = Email::from_str("payment@example.com").unwrap();
+
+        let billing_details =
+            giropay.get_billing_details_for_redirect(Some(&billing_address), Some(&payment_email));
+        assert_eq!(billing_details.billing_name.unwrap().peek(), "John Doe");
+        assert_eq!(billing_details.email.unwrap().peek(), "giropay@example.com");
+    }
+}
+
+#[cfg(test)]
+mod cancel_request_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct TestConnectorMetaData {
+        capture_id: String,
+    }
+
+    #[test]
+    fn test_connector_metadata_is_parsed_to_the_requested_type() {
+        let cancel_data = PaymentsCancelData {
+            connector_meta: Some(serde_json::json!({ "capture_id": "capture_123" })),
+            ..Default::default()
+        };
+        let metadata = cancel_data
+            .get_connector_metadata_as::<TestConnectorMetaData>()
+            .unwrap();
+        assert_eq!(metadata.capture_id, "capture_123");
+    }
+
+    #[test]
+    fn test_missing_connector_metadata_is_a_missing_field_error() {
+        let cancel_data = PaymentsCancelData::default();
+        let error = cancel_data
+            .get_connector_metadata_as::<TestConnectorMetaData>()
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "connector_meta_data"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_malformed_connector_metadata_is_a_parsing_error() {
+        let cancel_data = PaymentsCancelData {
+            connector_meta: Some(serde_json::json!({ "void_id": "void_123" })),
+            ..Default::default()
+        };
+        let error = cancel_data
+            .get_connector_metadata_as::<TestConnectorMetaData>()
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::ParsingFailed
+        ));
+    }
+
+    #[test]
+    fn test_missing_webhook_url_is_a_missing_field_error() {
+        let cancel_data = PaymentsCancelData::default();
+        assert!(cancel_data.get_webhook_url().is_err());
+        let cancel_data = PaymentsCancelData {
+            webhook_url: Some("https://example.com/webhooks".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            cancel_data.get_webhook_url().unwrap(),
+            "https://example.com/webhooks"
+        );
+    }
+}
+
+#[cfg(test)]
+mod card_verification_results_tests {
+    use api_models::enums::{AvsResult, CvcResult};
+
+    use super::*;
+    use crate::connector::{
+        authorizedotnet::transformers as authorizedotnet, checkout::transformers as checkout,
+        cybersource::transformers as cybersource,
+    };
+
+    fn get_normalized_results<T: CardVerificationResultCodes>(
+        raw_avs_result: &str,
+        raw_cvc_result: &str,
+    ) -> (Option<AvsResult>, Option<CvcResult>) {
+        T::get_card_verification_results(
+            Some(raw_avs_result.to_string()),
+            Some(raw_cvc_result.to_string()),
+        )
+        .map_or((None, None), |results| {
+            (results.avs_result, results.cvc_result)
+        })
+    }
+
+    #[test]
+    fn test_equivalent_codes_are_normalized_identically() {
+        let expected = (Some(AvsResult::FullMatch), Some(CvcResult::Match));
+        assert_eq!(
+            get_normalized_results::<authorizedotnet::TransactionResponse>("Y", "M"),
+            expected
+        );
+        assert_eq!(
+            get_normalized_results::<cybersource::CybersourcePaymentsResponse>("Y", "M"),
+            expected
+        );
+        assert_eq!(
+            get_normalized_results::<checkout::PaymentsResponse>("Y", "Y"),
+            expected
+        );
+
+        let expected = (Some(AvsResult::PartialZip), Some(CvcResult::NoMatch));
+        assert_eq!(
+            get_normalized_results::<authorizedotnet::TransactionResponse>("Z", "N"),
+            expected
+        );
+        assert_eq!(
+            get_normalized_results::<cybersource::CybersourcePaymentsResponse>("Z", "N"),
+            expected
+        );
+        assert_eq!(
+            get_normalized_results::<checkout::PaymentsResponse>("Z", "N"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_raw_codes_are_kept_along_with_normalized_values() {
+        let results = cybersource::CybersourcePaymentsResponse::get_card_verification_results(
+            Some("A".to_string()),
+            None,
+        );
+        assert_eq!(
+            results,
+            Some(api_models::payments::CardVerificationResults {
+                avs_result: Some(AvsResult::PartialAddress),
+                raw_avs_result: Some("A".to_string()),
+                cvc_result: None,
+                raw_cvc_result: None,
+            })
+        );
+        assert_eq!(
+            cybersource::CybersourcePaymentsResponse::get_card_verification_results(None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unknown_codes_are_normalized_to_unavailable() {
+        assert_eq!(
+            get_normalized_results::<authorizedotnet::TransactionResponse>("?", "?"),
+            (Some(AvsResult::Unavailable), Some(CvcResult::NotProcessed))
+        );
+    }
+}
+
+#[cfg(test)]
+mod attempt_status_tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_aware_status_for_auto_and_manual_capture() {
+        let expected_statuses = [
+            ((true, true), enums::AttemptStatus::Charged),
+            ((true, false), enums::AttemptStatus::Authorized),
+            ((false, true), enums::AttemptStatus::Failure),
+            ((false, false), enums::AttemptStatus::AuthorizationFailed),
+        ];
+        for ((connector_indicated_success, is_auto_capture), expected_status) in expected_statuses {
+            assert_eq!(
+                map_capture_aware_status(connector_indicated_success, is_auto_capture),
+                expected_status
+            );
+        }
+    }
+
+    #[test]
+    fn test_capture_aware_pending_status() {
+        let expected_statuses = [
+            ((true, true), enums::AttemptStatus::AuthenticationPending),
+            ((true, false), enums::AttemptStatus::AuthenticationPending),
+            ((false, true), enums::AttemptStatus::Pending),
+            ((false, false), enums::AttemptStatus::Authorizing),
+        ];
+        for ((requires_redirection, is_auto_capture), expected_status) in expected_statuses {
+            assert_eq!(
+                map_capture_aware_pending_status(requires_redirection, is_auto_capture),
+                expected_status
+            );
+        }
+    }
+
+    #[test]
+    fn test_downgrading_a_charged_attempt_is_an_impossible_transition() {
+        assert!(is_impossible_status_transition(
+            enums::AttemptStatus::Charged,
+            enums::AttemptStatus::Authorized
+        ));
+        assert!(is_impossible_status_transition(
+            enums::AttemptStatus::Charged,
+            enums::AttemptStatus::Pending
+        ));
+        assert!(is_impossible_status_transition(
+            enums::AttemptStatus::Voided,
+            enums::AttemptStatus::Charged
+        ));
+    }
+
+    #[test]
+    fn test_possible_status_transitions() {
+        let transitions = [
+            (
+                enums::AttemptStatus::Authorized,
+                enums::AttemptStatus::Charged,
+            ),
+            (enums::AttemptStatus::Pending, enums::AttemptStatus::Charged),
+            (
+                enums::AttemptStatus::Authorizing,
+                enums::AttemptStatus::Authorized,
+            ),
+            (enums::AttemptStatus::Charged, enums::AttemptStatus::Charged),
+            (
+                enums::AttemptStatus::PartialCharged,
+                enums::AttemptStatus::Charged,
+            ),
+            (
+                enums::AttemptStatus::Authorized,
+                enums::AttemptStatus::Voided,
+            ),
+        ];
+        for (current_status, connector_status) in transitions {
+            assert!(!is_impossible_status_transition(
+                current_status,
+                connector_status
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod refund_request_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn refund_data(currency: diesel_models::enums::Currency) -> types::RefundsData {
+        types::RefundsData {
+            refund_id: "refund_123".to_string(),
+            connector_transaction_id: "transaction_123".to_string(),
+            connector_refund_id: None,
+            split_refund: None,
+            currency,
+            payment_amount: 10000,
+            reason: None,
+            webhook_url: None,
+            refund_amount: 2500,
+            connector_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_refund_amounts_are_converted_to_base_unit() {
+        let refund_data = refund_data(diesel_models::enums::Currency::USD);
+        assert_eq!(
+            refund_data
+                .get_original_payment_amount_in_base_unit(refund_data.currency)
+                .unwrap(),
+            "100.00"
+        );
+        assert_eq!(
+            refund_data
+                .get_refund_amount_in_base_unit(refund_data.currency)
+                .unwrap(),
+            "25.00"
+        );
+    }
+
+    #[test]
+    fn test_zero_decimal_currency_refund_amounts_are_not_scaled() {
+        let refund_data = refund_data(diesel_models::enums::Currency::JPY);
+        assert_eq!(
+            refund_data
+                .get_original_payment_amount_in_base_unit(refund_data.currency)
+                .unwrap(),
+            "10000.00"
+        );
+        assert_eq!(
+            refund_data
+                .get_refund_amount_in_base_unit(refund_data.currency)
+                .unwrap(),
+            "2500.00"
+        );
+    }
+}
+
+#[cfg(test)]
+mod payments_sync_request_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct TestMeta {
+        order_id: String,
+    }
+
+    #[test]
+    fn test_sync_accessors_return_present_values() {
+        let data = types::PaymentsSyncData {
+            encoded_data: Some("redirectResult=abc".to_string()),
+            connector_meta: Some(serde_json::json!({ "order_id": "order_1" })),
+            capture_id: Some("capture_1".to_string()),
+            sync_type: types::SyncRequestType::MultipleCaptureSync(vec![
+                "capture_1".to_string(),
+                "capture_2".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(data.get_encoded_data().unwrap(), "redirectResult=abc");
+        assert_eq!(
+            data.get_connector_metadata_as::<TestMeta>().unwrap(),
+            TestMeta {
+                order_id: "order_1".to_string()
+            }
+        );
+        assert_eq!(data.get_capture_id().unwrap(), "capture_1");
+        assert_eq!(
+            data.get_pending_connector_capture_ids(),
+            vec!["capture_1".to_string(), "capture_2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sync_accessors_report_missing_fields() {
+        let data = types::PaymentsSyncData::default();
+
+        assert!(matches!(
+            data.get_encoded_data().unwrap_err().current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "encoded_data"
+            }
+        ));
+        assert!(matches!(
+            data.get_capture_id().unwrap_err().current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "capture_id"
+            }
+        ));
+        assert!(data.get_connector_metadata_as::<TestMeta>().is_err());
+        assert!(data.get_pending_connector_capture_ids().is_empty());
+    }
+}
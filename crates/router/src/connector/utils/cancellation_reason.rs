@@ -0,0 +1,111 @@
+//! Mapping of the cancellation reason of the payment to the values accepted by the connectors.
+//! Some connectors accept only a fixed set of reasons, the others accept free text.
+
+use api_models::enums::{CancellationReason, Connector};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancellationReasonSupport {
+    /// The connector accepts only the listed values, reasons which are not listed are not sent
+    Codes(&'static [(CancellationReason, &'static str)]),
+    /// The connector accepts free text
+    FreeText,
+}
+
+const STRIPE_CANCELLATION_REASONS: &[(CancellationReason, &str)] = &[
+    (CancellationReason::Duplicate, "duplicate"),
+    (CancellationReason::FraudSuspected, "fraudulent"),
+    (
+        CancellationReason::RequestedByCustomer,
+        "requested_by_customer",
+    ),
+    (CancellationReason::Abandoned, "abandoned"),
+    (CancellationReason::Expired, "abandoned"),
+];
+
+pub fn get_cancellation_reason_support(connector: Connector) -> CancellationReasonSupport {
+    match connector {
+        Connector::Stripe => CancellationReasonSupport::Codes(STRIPE_CANCELLATION_REASONS),
+        _ => CancellationReasonSupport::FreeText,
+    }
+}
+
+/// Maps the reason of the cancellation to the value accepted by the connector. Connectors which
+/// accept free text receive the note of the merchant, or the reason code if there is no note.
+/// Connectors which accept only codes receive the note if there is no reason code and the note is
+/// one of the accepted values.
+pub fn map_cancellation_reason(
+    support: CancellationReasonSupport,
+    reason_code: Option<CancellationReason>,
+    note: Option<&str>,
+) -> Option<String> {
+    match support {
+        CancellationReasonSupport::Codes(accepted_reasons) => match reason_code {
+            Some(reason_code) => accepted_reasons
+                .iter()
+                .find(|(reason, _)| *reason == reason_code)
+                .map(|(_, connector_reason)| connector_reason.to_string()),
+            None => note
+                .filter(|note| {
+                    accepted_reasons
+                        .iter()
+                        .any(|(_, connector_reason)| connector_reason == note)
+                })
+                .map(ToString::to_string),
+        },
+        CancellationReasonSupport::FreeText => note
+            .map(ToString::to_string)
+            .or_else(|| reason_code.map(|reason_code| reason_code.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_codes_are_mapped_for_stripe() {
+        let stripe = get_cancellation_reason_support(Connector::Stripe);
+
+        assert_eq!(
+            map_cancellation_reason(
+                stripe,
+                Some(CancellationReason::FraudSuspected),
+                Some("Card reported stolen")
+            )
+            .as_deref(),
+            Some("fraudulent")
+        );
+        assert_eq!(
+            map_cancellation_reason(stripe, Some(CancellationReason::Expired), None).as_deref(),
+            Some("abandoned")
+        );
+        assert_eq!(
+            map_cancellation_reason(stripe, None, Some("requested_by_customer")).as_deref(),
+            Some("requested_by_customer")
+        );
+        assert_eq!(
+            map_cancellation_reason(stripe, None, Some("Customer changed their mind")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_free_text_reason_falls_back_to_reason_code() {
+        let support = get_cancellation_reason_support(Connector::Nmi);
+
+        assert_eq!(
+            map_cancellation_reason(
+                support,
+                Some(CancellationReason::Duplicate),
+                Some("Charged twice")
+            )
+            .as_deref(),
+            Some("Charged twice")
+        );
+        assert_eq!(
+            map_cancellation_reason(support, Some(CancellationReason::Abandoned), None).as_deref(),
+            Some("abandoned")
+        );
+        assert_eq!(map_cancellation_reason(support, None, None), None);
+    }
+}
@@ -0,0 +1,329 @@
+//! Capabilities which connectors declare: the payment methods, currencies and countries which
+//! they can process, and the flows which they support. Merchant connector accounts are validated
+//! against the capabilities when they are created or updated, so that a payment method which the
+//! connector cannot process is not enabled for it, and connectors which cannot process a payment
+//! are skipped when routing it. Connectors which do not declare their capabilities are not
+//! validated.
+
+use api_models::{
+    admin::{
+        AcceptedCountries, AcceptedCurrencies, ConnectorSupportedPaymentMethod,
+        PaymentMethodsEnabled,
+    },
+    enums::{
+        BankNames, CaptureMethod, Connector, CountryAlpha2, Currency, PaymentMethod,
+        PaymentMethodType,
+    },
+};
+
+use crate::{connector, core::errors};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedPaymentMethod {
+    pub payment_method: PaymentMethod,
+    pub payment_method_types: &'static [PaymentMethodType],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedBanks {
+    pub payment_method_type: PaymentMethodType,
+    /// The banks which the connector can process payments with, each with the code which the
+    /// connector identifies the bank with
+    pub bank_codes: &'static [(BankNames, &'static str)],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectorCapabilities {
+    pub payment_methods: &'static [SupportedPaymentMethod],
+    /// Currencies which the connector can process, `None` if it can process any currency
+    pub currencies: Option<&'static [Currency]>,
+    /// Countries of the customers which the connector can process payments for, `None` if it can
+    /// process payments for any country
+    pub countries: Option<&'static [CountryAlpha2]>,
+    pub capture_methods: &'static [CaptureMethod],
+    pub refunds_supported: bool,
+    pub mandates_supported: bool,
+    /// Whether the connector can split a payment between the platform and a sub-account
+    pub split_payments_supported: bool,
+    /// How the connector accepts the Apple Pay and Google Pay payments
+    pub wallet_support: WalletSupport,
+    /// Banks of the bank redirect payment method types for which the customer selects their bank
+    pub banks: &'static [SupportedBanks],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletSupport {
+    /// The connector is sent the wallet tokens, which it decrypts itself
+    Native,
+    /// The connector accepts only cards. The wallet tokens are decrypted before the payments are
+    /// authorized, and the connector is sent the decrypted network token as a card along with its
+    /// cryptogram, in `decrypted_wallet_card` of the authorize request.
+    DecryptedCardOnly,
+}
+
+/// Implemented by the connector, next to its integration, so that the declaration is updated
+/// along with the integration
+pub trait ConnectorCapabilitiesDeclaration {
+    const CAPABILITIES: ConnectorCapabilities;
+}
+
+pub fn get_connector_capabilities(connector: Connector) -> Option<ConnectorCapabilities> {
+    match connector {
+        Connector::Adyen => Some(connector::Adyen::CAPABILITIES),
+        Connector::Authorizedotnet => Some(connector::Authorizedotnet::CAPABILITIES),
+        Connector::Checkout => Some(connector::Checkout::CAPABILITIES),
+        Connector::Paypal => Some(connector::Paypal::CAPABILITIES),
+        Connector::Stripe => Some(connector::Stripe::CAPABILITIES),
+        _ => None,
+    }
+}
+
+impl ConnectorCapabilities {
+    pub fn supports_payment_method(&self, payment_method: PaymentMethod) -> bool {
+        self.payment_methods
+            .iter()
+            .any(|supported| supported.payment_method == payment_method)
+    }
+
+    pub fn supports_payment_method_type(
+        &self,
+        payment_method: PaymentMethod,
+        payment_method_type: PaymentMethodType,
+    ) -> bool {
+        self.payment_methods.iter().any(|supported| {
+            supported.payment_method == payment_method
+                && supported
+                    .payment_method_types
+                    .contains(&payment_method_type)
+        })
+    }
+
+    pub fn supports_currency(&self, currency: Currency) -> bool {
+        self.currencies
+            .map_or(true, |currencies| currencies.contains(&currency))
+    }
+
+    pub fn supports_country(&self, country: CountryAlpha2) -> bool {
+        self.countries
+            .map_or(true, |countries| countries.contains(&country))
+    }
+
+    /// The banks which the connector can process the payment method type with, empty if the
+    /// connector does not declare the banks of the payment method type
+    pub fn get_supported_banks(&self, payment_method_type: PaymentMethodType) -> Vec<BankNames> {
+        self.banks
+            .iter()
+            .filter(|supported| supported.payment_method_type == payment_method_type)
+            .flat_map(|supported| supported.bank_codes.iter().map(|(bank, _)| *bank))
+            .collect()
+    }
+
+    /// The code which the connector identifies the bank with, for payments with the payment
+    /// method type
+    pub fn get_bank_code(
+        &self,
+        payment_method_type: PaymentMethodType,
+        bank: BankNames,
+    ) -> Result<&'static str, errors::ConnectorError> {
+        self.banks
+            .iter()
+            .filter(|supported| supported.payment_method_type == payment_method_type)
+            .flat_map(|supported| supported.bank_codes.iter())
+            .find(|(supported_bank, _)| *supported_bank == bank)
+            .map(|(_, code)| *code)
+            .ok_or(errors::ConnectorError::UnsupportedBank {
+                bank,
+                payment_method_type,
+            })
+    }
+
+    /// Returns a description of each of the configurations in the payment methods enabled for a
+    /// merchant connector account which the connector does not support. Currencies and countries
+    /// are validated only if the payment method type is enabled for a list of them.
+    pub fn get_unsupported_configurations(
+        &self,
+        payment_methods_enabled: &[PaymentMethodsEnabled],
+    ) -> Vec<String> {
+        let mut unsupported = Vec::new();
+
+        for enabled in payment_methods_enabled {
+            let payment_method = enabled.payment_method;
+            if !self.supports_payment_method(payment_method) {
+                unsupported.push(format!("payment method {payment_method} is not supported"));
+                continue;
+            }
+
+            for enabled_type in enabled.payment_method_types.iter().flatten() {
+                let payment_method_type = enabled_type.payment_method_type;
+                if !self.supports_payment_method_type(payment_method, payment_method_type) {
+                    unsupported.push(format!(
+                        "payment method type {payment_method_type} of payment method {payment_method} is not supported"
+                    ));
+                    continue;
+                }
+
+                if let Some(AcceptedCurrencies::EnableOnly(currencies)) =
+                    &enabled_type.accepted_currencies
+                {
+                    unsupported.extend(
+                        currencies
+                            .iter()
+                            .filter(|currency| !self.supports_currency(**currency))
+                            .map(|currency| {
+                                format!("currency {currency} of payment method type {payment_method_type} is not supported")
+                            }),
+                    );
+                }
+
+                if let Some(AcceptedCountries::EnableOnly(countries)) =
+                    &enabled_type.accepted_countries
+                {
+                    unsupported.extend(
+                        countries
+                            .iter()
+                            .filter(|country| !self.supports_country(**country))
+                            .map(|country| {
+                                format!("country {country} of payment method type {payment_method_type} is not supported")
+                            }),
+                    );
+                }
+
+                if enabled_type.recurring_enabled && !self.mandates_supported {
+                    unsupported.push(format!(
+                        "recurring payments of payment method type {payment_method_type} are not supported"
+                    ));
+                }
+            }
+        }
+
+        unsupported
+    }
+}
+
+impl From<&SupportedPaymentMethod> for ConnectorSupportedPaymentMethod {
+    fn from(supported: &SupportedPaymentMethod) -> Self {
+        Self {
+            payment_method: supported.payment_method,
+            payment_method_types: supported.payment_method_types.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn payment_methods_enabled(value: serde_json::Value) -> Vec<PaymentMethodsEnabled> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_supported_configuration_is_accepted() {
+        let capabilities = get_connector_capabilities(Connector::Stripe).unwrap();
+        let payment_methods_enabled = payment_methods_enabled(serde_json::json!([{
+            "payment_method": "card",
+            "payment_method_types": [{
+                "payment_method_type": "credit",
+                "accepted_currencies": { "type": "enable_only", "list": ["USD", "EUR"] },
+                "recurring_enabled": true,
+                "installment_payment_enabled": false
+            }]
+        }]));
+
+        assert!(capabilities
+            .get_unsupported_configurations(&payment_methods_enabled)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_configurations_are_reported() {
+        let capabilities = get_connector_capabilities(Connector::Authorizedotnet).unwrap();
+        let payment_methods_enabled = payment_methods_enabled(serde_json::json!([
+            {
+                "payment_method": "bank_transfer",
+                "payment_method_types": [{
+                    "payment_method_type": "pix",
+                    "recurring_enabled": false,
+                    "installment_payment_enabled": false
+                }]
+            },
+            {
+                "payment_method": "card",
+                "payment_method_types": [{
+                    "payment_method_type": "debit",
+                    "accepted_currencies": { "type": "enable_only", "list": ["USD", "BRL"] },
+                    "accepted_countries": { "type": "enable_only", "list": ["US", "BR"] },
+                    "recurring_enabled": false,
+                    "installment_payment_enabled": false
+                }]
+            }
+        ]));
+
+        assert_eq!(
+            capabilities.get_unsupported_configurations(&payment_methods_enabled),
+            vec![
+                "payment method bank_transfer is not supported".to_string(),
+                "currency BRL of payment method type debit is not supported".to_string(),
+                "country BR of payment method type debit is not supported".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declared_capabilities_support_their_payment_methods() {
+        for connector in [
+            Connector::Adyen,
+            Connector::Authorizedotnet,
+            Connector::Checkout,
+            Connector::Paypal,
+            Connector::Stripe,
+        ] {
+            let capabilities = get_connector_capabilities(connector).unwrap();
+            assert!(!capabilities.payment_methods.is_empty());
+            assert!(!capabilities.capture_methods.is_empty());
+            for supported in capabilities.payment_methods {
+                assert!(!supported.payment_method_types.is_empty());
+            }
+        }
+        assert!(get_connector_capabilities(Connector::Nmi).is_none());
+    }
+
+    #[test]
+    fn test_ideal_bank_codes() {
+        let stripe = get_connector_capabilities(Connector::Stripe).unwrap();
+        assert_eq!(
+            stripe.get_bank_code(PaymentMethodType::Ideal, BankNames::Rabobank),
+            Ok("rabobank")
+        );
+
+        let adyen = get_connector_capabilities(Connector::Adyen).unwrap();
+        assert_eq!(
+            adyen.get_bank_code(PaymentMethodType::Ideal, BankNames::Rabobank),
+            Ok("1157")
+        );
+        assert!(adyen
+            .get_supported_banks(PaymentMethodType::Ideal)
+            .contains(&BankNames::AbnAmro));
+    }
+
+    #[test]
+    fn test_unknown_bank_is_rejected() {
+        let stripe = get_connector_capabilities(Connector::Stripe).unwrap();
+
+        // Citi is a bank of Przelewy24, not of iDEAL
+        assert_eq!(
+            stripe.get_bank_code(PaymentMethodType::Ideal, BankNames::Citi),
+            Err(errors::ConnectorError::UnsupportedBank {
+                bank: BankNames::Citi,
+                payment_method_type: PaymentMethodType::Ideal,
+            })
+        );
+        assert!(stripe
+            .get_bank_code(PaymentMethodType::Giropay, BankNames::Rabobank)
+            .is_err());
+        assert!(stripe
+            .get_supported_banks(PaymentMethodType::Giropay)
+            .is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+//! Rendering of the reference of the payment sent to the connectors from the format configured by
+//! the merchant, such as `{merchant_order_reference}` or `{payment_id}_{attempt}`. The rendered
+//! reference contains only the characters accepted by all the connectors, is truncated to the
+//! length accepted by the connector and is unique for every attempt of the payment.
+
+use api_models::enums::Connector;
+
+use crate::core::errors;
+
+const MERCHANT_ORDER_REFERENCE: &str = "merchant_order_reference";
+const PAYMENT_ID: &str = "payment_id";
+const ATTEMPT: &str = "attempt";
+
+/// Maximum length of the connector reference configured by the merchant
+pub const MAX_REFERENCE_FORMAT_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatPart<'a> {
+    Literal(&'a str),
+    MerchantOrderReference,
+    PaymentId,
+    Attempt,
+}
+
+/// Kind of a rendered segment of the reference, which decides how it is truncated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentKind {
+    /// A value of the payment, truncated first
+    Value,
+    /// A fixed part of the format, truncated from its start once the values are exhausted
+    Literal,
+    /// The ordinal of the attempt, never truncated
+    Attempt,
+}
+
+/// Details of the attempt used to render the connector reference
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceDetails<'a> {
+    pub payment_id: &'a str,
+    pub merchant_order_reference_id: Option<&'a str>,
+    /// Ordinal of the attempt of the payment, starting from 1
+    pub attempt_ordinal: i16,
+}
+
+/// Maximum length of the reference accepted by the connector, `None` if the connector does not
+/// limit it below the length of the references generated by the router
+pub fn get_max_reference_length(connector: Connector) -> Option<usize> {
+    match connector {
+        Connector::Authorizedotnet => Some(20),
+        Connector::Cybersource | Connector::Checkout => Some(50),
+        Connector::Adyen => Some(80),
+        Connector::Paypal => Some(127),
+        _ => None,
+    }
+}
+
+fn parse_reference_format(format: &str) -> Result<Vec<FormatPart<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut remaining = format;
+
+    while let Some(start) = remaining.find(['{', '}']) {
+        if remaining[start..].starts_with('}') {
+            return Err("unmatched `}`".to_string());
+        }
+        if start > 0 {
+            parts.push(FormatPart::Literal(&remaining[..start]));
+        }
+
+        let placeholder_end = remaining[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| "unmatched `{`".to_string())?;
+        let part = match &remaining[start + 1..placeholder_end] {
+            MERCHANT_ORDER_REFERENCE => FormatPart::MerchantOrderReference,
+            PAYMENT_ID => FormatPart::PaymentId,
+            ATTEMPT => FormatPart::Attempt,
+            placeholder => return Err(format!("unknown placeholder `{{{placeholder}}}`")),
+        };
+        parts.push(part);
+        remaining = &remaining[placeholder_end + 1..];
+    }
+
+    if !remaining.is_empty() {
+        parts.push(FormatPart::Literal(remaining));
+    }
+    Ok(parts)
+}
+
+/// Validates the connector reference format configured by the merchant. The format must include
+/// the merchant order reference or the payment id, so that the payments can be told apart.
+pub fn validate_reference_format(format: &str) -> Result<(), errors::ApiErrorResponse> {
+    let invalid_format = |reason: String| errors::ApiErrorResponse::InvalidRequestData {
+        message: format!("Invalid connector_request_reference_format: {reason}"),
+    };
+
+    if format.len() > MAX_REFERENCE_FORMAT_LENGTH {
+        return Err(invalid_format(format!(
+            "must be at most {MAX_REFERENCE_FORMAT_LENGTH} characters long"
+        )));
+    }
+
+    let parts = parse_reference_format(format).map_err(invalid_format)?;
+    if parts.iter().any(|part| {
+        matches!(
+            part,
+            FormatPart::MerchantOrderReference | FormatPart::PaymentId
+        )
+    }) {
+        Ok(())
+    } else {
+        Err(invalid_format(format!(
+            "must include `{{{MERCHANT_ORDER_REFERENCE}}}` or `{{{PAYMENT_ID}}}`"
+        )))
+    }
+}
+
+/// Replaces the characters which are not accepted by all the connectors with `-`
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Renders the connector reference from the format configured by the merchant. `None` is returned
+/// if the format is invalid, or if it includes the merchant order reference which was not passed
+/// for the payment, in which case the default reference is to be used.
+///
+/// The ordinal of the attempt is appended to the reference if the format does not include it, so
+/// that retries are not rejected by the connector as duplicates. When the reference is longer
+/// than `max_length`, the values of the payment are truncated first, then the fixed parts of the
+/// format from the start of the reference. The ordinal of the attempt is always kept.
+pub fn render_reference(
+    format: &str,
+    details: ReferenceDetails<'_>,
+    max_length: Option<usize>,
+) -> Option<String> {
+    let parts = parse_reference_format(format).ok()?;
+
+    // Rendered segments along with their kind
+    let mut segments = parts
+        .iter()
+        .map(|part| match part {
+            FormatPart::Literal(literal) => Some((sanitize(literal), SegmentKind::Literal)),
+            FormatPart::MerchantOrderReference => details
+                .merchant_order_reference_id
+                .map(|reference| (sanitize(reference), SegmentKind::Value)),
+            FormatPart::PaymentId => Some((sanitize(details.payment_id), SegmentKind::Value)),
+            FormatPart::Attempt => {
+                Some((details.attempt_ordinal.to_string(), SegmentKind::Attempt))
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if !parts.contains(&FormatPart::Attempt) && details.attempt_ordinal > 1 {
+        segments.push((
+            format!("_{}", details.attempt_ordinal),
+            SegmentKind::Attempt,
+        ));
+    }
+
+    if let Some(max_length) = max_length {
+        // The segments contain only ASCII characters, the length is the number of bytes
+        let mut excess = segments
+            .iter()
+            .map(|(segment, _)| segment.len())
+            .sum::<usize>()
+            .saturating_sub(max_length);
+
+        // The longest values are truncated first, so that every value keeps a part of it
+        while excess > 0 {
+            let Some((longest, _)) = segments
+                .iter_mut()
+                .filter(|(segment, kind)| *kind == SegmentKind::Value && !segment.is_empty())
+                .max_by_key(|(segment, _)| segment.len())
+            else {
+                break;
+            };
+            longest.pop();
+            excess -= 1;
+        }
+
+        // The fixed parts are then cut from the start of the reference
+        for (segment, _) in segments
+            .iter_mut()
+            .filter(|(_, kind)| *kind == SegmentKind::Literal)
+        {
+            if excess == 0 {
+                break;
+            }
+            let cut = excess.min(segment.len());
+            segment.drain(..cut);
+            excess -= cut;
+        }
+    }
+
+    Some(
+        segments
+            .into_iter()
+            .map(|(segment, _)| segment)
+            .collect::<String>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const TEST_PAYMENT_ID: &str = "pay_mbabizu24mvu3mela5njyhpit4";
+
+    fn details(attempt_ordinal: i16) -> ReferenceDetails<'static> {
+        ReferenceDetails {
+            payment_id: TEST_PAYMENT_ID,
+            merchant_order_reference_id: Some("ORDER #2023/1045"),
+            attempt_ordinal,
+        }
+    }
+
+    #[test]
+    fn test_reference_format_is_validated() {
+        assert!(validate_reference_format("{merchant_order_reference}").is_ok());
+        assert!(validate_reference_format("shop-{payment_id}_{attempt}").is_ok());
+        assert!(validate_reference_format("{attempt}").is_err());
+        assert!(validate_reference_format("{order_id}").is_err());
+        assert!(validate_reference_format("{payment_id").is_err());
+        assert!(validate_reference_format("payment_id}").is_err());
+    }
+
+    #[test]
+    fn test_reference_is_rendered_and_sanitized() {
+        assert_eq!(
+            render_reference("{merchant_order_reference}", details(1), None).as_deref(),
+            Some("ORDER--2023-1045")
+        );
+        assert_eq!(
+            render_reference("shop.{payment_id}_{attempt}", details(1), None).unwrap(),
+            format!("shop-{TEST_PAYMENT_ID}_1")
+        );
+
+        let without_order_reference = ReferenceDetails {
+            merchant_order_reference_id: None,
+            ..details(1)
+        };
+        assert_eq!(
+            render_reference("{merchant_order_reference}", without_order_reference, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reference_is_unique_across_retries() {
+        let references = (1..=3)
+            .map(|attempt_ordinal| {
+                render_reference(
+                    "{merchant_order_reference}",
+                    details(attempt_ordinal),
+                    get_max_reference_length(Connector::Authorizedotnet),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            references,
+            vec![
+                "ORDER--2023-1045",
+                "ORDER--2023-1045_2",
+                "ORDER--2023-1045_3"
+            ]
+        );
+        assert_eq!(
+            render_reference("{payment_id}-{attempt}", details(2), None).unwrap(),
+            format!("{TEST_PAYMENT_ID}-2")
+        );
+    }
+
+    #[test]
+    fn test_reference_is_truncated_to_connector_limit() {
+        let max_length = get_max_reference_length(Connector::Authorizedotnet);
+
+        let first_attempt = render_reference("{payment_id}", details(1), max_length).unwrap();
+        assert_eq!(first_attempt, "pay_mbabizu24mvu3mel");
+
+        let retry = render_reference("{payment_id}", details(12), max_length).unwrap();
+        assert_eq!(retry, "pay_mbabizu24mvu3_12");
+
+        let reference = render_reference(
+            "{merchant_order_reference}/{payment_id}_{attempt}",
+            details(2),
+            max_length,
+        )
+        .unwrap();
+        assert_eq!(reference, "ORDER--20-pay_mbab_2");
+        assert_eq!(reference.len(), 20);
+    }
+
+    #[test]
+    fn test_attempt_is_kept_when_fixed_parts_exceed_connector_limit() {
+        let max_length = get_max_reference_length(Connector::Authorizedotnet);
+
+        let reference = render_reference(
+            "merchant-shop-checkout-{payment_id}-{attempt}",
+            details(12),
+            max_length,
+        )
+        .unwrap();
+        assert_eq!(reference, "nt-shop-checkout--12");
+        assert_eq!(reference.len(), 20);
+    }
+}
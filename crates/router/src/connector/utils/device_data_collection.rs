@@ -0,0 +1,12 @@
+//! Device data collection which some connectors require before the authorization. The SDK runs
+//! the script of the connector with the parameters returned by the device data endpoint, and the
+//! session of the collection is sent to the connector along with the authorization, which the
+//! connector rejects without it. The parameters of the collection of each connector are configured
+//! in the `device_data_collection` settings, as they differ between the environments.
+
+use api_models::enums::Connector;
+
+/// Whether the connector rejects the authorizations without a device session
+pub fn is_device_session_required(connector: Connector) -> bool {
+    matches!(connector, Connector::Cybersource)
+}
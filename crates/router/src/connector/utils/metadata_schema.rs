@@ -0,0 +1,224 @@
+//! Schemas of the metadata which connectors expect in the merchant connector account.
+//! A connector declares its schema on the same type which it deserializes the metadata into, so
+//! that the metadata can be validated when the merchant connector account is created or updated,
+//! instead of failing during a payment.
+
+use std::collections::HashSet;
+
+use api_models::{
+    admin::{ConnectorMetadataField, ConnectorMetadataFieldType},
+    enums::Connector,
+};
+use error_stack::report;
+
+use super::parse_connector_meta;
+use crate::{connector, core::errors};
+
+type Error = error_stack::Report<errors::ConnectorError>;
+
+/// Fields which are configured in the metadata of any connector, like the wallet configurations
+const COMMON_METADATA_FIELDS: [&str; 4] = ["apple_pay", "google_pay", "airwallex", "noon"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataField {
+    pub name: &'static str,
+    pub field_type: ConnectorMetadataFieldType,
+    pub required: bool,
+}
+
+impl From<&MetadataField> for ConnectorMetadataField {
+    fn from(field: &MetadataField) -> Self {
+        Self {
+            name: field.name.to_string(),
+            field_type: field.field_type,
+            required: field.required,
+        }
+    }
+}
+
+/// Implemented by the type which a connector deserializes the metadata of the merchant connector
+/// account into
+pub trait ConnectorMetadataSchema: serde::de::DeserializeOwned {
+    const FIELDS: &'static [MetadataField];
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataSchema {
+    pub fields: &'static [MetadataField],
+    validator: fn(&serde_json::Value) -> Result<Vec<String>, Error>,
+}
+
+impl MetadataSchema {
+    fn of<T: ConnectorMetadataSchema>() -> Self {
+        Self {
+            fields: T::FIELDS,
+            validator: validate_connector_metadata::<T>,
+        }
+    }
+
+    /// Returns the fields which are not a part of the schema
+    pub fn validate(&self, metadata: &serde_json::Value) -> Result<Vec<String>, Error> {
+        (self.validator)(metadata)
+    }
+}
+
+pub fn get_connector_metadata_schema(connector: Connector) -> Option<MetadataSchema> {
+    match connector {
+        Connector::Bluesnap => Some(MetadataSchema::of::<
+            connector::bluesnap::transformers::BluesnapConnectorMetaData,
+        >()),
+        Connector::Braintree => Some(MetadataSchema::of::<
+            connector::braintree::transformers::BraintreeMeta,
+        >()),
+        Connector::Globalpay => Some(MetadataSchema::of::<
+            connector::globalpay::transformers::GlobalPayMeta,
+        >()),
+        _ => None,
+    }
+}
+
+/// Rejects the metadata if a required field is missing or if a field has a value of the wrong
+/// type. The fields which are not a part of the schema are returned, so that they can be reported
+/// without rejecting the metadata.
+pub fn validate_connector_metadata<T: ConnectorMetadataSchema>(
+    metadata: &serde_json::Value,
+) -> Result<Vec<String>, Error> {
+    let metadata_object = metadata.as_object().ok_or_else(|| {
+        report!(errors::ConnectorError::InvalidConnectorMetaData {
+            message: "expected a JSON object".to_string(),
+        })
+    })?;
+
+    let missing_fields = T::FIELDS
+        .iter()
+        .filter(|field| {
+            field.required
+                && metadata_object
+                    .get(field.name)
+                    .map_or(true, serde_json::Value::is_null)
+        })
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+    if !missing_fields.is_empty() {
+        return Err(report!(errors::ConnectorError::InvalidConnectorMetaData {
+            message: format!("missing required fields: {}", missing_fields.join(", ")),
+        }));
+    }
+
+    parse_connector_meta::<T>(metadata.clone())?;
+
+    let known_fields = T::FIELDS
+        .iter()
+        .map(|field| field.name)
+        .chain(COMMON_METADATA_FIELDS)
+        .collect::<HashSet<_>>();
+    Ok(metadata_object
+        .keys()
+        .filter(|key| !known_fields.contains(key.as_str()))
+        .cloned()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct TestMetadata {
+        merchant_account_id: String,
+        currency: Option<String>,
+    }
+
+    impl ConnectorMetadataSchema for TestMetadata {
+        const FIELDS: &'static [MetadataField] = &[
+            MetadataField {
+                name: "merchant_account_id",
+                field_type: ConnectorMetadataFieldType::String,
+                required: true,
+            },
+            MetadataField {
+                name: "currency",
+                field_type: ConnectorMetadataFieldType::String,
+                required: false,
+            },
+        ];
+    }
+
+    fn error_message(error: &Error) -> String {
+        error.current_context().to_string()
+    }
+
+    #[test]
+    fn test_valid_metadata_is_accepted() {
+        let unknown_fields = validate_connector_metadata::<TestMetadata>(&serde_json::json!({
+            "merchant_account_id": "merchant_123",
+            "apple_pay": {},
+        }))
+        .unwrap();
+        assert!(unknown_fields.is_empty());
+    }
+
+    #[test]
+    fn test_missing_required_field_is_rejected() {
+        let error = validate_connector_metadata::<TestMetadata>(&serde_json::json!({
+            "merchant_accont_id": "merchant_123",
+        }))
+        .unwrap_err();
+        assert_eq!(
+            error_message(&error),
+            "Invalid connector meta data: missing required fields: merchant_account_id"
+        );
+    }
+
+    #[test]
+    fn test_mistyped_field_is_rejected() {
+        let error = validate_connector_metadata::<TestMetadata>(&serde_json::json!({
+            "merchant_account_id": 123,
+        }))
+        .unwrap_err();
+        assert!(error_message(&error).contains("invalid type"));
+    }
+
+    #[test]
+    fn test_unknown_fields_are_returned() {
+        let unknown_fields = validate_connector_metadata::<TestMetadata>(&serde_json::json!({
+            "merchant_account_id": "merchant_123",
+            "merchant_accont_id": "merchant_123",
+        }))
+        .unwrap();
+        assert_eq!(unknown_fields, vec!["merchant_accont_id".to_string()]);
+    }
+
+    #[test]
+    fn test_declared_connector_schemas_accept_their_metadata() {
+        let cases = [
+            (
+                Connector::Bluesnap,
+                serde_json::json!({ "merchant_id": "merchant_123" }),
+            ),
+            (
+                Connector::Braintree,
+                serde_json::json!({
+                    "merchant_account_id": "merchant_123",
+                    "merchant_config_currency": "USD",
+                }),
+            ),
+            (
+                Connector::Globalpay,
+                serde_json::json!({ "account_name": "transaction_processing" }),
+            ),
+        ];
+        for (connector, metadata) in cases {
+            let schema = get_connector_metadata_schema(connector).unwrap();
+            assert!(schema.validate(&metadata).unwrap().is_empty());
+        }
+
+        let globalpay_schema = get_connector_metadata_schema(Connector::Globalpay).unwrap();
+        assert!(globalpay_schema
+            .validate(&serde_json::json!({ "account_nam": "transaction_processing" }))
+            .is_err());
+        assert!(get_connector_metadata_schema(Connector::Stripe).is_none());
+    }
+}
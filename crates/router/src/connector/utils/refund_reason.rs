@@ -0,0 +1,148 @@
+//! Mapping of the refund reason to the values accepted by the connectors. Some connectors accept
+//! only a fixed set of reasons, some accept free text of a limited length and some do not accept a
+//! reason at all.
+
+use api_models::enums::{Connector, RefundReason};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefundReasonSupport {
+    /// The connector accepts only the listed values, reasons which are not listed are not sent
+    Codes(&'static [(RefundReason, &'static str)]),
+    /// The connector accepts free text, which is truncated to `max_length` characters
+    FreeText { max_length: Option<usize> },
+    /// The connector does not accept a reason for the refund
+    NotSupported,
+}
+
+const STRIPE_REFUND_REASONS: &[(RefundReason, &str)] = &[
+    (RefundReason::Duplicate, "duplicate"),
+    (RefundReason::Fraudulent, "fraudulent"),
+    (RefundReason::RequestedByCustomer, "requested_by_customer"),
+];
+
+const ADYEN_REFUND_REASONS: &[(RefundReason, &str)] = &[
+    (RefundReason::Duplicate, "DUPLICATE"),
+    (RefundReason::Fraudulent, "FRAUD"),
+    (RefundReason::RequestedByCustomer, "CUSTOMER REQUEST"),
+    (RefundReason::Other, "OTHER"),
+];
+
+pub fn get_refund_reason_support(connector: Connector) -> RefundReasonSupport {
+    match connector {
+        Connector::Adyen => RefundReasonSupport::Codes(ADYEN_REFUND_REASONS),
+        Connector::Stripe => RefundReasonSupport::Codes(STRIPE_REFUND_REASONS),
+        Connector::Mollie => RefundReasonSupport::FreeText {
+            max_length: Some(255),
+        },
+        Connector::Checkout => RefundReasonSupport::NotSupported,
+        _ => RefundReasonSupport::FreeText { max_length: None },
+    }
+}
+
+/// Maps the reason of the refund to the value accepted by the connector. Connectors which accept
+/// free text receive the note of the merchant, or the reason code if there is no note.
+pub fn map_refund_reason(
+    support: RefundReasonSupport,
+    reason_code: Option<RefundReason>,
+    note: Option<&str>,
+) -> Option<String> {
+    match support {
+        RefundReasonSupport::Codes(accepted_reasons) => reason_code.and_then(|reason_code| {
+            accepted_reasons
+                .iter()
+                .find(|(reason, _)| *reason == reason_code)
+                .map(|(_, connector_reason)| connector_reason.to_string())
+        }),
+        RefundReasonSupport::FreeText { max_length } => note
+            .map(ToString::to_string)
+            .or_else(|| reason_code.map(|reason_code| reason_code.to_string()))
+            .map(|reason| match max_length {
+                Some(max_length) => truncate_to_char_boundary(&reason, max_length).to_string(),
+                None => reason,
+            }),
+        RefundReasonSupport::NotSupported => None,
+    }
+}
+
+/// Truncates the value to at most `max_length` characters, without splitting a character
+fn truncate_to_char_boundary(value: &str, max_length: usize) -> &str {
+    value
+        .char_indices()
+        .nth(max_length)
+        .map_or(value, |(index, _)| &value[..index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_codes_are_mapped_per_connector() {
+        let stripe = get_refund_reason_support(Connector::Stripe);
+        let adyen = get_refund_reason_support(Connector::Adyen);
+
+        assert_eq!(
+            map_refund_reason(stripe, Some(RefundReason::Fraudulent), None).as_deref(),
+            Some("fraudulent")
+        );
+        assert_eq!(
+            map_refund_reason(adyen, Some(RefundReason::Fraudulent), None).as_deref(),
+            Some("FRAUD")
+        );
+        assert_eq!(
+            map_refund_reason(
+                adyen,
+                Some(RefundReason::RequestedByCustomer),
+                Some("Customer returned the product")
+            )
+            .as_deref(),
+            Some("CUSTOMER REQUEST")
+        );
+    }
+
+    #[test]
+    fn test_reasons_not_accepted_by_connector_are_not_sent() {
+        let stripe = get_refund_reason_support(Connector::Stripe);
+        assert_eq!(
+            map_refund_reason(stripe, Some(RefundReason::Other), Some("Other")),
+            None
+        );
+        assert_eq!(map_refund_reason(stripe, None, Some("Damaged item")), None);
+        assert_eq!(
+            map_refund_reason(
+                get_refund_reason_support(Connector::Checkout),
+                Some(RefundReason::Duplicate),
+                Some("Charged twice")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_free_text_reason_is_truncated_at_char_boundary() {
+        let support = RefundReasonSupport::FreeText {
+            max_length: Some(4),
+        };
+        assert_eq!(
+            map_refund_reason(support, None, Some("Rückgabe")).as_deref(),
+            Some("Rück")
+        );
+        assert_eq!(
+            map_refund_reason(support, None, Some("€€€€€")).as_deref(),
+            Some("€€€€")
+        );
+        assert_eq!(
+            map_refund_reason(support, Some(RefundReason::Duplicate), None).as_deref(),
+            Some("dupl")
+        );
+        assert_eq!(
+            map_refund_reason(
+                get_refund_reason_support(Connector::Mollie),
+                Some(RefundReason::Duplicate),
+                Some("Charged twice")
+            )
+            .as_deref(),
+            Some("Charged twice")
+        );
+    }
+}
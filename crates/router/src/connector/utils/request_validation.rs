@@ -0,0 +1,112 @@
+//! Validation of the connector requests against the constraints of the connector, before the
+//! requests are sent. A request which would be rejected by the connector for a malformed field
+//! fails locally, with the field and the violated constraint in the error, instead of failing at
+//! the connector with an error which does not name the field.
+
+use regex::Regex;
+
+use crate::core::errors;
+
+type Error = error_stack::Report<errors::ConnectorError>;
+
+pub trait ValidateConnectorRequest {
+    /// Validates the request against the constraints of the connector. Requests without
+    /// constraints are not validated.
+    fn validate_request(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn validation_failed(field: &'static str, constraint: String) -> Error {
+    errors::ConnectorError::RequestValidationFailed { field, constraint }.into()
+}
+
+/// The value must be at most `max` characters long
+pub fn max_len(field: &'static str, value: Option<&str>, max: usize) -> Result<(), Error> {
+    match value {
+        Some(value) if value.chars().count() > max => Err(validation_failed(
+            field,
+            format!("must be at most {max} characters long"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The value must match the pattern, `description` describes the pattern in the error
+pub fn matches_regex(
+    field: &'static str,
+    value: Option<&str>,
+    pattern: &Regex,
+    description: &str,
+) -> Result<(), Error> {
+    match value {
+        Some(value) if !pattern.is_match(value) => {
+            Err(validation_failed(field, format!("must {description}")))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The value must be one of the `allowed` values
+pub fn one_of(field: &'static str, value: Option<&str>, allowed: &[&str]) -> Result<(), Error> {
+    match value {
+        Some(value) if !allowed.contains(&value) => Err(validation_failed(
+            field,
+            format!("must be one of {}", allowed.join(", ")),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_failed_constraint(result: Result<(), Error>) -> (&'static str, String) {
+        match result.unwrap_err().current_context() {
+            errors::ConnectorError::RequestValidationFailed { field, constraint } => {
+                (*field, constraint.clone())
+            }
+            error => panic!("unexpected error {error}"),
+        }
+    }
+
+    #[test]
+    fn test_max_len_counts_characters() {
+        assert!(max_len("descriptor", Some("Größe"), 5).is_ok());
+        assert!(max_len("descriptor", None, 0).is_ok());
+        assert_eq!(
+            get_failed_constraint(max_len("descriptor", Some("Größen"), 5)),
+            (
+                "descriptor",
+                "must be at most 5 characters long".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_matches_regex_and_one_of() {
+        let pattern = Regex::new(r"^[A-Z]{3}$").unwrap();
+        assert!(matches_regex("currency", Some("EUR"), &pattern, "be a currency code").is_ok());
+        assert_eq!(
+            get_failed_constraint(matches_regex(
+                "currency",
+                Some("eur"),
+                &pattern,
+                "be a currency code"
+            )),
+            ("currency", "must be a currency code".to_string())
+        );
+
+        assert!(one_of("payment_type", Some("Plain"), &["Plain", "Preauth"]).is_ok());
+        assert_eq!(
+            get_failed_constraint(one_of(
+                "payment_type",
+                Some("Recurring"),
+                &["Plain", "Preauth"]
+            )),
+            ("payment_type", "must be one of Plain, Preauth".to_string())
+        );
+    }
+}
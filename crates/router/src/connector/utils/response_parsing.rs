@@ -0,0 +1,188 @@
+//! Two phase parsing of the connector responses. The fields which decide the status of the
+//! payment are parsed first into a minimal status struct, followed by the full response with the
+//! fields which only enrich it. A response whose status fields cannot be parsed fails, whereas a
+//! response whose enrichment fields cannot be parsed is handled from its status fields, as the
+//! payment has been processed by the connector all the same. The failure is recorded as a warning
+//! with the masked body of the response, from which the missing fields can be backfilled.
+
+use common_utils::ext_traits::BytesExt;
+use error_stack::ResultExt;
+use router_env::logger;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    core::errors::{self, CustomResult},
+    routes::metrics,
+    services::api::masked_logging,
+    types,
+};
+
+/// Response of the connector, parsed in full or only its status fields
+#[derive(Debug)]
+pub enum TwoPhaseResponse<S, F> {
+    Full(F),
+    StatusOnly(S),
+}
+
+/// Parses the response into the status struct `S`, then into the full response `F`. Only a
+/// failure to parse the status struct fails the response.
+pub fn parse_response_in_two_phases<S, F>(
+    connector: &str,
+    response: &types::Response,
+    type_name: &'static str,
+) -> CustomResult<TwoPhaseResponse<S, F>, errors::ConnectorError>
+where
+    S: DeserializeOwned,
+    F: DeserializeOwned,
+{
+    let status_response = response
+        .response
+        .parse_struct::<S>(type_name)
+        .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+
+    match response.response.parse_struct::<F>(type_name) {
+        Ok(full_response) => Ok(TwoPhaseResponse::Full(full_response)),
+        Err(error) => {
+            let error =
+                error.change_context(errors::ConnectorError::ResponsePartialDeserializationFailure);
+            let masked_response_body =
+                masked_logging::get_masked_body(connector, &response.response, false);
+            logger::warn!(
+                ?error,
+                connector,
+                connector_response_body = %masked_response_body,
+                "Failed to parse the enrichment fields of the {type_name}, the response is handled from its status fields"
+            );
+            metrics::RESPONSE_PARTIAL_DESERIALIZATION_FAILURE.add(
+                &metrics::CONTEXT,
+                1,
+                &[metrics::request::add_attributes(
+                    "connector",
+                    connector.to_string(),
+                )],
+            );
+            Ok(TwoPhaseResponse::StatusOnly(status_response))
+        }
+    }
+}
+
+impl<S, F> TwoPhaseResponse<S, F> {
+    /// Builds the router data from the full response, or from the status struct when only it
+    /// could be parsed
+    pub fn into_router_data<Flow, Request, Response>(
+        self,
+        data: &types::RouterData<Flow, Request, Response>,
+        http_code: u16,
+    ) -> CustomResult<types::RouterData<Flow, Request, Response>, errors::ConnectorError>
+    where
+        Flow: Clone,
+        Request: Clone,
+        Response: Clone,
+        types::RouterData<Flow, Request, Response>: TryFrom<
+                types::ResponseRouterData<Flow, S, Request, Response>,
+                Error = error_stack::Report<errors::ConnectorError>,
+            > + TryFrom<
+                types::ResponseRouterData<Flow, F, Request, Response>,
+                Error = error_stack::Report<errors::ConnectorError>,
+            >,
+    {
+        match self {
+            Self::Full(response) => types::RouterData::try_from(types::ResponseRouterData {
+                response,
+                data: data.clone(),
+                http_code,
+            }),
+            Self::StatusOnly(response) => types::RouterData::try_from(types::ResponseRouterData {
+                response,
+                data: data.clone(),
+                http_code,
+            }),
+        }
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct StatusResponse {
+        id: String,
+        status: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct FullResponse {
+        id: String,
+        status: String,
+        balance: i64,
+    }
+
+    fn get_response(body: serde_json::Value) -> types::Response {
+        types::Response {
+            headers: None,
+            response: body.to_string().into(),
+            status_code: 200,
+        }
+    }
+
+    #[test]
+    fn test_full_response_is_parsed() {
+        let response = get_response(
+            serde_json::json!({ "id": "char_1", "status": "successful", "balance": 1000 }),
+        );
+
+        let parsed = parse_response_in_two_phases::<StatusResponse, FullResponse>(
+            "shift4",
+            &response,
+            "TestResponse",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            parsed,
+            TwoPhaseResponse::Full(FullResponse { balance: 1000, .. })
+        ));
+    }
+
+    #[test]
+    fn test_status_fields_are_used_when_enrichment_fields_fail() {
+        let response = get_response(serde_json::json!({ "id": "char_1", "status": "successful" }));
+
+        let parsed = parse_response_in_two_phases::<StatusResponse, FullResponse>(
+            "shift4",
+            &response,
+            "TestResponse",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            parsed,
+            TwoPhaseResponse::StatusOnly(StatusResponse { ref id, ref status })
+                if id == "char_1" && status == "successful"
+        ));
+    }
+
+    #[test]
+    fn test_missing_status_fields_fail_the_response() {
+        let response = get_response(serde_json::json!({ "id": "char_1", "balance": 1000 }));
+
+        let error = parse_response_in_two_phases::<StatusResponse, FullResponse>(
+            "shift4",
+            &response,
+            "TestResponse",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::ResponseDeserializationFailed
+        );
+    }
+}
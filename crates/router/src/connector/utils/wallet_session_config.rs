@@ -0,0 +1,602 @@
+//! Configuration of the Apple Pay and Google Pay sessions, stored in the metadata of the merchant
+//! connector account. The metadata is read into typed configurations, so that an incomplete
+//! configuration is rejected when the merchant connector account is created or updated, and a
+//! session token request fails with the key which is missing instead of a generic session failure.
+
+use api_models::{
+    enums::{CardNetwork, Connector},
+    payments as payment_types,
+};
+use serde_json::Value;
+
+pub const APPLE_PAY_METADATA_KEY: &str = "apple_pay";
+pub const APPLE_PAY_COMBINED_METADATA_KEY: &str = "apple_pay_combined";
+pub const GOOGLE_PAY_METADATA_KEY: &str = "google_pay";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "The {wallet} configuration in the connector metadata is missing or has an invalid `{key}`"
+)]
+pub struct WalletConfigError {
+    pub wallet: &'static str,
+    /// Path of the key in the metadata, such as `google_pay.merchant_info.merchant_name`
+    pub key: String,
+}
+
+/// Card networks accepted by the connector, which are offered in the wallet sessions when the
+/// merchant does not configure the networks
+pub fn get_supported_card_networks(connector: Connector) -> &'static [CardNetwork] {
+    match connector {
+        Connector::Adyen => &[
+            CardNetwork::Visa,
+            CardNetwork::Mastercard,
+            CardNetwork::AmericanExpress,
+            CardNetwork::Discover,
+            CardNetwork::JCB,
+            CardNetwork::DinersClub,
+            CardNetwork::UnionPay,
+            CardNetwork::CartesBancaires,
+            CardNetwork::Interac,
+            CardNetwork::Maestro,
+        ],
+        Connector::Stripe => &[
+            CardNetwork::Visa,
+            CardNetwork::Mastercard,
+            CardNetwork::AmericanExpress,
+            CardNetwork::Discover,
+            CardNetwork::JCB,
+            CardNetwork::DinersClub,
+            CardNetwork::UnionPay,
+            CardNetwork::CartesBancaires,
+            CardNetwork::Interac,
+        ],
+        Connector::Checkout | Connector::Cybersource => &[
+            CardNetwork::Visa,
+            CardNetwork::Mastercard,
+            CardNetwork::AmericanExpress,
+            CardNetwork::Discover,
+            CardNetwork::JCB,
+            CardNetwork::DinersClub,
+            CardNetwork::CartesBancaires,
+            CardNetwork::Maestro,
+        ],
+        Connector::Authorizedotnet | Connector::Bluesnap | Connector::Braintree => &[
+            CardNetwork::Visa,
+            CardNetwork::Mastercard,
+            CardNetwork::AmericanExpress,
+            CardNetwork::Discover,
+            CardNetwork::JCB,
+            CardNetwork::DinersClub,
+        ],
+        _ => &[CardNetwork::Visa, CardNetwork::Mastercard],
+    }
+}
+
+/// Name of the card network in the `supportedNetworks` of an Apple Pay payment request
+fn get_apple_pay_network(network: &CardNetwork) -> Option<&'static str> {
+    match network {
+        CardNetwork::Visa => Some("visa"),
+        CardNetwork::Mastercard => Some("masterCard"),
+        CardNetwork::AmericanExpress => Some("amex"),
+        CardNetwork::Discover => Some("discover"),
+        CardNetwork::JCB => Some("jcb"),
+        CardNetwork::CartesBancaires => Some("cartesBancaires"),
+        CardNetwork::UnionPay => Some("chinaUnionPay"),
+        CardNetwork::Interac => Some("interac"),
+        CardNetwork::Maestro => Some("maestro"),
+        CardNetwork::DinersClub | CardNetwork::RuPay => None,
+    }
+}
+
+/// Name of the card network in the `allowedCardNetworks` of a Google Pay payment method
+fn get_google_pay_network(network: &CardNetwork) -> Option<&'static str> {
+    match network {
+        CardNetwork::Visa => Some("VISA"),
+        CardNetwork::Mastercard => Some("MASTERCARD"),
+        CardNetwork::AmericanExpress => Some("AMEX"),
+        CardNetwork::Discover => Some("DISCOVER"),
+        CardNetwork::JCB => Some("JCB"),
+        CardNetwork::Interac => Some("INTERAC"),
+        CardNetwork::DinersClub
+        | CardNetwork::CartesBancaires
+        | CardNetwork::UnionPay
+        | CardNetwork::RuPay
+        | CardNetwork::Maestro => None,
+    }
+}
+
+fn get_default_networks(
+    connector: Connector,
+    network_name: fn(&CardNetwork) -> Option<&'static str>,
+) -> Vec<String> {
+    get_supported_card_networks(connector)
+        .iter()
+        .filter_map(network_name)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Reads the values of the configuration, keeping track of the path of the value so that the key
+/// which is missing or invalid can be reported
+#[derive(Clone, Copy)]
+struct ConfigReader<'p, 'v> {
+    wallet: &'static str,
+    path: &'p str,
+    value: &'v Value,
+}
+
+impl<'p, 'v> ConfigReader<'p, 'v> {
+    fn error(&self, key: String) -> WalletConfigError {
+        WalletConfigError {
+            wallet: self.wallet,
+            key,
+        }
+    }
+
+    fn key_path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{key}", self.path)
+        }
+    }
+
+    /// The value of the key, `None` if the key is absent or null
+    fn optional(&self, key: &str) -> Option<(String, &'v Value)> {
+        self.value
+            .get(key)
+            .filter(|value| !value.is_null())
+            .map(|value| (self.key_path(key), value))
+    }
+
+    fn required(&self, key: &str) -> Result<(String, &'v Value), WalletConfigError> {
+        self.optional(key)
+            .ok_or_else(|| self.error(self.key_path(key)))
+    }
+
+    fn string(&self, key: &str) -> Result<String, WalletConfigError> {
+        let (path, value) = self.required(key)?;
+        value
+            .as_str()
+            .filter(|value| !value.trim().is_empty())
+            .map(ToString::to_string)
+            .ok_or_else(|| self.error(path))
+    }
+
+    fn optional_string(&self, key: &str) -> Result<Option<String>, WalletConfigError> {
+        self.optional(key)
+            .map(|(path, value)| {
+                value
+                    .as_str()
+                    .map(ToString::to_string)
+                    .ok_or_else(|| self.error(path))
+            })
+            .transpose()
+    }
+
+    fn optional_string_list(&self, key: &str) -> Result<Option<Vec<String>>, WalletConfigError> {
+        self.optional(key)
+            .map(|(path, value)| {
+                value
+                    .as_array()
+                    .filter(|values| !values.is_empty())
+                    .and_then(|values| {
+                        values
+                            .iter()
+                            .map(|value| value.as_str().map(ToString::to_string))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| self.error(path))
+            })
+            .transpose()
+    }
+
+    fn string_list(&self, key: &str) -> Result<Vec<String>, WalletConfigError> {
+        self.optional_string_list(key)?
+            .ok_or_else(|| self.error(self.key_path(key)))
+    }
+}
+
+/// Credentials used to create the Apple Pay session with Apple
+#[derive(Debug, Clone)]
+pub enum ApplePaySessionCredentials {
+    /// The session is created with the merchant identifier and the certificates of the router,
+    /// for the domain which the merchant verified with the router
+    Simplified { initiative_context: String },
+    /// The session is created with the merchant identifier and the certificates of the merchant
+    Manual(payment_types::SessionTokenInfo),
+}
+
+#[derive(Debug, Clone)]
+pub struct ApplePaySessionConfig {
+    pub credentials: ApplePaySessionCredentials,
+    pub label: String,
+    pub supported_networks: Vec<String>,
+    pub merchant_capabilities: Vec<String>,
+}
+
+impl ApplePaySessionConfig {
+    const WALLET: &'static str = "Apple Pay";
+
+    /// Reads the Apple Pay configuration, in either the `apple_pay_combined` or the `apple_pay`
+    /// layout. The supported networks default to the card networks supported by the connector.
+    pub fn from_metadata(
+        connector: Connector,
+        metadata: Option<&Value>,
+    ) -> Result<Self, WalletConfigError> {
+        let root = ConfigReader {
+            wallet: Self::WALLET,
+            path: "",
+            value: metadata.unwrap_or(&Value::Null),
+        };
+
+        let (path, config, is_simplified) = match root.optional(APPLE_PAY_COMBINED_METADATA_KEY) {
+            Some((combined_path, combined)) => {
+                let combined = ConfigReader {
+                    path: &combined_path,
+                    value: combined,
+                    ..root
+                };
+                match (combined.optional("simplified"), combined.optional("manual")) {
+                    (Some((path, config)), _) => (path, config, true),
+                    (None, Some((path, config))) => (path, config, false),
+                    (None, None) => return Err(root.error(combined.key_path("manual"))),
+                }
+            }
+            None => {
+                let (path, config) = root.required(APPLE_PAY_METADATA_KEY)?;
+                (path, config, false)
+            }
+        };
+        let config = ConfigReader {
+            path: &path,
+            value: config,
+            ..root
+        };
+
+        let (session_token_data_path, session_token_data) =
+            config.required("session_token_data")?;
+        let session_token_data = ConfigReader {
+            path: &session_token_data_path,
+            value: session_token_data,
+            ..root
+        };
+        let credentials = if is_simplified {
+            ApplePaySessionCredentials::Simplified {
+                initiative_context: session_token_data.string("initiative_context")?,
+            }
+        } else {
+            ApplePaySessionCredentials::Manual(payment_types::SessionTokenInfo {
+                certificate: session_token_data.string("certificate")?,
+                certificate_keys: session_token_data.string("certificate_keys")?,
+                merchant_identifier: session_token_data.string("merchant_identifier")?,
+                display_name: session_token_data.string("display_name")?,
+                initiative: session_token_data.string("initiative")?,
+                initiative_context: session_token_data.string("initiative_context")?,
+            })
+        };
+
+        let (payment_request_data_path, payment_request_data) =
+            config.required("payment_request_data")?;
+        let payment_request_data = ConfigReader {
+            path: &payment_request_data_path,
+            value: payment_request_data,
+            ..root
+        };
+
+        Ok(Self {
+            credentials,
+            label: payment_request_data.string("label")?,
+            supported_networks: payment_request_data
+                .optional_string_list("supported_networks")?
+                .unwrap_or_else(|| get_default_networks(connector, get_apple_pay_network)),
+            merchant_capabilities: payment_request_data.string_list("merchant_capabilities")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GooglePaySessionConfig {
+    pub merchant_info: payment_types::GpayMerchantInfo,
+    pub allowed_payment_methods: Vec<payment_types::GpayAllowedPaymentMethods>,
+}
+
+impl GooglePaySessionConfig {
+    const WALLET: &'static str = "Google Pay";
+
+    /// Reads the Google Pay configuration. The allowed card networks default to the card networks
+    /// supported by the connector.
+    pub fn from_metadata(
+        connector: Connector,
+        metadata: Option<&Value>,
+    ) -> Result<Self, WalletConfigError> {
+        let root = ConfigReader {
+            wallet: Self::WALLET,
+            path: "",
+            value: metadata.unwrap_or(&Value::Null),
+        };
+        let (path, config) = root.required(GOOGLE_PAY_METADATA_KEY)?;
+        let config = ConfigReader {
+            path: &path,
+            value: config,
+            ..root
+        };
+
+        let (merchant_info_path, merchant_info) = config.required("merchant_info")?;
+        let merchant_info = ConfigReader {
+            path: &merchant_info_path,
+            value: merchant_info,
+            ..root
+        };
+        let merchant_info = payment_types::GpayMerchantInfo {
+            merchant_id: merchant_info.optional_string("merchant_id")?,
+            merchant_name: merchant_info.string("merchant_name")?,
+        };
+
+        let (payment_methods_path, payment_methods) = config.required("allowed_payment_methods")?;
+        let allowed_payment_methods = payment_methods
+            .as_array()
+            .filter(|payment_methods| !payment_methods.is_empty())
+            .ok_or_else(|| root.error(payment_methods_path.clone()))?
+            .iter()
+            .enumerate()
+            .map(|(index, payment_method)| {
+                let path = format!("{payment_methods_path}[{index}]");
+                Self::read_payment_method(
+                    connector,
+                    ConfigReader {
+                        path: &path,
+                        value: payment_method,
+                        ..root
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            merchant_info,
+            allowed_payment_methods,
+        })
+    }
+
+    fn read_payment_method(
+        connector: Connector,
+        payment_method: ConfigReader<'_, '_>,
+    ) -> Result<payment_types::GpayAllowedPaymentMethods, WalletConfigError> {
+        let (parameters_path, parameters) = payment_method.required("parameters")?;
+        let parameters = ConfigReader {
+            path: &parameters_path,
+            value: parameters,
+            ..payment_method
+        };
+
+        let (tokenization_path, tokenization) =
+            payment_method.required("tokenization_specification")?;
+        let tokenization = ConfigReader {
+            path: &tokenization_path,
+            value: tokenization,
+            ..payment_method
+        };
+        let (token_parameters_path, token_parameters) = tokenization.required("parameters")?;
+        let token_parameters = ConfigReader {
+            path: &token_parameters_path,
+            value: token_parameters,
+            ..payment_method
+        };
+
+        // Gateways identify the merchant either by the gateway merchant id or, for stripe, by the
+        // publishable key
+        let stripe_publishable_key = token_parameters.optional_string("stripe:publishableKey")?;
+        let gateway_merchant_id = match stripe_publishable_key {
+            Some(_) => token_parameters.optional_string("gateway_merchant_id")?,
+            None => Some(token_parameters.string("gateway_merchant_id")?),
+        };
+
+        Ok(payment_types::GpayAllowedPaymentMethods {
+            payment_method_type: payment_method.string("type")?,
+            parameters: payment_types::GpayAllowedMethodsParameters {
+                allowed_auth_methods: parameters.string_list("allowed_auth_methods")?,
+                allowed_card_networks: parameters
+                    .optional_string_list("allowed_card_networks")?
+                    .unwrap_or_else(|| get_default_networks(connector, get_google_pay_network)),
+            },
+            tokenization_specification: payment_types::GpayTokenizationSpecification {
+                token_specification_type: tokenization.string("type")?,
+                parameters: payment_types::GpayTokenParameters {
+                    gateway: token_parameters.string("gateway")?,
+                    gateway_merchant_id,
+                    stripe_version: token_parameters.optional_string("stripe:version")?,
+                    stripe_publishable_key,
+                },
+            },
+        })
+    }
+}
+
+/// Validates the wallet configurations present in the metadata of the merchant connector account
+pub fn validate_wallet_session_configs(
+    connector: Connector,
+    metadata: &Value,
+) -> Result<(), WalletConfigError> {
+    if metadata.get(APPLE_PAY_METADATA_KEY).is_some()
+        || metadata.get(APPLE_PAY_COMBINED_METADATA_KEY).is_some()
+    {
+        ApplePaySessionConfig::from_metadata(connector, Some(metadata))?;
+    }
+    if metadata.get(GOOGLE_PAY_METADATA_KEY).is_some() {
+        GooglePaySessionConfig::from_metadata(connector, Some(metadata))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn manual_apple_pay_metadata() -> Value {
+        serde_json::json!({
+            "apple_pay": {
+                "session_token_data": {
+                    "certificate": "certificate",
+                    "certificate_keys": "certificate_keys",
+                    "merchant_identifier": "merchant.com.example",
+                    "display_name": "Example",
+                    "initiative": "web",
+                    "initiative_context": "example.com"
+                },
+                "payment_request_data": {
+                    "label": "Example",
+                    "merchant_capabilities": ["supports3DS"]
+                }
+            }
+        })
+    }
+
+    fn google_pay_metadata() -> Value {
+        serde_json::json!({
+            "google_pay": {
+                "merchant_info": { "merchant_name": "Example" },
+                "allowed_payment_methods": [{
+                    "type": "CARD",
+                    "parameters": { "allowed_auth_methods": ["PAN_ONLY", "CRYPTOGRAM_3DS"] },
+                    "tokenization_specification": {
+                        "type": "PAYMENT_GATEWAY",
+                        "parameters": {
+                            "gateway": "adyen",
+                            "gateway_merchant_id": "ExampleMerchantAccount"
+                        }
+                    }
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_apple_pay_config_is_read_with_default_networks() {
+        let metadata = manual_apple_pay_metadata();
+        let config =
+            ApplePaySessionConfig::from_metadata(Connector::Adyen, Some(&metadata)).unwrap();
+
+        assert!(matches!(
+            config.credentials,
+            ApplePaySessionCredentials::Manual(ref session_token_data)
+                if session_token_data.merchant_identifier == "merchant.com.example"
+        ));
+        assert_eq!(
+            config.supported_networks,
+            vec![
+                "visa",
+                "masterCard",
+                "amex",
+                "discover",
+                "jcb",
+                "chinaUnionPay",
+                "cartesBancaires",
+                "interac",
+                "maestro"
+            ]
+        );
+
+        let simplified = serde_json::json!({
+            "apple_pay_combined": {
+                "simplified": {
+                    "session_token_data": { "initiative_context": "example.com" },
+                    "payment_request_data": {
+                        "label": "Example",
+                        "supported_networks": ["visa"],
+                        "merchant_capabilities": ["supports3DS"]
+                    }
+                }
+            }
+        });
+        let config =
+            ApplePaySessionConfig::from_metadata(Connector::Adyen, Some(&simplified)).unwrap();
+        assert!(matches!(
+            config.credentials,
+            ApplePaySessionCredentials::Simplified { .. }
+        ));
+        assert_eq!(config.supported_networks, vec!["visa"]);
+    }
+
+    #[test]
+    fn test_missing_apple_pay_key_is_reported() {
+        let mut metadata = manual_apple_pay_metadata();
+        metadata["apple_pay"]["session_token_data"]
+            .as_object_mut()
+            .unwrap()
+            .remove("merchant_identifier");
+
+        let error =
+            ApplePaySessionConfig::from_metadata(Connector::Adyen, Some(&metadata)).unwrap_err();
+        assert_eq!(
+            error.key,
+            "apple_pay.session_token_data.merchant_identifier"
+        );
+
+        let error = ApplePaySessionConfig::from_metadata(Connector::Adyen, None).unwrap_err();
+        assert_eq!(error.key, "apple_pay");
+    }
+
+    #[test]
+    fn test_google_pay_config_is_read_with_default_networks() {
+        let metadata = google_pay_metadata();
+        let config =
+            GooglePaySessionConfig::from_metadata(Connector::Checkout, Some(&metadata)).unwrap();
+
+        assert_eq!(
+            config.allowed_payment_methods[0]
+                .parameters
+                .allowed_card_networks,
+            vec!["VISA", "MASTERCARD", "AMEX", "DISCOVER", "JCB"]
+        );
+        assert_eq!(
+            config.allowed_payment_methods[0]
+                .tokenization_specification
+                .parameters
+                .gateway_merchant_id
+                .as_deref(),
+            Some("ExampleMerchantAccount")
+        );
+    }
+
+    #[test]
+    fn test_google_pay_gateway_merchant_id_is_required() {
+        let mut metadata = google_pay_metadata();
+        metadata["google_pay"]["allowed_payment_methods"][0]["tokenization_specification"]
+            ["parameters"]
+            .as_object_mut()
+            .unwrap()
+            .remove("gateway_merchant_id");
+
+        let error =
+            GooglePaySessionConfig::from_metadata(Connector::Adyen, Some(&metadata)).unwrap_err();
+        assert_eq!(
+            error.key,
+            "google_pay.allowed_payment_methods[0].tokenization_specification.parameters.gateway_merchant_id"
+        );
+
+        metadata["google_pay"]["allowed_payment_methods"][0]["tokenization_specification"]
+            ["parameters"]["stripe:publishableKey"] = Value::from("pk_test");
+        assert!(GooglePaySessionConfig::from_metadata(Connector::Stripe, Some(&metadata)).is_ok());
+    }
+
+    #[test]
+    fn test_only_configured_wallets_are_validated() {
+        assert!(validate_wallet_session_configs(
+            Connector::Adyen,
+            &serde_json::json!({ "account_name": "example" })
+        )
+        .is_ok());
+        assert_eq!(
+            validate_wallet_session_configs(
+                Connector::Adyen,
+                &serde_json::json!({ "google_pay": { "merchant_info": {} } })
+            )
+            .unwrap_err()
+            .key,
+            "google_pay.merchant_info.merchant_name"
+        );
+    }
+}
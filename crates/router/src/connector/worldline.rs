@@ -3,12 +3,11 @@ pub mod transformers;
 use std::fmt::Debug;
 
 use base64::Engine;
-use common_utils::ext_traits::ByteSliceExt;
+use common_utils::{date_time, ext_traits::ByteSliceExt};
 use diesel_models::enums;
 use error_stack::{IntoReport, ResultExt};
 use masking::{ExposeInterface, PeekInterface};
 use ring::hmac;
-use time::{format_description, OffsetDateTime};
 use transformers as worldline;
 
 use super::utils::RefundsRequestData;
@@ -17,7 +16,7 @@ use crate::{
     connector::{utils as connector_utils, utils as conn_utils},
     consts,
     core::errors::{self, CustomResult},
-    headers, logger,
+    headers,
     services::{
         self,
         request::{self, Mask},
@@ -62,13 +61,7 @@ impl Worldline {
     }
 
     pub fn get_current_date_time() -> CustomResult<String, errors::ConnectorError> {
-        let format = format_description::parse(
-            "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT",
-        )
-        .into_report()
-        .change_context(errors::ConnectorError::InvalidDateFormat)?;
-        OffsetDateTime::now_utc()
-            .format(&format)
+        date_time::now_utc_formatted(date_time::ConnectorDateFormat::Rfc1123)
             .into_report()
             .change_context(errors::ConnectorError::InvalidDateFormat)
     }
@@ -232,7 +225,6 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
             .response
             .parse_struct("Worldline PaymentResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
-        logger::debug!(payments_cancel_response=?response);
         types::RouterData::try_from(types::ResponseRouterData {
             response,
             data: data.clone(),
@@ -314,7 +306,6 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         data: &types::PaymentsSyncRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsSyncRouterData, errors::ConnectorError> {
-        logger::debug!(payment_sync_response=?res);
         let mut response: worldline::Payment = res
             .response
             .parse_struct("Worldline Payment")
@@ -420,7 +411,6 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
         types::PaymentsCaptureData: Clone,
         types::PaymentsResponseData: Clone,
     {
-        logger::debug!(payment_capture_response=?res);
         let mut response: worldline::PaymentResponse = res
             .response
             .parse_struct("Worldline PaymentResponse")
@@ -519,7 +509,6 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
         data: &types::PaymentsAuthorizeRouterData,
         res: types::Response,
     ) -> CustomResult<types::PaymentsAuthorizeRouterData, errors::ConnectorError> {
-        logger::debug!(payment_authorize_response=?res);
         let mut response: worldline::PaymentResponse = res
             .response
             .parse_struct("Worldline PaymentResponse")
@@ -606,7 +595,6 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
         data: &types::RefundsRouterData<api::Execute>,
         res: types::Response,
     ) -> CustomResult<types::RefundsRouterData<api::Execute>, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::worldline", response=?res);
         let response: worldline::RefundResponse = res
             .response
             .parse_struct("Worldline RefundResponse")
@@ -682,7 +670,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
         data: &types::RefundSyncRouterData,
         res: types::Response,
     ) -> CustomResult<types::RefundSyncRouterData, errors::ConnectorError> {
-        logger::debug!(target: "router::connector::worldline", response=?res);
         let response: worldline::RefundResponse = res
             .response
             .parse_struct("Worldline RefundResponse")
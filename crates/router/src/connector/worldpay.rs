@@ -199,6 +199,10 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..data.clone()
                 })
@@ -297,6 +301,10 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..data.clone()
         })
@@ -356,6 +364,10 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
                         connector_metadata: None,
                         network_txn_id: None,
                         connector_response_reference_id: None,
+                        card_verification_results: None,
+                        connector_fee: None,
+                        auth_code: None,
+                        acquirer_reference_number: None,
                     }),
                     ..data.clone()
                 })
@@ -476,6 +488,50 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn build_poll_request(
+        &self,
+        req: &types::PaymentsAuthorizeRouterData,
+        url: &str,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        // The links of the responses are relative to the base URL
+        let events_url = url::Url::parse(self.base_url(connectors))
+            .and_then(|base_url| base_url.join(url))
+            .into_report()
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Get)
+                .url(events_url.as_str())
+                .attach_default_headers()
+                .headers(types::PaymentsAuthorizeType::get_headers(
+                    self, req, connectors,
+                )?)
+                .build(),
+        ))
+    }
+
+    fn handle_poll_response(
+        &self,
+        data: &types::PaymentsAuthorizeRouterData,
+        res: Response,
+    ) -> CustomResult<types::PaymentsAuthorizeRouterData, errors::ConnectorError> {
+        let response: WorldpayEventResponse =
+            res.response
+                .parse_struct("Worldpay EventResponse")
+                .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let status = enums::AttemptStatus::from(response.last_event);
+        Ok(types::PaymentsAuthorizeRouterData {
+            status,
+            // The events are polled for as long as the authorization has no outcome
+            poll_config: data
+                .poll_config
+                .clone()
+                .filter(|_| status == enums::AttemptStatus::Pending),
+            ..data.clone()
+        })
+    }
 }
 
 impl api::Refund for Worldpay {}
@@ -551,11 +607,16 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
                     .response
                     .parse_struct("Worldpay PaymentsResponse")
                     .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+                // The refund is accepted before it is processed, its events are polled for its
+                // outcome
+                let poll_config = worldpay::get_refund_poll_config(response.links.as_ref())?;
                 Ok(types::RefundExecuteRouterData {
                     response: Ok(types::RefundsResponseData {
                         connector_refund_id: ResponseIdStr::try_from(response.links)?.id,
-                        refund_status: enums::RefundStatus::Success,
+                        refund_status: enums::RefundStatus::Pending,
+                        acquirer_reference_number: None,
                     }),
+                    poll_config: Some(poll_config),
                     ..data.clone()
                 })
             }
@@ -569,6 +630,56 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
     ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
         self.build_error_response(res)
     }
+
+    fn build_poll_request(
+        &self,
+        req: &types::RefundExecuteRouterData,
+        url: &str,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        // The links of the responses are relative to the base URL
+        let events_url = url::Url::parse(self.base_url(connectors))
+            .and_then(|base_url| base_url.join(url))
+            .into_report()
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Get)
+                .url(events_url.as_str())
+                .attach_default_headers()
+                .headers(types::RefundExecuteType::get_headers(
+                    self, req, connectors,
+                )?)
+                .build(),
+        ))
+    }
+
+    fn handle_poll_response(
+        &self,
+        data: &types::RefundExecuteRouterData,
+        res: Response,
+    ) -> CustomResult<types::RefundExecuteRouterData, errors::ConnectorError> {
+        let response: WorldpayEventResponse =
+            res.response
+                .parse_struct("Worldpay EventResponse")
+                .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let refund_status = enums::RefundStatus::from(response.last_event);
+        Ok(types::RefundExecuteRouterData {
+            response: data
+                .response
+                .clone()
+                .map(|response| types::RefundsResponseData {
+                    refund_status,
+                    ..response
+                }),
+            // The events are polled for as long as the refund has no outcome
+            poll_config: data
+                .poll_config
+                .clone()
+                .filter(|_| refund_status == enums::RefundStatus::Pending),
+            ..data.clone()
+        })
+    }
 }
 
 impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponseData> for Worldpay {
@@ -625,6 +736,7 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: data.request.refund_id.clone(),
                 refund_status: enums::RefundStatus::from(response.last_event),
+                acquirer_reference_number: None,
             }),
             ..data.clone()
         })
@@ -715,6 +827,7 @@ impl api::IncomingWebhook for Worldpay {
                 Ok(api::IncomingWebhookEvent::PaymentIntentFailure)
             }
             EventType::Unknown
+            | EventType::SentForAuthorization
             | EventType::Authorized
             | EventType::Cancelled
             | EventType::Refused
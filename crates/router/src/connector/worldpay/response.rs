@@ -40,6 +40,7 @@ pub struct WorldpayEventResponse {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum EventType {
+    SentForAuthorization,
     Authorized,
     Cancelled,
     Charged,
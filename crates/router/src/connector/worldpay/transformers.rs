@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use base64::Engine;
 use common_utils::errors::CustomResult;
 use diesel_models::enums;
@@ -12,6 +14,39 @@ use crate::{
     types::{self, api},
 };
 
+/// Polling of the authorizations which are accepted without an outcome, their events are polled
+/// until the outcome is known
+const AUTHORIZATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const AUTHORIZATION_MAX_POLL_ATTEMPTS: u16 = 5;
+/// Polling of the refunds, which are accepted before they are processed
+const REFUND_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const REFUND_MAX_POLL_ATTEMPTS: u16 = 5;
+
+/// Polling of the events of an operation which is accepted before it is processed
+fn get_events_poll_config(
+    links: Option<&PaymentLinks>,
+    interval: Duration,
+    max_attempts: u16,
+) -> Result<types::PollConfig, errors::ConnectorError> {
+    let events_url = links
+        .and_then(|links| links.events.as_ref())
+        .map(|events| events.href.clone())
+        .ok_or(errors::ConnectorError::MissingRequiredField {
+            field_name: "links.events",
+        })?;
+    Ok(types::PollConfig {
+        url_source: types::PollUrlSource::Url(events_url),
+        interval,
+        max_attempts,
+    })
+}
+
+pub fn get_refund_poll_config(
+    links: Option<&PaymentLinks>,
+) -> Result<types::PollConfig, errors::ConnectorError> {
+    get_events_poll_config(links, REFUND_POLL_INTERVAL, REFUND_MAX_POLL_ATTEMPTS)
+}
+
 fn fetch_payment_instrument(
     payment_method: api::PaymentMethodData,
 ) -> CustomResult<PaymentInstrument, errors::ConnectorError> {
@@ -187,13 +222,24 @@ impl TryFrom<types::PaymentsResponseRouterData<WorldpayPaymentsResponse>>
     fn try_from(
         item: types::PaymentsResponseRouterData<WorldpayPaymentsResponse>,
     ) -> Result<Self, Self::Error> {
+        let (status, poll_config) = match item.response.outcome {
+            Some(outcome) => (enums::AttemptStatus::from(outcome), None),
+            // The authorization is accepted without an outcome, its events are polled
+            None if item.http_code == 202 => (
+                enums::AttemptStatus::Pending,
+                Some(get_events_poll_config(
+                    item.response.links.as_ref(),
+                    AUTHORIZATION_POLL_INTERVAL,
+                    AUTHORIZATION_MAX_POLL_ATTEMPTS,
+                )?),
+            ),
+            None => Err(errors::ConnectorError::MissingRequiredField {
+                field_name: "outcome",
+            })?,
+        };
         Ok(Self {
-            status: match item.response.outcome {
-                Some(outcome) => enums::AttemptStatus::from(outcome),
-                None => Err(errors::ConnectorError::MissingRequiredField {
-                    field_name: "outcome",
-                })?,
-            },
+            status,
+            poll_config,
             description: item.response.description,
             response: Ok(types::PaymentsResponseData::TransactionResponse {
                 resource_id: types::ResponseId::try_from(item.response.links)?,
@@ -202,6 +248,10 @@ impl TryFrom<types::PaymentsResponseRouterData<WorldpayPaymentsResponse>>
                 connector_metadata: None,
                 network_txn_id: None,
                 connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
             }),
             ..item.data
         })
@@ -1,6 +1,7 @@
 // ID generation
 pub(crate) const ID_LENGTH: usize = 20;
 pub(crate) const MAX_ID_LENGTH: usize = 64;
+pub(crate) const MAX_CONNECTOR_LABEL_LENGTH: usize = 64;
 #[rustfmt::skip]
 pub(crate) const ALPHABETS: [char; 62] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
@@ -11,12 +12,92 @@ pub(crate) const ALPHABETS: [char; 62] = [
 ];
 /// API client request timeout (in seconds)
 pub const REQUEST_TIME_OUT: u64 = 30;
+/// API client connect timeout (in seconds)
+pub const REQUEST_CONNECT_TIME_OUT: u64 = 10;
 pub const REQUEST_TIMEOUT_ERROR_CODE: &str = "TIMEOUT";
 pub const REQUEST_TIMEOUT_ERROR_MESSAGE: &str = "Connector did not respond in specified time";
+pub const CONNECTOR_UNAVAILABLE_ERROR_CODE: &str = "CONNECTOR_UNAVAILABLE";
+pub const CONNECTOR_UNAVAILABLE_ERROR_MESSAGE: &str =
+    "Connector is temporarily unavailable, request was not sent to the connector";
+pub const CONNECTOR_UNDER_MAINTENANCE_ERROR_MESSAGE: &str =
+    "Connector is under maintenance, request was not sent to the connector";
+pub const CONNECT_TIMEOUT_ERROR_CODE: &str = "CONNECT_TIMEOUT";
+pub const DNS_RESOLUTION_FAILED_ERROR_CODE: &str = "DNS_RESOLUTION_FAILED";
+pub const CONNECTION_REFUSED_ERROR_CODE: &str = "CONNECTION_REFUSED";
+pub const TLS_HANDSHAKE_FAILED_ERROR_CODE: &str = "TLS_HANDSHAKE_FAILED";
+pub const CONNECTOR_HTTP_ERROR_ERROR_CODE: &str = "CONNECTOR_HTTP_ERROR";
+/// Maximum number of characters of the connector response body stored with the error
+pub const CONNECTOR_ERROR_BODY_EXCERPT_LENGTH: usize = 256;
+/// Maximum number of characters of the connector response body captured for a merchant
+pub const CONNECTOR_RESPONSE_CAPTURE_MAX_LENGTH: usize = 8192;
 
 ///Payment intent fulfillment default timeout (in seconds)
 pub const DEFAULT_FULFILLMENT_TIME: i64 = 15 * 60;
 
+///Payment intent session expiry minimum and maximum (in seconds)
+pub const MIN_SESSION_EXPIRY: u32 = 60;
+pub const MAX_SESSION_EXPIRY: u32 = 7 * 24 * 60 * 60;
+
+/// Default, minimum and maximum window within which the payments with the same merchant order
+/// reference are considered duplicates (in seconds)
+pub const DEFAULT_DUPLICATE_ORDER_REFERENCE_WINDOW: i64 = 24 * 60 * 60;
+pub const MIN_DUPLICATE_ORDER_REFERENCE_WINDOW: u32 = 1;
+pub const MAX_DUPLICATE_ORDER_REFERENCE_WINDOW: u32 = 30 * 24 * 60 * 60;
+
+/// Locale passed to the connectors when the locale of the shopper is not known
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Maximum length of a BCP-47 language tag accepted as a locale
+pub const MAX_LOCALE_LENGTH: usize = 35;
+
+/// Maximum length of the category of the order passed in a payment
+pub const MAX_ORDER_CATEGORY_LENGTH: usize = 255;
+
+/// Maximum length of the reference of the order in the merchant's system passed in a payment
+pub const MAX_MERCHANT_ORDER_REFERENCE_ID_LENGTH: usize = 255;
+
+/// Maximum length of the reference of the sub-account receiving a split payment
+pub const MAX_SPLIT_PAYMENT_DESTINATION_ACCOUNT_LENGTH: usize = 255;
+
+/// Basis points in a whole, a platform fee of 10000 basis points is the whole payment amount
+pub const BASIS_POINTS_PER_WHOLE: i64 = 10000;
+
+/// Ranges of Merchant Category Codes assigned by ISO 18245, the ranges reserved for ISO use
+/// (0000-0699 and 1000-1499) are not accepted
+pub const MERCHANT_CATEGORY_CODE_RANGES: [(u16, u16); 13] = [
+    (700, 999),   // Agricultural services
+    (1500, 2999), // Contracted services
+    (3000, 3299), // Airlines
+    (3300, 3499), // Car rental
+    (3500, 3999), // Lodging
+    (4000, 4799), // Transportation services
+    (4800, 4999), // Utility services
+    (5000, 5599), // Retail outlet services
+    (5600, 5699), // Clothing stores
+    (5700, 7299), // Miscellaneous stores
+    (7300, 7999), // Business services
+    (8000, 8999), // Professional services and membership organizations
+    (9000, 9999), // Government services
+];
+
+/// Cancellation reason set on payments cancelled on expiry of their session
+pub const SESSION_EXPIRED_CANCELLATION_REASON: &str = "expired";
+
+/// Cancellation reason set on payments whose authorization expired without being captured
+pub const AUTHORIZATION_EXPIRED_CANCELLATION_REASON: &str = "authorization_expired";
+
+/// Cancellation reason set on authorized payments which the customer cancelled on the page of the
+/// connector
+pub const CUSTOMER_ABANDONED_CANCELLATION_REASON: &str = "abandoned_on_connector_page";
+
+/// Reason of the refunds returning the received amount of partially funded bank transfers which
+/// are cancelled on expiry
+pub const PARTIAL_BANK_TRANSFER_REFUND_REASON: &str = "partially_funded_payment_expired";
+
+/// Cancellation reason set on payments which the connector authorized for less than their amount,
+/// when the merchant did not request partial authorizations
+pub const PARTIAL_AUTHORIZATION_CANCELLATION_REASON: &str = "partially_authorized";
+
 // String literals
 pub(crate) const NO_ERROR_MESSAGE: &str = "No error message";
 pub(crate) const NO_ERROR_CODE: &str = "No error code";
@@ -40,8 +121,33 @@ pub(crate) const APPLEPAY_VALIDATION_URL: &str =
 // The base64 image data will be appended to it to image data source
 pub(crate) const QR_IMAGE_DATA_SOURCE_STRING: &str = "data:image/png;base64";
 
+// Maximum length of the payload that can be encoded into a Qr code image
+pub(crate) const MAX_QR_CODE_PAYLOAD_LENGTH: usize = 2048;
+
+// Default dimension (in pixels) of the generated Qr code image
+pub(crate) const DEFAULT_QR_CODE_IMAGE_SIZE: u32 = 256;
+
+// Interval (in seconds) at which the sdk polls for the payment status while displaying a wait screen
+pub(crate) const DEFAULT_WAIT_SCREEN_POLL_INTERVAL_IN_SECS: u16 = 5;
+
 // OID (Object Identifier) for the merchant ID field extension.
 #[cfg(feature = "kms")]
 pub(crate) const MERCHANT_ID_FIELD_EXTENSION_ID: &str = "1.2.840.113635.100.6.32";
 
 pub(crate) const METRICS_HOST_TAG_NAME: &str = "host";
+
+// Number of consecutive declines after which a mandate is marked as requiring attention
+pub(crate) const MAX_CONSECUTIVE_MANDATE_FAILURES: i32 = 3;
+
+// Maximum number of refunds that can be initiated in a single bulk refund request
+pub(crate) const MAX_BULK_REFUND_ITEMS: usize = 500;
+
+// Maximum number of mandates that can be migrated in a single mandate migration request
+pub(crate) const MAX_MANDATE_MIGRATION_ITEMS: usize = 1000;
+
+// Number of mandates of a mandate migration migrated between two saves of its progress
+pub(crate) const MANDATE_MIGRATION_BATCH_SIZE: usize = 50;
+
+// Query parameter of the return URLs of the redirect flows identifying the payment attempt which the
+// customer is redirected back for
+pub(crate) const REDIRECT_ATTEMPT_ID_QUERY_PARAM: &str = "hs_attempt_id";
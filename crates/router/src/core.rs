@@ -1,21 +1,41 @@
 pub mod admin;
+pub mod alerts;
+pub mod amount_limits;
 pub mod api_keys;
 pub mod api_locking;
+pub mod audit_events;
+pub mod bin_database;
+pub mod blocklist;
 pub mod cache;
 pub mod cards_info;
+pub mod circuit_breaker;
 pub mod configs;
+pub mod connector_maintenance;
+pub mod connector_response_capture;
+pub mod connector_templates;
 pub mod customers;
+pub mod data_retention;
 pub mod disputes;
 pub mod errors;
 pub mod files;
+pub mod forex;
 pub mod mandate;
+pub mod metadata;
 pub mod metrics;
 pub mod payment_methods;
+pub mod payment_timeline;
 pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
+pub mod rate_limiter;
 pub mod refunds;
+#[cfg(feature = "olap")]
+pub mod reports;
+pub mod routing_decisions;
 pub mod utils;
 #[cfg(all(feature = "olap", feature = "kms"))]
 pub mod verification;
+#[cfg(feature = "olap")]
+pub mod verify_connector;
+pub mod webhook_endpoints;
 pub mod webhooks;
@@ -1,3 +1,8 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
 use api_models::{admin as admin_types, enums as api_enums};
 use common_utils::{
     crypto::{generate_cryptographically_secure_random_string, OptionalSecretValue},
@@ -5,16 +10,24 @@ use common_utils::{
     ext_traits::{AsyncExt, ConfigExt, Encode, ValueExt},
 };
 use data_models::MerchantStorageScheme;
-use error_stack::{report, FutureExt, ResultExt};
-use masking::{PeekInterface, Secret};
+use diesel_models::encryption::Encryption;
+use error_stack::{report, FutureExt, IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 use crate::{
+    configs::settings::{self, UnsupportedConfigurationBehaviour},
+    connector::utils::{capabilities, connector_reference, metadata_schema, wallet_session_config},
     consts,
     core::{
+        alerts, audit_events, circuit_breaker,
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
-        payments::helpers,
+        forex, metadata as core_metadata,
+        payment_methods::list_cache,
+        payments::{helpers, three_ds_decision},
         utils as core_utils,
+        webhook_endpoints::{self, WebhookEndpointAction},
     },
     db::StorageInterface,
     routes::{metrics, AppState},
@@ -42,7 +55,7 @@ pub fn create_merchant_publishable_key() -> String {
 
 pub async fn create_merchant_account(
     state: AppState,
-    req: api::MerchantAccountCreate,
+    mut req: api::MerchantAccountCreate,
 ) -> RouterResponse<api::MerchantAccountResponse> {
     let db = state.store.as_ref();
     let master_key = db.get_master_key();
@@ -73,6 +86,22 @@ pub async fn create_merchant_account(
             .transpose()?
             .map(Into::into);
 
+    req.webhook_details
+        .as_ref()
+        .map(validate_webhook_subscriptions)
+        .transpose()?;
+
+    if let Some(webhook_details) = req.webhook_details.as_mut() {
+        pin_webhook_payload_versions(webhook_details, None);
+    }
+
+    let webhook_auth_details = req
+        .webhook_details
+        .as_mut()
+        .map(|webhook_details| get_webhook_auth_details(webhook_details, None))
+        .transpose()?
+        .flatten();
+
     let webhook_details =
         req.webhook_details
             .as_ref()
@@ -84,6 +113,12 @@ pub async fn create_merchant_account(
             })
             .transpose()?;
 
+    let pii_policy = req
+        .pii_policy
+        .as_ref()
+        .map(validate_and_encode_pii_policy)
+        .transpose()?;
+
     if let Some(ref routing_algorithm) = req.routing_algorithm {
         let _: api::RoutingAlgorithm = routing_algorithm
             .clone()
@@ -168,6 +203,12 @@ pub async fn create_merchant_account(
             is_recon_enabled: false,
             default_profile: None,
             recon_status: diesel_models::enums::ReconStatus::NotRequested,
+            webhook_auth_details: webhook_auth_details
+                .async_lift(|inner| domain_types::encrypt_optional(inner, &key))
+                .await?,
+            default_currency: req.default_currency,
+            default_country: req.default_country,
+            pii_policy,
         })
     }
     .await
@@ -190,6 +231,7 @@ pub async fn create_merchant_account(
                 db,
                 business_profile_create_request,
                 merchant_account.clone(),
+                &key_store,
             )
             .await
             .map_err(|business_profile_insert_error| {
@@ -208,6 +250,7 @@ pub async fn create_merchant_account(
             db,
             api_models::admin::BusinessProfileCreate::default(),
             merchant_account.clone(),
+            &key_store,
         )
         .await?;
 
@@ -219,12 +262,23 @@ pub async fn create_merchant_account(
         .insert_merchant(merchant_account, &key_store)
         .await
         .to_duplicate_response(errors::ApiErrorResponse::DuplicateMerchantAccount)?;
-    Ok(service_api::ApplicationResponse::Json(
-        merchant_account
-            .try_into()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while generating response")?,
-    ))
+    let response: api::MerchantAccountResponse = merchant_account
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
+    audit_events::record_audit_event(
+        &state,
+        &response.merchant_id,
+        storage::enums::AuditResourceType::MerchantAccount,
+        &response.merchant_id,
+        storage::enums::AuditAction::Created,
+        None,
+        Some(&response),
+    )
+    .await;
+
+    Ok(service_api::ApplicationResponse::Json(response))
 }
 
 pub async fn get_merchant_account(
@@ -293,6 +347,7 @@ pub async fn create_business_profile_from_business_labels(
             db,
             business_profile_create_request,
             merchant_account.clone(),
+            key_store,
         )
         .await
         .map_err(|business_profile_insert_error| {
@@ -316,7 +371,7 @@ pub async fn create_business_profile_from_business_labels(
 pub async fn merchant_account_update(
     state: AppState,
     merchant_id: &String,
-    req: api::MerchantAccountUpdate,
+    mut req: api::MerchantAccountUpdate,
 ) -> RouterResponse<api::MerchantAccountResponse> {
     let db = state.store.as_ref();
     let key_store = db
@@ -339,6 +394,20 @@ pub async fn merchant_account_update(
         }))?;
     }
 
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let previous_webhook_auth_details = parse_webhook_auth_details(
+        merchant_account
+            .webhook_auth_details
+            .clone()
+            .map(|webhook_auth_details| webhook_auth_details.into_inner()),
+    )?;
+    // The merchant account before the update is recorded in the audit log
+    let previous_merchant_account: Option<api::MerchantAccountResponse> =
+        merchant_account.try_into().ok();
+
     if let Some(ref routing_algorithm) = req.routing_algorithm {
         let _: api::RoutingAlgorithm = routing_algorithm
             .clone()
@@ -378,6 +447,37 @@ pub async fn merchant_account_update(
 
     let key = key_store.key.get_inner().peek();
 
+    // The authentication details are updated only if they are passed, as the ones configured
+    // earlier cannot be read back by the merchant
+    req.webhook_details
+        .as_ref()
+        .map(validate_webhook_subscriptions)
+        .transpose()?;
+
+    if let Some(webhook_details) = req.webhook_details.as_mut() {
+        pin_webhook_payload_versions(
+            webhook_details,
+            previous_merchant_account
+                .as_ref()
+                .and_then(|merchant_account| merchant_account.webhook_details.clone()),
+        );
+    }
+
+    let webhook_auth_details = req
+        .webhook_details
+        .as_mut()
+        .map(|webhook_details| {
+            get_webhook_auth_details(webhook_details, previous_webhook_auth_details.clone())
+        })
+        .transpose()?
+        .flatten();
+
+    let pii_policy = req
+        .pii_policy
+        .as_ref()
+        .map(validate_and_encode_pii_policy)
+        .transpose()?;
+
     let business_profile_id_update = if let Some(profile_id) = req.default_profile {
         if !profile_id.is_empty_after_trim() {
             // Validate whether profile_id passed in request is valid and is linked to the merchant
@@ -444,6 +544,14 @@ pub async fn merchant_account_update(
         intent_fulfillment_time: req.intent_fulfillment_time.map(i64::from),
         payout_routing_algorithm: req.payout_routing_algorithm,
         default_profile: business_profile_id_update,
+        webhook_auth_details: webhook_auth_details
+            .async_lift(|inner| domain_types::encrypt_optional(inner, key))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt webhook authentication details")?,
+        default_currency: req.default_currency,
+        default_country: req.default_country,
+        pii_policy,
     };
 
     let response = db
@@ -453,12 +561,331 @@ pub async fn merchant_account_update(
 
     // If there are any new business labels generated, create business profile
 
-    Ok(service_api::ApplicationResponse::Json(
-        response
-            .try_into()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while generating response")?,
-    ))
+    let response: api::MerchantAccountResponse = response
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
+    audit_events::record_audit_event(
+        &state,
+        merchant_id,
+        storage::enums::AuditResourceType::MerchantAccount,
+        merchant_id,
+        storage::enums::AuditAction::Updated,
+        previous_merchant_account.as_ref(),
+        Some(&response),
+    )
+    .await;
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+/// Takes the authentication details out of the webhook details, so that they can be stored
+/// encrypted. The custom headers and the client certificate are validated here, as they would
+/// otherwise fail only when a webhook is delivered. The details configured earlier which are not
+/// passed again are kept, as they cannot be read back by the merchant.
+fn get_webhook_auth_details(
+    webhook_details: &mut api::WebhookDetails,
+    previous_auth_details: Option<admin_types::WebhookAuthDetails>,
+) -> RouterResult<Option<Secret<serde_json::Value>>> {
+    let auth_details = webhook_details.take_auth_details().unwrap_or_default();
+
+    for (name, value) in auth_details.custom_headers.iter().flatten() {
+        http::HeaderName::from_bytes(name.as_bytes())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "webhook_details.custom_headers",
+            })
+            .attach_printable_lazy(|| format!("Invalid header name: {name}"))?;
+        http::HeaderValue::from_str(value.peek())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "webhook_details.custom_headers",
+            })
+            .attach_printable_lazy(|| format!("Invalid value for header: {name}"))?;
+    }
+
+    match (
+        auth_details.client_certificate.as_ref(),
+        auth_details.client_certificate_key.as_ref(),
+    ) {
+        (Some(certificate), Some(certificate_key)) => {
+            helpers::create_identity_from_pem(certificate.peek(), certificate_key.peek())
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "webhook_details.client_certificate",
+                })
+                .attach_printable("Failed to parse the client certificate and key")?;
+        }
+        (Some(_), None) => Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "webhook_details.client_certificate_key",
+        })?,
+        (None, Some(_)) => Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "webhook_details.client_certificate",
+        })?,
+        (None, None) => {}
+    }
+
+    let previous_auth_details = previous_auth_details.unwrap_or_default();
+    let auth_details = admin_types::WebhookAuthDetails {
+        custom_headers: auth_details
+            .custom_headers
+            .or(previous_auth_details.custom_headers),
+        authentication: auth_details
+            .authentication
+            .or(previous_auth_details.authentication),
+        client_certificate: auth_details
+            .client_certificate
+            .or(previous_auth_details.client_certificate),
+        client_certificate_key: auth_details
+            .client_certificate_key
+            .or(previous_auth_details.client_certificate_key),
+        subscription_secrets: get_webhook_subscription_secrets(
+            webhook_details,
+            auth_details.subscription_secrets.unwrap_or_default(),
+            previous_auth_details
+                .subscription_secrets
+                .unwrap_or_default(),
+        )?,
+    };
+    let is_empty = auth_details.custom_headers.is_none()
+        && auth_details.authentication.is_none()
+        && auth_details.client_certificate.is_none()
+        && auth_details.client_certificate_key.is_none()
+        && auth_details.subscription_secrets.is_none();
+    if is_empty {
+        return Ok(None);
+    }
+
+    utils::Encode::<admin_types::WebhookAuthDetails>::encode_to_value(&auth_details)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to encode webhook authentication details")
+        .map(|auth_details| Some(Secret::new(auth_details)))
+}
+
+/// The keys signing the events delivered to the subscriptions, a subscription which is not passed
+/// a key keeping the key it was configured with earlier
+fn get_webhook_subscription_secrets(
+    webhook_details: &api::WebhookDetails,
+    mut secrets: HashMap<String, Secret<String>>,
+    mut previous_secrets: HashMap<String, Secret<String>>,
+) -> RouterResult<Option<HashMap<String, Secret<String>>>> {
+    let subscription_secrets = webhook_details
+        .subscriptions
+        .iter()
+        .flatten()
+        .map(|subscription| {
+            let subscription_id = &subscription.subscription_id;
+            secrets
+                .remove(subscription_id)
+                .or_else(|| previous_secrets.remove(subscription_id))
+                .map(|secret| (subscription_id.clone(), secret))
+                .ok_or(report!(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "webhook_details.subscriptions.secret",
+                }))
+        })
+        .collect::<RouterResult<HashMap<_, _>>>()?;
+
+    Ok((!subscription_secrets.is_empty()).then_some(subscription_secrets))
+}
+
+/// Parses the decrypted authentication details of the webhooks of a merchant account or a business
+/// profile
+pub fn parse_webhook_auth_details(
+    webhook_auth_details: Option<Secret<serde_json::Value>>,
+) -> RouterResult<Option<admin_types::WebhookAuthDetails>> {
+    webhook_auth_details
+        .map(|webhook_auth_details| {
+            webhook_auth_details
+                .expose()
+                .parse_value::<admin_types::WebhookAuthDetails>("WebhookAuthDetails")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to parse webhook authentication details")
+}
+
+/// Decrypts the authentication details of the webhooks of the business profile, which hold the
+/// keys of its subscriptions
+pub async fn get_business_profile_webhook_auth_details(
+    business_profile: &storage::business_profile::BusinessProfile,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<Option<admin_types::WebhookAuthDetails>> {
+    let webhook_auth_details = domain_types::decrypt::<serde_json::Value, masking::WithType>(
+        business_profile.webhook_auth_details.clone(),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Unable to decrypt webhook authentication details")?;
+
+    parse_webhook_auth_details(
+        webhook_auth_details.map(|webhook_auth_details| webhook_auth_details.into_inner()),
+    )
+}
+
+/// Encrypts the authentication details of the webhooks of a business profile
+async fn encrypt_business_profile_webhook_auth_details(
+    webhook_auth_details: Option<Secret<serde_json::Value>>,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<Option<Encryption>> {
+    webhook_auth_details
+        .async_lift(|inner| domain_types::encrypt_optional(inner, key_store.key.get_inner().peek()))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to encrypt webhook authentication details")
+        .map(|webhook_auth_details| webhook_auth_details.map(Encryption::from))
+}
+
+/// Validates the webhook subscriptions, as an invalid event type pattern would otherwise only
+/// result in the events never being delivered to the subscription
+fn validate_webhook_subscriptions(webhook_details: &api::WebhookDetails) -> RouterResult<()> {
+    let mut subscription_ids = HashSet::new();
+    for subscription in webhook_details.subscriptions.iter().flatten() {
+        let subscription_id = subscription.subscription_id.as_str();
+        utils::when(
+            subscription.subscription_id.is_empty_after_trim() || subscription_id.len() > 64,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "Webhook subscription id must be between 1 and 64 characters long"
+                        .to_string(),
+                }))
+            },
+        )?;
+        utils::when(!subscription_ids.insert(subscription_id), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("Duplicate webhook subscription id: {subscription_id}"),
+            }))
+        })?;
+        utils::when(
+            !api_enums::EventType::iter().any(|event_type| subscription.matches(event_type)),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "Webhook subscription {subscription_id} has an event type matching no \
+                         event: {}",
+                        subscription.event_type
+                    ),
+                }))
+            },
+        )?;
+        let url = url::Url::parse(subscription.url.peek())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "webhook_details.subscriptions.url",
+            })?;
+        // The events are signed but not encrypted, they are only delivered over TLS
+        utils::when(url.scheme() != "https", || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("Webhook subscription {subscription_id} url must be an HTTPS url"),
+            }))
+        })?;
+        utils::when(
+            subscription
+                .secret
+                .as_ref()
+                .map_or(false, |secret| secret.peek().is_empty()),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!("Webhook subscription {subscription_id} secret is empty"),
+                }))
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// The authentication details of webhooks are stored only on the merchant account, so they are
+/// rejected in the webhook details of a business profile
+fn validate_business_profile_webhook_details(
+    webhook_details: Option<&api::WebhookDetails>,
+) -> RouterResult<()> {
+    let has_auth_details = webhook_details.map_or(false, |webhook_details| {
+        webhook_details.custom_headers.is_some()
+            || webhook_details.authentication.is_some()
+            || webhook_details.client_certificate.is_some()
+            || webhook_details.client_certificate_key.is_some()
+    });
+
+    utils::when(has_auth_details, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Webhook custom headers, authentication and client certificate can be \
+                      configured only on the merchant account"
+                .to_string(),
+        }))
+    })?;
+
+    webhook_details
+        .map(validate_webhook_subscriptions)
+        .transpose()
+        .map(|_| ())
+}
+
+/// The fields of the PII policy are located by JSON pointers, which are rejected unless they point
+/// to a field inside the body
+fn validate_pii_policy(pii_policy: &admin_types::PiiPolicy) -> RouterResult<()> {
+    let redactions = [&pii_policy.api_response, &pii_policy.outgoing_webhook];
+    let fields = redactions.into_iter().flatten().flat_map(|redaction| {
+        redaction
+            .masked_fields
+            .iter()
+            .chain(redaction.removed_fields.iter())
+    });
+    for field in fields {
+        utils::when(!field.starts_with('/') || field.len() == 1, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "PII policy field `{field}` is not a JSON pointer to a field of the body"
+                ),
+            }))
+        })?;
+    }
+    Ok(())
+}
+
+/// The responses to the API keys are redacted with the PII policy of the merchant account, as the
+/// API keys are not scoped to a business profile, so only the redaction of the outgoing webhooks
+/// can be configured on a business profile
+fn validate_business_profile_pii_policy(pii_policy: &admin_types::PiiPolicy) -> RouterResult<()> {
+    utils::when(pii_policy.api_response.is_some(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The PII policy of the API responses can be configured only on the merchant \
+                      account"
+                .to_string(),
+        }))
+    })?;
+    validate_pii_policy(pii_policy)
+}
+
+fn encode_pii_policy(pii_policy: &admin_types::PiiPolicy) -> RouterResult<serde_json::Value> {
+    utils::Encode::<admin_types::PiiPolicy>::encode_to_value(pii_policy).change_context(
+        errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "pii_policy",
+        },
+    )
+}
+
+fn validate_and_encode_pii_policy(
+    pii_policy: &admin_types::PiiPolicy,
+) -> RouterResult<serde_json::Value> {
+    validate_pii_policy(pii_policy)?;
+    encode_pii_policy(pii_policy)
+}
+
+/// Pins the versions of the payloads of the webhooks to the ones of the webhook details stored
+/// earlier, the webhooks configured for the first time being set to the latest version
+pub fn pin_webhook_payload_versions(
+    webhook_details: &mut api::WebhookDetails,
+    previous_webhook_details: Option<serde_json::Value>,
+) {
+    let previous_webhook_details = previous_webhook_details.and_then(|webhook_details| {
+        webhook_details
+            .parse_value::<api::WebhookDetails>("WebhookDetails")
+            .map_err(|error| {
+                crate::logger::warn!(?error, "Failed to parse the stored webhook details")
+            })
+            .ok()
+    });
+    webhook_details.pin_payload_versions(previous_webhook_details.as_ref());
 }
 
 pub async fn merchant_account_delete(
@@ -466,10 +893,37 @@ pub async fn merchant_account_delete(
     merchant_id: String,
 ) -> RouterResponse<api::MerchantAccountDeleteResponse> {
     let db = state.store.as_ref();
+    // The merchant account is read before it is deleted, to be recorded in the audit log
+    let previous_merchant_account: Option<api::MerchantAccountResponse> = match db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+    {
+        Ok(key_store) => db
+            .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+            .await
+            .ok()
+            .and_then(|merchant_account| merchant_account.try_into().ok()),
+        Err(_) => None,
+    };
+
     let is_deleted = db
         .delete_merchant_account_by_merchant_id(&merchant_id)
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    if is_deleted {
+        audit_events::record_audit_event(
+            &state,
+            &merchant_id,
+            storage::enums::AuditResourceType::MerchantAccount,
+            &merchant_id,
+            storage::enums::AuditAction::Deleted,
+            previous_merchant_account.as_ref(),
+            None,
+        )
+        .await;
+    }
+
     let response = api::MerchantAccountDeleteResponse {
         merchant_id,
         deleted: is_deleted,
@@ -550,6 +1004,308 @@ fn validate_certificate_in_mca_metadata(
     Ok(())
 }
 
+/// Validates the metadata against the schema declared by the connector, if it declares one
+fn validate_metadata_against_connector_schema(
+    connector: api_enums::Connector,
+    metadata: &Secret<serde_json::Value>,
+) -> RouterResult<()> {
+    let Some(schema) = metadata_schema::get_connector_metadata_schema(connector) else {
+        return Ok(());
+    };
+
+    let unknown_fields = schema.validate(metadata.peek()).map_err(|error| {
+        let message = error.current_context().to_string();
+        error.change_context(errors::ApiErrorResponse::InvalidRequestData { message })
+    })?;
+    if !unknown_fields.is_empty() {
+        crate::logger::warn!(
+            %connector,
+            ?unknown_fields,
+            "Connector metadata contains fields which are not a part of the connector's schema"
+        );
+    }
+
+    Ok(())
+}
+
+/// Validates the outbound proxy in the connector account details, through which the requests of
+/// the merchant connector account are sent instead of the proxy configured for the connector. Only
+/// the proxies allowed by the operator can be configured.
+fn validate_outbound_proxy_in_connector_account_details(
+    config: &settings::ConnectorProxyConfig,
+    connector_account_details: &Secret<serde_json::Value>,
+) -> RouterResult<()> {
+    let outbound_proxy =
+        services::connector_proxy::get_merchant_connector_proxy(connector_account_details.peek())
+            .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("invalid outbound_proxy in connector_account_details: {error}"),
+            })
+            .into_report()?;
+
+    let Some(outbound_proxy) = outbound_proxy else {
+        return Ok(());
+    };
+    outbound_proxy
+        .validate("the merchant connector account")
+        .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+            message: match error {
+                errors::ApplicationError::InvalidConfigurationValueError(message) => message,
+                error => error.to_string(),
+            },
+        })
+        .into_report()?;
+    utils::when(
+        !services::connector_proxy::is_allowed_merchant_connector_proxy(config, &outbound_proxy),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "outbound_proxy url is not one of the allowed proxies".to_string(),
+            }))
+        },
+    )
+}
+
+/// Validates the wallet configurations in the metadata, so that a merchant connector account
+/// missing a key required to create the wallet sessions is rejected when it is configured
+fn validate_wallet_configurations_in_metadata(
+    connector: api_enums::Connector,
+    metadata: &Secret<serde_json::Value>,
+) -> RouterResult<()> {
+    wallet_session_config::validate_wallet_session_configs(connector, metadata.peek())
+        .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+            message: error.to_string(),
+        })
+        .into_report()
+}
+
+/// Validates the payment methods enabled for the merchant connector account against the
+/// capabilities declared by the connector, if it declares them
+pub(crate) fn validate_payment_methods_against_connector_capabilities(
+    connector: api_enums::Connector,
+    payment_methods_enabled: &[api::PaymentMethodsEnabled],
+    behaviour: UnsupportedConfigurationBehaviour,
+) -> RouterResult<()> {
+    let Some(connector_capabilities) = capabilities::get_connector_capabilities(connector) else {
+        return Ok(());
+    };
+
+    let unsupported_configurations =
+        connector_capabilities.get_unsupported_configurations(payment_methods_enabled);
+    if unsupported_configurations.is_empty() {
+        return Ok(());
+    }
+
+    match behaviour {
+        UnsupportedConfigurationBehaviour::Reject => {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The connector {connector} does not support the configured payment methods: {}",
+                    unsupported_configurations.join(", ")
+                ),
+            }))
+        }
+        UnsupportedConfigurationBehaviour::Warn => {
+            crate::logger::warn!(
+                %connector,
+                ?unsupported_configurations,
+                "Merchant connector account enables payment methods which the connector does not support"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Whether the credentials look like live credentials of the connector, from the prefixes of its
+/// keys. The connectors whose live and test keys cannot be told apart are not checked.
+fn are_live_credentials(connector: api_enums::Connector, auth: &types::ConnectorAuthType) -> bool {
+    let keys = match auth {
+        types::ConnectorAuthType::HeaderKey { api_key } => vec![api_key],
+        types::ConnectorAuthType::BodyKey { api_key, key1 } => vec![api_key, key1],
+        types::ConnectorAuthType::SignatureKey {
+            api_key,
+            key1,
+            api_secret,
+        } => vec![api_key, key1, api_secret],
+        types::ConnectorAuthType::MultiAuthKey {
+            api_key,
+            key1,
+            api_secret,
+            key2,
+        } => vec![api_key, key1, api_secret, key2],
+        types::ConnectorAuthType::CurrencyAuthKey { .. } | types::ConnectorAuthType::NoKey => {
+            vec![]
+        }
+    };
+    let is_live_key = |key: &str| match connector {
+        api_enums::Connector::Stripe | api_enums::Connector::Shift4 => {
+            ["sk_live_", "pk_live_", "rk_live_"]
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+        }
+        api_enums::Connector::Mollie => key.starts_with("live_"),
+        // The sandbox keys of Checkout are prefixed with `sk_sbox_` and `pk_sbox_`, or with
+        // `sk_test_` and `pk_test_` for the older accounts
+        api_enums::Connector::Checkout => {
+            (key.starts_with("sk_") || key.starts_with("pk_"))
+                && !["sk_sbox_", "pk_sbox_", "sk_test_", "pk_test_"]
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+        }
+        _ => false,
+    };
+
+    keys.into_iter().any(|key| is_live_key(key.peek().as_str()))
+}
+
+/// Validates that a merchant connector account in test mode is not configured with credentials
+/// which look like live credentials of the connector
+fn validate_test_mode_credentials(
+    connector: api_enums::Connector,
+    test_mode: Option<bool>,
+    auth: &types::ConnectorAuthType,
+    behaviour: UnsupportedConfigurationBehaviour,
+) -> RouterResult<()> {
+    if test_mode != Some(true) || !are_live_credentials(connector, auth) {
+        return Ok(());
+    }
+
+    match behaviour {
+        UnsupportedConfigurationBehaviour::Reject => {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The credentials look like live credentials of the connector {connector}, but the merchant connector account is in test mode"
+                ),
+            }))
+        }
+        UnsupportedConfigurationBehaviour::Warn => {
+            crate::logger::warn!(
+                %connector,
+                "Merchant connector account in test mode is configured with credentials which look like live credentials"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Validates the amount ranges of the payment method types. The amounts are in the lowest
+/// denomination of the currency of the range, or of the currency of the payment when the range has
+/// no currency. Payments in another currency than the currency of the range are validated with the
+/// current exchange rates, so a range with a currency requires exchange rates to be configured
+/// unless the payment method type accepts only the currency of the range.
+fn validate_payment_method_amount_ranges(
+    payment_methods_enabled: &[api::PaymentMethodsEnabled],
+    is_forex_configured: bool,
+) -> RouterResult<()> {
+    for payment_method_type in payment_methods_enabled
+        .iter()
+        .flat_map(|enabled| enabled.payment_method_types.iter().flatten())
+    {
+        let invalid_amount_range = |message: String| {
+            report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "Invalid amount range of payment method type {}: {message}",
+                    payment_method_type.payment_method_type
+                ),
+            })
+        };
+        let (minimum_amount, maximum_amount) = (
+            payment_method_type.minimum_amount,
+            payment_method_type.maximum_amount,
+        );
+
+        if minimum_amount.map_or(false, |amount| amount < 0)
+            || maximum_amount.map_or(false, |amount| amount < 0)
+        {
+            return Err(invalid_amount_range(
+                "`minimum_amount` and `maximum_amount` must not be negative".to_string(),
+            ));
+        }
+        if let (Some(minimum_amount), Some(maximum_amount)) = (minimum_amount, maximum_amount) {
+            if minimum_amount > maximum_amount {
+                return Err(invalid_amount_range(format!(
+                    "`minimum_amount` {minimum_amount} is greater than `maximum_amount` {maximum_amount}"
+                )));
+            }
+        }
+        if minimum_amount.is_none() && maximum_amount.is_none() {
+            continue;
+        }
+
+        let enabled_currencies = match payment_method_type.accepted_currencies.as_ref() {
+            Some(admin_types::AcceptedCurrencies::EnableOnly(currencies)) => Some(currencies),
+            _ => None,
+        };
+        match payment_method_type.amount_range_currency {
+            Some(range_currency) => {
+                let accepts_only_range_currency = enabled_currencies.map_or(false, |currencies| {
+                    currencies
+                        .iter()
+                        .all(|currency| *currency == range_currency)
+                });
+                if !is_forex_configured && !accepts_only_range_currency {
+                    return Err(invalid_amount_range(format!(
+                        "exchange rates are not configured to convert the amount of payments in other currencies to {range_currency}, enable only {range_currency} for the payment method type"
+                    )));
+                }
+            }
+            // The same amount in the lowest denomination is a different amount in currencies with
+            // a different number of decimal digits
+            None => {
+                let decimal_digits = enabled_currencies
+                    .into_iter()
+                    .flatten()
+                    .map(|currency| currency.number_of_digits_after_decimal_point())
+                    .collect::<HashSet<_>>();
+                if decimal_digits.len() > 1 {
+                    return Err(invalid_amount_range(
+                        "the accepted currencies have different numbers of decimal digits, set `amount_range_currency` to the currency of the amounts".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn retrieve_connector_capabilities(
+    connector: api_enums::Connector,
+) -> RouterResponse<admin_types::ConnectorCapabilitiesResponse> {
+    let connector_capabilities =
+        capabilities::get_connector_capabilities(connector).ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("The connector {connector} does not declare its capabilities"),
+            })
+        })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ConnectorCapabilitiesResponse {
+            connector,
+            payment_methods: connector_capabilities
+                .payment_methods
+                .iter()
+                .map(Into::into)
+                .collect(),
+            currencies: connector_capabilities.currencies.map(<[_]>::to_vec),
+            countries: connector_capabilities.countries.map(<[_]>::to_vec),
+            capture_methods: connector_capabilities.capture_methods.to_vec(),
+            refunds_supported: connector_capabilities.refunds_supported,
+            mandates_supported: connector_capabilities.mandates_supported,
+            split_payments_supported: connector_capabilities.split_payments_supported,
+        },
+    ))
+}
+
+pub async fn retrieve_connector_metadata_schema(
+    connector: api_enums::Connector,
+) -> RouterResponse<admin_types::ConnectorMetadataSchemaResponse> {
+    let fields = metadata_schema::get_connector_metadata_schema(connector)
+        .map(|schema| schema.fields.iter().map(Into::into).collect())
+        .unwrap_or_default();
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ConnectorMetadataSchemaResponse { connector, fields },
+    ))
+}
+
 pub async fn create_payment_connector(
     state: AppState,
     req: api::MerchantConnectorCreate,
@@ -566,10 +1322,50 @@ pub async fn create_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+    req.metadata
+        .as_ref()
+        .map(|metadata| {
+            core_metadata::validate_metadata(&state.conf.merchant_metadata, "metadata", metadata)
+        })
+        .transpose()?;
     req.metadata
         .clone()
         .map(validate_certificate_in_mca_metadata)
         .transpose()?;
+    req.metadata
+        .as_ref()
+        .map(|metadata| validate_metadata_against_connector_schema(req.connector_name, metadata))
+        .transpose()?;
+    validate_outbound_proxy_in_connector_account_details(
+        &state.conf.connector_proxies,
+        &req.connector_account_details,
+    )?;
+    req.metadata
+        .as_ref()
+        .map(|metadata| validate_wallet_configurations_in_metadata(req.connector_name, metadata))
+        .transpose()?;
+    req.payment_methods_enabled
+        .as_deref()
+        .map(|payment_methods_enabled| {
+            validate_payment_methods_against_connector_capabilities(
+                req.connector_name,
+                payment_methods_enabled,
+                state
+                    .conf
+                    .connector_capabilities
+                    .unsupported_configuration_behaviour,
+            )
+        })
+        .transpose()?;
+    req.payment_methods_enabled
+        .as_deref()
+        .map(|payment_methods_enabled| {
+            validate_payment_method_amount_ranges(
+                payment_methods_enabled,
+                forex::is_forex_configured(&state.conf.forex),
+            )
+        })
+        .transpose()?;
 
     let merchant_account = state
         .store
@@ -583,12 +1379,19 @@ pub async fn create_payment_connector(
         &merchant_account,
     )?;
 
-    let connector_label = core_utils::get_connector_label(
-        req.business_country,
-        req.business_label.as_ref(),
-        req.business_sub_label.as_ref(),
-        &req.connector_name.to_string(),
-    );
+    let connector_label = get_connector_label_for_create(
+        store,
+        merchant_id,
+        &key_store,
+        req.connector_label.clone(),
+        core_utils::get_connector_label(
+            req.business_country,
+            req.business_label.as_ref(),
+            req.business_sub_label.as_ref(),
+            &req.connector_name.to_string(),
+        ),
+    )
+    .await?;
 
     let mut vec = Vec::new();
     let payment_methods_enabled = match req.payment_methods_enabled {
@@ -627,6 +1430,18 @@ pub async fn create_payment_connector(
             })
         }
     })?;
+    validate_test_mode_credentials(
+        req.connector_name,
+        req.test_mode,
+        &auth,
+        state.conf.livemode.live_credentials_in_test_mode_behaviour,
+    )?;
+
+    let is_webhook_endpoint_management_supported = validate_webhook_endpoint_registration(
+        &state,
+        &req.connector_name.to_string(),
+        req.register_webhook_endpoint,
+    )?;
 
     let frm_configs = get_frm_config_as_secret(req.frm_configs);
 
@@ -682,6 +1497,8 @@ pub async fn create_payment_connector(
         },
         profile_id: Some(profile_id.clone()),
         applepay_verified_domains: None,
+        disabled_flows: req.disabled_flows,
+        previous_credentials: None,
     };
 
     let mca = state
@@ -703,11 +1520,150 @@ pub async fn create_payment_connector(
             metrics::request::add_attributes("merchant", merchant_id.to_string()),
         ],
     );
+    list_cache::invalidate_payment_method_list_cache(&state, merchant_id).await;
 
-    let mca_response = mca.try_into()?;
+    let webhook_endpoint_action = (is_webhook_endpoint_management_supported
+        && req.register_webhook_endpoint == Some(true))
+    .then_some(WebhookEndpointAction::Register);
+    let (mca, webhook_endpoint_registration) =
+        sync_connector_webhook_endpoint(&state, &key_store, mca, webhook_endpoint_action).await?;
+
+    let mut mca_response: api_models::admin::MerchantConnectorResponse = mca.try_into()?;
+    audit_events::record_audit_event(
+        &state,
+        merchant_id,
+        storage::enums::AuditResourceType::MerchantConnectorAccount,
+        &mca_response.merchant_connector_id,
+        storage::enums::AuditAction::Created,
+        None,
+        Some(&mca_response),
+    )
+    .await;
+    mca_response.webhook_endpoint_registration = webhook_endpoint_registration;
     Ok(service_api::ApplicationResponse::Json(mca_response))
 }
 
+/// Checks that the webhook endpoint can be registered with the connector if requested, returns
+/// whether the connector allows managing webhook endpoints
+fn validate_webhook_endpoint_registration(
+    state: &AppState,
+    connector_name: &str,
+    register_webhook_endpoint: Option<bool>,
+) -> RouterResult<bool> {
+    let is_supported = api_enums::Connector::from_str(connector_name)
+        .map(|connector| {
+            webhook_endpoints::is_webhook_endpoint_management_supported(state, connector)
+        })
+        .unwrap_or(false);
+    if register_webhook_endpoint.is_some() && !is_supported {
+        return Err(report!(errors::ApiErrorResponse::NotSupported {
+            message: format!(
+                "Managing the webhook endpoint through the API of the connector {connector_name}"
+            ),
+        }));
+    }
+    Ok(is_supported)
+}
+
+async fn sync_connector_webhook_endpoint(
+    state: &AppState,
+    key_store: &domain::MerchantKeyStore,
+    mca: domain::MerchantConnectorAccount,
+    webhook_endpoint_action: Option<WebhookEndpointAction>,
+) -> RouterResult<(
+    domain::MerchantConnectorAccount,
+    Option<admin_types::WebhookEndpointRegistration>,
+)> {
+    match webhook_endpoint_action {
+        Some(webhook_endpoint_action) => {
+            let executor =
+                webhook_endpoints::ConnectorIntegrationExecutor::new(state, &mca.connector_name)?;
+            let (mca, registration) = webhook_endpoints::sync_webhook_endpoint(
+                state,
+                &executor,
+                key_store,
+                mca,
+                webhook_endpoint_action,
+            )
+            .await?;
+            Ok((mca, Some(registration)))
+        }
+        None => Ok((mca, None)),
+    }
+}
+
+pub(crate) fn validate_connector_label(connector_label: &str) -> RouterResult<()> {
+    let is_valid = (1..=consts::MAX_CONNECTOR_LABEL_LENGTH).contains(&connector_label.len())
+        && connector_label
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '_' | '-'));
+    if !is_valid {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "connector_label must be made of 1 to {} letters, digits, `_` or `-`",
+                consts::MAX_CONNECTOR_LABEL_LENGTH
+            ),
+        }))?
+    }
+    Ok(())
+}
+
+/// Suffixes the label with the first counter from 2 which makes it unused among the labels of
+/// the merchant, `stripe_US_default` becomes `stripe_US_default_2`
+fn get_unused_connector_label(connector_label: String, used_labels: &HashSet<&str>) -> String {
+    if !used_labels.contains(connector_label.as_str()) {
+        return connector_label;
+    }
+    let unused_label = (2u32..)
+        .map(|counter| format!("{connector_label}_{counter}"))
+        .find(|candidate| !used_labels.contains(candidate.as_str()));
+    unused_label.unwrap_or(connector_label)
+}
+
+/// Label of the connector account being created, the label in the request if provided, else the
+/// label generated from the business details, disambiguated from the labels already used by the
+/// connector accounts of the merchant. The labels are unique for the accounts created from now on,
+/// the accounts which already share a label keep it.
+async fn get_connector_label_for_create(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    key_store: &domain::MerchantKeyStore,
+    requested_label: Option<String>,
+    generated_label: Option<String>,
+) -> RouterResult<Option<String>> {
+    if let Some(connector_label) = requested_label.as_deref() {
+        validate_connector_label(connector_label)?;
+    }
+    if requested_label.is_none() && generated_label.is_none() {
+        return Ok(None);
+    }
+
+    let merchant_connector_accounts = db
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            merchant_id,
+            true,
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the connector accounts of the merchant")?;
+    let used_labels = merchant_connector_accounts
+        .iter()
+        .filter_map(|mca| mca.connector_label.as_deref())
+        .collect::<HashSet<_>>();
+
+    match requested_label {
+        Some(connector_label) if used_labels.contains(connector_label.as_str()) => {
+            Err(report!(errors::ApiErrorResponse::DuplicateConnectorLabel {
+                connector_label
+            }))
+        }
+        Some(connector_label) => Ok(Some(connector_label)),
+        None => Ok(generated_label
+            .map(|generated_label| get_unused_connector_label(generated_label, &used_labels))),
+    }
+}
+
 pub async fn retrieve_payment_connector(
     state: AppState,
     merchant_id: String,
@@ -770,12 +1726,163 @@ pub async fn list_payment_connectors(
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
     let mut response = vec![];
 
-    // The can be eliminated once [#79711](https://github.com/rust-lang/rust/issues/79711) is stabilized
-    for mca in merchant_connector_accounts.into_iter() {
-        response.push(mca.try_into()?);
-    }
+    // The can be eliminated once [#79711](https://github.com/rust-lang/rust/issues/79711) is stabilized
+    for mca in merchant_connector_accounts.into_iter() {
+        response.push(mca.try_into()?);
+    }
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+pub async fn retrieve_connector_circuit_breakers(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<admin_types::ConnectorCircuitBreakerStatusResponse> {
+    let store = state.store.as_ref();
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(
+            &merchant_id,
+            &store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_connector_accounts = store
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_id,
+            true,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
+
+    // Circuits are tracked per connector, a connector could have multiple merchant connector accounts
+    let mut connector_names = merchant_connector_accounts
+        .into_iter()
+        .map(|mca| mca.connector_name)
+        .collect::<Vec<_>>();
+    connector_names.sort();
+    connector_names.dedup();
+
+    let mut circuit_breakers = vec![];
+    for connector_name in connector_names {
+        let circuits =
+            circuit_breaker::get_connector_circuits(&state, &merchant_id, &connector_name).await?;
+        circuit_breakers.extend(circuits.into_iter().map(|circuit| {
+            admin_types::ConnectorCircuitBreakerStatus {
+                connector_name: connector_name.clone(),
+                flow: circuit.flow,
+                state: circuit.state,
+                request_count: circuit.request_count,
+                failure_count: circuit.failure_count,
+                opened_at: circuit.opened_at,
+            }
+        }));
+    }
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ConnectorCircuitBreakerStatusResponse {
+            merchant_id,
+            circuit_breakers,
+        },
+    ))
+}
+
+async fn validate_merchant_account_exists(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<()> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    db.find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    Ok(())
+}
+
+pub async fn retrieve_three_ds_decision_rules(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<admin_types::ThreeDsDecisionRulesResponse> {
+    let db = state.store.as_ref();
+    validate_merchant_account_exists(db, &merchant_id).await?;
+
+    let rules = three_ds_decision::get_three_ds_decision_rules(db, &merchant_id).await;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ThreeDsDecisionRulesResponse { merchant_id, rules },
+    ))
+}
+
+pub async fn update_three_ds_decision_rules(
+    state: AppState,
+    merchant_id: String,
+    req: admin_types::ThreeDsDecisionRulesRequest,
+) -> RouterResponse<admin_types::ThreeDsDecisionRulesResponse> {
+    let db = state.store.as_ref();
+    validate_merchant_account_exists(db, &merchant_id).await?;
+    three_ds_decision::validate_three_ds_decision_rules(&req.rules)?;
+
+    let key = three_ds_decision::get_three_ds_decision_rules_key(&merchant_id);
+    let config =
+        Encode::<Vec<admin_types::ThreeDsDecisionRule>>::encode_to_string_of_json(&req.rules)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encode the 3DS decision rules")?;
+
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_by_key(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the 3DS decision rules")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the 3DS decision rules")?,
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the 3DS decision rules")?,
+    };
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ThreeDsDecisionRulesResponse {
+            merchant_id,
+            rules: req.rules,
+        },
+    ))
+}
 
-    Ok(service_api::ApplicationResponse::Json(response))
+pub async fn delete_three_ds_decision_rules(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<admin_types::ThreeDsDecisionRulesResponse> {
+    let db = state.store.as_ref();
+    validate_merchant_account_exists(db, &merchant_id).await?;
+
+    db.delete_config_by_key(&three_ds_decision::get_three_ds_decision_rules_key(
+        &merchant_id,
+    ))
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+        message: "3DS decision rules are not configured for the merchant".to_string(),
+    })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::ThreeDsDecisionRulesResponse {
+            merchant_id,
+            rules: vec![],
+        },
+    ))
 }
 
 pub async fn update_payment_connector(
@@ -806,6 +1913,69 @@ pub async fn update_payment_connector(
             id: merchant_connector_id.to_string(),
         })?;
 
+    req.metadata
+        .as_ref()
+        .map(|metadata| {
+            core_metadata::validate_metadata(&state.conf.merchant_metadata, "metadata", metadata)
+        })
+        .transpose()?;
+    // Connectors which are not payment connectors do not declare a metadata schema
+    if let (Some(metadata), Ok(connector)) = (
+        req.metadata.as_ref(),
+        api_enums::Connector::from_str(&mca.connector_name),
+    ) {
+        validate_metadata_against_connector_schema(connector, metadata)?;
+        validate_wallet_configurations_in_metadata(connector, metadata)?;
+    }
+    req.connector_account_details
+        .as_ref()
+        .or(req.connector_account_details_new.as_ref())
+        .map(|connector_account_details| {
+            validate_outbound_proxy_in_connector_account_details(
+                &state.conf.connector_proxies,
+                connector_account_details,
+            )
+        })
+        .transpose()?;
+    if let (Some(payment_methods_enabled), Ok(connector)) = (
+        req.payment_methods_enabled.as_deref(),
+        api_enums::Connector::from_str(&mca.connector_name),
+    ) {
+        validate_payment_methods_against_connector_capabilities(
+            connector,
+            payment_methods_enabled,
+            state
+                .conf
+                .connector_capabilities
+                .unsupported_configuration_behaviour,
+        )?;
+    }
+    if let Some(payment_methods_enabled) = req.payment_methods_enabled.as_deref() {
+        validate_payment_method_amount_ranges(
+            payment_methods_enabled,
+            forex::is_forex_configured(&state.conf.forex),
+        )?;
+    }
+    if let (Some(connector_account_details), Ok(connector)) = (
+        req.connector_account_details
+            .as_ref()
+            .or(req.connector_account_details_new.as_ref()),
+        api_enums::Connector::from_str(&mca.connector_name),
+    ) {
+        // The format of the credentials is validated by the connector on its use
+        if let Ok(auth) = connector_account_details
+            .clone()
+            .parse_value::<types::ConnectorAuthType>("ConnectorAuthType")
+        {
+            validate_test_mode_credentials(
+                connector,
+                mca.test_mode,
+                &auth,
+                state.conf.livemode.live_credentials_in_test_mode_behaviour,
+            )?;
+        }
+    }
+
     let payment_methods_enabled = req.payment_methods_enabled.map(|pm_enabled| {
         pm_enabled
             .iter()
@@ -815,8 +1985,49 @@ pub async fn update_payment_connector(
             .collect::<Vec<serde_json::Value>>()
     });
 
+    let is_webhook_endpoint_management_supported = validate_webhook_endpoint_registration(
+        &state,
+        &mca.connector_name,
+        req.register_webhook_endpoint,
+    )?;
+    let connector_webhook_endpoint_id = webhook_endpoints::get_connector_webhook_details(&mca)?
+        .and_then(|webhook_details| webhook_details.connector_webhook_endpoint_id);
+    let webhook_endpoint_action = match (
+        req.register_webhook_endpoint,
+        connector_webhook_endpoint_id.is_some(),
+    ) {
+        (Some(true), false) => Some(WebhookEndpointAction::Register),
+        (Some(true), true) => Some(WebhookEndpointAction::Update),
+        (Some(false), true) => Some(WebhookEndpointAction::Delete),
+        // The registered endpoint is updated along with the merchant connector account
+        (None, true) if is_webhook_endpoint_management_supported => {
+            Some(WebhookEndpointAction::Update)
+        }
+        _ => None,
+    };
+
     let frm_configs = get_frm_config_as_secret(req.frm_configs);
 
+    if req.connector_account_details.is_some() && req.connector_account_details_new.is_some() {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`connector_account_details` and `connector_account_details_new` cannot be passed together".to_string(),
+        }
+        .into());
+    }
+    // The current credentials are kept to verify the webhooks signed with them, which are sent by
+    // the connector until the merchant completes the rotation on the connector side
+    let previous_credentials =
+        req.connector_account_details_new
+            .is_some()
+            .then(|| domain::PreviousConnectorCredentials {
+                connector_account_details: mca.connector_account_details.clone(),
+                connector_webhook_details: mca.connector_webhook_details.clone(),
+                expire_at: date_time::now()
+                    + time::Duration::seconds(
+                        state.conf.credential_rotation.grace_period_in_secs.into(),
+                    ),
+            });
+
     let payment_connector = storage::MerchantConnectorAccountUpdate::Update {
         merchant_id: None,
         connector_type: Some(req.connector_type),
@@ -824,6 +2035,7 @@ pub async fn update_payment_connector(
         merchant_connector_id: None,
         connector_account_details: req
             .connector_account_details
+            .or(req.connector_account_details_new)
             .async_lift(|inner| {
                 domain_types::encrypt_optional(inner, key_store.key.get_inner().peek())
             })
@@ -837,8 +2049,17 @@ pub async fn update_payment_connector(
         frm_configs,
         connector_webhook_details: match &req.connector_webhook_details {
             Some(connector_webhook_details) => {
+                // The registered endpoint is kept when the webhook details are updated
+                let connector_webhook_details =
+                    api_models::admin::MerchantConnectorWebhookDetails {
+                        connector_webhook_endpoint_id: connector_webhook_details
+                            .connector_webhook_endpoint_id
+                            .clone()
+                            .or(connector_webhook_endpoint_id),
+                        ..connector_webhook_details.clone()
+                    };
                 Encode::<api_models::admin::MerchantConnectorWebhookDetails>::encode_to_value(
-                    connector_webhook_details,
+                    &connector_webhook_details,
                 )
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .map(Some)?
@@ -847,8 +2068,11 @@ pub async fn update_payment_connector(
             None => None,
         },
         applepay_verified_domains: None,
+        disabled_flows: req.disabled_flows,
+        previous_credentials,
     };
 
+    let previous_mca = api_models::admin::MerchantConnectorResponse::try_from(mca.clone()).ok();
     let updated_mca = db
         .update_merchant_connector_account(mca, payment_connector.into(), &key_store)
         .await
@@ -856,8 +2080,84 @@ pub async fn update_payment_connector(
         .attach_printable_lazy(|| {
             format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
         })?;
+    list_cache::invalidate_payment_method_list_cache(&state, merchant_id).await;
+
+    let (updated_mca, webhook_endpoint_registration) =
+        sync_connector_webhook_endpoint(&state, &key_store, updated_mca, webhook_endpoint_action)
+            .await?;
+
+    let mut response: api_models::admin::MerchantConnectorResponse = updated_mca.try_into()?;
+    audit_events::record_audit_event(
+        &state,
+        merchant_id,
+        storage::enums::AuditResourceType::MerchantConnectorAccount,
+        merchant_connector_id,
+        storage::enums::AuditAction::Updated,
+        previous_mca.as_ref(),
+        Some(&response),
+    )
+    .await;
+    response.webhook_endpoint_registration = webhook_endpoint_registration;
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+/// Drops the credentials replaced in the last rotation, after which the webhooks signed with them
+/// are not accepted
+pub async fn finalize_connector_credential_rotation(
+    state: AppState,
+    merchant_id: &str,
+    merchant_connector_id: &str,
+) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mca = db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            merchant_id,
+            merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.to_string(),
+        })?;
+
+    if mca.previous_credentials.is_none() {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The credentials of the merchant connector account are not being rotated"
+                .to_string(),
+        }
+        .into());
+    }
+
+    let previous_mca = api_models::admin::MerchantConnectorResponse::try_from(mca.clone()).ok();
+    let updated_mca = db
+        .update_merchant_connector_account(
+            mca,
+            storage::MerchantConnectorAccountUpdate::FinalizeCredentialRotation.into(),
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
+        })?;
 
-    let response = updated_mca.try_into()?;
+    let response: api_models::admin::MerchantConnectorResponse = updated_mca.try_into()?;
+    audit_events::record_audit_event(
+        &state,
+        merchant_id,
+        storage::enums::AuditResourceType::MerchantConnectorAccount,
+        merchant_connector_id,
+        storage::enums::AuditAction::Updated,
+        previous_mca.as_ref(),
+        Some(&response),
+    )
+    .await;
 
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -878,7 +2178,7 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let _mca = db
+    let mca = db
         .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
             &merchant_id,
             &merchant_connector_id,
@@ -889,6 +2189,19 @@ pub async fn delete_payment_connector(
             id: merchant_connector_id.clone(),
         })?;
 
+    let is_webhook_endpoint_registered = webhook_endpoints::get_connector_webhook_details(&mca)?
+        .and_then(|webhook_details| webhook_details.connector_webhook_endpoint_id)
+        .is_some();
+    let webhook_endpoint_registration = if is_webhook_endpoint_registered
+        && validate_webhook_endpoint_registration(&state, &mca.connector_name, None)?
+    {
+        let executor =
+            webhook_endpoints::ConnectorIntegrationExecutor::new(&state, &mca.connector_name)?;
+        Some(webhook_endpoints::delete_webhook_endpoint(&state, &executor, &key_store, &mca).await?)
+    } else {
+        None
+    };
+
     let is_deleted = db
         .delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
             &merchant_id,
@@ -899,10 +2212,27 @@ pub async fn delete_payment_connector(
             id: merchant_connector_id.clone(),
         })?;
 
+    if is_deleted {
+        list_cache::invalidate_payment_method_list_cache(&state, &merchant_id).await;
+        audit_events::record_audit_event(
+            &state,
+            &merchant_id,
+            storage::enums::AuditResourceType::MerchantConnectorAccount,
+            &merchant_connector_id,
+            storage::enums::AuditAction::Deleted,
+            api_models::admin::MerchantConnectorResponse::try_from(mca)
+                .ok()
+                .as_ref(),
+            None,
+        )
+        .await;
+    }
+
     let response = api::MerchantConnectorDeleteResponse {
         merchant_id,
         merchant_connector_id,
         deleted: is_deleted,
+        webhook_endpoint_registration,
     };
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -1018,13 +2348,30 @@ pub fn get_frm_config_as_secret(
 
 pub async fn create_and_insert_business_profile(
     db: &dyn StorageInterface,
-    request: api::BusinessProfileCreate,
+    mut request: api::BusinessProfileCreate,
     merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
 ) -> RouterResult<storage::business_profile::BusinessProfile> {
-    let business_profile_new = storage::business_profile::BusinessProfileNew::foreign_try_from((
-        merchant_account,
-        request,
-    ))?;
+    // A profile created without webhook details inherits the ones of the merchant account, its
+    // subscriptions are then signed with the keys of the merchant account
+    let webhook_auth_details = request
+        .webhook_details
+        .as_mut()
+        .map(|webhook_details| get_webhook_auth_details(webhook_details, None))
+        .transpose()?
+        .flatten();
+
+    let business_profile_new = storage::business_profile::BusinessProfileNew {
+        webhook_auth_details: encrypt_business_profile_webhook_auth_details(
+            webhook_auth_details,
+            key_store,
+        )
+        .await?,
+        ..storage::business_profile::BusinessProfileNew::foreign_try_from((
+            merchant_account,
+            request,
+        ))?
+    };
 
     let profile_name = business_profile_new.profile_name.clone();
 
@@ -1066,8 +2413,44 @@ pub async fn create_business_profile(
             .attach_printable("Invalid routing algorithm given")?;
     }
 
+    validate_business_profile_webhook_details(request.webhook_details.as_ref())?;
+
+    request
+        .pii_policy
+        .as_ref()
+        .map(validate_business_profile_pii_policy)
+        .transpose()?;
+
+    request
+        .session_expiry
+        .map(helpers::validate_session_expiry)
+        .transpose()?;
+
+    request
+        .default_locale
+        .as_deref()
+        .map(helpers::validate_locale)
+        .transpose()?;
+
+    request
+        .connector_request_reference_format
+        .as_deref()
+        .map(connector_reference::validate_reference_format)
+        .transpose()?;
+
+    request
+        .duplicate_order_reference_window
+        .map(helpers::validate_duplicate_order_reference_window)
+        .transpose()?;
+
+    request
+        .alert_auto_refund_max_amount
+        .map(alerts::validate_auto_refund_max_amount)
+        .transpose()?;
+
     let business_profile =
-        create_and_insert_business_profile(db, request, merchant_account.clone()).await?;
+        create_and_insert_business_profile(db, request, merchant_account.clone(), &key_store)
+            .await?;
 
     if merchant_account.default_profile.is_some() {
         let unset_default_profile = domain::MerchantAccountUpdate::UnsetDefaultProfile;
@@ -1105,15 +2488,22 @@ pub async fn list_business_profile(
 pub async fn retrieve_business_profile(
     state: AppState,
     profile_id: String,
+    merchant_id: &str,
 ) -> RouterResponse<api_models::admin::BusinessProfileResponse> {
     let db = state.store.as_ref();
     let business_profile = db
         .find_business_profile_by_profile_id(&profile_id)
         .await
         .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
-            id: profile_id,
+            id: profile_id.clone(),
         })?;
 
+    if business_profile.merchant_id != merchant_id {
+        Err(errors::ApiErrorResponse::AccessForbidden {
+            resource: profile_id,
+        })?
+    }
+
     Ok(service_api::ApplicationResponse::Json(
         api_models::admin::BusinessProfileResponse::foreign_try_from(business_profile)
             .change_context(errors::ApiErrorResponse::InternalServerError)?,
@@ -1156,8 +2546,30 @@ pub async fn update_business_profile(
         })?
     }
 
-    let webhook_details = request
-        .webhook_details
+    validate_business_profile_webhook_details(request.webhook_details.as_ref())?;
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let previous_webhook_auth_details =
+        get_business_profile_webhook_auth_details(&business_profile, &key_store).await?;
+
+    let mut webhook_details = request.webhook_details;
+    if let Some(webhook_details) = webhook_details.as_mut() {
+        pin_webhook_payload_versions(webhook_details, business_profile.webhook_details.clone());
+    }
+    let webhook_auth_details = webhook_details
+        .as_mut()
+        .map(|webhook_details| {
+            get_webhook_auth_details(webhook_details, previous_webhook_auth_details)
+        })
+        .transpose()?
+        .flatten();
+    let webhook_auth_details =
+        encrypt_business_profile_webhook_auth_details(webhook_auth_details, &key_store).await?;
+
+    let webhook_details = webhook_details
         .as_ref()
         .map(|webhook_details| {
             utils::Encode::<api::WebhookDetails>::encode_to_value(webhook_details).change_context(
@@ -1168,6 +2580,42 @@ pub async fn update_business_profile(
         })
         .transpose()?;
 
+    let pii_policy = request
+        .pii_policy
+        .as_ref()
+        .map(|pii_policy| {
+            validate_business_profile_pii_policy(pii_policy)?;
+            encode_pii_policy(pii_policy)
+        })
+        .transpose()?;
+
+    request
+        .session_expiry
+        .map(helpers::validate_session_expiry)
+        .transpose()?;
+
+    request
+        .default_locale
+        .as_deref()
+        .map(helpers::validate_locale)
+        .transpose()?;
+
+    request
+        .connector_request_reference_format
+        .as_deref()
+        .map(connector_reference::validate_reference_format)
+        .transpose()?;
+
+    request
+        .duplicate_order_reference_window
+        .map(helpers::validate_duplicate_order_reference_window)
+        .transpose()?;
+
+    request
+        .alert_auto_refund_max_amount
+        .map(alerts::validate_auto_refund_max_amount)
+        .transpose()?;
+
     if let Some(ref routing_algorithm) = request.routing_algorithm {
         let _: api::RoutingAlgorithm = routing_algorithm
             .clone()
@@ -1193,6 +2641,19 @@ pub async fn update_business_profile(
         payout_routing_algorithm: request.payout_routing_algorithm,
         is_recon_enabled: None,
         applepay_verified_domains: request.applepay_verified_domains,
+        statement_descriptor_name: request.statement_descriptor_name,
+        statement_descriptor_suffix: request.statement_descriptor_suffix,
+        session_expiry: request.session_expiry.map(i64::from),
+        default_locale: request.default_locale,
+        default_payment_description: request.default_payment_description,
+        connector_request_reference_format: request.connector_request_reference_format,
+        duplicate_order_reference_behaviour: request.duplicate_order_reference_behaviour,
+        duplicate_order_reference_window: request.duplicate_order_reference_window.map(i64::from),
+        alert_auto_refund_max_amount: request.alert_auto_refund_max_amount,
+        default_currency: request.default_currency,
+        default_country: request.default_country,
+        pii_policy,
+        webhook_auth_details,
     };
 
     let updated_business_profile = db
@@ -1434,3 +2895,528 @@ pub async fn validate_dummy_connector_enabled(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_webhook_details(subscriptions: serde_json::Value) -> api::WebhookDetails {
+        serde_json::from_value(serde_json::json!({ "subscriptions": subscriptions })).unwrap()
+    }
+
+    #[test]
+    fn test_webhook_subscription_secrets_are_stored_apart_and_kept_on_update() {
+        let mut webhook_details = get_webhook_details(serde_json::json!([{
+            "subscription_id": "refunds",
+            "event_type": "refund_*",
+            "url": "https://ekart.com/webhooks/refunds",
+            "secret": "whsec_refunds",
+        }]));
+        let auth_details = get_webhook_auth_details(&mut webhook_details, None)
+            .unwrap()
+            .unwrap();
+
+        // The secret is neither stored with the webhook details nor returned in the responses
+        let stored_webhook_details = serde_json::to_value(&webhook_details).unwrap();
+        assert!(stored_webhook_details["subscriptions"][0]
+            .get("secret")
+            .is_none());
+        let auth_details = parse_webhook_auth_details(Some(auth_details))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            auth_details
+                .subscription_secrets
+                .as_ref()
+                .and_then(|secrets| secrets.get("refunds"))
+                .map(|secret| secret.peek().as_str()),
+            Some("whsec_refunds")
+        );
+
+        // An update which does not pass the secret of the subscription keeps it
+        let mut webhook_details = get_webhook_details(serde_json::json!([{
+            "subscription_id": "refunds",
+            "event_type": "refund_succeeded",
+            "url": "https://ekart.com/webhooks/refunds",
+        }]));
+        let updated_auth_details =
+            get_webhook_auth_details(&mut webhook_details, Some(auth_details.clone()))
+                .unwrap()
+                .unwrap();
+        let updated_auth_details = parse_webhook_auth_details(Some(updated_auth_details))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated_auth_details
+                .subscription_secrets
+                .as_ref()
+                .and_then(|secrets| secrets.get("refunds"))
+                .map(|secret| secret.peek().as_str()),
+            Some("whsec_refunds")
+        );
+
+        // A new subscription requires a secret
+        let mut webhook_details = get_webhook_details(serde_json::json!([{
+            "subscription_id": "payments",
+            "event_type": "payment_*",
+            "url": "https://ekart.com/webhooks/payments",
+        }]));
+        let error = get_webhook_auth_details(&mut webhook_details, Some(auth_details)).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::MissingRequiredField { field_name }
+                if *field_name == "webhook_details.subscriptions.secret"
+        ));
+    }
+
+    #[test]
+    fn test_webhook_subscription_url_must_be_https() {
+        let webhook_details = get_webhook_details(serde_json::json!([{
+            "subscription_id": "refunds",
+            "event_type": "refund_*",
+            "url": "http://ekart.com/webhooks/refunds",
+            "secret": "whsec_refunds",
+        }]));
+        let error = validate_webhook_subscriptions(&webhook_details).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { message }
+                if message.contains("must be an HTTPS url")
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_payment_methods_are_rejected_per_behaviour() {
+        let payment_methods_enabled: Vec<api::PaymentMethodsEnabled> =
+            serde_json::from_value(serde_json::json!([{
+                "payment_method": "bank_transfer",
+                "payment_method_types": [{
+                    "payment_method_type": "pix",
+                    "recurring_enabled": false,
+                    "installment_payment_enabled": false
+                }]
+            }]))
+            .unwrap();
+
+        let error = validate_payment_methods_against_connector_capabilities(
+            api_enums::Connector::Authorizedotnet,
+            &payment_methods_enabled,
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { message }
+                if message.contains("payment method bank_transfer is not supported")
+        ));
+
+        assert!(validate_payment_methods_against_connector_capabilities(
+            api_enums::Connector::Authorizedotnet,
+            &payment_methods_enabled,
+            UnsupportedConfigurationBehaviour::Warn,
+        )
+        .is_ok());
+        assert!(validate_payment_methods_against_connector_capabilities(
+            api_enums::Connector::Adyen,
+            &payment_methods_enabled,
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .is_ok());
+        // Connectors which do not declare their capabilities are not validated
+        assert!(validate_payment_methods_against_connector_capabilities(
+            api_enums::Connector::Nmi,
+            &payment_methods_enabled,
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_live_credentials_in_test_mode_are_rejected_per_behaviour() {
+        let header_key = |api_key: &str| types::ConnectorAuthType::HeaderKey {
+            api_key: Secret::new(api_key.to_string()),
+        };
+        let live_auth = header_key("sk_live_51HfQ2e");
+
+        let error = validate_test_mode_credentials(
+            api_enums::Connector::Stripe,
+            Some(true),
+            &live_auth,
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { message }
+                if message.contains("live credentials of the connector stripe")
+        ));
+
+        assert!(validate_test_mode_credentials(
+            api_enums::Connector::Stripe,
+            Some(true),
+            &live_auth,
+            UnsupportedConfigurationBehaviour::Warn,
+        )
+        .is_ok());
+        assert!(validate_test_mode_credentials(
+            api_enums::Connector::Stripe,
+            None,
+            &live_auth,
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .is_ok());
+        assert!(validate_test_mode_credentials(
+            api_enums::Connector::Stripe,
+            Some(true),
+            &header_key("sk_test_51HfQ2e"),
+            UnsupportedConfigurationBehaviour::Reject,
+        )
+        .is_ok());
+
+        assert!(are_live_credentials(
+            api_enums::Connector::Mollie,
+            &header_key("live_dHar4XY7LxsDOtmnkVtjNVWXLSlXsM")
+        ));
+        assert!(!are_live_credentials(
+            api_enums::Connector::Checkout,
+            &types::ConnectorAuthType::SignatureKey {
+                api_key: Secret::new("pk_sbox_xg66bnn6tpspd6pt3psc7otrqa".to_string()),
+                key1: Secret::new("pc_ovo75iz4hdyudnx6tu74mum3fq".to_string()),
+                api_secret: Secret::new("sk_sbox_m73dzbpy7cf3gfd46xr4yj5xo4e".to_string()),
+            }
+        ));
+        // Connectors whose live and test keys cannot be told apart are not validated
+        assert!(!are_live_credentials(
+            api_enums::Connector::Adyen,
+            &header_key("AQEyhmfxK4")
+        ));
+    }
+
+    #[test]
+    fn test_amount_ranges_are_validated() {
+        let get_payment_methods_enabled = |payment_method_type: serde_json::Value| {
+            serde_json::from_value::<Vec<api::PaymentMethodsEnabled>>(serde_json::json!([{
+                "payment_method": "pay_later",
+                "payment_method_types": [payment_method_type]
+            }]))
+            .unwrap()
+        };
+        let get_error_message = |payment_methods_enabled: &[api::PaymentMethodsEnabled],
+                                 is_forex_configured: bool| {
+            match validate_payment_method_amount_ranges(
+                payment_methods_enabled,
+                is_forex_configured,
+            )
+            .unwrap_err()
+            .current_context()
+            {
+                errors::ApiErrorResponse::InvalidRequestData { message } => message.clone(),
+                error => panic!("Unexpected error {error:?}"),
+            }
+        };
+
+        let klarna = get_payment_methods_enabled(serde_json::json!({
+            "payment_method_type": "klarna",
+            "minimum_amount": 3500,
+            "maximum_amount": 100000,
+            "amount_range_currency": "EUR",
+            "accepted_currencies": { "type": "enable_only", "list": ["EUR"] },
+            "recurring_enabled": false,
+            "installment_payment_enabled": true
+        }));
+        assert!(validate_payment_method_amount_ranges(&klarna, false).is_ok());
+
+        let inverted_range = get_payment_methods_enabled(serde_json::json!({
+            "payment_method_type": "klarna",
+            "minimum_amount": 100000,
+            "maximum_amount": 3500,
+            "recurring_enabled": false,
+            "installment_payment_enabled": true
+        }));
+        assert_eq!(
+            get_error_message(&inverted_range, true),
+            "Invalid amount range of payment method type klarna: `minimum_amount` 100000 is greater than `maximum_amount` 3500"
+        );
+
+        // A range in USD applied to payments in other currencies requires exchange rates
+        let apple_pay = get_payment_methods_enabled(serde_json::json!({
+            "payment_method_type": "apple_pay",
+            "maximum_amount": 500000,
+            "amount_range_currency": "USD",
+            "accepted_currencies": { "type": "enable_only", "list": ["USD", "EUR"] },
+            "recurring_enabled": true,
+            "installment_payment_enabled": false
+        }));
+        assert!(get_error_message(&apple_pay, false)
+            .contains("exchange rates are not configured to convert the amount of payments"));
+        assert!(validate_payment_method_amount_ranges(&apple_pay, true).is_ok());
+
+        // 500000 is 5000.00 USD but 500000 JPY, the currency of the range is ambiguous
+        let ambiguous_currency = get_payment_methods_enabled(serde_json::json!({
+            "payment_method_type": "apple_pay",
+            "maximum_amount": 500000,
+            "accepted_currencies": { "type": "enable_only", "list": ["USD", "JPY"] },
+            "recurring_enabled": true,
+            "installment_payment_enabled": false
+        }));
+        assert!(get_error_message(&ambiguous_currency, true)
+            .contains("the accepted currencies have different numbers of decimal digits"));
+    }
+
+    async fn create_merchant_key_store(
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+    ) -> domain::MerchantKeyStore {
+        let master_key = db.get_master_key().to_vec();
+        db.insert_merchant_key_store(
+            domain::MerchantKeyStore {
+                merchant_id: merchant_id.to_string(),
+                key: domain_types::encrypt(
+                    services::generate_aes256_key().unwrap().to_vec().into(),
+                    &master_key,
+                )
+                .await
+                .unwrap(),
+                created_at: date_time::now(),
+            },
+            &master_key.into(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn get_merchant_connector_account(
+        key_store: &domain::MerchantKeyStore,
+        connector_label: Option<String>,
+    ) -> domain::MerchantConnectorAccount {
+        domain::MerchantConnectorAccount {
+            id: None,
+            merchant_id: key_store.merchant_id.clone(),
+            connector_name: "stripe".to_string(),
+            connector_account_details: domain_types::encrypt(
+                serde_json::Value::default().into(),
+                key_store.key.get_inner().peek(),
+            )
+            .await
+            .unwrap(),
+            test_mode: None,
+            disabled: None,
+            merchant_connector_id: utils::generate_id(consts::ID_LENGTH, "mca"),
+            payment_methods_enabled: None,
+            connector_type: api_enums::ConnectorType::PaymentProcessor,
+            metadata: None,
+            frm_configs: None,
+            connector_label,
+            business_country: Some(api_enums::CountryAlpha2::US),
+            business_label: Some("default".to_string()),
+            business_sub_label: None,
+            created_at: date_time::now(),
+            modified_at: date_time::now(),
+            connector_webhook_details: None,
+            profile_id: Some("pro_default".to_string()),
+            applepay_verified_domains: None,
+            disabled_flows: None,
+            previous_credentials: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generated_connector_labels_are_distinct() {
+        let mock_db = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let db: &dyn StorageInterface = &mock_db;
+        let key_store = create_merchant_key_store(db, "merchant_1").await;
+
+        let mut connector_labels = Vec::new();
+        for _ in 0..3 {
+            let connector_label = get_connector_label_for_create(
+                db,
+                "merchant_1",
+                &key_store,
+                None,
+                Some("stripe_US_default".to_string()),
+            )
+            .await
+            .unwrap();
+            db.insert_merchant_connector_account(
+                get_merchant_connector_account(&key_store, connector_label.clone()).await,
+                &key_store,
+            )
+            .await
+            .unwrap();
+            connector_labels.extend(connector_label);
+        }
+
+        assert_eq!(
+            connector_labels,
+            vec![
+                "stripe_US_default",
+                "stripe_US_default_2",
+                "stripe_US_default_3"
+            ]
+        );
+        let mca = db
+            .find_merchant_connector_account_by_merchant_id_connector_label(
+                "merchant_1",
+                "stripe_US_default_2",
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(mca.connector_label.as_deref(), Some("stripe_US_default_2"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_connector_label_is_rejected() {
+        let mock_db = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let db: &dyn StorageInterface = &mock_db;
+        let key_store = create_merchant_key_store(db, "merchant_1").await;
+        db.insert_merchant_connector_account(
+            get_merchant_connector_account(&key_store, Some("stripe_travel".to_string())).await,
+            &key_store,
+        )
+        .await
+        .unwrap();
+
+        let error = get_connector_label_for_create(
+            db,
+            "merchant_1",
+            &key_store,
+            Some("stripe_travel".to_string()),
+            Some("stripe_US_default".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::DuplicateConnectorLabel { connector_label }
+                if connector_label == "stripe_travel"
+        ));
+
+        let connector_label = get_connector_label_for_create(
+            db,
+            "merchant_1",
+            &key_store,
+            Some("stripe_travel_2".to_string()),
+            Some("stripe_US_default".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(connector_label.as_deref(), Some("stripe_travel_2"));
+    }
+
+    #[tokio::test]
+    async fn test_connector_credentials_update_is_audited_masked() {
+        let state = AppState::with_storage(
+            settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = state.store.as_ref();
+        let key_store = create_merchant_key_store(db, "merchant_audit").await;
+        db.insert_merchant(
+            domain::MerchantAccount {
+                id: None,
+                merchant_id: "merchant_audit".to_string(),
+                return_url: None,
+                enable_payment_response_hash: false,
+                payment_response_hash_key: None,
+                redirect_to_merchant_with_http_post: false,
+                merchant_name: None,
+                merchant_details: None,
+                webhook_details: None,
+                sub_merchants_enabled: None,
+                parent_merchant_id: None,
+                publishable_key: None,
+                storage_scheme: MerchantStorageScheme::PostgresOnly,
+                locker_id: None,
+                metadata: None,
+                routing_algorithm: None,
+                primary_business_details: serde_json::Value::Null,
+                frm_routing_algorithm: None,
+                created_at: date_time::now(),
+                modified_at: date_time::now(),
+                intent_fulfillment_time: None,
+                payout_routing_algorithm: None,
+                organization_id: None,
+                is_recon_enabled: false,
+                default_profile: None,
+                recon_status: storage::enums::ReconStatus::NotRequested,
+                webhook_auth_details: None,
+                default_currency: None,
+                default_country: None,
+                pii_policy: None,
+            },
+            &key_store,
+        )
+        .await
+        .unwrap();
+        let mca = db
+            .insert_merchant_connector_account(
+                get_merchant_connector_account(&key_store, Some("stripe_audit".to_string())).await,
+                &key_store,
+            )
+            .await
+            .unwrap();
+
+        let req: api_models::admin::MerchantConnectorUpdate =
+            serde_json::from_value(serde_json::json!({
+                "connector_type": "payment_processor",
+                "connector_account_details": {
+                    "auth_type": "HeaderKey",
+                    "api_key": "sk_test_updated_secret"
+                }
+            }))
+            .unwrap();
+        update_payment_connector(
+            state.clone(),
+            "merchant_audit",
+            &mca.merchant_connector_id,
+            req,
+        )
+        .await
+        .unwrap();
+
+        let audit_events = db
+            .list_audit_events_by_merchant_id(
+                "merchant_audit",
+                Some(storage::enums::AuditResourceType::MerchantConnectorAccount),
+                Some(&mca.merchant_connector_id),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(audit_events.len(), 1);
+        let audit_event = &audit_events[0];
+        assert_eq!(audit_event.action, storage::enums::AuditAction::Updated);
+        assert_eq!(
+            audit_event.diff["connector_account_details"],
+            serde_json::json!({
+                "before": masking::MASKED_VALUE,
+                "after": masking::MASKED_VALUE,
+            })
+        );
+        assert!(!audit_event
+            .diff
+            .to_string()
+            .contains("sk_test_updated_secret"));
+    }
+
+    #[test]
+    fn test_connector_label_validation() {
+        assert!(validate_connector_label("stripe_US-travel_2").is_ok());
+        assert!(validate_connector_label("").is_err());
+        assert!(validate_connector_label("stripe US").is_err());
+        assert!(validate_connector_label("stripe/US").is_err());
+        assert!(validate_connector_label(&"a".repeat(65)).is_err());
+    }
+}
@@ -0,0 +1,457 @@
+//! Chargeback prevention alerts, which the connectors send ahead of a chargeback when the issuer
+//! reports a payment as fraudulent or the card holder disputes it. The alert is matched to the
+//! payment by the connector transaction id, or by the acquirer reference number when the alert
+//! does not identify the payment at the connector, and is stored with the payload sent by the
+//! connector. A succeeded payment whose captured amount is within the auto-refund limit of the
+//! business profile is refunded, so that the chargeback and its fee are avoided.
+
+use std::future::Future;
+
+use api_models::refunds::{RefundRequest, RefundResponse, RefundStatus};
+use error_stack::{report, ResultExt};
+use masking::Secret;
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResult, StorageErrorExt},
+        refunds,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    types::{
+        api, domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+pub fn validate_auto_refund_max_amount(
+    alert_auto_refund_max_amount: i64,
+) -> Result<(), errors::ApiErrorResponse> {
+    if alert_auto_refund_max_amount <= 0 {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "alert_auto_refund_max_amount should be greater than 0".to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Finds the payment attempt of the alert by the connector transaction id, or by the acquirer
+/// reference number when the alert does not carry the connector transaction id
+pub async fn find_payment_attempt_for_alert(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    alert_details: &api::AlertPayload,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> RouterResult<storage::PaymentAttempt> {
+    match (
+        alert_details.connector_transaction_id.as_deref(),
+        alert_details.acquirer_reference_number.as_deref(),
+    ) {
+        (Some(connector_transaction_id), _) => db
+            .find_payment_attempt_by_merchant_id_connector_txn_id(
+                merchant_id,
+                connector_transaction_id,
+                storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound),
+        (None, Some(acquirer_reference_number)) => db
+            .find_payment_attempt_by_merchant_id_acquirer_reference_number(
+                merchant_id,
+                acquirer_reference_number,
+                storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound),
+        (None, None) => Err(report!(errors::ApiErrorResponse::WebhookResourceNotFound))
+            .attach_printable("Alert carries neither a connector transaction id nor an ARN"),
+    }
+}
+
+/// The action taken automatically on an alert about the payment. Only the succeeded payments whose
+/// captured amount is within the auto-refund limit are refunded, the auto-refund is disabled when
+/// the business profile has no limit.
+pub fn get_alert_action(
+    alert_auto_refund_max_amount: Option<i64>,
+    payment_intent: &storage::PaymentIntent,
+) -> Option<enums::AlertAction> {
+    let max_amount = alert_auto_refund_max_amount?;
+    let amount_captured = payment_intent.amount_captured?;
+    (payment_intent.status == enums::IntentStatus::Succeeded && amount_captured <= max_amount)
+        .then_some(enums::AlertAction::Refund)
+}
+
+fn get_refund_reason(alert_type: enums::AlertType) -> enums::RefundReason {
+    match alert_type {
+        enums::AlertType::Fraud => enums::RefundReason::Fraudulent,
+        enums::AlertType::Dispute => enums::RefundReason::Other,
+    }
+}
+
+/// Stores the alert and takes the action of the auto-refund policy on it. The refund is created
+/// with `refund`, a failure to refund the payment is recorded in the status of the alert and does
+/// not fail the alert.
+#[instrument(skip_all)]
+pub async fn record_alert<F, Fut>(
+    db: &dyn StorageInterface,
+    alert: storage::AlertNew,
+    payment_intent: &storage::PaymentIntent,
+    alert_auto_refund_max_amount: Option<i64>,
+    refund: F,
+) -> RouterResult<storage::Alert>
+where
+    F: FnOnce(RefundRequest) -> Fut,
+    Fut: Future<Output = RouterResult<RefundResponse>>,
+{
+    let alert = db
+        .insert_alert(alert)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the alert")?;
+
+    let Some(action) = get_alert_action(alert_auto_refund_max_amount, payment_intent) else {
+        return Ok(alert);
+    };
+
+    let refund_request = RefundRequest {
+        payment_id: alert.payment_id.clone(),
+        merchant_id: Some(alert.merchant_id.clone()),
+        reason: Some(format!(
+            "Refunded automatically on the {} alert {}",
+            alert.alert_type, alert.connector_alert_id
+        )),
+        reason_code: Some(get_refund_reason(alert.alert_type)),
+        ..Default::default()
+    };
+    let alert_update = match refund(refund_request).await {
+        Ok(refund_response) if refund_response.status != RefundStatus::Failed => {
+            storage::AlertUpdate::ActionUpdate {
+                status: enums::AlertStatus::Actioned,
+                action,
+                refund_id: Some(refund_response.refund_id),
+            }
+        }
+        Ok(refund_response) => {
+            logger::warn!(
+                "Refund {} of the alert {} failed",
+                refund_response.refund_id,
+                alert.alert_id
+            );
+            storage::AlertUpdate::ActionUpdate {
+                status: enums::AlertStatus::ActionFailed,
+                action,
+                refund_id: Some(refund_response.refund_id),
+            }
+        }
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to refund the payment of the alert {}",
+                alert.alert_id
+            );
+            storage::AlertUpdate::ActionUpdate {
+                status: enums::AlertStatus::ActionFailed,
+                action,
+                refund_id: None,
+            }
+        }
+    };
+
+    db.update_alert(alert, alert_update)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the action of the alert")
+}
+
+/// Processes the alert sent by the connector. Returns `None` when the alert has already been
+/// received, as the connectors may send the same alert more than once.
+#[instrument(skip_all)]
+pub async fn process_alert(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connector: &str,
+    alert_details: api::AlertPayload,
+    raw_payload: serde_json::Value,
+) -> RouterResult<Option<storage::Alert>> {
+    let db = &*state.store;
+    let existing_alert = db
+        .find_alert_by_merchant_id_connector_connector_alert_id(
+            &merchant_account.merchant_id,
+            connector,
+            &alert_details.connector_alert_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find the alert")?;
+    if let Some(existing_alert) = existing_alert {
+        logger::info!(
+            "Alert {} has already been received as {}",
+            alert_details.connector_alert_id,
+            existing_alert.alert_id
+        );
+        return Ok(None);
+    }
+
+    let payment_attempt = find_payment_attempt_for_alert(
+        db,
+        &merchant_account.merchant_id,
+        &alert_details,
+        merchant_account.storage_scheme,
+    )
+    .await?;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_attempt.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)?;
+
+    let alert_auto_refund_max_amount = match payment_intent.profile_id.as_ref() {
+        Some(profile_id) => {
+            db.find_business_profile_by_profile_id(profile_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+                    id: profile_id.to_owned(),
+                })?
+                .alert_auto_refund_max_amount
+        }
+        None => None,
+    };
+
+    let alert = storage::AlertNew {
+        alert_id: utils::generate_id(consts::ID_LENGTH, "alert"),
+        merchant_id: merchant_account.merchant_id.clone(),
+        payment_id: payment_attempt.payment_id,
+        attempt_id: payment_attempt.attempt_id,
+        connector: connector.to_owned(),
+        connector_alert_id: alert_details.connector_alert_id,
+        alert_type: alert_details.alert_type,
+        initiated_by: alert_details.initiated_by,
+        connector_reason: alert_details.connector_reason,
+        status: enums::AlertStatus::Received,
+        action: None,
+        refund_id: None,
+        raw_payload: Secret::new(raw_payload),
+    };
+
+    record_alert(
+        db,
+        alert,
+        &payment_intent,
+        alert_auto_refund_max_amount,
+        |refund_request| refunds::create_refund(state, merchant_account, key_store, refund_request),
+    )
+    .await
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::Mutex;
+
+    use super::*;
+
+    async fn get_mock_db() -> crate::db::MockDb {
+        crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap()
+    }
+
+    async fn insert_payment(db: &dyn StorageInterface, amount_captured: i64) {
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        db.insert_payment_intent(
+            storage::PaymentIntentNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                status: enums::IntentStatus::Succeeded,
+                amount: amount_captured,
+                amount_captured: Some(amount_captured),
+                currency: Some(enums::Currency::USD),
+                active_attempt_id: "pay_1_1".to_string(),
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        db.insert_payment_attempt(
+            storage::PaymentAttemptNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "pay_1_1".to_string(),
+                status: enums::AttemptStatus::Charged,
+                amount: amount_captured,
+                connector: Some("stripe".to_string()),
+                connector_transaction_id: Some("pi_1".to_string()),
+                acquirer_reference_number: Some("74537604221431003881865".to_string()),
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+    }
+
+    fn get_alert_payload(
+        connector_transaction_id: Option<&str>,
+        acquirer_reference_number: Option<&str>,
+    ) -> api::AlertPayload {
+        api::AlertPayload {
+            connector_alert_id: "issfr_1".to_string(),
+            alert_type: enums::AlertType::Fraud,
+            initiated_by: Some("issuer".to_string()),
+            connector_reason: Some("made_with_stolen_card".to_string()),
+            connector_transaction_id: connector_transaction_id.map(str::to_string),
+            acquirer_reference_number: acquirer_reference_number.map(str::to_string),
+        }
+    }
+
+    fn get_alert() -> storage::AlertNew {
+        storage::AlertNew {
+            alert_id: "alert_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            payment_id: "pay_1".to_string(),
+            attempt_id: "pay_1_1".to_string(),
+            connector: "stripe".to_string(),
+            connector_alert_id: "issfr_1".to_string(),
+            alert_type: enums::AlertType::Fraud,
+            initiated_by: Some("issuer".to_string()),
+            connector_reason: Some("made_with_stolen_card".to_string()),
+            status: enums::AlertStatus::Received,
+            action: None,
+            refund_id: None,
+            raw_payload: Secret::new(serde_json::json!({ "id": "issfr_1" })),
+        }
+    }
+
+    fn get_refund_response(request: &RefundRequest) -> RefundResponse {
+        RefundResponse {
+            refund_id: "ref_1".to_string(),
+            payment_id: request.payment_id.clone(),
+            amount: 1000,
+            currency: "USD".to_string(),
+            reason: request.reason.clone(),
+            reason_code: request.reason_code,
+            status: RefundStatus::Pending,
+            metadata: None,
+            error_message: None,
+            error_code: None,
+            created_at: None,
+            updated_at: None,
+            connector: "stripe".to_string(),
+            merchant_connector_id: None,
+            acquirer_reference_number: None,
+            connector_response: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alert_is_matched_by_acquirer_reference_number() {
+        let mockdb = get_mock_db().await;
+        let db: &dyn StorageInterface = &mockdb;
+        insert_payment(db, 1000).await;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        let payment_attempt = find_payment_attempt_for_alert(
+            db,
+            "merchant_1",
+            &get_alert_payload(None, Some("74537604221431003881865")),
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        assert_eq!(payment_attempt.attempt_id, "pay_1_1");
+
+        let error = find_payment_attempt_for_alert(
+            db,
+            "merchant_1",
+            &get_alert_payload(None, None),
+            storage_scheme,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error.current_context(),
+            &errors::ApiErrorResponse::WebhookResourceNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_within_the_limit_is_refunded() {
+        let mockdb = get_mock_db().await;
+        let db: &dyn StorageInterface = &mockdb;
+        insert_payment(db, 1000).await;
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        let refund_requests = Mutex::new(Vec::new());
+
+        let alert = record_alert(db, get_alert(), &payment_intent, Some(5000), |request| {
+            let response = get_refund_response(&request);
+            refund_requests.lock().unwrap().push(request);
+            async { Ok(response) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(alert.status, enums::AlertStatus::Actioned);
+        assert_eq!(alert.action, Some(enums::AlertAction::Refund));
+        assert_eq!(alert.refund_id.as_deref(), Some("ref_1"));
+        let refund_requests = refund_requests.into_inner().unwrap();
+        assert_eq!(refund_requests.len(), 1);
+        assert_eq!(refund_requests[0].payment_id, "pay_1");
+        assert_eq!(
+            refund_requests[0].reason_code,
+            Some(enums::RefundReason::Fraudulent)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_is_not_refunded_when_auto_refund_is_disabled() {
+        let mockdb = get_mock_db().await;
+        let db: &dyn StorageInterface = &mockdb;
+        insert_payment(db, 1000).await;
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        for alert_auto_refund_max_amount in [None, Some(500)] {
+            let alert = storage::AlertNew {
+                connector_alert_id: format!("issfr_{alert_auto_refund_max_amount:?}"),
+                ..get_alert()
+            };
+            let alert = record_alert(
+                db,
+                alert,
+                &payment_intent,
+                alert_auto_refund_max_amount,
+                |request| async move { Ok(get_refund_response(&request)) },
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(alert.status, enums::AlertStatus::Received);
+            assert_eq!(alert.action, None);
+            assert_eq!(alert.refund_id, None);
+        }
+    }
+}
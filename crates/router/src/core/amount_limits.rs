@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use api_models::enums::Currency;
+use common_utils::ext_traits::StringExt;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::errors::{self, RouterResult};
+use crate::{configs::settings::AmountLimit, routes::AppState};
+
+/// Key of the config holding the amount limits of a merchant by currency, which replace the
+/// globally configured limits of the currencies they are configured for
+pub fn get_amount_limits_override_key(merchant_id: &str) -> String {
+    format!("amount_limits_{merchant_id}")
+}
+
+/// The amount validated against the limits of its currency
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LimitedAmount {
+    /// The amount of a payment, a zero amount is allowed when the payment only sets up a mandate
+    /// or verifies the payment method
+    Payment {
+        is_setup: bool,
+    },
+    Capture,
+    Refund,
+}
+
+impl LimitedAmount {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Payment { .. } => "payment",
+            Self::Capture => "capture",
+            Self::Refund => "refund",
+        }
+    }
+}
+
+/// Fetches the limits of the currency configured for the merchant, or the globally configured
+/// limits of the currency
+pub async fn get_amount_limit(
+    state: &AppState,
+    merchant_id: &str,
+    currency: Currency,
+) -> Option<AmountLimit> {
+    let merchant_limits = state
+        .store
+        .find_config_by_key(&get_amount_limits_override_key(merchant_id))
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_struct::<HashMap<Currency, AmountLimit>>("AmountLimits")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match merchant_limits {
+        Ok(merchant_limits) => match merchant_limits.get(&currency) {
+            Some(limit) if limit.validate(currency).is_ok() => return Some(*limit),
+            Some(limit) => {
+                logger::error!(?limit, %currency, "Invalid amount limits configured for merchant")
+            }
+            None => {}
+        },
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, "Failed to fetch the amount limits of the merchant");
+            }
+        }
+    }
+
+    state.conf.amount_limits.currencies.get(&currency).copied()
+}
+
+/// Checks the amount against the limits of its currency, both bounds of the limits are inclusive.
+/// The reason of the rejection names the limit and the currency.
+pub fn check_amount_limit(
+    limit: &AmountLimit,
+    limited_amount: LimitedAmount,
+    amount: i64,
+    currency: Currency,
+) -> Result<(), String> {
+    let (minimum_amount, maximum_amount) = match limited_amount {
+        LimitedAmount::Payment { is_setup: true } if amount == 0 => return Ok(()),
+        LimitedAmount::Payment { .. } => (limit.minimum_amount, limit.maximum_amount),
+        LimitedAmount::Capture => (limit.minimum_capture_amount, limit.maximum_amount),
+        LimitedAmount::Refund => (limit.minimum_refund_amount, limit.maximum_amount),
+    };
+    let format_amount = |amount: i64| {
+        format!(
+            "{} {currency}",
+            currency.to_currency_base_unit_exact(amount)
+        )
+    };
+
+    match (minimum_amount, maximum_amount) {
+        (Some(minimum_amount), _) if amount < minimum_amount => Err(format!(
+            "amount {} is below the minimum {} amount {}",
+            format_amount(amount),
+            limited_amount.name(),
+            format_amount(minimum_amount)
+        )),
+        (_, Some(maximum_amount)) if amount > maximum_amount => Err(format!(
+            "amount {} is above the maximum {} amount {}",
+            format_amount(amount),
+            limited_amount.name(),
+            format_amount(maximum_amount)
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects the amount if it is outside the limits of its currency for the merchant
+#[instrument(skip(state))]
+pub async fn validate_amount_limits(
+    state: &AppState,
+    merchant_id: &str,
+    limited_amount: LimitedAmount,
+    amount: i64,
+    currency: Currency,
+) -> RouterResult<()> {
+    let Some(limit) = get_amount_limit(state, merchant_id, currency).await else {
+        return Ok(());
+    };
+
+    check_amount_limit(&limit, limited_amount, amount, currency)
+        .map_err(|reason| report!(errors::ApiErrorResponse::AmountOutsideLimits { reason }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_amounts_without_decimals_are_limited_in_their_minor_unit() {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(crate::services::MockApiClient),
+        )
+        .await;
+        let payment = LimitedAmount::Payment { is_setup: false };
+
+        // The minor unit of JPY is the yen itself
+        assert!(
+            validate_amount_limits(&state, "merchant_1", payment, 50, Currency::JPY)
+                .await
+                .is_ok()
+        );
+        let limit = get_amount_limit(&state, "merchant_1", Currency::JPY)
+            .await
+            .unwrap();
+        assert_eq!(
+            check_amount_limit(&limit, payment, 49, Currency::JPY),
+            Err("amount 49 JPY is below the minimum payment amount 50 JPY".to_string())
+        );
+        // The amounts are not capped by default
+        assert!(check_amount_limit(&limit, payment, 100_000_000, Currency::JPY).is_ok());
+        let limit = AmountLimit {
+            maximum_amount: Some(99_999_999),
+            ..limit
+        };
+        assert_eq!(
+            check_amount_limit(&limit, payment, 100_000_000, Currency::JPY),
+            Err(
+                "amount 100000000 JPY is above the maximum payment amount 99999999 JPY".to_string()
+            )
+        );
+
+        let limit = get_amount_limit(&state, "merchant_1", Currency::USD)
+            .await
+            .unwrap();
+        assert_eq!(
+            check_amount_limit(&limit, payment, 49, Currency::USD),
+            Err("amount 0.49 USD is below the minimum payment amount 0.50 USD".to_string())
+        );
+        assert_eq!(
+            check_amount_limit(&limit, LimitedAmount::Capture, 30, Currency::USD),
+            Err("amount 0.30 USD is below the minimum capture amount 0.50 USD".to_string())
+        );
+        // Refunds of any amount are accepted by the card networks
+        assert!(check_amount_limit(&limit, LimitedAmount::Refund, 1, Currency::USD).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_merchant_limits_override_the_configured_limits() {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(crate::services::MockApiClient),
+        )
+        .await;
+        state
+            .store
+            .insert_config(crate::types::storage::ConfigNew {
+                key: get_amount_limits_override_key("merchant_1"),
+                config: r#"{"USD":{"minimum_amount":10,"minimum_refund_amount":5}}"#.to_string(),
+            })
+            .await
+            .unwrap();
+        let payment = LimitedAmount::Payment { is_setup: false };
+
+        assert!(
+            validate_amount_limits(&state, "merchant_1", payment, 10, Currency::USD)
+                .await
+                .is_ok()
+        );
+        let error = validate_amount_limits(
+            &state,
+            "merchant_1",
+            LimitedAmount::Refund,
+            4,
+            Currency::USD,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::AmountOutsideLimits { reason }
+                if reason == "amount 0.04 USD is below the minimum refund amount 0.05 USD"
+        ));
+
+        // The limits of the other merchants and of the other currencies are left as configured
+        assert!(
+            validate_amount_limits(&state, "merchant_2", payment, 10, Currency::USD)
+                .await
+                .is_err()
+        );
+        assert!(
+            validate_amount_limits(&state, "merchant_1", payment, 10, Currency::EUR)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_zero_amount_is_allowed_only_for_setup_payments() {
+        let limit = AmountLimit {
+            minimum_amount: Some(50),
+            ..Default::default()
+        };
+
+        assert!(check_amount_limit(
+            &limit,
+            LimitedAmount::Payment { is_setup: true },
+            0,
+            Currency::USD
+        )
+        .is_ok());
+        assert!(check_amount_limit(
+            &limit,
+            LimitedAmount::Payment { is_setup: true },
+            10,
+            Currency::USD
+        )
+        .is_err());
+        assert!(check_amount_limit(
+            &limit,
+            LimitedAmount::Payment { is_setup: false },
+            0,
+            Currency::USD
+        )
+        .is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use common_utils::date_time;
+use common_utils::{date_time, fp_utils::when};
 #[cfg(feature = "email")]
 use diesel_models::{api_keys::ApiKey, enums as storage_enums};
 use error_stack::{report, IntoReport, ResultExt};
@@ -6,13 +6,18 @@ use error_stack::{report, IntoReport, ResultExt};
 use external_services::kms;
 use masking::{PeekInterface, StrongSecret};
 use router_env::{instrument, tracing};
+use strum::IntoEnumIterator;
 
 #[cfg(feature = "email")]
 use crate::types::storage::enums;
 use crate::{
     configs::settings,
     consts,
-    core::errors::{self, RouterResponse, StorageErrorExt},
+    core::{
+        audit_events,
+        errors::{self, RouterResponse, StorageErrorExt},
+        utils as core_utils,
+    },
     routes::{metrics, AppState},
     services::ApplicationResponse,
     types::{api, storage, transformers::ForeignInto},
@@ -149,6 +154,12 @@ pub async fn create_api_key(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+    // API keys created without explicit permissions retain full access
+    let permissions = api_key
+        .permissions
+        .unwrap_or_else(|| api_models::enums::ApiKeyPermission::iter().collect());
+    validate_api_key_permissions(&permissions)?;
+
     let hash_key = get_hash_key(
         api_key_config,
         #[cfg(feature = "kms")]
@@ -166,6 +177,12 @@ pub async fn create_api_key(
         created_at: date_time::now(),
         expires_at: api_key.expiration.into(),
         last_used: None,
+        permissions,
+        livemode: api_key
+            .livemode
+            .unwrap_or_else(core_utils::get_default_livemode),
+        strict_request_validation: api_key.strict_request_validation.unwrap_or(false),
+        pii_restricted: api_key.pii_restricted.unwrap_or(false),
     };
 
     let api_key = store
@@ -180,6 +197,19 @@ pub async fn create_api_key(
         &[metrics::request::add_attributes("merchant", merchant_id)],
     );
 
+    audit_events::record_audit_event(
+        &state,
+        &api_key.merchant_id,
+        storage::enums::AuditResourceType::ApiKey,
+        &api_key.key_id,
+        storage::enums::AuditAction::Created,
+        None,
+        Some(&ForeignInto::<api::RetrieveApiKeyResponse>::foreign_into(
+            api_key.clone(),
+        )),
+    )
+    .await;
+
     // Add process to process_tracker for email reminder, only if expiry is set to future date
     // If the `api_key` is set to expire in less than 7 days, the merchant is not notified about it's expiry
     #[cfg(feature = "email")]
@@ -300,6 +330,18 @@ pub async fn update_api_key(
 ) -> RouterResponse<api::RetrieveApiKeyResponse> {
     let store = state.store.as_ref();
 
+    if let Some(permissions) = api_key.permissions.as_ref() {
+        validate_api_key_permissions(permissions)?;
+    }
+
+    // The API key before the update is recorded in the audit log
+    let previous_api_key = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .map(ForeignInto::<api::RetrieveApiKeyResponse>::foreign_into);
+
     let api_key = store
         .update_api_key(
             merchant_id.to_owned(),
@@ -363,7 +405,19 @@ pub async fn update_api_key(
         }
     }
 
-    Ok(ApplicationResponse::Json(api_key.foreign_into()))
+    let response: api::RetrieveApiKeyResponse = api_key.foreign_into();
+    audit_events::record_audit_event(
+        &state,
+        merchant_id,
+        storage::enums::AuditResourceType::ApiKey,
+        key_id,
+        storage::enums::AuditAction::Updated,
+        previous_api_key.as_ref(),
+        Some(&response),
+    )
+    .await;
+
+    Ok(ApplicationResponse::Json(response))
 }
 
 // Update api_key_expiry task in the process_tracker table.
@@ -434,6 +488,14 @@ pub async fn revoke_api_key(
     key_id: &str,
 ) -> RouterResponse<api::RevokeApiKeyResponse> {
     let store = state.store.as_ref();
+    // The API key is read before it is revoked, to be recorded in the audit log
+    let previous_api_key = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .map(ForeignInto::<api::RetrieveApiKeyResponse>::foreign_into);
+
     let revoked = store
         .revoke_api_key(merchant_id, key_id)
         .await
@@ -441,6 +503,19 @@ pub async fn revoke_api_key(
 
     metrics::API_KEY_REVOKED.add(&metrics::CONTEXT, 1, &[]);
 
+    if revoked {
+        audit_events::record_audit_event(
+            &state,
+            merchant_id,
+            storage::enums::AuditResourceType::ApiKey,
+            key_id,
+            storage::enums::AuditAction::Deleted,
+            previous_api_key.as_ref(),
+            None,
+        )
+        .await;
+    }
+
     #[cfg(feature = "email")]
     {
         let task_id = generate_task_id_for_api_key_expiry_workflow(key_id);
@@ -517,6 +592,16 @@ pub async fn list_api_keys(
     Ok(ApplicationResponse::Json(api_keys))
 }
 
+fn validate_api_key_permissions(
+    permissions: &[api_models::enums::ApiKeyPermission],
+) -> errors::RouterResult<()> {
+    when(permissions.is_empty(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "At least one permission must be granted to the API key".to_string(),
+        }))
+    })
+}
+
 #[cfg(feature = "email")]
 fn generate_task_id_for_api_key_expiry_workflow(key_id: &str) -> String {
     format!("{API_KEY_EXPIRY_RUNNER}_{API_KEY_EXPIRY_NAME}_{key_id}")
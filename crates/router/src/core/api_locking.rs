@@ -38,7 +38,7 @@ pub struct LockingInput {
 }
 
 impl LockingInput {
-    fn get_redis_locking_key(&self, merchant_id: String) -> String {
+    pub fn get_redis_locking_key(&self, merchant_id: String) -> String {
         format!(
             "{}_{}_{}_{}",
             API_LOCK_PREFIX, merchant_id, self.api_identifier, self.unique_locking_key
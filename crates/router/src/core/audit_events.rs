@@ -0,0 +1,397 @@
+//! Audit log of the changes made to merchant accounts, merchant connector accounts and API keys
+//! through the admin API. Each change is recorded with the fields of the resource which it
+//! changed, the values of the secret fields are masked before they are stored. Recording an event
+//! never fails the change itself, the events which cannot be stored are retried by the scheduler.
+
+use std::collections::BTreeSet;
+
+use api_models::audit_events as audit_events_api;
+use common_utils::date_time;
+use error_stack::ResultExt;
+use masking::Secret;
+use router_env::{instrument, logger, tracing};
+use scheduler::{
+    consumer::types::process_data, db::process_tracker::ProcessTrackerExt, errors as sch_errors,
+    utils as pt_utils,
+};
+use serde::Serialize;
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    db::StorageInterface,
+    routes::{app::AppStateInfo, AppState},
+    services::ApplicationResponse,
+    types::{
+        api,
+        storage::{self, enums},
+        transformers::ForeignInto,
+    },
+    utils,
+};
+
+/// The actor recorded for the changes made through the admin API, which is authenticated with the
+/// single admin API key
+pub const ADMIN_API_KEY_ACTOR: &str = "admin_api_key";
+
+pub const AUDIT_EVENT_WORKFLOW_RUNNER: &str = "AUDIT_EVENT_WORKFLOW";
+const AUDIT_EVENT_WORKFLOW_TASK: &str = "AUDIT_EVENT_INSERT";
+
+const AUDIT_EVENT_LIST_LOWER_LIMIT: i64 = 1;
+const AUDIT_EVENT_LIST_UPPER_LIMIT: i64 = 100;
+const AUDIT_EVENT_LIST_DEFAULT_LIMIT: i64 = 10;
+
+/// A resource whose changes are recorded in the audit log
+pub trait AuditResource: Serialize {
+    /// The representation of the resource recorded in the diffs, with the values of its secret
+    /// fields masked
+    fn to_masked_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        masking::masked_serialize(self)
+    }
+}
+
+impl AuditResource for api_models::admin::MerchantConnectorResponse {}
+
+impl AuditResource for api::RetrieveApiKeyResponse {}
+
+impl AuditResource for api_models::admin::MerchantAccountResponse {
+    fn to_masked_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        let mut value = masking::masked_serialize(self)?;
+        // The webhook details and the hash key are not typed as secrets in the response
+        if let serde_json::Value::Object(object) = &mut value {
+            if let Some(webhook_details) = &self.webhook_details {
+                let masked_webhook_details = match serde_json::from_value::<
+                    api_models::admin::WebhookDetails,
+                >(webhook_details.clone())
+                {
+                    Ok(webhook_details) => masking::masked_serialize(&webhook_details)?,
+                    Err(_) => masking::masked_serialize(&Secret::<serde_json::Value>::new(
+                        webhook_details.clone(),
+                    ))?,
+                };
+                object.insert("webhook_details".to_owned(), masked_webhook_details);
+            }
+            if let Some(payment_response_hash_key) = &self.payment_response_hash_key {
+                object.insert(
+                    "payment_response_hash_key".to_owned(),
+                    masking::masked_serialize(&Secret::<String>::new(
+                        payment_response_hash_key.clone(),
+                    ))?,
+                );
+            }
+        }
+        Ok(value)
+    }
+}
+
+fn to_masked_object<T: AuditResource>(
+    resource_type: enums::AuditResourceType,
+    resource: Option<&T>,
+) -> (
+    serde_json::Map<String, serde_json::Value>,
+    serde_json::Map<String, serde_json::Value>,
+) {
+    let values = resource
+        .map(|resource| -> Result<_, serde_json::Error> {
+            Ok((serde_json::to_value(resource)?, resource.to_masked_value()?))
+        })
+        .transpose();
+    match values {
+        Ok(Some((serde_json::Value::Object(object), serde_json::Value::Object(masked_object)))) => {
+            (object, masked_object)
+        }
+        Ok(_) => (serde_json::Map::new(), serde_json::Map::new()),
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to serialize the {resource_type} of the audit event"
+            );
+            (serde_json::Map::new(), serde_json::Map::new())
+        }
+    }
+}
+
+/// The fields which differ between the two versions of the resource, each with its value before
+/// and after the change. The fields are compared before they are masked, so that a change of a
+/// secret is recorded, with both of its values masked.
+pub fn get_redacted_diff<T: AuditResource>(
+    resource_type: enums::AuditResourceType,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> serde_json::Value {
+    let (before, masked_before) = to_masked_object(resource_type, before);
+    let (after, masked_after) = to_masked_object(resource_type, after);
+
+    let diff = before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| {
+            let get_value = |object: &serde_json::Map<String, serde_json::Value>| {
+                object
+                    .get(field)
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null)
+            };
+            (
+                field.to_owned(),
+                serde_json::json!({
+                    "before": get_value(&masked_before),
+                    "after": get_value(&masked_after),
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    serde_json::Value::Object(diff)
+}
+
+/// Records the change of a resource in the audit log. A failure to store the event is logged and
+/// the event is stored later by the scheduler, the change itself is not failed.
+#[instrument(skip_all)]
+pub async fn record_audit_event<T: AuditResource>(
+    state: &AppState,
+    merchant_id: &str,
+    resource_type: enums::AuditResourceType,
+    resource_id: &str,
+    action: enums::AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) {
+    let audit_event = storage::AuditEventNew {
+        audit_event_id: utils::generate_id(consts::ID_LENGTH, "audit"),
+        merchant_id: merchant_id.to_owned(),
+        actor_key_id: ADMIN_API_KEY_ACTOR.to_owned(),
+        resource_type,
+        resource_id: resource_id.to_owned(),
+        action,
+        diff: get_redacted_diff(resource_type, before, after),
+        request_id: state.get_request_id(),
+        created_at: date_time::now(),
+    };
+
+    let db = &*state.store;
+    if let Err(error) = db.insert_audit_event(audit_event.clone()).await {
+        logger::error!(
+            ?error,
+            "Failed to record the audit event {} of {resource_type} {resource_id}, retrying it in the background",
+            audit_event.audit_event_id
+        );
+        if let Err(error) = add_audit_event_task(db, audit_event).await {
+            logger::error!(process_tracker_error=?error);
+        }
+    }
+}
+
+async fn add_audit_event_task(
+    db: &dyn StorageInterface,
+    audit_event: storage::AuditEventNew,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        AUDIT_EVENT_WORKFLOW_RUNNER,
+        AUDIT_EVENT_WORKFLOW_TASK,
+        &audit_event.audit_event_id,
+        &audit_event.merchant_id,
+    );
+    let schedule_time = get_audit_event_retry_schedule_time(&audit_event.merchant_id, 0)
+        .unwrap_or_else(date_time::now);
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        AUDIT_EVENT_WORKFLOW_TASK,
+        AUDIT_EVENT_WORKFLOW_RUNNER,
+        audit_event,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+pub fn get_audit_event_retry_schedule_time(
+    merchant_id: &str,
+    retry_count: i32,
+) -> Option<time::PrimitiveDateTime> {
+    let time_delta = pt_utils::get_schedule_time(
+        process_data::ConnectorPTMapping::default(),
+        merchant_id,
+        retry_count + 1,
+    );
+    pt_utils::get_time_from_delta(time_delta)
+}
+
+#[instrument(skip_all)]
+pub async fn list_audit_events(
+    state: AppState,
+    merchant_id: String,
+    constraints: audit_events_api::ListAuditEventsConstraints,
+) -> RouterResponse<audit_events_api::AuditEventListResponse> {
+    let db = state.store.as_ref();
+    // Only the merchant key store is fetched, as it is sufficient to identify non-existence of a
+    // merchant account, the events of deleted merchant accounts are not listed
+    db.get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let limit = validate_audit_event_list(constraints.limit)?;
+    let data: Vec<audit_events_api::AuditEventResponse> = db
+        .list_audit_events_by_merchant_id(
+            &merchant_id,
+            constraints.resource_type,
+            constraints.resource_id.as_deref(),
+            constraints.created_after,
+            constraints.created_before,
+            Some(limit),
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list audit events")?
+        .into_iter()
+        .map(ForeignInto::foreign_into)
+        .collect();
+
+    let total_count = db
+        .get_total_count_of_audit_events(
+            &merchant_id,
+            constraints.resource_type,
+            constraints.resource_id.as_deref(),
+            constraints.created_after,
+            constraints.created_before,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to count audit events")?;
+
+    Ok(ApplicationResponse::Json(
+        audit_events_api::AuditEventListResponse {
+            count: data.len(),
+            total_count,
+            data,
+        },
+    ))
+}
+
+fn validate_audit_event_list(limit: Option<i64>) -> RouterResult<i64> {
+    match limit {
+        Some(limit)
+            if !(AUDIT_EVENT_LIST_LOWER_LIMIT..=AUDIT_EVENT_LIST_UPPER_LIMIT).contains(&limit) =>
+        {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "limit should be in between 1 and 100".to_string(),
+            }
+            .into())
+        }
+        Some(limit) => Ok(limit),
+        None => Ok(AUDIT_EVENT_LIST_DEFAULT_LIMIT),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{configs::settings, routes, services};
+
+    async fn get_app_state() -> AppState {
+        routes::AppState::with_storage(
+            settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await
+    }
+
+    fn get_merchant_connector_response(
+        api_key: &str,
+        disabled: bool,
+    ) -> api_models::admin::MerchantConnectorResponse {
+        serde_json::from_value(serde_json::json!({
+            "connector_type": "payment_processor",
+            "connector_name": "stripe",
+            "connector_label": "stripe_US_default",
+            "merchant_connector_id": "mca_audit",
+            "connector_account_details": { "auth_type": "HeaderKey", "api_key": api_key },
+            "test_mode": true,
+            "disabled": disabled,
+            "connector_webhook_details": { "merchant_secret": "whsec_audit_secret" },
+            "profile_id": "pro_default",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_secret_fields_are_masked_in_the_diff() {
+        let before = get_merchant_connector_response("sk_test_previous_secret", false);
+        let after = get_merchant_connector_response("sk_test_current_secret", false);
+
+        let diff = get_redacted_diff(
+            enums::AuditResourceType::MerchantConnectorAccount,
+            Some(&before),
+            Some(&after),
+        );
+        assert_eq!(
+            diff,
+            serde_json::json!({
+                "connector_account_details": { "before": masking::MASKED_VALUE,
+                    "after": masking::MASKED_VALUE,
+                 },
+            })
+        );
+
+        let diff = get_redacted_diff(
+            enums::AuditResourceType::MerchantConnectorAccount,
+            None,
+            Some(&after),
+        )
+        .to_string();
+        assert!(!diff.contains("sk_test_current_secret"));
+        assert!(!diff.contains("whsec_audit_secret"));
+        assert!(diff.contains("stripe_US_default"));
+    }
+
+    #[actix_rt::test]
+    async fn test_disabling_a_connector_account_is_recorded() {
+        let state = get_app_state().await;
+        let before = get_merchant_connector_response("sk_test_secret", false);
+        let after = get_merchant_connector_response("sk_test_secret", true);
+
+        record_audit_event(
+            &state,
+            "merchant_1",
+            enums::AuditResourceType::MerchantConnectorAccount,
+            "mca_audit",
+            enums::AuditAction::Updated,
+            Some(&before),
+            Some(&after),
+        )
+        .await;
+
+        let audit_events = state
+            .store
+            .list_audit_events_by_merchant_id(
+                "merchant_1",
+                Some(enums::AuditResourceType::MerchantConnectorAccount),
+                Some("mca_audit"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(audit_events.len(), 1);
+
+        let audit_event = &audit_events[0];
+        assert_eq!(audit_event.action, enums::AuditAction::Updated);
+        assert_eq!(audit_event.actor_key_id, ADMIN_API_KEY_ACTOR);
+        assert_eq!(
+            audit_event.diff,
+            serde_json::json!({ "disabled": { "before": false, "after": true } })
+        );
+        assert!(!audit_event.diff.to_string().contains("sk_test_secret"));
+    }
+}
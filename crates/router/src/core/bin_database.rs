@@ -0,0 +1,229 @@
+//! Metadata of the card BINs, looked up in a BIN database loaded in memory at startup from the
+//! file configured in `bin_database.file_path`. The file is a CSV file with the columns
+//! `bin,network,card_type,issuer_country,bank_name`, where the BIN is a prefix of 1 to 8 digits and
+//! the other columns may be empty. When BINs overlap, the longest matching prefix is used. Card
+//! BIN metadata is not available when the file is not configured or cannot be read.
+
+use std::str::FromStr;
+
+use api_models::enums as api_enums;
+use once_cell::sync::OnceCell;
+use router_env::logger;
+
+use crate::configs::settings::BinDatabaseConfig;
+
+/// Number of leading digits of the card number considered for the lookups
+const MAX_BIN_LENGTH: usize = 8;
+
+static BIN_DATABASE: OnceCell<BinDatabase> = OnceCell::new();
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
+pub enum CardType {
+    Credit,
+    Debit,
+    Prepaid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BinInfo {
+    pub network: Option<api_enums::CardNetwork>,
+    pub card_type: Option<CardType>,
+    pub issuer_country: Option<api_enums::CountryAlpha2>,
+    pub bank_name: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct BinDatabase {
+    /// BINs of each length, the BINs of length `n` at index `n - 1`, each sorted by BIN
+    bins: [Vec<(u32, BinInfo)>; MAX_BIN_LENGTH],
+}
+
+impl BinDatabase {
+    /// Builds the database from the contents of a BIN database file. Invalid rows are skipped,
+    /// and the first row is used when a BIN is listed more than once.
+    pub fn from_csv(contents: &str) -> Self {
+        let mut database = Self::default();
+        let mut invalid_rows = 0;
+
+        for row in contents
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty() && !row.starts_with('#') && !row.starts_with("bin,"))
+        {
+            match parse_row(row) {
+                Some((length, bin, bin_info)) => database.bins[length - 1].push((bin, bin_info)),
+                None => invalid_rows += 1,
+            }
+        }
+
+        for bins in database.bins.iter_mut() {
+            bins.sort_by_key(|(bin, _)| *bin);
+            bins.dedup_by_key(|(bin, _)| *bin);
+            bins.shrink_to_fit();
+        }
+
+        if invalid_rows > 0 {
+            logger::warn!("Skipped {invalid_rows} invalid rows of the BIN database");
+        }
+        database
+    }
+
+    pub fn len(&self) -> usize {
+        self.bins.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Metadata of the longest BIN matching the leading digits of the card number, only the
+    /// first 8 digits are read
+    pub fn lookup(&self, card_number: &str) -> Option<&BinInfo> {
+        let mut prefixes = [0_u32; MAX_BIN_LENGTH];
+        let mut length = 0;
+        for digit in card_number
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .take(MAX_BIN_LENGTH)
+        {
+            let previous_prefix = length.checked_sub(1).map_or(0, |index| prefixes[index]);
+            prefixes[length] = previous_prefix * 10 + u32::from(digit - b'0');
+            length += 1;
+        }
+
+        (1..=length).rev().find_map(|length| {
+            let bins = &self.bins[length - 1];
+            bins.binary_search_by_key(&prefixes[length - 1], |(bin, _)| *bin)
+                .ok()
+                .map(|index| &bins[index].1)
+        })
+    }
+}
+
+fn parse_row(row: &str) -> Option<(usize, u32, BinInfo)> {
+    let mut columns = row.splitn(5, ',').map(str::trim);
+    let bin = columns.next()?;
+    if bin.is_empty() || bin.len() > MAX_BIN_LENGTH || !bin.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut next_column = || columns.next().filter(|column| !column.is_empty());
+
+    let network = next_column()
+        .map(api_enums::CardNetwork::from_str)
+        .transpose()
+        .ok()?;
+    let card_type = next_column().map(CardType::from_str).transpose().ok()?;
+    let issuer_country = next_column()
+        .map(api_enums::CountryAlpha2::from_str)
+        .transpose()
+        .ok()?;
+    let bank_name = next_column().map(str::to_string);
+
+    Some((
+        bin.len(),
+        bin.parse().ok()?,
+        BinInfo {
+            network,
+            card_type,
+            issuer_country,
+            bank_name,
+        },
+    ))
+}
+
+/// Loads the BIN database from the configured file, the lookups find no BIN when the file is not
+/// configured or cannot be read
+pub fn load_bin_database(config: &BinDatabaseConfig) {
+    let Some(file_path) = config.file_path.as_deref() else {
+        logger::info!("BIN database file is not configured, card BIN metadata is not available");
+        return;
+    };
+
+    match std::fs::read_to_string(file_path) {
+        Ok(contents) => {
+            let database = BinDatabase::from_csv(&contents);
+            logger::info!("Loaded {} BINs from {file_path}", database.len());
+            if BIN_DATABASE.set(database).is_err() {
+                logger::warn!("BIN database is already loaded, {file_path} is not used");
+            }
+        }
+        Err(error) => logger::warn!(
+            ?error,
+            "Failed to read the BIN database from {file_path}, card BIN metadata is not available"
+        ),
+    }
+}
+
+/// Metadata of the BIN of the card number in the loaded BIN database
+pub fn lookup_bin(card_number: &str) -> Option<&'static BinInfo> {
+    BIN_DATABASE.get()?.lookup(card_number)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const FIXTURE: &str = "\
+bin,network,card_type,issuer_country,bank_name
+# Visa, with an issuer specific range
+4,Visa,,,
+424242,Visa,CREDIT,US,Stripe Test Bank
+42424201,Visa,prepaid,GB,Prepaid Bank, Ltd.
+# Mastercard debit range overlapping a shorter credit range
+51,Mastercard,credit,DE,
+5105,Mastercard,debit,DE,Berliner Bank
+5105,Mastercard,credit,FR,Duplicate row
+123456789,Visa,credit,US,BIN too long
+6011,Discover,unknown,US,Invalid card type
+";
+
+    #[test]
+    fn test_longest_matching_bin_is_used() {
+        let database = BinDatabase::from_csv(FIXTURE);
+        assert_eq!(database.len(), 5);
+
+        let bin_info = database.lookup("4242420112345678").unwrap();
+        assert_eq!(bin_info.card_type, Some(CardType::Prepaid));
+        assert_eq!(bin_info.issuer_country, Some(api_enums::CountryAlpha2::GB));
+        assert_eq!(bin_info.bank_name.as_deref(), Some("Prepaid Bank, Ltd."));
+
+        let bin_info = database.lookup("4242424242424242").unwrap();
+        assert_eq!(bin_info.card_type, Some(CardType::Credit));
+        assert_eq!(bin_info.bank_name.as_deref(), Some("Stripe Test Bank"));
+
+        let bin_info = database.lookup("4000000000000002").unwrap();
+        assert_eq!(bin_info.network, Some(api_enums::CardNetwork::Visa));
+        assert_eq!(bin_info.card_type, None);
+        assert_eq!(bin_info.issuer_country, None);
+    }
+
+    #[test]
+    fn test_overlapping_and_duplicate_bins() {
+        let database = BinDatabase::from_csv(FIXTURE);
+
+        let bin_info = database.lookup("5105105105105100").unwrap();
+        assert_eq!(bin_info.card_type, Some(CardType::Debit));
+        assert_eq!(bin_info.bank_name.as_deref(), Some("Berliner Bank"));
+
+        let bin_info = database.lookup("5100000000000008").unwrap();
+        assert_eq!(bin_info.card_type, Some(CardType::Credit));
+        assert_eq!(bin_info.network, Some(api_enums::CardNetwork::Mastercard));
+    }
+
+    #[test]
+    fn test_unknown_and_short_card_numbers() {
+        let database = BinDatabase::from_csv(FIXTURE);
+
+        assert!(database.lookup("6011111111111117").is_none());
+        assert!(database.lookup("").is_none());
+        assert_eq!(
+            database.lookup("424242").unwrap().bank_name.as_deref(),
+            Some("Stripe Test Bank")
+        );
+        assert!(database.lookup("42424").unwrap().bank_name.is_none());
+        assert!(BinDatabase::from_csv("").is_empty());
+    }
+}
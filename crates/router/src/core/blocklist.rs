@@ -0,0 +1,149 @@
+pub mod utils;
+
+use api_models::blocklist as blocklist_api;
+use common_utils::date_time;
+use error_stack::ResultExt;
+use masking::PeekInterface;
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, StorageErrorExt},
+    routes::{metrics, AppState},
+    services::ApplicationResponse,
+    types::{storage, transformers::ForeignInto},
+    utils as router_utils,
+};
+
+#[instrument(skip_all)]
+pub async fn create_blocklist_entry(
+    state: AppState,
+    merchant_id: String,
+    request: blocklist_api::CreateBlocklistRequest,
+) -> RouterResponse<blocklist_api::BlocklistResponse> {
+    let store = state.store.as_ref();
+    // Only the merchant key store is fetched, as it is sufficient to identify non-existence of a
+    // merchant account.
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(
+            merchant_id.as_str(),
+            &store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let secret = utils::get_merchant_fingerprint_secret(store, &merchant_id, &key_store).await?;
+    let fingerprint =
+        utils::get_blocklist_fingerprint(&secret, request.data_kind, request.data.peek())?;
+
+    let now = date_time::now();
+    let blocklist_entry = storage::BlocklistNew {
+        blocklist_id: router_utils::generate_id(consts::ID_LENGTH, "blocklist"),
+        merchant_id: merchant_id.clone(),
+        data_kind: request.data_kind,
+        fingerprint,
+        reason: request.reason,
+        expires_at: request.expires_at,
+        created_at: now,
+        modified_at: now,
+    };
+
+    let blocklist_entry = store
+        .insert_blocklist_entry(blocklist_entry)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::DuplicateBlocklistEntry)?;
+
+    utils::add_entry_to_cache(store, &blocklist_entry).await;
+
+    metrics::BLOCKLIST_ENTRY_CREATED.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::request::add_attributes("merchant", merchant_id)],
+    );
+
+    Ok(ApplicationResponse::Json(blocklist_entry.foreign_into()))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_blocklist_entry(
+    state: AppState,
+    merchant_id: &str,
+    blocklist_id: &str,
+) -> RouterResponse<blocklist_api::BlocklistResponse> {
+    let blocklist_entry = state
+        .store
+        .find_blocklist_entry_by_merchant_id_blocklist_id(merchant_id, blocklist_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BlocklistEntryNotFound {
+            id: blocklist_id.to_owned(),
+        })?;
+
+    Ok(ApplicationResponse::Json(blocklist_entry.foreign_into()))
+}
+
+#[instrument(skip_all)]
+pub async fn update_blocklist_entry(
+    state: AppState,
+    merchant_id: &str,
+    blocklist_id: &str,
+    request: blocklist_api::UpdateBlocklistRequest,
+) -> RouterResponse<blocklist_api::BlocklistResponse> {
+    let blocklist_entry = state
+        .store
+        .update_blocklist_entry(merchant_id, blocklist_id, request.foreign_into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BlocklistEntryNotFound {
+            id: blocklist_id.to_owned(),
+        })?;
+
+    Ok(ApplicationResponse::Json(blocklist_entry.foreign_into()))
+}
+
+#[instrument(skip_all)]
+pub async fn delete_blocklist_entry(
+    state: AppState,
+    merchant_id: &str,
+    blocklist_id: &str,
+) -> RouterResponse<blocklist_api::DeleteBlocklistResponse> {
+    let store = state.store.as_ref();
+    let blocklist_entry = store
+        .delete_blocklist_entry(merchant_id, blocklist_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BlocklistEntryNotFound {
+            id: blocklist_id.to_owned(),
+        })?;
+
+    utils::remove_entry_from_cache(store, &blocklist_entry).await;
+
+    Ok(ApplicationResponse::Json(
+        blocklist_api::DeleteBlocklistResponse {
+            merchant_id: blocklist_entry.merchant_id,
+            blocklist_id: blocklist_entry.blocklist_id,
+            deleted: true,
+        },
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn list_blocklist_entries(
+    state: AppState,
+    merchant_id: String,
+    constraints: blocklist_api::ListBlocklistConstraints,
+) -> RouterResponse<Vec<blocklist_api::BlocklistResponse>> {
+    let blocklist_entries = state
+        .store
+        .list_blocklist_entries_by_merchant_id(
+            &merchant_id,
+            constraints.data_kind,
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list blocklist entries")?
+        .into_iter()
+        .map(ForeignInto::foreign_into)
+        .collect();
+
+    Ok(ApplicationResponse::Json(blocklist_entries))
+}
@@ -0,0 +1,706 @@
+use std::{net::IpAddr, str::FromStr};
+
+use common_utils::{
+    crypto::{self, SignMessage},
+    ext_traits::ValueExt,
+};
+use diesel_models::encryption::Encryption;
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface};
+use router_env::{instrument, logger, tracing};
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        errors::{self, RouterResult},
+        payments::PaymentData,
+    },
+    db::StorageInterface,
+    routes::{metrics, AppState},
+    types::{
+        self, api, domain,
+        storage::{self, enums},
+    },
+};
+
+/// The length of the secret used to fingerprint blocked data, generated per merchant
+const FINGERPRINT_SECRET_LENGTH: usize = 64;
+
+/// Prefix of the stored fingerprint secrets which are encrypted with the key of the merchant
+const ENCRYPTED_FINGERPRINT_SECRET_PREFIX: &str = "encrypted:";
+
+/// The error code with which blocked payments are failed
+pub const PAYMENT_BLOCKED_ERROR_CODE: &str = "IR_28";
+
+/// The error message with which blocked payments are failed
+pub const PAYMENT_BLOCKED_ERROR_MESSAGE: &str =
+    "The payment was blocked as its details are present in the blocklist of the merchant";
+
+pub fn get_merchant_fingerprint_secret_key(merchant_id: &str) -> String {
+    format!("fingerprint_secret_{merchant_id}")
+}
+
+fn get_blocklist_cache_key(merchant_id: &str, data_kind: enums::BlocklistDataKind) -> String {
+    format!("blocklist_{merchant_id}_{data_kind}")
+}
+
+fn get_blocklist_cache_populated_key(merchant_id: &str) -> String {
+    format!("blocklist_{merchant_id}_populated")
+}
+
+/// Fetches the secret with which the blocked data of the merchant is fingerprinted, generating
+/// it when the merchant blocks data for the first time. The secret is stored encrypted with the
+/// key of the merchant.
+#[instrument(skip_all)]
+pub async fn get_merchant_fingerprint_secret(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<String> {
+    let key = get_merchant_fingerprint_secret_key(merchant_id);
+    let config = match db.find_config_by_key(&key).await {
+        Ok(config) => config,
+        Err(error) if error.current_context().is_db_not_found() => {
+            let secret =
+                crypto::generate_cryptographically_secure_random_string(FINGERPRINT_SECRET_LENGTH);
+            match db
+                .insert_config(storage::ConfigNew {
+                    key: key.clone(),
+                    config: encrypt_fingerprint_secret(&secret, key_store).await?,
+                })
+                .await
+            {
+                Ok(_) => return Ok(secret),
+                // The secret could have been generated by a concurrent request
+                Err(error) if error.current_context().is_db_unique_violation() => db
+                    .find_config_by_key(&key)
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to fetch the fingerprint secret of the merchant")?,
+                Err(error) => {
+                    return Err(error)
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable(
+                            "Failed to insert the fingerprint secret of the merchant",
+                        )
+                }
+            }
+        }
+        Err(error) => {
+            return Err(error)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to fetch the fingerprint secret of the merchant")
+        }
+    };
+
+    match config
+        .config
+        .strip_prefix(ENCRYPTED_FINGERPRINT_SECRET_PREFIX)
+    {
+        Some(encrypted_secret) => decrypt_fingerprint_secret(encrypted_secret, key_store).await,
+        // The secrets generated before they were encrypted are encrypted on their first use
+        None => {
+            db.update_config_by_key(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(encrypt_fingerprint_secret(&config.config, key_store).await?),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encrypt the fingerprint secret of the merchant")?;
+            Ok(config.config)
+        }
+    }
+}
+
+async fn encrypt_fingerprint_secret(
+    secret: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<String> {
+    let encrypted_secret = domain::types::encrypt::<String, masking::WithType>(
+        secret.to_owned().into(),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encrypt the fingerprint secret of the merchant")?;
+
+    Ok(format!(
+        "{ENCRYPTED_FINGERPRINT_SECRET_PREFIX}{}",
+        hex::encode(encrypted_secret.into_encrypted().peek())
+    ))
+}
+
+async fn decrypt_fingerprint_secret(
+    encrypted_secret: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<String> {
+    let encrypted_secret = hex::decode(encrypted_secret)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to decode the fingerprint secret of the merchant")?;
+
+    domain::types::decrypt::<String, masking::WithType>(
+        Some(Encryption::new(encrypted_secret.into())),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to decrypt the fingerprint secret of the merchant")?
+    .map(|secret| secret.into_inner().expose())
+    .ok_or(errors::ApiErrorResponse::InternalServerError)
+    .into_report()
+    .attach_printable("Fingerprint secret of the merchant is empty")
+}
+
+pub fn generate_fingerprint(secret: &str, data: &str) -> RouterResult<String> {
+    crypto::HmacSha512
+        .sign_message(secret.as_bytes(), data.as_bytes())
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to generate fingerprint")
+        .map(hex::encode)
+}
+
+/// Normalizes the data so that equivalent representations of the same value match the same
+/// blocklist entry.
+fn normalize_data(data_kind: enums::BlocklistDataKind, data: &str) -> RouterResult<String> {
+    let data = data.trim();
+    match data_kind {
+        enums::BlocklistDataKind::CardBin => {
+            let card_bin: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+            if matches!(card_bin.len(), 6 | 8) && card_bin.chars().all(|c| c.is_ascii_digit()) {
+                Ok(card_bin)
+            } else {
+                Err(errors::ApiErrorResponse::InvalidDataValue { field_name: "data" })
+                    .into_report()
+                    .attach_printable("Card BIN must consist of either 6 or 8 digits")
+            }
+        }
+        enums::BlocklistDataKind::CardFingerprint => cards::CardNumber::from_str(data)
+            .map(|card_number| card_number.peek().clone())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue { field_name: "data" }),
+        enums::BlocklistDataKind::Email => Ok(data.to_lowercase()),
+        enums::BlocklistDataKind::Ip => IpAddr::from_str(data)
+            .map(|ip_address| ip_address.to_string())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue { field_name: "data" }),
+    }
+}
+
+/// Computes the value that the data is matched against. Card BINs are not sensitive and are
+/// stored as they are, while all other kinds of data are fingerprinted with the secret of the
+/// merchant.
+pub fn get_blocklist_fingerprint(
+    secret: &str,
+    data_kind: enums::BlocklistDataKind,
+    data: &str,
+) -> RouterResult<String> {
+    let data = normalize_data(data_kind, data)?;
+    match data_kind {
+        enums::BlocklistDataKind::CardBin => Ok(data),
+        enums::BlocklistDataKind::CardFingerprint
+        | enums::BlocklistDataKind::Email
+        | enums::BlocklistDataKind::Ip => generate_fingerprint(secret, &data),
+    }
+}
+
+/// Adds the entry to the cached set of blocked values of the merchant. If the cache could not be
+/// updated, it is invalidated so that it is populated from the database on the next lookup.
+#[instrument(skip_all)]
+pub async fn add_entry_to_cache(db: &dyn StorageInterface, entry: &storage::Blocklist) {
+    let result = async {
+        let redis_conn = db.get_redis_conn()?;
+        redis_conn
+            .add_members_to_set(
+                &get_blocklist_cache_key(&entry.merchant_id, entry.data_kind),
+                entry.fingerprint.clone(),
+            )
+            .await
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to add blocklist entry to cache");
+        invalidate_cache(db, &entry.merchant_id).await;
+    }
+}
+
+/// Removes the entry from the cached set of blocked values of the merchant.
+#[instrument(skip_all)]
+pub async fn remove_entry_from_cache(db: &dyn StorageInterface, entry: &storage::Blocklist) {
+    let result = async {
+        let redis_conn = db.get_redis_conn()?;
+        redis_conn
+            .remove_members_from_set(
+                &get_blocklist_cache_key(&entry.merchant_id, entry.data_kind),
+                entry.fingerprint.clone(),
+            )
+            .await
+    }
+    .await;
+
+    // A stale member only causes an additional database lookup, so the cache is left as it is
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to remove blocklist entry from cache");
+    }
+}
+
+async fn invalidate_cache(db: &dyn StorageInterface, merchant_id: &str) {
+    let result = async {
+        db.get_redis_conn()?
+            .delete_key(&get_blocklist_cache_populated_key(merchant_id))
+            .await
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to invalidate blocklist cache");
+    }
+}
+
+/// Populates the cached sets of blocked values of the merchant from the database, unless they
+/// have already been populated.
+async fn populate_cache(db: &dyn StorageInterface, merchant_id: &str) -> RouterResult<()> {
+    let redis_conn = db
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let populated_key = get_blocklist_cache_populated_key(merchant_id);
+
+    if redis_conn
+        .exists::<Vec<u8>>(&populated_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+    {
+        return Ok(());
+    }
+
+    let entries = db
+        .list_blocklist_entries_by_merchant_id(merchant_id, None, None, None)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list blocklist entries")?;
+
+    for data_kind in enums::BlocklistDataKind::iter() {
+        let fingerprints = entries
+            .iter()
+            .filter(|entry| entry.data_kind == data_kind)
+            .map(|entry| entry.fingerprint.clone())
+            .collect::<Vec<_>>();
+
+        if !fingerprints.is_empty() {
+            redis_conn
+                .add_members_to_set(
+                    &get_blocklist_cache_key(merchant_id, data_kind),
+                    fingerprints,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        }
+    }
+
+    redis_conn
+        .set_key(&populated_key, "true")
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+}
+
+/// Checks whether the fingerprint may be present in the blocklist of the merchant. The cache
+/// only lets lookups of values that are not blocked skip the database, so any error while
+/// reading it is treated as a possible match.
+async fn may_be_blocked(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    data_kind: enums::BlocklistDataKind,
+    fingerprint: &str,
+) -> bool {
+    let result = async {
+        populate_cache(db, merchant_id).await?;
+        db.get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?
+            .is_member_of_set(
+                &get_blocklist_cache_key(merchant_id, data_kind),
+                fingerprint.to_owned(),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+    }
+    .await;
+
+    result.unwrap_or_else(|error| {
+        logger::error!(
+            ?error,
+            "Failed to look up blocklist cache, falling back to database"
+        );
+        true
+    })
+}
+
+/// Finds the first unexpired blocklist entry of the merchant which matches any of the
+/// fingerprints.
+#[instrument(skip_all)]
+pub async fn find_blocking_entry(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    fingerprints: &[(enums::BlocklistDataKind, String)],
+) -> RouterResult<Option<storage::Blocklist>> {
+    let current_time = common_utils::date_time::now();
+
+    for (data_kind, fingerprint) in fingerprints {
+        if !may_be_blocked(db, merchant_id, *data_kind, fingerprint).await {
+            continue;
+        }
+
+        let entry = db
+            .find_blocklist_entry_by_merchant_id_data_kind_fingerprint_optional(
+                merchant_id,
+                *data_kind,
+                fingerprint,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find blocklist entry")?;
+
+        if let Some(entry) = entry.filter(|entry| !entry.is_expired(current_time)) {
+            return Ok(Some(entry));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Collects the fingerprints of the card, email and IP address used for the payment.
+fn get_payment_fingerprints<F: Clone>(
+    secret: &str,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<Vec<(enums::BlocklistDataKind, String)>> {
+    let mut fingerprints = Vec::new();
+
+    if let Some(api::PaymentMethodData::Card(card)) = payment_data.payment_method_data.as_ref() {
+        let card_number = card.card_number.peek();
+        // Both the 6 and the 8 digit BINs of the card are checked
+        for bin_length in [6, 8] {
+            if card_number.len() > bin_length {
+                fingerprints.push((
+                    enums::BlocklistDataKind::CardBin,
+                    card_number.chars().take(bin_length).collect(),
+                ));
+            }
+        }
+        fingerprints.push((
+            enums::BlocklistDataKind::CardFingerprint,
+            generate_fingerprint(secret, card_number)?,
+        ));
+    }
+
+    if let Some(email) = payment_data.email.as_ref() {
+        fingerprints.push((
+            enums::BlocklistDataKind::Email,
+            get_blocklist_fingerprint(secret, enums::BlocklistDataKind::Email, email.peek())?,
+        ));
+    }
+
+    let ip_address = payment_data
+        .payment_attempt
+        .browser_info
+        .clone()
+        .map(|browser_info| {
+            browser_info.parse_value::<types::BrowserInformation>("BrowserInformation")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "browser_info",
+        })?
+        .and_then(|browser_info| browser_info.ip_address);
+    if let Some(ip_address) = ip_address {
+        fingerprints.push((
+            enums::BlocklistDataKind::Ip,
+            generate_fingerprint(secret, &ip_address.to_string())?,
+        ));
+    }
+
+    Ok(fingerprints)
+}
+
+/// Fails the payment if its card, email or IP address is present in the blocklist of the
+/// merchant, recording the hit on the matching blocklist entry.
+#[instrument(skip_all)]
+pub async fn validate_data_for_blocklist<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_data: &mut PaymentData<F>,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+
+    let secret = get_merchant_fingerprint_secret(db, merchant_id, key_store).await?;
+    let fingerprints = get_payment_fingerprints(&secret, payment_data)?;
+
+    let entry = match find_blocking_entry(db, merchant_id, &fingerprints).await? {
+        Some(entry) => entry,
+        None => return Ok(()),
+    };
+
+    logger::info!(
+        blocklist_id = %entry.blocklist_id,
+        data_kind = %entry.data_kind,
+        "Payment blocked by blocklist entry"
+    );
+
+    let hit_count = entry.hit_count.saturating_add(1);
+    if let Err(error) = db
+        .update_blocklist_entry(
+            merchant_id,
+            &entry.blocklist_id,
+            storage::BlocklistUpdate::HitUpdate {
+                hit_count,
+                last_hit_at: common_utils::date_time::now(),
+            },
+        )
+        .await
+    {
+        logger::error!(?error, "Failed to record hit on blocklist entry");
+    }
+
+    metrics::BLOCKED_PAYMENT_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes("merchant", merchant_id.to_owned()),
+            metrics::request::add_attributes("data_kind", entry.data_kind.to_string()),
+        ],
+    );
+
+    payment_data.payment_attempt = db
+        .update_payment_attempt_with_attempt_id(
+            payment_data.payment_attempt.clone(),
+            storage::PaymentAttemptUpdate::ErrorUpdate {
+                connector: None,
+                status: enums::AttemptStatus::Failure,
+                error_code: Some(Some(PAYMENT_BLOCKED_ERROR_CODE.to_string())),
+                error_message: Some(Some(PAYMENT_BLOCKED_ERROR_MESSAGE.to_string())),
+                error_reason: Some(entry.reason.clone()),
+                amount_capturable: Some(0),
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update payment attempt of blocked payment")?;
+
+    payment_data.payment_intent = db
+        .update_payment_intent(
+            payment_data.payment_intent.clone(),
+            storage::PaymentIntentUpdate::PGStatusUpdate {
+                status: enums::IntentStatus::Failed,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update payment intent of blocked payment")?;
+
+    Err(errors::ApiErrorResponse::PaymentBlocked {
+        payment_id: payment_data.payment_intent.payment_id.clone(),
+    })
+    .into_report()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::db::{blocklist::BlocklistInterface, configs::ConfigInterface, MockDb};
+
+    const MERCHANT_ID: &str = "merchant_blocklist";
+    const SECRET: &str = "secret";
+
+    async fn get_db() -> MockDb {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        db.insert_config(storage::ConfigNew {
+            key: get_merchant_fingerprint_secret_key(MERCHANT_ID),
+            config: SECRET.to_string(),
+        })
+        .await
+        .unwrap();
+        db
+    }
+
+    async fn get_key_store() -> domain::MerchantKeyStore {
+        domain::MerchantKeyStore {
+            merchant_id: MERCHANT_ID.to_string(),
+            key: domain::types::encrypt(
+                crate::services::generate_aes256_key()
+                    .unwrap()
+                    .to_vec()
+                    .into(),
+                &crate::services::generate_aes256_key().unwrap(),
+            )
+            .await
+            .unwrap(),
+            created_at: common_utils::date_time::now(),
+        }
+    }
+
+    async fn insert_entry(
+        db: &MockDb,
+        data_kind: enums::BlocklistDataKind,
+        data: &str,
+        expires_at: Option<time::PrimitiveDateTime>,
+    ) -> storage::Blocklist {
+        let now = common_utils::date_time::now();
+        let entry = db
+            .insert_blocklist_entry(storage::BlocklistNew {
+                blocklist_id: "blocklist_test".to_string(),
+                merchant_id: MERCHANT_ID.to_string(),
+                data_kind,
+                fingerprint: get_blocklist_fingerprint(SECRET, data_kind, data).unwrap(),
+                reason: Some("Fraud".to_string()),
+                expires_at,
+                created_at: now,
+                modified_at: now,
+            })
+            .await
+            .unwrap();
+        add_entry_to_cache(db, &entry).await;
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_blocked_email_is_declined() {
+        let db = get_db().await;
+        let entry = insert_entry(
+            &db,
+            enums::BlocklistDataKind::Email,
+            "Fraudster@Example.com",
+            None,
+        )
+        .await;
+
+        let secret = get_merchant_fingerprint_secret(&db, MERCHANT_ID, &get_key_store().await)
+            .await
+            .unwrap();
+        let fingerprints = vec![(
+            enums::BlocklistDataKind::Email,
+            get_blocklist_fingerprint(
+                &secret,
+                enums::BlocklistDataKind::Email,
+                " fraudster@example.com",
+            )
+            .unwrap(),
+        )];
+
+        let blocking_entry = find_blocking_entry(&db, MERCHANT_ID, &fingerprints)
+            .await
+            .unwrap();
+        assert_eq!(
+            blocking_entry.map(|entry| entry.blocklist_id),
+            Some(entry.blocklist_id)
+        );
+
+        let other_fingerprints = vec![(
+            enums::BlocklistDataKind::Email,
+            get_blocklist_fingerprint(
+                &secret,
+                enums::BlocklistDataKind::Email,
+                "customer@example.com",
+            )
+            .unwrap(),
+        )];
+        assert!(find_blocking_entry(&db, MERCHANT_ID, &other_fingerprints)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_secret_is_stored_encrypted() {
+        let db = get_db().await;
+        let key_store = get_key_store().await;
+
+        // The secret stored in plain text is encrypted on its first use
+        let secret = get_merchant_fingerprint_secret(&db, MERCHANT_ID, &key_store)
+            .await
+            .unwrap();
+        assert_eq!(secret, SECRET);
+
+        let stored_secret = db
+            .find_config_by_key(&get_merchant_fingerprint_secret_key(MERCHANT_ID))
+            .await
+            .unwrap()
+            .config;
+        assert!(stored_secret.starts_with(ENCRYPTED_FINGERPRINT_SECRET_PREFIX));
+        assert!(!stored_secret.contains(SECRET));
+
+        assert_eq!(
+            get_merchant_fingerprint_secret(&db, MERCHANT_ID, &key_store)
+                .await
+                .unwrap(),
+            SECRET
+        );
+        assert!(
+            get_merchant_fingerprint_secret(&db, MERCHANT_ID, &get_key_store().await)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_does_not_block() {
+        let db = get_db().await;
+        let expires_at = common_utils::date_time::now() - time::Duration::minutes(1);
+        insert_entry(
+            &db,
+            enums::BlocklistDataKind::Ip,
+            "203.0.113.7",
+            Some(expires_at),
+        )
+        .await;
+
+        let fingerprints = vec![(
+            enums::BlocklistDataKind::Ip,
+            get_blocklist_fingerprint(SECRET, enums::BlocklistDataKind::Ip, "203.0.113.7").unwrap(),
+        )];
+        assert!(find_blocking_entry(&db, MERCHANT_ID, &fingerprints)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_card_fingerprint_is_stable() {
+        let card_number = "4242 4242 4242 4242";
+        let data_kind = enums::BlocklistDataKind::CardFingerprint;
+
+        let fingerprint = get_blocklist_fingerprint(SECRET, data_kind, card_number).unwrap();
+        assert_eq!(
+            fingerprint,
+            get_blocklist_fingerprint(SECRET, data_kind, "4242424242424242").unwrap()
+        );
+        assert_eq!(
+            fingerprint,
+            generate_fingerprint(SECRET, "4242424242424242").unwrap()
+        );
+        assert_ne!(
+            fingerprint,
+            get_blocklist_fingerprint("other_secret", data_kind, card_number).unwrap()
+        );
+        assert!(get_blocklist_fingerprint(SECRET, data_kind, "4242424242424241").is_err());
+    }
+
+    #[test]
+    fn test_card_bin_is_not_fingerprinted() {
+        assert_eq!(
+            get_blocklist_fingerprint(SECRET, enums::BlocklistDataKind::CardBin, "424242").unwrap(),
+            "424242"
+        );
+        assert!(
+            get_blocklist_fingerprint(SECRET, enums::BlocklistDataKind::CardBin, "42424").is_err()
+        );
+    }
+}
@@ -0,0 +1,679 @@
+use api_models::{admin::ConnectorCircuitState, enums as api_enums, payments::MaintenanceFallback};
+use common_utils::{date_time, ext_traits::StringExt};
+use error_stack::ResultExt;
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    connector_maintenance,
+    errors::{self, RouterResult},
+};
+use crate::{
+    configs::settings::CircuitBreakerConfig,
+    routes::{metrics, AppState},
+};
+
+pub const CIRCUIT_BREAKER_PREFIX: &str = "CIRCUIT_BREAKER";
+
+/// Key of the redis hash holding the circuits of a connector for a merchant, with a field for each flow
+pub fn get_circuit_breaker_key(merchant_id: &str, connector: &str) -> String {
+    format!("{CIRCUIT_BREAKER_PREFIX}_{merchant_id}_{connector}")
+}
+
+/// Key of the config holding the connectors to fall back to, when the circuit of the connector
+/// chosen by the merchant routing algorithm is open
+pub fn get_routing_fallback_connectors_key(merchant_id: &str) -> String {
+    format!("routing_fallback_connectors_{merchant_id}")
+}
+
+pub fn get_flow_name<F>() -> String {
+    std::any::type_name::<F>()
+        .split("::")
+        .last()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitDecision {
+    // Circuit is closed, the request can be sent to the connector
+    Allow,
+    // Circuit is half open, the request is sent to the connector as a probe
+    AllowProbe,
+    // Circuit is open, the request must not be sent to the connector
+    Reject,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectorCallOutcome {
+    // The connector processed the request, this includes business declines and 4xx responses
+    Success,
+    // The connector could not be reached, timed out or responded with a 5xx
+    Failure,
+    // The outcome does not say anything about the availability of the connector
+    Ignored,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CircuitBreakerState {
+    pub flow: String,
+    pub state: ConnectorCircuitState,
+    pub window_started_at: i64,
+    pub request_count: u32,
+    pub failure_count: u32,
+    pub opened_at: Option<i64>,
+    pub half_open_probes: u32,
+    pub last_probe_at: Option<i64>,
+}
+
+impl CircuitBreakerState {
+    pub fn new(flow: String, now: i64) -> Self {
+        Self {
+            flow,
+            state: ConnectorCircuitState::Closed,
+            window_started_at: now,
+            request_count: 0,
+            failure_count: 0,
+            opened_at: None,
+            half_open_probes: 0,
+            last_probe_at: None,
+        }
+    }
+
+    fn open_duration_elapsed(since: Option<i64>, config: &CircuitBreakerConfig, now: i64) -> bool {
+        since.map_or(true, |since| {
+            now.saturating_sub(since) >= i64::from(config.open_duration_in_secs)
+        })
+    }
+
+    /// Whether requests are currently being short-circuited, without any probe being allowed
+    pub fn is_open(&self, config: &CircuitBreakerConfig, now: i64) -> bool {
+        self.state == ConnectorCircuitState::Open
+            && !Self::open_duration_elapsed(self.opened_at, config, now)
+    }
+
+    /// Decides whether a request can be sent to the connector, moving an open circuit to half open
+    /// once the open duration has elapsed
+    pub fn check_request(&mut self, config: &CircuitBreakerConfig, now: i64) -> CircuitDecision {
+        match self.state {
+            ConnectorCircuitState::Closed => CircuitDecision::Allow,
+            ConnectorCircuitState::Open => {
+                if Self::open_duration_elapsed(self.opened_at, config, now) {
+                    self.state = ConnectorCircuitState::HalfOpen;
+                    self.half_open_probes = 1;
+                    self.last_probe_at = Some(now);
+                    CircuitDecision::AllowProbe
+                } else {
+                    CircuitDecision::Reject
+                }
+            }
+            ConnectorCircuitState::HalfOpen => {
+                // A probe whose outcome was never recorded must not keep the circuit half open forever
+                if Self::open_duration_elapsed(self.last_probe_at, config, now) {
+                    self.half_open_probes = 0;
+                }
+                if self.half_open_probes < config.half_open_max_probes {
+                    self.half_open_probes += 1;
+                    self.last_probe_at = Some(now);
+                    CircuitDecision::AllowProbe
+                } else {
+                    CircuitDecision::Reject
+                }
+            }
+        }
+    }
+
+    /// Records the outcome of a connector call, returns the new state if the circuit transitioned
+    pub fn record_outcome(
+        &mut self,
+        outcome: ConnectorCallOutcome,
+        config: &CircuitBreakerConfig,
+        now: i64,
+    ) -> Option<ConnectorCircuitState> {
+        match (self.state, outcome) {
+            (_, ConnectorCallOutcome::Ignored) => None,
+            (ConnectorCircuitState::Closed, _) => {
+                if now.saturating_sub(self.window_started_at)
+                    >= i64::from(config.window_duration_in_secs)
+                {
+                    self.reset_window(now);
+                }
+                self.request_count = self.request_count.saturating_add(1);
+                if outcome == ConnectorCallOutcome::Failure {
+                    self.failure_count = self.failure_count.saturating_add(1);
+                }
+
+                let failure_rate_exceeded = u64::from(self.failure_count) * 100
+                    >= u64::from(config.failure_rate_threshold) * u64::from(self.request_count);
+                (self.request_count >= config.minimum_requests && failure_rate_exceeded).then(
+                    || {
+                        self.open(now);
+                        self.state
+                    },
+                )
+            }
+            (ConnectorCircuitState::HalfOpen, ConnectorCallOutcome::Success) => {
+                self.state = ConnectorCircuitState::Closed;
+                self.opened_at = None;
+                self.reset_window(now);
+                Some(self.state)
+            }
+            (ConnectorCircuitState::HalfOpen, ConnectorCallOutcome::Failure) => {
+                self.open(now);
+                Some(self.state)
+            }
+            // Outcomes of requests which were sent before the circuit was opened
+            (ConnectorCircuitState::Open, _) => None,
+        }
+    }
+
+    fn open(&mut self, now: i64) {
+        self.state = ConnectorCircuitState::Open;
+        self.opened_at = Some(now);
+        self.half_open_probes = 0;
+        self.last_probe_at = None;
+    }
+
+    fn reset_window(&mut self, now: i64) {
+        self.window_started_at = now;
+        self.request_count = 0;
+        self.failure_count = 0;
+        self.half_open_probes = 0;
+        self.last_probe_at = None;
+    }
+}
+
+/// Picks the first connector whose circuit is not open, starting with the primary connector.
+/// The primary connector is returned if the circuits of all the connectors are open.
+pub fn choose_connector_with_fallback<C, P>(primary: C, fallbacks: Vec<C>, is_open: P) -> C
+where
+    C: PartialEq,
+    P: Fn(&C) -> bool,
+{
+    if !is_open(&primary) {
+        return primary;
+    }
+
+    fallbacks
+        .into_iter()
+        .filter(|connector| connector != &primary)
+        .find(|connector| !is_open(connector))
+        .unwrap_or(primary)
+}
+
+async fn get_circuit(
+    redis_conn: &RedisConnectionPool,
+    key: &str,
+    flow: &str,
+) -> Option<CircuitBreakerState> {
+    match redis_conn
+        .get_hash_field_and_deserialize(key, flow, "CircuitBreakerState")
+        .await
+    {
+        Ok(circuit) => Some(circuit),
+        Err(error) => {
+            if !matches!(error.current_context(), errors::RedisError::NotFound) {
+                logger::error!(?error, "Failed to fetch the circuit breaker state");
+            }
+            None
+        }
+    }
+}
+
+async fn store_circuit(redis_conn: &RedisConnectionPool, key: &str, circuit: &CircuitBreakerState) {
+    let redis_value = match serde_json::to_string(circuit) {
+        Ok(redis_value) => redis_value,
+        Err(error) => {
+            logger::error!(?error, "Failed to serialize the circuit breaker state");
+            return;
+        }
+    };
+
+    if let Err(error) = redis_conn
+        .set_hash_fields(key, (&circuit.flow, &redis_value))
+        .await
+    {
+        logger::error!(?error, "Failed to store the circuit breaker state");
+    }
+}
+
+/// Checks the circuit of the connector for the flow, before the request is sent to the connector.
+///
+/// The circuit is read and written back without a lock, concurrent requests may briefly see a
+/// stale state. Failures in accessing redis do not block the request.
+#[instrument(skip_all)]
+pub async fn check_circuit(
+    state: &AppState,
+    merchant_id: &str,
+    connector: &str,
+    flow: &str,
+) -> CircuitDecision {
+    let config = &state.conf.circuit_breaker;
+    if !config.enabled {
+        return CircuitDecision::Allow;
+    }
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection for circuit breaker");
+            return CircuitDecision::Allow;
+        }
+    };
+
+    let key = get_circuit_breaker_key(merchant_id, connector);
+    let mut circuit = match get_circuit(&redis_conn, &key, flow).await {
+        Some(circuit) => circuit,
+        None => return CircuitDecision::Allow,
+    };
+
+    let previous_circuit = circuit.clone();
+    let decision = circuit.check_request(config, date_time::now_unix_timestamp());
+    if circuit != previous_circuit {
+        store_circuit(&redis_conn, &key, &circuit).await;
+    }
+
+    decision
+}
+
+/// Records the outcome of a connector call in the circuit of the connector for the flow
+#[instrument(skip_all)]
+pub async fn record_connector_call_outcome(
+    state: &AppState,
+    merchant_id: &str,
+    connector: &str,
+    flow: &str,
+    outcome: ConnectorCallOutcome,
+) {
+    let config = &state.conf.circuit_breaker;
+    if !config.enabled || outcome == ConnectorCallOutcome::Ignored {
+        return;
+    }
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection for circuit breaker");
+            return;
+        }
+    };
+
+    let now = date_time::now_unix_timestamp();
+    let key = get_circuit_breaker_key(merchant_id, connector);
+    let mut circuit = get_circuit(&redis_conn, &key, flow)
+        .await
+        .unwrap_or_else(|| CircuitBreakerState::new(flow.to_string(), now));
+
+    let transition = circuit.record_outcome(outcome, config, now);
+    match transition {
+        Some(ConnectorCircuitState::Open) => {
+            logger::warn!(connector, flow, "Circuit breaker opened");
+            metrics::CIRCUIT_BREAKER_OPENED.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::request::add_attributes("connector", connector.to_string()),
+                    metrics::request::add_attributes("flow", flow.to_string()),
+                ],
+            );
+        }
+        Some(ConnectorCircuitState::Closed) => {
+            logger::info!(connector, flow, "Circuit breaker closed");
+            metrics::CIRCUIT_BREAKER_CLOSED.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::request::add_attributes("connector", connector.to_string()),
+                    metrics::request::add_attributes("flow", flow.to_string()),
+                ],
+            );
+        }
+        Some(ConnectorCircuitState::HalfOpen) | None => {}
+    }
+
+    store_circuit(&redis_conn, &key, &circuit).await;
+}
+
+/// Whether the circuit of the connector for the flow is open, used to avoid routing to the connector
+pub async fn is_circuit_open(
+    state: &AppState,
+    merchant_id: &str,
+    connector: &str,
+    flow: &str,
+) -> bool {
+    let config = &state.conf.circuit_breaker;
+    if !config.enabled {
+        return false;
+    }
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection for circuit breaker");
+            return false;
+        }
+    };
+
+    get_circuit(
+        &redis_conn,
+        &get_circuit_breaker_key(merchant_id, connector),
+        flow,
+    )
+    .await
+    .map_or(false, |circuit| {
+        circuit.is_open(config, date_time::now_unix_timestamp())
+    })
+}
+
+/// Fetches the connectors configured to be used when the circuit of the connector chosen by the
+/// merchant routing algorithm is open
+pub async fn get_routing_fallback_connectors(
+    state: &AppState,
+    merchant_id: &str,
+) -> Vec<api_enums::RoutableConnectors> {
+    let fallback_connectors = state
+        .store
+        .find_config_by_key(&get_routing_fallback_connectors_key(merchant_id))
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_struct("RoutingFallbackConnectors")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match fallback_connectors {
+        Ok(fallback_connectors) => fallback_connectors,
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, "Failed to fetch the routing fallback connectors");
+            }
+            vec![]
+        }
+    }
+}
+
+/// The connector to route to, with the maintenance window of the primary connector when the
+/// payment was routed around the primary connector because of it
+#[derive(Clone, Debug)]
+pub struct AvailableConnector {
+    pub connector: api_enums::RoutableConnectors,
+    pub maintenance_fallback: Option<MaintenanceFallback>,
+    /// The connectors preferred over the connector to route to which were skipped, in the order
+    /// of preference
+    pub skipped_connectors: Vec<SkippedConnector>,
+}
+
+/// A connector which the payment was routed around
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SkippedConnector {
+    pub connector: api_enums::RoutableConnectors,
+    pub reason: api_enums::RoutingFallbackReason,
+}
+
+/// Returns the connector to route to, falling back to the configured connectors when the circuit
+/// of the primary connector is open or when the primary connector is under maintenance
+pub async fn get_connector_with_fallback(
+    state: &AppState,
+    merchant_id: &str,
+    primary: api_enums::RoutableConnectors,
+    flow: &str,
+) -> AvailableConnector {
+    let is_under_maintenance = connector_maintenance::get_ongoing_maintenance_window(
+        state.store.as_ref(),
+        &primary.to_string(),
+        flow,
+    )
+    .await
+    .is_some();
+    if !is_under_maintenance
+        && !is_circuit_open(state, merchant_id, &primary.to_string(), flow).await
+    {
+        return AvailableConnector {
+            connector: primary,
+            maintenance_fallback: None,
+            skipped_connectors: vec![],
+        };
+    }
+
+    let fallbacks = get_routing_fallback_connectors(state, merchant_id).await;
+    choose_available_connector(state, merchant_id, primary, fallbacks, flow).await
+}
+
+/// Returns the first of the connectors whose circuit is not open and which are not under
+/// maintenance, starting with the primary connector
+pub async fn choose_available_connector(
+    state: &AppState,
+    merchant_id: &str,
+    primary: api_enums::RoutableConnectors,
+    fallbacks: Vec<api_enums::RoutableConnectors>,
+    flow: &str,
+) -> AvailableConnector {
+    let mut unavailable_connectors: Vec<SkippedConnector> = vec![];
+    // The unavailable connectors which precede the first available connector are skipped
+    let mut skipped_count = None;
+    let mut primary_maintenance_window = None;
+    for connector in std::iter::once(&primary).chain(fallbacks.iter()) {
+        if unavailable_connectors
+            .iter()
+            .any(|unavailable| &unavailable.connector == connector)
+        {
+            continue;
+        }
+        let maintenance_window = connector_maintenance::get_ongoing_maintenance_window(
+            state.store.as_ref(),
+            &connector.to_string(),
+            flow,
+        )
+        .await;
+        let reason = if maintenance_window.is_some() {
+            Some(api_enums::RoutingFallbackReason::UnderMaintenance)
+        } else if is_circuit_open(state, merchant_id, &connector.to_string(), flow).await {
+            Some(api_enums::RoutingFallbackReason::CircuitOpen)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => unavailable_connectors.push(SkippedConnector {
+                connector: *connector,
+                reason,
+            }),
+            None => {
+                skipped_count.get_or_insert(unavailable_connectors.len());
+            }
+        }
+        if connector == &primary {
+            primary_maintenance_window = maintenance_window;
+        }
+    }
+
+    let connector = choose_connector_with_fallback(primary, fallbacks, |connector| {
+        unavailable_connectors
+            .iter()
+            .any(|unavailable| &unavailable.connector == connector)
+    });
+    // None of the connectors are skipped when all of them are unavailable, the payment is routed
+    // to the primary connector then
+    unavailable_connectors.truncate(skipped_count.unwrap_or(0));
+    let maintenance_fallback = primary_maintenance_window
+        .filter(|_| connector != primary)
+        .map(|window| connector_maintenance::get_maintenance_fallback(primary.to_string(), window));
+    if connector != primary {
+        logger::info!(
+            %primary,
+            fallback = %connector,
+            is_under_maintenance = maintenance_fallback.is_some(),
+            "Routing to fallback connector as the primary connector is unavailable"
+        );
+    }
+
+    AvailableConnector {
+        connector,
+        maintenance_fallback,
+        skipped_connectors: unavailable_connectors,
+    }
+}
+
+/// Fetches the circuits of all the flows tracked for the connector
+pub async fn get_connector_circuits(
+    state: &AppState,
+    merchant_id: &str,
+    connector: &str,
+) -> RouterResult<Vec<CircuitBreakerState>> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+
+    redis_conn
+        .hscan_and_deserialize(&get_circuit_breaker_key(merchant_id, connector), "*", None)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the circuit breaker states")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            enabled: true,
+            window_duration_in_secs: 60,
+            minimum_requests: 4,
+            failure_rate_threshold: 50,
+            open_duration_in_secs: 30,
+            half_open_max_probes: 1,
+        }
+    }
+
+    #[test]
+    fn test_circuit_opens_when_failure_rate_exceeds_threshold() {
+        let config = config();
+        let mut circuit = CircuitBreakerState::new("Authorize".to_string(), 0);
+
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Success, &config, 1),
+            None
+        );
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, 2),
+            None
+        );
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Ignored, &config, 3),
+            None
+        );
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Success, &config, 4),
+            None
+        );
+        assert_eq!(circuit.request_count, 3);
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, 5),
+            Some(ConnectorCircuitState::Open)
+        );
+        assert_eq!(circuit.opened_at, Some(5));
+        assert!(circuit.is_open(&config, 6));
+        assert_eq!(circuit.check_request(&config, 6), CircuitDecision::Reject);
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_not_counted() {
+        let config = config();
+        let mut circuit = CircuitBreakerState::new("Authorize".to_string(), 0);
+
+        for now in 0..3 {
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, now);
+        }
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, 61),
+            None
+        );
+        assert_eq!(circuit.state, ConnectorCircuitState::Closed);
+        assert_eq!(circuit.request_count, 1);
+        assert_eq!(circuit.window_started_at, 61);
+    }
+
+    #[test]
+    fn test_flapping_connector() {
+        let config = config();
+        let mut circuit = CircuitBreakerState::new("Authorize".to_string(), 0);
+
+        for now in 0..4 {
+            assert_eq!(circuit.check_request(&config, now), CircuitDecision::Allow);
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, now);
+        }
+        assert_eq!(circuit.state, ConnectorCircuitState::Open);
+
+        // Probe fails, circuit is opened again
+        assert_eq!(
+            circuit.check_request(&config, 33),
+            CircuitDecision::AllowProbe
+        );
+        assert_eq!(circuit.state, ConnectorCircuitState::HalfOpen);
+        assert_eq!(circuit.check_request(&config, 34), CircuitDecision::Reject);
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, 35),
+            Some(ConnectorCircuitState::Open)
+        );
+        assert_eq!(circuit.check_request(&config, 50), CircuitDecision::Reject);
+
+        // Probe succeeds, circuit is closed with a fresh window
+        assert_eq!(
+            circuit.check_request(&config, 65),
+            CircuitDecision::AllowProbe
+        );
+        assert_eq!(
+            circuit.record_outcome(ConnectorCallOutcome::Success, &config, 66),
+            Some(ConnectorCircuitState::Closed)
+        );
+        assert_eq!(circuit.request_count, 0);
+        assert_eq!(circuit.opened_at, None);
+        assert_eq!(circuit.check_request(&config, 67), CircuitDecision::Allow);
+    }
+
+    #[test]
+    fn test_lost_probe_does_not_keep_circuit_half_open() {
+        let config = config();
+        let mut circuit = CircuitBreakerState::new("Authorize".to_string(), 0);
+        for now in 0..4 {
+            circuit.record_outcome(ConnectorCallOutcome::Failure, &config, now);
+        }
+
+        assert_eq!(
+            circuit.check_request(&config, 40),
+            CircuitDecision::AllowProbe
+        );
+        assert_eq!(circuit.check_request(&config, 50), CircuitDecision::Reject);
+        assert_eq!(
+            circuit.check_request(&config, 70),
+            CircuitDecision::AllowProbe
+        );
+    }
+
+    #[test]
+    fn test_choose_connector_with_fallback() {
+        let open = ["stripe", "adyen"];
+        let is_open = |connector: &&str| open.contains(connector);
+
+        assert_eq!(
+            choose_connector_with_fallback("checkout", vec!["stripe"], is_open),
+            "checkout"
+        );
+        assert_eq!(
+            choose_connector_with_fallback("stripe", vec!["adyen", "checkout"], is_open),
+            "checkout"
+        );
+        assert_eq!(
+            choose_connector_with_fallback("stripe", vec!["stripe", "adyen"], is_open),
+            "stripe"
+        );
+        assert_eq!(
+            choose_connector_with_fallback("stripe", vec![], is_open),
+            "stripe"
+        );
+    }
+}
@@ -0,0 +1,465 @@
+//! Maintenance windows announced by the connectors. During a window, the flows it affects are not
+//! sent to the connector: new payments are routed to the fallback connectors of the merchant, and
+//! the other affected flows are rejected without calling the connector. The syncs and the webhooks
+//! of the connector are never affected, so that the payments already processed by the connector
+//! are still resolved during the window.
+//!
+//! The windows of a connector are stored in a config, with their timestamps in UTC. The windows
+//! which have ended are ignored when the windows are read, and dropped when they are written.
+
+use api_models::{
+    admin::{
+        ConnectorMaintenanceFlow, ConnectorMaintenanceWindow, ConnectorMaintenanceWindowRequest,
+        ConnectorMaintenanceWindowsResponse,
+    },
+    enums as api_enums,
+    payments::MaintenanceFallback,
+};
+use common_utils::{
+    date_time,
+    ext_traits::{Encode, StringExt},
+};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
+
+use super::errors::{self, RouterResponse, RouterResult};
+use crate::{
+    consts, db::StorageInterface, routes::AppState, services::ApplicationResponse, types::storage,
+    utils,
+};
+
+/// Maintenance windows of the connectors which have not announced any
+const EMPTY_MAINTENANCE_WINDOWS: &str = "[]";
+
+/// Key of the config holding the maintenance windows of the connector
+pub fn get_maintenance_windows_key(connector: &str) -> String {
+    format!("maintenance_windows_{connector}")
+}
+
+/// The flow affected by the maintenance windows which include it, for a flow of the connector.
+/// None for the flows which are never affected, like the syncs.
+pub fn get_maintenance_flow(flow: &str) -> Option<ConnectorMaintenanceFlow> {
+    match flow {
+        "Authorize" | "SetupMandate" => Some(ConnectorMaintenanceFlow::Payment),
+        "Capture" => Some(ConnectorMaintenanceFlow::Capture),
+        "Void" => Some(ConnectorMaintenanceFlow::Void),
+        "Execute" => Some(ConnectorMaintenanceFlow::Refund),
+        _ => None,
+    }
+}
+
+/// Fetches the maintenance windows of the connector which have not ended, in order of their start
+pub async fn get_maintenance_windows(
+    db: &dyn StorageInterface,
+    connector: &str,
+) -> Vec<ConnectorMaintenanceWindow> {
+    // An empty list is stored for the connectors without windows, so that their absence of windows
+    // is cached like the windows of the other connectors, instead of being read on every payment
+    let windows = db
+        .find_config_by_key_unwrap_or(
+            &get_maintenance_windows_key(connector),
+            Some(EMPTY_MAINTENANCE_WINDOWS.to_string()),
+        )
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_struct::<Vec<ConnectorMaintenanceWindow>>("ConnectorMaintenanceWindows")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match windows {
+        Ok(windows) => get_windows_not_ended(windows, date_time::now()),
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, %connector, "Failed to fetch the maintenance windows");
+            }
+            vec![]
+        }
+    }
+}
+
+fn get_windows_not_ended(
+    windows: Vec<ConnectorMaintenanceWindow>,
+    now: PrimitiveDateTime,
+) -> Vec<ConnectorMaintenanceWindow> {
+    let mut windows = windows
+        .into_iter()
+        .filter(|window| window.end_time > now)
+        .collect::<Vec<_>>();
+    windows.sort_by_key(|window| window.start_time);
+    windows
+}
+
+/// The ongoing window affecting the flow, the one ending last when overlapping windows affect it
+pub fn find_ongoing_window(
+    windows: &[ConnectorMaintenanceWindow],
+    flow: ConnectorMaintenanceFlow,
+    now: PrimitiveDateTime,
+) -> Option<&ConnectorMaintenanceWindow> {
+    windows
+        .iter()
+        .filter(|window| {
+            window.flows.contains(&flow) && window.start_time <= now && now < window.end_time
+        })
+        .max_by_key(|window| window.end_time)
+}
+
+/// The ongoing maintenance window of the connector which affects the flow of the connector
+#[instrument(skip(db))]
+pub async fn get_ongoing_maintenance_window(
+    db: &dyn StorageInterface,
+    connector: &str,
+    flow: &str,
+) -> Option<ConnectorMaintenanceWindow> {
+    let maintenance_flow = get_maintenance_flow(flow)?;
+    let windows = get_maintenance_windows(db, connector).await;
+
+    find_ongoing_window(&windows, maintenance_flow, date_time::now()).cloned()
+}
+
+pub fn get_maintenance_fallback(
+    skipped_connector: String,
+    window: ConnectorMaintenanceWindow,
+) -> MaintenanceFallback {
+    MaintenanceFallback {
+        skipped_connector,
+        window_id: window.window_id,
+        end_time: window.end_time,
+        message: window.message,
+    }
+}
+
+fn validate_maintenance_window(
+    req: &ConnectorMaintenanceWindowRequest,
+    windows: &[ConnectorMaintenanceWindow],
+    now: PrimitiveDateTime,
+) -> RouterResult<()> {
+    let invalid_window = |message: &str| {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: message.to_string(),
+        }))
+    };
+
+    if req.end_time <= req.start_time {
+        return invalid_window("end_time must be after start_time");
+    }
+    if req.end_time <= now {
+        return invalid_window("end_time must be in the future");
+    }
+    if req.flows.is_empty() {
+        return invalid_window("flows must not be empty");
+    }
+    // Overlapping windows are allowed, as a connector can announce the maintenance of its
+    // platforms separately, but the same window is not stored twice
+    if windows.iter().any(|window| {
+        window.start_time == req.start_time
+            && window.end_time == req.end_time
+            && window.flows == req.flows
+    }) {
+        return invalid_window("the same maintenance window already exists");
+    }
+
+    Ok(())
+}
+
+async fn store_maintenance_windows(
+    db: &dyn StorageInterface,
+    connector: &str,
+    windows: &[ConnectorMaintenanceWindow],
+) -> RouterResult<()> {
+    let key = get_maintenance_windows_key(connector);
+    let config = Encode::<Vec<ConnectorMaintenanceWindow>>::encode_to_string_of_json(&windows)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the maintenance windows")?;
+
+    match db.find_config_by_key_from_db(&key).await {
+        Ok(_) => db
+            .update_config_by_key(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the maintenance windows")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the maintenance windows")?,
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the maintenance windows")?,
+    };
+
+    Ok(())
+}
+
+pub async fn list_maintenance_windows(
+    state: AppState,
+    connector: api_enums::Connector,
+) -> RouterResponse<ConnectorMaintenanceWindowsResponse> {
+    let maintenance_windows =
+        get_maintenance_windows(state.store.as_ref(), &connector.to_string()).await;
+
+    Ok(ApplicationResponse::Json(
+        ConnectorMaintenanceWindowsResponse {
+            connector,
+            maintenance_windows,
+        },
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn create_maintenance_window(
+    state: AppState,
+    connector: api_enums::Connector,
+    req: ConnectorMaintenanceWindowRequest,
+) -> RouterResponse<ConnectorMaintenanceWindowsResponse> {
+    let db = state.store.as_ref();
+    let mut maintenance_windows = get_maintenance_windows(db, &connector.to_string()).await;
+    validate_maintenance_window(&req, &maintenance_windows, date_time::now())?;
+
+    maintenance_windows.push(ConnectorMaintenanceWindow {
+        window_id: utils::generate_id(consts::ID_LENGTH, "mw"),
+        start_time: req.start_time,
+        end_time: req.end_time,
+        flows: req.flows,
+        message: req.message,
+    });
+    maintenance_windows.sort_by_key(|window| window.start_time);
+    store_maintenance_windows(db, &connector.to_string(), &maintenance_windows).await?;
+
+    Ok(ApplicationResponse::Json(
+        ConnectorMaintenanceWindowsResponse {
+            connector,
+            maintenance_windows,
+        },
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn delete_maintenance_window(
+    state: AppState,
+    connector: api_enums::Connector,
+    window_id: String,
+) -> RouterResponse<ConnectorMaintenanceWindowsResponse> {
+    let db = state.store.as_ref();
+    let mut maintenance_windows = get_maintenance_windows(db, &connector.to_string()).await;
+    let windows_count = maintenance_windows.len();
+    maintenance_windows.retain(|window| window.window_id != window_id);
+    if maintenance_windows.len() == windows_count {
+        return Err(report!(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Maintenance window {window_id} does not exist for {connector}"),
+        }));
+    }
+    store_maintenance_windows(db, &connector.to_string(), &maintenance_windows).await?;
+
+    Ok(ApplicationResponse::Json(
+        ConnectorMaintenanceWindowsResponse {
+            connector,
+            maintenance_windows,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use time::Duration;
+
+    use super::*;
+    use crate::core::circuit_breaker;
+
+    fn get_window(
+        window_id: &str,
+        start_time: PrimitiveDateTime,
+        end_time: PrimitiveDateTime,
+        flows: Vec<ConnectorMaintenanceFlow>,
+    ) -> ConnectorMaintenanceWindow {
+        ConnectorMaintenanceWindow {
+            window_id: window_id.to_string(),
+            start_time,
+            end_time,
+            flows,
+            message: Some("Scheduled maintenance".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_ongoing_window_of_overlapping_windows() {
+        let now = date_time::now();
+        let windows = vec![
+            get_window(
+                "mw_1",
+                now - Duration::hours(1),
+                now + Duration::hours(1),
+                vec![ConnectorMaintenanceFlow::Payment],
+            ),
+            get_window(
+                "mw_2",
+                now - Duration::minutes(10),
+                now + Duration::hours(3),
+                vec![
+                    ConnectorMaintenanceFlow::Payment,
+                    ConnectorMaintenanceFlow::Refund,
+                ],
+            ),
+            get_window(
+                "mw_3",
+                now + Duration::hours(1),
+                now + Duration::hours(5),
+                vec![ConnectorMaintenanceFlow::Capture],
+            ),
+        ];
+
+        let window = find_ongoing_window(&windows, ConnectorMaintenanceFlow::Payment, now).unwrap();
+        assert_eq!(window.window_id, "mw_2");
+        let window = find_ongoing_window(&windows, ConnectorMaintenanceFlow::Refund, now).unwrap();
+        assert_eq!(window.window_id, "mw_2");
+        // The window of the captures has not started yet
+        assert!(find_ongoing_window(&windows, ConnectorMaintenanceFlow::Capture, now).is_none());
+        assert!(find_ongoing_window(&windows, ConnectorMaintenanceFlow::Void, now).is_none());
+        // The end of a window is exclusive
+        assert!(find_ongoing_window(
+            &windows,
+            ConnectorMaintenanceFlow::Refund,
+            now + Duration::hours(3)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_ended_windows_are_dropped() {
+        let now = date_time::now();
+        let windows = get_windows_not_ended(
+            vec![
+                get_window(
+                    "mw_2",
+                    now + Duration::hours(1),
+                    now + Duration::hours(2),
+                    vec![ConnectorMaintenanceFlow::Payment],
+                ),
+                get_window(
+                    "mw_1",
+                    now - Duration::hours(2),
+                    now - Duration::hours(1),
+                    vec![ConnectorMaintenanceFlow::Payment],
+                ),
+                get_window(
+                    "mw_3",
+                    now - Duration::hours(1),
+                    now + Duration::hours(1),
+                    vec![ConnectorMaintenanceFlow::Payment],
+                ),
+            ],
+            now,
+        );
+
+        assert_eq!(
+            windows
+                .iter()
+                .map(|window| window.window_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["mw_3", "mw_2"]
+        );
+    }
+
+    #[test]
+    fn test_window_timestamps_with_offsets_are_stored_in_utc() {
+        let req = serde_json::from_value::<ConnectorMaintenanceWindowRequest>(serde_json::json!({
+            "start_time": "2023-11-20T02:00:00+05:30",
+            "end_time": "2023-11-20T06:00:00+05:30",
+            "flows": ["payment"],
+        }))
+        .unwrap();
+
+        assert_eq!(req.start_time, time::macros::datetime!(2023-11-19 20:30:00));
+        assert_eq!(req.end_time, time::macros::datetime!(2023-11-20 00:30:00));
+        assert!(validate_maintenance_window(
+            &req,
+            &[],
+            time::macros::datetime!(2023-11-19 12:00:00)
+        )
+        .is_ok());
+        // The window has ended
+        assert!(validate_maintenance_window(
+            &req,
+            &[],
+            time::macros::datetime!(2023-11-20 01:00:00)
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_payment_during_the_window_routes_to_the_fallback_and_sync_is_allowed() {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(crate::services::MockApiClient),
+        )
+        .await;
+        let now = date_time::now();
+        create_maintenance_window(
+            state.clone(),
+            api_enums::Connector::Stripe,
+            ConnectorMaintenanceWindowRequest {
+                start_time: now - Duration::minutes(5),
+                end_time: now + Duration::hours(1),
+                flows: vec![ConnectorMaintenanceFlow::Payment],
+                message: Some("Scheduled maintenance".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        state
+            .store
+            .insert_config(storage::ConfigNew {
+                key: circuit_breaker::get_routing_fallback_connectors_key("merchant_1"),
+                config: r#"["stripe","adyen"]"#.to_string(),
+            })
+            .await
+            .unwrap();
+
+        let routed_connector = circuit_breaker::get_connector_with_fallback(
+            &state,
+            "merchant_1",
+            api_enums::RoutableConnectors::Stripe,
+            "Authorize",
+        )
+        .await;
+        assert_eq!(
+            routed_connector.connector,
+            api_enums::RoutableConnectors::Adyen
+        );
+        let maintenance_fallback = routed_connector.maintenance_fallback.unwrap();
+        assert_eq!(maintenance_fallback.skipped_connector, "stripe");
+        assert_eq!(
+            maintenance_fallback.message.as_deref(),
+            Some("Scheduled maintenance")
+        );
+
+        // The payments already processed by the connector are still synced, and the other
+        // connectors are not affected
+        assert!(
+            get_ongoing_maintenance_window(state.store.as_ref(), "stripe", "PSync")
+                .await
+                .is_none()
+        );
+        assert!(
+            get_ongoing_maintenance_window(state.store.as_ref(), "stripe", "Authorize")
+                .await
+                .is_some()
+        );
+        assert!(
+            get_ongoing_maintenance_window(state.store.as_ref(), "adyen", "Authorize")
+                .await
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Capture of the raw responses of the connectors, so that the response of the connector to a
+//! payment or a refund can be inspected without searching the logs. The capture is enabled per
+//! merchant, and the responses are stored as they are logged, with the sensitive fields and the
+//! card numbers masked, and truncated when they are too long.
+//!
+//! The latest response captured for a payment or a refund is returned by retrieve with
+//! `expand=connector_response`, to the API keys with the `connector_response_read` permission.
+
+use api_models::{
+    enums::ApiKeyPermission,
+    payments::{ConnectorResponseCaptureStatus, ConnectorResponseDetails},
+};
+use common_utils::date_time;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::errors::{self, RouterResult};
+use crate::{
+    consts,
+    db::StorageInterface,
+    services::{authentication, ApplicationResponse},
+    types::{self, api, storage},
+    utils,
+};
+
+/// Key of the config holding whether the responses of the connectors are captured for the merchant
+pub fn get_connector_response_capture_key(merchant_id: &str) -> String {
+    format!("connector_response_capture_{merchant_id}")
+}
+
+pub async fn is_connector_response_capture_enabled(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> bool {
+    let config = db
+        .find_config_by_key(&get_connector_response_capture_key(merchant_id))
+        .await;
+
+    match config {
+        Ok(config) => config.config == "true",
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(
+                    ?error,
+                    "Failed to fetch the connector response capture config"
+                );
+            }
+            false
+        }
+    }
+}
+
+/// The payment or refund which a response of the connector is for
+#[derive(Clone, Copy, Debug)]
+pub struct CapturedResource<'a> {
+    pub merchant_id: &'a str,
+    pub payment_id: &'a str,
+    pub attempt_id: &'a str,
+    /// `None` for the responses to the payment flows
+    pub refund_id: Option<&'a str>,
+    pub connector: &'a str,
+}
+
+/// Stores the response of the connector when the capture is enabled for the merchant. A response
+/// which cannot be stored is only logged, it never fails the payment or the refund.
+#[instrument(skip_all)]
+pub async fn capture_connector_response(
+    db: &dyn StorageInterface,
+    resource: CapturedResource<'_>,
+    response: Option<&types::RawConnectorResponse>,
+) {
+    let Some(response) = response else {
+        return;
+    };
+    if !is_connector_response_capture_enabled(db, resource.merchant_id).await {
+        return;
+    }
+
+    let capture = storage::ConnectorResponseCaptureNew {
+        capture_id: utils::generate_id(consts::ID_LENGTH, "crc"),
+        merchant_id: resource.merchant_id.to_string(),
+        payment_id: resource.payment_id.to_string(),
+        attempt_id: resource.attempt_id.to_string(),
+        refund_id: resource.refund_id.map(str::to_string),
+        connector: resource.connector.to_string(),
+        flow: response.flow.clone(),
+        status_code: i32::from(response.status_code),
+        response_body: response.body.clone(),
+        is_truncated: response.is_truncated,
+        created_at: date_time::now(),
+    };
+    if let Err(error) = db.insert_connector_response_capture(capture).await {
+        logger::error!(?error, "Failed to capture the connector response");
+    }
+}
+
+/// Checks that the request may expand the raw response of the connector. Unlike the permissions
+/// of the flows, the requests which were not authenticated with an API key are not allowed.
+pub fn check_connector_response_access(
+    api_key_permissions: Option<&[ApiKeyPermission]>,
+) -> RouterResult<()> {
+    match api_key_permissions {
+        Some(permissions) => authentication::check_api_key_has_permission(
+            Some(permissions),
+            ApiKeyPermission::ConnectorResponseRead,
+        ),
+        None => Err(report!(errors::ApiErrorResponse::MissingApiKeyPermission {
+            permission: ApiKeyPermission::ConnectorResponseRead.to_string(),
+        })),
+    }
+    .attach_printable("API key is not allowed to read the raw responses of the connectors")
+}
+
+/// The payment or refund whose latest captured response is read
+#[derive(Clone, Copy, Debug)]
+pub enum ConnectorResponseLookup<'a> {
+    Payment { payment_id: &'a str },
+    Refund { refund_id: &'a str },
+}
+
+/// Returns the latest response of the connector captured for the payment or refund, or a marker
+/// of why no response is returned
+pub async fn get_connector_response_details(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    lookup: ConnectorResponseLookup<'_>,
+) -> RouterResult<ConnectorResponseDetails> {
+    if !is_connector_response_capture_enabled(db, merchant_id).await {
+        return Ok(ConnectorResponseDetails::without_response(
+            ConnectorResponseCaptureStatus::NotCaptured,
+        ));
+    }
+
+    let capture = match lookup {
+        ConnectorResponseLookup::Payment { payment_id } => {
+            db.find_latest_connector_response_capture_by_merchant_id_payment_id(
+                merchant_id,
+                payment_id,
+            )
+            .await
+        }
+        ConnectorResponseLookup::Refund { refund_id } => {
+            db.find_latest_connector_response_capture_by_merchant_id_refund_id(
+                merchant_id,
+                refund_id,
+            )
+            .await
+        }
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to fetch the captured connector response")?;
+
+    Ok(match capture {
+        Some(capture) => ConnectorResponseDetails {
+            status: ConnectorResponseCaptureStatus::Captured,
+            connector: Some(capture.connector),
+            flow: Some(capture.flow),
+            attempt_id: Some(capture.attempt_id),
+            status_code: u16::try_from(capture.status_code).ok(),
+            body: Some(capture.response_body),
+            is_truncated: Some(capture.is_truncated),
+            captured_at: Some(capture.created_at),
+        },
+        None => {
+            ConnectorResponseDetails::without_response(ConnectorResponseCaptureStatus::NoResponse)
+        }
+    })
+}
+
+pub async fn add_connector_response_to_payments_response(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    response: ApplicationResponse<api::PaymentsResponse>,
+) -> RouterResult<ApplicationResponse<api::PaymentsResponse>> {
+    Ok(match response {
+        ApplicationResponse::Json(payments_response) => ApplicationResponse::Json(
+            with_connector_response(db, merchant_id, payments_response).await?,
+        ),
+        ApplicationResponse::JsonWithHeaders((payments_response, headers)) => {
+            ApplicationResponse::JsonWithHeaders((
+                with_connector_response(db, merchant_id, payments_response).await?,
+                headers,
+            ))
+        }
+        response => response,
+    })
+}
+
+async fn with_connector_response(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    mut payments_response: api::PaymentsResponse,
+) -> RouterResult<api::PaymentsResponse> {
+    let payment_id = payments_response.payment_id.clone().unwrap_or_default();
+    payments_response.connector_response = Some(
+        get_connector_response_details(
+            db,
+            merchant_id,
+            ConnectorResponseLookup::Payment {
+                payment_id: &payment_id,
+            },
+        )
+        .await?,
+    );
+    Ok(payments_response)
+}
+
+pub async fn add_connector_response_to_refund_response(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    response: ApplicationResponse<api::RefundResponse>,
+) -> RouterResult<ApplicationResponse<api::RefundResponse>> {
+    Ok(match response {
+        ApplicationResponse::Json(mut refund_response) => {
+            refund_response.connector_response = Some(
+                get_connector_response_details(
+                    db,
+                    merchant_id,
+                    ConnectorResponseLookup::Refund {
+                        refund_id: &refund_response.refund_id,
+                    },
+                )
+                .await?,
+            );
+            ApplicationResponse::Json(refund_response)
+        }
+        response => response,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{db::MockDb, services::api::masked_logging};
+
+    const CARD_NUMBER: &str = "4111111111111111";
+
+    fn get_resource(refund_id: Option<&str>) -> CapturedResource<'_> {
+        CapturedResource {
+            merchant_id: "merchant_1",
+            payment_id: "pay_1",
+            attempt_id: "pay_1_1",
+            refund_id,
+            connector: "adyen",
+        }
+    }
+
+    /// A decline of the connector which echoes the card number in a field which is not registered
+    /// as sensitive
+    fn get_declined_response() -> types::RawConnectorResponse {
+        let body = serde_json::json!({
+            "pspReference": "8835511210681341",
+            "resultCode": "Refused",
+            "refusalReason": format!("Card {CARD_NUMBER} declined: insufficient funds"),
+            "additionalData": { "cardSummary": "1111" },
+        })
+        .to_string();
+        let masked_body = masked_logging::get_masked_body("adyen", body.as_bytes(), false);
+        let (body, is_truncated) = masked_logging::get_captured_body(
+            &masked_body,
+            consts::CONNECTOR_RESPONSE_CAPTURE_MAX_LENGTH,
+        );
+        types::RawConnectorResponse {
+            flow: "Authorize".to_string(),
+            status_code: 422,
+            body,
+            is_truncated,
+        }
+    }
+
+    async fn get_db(capture_enabled: bool) -> MockDb {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        if capture_enabled {
+            db.insert_config(storage::ConfigNew {
+                key: get_connector_response_capture_key("merchant_1"),
+                config: "true".to_string(),
+            })
+            .await
+            .unwrap();
+        }
+        db
+    }
+
+    #[tokio::test]
+    async fn test_captured_response_is_masked() {
+        let db = get_db(true).await;
+        capture_connector_response(&db, get_resource(None), Some(&get_declined_response())).await;
+
+        let details = get_connector_response_details(
+            &db,
+            "merchant_1",
+            ConnectorResponseLookup::Payment {
+                payment_id: "pay_1",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(details.status, ConnectorResponseCaptureStatus::Captured);
+        assert_eq!(details.status_code, Some(422));
+        assert_eq!(details.attempt_id.as_deref(), Some("pay_1_1"));
+        let body = details.body.unwrap();
+        assert!(!body.contains(CARD_NUMBER), "{body} is not masked");
+        assert!(body.contains(masked_logging::MASKED_VALUE));
+        assert!(body.contains("Refused"));
+
+        // The responses to the refunds of the payment are not returned for the payment
+        capture_connector_response(
+            &db,
+            get_resource(Some("ref_1")),
+            Some(&types::RawConnectorResponse {
+                flow: "Execute".to_string(),
+                status_code: 200,
+                body: "{}".to_string(),
+                is_truncated: false,
+            }),
+        )
+        .await;
+        let details = get_connector_response_details(
+            &db,
+            "merchant_1",
+            ConnectorResponseLookup::Payment {
+                payment_id: "pay_1",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(details.flow.as_deref(), Some("Authorize"));
+        let details = get_connector_response_details(
+            &db,
+            "merchant_1",
+            ConnectorResponseLookup::Refund { refund_id: "ref_1" },
+        )
+        .await
+        .unwrap();
+        assert_eq!(details.flow.as_deref(), Some("Execute"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_capture_returns_the_not_captured_marker() {
+        let db = get_db(false).await;
+        capture_connector_response(&db, get_resource(None), Some(&get_declined_response())).await;
+        assert!(db.connector_response_captures.lock().await.is_empty());
+
+        let details = get_connector_response_details(
+            &db,
+            "merchant_1",
+            ConnectorResponseLookup::Payment {
+                payment_id: "pay_1",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            details,
+            ConnectorResponseDetails::without_response(ConnectorResponseCaptureStatus::NotCaptured)
+        );
+        assert_eq!(
+            serde_json::to_value(&details).unwrap()["status"],
+            "not_captured"
+        );
+
+        // A payment which has no captured response is distinguished from a disabled capture
+        let db = get_db(true).await;
+        let details = get_connector_response_details(
+            &db,
+            "merchant_1",
+            ConnectorResponseLookup::Payment {
+                payment_id: "pay_1",
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(details.status, ConnectorResponseCaptureStatus::NoResponse);
+    }
+
+    #[test]
+    fn test_connector_response_access_requires_the_permission() {
+        assert!(check_connector_response_access(Some(&[
+            ApiKeyPermission::PaymentsRead,
+            ApiKeyPermission::ConnectorResponseRead
+        ]))
+        .is_ok());
+        assert!(check_connector_response_access(Some(&[ApiKeyPermission::Admin])).is_ok());
+
+        for permissions in [Some(&[ApiKeyPermission::PaymentsRead][..]), None] {
+            let error = check_connector_response_access(permissions).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::MissingApiKeyPermission { permission }
+                    if permission == "connector_response_read"
+            ));
+        }
+    }
+}
@@ -0,0 +1,843 @@
+//! Templates of the connector accounts of a merchant, so that platforms can configure the connector
+//! accounts of their sub-merchants alike. A template is exported from a merchant with every
+//! credential replaced by a `{{variable}}` placeholder, and imported into a merchant with the
+//! values of the variables for that merchant.
+//!
+//! The entries of a template are identified by their connector labels: importing a template again
+//! updates the connector accounts created by the previous import, instead of creating them again.
+//! Each entry is imported on its own, an entry which fails to be imported is reported without
+//! preventing the other entries from being imported.
+
+use std::{collections::HashMap, str::FromStr};
+
+use api_models::{
+    admin::{
+        MerchantConnectorCreate, MerchantConnectorResponse, MerchantConnectorTemplate,
+        MerchantConnectorTemplateEntry, MerchantConnectorTemplateImportRequest,
+        MerchantConnectorTemplateImportResponse, MerchantConnectorTemplateImportResult,
+        MerchantConnectorTemplateImportStatus, MerchantConnectorUpdate,
+    },
+    enums as api_enums,
+};
+use common_utils::{errors::ErrorSwitch, ext_traits::ValueExt};
+use error_stack::{report, ResultExt};
+use masking::{PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use super::{
+    admin,
+    errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+};
+use crate::{
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{self, domain},
+};
+
+const AUTH_TYPE_FIELD: &str = "auth_type";
+
+/// Exports the connector accounts of the merchant as a template, with their credentials replaced
+/// by placeholders named after the label of the connector account and the name of the credential,
+/// like `{{stripe_US_travel_api_key}}`
+#[instrument(skip(state))]
+pub async fn export_connector_template(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<MerchantConnectorTemplate> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_connector_accounts = db
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_id,
+            true,
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the connector accounts of the merchant")?;
+
+    let mut connectors = Vec::with_capacity(merchant_connector_accounts.len());
+    for merchant_connector_account in merchant_connector_accounts {
+        if let Some(entry) = get_template_entry(merchant_connector_account)? {
+            let connector_account_details = get_credential_placeholders(
+                &entry.connector_label,
+                entry.connector_account_details.peek(),
+            );
+            connectors.push(MerchantConnectorTemplateEntry {
+                connector_account_details: Secret::new(connector_account_details),
+                ..entry
+            });
+        }
+    }
+
+    Ok(ApplicationResponse::Json(MerchantConnectorTemplate {
+        connectors,
+    }))
+}
+
+/// Applies the template to the merchant, creating the connector accounts of the entries whose
+/// labels are not used by the merchant and updating the others
+#[instrument(skip(state, req))]
+pub async fn import_connector_template(
+    state: AppState,
+    merchant_id: String,
+    req: MerchantConnectorTemplateImportRequest,
+) -> RouterResponse<MerchantConnectorTemplateImportResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let _merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mut results = Vec::with_capacity(req.template.connectors.len());
+    for entry in req.template.connectors {
+        let connector_label = entry.connector_label.clone();
+        let connector_name = entry.connector_name;
+        let result = match import_template_entry(
+            &state,
+            &merchant_id,
+            &key_store,
+            entry,
+            &req.variables,
+            req.dry_run,
+        )
+        .await
+        {
+            Ok((status, merchant_connector_id)) => MerchantConnectorTemplateImportResult {
+                connector_label,
+                connector_name,
+                status,
+                merchant_connector_id,
+                error_message: None,
+            },
+            Err(error) => {
+                logger::info!(
+                    ?error,
+                    "Failed to import the template entry {connector_label}"
+                );
+                MerchantConnectorTemplateImportResult {
+                    connector_label,
+                    connector_name,
+                    status: MerchantConnectorTemplateImportStatus::Failed,
+                    merchant_connector_id: None,
+                    error_message: Some(get_error_message(error.current_context())),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    Ok(ApplicationResponse::Json(
+        MerchantConnectorTemplateImportResponse {
+            merchant_id,
+            dry_run: req.dry_run,
+            results,
+        },
+    ))
+}
+
+/// Imports the entry, returns the outcome and the id of the connector account of the entry
+async fn import_template_entry(
+    state: &AppState,
+    merchant_id: &str,
+    key_store: &domain::MerchantKeyStore,
+    entry: MerchantConnectorTemplateEntry,
+    variables: &HashMap<String, Secret<String>>,
+    dry_run: bool,
+) -> RouterResult<(MerchantConnectorTemplateImportStatus, Option<String>)> {
+    let connector_account_details =
+        substitute_variables(entry.connector_account_details.peek().clone(), variables)
+            .map_err(|variable| {
+                report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "No value is provided for the variable `{variable}` of the connector account details"
+                    ),
+                })
+            })?;
+    let entry = MerchantConnectorTemplateEntry {
+        connector_account_details: Secret::new(connector_account_details),
+        ..entry
+    };
+    validate_template_entry(state, &entry)?;
+
+    let existing_merchant_connector_account = match state
+        .store
+        .find_merchant_connector_account_by_merchant_id_connector_label(
+            merchant_id,
+            &entry.connector_label,
+            key_store,
+        )
+        .await
+    {
+        Ok(merchant_connector_account) => Some(merchant_connector_account),
+        Err(error) if error.current_context().is_db_not_found() => None,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the connector account with the label"))?,
+    };
+
+    let Some(merchant_connector_account) = existing_merchant_connector_account else {
+        if dry_run {
+            return Ok((MerchantConnectorTemplateImportStatus::Created, None));
+        }
+        let response = admin::create_payment_connector(
+            state.clone(),
+            get_create_request(entry),
+            &merchant_id.to_string(),
+        )
+        .await?;
+        return Ok((
+            MerchantConnectorTemplateImportStatus::Created,
+            get_merchant_connector_id(response),
+        ));
+    };
+
+    let merchant_connector_id = merchant_connector_account.merchant_connector_id.clone();
+    let current_connector_name = merchant_connector_account.connector_name.clone();
+    let current_entry = get_template_entry(merchant_connector_account)?
+        .filter(|current_entry| current_entry.connector_name == entry.connector_name)
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::DuplicateConnectorLabel {
+                connector_label: entry.connector_label.clone(),
+            })
+            .attach_printable(format!(
+                "The label is used by a connector account of {current_connector_name}"
+            ))
+        })?;
+
+    let update_request = get_update_request(entry);
+    if serde_json::to_value(get_update_request(current_entry)).ok()
+        == serde_json::to_value(&update_request).ok()
+    {
+        return Ok((
+            MerchantConnectorTemplateImportStatus::Unchanged,
+            Some(merchant_connector_id),
+        ));
+    }
+    if !dry_run {
+        admin::update_payment_connector(
+            state.clone(),
+            merchant_id,
+            &merchant_connector_id,
+            update_request,
+        )
+        .await?;
+    }
+    Ok((
+        MerchantConnectorTemplateImportStatus::Updated,
+        Some(merchant_connector_id),
+    ))
+}
+
+/// Validates the entry against the connector, so that a dry run reports the entries which would
+/// be rejected
+fn validate_template_entry(
+    state: &AppState,
+    entry: &MerchantConnectorTemplateEntry,
+) -> RouterResult<()> {
+    admin::validate_connector_label(&entry.connector_label)?;
+
+    let auth: types::ConnectorAuthType = entry
+        .connector_account_details
+        .peek()
+        .clone()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "connector_account_details".to_string(),
+            expected_format: "auth_type and api_key".to_string(),
+        })?;
+    admin::validate_auth_type(entry.connector_name, &auth).change_context(
+        errors::ApiErrorResponse::InvalidRequestData {
+            message: "The auth type is invalid for the connector".to_string(),
+        },
+    )?;
+
+    entry
+        .payment_methods_enabled
+        .as_deref()
+        .map(|payment_methods_enabled| {
+            admin::validate_payment_methods_against_connector_capabilities(
+                entry.connector_name,
+                payment_methods_enabled,
+                state
+                    .conf
+                    .connector_capabilities
+                    .unsupported_configuration_behaviour,
+            )
+        })
+        .transpose()?;
+    Ok(())
+}
+
+/// The entry of the connector account, with its credentials, `None` for the connector accounts
+/// of the connectors which are not payment connectors
+fn get_template_entry(
+    merchant_connector_account: domain::MerchantConnectorAccount,
+) -> RouterResult<Option<MerchantConnectorTemplateEntry>> {
+    let merchant_connector_account =
+        MerchantConnectorResponse::try_from(merchant_connector_account)?;
+    let Ok(connector_name) =
+        api_enums::Connector::from_str(&merchant_connector_account.connector_name)
+    else {
+        logger::info!(
+            "The connector account {} of {} is not exported",
+            merchant_connector_account.merchant_connector_id,
+            merchant_connector_account.connector_name
+        );
+        return Ok(None);
+    };
+
+    Ok(Some(MerchantConnectorTemplateEntry {
+        connector_type: merchant_connector_account.connector_type,
+        connector_name,
+        connector_label: merchant_connector_account
+            .connector_label
+            .unwrap_or(merchant_connector_account.connector_name),
+        connector_account_details: merchant_connector_account.connector_account_details,
+        test_mode: merchant_connector_account.test_mode,
+        disabled: merchant_connector_account.disabled,
+        payment_methods_enabled: merchant_connector_account.payment_methods_enabled,
+        metadata: merchant_connector_account.metadata,
+        frm_configs: merchant_connector_account.frm_configs,
+        business_country: merchant_connector_account.business_country,
+        business_label: merchant_connector_account.business_label,
+        business_sub_label: merchant_connector_account.business_sub_label,
+        disabled_flows: merchant_connector_account.disabled_flows,
+    }))
+}
+
+fn get_create_request(entry: MerchantConnectorTemplateEntry) -> MerchantConnectorCreate {
+    MerchantConnectorCreate {
+        connector_type: entry.connector_type,
+        connector_name: entry.connector_name,
+        connector_label: Some(entry.connector_label),
+        merchant_connector_id: None,
+        connector_account_details: Some(entry.connector_account_details),
+        test_mode: entry.test_mode,
+        disabled: entry.disabled,
+        payment_methods_enabled: entry.payment_methods_enabled,
+        metadata: entry.metadata,
+        frm_configs: entry.frm_configs,
+        business_country: entry.business_country,
+        business_label: entry.business_label,
+        business_sub_label: entry.business_sub_label,
+        connector_webhook_details: None,
+        profile_id: None,
+        disabled_flows: entry.disabled_flows,
+        register_webhook_endpoint: None,
+    }
+}
+
+/// The update of an existing connector account to the entry. The business details and the test
+/// mode of a connector account cannot be updated, and are kept.
+fn get_update_request(entry: MerchantConnectorTemplateEntry) -> MerchantConnectorUpdate {
+    MerchantConnectorUpdate {
+        connector_type: entry.connector_type,
+        connector_account_details: Some(entry.connector_account_details),
+        connector_account_details_new: None,
+        test_mode: None,
+        disabled: entry.disabled,
+        payment_methods_enabled: entry.payment_methods_enabled,
+        metadata: entry.metadata,
+        frm_configs: entry.frm_configs,
+        connector_webhook_details: None,
+        disabled_flows: entry.disabled_flows,
+        register_webhook_endpoint: None,
+    }
+}
+
+fn get_merchant_connector_id(
+    response: ApplicationResponse<MerchantConnectorResponse>,
+) -> Option<String> {
+    match response {
+        ApplicationResponse::Json(response) => Some(response.merchant_connector_id),
+        _ => None,
+    }
+}
+
+fn get_error_message(error: &errors::ApiErrorResponse) -> String {
+    let mut error: api_models::errors::types::ApiErrorResponse = error.switch();
+    error.get_internal_error_mut().error_message.clone()
+}
+
+/// Replaces every value other than the auth type with a placeholder named after the path of the
+/// value
+fn get_credential_placeholders(variable: &str, value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(members) => serde_json::Value::Object(
+            members
+                .iter()
+                .map(|(name, member)| {
+                    let member = if name == AUTH_TYPE_FIELD {
+                        member.clone()
+                    } else {
+                        get_credential_placeholders(&format!("{variable}_{name}"), member)
+                    };
+                    (name.clone(), member)
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(elements) => serde_json::Value::Array(
+            elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    get_credential_placeholders(&format!("{variable}_{index}"), element)
+                })
+                .collect(),
+        ),
+        _ => serde_json::Value::String(format!("{{{{{variable}}}}}")),
+    }
+}
+
+/// Substitutes the placeholders in the strings of the value with the values of their variables,
+/// fails with the name of the first variable which has no value
+fn substitute_variables(
+    value: serde_json::Value,
+    variables: &HashMap<String, Secret<String>>,
+) -> Result<serde_json::Value, String> {
+    match value {
+        serde_json::Value::String(value) => {
+            substitute_placeholders(&value, variables).map(serde_json::Value::String)
+        }
+        serde_json::Value::Object(members) => members
+            .into_iter()
+            .map(|(name, member)| Ok((name, substitute_variables(member, variables)?)))
+            .collect::<Result<_, _>>()
+            .map(serde_json::Value::Object),
+        serde_json::Value::Array(elements) => elements
+            .into_iter()
+            .map(|element| substitute_variables(element, variables))
+            .collect::<Result<_, _>>()
+            .map(serde_json::Value::Array),
+        value => Ok(value),
+    }
+}
+
+fn substitute_placeholders(
+    value: &str,
+    variables: &HashMap<String, Secret<String>>,
+) -> Result<String, String> {
+    let mut substituted = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some((before, after)) = remaining.split_once("{{") {
+        let Some((variable, after)) = after.split_once("}}") else {
+            break;
+        };
+        let variable = variable.trim();
+        let variable_value = variables
+            .get(variable)
+            .ok_or_else(|| variable.to_string())?;
+        substituted.push_str(before);
+        substituted.push_str(variable_value.peek());
+        remaining = after;
+    }
+    substituted.push_str(remaining);
+    Ok(substituted)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{db::StorageInterface, services, types::storage::enums};
+
+    async fn get_state() -> AppState {
+        AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await
+    }
+
+    /// Creates the merchant along with its default business profile
+    async fn create_merchant(
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+    ) -> domain::MerchantKeyStore {
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: merchant_id.to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+
+        let merchant_account = domain::MerchantAccount {
+            id: None,
+            merchant_id: merchant_id.to_string(),
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::Value::Null,
+            frm_routing_algorithm: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            intent_fulfillment_time: None,
+            payout_routing_algorithm: None,
+            organization_id: None,
+            is_recon_enabled: false,
+            default_profile: None,
+            recon_status: enums::ReconStatus::NotRequested,
+            webhook_auth_details: None,
+            default_currency: None,
+            default_country: None,
+            pii_policy: None,
+        };
+        let business_profile = admin::create_and_insert_business_profile(
+            db,
+            api_models::admin::BusinessProfileCreate {
+                profile_name: Some("default".to_string()),
+                ..Default::default()
+            },
+            merchant_account.clone(),
+        )
+        .await
+        .unwrap();
+        db.insert_merchant(
+            domain::MerchantAccount {
+                default_profile: Some(business_profile.profile_id),
+                ..merchant_account
+            },
+            &key_store,
+        )
+        .await
+        .unwrap();
+
+        key_store
+    }
+
+    async fn get_template(state: &AppState, merchant_id: &str) -> MerchantConnectorTemplate {
+        match export_connector_template(state.clone(), merchant_id.to_string())
+            .await
+            .unwrap()
+        {
+            ApplicationResponse::Json(template) => template,
+            _ => panic!("Unexpected response of the template export"),
+        }
+    }
+
+    async fn import_template(
+        state: &AppState,
+        merchant_id: &str,
+        template: MerchantConnectorTemplate,
+        variables: &[(&str, &str)],
+        dry_run: bool,
+    ) -> Vec<(
+        String,
+        MerchantConnectorTemplateImportStatus,
+        Option<String>,
+    )> {
+        let request = MerchantConnectorTemplateImportRequest {
+            template,
+            variables: variables
+                .iter()
+                .map(|(variable, value)| (variable.to_string(), Secret::new(value.to_string())))
+                .collect(),
+            dry_run,
+        };
+        match import_connector_template(state.clone(), merchant_id.to_string(), request)
+            .await
+            .unwrap()
+        {
+            ApplicationResponse::Json(response) => response
+                .results
+                .into_iter()
+                .map(|result| (result.connector_label, result.status, result.error_message))
+                .collect(),
+            _ => panic!("Unexpected response of the template import"),
+        }
+    }
+
+    /// A template exported from a merchant with a Stripe and an Adyen connector account
+    async fn get_source_template(state: &AppState) -> MerchantConnectorTemplate {
+        let db = &*state.store;
+        let key_store = create_merchant(db, "merchant_source").await;
+        for (connector_name, connector_label, connector_account_details) in [
+            (
+                "stripe",
+                "stripe_travel",
+                serde_json::json!({ "auth_type": "HeaderKey", "api_key": "sk_test_source" }),
+            ),
+            (
+                "adyen",
+                "adyen_default",
+                serde_json::json!({
+                    "auth_type": "BodyKey",
+                    "api_key": "adyen_key_source",
+                    "key1": "SourceMerchantAccount",
+                }),
+            ),
+        ] {
+            db.insert_merchant_connector_account(
+                domain::MerchantConnectorAccount {
+                    id: None,
+                    merchant_id: "merchant_source".to_string(),
+                    connector_name: connector_name.to_string(),
+                    connector_account_details: domain::types::encrypt(
+                        Secret::new(connector_account_details),
+                        key_store.key.get_inner().peek(),
+                    )
+                    .await
+                    .unwrap(),
+                    test_mode: Some(true),
+                    disabled: Some(false),
+                    merchant_connector_id: format!("mca_{connector_name}"),
+                    payment_methods_enabled: None,
+                    connector_type: enums::ConnectorType::PaymentProcessor,
+                    metadata: None,
+                    frm_configs: None,
+                    connector_label: Some(connector_label.to_string()),
+                    business_country: None,
+                    business_label: None,
+                    business_sub_label: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    connector_webhook_details: None,
+                    profile_id: Some("pro_source".to_string()),
+                    applepay_verified_domains: None,
+                    disabled_flows: Some(vec![api_enums::ConnectorFlow::Refund]),
+                    previous_credentials: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+        }
+
+        get_template(state, "merchant_source").await
+    }
+
+    const TARGET_VARIABLES: [(&str, &str); 3] = [
+        ("stripe_travel_api_key", "sk_test_target"),
+        ("adyen_default_api_key", "adyen_key_target"),
+        ("adyen_default_key1", "TargetMerchantAccount"),
+    ];
+
+    #[tokio::test]
+    async fn test_export_replaces_the_credentials_with_placeholders() {
+        let state = get_state().await;
+        let template = get_source_template(&state).await;
+
+        let connector_account_details = template
+            .connectors
+            .iter()
+            .map(|entry| entry.connector_account_details.peek().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            connector_account_details,
+            vec![
+                serde_json::json!({ "auth_type": "HeaderKey", "api_key": "{{stripe_travel_api_key}}" }),
+                serde_json::json!({
+                    "auth_type": "BodyKey",
+                    "api_key": "{{adyen_default_api_key}}",
+                    "key1": "{{adyen_default_key1}}",
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_reproduces_the_exported_connector_accounts() {
+        let state = get_state().await;
+        let template = get_source_template(&state).await;
+        let key_store = create_merchant(&*state.store, "merchant_target").await;
+
+        let results = import_template(
+            &state,
+            "merchant_target",
+            template.clone(),
+            &TARGET_VARIABLES,
+            false,
+        )
+        .await;
+        assert_eq!(
+            results,
+            vec![
+                (
+                    "stripe_travel".to_string(),
+                    MerchantConnectorTemplateImportStatus::Created,
+                    None
+                ),
+                (
+                    "adyen_default".to_string(),
+                    MerchantConnectorTemplateImportStatus::Created,
+                    None
+                ),
+            ]
+        );
+
+        assert_eq!(
+            serde_json::to_value(get_template(&state, "merchant_target").await).unwrap(),
+            serde_json::to_value(&template).unwrap()
+        );
+        let stripe_account = state
+            .store
+            .find_merchant_connector_account_by_merchant_id_connector_label(
+                "merchant_target",
+                "stripe_travel",
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            stripe_account.connector_account_details.get_inner().peek(),
+            &serde_json::json!({ "auth_type": "HeaderKey", "api_key": "sk_test_target" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reimport_is_a_no_op() {
+        let state = get_state().await;
+        let template = get_source_template(&state).await;
+        let key_store = create_merchant(&*state.store, "merchant_target").await;
+        import_template(
+            &state,
+            "merchant_target",
+            template.clone(),
+            &TARGET_VARIABLES,
+            false,
+        )
+        .await;
+
+        let results = import_template(
+            &state,
+            "merchant_target",
+            template,
+            &TARGET_VARIABLES,
+            false,
+        )
+        .await;
+        assert!(results
+            .iter()
+            .all(|(_, status, _)| *status == MerchantConnectorTemplateImportStatus::Unchanged));
+        let merchant_connector_accounts = state
+            .store
+            .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+                "merchant_target",
+                true,
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(merchant_connector_accounts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_missing_variable_fails_only_its_entry() {
+        let state = get_state().await;
+        let template = get_source_template(&state).await;
+        let key_store = create_merchant(&*state.store, "merchant_target").await;
+
+        let results = import_template(
+            &state,
+            "merchant_target",
+            template,
+            &TARGET_VARIABLES[..2],
+            false,
+        )
+        .await;
+        assert_eq!(
+            results,
+            vec![
+                (
+                    "stripe_travel".to_string(),
+                    MerchantConnectorTemplateImportStatus::Created,
+                    None
+                ),
+                (
+                    "adyen_default".to_string(),
+                    MerchantConnectorTemplateImportStatus::Failed,
+                    Some(
+                        "No value is provided for the variable `adyen_default_key1` of the connector account details"
+                            .to_string()
+                    )
+                ),
+            ]
+        );
+        let merchant_connector_accounts = state
+            .store
+            .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+                "merchant_target",
+                true,
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(merchant_connector_accounts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_create_connector_accounts() {
+        let state = get_state().await;
+        let template = get_source_template(&state).await;
+        let key_store = create_merchant(&*state.store, "merchant_target").await;
+
+        let results =
+            import_template(&state, "merchant_target", template, &TARGET_VARIABLES, true).await;
+        assert!(results
+            .iter()
+            .all(|(_, status, _)| *status == MerchantConnectorTemplateImportStatus::Created));
+        let merchant_connector_accounts = state
+            .store
+            .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+                "merchant_target",
+                true,
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert!(merchant_connector_accounts.is_empty());
+    }
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let variables =
+            HashMap::from([("api_key".to_string(), Secret::new("sk_test".to_string()))]);
+
+        assert_eq!(
+            substitute_placeholders("Bearer {{ api_key }}", &variables),
+            Ok("Bearer sk_test".to_string())
+        );
+        assert_eq!(
+            substitute_placeholders("{{unclosed", &variables),
+            Ok("{{unclosed".to_string())
+        );
+        assert_eq!(
+            substitute_placeholders("{{api_secret}}", &variables),
+            Err("api_secret".to_string())
+        );
+    }
+}
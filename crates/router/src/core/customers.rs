@@ -185,7 +185,7 @@ pub async fn delete_customer(
                         &state,
                         &req.customer_id,
                         &merchant_account.merchant_id,
-                        &pm.payment_method_id,
+                        cards::get_locker_id(&pm),
                     )
                     .await
                     .switch()?;
@@ -0,0 +1,526 @@
+//! Retention of the data of old payments. A recurring job of the scheduler redacts the browser info
+//! and the payment method data of the payment attempts older than the PII retention window, and
+//! archives the payments in a terminal status older than the archival window. The archived
+//! payments are kept in their table, so that they are still retrieved, refunded and disputed by
+//! their identifiers. The listing of the payments reads the payments which are not archived, and
+//! falls back to the archived payments when they do not fill the page.
+//!
+//! A run of the job is processed in batches with a pause between them. A task of the run processes
+//! a bounded number of batches and schedules the next task with the progress of the run, the last
+//! task of a run schedules the first task of the next run.
+
+use common_utils::date_time;
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+use scheduler::{db::process_tracker::ProcessTrackerExt, utils as pt_utils};
+use time::PrimitiveDateTime;
+
+use crate::{
+    configs::settings::DataRetentionConfig,
+    core::errors::{self, RouterResult},
+    db::StorageInterface,
+    types::storage::{self, enums},
+};
+
+pub const DATA_RETENTION_WORKFLOW_RUNNER: &str = "DATA_RETENTION_WORKFLOW";
+const DATA_RETENTION_WORKFLOW_TASK: &str = "DATA_RETENTION";
+
+/// The job covers the data of all the merchants
+const DATA_RETENTION_SCOPE: &str = "all_merchants";
+
+/// Statuses in which a payment no longer changes, the payments in these statuses are archived once
+/// they are older than the archival window
+const ARCHIVABLE_PAYMENT_STATUSES: [enums::IntentStatus; 3] = [
+    enums::IntentStatus::Succeeded,
+    enums::IntentStatus::Failed,
+    enums::IntentStatus::Cancelled,
+];
+
+// Tracking data by process_tracker
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DataRetentionTrackingData {
+    /// Scheduled time of the run, from which the cutoffs of the run are computed
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub run_scheduled_at: PrimitiveDateTime,
+    /// The payment attempts created before this time are redacted
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub redact_created_before: PrimitiveDateTime,
+    /// The payments in a terminal status created before this time are archived
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub archive_created_before: PrimitiveDateTime,
+    /// Number of the task within the run
+    pub task_number: u32,
+    /// Payment attempts redacted by the run so far
+    pub redacted_attempts: usize,
+    /// Payments archived by the run so far
+    pub archived_payments: usize,
+}
+
+impl DataRetentionTrackingData {
+    pub fn new(config: &DataRetentionConfig, run_scheduled_at: PrimitiveDateTime) -> Self {
+        Self {
+            run_scheduled_at,
+            redact_created_before: run_scheduled_at
+                .saturating_sub(time::Duration::days(config.pii_retention_days.into())),
+            archive_created_before: run_scheduled_at
+                .saturating_sub(time::Duration::days(config.archival_days.into())),
+            task_number: 0,
+            redacted_attempts: 0,
+            archived_payments: 0,
+        }
+    }
+}
+
+/// The scheduled time of the run of the job covering `current_time`. The runs are aligned to the
+/// run interval, so that a run is scheduled once however many times the producer of the scheduler
+/// starts within the interval.
+pub fn get_run_scheduled_at(
+    config: &DataRetentionConfig,
+    current_time: PrimitiveDateTime,
+) -> PrimitiveDateTime {
+    let run_interval = time::Duration::hours(config.run_interval_in_hours.into()).whole_seconds();
+    let timestamp = current_time.assume_utc().unix_timestamp();
+    time::OffsetDateTime::from_unix_timestamp(timestamp - timestamp.rem_euclid(run_interval))
+        .map(|run_time| PrimitiveDateTime::new(run_time.date(), run_time.time()))
+        .unwrap_or(current_time)
+}
+
+/// Whether a batch of `count` rows filled the batch, in which case rows may remain to be processed
+fn is_batch_full(count: usize, batch_size: i64) -> bool {
+    i64::try_from(count).map_or(true, |count| count >= batch_size)
+}
+
+/// Processes a batch of the run, the attempts are redacted before the payments are archived.
+/// Returns whether the run is complete.
+async fn process_data_retention_batch(
+    db: &dyn StorageInterface,
+    batch_size: i64,
+    tracking_data: &mut DataRetentionTrackingData,
+) -> RouterResult<bool> {
+    let redacted_attempts = db
+        .redact_payment_attempts_created_before(tracking_data.redact_created_before, batch_size)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to redact the payment attempts")?;
+    tracking_data.redacted_attempts = tracking_data
+        .redacted_attempts
+        .saturating_add(redacted_attempts);
+    if is_batch_full(redacted_attempts, batch_size) {
+        return Ok(false);
+    }
+
+    let archived_payments = db
+        .archive_payment_intents_created_before(
+            tracking_data.archive_created_before,
+            ARCHIVABLE_PAYMENT_STATUSES.to_vec(),
+            batch_size,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to archive the payments")?;
+    tracking_data.archived_payments = tracking_data
+        .archived_payments
+        .saturating_add(archived_payments);
+    Ok(!is_batch_full(archived_payments, batch_size))
+}
+
+/// Processes the batches of a task of the run, and records the progress of the run in the tracking
+/// data. Returns whether the run is complete.
+#[instrument(skip_all)]
+pub async fn execute_data_retention_task(
+    db: &dyn StorageInterface,
+    config: &DataRetentionConfig,
+    tracking_data: &mut DataRetentionTrackingData,
+) -> RouterResult<bool> {
+    let batch_size = i64::from(config.batch_size);
+    let mut is_run_complete = false;
+
+    for batch in 0..config.max_batches_per_task {
+        if batch > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                config.batch_interval_in_millis,
+            ))
+            .await;
+        }
+
+        is_run_complete = process_data_retention_batch(db, batch_size, tracking_data).await?;
+        if is_run_complete {
+            break;
+        }
+    }
+
+    logger::info!(
+        task_number = tracking_data.task_number,
+        redacted_attempts = tracking_data.redacted_attempts,
+        archived_payments = tracking_data.archived_payments,
+        is_run_complete,
+        "Processed the task of the data retention run"
+    );
+    Ok(is_run_complete)
+}
+
+async fn add_data_retention_task(
+    db: &dyn StorageInterface,
+    tracking_data: DataRetentionTrackingData,
+    schedule_time: PrimitiveDateTime,
+) -> RouterResult<()> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        DATA_RETENTION_WORKFLOW_RUNNER,
+        DATA_RETENTION_WORKFLOW_TASK,
+        &format!(
+            "{}_{}",
+            tracking_data.run_scheduled_at.assume_utc().unix_timestamp(),
+            tracking_data.task_number
+        ),
+        DATA_RETENTION_SCOPE,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        DATA_RETENTION_WORKFLOW_TASK,
+        DATA_RETENTION_WORKFLOW_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct data retention process tracker task")?;
+
+    match db.insert_process(process_tracker_entry).await {
+        Ok(_) => Ok(()),
+        // The task has already been scheduled, by another start of the producer
+        Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert data retention process tracker task"),
+    }
+}
+
+/// Schedules the next task of the run when the run is not complete, or the first task of the next
+/// run
+pub async fn add_next_data_retention_task(
+    db: &dyn StorageInterface,
+    config: &DataRetentionConfig,
+    tracking_data: DataRetentionTrackingData,
+    is_run_complete: bool,
+) -> RouterResult<()> {
+    if is_run_complete {
+        let next_run_scheduled_at = tracking_data
+            .run_scheduled_at
+            .saturating_add(time::Duration::hours(config.run_interval_in_hours.into()));
+        add_data_retention_task(
+            db,
+            DataRetentionTrackingData::new(config, next_run_scheduled_at),
+            next_run_scheduled_at,
+        )
+        .await
+    } else {
+        let next_tracking_data = DataRetentionTrackingData {
+            task_number: tracking_data.task_number.saturating_add(1),
+            ..tracking_data
+        };
+        add_data_retention_task(db, next_tracking_data, date_time::now()).await
+    }
+}
+
+/// Schedules the run of the job covering the current time, when the job is enabled
+pub async fn schedule_data_retention(
+    db: &dyn StorageInterface,
+    config: &DataRetentionConfig,
+) -> RouterResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let run_scheduled_at = get_run_scheduled_at(config, date_time::now());
+    add_data_retention_task(
+        db,
+        DataRetentionTrackingData::new(config, run_scheduled_at),
+        run_scheduled_at,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::types::storage::enums::MerchantStorageScheme;
+
+    fn get_config() -> DataRetentionConfig {
+        DataRetentionConfig {
+            enabled: true,
+            pii_retention_days: 90,
+            archival_days: 365,
+            batch_size: 2,
+            batch_interval_in_millis: 0,
+            max_batches_per_task: 10,
+            run_interval_in_hours: 24,
+        }
+    }
+
+    async fn insert_payment(
+        db: &dyn StorageInterface,
+        payment_id: &str,
+        status: enums::IntentStatus,
+        created_at: PrimitiveDateTime,
+    ) {
+        db.insert_payment_intent(
+            storage::PaymentIntentNew {
+                payment_id: payment_id.to_string(),
+                merchant_id: "merchant_1".to_string(),
+                status,
+                amount: 1000,
+                currency: Some(enums::Currency::USD),
+                active_attempt_id: format!("{payment_id}_1"),
+                created_at: Some(created_at),
+                modified_at: Some(created_at),
+                ..Default::default()
+            },
+            MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap();
+        db.insert_payment_attempt(
+            storage::PaymentAttemptNew {
+                payment_id: payment_id.to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: format!("{payment_id}_1"),
+                status: match status {
+                    enums::IntentStatus::Succeeded => enums::AttemptStatus::Charged,
+                    enums::IntentStatus::Failed => enums::AttemptStatus::Failure,
+                    _ => enums::AttemptStatus::Pending,
+                },
+                amount: 1000,
+                browser_info: Some(serde_json::json!({ "ip_address": "127.0.0.1" })),
+                payment_method_data: Some(serde_json::json!({ "card": { "last4": "4242" } })),
+                created_at: Some(created_at),
+                modified_at: Some(created_at),
+                ..Default::default()
+            },
+            MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn get_payment(
+        db: &dyn StorageInterface,
+        payment_id: &str,
+    ) -> (storage::PaymentIntent, storage::PaymentAttempt) {
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id,
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        let payment_attempt = db
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &payment_intent.active_attempt_id,
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        (payment_intent, payment_attempt)
+    }
+
+    #[tokio::test]
+    async fn test_aged_payments_are_redacted_and_archived() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let config = get_config();
+        let now = date_time::now();
+        let days_ago = |days| now.saturating_sub(time::Duration::days(days));
+
+        insert_payment(
+            db,
+            "pay_old_1",
+            enums::IntentStatus::Succeeded,
+            days_ago(400),
+        )
+        .await;
+        insert_payment(db, "pay_old_2", enums::IntentStatus::Failed, days_ago(380)).await;
+        insert_payment(
+            db,
+            "pay_old_3",
+            enums::IntentStatus::Processing,
+            days_ago(370),
+        )
+        .await;
+        insert_payment(
+            db,
+            "pay_aged",
+            enums::IntentStatus::Succeeded,
+            days_ago(100),
+        )
+        .await;
+        insert_payment(
+            db,
+            "pay_recent",
+            enums::IntentStatus::Succeeded,
+            days_ago(1),
+        )
+        .await;
+
+        let mut tracking_data = DataRetentionTrackingData::new(&config, now);
+        assert!(execute_data_retention_task(db, &config, &mut tracking_data)
+            .await
+            .unwrap());
+        assert_eq!(tracking_data.redacted_attempts, 4);
+        assert_eq!(tracking_data.archived_payments, 2);
+
+        // The archived payments are still retrieved by their identifiers, with their attempts
+        for payment_id in ["pay_old_1", "pay_old_2"] {
+            let (payment_intent, payment_attempt) = get_payment(db, payment_id).await;
+            assert!(payment_intent.archived_at.is_some());
+            assert!(payment_attempt.redacted_at.is_some());
+            assert_eq!(payment_attempt.browser_info, None);
+            assert_eq!(payment_attempt.payment_method_data, None);
+        }
+        let refunded_attempt = db
+            .find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
+                "pay_old_1",
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(refunded_attempt.attempt_id, "pay_old_1_1");
+
+        // A payment which can still change is redacted but not archived
+        let (payment_intent, payment_attempt) = get_payment(db, "pay_old_3").await;
+        assert_eq!(payment_intent.archived_at, None);
+        assert!(payment_attempt.redacted_at.is_some());
+
+        let (payment_intent, payment_attempt) = get_payment(db, "pay_aged").await;
+        assert_eq!(payment_intent.archived_at, None);
+        assert_eq!(payment_attempt.browser_info, None);
+
+        let (payment_intent, payment_attempt) = get_payment(db, "pay_recent").await;
+        assert_eq!(payment_intent.archived_at, None);
+        assert_eq!(payment_attempt.redacted_at, None);
+        assert!(payment_attempt.browser_info.is_some());
+        assert!(payment_attempt.payment_method_data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_run_is_processed_in_bounded_tasks() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let config = DataRetentionConfig {
+            batch_size: 1,
+            max_batches_per_task: 2,
+            ..get_config()
+        };
+        let now = date_time::now();
+        for payment_id in ["pay_1", "pay_2", "pay_3"] {
+            insert_payment(
+                db,
+                payment_id,
+                enums::IntentStatus::Processing,
+                now.saturating_sub(time::Duration::days(100)),
+            )
+            .await;
+        }
+
+        let mut tracking_data = DataRetentionTrackingData::new(&config, now);
+        assert!(
+            !execute_data_retention_task(db, &config, &mut tracking_data)
+                .await
+                .unwrap()
+        );
+        assert_eq!(tracking_data.redacted_attempts, 2);
+
+        assert!(execute_data_retention_task(db, &config, &mut tracking_data)
+            .await
+            .unwrap());
+        assert_eq!(tracking_data.redacted_attempts, 3);
+        assert_eq!(tracking_data.archived_payments, 0);
+    }
+
+    #[cfg(feature = "olap")]
+    #[tokio::test]
+    async fn test_listing_falls_back_to_archived_payments() {
+        use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
+
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let config = get_config();
+        let now = date_time::now();
+        insert_payment(
+            db,
+            "pay_old",
+            enums::IntentStatus::Succeeded,
+            now.saturating_sub(time::Duration::days(400)),
+        )
+        .await;
+        insert_payment(db, "pay_recent", enums::IntentStatus::Succeeded, now).await;
+        let mut tracking_data = DataRetentionTrackingData::new(&config, now);
+        execute_data_retention_task(db, &config, &mut tracking_data)
+            .await
+            .unwrap();
+
+        let constraints: PaymentIntentFetchConstraints =
+            serde_json::from_value::<api_models::payments::PaymentListConstraints>(
+                serde_json::json!({ "limit": 10 }),
+            )
+            .unwrap()
+            .into();
+        let payment_intents = db
+            .filter_payment_intent_by_constraints(
+                "merchant_1",
+                &constraints,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_intents.len(), 1);
+        assert_eq!(payment_intents[0].payment_id, "pay_recent");
+
+        assert!(constraints.should_fall_back_to_archived(payment_intents.len()));
+        let payment_intents = db
+            .filter_payment_intent_by_constraints(
+                "merchant_1",
+                &constraints.with_archived(),
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            payment_intents
+                .iter()
+                .map(|payment_intent| payment_intent.payment_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pay_recent", "pay_old"]
+        );
+    }
+
+    #[test]
+    fn test_runs_are_aligned_to_the_run_interval() {
+        let config = get_config();
+        let run_scheduled_at =
+            get_run_scheduled_at(&config, time::macros::datetime!(2023-11-07 13:45:10));
+        assert_eq!(run_scheduled_at, time::macros::datetime!(2023-11-07 00:00));
+
+        let tracking_data = DataRetentionTrackingData::new(&config, run_scheduled_at);
+        assert_eq!(
+            tracking_data.redact_created_before,
+            time::macros::datetime!(2023-08-09 00:00)
+        );
+        assert_eq!(
+            tracking_data.archive_created_before,
+            time::macros::datetime!(2022-11-07 00:00)
+        );
+    }
+}
@@ -2,7 +2,9 @@ pub mod api_error_response;
 pub mod customers_error_response;
 pub mod error_handlers;
 pub mod transformers;
+pub mod user_messages;
 pub mod utils;
+pub mod validation;
 
 use std::fmt::Display;
 
@@ -22,7 +24,7 @@ pub use self::{
     storage_impl_errors::*,
     utils::{ConnectorErrorExt, StorageErrorExt},
 };
-use crate::services;
+use crate::{consts, services};
 pub type RouterResult<T> = CustomResult<T, ApiErrorResponse>;
 pub type RouterResponse<T> = CustomResult<services::ApplicationResponse<T>, ApiErrorResponse>;
 
@@ -84,6 +86,11 @@ pub enum ConnectorError {
     ParsingFailed,
     #[error("Failed to deserialize connector response")]
     ResponseDeserializationFailed,
+    /// Only the status fields of the connector response could be deserialized. The failure is
+    /// recorded as a warning and the response is handled from its status fields, it does not fail
+    /// the payment attempt.
+    #[error("Failed to deserialize the enrichment fields of the connector response")]
+    ResponsePartialDeserializationFailure,
     #[error("Failed to execute a processing step: {0:?}")]
     ProcessingStepFailed(Option<bytes::Bytes>),
     #[error("The connector returned an unexpected response: {0:?}")]
@@ -108,8 +115,23 @@ pub enum ConnectorError {
     FailedToObtainCertificate,
     #[error("Connector meta data not found")]
     NoConnectorMetaData,
+    #[error("Invalid connector meta data: {message}")]
+    InvalidConnectorMetaData { message: String },
     #[error("Failed to obtain certificate key")]
     FailedToObtainCertificateKey,
+    #[error("Failed to parse the private key")]
+    InvalidPrivateKey,
+    #[error("Failed to decrypt the private key with the provided passphrase")]
+    InvalidPrivateKeyPassphrase,
+    #[error("Failed to parse the public key")]
+    InvalidPublicKey,
+    #[error("Failed to sign the connector request")]
+    RequestSigningFailed,
+    #[error("Invalid value for {field}: the value {constraint}")]
+    RequestValidationFailed {
+        field: &'static str,
+        constraint: String,
+    },
     #[error("This step has not been implemented for: {0}")]
     NotImplemented(String),
     #[error("{message} is not supported by {connector}")]
@@ -171,11 +193,26 @@ pub enum ConnectorError {
     InSufficientBalanceInPaymentMethod,
     #[error("Server responded with Request Timeout")]
     RequestTimeoutReceived,
+    #[error("Failed to resolve the address of the connector")]
+    DnsResolutionFailed,
+    #[error("Connection refused by the connector")]
+    ConnectionRefused,
+    #[error("TLS handshake with the connector failed")]
+    TlsHandshakeFailed,
+    #[error("Request to the connector timed out at the {stage:?} stage")]
+    RequestTimedOut { stage: TimeoutStage },
+    #[error("Connector responded with HTTP status {status}")]
+    ConnectorHttpError { status: u16, body_excerpt: String },
     #[error("The given currency method is not configured with the given connector")]
     CurrencyNotSupported {
         message: String,
         connector: &'static str,
     },
+    #[error("Unsupported bank {bank} for payment method type {payment_method_type}")]
+    UnsupportedBank {
+        bank: api_models::enums::BankNames,
+        payment_method_type: api_models::enums::PaymentMethodType,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -256,10 +293,28 @@ pub enum WebhooksFlowError {
     DisputeWebhookValidationFailed,
     #[error("Outgoing webhook body encoding failed")]
     OutgoingWebhookEncodingFailed,
+    #[error("Merchant webhook authentication details could not be parsed")]
+    WebhookAuthDetailsParsingFailed,
+    #[error("Webhook delivery could not be recorded")]
+    WebhookDeliveryRecordingFailed,
     #[error("Missing required field: {field_name}")]
     MissingRequiredField { field_name: &'static str },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ForexError {
+    #[error("Failed to fetch the exchange rates from the provider")]
+    ProviderCallFailed,
+    #[error("Failed to parse the exchange rates of the provider")]
+    ResponseDeserializationFailed,
+    #[error("No exchange rates recent enough to be used are available")]
+    RatesUnavailable,
+    #[error("No exchange rate is available for {0}")]
+    CurrencyNotAvailable(api_models::enums::Currency),
+    #[error("Failed to convert the amount")]
+    ConversionFailed,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ApplePayDecryptionError {
     #[error("Failed to base64 decode input data")]
@@ -276,9 +331,78 @@ pub enum ApplePayDecryptionError {
     DerivingSharedSecretKeyFailed,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum GooglePayDecryptionError {
+    #[error("Failed to parse the Google Pay token")]
+    InvalidToken,
+    #[error("Protocol version of the Google Pay token is not supported")]
+    UnsupportedProtocolVersion,
+    #[error("Failed to base64 decode input data")]
+    Base64DecodingFailed,
+    #[error("Key Deserialization failure")]
+    KeyDeserializationFailed,
+    #[error("Failed to verify the signature of the Google Pay token")]
+    SignatureVerificationFailed,
+    #[error("The Google Pay token has expired")]
+    TokenExpired,
+    #[error("Failed to Derive a shared secret key")]
+    DerivingSharedSecretKeyFailed,
+    #[error("Failed to decrypt input data")]
+    DecryptionFailed,
+}
+
 impl ConnectorError {
     pub fn is_connector_timeout(&self) -> bool {
-        self == &Self::RequestTimeoutReceived
+        matches!(
+            self,
+            Self::RequestTimeoutReceived | Self::RequestTimedOut { .. }
+        )
+    }
+
+    /// Maps the failures in reaching the connector to the error of the connector call, `None` is
+    /// returned for errors which are not caused by the transport
+    pub fn from_transport_error(error: &ApiClientError) -> Option<Self> {
+        match error {
+            ApiClientError::DnsResolutionFailed(_) => Some(Self::DnsResolutionFailed),
+            ApiClientError::ConnectionRefused(_) => Some(Self::ConnectionRefused),
+            ApiClientError::TlsHandshakeFailed(_) => Some(Self::TlsHandshakeFailed),
+            ApiClientError::RequestTimedOut { stage } => {
+                Some(Self::RequestTimedOut { stage: *stage })
+            }
+            ApiClientError::RequestTimeoutReceived => Some(Self::RequestTimedOut {
+                stage: TimeoutStage::Read,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Errors for which the request did not reach the connector, or was rejected by the connector
+    /// without being processed, so that it can be retried without being processed twice
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::DnsResolutionFailed | Self::ConnectionRefused | Self::TlsHandshakeFailed => true,
+            Self::RequestTimedOut { stage } => *stage == TimeoutStage::Connect,
+            Self::ConnectorHttpError { status, .. } => matches!(status, 429 | 502 | 503),
+            _ => false,
+        }
+    }
+
+    /// Error code stored in the payment attempt when the connector call fails with this error
+    pub fn get_error_code(&self) -> Option<&'static str> {
+        match self {
+            Self::DnsResolutionFailed => Some(consts::DNS_RESOLUTION_FAILED_ERROR_CODE),
+            Self::ConnectionRefused => Some(consts::CONNECTION_REFUSED_ERROR_CODE),
+            Self::TlsHandshakeFailed => Some(consts::TLS_HANDSHAKE_FAILED_ERROR_CODE),
+            Self::RequestTimedOut {
+                stage: TimeoutStage::Connect,
+            } => Some(consts::CONNECT_TIMEOUT_ERROR_CODE),
+            Self::RequestTimedOut {
+                stage: TimeoutStage::Read,
+            }
+            | Self::RequestTimeoutReceived => Some(consts::REQUEST_TIMEOUT_ERROR_CODE),
+            Self::ConnectorHttpError { .. } => Some(consts::CONNECTOR_HTTP_ERROR_ERROR_CODE),
+            _ => None,
+        }
     }
 }
 
@@ -96,6 +96,80 @@ pub enum ApiErrorResponse {
     FileProviderNotSupported { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_23", message = "{message}")]
     UnprocessableEntity { message: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_24", message = "Access forbidden. The API key used does not have the '{permission}' permission required to access this resource")]
+    MissingApiKeyPermission { permission: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_25", message = "None of the connectors are configured to accept the payment")]
+    NoEligibleConnector {
+        connectors: Vec<api_models::payments::ConnectorEligibility>,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_26", message = "The payment session has expired, the payment can no longer be confirmed")]
+    PaymentSessionExpired { payment_id: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_27", message = "The ephemeral key has expired, create a new ephemeral key for the customer")]
+    EphemeralKeyExpired,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_28", message = "The payment was blocked as its details are present in the blocklist of the merchant")]
+    PaymentBlocked { payment_id: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_29", message = "The refund currency {refund_currency} does not match the currency {payment_currency} of the payment")]
+    RefundCurrencyMismatch {
+        refund_currency: String,
+        payment_currency: String,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_30", message = "The authorization of the payment has expired and it can no longer be captured")]
+    PaymentAuthorizationExpired { payment_id: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_31", message = "Too many requests, retry after {retry_after_secs} seconds")]
+    TooManyRequests { retry_after_secs: u64 },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_32", message = "The {wallet} configuration of the merchant connector account {merchant_connector_account} is missing or has an invalid `{key}`")]
+    InvalidWalletConfiguration {
+        wallet: String,
+        key: String,
+        merchant_connector_account: String,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_33", message = "The mandate {mandate_id} cannot be charged: {reason}")]
+    MandateChargeLimitExceeded {
+        mandate_id: String,
+        reason: String,
+        next_charge_allowed_at: Option<time::PrimitiveDateTime>,
+    },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_34", message = "The payment {payment_id} is being confirmed by another request")]
+    PaymentConfirmInProgress {
+        payment_id: String,
+        status: api_models::enums::IntentStatus,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_35", message = "The {flow} flow is disabled for the {connector} merchant connector account {merchant_connector_id}")]
+    FlowDisabledForConnector {
+        flow: api_models::enums::ConnectorFlow,
+        connector: String,
+        merchant_connector_id: String,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_36", message = "The payment method is not available for the amount of the payment: {reason}")]
+    PaymentMethodNotAvailableForAmount { reason: String },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_37", message = "The payment {payment_id} has already been created for the merchant order reference {merchant_order_reference_id}")]
+    DuplicateOrderReference {
+        merchant_order_reference_id: String,
+        payment_id: String,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_38", message = "The {payment_mode} payment cannot be processed by the merchant connector account {merchant_connector_id} in {connector_mode} mode")]
+    ConnectorAccountModeMismatch {
+        payment_mode: &'static str,
+        connector_mode: &'static str,
+        merchant_connector_id: String,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_39", message = "The wallet token has expired, refresh the wallet session and retry the payment")]
+    WalletTokenExpired,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_40", message = "The request contains unknown fields", ignore = "fields")]
+    UnknownFields { fields: Vec<String> },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_41", message = "The amount is outside the limits of its currency: {reason}")]
+    AmountOutsideLimits { reason: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_42", message = "The request failed validation", ignore = "errors")]
+    ValidationFailed {
+        errors: Vec<super::validation::FieldValidationError>,
+    },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_43", message = "The idempotency key {idempotency_key} has already been used with a different request")]
+    IdempotencyKeyMismatch { idempotency_key: String },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_44", message = "A request with the idempotency key {idempotency_key} is in progress, retry after {retry_after_secs} seconds")]
+    IdempotencyKeyInUse {
+        idempotency_key: String,
+        retry_after_secs: u64,
+    },
     #[error(error_type = ErrorType::ConnectorError, code = "CE_00", message = "{code}: {message}", ignore = "status_code")]
     ExternalConnectorError {
         code: String,
@@ -127,6 +201,8 @@ pub enum ApiErrorResponse {
     InternalServerError,
     #[error(error_type = ErrorType::LockTimeout, code = "HE_00", message = "Resource is busy. Please try again later.")]
     ResourceBusy,
+    #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_06", message = "Failed to retrieve the saved card from the card vault")]
+    CardVaultRetrievalFailed,
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "Duplicate refund request. Refund already attempted with the refund ID")]
     DuplicateRefundRequest,
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "Duplicate mandate request. Mandate already attempted with the Mandate ID")]
@@ -138,6 +214,8 @@ pub enum ApiErrorResponse {
         profile_id: String,
         connector_name: String,
     },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The merchant connector account with the specified connector_label '{connector_label}' already exists in our records")]
+    DuplicateConnectorLabel { connector_label: String },
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The payment method with the specified details already exists in our records")]
     DuplicatePaymentMethod,
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The payment with the specified payment_id already exists in our records")]
@@ -146,6 +224,8 @@ pub enum ApiErrorResponse {
     DuplicatePayout { payout_id: String },
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The config with the specified key already exists in our records")]
     DuplicateConfig,
+    #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The blocklist entry with the specified data already exists in our records")]
+    DuplicateBlocklistEntry,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Refund does not exist in our records")]
     RefundNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Customer does not exist in our records")]
@@ -170,6 +250,8 @@ pub enum ApiErrorResponse {
     MandateUpdateFailed,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "API Key does not exist in our records")]
     ApiKeyNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Blocklist entry with the given id '{id}' does not exist in our records")]
+    BlocklistEntryNotFound { id: String },
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payout does not exist in our records")]
     PayoutNotFound,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "Invalid mandate id passed from connector")]
@@ -96,6 +96,70 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
                 AER::BadRequest(ApiError::new("IR", 23, message.to_string(), None))
             },
             Self::UnprocessableEntity {message} => AER::Unprocessable(ApiError::new("IR", 23, message.to_string(), None)),
+            Self::MissingApiKeyPermission { permission } => {
+                AER::ForbiddenCommonResource(ApiError::new("IR", 24, format!("Access forbidden. The API key used does not have the '{permission}' permission required to access this resource"), Some(Extra { data: Some(serde_json::json!({ "required_permission": permission })), ..Default::default() })))
+            },
+            Self::NoEligibleConnector { connectors } => {
+                AER::BadRequest(ApiError::new("IR", 25, "None of the connectors are configured to accept the payment", Some(Extra { data: Some(serde_json::json!({ "connectors": connectors })), ..Default::default() })))
+            },
+            Self::PaymentSessionExpired { payment_id } => {
+                AER::BadRequest(ApiError::new("IR", 26, "The payment session has expired, the payment can no longer be confirmed", Some(Extra { payment_id: Some(payment_id.clone()), ..Default::default() })))
+            },
+            Self::EphemeralKeyExpired => AER::Unauthorized(ApiError::new("IR", 27, "The ephemeral key has expired, create a new ephemeral key for the customer", None)),
+            Self::PaymentBlocked { payment_id } => {
+                AER::BadRequest(ApiError::new("IR", 28, "The payment was blocked as its details are present in the blocklist of the merchant", Some(Extra { payment_id: Some(payment_id.clone()), ..Default::default() })))
+            },
+            Self::RefundCurrencyMismatch { refund_currency, payment_currency } => {
+                AER::BadRequest(ApiError::new("IR", 29, format!("The refund currency {refund_currency} does not match the currency {payment_currency} of the payment"), None))
+            },
+            Self::PaymentAuthorizationExpired { payment_id } => {
+                AER::BadRequest(ApiError::new("IR", 30, "The authorization of the payment has expired and it can no longer be captured", Some(Extra { payment_id: Some(payment_id.clone()), ..Default::default() })))
+            },
+            Self::TooManyRequests { retry_after_secs } => {
+                AER::TooManyRequests(ApiError::new("IR", 31, format!("Too many requests, retry after {retry_after_secs} seconds"), None), *retry_after_secs)
+            },
+            Self::InvalidWalletConfiguration { wallet, key, merchant_connector_account } => {
+                AER::BadRequest(ApiError::new("IR", 32, format!("The {wallet} configuration of the merchant connector account {merchant_connector_account} is missing or has an invalid `{key}`"), None))
+            },
+            Self::MandateChargeLimitExceeded { mandate_id, reason, next_charge_allowed_at } => {
+                let next_charge_allowed_at = next_charge_allowed_at.and_then(|next_charge_allowed_at| {
+                    common_utils::custom_serde::iso8601::serialize(&next_charge_allowed_at, serde_json::value::Serializer).ok()
+                });
+                AER::BadRequest(ApiError::new("IR", 33, format!("The mandate {mandate_id} cannot be charged: {reason}"), Some(Extra { data: Some(serde_json::json!({ "next_charge_allowed_at": next_charge_allowed_at })), ..Default::default() })))
+            },
+            Self::PaymentConfirmInProgress { payment_id, status } => {
+                AER::Conflict(ApiError::new("IR", 34, format!("The payment {payment_id} is being confirmed by another request"), Some(Extra { data: Some(serde_json::json!({ "status": status })), ..Default::default() })))
+            },
+            Self::FlowDisabledForConnector { flow, connector, merchant_connector_id } => {
+                AER::BadRequest(ApiError::new("IR", 35, format!("The {flow} flow is disabled for the {connector} merchant connector account {merchant_connector_id}"), Some(Extra { connector: Some(connector.clone()), ..Default::default() })))
+            },
+            Self::PaymentMethodNotAvailableForAmount { reason } => {
+                AER::BadRequest(ApiError::new("IR", 36, format!("The payment method is not available for the amount of the payment: {reason}"), None))
+            },
+            Self::DuplicateOrderReference { merchant_order_reference_id, payment_id } => {
+                AER::Conflict(ApiError::new("IR", 37, format!("The payment {payment_id} has already been created for the merchant order reference {merchant_order_reference_id}"), Some(Extra { payment_id: Some(payment_id.clone()), ..Default::default() })))
+            },
+            Self::ConnectorAccountModeMismatch { payment_mode, connector_mode, merchant_connector_id } => {
+                AER::BadRequest(ApiError::new("IR", 38, format!("The {payment_mode} payment cannot be processed by the merchant connector account {merchant_connector_id} in {connector_mode} mode"), None))
+            },
+            Self::WalletTokenExpired => {
+                AER::BadRequest(ApiError::new("IR", 39, "The wallet token has expired, refresh the wallet session and retry the payment", None))
+            },
+            Self::UnknownFields { fields } => {
+                AER::BadRequest(ApiError::new("IR", 40, format!("The request contains unknown fields: {}", fields.join(", ")), Some(Extra { data: Some(serde_json::json!({ "unknown_fields": fields })), ..Default::default() })))
+            },
+            Self::AmountOutsideLimits { reason } => {
+                AER::BadRequest(ApiError::new("IR", 41, format!("The amount is outside the limits of its currency: {reason}"), None))
+            },
+            Self::ValidationFailed { errors } => {
+                AER::BadRequest(ApiError::new("IR", 42, "The request failed validation", Some(Extra { data: Some(serde_json::json!(errors)), ..Default::default() })))
+            },
+            Self::IdempotencyKeyMismatch { idempotency_key } => {
+                AER::Conflict(ApiError::new("IR", 43, format!("The idempotency key {idempotency_key} has already been used with a different request"), None))
+            },
+            Self::IdempotencyKeyInUse { idempotency_key, retry_after_secs } => {
+                AER::RequestInProgress(ApiError::new("IR", 44, format!("A request with the idempotency key {idempotency_key} is in progress, retry after {retry_after_secs} seconds"), None), *retry_after_secs)
+            },
             Self::ExternalConnectorError {
                 code,
                 message,
@@ -124,6 +188,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::MandateUpdateFailed | Self::MandateSerializationFailed | Self::MandateDeserializationFailed | Self::InternalServerError => {
                 AER::InternalServerError(ApiError::new("HE", 0, "Something went wrong", None))
             }
+            Self::CardVaultRetrievalFailed => {
+                AER::InternalServerError(ApiError::new("HE", 6, "Failed to retrieve the saved card from the card vault", None))
+            }
             Self::PayoutFailed { data } => {
                 AER::BadRequest(ApiError::new("CE", 4, "Payout failed while processing with connector.", Some(Extra { data: data.clone(), ..Default::default()})))
             },
@@ -133,6 +200,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::DuplicateMerchantConnectorAccount { profile_id, connector_name } => {
                 AER::BadRequest(ApiError::new("HE", 1, format!("The merchant connector account with the specified profile_id '{profile_id}' and connector_name '{connector_name}' already exists in our records"), None))
             }
+            Self::DuplicateConnectorLabel { connector_label } => {
+                AER::BadRequest(ApiError::new("HE", 1, format!("The merchant connector account with the specified connector_label '{connector_label}' already exists in our records"), None))
+            }
             Self::DuplicatePaymentMethod => AER::BadRequest(ApiError::new("HE", 1, "The payment method with the specified details already exists in our records", None)),
             Self::DuplicatePayment { payment_id } => {
                 AER::BadRequest(ApiError::new("HE", 1, "The payment with the specified payment_id already exists in our records", Some(Extra {reason: Some(format!("{payment_id} already exists")), ..Default::default()})))
@@ -155,6 +225,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::DuplicateConfig => {
                 AER::BadRequest(ApiError::new("HE", 1, "The config with the specified key already exists in our records", None))
             }
+            Self::DuplicateBlocklistEntry => {
+                AER::BadRequest(ApiError::new("HE", 1, "The blocklist entry with the specified data already exists in our records", None))
+            }
             Self::PaymentNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "Payment does not exist in our records", None))
             }
@@ -202,6 +275,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::ApiKeyNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "API Key does not exist in our records", None))
             }
+            Self::BlocklistEntryNotFound { id } => {
+                AER::NotFound(ApiError::new("HE", 2, format!("Blocklist entry with the given id {id} does not exist"), None))
+            }
             Self::NotSupported { message } => {
                 AER::BadRequest(ApiError::new("HE", 3, "Payment method type not supported", Some(Extra {reason: Some(message.to_owned()), ..Default::default()})))
             },
@@ -0,0 +1,389 @@
+//! Messages for the shoppers of the merchants, returned as the `user_message` of the API errors.
+//! The errors are classified by unified codes, the message of each code is looked up in the
+//! catalog of the locale of the payment, or of the `Accept-Language` header of the request for the
+//! errors which occur before the locale of the payment is known. The catalogs are embedded from the per-locale files in
+//! the `user_messages` directory, and never contain the technical details of the error, which
+//! remain in the `message` of the error for the merchant.
+
+use std::{collections::HashMap, str::FromStr};
+
+use actix_web::{http::header, HttpRequest};
+use error_stack::Report;
+use once_cell::sync::Lazy;
+use router_env::logger;
+
+use super::ApiErrorResponse;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Message used when neither the locale nor the default locale has a message for the code
+const FALLBACK_USER_MESSAGE: &str =
+    "Something went wrong while processing your payment. Please try again later.";
+
+const CATALOG_FILES: &[(&str, &str)] = &[
+    ("en", include_str!("user_messages/en.json")),
+    ("de", include_str!("user_messages/de.json")),
+    ("fr", include_str!("user_messages/fr.json")),
+];
+
+static CATALOGS: Lazy<HashMap<&'static str, HashMap<UnifiedCode, String>>> = Lazy::new(|| {
+    CATALOG_FILES
+        .iter()
+        .filter_map(|(locale, file)| {
+            serde_json::from_str::<HashMap<String, String>>(file)
+                .map_err(|error| {
+                    logger::error!(?error, "Invalid user message catalog for locale {locale}")
+                })
+                .ok()
+                .map(|messages| (*locale, parse_catalog(locale, messages)))
+        })
+        .collect()
+});
+
+/// Classification of the errors by what the shopper can do about them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum UnifiedCode {
+    PaymentDeclined,
+    AuthenticationFailed,
+    CardExpired,
+    InvalidPaymentDetails,
+    PaymentMethodNotSupported,
+    SessionExpired,
+    PaymentInProgress,
+    DuplicatePayment,
+    PaymentNotModifiable,
+    TooManyAttempts,
+    /// Internal and technical errors, about which the shopper is not told more
+    ProcessingError,
+}
+
+/// Locale of the payment for which the error occurred, attached to the error report
+#[derive(Debug, Clone)]
+pub struct ErrorLocale(pub String);
+
+fn parse_catalog(locale: &str, messages: HashMap<String, String>) -> HashMap<UnifiedCode, String> {
+    messages
+        .into_iter()
+        .filter_map(|(code, message)| {
+            UnifiedCode::from_str(&code)
+                .map_err(|_| {
+                    logger::warn!(
+                        "Unknown unified code {code} in user message catalog for locale {locale}"
+                    )
+                })
+                .ok()
+                .map(|code| (code, message))
+        })
+        .collect()
+}
+
+impl ApiErrorResponse {
+    pub fn get_unified_code(&self) -> UnifiedCode {
+        match self {
+            Self::ExternalConnectorError { status_code, .. } if *status_code >= 500 => {
+                UnifiedCode::ProcessingError
+            }
+            Self::ExternalConnectorError { .. }
+            | Self::PaymentAuthorizationFailed { .. }
+            | Self::PaymentCaptureFailed { .. }
+            | Self::VerificationFailed { .. }
+            | Self::PaymentBlocked { .. } => UnifiedCode::PaymentDeclined,
+            Self::PaymentAuthenticationFailed { .. } => UnifiedCode::AuthenticationFailed,
+            Self::CardExpired { .. } => UnifiedCode::CardExpired,
+            Self::InvalidCardData { .. }
+            | Self::InvalidCardIin
+            | Self::InvalidCardIinLength
+            | Self::MissingRequiredField { .. }
+            | Self::MissingRequiredFields { .. }
+            | Self::InvalidDataValue { .. }
+            | Self::InvalidDataFormat { .. } => UnifiedCode::InvalidPaymentDetails,
+            Self::NotSupported { .. }
+            | Self::FlowNotSupported { .. }
+            | Self::CurrencyNotSupported { .. }
+            | Self::NoEligibleConnector { .. }
+            | Self::PaymentMethodNotAvailableForAmount { .. } => {
+                UnifiedCode::PaymentMethodNotSupported
+            }
+            Self::FlowDisabledForConnector { .. } => UnifiedCode::PaymentNotModifiable,
+            Self::ClientSecretExpired | Self::PaymentSessionExpired { .. } => {
+                UnifiedCode::SessionExpired
+            }
+            Self::PaymentConfirmInProgress { .. }
+            | Self::ResourceBusy
+            | Self::IdempotencyKeyInUse { .. } => UnifiedCode::PaymentInProgress,
+            Self::DuplicatePayment { .. } | Self::DuplicateOrderReference { .. } => {
+                UnifiedCode::DuplicatePayment
+            }
+            Self::PaymentUnexpectedState { .. } => UnifiedCode::PaymentNotModifiable,
+            Self::TooManyRequests { .. } => UnifiedCode::TooManyAttempts,
+            _ => UnifiedCode::ProcessingError,
+        }
+    }
+
+    /// Values for the placeholders of the message templates, only values which are safe to show
+    /// to the shopper are passed
+    fn get_user_message_params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::TooManyRequests { retry_after_secs } => {
+                vec![("retry_after_secs", retry_after_secs.to_string())]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Picks the catalog for the locale, trying the full language tag, then its primary language,
+/// then the default locale. `fr-CA` falls back to `fr`, unknown locales to `en`.
+pub fn get_catalog_locale(locale: Option<&str>) -> &'static str {
+    let Some(locale) = locale.map(|locale| locale.trim().replace('_', "-").to_lowercase()) else {
+        return DEFAULT_LOCALE;
+    };
+    let language = locale.split('-').next().unwrap_or_default();
+    [locale.as_str(), language]
+        .into_iter()
+        .find_map(|candidate| {
+            CATALOG_FILES
+                .iter()
+                .map(|(catalog_locale, _)| *catalog_locale)
+                .find(|catalog_locale| *catalog_locale == candidate)
+        })
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+fn render_template(template: &str, params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Message of the code in the locale, falling back to the message of the default locale
+pub fn get_message(code: UnifiedCode, locale: Option<&str>, params: &[(&str, String)]) -> String {
+    let template = [get_catalog_locale(locale), DEFAULT_LOCALE]
+        .into_iter()
+        .find_map(|locale| CATALOGS.get(locale).and_then(|catalog| catalog.get(&code)))
+        .map(String::as_str)
+        .unwrap_or(FALLBACK_USER_MESSAGE);
+    render_template(template, params)
+}
+
+pub fn get_user_message(error: &ApiErrorResponse, locale: Option<&str>) -> String {
+    get_message(
+        error.get_unified_code(),
+        locale,
+        &error.get_user_message_params(),
+    )
+}
+
+/// Attaches the locale of the payment to the error, for its user message to be localized
+pub fn attach_locale<C>(report: Report<C>, locale: Option<&str>) -> Report<C> {
+    match locale {
+        Some(locale) => report.attach(ErrorLocale(locale.to_string())),
+        None => report,
+    }
+}
+
+/// Attaches the locale of the request, from the first language of its `Accept-Language` header, to
+/// an error for which no payment locale was attached, before the error response is built
+pub fn attach_request_locale<C>(report: Report<C>, request: &HttpRequest) -> Report<C> {
+    if report
+        .frames()
+        .any(|frame| frame.downcast_ref::<ErrorLocale>().is_some())
+    {
+        return report;
+    }
+    let request_locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(get_accept_language_locale);
+    attach_locale(report, request_locale)
+}
+
+/// The first language of an `Accept-Language` header value, `*` is not a locale
+fn get_accept_language_locale(accept_language: &str) -> Option<&str> {
+    accept_language
+        .split(',')
+        .next()
+        .and_then(|language| language.split(';').next())
+        .map(str::trim)
+        .filter(|language| !language.is_empty() && *language != "*")
+}
+
+/// Adds the user message to the error returned in the response, from the error which caused it
+/// and the locale attached to the report
+pub fn embed_user_message(
+    mut report: Report<api_models::errors::types::ApiErrorResponse>,
+) -> Report<api_models::errors::types::ApiErrorResponse> {
+    let locale = report
+        .frames()
+        .find_map(|frame| frame.downcast_ref::<ErrorLocale>())
+        .map(|locale| locale.0.clone());
+    let user_message = match report
+        .frames()
+        .find_map(|frame| frame.downcast_ref::<ApiErrorResponse>())
+    {
+        Some(error) => get_user_message(error, locale.as_deref()),
+        None => get_message(UnifiedCode::ProcessingError, locale.as_deref(), &[]),
+    };
+
+    if let Some(error) = report.downcast_mut::<api_models::errors::types::ApiErrorResponse>() {
+        error
+            .get_internal_error_mut()
+            .extra
+            .get_or_insert_with(Default::default)
+            .user_message = Some(user_message);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_catalogs_define_every_code() {
+        let default_catalog = CATALOGS.get(DEFAULT_LOCALE).unwrap();
+        assert!(default_catalog.contains_key(&UnifiedCode::ProcessingError));
+        for (locale, _) in CATALOG_FILES {
+            let catalog = CATALOGS.get(locale).unwrap();
+            assert!(
+                default_catalog
+                    .keys()
+                    .all(|code| catalog.contains_key(code)),
+                "Catalog of the locale {locale} is incomplete"
+            );
+        }
+    }
+
+    #[test]
+    fn test_locale_fallback() {
+        assert_eq!(get_catalog_locale(Some("de")), "de");
+        assert_eq!(get_catalog_locale(Some("fr-CA")), "fr");
+        assert_eq!(get_catalog_locale(Some("de_AT")), "de");
+        assert_eq!(get_catalog_locale(Some("ja-JP")), "en");
+        assert_eq!(get_catalog_locale(None), "en");
+
+        let error = ApiErrorResponse::CardExpired { data: None };
+        assert_eq!(
+            get_user_message(&error, Some("fr-FR")),
+            "Votre carte a expiré. Veuillez utiliser une autre carte."
+        );
+        assert_eq!(
+            get_user_message(&error, Some("pt-BR")),
+            get_user_message(&error, None)
+        );
+    }
+
+    #[test]
+    fn test_templates_are_rendered() {
+        let error = ApiErrorResponse::TooManyRequests {
+            retry_after_secs: 30,
+        };
+
+        assert_eq!(
+            get_user_message(&error, Some("de")),
+            "Zu viele Versuche. Bitte versuchen Sie es in 30 Sekunden erneut."
+        );
+    }
+
+    #[test]
+    fn test_connector_text_does_not_leak_into_user_message() {
+        let connector_message = "ConnectorError: RequestEncodingFailed at field card.number";
+        let errors = [
+            ApiErrorResponse::ExternalConnectorError {
+                code: "invalid_request".to_string(),
+                message: connector_message.to_string(),
+                connector: "stripe".to_string(),
+                status_code: 400,
+                reason: Some(connector_message.to_string()),
+            },
+            ApiErrorResponse::ExternalConnectorError {
+                code: "api_error".to_string(),
+                message: connector_message.to_string(),
+                connector: "stripe".to_string(),
+                status_code: 502,
+                reason: None,
+            },
+            ApiErrorResponse::NotSupported {
+                message: connector_message.to_string(),
+            },
+            ApiErrorResponse::InternalServerError,
+        ];
+
+        for error in errors {
+            for locale in ["en", "de", "fr"] {
+                let user_message = get_user_message(&error, Some(locale));
+                assert!(!user_message.contains("Connector"));
+                assert!(!user_message.contains("RequestEncodingFailed"));
+            }
+        }
+        assert_eq!(
+            ApiErrorResponse::InternalServerError.get_unified_code(),
+            UnifiedCode::ProcessingError
+        );
+    }
+
+    #[test]
+    fn test_request_locale_does_not_override_payment_locale() {
+        assert_eq!(
+            get_accept_language_locale("fr-CH, fr;q=0.9, en;q=0.8"),
+            Some("fr-CH")
+        );
+        assert_eq!(get_accept_language_locale("*"), None);
+
+        let request = actix_web::test::TestRequest::default()
+            .insert_header((header::ACCEPT_LANGUAGE, "de-DE,de;q=0.9"))
+            .to_http_request();
+        let get_locale = |report: Report<ApiErrorResponse>| {
+            report
+                .frames()
+                .find_map(|frame| frame.downcast_ref::<ErrorLocale>())
+                .map(|locale| locale.0.clone())
+        };
+
+        let report = attach_request_locale(
+            Report::new(ApiErrorResponse::PaymentSessionExpired {
+                payment_id: "pay_1".to_string(),
+            }),
+            &request,
+        );
+        assert_eq!(get_locale(report), Some("de-DE".to_string()));
+
+        let report = attach_request_locale(
+            attach_locale(
+                Report::new(ApiErrorResponse::CardExpired { data: None }),
+                Some("fr"),
+            ),
+            &request,
+        );
+        assert_eq!(get_locale(report), Some("fr".to_string()));
+    }
+
+    #[test]
+    fn test_user_message_is_embedded_in_the_error_response() {
+        let report = attach_locale(
+            Report::new(ApiErrorResponse::PaymentAuthenticationFailed { data: None }),
+            Some("de-DE"),
+        );
+        let report =
+            report.change_context(api_models::errors::types::ApiErrorResponse::BadRequest(
+                api_models::errors::types::ApiError::new("CE", 2, "Payment failed", None),
+            ));
+
+        let report = embed_user_message(report);
+        let response =
+            serde_json::from_str::<serde_json::Value>(&report.current_context().to_string())
+                .unwrap();
+
+        assert_eq!(response["error"]["message"], "Payment failed");
+        assert_eq!(
+            response["error"]["user_message"],
+            serde_json::json!("Ihre Zahlung konnte nicht bestätigt werden. Bitte versuchen Sie es erneut oder verwenden Sie eine andere Zahlungsmethode.")
+        );
+    }
+}
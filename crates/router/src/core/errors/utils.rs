@@ -205,6 +205,12 @@ impl<T> ConnectorErrorExt<T> for error_stack::Result<T, errors::ConnectorError>
                     errors::ApiErrorResponse::InvalidDataValue { field_name }
                 },
                 errors::ConnectorError::CurrencyNotSupported { message, connector} => errors::ApiErrorResponse::CurrencyNotSupported { message: format!("Credentials for the currency {message} are not configured with the connector {connector}/hyperswitch") },
+                errors::ConnectorError::UnsupportedBank { bank, payment_method_type } => {
+                    errors::ApiErrorResponse::InvalidRequestData { message: format!("Bank {bank} is not supported for payment method type {payment_method_type} by the connector") }
+                },
+                errors::ConnectorError::RequestValidationFailed { field, constraint } => {
+                    errors::ApiErrorResponse::InvalidRequestData { message: format!("Invalid value for {field}: the value {constraint}") }
+                },
                 _ => errors::ApiErrorResponse::InternalServerError,
             };
             err.change_context(error)
@@ -0,0 +1,199 @@
+//! Accumulation of the validation failures of a request, so that all the problems with the fields
+//! of the request are reported at once instead of failing on the first one.
+
+use error_stack::{report, Report};
+
+use super::{ApiErrorResponse, RouterResult};
+
+/// A problem with a field of the request
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct FieldValidationError {
+    /// Path of the field in the request, such as `billing.address.country`
+    pub field: String,
+    /// Code of the error which the problem would have failed the request with on its own
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldValidationError {
+    pub fn missing_field(field: impl Into<String>) -> Self {
+        let field = field.into();
+        Self {
+            code: ApiErrorResponse::MissingRequiredField { field_name: "" }.error_code(),
+            message: format!("Missing required param: {field}"),
+            field,
+        }
+    }
+
+    fn from_api_error(field: &str, error: &ApiErrorResponse) -> Self {
+        Self {
+            field: field.to_string(),
+            code: error.error_code(),
+            message: error.error_message(),
+        }
+    }
+}
+
+/// Collects the validation failures of a request. A failure which is not about the fields of the
+/// request, like a storage error, is not collected and fails the validation immediately.
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    errors: Vec<FieldValidationError>,
+    /// The error of the first failure, returned as is when it is the only failure, so that a
+    /// request with a single problem fails as it did before the failures were collected
+    first_error: Option<Report<ApiErrorResponse>>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the failure of a check of the field, the field is overridden by the field named by
+    /// the error when it names one
+    pub fn collect<T, E>(&mut self, field: &str, result: Result<T, E>) -> RouterResult<Option<T>>
+    where
+        Report<ApiErrorResponse>: From<E>,
+    {
+        let error = match result {
+            Ok(value) => return Ok(Some(value)),
+            Err(error) => Report::from(error),
+        };
+
+        let context = error.current_context();
+        let errors = match context {
+            ApiErrorResponse::MissingRequiredFields { field_names } => field_names
+                .iter()
+                .map(|field_name| FieldValidationError::missing_field(*field_name))
+                .collect(),
+            ApiErrorResponse::MissingRequiredField { field_name }
+            | ApiErrorResponse::InvalidDataValue { field_name } => {
+                vec![FieldValidationError::from_api_error(field_name, context)]
+            }
+            ApiErrorResponse::InvalidDataFormat { field_name, .. } => {
+                vec![FieldValidationError::from_api_error(field_name, context)]
+            }
+            ApiErrorResponse::InvalidRequestData { .. }
+            | ApiErrorResponse::PreconditionFailed { .. }
+            | ApiErrorResponse::AmountOutsideLimits { .. }
+            | ApiErrorResponse::WalletTokenExpired => {
+                vec![FieldValidationError::from_api_error(field, context)]
+            }
+            _ => return Err(error),
+        };
+        if errors.is_empty() {
+            return Err(error);
+        }
+
+        self.errors.extend(errors);
+        self.first_error.get_or_insert(error);
+        Ok(None)
+    }
+
+    /// Records a field which is missing from the request
+    pub fn push_missing_field(&mut self, field: impl Into<String>) {
+        self.errors.push(FieldValidationError::missing_field(field));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Fails with all the collected failures, or with the error of the failure if it is the only one
+    pub fn into_result(self) -> RouterResult<()> {
+        match (self.errors.len(), self.first_error) {
+            (0, _) => Ok(()),
+            (1, Some(error)) => Err(error),
+            _ => Err(report!(ApiErrorResponse::ValidationFailed {
+                errors: self.errors
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use error_stack::IntoReport;
+
+    use super::*;
+
+    #[test]
+    fn test_single_failure_keeps_its_error() {
+        let mut errors = ValidationErrors::new();
+        errors
+            .collect(
+                "locale",
+                Err::<(), _>(ApiErrorResponse::InvalidDataFormat {
+                    field_name: "locale".to_string(),
+                    expected_format: "BCP-47 language tag".to_string(),
+                }),
+            )
+            .unwrap();
+
+        let error = errors.into_result().unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            ApiErrorResponse::InvalidDataFormat { field_name, .. } if field_name == "locale"
+        ));
+    }
+
+    #[test]
+    fn test_failures_are_accumulated() {
+        let mut errors = ValidationErrors::new();
+        assert_eq!(
+            errors
+                .collect("amount", Ok::<_, ApiErrorResponse>(1))
+                .unwrap(),
+            Some(1)
+        );
+        errors
+            .collect(
+                "payment_method_data",
+                Err::<(), _>(ApiErrorResponse::MissingRequiredFields {
+                    field_names: vec!["payment_method", "payment_method_type"],
+                }),
+            )
+            .unwrap();
+        errors
+            .collect(
+                "amount",
+                Err::<(), _>(ApiErrorResponse::AmountOutsideLimits {
+                    reason: "the amount is below the minimum".to_string(),
+                })
+                .into_report(),
+            )
+            .unwrap();
+        errors.push_missing_field("billing.address.country");
+
+        let error = errors.into_result().unwrap_err();
+        let errors = match error.current_context() {
+            ApiErrorResponse::ValidationFailed { errors } => errors.clone(),
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.field.as_str(), error.code.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("payment_method", "IR_04"),
+                ("payment_method_type", "IR_04"),
+                ("amount", "IR_41"),
+                ("billing.address.country", "IR_04"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_errors_are_not_collected() {
+        let mut errors = ValidationErrors::new();
+        assert!(errors
+            .collect(
+                "payment_id",
+                Err::<(), _>(ApiErrorResponse::PaymentNotFound)
+            )
+            .is_err());
+        assert!(errors.is_empty());
+    }
+}
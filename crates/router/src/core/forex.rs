@@ -0,0 +1,505 @@
+//! Indicative exchange rates, used to display the amount of a payment in the currency of the
+//! customer while the payment is charged in its own currency. The rates are fetched from the
+//! provider and cached in redis, stale rates are served while the provider is unavailable until
+//! they are older than the configured maximum age, after which no conversion is made.
+
+use std::{collections::HashMap, str::FromStr};
+
+use api_models::{enums::Currency, payments::DisplayAmount};
+use common_utils::{date_time, ext_traits::BytesExt};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::{Mask, PeekInterface};
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use super::errors::{self, CustomResult};
+use crate::{
+    configs::settings::ForexConfig,
+    headers,
+    routes::AppState,
+    services::{self, ApplicationResponse},
+    types::api,
+};
+
+pub const FOREX_RATES_KEY: &str = "FOREX_RATES";
+
+/// Rates of the currencies against the base currency of the provider
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForexRates {
+    pub base_currency: Currency,
+    /// Units of each currency for one unit of the base currency
+    pub rates: HashMap<Currency, f64>,
+    /// Unix timestamp at which the rates were published by the provider
+    pub as_of: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedForexRates {
+    pub rates: ForexRates,
+    /// Unix timestamp at which the rates were fetched from the provider
+    pub fetched_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForexConversion {
+    /// The converted amount, in the lowest denomination of the target currency
+    pub converted_amount: i64,
+    /// Units of the target currency for one unit of the source currency
+    pub rate: f64,
+    /// Unix timestamp at which the rates used were published
+    pub as_of: i64,
+}
+
+#[async_trait::async_trait]
+pub trait ForexProvider: Send + Sync {
+    async fn fetch_rates(&self) -> CustomResult<ForexRates, errors::ForexError>;
+}
+
+#[async_trait::async_trait]
+pub trait ForexRatesCache: Send + Sync {
+    /// `None` if no rates are cached or the cache could not be read
+    async fn get_rates(&self) -> Option<CachedForexRates>;
+    async fn set_rates(&self, rates: &CachedForexRates, expiry_in_secs: i64);
+}
+
+/// Provider of the latest rates of the Open Exchange Rates API
+pub struct OpenExchangeRates<'a> {
+    pub state: &'a AppState,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenExchangeRatesResponse {
+    timestamp: i64,
+    base: String,
+    rates: HashMap<String, f64>,
+}
+
+#[async_trait::async_trait]
+impl ForexProvider for OpenExchangeRates<'_> {
+    async fn fetch_rates(&self) -> CustomResult<ForexRates, errors::ForexError> {
+        let config = &self.state.conf.forex;
+        let request = services::RequestBuilder::new()
+            .method(services::Method::Get)
+            .url(&config.base_url)
+            .attach_default_headers()
+            .headers(vec![(
+                headers::AUTHORIZATION.to_string(),
+                format!("Token {}", config.api_key.peek()).into_masked(),
+            )])
+            .build();
+
+        let response = services::call_connector_api(self.state, request)
+            .await
+            .change_context(errors::ForexError::ProviderCallFailed)?
+            .map_err(|error_response| {
+                report!(errors::ForexError::ProviderCallFailed).attach_printable(format!(
+                    "Exchange rates provider responded with status code {}",
+                    error_response.status_code
+                ))
+            })?;
+        let response: OpenExchangeRatesResponse = response
+            .response
+            .parse_struct("OpenExchangeRatesResponse")
+            .change_context(errors::ForexError::ResponseDeserializationFailed)?;
+
+        let base_currency = Currency::from_str(&response.base)
+            .into_report()
+            .change_context(errors::ForexError::ResponseDeserializationFailed)
+            .attach_printable("Unknown base currency of the exchange rates")?;
+        // The provider also quotes currencies which are not supported, such as cryptocurrencies
+        let rates = response
+            .rates
+            .into_iter()
+            .filter_map(|(currency, rate)| {
+                Currency::from_str(&currency)
+                    .ok()
+                    .map(|currency| (currency, rate))
+            })
+            .collect();
+
+        Ok(ForexRates {
+            base_currency,
+            rates,
+            as_of: response.timestamp,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ForexRatesCache for RedisConnectionPool {
+    async fn get_rates(&self) -> Option<CachedForexRates> {
+        match self
+            .get_and_deserialize_key::<CachedForexRates>(FOREX_RATES_KEY, "CachedForexRates")
+            .await
+        {
+            Ok(rates) => Some(rates),
+            Err(error) if matches!(error.current_context(), errors::RedisError::NotFound) => None,
+            Err(error) => {
+                logger::error!(?error, "Failed to fetch the cached exchange rates");
+                None
+            }
+        }
+    }
+
+    async fn set_rates(&self, rates: &CachedForexRates, expiry_in_secs: i64) {
+        if let Err(error) = self
+            .serialize_and_set_key_with_expiry(FOREX_RATES_KEY, rates, expiry_in_secs)
+            .await
+        {
+            logger::error!(?error, "Failed to cache the exchange rates");
+        }
+    }
+}
+
+/// Whether exchange rates can be fetched from the provider, which requires its API key
+pub fn is_forex_configured(config: &ForexConfig) -> bool {
+    !config.api_key.peek().is_empty()
+}
+
+fn now_unix_timestamp() -> i64 {
+    date_time::now().assume_utc().unix_timestamp()
+}
+
+/// Rates which are recent enough to be used. The rates are fetched from the provider once the
+/// cached rates are older than the cache TTL, the cached rates are served when the provider cannot
+/// be reached as long as they were published within the maximum age.
+pub async fn get_forex_rates(
+    provider: &dyn ForexProvider,
+    cache: &dyn ForexRatesCache,
+    config: &ForexConfig,
+    now: i64,
+) -> CustomResult<ForexRates, errors::ForexError> {
+    let max_rates_age = i64::from(config.max_rates_age_in_secs);
+    let is_recent_enough = |rates: &ForexRates| now.saturating_sub(rates.as_of) <= max_rates_age;
+
+    let cached_rates = cache.get_rates().await;
+    if let Some(cached_rates) = cached_rates.as_ref().filter(|cached_rates| {
+        now.saturating_sub(cached_rates.fetched_at) < i64::from(config.cache_ttl_in_secs)
+            && is_recent_enough(&cached_rates.rates)
+    }) {
+        return Ok(cached_rates.rates.clone());
+    }
+
+    match provider.fetch_rates().await {
+        Ok(rates) if is_recent_enough(&rates) => {
+            cache
+                .set_rates(
+                    &CachedForexRates {
+                        rates: rates.clone(),
+                        fetched_at: now,
+                    },
+                    max_rates_age,
+                )
+                .await;
+            Ok(rates)
+        }
+        fetch_result => {
+            match fetch_result {
+                Ok(rates) => logger::warn!(
+                    as_of = rates.as_of,
+                    "Exchange rates provider returned rates older than the maximum age"
+                ),
+                Err(error) => logger::warn!(?error, "Failed to fetch the exchange rates"),
+            }
+            cached_rates
+                .map(|cached_rates| cached_rates.rates)
+                .filter(is_recent_enough)
+                .ok_or(report!(errors::ForexError::RatesUnavailable))
+        }
+    }
+}
+
+fn get_rate_against_base(
+    rates: &ForexRates,
+    currency: Currency,
+) -> CustomResult<f64, errors::ForexError> {
+    if currency == rates.base_currency {
+        return Ok(1.0);
+    }
+    rates
+        .rates
+        .get(&currency)
+        .copied()
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .ok_or(report!(errors::ForexError::CurrencyNotAvailable(currency)))
+}
+
+/// Converts an amount in the lowest denomination of `from` to the lowest denomination of `to`,
+/// rounded to the nearest unit of the lowest denomination of `to`
+pub fn convert(
+    rates: &ForexRates,
+    amount: i64,
+    from: Currency,
+    to: Currency,
+) -> CustomResult<ForexConversion, errors::ForexError> {
+    let rate = get_rate_against_base(rates, to)? / get_rate_against_base(rates, from)?;
+    let amount_in_base_unit = from
+        .to_currency_base_unit_exact(amount)
+        .parse::<f64>()
+        .into_report()
+        .change_context(errors::ForexError::ConversionFailed)?;
+    let decimal_digits = usize::from(to.number_of_digits_after_decimal_point());
+    let converted_amount = to
+        .to_currency_lower_unit_exact(&format!("{:.decimal_digits$}", amount_in_base_unit * rate))
+        .ok_or(report!(errors::ForexError::ConversionFailed))
+        .attach_printable("Converted amount is out of range")?;
+
+    Ok(ForexConversion {
+        converted_amount,
+        rate,
+        as_of: rates.as_of,
+    })
+}
+
+/// Converts the amount with the rates of the provider configured in the application
+#[instrument(skip(state))]
+pub async fn convert_amount(
+    state: &AppState,
+    amount: i64,
+    from: Currency,
+    to: Currency,
+) -> CustomResult<ForexConversion, errors::ForexError> {
+    let now = now_unix_timestamp();
+    if from == to {
+        return Ok(ForexConversion {
+            converted_amount: amount,
+            rate: 1.0,
+            as_of: now,
+        });
+    }
+
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ForexError::RatesUnavailable)
+        .attach_printable("Failed to get the redis connection for the exchange rates cache")?;
+    let rates = get_forex_rates(
+        &OpenExchangeRates { state },
+        redis_conn.as_ref(),
+        &state.conf.forex,
+        now,
+    )
+    .await?;
+    convert(&rates, amount, from, to)
+}
+
+/// The amount of the payment in the display currency, `None` if the amount could not be converted
+pub async fn get_display_amount(
+    state: &AppState,
+    amount: i64,
+    currency: Currency,
+    display_currency: Currency,
+) -> Option<DisplayAmount> {
+    let conversion = convert_amount(state, amount, currency, display_currency)
+        .await
+        .map_err(|error| logger::warn!(?error, "Display amount is unavailable"))
+        .ok()?;
+    let rates_as_of = OffsetDateTime::from_unix_timestamp(conversion.as_of)
+        .ok()
+        .map(|as_of| PrimitiveDateTime::new(as_of.date(), as_of.time()))?;
+
+    Some(DisplayAmount {
+        currency: display_currency,
+        amount: conversion.converted_amount,
+        exchange_rate: conversion.rate,
+        rates_as_of,
+    })
+}
+
+/// Adds the amount of the payment in the display currency requested on payment creation to the
+/// payments response
+pub async fn add_display_amount(
+    state: &AppState,
+    response: ApplicationResponse<api::PaymentsResponse>,
+    display_currency: Option<Currency>,
+) -> ApplicationResponse<api::PaymentsResponse> {
+    let Some(display_currency) = display_currency else {
+        return response;
+    };
+    match response {
+        ApplicationResponse::Json(payments_response) => ApplicationResponse::Json(
+            with_display_amount(state, payments_response, display_currency).await,
+        ),
+        ApplicationResponse::JsonWithHeaders((payments_response, headers)) => {
+            ApplicationResponse::JsonWithHeaders((
+                with_display_amount(state, payments_response, display_currency).await,
+                headers,
+            ))
+        }
+        response => response,
+    }
+}
+
+async fn with_display_amount(
+    state: &AppState,
+    mut payments_response: api::PaymentsResponse,
+    display_currency: Currency,
+) -> api::PaymentsResponse {
+    match Currency::from_str(&payments_response.currency) {
+        Ok(currency) => {
+            payments_response.display_amount =
+                get_display_amount(state, payments_response.amount, currency, display_currency)
+                    .await;
+        }
+        Err(error) => logger::error!(?error, "Invalid currency in the payments response"),
+    }
+    payments_response
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// Provider returning fixed rates, or failing when no rates are set to simulate an outage
+    struct FixtureProvider {
+        rates: Mutex<Option<ForexRates>>,
+        calls: AtomicUsize,
+    }
+
+    impl FixtureProvider {
+        fn new(rates: Option<ForexRates>) -> Self {
+            Self {
+                rates: Mutex::new(rates),
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ForexProvider for FixtureProvider {
+        async fn fetch_rates(&self) -> CustomResult<ForexRates, errors::ForexError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.rates
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or(report!(errors::ForexError::ProviderCallFailed))
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryCache(Mutex<Option<CachedForexRates>>);
+
+    #[async_trait::async_trait]
+    impl ForexRatesCache for InMemoryCache {
+        async fn get_rates(&self) -> Option<CachedForexRates> {
+            self.0.lock().unwrap().clone()
+        }
+
+        async fn set_rates(&self, rates: &CachedForexRates, _expiry_in_secs: i64) {
+            *self.0.lock().unwrap() = Some(rates.clone());
+        }
+    }
+
+    fn fixture_rates(as_of: i64) -> ForexRates {
+        ForexRates {
+            base_currency: Currency::USD,
+            rates: HashMap::from([
+                (Currency::EUR, 0.92),
+                (Currency::JPY, 149.5),
+                (Currency::KWD, 0.308),
+            ]),
+            as_of,
+        }
+    }
+
+    fn config() -> ForexConfig {
+        ForexConfig {
+            cache_ttl_in_secs: 3600,
+            max_rates_age_in_secs: 86400,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rates_are_cached_until_ttl() {
+        let provider = FixtureProvider::new(Some(fixture_rates(NOW)));
+        let cache = InMemoryCache::default();
+
+        for now in [NOW, NOW + 60, NOW + 3599] {
+            let rates = get_forex_rates(&provider, &cache, &config(), now)
+                .await
+                .unwrap();
+            assert_eq!(rates, fixture_rates(NOW));
+        }
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+
+        *provider.rates.lock().unwrap() = Some(fixture_rates(NOW + 3600));
+        let rates = get_forex_rates(&provider, &cache, &config(), NOW + 3600)
+            .await
+            .unwrap();
+        assert_eq!(rates.as_of, NOW + 3600);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.get_rates().await.unwrap().fetched_at, NOW + 3600);
+    }
+
+    #[tokio::test]
+    async fn test_stale_rates_are_served_until_max_age() {
+        let provider = FixtureProvider::new(Some(fixture_rates(NOW)));
+        let cache = InMemoryCache::default();
+        get_forex_rates(&provider, &cache, &config(), NOW)
+            .await
+            .unwrap();
+
+        // The provider is unavailable, the rates cached past their TTL are still served
+        *provider.rates.lock().unwrap() = None;
+        let rates = get_forex_rates(&provider, &cache, &config(), NOW + 7200)
+            .await
+            .unwrap();
+        assert_eq!(rates, fixture_rates(NOW));
+        assert!(get_forex_rates(&provider, &cache, &config(), NOW + 86400)
+            .await
+            .is_ok());
+
+        // Beyond the maximum age, the rates are unavailable rather than wrong
+        let error = get_forex_rates(&provider, &cache, &config(), NOW + 86401)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ForexError::RatesUnavailable
+        ));
+
+        // Rates published too long ago by the provider are not used either
+        *provider.rates.lock().unwrap() = Some(fixture_rates(NOW));
+        assert!(get_forex_rates(&provider, &cache, &config(), NOW + 86401)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_conversion_to_zero_and_three_decimal_currencies() {
+        let rates = fixture_rates(NOW);
+
+        // 10.99 USD is 1643.005 JPY, rounded to 1643 as JPY has no minor unit
+        let conversion = convert(&rates, 1099, Currency::USD, Currency::JPY).unwrap();
+        assert_eq!(conversion.converted_amount, 1643);
+        assert_eq!(conversion.as_of, NOW);
+
+        // 1500 JPY is 9.23 EUR through the rates against USD
+        let conversion = convert(&rates, 1500, Currency::JPY, Currency::EUR).unwrap();
+        assert_eq!(conversion.converted_amount, 923);
+        assert!((conversion.rate - 0.92 / 149.5).abs() < f64::EPSILON);
+
+        // 10.00 EUR is 3.348 KWD, which has three decimal digits
+        let conversion = convert(&rates, 1000, Currency::EUR, Currency::KWD).unwrap();
+        assert_eq!(conversion.converted_amount, 3348);
+
+        let error = convert(&rates, 1000, Currency::USD, Currency::GBP).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ForexError::CurrencyNotAvailable(Currency::GBP)
+        ));
+    }
+}
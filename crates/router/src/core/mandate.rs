@@ -1,26 +1,30 @@
+pub mod migration;
+pub mod usage;
+
 use api_models::payments;
-use common_utils::{ext_traits::Encode, pii};
+use common_utils::{date_time, ext_traits::Encode, pii};
 use diesel_models::enums as storage_enums;
 use error_stack::{report, ResultExt};
 use futures::future;
 use router_env::{instrument, logger, tracing};
 
-use super::payments::helpers;
+use super::payments::{self as payment_flows, helpers, operations};
 use crate::{
+    consts,
     core::errors::{self, RouterResponse, StorageErrorExt},
     db::StorageInterface,
     routes::{metrics, AppState},
-    services,
+    services::{self, authentication},
     types::{
         self,
         api::{
-            customers,
+            self, customers,
             mandates::{self, MandateResponseExt},
         },
         domain, storage,
         transformers::ForeignTryFrom,
     },
-    utils::OptionExt,
+    utils::{self, OptionExt},
 };
 
 #[instrument(skip(state))]
@@ -66,6 +70,106 @@ pub async fn revoke_mandate(
     ))
 }
 
+#[instrument(skip_all)]
+pub async fn charge_recurring_mandate(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    api_key_permissions: Option<Vec<api_models::enums::ApiKeyPermission>>,
+    api_key_livemode: Option<bool>,
+    req: payments::RecurringPaymentsRequest,
+) -> RouterResponse<payments::PaymentsResponse> {
+    utils::when(req.amount <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "amount"
+        }))
+    })?;
+
+    let override_mandate_limits = req.override_mandate_limits.unwrap_or(false);
+    if override_mandate_limits {
+        authentication::check_api_key_has_permission(
+            api_key_permissions.as_deref(),
+            api_models::enums::ApiKeyPermission::MandateLimitOverride,
+        )
+        .attach_printable("API key is not allowed to override the limits of the mandate")?;
+    }
+
+    let mandate = find_mandate_for_recurring_charge(&state, &merchant_account, &req).await?;
+
+    // The payment is initiated by the merchant with the customer off session, the mandate
+    // reference or the network transaction id of the mandate is used in place of authentication
+    let payments_request = payments::PaymentsRequest {
+        amount: Some(req.amount.into()),
+        currency: Some(req.currency),
+        capture_method: req.capture_method,
+        confirm: Some(true),
+        off_session: Some(true),
+        customer_id: Some(req.customer_id.unwrap_or(mandate.customer_id)),
+        mandate_id: Some(mandate.mandate_id),
+        authentication_type: Some(api_models::enums::AuthenticationType::NoThreeDs),
+        description: req.description,
+        metadata: req.metadata,
+        ..Default::default()
+    };
+
+    payment_flows::payments_core::<api::Authorize, payments::PaymentsResponse, _, _, _>(
+        state,
+        merchant_account,
+        key_store,
+        operations::PaymentCreate {
+            override_mandate_limits,
+            livemode: api_key_livemode,
+            ..Default::default()
+        },
+        payments_request,
+        services::AuthFlow::Merchant,
+        payment_flows::CallConnectorAction::Trigger,
+        payments::HeaderPayload::default(),
+    )
+    .await
+}
+
+async fn find_mandate_for_recurring_charge(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    req: &payments::RecurringPaymentsRequest,
+) -> errors::RouterResult<storage::Mandate> {
+    let db = state.store.as_ref();
+    match &req.mandate_id {
+        Some(mandate_id) => db
+            .find_mandate_by_merchant_id_mandate_id(&merchant_account.merchant_id, mandate_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::MandateNotFound),
+        None => {
+            let customer_id = req.customer_id.as_ref().get_required_value("customer_id")?;
+            let payment_method_id = req
+                .payment_method_id
+                .as_ref()
+                .get_required_value("payment_method_id")?;
+            db.find_mandate_by_merchant_id_customer_id(&merchant_account.merchant_id, customer_id)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Failed while finding mandate: merchant_id: {}, customer_id: {}",
+                        merchant_account.merchant_id, customer_id
+                    )
+                })?
+                .into_iter()
+                .find(|mandate| {
+                    mandate.payment_method_id == *payment_method_id
+                        && matches!(
+                            mandate.mandate_status,
+                            storage_enums::MandateStatus::Active
+                                | storage_enums::MandateStatus::RequiresAttention
+                        )
+                })
+                .ok_or(report!(errors::ApiErrorResponse::MandateNotFound))
+                .attach_printable("No active mandate found for the payment method")
+        }
+    }
+}
+
 #[instrument(skip(db))]
 pub async fn update_connector_mandate_id(
     db: &dyn StorageInterface,
@@ -138,6 +242,86 @@ where
     }
 }
 
+/// A charge counted against a mandate before it is made
+#[derive(Clone, Debug)]
+pub struct MandateChargeReservation {
+    mandate_id: String,
+    period_started_at: Option<time::PrimitiveDateTime>,
+}
+
+/// Counts the charge against the mandate it is made with before it is made. The limit of the
+/// frequency of the mandate is enforced by the conditional update of its counters, so that
+/// concurrent charges can not exceed it.
+pub async fn reserve_mandate_charge<F, FData>(
+    state: &AppState,
+    router_data: &types::RouterData<F, FData, types::PaymentsResponseData>,
+) -> errors::RouterResult<Option<MandateChargeReservation>>
+where
+    FData: MandateBehaviour,
+{
+    let Some(mandate_id) = router_data.request.get_mandate_id() else {
+        return Ok(None);
+    };
+    let mandate = state
+        .store
+        .find_mandate_by_merchant_id_mandate_id(&router_data.merchant_id, &mandate_id.mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+    let override_mandate_limits = router_data
+        .recurring_mandate_payment_data
+        .as_ref()
+        .map_or(false, |data| data.override_mandate_limits)
+        && mandate.mandate_type == storage_enums::MandateType::MultiUse;
+    let now = date_time::now();
+    let period_started_at = mandate
+        .frequency
+        .map(|frequency| usage::get_period_start(frequency, now));
+
+    let is_reserved = state
+        .store
+        .reserve_mandate_charge(
+            &router_data.merchant_id,
+            &mandate.mandate_id,
+            period_started_at,
+            !override_mandate_limits,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::MandateUpdateFailed)?;
+    match mandate.frequency {
+        Some(frequency) if !is_reserved => {
+            Err(report!(helpers::get_mandate_charge_limit_exceeded_error(
+                &mandate,
+                usage::get_next_period_start(frequency, now),
+            )))
+        }
+        _ => Ok(Some(MandateChargeReservation {
+            mandate_id: mandate.mandate_id,
+            period_started_at,
+        })),
+    }
+}
+
+/// Releases the charge counted by [`reserve_mandate_charge`] when it was not made
+pub async fn release_mandate_charge(
+    state: &AppState,
+    merchant_id: &str,
+    reservation: Option<&MandateChargeReservation>,
+) {
+    if let Some(reservation) = reservation {
+        if let Err(error) = state
+            .store
+            .release_mandate_charge(
+                merchant_id,
+                &reservation.mandate_id,
+                reservation.period_started_at,
+            )
+            .await
+        {
+            logger::error!(mandate_charge_release_error=?error);
+        }
+    }
+}
+
 pub async fn mandate_procedure<F, FData>(
     state: &AppState,
     mut resp: types::RouterData<F, FData, types::PaymentsResponseData>,
@@ -148,7 +332,36 @@ where
     FData: MandateBehaviour,
 {
     match resp.response {
-        Err(_) => {}
+        Err(_) => {
+            if let Some(mandate_id) = resp.request.get_mandate_id() {
+                let mandate_id = &mandate_id.mandate_id;
+                let mandate = state
+                    .store
+                    .find_mandate_by_merchant_id_mandate_id(resp.merchant_id.as_ref(), mandate_id)
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+                let consecutive_failure_count = mandate.consecutive_failure_count + 1;
+                let mandate_status = get_mandate_status_after_failure(consecutive_failure_count);
+                if mandate_status.is_some() {
+                    logger::warn!(
+                        "Mandate {mandate_id} requires attention after \
+                        {consecutive_failure_count} consecutive failures"
+                    );
+                }
+                state
+                    .store
+                    .update_mandate_by_merchant_id_mandate_id(
+                        &resp.merchant_id,
+                        mandate_id,
+                        storage::MandateUpdate::FailureCountUpdate {
+                            consecutive_failure_count,
+                            mandate_status,
+                        },
+                    )
+                    .await
+                    .change_context(errors::ApiErrorResponse::MandateUpdateFailed)?;
+            }
+        }
         Ok(_) => match resp.request.get_mandate_id() {
             Some(mandate_id) => {
                 let mandate_id = &mandate_id.mandate_id;
@@ -157,33 +370,38 @@ where
                     .find_mandate_by_merchant_id_mandate_id(resp.merchant_id.as_ref(), mandate_id)
                     .await
                     .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
-                let mandate = match mandate.mandate_type {
-                    storage_enums::MandateType::SingleUse => state
-                        .store
-                        .update_mandate_by_merchant_id_mandate_id(
-                            &resp.merchant_id,
-                            mandate_id,
-                            storage::MandateUpdate::StatusUpdate {
-                                mandate_status: storage_enums::MandateStatus::Revoked,
-                            },
-                        )
-                        .await
-                        .change_context(errors::ApiErrorResponse::MandateUpdateFailed),
-                    storage_enums::MandateType::MultiUse => state
-                        .store
-                        .update_mandate_by_merchant_id_mandate_id(
-                            &resp.merchant_id,
-                            mandate_id,
-                            storage::MandateUpdate::CaptureAmountUpdate {
-                                amount_captured: Some(
-                                    mandate.amount_captured.unwrap_or(0)
-                                        + resp.request.get_amount(),
-                                ),
-                            },
-                        )
-                        .await
-                        .change_context(errors::ApiErrorResponse::MandateUpdateFailed),
-                }?;
+                let amount_captured =
+                    Some(mandate.amount_captured.unwrap_or(0) + resp.request.get_amount());
+                // The amount captured on a single use mandate marks it as consumed
+                let mandate_update = match mandate.mandate_type {
+                    storage_enums::MandateType::SingleUse => {
+                        storage::MandateUpdate::CaptureAmountAndStatusUpdate {
+                            amount_captured,
+                            mandate_status: storage_enums::MandateStatus::Revoked,
+                        }
+                    }
+                    storage_enums::MandateType::MultiUse
+                        if mandate.mandate_status
+                            == storage_enums::MandateStatus::RequiresAttention =>
+                    {
+                        storage::MandateUpdate::CaptureAmountAndStatusUpdate {
+                            amount_captured,
+                            mandate_status: storage_enums::MandateStatus::Active,
+                        }
+                    }
+                    storage_enums::MandateType::MultiUse => {
+                        storage::MandateUpdate::CaptureAmountUpdate { amount_captured }
+                    }
+                };
+                let mandate = state
+                    .store
+                    .update_mandate_by_merchant_id_mandate_id(
+                        &resp.merchant_id,
+                        mandate_id,
+                        mandate_update,
+                    )
+                    .await
+                    .change_context(errors::ApiErrorResponse::MandateUpdateFailed)?;
                 metrics::SUBSEQUENT_MANDATE_PAYMENT.add(
                     &metrics::CONTEXT,
                     1,
@@ -273,6 +491,14 @@ where
     Ok(resp)
 }
 
+/// A mandate which was declined too many times in a row is flagged for the merchant to review
+fn get_mandate_status_after_failure(
+    consecutive_failure_count: i32,
+) -> Option<storage_enums::MandateStatus> {
+    (consecutive_failure_count >= consts::MAX_CONSECUTIVE_MANDATE_FAILURES)
+        .then_some(storage_enums::MandateStatus::RequiresAttention)
+}
+
 #[instrument(skip(state))]
 pub async fn retrieve_mandates_list(
     state: AppState,
@@ -0,0 +1,1098 @@
+//! Migration of mandates from one merchant connector account to another, such as when the
+//! merchant moves to a new processor. The merchant provides the mapping produced by the token
+//! migration program of the connectors, from the connector mandate id of each mandate at the old
+//! connector to its reference at the new connector. The migration is executed in the background
+//! by the scheduler, every mandate is moved independently of the others and its outcome can be
+//! retrieved with the identifier of the migration. A mandate which is not migrated stays on the
+//! old connector and can still be charged through it.
+
+use std::{collections::HashMap, future::Future, str::FromStr};
+
+use api_models::{mandates as mandates_api, payments as payments_api};
+use common_utils::{date_time, ext_traits::Encode};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+use scheduler::{db::process_tracker::ProcessTrackerExt, errors as sch_errors, utils as pt_utils};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payment_methods::transformers::PaymentMethodMetadata,
+        payments as payment_flows,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{
+        self, api, domain,
+        storage::{self, enums},
+    },
+    utils::{self, ValueExt},
+};
+
+pub const MANDATE_MIGRATION_WORKFLOW_RUNNER: &str = "MANDATE_MIGRATION_WORKFLOW";
+const MANDATE_MIGRATION_WORKFLOW_TASK: &str = "MANDATE_MIGRATION";
+
+/// Tracking data of the process which executes a mandate migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MandateMigrationTrackingData {
+    pub merchant_id: String,
+    pub migration_id: String,
+}
+
+/// The merchant connector account the mandates are migrated to
+#[derive(Debug, Clone)]
+pub struct MigrationTarget {
+    pub connector_name: String,
+    pub merchant_connector_id: String,
+}
+
+/// The outcome of the charge of 0 with which a migrated mandate is verified
+#[derive(Debug, Clone)]
+pub enum MandateVerification {
+    Verified,
+    Declined {
+        error_code: Option<String>,
+        error_message: Option<String>,
+    },
+}
+
+#[instrument(skip_all)]
+pub async fn mandate_migration_create_core(
+    state: AppState,
+    merchant_id: String,
+    req: mandates_api::MandateMigrationRequest,
+) -> RouterResponse<mandates_api::MandateMigrationResponse> {
+    validate_mandate_migration_request(&req)?;
+
+    let db = &*state.store;
+    let key_store = get_merchant_key_store(db, &merchant_id).await?;
+    for merchant_connector_id in [
+        &req.source_merchant_connector_id,
+        &req.target_merchant_connector_id,
+    ] {
+        find_merchant_connector_account(db, &merchant_id, merchant_connector_id, &key_store)
+            .await?;
+    }
+
+    let items = req
+        .mandates
+        .into_iter()
+        .map(|item| storage::MandateMigrationItem {
+            connector_mandate_id: item.connector_mandate_id,
+            new_connector_mandate_id: item.new_connector_mandate_id,
+            network_transaction_id: item.network_transaction_id,
+            mandate_id: None,
+            status: enums::MandateMigrationItemStatus::Pending,
+            error_code: None,
+            error_message: None,
+        })
+        .collect::<Vec<_>>();
+    let now = date_time::now();
+    let mandate_migration = storage::MandateMigrationNew {
+        migration_id: utils::generate_id(consts::ID_LENGTH, "mig"),
+        merchant_id,
+        source_merchant_connector_id: req.source_merchant_connector_id,
+        target_merchant_connector_id: req.target_merchant_connector_id,
+        verify: req.verify,
+        status: enums::MandateMigrationStatus::Pending,
+        items: encode_items(items)?,
+        created_at: now,
+        modified_at: now,
+    };
+    let mandate_migration = db
+        .insert_mandate_migration(mandate_migration)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while inserting the mandate migration")?;
+
+    add_mandate_migration_task(db, &mandate_migration)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while scheduling the mandate migration")?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_mandate_migration_response(mandate_migration)?,
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn mandate_migration_retrieve_core(
+    state: AppState,
+    merchant_id: String,
+    migration_id: String,
+) -> RouterResponse<mandates_api::MandateMigrationResponse> {
+    let mandate_migration = state
+        .store
+        .find_mandate_migration_by_merchant_id_migration_id(&merchant_id, &migration_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Mandate migration with id {migration_id} does not exist"),
+        })?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_mandate_migration_response(mandate_migration)?,
+    ))
+}
+
+pub fn validate_mandate_migration_request(
+    req: &mandates_api::MandateMigrationRequest,
+) -> RouterResult<()> {
+    utils::when(
+        req.mandates.is_empty() || req.mandates.len() > consts::MAX_MANDATE_MIGRATION_ITEMS,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "mandates must contain between 1 and {} mandates",
+                    consts::MAX_MANDATE_MIGRATION_ITEMS
+                ),
+            }))
+        },
+    )?;
+    utils::when(
+        req.source_merchant_connector_id == req.target_merchant_connector_id,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "source_merchant_connector_id and target_merchant_connector_id must be different".to_string(),
+            }))
+        },
+    )
+}
+
+async fn get_merchant_key_store(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<domain::MerchantKeyStore> {
+    db.get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
+}
+
+async fn find_merchant_connector_account(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    merchant_connector_id: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<domain::MerchantConnectorAccount> {
+    db.find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+        merchant_id,
+        merchant_connector_id,
+        key_store,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+        id: merchant_connector_id.to_owned(),
+    })
+}
+
+fn encode_items(items: Vec<storage::MandateMigrationItem>) -> RouterResult<serde_json::Value> {
+    Encode::<Vec<storage::MandateMigrationItem>>::encode_to_value(&items)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the mandates of the mandate migration")
+}
+
+fn decode_items(
+    mandate_migration: &storage::MandateMigration,
+) -> RouterResult<Vec<storage::MandateMigrationItem>> {
+    mandate_migration
+        .items
+        .clone()
+        .parse_value::<Vec<storage::MandateMigrationItem>>("MandateMigrationItem")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the mandates of the mandate migration")
+}
+
+async fn add_mandate_migration_task(
+    db: &dyn StorageInterface,
+    mandate_migration: &storage::MandateMigration,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        MANDATE_MIGRATION_WORKFLOW_RUNNER,
+        MANDATE_MIGRATION_WORKFLOW_TASK,
+        &mandate_migration.migration_id,
+        &mandate_migration.merchant_id,
+    );
+    let tracking_data = MandateMigrationTrackingData {
+        merchant_id: mandate_migration.merchant_id.clone(),
+        migration_id: mandate_migration.migration_id.clone(),
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        MANDATE_MIGRATION_WORKFLOW_TASK,
+        MANDATE_MIGRATION_WORKFLOW_RUNNER,
+        tracking_data,
+        date_time::now(),
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+/// Migrates the mandates of the migration which are still pending, and marks the migration as
+/// completed. The outcome of the mandates is saved after every batch of mandates, so that the
+/// mandates migrated by an earlier attempt of the task are not migrated again.
+#[instrument(skip_all)]
+pub async fn execute_mandate_migration(
+    state: &AppState,
+    tracking_data: &MandateMigrationTrackingData,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let merchant_id = &tracking_data.merchant_id;
+    let key_store = get_merchant_key_store(db, merchant_id).await?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let mandate_migration = db
+        .find_mandate_migration_by_merchant_id_migration_id(
+            merchant_id,
+            &tracking_data.migration_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!(
+                "Mandate migration with id {} does not exist",
+                tracking_data.migration_id
+            ),
+        })?;
+
+    let source = find_merchant_connector_account(
+        db,
+        merchant_id,
+        &mandate_migration.source_merchant_connector_id,
+        &key_store,
+    )
+    .await?;
+    let target = find_merchant_connector_account(
+        db,
+        merchant_id,
+        &mandate_migration.target_merchant_connector_id,
+        &key_store,
+    )
+    .await?;
+
+    let mandates = get_mandates_by_connector_mandate_id(
+        db,
+        merchant_id,
+        &source.connector_name,
+        &source.merchant_connector_id,
+    )
+    .await?;
+    let target_profile_id = target.profile_id.clone();
+    let target = MigrationTarget {
+        connector_name: target.connector_name.clone(),
+        merchant_connector_id: target.merchant_connector_id.clone(),
+    };
+
+    let verify_mandate = |mandate| {
+        verify_migrated_mandate(
+            state,
+            &merchant_account,
+            &key_store,
+            &target.connector_name,
+            target_profile_id.clone(),
+            mandate,
+        )
+    };
+
+    migrate_mandates_in_batches(db, mandate_migration, &mandates, &target, verify_mandate).await?;
+    Ok(())
+}
+
+/// Migrates the pending mandates of the migration batch by batch, saving the outcome of the
+/// mandates after every batch, and marks the migration as completed
+pub async fn migrate_mandates_in_batches<F, Fut>(
+    db: &dyn StorageInterface,
+    mandate_migration: storage::MandateMigration,
+    mandates: &HashMap<String, storage::Mandate>,
+    target: &MigrationTarget,
+    verify_mandate: F,
+) -> RouterResult<storage::MandateMigration>
+where
+    F: Fn(storage::Mandate) -> Fut,
+    Fut: Future<Output = RouterResult<MandateVerification>>,
+{
+    let verify = mandate_migration.verify;
+    let mut items = decode_items(&mandate_migration)?;
+    let mut mandate_migration = mandate_migration;
+    let mut batch_start = 0;
+    while batch_start < items.len() {
+        let batch_end = (batch_start + consts::MANDATE_MIGRATION_BATCH_SIZE).min(items.len());
+        let batch = &items[batch_start..batch_end];
+        if batch
+            .iter()
+            .any(|item| item.status == enums::MandateMigrationItemStatus::Pending)
+        {
+            let migrated_items = migrate_mandate_items(
+                db,
+                batch.to_vec(),
+                mandates,
+                target,
+                verify,
+                &verify_mandate,
+            )
+            .await;
+            items.splice(batch_start..batch_end, migrated_items);
+            mandate_migration = update_mandate_migration_items(
+                db,
+                mandate_migration,
+                enums::MandateMigrationStatus::Pending,
+                &items,
+            )
+            .await?;
+        }
+        batch_start = batch_end;
+    }
+
+    update_mandate_migration_items(
+        db,
+        mandate_migration,
+        enums::MandateMigrationStatus::Completed,
+        &items,
+    )
+    .await
+}
+
+async fn update_mandate_migration_items(
+    db: &dyn StorageInterface,
+    mandate_migration: storage::MandateMigration,
+    status: enums::MandateMigrationStatus,
+    items: &[storage::MandateMigrationItem],
+) -> RouterResult<storage::MandateMigration> {
+    db.update_mandate_migration(
+        mandate_migration,
+        storage::MandateMigrationUpdate::ItemsUpdate {
+            status,
+            items: encode_items(items.to_vec())?,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while updating the mandate migration")
+}
+
+/// The mandates of the merchant on the source merchant connector account, by their connector
+/// mandate id. Mandates created before the merchant connector account was recorded on them are
+/// matched by their connector.
+async fn get_mandates_by_connector_mandate_id(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    connector_name: &str,
+    merchant_connector_id: &str,
+) -> RouterResult<HashMap<String, storage::Mandate>> {
+    let mandates = db
+        .find_mandates_by_merchant_id(
+            merchant_id,
+            mandates_api::MandateListConstraints {
+                limit: None,
+                mandate_status: None,
+                connector: Some(connector_name.to_owned()),
+                created_time: None,
+                created_time_lt: None,
+                created_time_gt: None,
+                created_time_lte: None,
+                created_time_gte: None,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while finding the mandates of the source connector")?;
+
+    Ok(mandates
+        .into_iter()
+        .filter(|mandate| {
+            mandate
+                .merchant_connector_id
+                .as_ref()
+                .map_or(true, |mandate_merchant_connector_id| {
+                    mandate_merchant_connector_id == merchant_connector_id
+                })
+        })
+        .filter_map(|mandate| {
+            get_connector_mandate_id(&mandate)
+                .map(|connector_mandate_id| (connector_mandate_id, mandate))
+        })
+        .collect())
+}
+
+fn get_connector_mandate_id(mandate: &storage::Mandate) -> Option<String> {
+    mandate
+        .connector_mandate_ids
+        .clone()
+        .and_then(|connector_mandate_ids| {
+            connector_mandate_ids
+                .parse_value::<types::MandateReference>("MandateReference")
+                .ok()
+        })
+        .and_then(|mandate_reference| mandate_reference.connector_mandate_id)
+        .or_else(|| mandate.connector_mandate_id.clone())
+}
+
+/// Moves the mandate of every pending item, and the connector token of its payment method, to the
+/// target merchant connector account, one after the other. When `verify` is set, a migrated
+/// mandate whose verification is declined or fails is moved back to the source merchant connector
+/// account. An item which fails is recorded as failed
+/// and the remaining items are still migrated.
+pub async fn migrate_mandate_items<F, Fut>(
+    db: &dyn StorageInterface,
+    items: Vec<storage::MandateMigrationItem>,
+    mandates: &HashMap<String, storage::Mandate>,
+    target: &MigrationTarget,
+    verify: bool,
+    verify_mandate: F,
+) -> Vec<storage::MandateMigrationItem>
+where
+    F: Fn(storage::Mandate) -> Fut,
+    Fut: Future<Output = RouterResult<MandateVerification>>,
+{
+    let mut migrated_items = Vec::with_capacity(items.len());
+
+    for mut item in items {
+        if item.status != enums::MandateMigrationItemStatus::Pending {
+            migrated_items.push(item);
+            continue;
+        }
+        let Some(mandate) = mandates.get(&item.connector_mandate_id) else {
+            migrated_items.push(get_failed_item(
+                item,
+                &errors::ApiErrorResponse::MandateNotFound,
+            ));
+            continue;
+        };
+        item.mandate_id = Some(mandate.mandate_id.clone());
+
+        let migrated_mandate = match migrate_mandate(db, mandate, &item, target).await {
+            Ok(migrated_mandate) => migrated_mandate,
+            Err(error) => {
+                logger::info!(?error, mandate_id = %mandate.mandate_id, "mandate of the mandate migration was not migrated");
+                migrated_items.push(get_failed_item(item, error.current_context()));
+                continue;
+            }
+        };
+        let payment_method = match repoint_payment_method(db, mandate, &item, target).await {
+            Ok(payment_method) => payment_method,
+            Err(error) => {
+                logger::info!(?error, mandate_id = %mandate.mandate_id, "payment method of the mandate was not migrated, moving the mandate back to the source connector");
+                if let Err(error) = revert_mandate(db, mandate).await {
+                    logger::error!(?error, mandate_id = %mandate.mandate_id, "Failed to move the mandate back to the source connector");
+                }
+                migrated_items.push(get_failed_item(item, error.current_context()));
+                continue;
+            }
+        };
+
+        let verification = if verify {
+            verify_mandate(migrated_mandate).await
+        } else {
+            Ok(MandateVerification::Verified)
+        };
+        let (error_code, error_message) = match verification {
+            Ok(MandateVerification::Verified) => {
+                item.status = enums::MandateMigrationItemStatus::Migrated;
+                migrated_items.push(item);
+                continue;
+            }
+            Ok(MandateVerification::Declined {
+                error_code,
+                error_message,
+            }) => (error_code, error_message),
+            Err(error) => {
+                let error = error.current_context();
+                (Some(error.error_code()), Some(error.error_message()))
+            }
+        };
+
+        logger::info!(mandate_id = %mandate.mandate_id, "verification of the migrated mandate failed, moving it back to the source connector");
+        if let Err(error) = revert_mandate(db, mandate).await {
+            logger::error!(?error, mandate_id = %mandate.mandate_id, "Failed to move the mandate back to the source connector");
+        }
+        if let Some(payment_method) = payment_method {
+            if let Err(error) = restore_payment_method(db, payment_method).await {
+                logger::error!(?error, mandate_id = %mandate.mandate_id, "Failed to move the payment method back to the source connector");
+            }
+        }
+        item.status = enums::MandateMigrationItemStatus::Failed;
+        item.error_code = error_code;
+        item.error_message = error_message;
+        migrated_items.push(item);
+    }
+
+    migrated_items
+}
+
+fn get_failed_item(
+    mut item: storage::MandateMigrationItem,
+    error: &errors::ApiErrorResponse,
+) -> storage::MandateMigrationItem {
+    item.status = enums::MandateMigrationItemStatus::Failed;
+    item.error_code = Some(error.error_code());
+    item.error_message = Some(error.error_message());
+    item
+}
+
+/// Moves the mandate to the target merchant connector account with its reference at the new
+/// connector. The payment method of the mandate is kept, a mandate migrated with only a network
+/// transaction id has no connector mandate id at the new connector.
+async fn migrate_mandate(
+    db: &dyn StorageInterface,
+    mandate: &storage::Mandate,
+    item: &storage::MandateMigrationItem,
+    target: &MigrationTarget,
+) -> RouterResult<storage::Mandate> {
+    if item.new_connector_mandate_id.is_none() && item.network_transaction_id.is_none() {
+        Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "new_connector_mandate_id"
+        }))?
+    }
+
+    let connector_mandate_ids = item
+        .new_connector_mandate_id
+        .clone()
+        .map(|connector_mandate_id| {
+            let payment_method_id = mandate
+                .connector_mandate_ids
+                .clone()
+                .and_then(|connector_mandate_ids| {
+                    connector_mandate_ids
+                        .parse_value::<types::MandateReference>("MandateReference")
+                        .ok()
+                })
+                .and_then(|mandate_reference| mandate_reference.payment_method_id);
+            Encode::<types::MandateReference>::encode_to_value(&types::MandateReference {
+                connector_mandate_id: Some(connector_mandate_id),
+                payment_method_id,
+            })
+            .change_context(errors::ApiErrorResponse::MandateSerializationFailed)
+            .map(masking::Secret::new)
+        })
+        .transpose()?;
+
+    db.update_mandate_by_merchant_id_mandate_id(
+        &mandate.merchant_id,
+        &mandate.mandate_id,
+        storage::MandateUpdate::ConnectorMigrationUpdate {
+            connector: target.connector_name.clone(),
+            merchant_connector_id: Some(target.merchant_connector_id.clone()),
+            connector_mandate_ids,
+            network_transaction_id: item.network_transaction_id.clone(),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::MandateUpdateFailed)
+}
+
+async fn revert_mandate(
+    db: &dyn StorageInterface,
+    mandate: &storage::Mandate,
+) -> RouterResult<storage::Mandate> {
+    db.update_mandate_by_merchant_id_mandate_id(
+        &mandate.merchant_id,
+        &mandate.mandate_id,
+        storage::MandateUpdate::ConnectorMigrationUpdate {
+            connector: mandate.connector.clone(),
+            merchant_connector_id: mandate.merchant_connector_id.clone(),
+            connector_mandate_ids: mandate.connector_mandate_ids.clone(),
+            network_transaction_id: mandate.network_transaction_id.clone(),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::MandateUpdateFailed)
+}
+
+/// The connector tokens of the payment method, by connector name
+fn get_connector_tokens(payment_method: &storage::PaymentMethod) -> HashMap<String, String> {
+    payment_method
+        .metadata
+        .clone()
+        .and_then(|metadata| {
+            metadata
+                .expose()
+                .parse_value::<PaymentMethodMetadata>("PaymentMethodMetadata")
+                .ok()
+        })
+        .map(|metadata| metadata.payment_method_tokenization)
+        .unwrap_or_default()
+}
+
+fn encode_connector_tokens(
+    connector_tokens: HashMap<String, String>,
+) -> RouterResult<serde_json::Value> {
+    Encode::<PaymentMethodMetadata>::encode_to_value(&PaymentMethodMetadata {
+        payment_method_tokenization: connector_tokens,
+    })
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to serialize the connector tokens of the payment method")
+}
+
+/// Moves the connector token of the payment method of the mandate from the source connector to
+/// the new reference of the mandate at the target connector, so that the saved payment method is
+/// no longer used through the source connector. Returns the payment method as it was before, to
+/// be restored if the migrated mandate is moved back. A mandate whose payment method was deleted
+/// has nothing to move.
+async fn repoint_payment_method(
+    db: &dyn StorageInterface,
+    mandate: &storage::Mandate,
+    item: &storage::MandateMigrationItem,
+    target: &MigrationTarget,
+) -> RouterResult<Option<storage::PaymentMethod>> {
+    let payment_method = match db.find_payment_method(&mandate.payment_method_id).await {
+        Ok(payment_method) => payment_method,
+        Err(error)
+            if error.current_context().is_db_not_found()
+                || matches!(
+                    error.current_context(),
+                    errors::StorageError::ValueNotFound(_)
+                ) =>
+        {
+            return Ok(None)
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while finding the payment method of the mandate")?,
+    };
+
+    let mut connector_tokens = get_connector_tokens(&payment_method);
+    connector_tokens.remove(&mandate.connector);
+    if let Some(new_connector_mandate_id) = item.new_connector_mandate_id.clone() {
+        connector_tokens.insert(target.connector_name.clone(), new_connector_mandate_id);
+    }
+    let metadata = encode_connector_tokens(connector_tokens)?;
+
+    db.update_payment_method(
+        payment_method.clone(),
+        storage::PaymentMethodUpdate::MetadataUpdate {
+            metadata: Some(metadata),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while updating the payment method of the mandate")?;
+    Ok(Some(payment_method))
+}
+
+async fn restore_payment_method(
+    db: &dyn StorageInterface,
+    payment_method: storage::PaymentMethod,
+) -> RouterResult<storage::PaymentMethod> {
+    let metadata = match payment_method.metadata.clone() {
+        Some(metadata) => metadata.expose(),
+        None => encode_connector_tokens(HashMap::new())?,
+    };
+    db.update_payment_method(
+        payment_method,
+        storage::PaymentMethodUpdate::MetadataUpdate {
+            metadata: Some(metadata),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while restoring the payment method of the mandate")
+}
+
+/// Verifies the migrated mandate with a charge of 0 through the new connector, in the currency of
+/// the mandate
+async fn verify_migrated_mandate(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connector_name: &str,
+    profile_id: Option<String>,
+    mandate: storage::Mandate,
+) -> RouterResult<MandateVerification> {
+    let currency =
+        mandate
+            .mandate_currency
+            .ok_or(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "mandate has no currency to be verified in".to_string(),
+            }))?;
+    let connector = api_models::enums::Connector::from_str(connector_name)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("unable to parse connector name {connector_name}"))?;
+
+    let payments_request = payments_api::PaymentsRequest {
+        amount: Some(payments_api::Amount::Zero),
+        currency: Some(currency),
+        confirm: Some(true),
+        off_session: Some(true),
+        customer_id: Some(mandate.customer_id),
+        mandate_id: Some(mandate.mandate_id),
+        connector: Some(vec![connector]),
+        profile_id,
+        authentication_type: Some(api_models::enums::AuthenticationType::NoThreeDs),
+        description: Some("Verification of the migrated mandate".to_string()),
+        ..Default::default()
+    };
+
+    let response =
+        payment_flows::payments_core::<api::Authorize, payments_api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            payment_flows::operations::PaymentCreate::default(),
+            payments_request,
+            services::AuthFlow::Merchant,
+            payment_flows::CallConnectorAction::Trigger,
+            payments_api::HeaderPayload::default(),
+        )
+        .await?;
+
+    let services::ApplicationResponse::JsonWithHeaders((payment, _)) = response else {
+        Err(report!(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable("Unexpected response for the verification of the migrated mandate")?
+    };
+    Ok(match payment.status {
+        api_models::enums::IntentStatus::Succeeded
+        | api_models::enums::IntentStatus::RequiresCapture => MandateVerification::Verified,
+        _ => MandateVerification::Declined {
+            error_code: payment.error_code,
+            error_message: payment.error_message,
+        },
+    })
+}
+
+pub fn get_mandate_migration_response(
+    mandate_migration: storage::MandateMigration,
+) -> RouterResult<mandates_api::MandateMigrationResponse> {
+    let mandates = decode_items(&mandate_migration)?
+        .into_iter()
+        .map(|item| mandates_api::MandateMigrationItemResponse {
+            connector_mandate_id: item.connector_mandate_id,
+            mandate_id: item.mandate_id,
+            status: item.status,
+            error_code: item.error_code,
+            error_message: item.error_message,
+        })
+        .collect();
+
+    Ok(mandates_api::MandateMigrationResponse {
+        migration_id: mandate_migration.migration_id,
+        source_merchant_connector_id: mandate_migration.source_merchant_connector_id,
+        target_merchant_connector_id: mandate_migration.target_merchant_connector_id,
+        verify: mandate_migration.verify,
+        status: mandate_migration.status,
+        mandates,
+        created_at: mandate_migration.created_at,
+        modified_at: mandate_migration.modified_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::db::MockDb;
+
+    async fn insert_mandate(db: &MockDb, mandate_id: &str, connector_mandate_id: &str) {
+        let connector_mandate_ids = serde_json::json!({
+            "connector_mandate_id": connector_mandate_id,
+            "payment_method_id": "pm_stripe_1",
+        });
+        db.insert_mandate(storage::MandateNew {
+            mandate_id: mandate_id.to_string(),
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            payment_method_id: format!("pm_{mandate_id}"),
+            connector: "stripe".to_string(),
+            connector_mandate_ids: Some(masking::Secret::new(connector_mandate_ids)),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    }
+
+    async fn insert_payment_method(db: &MockDb, payment_method_id: &str, connector_token: &str) {
+        db.insert_payment_method(storage::PaymentMethodNew {
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            payment_method_id: payment_method_id.to_string(),
+            metadata: Some(masking::Secret::new(serde_json::json!({
+                "stripe": connector_token,
+            }))),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    }
+
+    async fn get_connector_tokens_of(
+        db: &MockDb,
+        payment_method_id: &str,
+    ) -> HashMap<String, String> {
+        get_connector_tokens(&db.find_payment_method(payment_method_id).await.unwrap())
+    }
+
+    fn get_item(
+        connector_mandate_id: &str,
+        new_connector_mandate_id: Option<&str>,
+        network_transaction_id: Option<&str>,
+    ) -> storage::MandateMigrationItem {
+        storage::MandateMigrationItem {
+            connector_mandate_id: connector_mandate_id.to_string(),
+            new_connector_mandate_id: new_connector_mandate_id.map(ToString::to_string),
+            network_transaction_id: network_transaction_id.map(ToString::to_string),
+            mandate_id: None,
+            status: enums::MandateMigrationItemStatus::Pending,
+            error_code: None,
+            error_message: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mandates_are_migrated_independently() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        for (mandate_id, connector_mandate_id) in [
+            ("man_1", "seti_1"),
+            ("man_2", "seti_2"),
+            ("man_3", "seti_3"),
+            ("man_4", "seti_4"),
+        ] {
+            insert_mandate(&db, mandate_id, connector_mandate_id).await;
+        }
+        let mandates =
+            get_mandates_by_connector_mandate_id(&db, "merchant_1", "stripe", "mca_stripe")
+                .await
+                .unwrap();
+        let target = MigrationTarget {
+            connector_name: "adyen".to_string(),
+            merchant_connector_id: "mca_adyen".to_string(),
+        };
+        let items = vec![
+            get_item("seti_1", Some("8835511112385671"), None),
+            get_item("seti_2", None, Some("ntid_2")),
+            get_item("seti_3", Some("8835511112385673"), None),
+            get_item("seti_4", None, None),
+            get_item("seti_unknown", Some("8835511112385675"), None),
+        ];
+
+        let items =
+            migrate_mandate_items(&db, items, &mandates, &target, true, |mandate| async move {
+                Ok(if mandate.mandate_id == "man_3" {
+                    MandateVerification::Declined {
+                        error_code: Some("refused".to_string()),
+                        error_message: Some("Refused".to_string()),
+                    }
+                } else {
+                    MandateVerification::Verified
+                })
+            })
+            .await;
+
+        let statuses = items.iter().map(|item| item.status).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                enums::MandateMigrationItemStatus::Migrated,
+                enums::MandateMigrationItemStatus::Migrated,
+                enums::MandateMigrationItemStatus::Failed,
+                enums::MandateMigrationItemStatus::Failed,
+                enums::MandateMigrationItemStatus::Failed,
+            ]
+        );
+        assert_eq!(items[2].error_code.as_deref(), Some("refused"));
+        assert_eq!(items[4].mandate_id, None);
+
+        let mandate = db
+            .find_mandate_by_merchant_id_mandate_id("merchant_1", "man_1")
+            .await
+            .unwrap();
+        assert_eq!(mandate.connector, "adyen");
+        assert_eq!(mandate.merchant_connector_id.as_deref(), Some("mca_adyen"));
+        let mandate_reference = mandate
+            .connector_mandate_ids
+            .unwrap()
+            .parse_value::<types::MandateReference>("MandateReference")
+            .unwrap();
+        assert_eq!(
+            mandate_reference.connector_mandate_id.as_deref(),
+            Some("8835511112385671")
+        );
+        assert_eq!(
+            mandate_reference.payment_method_id.as_deref(),
+            Some("pm_stripe_1")
+        );
+
+        let mandate = db
+            .find_mandate_by_merchant_id_mandate_id("merchant_1", "man_2")
+            .await
+            .unwrap();
+        assert!(mandate.connector_mandate_ids.is_none());
+        assert_eq!(mandate.network_transaction_id.as_deref(), Some("ntid_2"));
+
+        // The mandates which were not migrated stay on the source connector
+        for (mandate_id, connector_mandate_id) in [("man_3", "seti_3"), ("man_4", "seti_4")] {
+            let mandate = db
+                .find_mandate_by_merchant_id_mandate_id("merchant_1", mandate_id)
+                .await
+                .unwrap();
+            assert_eq!(mandate.connector, "stripe");
+            assert_eq!(mandate.merchant_connector_id, None);
+            assert_eq!(
+                get_connector_mandate_id(&mandate).as_deref(),
+                Some(connector_mandate_id)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrated_mandates_are_not_migrated_again() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let target = MigrationTarget {
+            connector_name: "adyen".to_string(),
+            merchant_connector_id: "mca_adyen".to_string(),
+        };
+        let item = storage::MandateMigrationItem {
+            mandate_id: Some("man_1".to_string()),
+            status: enums::MandateMigrationItemStatus::Migrated,
+            ..get_item("seti_1", Some("8835511112385671"), None)
+        };
+
+        let items = migrate_mandate_items(
+            &db,
+            vec![item.clone()],
+            &HashMap::new(),
+            &target,
+            true,
+            |_| async { Ok(MandateVerification::Verified) },
+        )
+        .await;
+
+        assert_eq!(items, vec![item]);
+    }
+
+    #[tokio::test]
+    async fn test_payment_methods_of_migrated_mandates_are_moved_to_the_target_connector() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        for (mandate_id, connector_mandate_id) in [("man_1", "seti_1"), ("man_2", "seti_2")] {
+            insert_mandate(&db, mandate_id, connector_mandate_id).await;
+            insert_payment_method(&db, &format!("pm_{mandate_id}"), connector_mandate_id).await;
+        }
+        let mandates =
+            get_mandates_by_connector_mandate_id(&db, "merchant_1", "stripe", "mca_stripe")
+                .await
+                .unwrap();
+        let target = MigrationTarget {
+            connector_name: "adyen".to_string(),
+            merchant_connector_id: "mca_adyen".to_string(),
+        };
+        let items = vec![
+            get_item("seti_1", Some("8835511112385671"), None),
+            get_item("seti_2", Some("8835511112385672"), None),
+        ];
+
+        migrate_mandate_items(&db, items, &mandates, &target, true, |mandate| async move {
+            Ok(if mandate.mandate_id == "man_2" {
+                MandateVerification::Declined {
+                    error_code: Some("refused".to_string()),
+                    error_message: Some("Refused".to_string()),
+                }
+            } else {
+                MandateVerification::Verified
+            })
+        })
+        .await;
+
+        assert_eq!(
+            get_connector_tokens_of(&db, "pm_man_1").await,
+            HashMap::from([("adyen".to_string(), "8835511112385671".to_string())])
+        );
+        // The payment method of the mandate which was moved back stays on the source connector
+        assert_eq!(
+            get_connector_tokens_of(&db, "pm_man_2").await,
+            HashMap::from([("stripe".to_string(), "seti_2".to_string())])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outcome_of_mandates_is_saved_after_every_batch() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let mandate_count = consts::MANDATE_MIGRATION_BATCH_SIZE + 1;
+        let mut items = Vec::with_capacity(mandate_count);
+        for index in 0..mandate_count {
+            let connector_mandate_id = format!("seti_{index}");
+            insert_mandate(&db, &format!("man_{index}"), &connector_mandate_id).await;
+            items.push(get_item(
+                &connector_mandate_id,
+                Some("8835511112385671"),
+                None,
+            ));
+        }
+        let mandates =
+            get_mandates_by_connector_mandate_id(&db, "merchant_1", "stripe", "mca_stripe")
+                .await
+                .unwrap();
+        let target = MigrationTarget {
+            connector_name: "adyen".to_string(),
+            merchant_connector_id: "mca_adyen".to_string(),
+        };
+        let now = date_time::now();
+        let mandate_migration = db
+            .insert_mandate_migration(storage::MandateMigrationNew {
+                migration_id: "mig_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                source_merchant_connector_id: "mca_stripe".to_string(),
+                target_merchant_connector_id: "mca_adyen".to_string(),
+                verify: true,
+                status: enums::MandateMigrationStatus::Pending,
+                items: encode_items(items).unwrap(),
+                created_at: now,
+                modified_at: now,
+            })
+            .await
+            .unwrap();
+        let last_mandate_id = format!("man_{}", consts::MANDATE_MIGRATION_BATCH_SIZE);
+
+        // The mandate of the second batch is verified only if the first batch is already saved
+        let mandate_migration =
+            migrate_mandates_in_batches(&db, mandate_migration, &mandates, &target, |mandate| {
+                let db = &db;
+                let is_last_mandate = mandate.mandate_id == last_mandate_id;
+                async move {
+                    if !is_last_mandate {
+                        return Ok(MandateVerification::Verified);
+                    }
+                    let saved_mandate_migration = db
+                        .find_mandate_migration_by_merchant_id_migration_id("merchant_1", "mig_1")
+                        .await
+                        .unwrap();
+                    let saved_items = decode_items(&saved_mandate_migration).unwrap();
+                    Ok(
+                        if saved_items[..consts::MANDATE_MIGRATION_BATCH_SIZE]
+                            .iter()
+                            .all(|item| item.status == enums::MandateMigrationItemStatus::Migrated)
+                        {
+                            MandateVerification::Verified
+                        } else {
+                            MandateVerification::Declined {
+                                error_code: None,
+                                error_message: None,
+                            }
+                        },
+                    )
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mandate_migration.status,
+            enums::MandateMigrationStatus::Completed
+        );
+        assert!(decode_items(&mandate_migration)
+            .unwrap()
+            .iter()
+            .all(|item| item.status == enums::MandateMigrationItemStatus::Migrated));
+    }
+}
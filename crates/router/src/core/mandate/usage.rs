@@ -0,0 +1,196 @@
+//! Tracking of the charges made against a mandate in the calendar periods of its frequency. The
+//! periods are in UTC, weeks start on Monday.
+
+use time::{Duration, PrimitiveDateTime};
+
+use crate::types::storage::{self, enums as storage_enums};
+
+/// Start of the period of the frequency in which the time falls
+pub fn get_period_start(
+    frequency: storage_enums::MandateFrequency,
+    at: PrimitiveDateTime,
+) -> PrimitiveDateTime {
+    let date = at.date();
+    let days_since_period_start = match frequency {
+        storage_enums::MandateFrequency::Daily => 0,
+        storage_enums::MandateFrequency::Weekly => {
+            i64::from(date.weekday().number_days_from_monday())
+        }
+        storage_enums::MandateFrequency::Monthly => i64::from(date.day()) - 1,
+        storage_enums::MandateFrequency::Yearly => i64::from(date.ordinal()) - 1,
+    };
+    (date - Duration::days(days_since_period_start)).midnight()
+}
+
+/// Start of the period following the period in which the time falls
+pub fn get_next_period_start(
+    frequency: storage_enums::MandateFrequency,
+    at: PrimitiveDateTime,
+) -> PrimitiveDateTime {
+    // The number of days is enough to reach the next period from the start of any period, but
+    // not to skip over it
+    let days_in_longest_period = match frequency {
+        storage_enums::MandateFrequency::Daily => 1,
+        storage_enums::MandateFrequency::Weekly => 7,
+        storage_enums::MandateFrequency::Monthly => 31,
+        storage_enums::MandateFrequency::Yearly => 366,
+    };
+    get_period_start(
+        frequency,
+        get_period_start(frequency, at) + Duration::days(days_in_longest_period),
+    )
+}
+
+/// Number of charges made against the mandate in the current period, the count of a past period
+/// no longer applies
+pub fn get_period_charge_count(mandate: &storage::Mandate, now: PrimitiveDateTime) -> i32 {
+    match (mandate.frequency, mandate.period_started_at) {
+        (Some(frequency), Some(period_started_at))
+            if period_started_at == get_period_start(frequency, now) =>
+        {
+            mandate.period_charge_count
+        }
+        _ => 0,
+    }
+}
+
+/// Time from which the mandate can be charged again, `None` if the mandate can be charged now
+pub fn get_next_charge_allowed_at(
+    mandate: &storage::Mandate,
+    now: PrimitiveDateTime,
+) -> Option<PrimitiveDateTime> {
+    let frequency = mandate.frequency?;
+    let max_charges_per_period = mandate.max_charges_per_period?;
+    (get_period_charge_count(mandate, now) >= max_charges_per_period)
+        .then(|| get_next_period_start(frequency, now))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::db::{mandate::MandateInterface, MockDb};
+
+    #[test]
+    fn test_period_start_is_calendar_aligned() {
+        // Wednesday
+        let at = datetime!(2023-11-15 17:45:10);
+
+        assert_eq!(
+            get_period_start(storage_enums::MandateFrequency::Daily, at),
+            datetime!(2023-11-15 00:00:00)
+        );
+        assert_eq!(
+            get_period_start(storage_enums::MandateFrequency::Weekly, at),
+            datetime!(2023-11-13 00:00:00)
+        );
+        assert_eq!(
+            get_period_start(storage_enums::MandateFrequency::Monthly, at),
+            datetime!(2023-11-01 00:00:00)
+        );
+        assert_eq!(
+            get_period_start(storage_enums::MandateFrequency::Yearly, at),
+            datetime!(2023-01-01 00:00:00)
+        );
+    }
+
+    #[test]
+    fn test_next_period_start() {
+        assert_eq!(
+            get_next_period_start(
+                storage_enums::MandateFrequency::Daily,
+                datetime!(2023-12-31 23:59:59)
+            ),
+            datetime!(2024-01-01 00:00:00)
+        );
+        assert_eq!(
+            get_next_period_start(
+                storage_enums::MandateFrequency::Weekly,
+                datetime!(2023-11-19 10:00:00)
+            ),
+            datetime!(2023-11-20 00:00:00)
+        );
+        assert_eq!(
+            get_next_period_start(
+                storage_enums::MandateFrequency::Monthly,
+                datetime!(2024-01-31 10:00:00)
+            ),
+            datetime!(2024-02-01 00:00:00)
+        );
+        assert_eq!(
+            get_next_period_start(
+                storage_enums::MandateFrequency::Monthly,
+                datetime!(2024-02-29 10:00:00)
+            ),
+            datetime!(2024-03-01 00:00:00)
+        );
+        assert_eq!(
+            get_next_period_start(
+                storage_enums::MandateFrequency::Yearly,
+                datetime!(2024-06-15 10:00:00)
+            ),
+            datetime!(2025-01-01 00:00:00)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_charges_over_period_limit_are_not_reserved() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        db.insert_mandate(storage::MandateNew {
+            mandate_id: "man_1".to_string(),
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            frequency: Some(storage_enums::MandateFrequency::Monthly),
+            max_charges_per_period: Some(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let november = Some(datetime!(2023-11-01 00:00:00));
+
+        assert!(db
+            .reserve_mandate_charge("merchant_1", "man_1", november, true)
+            .await
+            .unwrap());
+        assert!(!db
+            .reserve_mandate_charge("merchant_1", "man_1", november, true)
+            .await
+            .unwrap());
+
+        // A charge which was not made no longer counts against the limit
+        db.release_mandate_charge("merchant_1", "man_1", november)
+            .await
+            .unwrap();
+        assert!(db
+            .reserve_mandate_charge("merchant_1", "man_1", november, true)
+            .await
+            .unwrap());
+
+        // The limit is not enforced for an overridden charge, and a new period resets the count
+        assert!(db
+            .reserve_mandate_charge("merchant_1", "man_1", november, false)
+            .await
+            .unwrap());
+        assert!(db
+            .reserve_mandate_charge(
+                "merchant_1",
+                "man_1",
+                Some(datetime!(2023-12-01 00:00:00)),
+                true
+            )
+            .await
+            .unwrap());
+
+        let mandate = db
+            .find_mandate_by_merchant_id_mandate_id("merchant_1", "man_1")
+            .await
+            .unwrap();
+        assert_eq!(mandate.charge_count, 3);
+        assert_eq!(mandate.period_charge_count, 1);
+    }
+}
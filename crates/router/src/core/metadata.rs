@@ -0,0 +1,264 @@
+//! Limits on the metadata which merchants attach to payments and merchant connector accounts, and
+//! the selection of the payment metadata forwarded to each connector. The limits are checked only
+//! when the metadata is set through the API, metadata stored before the limits were lowered is
+//! read as it is.
+
+use common_utils::pii;
+use error_stack::report;
+use masking::{PeekInterface, Secret};
+
+use super::errors::{self, RouterResult};
+use crate::configs::settings::MerchantMetadataConfig;
+
+/// Rejects metadata which exceeds the configured limits, the error lists the keys which have to be
+/// removed or shortened
+pub fn validate_metadata(
+    config: &MerchantMetadataConfig,
+    field_name: &str,
+    metadata: &pii::SecretSerdeValue,
+) -> RouterResult<()> {
+    let metadata = metadata.peek();
+    let invalid_metadata = |message: String| {
+        report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{field_name}` {message}"),
+        })
+    };
+
+    let size = metadata.to_string().len();
+    if size > config.max_size_in_bytes {
+        return Err(invalid_metadata(format!(
+            "is {size} bytes, exceeding the limit of {} bytes, largest keys: [{}]",
+            config.max_size_in_bytes,
+            get_largest_keys(metadata, size - config.max_size_in_bytes).join(", ")
+        )));
+    }
+
+    if get_depth(metadata) > config.max_depth {
+        let too_deep_keys = get_top_level_entries(metadata)
+            .filter(|(_, value)| get_depth(value) >= config.max_depth)
+            .map(|(key, _)| key.to_owned())
+            .collect::<Vec<_>>();
+        return Err(invalid_metadata(format!(
+            "is nested deeper than {} levels, offending keys: [{}]",
+            config.max_depth,
+            too_deep_keys.join(", ")
+        )));
+    }
+
+    let key_count = get_top_level_entries(metadata).count();
+    if key_count > config.max_keys {
+        return Err(invalid_metadata(format!(
+            "has {key_count} keys, exceeding the limit of {} keys",
+            config.max_keys
+        )));
+    }
+
+    let mut long_keys = Vec::new();
+    collect_long_keys(metadata, config.max_key_length, None, &mut long_keys);
+    if !long_keys.is_empty() {
+        return Err(invalid_metadata(format!(
+            "has keys longer than {} characters, offending keys: [{}]",
+            config.max_key_length,
+            long_keys.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Payment metadata to be sent to the connector, made of only the keys allowed for the connector
+pub fn get_connector_pass_through_metadata(
+    config: &MerchantMetadataConfig,
+    connector_name: &str,
+    metadata: Option<&pii::SecretSerdeValue>,
+) -> Option<pii::SecretSerdeValue> {
+    let allowed_keys = config.connector_pass_through_keys.get(connector_name)?;
+    let metadata = metadata?.peek().as_object()?;
+    let pass_through_metadata = metadata
+        .iter()
+        .filter(|(key, _)| allowed_keys.contains(key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<serde_json::Map<_, _>>();
+
+    (!pass_through_metadata.is_empty())
+        .then(|| Secret::new(serde_json::Value::Object(pass_through_metadata)))
+}
+
+fn get_top_level_entries(
+    metadata: &serde_json::Value,
+) -> impl Iterator<Item = (&String, &serde_json::Value)> {
+    metadata.as_object().into_iter().flatten()
+}
+
+/// Levels of objects and arrays in the value, a scalar has no levels
+fn get_depth(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Object(map) => 1 + map.values().map(get_depth).max().unwrap_or(0),
+        serde_json::Value::Array(values) => 1 + values.iter().map(get_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Largest top level keys, enough of them to bring the metadata within the size limit if they were
+/// removed
+fn get_largest_keys(metadata: &serde_json::Value, excess_size: usize) -> Vec<String> {
+    let mut entry_sizes = get_top_level_entries(metadata)
+        .map(|(key, value)| (key, key.len() + value.to_string().len()))
+        .collect::<Vec<_>>();
+    entry_sizes.sort_by(|(_, size), (_, other_size)| other_size.cmp(size));
+
+    let mut removed_size = 0;
+    entry_sizes
+        .into_iter()
+        .take_while(|(_, size)| {
+            let is_within_limit = removed_size >= excess_size;
+            removed_size += size;
+            !is_within_limit
+        })
+        .map(|(key, _)| key.to_owned())
+        .collect()
+}
+
+/// Paths of the keys longer than the limit, at any level of the metadata
+fn collect_long_keys(
+    value: &serde_json::Value,
+    max_key_length: usize,
+    path: Option<&str>,
+    long_keys: &mut Vec<String>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key_path = path.map_or_else(|| key.to_owned(), |path| format!("{path}.{key}"));
+                if key.chars().count() > max_key_length {
+                    long_keys.push(key_path.clone());
+                }
+                collect_long_keys(value, max_key_length, Some(&key_path), long_keys);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                let index_path = format!("{}[{index}]", path.unwrap_or_default());
+                collect_long_keys(value, max_key_length, Some(&index_path), long_keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+
+    fn get_config() -> MerchantMetadataConfig {
+        MerchantMetadataConfig {
+            max_size_in_bytes: 256,
+            max_keys: 4,
+            max_key_length: 16,
+            max_depth: 3,
+            connector_pass_through_keys: HashMap::from([(
+                "stripe".to_string(),
+                HashSet::from(["order_reference".to_string()]),
+            )]),
+        }
+    }
+
+    fn get_error_message(metadata: serde_json::Value) -> String {
+        match validate_metadata(&get_config(), "metadata", &Secret::new(metadata))
+            .unwrap_err()
+            .current_context()
+        {
+            errors::ApiErrorResponse::InvalidRequestData { message } => message.clone(),
+            error => panic!("Unexpected error {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_metadata_within_limits_is_accepted() {
+        let metadata = serde_json::json!({
+            "order_reference": "ord_123",
+            "customer": { "tier": "gold", "tags": ["new", "web"] },
+        });
+
+        assert!(validate_metadata(&get_config(), "metadata", &Secret::new(metadata)).is_ok());
+    }
+
+    #[test]
+    fn test_oversized_metadata_is_rejected_with_the_largest_keys() {
+        let message = get_error_message(serde_json::json!({
+            "order_reference": "ord_123",
+            "blob": "x".repeat(400),
+            "notes": "y".repeat(100),
+        }));
+
+        assert!(message.starts_with("`metadata` is "));
+        assert!(message.ends_with("largest keys: [blob]"));
+    }
+
+    #[test]
+    fn test_nesting_beyond_the_depth_limit_is_rejected() {
+        let message = get_error_message(serde_json::json!({
+            "shallow": { "level": 2 },
+            "deep": { "level_2": { "level_3": { "level_4": true } } },
+        }));
+
+        assert_eq!(
+            message,
+            "`metadata` is nested deeper than 3 levels, offending keys: [deep]"
+        );
+
+        let message = get_error_message(serde_json::json!({
+            "items": [[["too deep"]]],
+        }));
+        assert!(message.ends_with("offending keys: [items]"));
+    }
+
+    #[test]
+    fn test_key_count_and_key_length_limits() {
+        let message = get_error_message(serde_json::json!({
+            "a": 1, "b": 2, "c": 3, "d": 4, "e": 5,
+        }));
+        assert_eq!(
+            message,
+            "`metadata` has 5 keys, exceeding the limit of 4 keys"
+        );
+
+        let message = get_error_message(serde_json::json!({
+            "customer": { "a_very_long_key_name": true },
+            "items": [{ "another_long_key_name": 1 }],
+        }));
+        assert_eq!(
+            message,
+            "`metadata` has keys longer than 16 characters, offending keys: [customer.a_very_long_key_name, items[0].another_long_key_name]"
+        );
+    }
+
+    #[test]
+    fn test_only_allowed_keys_are_passed_to_the_connector() {
+        let config = get_config();
+        let metadata = Secret::new(serde_json::json!({
+            "order_reference": "ord_123",
+            "internal_notes": "not for the connector",
+        }));
+
+        let stripe_metadata =
+            get_connector_pass_through_metadata(&config, "stripe", Some(&metadata)).unwrap();
+        assert_eq!(
+            stripe_metadata.peek(),
+            &serde_json::json!({ "order_reference": "ord_123" })
+        );
+        assert!(get_connector_pass_through_metadata(&config, "adyen", Some(&metadata)).is_none());
+        assert!(get_connector_pass_through_metadata(
+            &config,
+            "stripe",
+            Some(&Secret::new(
+                serde_json::json!({ "internal_notes": "local" })
+            ))
+        )
+        .is_none());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod card_fingerprint_backfill;
 pub mod cards;
+pub mod list_cache;
 pub mod transformers;
 pub mod vault;
@@ -0,0 +1,167 @@
+//! Backfill of the fingerprints of the cards saved before the cards were fingerprinted. The job is
+//! scheduled once, when the producer of the scheduler first starts, and a task of the job
+//! fingerprints a batch of the cards, in the order of their payment methods, before scheduling the
+//! task of the next batch. The cards which cannot be read from the locker are left without a
+//! fingerprint, and are listed as they are.
+
+use common_utils::date_time;
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+use scheduler::{db::process_tracker::ProcessTrackerExt, utils as pt_utils};
+
+use super::cards;
+use crate::{
+    core::errors::{self, RouterResult, StorageErrorExt},
+    routes::AppState,
+    types::storage,
+};
+
+pub const CARD_FINGERPRINT_BACKFILL_WORKFLOW_RUNNER: &str = "CARD_FINGERPRINT_BACKFILL_WORKFLOW";
+const CARD_FINGERPRINT_BACKFILL_WORKFLOW_TASK: &str = "CARD_FINGERPRINT_BACKFILL";
+
+/// The job covers the payment methods of all the merchants
+const CARD_FINGERPRINT_BACKFILL_SCOPE: &str = "all_merchants";
+
+const CARD_FINGERPRINT_BACKFILL_BATCH_SIZE: i64 = 100;
+
+// Tracking data by process_tracker
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CardFingerprintBackfillTrackingData {
+    /// The payment methods up to this id have been processed
+    pub after_id: i32,
+    /// Number of the task within the job
+    pub task_number: u32,
+    /// Cards fingerprinted by the job so far
+    pub fingerprinted_cards: usize,
+}
+
+async fn add_card_fingerprint(
+    state: &AppState,
+    payment_method: storage::PaymentMethod,
+) -> RouterResult<storage::PaymentMethod> {
+    let card = cards::get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        cards::get_locker_id(&payment_method),
+    )
+    .await?;
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            &payment_method.merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let card_fingerprint = cards::get_card_fingerprint(
+        &*state.store,
+        &payment_method.merchant_id,
+        &key_store,
+        &card.card_number,
+    )
+    .await?;
+    state
+        .store
+        .update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::CardFingerprintUpdate {
+                card_fingerprint: Some(card_fingerprint),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the card fingerprint of the payment method")
+}
+
+/// Fingerprints the cards of a batch of the payment methods, and records the progress of the job
+/// in the tracking data. Returns whether the job is complete.
+#[instrument(skip_all)]
+pub async fn execute_card_fingerprint_backfill_task(
+    state: &AppState,
+    tracking_data: &mut CardFingerprintBackfillTrackingData,
+) -> RouterResult<bool> {
+    let payment_methods = state
+        .store
+        .find_card_payment_methods_without_fingerprint(
+            tracking_data.after_id,
+            CARD_FINGERPRINT_BACKFILL_BATCH_SIZE,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find the payment methods without a card fingerprint")?;
+    let is_job_complete = i64::try_from(payment_methods.len())
+        .map_or(false, |count| count < CARD_FINGERPRINT_BACKFILL_BATCH_SIZE);
+
+    for payment_method in payment_methods {
+        tracking_data.after_id = tracking_data.after_id.max(payment_method.id);
+        match add_card_fingerprint(state, payment_method).await {
+            Ok(_) => {
+                tracking_data.fingerprinted_cards =
+                    tracking_data.fingerprinted_cards.saturating_add(1);
+            }
+            Err(error) => logger::error!(?error, "Failed to fingerprint the saved card"),
+        }
+    }
+
+    logger::info!(
+        task_number = tracking_data.task_number,
+        fingerprinted_cards = tracking_data.fingerprinted_cards,
+        is_job_complete,
+        "Processed the task of the card fingerprint backfill"
+    );
+    Ok(is_job_complete)
+}
+
+async fn add_card_fingerprint_backfill_task(
+    state: &AppState,
+    tracking_data: CardFingerprintBackfillTrackingData,
+) -> RouterResult<()> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        CARD_FINGERPRINT_BACKFILL_WORKFLOW_RUNNER,
+        CARD_FINGERPRINT_BACKFILL_WORKFLOW_TASK,
+        &tracking_data.task_number.to_string(),
+        CARD_FINGERPRINT_BACKFILL_SCOPE,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        CARD_FINGERPRINT_BACKFILL_WORKFLOW_TASK,
+        CARD_FINGERPRINT_BACKFILL_WORKFLOW_RUNNER,
+        tracking_data,
+        date_time::now(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct card fingerprint backfill process tracker task")?;
+
+    match state.store.insert_process(process_tracker_entry).await {
+        Ok(_) => Ok(()),
+        // The task has already been scheduled, by another start of the producer
+        Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert card fingerprint backfill process tracker task"),
+    }
+}
+
+/// Schedules the task of the next batch when the job is not complete
+pub async fn add_next_card_fingerprint_backfill_task(
+    state: &AppState,
+    tracking_data: CardFingerprintBackfillTrackingData,
+    is_job_complete: bool,
+) -> RouterResult<()> {
+    if is_job_complete {
+        return Ok(());
+    }
+
+    let next_tracking_data = CardFingerprintBackfillTrackingData {
+        task_number: tracking_data.task_number.saturating_add(1),
+        ..tracking_data
+    };
+    add_card_fingerprint_backfill_task(state, next_tracking_data).await
+}
+
+/// Schedules the first task of the job. The id of the task is fixed, so that the job runs once
+/// however many times the producer of the scheduler starts.
+pub async fn schedule_card_fingerprint_backfill(state: &AppState) -> RouterResult<()> {
+    add_card_fingerprint_backfill_task(state, CardFingerprintBackfillTrackingData::default()).await
+}
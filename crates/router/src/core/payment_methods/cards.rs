@@ -25,13 +25,18 @@ use router_env::{instrument, tracing};
 
 use crate::{
     configs::settings,
+    connector::utils::{
+        capabilities, get_saved_card_cvc_support, SavedCardCvcSupport, SavedCardFlow,
+    },
     core::{
+        blocklist::utils as blocklist_utils,
         errors::{self, StorageErrorExt},
         payment_methods::{
+            list_cache,
             transformers::{self as payment_methods},
             vault,
         },
-        payments::helpers,
+        payments::{eligibility, helpers},
     },
     db, logger,
     pii::prelude::*,
@@ -62,6 +67,7 @@ pub async fn create_payment_method(
     merchant_id: &str,
     pm_metadata: Option<serde_json::Value>,
     payment_method_data: Option<Encryption>,
+    card_fingerprint: Option<String>,
 ) -> errors::CustomResult<storage::PaymentMethod, errors::StorageError> {
     let response = db
         .insert_payment_method(storage::PaymentMethodNew {
@@ -74,6 +80,7 @@ pub async fn create_payment_method(
             scheme: req.card_network.clone(),
             metadata: pm_metadata.map(masking::Secret::new),
             payment_method_data,
+            card_fingerprint,
             ..storage::PaymentMethodNew::default()
         })
         .await?;
@@ -91,17 +98,25 @@ pub async fn add_payment_method(
     req.validate()?;
     let merchant_id = &merchant_account.merchant_id;
     let customer_id = req.customer_id.clone().get_required_value("customer_id")?;
-    let response = match req.card.clone() {
-        Some(card) => add_card_to_locker(
-            &state,
-            req.clone(),
-            card,
-            customer_id.clone(),
-            merchant_account,
-        )
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Add Card Failed"),
+    let card_fingerprint = match req.card.as_ref() {
+        Some(card) => Some(
+            get_card_fingerprint(&*state.store, merchant_id, key_store, &card.card_number).await?,
+        ),
+        None => None,
+    };
+    let response = match req.card.clone().zip(card_fingerprint.as_deref()) {
+        Some((card, card_fingerprint)) => {
+            save_card_of_customer(
+                &state,
+                req.clone(),
+                card,
+                customer_id.clone(),
+                card_fingerprint,
+                merchant_account,
+                key_store,
+            )
+            .await
+        }
         None => {
             let pm_id = generate_id(consts::ID_LENGTH, "pm");
             let payment_method_response = api::PaymentMethodResponse {
@@ -141,6 +156,7 @@ pub async fn add_payment_method(
             &resp.merchant_id,
             pm_metadata.cloned(),
             pm_data_encrypted,
+            card_fingerprint,
         )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -150,6 +166,228 @@ pub async fn add_payment_method(
     Ok(resp).map(services::ApplicationResponse::Json)
 }
 
+/// Fingerprint of the card number scoped to the merchant, with which the same card saved more than
+/// once by a customer is identified. It is generated the same way as the fingerprints of the
+/// blocked cards of the merchant.
+pub async fn get_card_fingerprint(
+    db: &dyn db::StorageInterface,
+    merchant_id: &str,
+    key_store: &domain::MerchantKeyStore,
+    card_number: &::cards::CardNumber,
+) -> errors::RouterResult<String> {
+    let secret =
+        blocklist_utils::get_merchant_fingerprint_secret(db, merchant_id, key_store).await?;
+    blocklist_utils::get_blocklist_fingerprint(
+        &secret,
+        api_enums::BlocklistDataKind::CardFingerprint,
+        card_number.peek(),
+    )
+}
+
+/// The most recently saved payment method of the customer with the card of the fingerprint
+pub async fn find_payment_method_by_card_fingerprint(
+    db: &dyn db::StorageInterface,
+    customer_id: &str,
+    merchant_id: &str,
+    card_fingerprint: &str,
+) -> errors::RouterResult<Option<storage::PaymentMethod>> {
+    match db
+        .find_payment_method_by_customer_id_merchant_id_card_fingerprint_list(
+            customer_id,
+            merchant_id,
+            card_fingerprint,
+        )
+        .await
+    {
+        Ok(payment_methods) => Ok(payment_methods
+            .into_iter()
+            .max_by_key(|payment_method| payment_method.created_at)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find payment methods by card fingerprint"),
+    }
+}
+
+/// Saves the card of the customer in the locker. If the customer has already saved the card, the
+/// payment method already saved is returned as a duplicate instead, unless the request asks for a
+/// new payment method to be created for the card.
+#[instrument(skip_all)]
+pub async fn save_card_of_customer(
+    state: &routes::AppState,
+    req: api::PaymentMethodCreate,
+    card: api::CardDetail,
+    customer_id: String,
+    card_fingerprint: &str,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResult<(api::PaymentMethodResponse, bool)> {
+    let saved_payment_method = match req.duplicate_behaviour.unwrap_or_default() {
+        api_enums::DuplicatePaymentMethodBehaviour::ReturnExisting => {
+            find_payment_method_by_card_fingerprint(
+                &*state.store,
+                &customer_id,
+                &merchant_account.merchant_id,
+                card_fingerprint,
+            )
+            .await?
+        }
+        api_enums::DuplicatePaymentMethodBehaviour::CreateNew => None,
+    };
+
+    match saved_payment_method {
+        Some(payment_method) => {
+            get_saved_card_payment_method(state, req, card, payment_method, key_store)
+                .await
+                .map(|payment_method_response| (payment_method_response, true))
+        }
+        None => add_card_to_locker(state, req, card, customer_id, merchant_account)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Add Card Failed"),
+    }
+}
+
+/// Response of the payment method with which the customer has already saved the card. The expiry
+/// of the saved card is updated when the card being saved expires later.
+async fn get_saved_card_payment_method(
+    state: &routes::AppState,
+    req: api::PaymentMethodCreate,
+    card: api::CardDetail,
+    payment_method: storage::PaymentMethod,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResult<api::PaymentMethodResponse> {
+    let saved_card = get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        get_locker_id(&payment_method),
+    )
+    .await?;
+    let is_expiry_refreshed = is_later_expiry(
+        (
+            saved_card.card_exp_month.peek(),
+            saved_card.card_exp_year.peek(),
+        ),
+        (card.card_exp_month.peek(), card.card_exp_year.peek()),
+    );
+    let card = if is_expiry_refreshed {
+        card
+    } else {
+        api::CardDetail {
+            card_exp_month: saved_card.card_exp_month,
+            card_exp_year: saved_card.card_exp_year,
+            ..card
+        }
+    };
+
+    let payment_method_response = api::PaymentMethodResponse {
+        created: Some(payment_method.created_at),
+        ..payment_methods::mk_add_card_response_hs(
+            card.clone(),
+            payment_method.payment_method_id.clone(),
+            req,
+            &payment_method.merchant_id,
+        )
+    };
+
+    if is_expiry_refreshed {
+        update_saved_card(
+            state,
+            card,
+            payment_method,
+            payment_method_response.card.clone(),
+            key_store,
+        )
+        .await?;
+    }
+
+    Ok(payment_method_response)
+}
+
+/// Replaces the card saved in the locker for the payment method. The new card is saved under a new
+/// reference before the old one is deleted, so that the payment method is never left without a
+/// card when the locker fails midway.
+async fn update_saved_card(
+    state: &routes::AppState,
+    card: api::CardDetail,
+    payment_method: storage::PaymentMethod,
+    card_details: Option<api::CardDetailFromLocker>,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResult<()> {
+    let old_locker_id = get_locker_id(&payment_method).to_owned();
+    let locker_id = generate_id(consts::ID_LENGTH, "card");
+
+    let payload = payment_methods::StoreLockerReq::LockerCard(payment_methods::StoreCardReq {
+        merchant_id: &payment_method.merchant_id,
+        merchant_customer_id: payment_method.customer_id.to_owned(),
+        requestor_card_reference: Some(locker_id.clone()),
+        card: payment_methods::Card {
+            card_number: card.card_number,
+            name_on_card: card.card_holder_name,
+            card_exp_month: card.card_exp_month,
+            card_exp_year: card.card_exp_year,
+            card_brand: None,
+            card_isin: None,
+            nick_name: card.nick_name.as_ref().map(masking::Secret::peek).cloned(),
+        },
+    });
+    call_to_locker_hs(state, &payload, &payment_method.customer_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the saved card in the locker")?;
+
+    let payment_method_data = create_encrypted_payment_method_data(
+        key_store,
+        card_details.map(|card| PaymentMethodsData::Card(CardDetailsPaymentMethod::from(card))),
+    )
+    .await;
+    let payment_method = state
+        .store
+        .update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::LockerCardUpdate {
+                locker_id: Some(locker_id),
+                payment_method_data,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the card details of the payment method")?;
+
+    // The payment method already refers to the new card, the old one is only left behind in the
+    // locker when it cannot be deleted
+    if let Err(error) = delete_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        &old_locker_id,
+    )
+    .await
+    {
+        logger::error!(?error, "Failed to delete the replaced card from the locker");
+    }
+
+    Ok(())
+}
+
+/// Whether the new expiry, as the month and the year, is later than the saved one. The year is
+/// either in two or in four digits, an expiry which cannot be parsed is never later.
+fn is_later_expiry(saved_expiry: (&str, &str), new_expiry: (&str, &str)) -> bool {
+    fn parse_expiry((month, year): (&str, &str)) -> Option<(u16, u8)> {
+        let month = month.trim().parse::<u8>().ok()?;
+        let year = year.trim().parse::<u16>().ok()?;
+        let year = if year < 100 { year + 2000 } else { year };
+        Some((year, month))
+    }
+
+    match (parse_expiry(saved_expiry), parse_expiry(new_expiry)) {
+        (Some(saved_expiry), Some(new_expiry)) => new_expiry > saved_expiry,
+        (None, Some(_)) => true,
+        (_, None) => false,
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn update_customer_payment_method(
     state: routes::AppState,
@@ -167,13 +405,8 @@ pub async fn update_customer_payment_method(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
     if pm.payment_method == enums::PaymentMethod::Card {
-        delete_card_from_locker(
-            &state,
-            &pm.customer_id,
-            &pm.merchant_id,
-            &pm.payment_method_id,
-        )
-        .await?;
+        delete_card_from_locker(&state, &pm.customer_id, &pm.merchant_id, get_locker_id(&pm))
+            .await?;
     };
     let new_pm = api::PaymentMethodCreate {
         payment_method: pm.payment_method,
@@ -187,10 +420,20 @@ pub async fn update_customer_payment_method(
             .card_network
             .as_ref()
             .map(|card_network| card_network.to_string()),
+        duplicate_behaviour: None,
     };
     add_payment_method(state, new_pm, &merchant_account, &key_store).await
 }
 
+/// Reference of the card of the payment method in the locker. The cards saved before it could be
+/// replaced are referenced by the id of the payment method
+pub fn get_locker_id(payment_method: &storage::PaymentMethod) -> &str {
+    payment_method
+        .locker_id
+        .as_deref()
+        .unwrap_or(&payment_method.payment_method_id)
+}
+
 // Wrapper function to switch lockers
 
 /// The response will be the tuple of PaymentMethodResponse and the duplication check of payment_method
@@ -229,7 +472,7 @@ pub async fn get_card_from_locker(
         async {
             get_card_from_hs_locker(state, customer_id, merchant_id, card_reference)
                 .await
-                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .change_context(errors::ApiErrorResponse::CardVaultRetrievalFailed)
                 .attach_printable("Failed while getting card from basilisk_hs")
                 .map_err(|error| {
                     metrics::CARD_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
@@ -276,6 +519,7 @@ pub async fn add_card_hs(
     let payload = payment_methods::StoreLockerReq::LockerCard(payment_methods::StoreCardReq {
         merchant_id: &merchant_account.merchant_id,
         merchant_customer_id: customer_id.to_owned(),
+        requestor_card_reference: None,
         card: payment_methods::Card {
             card_number: card.card_number.to_owned(),
             name_on_card: card.card_holder_name.to_owned(),
@@ -410,7 +654,13 @@ pub async fn call_to_locker_hs<'a>(
             .change_context(errors::VaultError::ResponseDeserializationFailed)?;
         stored_card_resp
     } else {
-        let card_id = generate_id(consts::ID_LENGTH, "card");
+        let card_id = match payload {
+            payment_methods::StoreLockerReq::LockerCard(store_card_req) => {
+                store_card_req.requestor_card_reference.clone()
+            }
+            payment_methods::StoreLockerReq::LockerGeneric(_) => None,
+        }
+        .unwrap_or_else(|| generate_id(consts::ID_LENGTH, "card"));
         mock_call_to_locker_hs(db, &card_id, payload, None, None, Some(customer_id)).await?
     };
 
@@ -501,18 +751,17 @@ pub async fn delete_card_from_hs_locker<'a>(
     #[cfg(feature = "kms")]
     let jwekey = &state.kms_secrets;
 
-    let request = payment_methods::mk_delete_card_request_hs(
-        jwekey,
-        locker,
-        customer_id,
-        merchant_id,
-        card_reference,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Making delete card request failed")?;
-
     if !locker.mock_locker {
+        let request = payment_methods::mk_delete_card_request_hs(
+            jwekey,
+            locker,
+            customer_id,
+            merchant_id,
+            card_reference,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Making delete card request failed")?;
         let response = services::call_connector_api(state, request)
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -777,18 +1026,173 @@ fn get_val(str: String, val: &serde_json::Value) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-pub async fn list_payment_methods(
-    state: routes::AppState,
-    merchant_account: domain::MerchantAccount,
-    key_store: domain::MerchantKeyStore,
+/// Field of the card verification code collected when paying with a saved card, so that client
+/// side SDKs show it for the connectors which accept the code along with the card token. The code
+/// is not collected for the payments which set up a mandate.
+fn get_saved_card_cvc_field(
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: api_enums::PaymentMethodType,
+    connector: api_enums::Connector,
+    is_mandate: Option<bool>,
+) -> Option<(String, RequiredFieldInfo)> {
+    let flow = if is_mandate == Some(true) {
+        SavedCardFlow::Mandate
+    } else {
+        SavedCardFlow::CustomerInitiated
+    };
+    (payment_method == api_enums::PaymentMethod::Card
+        && get_saved_card_cvc_support(connector, payment_method_type, flow)
+            != SavedCardCvcSupport::NotSupported)
+        .then(|| {
+            (
+                "card_cvc".to_string(),
+                RequiredFieldInfo {
+                    required_field: "card_cvc".to_string(),
+                    display_name: "card_cvc".to_string(),
+                    field_type: api_enums::FieldType::UserCardCvc,
+                    value: None,
+                },
+            )
+        })
+}
+
+/// Collects the fields that the connector requires for the payment method type, prefilling the
+/// fields whose values are already present in the payment. The mandate or non-mandate specific
+/// fields are only included when it is known whether the payment sets up a mandate.
+pub fn get_required_fields_for_connector(
+    required_fields: &settings::RequiredFields,
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: api_enums::PaymentMethodType,
+    connector: api_enums::Connector,
+    is_mandate: Option<bool>,
+    request_value: Option<&serde_json::Value>,
+) -> Option<HashMap<String, RequiredFieldInfo>> {
+    let required_fields_final = required_fields
+        .0
+        .get(&payment_method)
+        .and_then(|required_fields| required_fields.0.get(&payment_method_type))
+        .and_then(|required_fields| required_fields.fields.get(&connector));
+    let saved_card_cvc_field =
+        get_saved_card_cvc_field(payment_method, payment_method_type, connector, is_mandate);
+    if required_fields_final.is_none() && saved_card_cvc_field.is_none() {
+        return None;
+    }
+
+    let mut required_fields_hs = HashMap::new();
+    if let Some(required_fields_final) = required_fields_final {
+        required_fields_hs.extend(required_fields_final.common.clone());
+        match is_mandate {
+            Some(true) => required_fields_hs.extend(required_fields_final.mandate.clone()),
+            Some(false) => required_fields_hs.extend(required_fields_final.non_mandate.clone()),
+            None => (),
+        }
+    }
+    required_fields_hs.extend(saved_card_cvc_field);
+
+    for (key, val) in &mut required_fields_hs {
+        // The values are looked up by the key, so it must be the path of the required field
+        debug_assert_eq!(key, &val.required_field);
+        if let Some(value) = request_value.and_then(|r| get_val(key.to_owned(), r)) {
+            val.value = Some(value)
+        };
+    }
+
+    Some(required_fields_hs)
+}
+
+/// Merges the fields required for the payment method type by each of the connectors
+pub fn merge_required_fields_for_connectors(
+    required_fields: &settings::RequiredFields,
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: api_enums::PaymentMethodType,
+    connectors: &[api_enums::Connector],
+    is_mandate: Option<bool>,
+    request_value: Option<&serde_json::Value>,
+) -> HashMap<String, RequiredFieldInfo> {
+    connectors
+        .iter()
+        .filter_map(|connector| {
+            get_required_fields_for_connector(
+                required_fields,
+                payment_method,
+                payment_method_type,
+                *connector,
+                is_mandate,
+                request_value,
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+/// Collects the fields that all the connectors require for the payment method type, a payment
+/// without one of these fields would fail whichever of the connectors it is routed to
+pub fn get_fields_required_by_all_connectors(
+    required_fields: &settings::RequiredFields,
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: api_enums::PaymentMethodType,
+    connectors: &[api_enums::Connector],
+    is_mandate: Option<bool>,
+) -> Vec<String> {
+    let mut fields_of_connectors = connectors.iter().map(|connector| {
+        get_required_fields_for_connector(
+            required_fields,
+            payment_method,
+            payment_method_type,
+            *connector,
+            is_mandate,
+            None,
+        )
+        .unwrap_or_default()
+        .into_keys()
+        .collect::<HashSet<_>>()
+    });
+    let Some(first_connector_fields) = fields_of_connectors.next() else {
+        return Vec::new();
+    };
+
+    let mut fields = fields_of_connectors
+        .fold(first_connector_fields, |fields, connector_fields| {
+            fields.intersection(&connector_fields).cloned().collect()
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    fields.sort();
+    fields
+}
+
+/// Whether the request has a value at the path of the required field
+pub fn is_required_field_present(field: &str, request_value: &serde_json::Value) -> bool {
+    field
+        .split('.')
+        .try_fold(request_value, |acc, x| acc.get(x))
+        .map_or(false, |value| {
+            !(value.is_null() || value.as_str().map_or(false, str::is_empty))
+        })
+}
+
+/// The payment methods of the merchant that are eligible for a payment, along with the details
+/// of the payment that are used to prefill the fields required by connectors, and the ETag of the
+/// payment methods listed from them
+struct EligiblePaymentMethods {
+    payment_methods: Vec<ResponsePaymentMethodIntermediate>,
+    payment_attempt: Option<storage::PaymentAttempt>,
+    request_value: Option<serde_json::Value>,
+    etag: String,
+}
+
+async fn get_eligible_payment_methods(
+    state: &routes::AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
     mut req: api::PaymentMethodListRequest,
-) -> errors::RouterResponse<api::PaymentMethodListResponse> {
+) -> errors::RouterResult<EligiblePaymentMethods> {
     let db = &*state.store;
     let pm_config_mapping = &state.conf.pm_filters;
 
     let payment_intent = helpers::verify_payment_intent_time_and_client_secret(
         db,
-        &merchant_account,
+        merchant_account,
         req.client_secret.clone(),
     )
     .await?;
@@ -799,7 +1203,7 @@ pub async fn list_payment_methods(
             helpers::get_address_by_id(
                 db,
                 pi.shipping_address_id.clone(),
-                &key_store,
+                key_store,
                 pi.payment_id.clone(),
                 merchant_account.merchant_id.clone(),
                 merchant_account.storage_scheme,
@@ -816,7 +1220,7 @@ pub async fn list_payment_methods(
             helpers::get_address_by_id(
                 db,
                 pi.billing_address_id.clone(),
-                &key_store,
+                key_store,
                 pi.payment_id.clone(),
                 merchant_account.merchant_id.clone(),
                 merchant_account.storage_scheme,
@@ -836,7 +1240,7 @@ pub async fn list_payment_methods(
                     db.find_customer_by_customer_id_merchant_id(
                         cust.as_str(),
                         &pi.merchant_id,
-                        &key_store,
+                        key_store,
                     )
                     .await
                     .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)
@@ -865,7 +1269,7 @@ pub async fn list_payment_methods(
         .find_merchant_connector_account_by_merchant_id_and_disabled_list(
             &merchant_account.merchant_id,
             false,
-            &key_store,
+            key_store,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
@@ -876,7 +1280,7 @@ pub async fn list_payment_methods(
             crate::core::utils::get_profile_id_from_business_details(
                 payment_intent.business_country,
                 payment_intent.business_label.as_ref(),
-                &merchant_account,
+                merchant_account,
                 payment_intent.profile_id.as_ref(),
                 db,
                 false,
@@ -892,26 +1296,75 @@ pub async fn list_payment_methods(
 
     logger::debug!(mca_before_filtering=?filtered_mcas);
 
-    let mut response: Vec<ResponsePaymentMethodIntermediate> = vec![];
-    for mca in filtered_mcas {
-        let payment_methods = match mca.payment_methods_enabled {
-            Some(pm) => pm,
-            None => continue,
-        };
+    let mut payment_amount = payment_intent.as_ref().and_then(|payment_intent| {
+        Some(eligibility::PaymentAmount::new(
+            payment_intent.amount,
+            payment_intent.currency?,
+        ))
+    });
+    if let Some(payment_amount) = payment_amount.as_mut() {
+        payment_amount
+            .convert_to_range_currencies(state, filtered_mcas.iter())
+            .await;
+    }
 
-        filter_payment_methods(
-            payment_methods,
-            &mut req,
-            &mut response,
-            payment_intent.as_ref(),
-            payment_attempt.as_ref(),
+    let list_hash = list_cache::get_inputs_hash(&list_cache::PaymentMethodListInputs::new(
+        merchant_account,
+        profile_id.as_ref(),
+        &filtered_mcas,
+        payment_intent.as_ref(),
+        payment_attempt.as_ref(),
+        payment_amount.as_ref(),
+        billing_address.as_ref(),
+        &req,
+    ))?;
+    let etag = list_cache::get_etag(
+        &list_hash,
+        &list_cache::CustomerSectionInputs::new(
+            customer.as_ref(),
+            shipping_address.as_ref(),
             billing_address.as_ref(),
-            mca.connector_name,
-            pm_config_mapping,
-            &state.conf.mandates.supported_payment_methods,
-        )
-        .await?;
-    }
+            payment_attempt.as_ref(),
+        ),
+    )?;
+
+    let cached_response =
+        list_cache::get_cached_payment_methods(state, &merchant_account.merchant_id, &list_hash)
+            .await;
+    let response = match cached_response {
+        Some(response) => response,
+        None => {
+            let mut response: Vec<ResponsePaymentMethodIntermediate> = vec![];
+            for mca in filtered_mcas {
+                let payment_methods = match mca.payment_methods_enabled {
+                    Some(pm) => pm,
+                    None => continue,
+                };
+
+                filter_payment_methods(
+                    payment_methods,
+                    &mut req,
+                    &mut response,
+                    payment_intent.as_ref(),
+                    payment_attempt.as_ref(),
+                    payment_amount.as_ref(),
+                    billing_address.as_ref(),
+                    mca.connector_name,
+                    pm_config_mapping,
+                    &state.conf.mandates.supported_payment_methods,
+                )
+                .await?;
+            }
+            list_cache::cache_payment_methods(
+                state,
+                &merchant_account.merchant_id,
+                &list_hash,
+                &response,
+            )
+            .await;
+            response
+        }
+    };
 
     let req = api_models::payments::PaymentsRequest::foreign_from((
         payment_attempt.as_ref(),
@@ -919,9 +1372,41 @@ pub async fn list_payment_methods(
         billing_address.as_ref(),
         customer.as_ref(),
     ));
-    let req_val = serde_json::to_value(req).ok();
     logger::debug!(filtered_payment_methods=?response);
 
+    Ok(EligiblePaymentMethods {
+        payment_methods: response,
+        payment_attempt,
+        request_value: serde_json::to_value(req).ok(),
+        etag,
+    })
+}
+
+/// Lists the payment methods eligible for a payment. The response carries an ETag, a request with
+/// the ETag of the response in the `If-None-Match` header is answered with `304 Not Modified` while
+/// the inputs of the list do not change. The connector details are left out of the response to
+/// the clients authenticated with the publishable key.
+pub async fn list_payment_methods(
+    state: routes::AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentMethodListRequest,
+    if_none_match: Option<String>,
+    auth_flow: services::AuthFlow,
+) -> errors::RouterResponse<api::PaymentMethodListResponse> {
+    let EligiblePaymentMethods {
+        payment_methods: response,
+        payment_attempt,
+        request_value: req_val,
+        etag,
+    } = get_eligible_payment_methods(&state, &merchant_account, &key_store, req).await?;
+
+    if list_cache::is_not_modified(if_none_match.as_deref(), &etag) {
+        return Ok(services::ApplicationResponse::NotModified(
+            list_cache::get_cache_headers(etag),
+        ));
+    }
+
     let mut payment_experiences_consolidated_hm: HashMap<
         api_enums::PaymentMethod,
         HashMap<api_enums::PaymentMethodType, HashMap<api_enums::PaymentExperience, Vec<String>>>,
@@ -946,6 +1431,10 @@ pub async fn list_payment_methods(
         HashMap<api_enums::PaymentMethodType, HashMap<String, RequiredFieldInfo>>,
     >::new();
 
+    let is_mandate = payment_attempt
+        .as_ref()
+        .map(|payment_attempt| payment_attempt.mandate_details.is_some());
+
     for element in response.clone() {
         let payment_method = element.payment_method;
         let payment_method_type = element.payment_method_type;
@@ -958,57 +1447,22 @@ pub async fn list_payment_methods(
                 field_name: "connector",
             })
             .attach_printable_lazy(|| format!("unable to parse connector name {connector:?}"))?;
-        state.conf.required_fields.0.get(&payment_method).map(
-            |required_fields_hm_for_each_payment_method_type| {
-                required_fields_hm_for_each_payment_method_type
-                    .0
-                    .get(&payment_method_type)
-                    .map(|required_fields_hm_for_each_connector| {
-                        required_fields_hm
-                            .entry(payment_method)
-                            .or_insert(HashMap::new());
-                        required_fields_hm_for_each_connector
-                            .fields
-                            .get(&connector_variant)
-                            .map(|required_fields_final| {
-                                let mut required_fields_hs = required_fields_final.common.clone();
-                                if let Some(pa) = payment_attempt.as_ref() {
-                                    if let Some(_mandate) = &pa.mandate_details {
-                                        required_fields_hs
-                                            .extend(required_fields_final.mandate.clone());
-                                    } else {
-                                        required_fields_hs
-                                            .extend(required_fields_final.non_mandate.clone());
-                                    }
-                                }
-
-                                {
-                                    for (key, val) in &mut required_fields_hs {
-                                        let temp = req_val
-                                            .as_ref()
-                                            .and_then(|r| get_val(key.to_owned(), r));
-                                        if let Some(s) = temp {
-                                            val.value = Some(s)
-                                        };
-                                    }
-                                }
-
-                                let existing_req_fields_hs = required_fields_hm
-                                    .get_mut(&payment_method)
-                                    .and_then(|inner_hm| inner_hm.get_mut(&payment_method_type));
-
-                                // If payment_method_type already exist in required_fields_hm, extend the required_fields hs to existing hs.
-                                if let Some(inner_hs) = existing_req_fields_hs {
-                                    inner_hs.extend(required_fields_hs);
-                                } else {
-                                    required_fields_hm.get_mut(&payment_method).map(|inner_hm| {
-                                        inner_hm.insert(payment_method_type, required_fields_hs)
-                                    });
-                                }
-                            })
-                    })
-            },
-        );
+        if let Some(required_fields) = get_required_fields_for_connector(
+            &state.conf.required_fields,
+            payment_method,
+            payment_method_type,
+            connector_variant,
+            is_mandate,
+            req_val.as_ref(),
+        ) {
+            // Fields required by multiple connectors for the same payment method type are merged
+            required_fields_hm
+                .entry(payment_method)
+                .or_default()
+                .entry(payment_method_type)
+                .or_default()
+                .extend(required_fields);
+        }
 
         if let Some(payment_experience) = element.payment_experience {
             if let Some(payment_method_hm) =
@@ -1276,40 +1730,247 @@ pub async fn list_payment_methods(
         });
     }
 
+    let response = api::PaymentMethodListResponse {
+        redirect_url: merchant_account.return_url,
+        merchant_name: merchant_account.merchant_name,
+        payment_methods: payment_method_responses,
+        mandate_payment: payment_attempt
+            .and_then(|inner| inner.mandate_details)
+            .map(|d| match d {
+                data_models::mandates::MandateDataType::SingleUse(i) => {
+                    api::MandateType::SingleUse(api::MandateAmountData {
+                        amount: i.amount,
+                        currency: i.currency,
+                        start_date: i.start_date,
+                        end_date: i.end_date,
+                        metadata: i.metadata,
+                        frequency: i.frequency,
+                        max_charges_per_period: i.max_charges_per_period,
+                    })
+                }
+                data_models::mandates::MandateDataType::MultiUse(Some(i)) => {
+                    api::MandateType::MultiUse(Some(api::MandateAmountData {
+                        amount: i.amount,
+                        currency: i.currency,
+                        start_date: i.start_date,
+                        end_date: i.end_date,
+                        metadata: i.metadata,
+                        frequency: i.frequency,
+                        max_charges_per_period: i.max_charges_per_period,
+                    }))
+                }
+                data_models::mandates::MandateDataType::MultiUse(None) => {
+                    api::MandateType::MultiUse(None)
+                }
+            }),
+    };
+    let response = match auth_flow {
+        services::AuthFlow::Client => trim_payment_method_list_response_for_client(response),
+        services::AuthFlow::Merchant => response,
+    };
+
+    Ok(services::ApplicationResponse::JsonWithHeaders((
+        response,
+        list_cache::get_cache_headers(etag),
+    )))
+}
+
+/// Lists the fields required to pay with a payment method type, merged across the connectors that
+/// are eligible for the payment, so that client side SDKs can collect them before confirming it.
+pub async fn list_required_fields(
+    state: routes::AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::RequiredFieldsRequest,
+) -> errors::RouterResponse<api::RequiredFieldsResponse> {
+    let EligiblePaymentMethods {
+        payment_methods,
+        payment_attempt,
+        request_value,
+        ..
+    } = get_eligible_payment_methods(
+        &state,
+        &merchant_account,
+        &key_store,
+        api::PaymentMethodListRequest {
+            client_secret: req.client_secret,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let mut eligible_connectors = Vec::new();
+    for element in payment_methods {
+        if element.payment_method == req.payment_method
+            && element.payment_method_type == req.payment_method_type
+            && !eligible_connectors.contains(&element.connector)
+        {
+            eligible_connectors.push(element.connector);
+        }
+    }
+
+    let connectors = eligible_connectors
+        .iter()
+        .map(|connector| {
+            api_enums::Connector::from_str(connector)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "connector",
+                })
+                .attach_printable_lazy(|| format!("unable to parse connector name {connector:?}"))
+        })
+        .collect::<errors::RouterResult<Vec<_>>>()?;
+
+    let required_fields = merge_required_fields_for_connectors(
+        &state.conf.required_fields,
+        req.payment_method,
+        req.payment_method_type,
+        &connectors,
+        payment_attempt
+            .as_ref()
+            .map(|payment_attempt| payment_attempt.mandate_details.is_some()),
+        request_value.as_ref(),
+    );
+
     Ok(services::ApplicationResponse::Json(
-        api::PaymentMethodListResponse {
-            redirect_url: merchant_account.return_url,
-            merchant_name: merchant_account.merchant_name,
-            payment_methods: payment_method_responses,
-            mandate_payment: payment_attempt.and_then(|inner| inner.mandate_details).map(
-                |d| match d {
-                    data_models::mandates::MandateDataType::SingleUse(i) => {
-                        api::MandateType::SingleUse(api::MandateAmountData {
-                            amount: i.amount,
-                            currency: i.currency,
-                            start_date: i.start_date,
-                            end_date: i.end_date,
-                            metadata: i.metadata,
-                        })
-                    }
-                    data_models::mandates::MandateDataType::MultiUse(Some(i)) => {
-                        api::MandateType::MultiUse(Some(api::MandateAmountData {
-                            amount: i.amount,
-                            currency: i.currency,
-                            start_date: i.start_date,
-                            end_date: i.end_date,
-                            metadata: i.metadata,
-                        }))
-                    }
-                    data_models::mandates::MandateDataType::MultiUse(None) => {
-                        api::MandateType::MultiUse(None)
-                    }
-                },
-            ),
+        api::RequiredFieldsResponse {
+            payment_method: req.payment_method,
+            payment_method_type: req.payment_method_type,
+            eligible_connectors,
+            required_fields,
         },
     ))
 }
 
+/// Lists the banks which can be selected to pay with a bank redirect payment method type, for each
+/// of the connectors eligible for the payment which declare the banks they support.
+pub async fn list_banks_for_payment_method_type(
+    state: routes::AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_method_type: api_enums::PaymentMethodType,
+    req: api::BankListRequest,
+) -> errors::RouterResponse<api::BankListResponse> {
+    let EligiblePaymentMethods {
+        payment_methods, ..
+    } = get_eligible_payment_methods(
+        &state,
+        &merchant_account,
+        &key_store,
+        api::PaymentMethodListRequest {
+            client_secret: req.client_secret,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let eligible_connectors = payment_methods
+        .into_iter()
+        .filter(|element| element.payment_method_type == payment_method_type)
+        .map(|element| element.connector);
+
+    Ok(services::ApplicationResponse::Json(api::BankListResponse {
+        payment_method_type,
+        connectors: get_banks_of_connectors(payment_method_type, eligible_connectors),
+    }))
+}
+
+/// The banks of the payment method type declared in the capabilities of each of the connectors,
+/// the connectors which do not declare any bank for the payment method type are left out
+fn get_banks_of_connectors(
+    payment_method_type: api_enums::PaymentMethodType,
+    connectors: impl IntoIterator<Item = String>,
+) -> Vec<api::ConnectorBanks> {
+    let mut connector_banks: Vec<api::ConnectorBanks> = Vec::new();
+    for connector in connectors {
+        if connector_banks
+            .iter()
+            .any(|banks| banks.connector == connector)
+        {
+            continue;
+        }
+
+        let banks = api_enums::Connector::from_str(&connector)
+            .ok()
+            .and_then(capabilities::get_connector_capabilities)
+            .map(|capabilities| capabilities.get_supported_banks(payment_method_type))
+            .unwrap_or_default();
+        if !banks.is_empty() {
+            connector_banks.push(api::ConnectorBanks { connector, banks });
+        }
+    }
+    connector_banks
+}
+
+/// Lists the payment methods eligible for a specific payment, for use by client side SDKs
+/// authenticated with the publishable key and the client secret of the payment.
+pub async fn list_payment_methods_for_payment(
+    state: routes::AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+    req: api::PaymentMethodListRequest,
+    if_none_match: Option<String>,
+) -> errors::RouterResponse<api::PaymentMethodListResponse> {
+    let client_secret = req
+        .client_secret
+        .as_deref()
+        .get_required_value("client_secret")?;
+
+    utils::when(
+        helpers::get_payment_id_from_client_secret(client_secret)? != payment_id,
+        || Err(report!(errors::ApiErrorResponse::ClientSecretInvalid)),
+    )?;
+
+    list_payment_methods(
+        state,
+        merchant_account,
+        key_store,
+        req,
+        if_none_match,
+        services::AuthFlow::Client,
+    )
+    .await
+}
+
+/// Removes the connector details from the payment method list response, which must not be
+/// exposed to clients authenticated with the publishable key
+pub fn trim_payment_method_list_response_for_client(
+    mut response: api::PaymentMethodListResponse,
+) -> api::PaymentMethodListResponse {
+    response
+        .payment_methods
+        .iter_mut()
+        .flat_map(|payment_method| payment_method.payment_method_types.iter_mut())
+        .for_each(|payment_method_type| {
+            payment_method_type
+                .payment_experience
+                .iter_mut()
+                .flatten()
+                .for_each(|experience| experience.eligible_connectors.clear());
+            payment_method_type
+                .card_networks
+                .iter_mut()
+                .flatten()
+                .for_each(|network| network.eligible_connectors.clear());
+            payment_method_type
+                .bank_names
+                .iter_mut()
+                .flatten()
+                .for_each(|bank| bank.eligible_connectors.clear());
+            payment_method_type
+                .bank_debits
+                .iter_mut()
+                .for_each(|bank_debit| bank_debit.eligible_connectors.clear());
+            payment_method_type
+                .bank_transfers
+                .iter_mut()
+                .for_each(|bank_transfer| bank_transfer.eligible_connectors.clear());
+        });
+
+    response
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn filter_payment_methods(
     payment_methods: Vec<serde_json::Value>,
@@ -1317,6 +1978,7 @@ pub async fn filter_payment_methods(
     resp: &mut Vec<ResponsePaymentMethodIntermediate>,
     payment_intent: Option<&storage::PaymentIntent>,
     payment_attempt: Option<&storage::PaymentAttempt>,
+    payment_amount: Option<&eligibility::PaymentAmount>,
     address: Option<&domain::Address>,
     connector: String,
     config: &settings::ConnectorFilters,
@@ -1378,7 +2040,7 @@ pub async fn filter_payment_methods(
                     let filter3 = if let Some(payment_intent) = payment_intent {
                         filter_payment_country_based(&payment_method_object, address).await?
                             && filter_payment_currency_based(payment_intent, &payment_method_object)
-                            && filter_payment_amount_based(payment_intent, &payment_method_object)
+                            && filter_payment_amount_based(payment_amount, &payment_method_object)
                             && filter_payment_mandate_based(payment_attempt, &payment_method_object)
                                 .await?
                     } else {
@@ -1753,12 +2415,12 @@ fn filter_payment_currency_based(
 }
 
 fn filter_payment_amount_based(
-    payment_intent: &storage::PaymentIntent,
+    payment_amount: Option<&eligibility::PaymentAmount>,
     pm: &RequestPaymentMethodTypes,
 ) -> bool {
-    let amount = payment_intent.amount;
-    pm.maximum_amount.map_or(true, |amt| amount < amt.into())
-        && pm.minimum_amount.map_or(true, |amt| amount > amt.into())
+    payment_amount.map_or(true, |payment_amount| {
+        eligibility::check_amount_range(pm, payment_amount).is_ok()
+    })
 }
 
 async fn filter_payment_mandate_based(
@@ -1842,6 +2504,7 @@ pub async fn list_customer_payment_method(
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+    let resp = filter_duplicate_card_payment_methods(resp);
     //let mca = query::find_mca_by_merchant_id(conn, &merchant_account.merchant_id)?;
     let mut customer_pms = Vec::new();
     for pm in resp.into_iter() {
@@ -1939,6 +2602,38 @@ pub async fn list_customer_payment_method(
     Ok(services::ApplicationResponse::Json(response))
 }
 
+/// Keeps only the most recently saved of the payment methods with the same card, the order of the
+/// payment methods being preserved
+fn filter_duplicate_card_payment_methods(
+    payment_methods: Vec<storage::PaymentMethod>,
+) -> Vec<storage::PaymentMethod> {
+    let mut latest_payment_methods: HashMap<&str, &storage::PaymentMethod> = HashMap::new();
+    for payment_method in &payment_methods {
+        if let Some(card_fingerprint) = payment_method.card_fingerprint.as_deref() {
+            latest_payment_methods
+                .entry(card_fingerprint)
+                .and_modify(|latest_payment_method| {
+                    if payment_method.created_at > latest_payment_method.created_at {
+                        *latest_payment_method = payment_method;
+                    }
+                })
+                .or_insert(payment_method);
+        }
+    }
+    let latest_payment_method_ids: HashSet<String> = latest_payment_methods
+        .into_values()
+        .map(|payment_method| payment_method.payment_method_id.clone())
+        .collect();
+
+    payment_methods
+        .into_iter()
+        .filter(|payment_method| {
+            payment_method.card_fingerprint.is_none()
+                || latest_payment_method_ids.contains(&payment_method.payment_method_id)
+        })
+        .collect()
+}
+
 async fn get_card_details(
     pm: &payment_method::PaymentMethod,
     key: &[u8],
@@ -1968,15 +2663,9 @@ pub async fn get_lookup_key_from_locker(
     payment_token: &str,
     pm: &storage::PaymentMethod,
 ) -> errors::RouterResult<api::CardDetailFromLocker> {
-    let card = get_card_from_locker(
-        state,
-        &pm.customer_id,
-        &pm.merchant_id,
-        &pm.payment_method_id,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Error getting card from card vault")?;
+    let card = get_card_from_locker(state, &pm.customer_id, &pm.merchant_id, get_locker_id(pm))
+        .await
+        .attach_printable("Error getting card from card vault")?;
     let card_detail = payment_methods::get_card_detail(pm, card)
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Get Card Details Failed")?;
@@ -2206,15 +2895,10 @@ pub async fn retrieve_payment_method(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
     let card = if pm.payment_method == enums::PaymentMethod::Card {
-        let card = get_card_from_locker(
-            &state,
-            &pm.customer_id,
-            &pm.merchant_id,
-            &pm.payment_method_id,
-        )
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting card from card vault")?;
+        let card =
+            get_card_from_locker(&state, &pm.customer_id, &pm.merchant_id, get_locker_id(&pm))
+                .await
+                .attach_printable("Error getting card from card vault")?;
         let card_detail = payment_methods::get_card_detail(&pm, card)
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed while getting card details from locker")?;
@@ -2251,12 +2935,17 @@ pub async fn delete_payment_method(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
 
+    // The card must not be removed from the locker on behalf of another merchant
+    utils::when(key.merchant_id != merchant_account.merchant_id, || {
+        Err(report!(errors::ApiErrorResponse::PaymentMethodNotFound))
+    })?;
+
     if key.payment_method == enums::PaymentMethod::Card {
         let response = delete_card_from_locker(
             &state,
             &key.customer_id,
             &key.merchant_id,
-            pm_id.payment_method_id.as_str(),
+            get_locker_id(&key),
         )
         .await?;
 
@@ -2312,3 +3001,563 @@ pub async fn create_encrypted_payment_method_data(
 
     pm_data_encrypted
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::payment_methods::card_fingerprint_backfill;
+
+    #[test]
+    fn test_trim_payment_method_list_response_for_client() {
+        let response = api::PaymentMethodListResponse {
+            redirect_url: None,
+            merchant_name: None,
+            mandate_payment: None,
+            payment_methods: vec![ResponsePaymentMethodsEnabled {
+                payment_method: api_enums::PaymentMethod::Card,
+                payment_method_types: vec![ResponsePaymentMethodTypes {
+                    payment_method_type: api_enums::PaymentMethodType::Credit,
+                    payment_experience: None,
+                    card_networks: Some(vec![CardNetworkTypes {
+                        card_network: api_enums::CardNetwork::Visa,
+                        eligible_connectors: vec!["stripe".to_string()],
+                    }]),
+                    bank_names: None,
+                    bank_debits: None,
+                    bank_transfers: None,
+                    required_fields: None,
+                }],
+            }],
+        };
+
+        let trimmed_response = trim_payment_method_list_response_for_client(response);
+        let payment_method_type = &trimmed_response.payment_methods[0].payment_method_types[0];
+
+        assert_eq!(
+            payment_method_type.payment_method_type,
+            api_enums::PaymentMethodType::Credit
+        );
+        assert!(payment_method_type
+            .card_networks
+            .iter()
+            .flatten()
+            .all(|network| network.eligible_connectors.is_empty()));
+    }
+
+    #[test]
+    fn test_payment_methods_outside_amount_range_are_not_listed() {
+        let klarna: RequestPaymentMethodTypes = serde_json::from_value(serde_json::json!({
+            "payment_method_type": "klarna",
+            "minimum_amount": 3500,
+            "maximum_amount": 100000,
+            "amount_range_currency": "EUR",
+            "recurring_enabled": false,
+            "installment_payment_enabled": true
+        }))
+        .unwrap_or_else(|error| panic!("{error}"));
+
+        let payment_amount = eligibility::PaymentAmount::new(2000, api_enums::Currency::EUR);
+        assert!(!filter_payment_amount_based(Some(&payment_amount), &klarna));
+        // Both bounds of the range are inclusive
+        for amount in [3500, 5000, 100000] {
+            let payment_amount = eligibility::PaymentAmount::new(amount, api_enums::Currency::EUR);
+            assert!(filter_payment_amount_based(Some(&payment_amount), &klarna));
+        }
+        // Payments without an amount in a currency are not filtered
+        assert!(filter_payment_amount_based(None, &klarna));
+    }
+
+    #[test]
+    fn test_merge_required_fields_for_connectors() {
+        let required_fields = settings::RequiredFields::default();
+        let request_value = serde_json::json!({ "email": "guest@example.com" });
+
+        let merged_fields = merge_required_fields_for_connectors(
+            &required_fields,
+            api_enums::PaymentMethod::PayLater,
+            api_enums::PaymentMethodType::Klarna,
+            &[api_enums::Connector::Stripe, api_enums::Connector::Adyen],
+            Some(false),
+            Some(&request_value),
+        );
+
+        assert!(merged_fields.contains_key("payment_method_data.pay_later.klarna.billing_country"));
+        assert!(merged_fields.contains_key("billing.address.first_name"));
+        assert_eq!(
+            merged_fields
+                .get("email")
+                .and_then(|field| field.value.as_deref()),
+            Some("guest@example.com")
+        );
+    }
+
+    #[test]
+    fn test_saved_card_cvc_is_required_for_connectors_accepting_it() {
+        let required_fields = settings::RequiredFields::default();
+
+        let adyen_fields = get_required_fields_for_connector(
+            &required_fields,
+            api_enums::PaymentMethod::Card,
+            api_enums::PaymentMethodType::Credit,
+            api_enums::Connector::Adyen,
+            None,
+            None,
+        )
+        .unwrap_or_default();
+        assert_eq!(
+            adyen_fields
+                .get("card_cvc")
+                .map(|field| field.field_type.clone()),
+            Some(api_enums::FieldType::UserCardCvc)
+        );
+
+        let checkout_fields = get_required_fields_for_connector(
+            &required_fields,
+            api_enums::PaymentMethod::Card,
+            api_enums::PaymentMethodType::Credit,
+            api_enums::Connector::Checkout,
+            None,
+            None,
+        )
+        .unwrap_or_default();
+        assert!(!checkout_fields.contains_key("card_cvc"));
+
+        let adyen_mandate_fields = get_required_fields_for_connector(
+            &required_fields,
+            api_enums::PaymentMethod::Card,
+            api_enums::PaymentMethodType::Credit,
+            api_enums::Connector::Adyen,
+            Some(true),
+            None,
+        )
+        .unwrap_or_default();
+        assert!(!adyen_mandate_fields.contains_key("card_cvc"));
+    }
+
+    #[test]
+    fn test_default_required_fields_are_valid() {
+        assert!(settings::RequiredFields::default().validate().is_ok());
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mock_locker_store_retrieve_and_delete_card() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn db::StorageInterface = &mockdb;
+
+        let payload = payment_methods::StoreLockerReq::LockerCard(payment_methods::StoreCardReq {
+            merchant_id: "merchant_1",
+            merchant_customer_id: "customer_1".to_string(),
+            requestor_card_reference: None,
+            card: payment_methods::Card {
+                card_number: "4242424242424242".to_string().try_into().unwrap(),
+                name_on_card: Some("John Doe".to_string().into()),
+                card_exp_month: "10".to_string().into(),
+                card_exp_year: "2030".to_string().into(),
+                card_brand: None,
+                card_isin: None,
+                nick_name: None,
+            },
+        });
+
+        let stored = mock_call_to_locker_hs(
+            db,
+            "card_1",
+            &payload,
+            None,
+            Some("pm_1".to_string()),
+            Some("customer_1"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            stored.payload.map(|payload| payload.card_reference),
+            Some("card_1".to_string())
+        );
+
+        let (retrieved, _) = mock_get_card(db, "card_1").await.unwrap();
+        assert_eq!(retrieved.card.card_id, "pm_1");
+        assert_eq!(retrieved.card.merchant_id.as_deref(), Some("merchant_1"));
+        assert_eq!(
+            retrieved.card.card_number.map(|number| number.get_last4()),
+            Some("4242".to_string())
+        );
+
+        mock_delete_card_hs(db, "card_1").await.unwrap();
+        assert!(mock_get_card(db, "card_1").await.is_err());
+    }
+
+    #[allow(clippy::unwrap_used)]
+    async fn get_state_with_merchant() -> (
+        routes::AppState,
+        domain::MerchantAccount,
+        domain::MerchantKeyStore,
+    ) {
+        let state = routes::AppState::with_storage(
+            settings::Settings::default(),
+            db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+        db.insert_config(storage::ConfigNew {
+            key: blocklist_utils::get_merchant_fingerprint_secret_key("merchant_1"),
+            config: "fingerprint_secret".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let merchant_account = domain::MerchantAccount {
+            id: None,
+            merchant_id: "merchant_1".to_string(),
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::Value::Null,
+            frm_routing_algorithm: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            intent_fulfillment_time: None,
+            payout_routing_algorithm: None,
+            organization_id: None,
+            is_recon_enabled: false,
+            default_profile: None,
+            recon_status: enums::ReconStatus::NotRequested,
+            webhook_auth_details: None,
+            default_currency: None,
+            default_country: None,
+            pii_policy: None,
+        };
+
+        (state, merchant_account, key_store)
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn get_card_payment_method_create(
+        card_number: &str,
+        card_exp_year: &str,
+        duplicate_behaviour: Option<api_enums::DuplicatePaymentMethodBehaviour>,
+    ) -> api::PaymentMethodCreate {
+        api::PaymentMethodCreate {
+            payment_method: api_enums::PaymentMethod::Card,
+            payment_method_type: Some(api_enums::PaymentMethodType::Credit),
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            card: Some(api::CardDetail {
+                card_number: card_number.to_string().try_into().unwrap(),
+                card_exp_month: "10".to_string().into(),
+                card_exp_year: card_exp_year.to_string().into(),
+                card_holder_name: Some("John Doe".to_string().into()),
+                nick_name: None,
+            }),
+            metadata: None,
+            customer_id: Some("customer_1".to_string()),
+            card_network: None,
+            duplicate_behaviour,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    async fn save_card(
+        state: &routes::AppState,
+        merchant_account: &domain::MerchantAccount,
+        key_store: &domain::MerchantKeyStore,
+        req: api::PaymentMethodCreate,
+    ) -> api::PaymentMethodResponse {
+        match add_payment_method(state.clone(), req, merchant_account, key_store)
+            .await
+            .unwrap()
+        {
+            services::ApplicationResponse::Json(response) => Some(response),
+            _ => None,
+        }
+        .unwrap()
+    }
+
+    #[allow(clippy::unwrap_used)]
+    async fn get_saved_payment_methods(state: &routes::AppState) -> Vec<storage::PaymentMethod> {
+        state
+            .store
+            .find_payment_method_by_customer_id_merchant_id_list("customer_1", "merchant_1")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_saving_the_same_card_twice_returns_the_saved_payment_method() {
+        let (state, merchant_account, key_store) = get_state_with_merchant().await;
+
+        let first = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+        let second = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+
+        assert_eq!(first.payment_method_id, second.payment_method_id);
+        assert_eq!(get_saved_payment_methods(&state).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_saving_a_different_card_creates_a_payment_method() {
+        let (state, merchant_account, key_store) = get_state_with_merchant().await;
+
+        let first = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+        let second = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4000056655665556", "2030", None),
+        )
+        .await;
+
+        assert_ne!(first.payment_method_id, second.payment_method_id);
+        let payment_methods = get_saved_payment_methods(&state).await;
+        assert_eq!(payment_methods.len(), 2);
+        assert_ne!(
+            payment_methods[0].card_fingerprint,
+            payment_methods[1].card_fingerprint
+        );
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_saving_the_same_card_refreshes_the_expiry() {
+        let (state, merchant_account, key_store) = get_state_with_merchant().await;
+
+        let saved = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+        let refreshed = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "32", None),
+        )
+        .await;
+        let not_refreshed = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2031", None),
+        )
+        .await;
+
+        assert_eq!(saved.payment_method_id, refreshed.payment_method_id);
+        assert_eq!(saved.payment_method_id, not_refreshed.payment_method_id);
+        for response in [refreshed, not_refreshed] {
+            assert_eq!(
+                response
+                    .card
+                    .and_then(|card| card.expiry_year)
+                    .map(|expiry_year| expiry_year.peek().clone()),
+                Some("32".to_string())
+            );
+        }
+        let payment_method = state
+            .store
+            .find_payment_method(&saved.payment_method_id)
+            .await
+            .unwrap();
+        assert_ne!(
+            get_locker_id(&payment_method),
+            payment_method.payment_method_id
+        );
+        let saved_card = get_card_from_locker(
+            &state,
+            "customer_1",
+            "merchant_1",
+            get_locker_id(&payment_method),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved_card.card_exp_year.peek(), "32");
+        // The replaced card is deleted once the new one is saved
+        assert!(
+            get_card_from_locker(&state, "customer_1", "merchant_1", &saved.payment_method_id)
+                .await
+                .is_err()
+        );
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_duplicate_card_payment_methods_are_not_listed() {
+        let (state, merchant_account, key_store) = get_state_with_merchant().await;
+
+        let saved = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+        let created = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create(
+                "4242424242424242",
+                "2030",
+                Some(api_enums::DuplicatePaymentMethodBehaviour::CreateNew),
+            ),
+        )
+        .await;
+        assert_ne!(saved.payment_method_id, created.payment_method_id);
+
+        let mut payment_methods = get_saved_payment_methods(&state).await;
+        assert_eq!(
+            payment_methods[0].card_fingerprint,
+            payment_methods[1].card_fingerprint
+        );
+        payment_methods[1].created_at = payment_methods[0].created_at + time::Duration::seconds(1);
+
+        let listed_payment_methods = filter_duplicate_card_payment_methods(payment_methods);
+        assert_eq!(listed_payment_methods.len(), 1);
+        assert_eq!(
+            listed_payment_methods[0].payment_method_id,
+            created.payment_method_id
+        );
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_card_fingerprints_are_backfilled() {
+        let (state, merchant_account, key_store) = get_state_with_merchant().await;
+
+        let saved = save_card(
+            &state,
+            &merchant_account,
+            &key_store,
+            get_card_payment_method_create("4242424242424242", "2030", None),
+        )
+        .await;
+        let saved = state
+            .store
+            .find_payment_method(&saved.payment_method_id)
+            .await
+            .unwrap();
+        // A payment method saved before the cards were fingerprinted, with the same card
+        state
+            .store
+            .insert_payment_method(storage::PaymentMethodNew {
+                customer_id: "customer_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                payment_method_id: "pm_without_fingerprint".to_string(),
+                payment_method: enums::PaymentMethod::Card,
+                locker_id: Some(get_locker_id(&saved).to_string()),
+                ..storage::PaymentMethodNew::default()
+            })
+            .await
+            .unwrap();
+
+        let mut tracking_data =
+            card_fingerprint_backfill::CardFingerprintBackfillTrackingData::default();
+        let is_job_complete = card_fingerprint_backfill::execute_card_fingerprint_backfill_task(
+            &state,
+            &mut tracking_data,
+        )
+        .await
+        .unwrap();
+
+        assert!(is_job_complete);
+        assert_eq!(tracking_data.fingerprinted_cards, 1);
+        let backfilled = state
+            .store
+            .find_payment_method("pm_without_fingerprint")
+            .await
+            .unwrap();
+        assert_eq!(backfilled.card_fingerprint, saved.card_fingerprint);
+    }
+
+    #[test]
+    fn test_is_later_expiry() {
+        assert!(is_later_expiry(("10", "2030"), ("11", "2030")));
+        assert!(is_later_expiry(("10", "2030"), ("01", "31")));
+        assert!(!is_later_expiry(("10", "30"), ("10", "2030")));
+        assert!(!is_later_expiry(("10", "2030"), ("09", "2030")));
+        assert!(!is_later_expiry(("10", "2030"), ("ab", "2031")));
+    }
+
+    #[test]
+    fn test_banks_are_listed_for_the_eligible_connectors() {
+        let eligible_connectors = ["adyen", "stripe", "adyen", "checkout", "unknown_connector"]
+            .into_iter()
+            .map(String::from);
+
+        let connector_banks =
+            get_banks_of_connectors(api_enums::PaymentMethodType::Ideal, eligible_connectors);
+
+        // Checkout does not declare iDEAL banks and the unknown connector has no capabilities
+        assert_eq!(
+            connector_banks
+                .iter()
+                .map(|banks| banks.connector.as_str())
+                .collect::<Vec<_>>(),
+            vec!["adyen", "stripe"]
+        );
+        assert!(connector_banks[1]
+            .banks
+            .contains(&api_enums::BankNames::Rabobank));
+        assert!(!connector_banks[1]
+            .banks
+            .contains(&api_enums::BankNames::Citi));
+
+        assert!(get_banks_of_connectors(
+            api_enums::PaymentMethodType::Giropay,
+            vec!["stripe".to_string()]
+        )
+        .is_empty());
+    }
+}
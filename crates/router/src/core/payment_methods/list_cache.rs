@@ -0,0 +1,433 @@
+//! Caching of the payment methods listed for a payment, which client side SDKs fetch on every load
+//! of the checkout page while the list rarely changes.
+//!
+//! The payment methods eligible for a payment are cached in redis, keyed by a hash of the inputs
+//! they are computed from: the connector accounts of the merchant and the time they were last
+//! changed, the amount ranges which the amount of the payment falls into, the currency and the
+//! country of the payment, and the filters of the request. The cached list does not depend on the
+//! customer and is shared by the payments with the same inputs. The lists of a merchant are removed
+//! when one of its connector accounts is created, updated or deleted, and expire with the hash
+//! holding them otherwise.
+//!
+//! The values prefilled in the required fields are taken from the customer and the addresses of
+//! the payment, they are computed on every request and never cached. The ETag of the response
+//! covers both the cached list and the customer inputs, clients sending it back in the
+//! `If-None-Match` header receive a `304 Not Modified` response while neither has changed.
+
+use api_models::payment_methods::{
+    PaymentMethodsEnabled, RequestPaymentMethodTypes, ResponsePaymentMethodIntermediate,
+};
+use common_utils::crypto::{self, GenerateDigest};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde::Serialize;
+
+use crate::{
+    core::{
+        errors::{self, RouterResult},
+        payments::eligibility,
+    },
+    headers,
+    routes::AppState,
+    types::{
+        api::{self, enums as api_enums},
+        domain,
+        storage::{self, enums as storage_enums},
+    },
+};
+
+const PAYMENT_METHOD_LIST_CACHE_PREFIX: &str = "payment_method_list";
+
+/// Key of the redis hash holding the cached lists of the merchant, by the hash of their inputs
+pub fn get_payment_method_list_cache_key(merchant_id: &str) -> String {
+    format!("{PAYMENT_METHOD_LIST_CACHE_PREFIX}_{merchant_id}")
+}
+
+/// Inputs of the payment methods eligible for a payment, none of which depend on the customer
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PaymentMethodListInputs {
+    pub merchant_id: String,
+    pub merchant_modified_at: String,
+    pub profile_id: Option<String>,
+    /// Identifier and time of the last change of the connector accounts, sorted by identifier
+    pub connector_accounts: Vec<(String, String)>,
+    /// Whether the amount of the payment is within each of the amount ranges configured on the
+    /// payment method types of the connector accounts, in the order of the connector accounts
+    pub amount_bucket: Vec<bool>,
+    pub is_payment: bool,
+    pub currency: Option<api_enums::Currency>,
+    pub attempt_currency: Option<api_enums::Currency>,
+    pub country: Option<api_enums::CountryAlpha2>,
+    pub capture_method: Option<storage_enums::CaptureMethod>,
+    pub is_mandate: bool,
+    pub has_mandate_id: bool,
+    pub allowed_payment_method_types: Option<serde_json::Value>,
+    /// The filters of the request, without the client secret
+    pub filters: api::PaymentMethodListRequest,
+}
+
+impl PaymentMethodListInputs {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        merchant_account: &domain::MerchantAccount,
+        profile_id: Option<&String>,
+        connector_accounts: &[domain::MerchantConnectorAccount],
+        payment_intent: Option<&storage::PaymentIntent>,
+        payment_attempt: Option<&storage::PaymentAttempt>,
+        payment_amount: Option<&eligibility::PaymentAmount>,
+        billing_address: Option<&domain::Address>,
+        req: &api::PaymentMethodListRequest,
+    ) -> Self {
+        let mut connector_accounts = connector_accounts.iter().collect::<Vec<_>>();
+        connector_accounts.sort_by(|a, b| a.merchant_connector_id.cmp(&b.merchant_connector_id));
+
+        Self {
+            merchant_id: merchant_account.merchant_id.clone(),
+            merchant_modified_at: merchant_account.modified_at.to_string(),
+            profile_id: profile_id.cloned(),
+            amount_bucket: payment_amount
+                .map(|payment_amount| get_amount_bucket(&connector_accounts, payment_amount))
+                .unwrap_or_default(),
+            connector_accounts: connector_accounts
+                .iter()
+                .map(|mca| {
+                    (
+                        mca.merchant_connector_id.clone(),
+                        mca.modified_at.to_string(),
+                    )
+                })
+                .collect(),
+            is_payment: payment_intent.is_some(),
+            currency: payment_intent.and_then(|payment_intent| payment_intent.currency),
+            attempt_currency: payment_attempt.and_then(|payment_attempt| payment_attempt.currency),
+            country: billing_address.and_then(|address| address.country),
+            capture_method: payment_attempt
+                .and_then(|payment_attempt| payment_attempt.capture_method),
+            is_mandate: payment_attempt.map_or(false, |payment_attempt| {
+                payment_attempt.mandate_details.is_some()
+            }),
+            has_mandate_id: payment_attempt.map_or(false, |payment_attempt| {
+                payment_attempt.mandate_id.is_some()
+            }),
+            allowed_payment_method_types: payment_intent
+                .and_then(|payment_intent| payment_intent.allowed_payment_method_types.clone()),
+            filters: api::PaymentMethodListRequest {
+                client_secret: None,
+                ..req.clone()
+            },
+        }
+    }
+}
+
+/// Inputs of the listed payment methods which are specific to the customer of the payment, from
+/// which the values prefilled in the required fields are taken
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CustomerSectionInputs {
+    pub customer_id: Option<String>,
+    pub customer_modified_at: Option<String>,
+    /// Identifier and time of the last change of the shipping and billing addresses
+    pub addresses: Vec<(String, String)>,
+    /// Identifier and time of the last change of the payment attempt
+    pub payment_attempt: Option<(String, String)>,
+}
+
+impl CustomerSectionInputs {
+    pub fn new(
+        customer: Option<&domain::Customer>,
+        shipping_address: Option<&domain::Address>,
+        billing_address: Option<&domain::Address>,
+        payment_attempt: Option<&storage::PaymentAttempt>,
+    ) -> Self {
+        Self {
+            customer_id: customer.map(|customer| customer.customer_id.clone()),
+            customer_modified_at: customer.map(|customer| customer.modified_at.to_string()),
+            addresses: [shipping_address, billing_address]
+                .into_iter()
+                .flatten()
+                .map(|address| (address.address_id.clone(), address.modified_at.to_string()))
+                .collect(),
+            payment_attempt: payment_attempt.map(|payment_attempt| {
+                (
+                    payment_attempt.attempt_id.clone(),
+                    payment_attempt.modified_at.to_string(),
+                )
+            }),
+        }
+    }
+}
+
+/// The amount ranges configured on the payment method types of the connector accounts which the
+/// amount falls into, payment method types without an amount range are left out
+fn get_amount_bucket(
+    connector_accounts: &[&domain::MerchantConnectorAccount],
+    payment_amount: &eligibility::PaymentAmount,
+) -> Vec<bool> {
+    connector_accounts
+        .iter()
+        .flat_map(|mca| mca.payment_methods_enabled.iter().flatten())
+        .filter_map(|payment_methods| {
+            serde_json::from_value::<PaymentMethodsEnabled>(payment_methods.clone()).ok()
+        })
+        .flat_map(|payment_methods| payment_methods.payment_method_types.unwrap_or_default())
+        .filter(|payment_method_type: &RequestPaymentMethodTypes| {
+            payment_method_type.minimum_amount.is_some()
+                || payment_method_type.maximum_amount.is_some()
+        })
+        .map(|payment_method_type| {
+            eligibility::check_amount_range(&payment_method_type, payment_amount).is_ok()
+        })
+        .collect()
+}
+
+/// Deterministic hash of the inputs, as a hex string
+pub fn get_inputs_hash<T: Serialize>(inputs: &T) -> RouterResult<String> {
+    let serialized_inputs = serde_json::to_vec(inputs)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the inputs of the payment method list")?;
+    crypto::Sha256
+        .generate_digest(&serialized_inputs)
+        .map(hex::encode)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to hash the inputs of the payment method list")
+}
+
+/// ETag of the listed payment methods, from the hash of the inputs of the cached list and from the
+/// inputs specific to the customer
+pub fn get_etag(list_hash: &str, customer_inputs: &CustomerSectionInputs) -> RouterResult<String> {
+    get_inputs_hash(&(list_hash, customer_inputs)).map(|hash| format!("\"{hash}\""))
+}
+
+/// Whether one of the ETags of the `If-None-Match` header of the request matches the ETag of the
+/// response. Weak ETags are compared by their value.
+pub fn is_not_modified(if_none_match: Option<&str>, etag: &str) -> bool {
+    let strip_weak = |etag: &str| etag.trim().trim_start_matches("W/").to_string();
+    if_none_match.map_or(false, |if_none_match| {
+        if_none_match.trim() == "*"
+            || if_none_match
+                .split(',')
+                .any(|requested_etag| strip_weak(requested_etag) == strip_weak(etag))
+    })
+}
+
+/// Headers sent with the listed payment methods, and with the `304 Not Modified` responses
+pub fn get_cache_headers(etag: String) -> Vec<(String, String)> {
+    vec![
+        (headers::ETAG.to_string(), etag),
+        // The response holds the details of the customer, it must not be stored by shared caches
+        (
+            headers::CACHE_CONTROL.to_string(),
+            "private, no-cache".to_string(),
+        ),
+    ]
+}
+
+/// Fetches the cached list, failures in accessing redis are treated as a cache miss
+#[instrument(skip_all)]
+pub async fn get_cached_payment_methods(
+    state: &AppState,
+    merchant_id: &str,
+    list_hash: &str,
+) -> Option<Vec<ResponsePaymentMethodIntermediate>> {
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to get redis connection for payment method list"
+            );
+            return None;
+        }
+    };
+
+    match redis_conn
+        .get_hash_field_and_deserialize(
+            &get_payment_method_list_cache_key(merchant_id),
+            list_hash,
+            "Vec<ResponsePaymentMethodIntermediate>",
+        )
+        .await
+    {
+        Ok(payment_methods) => Some(payment_methods),
+        Err(error) => {
+            if !matches!(error.current_context(), errors::RedisError::NotFound) {
+                logger::error!(?error, "Failed to fetch the cached payment method list");
+            }
+            None
+        }
+    }
+}
+
+/// Caches the list, failures in accessing redis do not fail the request
+#[instrument(skip_all)]
+pub async fn cache_payment_methods(
+    state: &AppState,
+    merchant_id: &str,
+    list_hash: &str,
+    payment_methods: &[ResponsePaymentMethodIntermediate],
+) {
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to get redis connection for payment method list"
+            );
+            return;
+        }
+    };
+    let redis_value = match serde_json::to_string(payment_methods) {
+        Ok(redis_value) => redis_value,
+        Err(error) => {
+            logger::error!(?error, "Failed to serialize the payment method list");
+            return;
+        }
+    };
+
+    if let Err(error) = redis_conn
+        .set_hash_fields(
+            &get_payment_method_list_cache_key(merchant_id),
+            (list_hash, &redis_value),
+        )
+        .await
+    {
+        logger::error!(?error, "Failed to cache the payment method list");
+    }
+}
+
+/// Removes the cached lists of the merchant, when one of its connector accounts is changed
+#[instrument(skip_all)]
+pub async fn invalidate_payment_method_list_cache(state: &AppState, merchant_id: &str) {
+    match state.store.get_redis_conn() {
+        Ok(redis_conn) => {
+            if let Err(error) = redis_conn
+                .delete_key(&get_payment_method_list_cache_key(merchant_id))
+                .await
+            {
+                logger::error!(?error, "Failed to invalidate the payment method list");
+            }
+        }
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to get redis connection for payment method list"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn get_inputs() -> PaymentMethodListInputs {
+        PaymentMethodListInputs {
+            merchant_id: "merchant_1".to_string(),
+            merchant_modified_at: "2023-11-15 10:00:00.0".to_string(),
+            profile_id: Some("pro_1".to_string()),
+            connector_accounts: vec![
+                ("mca_adyen".to_string(), "2023-11-15 10:00:00.0".to_string()),
+                (
+                    "mca_stripe".to_string(),
+                    "2023-11-15 10:00:00.0".to_string(),
+                ),
+            ],
+            amount_bucket: vec![true, false],
+            is_payment: true,
+            currency: Some(api_enums::Currency::EUR),
+            attempt_currency: Some(api_enums::Currency::EUR),
+            country: Some(api_enums::CountryAlpha2::NL),
+            ..Default::default()
+        }
+    }
+
+    fn get_customer_inputs(customer_id: &str) -> CustomerSectionInputs {
+        CustomerSectionInputs {
+            customer_id: Some(customer_id.to_string()),
+            customer_modified_at: Some("2023-11-15 10:00:00.0".to_string()),
+            addresses: vec![(
+                format!("add_{customer_id}"),
+                "2023-11-15 10:00:00.0".to_string(),
+            )],
+            payment_attempt: Some((
+                format!("pay_{customer_id}_1"),
+                "2023-11-15 10:00:00.0".to_string(),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_unchanged_inputs_are_not_modified() {
+        let list_hash = get_inputs_hash(&get_inputs()).unwrap();
+        let etag = get_etag(&list_hash, &get_customer_inputs("cus_1")).unwrap();
+
+        // The same inputs give the same ETag on the next load of the checkout page
+        let next_list_hash = get_inputs_hash(&get_inputs()).unwrap();
+        let next_etag = get_etag(&next_list_hash, &get_customer_inputs("cus_1")).unwrap();
+        assert_eq!(list_hash, next_list_hash);
+        assert!(is_not_modified(Some(&etag), &next_etag));
+        assert!(is_not_modified(Some(&format!("W/{etag}")), &next_etag));
+        assert!(is_not_modified(
+            Some(&format!("\"stale\", {etag}")),
+            &next_etag
+        ));
+        assert!(is_not_modified(Some("*"), &next_etag));
+
+        assert!(!is_not_modified(None, &next_etag));
+        assert!(!is_not_modified(Some("\"stale\""), &next_etag));
+    }
+
+    #[test]
+    fn test_connector_account_update_changes_cached_list() {
+        let inputs = get_inputs();
+        let list_hash = get_inputs_hash(&inputs).unwrap();
+        let etag = get_etag(&list_hash, &get_customer_inputs("cus_1")).unwrap();
+
+        let mut updated_inputs = inputs.clone();
+        updated_inputs.connector_accounts[1].1 = "2023-11-15 11:00:00.0".to_string();
+        let updated_list_hash = get_inputs_hash(&updated_inputs).unwrap();
+        let updated_etag = get_etag(&updated_list_hash, &get_customer_inputs("cus_1")).unwrap();
+        assert_ne!(list_hash, updated_list_hash);
+        assert!(!is_not_modified(Some(&etag), &updated_etag));
+
+        // A connector account added to the merchant changes the list as well
+        let mut added_inputs = inputs.clone();
+        added_inputs.connector_accounts.push((
+            "mca_worldpay".to_string(),
+            "2023-11-15 11:00:00.0".to_string(),
+        ));
+        assert_ne!(list_hash, get_inputs_hash(&added_inputs).unwrap());
+
+        // Amounts within the same amount ranges share the list, other ranges do not
+        let mut other_amount_inputs = inputs;
+        other_amount_inputs.amount_bucket = vec![true, true];
+        assert_ne!(list_hash, get_inputs_hash(&other_amount_inputs).unwrap());
+
+        assert_eq!(
+            get_payment_method_list_cache_key("merchant_1"),
+            "payment_method_list_merchant_1"
+        );
+    }
+
+    #[test]
+    fn test_customers_do_not_share_customer_section() {
+        let list_hash = get_inputs_hash(&get_inputs()).unwrap();
+
+        // The cached list is shared by the payments of both customers, the response is not
+        let first_etag = get_etag(&list_hash, &get_customer_inputs("cus_1")).unwrap();
+        let second_etag = get_etag(&list_hash, &get_customer_inputs("cus_2")).unwrap();
+        assert_ne!(first_etag, second_etag);
+        assert!(!is_not_modified(Some(&first_etag), &second_etag));
+
+        // The customer is not part of the inputs of the cached list
+        let serialized_inputs = serde_json::to_string(&get_inputs()).unwrap();
+        assert!(!serialized_inputs.contains("cus_1"));
+
+        // A change of the details of the customer changes the response
+        let mut updated_customer = get_customer_inputs("cus_1");
+        updated_customer.customer_modified_at = Some("2023-11-15 11:00:00.0".to_string());
+        assert_ne!(first_etag, get_etag(&list_hash, &updated_customer).unwrap());
+    }
+}
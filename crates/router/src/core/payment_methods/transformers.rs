@@ -26,6 +26,9 @@ pub enum StoreLockerReq<'a> {
 pub struct StoreCardReq<'a> {
     pub merchant_id: &'a str,
     pub merchant_customer_id: String,
+    /// Reference with which the card is stored, instead of one generated by the locker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requestor_card_reference: Option<String>,
     pub card: Card,
 }
 
@@ -0,0 +1,449 @@
+//! Timeline of the steps of the processing of the payment attempts, so that the progress of a
+//! payment can be reported to the customer ("contacting bank", "awaiting authentication") while
+//! the status of the payment is still `processing`. The steps are recorded when they happen and are
+//! never updated, and they are informational only: no status is derived from them.
+//!
+//! The timeline of a payment is returned by retrieve with `expand=timeline`, and in the outgoing
+//! webhooks of the merchants which opted in to it.
+
+use api_models::{payments::PaymentTimelineEvent, webhooks::OutgoingWebhookContent};
+use common_utils::date_time;
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+
+use super::{
+    errors::{self, RouterResult},
+    payments::CallConnectorAction,
+};
+use crate::{
+    db::StorageInterface,
+    services::ApplicationResponse,
+    types::{self, api, storage, storage::enums},
+};
+
+/// Key of the config holding whether the timeline of the payments is sent in the outgoing webhooks
+/// of the merchant
+pub fn get_payment_timeline_webhooks_key(merchant_id: &str) -> String {
+    format!("payment_timeline_webhooks_{merchant_id}")
+}
+
+pub async fn is_timeline_webhook_enabled(db: &dyn StorageInterface, merchant_id: &str) -> bool {
+    let config = db
+        .find_config_by_key(&get_payment_timeline_webhooks_key(merchant_id))
+        .await;
+
+    match config {
+        Ok(config) => config.config == "true",
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(
+                    ?error,
+                    "Failed to fetch the payment timeline webhooks config"
+                );
+            }
+            false
+        }
+    }
+}
+
+/// The attempt which a step is recorded for
+#[derive(Clone, Copy, Debug)]
+pub struct StepEventSource<'a> {
+    pub merchant_id: &'a str,
+    pub payment_id: &'a str,
+    pub attempt_id: &'a str,
+    pub connector: Option<&'a str>,
+}
+
+impl<'a, F, Req, Res> From<&'a types::RouterData<F, Req, Res>> for StepEventSource<'a> {
+    fn from(router_data: &'a types::RouterData<F, Req, Res>) -> Self {
+        Self {
+            merchant_id: &router_data.merchant_id,
+            payment_id: &router_data.payment_id,
+            attempt_id: &router_data.attempt_id,
+            connector: Some(&router_data.connector),
+        }
+    }
+}
+
+/// Records a step of the attempt. A step which cannot be recorded is only logged, it never fails
+/// the payment.
+#[instrument(skip(db))]
+pub async fn record_step(
+    db: &dyn StorageInterface,
+    source: StepEventSource<'_>,
+    step: enums::PaymentAttemptStep,
+) {
+    let event = storage::PaymentAttemptStepEventNew {
+        merchant_id: source.merchant_id.to_string(),
+        payment_id: source.payment_id.to_string(),
+        attempt_id: source.attempt_id.to_string(),
+        step,
+        connector: source.connector.map(str::to_string),
+        created_at: date_time::now(),
+    };
+    if let Err(error) = db.insert_payment_attempt_step_event(event).await {
+        logger::error!(?error, "Failed to record the step of the payment attempt");
+    }
+}
+
+/// Records the call to the connector in a confirm flow. The flows which only handle a response
+/// received from the connector, like a redirection, do not call the connector.
+pub async fn record_connector_call_started<F, Req, Res>(
+    db: &dyn StorageInterface,
+    router_data: &types::RouterData<F, Req, Res>,
+    call_connector_action: &CallConnectorAction,
+) {
+    if matches!(call_connector_action, CallConnectorAction::Trigger) {
+        record_step(
+            db,
+            StepEventSource::from(router_data),
+            enums::PaymentAttemptStep::ConnectorCallStarted,
+        )
+        .await;
+    }
+}
+
+/// The step which follows from the response of the connector in a confirm flow
+pub fn get_confirm_response_step(
+    response: &Result<types::PaymentsResponseData, types::ErrorResponse>,
+) -> Option<enums::PaymentAttemptStep> {
+    match response {
+        Ok(types::PaymentsResponseData::TransactionResponse {
+            redirection_data: Some(_),
+            ..
+        }) => Some(enums::PaymentAttemptStep::RedirectIssued),
+        _ => None,
+    }
+}
+
+/// Whether the payment is still being processed by the connector after it was synced
+pub fn is_sync_pending(status: enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        enums::AttemptStatus::Pending
+            | enums::AttemptStatus::Authorizing
+            | enums::AttemptStatus::AuthenticationPending
+            | enums::AttemptStatus::AuthenticationSuccessful
+            | enums::AttemptStatus::DeviceDataCollectionPending
+            | enums::AttemptStatus::CaptureInitiated
+            | enums::AttemptStatus::VoidInitiated
+            | enums::AttemptStatus::Unresolved
+    )
+}
+
+pub async fn record_confirm_response_steps<F, Req>(
+    db: &dyn StorageInterface,
+    router_data: &types::RouterData<F, Req, types::PaymentsResponseData>,
+) {
+    if let Some(step) = get_confirm_response_step(&router_data.response) {
+        record_step(db, StepEventSource::from(router_data), step).await;
+    }
+}
+
+/// Records that the payment is still pending after a sync. A payment which is synced repeatedly
+/// while it is pending, like when the status is polled, has the step recorded once.
+pub async fn record_sync_response_steps<F, Req>(
+    db: &dyn StorageInterface,
+    router_data: &types::RouterData<F, Req, types::PaymentsResponseData>,
+) {
+    if router_data.response.is_err() || !is_sync_pending(router_data.status) {
+        return;
+    }
+    let source = StepEventSource::from(router_data);
+    let events = match db
+        .find_payment_attempt_step_events_by_merchant_id_payment_id(
+            source.merchant_id,
+            source.payment_id,
+        )
+        .await
+    {
+        Ok(events) => events,
+        Err(error) => {
+            logger::error!(?error, "Failed to fetch the steps of the payment attempt");
+            return;
+        }
+    };
+    let is_already_pending = events.last().map_or(false, |event| {
+        event.attempt_id == source.attempt_id
+            && event.step == enums::PaymentAttemptStep::SyncPending
+    });
+    if !is_already_pending {
+        record_step(db, source, enums::PaymentAttemptStep::SyncPending).await;
+    }
+}
+
+/// Records the return of the customer from the redirection. The attempt is the one appended by the
+/// router to the return URL, or the active attempt of the payment for the return URLs without it.
+#[instrument(skip(db))]
+pub async fn record_redirect_returned(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    storage_scheme: enums::MerchantStorageScheme,
+    payment_id: &str,
+    attempt_id: Option<&str>,
+    connector: &str,
+) {
+    let attempt_id = match attempt_id {
+        Some(attempt_id) => attempt_id.to_string(),
+        None => match db
+            .find_payment_intent_by_payment_id_merchant_id(payment_id, merchant_id, storage_scheme)
+            .await
+        {
+            Ok(payment_intent) => payment_intent.active_attempt_id,
+            Err(error) => {
+                logger::error!(
+                    ?error,
+                    "Failed to find the payment of the redirect response"
+                );
+                return;
+            }
+        },
+    };
+    record_step(
+        db,
+        StepEventSource {
+            merchant_id,
+            payment_id,
+            attempt_id: &attempt_id,
+            connector: Some(connector),
+        },
+        enums::PaymentAttemptStep::RedirectReturned,
+    )
+    .await;
+}
+
+/// Returns the steps of the attempts of the payment, in the order they were recorded
+pub async fn get_payment_timeline(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    payment_id: &str,
+) -> RouterResult<Vec<PaymentTimelineEvent>> {
+    let events = db
+        .find_payment_attempt_step_events_by_merchant_id_payment_id(merchant_id, payment_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the timeline of the payment")?;
+
+    Ok(events
+        .into_iter()
+        .map(|event| PaymentTimelineEvent {
+            step: event.step,
+            attempt_id: event.attempt_id,
+            connector: event.connector,
+            created_at: event.created_at,
+        })
+        .collect())
+}
+
+pub async fn add_timeline_to_payments_response(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    response: ApplicationResponse<api::PaymentsResponse>,
+) -> RouterResult<ApplicationResponse<api::PaymentsResponse>> {
+    Ok(match response {
+        ApplicationResponse::Json(payments_response) => {
+            ApplicationResponse::Json(with_timeline(db, merchant_id, payments_response).await?)
+        }
+        ApplicationResponse::JsonWithHeaders((payments_response, headers)) => {
+            ApplicationResponse::JsonWithHeaders((
+                with_timeline(db, merchant_id, payments_response).await?,
+                headers,
+            ))
+        }
+        response => response,
+    })
+}
+
+async fn with_timeline(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    mut payments_response: api::PaymentsResponse,
+) -> RouterResult<api::PaymentsResponse> {
+    let payment_id = payments_response.payment_id.clone().unwrap_or_default();
+    payments_response.timeline = Some(get_payment_timeline(db, merchant_id, &payment_id).await?);
+    Ok(payments_response)
+}
+
+/// The timeline sent in the outgoing webhooks of the payments, for the merchants which opted in to
+/// it. A timeline which cannot be fetched is left out of the webhook.
+pub async fn get_outgoing_webhook_timeline(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    content: &OutgoingWebhookContent,
+) -> Option<Vec<PaymentTimelineEvent>> {
+    let OutgoingWebhookContent::PaymentDetails(payments_response) = content else {
+        return None;
+    };
+    if !is_timeline_webhook_enabled(db, merchant_id).await {
+        return None;
+    }
+    let payment_id = payments_response.payment_id.as_deref()?;
+    get_payment_timeline(db, merchant_id, payment_id)
+        .await
+        .map_err(|error| logger::error!(?error, "Failed to fetch the timeline of the webhook"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::db::MockDb;
+
+    async fn get_db(timeline_webhooks_enabled: bool) -> MockDb {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        if timeline_webhooks_enabled {
+            db.insert_config(storage::ConfigNew {
+                key: get_payment_timeline_webhooks_key("merchant_1"),
+                config: "true".to_string(),
+            })
+            .await
+            .unwrap();
+        }
+        db
+    }
+
+    fn get_steps(timeline: &[PaymentTimelineEvent]) -> Vec<enums::PaymentAttemptStep> {
+        timeline.iter().map(|event| event.step).collect()
+    }
+
+    /// A 3DS payment with the dummy connector: the payment is redirected for authentication,
+    /// stays pending while it is polled after the customer returns, and then succeeds
+    #[cfg(feature = "dummy_connector")]
+    #[tokio::test]
+    async fn test_steps_of_a_three_ds_payment_are_recorded_in_order() {
+        use crate::{
+            connector::dummyconnector::transformers as dummyconnector,
+            types::test_utils::get_authorize_router_data,
+        };
+
+        fn get_connector_response(
+            status: &str,
+            next_action: Option<serde_json::Value>,
+        ) -> types::PaymentsAuthorizeRouterData {
+            let mut router_data = get_authorize_router_data(None);
+            router_data.merchant_id = "merchant_1".to_string();
+            router_data.connector = "dummyconnector".to_string();
+            let response: dummyconnector::PaymentsResponse =
+                serde_json::from_value(serde_json::json!({
+                    "status": status,
+                    "id": "dummy_pay_1",
+                    "amount": 1000,
+                    "currency": "USD",
+                    "created": "2023-11-12T10:00:00.000Z",
+                    "payment_method_type": "card",
+                    "next_action": next_action,
+                }))
+                .unwrap();
+            types::RouterData::try_from(types::ResponseRouterData {
+                response,
+                data: router_data,
+                http_code: 200,
+            })
+            .unwrap()
+        }
+
+        let db = get_db(false).await;
+        let authorize_request = get_connector_response("processing", None);
+
+        // Authorize, answered with a redirection for 3DS
+        record_connector_call_started(&db, &authorize_request, &CallConnectorAction::Trigger).await;
+        let authorize_response = get_connector_response(
+            "processing",
+            Some(serde_json::json!({
+                "redirect_to_url": "https://dummy-connector.example.com/authorize/dummy_pay_1"
+            })),
+        );
+        record_confirm_response_steps(&db, &authorize_response).await;
+
+        // The customer returns, and the status is polled twice before the payment succeeds
+        record_redirect_returned(
+            &db,
+            "merchant_1",
+            enums::MerchantStorageScheme::PostgresOnly,
+            "pay_1",
+            Some("pay_1_1"),
+            "dummyconnector",
+        )
+        .await;
+        let pending_sync_response = get_connector_response("processing", None);
+        record_sync_response_steps(&db, &pending_sync_response).await;
+        record_sync_response_steps(&db, &pending_sync_response).await;
+        record_sync_response_steps(&db, &get_connector_response("succeeded", None)).await;
+
+        let timeline = get_payment_timeline(&db, "merchant_1", "pay_1")
+            .await
+            .unwrap();
+        assert_eq!(
+            get_steps(&timeline),
+            vec![
+                enums::PaymentAttemptStep::ConnectorCallStarted,
+                enums::PaymentAttemptStep::RedirectIssued,
+                enums::PaymentAttemptStep::RedirectReturned,
+                enums::PaymentAttemptStep::SyncPending,
+            ]
+        );
+        assert!(timeline.iter().all(|event| event.attempt_id == "pay_1_1"
+            && event.connector.as_deref() == Some("dummyconnector")));
+        // The steps do not change the status reported by the connector
+        assert_eq!(
+            authorize_response.status,
+            enums::AttemptStatus::AuthenticationPending
+        );
+
+        // A redirection handled from its response does not call the connector
+        record_connector_call_started(
+            &db,
+            &authorize_request,
+            &CallConnectorAction::HandleResponse(Vec::new()),
+        )
+        .await;
+        assert_eq!(
+            get_payment_timeline(&db, "merchant_1", "pay_1")
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeline_is_sent_in_the_webhooks_of_the_merchants_which_opted_in() {
+        let content =
+            OutgoingWebhookContent::PaymentDetails(api_models::payments::PaymentsResponse {
+                payment_id: Some("pay_1".to_string()),
+                ..Default::default()
+            });
+        let source = StepEventSource {
+            merchant_id: "merchant_1",
+            payment_id: "pay_1",
+            attempt_id: "pay_1_1",
+            connector: Some("stripe"),
+        };
+
+        let db = get_db(false).await;
+        record_step(&db, source, enums::PaymentAttemptStep::ConnectorCallStarted).await;
+        assert_eq!(
+            get_outgoing_webhook_timeline(&db, "merchant_1", &content).await,
+            None
+        );
+
+        let db = get_db(true).await;
+        record_step(&db, source, enums::PaymentAttemptStep::ConnectorCallStarted).await;
+        let timeline = get_outgoing_webhook_timeline(&db, "merchant_1", &content)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_steps(&timeline),
+            vec![enums::PaymentAttemptStep::ConnectorCallStarted]
+        );
+        assert_eq!(
+            serde_json::to_value(&timeline[0]).unwrap()["step"],
+            "connector_call_started"
+        );
+    }
+}
@@ -1,17 +1,28 @@
 pub mod access_token;
+pub mod amounts;
+pub mod confirm_lock;
+pub mod connector_account_selection;
 pub mod customers;
+pub mod device_data;
+pub mod duplicate_order_reference;
+pub mod eligibility;
 pub mod flows;
 pub mod helpers;
 pub mod operations;
+pub mod redirect_identification;
+pub mod three_ds_decision;
 pub mod tokenization;
 pub mod transformers;
 pub mod types;
+pub mod wallet_decryption;
 
 use std::{fmt::Debug, marker::PhantomData, ops::Deref, time::Instant};
 
 use api_models::{enums, payments::HeaderPayload};
 use common_utils::{ext_traits::AsyncExt, pii};
 use data_models::mandates::MandateData;
+#[cfg(feature = "olap")]
+use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
 use diesel_models::{ephemeral_key, fraud_check::FraudCheck};
 use error_stack::{IntoReport, ResultExt};
 use futures::future::join_all;
@@ -36,9 +47,12 @@ use super::errors::StorageErrorExt;
 use crate::types::transformers::ForeignFrom;
 use crate::{
     configs::settings::PaymentMethodTypeTokenFilter,
+    connector::utils::capabilities,
+    consts,
     core::{
+        circuit_breaker,
         errors::{self, CustomResult, RouterResponse, RouterResult},
-        utils,
+        payment_timeline, routing_decisions, utils, webhooks,
     },
     db::StorageInterface,
     logger,
@@ -47,6 +61,7 @@ use crate::{
     types::{
         self as router_types, api, domain,
         storage::{self, enums as storage_enums},
+        transformers::ForeignInto,
     },
     utils::{
         add_apple_pay_flow_metrics, add_connector_http_status_code_metrics, Encode, OptionExt,
@@ -118,6 +133,11 @@ where
         .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)
         .attach_printable("Failed while fetching/creating customer")?;
 
+    operation
+        .to_domain()?
+        .guard_payment_against_blocklist(state, &merchant_account, &key_store, &mut payment_data)
+        .await?;
+
     let connector = get_connector_choice(
         &operation,
         state,
@@ -128,6 +148,14 @@ where
     )
     .await?;
 
+    // Split payments are rejected before calling a connector which would process the whole amount
+    if let Some(api::ConnectorCallType::Single(connector_data)) = &connector {
+        helpers::validate_split_payment_support(
+            &payment_data.payment_intent,
+            connector_data.connector_name,
+        )?;
+    }
+
     let schedule_time = match &connector {
         Some(api::ConnectorCallType::Single(connector_data)) => {
             if should_add_task_to_process_tracker(&payment_data) {
@@ -135,6 +163,7 @@ where
                     &*state.store,
                     connector_data.connector.id(),
                     &merchant_account.merchant_id,
+                    payment_data.payment_attempt.payment_method,
                     0,
                 )
                 .await
@@ -161,11 +190,11 @@ where
     if let Some(connector_details) = connector {
         payment_data = match connector_details {
             api::ConnectorCallType::Single(connector) => {
-                let router_data = call_connector_service(
+                let router_data_result = call_connector_service(
                     state,
                     &merchant_account,
                     &key_store,
-                    connector,
+                    connector.clone(),
                     &operation,
                     &mut payment_data,
                     &customer,
@@ -174,14 +203,25 @@ where
                     schedule_time,
                     header_payload,
                 )
-                .await?;
+                .await;
+                // The locale of the payment is resolved while calling the connector
+                let router_data = router_data_result.map_err(|error| {
+                    errors::user_messages::attach_locale(
+                        error,
+                        payment_data.payment_attempt.locale.as_deref(),
+                    )
+                })?;
 
                 let operation = Box::new(PaymentResponse);
                 let db = &*state.store;
                 connector_http_status_code = router_data.connector_http_status_code;
                 //add connector http status code metrics
                 add_connector_http_status_code_metrics(connector_http_status_code);
-                operation
+                let router_data_to_poll = router_data
+                    .poll_config
+                    .is_some()
+                    .then(|| router_data.clone());
+                let mut payment_data = operation
                     .to_post_update_tracker()?
                     .update_tracker(
                         db,
@@ -190,7 +230,38 @@ where
                         router_data,
                         merchant_account.storage_scheme,
                     )
-                    .await?
+                    .await?;
+
+                // The pending status of an operation which the connector accepted without
+                // completing it is stored before its status is polled
+                if let Some(router_data) = router_data_to_poll {
+                    let router_data = poll_connector_operation(
+                        state,
+                        &connector,
+                        &payment_data.payment_attempt,
+                        router_data,
+                        schedule_time,
+                    )
+                    .await;
+                    payment_data = operation
+                        .to_post_update_tracker()?
+                        .update_tracker(
+                            db,
+                            &validate_result.payment_id,
+                            payment_data,
+                            router_data,
+                            merchant_account.storage_scheme,
+                        )
+                        .await?;
+                }
+
+                track_authorization_expiry_if_required(
+                    state,
+                    &mut payment_data,
+                    merchant_account.storage_scheme,
+                )
+                .await?;
+                payment_data
             }
 
             api::ConnectorCallType::Multiple(connectors) => {
@@ -353,10 +424,9 @@ pub trait PaymentRedirectFlow: Sync {
 
         let query_params = req.param.clone().get_required_value("param")?;
 
-        let resource_id = api::PaymentIdTypeExt::get_payment_intent_id(&req.resource_id)
-            .change_context(errors::ApiErrorResponse::MissingRequiredField {
-                field_name: "payment_id",
-            })?;
+        // The attempt appended by the router to the return URL is not passed to the connector
+        let (attempt_id, query_params) =
+            redirect_identification::split_redirect_attempt_id(&query_params);
 
         let connector_data = api::ConnectorData::get_connector_by_name(
             &state.conf.connectors,
@@ -364,6 +434,44 @@ pub trait PaymentRedirectFlow: Sync {
             api::GetToken::Connector,
         )?;
 
+        let path_payment_id = api::PaymentIdTypeExt::get_payment_intent_id(&req.resource_id).ok();
+        let identification = redirect_identification::identify_redirect_payment(
+            &state,
+            &merchant_account,
+            &connector_data,
+            &connector,
+            path_payment_id.as_deref(),
+            attempt_id.as_deref(),
+            &query_params,
+            req.json_payload.as_ref(),
+        )
+        .await?;
+        let resource_id = match identification {
+            Ok(payment_id) => payment_id,
+            Err(error) => {
+                return redirect_identification::get_failure_redirection_response(
+                    &state.conf.redirect_response,
+                    &merchant_account,
+                    error,
+                )
+                .map(services::ApplicationResponse::JsonForRedirection);
+            }
+        };
+        payment_timeline::record_redirect_returned(
+            &*state.store,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+            &resource_id,
+            attempt_id.as_deref(),
+            &connector,
+        )
+        .await;
+        let req = PaymentsRedirectResponseData {
+            resource_id: api::PaymentIdType::PaymentIntentId(resource_id.clone()),
+            param: Some(query_params.clone()),
+            ..req
+        };
+
         let flow_type = connector_data
             .connector
             .get_flow_type(
@@ -374,11 +482,17 @@ pub trait PaymentRedirectFlow: Sync {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to decide the response flow")?;
 
+        let is_cancelled_by_customer = connector_data
+            .connector
+            .is_cancelled_by_customer(&query_params, req.json_payload.as_ref())
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to check whether the customer cancelled the payment")?;
+
         let response = self
             .call_payment_flow(
                 &state,
                 merchant_account.clone(),
-                key_store,
+                key_store.clone(),
                 req.clone(),
                 flow_type,
             )
@@ -392,6 +506,16 @@ pub trait PaymentRedirectFlow: Sync {
                 .attach_printable("Failed to get the response in json"),
         }?;
 
+        let void_merchant_account = merchant_account.clone();
+        let payments_response = void_abandoned_payment_if_required(
+            is_cancelled_by_customer,
+            payments_response,
+            &resource_id,
+            req.creds_identifier,
+            |cancel_request| void_payment(&state, void_merchant_account, key_store, cancel_request),
+        )
+        .await?;
+
         let result =
             self.generate_response(payments_response, merchant_account, resource_id, connector)?;
 
@@ -399,6 +523,201 @@ pub trait PaymentRedirectFlow: Sync {
     }
 }
 
+/// A payment cancelled by the customer on the page of the connector is voided only if it was
+/// authorized, payments which were not authorized are left with the status from the connector
+fn should_void_abandoned_payment(
+    is_cancelled_by_customer: bool,
+    payments_response: &api::PaymentsResponse,
+) -> bool {
+    is_cancelled_by_customer
+        && payments_response.status == enums::IntentStatus::RequiresCapture
+        && payments_response.connector_transaction_id.is_some()
+}
+
+/// Voids the authorization of a payment which the customer cancelled on the page of the connector,
+/// other payments are returned as they are
+async fn void_abandoned_payment_if_required<V, Fut>(
+    is_cancelled_by_customer: bool,
+    payments_response: api::PaymentsResponse,
+    payment_id: &str,
+    creds_identifier: Option<String>,
+    void_payment: V,
+) -> RouterResult<api::PaymentsResponse>
+where
+    V: FnOnce(api::PaymentsCancelRequest) -> Fut,
+    Fut: futures::Future<Output = RouterResult<api::PaymentsResponse>>,
+{
+    if !should_void_abandoned_payment(is_cancelled_by_customer, &payments_response) {
+        return Ok(payments_response);
+    }
+
+    logger::info!("Voiding payment {payment_id} cancelled by the customer on the connector page");
+    void_payment(get_abandoned_payment_cancel_request(
+        payment_id.to_owned(),
+        creds_identifier,
+    ))
+    .await
+}
+
+fn get_abandoned_payment_cancel_request(
+    payment_id: String,
+    creds_identifier: Option<String>,
+) -> api::PaymentsCancelRequest {
+    api::PaymentsCancelRequest {
+        payment_id,
+        cancellation_reason: Some(consts::CUSTOMER_ABANDONED_CANCELLATION_REASON.to_string()),
+        cancellation_reason_code: Some(enums::CancellationReason::Abandoned),
+        merchant_connector_details: creds_identifier.map(|creds_identifier| {
+            api::MerchantConnectorDetailsWrap {
+                creds_identifier,
+                encoded_data: None,
+            }
+        }),
+    }
+}
+
+fn get_partial_authorization_cancel_request(payment_id: String) -> api::PaymentsCancelRequest {
+    api::PaymentsCancelRequest {
+        payment_id,
+        cancellation_reason: Some(consts::PARTIAL_AUTHORIZATION_CANCELLATION_REASON.to_string()),
+        cancellation_reason_code: Some(enums::CancellationReason::PartiallyAuthorized),
+        merchant_connector_details: None,
+    }
+}
+
+/// What is done with a payment which the connector authorized for less than its amount
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PartialAuthorizationAction {
+    /// The merchant requested partial authorizations, and is notified to capture the authorized
+    /// amount or to void the payment
+    Notify,
+    /// The merchant did not request partial authorizations, the authorization is voided
+    Void,
+}
+
+fn get_partial_authorization_action(
+    payments_response: &api::PaymentsResponse,
+) -> Option<PartialAuthorizationAction> {
+    let is_partially_authorized = payments_response.status == enums::IntentStatus::RequiresCapture
+        && payments_response.authorized_amount.is_some();
+
+    is_partially_authorized.then_some(
+        if payments_response.request_partial_authorization == Some(true) {
+            PartialAuthorizationAction::Notify
+        } else {
+            PartialAuthorizationAction::Void
+        },
+    )
+}
+
+/// Notifies the merchant of a payment which the connector authorized for less than its amount, or
+/// voids the authorization if the merchant did not request partial authorizations. Payments which
+/// are not partially authorized are returned as they are. This is run by the scheduler, in the
+/// task which the response of the connector schedules once the payment is partially authorized.
+pub async fn handle_partial_authorization(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payments_response: api::PaymentsResponse,
+) -> RouterResult<api::PaymentsResponse> {
+    let void_merchant_account = merchant_account.clone();
+    handle_partial_authorization_with_void(
+        state,
+        merchant_account,
+        payments_response,
+        |cancel_request| void_payment(state, void_merchant_account, key_store, cancel_request),
+    )
+    .await
+}
+
+async fn handle_partial_authorization_with_void<V, Fut>(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payments_response: api::PaymentsResponse,
+    void_payment: V,
+) -> RouterResult<api::PaymentsResponse>
+where
+    V: FnOnce(api::PaymentsCancelRequest) -> Fut,
+    Fut: futures::Future<Output = RouterResult<api::PaymentsResponse>>,
+{
+    let Some(action) = get_partial_authorization_action(&payments_response) else {
+        return Ok(payments_response);
+    };
+    let payment_id = payments_response
+        .payment_id
+        .clone()
+        .get_required_value("payment_id")?;
+
+    match action {
+        PartialAuthorizationAction::Notify => {
+            webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+                state.clone(),
+                merchant_account,
+                storage_enums::EventType::PaymentPartiallyAuthorized,
+                storage_enums::EventClass::Payments,
+                None,
+                payment_id,
+                storage_enums::EventObjectType::PaymentDetails,
+                api::OutgoingWebhookContent::PaymentDetails(payments_response.clone()),
+            )
+            .await?;
+            Ok(payments_response)
+        }
+        PartialAuthorizationAction::Void => {
+            logger::info!(
+                authorized_amount = ?payments_response.authorized_amount,
+                "Voiding payment {payment_id} partially authorized without a request for partial authorizations"
+            );
+            void_payment(get_partial_authorization_cancel_request(payment_id)).await
+        }
+    }
+}
+
+/// Voids the authorization of a payment, and notifies the merchant once the payment is cancelled
+async fn void_payment(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    cancel_request: api::PaymentsCancelRequest,
+) -> RouterResult<api::PaymentsResponse> {
+    let payment_id = cancel_request.payment_id.clone();
+    let response = payments_core::<api::Void, api::PaymentsResponse, _, _, _>(
+        state.clone(),
+        merchant_account.clone(),
+        key_store,
+        PaymentCancel,
+        cancel_request,
+        services::AuthFlow::Merchant,
+        CallConnectorAction::Trigger,
+        HeaderPayload::default(),
+    )
+    .await?;
+
+    let payments_response = match response {
+        services::ApplicationResponse::Json(response)
+        | services::ApplicationResponse::JsonWithHeaders((response, _)) => Ok(response),
+        _ => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Failed to get the void response in json"),
+    }?;
+
+    if payments_response.status == enums::IntentStatus::Cancelled {
+        webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+            state.clone(),
+            merchant_account,
+            storage_enums::EventType::PaymentCancelled,
+            storage_enums::EventClass::Payments,
+            None,
+            payment_id,
+            storage_enums::EventObjectType::PaymentDetails,
+            api::OutgoingWebhookContent::PaymentDetails(payments_response.clone()),
+        )
+        .await?;
+    }
+
+    Ok(payments_response)
+}
+
 #[derive(Clone, Debug)]
 pub struct PaymentRedirectCompleteAuthorize;
 
@@ -461,6 +780,7 @@ impl PaymentRedirectFlow for PaymentRedirectCompleteAuthorize {
                         api_models::payments::NextActionData::QrCodeInformation{..} => None,
                         api_models::payments::NextActionData::DisplayVoucherInformation{ .. } => None,
                         api_models::payments::NextActionData::WaitScreenInformation{..} => None,
+                        api_models::payments::NextActionData::CollectOtp{ .. } => None,
                     })
                     .ok_or(errors::ApiErrorResponse::InternalServerError)
                     .into_report()
@@ -599,6 +919,13 @@ where
     )
     .await?;
 
+    // The attempt records the merchant connector account it is made through, which its refunds are
+    // made through even when the payment would now be routed to another account
+    if payment_data.confirm.unwrap_or(false) {
+        payment_data.payment_attempt.merchant_connector_id =
+            merchant_connector_account.get_mca_id();
+    }
+
     let (pd, tokenization_action) = get_connector_tokenization_action_when_confirm_true(
         state,
         operation,
@@ -610,6 +937,30 @@ where
 
     *payment_data = pd;
 
+    // The payment method data is available only after tokenization, for payments made with a saved card
+    if is_operation_confirm(operation) {
+        three_ds_decision::decide_authentication_type(state, merchant_account, payment_data)
+            .await?;
+
+        payment_data.decrypted_wallet_card = wallet_decryption::get_decrypted_wallet_card(
+            state,
+            capabilities::get_connector_capabilities(connector.connector_name),
+            payment_data.payment_method_data.as_ref(),
+        )
+        .await?;
+    }
+
+    payment_data.mandate_id = helpers::get_mandate_ids_for_connector(
+        payment_data.mandate_id.clone(),
+        payment_data.mandate_connector.as_deref(),
+        connector_name,
+        payment_data.payment_method_data.as_ref(),
+    )?;
+
+    if payment_data.confirm.unwrap_or(false) {
+        helpers::resolve_locale(state, payment_data).await?;
+    }
+
     let updated_customer = call_create_connector_customer_if_required(
         state,
         customer,
@@ -665,6 +1016,10 @@ where
         let apple_pay_predecrypt = apple_pay_data
             .parse_value::<router_types::ApplePayPredecryptData>("ApplePayPredecryptData")
             .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        wallet_decryption::validate_apple_pay_token_freshness(
+            &apple_pay_predecrypt,
+            common_utils::date_time::now(),
+        )?;
 
         router_data.payment_method_token = Some(router_types::PaymentMethodToken::ApplePayDecrypt(
             Box::new(apple_pay_predecrypt),
@@ -1062,6 +1417,24 @@ pub async fn construct_profile_id_and_get_mca<'a, F>(
 where
     F: Clone,
 {
+    // The payments routed to an account of the connector go through that account for every flow
+    if let (Some(merchant_connector_id), None) = (
+        connector_account_selection::get_routed_merchant_connector_id(
+            &payment_data.payment_attempt,
+            connector_id,
+        ),
+        payment_data.creds_identifier.as_ref(),
+    ) {
+        return utils::get_merchant_connector_account_of_attempt(
+            state,
+            connector_id,
+            merchant_account,
+            key_store,
+            &merchant_connector_id,
+        )
+        .await;
+    }
+
     let profile_id = utils::get_profile_id_from_business_details(
         payment_data.payment_intent.business_country,
         payment_data.payment_intent.business_label.as_ref(),
@@ -1081,6 +1454,9 @@ where
         key_store,
         &profile_id,
         connector_id,
+        Some(&connector_account_selection::PaymentMethodDetails::from(
+            &payment_data.payment_attempt,
+        )),
     )
     .await?;
 
@@ -1418,11 +1794,18 @@ where
     pub ephemeral_key: Option<ephemeral_key::EphemeralKey>,
     pub redirect_response: Option<api_models::payments::RedirectResponse>,
     pub frm_message: Option<FraudCheck>,
+    pub maintenance_fallback: Option<api_models::payments::MaintenanceFallback>,
+    pub decrypted_wallet_card: Option<router_types::DecryptedWalletCard>,
+    // 3DS authentication performed by the merchant outside of the router, decrypted from the
+    // payment attempt when it is not passed in the request
+    pub external_authentication_data: Option<api_models::payments::ExternalAuthenticationData>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct RecurringMandatePaymentData {
     pub payment_method_type: Option<storage_enums::PaymentMethodType>, //required for making recurring payment using saved payment method through stripe
+    // Whether the amount and frequency limits of the mandate are not enforced for the charge
+    pub override_mandate_limits: bool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1579,12 +1962,16 @@ pub async fn list_payments(
             .collect::<Result<Vec<(storage::PaymentIntent, storage::PaymentAttempt)>, _>>();
     //Will collect responses in same order async, leading to sorted responses
 
-    //Converting Intent-Attempt array to Response if no error
-    let data: Vec<api::PaymentsResponse> = pi_pa_tuple_vec
-        .change_context(errors::ApiErrorResponse::InternalServerError)?
-        .into_iter()
-        .map(ForeignFrom::foreign_from)
-        .collect();
+    let pi_pa_tuple_vec =
+        pi_pa_tuple_vec.change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    //Converting Intent-Attempt-Refunds array to Response if no error
+    let data: Vec<api::PaymentsResponse> =
+        find_refunds_of_payments(db, &merchant.merchant_id, pi_pa_tuple_vec)
+            .await?
+            .into_iter()
+            .map(ForeignFrom::foreign_from)
+            .collect();
 
     Ok(services::ApplicationResponse::Json(
         api::PaymentListResponse {
@@ -1593,6 +1980,62 @@ pub async fn list_payments(
         },
     ))
 }
+
+/// Finds the refunds of the listed payments in a single query, which the refunded and refundable
+/// amounts of the payments are derived from
+#[cfg(feature = "olap")]
+async fn find_refunds_of_payments(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    payments: Vec<(storage::PaymentIntent, storage::PaymentAttempt)>,
+) -> RouterResult<
+    Vec<(
+        storage::PaymentIntent,
+        storage::PaymentAttempt,
+        Vec<storage::Refund>,
+    )>,
+> {
+    if payments.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let payment_ids = payments
+        .iter()
+        .map(|(pi, _)| pi.payment_id.clone())
+        .collect::<Vec<_>>();
+    let mut refunds_by_payment_id = db
+        .find_refunds_by_merchant_id_payment_ids(
+            merchant_id,
+            &payment_ids,
+            // since OLAP doesn't have KV. Force to get the data from PSQL.
+            storage_enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while finding the refunds of the payments")?
+        .into_iter()
+        .fold(
+            std::collections::HashMap::<String, Vec<storage::Refund>>::new(),
+            |mut refunds_by_payment_id, refund| {
+                refunds_by_payment_id
+                    .entry(refund.payment_id.clone())
+                    .or_default()
+                    .push(refund);
+                refunds_by_payment_id
+            },
+        );
+
+    Ok(payments
+        .into_iter()
+        .map(|(pi, pa)| {
+            let refunds = refunds_by_payment_id
+                .remove(&pi.payment_id)
+                .unwrap_or_default();
+            (pi, pa, refunds)
+        })
+        .collect())
+}
+
 #[cfg(feature = "olap")]
 pub async fn apply_filters_on_payments(
     state: AppState,
@@ -1602,25 +2045,41 @@ pub async fn apply_filters_on_payments(
     let limit = &constraints.limit;
     helpers::validate_payment_list_request_for_joins(*limit)?;
     let db = state.store.as_ref();
-    let list: Vec<(storage::PaymentIntent, storage::PaymentAttempt)> = db
+    let mut fetch_constraints: PaymentIntentFetchConstraints = constraints.clone().into();
+    let mut list: Vec<(storage::PaymentIntent, storage::PaymentAttempt)> = db
         .get_filtered_payment_intents_attempt(
             &merchant.merchant_id,
-            &constraints.clone().into(),
+            &fetch_constraints,
             merchant.storage_scheme,
         )
         .await
-        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?
-        .into_iter()
-        .map(|(pi, pa)| (pi, pa))
-        .collect();
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    // The archived payments are listed when the payments which are not archived do not fill the
+    // page, and are then counted in the total as well
+    if fetch_constraints.should_fall_back_to_archived(list.len()) {
+        fetch_constraints = fetch_constraints.with_archived();
+        list = db
+            .get_filtered_payment_intents_attempt(
+                &merchant.merchant_id,
+                &fetch_constraints,
+                merchant.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    }
 
     let data: Vec<api::PaymentsResponse> =
-        list.into_iter().map(ForeignFrom::foreign_from).collect();
+        find_refunds_of_payments(db, &merchant.merchant_id, list)
+            .await?
+            .into_iter()
+            .map(ForeignFrom::foreign_from)
+            .collect();
 
     let active_attempt_ids = db
         .get_filtered_active_attempt_ids_for_total_count(
             &merchant.merchant_id,
-            &constraints.clone().into(),
+            &fetch_constraints,
             merchant.storage_scheme,
         )
         .await
@@ -1682,6 +2141,58 @@ pub async fn get_filters_for_payments(
     ))
 }
 
+/// Lists all the attempts made for the payment, in the order in which they were created
+#[instrument(skip_all, fields(payment_id, merchant_id))]
+pub async fn list_payment_attempts(
+    state: AppState,
+    merchant: domain::MerchantAccount,
+    payment_id: String,
+) -> RouterResponse<api_models::payments::PaymentAttemptListResponse> {
+    let db = state.store.as_ref();
+    db.find_payment_intent_by_payment_id_merchant_id(
+        &payment_id,
+        &merchant.merchant_id,
+        merchant.storage_scheme,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let attempts = get_payment_attempts(
+        db,
+        &merchant.merchant_id,
+        &payment_id,
+        merchant.storage_scheme,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::Json(
+        api_models::payments::PaymentAttemptListResponse {
+            payment_id,
+            attempts,
+        },
+    ))
+}
+
+async fn get_payment_attempts(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    payment_id: &str,
+    storage_scheme: storage_enums::MerchantStorageScheme,
+) -> RouterResult<Vec<api_models::payments::PaymentAttemptResponse>> {
+    db.find_attempts_by_merchant_id_payment_id(merchant_id, payment_id, storage_scheme)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to retrieve the attempts of the payment {payment_id}")
+        })
+        .map(|payment_attempts| {
+            payment_attempts
+                .into_iter()
+                .map(ForeignInto::foreign_into)
+                .collect()
+        })
+}
+
 pub async fn add_process_sync_task(
     db: &dyn StorageInterface,
     payment_attempt: &storage::PaymentAttempt,
@@ -1713,6 +2224,197 @@ pub async fn add_process_sync_task(
     Ok(())
 }
 
+/// Polls the status of an operation which the connector accepted without completing it. A sync
+/// task is added when none is scheduled for the attempt, so that the operation is resumed by the
+/// scheduler when the polling is interrupted, or when it is not complete within the polling budget.
+#[instrument(skip_all)]
+pub async fn poll_connector_operation<F, FData>(
+    state: &AppState,
+    connector: &api::ConnectorData,
+    payment_attempt: &storage::PaymentAttempt,
+    router_data: router_types::RouterData<F, FData, router_types::PaymentsResponseData>,
+    schedule_time: Option<time::PrimitiveDateTime>,
+) -> router_types::RouterData<F, FData, router_types::PaymentsResponseData>
+where
+    F: Clone + Debug + 'static,
+    FData: Debug + Clone + 'static,
+    dyn api::Connector:
+        services::api::ConnectorIntegration<F, FData, router_types::PaymentsResponseData>,
+{
+    if let (None, Some(poll_config)) = (schedule_time, &router_data.poll_config) {
+        let schedule_time = common_utils::date_time::now().saturating_add(
+            time::Duration::try_from(poll_config.get_polling_budget()).unwrap_or_default(),
+        );
+        add_process_sync_task(&*state.store, payment_attempt, schedule_time)
+            .await
+            .map_err(|error| logger::error!(process_tracker_error=?error))
+            .ok();
+    }
+
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        F,
+        FData,
+        router_types::PaymentsResponseData,
+    > = connector.connector.get_connector_integration();
+    services::api::polling::execute_connector_polling(state, &connector_integration, router_data)
+        .await
+}
+
+pub async fn add_payment_expiry_task(
+    db: &dyn StorageInterface,
+    payment_intent: &storage::PaymentIntent,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let schedule_time = payment_intent
+        .session_expiry
+        .ok_or(sch_errors::ProcessTrackerError::MissingRequiredField)?;
+    let tracking_data = api::PaymentsRetrieveRequest {
+        force_sync: true,
+        merchant_id: Some(payment_intent.merchant_id.clone()),
+        resource_id: api::PaymentIdType::PaymentIntentId(payment_intent.payment_id.clone()),
+        ..Default::default()
+    };
+    let runner = "PAYMENTS_EXPIRY_WORKFLOW";
+    let task = "PAYMENTS_EXPIRY";
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        &payment_intent.payment_id,
+        &payment_intent.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        task,
+        runner,
+        tracking_data,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+/// Records when a manual capture authorization expires at the connector, and schedules the task
+/// which warns the merchant, or captures the payment, before the authorization expires
+pub async fn track_authorization_expiry_if_required<F: Clone>(
+    state: &AppState,
+    payment_data: &mut PaymentData<F>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
+) -> RouterResult<()> {
+    let payment_attempt = &payment_data.payment_attempt;
+    let is_manual_capture = matches!(
+        payment_attempt.capture_method,
+        Some(storage_enums::CaptureMethod::Manual | storage_enums::CaptureMethod::ManualMultiple)
+    );
+    let is_authorized = matches!(
+        payment_attempt.status,
+        storage_enums::AttemptStatus::Authorized
+            | storage_enums::AttemptStatus::PartiallyAuthorized
+    );
+    if !is_authorized || !is_manual_capture || payment_attempt.authorization_expires_at.is_some() {
+        return Ok(());
+    }
+
+    let connector = payment_attempt
+        .connector
+        .as_deref()
+        .get_required_value("connector")?;
+    let authorization_validity = &state.conf.authorization_validity;
+    let authorized_at = common_utils::date_time::now();
+    let authorization_expires_at = authorized_at
+        + time::Duration::seconds(
+            authorization_validity
+                .get_validity_in_secs(connector)
+                .into(),
+        );
+
+    let payment_attempt = state
+        .store
+        .update_payment_attempt_with_attempt_id(
+            payment_attempt.clone(),
+            storage::PaymentAttemptUpdate::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let schedule_time = authorization_expires_at
+        - time::Duration::seconds(authorization_validity.lead_time_in_secs.into());
+    add_authorization_expiry_task(&*state.store, &payment_attempt, schedule_time)
+        .await
+        .map_err(|error| logger::error!(process_tracker_error=?error))
+        .ok();
+
+    payment_data.payment_attempt = payment_attempt;
+    Ok(())
+}
+
+/// Schedules the handling of a payment which the connector authorized for less than its amount, the
+/// task is scheduled once for the attempt
+pub async fn add_partial_authorization_task(
+    db: &dyn StorageInterface,
+    payment_attempt: &storage::PaymentAttempt,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let tracking_data = api::PaymentsRetrieveRequest {
+        force_sync: false,
+        merchant_id: Some(payment_attempt.merchant_id.clone()),
+        resource_id: api::PaymentIdType::PaymentIntentId(payment_attempt.payment_id.clone()),
+        ..Default::default()
+    };
+    let runner = "PAYMENTS_PARTIAL_AUTHORIZATION_WORKFLOW";
+    let task = "PAYMENTS_PARTIAL_AUTHORIZATION";
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        &payment_attempt.attempt_id,
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        task,
+        runner,
+        tracking_data,
+        common_utils::date_time::now(),
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+pub async fn add_authorization_expiry_task(
+    db: &dyn StorageInterface,
+    payment_attempt: &storage::PaymentAttempt,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let tracking_data = api::PaymentsRetrieveRequest {
+        force_sync: true,
+        merchant_id: Some(payment_attempt.merchant_id.clone()),
+        resource_id: api::PaymentIdType::PaymentIntentId(payment_attempt.payment_id.clone()),
+        ..Default::default()
+    };
+    let runner = "PAYMENTS_AUTHORIZATION_EXPIRY_WORKFLOW";
+    let task = "PAYMENTS_AUTHORIZATION_EXPIRY";
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        &payment_attempt.attempt_id,
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        task,
+        runner,
+        tracking_data,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
 pub async fn reset_process_sync_task(
     db: &dyn StorageInterface,
     payment_attempt: &storage::PaymentAttempt,
@@ -1743,9 +2445,9 @@ pub fn update_straight_through_routing<F>(
 where
     F: Send + Clone,
 {
-    let _: api::RoutingAlgorithm = request_straight_through
+    let _: api::StraightThroughAlgorithm = request_straight_through
         .clone()
-        .parse_value("RoutingAlgorithm")
+        .parse_value("StraightThroughAlgorithm")
         .attach_printable("Invalid straight through routing rules format")?;
 
     payment_data.payment_attempt.straight_through_algorithm = Some(request_straight_through);
@@ -1781,15 +2483,19 @@ where
                 api::ConnectorCallType::Multiple(session_connectors)
             }
 
-            api::ConnectorChoice::StraightThrough(straight_through) => connector_selection(
-                state,
-                merchant_account,
-                payment_data,
-                Some(straight_through),
-            )?,
+            api::ConnectorChoice::StraightThrough(straight_through) => {
+                connector_selection(
+                    state,
+                    merchant_account,
+                    key_store,
+                    payment_data,
+                    Some(straight_through),
+                )
+                .await?
+            }
 
             api::ConnectorChoice::Decide => {
-                connector_selection(state, merchant_account, payment_data, None)?
+                connector_selection(state, merchant_account, key_store, payment_data, None).await?
             }
         })
     } else if let api::ConnectorChoice::StraightThrough(val) = connector_choice {
@@ -1804,9 +2510,10 @@ where
     Ok(connector)
 }
 
-pub fn connector_selection<F>(
+pub async fn connector_selection<F>(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
     payment_data: &mut PaymentData<F>,
     request_straight_through: Option<serde_json::Value>,
 ) -> RouterResult<api::ConnectorCallType>
@@ -1835,6 +2542,9 @@ where
             .transpose()
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Invalid straight through algorithm format in payment attempt")?,
+        maintenance_fallback: None,
+        routing_rule: None,
+        skipped_connectors: vec![],
     };
 
     let request_straight_through: Option<api::StraightThroughAlgorithm> = request_straight_through
@@ -1843,12 +2553,35 @@ where
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Invalid straight through routing rules format")?;
 
+    let eligibility_data = eligibility::PaymentEligibilityData::from_payment_data(payment_data);
     let decided_connector = decide_connector(
         state,
         merchant_account,
+        key_store,
+        eligibility_data.as_ref(),
         request_straight_through,
         &mut routing_data,
-    )?;
+        payment_data.payment_attempt.payment_method_type,
+    )
+    .await?;
+
+    // The merchant connector account of the connector is selected for the payment method of the
+    // payment, only for the payments which use the connector accounts of their business profile
+    if let (Some(eligibility_data), Some(connector_name)) =
+        (eligibility_data, routing_data.routed_through.as_deref())
+    {
+        payment_data.payment_attempt.routing_metadata =
+            connector_account_selection::get_routing_metadata(
+                &*state.store,
+                key_store,
+                &eligibility_data.profile_id,
+                connector_name,
+                &connector_account_selection::PaymentMethodDetails::from(
+                    &payment_data.payment_attempt,
+                ),
+            )
+            .await?;
+    }
 
     let encoded_algorithm = routing_data
         .algorithm
@@ -1857,17 +2590,28 @@ where
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to serialize routing algorithm to serde value")?;
 
+    routing_decisions::record_routing_decisions(
+        &*state.store,
+        routing_decisions::DecisionSource::from(&payment_data.payment_attempt),
+        &routing_data,
+    )
+    .await;
+
     payment_data.payment_attempt.connector = routing_data.routed_through;
     payment_data.payment_attempt.straight_through_algorithm = encoded_algorithm;
+    payment_data.maintenance_fallback = routing_data.maintenance_fallback;
 
     Ok(decided_connector)
 }
 
-pub fn decide_connector(
+pub async fn decide_connector(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    eligibility_data: Option<&eligibility::PaymentEligibilityData>,
     request_straight_through: Option<api::StraightThroughAlgorithm>,
     routing_data: &mut storage::RoutingData,
+    payment_method_type: Option<enums::PaymentMethodType>,
 ) -> RouterResult<api::ConnectorCallType> {
     if let Some(ref connector_name) = routing_data.routed_through {
         let connector_data = api::ConnectorData::get_connector_by_name(
@@ -1881,38 +2625,40 @@ pub fn decide_connector(
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
 
+    // A connector passed in the request falls back to the configured connectors as well when it is
+    // unavailable
     if let Some(routing_algorithm) = request_straight_through {
-        let connector_name = match &routing_algorithm {
-            api::StraightThroughAlgorithm::Single(conn) => conn.to_string(),
+        let connector = match &routing_algorithm {
+            api::StraightThroughAlgorithm::Single(conn) => *conn,
         };
-
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &connector_name,
-            api::GetToken::Connector,
+        let available_connector = circuit_breaker::get_connector_with_fallback(
+            state,
+            &merchant_account.merchant_id,
+            connector,
+            &circuit_breaker::get_flow_name::<api::Authorize>(),
         )
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Invalid connector name received in routing algorithm")?;
+        .await;
 
-        routing_data.routed_through = Some(connector_name);
+        let connector_data = set_routed_connector(state, routing_data, available_connector)?;
         routing_data.algorithm = Some(routing_algorithm);
+        routing_data.routing_rule = Some(storage_enums::RoutingRule::RequestStraightThrough);
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
 
     if let Some(ref routing_algorithm) = routing_data.algorithm {
-        let connector_name = match routing_algorithm {
-            api::StraightThroughAlgorithm::Single(conn) => conn.to_string(),
+        let connector = match routing_algorithm {
+            api::StraightThroughAlgorithm::Single(conn) => *conn,
         };
-
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &connector_name,
-            api::GetToken::Connector,
+        let available_connector = circuit_breaker::get_connector_with_fallback(
+            state,
+            &merchant_account.merchant_id,
+            connector,
+            &circuit_breaker::get_flow_name::<api::Authorize>(),
         )
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Invalid connector name received in routing algorithm")?;
+        .await;
 
-        routing_data.routed_through = Some(connector_name);
+        let connector_data = set_routed_connector(state, routing_data, available_connector)?;
+        routing_data.routing_rule = Some(storage_enums::RoutingRule::PaymentStraightThrough);
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
 
@@ -1927,9 +2673,43 @@ pub fn decide_connector(
         .change_context(errors::ApiErrorResponse::InternalServerError) // Deserialization failed
         .attach_printable("Unable to deserialize merchant routing algorithm")?;
 
-    let connector_name = match routing_algorithm {
-        api::RoutingAlgorithm::Single(conn) => conn.to_string(),
+    let connector = routing_algorithm.get_connector(payment_method_type);
+    // Connectors which are not configured to accept the payment are skipped only when routing
+    // through the merchant routing algorithm, connectors passed in the request are used as is
+    let available_connector = match eligibility_data {
+        Some(eligibility_data) => {
+            eligibility::get_eligible_connector(
+                state,
+                merchant_account,
+                key_store,
+                connector,
+                eligibility_data,
+            )
+            .await?
+        }
+        None => {
+            circuit_breaker::get_connector_with_fallback(
+                state,
+                &merchant_account.merchant_id,
+                connector,
+                &circuit_breaker::get_flow_name::<api::Authorize>(),
+            )
+            .await
+        }
     };
+    let connector_data = set_routed_connector(state, routing_data, available_connector)?;
+    routing_data.routing_rule = Some(storage_enums::RoutingRule::MerchantRoutingAlgorithm);
+
+    Ok(api::ConnectorCallType::Single(connector_data))
+}
+
+/// Records the connector the payment is routed to, along with the connectors it was routed around
+fn set_routed_connector(
+    state: &AppState,
+    routing_data: &mut storage::RoutingData,
+    available_connector: circuit_breaker::AvailableConnector,
+) -> RouterResult<api::ConnectorData> {
+    let connector_name = available_connector.connector.to_string();
 
     let connector_data = api::ConnectorData::get_connector_by_name(
         &state.conf.connectors,
@@ -1940,18 +2720,644 @@ pub fn decide_connector(
     .attach_printable("Routing algorithm gave invalid connector")?;
 
     routing_data.routed_through = Some(connector_name);
+    routing_data.maintenance_fallback = available_connector.maintenance_fallback;
+    routing_data.skipped_connectors = available_connector.skipped_connectors;
 
-    Ok(api::ConnectorCallType::Single(connector_data))
+    Ok(connector_data)
 }
 
 pub fn should_add_task_to_process_tracker<F: Clone>(payment_data: &PaymentData<F>) -> bool {
     let connector = payment_data.payment_attempt.connector.as_deref();
 
+    // Virtual account transfers can be completed days later, they are synced as a fallback to the webhooks
     !matches!(
-        (payment_data.payment_attempt.payment_method, connector),
+        (
+            payment_data.payment_attempt.payment_method,
+            payment_data.payment_attempt.payment_method_type,
+            connector
+        ),
         (
             Some(storage_enums::PaymentMethod::BankTransfer),
+            payment_method_type,
             Some("stripe")
-        )
+        ) if payment_method_type != Some(storage_enums::PaymentMethodType::VirtualAccount)
     )
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use time::Duration;
+
+    use super::{
+        operations::{GetTracker, UpdateTracker},
+        *,
+    };
+    use crate::configs::settings;
+
+    #[tokio::test]
+    async fn test_payment_attempts_are_listed_in_creation_order() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let now = common_utils::date_time::now();
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+
+        // The successful retry is stored first, so that the ordering does not rely on insertion
+        db.insert_payment_attempt(
+            storage::PaymentAttemptNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "pay_1_2".to_string(),
+                status: storage_enums::AttemptStatus::Charged,
+                amount: 1000,
+                currency: Some(storage_enums::Currency::USD),
+                connector: Some("adyen".to_string()),
+                capture_method: Some(storage_enums::CaptureMethod::Automatic),
+                amount_capturable: 0,
+                created_at: Some(now),
+                modified_at: Some(now),
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        db.insert_payment_attempt(
+            storage::PaymentAttemptNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "pay_1_1".to_string(),
+                status: storage_enums::AttemptStatus::Failure,
+                amount: 1000,
+                currency: Some(storage_enums::Currency::USD),
+                connector: Some("stripe".to_string()),
+                capture_method: Some(storage_enums::CaptureMethod::Automatic),
+                error_code: Some("card_declined".to_string()),
+                error_message: Some("Your card was declined.".to_string()),
+                error_reason: Some("insufficient_funds".to_string()),
+                amount_capturable: 1000,
+                created_at: Some(now.saturating_sub(Duration::minutes(5))),
+                modified_at: Some(now.saturating_sub(Duration::minutes(4))),
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        mockdb.payment_attempts.lock().await[0].connector_transaction_id =
+            Some("psp_txn_2".to_string());
+
+        let attempts = get_payment_attempts(db, "merchant_1", "pay_1", storage_scheme)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            attempts
+                .iter()
+                .map(|attempt| attempt.attempt_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pay_1_1", "pay_1_2"]
+        );
+
+        let failed_attempt = &attempts[0];
+        assert_eq!(failed_attempt.status, storage_enums::AttemptStatus::Failure);
+        assert_eq!(failed_attempt.connector.as_deref(), Some("stripe"));
+        assert_eq!(failed_attempt.error_code.as_deref(), Some("card_declined"));
+        assert_eq!(
+            failed_attempt.error_message.as_deref(),
+            Some("Your card was declined.")
+        );
+        assert_eq!(
+            failed_attempt.error_reason.as_deref(),
+            Some("insufficient_funds")
+        );
+        assert_eq!(
+            failed_attempt.created_at,
+            Some(now.saturating_sub(Duration::minutes(5)))
+        );
+        assert_eq!(
+            failed_attempt.modified_at,
+            Some(now.saturating_sub(Duration::minutes(4)))
+        );
+
+        let successful_attempt = &attempts[1];
+        assert_eq!(
+            successful_attempt.status,
+            storage_enums::AttemptStatus::Charged
+        );
+        assert_eq!(successful_attempt.amount, 1000);
+        assert_eq!(
+            successful_attempt.currency,
+            Some(storage_enums::Currency::USD)
+        );
+        assert_eq!(
+            successful_attempt.connector_transaction_id.as_deref(),
+            Some("psp_txn_2")
+        );
+        assert_eq!(
+            successful_attempt.capture_method,
+            Some(storage_enums::CaptureMethod::Automatic)
+        );
+        assert_eq!(successful_attempt.error_code, None);
+        assert_eq!(successful_attempt.created_at, Some(now));
+    }
+
+    #[test]
+    fn test_only_authorized_payments_cancelled_by_customer_are_voided() {
+        let authorized = api::PaymentsResponse {
+            status: enums::IntentStatus::RequiresCapture,
+            connector_transaction_id: Some("psp_txn_1".to_string()),
+            ..Default::default()
+        };
+        let not_authorized = api::PaymentsResponse {
+            status: enums::IntentStatus::Failed,
+            ..Default::default()
+        };
+
+        assert!(should_void_abandoned_payment(true, &authorized));
+        assert!(!should_void_abandoned_payment(false, &authorized));
+        assert!(!should_void_abandoned_payment(true, &not_authorized));
+        assert!(!should_void_abandoned_payment(
+            true,
+            &api::PaymentsResponse {
+                connector_transaction_id: None,
+                ..authorized
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_void_of_payment_cancelled_on_connector_page_stores_reason() {
+        let state = AppState::with_storage(
+            settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let now = common_utils::date_time::now();
+
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: now,
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+        let merchant_account = domain::MerchantAccount {
+            id: None,
+            merchant_id: "merchant_1".to_string(),
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::Value::Null,
+            frm_routing_algorithm: None,
+            created_at: now,
+            modified_at: now,
+            intent_fulfillment_time: None,
+            payout_routing_algorithm: None,
+            organization_id: None,
+            is_recon_enabled: false,
+            default_profile: None,
+            recon_status: storage_enums::ReconStatus::NotRequested,
+            webhook_auth_details: None,
+            default_currency: None,
+            default_country: None,
+            pii_policy: None,
+        };
+
+        db.insert_payment_intent(
+            storage::PaymentIntentNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                status: storage_enums::IntentStatus::RequiresCapture,
+                amount: 1000,
+                currency: Some(storage_enums::Currency::USD),
+                active_attempt_id: "pay_1_1".to_string(),
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        db.insert_payment_attempt(
+            storage::PaymentAttemptNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "pay_1_1".to_string(),
+                status: storage_enums::AttemptStatus::Authorized,
+                amount: 1000,
+                currency: Some(storage_enums::Currency::USD),
+                connector: Some("paypal".to_string()),
+                capture_method: Some(storage_enums::CaptureMethod::Manual),
+                amount_capturable: 1000,
+                ..Default::default()
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        db.insert_connector_response(
+            storage::ConnectorResponseNew {
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "pay_1_1".to_string(),
+                created_at: now,
+                modified_at: now,
+                connector_name: Some("paypal".to_string()),
+                connector_transaction_id: Some("txn_1".to_string()),
+                authentication_data: None,
+                encoded_data: None,
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+
+        // The void runs the trackers of the cancel operation, the connector call is left out
+        let response = void_abandoned_payment_if_required(
+            true,
+            api::PaymentsResponse {
+                payment_id: Some("pay_1".to_string()),
+                status: enums::IntentStatus::RequiresCapture,
+                connector_transaction_id: Some("txn_1".to_string()),
+                ..Default::default()
+            },
+            "pay_1",
+            None,
+            |cancel_request| async {
+                let (_, payment_data, _): (_, PaymentData<api::Void>, _) = PaymentCancel
+                    .get_trackers(
+                        &state,
+                        &api::PaymentIdType::PaymentIntentId(cancel_request.payment_id.clone()),
+                        &cancel_request,
+                        None,
+                        &merchant_account,
+                        &key_store,
+                        services::AuthFlow::Merchant,
+                    )
+                    .await?;
+                let (_, payment_data) = PaymentCancel
+                    .update_trackers(
+                        db,
+                        payment_data,
+                        None,
+                        storage_scheme,
+                        None,
+                        &key_store,
+                        None,
+                        HeaderPayload::default(),
+                    )
+                    .await?;
+                Ok(api::PaymentsResponse {
+                    payment_id: Some(payment_data.payment_intent.payment_id),
+                    status: payment_data.payment_intent.status,
+                    ..Default::default()
+                })
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.payment_id.as_deref(), Some("pay_1"));
+
+        let voided_attempt = db
+            .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+                "pay_1",
+                "merchant_1",
+                "pay_1_1",
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            voided_attempt.status,
+            storage_enums::AttemptStatus::VoidInitiated
+        );
+        assert_eq!(
+            voided_attempt.cancellation_reason.as_deref(),
+            Some(consts::CUSTOMER_ABANDONED_CANCELLATION_REASON)
+        );
+        assert_eq!(
+            voided_attempt.cancellation_reason_code,
+            Some(enums::CancellationReason::Abandoned)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_not_cancelled_on_connector_page_is_not_voided() {
+        let payments_response = api::PaymentsResponse {
+            payment_id: Some("pay_1".to_string()),
+            status: enums::IntentStatus::RequiresCapture,
+            connector_transaction_id: Some("txn_1".to_string()),
+            ..Default::default()
+        };
+
+        let response = void_abandoned_payment_if_required(
+            false,
+            payments_response.clone(),
+            "pay_1",
+            None,
+            |_| async { Err(errors::ApiErrorResponse::InternalServerError.into()) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response, payments_response);
+    }
+
+    #[test]
+    fn test_partial_authorization_is_notified_only_when_requested() {
+        let partially_authorized = api::PaymentsResponse {
+            status: enums::IntentStatus::RequiresCapture,
+            authorized_amount: Some(6000),
+            request_partial_authorization: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_partial_authorization_action(&partially_authorized),
+            Some(PartialAuthorizationAction::Notify)
+        );
+        assert_eq!(
+            get_partial_authorization_action(&api::PaymentsResponse {
+                request_partial_authorization: None,
+                ..partially_authorized.clone()
+            }),
+            Some(PartialAuthorizationAction::Void)
+        );
+        // Authorizations of the full amount, and partial authorizations which were already voided
+        // or captured, are left as they are
+        assert_eq!(
+            get_partial_authorization_action(&api::PaymentsResponse {
+                authorized_amount: None,
+                ..partially_authorized.clone()
+            }),
+            None
+        );
+        assert_eq!(
+            get_partial_authorization_action(&api::PaymentsResponse {
+                status: enums::IntentStatus::Cancelled,
+                ..partially_authorized
+            }),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partially_authorized_attempt_is_capturable_up_to_authorized_amount() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    status: storage_enums::AttemptStatus::Authorizing,
+                    amount: 10000,
+                    currency: Some(storage_enums::Currency::USD),
+                    connector: Some("checkout".to_string()),
+                    capture_method: Some(storage_enums::CaptureMethod::Manual),
+                    amount_capturable: 10000,
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        // The connector approved 60.00 of the 100.00 requested
+        let authorized_amount = helpers::get_partially_authorized_amount(
+            storage_enums::AttemptStatus::Authorized,
+            payment_attempt.amount,
+            Some(6000),
+        );
+        assert_eq!(authorized_amount, Some(6000));
+        assert_eq!(
+            helpers::get_partially_authorized_amount(
+                storage_enums::AttemptStatus::Authorized,
+                payment_attempt.amount,
+                Some(10000),
+            ),
+            None
+        );
+
+        let payment_attempt = db
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::ResponseUpdate {
+                    status: storage_enums::AttemptStatus::PartiallyAuthorized,
+                    connector: None,
+                    connector_transaction_id: Some("pay_mbabizu24mvu3mela5njyhpit4".to_string()),
+                    authentication_type: None,
+                    payment_method_id: None,
+                    mandate_id: None,
+                    connector_metadata: None,
+                    payment_token: None,
+                    error_code: None,
+                    error_message: None,
+                    error_reason: None,
+                    connector_response_reference_id: None,
+                    amount_capturable: authorized_amount,
+                    card_verification_results: None,
+                    connector_fee_amount: None,
+                    fee_currency: None,
+                    net_amount: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
+                    authorized_amount,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(payment_attempt.amount, 10000);
+        assert_eq!(payment_attempt.authorized_amount, Some(6000));
+        assert_eq!(payment_attempt.amount_capturable, 6000);
+        assert_eq!(
+            storage::payment_attempt::PaymentAttemptExt::get_intent_status(&payment_attempt, None),
+            enums::IntentStatus::RequiresCapture
+        );
+        assert!(helpers::validate_amount_to_capture(6000, Some(6000)).is_ok());
+        assert!(helpers::validate_amount_to_capture(6000, Some(10000)).is_err());
+    }
+
+    fn get_merchant_account() -> domain::MerchantAccount {
+        domain::MerchantAccount {
+            id: None,
+            merchant_id: "merchant_1".to_string(),
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: storage_enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::Value::Null,
+            frm_routing_algorithm: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            intent_fulfillment_time: None,
+            payout_routing_algorithm: None,
+            organization_id: None,
+            is_recon_enabled: false,
+            default_profile: None,
+            recon_status: storage_enums::ReconStatus::NotRequested,
+            webhook_auth_details: None,
+            default_currency: None,
+            default_country: None,
+            pii_policy: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partially_authorized_payment_is_voided_without_a_request_for_it() {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let partially_authorized = api::PaymentsResponse {
+            payment_id: Some("pay_1".to_string()),
+            status: enums::IntentStatus::RequiresCapture,
+            amount: 10000,
+            authorized_amount: Some(6000),
+            ..Default::default()
+        };
+
+        let response = handle_partial_authorization_with_void(
+            &state,
+            get_merchant_account(),
+            partially_authorized.clone(),
+            |cancel_request| async move {
+                Ok(api::PaymentsResponse {
+                    payment_id: Some(cancel_request.payment_id),
+                    status: enums::IntentStatus::Cancelled,
+                    cancellation_reason: cancel_request.cancellation_reason,
+                    cancellation_reason_code: cancel_request.cancellation_reason_code,
+                    ..partially_authorized
+                })
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.payment_id.as_deref(), Some("pay_1"));
+        assert_eq!(response.status, enums::IntentStatus::Cancelled);
+        assert_eq!(
+            response.cancellation_reason.as_deref(),
+            Some(consts::PARTIAL_AUTHORIZATION_CANCELLATION_REASON)
+        );
+        assert_eq!(
+            response.cancellation_reason_code,
+            Some(enums::CancellationReason::PartiallyAuthorized)
+        );
+        assert_eq!(response.authorized_amount, Some(6000));
+
+        // Authorizations of the full amount are not voided
+        let response = handle_partial_authorization_with_void(
+            &state,
+            get_merchant_account(),
+            api::PaymentsResponse {
+                payment_id: Some("pay_2".to_string()),
+                status: enums::IntentStatus::RequiresCapture,
+                amount: 10000,
+                ..Default::default()
+            },
+            |_| async { Err(errors::ApiErrorResponse::InternalServerError.into()) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, enums::IntentStatus::RequiresCapture);
+    }
+
+    #[cfg(feature = "olap")]
+    #[tokio::test]
+    async fn test_payments_list_with_livemode_false_excludes_live_payments() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+
+        for (payment_id, livemode) in [("pay_live", true), ("pay_test", false)] {
+            db.insert_payment_intent(
+                storage::PaymentIntentNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    status: storage_enums::IntentStatus::Succeeded,
+                    amount: 1000,
+                    currency: Some(storage_enums::Currency::USD),
+                    active_attempt_id: format!("{payment_id}_1"),
+                    livemode,
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        }
+
+        let list_payment_ids = |livemode: serde_json::Value| async move {
+            let constraints: api::PaymentListConstraints =
+                serde_json::from_value(serde_json::json!({ "livemode": livemode })).unwrap();
+            helpers::filter_by_constraints(db, &constraints, "merchant_1", storage_scheme)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|payment_intent| payment_intent.payment_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            list_payment_ids(serde_json::json!(false)).await,
+            vec!["pay_test".to_string()]
+        );
+        assert_eq!(
+            list_payment_ids(serde_json::json!(true)).await,
+            vec!["pay_live".to_string()]
+        );
+        assert_eq!(list_payment_ids(serde_json::Value::Null).await.len(), 2);
+    }
+}
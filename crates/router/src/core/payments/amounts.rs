@@ -0,0 +1,424 @@
+//! Amounts of a payment which were captured, can still be captured, were refunded and can still be
+//! refunded. The amounts are derived from the payment attempt, from the amount captured by its
+//! captures and from its refunds, and the same amounts are returned in the payments response and
+//! enforced when a refund is created.
+//!
+//! The authorized amount is the amount of the attempt with its surcharge and tax, or the amount
+//! approved by the connector for partial authorizations. Connectors which capture more than was
+//! authorized raise the authorized amount to the captured amount. Refunds which failed are not
+//! counted as refunded.
+
+use crate::types::storage::{self, enums};
+
+/// Fields of the payment attempt which the amounts of the payment are derived from
+#[derive(Clone, Debug, Default)]
+pub struct AttemptAmounts {
+    pub status: enums::AttemptStatus,
+    pub amount: i64,
+    pub surcharge_amount: Option<i64>,
+    pub tax_amount: Option<i64>,
+    pub authorized_amount: Option<i64>,
+    pub multiple_capture_count: Option<i16>,
+}
+
+impl From<&storage::PaymentAttempt> for AttemptAmounts {
+    fn from(payment_attempt: &storage::PaymentAttempt) -> Self {
+        Self {
+            status: payment_attempt.status,
+            amount: payment_attempt.amount,
+            surcharge_amount: payment_attempt.surcharge_amount,
+            tax_amount: payment_attempt.tax_amount,
+            authorized_amount: payment_attempt.authorized_amount,
+            multiple_capture_count: payment_attempt.multiple_capture_count,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PaymentAmounts {
+    pub amount_authorized: i64,
+    pub amount_capturable: i64,
+    pub amount_captured: i64,
+    pub amount_refunded: i64,
+    pub amount_refundable: i64,
+}
+
+fn is_authorized(status: enums::AttemptStatus) -> bool {
+    match status {
+        enums::AttemptStatus::Authorized
+        | enums::AttemptStatus::PartiallyAuthorized
+        | enums::AttemptStatus::Charged
+        | enums::AttemptStatus::PartialCharged
+        | enums::AttemptStatus::CaptureInitiated
+        | enums::AttemptStatus::CaptureFailed
+        | enums::AttemptStatus::Voided
+        | enums::AttemptStatus::VoidInitiated
+        | enums::AttemptStatus::VoidFailed
+        | enums::AttemptStatus::AutoRefunded
+        | enums::AttemptStatus::AuthorizationExpired => true,
+        enums::AttemptStatus::Started
+        | enums::AttemptStatus::AuthenticationFailed
+        | enums::AttemptStatus::RouterDeclined
+        | enums::AttemptStatus::AuthenticationPending
+        | enums::AttemptStatus::AuthenticationSuccessful
+        | enums::AttemptStatus::AuthorizationFailed
+        | enums::AttemptStatus::Authorizing
+        | enums::AttemptStatus::CodInitiated
+        | enums::AttemptStatus::Unresolved
+        | enums::AttemptStatus::Pending
+        | enums::AttemptStatus::Failure
+        | enums::AttemptStatus::PaymentMethodAwaited
+        | enums::AttemptStatus::ConfirmationAwaited
+        | enums::AttemptStatus::DeviceDataCollectionPending => false,
+    }
+}
+
+/// The remainder of the authorization can only be captured while the attempt is authorized, or
+/// after a partial capture or while a capture is pending when the attempt supports multiple
+/// captures. The amount captured by single capture connectors is only known once charged.
+fn is_capturable(attempt: &AttemptAmounts) -> bool {
+    match attempt.status {
+        enums::AttemptStatus::Authorized
+        | enums::AttemptStatus::PartiallyAuthorized
+        | enums::AttemptStatus::VoidFailed => true,
+        enums::AttemptStatus::PartialCharged | enums::AttemptStatus::CaptureInitiated => {
+            attempt.multiple_capture_count.is_some()
+        }
+        _ => false,
+    }
+}
+
+pub fn get_refunded_amount(refunds: &[storage::Refund]) -> i64 {
+    refunds
+        .iter()
+        .filter(|refund| {
+            refund.refund_status != enums::RefundStatus::Failure
+                && refund.refund_status != enums::RefundStatus::TransactionFailure
+        })
+        .map(|refund| refund.refund_amount)
+        .sum()
+}
+
+/// Derives the amounts of the payment from its attempt, from the amount captured recorded on the
+/// payment intent and from its refunds
+pub fn get_payment_amounts(
+    attempt: &AttemptAmounts,
+    amount_captured: Option<i64>,
+    refunds: &[storage::Refund],
+) -> PaymentAmounts {
+    let authorized = if is_authorized(attempt.status) {
+        attempt.authorized_amount.unwrap_or(
+            attempt.amount
+                + attempt.surcharge_amount.unwrap_or_default()
+                + attempt.tax_amount.unwrap_or_default(),
+        )
+    } else {
+        0
+    };
+    let amount_captured = amount_captured
+        .unwrap_or(if attempt.status == enums::AttemptStatus::Charged {
+            authorized
+        } else {
+            0
+        })
+        .max(0);
+    let amount_authorized = authorized.max(amount_captured);
+    let amount_capturable = if is_capturable(attempt) {
+        amount_authorized - amount_captured
+    } else {
+        0
+    };
+    let amount_refunded = get_refunded_amount(refunds);
+
+    PaymentAmounts {
+        amount_authorized,
+        amount_capturable,
+        amount_captured,
+        amount_refunded,
+        amount_refundable: (amount_captured - amount_refunded).max(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn get_refund(refund_amount: i64, refund_status: enums::RefundStatus) -> storage::Refund {
+        let created_at = common_utils::date_time::now();
+        storage::Refund {
+            id: 1,
+            internal_reference_id: "refid_1".to_string(),
+            refund_id: "ref_1".to_string(),
+            payment_id: "pay_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            connector_transaction_id: "txn_1".to_string(),
+            connector: "stripe".to_string(),
+            connector_refund_id: None,
+            external_reference_id: None,
+            refund_type: enums::RefundType::RegularRefund,
+            total_amount: 10000,
+            currency: enums::Currency::USD,
+            refund_amount,
+            refund_status,
+            sent_to_gateway: false,
+            refund_error_message: None,
+            metadata: None,
+            refund_arn: None,
+            created_at,
+            updated_at: created_at,
+            description: None,
+            attempt_id: "pay_1_1".to_string(),
+            refund_reason: None,
+            refund_error_code: None,
+            profile_id: None,
+            refund_reason_code: None,
+            merchant_connector_id: None,
+        }
+    }
+
+    fn get_attempt(status: enums::AttemptStatus) -> AttemptAmounts {
+        AttemptAmounts {
+            status,
+            amount: 10000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_payment_amounts_across_payment_states() {
+        let refunds = vec![
+            get_refund(2000, enums::RefundStatus::Success),
+            get_refund(1000, enums::RefundStatus::Pending),
+            get_refund(5000, enums::RefundStatus::Failure),
+            get_refund(5000, enums::RefundStatus::TransactionFailure),
+        ];
+        let cases = [
+            // Not yet authorized
+            (
+                get_attempt(enums::AttemptStatus::Authorizing),
+                None,
+                vec![],
+                PaymentAmounts::default(),
+            ),
+            (
+                get_attempt(enums::AttemptStatus::Failure),
+                None,
+                vec![],
+                PaymentAmounts::default(),
+            ),
+            // Authorized only
+            (
+                get_attempt(enums::AttemptStatus::Authorized),
+                None,
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_capturable: 10000,
+                    ..Default::default()
+                },
+            ),
+            // Partially authorized by the connector
+            (
+                AttemptAmounts {
+                    authorized_amount: Some(6000),
+                    ..get_attempt(enums::AttemptStatus::PartiallyAuthorized)
+                },
+                None,
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 6000,
+                    amount_capturable: 6000,
+                    ..Default::default()
+                },
+            ),
+            // Authorized with a surcharge and tax
+            (
+                AttemptAmounts {
+                    surcharge_amount: Some(300),
+                    tax_amount: Some(200),
+                    ..get_attempt(enums::AttemptStatus::Authorized)
+                },
+                None,
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10500,
+                    amount_capturable: 10500,
+                    ..Default::default()
+                },
+            ),
+            // Partially captured with multiple captures
+            (
+                AttemptAmounts {
+                    multiple_capture_count: Some(1),
+                    ..get_attempt(enums::AttemptStatus::PartialCharged)
+                },
+                Some(4000),
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_capturable: 6000,
+                    amount_captured: 4000,
+                    amount_refundable: 4000,
+                    ..Default::default()
+                },
+            ),
+            // Partially captured by a single capture, the remainder was released
+            (
+                get_attempt(enums::AttemptStatus::PartialCharged),
+                Some(4000),
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_captured: 4000,
+                    amount_refundable: 4000,
+                    ..Default::default()
+                },
+            ),
+            // Fully captured with a partial refund
+            (
+                get_attempt(enums::AttemptStatus::Charged),
+                Some(10000),
+                refunds.clone(),
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_captured: 10000,
+                    amount_refunded: 3000,
+                    amount_refundable: 7000,
+                    ..Default::default()
+                },
+            ),
+            // Charged without the captured amount recorded on the payment
+            (
+                get_attempt(enums::AttemptStatus::Charged),
+                None,
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_captured: 10000,
+                    amount_refundable: 10000,
+                    ..Default::default()
+                },
+            ),
+            // Voided without a capture
+            (
+                get_attempt(enums::AttemptStatus::Voided),
+                None,
+                vec![],
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    ..Default::default()
+                },
+            ),
+            // Remainder voided after a partial capture
+            (
+                AttemptAmounts {
+                    multiple_capture_count: Some(1),
+                    ..get_attempt(enums::AttemptStatus::Voided)
+                },
+                Some(4000),
+                refunds.clone(),
+                PaymentAmounts {
+                    amount_authorized: 10000,
+                    amount_captured: 4000,
+                    amount_refunded: 3000,
+                    amount_refundable: 1000,
+                    ..Default::default()
+                },
+            ),
+            // Connectors capturing more than was authorized
+            (
+                get_attempt(enums::AttemptStatus::Charged),
+                Some(11500),
+                refunds,
+                PaymentAmounts {
+                    amount_authorized: 11500,
+                    amount_captured: 11500,
+                    amount_refunded: 3000,
+                    amount_refundable: 8500,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (attempt, amount_captured, refunds, expected) in cases {
+            assert_eq!(
+                get_payment_amounts(&attempt, amount_captured, &refunds),
+                expected,
+                "{attempt:?} with {amount_captured:?} captured"
+            );
+        }
+    }
+
+    #[test]
+    fn test_payment_amounts_invariants() {
+        let statuses = [
+            enums::AttemptStatus::Started,
+            enums::AttemptStatus::Authorizing,
+            enums::AttemptStatus::Authorized,
+            enums::AttemptStatus::PartiallyAuthorized,
+            enums::AttemptStatus::Charged,
+            enums::AttemptStatus::PartialCharged,
+            enums::AttemptStatus::CaptureInitiated,
+            enums::AttemptStatus::CaptureFailed,
+            enums::AttemptStatus::Voided,
+            enums::AttemptStatus::VoidFailed,
+            enums::AttemptStatus::AutoRefunded,
+            enums::AttemptStatus::AuthorizationExpired,
+            enums::AttemptStatus::Failure,
+        ];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(200);
+
+        for _ in 0..10000 {
+            let amount = rng.gen_range(1..100000);
+            let attempt = AttemptAmounts {
+                status: statuses[rng.gen_range(0..statuses.len())],
+                amount,
+                surcharge_amount: rng.gen_bool(0.3).then(|| rng.gen_range(0..1000)),
+                tax_amount: rng.gen_bool(0.3).then(|| rng.gen_range(0..1000)),
+                authorized_amount: rng.gen_bool(0.2).then(|| rng.gen_range(1..=amount)),
+                multiple_capture_count: rng.gen_bool(0.5).then_some(1),
+            };
+            // Captures of up to a tenth more than the amount, for over-capture connectors
+            let amount_captured = rng
+                .gen_bool(0.8)
+                .then(|| rng.gen_range(0..=amount + amount / 10));
+
+            // Refunds are only created for amounts which are still refundable, failed refunds are
+            // created for any amount
+            let mut refunds = vec![];
+            for _ in 0..rng.gen_range(0..5) {
+                let refundable =
+                    get_payment_amounts(&attempt, amount_captured, &refunds).amount_refundable;
+                if rng.gen_bool(0.3) {
+                    refunds.push(get_refund(
+                        rng.gen_range(1..=amount),
+                        enums::RefundStatus::Failure,
+                    ));
+                } else if refundable > 0 {
+                    refunds.push(get_refund(
+                        rng.gen_range(1..=refundable),
+                        enums::RefundStatus::Success,
+                    ));
+                }
+            }
+
+            let amounts = get_payment_amounts(&attempt, amount_captured, &refunds);
+            assert!(
+                amounts.amount_captured + amounts.amount_capturable <= amounts.amount_authorized,
+                "{attempt:?} {amounts:?}"
+            );
+            assert!(
+                amounts.amount_refunded <= amounts.amount_captured,
+                "{attempt:?} {amounts:?}"
+            );
+            assert_eq!(
+                amounts.amount_refunded + amounts.amount_refundable,
+                amounts.amount_captured,
+                "{attempt:?} {amounts:?}"
+            );
+            assert!(amounts.amount_capturable >= 0 && amounts.amount_refundable >= 0);
+        }
+    }
+}
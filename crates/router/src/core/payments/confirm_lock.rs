@@ -0,0 +1,506 @@
+//! Protection of the confirm flow against concurrent confirm requests of the same payment, such
+//! as a double click or a retry of the client. The lock is held across the connector call, a
+//! concurrent request waits for it and returns the result of the request holding it, or is
+//! rejected with the status of the payment if the wait times out. A payment left in processing,
+//! because the lock expired or the request holding it failed midway, is synced with the connector
+//! instead of being authorized again.
+
+use std::{future::Future, sync::Arc};
+
+use api_models::{enums as api_enums, payments::HeaderPayload};
+use async_trait::async_trait;
+use error_stack::{report, ResultExt};
+use redis_interface as redis;
+use router_env::{instrument, logger, tracing};
+
+use super::{payments_core, CallConnectorAction, PaymentStatus};
+use crate::{
+    configs::settings::PaymentConfirmLockConfig,
+    consts,
+    core::{
+        api_locking,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    },
+    routes::{app::AppStateInfo, lock_utils, AppState},
+    services,
+    types::{api, domain},
+    utils,
+};
+
+#[async_trait]
+pub trait PaymentLock {
+    /// Acquires the lock, `false` if it is held by another request
+    async fn try_acquire(&self) -> RouterResult<bool>;
+    /// Releases the lock, `false` if it was no longer held by this request
+    async fn release(&self) -> RouterResult<bool>;
+}
+
+pub struct RedisPaymentLock {
+    redis_conn: Arc<redis::RedisConnectionPool>,
+    key: String,
+    holder: String,
+    expiry_in_secs: u32,
+}
+
+impl RedisPaymentLock {
+    /// The lock shares the key of the API lock of the payment, so that the other flows of the
+    /// payment wait for the confirm request
+    pub fn new(state: &AppState, merchant_id: &str, payment_id: &str) -> RouterResult<Self> {
+        Self::with_locking_key(state, merchant_id, payment_id.to_owned())
+    }
+
+    /// The lock of the merchant order reference, held while a payment created with it is checked
+    /// for duplicates and inserted
+    pub fn for_merchant_order_reference(
+        state: &AppState,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+    ) -> RouterResult<Self> {
+        Self::with_locking_key(
+            state,
+            merchant_id,
+            format!("order_reference_{merchant_order_reference_id}"),
+        )
+    }
+
+    fn with_locking_key(
+        state: &AppState,
+        merchant_id: &str,
+        unique_locking_key: String,
+    ) -> RouterResult<Self> {
+        let redis_conn = state
+            .store
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        let key = api_locking::LockingInput {
+            unique_locking_key,
+            api_identifier: lock_utils::ApiIdentifier::Payments,
+            override_lock_retries: None,
+        }
+        .get_redis_locking_key(merchant_id.to_owned());
+        let holder = state
+            .get_request_id()
+            .unwrap_or_else(|| utils::generate_id(consts::ID_LENGTH, "confirm"));
+
+        Ok(Self {
+            redis_conn,
+            key,
+            holder,
+            expiry_in_secs: state.conf.payment_confirm_lock.lock_expiry_in_secs,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentLock for RedisPaymentLock {
+    async fn try_acquire(&self) -> RouterResult<bool> {
+        let reply = self
+            .redis_conn
+            .set_key_if_not_exists_with_expiry(
+                &self.key,
+                self.holder.as_str(),
+                Some(i64::from(self.expiry_in_secs)),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        Ok(reply == redis::SetnxReply::KeySet)
+    }
+
+    async fn release(&self) -> RouterResult<bool> {
+        let holder = self
+            .redis_conn
+            .get_key::<Option<String>>(&self.key)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        if holder.as_deref() != Some(self.holder.as_str()) {
+            return Ok(false);
+        }
+        let reply = self
+            .redis_conn
+            .delete_key(&self.key)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        Ok(matches!(reply, redis::types::DelReply::KeyDeleted))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmDecision {
+    /// The payment is confirmed with the connector
+    Confirm,
+    /// The payment has already been confirmed, its current result is returned
+    ReturnResult,
+    /// The payment may have reached the connector without its result being recorded, it is synced
+    /// with the connector
+    SyncWithConnector,
+}
+
+/// Decides how a confirm request proceeds from the status of the payment. A payment which failed
+/// or requires customer action is confirmed again, unless the request waited for a concurrent
+/// confirm request, in which case the result of that request is returned.
+pub fn get_confirm_decision(
+    status: api_enums::IntentStatus,
+    waited_for_concurrent_request: bool,
+) -> ConfirmDecision {
+    match status {
+        api_enums::IntentStatus::Processing => ConfirmDecision::SyncWithConnector,
+        api_enums::IntentStatus::Succeeded
+        | api_enums::IntentStatus::RequiresCapture
+        | api_enums::IntentStatus::PartiallyCaptured
+        | api_enums::IntentStatus::RequiresMerchantAction => ConfirmDecision::ReturnResult,
+        api_enums::IntentStatus::Failed
+        | api_enums::IntentStatus::Cancelled
+        | api_enums::IntentStatus::RequiresCustomerAction
+            if waited_for_concurrent_request =>
+        {
+            ConfirmDecision::ReturnResult
+        }
+        _ => ConfirmDecision::Confirm,
+    }
+}
+
+/// Acquires the lock within the wait time of the config. `None` if it could not be acquired,
+/// otherwise whether the request had to wait for another request to release it.
+pub(super) async fn acquire_lock<L: PaymentLock + Sync>(
+    lock: &L,
+    config: &PaymentConfirmLockConfig,
+) -> RouterResult<Option<bool>> {
+    let retries = config
+        .wait_time_in_millis
+        .checked_div(config.delay_between_retries_in_millis)
+        .unwrap_or(0);
+    for attempt in 0..=retries {
+        if lock.try_acquire().await? {
+            return Ok(Some(attempt > 0));
+        }
+        if attempt < retries {
+            tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
+                config.delay_between_retries_in_millis,
+            )))
+            .await;
+        }
+    }
+    Ok(None)
+}
+
+/// Runs the confirm request of the payment under the lock. `retrieve` returns the current result
+/// of the payment, synced with the connector if its argument is `true`.
+#[instrument(skip_all)]
+pub async fn confirm_with_lock<L, R, S, SFut, C, CFut, T, TFut>(
+    lock: &L,
+    config: &PaymentConfirmLockConfig,
+    payment_id: &str,
+    get_status: S,
+    confirm: C,
+    retrieve: T,
+) -> RouterResult<R>
+where
+    L: PaymentLock + Sync,
+    S: Fn() -> SFut,
+    SFut: Future<Output = RouterResult<api_enums::IntentStatus>>,
+    C: FnOnce() -> CFut,
+    CFut: Future<Output = RouterResult<R>>,
+    T: FnOnce(bool) -> TFut,
+    TFut: Future<Output = RouterResult<R>>,
+{
+    let Some(waited_for_concurrent_request) = acquire_lock(lock, config).await? else {
+        let status = get_status().await?;
+        logger::info!(
+            "Confirm lock of the payment {payment_id} is held by another request, status {status}"
+        );
+        return match get_confirm_decision(status, true) {
+            ConfirmDecision::ReturnResult => retrieve(false).await,
+            ConfirmDecision::Confirm | ConfirmDecision::SyncWithConnector => Err(report!(
+                errors::ApiErrorResponse::PaymentConfirmInProgress {
+                    payment_id: payment_id.to_owned(),
+                    status,
+                }
+            )),
+        };
+    };
+
+    let result = async {
+        let status = get_status().await?;
+        match get_confirm_decision(status, waited_for_concurrent_request) {
+            ConfirmDecision::Confirm => confirm().await,
+            ConfirmDecision::ReturnResult => retrieve(false).await,
+            ConfirmDecision::SyncWithConnector => {
+                logger::warn!("Payment {payment_id} is in processing, syncing with the connector");
+                let synced_result = retrieve(true).await?;
+                match get_status().await? {
+                    api_enums::IntentStatus::Processing => Err(report!(
+                        errors::ApiErrorResponse::PaymentConfirmInProgress {
+                            payment_id: payment_id.to_owned(),
+                            status: api_enums::IntentStatus::Processing,
+                        }
+                    )),
+                    _ => Ok(synced_result),
+                }
+            }
+        }
+    }
+    .await;
+
+    // The result of the connector call is returned even if the lock could not be released, it
+    // expires on its own
+    match lock.release().await {
+        Ok(true) => (),
+        Ok(false) => logger::warn!(
+            "Confirm lock of the payment {payment_id} expired before the request completed"
+        ),
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to release the confirm lock of the payment {payment_id}"
+            )
+        }
+    }
+    result
+}
+
+/// Confirms the payment with `confirm` under the confirm lock of the payment
+pub async fn payments_confirm_with_lock<F, Fut>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_id: &str,
+    client_secret: Option<String>,
+    auth_flow: services::AuthFlow,
+    confirm: F,
+) -> RouterResponse<api::PaymentsResponse>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = RouterResponse<api::PaymentsResponse>>,
+{
+    let lock = RedisPaymentLock::new(state, &merchant_account.merchant_id, payment_id)?;
+    let get_status = || async {
+        state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id,
+                &merchant_account.merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+            .map(|payment_intent| payment_intent.status)
+    };
+    let retrieve = |force_sync| {
+        payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            PaymentStatus,
+            api::PaymentsRetrieveRequest {
+                resource_id: api::PaymentIdType::PaymentIntentId(payment_id.to_owned()),
+                force_sync,
+                client_secret,
+                ..Default::default()
+            },
+            auth_flow,
+            CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+    };
+
+    confirm_with_lock(
+        &lock,
+        &state.conf.payment_confirm_lock,
+        payment_id,
+        get_status,
+        confirm,
+        retrieve,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex,
+    };
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    struct InMemoryLock {
+        held: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl PaymentLock for InMemoryLock {
+        async fn try_acquire(&self) -> RouterResult<bool> {
+            Ok(self
+                .held
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok())
+        }
+
+        async fn release(&self) -> RouterResult<bool> {
+            Ok(self.held.swap(false, Ordering::SeqCst))
+        }
+    }
+
+    /// Payment confirmed against a connector which takes `connector_delay_in_millis` to authorize
+    #[derive(Clone)]
+    struct MockPayment {
+        held: Arc<AtomicBool>,
+        status: Arc<Mutex<api_enums::IntentStatus>>,
+        authorizations: Arc<AtomicU32>,
+        syncs: Arc<AtomicU32>,
+        connector_delay_in_millis: u64,
+    }
+
+    impl MockPayment {
+        fn new(status: api_enums::IntentStatus, connector_delay_in_millis: u64) -> Self {
+            Self {
+                held: Arc::new(AtomicBool::new(false)),
+                status: Arc::new(Mutex::new(status)),
+                authorizations: Arc::new(AtomicU32::new(0)),
+                syncs: Arc::new(AtomicU32::new(0)),
+                connector_delay_in_millis,
+            }
+        }
+
+        fn get_status(&self) -> api_enums::IntentStatus {
+            *self.status.lock().unwrap()
+        }
+
+        fn set_status(&self, status: api_enums::IntentStatus) {
+            *self.status.lock().unwrap() = status;
+        }
+
+        async fn confirm(&self, config: &PaymentConfirmLockConfig) -> RouterResult<String> {
+            let lock = InMemoryLock {
+                held: self.held.clone(),
+            };
+            confirm_with_lock(
+                &lock,
+                config,
+                "pay_123",
+                || async { Ok(self.get_status()) },
+                || async {
+                    self.set_status(api_enums::IntentStatus::Processing);
+                    tokio::time::sleep(tokio::time::Duration::from_millis(
+                        self.connector_delay_in_millis,
+                    ))
+                    .await;
+                    self.authorizations.fetch_add(1, Ordering::SeqCst);
+                    self.set_status(api_enums::IntentStatus::Succeeded);
+                    Ok("authorized".to_string())
+                },
+                |force_sync| async move {
+                    if force_sync {
+                        self.syncs.fetch_add(1, Ordering::SeqCst);
+                    }
+                    Ok(format!("retrieved {}", self.get_status()))
+                },
+            )
+            .await
+        }
+    }
+
+    fn get_config(wait_time_in_millis: u32) -> PaymentConfirmLockConfig {
+        PaymentConfirmLockConfig {
+            lock_expiry_in_secs: 120,
+            wait_time_in_millis,
+            delay_between_retries_in_millis: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_confirms_authorize_once() {
+        let payment = MockPayment::new(api_enums::IntentStatus::RequiresConfirmation, 100);
+        let config = get_config(2000);
+
+        let results = join_all((0..5).map(|_| payment.confirm(&config))).await;
+
+        assert_eq!(payment.authorizations.load(Ordering::SeqCst), 1);
+        let results = results.into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            results
+                .iter()
+                .filter(|result| *result == "authorized")
+                .count(),
+            1
+        );
+        assert_eq!(
+            results
+                .iter()
+                .filter(|result| *result == "retrieved succeeded")
+                .count(),
+            4
+        );
+        assert!(!payment.held.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_confirm_is_rejected_after_wait_time() {
+        let payment = MockPayment::new(api_enums::IntentStatus::RequiresConfirmation, 300);
+        let config = get_config(50);
+
+        let results = join_all((0..3).map(|_| payment.confirm(&config))).await;
+
+        assert_eq!(payment.authorizations.load(Ordering::SeqCst), 1);
+        let rejected = results
+            .iter()
+            .filter(|result| {
+                matches!(
+                    result.as_ref().map_err(|error| error.current_context()),
+                    Err(errors::ApiErrorResponse::PaymentConfirmInProgress {
+                        status: api_enums::IntentStatus::Processing,
+                        ..
+                    })
+                )
+            })
+            .count();
+        assert_eq!(rejected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_processing_payment_is_synced_instead_of_authorized() {
+        // The lock of the request which left the payment in processing has expired
+        let payment = MockPayment::new(api_enums::IntentStatus::Processing, 10);
+        let config = get_config(50);
+
+        let result = payment.confirm(&config).await;
+
+        assert_eq!(payment.authorizations.load(Ordering::SeqCst), 0);
+        assert_eq!(payment.syncs.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::PaymentConfirmInProgress { .. })
+        ));
+        assert!(!payment.held.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_payment_returns_result() {
+        let payment = MockPayment::new(api_enums::IntentStatus::Succeeded, 10);
+
+        let result = payment.confirm(&get_config(50)).await.unwrap();
+
+        assert_eq!(result, "retrieved succeeded");
+        assert_eq!(payment.authorizations.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_failed_payment_is_confirmed_again_only_without_concurrent_request() {
+        assert_eq!(
+            get_confirm_decision(api_enums::IntentStatus::Failed, false),
+            ConfirmDecision::Confirm
+        );
+        assert_eq!(
+            get_confirm_decision(api_enums::IntentStatus::Failed, true),
+            ConfirmDecision::ReturnResult
+        );
+        assert_eq!(
+            get_confirm_decision(api_enums::IntentStatus::RequiresPaymentMethod, true),
+            ConfirmDecision::Confirm
+        );
+    }
+}
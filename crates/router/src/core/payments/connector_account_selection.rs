@@ -0,0 +1,443 @@
+//! Selection of the merchant connector account through which a payment is processed, when the
+//! connector has more than one account in the business profile, such as a processor which requires
+//! different merchant IDs for cards and wallets. An account whose payment methods enabled list the
+//! payment method type of the payment explicitly is preferred over an account which enables the
+//! payment method for any payment method type. The account chosen and the reason for the choice are
+//! recorded in the routing metadata of the payment attempt.
+
+use api_models::enums as api_enums;
+use common_utils::ext_traits::{Encode, ValueExt};
+use error_stack::ResultExt;
+use serde::{Deserialize, Serialize};
+
+use super::eligibility;
+use crate::{
+    core::errors::{self, RouterResult},
+    db::StorageInterface,
+    types::{domain, storage},
+};
+
+/// Payment method of the payment, for which its merchant connector account is selected
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PaymentMethodDetails {
+    pub payment_method: Option<api_enums::PaymentMethod>,
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+}
+
+impl From<&storage::PaymentAttempt> for PaymentMethodDetails {
+    fn from(payment_attempt: &storage::PaymentAttempt) -> Self {
+        Self {
+            payment_method: payment_attempt.payment_method,
+            payment_method_type: payment_attempt.payment_method_type,
+        }
+    }
+}
+
+/// Reason for which a merchant connector account is selected, in the order of preference
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionReason {
+    /// The payment method type of the payment is enabled explicitly for the account
+    PaymentMethodTypeEnabled,
+    /// The payment method of the payment is enabled for the account for any payment method type
+    PaymentMethodEnabled,
+    /// The payment method is not enabled for any of the accounts of the connector, the account
+    /// created first is used
+    Default,
+}
+
+/// Merchant connector account through which the payment attempt is processed
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RoutingMetadata {
+    pub connector: String,
+    pub merchant_connector_id: String,
+    pub selection_reason: SelectionReason,
+}
+
+fn get_selection_reason(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    payment_method_details: &PaymentMethodDetails,
+) -> SelectionReason {
+    let Some(payment_method) = payment_method_details.payment_method else {
+        return SelectionReason::Default;
+    };
+    // Payment methods are not restricted if they are not configured
+    let Some(payment_methods_enabled) = merchant_connector_account
+        .payment_methods_enabled
+        .as_deref()
+    else {
+        return SelectionReason::PaymentMethodEnabled;
+    };
+
+    let enabled_for_payment_method =
+        eligibility::parse_payment_methods_enabled(payment_methods_enabled)
+            .filter(|enabled| enabled.payment_method == payment_method)
+            .collect::<Vec<_>>();
+
+    let is_payment_method_type_enabled =
+        payment_method_details
+            .payment_method_type
+            .map_or(false, |payment_method_type| {
+                enabled_for_payment_method.iter().any(|enabled| {
+                    enabled
+                        .payment_method_types
+                        .iter()
+                        .flatten()
+                        .any(|enabled_type| enabled_type.payment_method_type == payment_method_type)
+                })
+            });
+
+    if is_payment_method_type_enabled {
+        SelectionReason::PaymentMethodTypeEnabled
+    } else if enabled_for_payment_method.iter().any(|enabled| {
+        enabled.payment_method_types.is_none()
+            || payment_method_details.payment_method_type.is_none()
+    }) {
+        SelectionReason::PaymentMethodEnabled
+    } else {
+        SelectionReason::Default
+    }
+}
+
+/// Selects the merchant connector account of the payment from the accounts of a connector. The
+/// accounts are considered in the order of their creation, so that the same account is selected
+/// for every flow of the payment.
+pub fn select_merchant_connector_account(
+    mut merchant_connector_accounts: Vec<domain::MerchantConnectorAccount>,
+    payment_method_details: &PaymentMethodDetails,
+) -> Option<(domain::MerchantConnectorAccount, SelectionReason)> {
+    merchant_connector_accounts
+        .sort_by_key(|merchant_connector_account| merchant_connector_account.created_at);
+
+    // Disabled accounts are selected only when all the accounts of the connector are disabled, so
+    // that the payment is reported as not eligible for the connector
+    let (enabled, disabled): (Vec<_>, Vec<_>) =
+        merchant_connector_accounts
+            .into_iter()
+            .partition(|merchant_connector_account| {
+                !merchant_connector_account.disabled.unwrap_or(false)
+            });
+    let candidates = if enabled.is_empty() {
+        disabled
+    } else {
+        enabled
+    };
+
+    candidates
+        .into_iter()
+        .map(|merchant_connector_account| {
+            let reason = get_selection_reason(&merchant_connector_account, payment_method_details);
+            (merchant_connector_account, reason)
+        })
+        .min_by_key(|(_, reason)| *reason)
+}
+
+/// Finds the merchant connector account of the connector in the business profile through which
+/// the payment is processed
+pub async fn find_merchant_connector_account(
+    db: &dyn StorageInterface,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &str,
+    connector_name: &str,
+    payment_method_details: &PaymentMethodDetails,
+) -> RouterResult<Option<(domain::MerchantConnectorAccount, SelectionReason)>> {
+    let merchant_connector_accounts = db
+        .find_merchant_connector_accounts_by_profile_id_connector_name(
+            profile_id,
+            connector_name,
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the merchant connector accounts of the connector")?;
+
+    Ok(select_merchant_connector_account(
+        merchant_connector_accounts,
+        payment_method_details,
+    ))
+}
+
+/// Merchant connector account which the payment attempt was routed to, when it was routed to the
+/// connector. The flows which follow the authorization of the payment go through this account
+/// instead of selecting an account again, as the accounts of the connector may have changed since.
+pub fn get_routed_merchant_connector_id(
+    payment_attempt: &storage::PaymentAttempt,
+    connector_name: &str,
+) -> Option<String> {
+    payment_attempt
+        .routing_metadata
+        .clone()
+        .and_then(|routing_metadata| {
+            routing_metadata
+                .parse_value::<RoutingMetadata>("RoutingMetadata")
+                .ok()
+        })
+        .filter(|routing_metadata| routing_metadata.connector == connector_name)
+        .map(|routing_metadata| routing_metadata.merchant_connector_id)
+}
+
+/// Routing metadata of the payment attempt routed to the connector, recording the merchant
+/// connector account selected for the payment
+pub async fn get_routing_metadata(
+    db: &dyn StorageInterface,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &str,
+    connector_name: &str,
+    payment_method_details: &PaymentMethodDetails,
+) -> RouterResult<Option<serde_json::Value>> {
+    let Some((merchant_connector_account, selection_reason)) = find_merchant_connector_account(
+        db,
+        key_store,
+        profile_id,
+        connector_name,
+        payment_method_details,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    Encode::<RoutingMetadata>::encode_to_value(&RoutingMetadata {
+        connector: connector_name.to_owned(),
+        merchant_connector_id: merchant_connector_account.merchant_connector_id,
+        selection_reason,
+    })
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to serialize the routing metadata")
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use data_models::payments::payment_attempt::PaymentAttemptInterface;
+    use masking::Secret;
+
+    use super::*;
+
+    fn get_merchant_connector_account(
+        merchant_connector_id: &str,
+        payment_methods_enabled: serde_json::Value,
+        created_at: time::PrimitiveDateTime,
+    ) -> domain::MerchantConnectorAccount {
+        domain::MerchantConnectorAccount {
+            id: None,
+            merchant_id: "merchant_1".to_string(),
+            connector_name: "stripe".to_string(),
+            connector_account_details: common_utils::crypto::Encryptable::new(
+                Secret::new(serde_json::json!({ "auth_type": "HeaderKey" })),
+                Secret::new(Vec::new()),
+            ),
+            test_mode: None,
+            disabled: Some(false),
+            merchant_connector_id: merchant_connector_id.to_string(),
+            payment_methods_enabled: Some(vec![payment_methods_enabled]),
+            connector_type: api_enums::ConnectorType::PaymentProcessor,
+            metadata: None,
+            frm_configs: None,
+            connector_label: Some(format!("stripe_{merchant_connector_id}")),
+            business_country: None,
+            business_label: None,
+            business_sub_label: None,
+            created_at,
+            modified_at: created_at,
+            connector_webhook_details: None,
+            profile_id: Some("pro_1".to_string()),
+            applepay_verified_domains: None,
+            disabled_flows: None,
+            previous_credentials: None,
+        }
+    }
+
+    fn get_payment_method_type(payment_method_type: &str) -> serde_json::Value {
+        serde_json::json!({
+            "payment_method_type": payment_method_type,
+            "recurring_enabled": true,
+            "installment_payment_enabled": false,
+        })
+    }
+
+    /// A Stripe account which is created first for the wallets, and one for the cards
+    fn get_stripe_accounts() -> Vec<domain::MerchantConnectorAccount> {
+        let now = common_utils::date_time::now();
+        vec![
+            get_merchant_connector_account(
+                "mca_wallets",
+                serde_json::json!({
+                    "payment_method": "wallet",
+                    "payment_method_types": [
+                        get_payment_method_type("apple_pay"),
+                        get_payment_method_type("google_pay"),
+                    ],
+                }),
+                now.saturating_sub(time::Duration::days(1)),
+            ),
+            get_merchant_connector_account(
+                "mca_cards",
+                serde_json::json!({
+                    "payment_method": "card",
+                    "payment_method_types": [
+                        get_payment_method_type("credit"),
+                        get_payment_method_type("debit"),
+                    ],
+                }),
+                now,
+            ),
+        ]
+    }
+
+    fn select(
+        payment_method: api_enums::PaymentMethod,
+        payment_method_type: Option<api_enums::PaymentMethodType>,
+    ) -> (String, SelectionReason) {
+        let (merchant_connector_account, reason) = select_merchant_connector_account(
+            get_stripe_accounts(),
+            &PaymentMethodDetails {
+                payment_method: Some(payment_method),
+                payment_method_type,
+            },
+        )
+        .unwrap();
+        (merchant_connector_account.merchant_connector_id, reason)
+    }
+
+    #[test]
+    fn test_each_account_receives_only_its_payment_method_types() {
+        for payment_method_type in [
+            api_enums::PaymentMethodType::ApplePay,
+            api_enums::PaymentMethodType::GooglePay,
+        ] {
+            assert_eq!(
+                select(api_enums::PaymentMethod::Wallet, Some(payment_method_type)),
+                (
+                    "mca_wallets".to_string(),
+                    SelectionReason::PaymentMethodTypeEnabled
+                )
+            );
+        }
+        for payment_method_type in [
+            api_enums::PaymentMethodType::Credit,
+            api_enums::PaymentMethodType::Debit,
+        ] {
+            assert_eq!(
+                select(api_enums::PaymentMethod::Card, Some(payment_method_type)),
+                (
+                    "mca_cards".to_string(),
+                    SelectionReason::PaymentMethodTypeEnabled
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_enabling_the_payment_method_is_selected_without_payment_method_type() {
+        assert_eq!(
+            select(api_enums::PaymentMethod::Card, None),
+            (
+                "mca_cards".to_string(),
+                SelectionReason::PaymentMethodEnabled
+            )
+        );
+    }
+
+    #[test]
+    fn test_explicit_payment_method_type_is_preferred_over_generic_account() {
+        let mut merchant_connector_accounts = get_stripe_accounts();
+        merchant_connector_accounts.insert(
+            0,
+            get_merchant_connector_account(
+                "mca_generic",
+                serde_json::json!({ "payment_method": "wallet" }),
+                common_utils::date_time::now().saturating_sub(time::Duration::days(2)),
+            ),
+        );
+        let payment_method_details = PaymentMethodDetails {
+            payment_method: Some(api_enums::PaymentMethod::Wallet),
+            payment_method_type: Some(api_enums::PaymentMethodType::ApplePay),
+        };
+
+        let (merchant_connector_account, reason) = select_merchant_connector_account(
+            merchant_connector_accounts.clone(),
+            &payment_method_details,
+        )
+        .unwrap();
+        assert_eq!(
+            merchant_connector_account.merchant_connector_id,
+            "mca_wallets"
+        );
+        assert_eq!(reason, SelectionReason::PaymentMethodTypeEnabled);
+
+        // The generic account is the fallback of the payment method types not listed explicitly
+        let (merchant_connector_account, reason) = select_merchant_connector_account(
+            merchant_connector_accounts,
+            &PaymentMethodDetails {
+                payment_method_type: Some(api_enums::PaymentMethodType::Paypal),
+                ..payment_method_details
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            merchant_connector_account.merchant_connector_id,
+            "mca_generic"
+        );
+        assert_eq!(reason, SelectionReason::PaymentMethodEnabled);
+    }
+
+    #[test]
+    fn test_disabled_account_is_not_selected() {
+        let mut merchant_connector_accounts = get_stripe_accounts();
+        merchant_connector_accounts[1].disabled = Some(true);
+
+        let (merchant_connector_account, reason) = select_merchant_connector_account(
+            merchant_connector_accounts,
+            &PaymentMethodDetails {
+                payment_method: Some(api_enums::PaymentMethod::Card),
+                payment_method_type: Some(api_enums::PaymentMethodType::Credit),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            merchant_connector_account.merchant_connector_id,
+            "mca_wallets"
+        );
+        assert_eq!(reason, SelectionReason::Default);
+    }
+
+    #[tokio::test]
+    async fn test_routed_account_is_used_for_the_connector_of_the_routing() {
+        let db = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let routing_metadata = Encode::<RoutingMetadata>::encode_to_value(&RoutingMetadata {
+            connector: "stripe".to_string(),
+            merchant_connector_id: "mca_cards".to_string(),
+            selection_reason: SelectionReason::PaymentMethodTypeEnabled,
+        })
+        .unwrap();
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    connector: Some("stripe".to_string()),
+                    routing_metadata: Some(routing_metadata),
+                    ..Default::default()
+                },
+                storage::enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_routed_merchant_connector_id(&payment_attempt, "stripe").as_deref(),
+            Some("mca_cards")
+        );
+        assert_eq!(
+            get_routed_merchant_connector_id(&payment_attempt, "adyen"),
+            None
+        );
+    }
+}
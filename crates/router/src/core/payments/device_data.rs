@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use api_models::{
+    enums as api_enums,
+    payments::{DeviceDataCollectionParams, PaymentsDeviceDataRequest, PaymentsDeviceDataResponse},
+};
+use common_utils::generate_id;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    configs::settings::DeviceDataCollectionConfig,
+    connector::utils::device_data_collection,
+    consts,
+    core::{
+        errors::{self, RouterResponse, StorageErrorExt},
+        payments::helpers,
+    },
+    routes::AppState,
+    services,
+    types::{domain, storage, storage::enums as storage_enums},
+};
+
+/// Creates the device data session of the payment, which the SDK runs the device data collection
+/// of the connectors requiring it with, before the payment is confirmed. The session is stored on
+/// the payment and sent along with the authorization, so the same session is returned when the
+/// endpoint is called again for the payment.
+#[instrument(skip_all)]
+pub async fn create_device_data_session(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: PaymentsDeviceDataRequest,
+) -> RouterResponse<PaymentsDeviceDataResponse> {
+    let db = state.store.as_ref();
+    let payment_intent = helpers::verify_payment_intent_time_and_client_secret(
+        db,
+        &merchant_account,
+        Some(request.client_secret),
+    )
+    .await?
+    .ok_or(errors::ApiErrorResponse::PaymentNotFound)
+    .into_report()?;
+
+    helpers::validate_payment_status_against_not_allowed_statuses(
+        &payment_intent.status,
+        &[
+            storage_enums::IntentStatus::Failed,
+            storage_enums::IntentStatus::Succeeded,
+            storage_enums::IntentStatus::Cancelled,
+            storage_enums::IntentStatus::Processing,
+            storage_enums::IntentStatus::RequiresCapture,
+            storage_enums::IntentStatus::PartiallyCaptured,
+        ],
+        "collect the device data for",
+    )?;
+
+    let payment_intent = match payment_intent.device_session_id.clone() {
+        Some(_) => payment_intent,
+        None => db
+            .update_payment_intent(
+                payment_intent,
+                storage::PaymentIntentUpdate::DeviceSessionUpdate {
+                    device_session_id: generate_id(consts::ID_LENGTH, "dds"),
+                },
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?,
+    };
+    let device_session_id = payment_intent
+        .device_session_id
+        .clone()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("device_session_id is not set in payment_intent")?;
+
+    let merchant_connector_accounts = db
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_account.merchant_id,
+            false,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?
+        .into_iter()
+        .filter(|merchant_connector_account| {
+            payment_intent.profile_id.is_none()
+                || merchant_connector_account.profile_id == payment_intent.profile_id
+        })
+        .map(|merchant_connector_account| {
+            (
+                merchant_connector_account.connector_name,
+                merchant_connector_account.test_mode.unwrap_or(false),
+            )
+        });
+
+    Ok(services::ApplicationResponse::Json(
+        PaymentsDeviceDataResponse {
+            payment_id: payment_intent.payment_id,
+            device_data_collection: get_device_data_collection_params(
+                &state.conf.device_data_collection,
+                merchant_connector_accounts,
+                &device_session_id,
+            ),
+            device_session_id,
+        },
+    ))
+}
+
+/// Parameters of the device data collection of each of the connectors requiring it, once for each
+/// connector and mode even if the merchant has several accounts of the connector
+fn get_device_data_collection_params(
+    config: &DeviceDataCollectionConfig,
+    connectors: impl Iterator<Item = (String, bool)>,
+    device_session_id: &str,
+) -> Vec<DeviceDataCollectionParams> {
+    let mut params: Vec<DeviceDataCollectionParams> = Vec::new();
+    for (connector_name, test_mode) in connectors {
+        let is_device_session_required = api_enums::Connector::from_str(&connector_name)
+            .map(device_data_collection::is_device_session_required)
+            .unwrap_or(false);
+        if !is_device_session_required {
+            continue;
+        }
+        let Some(collection) = config.0.get(&connector_name) else {
+            logger::error!(
+                "Device data collection is not configured for the connector {connector_name}"
+            );
+            continue;
+        };
+        let connector_params = DeviceDataCollectionParams {
+            connector: connector_name,
+            org_id: if test_mode {
+                collection.test_org_id.clone()
+            } else {
+                collection.live_org_id.clone()
+            },
+            session_id: device_session_id.to_string(),
+            script_url: collection.script_url.clone(),
+        };
+        if !params.contains(&connector_params) {
+            params.push(connector_params);
+        }
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::settings::ConnectorDeviceDataCollection;
+
+    fn get_device_data_collection_config() -> DeviceDataCollectionConfig {
+        DeviceDataCollectionConfig(
+            [(
+                "cybersource".to_string(),
+                ConnectorDeviceDataCollection {
+                    test_org_id: "test_org".to_string(),
+                    live_org_id: "live_org".to_string(),
+                    script_url: "https://h.online-metrix.net/fp/tags.js".to_string(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn test_device_data_collection_params_of_connectors_requiring_it() {
+        let params = get_device_data_collection_params(
+            &get_device_data_collection_config(),
+            [
+                ("stripe".to_string(), true),
+                ("cybersource".to_string(), true),
+                ("cybersource".to_string(), true),
+                ("cybersource".to_string(), false),
+                ("unknown_connector".to_string(), false),
+            ]
+            .into_iter(),
+            "dds_1",
+        );
+
+        assert_eq!(
+            params,
+            vec![
+                DeviceDataCollectionParams {
+                    connector: "cybersource".to_string(),
+                    org_id: "test_org".to_string(),
+                    session_id: "dds_1".to_string(),
+                    script_url: "https://h.online-metrix.net/fp/tags.js".to_string(),
+                },
+                DeviceDataCollectionParams {
+                    connector: "cybersource".to_string(),
+                    org_id: "live_org".to_string(),
+                    session_id: "dds_1".to_string(),
+                    script_url: "https://h.online-metrix.net/fp/tags.js".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_device_data_collection_without_connectors_requiring_it() {
+        let params = get_device_data_collection_params(
+            &get_device_data_collection_config(),
+            [("stripe".to_string(), true), ("adyen".to_string(), false)].into_iter(),
+            "dds_1",
+        );
+
+        assert!(params.is_empty());
+    }
+}
@@ -0,0 +1,356 @@
+//! Detection of the payments created more than once for the same order of the merchant, such as
+//! when the customer submits the checkout twice. When a payment is created with the merchant order
+//! reference of a payment created earlier within the duplicate order reference window of the
+//! business profile, which has not failed or been cancelled, the new payment is rejected, the
+//! earlier payment is returned instead, or the new payment is flagged as a suspected duplicate of
+//! it, as configured in the business profile. The lookup and the creation of the payment run under
+//! a lock of the merchant order reference, so that concurrent requests with the same reference are
+//! checked one after the other.
+
+use std::future::Future;
+
+use api_models::{enums as api_enums, payments::HeaderPayload};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::{
+    confirm_lock::{self, PaymentLock, RedisPaymentLock},
+    payments_core, CallConnectorAction, PaymentStatus,
+};
+use crate::{
+    configs::settings::PaymentConfirmLockConfig,
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        utils as core_utils,
+    },
+    routes::AppState,
+    services,
+    types::{api, domain},
+};
+
+/// Creates the payment under the lock of the merchant order reference. `find_existing` returns the
+/// identifier of the payment created earlier with the reference, `create` is passed the payment
+/// which the new payment is suspected to be a duplicate of and `retrieve_existing` returns the
+/// earlier payment.
+#[instrument(skip_all)]
+pub async fn create_with_duplicate_detection<L, R, E, EFut, C, CFut, T, TFut>(
+    lock: &L,
+    config: &PaymentConfirmLockConfig,
+    behaviour: api_enums::DuplicateOrderReferenceBehaviour,
+    merchant_order_reference_id: &str,
+    find_existing: E,
+    create: C,
+    retrieve_existing: T,
+) -> RouterResult<R>
+where
+    L: PaymentLock + Sync,
+    E: FnOnce() -> EFut,
+    EFut: Future<Output = RouterResult<Option<String>>>,
+    C: FnOnce(Option<String>) -> CFut,
+    CFut: Future<Output = RouterResult<R>>,
+    T: FnOnce(String) -> TFut,
+    TFut: Future<Output = RouterResult<R>>,
+{
+    if confirm_lock::acquire_lock(lock, config).await?.is_none() {
+        logger::info!(
+            "Lock of the merchant order reference {merchant_order_reference_id} is held by another request"
+        );
+        return Err(report!(errors::ApiErrorResponse::ResourceBusy));
+    }
+
+    let result = async {
+        let Some(payment_id) = find_existing().await? else {
+            return create(None).await;
+        };
+        logger::info!(
+            "Payment {payment_id} has already been created for the merchant order reference {merchant_order_reference_id}"
+        );
+        match behaviour {
+            api_enums::DuplicateOrderReferenceBehaviour::Reject => Err(report!(
+                errors::ApiErrorResponse::DuplicateOrderReference {
+                    merchant_order_reference_id: merchant_order_reference_id.to_owned(),
+                    payment_id,
+                }
+            )),
+            api_enums::DuplicateOrderReferenceBehaviour::ReturnExisting => {
+                retrieve_existing(payment_id).await
+            }
+            api_enums::DuplicateOrderReferenceBehaviour::Flag => create(Some(payment_id)).await,
+        }
+    }
+    .await;
+
+    match lock.release().await {
+        Ok(true) => (),
+        Ok(false) => logger::warn!(
+            "Lock of the merchant order reference {merchant_order_reference_id} expired before the request completed"
+        ),
+        Err(error) => logger::error!(
+            ?error,
+            "Failed to release the lock of the merchant order reference {merchant_order_reference_id}"
+        ),
+    }
+    result
+}
+
+/// Creates the payment with `create`, checking it for duplicates when the request carries a
+/// merchant order reference and the business profile configures the duplicate detection. `create`
+/// is passed the request and the payment which the new payment is suspected to be a duplicate of
+pub async fn payments_create_with_duplicate_detection<F, Fut>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    request: api::PaymentsRequest,
+    create: F,
+) -> RouterResponse<api::PaymentsResponse>
+where
+    F: FnOnce(api::PaymentsRequest, Option<String>) -> Fut,
+    Fut: Future<Output = RouterResponse<api::PaymentsResponse>>,
+{
+    let Some(merchant_order_reference_id) = request.merchant_order_reference_id.clone() else {
+        return create(request, None).await;
+    };
+
+    let profile_id = core_utils::get_profile_id_from_business_details(
+        request.business_country,
+        request.business_label.as_ref(),
+        merchant_account,
+        request.profile_id.as_ref(),
+        &*state.store,
+        true,
+    )
+    .await?;
+    let business_profile = state
+        .store
+        .find_business_profile_by_profile_id(&profile_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+            id: profile_id,
+        })?;
+    let Some(behaviour) = business_profile.duplicate_order_reference_behaviour else {
+        return create(request, None).await;
+    };
+
+    let window = business_profile
+        .duplicate_order_reference_window
+        .unwrap_or(consts::DEFAULT_DUPLICATE_ORDER_REFERENCE_WINDOW);
+    let created_after =
+        common_utils::date_time::now().saturating_sub(time::Duration::seconds(window));
+    let lock = RedisPaymentLock::for_merchant_order_reference(
+        state,
+        &merchant_account.merchant_id,
+        &merchant_order_reference_id,
+    )?;
+
+    let find_existing = || async {
+        state
+            .store
+            .find_payment_intent_by_merchant_order_reference_id(
+                &merchant_account.merchant_id,
+                &merchant_order_reference_id,
+                created_after,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the payment of the merchant order reference")
+            .map(|payment_intent| payment_intent.map(|payment_intent| payment_intent.payment_id))
+    };
+    let create_payment = |suspected_duplicate_of| create(request, suspected_duplicate_of);
+    let retrieve_existing = |payment_id| {
+        payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            PaymentStatus,
+            api::PaymentsRetrieveRequest {
+                resource_id: api::PaymentIdType::PaymentIntentId(payment_id),
+                ..Default::default()
+            },
+            services::AuthFlow::Merchant,
+            CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+    };
+
+    create_with_duplicate_detection(
+        &lock,
+        &state.conf.payment_confirm_lock,
+        behaviour,
+        &merchant_order_reference_id,
+        find_existing,
+        create_payment,
+        retrieve_existing,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    };
+
+    use async_trait::async_trait;
+    use futures::future::join_all;
+
+    use super::*;
+
+    struct InMemoryLock {
+        held: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl PaymentLock for InMemoryLock {
+        async fn try_acquire(&self) -> RouterResult<bool> {
+            Ok(self
+                .held
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok())
+        }
+
+        async fn release(&self) -> RouterResult<bool> {
+            Ok(self.held.swap(false, Ordering::SeqCst))
+        }
+    }
+
+    /// Payments of a single merchant order reference, each with the payment it is suspected to be
+    /// a duplicate of
+    #[derive(Clone, Default)]
+    struct MockPayments {
+        held: Arc<AtomicBool>,
+        payments: Arc<Mutex<Vec<(String, Option<String>)>>>,
+    }
+
+    impl MockPayments {
+        async fn create(
+            &self,
+            behaviour: api_enums::DuplicateOrderReferenceBehaviour,
+            payment_id: &str,
+        ) -> RouterResult<String> {
+            let lock = InMemoryLock {
+                held: self.held.clone(),
+            };
+            let config = PaymentConfirmLockConfig {
+                lock_expiry_in_secs: 120,
+                wait_time_in_millis: 2000,
+                delay_between_retries_in_millis: 10,
+            };
+            create_with_duplicate_detection(
+                &lock,
+                &config,
+                behaviour,
+                "order_2023_1045",
+                || async {
+                    Ok(self
+                        .payments
+                        .lock()
+                        .unwrap()
+                        .first()
+                        .map(|(payment_id, _)| payment_id.clone()))
+                },
+                |suspected_duplicate_of| async move {
+                    // The payment is inserted some time after the lookup
+                    tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+                    self.payments
+                        .lock()
+                        .unwrap()
+                        .push((payment_id.to_owned(), suspected_duplicate_of));
+                    Ok(format!("created {payment_id}"))
+                },
+                |existing_payment_id| async move { Ok(format!("retrieved {existing_payment_id}")) },
+            )
+            .await
+        }
+
+        fn get_payments(&self) -> Vec<(String, Option<String>)> {
+            self.payments.lock().unwrap().clone()
+        }
+    }
+
+    fn is_rejected_as_duplicate_of(result: &RouterResult<String>, payment_id: &str) -> bool {
+        matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::DuplicateOrderReference {
+                payment_id: existing_payment_id,
+                ..
+            }) if existing_payment_id == payment_id
+        )
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_payment_is_rejected() {
+        let payments = MockPayments::default();
+        let behaviour = api_enums::DuplicateOrderReferenceBehaviour::Reject;
+
+        let result = payments.create(behaviour, "pay_1").await.unwrap();
+        assert_eq!(result, "created pay_1");
+
+        let result = payments.create(behaviour, "pay_2").await;
+        assert!(is_rejected_as_duplicate_of(&result, "pay_1"));
+        assert_eq!(payments.get_payments(), vec![("pay_1".to_string(), None)]);
+        assert!(!payments.held.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_existing_payment_is_returned_for_duplicate() {
+        let payments = MockPayments::default();
+        let behaviour = api_enums::DuplicateOrderReferenceBehaviour::ReturnExisting;
+
+        payments.create(behaviour, "pay_1").await.unwrap();
+        let result = payments.create(behaviour, "pay_2").await.unwrap();
+
+        assert_eq!(result, "retrieved pay_1");
+        assert_eq!(payments.get_payments().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_payment_is_flagged() {
+        let payments = MockPayments::default();
+        let behaviour = api_enums::DuplicateOrderReferenceBehaviour::Flag;
+
+        payments.create(behaviour, "pay_1").await.unwrap();
+        let result = payments.create(behaviour, "pay_2").await.unwrap();
+
+        assert_eq!(result, "created pay_2");
+        assert_eq!(
+            payments.get_payments(),
+            vec![
+                ("pay_1".to_string(), None),
+                ("pay_2".to_string(), Some("pay_1".to_string()))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_creates_are_checked_one_after_the_other() {
+        let payments = MockPayments::default();
+        let payment_ids = (0..5)
+            .map(|index| format!("pay_{index}"))
+            .collect::<Vec<_>>();
+
+        let results = join_all(payment_ids.iter().map(|payment_id| {
+            payments.create(
+                api_enums::DuplicateOrderReferenceBehaviour::Reject,
+                payment_id,
+            )
+        }))
+        .await;
+
+        let created = payments.get_payments();
+        assert_eq!(created.len(), 1);
+        let (first_payment_id, _) = &created[0];
+        assert_eq!(
+            results
+                .iter()
+                .filter(|result| is_rejected_as_duplicate_of(result, first_payment_id))
+                .count(),
+            4
+        );
+        assert!(!payments.held.load(Ordering::SeqCst));
+    }
+}
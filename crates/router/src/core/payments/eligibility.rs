@@ -0,0 +1,1208 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use api_models::{
+    admin::{AcceptedCountries, AcceptedCurrencies, PaymentMethodsEnabled},
+    enums as api_enums,
+    payment_methods::RequestPaymentMethodTypes,
+    payments::{ConnectorEligibility, EligibilityCriterion},
+};
+use common_utils::ext_traits::ValueExt;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::{connector_account_selection, PaymentData};
+use crate::{
+    connector::utils::capabilities,
+    core::{
+        circuit_breaker,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        forex,
+        payments::helpers,
+    },
+    routes::AppState,
+    services,
+    types::{api, domain},
+};
+
+/// Details of the payment which are validated against the payment methods enabled for a connector
+#[derive(Clone, Debug, Default)]
+pub struct PaymentEligibilityData {
+    pub profile_id: String,
+    pub payment_method: Option<api_enums::PaymentMethod>,
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+    pub currency: Option<api_enums::Currency>,
+    pub country: Option<api_enums::CountryAlpha2>,
+    pub amount: Option<PaymentAmount>,
+}
+
+impl PaymentEligibilityData {
+    /// Eligibility can be validated only for payments which use the connector accounts of a
+    /// business profile, and not the connector details passed in the request
+    pub fn from_payment_data<F: Clone>(payment_data: &PaymentData<F>) -> Option<Self> {
+        if payment_data.creds_identifier.is_some() {
+            return None;
+        }
+
+        Some(Self {
+            profile_id: payment_data.payment_intent.profile_id.clone()?,
+            payment_method: payment_data.payment_attempt.payment_method,
+            payment_method_type: payment_data.payment_attempt.payment_method_type,
+            currency: Some(payment_data.currency),
+            country: payment_data
+                .address
+                .billing
+                .as_ref()
+                .and_then(|billing| billing.address.as_ref())
+                .and_then(|address| address.country),
+            amount: Some(PaymentAmount::new(
+                payment_data.payment_attempt.amount,
+                payment_data.currency,
+            )),
+        })
+    }
+
+    /// Converts the amount of the payment to the currencies of the amount ranges configured for
+    /// the merchant connector accounts, so that they can be validated
+    pub async fn with_converted_amount<'a>(
+        mut self,
+        state: &AppState,
+        merchant_connector_accounts: impl Iterator<Item = &'a domain::MerchantConnectorAccount>,
+    ) -> Self {
+        if let Some(amount) = self.amount.as_mut() {
+            amount
+                .convert_to_range_currencies(state, merchant_connector_accounts)
+                .await;
+        }
+        self
+    }
+}
+
+/// Amount of a payment, validated against the amount ranges of the payment method types
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentAmount {
+    /// The amount, in the lowest denomination of the currency
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    /// The amount in the lowest denomination of the other currencies in which amount ranges are
+    /// configured, a currency is absent when the amount could not be converted to it
+    pub converted_amounts: HashMap<api_enums::Currency, i64>,
+}
+
+impl PaymentAmount {
+    pub fn new(amount: i64, currency: api_enums::Currency) -> Self {
+        Self {
+            amount,
+            currency,
+            converted_amounts: HashMap::new(),
+        }
+    }
+
+    /// Converts the amount to the currencies of the amount ranges which are configured in another
+    /// currency for the merchant connector accounts
+    pub async fn convert_to_range_currencies<'a>(
+        &mut self,
+        state: &AppState,
+        merchant_connector_accounts: impl Iterator<Item = &'a domain::MerchantConnectorAccount>,
+    ) {
+        let range_currencies = merchant_connector_accounts
+            .flat_map(|merchant_connector_account| {
+                parse_payment_methods_enabled(
+                    merchant_connector_account
+                        .payment_methods_enabled
+                        .as_deref()
+                        .unwrap_or_default(),
+                )
+            })
+            .flat_map(|enabled| enabled.payment_method_types.unwrap_or_default())
+            .filter_map(|payment_method_type| payment_method_type.amount_range_currency)
+            .filter(|currency| {
+                *currency != self.currency && !self.converted_amounts.contains_key(currency)
+            })
+            .collect::<HashSet<_>>();
+
+        for range_currency in range_currencies {
+            match forex::convert_amount(state, self.amount, self.currency, range_currency).await {
+                Ok(conversion) => {
+                    self.converted_amounts
+                        .insert(range_currency, conversion.converted_amount);
+                }
+                Err(error) => logger::warn!(
+                    ?error,
+                    "Failed to convert the payment amount to {range_currency}, the payment method types with amount ranges in {range_currency} are not available"
+                ),
+            }
+        }
+    }
+
+    /// The amount in the lowest denomination of the currency, `None` if it was not converted to it
+    fn in_currency(&self, currency: api_enums::Currency) -> Option<i64> {
+        if currency == self.currency {
+            Some(self.amount)
+        } else {
+            self.converted_amounts.get(&currency).copied()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ineligibility {
+    pub criterion: EligibilityCriterion,
+    pub reason: String,
+}
+
+fn is_currency_accepted(
+    accepted_currencies: Option<&AcceptedCurrencies>,
+    currency: api_enums::Currency,
+) -> bool {
+    match accepted_currencies {
+        Some(AcceptedCurrencies::EnableOnly(currencies)) => currencies.contains(&currency),
+        Some(AcceptedCurrencies::DisableOnly(currencies)) => !currencies.contains(&currency),
+        Some(AcceptedCurrencies::AllAccepted) | None => true,
+    }
+}
+
+/// Validates the amount of the payment against the amount range configured for the payment method
+/// type, both bounds of the range are inclusive. The amount is not available when the range is
+/// configured in another currency and the amount could not be converted to it, in which case the
+/// payment method type is not available for the payment.
+pub fn check_amount_range(
+    payment_method_type: &RequestPaymentMethodTypes,
+    amount: &PaymentAmount,
+) -> Result<(), String> {
+    if payment_method_type.minimum_amount.is_none() && payment_method_type.maximum_amount.is_none()
+    {
+        return Ok(());
+    }
+
+    let range_currency = payment_method_type
+        .amount_range_currency
+        .unwrap_or(amount.currency);
+    let Some(amount_in_range_currency) = amount.in_currency(range_currency) else {
+        return Err(format!(
+            "the amount cannot be converted to {range_currency}, the currency of the amount range of payment method type {}",
+            payment_method_type.payment_method_type
+        ));
+    };
+
+    let format_amount = |amount: i64| {
+        format!(
+            "{} {range_currency}",
+            range_currency.to_currency_base_unit_exact(amount)
+        )
+    };
+    let described_amount = if range_currency == amount.currency {
+        format_amount(amount.amount)
+    } else {
+        format!(
+            "{} {} ({})",
+            amount.currency.to_currency_base_unit_exact(amount.amount),
+            amount.currency,
+            format_amount(amount_in_range_currency)
+        )
+    };
+
+    match (
+        payment_method_type.minimum_amount.map(i64::from),
+        payment_method_type.maximum_amount.map(i64::from),
+    ) {
+        (Some(minimum_amount), _) if amount_in_range_currency < minimum_amount => Err(format!(
+            "amount {described_amount} is below the minimum amount {} of payment method type {}",
+            format_amount(minimum_amount),
+            payment_method_type.payment_method_type
+        )),
+        (_, Some(maximum_amount)) if amount_in_range_currency > maximum_amount => Err(format!(
+            "amount {described_amount} is above the maximum amount {} of payment method type {}",
+            format_amount(maximum_amount),
+            payment_method_type.payment_method_type
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn is_country_accepted(
+    accepted_countries: Option<&AcceptedCountries>,
+    country: api_enums::CountryAlpha2,
+) -> bool {
+    match accepted_countries {
+        Some(AcceptedCountries::EnableOnly(countries)) => countries.contains(&country),
+        Some(AcceptedCountries::DisableOnly(countries)) => !countries.contains(&country),
+        Some(AcceptedCountries::AllAccepted) | None => true,
+    }
+}
+
+/// Validates the payment against the payment methods enabled for a merchant connector account.
+/// Criteria which are not known for the payment, or not configured for the connector, are not
+/// validated.
+pub fn check_payment_methods_enabled(
+    payment_methods_enabled: Option<&[serde_json::Value]>,
+    data: &PaymentEligibilityData,
+) -> Result<(), Ineligibility> {
+    let (payment_methods_enabled, payment_method) =
+        match (payment_methods_enabled, data.payment_method) {
+            (Some(payment_methods_enabled), Some(payment_method)) => {
+                (payment_methods_enabled, payment_method)
+            }
+            _ => return Ok(()),
+        };
+
+    let enabled_for_payment_method = parse_payment_methods_enabled(payment_methods_enabled)
+        .filter(|enabled| enabled.payment_method == payment_method)
+        .collect::<Vec<_>>();
+
+    if enabled_for_payment_method.is_empty() {
+        return Err(Ineligibility {
+            criterion: EligibilityCriterion::PaymentMethod,
+            reason: format!("payment method {payment_method} is not enabled"),
+        });
+    }
+
+    // Payment method types are not restricted if they are not configured
+    if enabled_for_payment_method
+        .iter()
+        .any(|enabled| enabled.payment_method_types.is_none())
+    {
+        return Ok(());
+    }
+
+    let payment_method_types = enabled_for_payment_method
+        .into_iter()
+        .flat_map(|enabled| enabled.payment_method_types.unwrap_or_default())
+        .filter(|payment_method_type| {
+            data.payment_method_type.map_or(true, |requested| {
+                payment_method_type.payment_method_type == requested
+            })
+        })
+        .collect::<Vec<RequestPaymentMethodTypes>>();
+
+    let payment_method_description = data
+        .payment_method_type
+        .map(|payment_method_type| format!("payment method type {payment_method_type}"))
+        .unwrap_or_else(|| format!("payment method {payment_method}"));
+
+    if payment_method_types.is_empty() {
+        return Err(Ineligibility {
+            criterion: EligibilityCriterion::PaymentMethodType,
+            reason: format!("{payment_method_description} is not enabled"),
+        });
+    }
+
+    let payment_method_types = match data.currency {
+        Some(currency) => {
+            let accepting_currency = payment_method_types
+                .into_iter()
+                .filter(|payment_method_type| {
+                    is_currency_accepted(payment_method_type.accepted_currencies.as_ref(), currency)
+                })
+                .collect::<Vec<_>>();
+            if accepting_currency.is_empty() {
+                return Err(Ineligibility {
+                    criterion: EligibilityCriterion::Currency,
+                    reason: format!(
+                        "currency {currency} is not enabled for {payment_method_description}"
+                    ),
+                });
+            }
+            accepting_currency
+        }
+        None => payment_method_types,
+    };
+
+    let payment_method_types = match data.country {
+        Some(country) => {
+            let accepting_country = payment_method_types
+                .into_iter()
+                .filter(|payment_method_type| {
+                    is_country_accepted(payment_method_type.accepted_countries.as_ref(), country)
+                })
+                .collect::<Vec<_>>();
+            if accepting_country.is_empty() {
+                return Err(Ineligibility {
+                    criterion: EligibilityCriterion::Country,
+                    reason: format!(
+                        "country {country} is not enabled for {payment_method_description}"
+                    ),
+                });
+            }
+            accepting_country
+        }
+        None => payment_method_types,
+    };
+
+    let Some(amount) = data.amount.as_ref() else {
+        return Ok(());
+    };
+    // The payment method type can be enabled more than once, with different amount ranges
+    let mut out_of_range_reason = None;
+    for payment_method_type in &payment_method_types {
+        match check_amount_range(payment_method_type, amount) {
+            Ok(()) => return Ok(()),
+            Err(reason) => {
+                out_of_range_reason.get_or_insert(reason);
+            }
+        }
+    }
+    Err(Ineligibility {
+        criterion: EligibilityCriterion::Amount,
+        reason: out_of_range_reason.unwrap_or_default(),
+    })
+}
+
+pub fn parse_payment_methods_enabled(
+    payment_methods_enabled: &[serde_json::Value],
+) -> impl Iterator<Item = PaymentMethodsEnabled> + '_ {
+    payment_methods_enabled.iter().filter_map(|value| {
+        value
+            .clone()
+            .parse_value::<PaymentMethodsEnabled>("PaymentMethodsEnabled")
+            .map_err(|error| logger::warn!(?error, "Invalid payment_methods_enabled value"))
+            .ok()
+    })
+}
+
+/// Validates the payment against the capabilities declared by the connector, so that a connector
+/// which cannot process the payment is skipped even if the payment method is enabled for it.
+/// Connectors which do not declare their capabilities are not validated.
+pub fn check_connector_capabilities(
+    connector_name: &str,
+    data: &PaymentEligibilityData,
+) -> Result<(), Ineligibility> {
+    let Some(connector_capabilities) = api_enums::Connector::from_str(connector_name)
+        .ok()
+        .and_then(capabilities::get_connector_capabilities)
+    else {
+        return Ok(());
+    };
+
+    match (data.payment_method, data.payment_method_type) {
+        (Some(payment_method), Some(payment_method_type))
+            if !connector_capabilities
+                .supports_payment_method_type(payment_method, payment_method_type) =>
+        {
+            return Err(Ineligibility {
+                criterion: EligibilityCriterion::PaymentMethodType,
+                reason: format!(
+                    "connector does not support payment method type {payment_method_type}"
+                ),
+            });
+        }
+        (Some(payment_method), None)
+            if !connector_capabilities.supports_payment_method(payment_method) =>
+        {
+            return Err(Ineligibility {
+                criterion: EligibilityCriterion::PaymentMethod,
+                reason: format!("connector does not support payment method {payment_method}"),
+            });
+        }
+        _ => (),
+    }
+
+    if let Some(currency) = data
+        .currency
+        .filter(|currency| !connector_capabilities.supports_currency(*currency))
+    {
+        return Err(Ineligibility {
+            criterion: EligibilityCriterion::Currency,
+            reason: format!("connector does not support currency {currency}"),
+        });
+    }
+
+    match data.country {
+        Some(country) if !connector_capabilities.supports_country(country) => Err(Ineligibility {
+            criterion: EligibilityCriterion::Country,
+            reason: format!("connector does not support country {country}"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub fn check_merchant_connector_account(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    data: &PaymentEligibilityData,
+) -> ConnectorEligibility {
+    let result = if merchant_connector_account.disabled.unwrap_or(false) {
+        Err(Ineligibility {
+            criterion: EligibilityCriterion::ConnectorAccount,
+            reason: "merchant connector account is disabled".to_string(),
+        })
+    } else {
+        check_payment_methods_enabled(
+            merchant_connector_account
+                .payment_methods_enabled
+                .as_deref(),
+            data,
+        )
+        .and_then(|()| {
+            check_connector_capabilities(&merchant_connector_account.connector_name, data)
+        })
+    };
+
+    get_connector_eligibility(
+        merchant_connector_account.connector_name.clone(),
+        Some(merchant_connector_account.merchant_connector_id.clone()),
+        result,
+    )
+}
+
+pub fn get_connector_eligibility(
+    connector: String,
+    merchant_connector_id: Option<String>,
+    result: Result<(), Ineligibility>,
+) -> ConnectorEligibility {
+    match result {
+        Ok(()) => ConnectorEligibility {
+            connector,
+            merchant_connector_id,
+            eligible: true,
+            failed_criterion: None,
+            reason: None,
+        },
+        Err(ineligibility) => ConnectorEligibility {
+            connector,
+            merchant_connector_id,
+            eligible: false,
+            failed_criterion: Some(ineligibility.criterion),
+            reason: Some(ineligibility.reason),
+        },
+    }
+}
+
+/// Returns the eligible connectors in the order in which they were passed, fails with the
+/// eligibility of all the connectors if none of them are eligible. When a connector is configured
+/// to accept the payment but for its amount, the payment method is reported as not available for
+/// the amount.
+pub fn filter_eligible_connectors<C>(
+    candidates: Vec<(C, ConnectorEligibility)>,
+) -> RouterResult<Vec<C>> {
+    let (eligible, ineligible): (Vec<_>, Vec<_>) = candidates
+        .into_iter()
+        .partition(|(_, eligibility)| eligibility.eligible);
+
+    if eligible.is_empty() {
+        if let Some(reason) = ineligible.iter().find_map(|(_, eligibility)| {
+            (eligibility.failed_criterion == Some(EligibilityCriterion::Amount))
+                .then(|| eligibility.reason.clone())
+                .flatten()
+        }) {
+            return Err(errors::ApiErrorResponse::PaymentMethodNotAvailableForAmount { reason })
+                .into_report();
+        }
+        return Err(errors::ApiErrorResponse::NoEligibleConnector {
+            connectors: ineligible
+                .into_iter()
+                .map(|(_, eligibility)| eligibility)
+                .collect(),
+        })
+        .into_report();
+    }
+
+    Ok(eligible
+        .into_iter()
+        .map(|(connector, _)| connector)
+        .collect())
+}
+
+/// The merchant connector account of the connector which would process the payment, the payment is
+/// validated against it
+async fn find_merchant_connector_account(
+    state: &AppState,
+    key_store: &domain::MerchantKeyStore,
+    connector_name: &str,
+    data: &PaymentEligibilityData,
+) -> RouterResult<Option<domain::MerchantConnectorAccount>> {
+    connector_account_selection::find_merchant_connector_account(
+        &*state.store,
+        key_store,
+        &data.profile_id,
+        connector_name,
+        &connector_account_selection::PaymentMethodDetails {
+            payment_method: data.payment_method,
+            payment_method_type: data.payment_method_type,
+        },
+    )
+    .await
+    .map(|selected| selected.map(|(merchant_connector_account, _)| merchant_connector_account))
+}
+
+fn check_connector(
+    connector_name: &str,
+    merchant_connector_account: Option<&domain::MerchantConnectorAccount>,
+    data: &PaymentEligibilityData,
+) -> ConnectorEligibility {
+    match merchant_connector_account {
+        Some(merchant_connector_account) => {
+            check_merchant_connector_account(merchant_connector_account, data)
+        }
+        None => get_connector_eligibility(
+            connector_name.to_string(),
+            None,
+            Err(Ineligibility {
+                criterion: EligibilityCriterion::ConnectorAccount,
+                reason: format!(
+                    "no merchant connector account found for business profile {}",
+                    data.profile_id
+                ),
+            }),
+        ),
+    }
+}
+
+/// Picks the connector to route the payment to, from the connector chosen by the routing algorithm
+/// and the fallback connectors, skipping the connectors which are not configured to accept the payment
+#[instrument(skip_all)]
+pub async fn get_eligible_connector(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    primary: api_enums::RoutableConnectors,
+    data: &PaymentEligibilityData,
+) -> RouterResult<circuit_breaker::AvailableConnector> {
+    let mut connectors = vec![primary];
+    for connector in
+        circuit_breaker::get_routing_fallback_connectors(state, &merchant_account.merchant_id).await
+    {
+        if !connectors.contains(&connector) {
+            connectors.push(connector);
+        }
+    }
+
+    let mut merchant_connector_accounts = Vec::with_capacity(connectors.len());
+    for connector in &connectors {
+        let merchant_connector_account =
+            find_merchant_connector_account(state, key_store, &connector.to_string(), data).await?;
+        merchant_connector_accounts.push((*connector, merchant_connector_account));
+    }
+
+    let data = data
+        .clone()
+        .with_converted_amount(
+            state,
+            merchant_connector_accounts
+                .iter()
+                .filter_map(|(_, merchant_connector_account)| merchant_connector_account.as_ref()),
+        )
+        .await;
+    let candidates: Vec<_> = merchant_connector_accounts
+        .iter()
+        .map(|(connector, merchant_connector_account)| {
+            let eligibility = check_connector(
+                &connector.to_string(),
+                merchant_connector_account.as_ref(),
+                &data,
+            );
+            if !eligibility.eligible {
+                logger::info!(?eligibility, "Skipping connector which is not eligible");
+            }
+            (*connector, eligibility)
+        })
+        .collect();
+
+    let ineligible_connectors: Vec<_> = candidates
+        .iter()
+        .filter(|(_, eligibility)| !eligibility.eligible)
+        .map(|(connector, _)| *connector)
+        .collect();
+    let mut eligible_connectors = filter_eligible_connectors(candidates)?.into_iter();
+    let connector = eligible_connectors
+        .next()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()?;
+
+    let mut available_connector = circuit_breaker::choose_available_connector(
+        state,
+        &merchant_account.merchant_id,
+        connector,
+        eligible_connectors.collect(),
+        &circuit_breaker::get_flow_name::<api::Authorize>(),
+    )
+    .await;
+    available_connector.skipped_connectors = get_skipped_connectors(
+        &connectors,
+        &ineligible_connectors,
+        available_connector.connector,
+        available_connector.skipped_connectors,
+    );
+
+    Ok(available_connector)
+}
+
+/// The connectors preferred over the connector the payment is routed to, which were skipped
+/// either because they are not eligible for the payment or because they are unavailable
+fn get_skipped_connectors(
+    connectors: &[api_enums::RoutableConnectors],
+    ineligible_connectors: &[api_enums::RoutableConnectors],
+    routed_connector: api_enums::RoutableConnectors,
+    unavailable_connectors: Vec<circuit_breaker::SkippedConnector>,
+) -> Vec<circuit_breaker::SkippedConnector> {
+    connectors
+        .iter()
+        .take_while(|connector| **connector != routed_connector)
+        .filter_map(|connector| {
+            if ineligible_connectors.contains(connector) {
+                Some(circuit_breaker::SkippedConnector {
+                    connector: *connector,
+                    reason: api_enums::RoutingFallbackReason::Ineligible,
+                })
+            } else {
+                unavailable_connectors
+                    .iter()
+                    .find(|unavailable| unavailable.connector == *connector)
+                    .cloned()
+            }
+        })
+        .collect()
+}
+
+/// The connectors which the payment can be routed to, which are the connectors of the merchant
+/// connector accounts of its business profile that are configured to accept it
+#[instrument(skip_all)]
+pub async fn get_candidate_connectors(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    data: &PaymentEligibilityData,
+) -> RouterResult<Vec<api_enums::Connector>> {
+    let merchant_connector_accounts = state
+        .store
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_account.merchant_id,
+            true,
+            key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
+
+    let mut connectors = Vec::new();
+    for merchant_connector_account in merchant_connector_accounts.iter().filter(|account| {
+        account.profile_id.as_ref() == Some(&data.profile_id)
+            && account.connector_type == api_enums::ConnectorType::PaymentProcessor
+    }) {
+        if !check_merchant_connector_account(merchant_connector_account, data).eligible {
+            continue;
+        }
+        match api_enums::Connector::from_str(&merchant_connector_account.connector_name) {
+            Ok(connector) if !connectors.contains(&connector) => connectors.push(connector),
+            Ok(_) => (),
+            Err(error) => logger::warn!(
+                ?error,
+                connector = %merchant_connector_account.connector_name,
+                "Skipping merchant connector account with an unknown connector"
+            ),
+        }
+    }
+
+    Ok(connectors)
+}
+
+/// Whether the currency is accepted by any of the payment method types enabled for the merchant
+/// connector account, payment method types which are not configured accept every currency
+fn is_currency_enabled(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    currency: api_enums::Currency,
+) -> bool {
+    let Some(payment_methods_enabled) = merchant_connector_account
+        .payment_methods_enabled
+        .as_deref()
+    else {
+        return true;
+    };
+
+    parse_payment_methods_enabled(payment_methods_enabled).any(|enabled| {
+        enabled
+            .payment_method_types
+            .as_ref()
+            .map_or(true, |payment_method_types| {
+                payment_method_types.iter().any(|payment_method_type| {
+                    is_currency_accepted(payment_method_type.accepted_currencies.as_ref(), currency)
+                })
+            })
+    })
+}
+
+/// Validates that the currency is enabled on at least one of the connector accounts of the
+/// business profile, so that a payment is not created in a default currency which none of its
+/// connectors can process
+#[instrument(skip_all)]
+pub async fn validate_currency_enabled(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &str,
+    currency: api_enums::Currency,
+) -> RouterResult<()> {
+    let merchant_connector_accounts = state
+        .store
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_account.merchant_id,
+            true,
+            key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
+
+    let data = PaymentEligibilityData {
+        profile_id: profile_id.to_string(),
+        currency: Some(currency),
+        ..Default::default()
+    };
+    let is_enabled = merchant_connector_accounts.iter().any(|account| {
+        account.profile_id.as_deref() == Some(profile_id)
+            && account.connector_type == api_enums::ConnectorType::PaymentProcessor
+            && check_merchant_connector_account(account, &data).eligible
+            && is_currency_enabled(account, currency)
+    });
+
+    if !is_enabled {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "currency {currency} is not enabled on any of the connector accounts of the business profile"
+            ),
+        }));
+    }
+    Ok(())
+}
+
+/// Validates the payment against each of the connectors configured for its business profile
+pub async fn list_eligible_connectors(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+) -> RouterResponse<api_models::payments::EligibleConnectorsResponse> {
+    let db = state.store.as_ref();
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let billing_address = helpers::get_address_by_id(
+        db,
+        payment_intent.billing_address_id.clone(),
+        &key_store,
+        payment_intent.payment_id.clone(),
+        merchant_account.merchant_id.clone(),
+        merchant_account.storage_scheme,
+    )
+    .await?;
+
+    let profile_id = payment_intent
+        .profile_id
+        .clone()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("profile_id is not set in payment_intent")?;
+
+    let currency = payment_attempt.currency.or(payment_intent.currency);
+    let data = PaymentEligibilityData {
+        profile_id,
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        currency,
+        country: billing_address.and_then(|address| address.country),
+        amount: currency.map(|currency| PaymentAmount::new(payment_attempt.amount, currency)),
+    };
+
+    let merchant_connector_accounts = db
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_account.merchant_id,
+            true,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?
+        .into_iter()
+        .filter(|merchant_connector_account| {
+            merchant_connector_account.profile_id.as_ref() == Some(&data.profile_id)
+                && merchant_connector_account.connector_type
+                    == api_enums::ConnectorType::PaymentProcessor
+        })
+        .collect::<Vec<_>>();
+    let data = data
+        .with_converted_amount(&state, merchant_connector_accounts.iter())
+        .await;
+
+    let connectors = merchant_connector_accounts
+        .iter()
+        .map(|merchant_connector_account| {
+            check_merchant_connector_account(merchant_connector_account, &data)
+        })
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        api_models::payments::EligibleConnectorsResponse {
+            payment_id: payment_intent.payment_id,
+            connectors,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn payment_methods_enabled() -> Vec<serde_json::Value> {
+        vec![serde_json::json!({
+            "payment_method": "card",
+            "payment_method_types": [
+                {
+                    "payment_method_type": "credit",
+                    "accepted_currencies": {
+                        "type": "enable_only",
+                        "list": ["USD"]
+                    },
+                    "accepted_countries": {
+                        "type": "disable_only",
+                        "list": ["IN"]
+                    },
+                    "minimum_amount": 1,
+                    "maximum_amount": 68607706,
+                    "recurring_enabled": true,
+                    "installment_payment_enabled": true
+                }
+            ]
+        })]
+    }
+
+    fn eligibility_data(currency: api_enums::Currency) -> PaymentEligibilityData {
+        PaymentEligibilityData {
+            profile_id: "pro_abcdefghijklmnop".to_string(),
+            payment_method: Some(api_enums::PaymentMethod::Card),
+            payment_method_type: Some(api_enums::PaymentMethodType::Credit),
+            currency: Some(currency),
+            country: Some(api_enums::CountryAlpha2::US),
+            amount: None,
+        }
+    }
+
+    #[test]
+    fn test_check_payment_methods_enabled() {
+        let payment_methods_enabled = payment_methods_enabled();
+        let data = eligibility_data(api_enums::Currency::USD);
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data),
+            Ok(())
+        );
+
+        let data = PaymentEligibilityData {
+            country: Some(api_enums::CountryAlpha2::IN),
+            ..eligibility_data(api_enums::Currency::USD)
+        };
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err()
+                .criterion,
+            EligibilityCriterion::Country
+        );
+
+        let data = PaymentEligibilityData {
+            payment_method_type: Some(api_enums::PaymentMethodType::Debit),
+            ..eligibility_data(api_enums::Currency::USD)
+        };
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err()
+                .criterion,
+            EligibilityCriterion::PaymentMethodType
+        );
+
+        let data = PaymentEligibilityData {
+            payment_method: Some(api_enums::PaymentMethod::Wallet),
+            payment_method_type: Some(api_enums::PaymentMethodType::ApplePay),
+            ..eligibility_data(api_enums::Currency::USD)
+        };
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err()
+                .criterion,
+            EligibilityCriterion::PaymentMethod
+        );
+
+        // Connectors without any payment methods configured are not restricted
+        assert_eq!(check_payment_methods_enabled(None, &data), Ok(()));
+    }
+
+    fn klarna_payment_methods_enabled() -> Vec<serde_json::Value> {
+        vec![serde_json::json!({
+            "payment_method": "pay_later",
+            "payment_method_types": [
+                {
+                    "payment_method_type": "klarna",
+                    "minimum_amount": 3500,
+                    "maximum_amount": 100000,
+                    "amount_range_currency": "EUR",
+                    "recurring_enabled": false,
+                    "installment_payment_enabled": true
+                }
+            ]
+        })]
+    }
+
+    fn klarna_eligibility_data(amount: PaymentAmount) -> PaymentEligibilityData {
+        PaymentEligibilityData {
+            payment_method: Some(api_enums::PaymentMethod::PayLater),
+            payment_method_type: Some(api_enums::PaymentMethodType::Klarna),
+            currency: Some(amount.currency),
+            amount: Some(amount),
+            ..eligibility_data(api_enums::Currency::EUR)
+        }
+    }
+
+    #[test]
+    fn test_payment_outside_amount_range_is_rejected() {
+        let payment_methods_enabled = klarna_payment_methods_enabled();
+
+        let data = klarna_eligibility_data(PaymentAmount::new(2000, api_enums::Currency::EUR));
+        let ineligibility =
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err();
+        assert_eq!(ineligibility.criterion, EligibilityCriterion::Amount);
+        assert_eq!(
+            ineligibility.reason,
+            "amount 20.00 EUR is below the minimum amount 35.00 EUR of payment method type klarna"
+        );
+
+        let candidates = vec![(
+            "klarna",
+            get_connector_eligibility(
+                "klarna".to_string(),
+                Some("mca_klarna".to_string()),
+                Err(ineligibility),
+            ),
+        )];
+        let error = filter_eligible_connectors(candidates).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::PaymentMethodNotAvailableForAmount { reason }
+                if reason.starts_with("amount 20.00 EUR is below the minimum amount")
+        ));
+
+        let data = klarna_eligibility_data(PaymentAmount::new(5000, api_enums::Currency::EUR));
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_amount_range_in_another_currency() {
+        let payment_methods_enabled = klarna_payment_methods_enabled();
+        let get_amount = |converted_amounts: HashMap<api_enums::Currency, i64>| PaymentAmount {
+            amount: 2000,
+            currency: api_enums::Currency::USD,
+            converted_amounts,
+        };
+
+        // 20.00 USD converted to 18.40 EUR
+        let data = klarna_eligibility_data(get_amount(HashMap::from([(
+            api_enums::Currency::EUR,
+            1840,
+        )])));
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err()
+                .reason,
+            "amount 20.00 USD (18.40 EUR) is below the minimum amount 35.00 EUR of payment method type klarna"
+        );
+
+        let data = klarna_eligibility_data(PaymentAmount {
+            amount: 5000,
+            ..get_amount(HashMap::from([(api_enums::Currency::EUR, 4600)]))
+        });
+        assert_eq!(
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data),
+            Ok(())
+        );
+
+        // The range cannot be validated without exchange rates, the payment method type is not
+        // available rather than accepting amounts outside of the range
+        let data = klarna_eligibility_data(get_amount(HashMap::new()));
+        let ineligibility =
+            check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data)
+                .unwrap_err();
+        assert_eq!(ineligibility.criterion, EligibilityCriterion::Amount);
+        assert!(ineligibility
+            .reason
+            .starts_with("the amount cannot be converted to EUR"));
+    }
+
+    #[test]
+    fn test_currency_mismatch_skips_to_next_connector() {
+        let data = eligibility_data(api_enums::Currency::EUR);
+        let payment_methods_enabled = payment_methods_enabled();
+
+        let candidates = vec![
+            (
+                "stripe",
+                get_connector_eligibility(
+                    "stripe".to_string(),
+                    Some("mca_stripe".to_string()),
+                    check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data),
+                ),
+            ),
+            (
+                "adyen",
+                get_connector_eligibility(
+                    "adyen".to_string(),
+                    Some("mca_adyen".to_string()),
+                    check_payment_methods_enabled(None, &data),
+                ),
+            ),
+        ];
+
+        assert_eq!(
+            filter_eligible_connectors(candidates).unwrap(),
+            vec!["adyen"]
+        );
+    }
+
+    #[test]
+    fn test_connector_without_capability_is_filtered_from_routing() {
+        let data = PaymentEligibilityData {
+            payment_method: Some(api_enums::PaymentMethod::BankTransfer),
+            payment_method_type: Some(api_enums::PaymentMethodType::Pix),
+            currency: Some(api_enums::Currency::BRL),
+            country: Some(api_enums::CountryAlpha2::BR),
+            ..eligibility_data(api_enums::Currency::BRL)
+        };
+
+        // The payment method is enabled for both connectors, but only adyen can process it
+        let candidates = ["authorizedotnet", "adyen"]
+            .into_iter()
+            .map(|connector| {
+                (
+                    connector,
+                    get_connector_eligibility(
+                        connector.to_string(),
+                        Some(format!("mca_{connector}")),
+                        check_payment_methods_enabled(None, &data)
+                            .and_then(|()| check_connector_capabilities(connector, &data)),
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            candidates[0].1.failed_criterion,
+            Some(EligibilityCriterion::PaymentMethodType)
+        );
+        assert_eq!(
+            filter_eligible_connectors(candidates).unwrap(),
+            vec!["adyen"]
+        );
+
+        let data = PaymentEligibilityData {
+            currency: Some(api_enums::Currency::BRL),
+            ..eligibility_data(api_enums::Currency::BRL)
+        };
+        assert_eq!(
+            check_connector_capabilities("authorizedotnet", &data)
+                .unwrap_err()
+                .criterion,
+            EligibilityCriterion::Currency
+        );
+        // Connectors which do not declare their capabilities are not filtered
+        assert_eq!(check_connector_capabilities("nmi", &data), Ok(()));
+    }
+
+    #[test]
+    fn test_all_connectors_ineligible() {
+        let data = eligibility_data(api_enums::Currency::EUR);
+        let payment_methods_enabled = payment_methods_enabled();
+
+        let candidates = vec![
+            (
+                "stripe",
+                get_connector_eligibility(
+                    "stripe".to_string(),
+                    Some("mca_stripe".to_string()),
+                    check_payment_methods_enabled(Some(payment_methods_enabled.as_slice()), &data),
+                ),
+            ),
+            (
+                "adyen",
+                get_connector_eligibility(
+                    "adyen".to_string(),
+                    None,
+                    Err(Ineligibility {
+                        criterion: EligibilityCriterion::ConnectorAccount,
+                        reason: "no merchant connector account found".to_string(),
+                    }),
+                ),
+            ),
+        ];
+
+        let error = filter_eligible_connectors(candidates).unwrap_err();
+        let connectors = match error.current_context() {
+            errors::ApiErrorResponse::NoEligibleConnector { connectors } => connectors.clone(),
+            _ => vec![],
+        };
+        assert_eq!(connectors.len(), 2);
+        assert_eq!(connectors[0].connector, "stripe");
+        assert_eq!(
+            connectors[0].failed_criterion,
+            Some(EligibilityCriterion::Currency)
+        );
+        assert_eq!(
+            connectors[0].reason.as_deref(),
+            Some("currency EUR is not enabled for payment method type credit")
+        );
+        assert_eq!(
+            connectors[1].failed_criterion,
+            Some(EligibilityCriterion::ConnectorAccount)
+        );
+    }
+
+    #[test]
+    fn test_ineligible_and_unavailable_connectors_preferred_over_the_routed_one_are_skipped() {
+        let connectors = [
+            api_enums::RoutableConnectors::Stripe,
+            api_enums::RoutableConnectors::Adyen,
+            api_enums::RoutableConnectors::Checkout,
+            api_enums::RoutableConnectors::Cybersource,
+        ];
+        let unavailable_connectors = vec![circuit_breaker::SkippedConnector {
+            connector: api_enums::RoutableConnectors::Adyen,
+            reason: api_enums::RoutingFallbackReason::CircuitOpen,
+        }];
+
+        let skipped_connectors = get_skipped_connectors(
+            &connectors,
+            &[
+                api_enums::RoutableConnectors::Stripe,
+                api_enums::RoutableConnectors::Cybersource,
+            ],
+            api_enums::RoutableConnectors::Checkout,
+            unavailable_connectors,
+        );
+
+        assert_eq!(
+            skipped_connectors,
+            vec![
+                circuit_breaker::SkippedConnector {
+                    connector: api_enums::RoutableConnectors::Stripe,
+                    reason: api_enums::RoutingFallbackReason::Ineligible,
+                },
+                circuit_breaker::SkippedConnector {
+                    connector: api_enums::RoutableConnectors::Adyen,
+                    reason: api_enums::RoutingFallbackReason::CircuitOpen,
+                },
+            ]
+        );
+    }
+}
@@ -1524,3 +1524,111 @@ default_imp_for_reject!(
     connector::Worldpay,
     connector::Zen
 );
+
+macro_rules! default_imp_for_webhook_endpoint {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::ConnectorWebhookEndpoint for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::CreateWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > for $path::$connector
+        {}
+            impl
+            services::ConnectorIntegration<
+            api::UpdateWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > for $path::$connector
+        {}
+            impl
+            services::ConnectorIntegration<
+            api::DeleteWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > for $path::$connector
+        {}
+    )*
+    };
+}
+
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::ConnectorWebhookEndpoint for connector::DummyConnector<T> {}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::CreateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for connector::DummyConnector<T>
+{
+}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::UpdateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for connector::DummyConnector<T>
+{
+}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::DeleteWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > for connector::DummyConnector<T>
+{
+}
+
+default_imp_for_webhook_endpoint!(
+    connector::Aci,
+    connector::Adyen,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Gocardless,
+    connector::Helcim,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
@@ -3,9 +3,10 @@ use error_stack;
 
 use super::{ConstructFlowSpecificData, Feature};
 use crate::{
+    connector::utils::PaymentsAuthorizeRequestData,
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
-        mandate,
+        mandate, payment_timeline,
         payments::{
             self, access_token, customers, helpers, tokenization, transformers, PaymentData,
         },
@@ -77,7 +78,19 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
         if self.should_proceed_with_authorize() {
             self.decide_authentication_type();
             logger::debug!(auth_type=?self.auth_type);
-            let resp = services::execute_connector_processing_step(
+            payment_timeline::record_connector_call_started(
+                &*state.store,
+                &self,
+                &call_connector_action,
+            )
+            .await;
+            let mandate_charge_reservation = match call_connector_action {
+                payments::CallConnectorAction::Trigger => {
+                    mandate::reserve_mandate_charge(state, &self).await?
+                }
+                _ => None,
+            };
+            let resp = match services::execute_connector_processing_step(
                 state,
                 connector_integration,
                 &self,
@@ -85,7 +98,28 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
                 connector_request,
             )
             .await
-            .to_payment_failed_response()?;
+            .to_payment_failed_response()
+            {
+                Ok(resp) => resp,
+                Err(error) => {
+                    mandate::release_mandate_charge(
+                        state,
+                        &self.merchant_id,
+                        mandate_charge_reservation.as_ref(),
+                    )
+                    .await;
+                    return Err(error);
+                }
+            };
+            if resp.response.is_err() {
+                mandate::release_mandate_charge(
+                    state,
+                    &resp.merchant_id,
+                    mandate_charge_reservation.as_ref(),
+                )
+                .await;
+            }
+            payment_timeline::record_confirm_response_steps(&*state.store, &resp).await;
 
             metrics::PAYMENT_COUNT.add(&metrics::CONTEXT, 1, &[]); // Metrics
 
@@ -220,9 +254,14 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
 }
 
 impl types::PaymentsAuthorizeRouterData {
+    /// 3DS is skipped at the connector if the card is not enrolled for it, if the merchant has
+    /// already authenticated the customer outside of the router, or if the customer is not present
+    /// in a mail order or telephone order payment
     fn decide_authentication_type(&mut self) {
         if self.auth_type == diesel_models::enums::AuthenticationType::ThreeDs
-            && !self.request.enrolled_for_3ds
+            && (!self.request.enrolled_for_3ds
+                || self.request.external_authentication_data.is_some()
+                || self.request.is_moto())
         {
             self.auth_type = diesel_models::enums::AuthenticationType::NoThreeDs
         }
@@ -383,3 +422,30 @@ impl TryFrom<types::PaymentsAuthorizeData> for types::PaymentsPreProcessingData
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use masking::Secret;
+
+    use crate::types::{storage::enums, test_utils::get_authorize_router_data};
+
+    #[test]
+    fn test_external_authentication_data_skips_three_ds_redirection() {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.decide_authentication_type();
+        assert_eq!(router_data.auth_type, enums::AuthenticationType::ThreeDs);
+
+        // The customer was authenticated by the merchant, the connector is not asked for a 3DS
+        // challenge which would redirect the customer
+        let mut router_data =
+            get_authorize_router_data(Some(api_models::payments::ExternalAuthenticationData {
+                cavv: Secret::new("AAABBEg0VhI0VniQEjRWAAAAAAA=".to_string()),
+                eci: "05".to_string(),
+                ds_transaction_id: None,
+                message_version: "2.2.0".to_string(),
+                threeds_server_transaction_id: None,
+            }));
+        router_data.decide_authentication_type();
+        assert_eq!(router_data.auth_type, enums::AuthenticationType::NoThreeDs);
+    }
+}
@@ -24,6 +24,11 @@ impl ConstructFlowSpecificData<api::Void, types::PaymentsCancelData, types::Paym
         customer: &Option<domain::Customer>,
         merchant_connector_account: &helpers::MerchantConnectorAccountType,
     ) -> RouterResult<types::PaymentsCancelRouterData> {
+        helpers::validate_connector_flow_enabled(
+            merchant_connector_account,
+            connector_id,
+            api::enums::ConnectorFlow::Void,
+        )?;
         transformers::construct_payment_router_data::<api::Void, types::PaymentsCancelData>(
             state,
             self.clone(),
@@ -25,6 +25,11 @@ impl
         customer: &Option<domain::Customer>,
         merchant_connector_account: &helpers::MerchantConnectorAccountType,
     ) -> RouterResult<types::PaymentsCaptureRouterData> {
+        helpers::validate_connector_flow_enabled(
+            merchant_connector_account,
+            connector_id,
+            api::enums::ConnectorFlow::Capture,
+        )?;
         transformers::construct_payment_router_data::<api::Capture, types::PaymentsCaptureData>(
             state,
             self.clone(),
@@ -4,6 +4,7 @@ use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
+        payment_timeline,
         payments::{self, access_token, helpers, transformers, PaymentData},
     },
     routes::AppState,
@@ -76,6 +77,12 @@ impl Feature<api::CompleteAuthorize, types::CompleteAuthorizeData>
             types::PaymentsResponseData,
         > = connector.connector.get_connector_integration();
 
+        payment_timeline::record_connector_call_started(
+            &*state.store,
+            &self,
+            &call_connector_action,
+        )
+        .await;
         let resp = services::execute_connector_processing_step(
             state,
             connector_integration,
@@ -85,6 +92,7 @@ impl Feature<api::CompleteAuthorize, types::CompleteAuthorizeData>
         )
         .await
         .to_payment_failed_response()?;
+        payment_timeline::record_confirm_response_steps(&*state.store, &resp).await;
 
         Ok(resp)
     }
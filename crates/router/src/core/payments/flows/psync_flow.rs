@@ -6,6 +6,7 @@ use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ApiErrorResponse, ConnectorErrorExt, RouterResult},
+        payment_timeline,
         payments::{self, access_token, helpers, transformers, PaymentData},
     },
     routes::AppState,
@@ -93,6 +94,7 @@ impl Feature<api::PSync, types::PaymentsSyncData>
                 )
                 .await
                 .to_payment_failed_response()?;
+                payment_timeline::record_sync_response_steps(&*state.store, &resp).await;
                 Ok(resp)
             }
         }
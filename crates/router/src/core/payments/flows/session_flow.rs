@@ -1,12 +1,14 @@
 use api_models::payments as payment_types;
 use async_trait::async_trait;
 use common_utils::ext_traits::ByteSliceExt;
-use error_stack::{IntoReport, Report, ResultExt};
+use error_stack::{report, IntoReport, Report, ResultExt};
 #[cfg(feature = "kms")]
 use external_services::kms;
+use masking::PeekInterface;
 
 use super::{ConstructFlowSpecificData, Feature};
 use crate::{
+    connector::utils::wallet_session_config,
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
         payments::{self, access_token, helpers, transformers, PaymentData},
@@ -32,7 +34,10 @@ impl
         customer: &Option<domain::Customer>,
         merchant_connector_account: &helpers::MerchantConnectorAccountType,
     ) -> RouterResult<types::PaymentsSessionRouterData> {
-        transformers::construct_payment_router_data::<api::Session, types::PaymentsSessionData>(
+        let mut router_data = transformers::construct_payment_router_data::<
+            api::Session,
+            types::PaymentsSessionData,
+        >(
             state,
             self.clone(),
             connector_id,
@@ -41,7 +46,9 @@ impl
             customer,
             merchant_connector_account,
         )
-        .await
+        .await?;
+        router_data.request.merchant_connector_id = merchant_connector_account.get_mca_id();
+        Ok(router_data)
     }
 }
 
@@ -85,15 +92,60 @@ impl Feature<api::Session, types::PaymentsSessionData> for types::PaymentsSessio
     }
 }
 
-fn get_applepay_metadata(
-    connector_metadata: Option<common_utils::pii::SecretSerdeValue>,
-) -> RouterResult<payment_types::ApplepaySessionTokenData> {
-    connector_metadata
-        .parse_value::<payment_types::ApplepaySessionTokenData>("ApplepaySessionTokenData")
-        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-            field_name: "connector_metadata".to_string(),
-            expected_format: "applepay_metadata_format".to_string(),
-        })
+/// Error naming the key which is missing in the wallet configuration and the merchant connector
+/// account holding it, or the connector when the account details were passed in the request
+fn wallet_configuration_error(
+    session_data: &types::PaymentsSessionData,
+    connector_name: &str,
+    error: wallet_session_config::WalletConfigError,
+) -> Report<errors::ApiErrorResponse> {
+    let merchant_connector_account = session_data
+        .merchant_connector_id
+        .clone()
+        .unwrap_or_else(|| connector_name.to_string());
+    logger::error!(
+        %merchant_connector_account,
+        wallet = error.wallet,
+        key = %error.key,
+        "Invalid wallet configuration in the merchant connector account metadata"
+    );
+    report!(errors::ApiErrorResponse::InvalidWalletConfiguration {
+        wallet: error.wallet.to_string(),
+        key: error.key,
+        merchant_connector_account,
+    })
+}
+
+fn get_apple_pay_session_config(
+    router_data: &types::PaymentsSessionRouterData,
+    connector: &api::ConnectorData,
+) -> RouterResult<wallet_session_config::ApplePaySessionConfig> {
+    wallet_session_config::ApplePaySessionConfig::from_metadata(
+        connector.connector_name,
+        router_data
+            .connector_meta_data
+            .as_ref()
+            .map(|metadata| metadata.peek()),
+    )
+    .map_err(|error| {
+        wallet_configuration_error(&router_data.request, &router_data.connector, error)
+    })
+}
+
+fn get_google_pay_session_config(
+    router_data: &types::PaymentsSessionRouterData,
+    connector: &api::ConnectorData,
+) -> RouterResult<wallet_session_config::GooglePaySessionConfig> {
+    wallet_session_config::GooglePaySessionConfig::from_metadata(
+        connector.connector_name,
+        router_data
+            .connector_meta_data
+            .as_ref()
+            .map(|metadata| metadata.peek()),
+    )
+    .map_err(|error| {
+        wallet_configuration_error(&router_data.request, &router_data.connector, error)
+    })
 }
 
 fn build_apple_pay_session_request(
@@ -145,22 +197,13 @@ async fn create_applepay_session_token(
             payment_types::NextActionCall::Confirm,
         )
     } else {
-        // Get the apple pay metadata
-        let apple_pay_metadata = get_applepay_metadata(router_data.connector_meta_data.clone())?;
+        let apple_pay_config = get_apple_pay_session_config(router_data, connector)?;
 
-        // Get payment request data , apple pay session request and merchant keys
-        let (
-            payment_request_data,
-            apple_pay_session_request,
-            apple_pay_merchant_cert,
-            apple_pay_merchant_cert_key,
-        ) = match apple_pay_metadata.data {
-            payment_types::ApplepaySessionTokenMetadata::ApplePayCombined(
-                apple_pay_combined_metadata,
-            ) => match apple_pay_combined_metadata {
-                payment_types::ApplePayCombinedMetadata::Simplified {
-                    payment_request_data,
-                    session_token_data,
+        // Get apple pay session request and merchant keys
+        let (apple_pay_session_request, apple_pay_merchant_cert, apple_pay_merchant_cert_key) =
+            match apple_pay_config.credentials.clone() {
+                wallet_session_config::ApplePaySessionCredentials::Simplified {
+                    initiative_context,
                 } => {
                     #[cfg(feature = "kms")]
                     let decrypted_apple_pay_merchant_cert = kms::get_kms_client(&state.conf.kms)
@@ -202,7 +245,7 @@ async fn create_applepay_session_token(
 
                     let apple_pay_session_request = get_session_request_for_simplified_apple_pay(
                         decrypted_merchant_identifier.to_string(),
-                        session_token_data,
+                        initiative_context,
                     );
 
                     #[cfg(not(feature = "kms"))]
@@ -214,49 +257,32 @@ async fn create_applepay_session_token(
                         &state.conf.applepay_decrypt_keys.apple_pay_merchant_cert_key;
 
                     (
-                        payment_request_data,
                         apple_pay_session_request,
                         decrypted_apple_pay_merchant_cert.to_owned(),
                         decrypted_apple_pay_merchant_cert_key.to_owned(),
                     )
                 }
-                payment_types::ApplePayCombinedMetadata::Manual {
-                    payment_request_data,
-                    session_token_data,
-                } => {
+                wallet_session_config::ApplePaySessionCredentials::Manual(session_token_data) => {
                     let apple_pay_session_request =
                         get_session_request_for_manual_apple_pay(session_token_data.clone());
                     (
-                        payment_request_data,
                         apple_pay_session_request,
-                        session_token_data.certificate.clone(),
+                        session_token_data.certificate,
                         session_token_data.certificate_keys,
                     )
                 }
-            },
-            payment_types::ApplepaySessionTokenMetadata::ApplePay(apple_pay_metadata) => {
-                let apple_pay_session_request = get_session_request_for_manual_apple_pay(
-                    apple_pay_metadata.session_token_data.clone(),
-                );
-                (
-                    apple_pay_metadata.payment_request_data,
-                    apple_pay_session_request,
-                    apple_pay_metadata.session_token_data.certificate.clone(),
-                    apple_pay_metadata.session_token_data.certificate_keys,
-                )
-            }
-        };
+            };
 
         // Get amount info for apple pay
         let amount_info = get_apple_pay_amount_info(
-            payment_request_data.label.as_str(),
+            apple_pay_config.label.as_str(),
             router_data.request.to_owned(),
         )?;
 
         // Get apple pay payment request
         let applepay_payment_request = get_apple_pay_payment_request(
             amount_info,
-            payment_request_data,
+            apple_pay_config,
             router_data.request.to_owned(),
             apple_pay_session_request.merchant_identifier.as_str(),
         )?;
@@ -309,13 +335,13 @@ async fn create_applepay_session_token(
 
 fn get_session_request_for_simplified_apple_pay(
     apple_pay_merchant_identifier: String,
-    session_token_data: payment_types::SessionTokenForSimplifiedApplePay,
+    initiative_context: String,
 ) -> payment_types::ApplepaySessionRequest {
     payment_types::ApplepaySessionRequest {
         merchant_identifier: apple_pay_merchant_identifier,
         display_name: "Apple pay".to_string(),
         initiative: "web".to_string(),
-        initiative_context: session_token_data.initiative_context,
+        initiative_context,
     }
 }
 
@@ -351,7 +377,7 @@ fn get_apple_pay_amount_info(
 
 fn get_apple_pay_payment_request(
     amount_info: payment_types::AmountInfo,
-    payment_request_data: payment_types::PaymentRequestMetadata,
+    apple_pay_config: wallet_session_config::ApplePaySessionConfig,
     session_data: types::PaymentsSessionData,
     merchant_identifier: &str,
 ) -> RouterResult<payment_types::ApplePayPaymentRequest> {
@@ -365,8 +391,8 @@ fn get_apple_pay_payment_request(
             })?,
         currency_code: session_data.currency,
         total: amount_info,
-        merchant_capabilities: Some(payment_request_data.merchant_capabilities),
-        supported_networks: Some(payment_request_data.supported_networks),
+        merchant_capabilities: Some(apple_pay_config.merchant_capabilities),
+        supported_networks: Some(apple_pay_config.supported_networks),
         merchant_identifier: Some(merchant_identifier.to_string()),
     };
     Ok(applepay_payment_request)
@@ -412,7 +438,6 @@ fn create_gpay_session_token(
     router_data: &types::PaymentsSessionRouterData,
     connector: &api::ConnectorData,
 ) -> RouterResult<types::PaymentsSessionRouterData> {
-    let connector_metadata = router_data.connector_meta_data.clone();
     let delayed_response = is_session_response_delayed(state, connector);
 
     if delayed_response {
@@ -433,52 +458,17 @@ fn create_gpay_session_token(
             ..router_data.clone()
         })
     } else {
-        let gpay_data = connector_metadata
-            .clone()
-            .parse_value::<payment_types::GpaySessionTokenData>("GpaySessionTokenData")
-            .change_context(errors::ConnectorError::NoConnectorMetaData)
-            .attach_printable(format!(
-                "cannot parse gpay metadata from the given value {connector_metadata:?}"
-            ))
-            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-                field_name: "connector_metadata".to_string(),
-                expected_format: "gpay_metadata_format".to_string(),
-            })?;
-
-        let session_data = router_data.request.clone();
-        let transaction_info = payment_types::GpayTransactionInfo {
-            country_code: session_data.country.unwrap_or_default(),
-            currency_code: router_data.request.currency,
-            total_price_status: "Final".to_string(),
-            total_price: router_data
-                .request
-                .currency
-                .to_currency_base_unit(router_data.request.amount)
-                .into_report()
-                .attach_printable(
-                    "Cannot convert given amount to base currency denomination".to_string(),
-                )
-                .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                    field_name: "amount",
-                })?,
-        };
+        let gpay_config = get_google_pay_session_config(router_data, connector)?;
+        let session_response = get_google_pay_session_response(
+            gpay_config,
+            &router_data.request,
+            connector.connector_name.to_string(),
+        )?;
 
         Ok(types::PaymentsSessionRouterData {
             response: Ok(types::PaymentsResponseData::SessionResponse {
                 session_token: payment_types::SessionToken::GooglePay(Box::new(
-                    payment_types::GpaySessionTokenResponse::GooglePaySession(
-                        payment_types::GooglePaySessionResponse {
-                            merchant_info: gpay_data.data.merchant_info,
-                            allowed_payment_methods: gpay_data.data.allowed_payment_methods,
-                            transaction_info,
-                            connector: connector.connector_name.to_string(),
-                            sdk_next_action: payment_types::SdkNextAction {
-                                next_action: payment_types::NextActionCall::Confirm,
-                            },
-                            delayed_session_token: false,
-                            secrets: None,
-                        },
-                    ),
+                    payment_types::GpaySessionTokenResponse::GooglePaySession(session_response),
                 )),
             }),
             ..router_data.clone()
@@ -486,6 +476,40 @@ fn create_gpay_session_token(
     }
 }
 
+fn get_google_pay_session_response(
+    gpay_config: wallet_session_config::GooglePaySessionConfig,
+    session_data: &types::PaymentsSessionData,
+    connector_name: String,
+) -> RouterResult<payment_types::GooglePaySessionResponse> {
+    let transaction_info = payment_types::GpayTransactionInfo {
+        country_code: session_data.country.unwrap_or_default(),
+        currency_code: session_data.currency,
+        total_price_status: "Final".to_string(),
+        total_price: session_data
+            .currency
+            .to_currency_base_unit(session_data.amount)
+            .into_report()
+            .attach_printable(
+                "Cannot convert given amount to base currency denomination".to_string(),
+            )
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "amount",
+            })?,
+    };
+
+    Ok(payment_types::GooglePaySessionResponse {
+        merchant_info: gpay_config.merchant_info,
+        allowed_payment_methods: gpay_config.allowed_payment_methods,
+        transaction_info,
+        connector: connector_name,
+        sdk_next_action: payment_types::SdkNextAction {
+            next_action: payment_types::NextActionCall::Confirm,
+        },
+        delayed_session_token: false,
+        secrets: None,
+    })
+}
+
 fn is_session_response_delayed(state: &routes::AppState, connector: &api::ConnectorData) -> bool {
     let connectors_with_delayed_response = &state
         .conf
@@ -543,3 +567,135 @@ impl types::PaymentsSessionRouterData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn session_data() -> types::PaymentsSessionData {
+        types::PaymentsSessionData {
+            amount: 1050,
+            currency: api_models::enums::Currency::USD,
+            country: Some(api_models::enums::CountryAlpha2::US),
+            order_details: None,
+            merchant_connector_id: Some("mca_123".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_missing_apple_pay_merchant_identifier_is_a_configuration_error() {
+        let metadata = serde_json::json!({
+            "apple_pay": {
+                "session_token_data": {
+                    "certificate": "certificate",
+                    "certificate_keys": "certificate_keys",
+                    "display_name": "Example",
+                    "initiative": "web",
+                    "initiative_context": "example.com"
+                },
+                "payment_request_data": {
+                    "label": "Example",
+                    "merchant_capabilities": ["supports3DS"]
+                }
+            }
+        });
+
+        let error = wallet_session_config::ApplePaySessionConfig::from_metadata(
+            api_models::enums::Connector::Stripe,
+            Some(&metadata),
+        )
+        .map_err(|error| wallet_configuration_error(&session_data(), "stripe", error))
+        .unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidWalletConfiguration {
+                wallet,
+                key,
+                merchant_connector_account,
+            } if wallet == "Apple Pay"
+                && key == "apple_pay.session_token_data.merchant_identifier"
+                && merchant_connector_account == "mca_123"
+        ));
+    }
+
+    #[test]
+    fn test_session_objects_are_built_from_wallet_configuration() {
+        let metadata = serde_json::json!({
+            "apple_pay_combined": {
+                "simplified": {
+                    "session_token_data": { "initiative_context": "example.com" },
+                    "payment_request_data": {
+                        "label": "Example",
+                        "merchant_capabilities": ["supports3DS"]
+                    }
+                }
+            },
+            "google_pay": {
+                "merchant_info": { "merchant_name": "Example" },
+                "allowed_payment_methods": [{
+                    "type": "CARD",
+                    "parameters": { "allowed_auth_methods": ["PAN_ONLY"] },
+                    "tokenization_specification": {
+                        "type": "PAYMENT_GATEWAY",
+                        "parameters": {
+                            "gateway": "example_gateway",
+                            "gateway_merchant_id": "example_merchant"
+                        }
+                    }
+                }]
+            }
+        });
+        let connector = api_models::enums::Connector::Nuvei;
+
+        let apple_pay_config =
+            wallet_session_config::ApplePaySessionConfig::from_metadata(connector, Some(&metadata))
+                .unwrap();
+        let amount_info = get_apple_pay_amount_info("Example", session_data()).unwrap();
+        let apple_pay_payment_request = get_apple_pay_payment_request(
+            amount_info,
+            apple_pay_config,
+            session_data(),
+            "merchant.com.example",
+        )
+        .unwrap();
+        assert_eq!(
+            apple_pay_payment_request,
+            payment_types::ApplePayPaymentRequest {
+                country_code: api_models::enums::CountryAlpha2::US,
+                currency_code: api_models::enums::Currency::USD,
+                total: payment_types::AmountInfo {
+                    label: "Example".to_string(),
+                    total_type: Some("final".to_string()),
+                    amount: "10.50".to_string(),
+                },
+                merchant_capabilities: Some(vec!["supports3DS".to_string()]),
+                supported_networks: Some(vec!["visa".to_string(), "masterCard".to_string()]),
+                merchant_identifier: Some("merchant.com.example".to_string()),
+            }
+        );
+
+        let google_pay_config = wallet_session_config::GooglePaySessionConfig::from_metadata(
+            connector,
+            Some(&metadata),
+        )
+        .unwrap();
+        let google_pay_session = get_google_pay_session_response(
+            google_pay_config,
+            &session_data(),
+            connector.to_string(),
+        )
+        .unwrap();
+        assert_eq!(google_pay_session.merchant_info.merchant_name, "Example");
+        assert_eq!(
+            google_pay_session.allowed_payment_methods[0]
+                .parameters
+                .allowed_card_networks,
+            vec!["VISA".to_string(), "MASTERCARD".to_string()]
+        );
+        assert_eq!(google_pay_session.transaction_info.total_price, "10.50");
+        assert_eq!(google_pay_session.connector, connector.to_string());
+    }
+}
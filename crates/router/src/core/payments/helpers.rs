@@ -1,8 +1,8 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, str::FromStr};
 
 use base64::Engine;
 use common_utils::{
-    ext_traits::{AsyncExt, ByteSliceExt, ValueExt},
+    ext_traits::{AsyncExt, ByteSliceExt, StringExt, ValueExt},
     fp_utils, generate_id, pii,
 };
 use data_models::{
@@ -29,16 +29,22 @@ use uuid::Uuid;
 use x509_parser::parse_x509_certificate;
 
 use super::{
+    connector_account_selection,
     operations::{BoxedOperation, Operation, PaymentResponse},
     CustomerDetails, PaymentData,
 };
 #[cfg(feature = "kms")]
 use crate::connector;
 use crate::{
-    configs::settings::{ConnectorRequestReferenceIdConfig, Server, TempLockerDisableConfig},
+    configs::settings::{
+        ConnectorProxy, ConnectorRequestReferenceIdConfig, LivemodeConfig, Server,
+        SubMerchantConfig, TempLockerDisableConfig, UnsupportedSubMerchantBehaviour,
+    },
     consts::{self, BASE64_ENGINE},
     core::{
+        bin_database,
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        mandate::usage as mandate_usage,
         payment_methods::{cards, vault},
         payments,
     },
@@ -84,6 +90,13 @@ pub fn create_identity_from_certificate_and_key(
         .into_report()
         .change_context(errors::ApiClientError::CertificateDecodeFailed)?;
 
+    create_identity_from_pem(&certificate, &certificate_key)
+}
+
+pub fn create_identity_from_pem(
+    certificate: &str,
+    certificate_key: &str,
+) -> Result<reqwest::Identity, error_stack::Report<errors::ApiClientError>> {
     reqwest::Identity::from_pkcs8_pem(certificate.as_bytes(), certificate_key.as_bytes())
         .into_report()
         .change_context(errors::ApiClientError::CertificateDecodeFailed)
@@ -383,6 +396,7 @@ pub async fn get_token_pm_type_mandate_details(
     request: &api::PaymentsRequest,
     mandate_type: Option<api::MandateTransactionType>,
     merchant_account: &domain::MerchantAccount,
+    override_mandate_limits: bool,
 ) -> RouterResult<(
     Option<String>,
     Option<storage_enums::PaymentMethod>,
@@ -411,7 +425,13 @@ pub async fn get_token_pm_type_mandate_details(
                 recurring_mandate_payment_data,
                 payment_method_type_,
                 mandate_connector,
-            ) = get_token_for_recurring_mandate(state, request, merchant_account).await?;
+            ) = get_token_for_recurring_mandate(
+                state,
+                request,
+                merchant_account,
+                override_mandate_limits,
+            )
+            .await?;
             Ok((
                 token_,
                 payment_method_,
@@ -436,6 +456,7 @@ pub async fn get_token_for_recurring_mandate(
     state: &AppState,
     req: &api::PaymentsRequest,
     merchant_account: &domain::MerchantAccount,
+    override_mandate_limits: bool,
 ) -> RouterResult<(
     Option<String>,
     Option<storage_enums::PaymentMethod>,
@@ -459,18 +480,25 @@ pub async fn get_token_for_recurring_mandate(
                 message: "customer_id must match mandate customer_id".into()
             }))?
         }
-        if mandate.mandate_status != storage_enums::MandateStatus::Active {
+        verify_recurring_mandate_charge(
+            req.amount.get_required_value("amount")?.into(),
+            req.currency.get_required_value("currency")?,
+            &mandate,
+            override_mandate_limits,
+            common_utils::date_time::now(),
+        )?;
+        // A mandate flagged after repeated declines can still be charged, a successful charge
+        // makes it active again
+        if !matches!(
+            mandate.mandate_status,
+            storage_enums::MandateStatus::Active | storage_enums::MandateStatus::RequiresAttention
+        ) {
             Err(report!(errors::ApiErrorResponse::PreconditionFailed {
                 message: "mandate is not active".into()
             }))?
         };
         mandate.payment_method_id.clone()
     };
-    verify_mandate_details(
-        req.amount.get_required_value("amount")?.into(),
-        req.currency.get_required_value("currency")?,
-        mandate.clone(),
-    )?;
 
     let payment_method = db
         .find_payment_method(payment_method_id.as_str())
@@ -498,6 +526,7 @@ pub async fn get_token_for_recurring_mandate(
             Some(payment_method.payment_method),
             Some(payments::RecurringMandatePaymentData {
                 payment_method_type,
+                override_mandate_limits,
             }),
             payment_method.payment_method_type,
             Some(mandate.connector),
@@ -508,6 +537,7 @@ pub async fn get_token_for_recurring_mandate(
             Some(payment_method.payment_method),
             Some(payments::RecurringMandatePaymentData {
                 payment_method_type,
+                override_mandate_limits,
             }),
             payment_method.payment_method_type,
             Some(mandate.connector),
@@ -515,6 +545,45 @@ pub async fn get_token_for_recurring_mandate(
     }
 }
 
+/// A connector mandate can be charged only through the connector it was set up with. When the
+/// payment is routed to a different connector, the connector mandate reference is dropped and the
+/// card retrieved from the card vault is charged on the routed connector instead.
+pub fn get_mandate_ids_for_connector(
+    mandate_ids: Option<api_models::payments::MandateIds>,
+    mandate_connector: Option<&str>,
+    connector_name: &str,
+    payment_method_data: Option<&api::PaymentMethodData>,
+) -> RouterResult<Option<api_models::payments::MandateIds>> {
+    match (mandate_ids, mandate_connector) {
+        (Some(mandate_ids), Some(mandate_connector))
+            if mandate_connector != connector_name
+                && matches!(
+                    mandate_ids.mandate_reference_id,
+                    Some(api_models::payments::MandateReferenceId::ConnectorMandateId(_))
+                ) =>
+        {
+            match payment_method_data {
+                Some(api::PaymentMethodData::Card(_)) => {
+                    logger::info!(
+                        "Charging the vaulted card of mandate {} on {connector_name} instead of {mandate_connector}",
+                        mandate_ids.mandate_id
+                    );
+                    Ok(Some(api_models::payments::MandateIds {
+                        mandate_reference_id: None,
+                        ..mandate_ids
+                    }))
+                }
+                _ => Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "mandate can only be charged through the {mandate_connector} connector"
+                    )
+                })),
+            }
+        }
+        (mandate_ids, _) => Ok(mandate_ids),
+    }
+}
+
 #[instrument(skip_all)]
 /// Check weather the merchant id in the request
 /// and merchant id in the merchant account are same.
@@ -569,27 +638,36 @@ pub fn validate_request_amount_and_amount_to_capture(
     }
 }
 
+/// Validates the card verification code, either of the card in the request or the one collected
+/// when paying with a saved card
+pub fn validate_card_cvc(
+    card_cvc: &masking::Secret<String>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let cvc = card_cvc.peek();
+    if cvc.len() < 3 || cvc.len() > 4 {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Invalid card_cvc length".to_string()
+        }))?
+    }
+    let card_cvc = cvc.parse::<u16>().into_report().change_context(
+        errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "card_cvc",
+        },
+    )?;
+    ::cards::CardSecurityCode::try_from(card_cvc).change_context(
+        errors::ApiErrorResponse::PreconditionFailed {
+            message: "Invalid Card CVC".to_string(),
+        },
+    )?;
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub fn validate_card_data(
     payment_method_data: Option<api::PaymentMethodData>,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     if let Some(api::PaymentMethodData::Card(card)) = payment_method_data {
-        let cvc = card.card_cvc.peek().to_string();
-        if cvc.len() < 3 || cvc.len() > 4 {
-            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
-                message: "Invalid card_cvc length".to_string()
-            }))?
-        }
-        let card_cvc = cvc.parse::<u16>().into_report().change_context(
-            errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "card_cvc",
-            },
-        )?;
-        ::cards::CardSecurityCode::try_from(card_cvc).change_context(
-            errors::ApiErrorResponse::PreconditionFailed {
-                message: "Invalid Card CVC".to_string(),
-            },
-        )?;
+        validate_card_cvc(&card.card_cvc)?;
 
         let exp_month = card
             .card_exp_month
@@ -635,6 +713,479 @@ pub fn validate_card_data(
     Ok(())
 }
 
+#[instrument(skip_all)]
+pub fn validate_external_authentication_data(
+    authentication_data: &api_models::payments::ExternalAuthenticationData,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let eci = &authentication_data.eci;
+    utils::when(
+        eci.len() != 2 || !eci.chars().all(|c| c.is_ascii_digit()),
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "authentication_data.eci".to_string(),
+                expected_format: "two digit electronic commerce indicator".to_string(),
+            })
+            .into_report()
+        },
+    )?;
+
+    // A CAVV / AAV is a 20 byte value which is base64 encoded by the 3DS server
+    let cavv = BASE64_ENGINE
+        .decode(authentication_data.cavv.peek())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "authentication_data.cavv".to_string(),
+            expected_format: "base64 encoded string".to_string(),
+        })?;
+    utils::when(cavv.len() != 20, || {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "authentication_data.cavv",
+        })
+        .into_report()
+        .attach_printable(format!(
+            "cavv is expected to be 20 bytes long, found {} bytes",
+            cavv.len()
+        ))
+    })
+}
+
+/// Encrypts the external authentication data with the key of the merchant, it is stored on the
+/// payment attempt until the payment is confirmed
+pub async fn encrypt_external_authentication_data(
+    authentication_data: Option<&api_models::payments::ExternalAuthenticationData>,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<Option<masking::Secret<Vec<u8>, pii::EncryptionStratergy>>> {
+    let authentication_data = authentication_data
+        .map(utils::Encode::<api_models::payments::ExternalAuthenticationData>::encode_to_value)
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the external authentication data")?
+        .map(masking::Secret::<_, masking::WithType>::new);
+
+    Ok(
+        types::encrypt_optional(authentication_data, key_store.key.get_inner().peek())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encrypt the external authentication data")?
+            .map(|authentication_data| authentication_data.into_encrypted()),
+    )
+}
+
+/// Decrypts the external authentication data stored on the payment attempt
+pub async fn decrypt_external_authentication_data(
+    payment_attempt: &PaymentAttempt,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<Option<api_models::payments::ExternalAuthenticationData>> {
+    types::decrypt::<serde_json::Value, masking::WithType>(
+        payment_attempt
+            .external_authentication_data
+            .clone()
+            .map(diesel_models::encryption::Encryption::new),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to decrypt the external authentication data")?
+    .map(|authentication_data| {
+        authentication_data
+            .into_inner()
+            .expose()
+            .parse_value("ExternalAuthenticationData")
+    })
+    .transpose()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to parse the external authentication data")
+}
+
+/// Validates that the Merchant Category Code is a 4 digit code in a range assigned by ISO 18245
+pub fn validate_merchant_category_code(mcc: &str) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        mcc.len() != 4 || !mcc.chars().all(|c| c.is_ascii_digit()),
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "sub_merchant_data.mcc".to_string(),
+                expected_format: "4 digit merchant category code".to_string(),
+            })
+            .into_report()
+        },
+    )?;
+
+    let code: u16 =
+        mcc.parse()
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sub_merchant_data.mcc",
+            })?;
+    let is_assigned = consts::MERCHANT_CATEGORY_CODE_RANGES
+        .iter()
+        .any(|(start, end)| (*start..=*end).contains(&code));
+    utils::when(!is_assigned, || {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "sub_merchant_data.mcc",
+        })
+        .into_report()
+        .attach_printable(format!(
+            "{mcc} is not a merchant category code assigned by ISO 18245"
+        ))
+    })
+}
+
+/// Validates the order details passed to connectors for risk assessment. Orders containing
+/// physical products cannot be delivered digitally, as digital delivery waives the shipping
+/// address required by some connectors
+pub fn validate_order_shipping_details(
+    request: &api::PaymentsRequest,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    if let Some(order_category) = &request.order_category {
+        utils::when(
+            order_category.trim().is_empty()
+                || order_category.chars().count() > consts::MAX_ORDER_CATEGORY_LENGTH,
+            || {
+                Err(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "order_category".to_string(),
+                    expected_format: format!(
+                        "non empty string of at most {} characters",
+                        consts::MAX_ORDER_CATEGORY_LENGTH
+                    ),
+                })
+                .into_report()
+            },
+        )?;
+    }
+
+    let has_physical_products = request
+        .order_details
+        .as_ref()
+        .map_or(false, |order_details| {
+            order_details
+                .iter()
+                .any(|order| order.product_type == Some(api_enums::ProductType::Physical))
+        });
+    utils::when(
+        request.shipping_method == Some(api_enums::ShippingMethod::Digital)
+            && has_physical_products,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message:
+                    "shipping_method cannot be digital for orders containing physical products"
+                        .to_string(),
+            })
+            .into_report()
+        },
+    )
+}
+
+/// Validates the reference of the order in the merchant's system, which is used to build the
+/// reference of the payment sent to the connectors
+pub fn validate_merchant_order_reference_id(
+    merchant_order_reference_id: &str,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        merchant_order_reference_id.trim().is_empty()
+            || merchant_order_reference_id.chars().count()
+                > consts::MAX_MERCHANT_ORDER_REFERENCE_ID_LENGTH,
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "merchant_order_reference_id".to_string(),
+                expected_format: format!(
+                    "non empty string of at most {} characters",
+                    consts::MAX_MERCHANT_ORDER_REFERENCE_ID_LENGTH
+                ),
+            })
+            .into_report()
+        },
+    )
+}
+
+/// Computes the platform fee of a split payment, a percentage fee is rounded half up to the lowest
+/// denomination of the currency. The fee must be less than the amount of the payment, so that
+/// the destination account receives a part of it.
+pub fn get_split_payment_details(
+    split_payment: &api_models::payments::SplitPaymentRequest,
+    amount: i64,
+) -> CustomResult<api_models::payments::SplitPaymentDetails, errors::ApiErrorResponse> {
+    let destination_account = split_payment.destination_account.trim();
+    utils::when(
+        destination_account.is_empty()
+            || destination_account.chars().count()
+                > consts::MAX_SPLIT_PAYMENT_DESTINATION_ACCOUNT_LENGTH,
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "split_payment.destination_account".to_string(),
+                expected_format: format!(
+                    "non empty string of at most {} characters",
+                    consts::MAX_SPLIT_PAYMENT_DESTINATION_ACCOUNT_LENGTH
+                ),
+            })
+            .into_report()
+        },
+    )?;
+
+    let platform_fee_amount = match split_payment.platform_fee {
+        api_models::payments::PlatformFee::Amount { amount } => amount,
+        api_models::payments::PlatformFee::Percentage { basis_points } => {
+            let basis_points = i64::from(basis_points);
+            utils::when(basis_points > consts::BASIS_POINTS_PER_WHOLE, || {
+                Err(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "split_payment.platform_fee.basis_points",
+                })
+                .into_report()
+                .attach_printable(format!(
+                    "{basis_points} basis points is more than the whole payment amount"
+                ))
+            })?;
+            amount
+                .checked_mul(basis_points)
+                .and_then(|fee| fee.checked_add(consts::BASIS_POINTS_PER_WHOLE / 2))
+                .map(|fee| fee / consts::BASIS_POINTS_PER_WHOLE)
+                .ok_or(report!(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "split_payment.platform_fee",
+                }))?
+        }
+    };
+    validate_platform_fee_amount(platform_fee_amount, amount)?;
+
+    Ok(api_models::payments::SplitPaymentDetails {
+        platform_fee_amount,
+        destination_account: destination_account.to_string(),
+        fee_refund_behaviour: split_payment.fee_refund_behaviour.unwrap_or_default(),
+    })
+}
+
+fn validate_platform_fee_amount(
+    platform_fee_amount: i64,
+    amount: i64,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        platform_fee_amount < 0 || platform_fee_amount >= amount,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "platform fee of the split payment must be less than the payment amount"
+                    .to_string(),
+            })
+            .into_report()
+            .attach_printable(format!(
+                "platform fee {platform_fee_amount} for the payment amount {amount}"
+            ))
+        },
+    )
+}
+
+pub fn get_split_payment_details_of_intent(
+    payment_intent: &PaymentIntent,
+) -> CustomResult<Option<api_models::payments::SplitPaymentDetails>, errors::ApiErrorResponse> {
+    payment_intent
+        .split_payment
+        .clone()
+        .map(|split_payment| {
+            split_payment
+                .parse_value::<api_models::payments::SplitPaymentDetails>("SplitPaymentDetails")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to deserialize the split payment of the payment intent")
+        })
+        .transpose()
+}
+
+/// Rejects a split payment routed to a connector which cannot split it, instead of processing
+/// the whole amount without the split. The fee is validated again, as the amount of the payment
+/// may have been updated after it was created.
+pub fn validate_split_payment_support(
+    payment_intent: &PaymentIntent,
+    connector: api_enums::Connector,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let Some(split_payment) = get_split_payment_details_of_intent(payment_intent)? else {
+        return Ok(());
+    };
+
+    let is_supported = crate::connector::utils::capabilities::get_connector_capabilities(connector)
+        .map_or(false, |capabilities| capabilities.split_payments_supported);
+    utils::when(!is_supported, || {
+        Err(errors::ApiErrorResponse::FlowNotSupported {
+            flow: "SplitPayment".to_string(),
+            connector: connector.to_string(),
+        })
+        .into_report()
+    })?;
+
+    validate_platform_fee_amount(split_payment.platform_fee_amount, payment_intent.amount)
+}
+
+/// Part of the platform fee returned with a refund of a split payment. A proportional reversal
+/// returns the share of the fee in the refunded amount, so a full refund returns the whole fee.
+pub fn get_platform_fee_refund_amount(
+    split_payment: &api_models::payments::SplitPaymentDetails,
+    payment_amount: i64,
+    refund_amount: i64,
+) -> i64 {
+    match split_payment.fee_refund_behaviour {
+        api_enums::PlatformFeeRefundBehaviour::Retain => 0,
+        api_enums::PlatformFeeRefundBehaviour::Proportional if payment_amount <= 0 => 0,
+        api_enums::PlatformFeeRefundBehaviour::Proportional => {
+            let fee_refund_amount = i128::from(split_payment.platform_fee_amount)
+                * i128::from(refund_amount.min(payment_amount))
+                / i128::from(payment_amount);
+            i64::try_from(fee_refund_amount).unwrap_or(split_payment.platform_fee_amount)
+        }
+    }
+}
+
+/// Validates the breakdown of the amount of a payment. The amounts of the line items of the order,
+/// with the tax, shipping cost and duty, less the discount, must add up to the amount of the
+/// payment. Without line items only the part of the amount which is not the price of the items is
+/// known, which must not exceed the amount of the payment.
+pub fn validate_amount_details(
+    amount_details: &api_models::payments::AmountDetails,
+    amount: i64,
+    order_details: Option<&[api_models::payments::OrderDetailsWithAmount]>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    for (field_name, value) in [
+        ("amount_details.tax_amount", amount_details.tax_amount),
+        ("amount_details.shipping_cost", amount_details.shipping_cost),
+        ("amount_details.duty_amount", amount_details.duty_amount),
+        (
+            "amount_details.discount_amount",
+            amount_details.discount_amount,
+        ),
+    ] {
+        utils::when(value.map_or(false, |value| value < 0), || {
+            Err(errors::ApiErrorResponse::InvalidDataValue { field_name }).into_report()
+        })?;
+    }
+
+    let charges_amount = [
+        amount_details.tax_amount,
+        amount_details.shipping_cost,
+        amount_details.duty_amount,
+    ]
+    .into_iter()
+    .flatten()
+    .map(i128::from)
+    .sum::<i128>()
+        - i128::from(amount_details.discount_amount.unwrap_or_default());
+    let line_items = order_details.unwrap_or_default();
+    let line_items_amount = line_items
+        .iter()
+        .map(|line_item| i128::from(line_item.amount) * i128::from(line_item.quantity))
+        .sum::<i128>();
+    let total_amount = line_items_amount + charges_amount;
+    let rounding_tolerance = if amount_details.allow_rounding_difference {
+        i128::try_from(line_items.len()).unwrap_or(i128::MAX).max(1)
+    } else {
+        0
+    };
+
+    let is_reconciled = if line_items.is_empty() {
+        charges_amount <= i128::from(amount)
+    } else {
+        (total_amount - i128::from(amount)).abs() <= rounding_tolerance
+    };
+    utils::when(!is_reconciled, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "amount_details and order_details do not add up to the amount of the payment"
+                .to_string(),
+        })
+        .into_report()
+        .attach_printable(format!(
+            "breakdown total {total_amount} for the payment amount {amount}"
+        ))
+    })
+}
+
+pub fn get_amount_details_of_intent(
+    payment_intent: &PaymentIntent,
+) -> CustomResult<Option<api_models::payments::AmountDetails>, errors::ApiErrorResponse> {
+    payment_intent
+        .amount_details
+        .clone()
+        .map(|amount_details| {
+            amount_details
+                .parse_value::<api_models::payments::AmountDetails>("AmountDetails")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to deserialize the amount details of the payment intent")
+        })
+        .transpose()
+}
+
+/// Breakdown of the amount of the payment passed to the connector. The payment is processed
+/// without it, as it would be without Level 2 and Level 3 data, when it no longer adds up to the
+/// amount of the payment, such as after the amount was updated
+pub fn get_amount_details_for_connector(
+    payment_intent: &PaymentIntent,
+    amount: i64,
+    order_details: Option<&[api_models::payments::OrderDetailsWithAmount]>,
+) -> Option<api_models::payments::AmountDetails> {
+    let amount_details = get_amount_details_of_intent(payment_intent)
+        .map_err(|error| logger::error!(?error))
+        .ok()
+        .flatten()?;
+
+    match validate_amount_details(&amount_details, amount, order_details) {
+        Ok(()) => Some(amount_details),
+        Err(error) => {
+            logger::warn!(
+                ?error,
+                "Dropping the amount details which do not add up to the amount of the payment"
+            );
+            None
+        }
+    }
+}
+
+/// Whether the merchant descriptor and sub-merchant details of the payment are passed to the
+/// connector, payments routed to connectors not supporting them are processed without them or
+/// failed, as configured
+pub fn should_pass_sub_merchant_data(
+    config: &SubMerchantConfig,
+    connector_name: &str,
+) -> CustomResult<bool, errors::ApiErrorResponse> {
+    let is_supported = api_enums::Connector::from_str(connector_name)
+        .map(|connector| config.supported_connectors.contains(&connector))
+        .unwrap_or(false);
+
+    match config.unsupported_connector_behaviour {
+        _ if is_supported => Ok(true),
+        UnsupportedSubMerchantBehaviour::Ignore => {
+            logger::debug!(
+                "Dropping sub-merchant details not supported by the connector {connector_name}"
+            );
+            Ok(false)
+        }
+        UnsupportedSubMerchantBehaviour::Reject => Err(errors::ApiErrorResponse::NotSupported {
+            message: format!(
+                "Sub-merchant details are not supported by the connector {connector_name}"
+            ),
+        })
+        .into_report(),
+    }
+}
+
+/// The connectors which require the device data collection reject the authorizations without its
+/// session, the payment is failed before it is sent to them when the merchant skipped the device
+/// data collection. Mail order and telephone order payments are not collected device data for.
+pub fn validate_device_session(
+    connector_name: &str,
+    payment_intent: &PaymentIntent,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let is_device_session_required = api_enums::Connector::from_str(connector_name)
+        .map(crate::connector::utils::device_data_collection::is_device_session_required)
+        .unwrap_or(false);
+    let is_moto = payment_intent
+        .payment_channel
+        .map_or(false, |payment_channel| payment_channel.is_moto());
+
+    utils::when(
+        is_device_session_required && !is_moto && payment_intent.device_session_id.is_none(),
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "The connector {connector_name} requires the device data collection before the authorization, call the device data endpoint for the payment and pass the returned `device_session_id`"
+                ),
+            })
+            .into_report()
+        },
+    )
+}
+
 pub fn validate_mandate(
     req: impl Into<api::MandateValidationFields>,
     is_confirm_operation: bool,
@@ -701,8 +1252,25 @@ fn validate_new_mandate_request(
     }
 
     let mandate_details = match mandate_data.mandate_type {
-        Some(api_models::payments::MandateType::SingleUse(details)) => Some(details),
-        Some(api_models::payments::MandateType::MultiUse(details)) => details,
+        Some(api_models::payments::MandateType::SingleUse(details)) => {
+            utils::when(
+                details.frequency.is_some() || details.max_charges_per_period.is_some(),
+                || {
+                    Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                        message: "`frequency` and `max_charges_per_period` are supported only \
+                                  for multi use mandates"
+                            .into()
+                    }))
+                },
+            )?;
+            Some(details)
+        }
+        Some(api_models::payments::MandateType::MultiUse(details)) => {
+            if let Some(details) = &details {
+                validate_mandate_charge_frequency(details)?;
+            }
+            details
+        }
         None => None,
     };
     mandate_details.and_then(|md| md.start_date.zip(md.end_date)).map(|(start_date, end_date)|
@@ -717,6 +1285,32 @@ fn validate_new_mandate_request(
     Ok(())
 }
 
+fn validate_mandate_charge_frequency(
+    mandate_details: &api_models::payments::MandateAmountData,
+) -> RouterResult<()> {
+    match (
+        mandate_details.frequency,
+        mandate_details.max_charges_per_period,
+    ) {
+        (Some(_), Some(max_charges_per_period)) if max_charges_per_period <= 0 => {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`mandate_data.mandate_type.multi_use.max_charges_per_period` must be \
+                          greater than 0"
+                    .into()
+            }))
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`mandate_data.mandate_type.multi_use.frequency` and \
+                          `mandate_data.mandate_type.multi_use.max_charges_per_period` must be \
+                          passed together"
+                    .into()
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn validate_customer_id_mandatory_cases(
     has_shipping: bool,
     has_billing: bool,
@@ -763,9 +1357,15 @@ pub fn create_redirect_url(
 ) -> String {
     let creds_identifier_path = creds_identifier.map_or_else(String::new, |cd| format!("/{}", cd));
     format!(
-        "{}/payments/{}/{}/redirect/response/{}",
-        router_base_url, payment_attempt.payment_id, payment_attempt.merchant_id, connector_name,
-    ) + creds_identifier_path.as_ref()
+        "{}/payments/{}/{}/redirect/response/{}{}?{}={}",
+        router_base_url,
+        payment_attempt.payment_id,
+        payment_attempt.merchant_id,
+        connector_name,
+        creds_identifier_path,
+        consts::REDIRECT_ATTEMPT_ID_QUERY_PARAM,
+        payment_attempt.attempt_id
+    )
 }
 
 pub fn create_webhook_url(
@@ -784,8 +1384,13 @@ pub fn create_complete_authorize_url(
     connector_name: &String,
 ) -> String {
     format!(
-        "{}/payments/{}/{}/redirect/complete/{}",
-        router_base_url, payment_attempt.payment_id, payment_attempt.merchant_id, connector_name
+        "{}/payments/{}/{}/redirect/complete/{}?{}={}",
+        router_base_url,
+        payment_attempt.payment_id,
+        payment_attempt.merchant_id,
+        connector_name,
+        consts::REDIRECT_ATTEMPT_ID_QUERY_PARAM,
+        payment_attempt.attempt_id
     )
 }
 
@@ -817,22 +1422,37 @@ pub fn verify_mandate_details(
     mandate: storage::Mandate,
 ) -> RouterResult<()> {
     match mandate.mandate_type {
-        storage_enums::MandateType::SingleUse => utils::when(
-            mandate
-                .mandate_amount
-                .map(|mandate_amount| request_amount > mandate_amount)
-                .unwrap_or(true),
-            || {
+        storage_enums::MandateType::SingleUse => {
+            utils::when(mandate.amount_captured.is_some(), || {
                 Err(report!(errors::ApiErrorResponse::MandateValidationFailed {
-                    reason: "request amount is greater than mandate amount".to_string()
+                    reason: "single use mandate has already been used".to_string()
                 }))
-            },
-        ),
+            })
+            .and_then(|_| {
+                utils::when(
+                    mandate
+                        .mandate_amount
+                        .map(|mandate_amount| request_amount > mandate_amount)
+                        .unwrap_or(true),
+                    || {
+                        Err(report!(errors::ApiErrorResponse::MandateValidationFailed {
+                            reason: "request amount is greater than mandate amount".to_string()
+                        }))
+                    },
+                )
+            })
+        }
+        // The amount of a mandate limited in frequency is the limit of every charge, otherwise
+        // it is the limit of all the charges made against the mandate
         storage::enums::MandateType::MultiUse => utils::when(
             mandate
                 .mandate_amount
                 .map(|mandate_amount| {
-                    (mandate.amount_captured.unwrap_or(0) + request_amount) > mandate_amount
+                    if mandate.frequency.is_some() {
+                        request_amount > mandate_amount
+                    } else {
+                        (mandate.amount_captured.unwrap_or(0) + request_amount) > mandate_amount
+                    }
                 })
                 .unwrap_or(false),
             || {
@@ -842,6 +1462,13 @@ pub fn verify_mandate_details(
             },
         ),
     }?;
+    verify_mandate_currency(request_currency, &mandate)
+}
+
+fn verify_mandate_currency(
+    request_currency: api_enums::Currency,
+    mandate: &storage::Mandate,
+) -> RouterResult<()> {
     utils::when(
         mandate
             .mandate_currency
@@ -855,6 +1482,60 @@ pub fn verify_mandate_details(
     )
 }
 
+/// Rejects the charge if the mandate was already charged the maximum number of times in the
+/// current period of its frequency
+pub fn verify_mandate_charge_frequency(
+    mandate: &storage::Mandate,
+    now: time::PrimitiveDateTime,
+) -> RouterResult<()> {
+    match mandate_usage::get_next_charge_allowed_at(mandate, now) {
+        Some(next_charge_allowed_at) => Err(report!(get_mandate_charge_limit_exceeded_error(
+            mandate,
+            next_charge_allowed_at
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub fn get_mandate_charge_limit_exceeded_error(
+    mandate: &storage::Mandate,
+    next_charge_allowed_at: time::PrimitiveDateTime,
+) -> errors::ApiErrorResponse {
+    errors::ApiErrorResponse::MandateChargeLimitExceeded {
+        mandate_id: mandate.mandate_id.clone(),
+        reason: format!(
+            "the mandate can be charged at most {} time(s) {}",
+            mandate.max_charges_per_period.unwrap_or_default(),
+            mandate
+                .frequency
+                .map(|frequency| frequency.to_string())
+                .unwrap_or_default()
+        ),
+        next_charge_allowed_at: Some(next_charge_allowed_at),
+    }
+}
+
+/// Verifies that the mandate can be charged the amount now. The amount and frequency limits of a
+/// multi use mandate are not enforced when the merchant is allowed to override them for the charge.
+pub fn verify_recurring_mandate_charge(
+    request_amount: i64,
+    request_currency: api_enums::Currency,
+    mandate: &storage::Mandate,
+    override_mandate_limits: bool,
+    now: time::PrimitiveDateTime,
+) -> RouterResult<()> {
+    if override_mandate_limits && mandate.mandate_type == storage_enums::MandateType::MultiUse {
+        logger::warn!(
+            "Charging mandate {} without enforcing its amount and frequency limits",
+            mandate.mandate_id
+        );
+        return verify_mandate_currency(request_currency, mandate);
+    }
+
+    verify_mandate_details(request_amount, request_currency, mandate.clone())?;
+    verify_mandate_charge_frequency(mandate, now)
+}
+
 #[instrument(skip_all)]
 pub fn payment_attempt_status_fsm(
     payment_method_data: &Option<api::PaymentMethodData>,
@@ -957,6 +1638,7 @@ pub(crate) async fn get_payment_method_create_request(
                             .card_network
                             .as_ref()
                             .map(|card_network| card_network.to_string()),
+                        duplicate_behaviour: None,
                     };
                     Ok(payment_method_request)
                 }
@@ -970,6 +1652,7 @@ pub(crate) async fn get_payment_method_create_request(
                         metadata: None,
                         customer_id: Some(customer.customer_id.to_owned()),
                         card_network: None,
+                        duplicate_behaviour: None,
                     };
                     Ok(payment_method_request)
                 }
@@ -1554,6 +2237,37 @@ pub(crate) fn validate_capture_method(
     )
 }
 
+/// An authorization which has expired at the connector can no longer be captured, the capture is
+/// rejected even if the authorization expiry task has not run yet
+fn is_authorization_expired(
+    status: storage_enums::AttemptStatus,
+    authorization_expires_at: Option<time::PrimitiveDateTime>,
+    current_time: time::PrimitiveDateTime,
+) -> bool {
+    status == storage_enums::AttemptStatus::AuthorizationExpired
+        || authorization_expires_at.map_or(false, |authorization_expires_at| {
+            authorization_expires_at <= current_time
+        })
+}
+
+pub(crate) fn validate_authorization_not_expired(
+    payment_attempt: &PaymentAttempt,
+    current_time: time::PrimitiveDateTime,
+) -> RouterResult<()> {
+    let is_authorization_expired = is_authorization_expired(
+        payment_attempt.status,
+        payment_attempt.authorization_expires_at,
+        current_time,
+    );
+    utils::when(is_authorization_expired, || {
+        Err(report!(
+            errors::ApiErrorResponse::PaymentAuthorizationExpired {
+                payment_id: payment_attempt.payment_id.clone(),
+            }
+        ))
+    })
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_status_with_capture_method(
     status: storage_enums::IntentStatus,
@@ -1599,6 +2313,23 @@ pub(crate) fn validate_amount_to_capture(
     )
 }
 
+/// The amount authorized by the connector when it authorized less than the amount of the attempt,
+/// as debit networks may approve only the balance available on the card. The authorized amount is
+/// kept by the later syncs of the attempt, which report it as authorized.
+pub fn get_partially_authorized_amount(
+    status: storage_enums::AttemptStatus,
+    amount: i64,
+    authorized_amount: Option<i64>,
+) -> Option<i64> {
+    match status {
+        storage_enums::AttemptStatus::Authorized
+        | storage_enums::AttemptStatus::PartiallyAuthorized => {
+            authorized_amount.filter(|authorized_amount| *authorized_amount < amount)
+        }
+        _ => None,
+    }
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_payment_method_fields_present(
     req: &api::PaymentsRequest,
@@ -1761,6 +2492,7 @@ pub fn validate_payment_method_type_against_payment_method(
                 | api_enums::PaymentMethodType::CimbVa
                 | api_enums::PaymentMethodType::DanamonVa
                 | api_enums::PaymentMethodType::MandiriVa
+                | api_enums::PaymentMethodType::VirtualAccount
         ),
         api_enums::PaymentMethod::BankDebit => matches!(
             payment_method_type,
@@ -1819,6 +2551,7 @@ pub fn check_force_psync_precondition(status: &storage_enums::AttemptStatus) ->
         storage_enums::AttemptStatus::Charged
             | storage_enums::AttemptStatus::AutoRefunded
             | storage_enums::AttemptStatus::Voided
+            | storage_enums::AttemptStatus::AuthorizationExpired
             | storage_enums::AttemptStatus::CodInitiated
             | storage_enums::AttemptStatus::Started
             | storage_enums::AttemptStatus::Failure
@@ -1832,6 +2565,8 @@ where
     Some(func(option1?, option2?))
 }
 
+/// Lists the payments which are not archived, and falls back to the listing of all the payments
+/// when they do not fill the page
 #[cfg(feature = "olap")]
 pub(super) async fn filter_by_constraints(
     db: &dyn StorageInterface,
@@ -1839,10 +2574,19 @@ pub(super) async fn filter_by_constraints(
     merchant_id: &str,
     storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Vec<PaymentIntent>, errors::DataStorageError> {
+    let constraints: data_models::payments::payment_intent::PaymentIntentFetchConstraints =
+        constraints.clone().into();
+    let result = db
+        .filter_payment_intent_by_constraints(merchant_id, &constraints, storage_scheme)
+        .await?;
+    if !constraints.should_fall_back_to_archived(result.len()) {
+        return Ok(result);
+    }
+
     let result = db
         .filter_payment_intent_by_constraints(
             merchant_id,
-            &constraints.clone().into(),
+            &constraints.with_archived(),
             storage_scheme,
         )
         .await?;
@@ -1973,8 +2717,30 @@ pub async fn make_ephemeral_key(
     state: AppState,
     customer_id: String,
     merchant_id: String,
+    invalidate_existing_keys: bool,
 ) -> errors::RouterResponse<ephemeral_key::EphemeralKey> {
     let store = &state.store;
+    if invalidate_existing_keys {
+        match store
+            .find_latest_ephemeral_key_by_merchant_id_customer_id(&merchant_id, &customer_id)
+            .await
+        {
+            Ok(existing_key) => {
+                // The existing key could have expired or been deleted already
+                if let Err(error) = store.delete_ephemeral_key(&existing_key.id).await {
+                    logger::warn!(?error, "Unable to delete the existing ephemeral key");
+                }
+            }
+            Err(error)
+                if matches!(
+                    error.current_context(),
+                    errors::StorageError::ValueNotFound(_)
+                ) => {}
+            Err(error) => Err(error)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to find the existing ephemeral key")?,
+        }
+    }
     let id = utils::generate_id(consts::ID_LENGTH, "eki");
     let secret = format!("epk_{}", &Uuid::new_v4().simple().to_string());
     let ek = ephemeral_key::EphemeralKeyNew {
@@ -2150,7 +2916,9 @@ pub fn generate_mandate(
                             .set_mandate_amount(Some(data.amount))
                             .set_mandate_currency(Some(data.currency))
                             .set_start_date(data.start_date)
-                            .set_end_date(data.end_date),
+                            .set_end_date(data.end_date)
+                            .set_frequency(data.frequency)
+                            .set_max_charges_per_period(data.max_charges_per_period),
                         // .set_metadata(data.metadata),
                         // we are storing PaymentMethodData in metadata of mandate
                         None => &mut new_mandate,
@@ -2194,6 +2962,356 @@ pub fn authenticate_client_secret(
     }
 }
 
+pub fn validate_session_expiry(session_expiry: u32) -> Result<(), errors::ApiErrorResponse> {
+    if !(consts::MIN_SESSION_EXPIRY..=consts::MAX_SESSION_EXPIRY).contains(&session_expiry) {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "session_expiry should be between {} and {} seconds",
+                consts::MIN_SESSION_EXPIRY,
+                consts::MAX_SESSION_EXPIRY
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_duplicate_order_reference_window(
+    duplicate_order_reference_window: u32,
+) -> Result<(), errors::ApiErrorResponse> {
+    if !(consts::MIN_DUPLICATE_ORDER_REFERENCE_WINDOW
+        ..=consts::MAX_DUPLICATE_ORDER_REFERENCE_WINDOW)
+        .contains(&duplicate_order_reference_window)
+    {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "duplicate_order_reference_window should be between {} and {} seconds",
+                consts::MIN_DUPLICATE_ORDER_REFERENCE_WINDOW,
+                consts::MAX_DUPLICATE_ORDER_REFERENCE_WINDOW
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates that the locale is a well formed BCP-47 language tag, such as `fr` or `fr-FR`
+pub fn validate_locale(locale: &str) -> Result<(), errors::ApiErrorResponse> {
+    if is_valid_locale(locale) {
+        Ok(())
+    } else {
+        Err(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "locale".to_string(),
+            expected_format: "BCP-47 language tag, such as fr-FR".to_string(),
+        })
+    }
+}
+
+fn is_valid_locale(locale: &str) -> bool {
+    let mut subtags = locale.split('-');
+    let is_valid_language = subtags.next().map_or(false, |language| {
+        (2..=8).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic())
+    });
+
+    locale.len() <= consts::MAX_LOCALE_LENGTH
+        && is_valid_language
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Locale passed to the connectors, the locale passed in the payment request takes precedence over
+/// the language of the shopper's browser, followed by the default locale of the business profile
+pub fn get_locale(
+    request_locale: Option<&str>,
+    browser_language: Option<&str>,
+    profile_default_locale: Option<&str>,
+) -> String {
+    [request_locale, browser_language, profile_default_locale]
+        .into_iter()
+        .flatten()
+        .find(|locale| is_valid_locale(locale))
+        .unwrap_or(consts::DEFAULT_LOCALE)
+        .to_string()
+}
+
+/// Resolves the locale of the shopper for the attempt being confirmed, the business profile is
+/// looked up only when neither the payment request nor the shopper's browser specify one
+pub async fn resolve_locale<F: Clone>(
+    state: &AppState,
+    payment_data: &mut PaymentData<F>,
+) -> RouterResult<()> {
+    let request_locale = payment_data.payment_attempt.locale.clone();
+    let browser_language = payment_data
+        .payment_attempt
+        .browser_info
+        .as_ref()
+        .and_then(|browser_info| browser_info.get("language"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string);
+
+    let is_shopper_locale_known = [request_locale.as_deref(), browser_language.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(is_valid_locale);
+
+    let profile_default_locale = match payment_data.payment_intent.profile_id.as_ref() {
+        Some(profile_id) if !is_shopper_locale_known => {
+            state
+                .store
+                .find_business_profile_by_profile_id(profile_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+                    id: profile_id.to_owned(),
+                })?
+                .default_locale
+        }
+        _ => None,
+    };
+
+    payment_data.payment_attempt.locale = Some(get_locale(
+        request_locale.as_deref(),
+        browser_language.as_deref(),
+        profile_default_locale.as_deref(),
+    ));
+    Ok(())
+}
+
+/// Fields of a payment for which the merchant can configure defaults, so that they need not be
+/// passed in every payment request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaymentDefaults {
+    pub return_url: Option<String>,
+    pub description: Option<String>,
+    pub statement_descriptor_name: Option<String>,
+    pub statement_descriptor_suffix: Option<String>,
+    pub currency: Option<api_enums::Currency>,
+    pub billing_country: Option<api_enums::CountryAlpha2>,
+}
+
+impl From<&api::PaymentsRequest> for PaymentDefaults {
+    fn from(request: &api::PaymentsRequest) -> Self {
+        Self {
+            return_url: request.return_url.as_ref().map(|url| url.to_string()),
+            description: request.description.clone(),
+            statement_descriptor_name: request.statement_descriptor_name.clone(),
+            statement_descriptor_suffix: request.statement_descriptor_suffix.clone(),
+            currency: request.currency,
+            billing_country: request
+                .billing
+                .as_ref()
+                .and_then(|billing| billing.address.as_ref())
+                .and_then(|address| address.country),
+        }
+    }
+}
+
+impl From<&storage::business_profile::BusinessProfile> for PaymentDefaults {
+    fn from(business_profile: &storage::business_profile::BusinessProfile) -> Self {
+        Self {
+            return_url: business_profile.return_url.clone(),
+            description: business_profile.default_payment_description.clone(),
+            statement_descriptor_name: business_profile.statement_descriptor_name.clone(),
+            statement_descriptor_suffix: business_profile.statement_descriptor_suffix.clone(),
+            currency: business_profile.default_currency,
+            billing_country: business_profile.default_country,
+        }
+    }
+}
+
+impl From<&domain::MerchantAccount> for PaymentDefaults {
+    fn from(merchant_account: &domain::MerchantAccount) -> Self {
+        Self {
+            return_url: merchant_account.return_url.clone(),
+            currency: merchant_account.default_currency,
+            billing_country: merchant_account.default_country,
+            ..Default::default()
+        }
+    }
+}
+
+impl PaymentDefaults {
+    /// Fills the fields which were not passed in the payment request. Each field is resolved in the
+    /// order request → business profile → merchant account, and the fields which were filled from
+    /// the defaults are returned along with the resolved values.
+    pub fn resolve(
+        request: Self,
+        profile_defaults: Self,
+        merchant_defaults: Self,
+    ) -> (Self, Vec<api_enums::PaymentDefaultField>) {
+        let mut applied_defaults = Vec::new();
+        let resolved = Self {
+            return_url: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::ReturnUrl,
+                request.return_url,
+                profile_defaults.return_url,
+                merchant_defaults.return_url,
+            ),
+            description: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::Description,
+                request.description,
+                profile_defaults.description,
+                merchant_defaults.description,
+            ),
+            statement_descriptor_name: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::StatementDescriptorName,
+                request.statement_descriptor_name,
+                profile_defaults.statement_descriptor_name,
+                merchant_defaults.statement_descriptor_name,
+            ),
+            statement_descriptor_suffix: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::StatementDescriptorSuffix,
+                request.statement_descriptor_suffix,
+                profile_defaults.statement_descriptor_suffix,
+                merchant_defaults.statement_descriptor_suffix,
+            ),
+            currency: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::Currency,
+                request.currency,
+                profile_defaults.currency,
+                merchant_defaults.currency,
+            ),
+            billing_country: resolve_field(
+                &mut applied_defaults,
+                api_enums::PaymentDefaultField::BillingCountry,
+                request.billing_country,
+                profile_defaults.billing_country,
+                merchant_defaults.billing_country,
+            ),
+        };
+
+        (resolved, applied_defaults)
+    }
+}
+
+fn resolve_field<T>(
+    applied_defaults: &mut Vec<api_enums::PaymentDefaultField>,
+    field: api_enums::PaymentDefaultField,
+    request_value: Option<T>,
+    profile_default: Option<T>,
+    merchant_default: Option<T>,
+) -> Option<T> {
+    request_value.or_else(|| {
+        let default = profile_default.or(merchant_default);
+        if default.is_some() {
+            applied_defaults.push(field);
+        }
+        default
+    })
+}
+
+/// Time at which an unconfirmed payment expires, the session expiry passed in the request takes
+/// precedence over the one configured on the business profile. The payment does not expire when
+/// neither of them is set
+pub fn get_session_expiry(
+    created_at: time::PrimitiveDateTime,
+    request_session_expiry: Option<u32>,
+    profile_session_expiry: Option<i64>,
+) -> Option<time::PrimitiveDateTime> {
+    request_session_expiry
+        .map(i64::from)
+        .or(profile_session_expiry)
+        .map(|session_expiry| created_at.saturating_add(Duration::seconds(session_expiry)))
+}
+
+pub fn validate_session_expiry_not_reached(
+    payment_intent: &PaymentIntent,
+) -> Result<(), errors::ApiErrorResponse> {
+    let current_timestamp = common_utils::date_time::now();
+    fp_utils::when(
+        payment_intent
+            .session_expiry
+            .map_or(false, |session_expiry| current_timestamp > session_expiry),
+        || {
+            Err(errors::ApiErrorResponse::PaymentSessionExpired {
+                payment_id: payment_intent.payment_id.clone(),
+            })
+        },
+    )
+}
+
+/// Key of the config holding how the partially funded bank transfers of the merchant are handled
+pub fn get_partial_bank_transfer_handling_key(merchant_id: &str) -> String {
+    format!("partial_bank_transfer_handling_{merchant_id}")
+}
+
+pub async fn get_partial_bank_transfer_handling(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> api_enums::PartialBankTransferHandling {
+    let handling = db
+        .find_config_by_key(&get_partial_bank_transfer_handling_key(merchant_id))
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_enum("PartialBankTransferHandling")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match handling {
+        Ok(handling) => handling,
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, "Failed to fetch the partial bank transfer handling");
+            }
+            api_enums::PartialBankTransferHandling::default()
+        }
+    }
+}
+
+/// Key of the config holding whether the manual capture payments of the merchant are captured
+/// automatically before their authorization expires at the connector
+pub fn get_auto_capture_before_authorization_expiry_key(merchant_id: &str) -> String {
+    format!("auto_capture_before_authorization_expiry_{merchant_id}")
+}
+
+pub async fn is_auto_capture_before_authorization_expiry_enabled(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> bool {
+    let config = db
+        .find_config_by_key(&get_auto_capture_before_authorization_expiry_key(
+            merchant_id,
+        ))
+        .await;
+
+    match config {
+        Ok(config) => config.config == "true",
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(
+                    ?error,
+                    "Failed to fetch the automatic capture before authorization expiry config"
+                );
+            }
+            false
+        }
+    }
+}
+
+/// Amount received for a bank transfer which is yet to be completely funded by the customer
+pub fn get_partially_received_amount(payment_attempt: &PaymentAttempt) -> Option<i64> {
+    match payments::transformers::connector_next_action_check(payment_attempt.clone()) {
+        Ok(Some(
+            api_models::payments::ConnectorNextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details,
+            },
+        )) => bank_transfer_steps_and_charges_details
+            .receiver
+            .map(|receiver| receiver.amount_received)
+            .filter(|amount_received| {
+                *amount_received > 0 && *amount_received < payment_attempt.amount
+            }),
+        _ => None,
+    }
+}
+
 pub(crate) fn validate_payment_status_against_not_allowed_statuses(
     intent_status: &storage_enums::IntentStatus,
     not_allowed_statuses: &[storage_enums::IntentStatus],
@@ -2339,20 +3457,218 @@ pub(crate) fn get_payment_id_from_client_secret(cs: &str) -> RouterResult<String
     Ok(payment_id.to_string())
 }
 
+/// Details of a payment which are validated against the fields required by the connectors that
+/// the payment can be routed to
+pub struct RequiredFieldsValidationData<'a> {
+    pub profile_id: Option<String>,
+    pub payment_method: Option<api_enums::PaymentMethod>,
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+    pub currency: Option<api_enums::Currency>,
+    pub customer_id: Option<&'a String>,
+    pub customer_details: &'a CustomerDetails,
+    pub billing_address: Option<&'a domain::Address>,
+    pub shipping_address: Option<&'a domain::Address>,
+    /// Billing address of the request, used when the payment has no stored billing address
+    pub request_billing: Option<&'a api::Address>,
+    /// Device session stored on the payment by the device data endpoint, used when the request
+    /// has none
+    pub device_session_id: Option<&'a String>,
+    /// Mail order and telephone order payments are not collected device data for
+    pub is_moto: bool,
+    pub is_mandate: bool,
+}
+
+/// Reports the fields which are required for the payment method type of the payment by all the
+/// connectors it can be routed to, and which are missing from the payment. The payments processed
+/// with the connector details passed in the request are not validated.
+#[instrument(skip_all)]
+pub async fn validate_required_fields_for_connectors(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    request: &api::PaymentsRequest,
+    data: RequiredFieldsValidationData<'_>,
+    validation: &mut errors::validation::ValidationErrors,
+) -> RouterResult<()> {
+    let (Some(payment_method), Some(payment_method_type), None) = (
+        data.payment_method,
+        data.payment_method_type,
+        request.merchant_connector_details.as_ref(),
+    ) else {
+        return Ok(());
+    };
+
+    let connectors = match request.connector.clone() {
+        Some(connectors) => connectors,
+        None => {
+            let Some(profile_id) = data.profile_id else {
+                return Ok(());
+            };
+            let eligibility_data = super::eligibility::PaymentEligibilityData {
+                profile_id,
+                payment_method: Some(payment_method),
+                payment_method_type: Some(payment_method_type),
+                currency: data.currency,
+                country: data
+                    .billing_address
+                    .and_then(|address| address.country)
+                    .or_else(|| {
+                        data.request_billing
+                            .and_then(|billing| billing.address.as_ref())
+                            .and_then(|address| address.country)
+                    }),
+                // The amount is validated against the limits of its currency and the amount ranges
+                // of the connectors are validated when the payment is routed
+                amount: None,
+            };
+            super::eligibility::get_candidate_connectors(
+                state,
+                merchant_account,
+                key_store,
+                &eligibility_data,
+            )
+            .await?
+        }
+    };
+
+    let mut required_fields = cards::get_fields_required_by_all_connectors(
+        &state.conf.required_fields,
+        payment_method,
+        payment_method_type,
+        &connectors,
+        Some(data.is_mandate),
+    );
+    if data.is_moto {
+        required_fields.retain(|field| field != "device_session_id");
+    }
+    if required_fields.is_empty() {
+        return Ok(());
+    }
+
+    let mut request_value = serde_json::to_value(request)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the payment request")?;
+    if let Some(request_object) = request_value.as_object_mut() {
+        for (key, address) in [
+            (
+                "billing",
+                data.billing_address
+                    .map(api::Address::from)
+                    .or_else(|| data.request_billing.cloned()),
+            ),
+            ("shipping", data.shipping_address.map(api::Address::from)),
+        ] {
+            if let Some(address) = address {
+                request_object.insert(
+                    key.to_string(),
+                    serde_json::to_value(address)
+                        .into_report()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to serialize the address of the payment")?,
+                );
+            }
+        }
+
+        let mut email = data
+            .customer_details
+            .email
+            .as_ref()
+            .map(|email| email.peek().clone());
+        let mut name = data
+            .customer_details
+            .name
+            .as_ref()
+            .map(|name| name.peek().clone());
+        if let Some(customer_id) = data
+            .customer_id
+            .filter(|_| email.is_none() || name.is_none())
+        {
+            if let Some(customer) = state
+                .store
+                .find_customer_optional_by_customer_id_merchant_id(
+                    customer_id,
+                    &merchant_account.merchant_id,
+                    key_store,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?
+            {
+                email = email.or_else(|| {
+                    customer
+                        .email
+                        .as_ref()
+                        .map(|email| email.get_inner().peek().clone())
+                });
+                name = name.or_else(|| {
+                    customer
+                        .name
+                        .as_ref()
+                        .map(|name| name.get_inner().peek().clone())
+                });
+            }
+        }
+        let device_session_id = request
+            .device_session_id
+            .as_ref()
+            .or(data.device_session_id)
+            .cloned();
+        for (key, value) in [
+            ("email", email),
+            ("name", name),
+            ("device_session_id", device_session_id),
+        ] {
+            if let Some(value) = value {
+                request_object.insert(key.to_string(), serde_json::Value::String(value));
+            }
+        }
+    }
+
+    collect_missing_required_fields(
+        &required_fields,
+        &request_value,
+        request.payment_method_data.is_some(),
+        validation,
+    );
+    Ok(())
+}
+
+/// Records the required fields which are missing from the request. The fields of the payment method
+/// data are not required when the payment is made with a token instead, nor is the verification
+/// code of saved cards, which only some of the connectors accept.
+fn collect_missing_required_fields(
+    required_fields: &[String],
+    request_value: &serde_json::Value,
+    has_payment_method_data: bool,
+    validation: &mut errors::validation::ValidationErrors,
+) {
+    for field in required_fields {
+        if field == "card_cvc"
+            || (!has_payment_method_data && field.starts_with("payment_method_data."))
+        {
+            continue;
+        }
+        if !cards::is_required_field_present(field, request_value) {
+            validation.push_missing_field(field.as_str());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
 
     use super::*;
 
-    #[test]
-    fn test_authenticate_client_secret_fulfillment_time_not_expired() {
-        let payment_intent = PaymentIntent {
+    /// Payment awaiting confirmation, to be overridden with the fields under test
+    fn get_payment_intent(payment_id: &str) -> PaymentIntent {
+        PaymentIntent {
             id: 21,
-            payment_id: "23".to_string(),
-            merchant_id: "22".to_string(),
-            status: storage_enums::IntentStatus::RequiresCapture,
-            amount: 200,
-            currency: None,
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            status: storage_enums::IntentStatus::RequiresConfirmation,
+            amount: 10000,
+            currency: Some(storage_enums::Currency::USD),
             amount_captured: None,
             customer_id: None,
             description: None,
@@ -2368,8 +3684,8 @@ mod tests {
             last_synced: None,
             setup_future_usage: None,
             off_session: None,
-            client_secret: Some("1".to_string()),
-            active_attempt_id: "nopes".to_string(),
+            client_secret: None,
+            active_attempt_id: format!("{payment_id}_1"),
             business_country: None,
             business_label: None,
             order_details: None,
@@ -2380,13 +3696,82 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
-        };
-        let req_cs = Some("1".to_string());
-        let merchant_fulfillment_time = Some(900);
-        assert!(authenticate_client_secret(
-            req_cs.as_ref(),
-            &payment_intent,
-            merchant_fulfillment_time
+            session_expiry: None,
+            applied_defaults: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            merchant_order_reference_id: None,
+            connector_request_reference_format: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: None,
+            archived_at: None,
+            device_session_id: None,
+            payment_channel: None,
+            suspected_duplicate_of: None,
+            livemode: true,
+        }
+    }
+
+    #[test]
+    fn test_authenticate_client_secret_fulfillment_time_not_expired() {
+        let payment_intent = PaymentIntent {
+            id: 21,
+            payment_id: "23".to_string(),
+            merchant_id: "22".to_string(),
+            status: storage_enums::IntentStatus::RequiresCapture,
+            amount: 200,
+            currency: None,
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: Some("1".to_string()),
+            active_attempt_id: "nopes".to_string(),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            profile_id: None,
+            merchant_decision: None,
+            payment_confirm_source: None,
+            session_expiry: None,
+            applied_defaults: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            merchant_order_reference_id: None,
+            connector_request_reference_format: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: None,
+            archived_at: None,
+            device_session_id: None,
+            payment_channel: None,
+            suspected_duplicate_of: None,
+            livemode: true,
+        };
+        let req_cs = Some("1".to_string());
+        let merchant_fulfillment_time = Some(900);
+        assert!(authenticate_client_secret(
+            req_cs.as_ref(),
+            &payment_intent,
+            merchant_fulfillment_time
         )
         .is_ok()); // Check if the result is an Ok variant
     }
@@ -2427,6 +3812,21 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
+            session_expiry: None,
+            applied_defaults: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            merchant_order_reference_id: None,
+            connector_request_reference_format: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: None,
+            archived_at: None,
+            device_session_id: None,
+            payment_channel: None,
+            suspected_duplicate_of: None,
+            livemode: true,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(10);
@@ -2474,6 +3874,21 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
+            session_expiry: None,
+            applied_defaults: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            merchant_order_reference_id: None,
+            connector_request_reference_format: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: None,
+            archived_at: None,
+            device_session_id: None,
+            payment_channel: None,
+            suspected_duplicate_of: None,
+            livemode: true,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(10);
@@ -2484,6 +3899,804 @@ mod tests {
         )
         .is_err())
     }
+
+    #[test]
+    fn test_validate_session_expiry() {
+        assert!(validate_session_expiry(consts::MIN_SESSION_EXPIRY).is_ok());
+        assert!(validate_session_expiry(consts::MAX_SESSION_EXPIRY).is_ok());
+        assert!(validate_session_expiry(consts::MIN_SESSION_EXPIRY - 1).is_err());
+        assert!(validate_session_expiry(consts::MAX_SESSION_EXPIRY + 1).is_err());
+    }
+
+    #[test]
+    fn test_get_session_expiry() {
+        let created_at = common_utils::date_time::now();
+        assert_eq!(
+            get_session_expiry(created_at, Some(900), Some(3600)),
+            Some(created_at.saturating_add(Duration::seconds(900)))
+        );
+        assert_eq!(
+            get_session_expiry(created_at, None, Some(3600)),
+            Some(created_at.saturating_add(Duration::seconds(3600)))
+        );
+        assert_eq!(get_session_expiry(created_at, None, None), None);
+    }
+
+    #[test]
+    fn test_payment_defaults_are_resolved_in_order() {
+        let request = PaymentDefaults {
+            description: Some("request description".to_string()),
+            ..Default::default()
+        };
+        let profile_defaults = PaymentDefaults {
+            return_url: Some("https://profile.example.com/return".to_string()),
+            description: Some("profile description".to_string()),
+            statement_descriptor_name: Some("PROFILE".to_string()),
+            statement_descriptor_suffix: None,
+            currency: None,
+            billing_country: Some(api_enums::CountryAlpha2::DE),
+        };
+        let merchant_defaults = PaymentDefaults {
+            return_url: Some("https://merchant.example.com/return".to_string()),
+            currency: Some(api_enums::Currency::EUR),
+            billing_country: Some(api_enums::CountryAlpha2::US),
+            ..Default::default()
+        };
+
+        let (resolved, applied_defaults) =
+            PaymentDefaults::resolve(request, profile_defaults, merchant_defaults);
+
+        assert_eq!(
+            resolved,
+            PaymentDefaults {
+                return_url: Some("https://profile.example.com/return".to_string()),
+                description: Some("request description".to_string()),
+                statement_descriptor_name: Some("PROFILE".to_string()),
+                statement_descriptor_suffix: None,
+                currency: Some(api_enums::Currency::EUR),
+                billing_country: Some(api_enums::CountryAlpha2::DE),
+            }
+        );
+        assert_eq!(
+            applied_defaults,
+            vec![
+                api_enums::PaymentDefaultField::ReturnUrl,
+                api_enums::PaymentDefaultField::StatementDescriptorName,
+                api_enums::PaymentDefaultField::Currency,
+                api_enums::PaymentDefaultField::BillingCountry,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merchant_return_url_is_used_when_profile_has_none() {
+        // The connector reads `router_data.return_url` from `payment_intent.return_url`, which is
+        // populated from the resolved value
+        let request = PaymentDefaults::from(&api::PaymentsRequest::default());
+        let merchant_defaults = PaymentDefaults {
+            return_url: Some("https://merchant.example.com/return".to_string()),
+            ..Default::default()
+        };
+
+        let (resolved, applied_defaults) =
+            PaymentDefaults::resolve(request, PaymentDefaults::default(), merchant_defaults);
+
+        assert_eq!(
+            resolved.return_url.as_deref(),
+            Some("https://merchant.example.com/return")
+        );
+        assert_eq!(
+            applied_defaults,
+            vec![api_enums::PaymentDefaultField::ReturnUrl]
+        );
+    }
+
+    #[test]
+    fn test_no_defaults_are_applied_when_request_has_all_fields() {
+        let request = PaymentDefaults {
+            return_url: Some("https://request.example.com/return".to_string()),
+            description: Some("request description".to_string()),
+            statement_descriptor_name: Some("REQUEST".to_string()),
+            statement_descriptor_suffix: Some("SUFFIX".to_string()),
+            currency: Some(api_enums::Currency::USD),
+            billing_country: Some(api_enums::CountryAlpha2::US),
+        };
+        let profile_defaults = PaymentDefaults {
+            return_url: Some("https://profile.example.com/return".to_string()),
+            description: Some("profile description".to_string()),
+            statement_descriptor_name: Some("PROFILE".to_string()),
+            statement_descriptor_suffix: Some("PROFILE_SUFFIX".to_string()),
+            currency: Some(api_enums::Currency::EUR),
+            billing_country: Some(api_enums::CountryAlpha2::DE),
+        };
+
+        let (resolved, applied_defaults) = PaymentDefaults::resolve(
+            request.clone(),
+            profile_defaults,
+            PaymentDefaults::default(),
+        );
+
+        assert_eq!(resolved, request);
+        assert!(applied_defaults.is_empty());
+    }
+
+    #[test]
+    fn test_validate_session_expiry_not_reached() {
+        let mut payment_intent = PaymentIntent {
+            created_at: common_utils::date_time::now().saturating_sub(Duration::seconds(120)),
+            session_expiry: Some(
+                common_utils::date_time::now().saturating_add(Duration::seconds(60)),
+            ),
+            ..get_payment_intent("pay_session")
+        };
+        assert!(validate_session_expiry_not_reached(&payment_intent).is_ok());
+
+        payment_intent.session_expiry =
+            Some(common_utils::date_time::now().saturating_sub(Duration::seconds(60)));
+        assert!(matches!(
+            validate_session_expiry_not_reached(&payment_intent),
+            Err(errors::ApiErrorResponse::PaymentSessionExpired { .. })
+        ));
+    }
+    fn get_mandate(
+        mandate_type: storage_enums::MandateType,
+        amount_captured: Option<i64>,
+    ) -> storage::Mandate {
+        storage::Mandate {
+            id: 1,
+            mandate_id: "man_1".to_string(),
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            payment_method_id: "pm_1".to_string(),
+            mandate_status: storage_enums::MandateStatus::Active,
+            mandate_type,
+            customer_accepted_at: None,
+            customer_ip_address: None,
+            customer_user_agent: None,
+            network_transaction_id: None,
+            previous_attempt_id: None,
+            created_at: common_utils::date_time::now(),
+            mandate_amount: Some(1000),
+            mandate_currency: Some(storage_enums::Currency::USD),
+            amount_captured,
+            connector: "stripe".to_string(),
+            connector_mandate_id: None,
+            start_date: None,
+            end_date: None,
+            metadata: None,
+            connector_mandate_ids: None,
+            consecutive_failure_count: 0,
+            frequency: None,
+            max_charges_per_period: None,
+            charge_count: amount_captured.map_or(0, |_| 1),
+            period_charge_count: 0,
+            period_started_at: None,
+            merchant_connector_id: None,
+        }
+    }
+
+    /// A mandate of 1000 per month, charged once in November 2023
+    fn get_monthly_mandate() -> storage::Mandate {
+        storage::Mandate {
+            frequency: Some(storage_enums::MandateFrequency::Monthly),
+            max_charges_per_period: Some(1),
+            period_charge_count: 1,
+            period_started_at: Some(time::macros::datetime!(2023-11-01 00:00:00)),
+            ..get_mandate(storage_enums::MandateType::MultiUse, Some(1000))
+        }
+    }
+
+    #[test]
+    fn test_verify_multi_use_mandate_within_limit() {
+        let mandate = get_mandate(storage_enums::MandateType::MultiUse, Some(600));
+        assert!(verify_mandate_details(400, storage_enums::Currency::USD, mandate).is_ok());
+    }
+
+    #[test]
+    fn test_verify_multi_use_mandate_exceeding_limit() {
+        let mandate = get_mandate(storage_enums::MandateType::MultiUse, Some(600));
+        let result = verify_mandate_details(500, storage_enums::Currency::USD, mandate);
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::MandateValidationFailed { reason })
+                if reason == "request amount is greater than mandate amount"
+        ));
+    }
+
+    #[test]
+    fn test_verify_consumed_single_use_mandate() {
+        let mandate = get_mandate(storage_enums::MandateType::SingleUse, None);
+        assert!(verify_mandate_details(1000, storage_enums::Currency::USD, mandate).is_ok());
+
+        let mandate = get_mandate(storage_enums::MandateType::SingleUse, Some(1000));
+        let result = verify_mandate_details(1000, storage_enums::Currency::USD, mandate);
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::MandateValidationFailed { reason })
+                if reason == "single use mandate has already been used"
+        ));
+    }
+
+    #[test]
+    fn test_verify_second_charge_in_same_period_rejected() {
+        let mandate = get_monthly_mandate();
+        let result = verify_recurring_mandate_charge(
+            1000,
+            storage_enums::Currency::USD,
+            &mandate,
+            false,
+            time::macros::datetime!(2023-11-20 10:00:00),
+        );
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::MandateChargeLimitExceeded {
+                next_charge_allowed_at,
+                ..
+            }) if next_charge_allowed_at == Some(time::macros::datetime!(2023-12-01 00:00:00))
+        ));
+    }
+
+    #[test]
+    fn test_verify_charge_in_next_period_allowed() {
+        let mandate = get_monthly_mandate();
+        assert!(verify_recurring_mandate_charge(
+            1000,
+            storage_enums::Currency::USD,
+            &mandate,
+            false,
+            time::macros::datetime!(2023-12-01 00:00:00),
+        )
+        .is_ok());
+
+        // Every charge is limited to the mandate amount
+        let result = verify_recurring_mandate_charge(
+            1001,
+            storage_enums::Currency::USD,
+            &mandate,
+            false,
+            time::macros::datetime!(2023-12-01 00:00:00),
+        );
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::MandateValidationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_mandate_limits_override() {
+        let mandate = get_monthly_mandate();
+        let now = time::macros::datetime!(2023-11-20 10:00:00);
+        assert!(verify_recurring_mandate_charge(
+            1500,
+            storage_enums::Currency::USD,
+            &mandate,
+            true,
+            now
+        )
+        .is_ok());
+
+        // The currency of the mandate is enforced even when the limits are overridden
+        let result = verify_recurring_mandate_charge(
+            1000,
+            storage_enums::Currency::EUR,
+            &mandate,
+            true,
+            now,
+        );
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::MandateValidationFailed { reason })
+                if reason == "cross currency mandates not supported"
+        ));
+    }
+
+    fn get_connector_mandate_ids() -> Option<api_models::payments::MandateIds> {
+        Some(api_models::payments::MandateIds {
+            mandate_id: "mandate_1".to_string(),
+            mandate_reference_id: Some(
+                api_models::payments::MandateReferenceId::ConnectorMandateId(
+                    api_models::payments::ConnectorMandateReferenceId {
+                        connector_mandate_id: Some("pm_stripe_1".to_string()),
+                        payment_method_id: None,
+                    },
+                ),
+            ),
+        })
+    }
+
+    #[test]
+    fn test_connector_mandate_charged_with_vaulted_card_on_different_connector() {
+        let card = api::PaymentMethodData::Card(api::Card::default());
+        let mandate_ids = get_mandate_ids_for_connector(
+            get_connector_mandate_ids(),
+            Some("stripe"),
+            "adyen",
+            Some(&card),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(mandate_ids.mandate_id, "mandate_1");
+        assert!(mandate_ids.mandate_reference_id.is_none());
+    }
+
+    #[test]
+    fn test_connector_mandate_kept_on_same_connector() {
+        let card = api::PaymentMethodData::Card(api::Card::default());
+        let mandate_ids = get_mandate_ids_for_connector(
+            get_connector_mandate_ids(),
+            Some("stripe"),
+            "stripe",
+            Some(&card),
+        )
+        .unwrap();
+        assert_eq!(mandate_ids, get_connector_mandate_ids());
+    }
+
+    #[test]
+    fn test_network_mandate_kept_on_different_connector() {
+        let mandate_ids = Some(api_models::payments::MandateIds {
+            mandate_id: "mandate_1".to_string(),
+            mandate_reference_id: Some(api_models::payments::MandateReferenceId::NetworkMandateId(
+                "network_txn_1".to_string(),
+            )),
+        });
+        let result =
+            get_mandate_ids_for_connector(mandate_ids.clone(), Some("stripe"), "adyen", None)
+                .unwrap();
+        assert_eq!(result, mandate_ids);
+    }
+
+    #[test]
+    fn test_connector_mandate_without_vaulted_card_on_different_connector() {
+        let result = get_mandate_ids_for_connector(
+            get_connector_mandate_ids(),
+            Some("stripe"),
+            "adyen",
+            None,
+        );
+        assert!(matches!(
+            result.map_err(|error| error.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_locale() {
+        assert!(validate_locale("fr-FR").is_ok());
+        assert!(validate_locale("en").is_ok());
+        assert!(validate_locale("zh-Hant-TW").is_ok());
+        assert!(matches!(
+            validate_locale("fr_FR"),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { .. })
+        ));
+        assert!(validate_locale("").is_err());
+        assert!(validate_locale("f-FR").is_err());
+        assert!(validate_locale("fr-").is_err());
+    }
+
+    #[test]
+    fn test_get_locale_fallback_order() {
+        assert_eq!(
+            get_locale(Some("fr-FR"), Some("de-DE"), Some("es")),
+            "fr-FR"
+        );
+        assert_eq!(get_locale(None, Some("de-DE"), Some("es")), "de-DE");
+        assert_eq!(get_locale(None, Some("de_DE"), Some("es")), "es");
+        assert_eq!(get_locale(None, None, None), consts::DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn test_validate_order_shipping_details() {
+        let order_line = |product_type| api_models::payments::OrderDetailsWithAmount {
+            product_name: "item".to_string(),
+            quantity: 1,
+            amount: 100,
+            product_type: Some(product_type),
+            commodity_code: None,
+        };
+        let digital_order = api::PaymentsRequest {
+            order_category: Some("software".to_string()),
+            shipping_method: Some(api_enums::ShippingMethod::Digital),
+            order_details: Some(vec![order_line(api_enums::ProductType::Digital)]),
+            ..Default::default()
+        };
+        assert!(validate_order_shipping_details(&digital_order).is_ok());
+
+        let mixed_order = api::PaymentsRequest {
+            order_details: Some(vec![
+                order_line(api_enums::ProductType::Digital),
+                order_line(api_enums::ProductType::Physical),
+            ]),
+            ..digital_order.clone()
+        };
+        assert!(validate_order_shipping_details(&mixed_order).is_err());
+        assert!(validate_order_shipping_details(&api::PaymentsRequest {
+            shipping_method: Some(api_enums::ShippingMethod::Express),
+            ..mixed_order
+        })
+        .is_ok());
+
+        for order_category in [" ".to_string(), "a".repeat(256)] {
+            assert!(validate_order_shipping_details(&api::PaymentsRequest {
+                order_category: Some(order_category),
+                ..digital_order.clone()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_merchant_category_code() {
+        assert!(validate_merchant_category_code("5411").is_ok());
+        assert!(validate_merchant_category_code("0742").is_ok());
+        assert!(validate_merchant_category_code("9399").is_ok());
+        assert!(validate_merchant_category_code("541").is_err());
+        assert!(validate_merchant_category_code("54111").is_err());
+        assert!(validate_merchant_category_code("54a1").is_err());
+        // Codes reserved for ISO use
+        assert!(validate_merchant_category_code("0001").is_err());
+        assert!(validate_merchant_category_code("1200").is_err());
+    }
+
+    #[test]
+    fn test_validate_card_cvc() {
+        let card_cvc = |cvc: &str| masking::Secret::new(cvc.to_string());
+
+        assert!(validate_card_cvc(&card_cvc("737")).is_ok());
+        assert!(validate_card_cvc(&card_cvc("1234")).is_ok());
+        assert!(validate_card_cvc(&card_cvc("12")).is_err());
+        assert!(validate_card_cvc(&card_cvc("12345")).is_err());
+        assert!(validate_card_cvc(&card_cvc("7a7")).is_err());
+    }
+
+    #[test]
+    fn test_should_pass_sub_merchant_data() {
+        let mut config = SubMerchantConfig {
+            supported_connectors: std::collections::HashSet::from([api_enums::Connector::Adyen]),
+            unsupported_connector_behaviour: UnsupportedSubMerchantBehaviour::Ignore,
+        };
+        assert!(should_pass_sub_merchant_data(&config, "adyen").unwrap());
+        assert!(!should_pass_sub_merchant_data(&config, "stripe").unwrap());
+
+        config.unsupported_connector_behaviour = UnsupportedSubMerchantBehaviour::Reject;
+        assert!(should_pass_sub_merchant_data(&config, "adyen").unwrap());
+        assert!(matches!(
+            should_pass_sub_merchant_data(&config, "stripe")
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::NotSupported { .. }
+        ));
+    }
+
+    #[test]
+    fn test_capture_is_rejected_once_authorization_expires() {
+        let authorized_at = common_utils::date_time::now();
+        // Validity shortened to a minute
+        let authorization_expires_at = authorized_at + Duration::seconds(60);
+
+        assert!(!is_authorization_expired(
+            storage_enums::AttemptStatus::Authorized,
+            Some(authorization_expires_at),
+            authorized_at + Duration::seconds(59),
+        ));
+        assert!(is_authorization_expired(
+            storage_enums::AttemptStatus::Authorized,
+            Some(authorization_expires_at),
+            authorization_expires_at,
+        ));
+        assert!(is_authorization_expired(
+            storage_enums::AttemptStatus::AuthorizationExpired,
+            Some(authorization_expires_at),
+            authorized_at,
+        ));
+        assert!(!is_authorization_expired(
+            storage_enums::AttemptStatus::Authorized,
+            None,
+            authorized_at,
+        ));
+    }
+
+    fn get_split_payment_request(
+        platform_fee: api_models::payments::PlatformFee,
+    ) -> api_models::payments::SplitPaymentRequest {
+        api_models::payments::SplitPaymentRequest {
+            platform_fee,
+            destination_account: "acct_1032D82eZvKYlo2C".to_string(),
+            fee_refund_behaviour: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_amount_details() {
+        let line_items = vec![
+            api_models::payments::OrderDetailsWithAmount {
+                product_name: "printer paper".to_string(),
+                quantity: 2,
+                amount: 1000,
+                ..Default::default()
+            },
+            api_models::payments::OrderDetailsWithAmount {
+                product_name: "toner".to_string(),
+                quantity: 1,
+                amount: 4000,
+                ..Default::default()
+            },
+        ];
+        let amount_details = api_models::payments::AmountDetails {
+            tax_amount: Some(480),
+            shipping_cost: Some(500),
+            duty_amount: None,
+            discount_amount: Some(200),
+            allow_rounding_difference: false,
+        };
+
+        assert!(validate_amount_details(&amount_details, 6780, Some(&line_items)).is_ok());
+        assert!(validate_amount_details(&amount_details, 6781, Some(&line_items)).is_err());
+
+        // The rounding of the taxes of the line items is tolerated when allowed
+        let rounded_amount_details = api_models::payments::AmountDetails {
+            allow_rounding_difference: true,
+            ..amount_details.clone()
+        };
+        assert!(validate_amount_details(&rounded_amount_details, 6782, Some(&line_items)).is_ok());
+        assert!(validate_amount_details(&rounded_amount_details, 6783, Some(&line_items)).is_err());
+
+        // Without line items the breakdown must not exceed the amount of the payment
+        assert!(validate_amount_details(&amount_details, 780, None).is_ok());
+        assert!(validate_amount_details(&amount_details, 779, None).is_err());
+
+        let negative_amount_details = api_models::payments::AmountDetails {
+            tax_amount: Some(-480),
+            ..amount_details
+        };
+        assert!(matches!(
+            validate_amount_details(&negative_amount_details, 6780, Some(&line_items))
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "amount_details.tax_amount"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_amount_details_not_adding_up_are_dropped_for_the_connector() {
+        let amount_details = api_models::payments::AmountDetails {
+            tax_amount: Some(80),
+            shipping_cost: Some(500),
+            ..Default::default()
+        };
+        let payment_intent = PaymentIntent {
+            amount: 1580,
+            amount_details: Some(serde_json::to_value(&amount_details).unwrap()),
+            ..get_payment_intent("pay_level_3")
+        };
+
+        assert_eq!(
+            get_amount_details_for_connector(&payment_intent, 1580, None),
+            Some(amount_details)
+        );
+        // The amount of the payment was updated below the breakdown after it was created
+        assert_eq!(
+            get_amount_details_for_connector(&payment_intent, 500, None),
+            None
+        );
+        // Payments without a breakdown are processed as before
+        assert_eq!(
+            get_amount_details_for_connector(
+                &PaymentIntent {
+                    amount_details: None,
+                    ..payment_intent
+                },
+                1580,
+                None
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_split_payment_details() {
+        let split_payment = get_split_payment_details(
+            &get_split_payment_request(api_models::payments::PlatformFee::Amount { amount: 250 }),
+            10000,
+        )
+        .unwrap();
+        assert_eq!(split_payment.platform_fee_amount, 250);
+        assert_eq!(
+            split_payment.fee_refund_behaviour,
+            api_enums::PlatformFeeRefundBehaviour::Proportional
+        );
+
+        // 2.5% of 1099 is 27.475, rounded half up
+        let split_payment = get_split_payment_details(
+            &get_split_payment_request(api_models::payments::PlatformFee::Percentage {
+                basis_points: 250,
+            }),
+            1099,
+        )
+        .unwrap();
+        assert_eq!(split_payment.platform_fee_amount, 27);
+
+        for platform_fee in [
+            api_models::payments::PlatformFee::Amount { amount: 10000 },
+            api_models::payments::PlatformFee::Amount { amount: -1 },
+            api_models::payments::PlatformFee::Percentage {
+                basis_points: 10000,
+            },
+            api_models::payments::PlatformFee::Percentage {
+                basis_points: 10001,
+            },
+        ] {
+            assert!(
+                get_split_payment_details(&get_split_payment_request(platform_fee), 10000).is_err()
+            );
+        }
+
+        let mut split_payment_request =
+            get_split_payment_request(api_models::payments::PlatformFee::Amount { amount: 250 });
+        split_payment_request.destination_account = " ".to_string();
+        assert!(get_split_payment_details(&split_payment_request, 10000).is_err());
+    }
+
+    #[test]
+    fn test_split_payment_is_rejected_on_unsupported_connector() {
+        let split_payment = get_split_payment_details(
+            &get_split_payment_request(api_models::payments::PlatformFee::Amount { amount: 250 }),
+            10000,
+        )
+        .unwrap();
+        let mut payment_intent = PaymentIntent {
+            split_payment: Some(serde_json::to_value(&split_payment).unwrap()),
+            ..get_payment_intent("pay_split")
+        };
+
+        assert!(
+            validate_split_payment_support(&payment_intent, api_enums::Connector::Stripe).is_ok()
+        );
+        assert!(matches!(
+            validate_split_payment_support(&payment_intent, api_enums::Connector::Adyen)
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::FlowNotSupported { .. }
+        ));
+
+        // The amount was updated below the fee after the payment was created
+        payment_intent.amount = 200;
+        assert!(
+            validate_split_payment_support(&payment_intent, api_enums::Connector::Stripe).is_err()
+        );
+
+        payment_intent.split_payment = None;
+        assert!(
+            validate_split_payment_support(&payment_intent, api_enums::Connector::Adyen).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_payment_without_device_session_is_rejected_on_connector_requiring_it() {
+        let mut payment_intent = get_payment_intent("pay_device");
+
+        assert!(validate_device_session("stripe", &payment_intent).is_ok());
+        assert!(matches!(
+            validate_device_session("cybersource", &payment_intent)
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+
+        // Mail order and telephone order payments are not collected device data for
+        payment_intent.payment_channel = Some(storage_enums::PaymentChannel::MailOrder);
+        assert!(validate_device_session("cybersource", &payment_intent).is_ok());
+
+        payment_intent.payment_channel = None;
+        payment_intent.device_session_id = Some("dds_1".to_string());
+        assert!(validate_device_session("cybersource", &payment_intent).is_ok());
+    }
+
+    #[test]
+    fn test_platform_fee_refund_amount() {
+        let mut split_payment = api_models::payments::SplitPaymentDetails {
+            platform_fee_amount: 250,
+            destination_account: "acct_1032D82eZvKYlo2C".to_string(),
+            fee_refund_behaviour: api_enums::PlatformFeeRefundBehaviour::Proportional,
+        };
+
+        assert_eq!(
+            get_platform_fee_refund_amount(&split_payment, 10000, 10000),
+            250
+        );
+        assert_eq!(
+            get_platform_fee_refund_amount(&split_payment, 10000, 2500),
+            62
+        );
+
+        split_payment.fee_refund_behaviour = api_enums::PlatformFeeRefundBehaviour::Retain;
+        assert_eq!(
+            get_platform_fee_refund_amount(&split_payment, 10000, 10000),
+            0
+        );
+    }
+
+    #[test]
+    fn test_all_missing_required_fields_are_reported() {
+        let request: api::PaymentsRequest = serde_json::from_value(serde_json::json!({
+            "amount": 6540,
+            "currency": "USD",
+            "confirm": true,
+            "payment_method": "card",
+            "payment_method_type": "debit",
+            "payment_method_data": {
+                "card": {
+                    "card_number": "4111111111111111",
+                    "card_exp_month": "03",
+                    "card_exp_year": "2030",
+                    "card_holder_name": "Joseph Doe",
+                    "card_cvc": "737"
+                }
+            },
+            "billing": {
+                "address": {
+                    "last_name": "Doe",
+                    "line1": "1467 Harrison Street",
+                    "city": "San Fransico",
+                    "state": "California",
+                    "zip": "94122"
+                }
+            }
+        }))
+        .unwrap();
+        let required_fields = cards::get_fields_required_by_all_connectors(
+            &crate::configs::settings::RequiredFields::default(),
+            api_enums::PaymentMethod::Card,
+            api_enums::PaymentMethodType::Debit,
+            &[api_enums::Connector::Cybersource],
+            Some(false),
+        );
+
+        let mut validation = errors::validation::ValidationErrors::new();
+        collect_missing_required_fields(
+            &required_fields,
+            &serde_json::to_value(&request).unwrap(),
+            request.payment_method_data.is_some(),
+            &mut validation,
+        );
+
+        let error = validation.into_result().unwrap_err();
+        let missing_fields = match error.current_context() {
+            errors::ApiErrorResponse::ValidationFailed { errors } => errors
+                .iter()
+                .map(|error| error.field.clone())
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            missing_fields,
+            vec![
+                "billing.address.country".to_string(),
+                "billing.address.first_name".to_string(),
+                "email".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_payment_method_data_fields_are_not_required_for_token_payments() {
+        let required_fields = vec![
+            "payment_method_data.card.card_number".to_string(),
+            "card_cvc".to_string(),
+            "email".to_string(),
+        ];
+
+        let mut validation = errors::validation::ValidationErrors::new();
+        collect_missing_required_fields(
+            &required_fields,
+            &serde_json::json!({ "email": "guest@example.com" }),
+            false,
+            &mut validation,
+        );
+        assert!(validation.is_empty());
+    }
 }
 
 // This function will be removed after moving this functionality to server_wrap and using cache instead of config
@@ -2541,6 +4754,17 @@ impl MerchantConnectorAccountType {
         }
     }
 
+    /// Outbound proxy configured in the connector account details, an invalid proxy is ignored
+    pub fn get_connector_proxy(&self) -> Option<ConnectorProxy> {
+        let connector_account_details = match self {
+            Self::DbVal(val) => val.connector_account_details.peek(),
+            Self::CacheVal(val) => val.connector_account_details.peek(),
+        };
+        services::connector_proxy::get_merchant_connector_proxy_or_default(
+            connector_account_details,
+        )
+    }
+
     pub fn is_disabled(&self) -> bool {
         match self {
             Self::DbVal(ref inner) => inner.disabled.unwrap_or(false),
@@ -2556,10 +4780,88 @@ impl MerchantConnectorAccountType {
             Self::CacheVal(_) => None,
         }
     }
+
+    pub fn get_mca_id(&self) -> Option<String> {
+        match self {
+            Self::DbVal(val) => Some(val.merchant_connector_id.clone()),
+            Self::CacheVal(_) => None,
+        }
+    }
+
+    pub fn is_flow_disabled(&self, flow: api_enums::ConnectorFlow) -> bool {
+        match self {
+            Self::DbVal(val) => val
+                .disabled_flows
+                .as_ref()
+                .map_or(false, |disabled_flows| disabled_flows.contains(&flow)),
+            // Connector details passed in the request have no flows disabled
+            Self::CacheVal(_) => false,
+        }
+    }
+}
+
+/// Rejects the flow before the connector is called when it is disabled for the merchant connector
+/// account
+pub fn validate_connector_flow_enabled(
+    merchant_connector_account: &MerchantConnectorAccountType,
+    connector_name: &str,
+    flow: api_enums::ConnectorFlow,
+) -> RouterResult<()> {
+    utils::when(merchant_connector_account.is_flow_disabled(flow), || {
+        Err(report!(
+            errors::ApiErrorResponse::FlowDisabledForConnector {
+                flow,
+                connector: connector_name.to_string(),
+                merchant_connector_id: merchant_connector_account.get_mca_id().unwrap_or_default(),
+            }
+        ))
+    })
+}
+
+fn get_mode_name(livemode: bool) -> &'static str {
+    if livemode {
+        "live"
+    } else {
+        "test"
+    }
+}
+
+/// Rejects the payment before the connector is called when the merchant connector account is not
+/// in the mode of the payment. The payments created with test API keys are only processed by the
+/// accounts in test mode, the live payments are only restricted to the accounts not in test mode
+/// when configured. The accounts whose mode was never set accept the payments of either mode, and
+/// the connector details passed in the request are not validated.
+pub fn validate_connector_account_mode(
+    config: &LivemodeConfig,
+    merchant_connector_account: &MerchantConnectorAccountType,
+    livemode: bool,
+) -> RouterResult<()> {
+    let Some(merchant_connector_id) = merchant_connector_account.get_mca_id() else {
+        return Ok(());
+    };
+    let Some(is_connector_test_mode) = merchant_connector_account.is_test_mode_on() else {
+        return Ok(());
+    };
+    let is_mode_mismatch = if livemode {
+        is_connector_test_mode && config.restrict_live_payments_to_live_connectors
+    } else {
+        !is_connector_test_mode
+    };
+
+    utils::when(is_mode_mismatch, || {
+        Err(report!(
+            errors::ApiErrorResponse::ConnectorAccountModeMismatch {
+                payment_mode: get_mode_name(livemode),
+                connector_mode: get_mode_name(!is_connector_test_mode),
+                merchant_connector_id,
+            }
+        ))
+    })
 }
 
 /// Query for merchant connector account either by business label or profile id
 /// If profile_id is passed use it, or use connector_label to query merchant connector account
+/// When the payment method of a payment is passed, the account of the connector is selected for it
 #[instrument(skip_all)]
 pub async fn get_merchant_connector_account(
     state: &AppState,
@@ -2568,6 +4870,7 @@ pub async fn get_merchant_connector_account(
     key_store: &domain::MerchantKeyStore,
     profile_id: &String,
     connector_name: &str,
+    payment_method_details: Option<&connector_account_selection::PaymentMethodDetails>,
 ) -> RouterResult<MerchantConnectorAccountType> {
     let db = &*state.store;
     match creds_identifier {
@@ -2609,18 +4912,34 @@ pub async fn get_merchant_connector_account(
 
             Ok(MerchantConnectorAccountType::CacheVal(res))
         }
-        None => {
-            db.find_merchant_connector_account_by_profile_id_connector_name(
-                profile_id,
-                connector_name,
-                key_store,
-            )
-            .await
-            .to_not_found_response(
-                errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+        None => match payment_method_details {
+            Some(payment_method_details) => {
+                connector_account_selection::find_merchant_connector_account(
+                    db,
+                    key_store,
+                    profile_id,
+                    connector_name,
+                    payment_method_details,
+                )
+                .await?
+                .map(|(merchant_connector_account, _)| merchant_connector_account)
+                .ok_or(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
                     id: format!("profile id {profile_id} and connector name {connector_name}"),
-                },
-            )
+                })
+                .into_report()
+            }
+            None => db
+                .find_merchant_connector_account_by_profile_id_connector_name(
+                    profile_id,
+                    connector_name,
+                    key_store,
+                )
+                .await
+                .to_not_found_response(
+                    errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                        id: format!("profile id {profile_id} and connector name {connector_name}"),
+                    },
+                ),
         }
         .map(MerchantConnectorAccountType::DbVal),
     }
@@ -2674,6 +4993,11 @@ pub fn router_data_type_conversion<F1, F2, Req1, Req2, Res1, Res2>(
         connector_api_version: router_data.connector_api_version,
         connector_http_status_code: router_data.connector_http_status_code,
         apple_pay_flow: router_data.apple_pay_flow,
+        locale: router_data.locale,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: router_data.connector_proxy,
+        authorized_amount: None,
     }
 }
 
@@ -2715,7 +5039,9 @@ pub fn get_attempt_type(
                     | enums::AttemptStatus::Voided
                     | enums::AttemptStatus::AutoRefunded
                     | enums::AttemptStatus::PaymentMethodAwaited
-                    | enums::AttemptStatus::DeviceDataCollectionPending => {
+                    | enums::AttemptStatus::DeviceDataCollectionPending
+                    | enums::AttemptStatus::AuthorizationExpired
+                    | enums::AttemptStatus::PartiallyAuthorized => {
                         metrics::MANUAL_RETRY_VALIDATION_FAILED.add(
                             &metrics::CONTEXT,
                             1,
@@ -2865,6 +5191,35 @@ impl AttemptType {
             connector_response_reference_id: None,
             amount_capturable: old_payment_attempt.amount,
             surcharge_metadata: old_payment_attempt.surcharge_metadata,
+            // Externally performed authentication belongs to the previous attempt, it is provided again in the confirm request if required.
+            external_authentication_data: None,
+            card_verification_results: None,
+            // The 3DS decision is re-evaluated by the merchant's rules when the new attempt is confirmed
+            three_ds_decision_data: None,
+            // The shopper and so the locale remain the same across attempts
+            locale: old_payment_attempt.locale,
+            // Fees are reported by the connector for the new attempt
+            connector_fee_amount: None,
+            fee_currency: None,
+            net_amount: None,
+            // The sub-merchant on whose behalf the payment is made remains the same across attempts
+            merchant_descriptor: old_payment_attempt.merchant_descriptor,
+            sub_merchant_data: old_payment_attempt.sub_merchant_data,
+            // The references are returned by the connector for the new attempt
+            auth_code: None,
+            acquirer_reference_number: None,
+            // The authorization of the new attempt has its own expiry
+            authorized_at: None,
+            authorization_expires_at: None,
+            cancellation_reason_code: None,
+            // The attempts of a payment are all made in the mode of the payment
+            livemode: old_payment_attempt.livemode,
+            // The new attempt is routed again, as its payment method type can differ
+            routing_metadata: None,
+            // The merchant connector account of the new attempt is recorded when it is confirmed
+            merchant_connector_id: None,
+            authorized_amount: None,
+            redacted_at: None,
         }
     }
 
@@ -3002,7 +5357,9 @@ pub fn is_manual_retry_allowed(
             | enums::AttemptStatus::Voided
             | enums::AttemptStatus::AutoRefunded
             | enums::AttemptStatus::PaymentMethodAwaited
-            | enums::AttemptStatus::DeviceDataCollectionPending => {
+            | enums::AttemptStatus::DeviceDataCollectionPending
+            | enums::AttemptStatus::AuthorizationExpired
+            | enums::AttemptStatus::PartiallyAuthorized => {
                 logger::error!("Payment Attempt should not be in this state because Attempt to Intent status mapping doesn't allow it");
                 None
             }
@@ -3036,6 +5393,107 @@ pub fn is_manual_retry_allowed(
 #[cfg(test)]
 mod test {
     #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use masking::Secret;
+
+    use crate::db::MockDb;
+
+    const CARD_NUMBER: &str = "4111111111111111";
+
+    fn get_card(
+        card_network: Option<api_models::enums::CardNetwork>,
+    ) -> api_models::payments::Card {
+        api_models::payments::Card {
+            card_number: cards::CardNumber::from_str(CARD_NUMBER).unwrap(),
+            card_exp_month: Secret::new("03".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_holder_name: Secret::new("John Test".to_string()),
+            card_cvc: Secret::new("123".to_string()),
+            card_network,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_card_payment_method_data_echo_is_same_for_raw_card_and_token() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        // The merchant passes the network with the raw card, the card retrieved from the locker
+        // for a token has no network
+        let raw_card = api_models::payments::PaymentMethodData::Card(get_card(Some(
+            api_models::enums::CardNetwork::Visa,
+        )));
+        let saved_card = api_models::payments::PaymentMethodData::Card(get_card(None));
+
+        let raw_card_echo = api_models::payments::PaymentMethodDataResponse::from(
+            super::get_additional_payment_data(&raw_card, &db).await,
+        );
+        let saved_card_echo = api_models::payments::PaymentMethodDataResponse::from(
+            super::get_additional_payment_data(&saved_card, &db).await,
+        );
+
+        assert_eq!(raw_card_echo, saved_card_echo);
+        let api_models::payments::PaymentMethodDataResponse::Card(card) = saved_card_echo else {
+            panic!("Expected the echo of a card");
+        };
+        assert_eq!(
+            card.card_network,
+            Some(api_models::enums::CardNetwork::Visa)
+        );
+        assert_eq!(card.last4.as_deref(), Some("1111"));
+    }
+
+    #[tokio::test]
+    async fn test_payment_method_data_echo_does_not_include_card_number() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let card = api_models::payments::PaymentMethodData::Card(get_card(None));
+
+        let echo = api_models::payments::PaymentMethodDataResponse::from(
+            super::get_additional_payment_data(&card, &db).await,
+        );
+        let serialized_echo = serde_json::to_string(&echo).unwrap();
+
+        assert!(!serialized_echo.contains(CARD_NUMBER));
+        assert!(!serialized_echo.contains("123"));
+        assert!(serialized_echo.contains("\"last4\":\"1111\""));
+    }
+
+    fn get_external_authentication_data(
+        cavv: &str,
+        eci: &str,
+    ) -> api_models::payments::ExternalAuthenticationData {
+        api_models::payments::ExternalAuthenticationData {
+            cavv: Secret::new(cavv.to_string()),
+            eci: eci.to_string(),
+            ds_transaction_id: Some("f25084f0-5b16-4c0a-ae5d-b24808a95e4b".to_string()),
+            message_version: "2.2.0".to_string(),
+            threeds_server_transaction_id: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_external_authentication_data() {
+        let valid = get_external_authentication_data("AAABBEg0VhI0VniQEjRWAAAAAAA=", "05");
+        assert!(super::validate_external_authentication_data(&valid).is_ok());
+
+        let invalid_eci = get_external_authentication_data("AAABBEg0VhI0VniQEjRWAAAAAAA=", "5");
+        assert!(super::validate_external_authentication_data(&invalid_eci).is_err());
+
+        let non_numeric_eci =
+            get_external_authentication_data("AAABBEg0VhI0VniQEjRWAAAAAAA=", "0a");
+        assert!(super::validate_external_authentication_data(&non_numeric_eci).is_err());
+
+        let invalid_cavv = get_external_authentication_data("not base64!", "05");
+        assert!(super::validate_external_authentication_data(&invalid_cavv).is_err());
+
+        let short_cavv = get_external_authentication_data("AAABBEg0VhI0", "05");
+        assert!(super::validate_external_authentication_data(&short_cavv).is_err());
+    }
+
     #[test]
     fn test_client_secret_parse() {
         let client_secret1 = "pay_3TgelAms4RQec8xSStjF_secret_fc34taHLw1ekPgNh92qr";
@@ -3056,6 +5514,281 @@ mod test {
             super::get_payment_id_from_client_secret(client_secret3).unwrap()
         );
     }
+
+    fn get_merchant_connector_account(
+        disabled_flows: Option<Vec<api_models::enums::ConnectorFlow>>,
+        test_mode: Option<bool>,
+    ) -> super::MerchantConnectorAccountType {
+        super::MerchantConnectorAccountType::DbVal(get_domain_merchant_connector_account(
+            disabled_flows,
+            test_mode,
+        ))
+    }
+
+    fn get_domain_merchant_connector_account(
+        disabled_flows: Option<Vec<api_models::enums::ConnectorFlow>>,
+        test_mode: Option<bool>,
+    ) -> super::domain::MerchantConnectorAccount {
+        super::domain::MerchantConnectorAccount {
+            id: Some(1),
+            merchant_id: "merchant_1".to_string(),
+            connector_name: "stripe".to_string(),
+            connector_account_details: common_utils::crypto::Encryptable::new(
+                Secret::new(serde_json::json!({ "auth_type": "HeaderKey" })),
+                Secret::new(Vec::new()),
+            ),
+            test_mode,
+            disabled: None,
+            merchant_connector_id: "mca_1".to_string(),
+            payment_methods_enabled: None,
+            connector_type: api_models::enums::ConnectorType::PaymentProcessor,
+            metadata: None,
+            frm_configs: None,
+            connector_label: Some("stripe_US_default".to_string()),
+            business_country: None,
+            business_label: None,
+            business_sub_label: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            connector_webhook_details: None,
+            profile_id: Some("pro_1".to_string()),
+            applepay_verified_domains: None,
+            disabled_flows,
+            previous_credentials: None,
+        }
+    }
+
+    #[test]
+    fn test_disabled_flow_is_rejected_for_the_merchant_connector_account() {
+        use api_models::enums::ConnectorFlow;
+
+        let merchant_connector_account =
+            get_merchant_connector_account(Some(vec![ConnectorFlow::Refund]), None);
+
+        let error = super::validate_connector_flow_enabled(
+            &merchant_connector_account,
+            "stripe",
+            ConnectorFlow::Refund,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            super::errors::ApiErrorResponse::FlowDisabledForConnector {
+                flow: ConnectorFlow::Refund,
+                merchant_connector_id,
+                ..
+            } if merchant_connector_id == "mca_1"
+        ));
+
+        for flow in [ConnectorFlow::Capture, ConnectorFlow::Void] {
+            assert!(super::validate_connector_flow_enabled(
+                &merchant_connector_account,
+                "stripe",
+                flow
+            )
+            .is_ok());
+        }
+        assert!(!merchant_connector_account.is_disabled());
+        assert!(!get_merchant_connector_account(None, None).is_flow_disabled(ConnectorFlow::Refund));
+    }
+
+    #[test]
+    fn test_test_payment_is_rejected_for_live_merchant_connector_account() {
+        let config = super::LivemodeConfig::default();
+
+        let error = super::validate_connector_account_mode(
+            &config,
+            &get_merchant_connector_account(None, Some(false)),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            super::errors::ApiErrorResponse::ConnectorAccountModeMismatch {
+                payment_mode: "test",
+                connector_mode: "live",
+                merchant_connector_id,
+            } if merchant_connector_id == "mca_1"
+        ));
+        assert!(super::validate_connector_account_mode(
+            &config,
+            &get_merchant_connector_account(None, Some(true)),
+            false
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_live_payment_is_restricted_to_live_merchant_connector_account_when_configured() {
+        let test_mode_account = get_merchant_connector_account(None, Some(true));
+
+        assert!(super::validate_connector_account_mode(
+            &super::LivemodeConfig::default(),
+            &test_mode_account,
+            true
+        )
+        .is_ok());
+
+        let config = super::LivemodeConfig {
+            restrict_live_payments_to_live_connectors: true,
+            ..Default::default()
+        };
+        assert!(super::validate_connector_account_mode(&config, &test_mode_account, true).is_err());
+        assert!(super::validate_connector_account_mode(
+            &config,
+            &get_merchant_connector_account(None, Some(false)),
+            true
+        )
+        .is_ok());
+    }
+
+    async fn get_state_with_key_store(
+        settings: crate::configs::settings::Settings,
+    ) -> (crate::routes::AppState, super::domain::MerchantKeyStore) {
+        use crate::{
+            db::{merchant_key_store::MerchantKeyStoreInterface, MasterKeyInterface},
+            services,
+        };
+
+        let state = crate::routes::AppState::with_storage(
+            settings,
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                super::domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: super::domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+        (state, key_store)
+    }
+
+    #[tokio::test]
+    async fn test_payment_of_key_without_mode_is_accepted_by_account_without_mode() {
+        use crate::db::api_keys::ApiKeyInterface;
+
+        let mut settings = crate::configs::settings::Settings::default();
+        settings.api_keys.hash_key =
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string();
+        settings.livemode.restrict_live_payments_to_live_connectors = true;
+        let (state, _) = get_state_with_key_store(settings).await;
+
+        // Neither the key nor the connector account of the merchant is given a mode
+        crate::core::api_keys::create_api_key(
+            state.clone(),
+            #[cfg(feature = "kms")]
+            external_services::kms::get_kms_client(&state.conf.kms).await,
+            api_models::api_keys::CreateApiKeyRequest {
+                name: "Integration key".to_string(),
+                description: None,
+                expiration: api_models::api_keys::ApiKeyExpiration::Never,
+                permissions: None,
+                livemode: None,
+                strict_request_validation: None,
+                pii_restricted: None,
+            },
+            "merchant_1".to_string(),
+        )
+        .await
+        .unwrap();
+        let api_keys = state
+            .store
+            .list_api_keys_by_merchant_id("merchant_1", None, None)
+            .await
+            .unwrap();
+
+        // The payment takes the mode of the key it is created with, and is confirmed with the
+        // connector account
+        assert_eq!(api_keys.len(), 1);
+        assert!(super::validate_connector_account_mode(
+            &state.conf.livemode,
+            &get_merchant_connector_account(None, None),
+            api_keys[0].livemode,
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_payments_are_routed_to_the_connector_account_of_their_business_profile() {
+        use masking::PeekInterface;
+
+        use crate::db::merchant_connector_account::MerchantConnectorAccountInterface;
+
+        let (state, key_store) =
+            get_state_with_key_store(crate::configs::settings::Settings::default()).await;
+        let db = &*state.store;
+
+        // Each storefront of the merchant has its own Stripe account
+        for (profile_id, merchant_connector_id) in [("pro_1", "mca_1"), ("pro_2", "mca_2")] {
+            db.insert_merchant_connector_account(
+                super::domain::MerchantConnectorAccount {
+                    id: None,
+                    merchant_connector_id: merchant_connector_id.to_string(),
+                    connector_account_details: super::domain::types::encrypt(
+                        Secret::new(serde_json::json!({
+                            "auth_type": "HeaderKey",
+                            "api_key": format!("stripe_key_{profile_id}"),
+                        })),
+                        key_store.key.get_inner().peek(),
+                    )
+                    .await
+                    .unwrap(),
+                    connector_label: Some(format!("stripe_{profile_id}")),
+                    profile_id: Some(profile_id.to_string()),
+                    ..get_domain_merchant_connector_account(None, None)
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+        }
+
+        for (profile_id, merchant_connector_id) in [("pro_2", "mca_2"), ("pro_1", "mca_1")] {
+            let merchant_connector_account = super::get_merchant_connector_account(
+                &state,
+                "merchant_1",
+                None,
+                &key_store,
+                &profile_id.to_string(),
+                "stripe",
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                merchant_connector_account.get_mca_id().as_deref(),
+                Some(merchant_connector_id)
+            );
+        }
+
+        // A profile without a Stripe account of its own does not fall back to another profile
+        assert!(super::get_merchant_connector_account(
+            &state,
+            "merchant_1",
+            None,
+            &key_store,
+            &"pro_3".to_string(),
+            "stripe",
+            None,
+        )
+        .await
+        .is_err());
+    }
 }
 
 #[instrument(skip_all)]
@@ -3067,6 +5800,13 @@ pub async fn get_additional_payment_data(
         api_models::payments::PaymentMethodData::Card(card_data) => {
             let card_isin = Some(card_data.card_number.clone().get_card_isin());
             let last4 = Some(card_data.card_number.clone().get_last4());
+            // The network is detected from the card number when it is neither passed in the
+            // request nor known for the BIN, as for saved cards retrieved from the locker
+            let detected_card_network = card_data.card_network.clone().or_else(|| {
+                connector::utils::CardData::get_card_issuer(card_data)
+                    .ok()
+                    .map(api_enums::CardNetwork::from)
+            });
             if card_data.card_issuer.is_some()
                 && card_data.card_network.is_some()
                 && card_data.card_type.is_some()
@@ -3087,6 +5827,35 @@ pub async fn get_additional_payment_data(
                         card_isin: card_isin.clone(),
                     },
                 ))
+            } else if let Some(bin_info) = bin_database::lookup_bin(card_data.card_number.peek()) {
+                // The details passed in the request take precedence over the BIN database
+                api_models::payments::AdditionalPaymentData::Card(Box::new(
+                    api_models::payments::AdditionalCardInfo {
+                        card_issuer: card_data
+                            .card_issuer
+                            .clone()
+                            .or_else(|| bin_info.bank_name.clone()),
+                        card_network: card_data
+                            .card_network
+                            .clone()
+                            .or_else(|| bin_info.network.clone())
+                            .or(detected_card_network),
+                        card_type: card_data
+                            .card_type
+                            .clone()
+                            .or_else(|| bin_info.card_type.map(|card_type| card_type.to_string())),
+                        card_issuing_country: card_data
+                            .card_issuing_country
+                            .clone()
+                            .or_else(|| bin_info.issuer_country.map(|country| country.to_string())),
+                        bank_code: card_data.bank_code.clone(),
+                        card_exp_month: Some(card_data.card_exp_month.clone()),
+                        card_exp_year: Some(card_data.card_exp_year.clone()),
+                        card_holder_name: Some(card_data.card_holder_name.clone()),
+                        last4,
+                        card_isin,
+                    },
+                ))
             } else {
                 let card_info = card_isin
                     .clone()
@@ -3102,7 +5871,10 @@ pub async fn get_additional_payment_data(
                         api_models::payments::AdditionalPaymentData::Card(Box::new(
                             api_models::payments::AdditionalCardInfo {
                                 card_issuer: card_info.card_issuer,
-                                card_network: card_info.card_network.clone(),
+                                card_network: card_info
+                                    .card_network
+                                    .clone()
+                                    .or(detected_card_network.clone()),
                                 bank_code: card_info.bank_code,
                                 card_type: card_info.card_type,
                                 card_issuing_country: card_info.card_issuing_country,
@@ -3117,7 +5889,7 @@ pub async fn get_additional_payment_data(
                 card_info.unwrap_or(api_models::payments::AdditionalPaymentData::Card(Box::new(
                     api_models::payments::AdditionalCardInfo {
                         card_issuer: None,
-                        card_network: None,
+                        card_network: detected_card_network,
                         bank_code: None,
                         card_type: None,
                         card_issuing_country: None,
@@ -3145,8 +5917,10 @@ pub async fn get_additional_payment_data(
                 _ => api_models::payments::AdditionalPaymentData::BankRedirect { bank_name: None },
             }
         }
-        api_models::payments::PaymentMethodData::Wallet(_) => {
-            api_models::payments::AdditionalPaymentData::Wallet {}
+        api_models::payments::PaymentMethodData::Wallet(wallet_data) => {
+            api_models::payments::AdditionalPaymentData::Wallet {
+                wallet_type: Some(wallet_data.get_payment_method_type()),
+            }
         }
         api_models::payments::PaymentMethodData::PayLater(_) => {
             api_models::payments::AdditionalPaymentData::PayLater {}
@@ -85,6 +85,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             request,
             mandate_type.clone(),
             merchant_account,
+            false,
         )
         .await?;
 
@@ -249,6 +250,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response,
                 frm_message: frm_response.ok(),
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
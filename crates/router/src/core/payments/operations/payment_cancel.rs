@@ -116,6 +116,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
         let amount = payment_attempt.amount.into();
 
         payment_attempt.cancellation_reason = request.cancellation_reason.clone();
+        payment_attempt.cancellation_reason_code = request.cancellation_reason_code;
 
         let creds_identifier = request
             .merchant_connector_details
@@ -169,6 +170,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             None,
         ))
@@ -196,6 +200,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsCancelRequest> for
         F: 'b + Send,
     {
         let cancellation_reason = payment_data.payment_attempt.cancellation_reason.clone();
+        let cancellation_reason_code = payment_data.payment_attempt.cancellation_reason_code;
         let (intent_status_update, attempt_status_update) =
             if payment_data.payment_intent.status != enums::IntentStatus::RequiresCapture {
                 let payment_intent_update = storage::PaymentIntentUpdate::PGStatusUpdate {
@@ -222,6 +227,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsCancelRequest> for
             storage::PaymentAttemptUpdate::VoidUpdate {
                 status: attempt_status_update,
                 cancellation_reason,
+                cancellation_reason_code,
             },
             storage_scheme,
         )
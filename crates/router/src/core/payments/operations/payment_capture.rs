@@ -10,6 +10,7 @@ use router_env::{instrument, tracing};
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
 use crate::{
     core::{
+        amount_limits,
         errors::{self, RouterResult, StorageErrorExt},
         payments::{self, helpers, operations, types::MultipleCaptureData},
     },
@@ -71,17 +72,40 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
-        payment_attempt
-            .amount_to_capture
-            .update_value(request.amount_to_capture);
+        // A partially authorized payment is captured up to the amount authorized by the connector
+        let authorized_amount = payment_attempt
+            .authorized_amount
+            .unwrap_or(payment_intent.amount);
+
+        payment_attempt.amount_to_capture.update_value(
+            request
+                .amount_to_capture
+                .or(payment_attempt.authorized_amount),
+        );
 
         let capture_method = payment_attempt
             .capture_method
             .get_required_value("capture_method")?;
 
+        helpers::validate_authorization_not_expired(
+            &payment_attempt,
+            common_utils::date_time::now(),
+        )?;
+
         helpers::validate_status_with_capture_method(payment_intent.status, capture_method)?;
 
-        helpers::validate_amount_to_capture(payment_intent.amount, request.amount_to_capture)?;
+        helpers::validate_amount_to_capture(authorized_amount, request.amount_to_capture)?;
+
+        if let Some(amount_to_capture) = request.amount_to_capture {
+            amount_limits::validate_amount_limits(
+                state,
+                merchant_id,
+                amount_limits::LimitedAmount::Capture,
+                amount_to_capture,
+                payment_attempt.currency.get_required_value("currency")?,
+            )
+            .await?;
+        }
 
         helpers::validate_capture_method(capture_method)?;
 
@@ -229,6 +253,9 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
                 multiple_capture_data,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             None,
         ))
@@ -84,6 +84,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Co
             request,
             mandate_type.clone(),
             merchant_account,
+            false,
         )
         .await?;
 
@@ -244,6 +245,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Co
                 multiple_capture_data: None,
                 redirect_response,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
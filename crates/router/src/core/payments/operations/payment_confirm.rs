@@ -11,7 +11,10 @@ use router_env::{instrument, tracing};
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
 use crate::{
     core::{
-        errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        amount_limits,
+        blocklist::utils as blocklist_utils,
+        errors::{self, validation::ValidationErrors, CustomResult, RouterResult, StorageErrorExt},
+        metadata,
         payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
     },
     db::StorageInterface,
@@ -47,6 +50,14 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         Option<CustomerDetails>,
     )> {
         let db = &*state.store;
+
+        request
+            .metadata
+            .as_ref()
+            .map(|metadata| {
+                metadata::validate_metadata(&state.conf.merchant_metadata, "metadata", metadata)
+            })
+            .transpose()?;
         let merchant_id = &merchant_account.merchant_id;
         let storage_scheme = merchant_account.storage_scheme;
         let (currency, amount);
@@ -66,6 +77,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             request,
             mandate_type.clone(),
             merchant_account,
+            false,
         );
 
         let (mut payment_intent, mandate_details) =
@@ -73,6 +85,8 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         helpers::validate_customer_access(&payment_intent, auth_flow, request)?;
 
+        helpers::validate_session_expiry_not_reached(&payment_intent)?;
+
         helpers::validate_payment_status_against_not_allowed_statuses(
             &payment_intent.status,
             &[
@@ -245,20 +259,42 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 field_name: "browser_info",
             })?;
 
-        helpers::validate_card_data(request.payment_method_data.clone())?;
+        let sub_merchant_data = request
+            .sub_merchant_data
+            .as_ref()
+            .map(Encode::<api_models::payments::SubMerchantData>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sub_merchant_data",
+            })?
+            .or(payment_attempt.sub_merchant_data.take());
+
+        // The authentication data passed on confirm takes the place of the one stored on create
+        let external_authentication_data = match request.authentication_data.as_ref() {
+            Some(authentication_data) => {
+                payment_attempt.external_authentication_data =
+                    helpers::encrypt_external_authentication_data(
+                        Some(authentication_data),
+                        key_store,
+                    )
+                    .await?;
+                Some(authentication_data.clone())
+            }
+            None => {
+                helpers::decrypt_external_authentication_data(&payment_attempt, key_store).await?
+            }
+        };
 
         let token = token.or_else(|| payment_attempt.payment_token.clone());
 
-        helpers::validate_pm_or_token_given(
-            &request.payment_method,
-            &request.payment_method_data,
-            &request.payment_method_type,
-            &mandate_type,
-            &token,
-        )?;
-
         payment_attempt.payment_method = payment_method.or(payment_attempt.payment_method);
         payment_attempt.browser_info = browser_info;
+        payment_attempt.locale = request.locale.clone().or(payment_attempt.locale.take());
+        payment_attempt.merchant_descriptor = request
+            .merchant_descriptor
+            .clone()
+            .or(payment_attempt.merchant_descriptor.take());
+        payment_attempt.sub_merchant_data = sub_merchant_data;
         payment_attempt.payment_method_type =
             payment_method_type.or(payment_attempt.payment_method_type);
 
@@ -271,15 +307,107 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.amount.into();
 
-        helpers::validate_customer_id_mandatory_cases(
-            request.shipping.is_some(),
-            request.billing.is_some(),
-            request.setup_future_usage.is_some(),
-            &payment_intent
-                .customer_id
-                .clone()
-                .or_else(|| customer_details.customer_id.clone()),
+        // The payment is validated before it is routed to a connector, all its problems are
+        // reported at once
+        let mut validation = ValidationErrors::new();
+        validation.collect(
+            "payment_method",
+            helpers::validate_payment_method_fields_present(request),
+        )?;
+        if let Some(authentication_data) = request.authentication_data.as_ref() {
+            validation.collect(
+                "authentication_data",
+                helpers::validate_external_authentication_data(authentication_data),
+            )?;
+        }
+        if let Some(locale) = request.locale.as_deref() {
+            validation.collect("locale", helpers::validate_locale(locale))?;
+        }
+        if let Some(sub_merchant_data) = request.sub_merchant_data.as_ref() {
+            validation.collect(
+                "sub_merchant_data.mcc",
+                helpers::validate_merchant_category_code(&sub_merchant_data.mcc),
+            )?;
+        }
+        validation.collect(
+            "payment_method_data.card",
+            helpers::validate_card_data(request.payment_method_data.clone()),
+        )?;
+        if let Some(card_cvc) = request.card_cvc.as_ref() {
+            validation.collect("card_cvc", helpers::validate_card_cvc(card_cvc))?;
+        }
+        validation.collect(
+            "payment_method_data",
+            helpers::validate_pm_or_token_given(
+                &request.payment_method,
+                &request.payment_method_data,
+                &request.payment_method_type,
+                &mandate_type,
+                &token,
+            ),
+        )?;
+        validation.collect(
+            "amount",
+            amount_limits::validate_amount_limits(
+                state,
+                merchant_id,
+                amount_limits::LimitedAmount::Payment {
+                    is_setup: setup_mandate.is_some()
+                        || payment_intent.setup_future_usage
+                            == Some(storage_enums::FutureUsage::OffSession),
+                },
+                payment_attempt.amount,
+                currency,
+            )
+            .await,
+        )?;
+        validation.collect(
+            "customer_id",
+            helpers::validate_customer_id_mandatory_cases(
+                request.shipping.is_some(),
+                request.billing.is_some(),
+                request.setup_future_usage.is_some(),
+                &payment_intent
+                    .customer_id
+                    .clone()
+                    .or_else(|| customer_details.customer_id.clone()),
+            ),
         )?;
+        // The recurring mandate payments are made with the payment method of the mandate
+        if !matches!(
+            mandate_type,
+            Some(api::MandateTransactionType::RecurringMandateTransaction)
+        ) {
+            helpers::validate_required_fields_for_connectors(
+                state,
+                merchant_account,
+                key_store,
+                request,
+                helpers::RequiredFieldsValidationData {
+                    profile_id: payment_intent.profile_id.clone(),
+                    payment_method: payment_attempt.payment_method,
+                    payment_method_type: payment_attempt.payment_method_type,
+                    currency: Some(currency),
+                    customer_id: payment_intent
+                        .customer_id
+                        .as_ref()
+                        .or(customer_details.customer_id.as_ref()),
+                    customer_details: &customer_details,
+                    billing_address: billing_address.as_ref(),
+                    shipping_address: shipping_address.as_ref(),
+                    request_billing: request.billing.as_ref(),
+                    device_session_id: payment_intent.device_session_id.as_ref(),
+                    is_moto: request
+                        .payment_channel
+                        .or(payment_intent.payment_channel)
+                        .map_or(false, |payment_channel| payment_channel.is_moto()),
+                    is_mandate: setup_mandate.is_some(),
+                },
+                &mut validation,
+            )
+            .await?;
+        }
+        validation.into_result()?;
 
         let creds_identifier = request
             .merchant_connector_details
@@ -312,6 +440,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .attach_printable("Error converting feature_metadata to Value")?
             .or(payment_intent.feature_metadata);
         payment_intent.metadata = request.metadata.clone().or(payment_intent.metadata);
+        payment_intent.device_session_id = request
+            .device_session_id
+            .clone()
+            .or(payment_intent.device_session_id);
         payment_attempt.business_sub_label = request
             .business_sub_label
             .clone()
@@ -354,6 +486,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data,
             },
             Some(customer_details),
         ))
@@ -430,6 +565,23 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentConfirm {
         // creating the payment or if none is passed then use the routing algorithm
         helpers::get_connector_default(state, request.routing.clone()).await
     }
+
+    #[instrument(skip_all)]
+    async fn guard_payment_against_blocklist<'a>(
+        &'a self,
+        state: &AppState,
+        merchant_account: &domain::MerchantAccount,
+        key_store: &domain::MerchantKeyStore,
+        payment_data: &mut PaymentData<F>,
+    ) -> CustomResult<(), errors::ApiErrorResponse> {
+        blocklist_utils::validate_data_for_blocklist(
+            state,
+            merchant_account,
+            key_store,
+            payment_data,
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -451,6 +603,16 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
     {
         let payment_method = payment_data.payment_attempt.payment_method;
         let browser_info = payment_data.payment_attempt.browser_info.clone();
+        let external_authentication_data = payment_data
+            .payment_attempt
+            .external_authentication_data
+            .clone();
+        let three_ds_decision_data = payment_data.payment_attempt.three_ds_decision_data.clone();
+        let routing_metadata = payment_data.payment_attempt.routing_metadata.clone();
+        let merchant_connector_id = payment_data.payment_attempt.merchant_connector_id.clone();
+        let locale = payment_data.payment_attempt.locale.clone();
+        let merchant_descriptor = payment_data.payment_attempt.merchant_descriptor.clone();
+        let sub_merchant_data = payment_data.payment_attempt.sub_merchant_data.clone();
         let frm_message = payment_data.frm_message.clone();
 
         let (intent_status, attempt_status, (error_code, error_message)) = match frm_suggestion {
@@ -519,6 +681,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .take();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
+        let device_session_id = payment_data.payment_intent.device_session_id.clone();
         let authorized_amount = payment_data.payment_attempt.amount;
         let payment_attempt_fut = db
             .update_payment_attempt_with_attempt_id(
@@ -540,6 +703,13 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     error_code,
                     error_message,
                     amount_capturable: Some(authorized_amount),
+                    external_authentication_data,
+                    three_ds_decision_data,
+                    routing_metadata,
+                    locale,
+                    merchant_descriptor,
+                    sub_merchant_data,
+                    merchant_connector_id,
                 },
                 storage_scheme,
             )
@@ -565,6 +735,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     order_details,
                     metadata,
                     payment_confirm_source: header_payload.payment_confirm_source,
+                    device_session_id,
                 },
                 storage_scheme,
             )
@@ -621,8 +792,6 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentConfir
                 expected_format: "merchant_id from merchant account".to_string(),
             })?;
 
-        helpers::validate_payment_method_fields_present(request)?;
-
         let mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
         let payment_id =
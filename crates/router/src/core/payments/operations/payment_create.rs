@@ -7,14 +7,19 @@ use data_models::mandates::MandateData;
 use diesel_models::ephemeral_key;
 use error_stack::{self, ResultExt};
 use router_derive::PaymentOperation;
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
 
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
 use crate::{
     consts,
     core::{
-        errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        amount_limits,
+        blocklist::utils as blocklist_utils,
+        errors::{self, validation::ValidationErrors, CustomResult, RouterResult, StorageErrorExt},
+        metadata,
+        payments::{
+            self, eligibility, helpers, operations, CustomerDetails, PaymentAddress, PaymentData,
+        },
         utils::{self as core_utils},
     },
     db::StorageInterface,
@@ -32,9 +37,26 @@ use crate::{
     utils::{self, OptionExt},
 };
 
-#[derive(Debug, Clone, Copy, PaymentOperation)]
+#[derive(Clone, Default, PaymentOperation)]
 #[operation(ops = "all", flow = "authorize")]
-pub struct PaymentCreate;
+pub struct PaymentCreate {
+    /// Whether the recurring charge is allowed to exceed the amount and frequency limits of the
+    /// mandate, as requested by an API key permitted to override them
+    pub override_mandate_limits: bool,
+    /// The payment created earlier with the same merchant order reference, when the business
+    /// profile flags the payment as a suspected duplicate of it
+    pub suspected_duplicate_of: Option<String>,
+    /// The mode of the API key with which the payment is created
+    pub livemode: Option<bool>,
+}
+
+// The operations are told apart by the name in their `Debug` output (in the metrics as well), the
+// context of the creation is left out of it
+impl std::fmt::Debug for PaymentCreate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PaymentCreate")
+    }
+}
 
 #[async_trait]
 impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for PaymentCreate {
@@ -54,12 +76,20 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         Option<CustomerDetails>,
     )> {
         let db = &*state.store;
+
+        request
+            .metadata
+            .as_ref()
+            .map(|metadata| {
+                metadata::validate_metadata(&state.conf.merchant_metadata, "metadata", metadata)
+            })
+            .transpose()?;
         let ephemeral_key = Self::get_ephemeral_key(request, state, merchant_account).await;
         let merchant_id = &merchant_account.merchant_id;
         let storage_scheme = merchant_account.storage_scheme;
         let (payment_intent, payment_attempt, connector_response);
 
-        let money @ (amount, currency) = payments_create_request_validation(request)?;
+        let amount = request.amount.get_required_value("amount")?;
 
         let payment_id = payment_id
             .get_payment_intent_id()
@@ -85,13 +115,237 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         ) = helpers::get_token_pm_type_mandate_details(
             state,
             request,
-            mandate_type,
+            mandate_type.clone(),
             merchant_account,
+            self.override_mandate_limits,
         )
         .await?;
 
         let customer_details = helpers::get_customer_details_from_request(request);
 
+        // The payment is validated before it is created, all its problems are reported at once
+        let mut validation = ValidationErrors::new();
+
+        // If profile id is not passed, get it from the business_country and business_label
+        let profile_id = validation.collect(
+            "profile_id",
+            core_utils::get_profile_id_from_business_details(
+                request.business_country,
+                request.business_label.as_ref(),
+                merchant_account,
+                request.profile_id.as_ref(),
+                db,
+                false,
+            )
+            .await,
+        )?;
+        let business_profile = match profile_id.as_ref() {
+            Some(profile_id) => Some(
+                db.find_business_profile_by_profile_id(profile_id)
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+                        id: profile_id.clone(),
+                    })?,
+            ),
+            None => None,
+        };
+
+        // Fields absent in the request are filled from the defaults of the business profile,
+        // followed by those of the merchant account. The filled values are stored on the payment,
+        // so that the later flows of the payment are not affected by changes to the defaults.
+        let (mut payment_defaults, mut applied_defaults) = helpers::PaymentDefaults::resolve(
+            request.into(),
+            business_profile
+                .as_ref()
+                .map(helpers::PaymentDefaults::from)
+                .unwrap_or_default(),
+            merchant_account.into(),
+        );
+        // The billing address of a payment is stored against its customer, so the billing country
+        // is not filled for the payments without a customer
+        if customer_details.customer_id.is_none()
+            && applied_defaults.contains(&api_models::enums::PaymentDefaultField::BillingCountry)
+        {
+            payment_defaults.billing_country = None;
+            applied_defaults
+                .retain(|field| *field != api_models::enums::PaymentDefaultField::BillingCountry);
+        }
+        let billing = match payment_defaults.billing_country.filter(|_| {
+            applied_defaults.contains(&api_models::enums::PaymentDefaultField::BillingCountry)
+        }) {
+            Some(billing_country) => {
+                let mut billing = request.billing.clone().unwrap_or_default();
+                billing.address.get_or_insert_with(Default::default).country =
+                    Some(billing_country);
+                Some(billing)
+            }
+            None => request.billing.clone(),
+        };
+
+        let currency = validation.collect(
+            "currency",
+            payment_defaults.currency.get_required_value("currency"),
+        )?;
+        // A default currency is validated against the connector accounts, as it is not chosen for
+        // the payment by the merchant
+        if let (Some(profile_id), Some(currency), None) = (
+            profile_id.as_deref(),
+            currency.filter(|_| {
+                applied_defaults.contains(&api_models::enums::PaymentDefaultField::Currency)
+            }),
+            request.merchant_connector_details.as_ref(),
+        ) {
+            validation.collect(
+                "currency",
+                eligibility::validate_currency_enabled(
+                    state,
+                    merchant_account,
+                    merchant_key_store,
+                    profile_id,
+                    currency,
+                )
+                .await,
+            )?;
+        }
+
+        validation.collect(
+            "amount_to_capture",
+            helpers::validate_request_amount_and_amount_to_capture(
+                request.amount,
+                request.amount_to_capture,
+            )
+            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "amount_to_capture".to_string(),
+                expected_format: "amount_to_capture lesser than amount".to_string(),
+            }),
+        )?;
+        validation.collect(
+            "payment_method_data.card",
+            helpers::validate_card_data(request.payment_method_data.clone()),
+        )?;
+        validation.collect(
+            "payment_method",
+            helpers::validate_payment_method_fields_present(request),
+        )?;
+        if let Some(authentication_data) = request.authentication_data.as_ref() {
+            validation.collect(
+                "authentication_data",
+                helpers::validate_external_authentication_data(authentication_data),
+            )?;
+        }
+        if let Some(session_expiry) = request.session_expiry {
+            validation.collect(
+                "session_expiry",
+                helpers::validate_session_expiry(session_expiry),
+            )?;
+        }
+        if let Some(locale) = request.locale.as_deref() {
+            validation.collect("locale", helpers::validate_locale(locale))?;
+        }
+        if let Some(sub_merchant_data) = request.sub_merchant_data.as_ref() {
+            validation.collect(
+                "sub_merchant_data.mcc",
+                helpers::validate_merchant_category_code(&sub_merchant_data.mcc),
+            )?;
+        }
+        validation.collect(
+            "order_details",
+            helpers::validate_order_shipping_details(request),
+        )?;
+        if let Some(merchant_order_reference_id) = request.merchant_order_reference_id.as_deref() {
+            validation.collect(
+                "merchant_order_reference_id",
+                helpers::validate_merchant_order_reference_id(merchant_order_reference_id),
+            )?;
+        }
+        if let Some(split_payment) = request.split_payment.as_ref() {
+            validation.collect(
+                "split_payment",
+                helpers::get_split_payment_details(split_payment, amount.into()),
+            )?;
+        }
+        if let Some(amount_details) = request.amount_details.as_ref() {
+            validation.collect(
+                "amount_details",
+                helpers::validate_amount_details(
+                    amount_details,
+                    amount.into(),
+                    request.order_details.as_deref(),
+                ),
+            )?;
+        }
+        if let Some(currency) = currency {
+            validation.collect(
+                "amount",
+                amount_limits::validate_amount_limits(
+                    state,
+                    merchant_id,
+                    amount_limits::LimitedAmount::Payment {
+                        is_setup: setup_mandate.is_some()
+                            || request.setup_future_usage == Some(enums::FutureUsage::OffSession),
+                    },
+                    amount.into(),
+                    currency,
+                )
+                .await,
+            )?;
+        }
+        if request.confirm.unwrap_or(false) {
+            validation.collect(
+                "payment_method_data",
+                helpers::validate_pm_or_token_given(
+                    &request.payment_method,
+                    &request.payment_method_data,
+                    &request.payment_method_type,
+                    &mandate_type,
+                    &request.payment_token,
+                ),
+            )?;
+            validation.collect(
+                "customer_id",
+                helpers::validate_customer_id_mandatory_cases(
+                    request.shipping.is_some(),
+                    request.billing.is_some(),
+                    request.setup_future_usage.is_some(),
+                    &customer_details.customer_id,
+                ),
+            )?;
+            // The recurring mandate payments are made with the payment method of the mandate
+            if !matches!(
+                mandate_type,
+                Some(api::MandateTransactionType::RecurringMandateTransaction)
+            ) {
+                helpers::validate_required_fields_for_connectors(
+                    state,
+                    merchant_account,
+                    merchant_key_store,
+                    request,
+                    helpers::RequiredFieldsValidationData {
+                        profile_id: profile_id.clone(),
+                        payment_method,
+                        payment_method_type,
+                        currency,
+                        customer_id: customer_details.customer_id.as_ref(),
+                        customer_details: &customer_details,
+                        billing_address: None,
+                        shipping_address: None,
+                        request_billing: billing.as_ref(),
+                        device_session_id: None,
+                        is_moto: request
+                            .payment_channel
+                            .map_or(false, |payment_channel| payment_channel.is_moto()),
+                        is_mandate: setup_mandate.is_some(),
+                    },
+                    &mut validation,
+                )
+                .await?;
+            }
+        }
+        validation.into_result()?;
+        let currency = currency.get_required_value("currency")?;
+        let money = (amount, currency);
+        let business_profile = business_profile.get_required_value("business_profile")?;
+
         let shipping_address = helpers::create_or_find_address_for_payment_by_request(
             db,
             request.shipping.as_ref(),
@@ -106,7 +360,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         let billing_address = helpers::create_or_find_address_for_payment_by_request(
             db,
-            request.billing.as_ref(),
+            billing.as_ref(),
             None,
             merchant_id,
             customer_details.customer_id.as_ref(),
@@ -127,6 +381,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 field_name: "browser_info",
             })?;
 
+        // The payments created without an API key, such as from the dashboard, take the mode of the
+        // deployment
+        let livemode = self
+            .livemode
+            .unwrap_or_else(core_utils::get_default_livemode);
+
         payment_attempt = db
             .insert_payment_attempt(
                 Self::make_payment_attempt(
@@ -138,6 +398,8 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                     request,
                     browser_info,
                     state,
+                    livemode,
+                    merchant_key_store,
                 )
                 .await?,
                 storage_scheme,
@@ -157,7 +419,11 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                     shipping_address.clone().map(|x| x.address_id),
                     billing_address.clone().map(|x| x.address_id),
                     payment_attempt.attempt_id.to_owned(),
-                    state,
+                    &business_profile,
+                    payment_defaults,
+                    applied_defaults,
+                    self.suspected_duplicate_of.clone(),
+                    livemode,
                 )
                 .await?,
                 storage_scheme,
@@ -166,6 +432,15 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
                 payment_id: payment_id.clone(),
             })?;
+
+        // Schedule the cancellation of the payment if it is not confirmed before its session expires
+        if payment_intent.session_expiry.is_some() {
+            payments::add_payment_expiry_task(db, &payment_intent)
+                .await
+                .map_err(|error| logger::error!(process_tracker_error=?error))
+                .ok();
+        }
+
         connector_response = db
             .insert_connector_response(
                 Self::make_connector_response(&payment_attempt),
@@ -282,6 +557,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: request.authentication_data.clone(),
             },
             Some(customer_details),
         ))
@@ -349,6 +627,28 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentCreate {
     ) -> CustomResult<api::ConnectorChoice, errors::ApiErrorResponse> {
         helpers::get_connector_default(state, request.routing.clone()).await
     }
+
+    #[instrument(skip_all)]
+    async fn guard_payment_against_blocklist<'a>(
+        &'a self,
+        state: &AppState,
+        merchant_account: &domain::MerchantAccount,
+        key_store: &domain::MerchantKeyStore,
+        payment_data: &mut PaymentData<F>,
+    ) -> CustomResult<(), errors::ApiErrorResponse> {
+        // Payments are only checked against the blocklist when they are confirmed
+        if payment_data.confirm == Some(true) {
+            blocklist_utils::validate_data_for_blocklist(
+                state,
+                merchant_account,
+                key_store,
+                payment_data,
+            )
+            .await
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[async_trait]
@@ -391,6 +691,9 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .straight_through_algorithm
             .clone();
         let authorized_amount = payment_data.payment_attempt.amount;
+        let locale = payment_data.payment_attempt.locale.clone();
+        let routing_metadata = payment_data.payment_attempt.routing_metadata.clone();
+        let merchant_connector_id = payment_data.payment_attempt.merchant_connector_id.clone();
 
         payment_data.payment_attempt = db
             .update_payment_attempt_with_attempt_id(
@@ -403,6 +706,9 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                         true => Some(authorized_amount),
                         false => None,
                     },
+                    locale,
+                    routing_metadata,
+                    merchant_connector_id,
                 },
                 storage_scheme,
             )
@@ -459,45 +765,11 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentCreate
         helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
             .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-        helpers::validate_request_amount_and_amount_to_capture(
-            request.amount,
-            request.amount_to_capture,
-        )
-        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-            field_name: "amount_to_capture".to_string(),
-            expected_format: "amount_to_capture lesser than amount".to_string(),
-        })?;
-
-        helpers::validate_card_data(request.payment_method_data.clone())?;
-
-        helpers::validate_payment_method_fields_present(request)?;
-
         let payment_id = core_utils::get_or_generate_id("payment_id", &given_payment_id, "pay")?;
 
         let mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
 
-        if request.confirm.unwrap_or(false) {
-            helpers::validate_pm_or_token_given(
-                &request.payment_method,
-                &request.payment_method_data,
-                &request.payment_method_type,
-                &mandate_type,
-                &request.payment_token,
-            )?;
-
-            helpers::validate_customer_id_mandatory_cases(
-                request.shipping.is_some(),
-                request.billing.is_some(),
-                request.setup_future_usage.is_some(),
-                &request
-                    .customer
-                    .clone()
-                    .map(|customer| customer.id)
-                    .or(request.customer_id.clone()),
-            )?;
-        }
-
         Ok((
             Box::new(self),
             operations::ValidateResult {
@@ -526,6 +798,8 @@ impl PaymentCreate {
         request: &api::PaymentsRequest,
         browser_info: Option<serde_json::Value>,
         state: &AppState,
+        livemode: bool,
+        key_store: &domain::MerchantKeyStore,
     ) -> RouterResult<storage::PaymentAttemptNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
         let status =
@@ -544,6 +818,19 @@ impl PaymentCreate {
             .transpose()
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to encode additional pm data")?;
+        let external_authentication_data = helpers::encrypt_external_authentication_data(
+            request.authentication_data.as_ref(),
+            key_store,
+        )
+        .await?;
+        let sub_merchant_data = request
+            .sub_merchant_data
+            .as_ref()
+            .map(Encode::<api_models::payments::SubMerchantData>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sub_merchant_data",
+            })?;
         let attempt_id = if core_utils::is_merchant_enabled_for_payment_id_as_connector_request_id(
             &state.conf,
             merchant_id,
@@ -580,6 +867,11 @@ impl PaymentCreate {
                 .mandate_data
                 .as_ref()
                 .and_then(|inner| inner.mandate_type.clone().map(Into::into)),
+            external_authentication_data,
+            locale: request.locale.clone(),
+            merchant_descriptor: request.merchant_descriptor.clone(),
+            sub_merchant_data,
+            livemode,
             ..storage::PaymentAttemptNew::default()
         })
     }
@@ -594,7 +886,11 @@ impl PaymentCreate {
         shipping_address_id: Option<String>,
         billing_address_id: Option<String>,
         active_attempt_id: String,
-        state: &AppState,
+        business_profile: &storage::business_profile::BusinessProfile,
+        payment_defaults: helpers::PaymentDefaults,
+        applied_defaults: Vec<api_models::enums::PaymentDefaultField>,
+        suspected_duplicate_of: Option<String>,
+        livemode: bool,
     ) -> RouterResult<storage::PaymentIntentNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
         let status =
@@ -608,16 +904,21 @@ impl PaymentCreate {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to convert order details to value")?;
 
-        // If profile id is not passed, get it from the business_country and business_label
-        let profile_id = core_utils::get_profile_id_from_business_details(
-            request.business_country,
-            request.business_label.as_ref(),
-            merchant_account,
-            request.profile_id.as_ref(),
-            &*state.store,
-            true,
-        )
-        .await?;
+        let applied_defaults = (!applied_defaults.is_empty())
+            .then(|| {
+                Encode::<Vec<api_models::enums::PaymentDefaultField>>::encode_to_value(
+                    &applied_defaults,
+                )
+            })
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the applied payment defaults")?;
+
+        let session_expiry = helpers::get_session_expiry(
+            common_utils::date_time::now(),
+            request.session_expiry,
+            business_profile.session_expiry,
+        );
 
         let allowed_payment_method_types = request
             .get_allowed_payment_method_types_as_value()
@@ -634,24 +935,44 @@ impl PaymentCreate {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Error converting feature_metadata to Value")?;
 
+        let split_payment = request
+            .split_payment
+            .as_ref()
+            .map(|split_payment| helpers::get_split_payment_details(split_payment, amount.into()))
+            .transpose()?
+            .map(|split_payment| {
+                Encode::<api_models::payments::SplitPaymentDetails>::encode_to_value(&split_payment)
+            })
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the split payment")?;
+
+        let amount_details = request
+            .amount_details
+            .as_ref()
+            .map(Encode::<api_models::payments::AmountDetails>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the amount details")?;
+
         Ok(storage::PaymentIntentNew {
             payment_id: payment_id.to_string(),
             merchant_id: merchant_account.merchant_id.to_string(),
             status,
             amount: amount.into(),
             currency,
-            description: request.description.clone(),
+            description: payment_defaults.description,
             created_at,
             modified_at,
             last_synced,
             client_secret: Some(client_secret),
             setup_future_usage: request.setup_future_usage,
             off_session: request.off_session,
-            return_url: request.return_url.as_ref().map(|a| a.to_string()),
+            return_url: payment_defaults.return_url,
             shipping_address_id,
             billing_address_id,
-            statement_descriptor_name: request.statement_descriptor_name.clone(),
-            statement_descriptor_suffix: request.statement_descriptor_suffix.clone(),
+            statement_descriptor_name: payment_defaults.statement_descriptor_name,
+            statement_descriptor_suffix: payment_defaults.statement_descriptor_suffix,
             metadata: request.metadata.clone(),
             business_country: request.business_country,
             business_label: request.business_label.clone(),
@@ -664,9 +985,26 @@ impl PaymentCreate {
             connector_metadata,
             feature_metadata,
             attempt_count: 1,
-            profile_id: Some(profile_id),
+            profile_id: Some(business_profile.profile_id.clone()),
             merchant_decision: None,
             payment_confirm_source: None,
+            session_expiry,
+            applied_defaults,
+            order_category: request.order_category.clone(),
+            shipping_method: request.shipping_method,
+            estimated_delivery_date: request.estimated_delivery_date,
+            merchant_order_reference_id: request.merchant_order_reference_id.clone(),
+            connector_request_reference_format: business_profile
+                .connector_request_reference_format
+                .clone(),
+            split_payment,
+            payment_channel: request.payment_channel,
+            suspected_duplicate_of,
+            livemode,
+            amount_details,
+            request_partial_authorization: request.request_partial_authorization,
+            archived_at: None,
+            device_session_id: request.device_session_id.clone(),
         })
     }
 
@@ -698,6 +1036,7 @@ impl PaymentCreate {
                 state.clone(),
                 customer_id,
                 merchant_account.merchant_id.clone(),
+                false,
             )
             .await
             .ok()
@@ -713,11 +1052,335 @@ impl PaymentCreate {
     }
 }
 
-#[instrument(skip_all)]
-pub fn payments_create_request_validation(
-    req: &api::PaymentsRequest,
-) -> RouterResult<(api::Amount, enums::Currency)> {
-    let currency = req.currency.get_required_value("currency")?;
-    let amount = req.amount.get_required_value("amount")?;
-    Ok((amount, currency))
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use masking::PeekInterface;
+
+    use super::*;
+    use crate::core::admin;
+
+    async fn get_state_with_business_profile() -> (
+        AppState,
+        domain::MerchantAccount,
+        domain::MerchantKeyStore,
+        storage::business_profile::BusinessProfile,
+    ) {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+
+        let merchant_account = db
+            .insert_merchant(
+                domain::MerchantAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    return_url: None,
+                    enable_payment_response_hash: false,
+                    payment_response_hash_key: None,
+                    redirect_to_merchant_with_http_post: false,
+                    merchant_name: None,
+                    merchant_details: None,
+                    webhook_details: None,
+                    sub_merchants_enabled: None,
+                    parent_merchant_id: None,
+                    publishable_key: None,
+                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+                    locker_id: None,
+                    metadata: None,
+                    routing_algorithm: None,
+                    primary_business_details: serde_json::Value::Null,
+                    frm_routing_algorithm: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    intent_fulfillment_time: None,
+                    payout_routing_algorithm: None,
+                    organization_id: None,
+                    is_recon_enabled: false,
+                    default_profile: None,
+                    recon_status: enums::ReconStatus::NotRequested,
+                    webhook_auth_details: None,
+                    default_currency: Some(enums::Currency::USD),
+                    default_country: None,
+                    pii_policy: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+
+        let business_profile = admin::create_and_insert_business_profile(
+            db,
+            api::BusinessProfileCreate {
+                profile_name: Some("default".to_string()),
+                default_currency: Some(enums::Currency::EUR),
+                ..Default::default()
+            },
+            merchant_account.clone(),
+        )
+        .await
+        .unwrap();
+
+        // Only euro card payments are enabled for the connector account of the profile
+        db.insert_merchant_connector_account(
+            domain::MerchantConnectorAccount {
+                id: None,
+                merchant_id: "merchant_1".to_string(),
+                connector_name: "stripe".to_string(),
+                connector_account_details: domain::types::encrypt(
+                    masking::Secret::new(serde_json::json!({
+                        "auth_type": "HeaderKey",
+                        "api_key": "stripe_key",
+                    })),
+                    key_store.key.get_inner().peek(),
+                )
+                .await
+                .unwrap(),
+                test_mode: None,
+                disabled: None,
+                merchant_connector_id: "mca_stripe".to_string(),
+                payment_methods_enabled: Some(vec![serde_json::json!({
+                    "payment_method": "card",
+                    "payment_method_types": [{
+                        "payment_method_type": "credit",
+                        "accepted_currencies": { "type": "enable_only", "list": ["EUR"] },
+                        "recurring_enabled": false,
+                        "installment_payment_enabled": false
+                    }]
+                })]),
+                connector_type: enums::ConnectorType::PaymentProcessor,
+                metadata: None,
+                frm_configs: None,
+                connector_label: None,
+                business_country: None,
+                business_label: None,
+                business_sub_label: None,
+                created_at: common_utils::date_time::now(),
+                modified_at: common_utils::date_time::now(),
+                connector_webhook_details: None,
+                profile_id: Some(business_profile.profile_id.clone()),
+                applepay_verified_domains: None,
+                disabled_flows: None,
+                previous_credentials: None,
+            },
+            &key_store,
+        )
+        .await
+        .unwrap();
+
+        (state, merchant_account, key_store, business_profile)
+    }
+
+    async fn create_payment(
+        state: &AppState,
+        merchant_account: &domain::MerchantAccount,
+        key_store: &domain::MerchantKeyStore,
+        payment_id: &str,
+        request: &api::PaymentsRequest,
+    ) -> RouterResult<storage::PaymentIntent> {
+        GetTracker::<api::Authorize, PaymentData<api::Authorize>, _>::get_trackers(
+            &PaymentCreate::default(),
+            state,
+            &api::PaymentIdType::PaymentIntentId(payment_id.to_string()),
+            request,
+            None,
+            merchant_account,
+            key_store,
+            services::AuthFlow::Merchant,
+        )
+        .await
+        .map(|(_, payment_data, _)| payment_data.payment_intent)
+    }
+
+    #[tokio::test]
+    async fn test_default_currency_is_recorded_on_the_payment() {
+        let (state, merchant_account, key_store, business_profile) =
+            get_state_with_business_profile().await;
+        let request = api::PaymentsRequest {
+            amount: Some(api::Amount::from(1000)),
+            profile_id: Some(business_profile.profile_id.clone()),
+            ..Default::default()
+        };
+
+        // The default of the profile takes precedence over the default of the merchant account
+        let payment_intent =
+            create_payment(&state, &merchant_account, &key_store, "pay_1", &request)
+                .await
+                .unwrap();
+        assert_eq!(payment_intent.currency, Some(enums::Currency::EUR));
+        assert_eq!(
+            payment_intent.applied_defaults,
+            Some(serde_json::json!(["currency"]))
+        );
+
+        // An explicitly passed currency is not replaced by the default
+        let payment_intent = create_payment(
+            &state,
+            &merchant_account,
+            &key_store,
+            "pay_2",
+            &api::PaymentsRequest {
+                currency: Some(enums::Currency::USD),
+                ..request.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(payment_intent.currency, Some(enums::Currency::USD));
+        assert_eq!(payment_intent.applied_defaults, None);
+
+        // A later change of the default does not change the payments created earlier
+        admin::update_business_profile(
+            state.clone(),
+            &business_profile.profile_id,
+            "merchant_1",
+            serde_json::from_value(serde_json::json!({ "default_currency": "GBP" })).unwrap(),
+        )
+        .await
+        .unwrap();
+        let payment_intent = state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_intent.currency, Some(enums::Currency::EUR));
+
+        // The new default is not enabled on the connector account of the profile
+        let error = create_payment(&state, &merchant_account, &key_store, "pay_3", &request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { message } if message.contains("GBP")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_authentication_data_passed_on_update_and_confirm_is_stored_on_the_attempt() {
+        let (state, merchant_account, key_store, business_profile) =
+            get_state_with_business_profile().await;
+        let payment_intent = create_payment(
+            &state,
+            &merchant_account,
+            &key_store,
+            "pay_1",
+            &api::PaymentsRequest {
+                amount: Some(api::Amount::from(1000)),
+                profile_id: Some(business_profile.profile_id.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let authentication_data = api_models::payments::ExternalAuthenticationData {
+            cavv: masking::Secret::new("AAABBEg0VhI0VniQEjRWAAAAAAA=".to_string()),
+            eci: "05".to_string(),
+            ds_transaction_id: None,
+            message_version: "2.2.0".to_string(),
+            threeds_server_transaction_id: None,
+        };
+        let (operation, payment_data, _) =
+            GetTracker::<api::Authorize, PaymentData<api::Authorize>, _>::get_trackers(
+                &operations::PaymentUpdate,
+                &state,
+                &api::PaymentIdType::PaymentIntentId("pay_1".to_string()),
+                &api::PaymentsRequest {
+                    confirm: Some(true),
+                    payment_method: Some(enums::PaymentMethod::Card),
+                    payment_method_data: Some(api::PaymentMethodData::Card(api::Card {
+                        card_number: cards::CardNumber::try_from("4000000000003220".to_string())
+                            .unwrap(),
+                        card_exp_month: masking::Secret::new("10".to_string()),
+                        card_exp_year: masking::Secret::new("2035".to_string()),
+                        card_holder_name: masking::Secret::new("John Doe".to_string()),
+                        card_cvc: masking::Secret::new("123".to_string()),
+                        card_issuer: None,
+                        card_network: None,
+                        card_type: None,
+                        card_issuing_country: None,
+                        bank_code: None,
+                        nick_name: None,
+                    })),
+                    authentication_data: Some(authentication_data.clone()),
+                    ..Default::default()
+                },
+                None,
+                &merchant_account,
+                &key_store,
+                services::AuthFlow::Merchant,
+            )
+            .await
+            .unwrap();
+
+        // The data is passed to the connector which the payment is confirmed with
+        assert_eq!(
+            payment_data.external_authentication_data,
+            Some(authentication_data.clone())
+        );
+
+        // and is stored encrypted on the attempt when the confirmation is tracked
+        let (_, payment_data) = operation
+            .to_update_tracker()
+            .unwrap()
+            .update_trackers(
+                &*state.store,
+                payment_data,
+                None,
+                enums::MerchantStorageScheme::PostgresOnly,
+                None,
+                &key_store,
+                None,
+                api::HeaderPayload::default(),
+            )
+            .await
+            .unwrap();
+        let payment_attempt = state
+            .store
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &payment_intent.active_attempt_id,
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            payment_attempt.external_authentication_data,
+            payment_data.payment_attempt.external_authentication_data
+        );
+        assert!(payment_attempt.external_authentication_data.is_some());
+        assert_eq!(
+            helpers::decrypt_external_authentication_data(&payment_attempt, &key_store)
+                .await
+                .unwrap(),
+            Some(authentication_data)
+        );
+    }
 }
@@ -191,6 +191,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::VerifyRequest> for Paym
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             Some(payments::CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -335,6 +338,9 @@ impl PaymentMethodValidate {
             created_at,
             modified_at,
             last_synced,
+            // Verify requests are not authenticated with API keys of a mode, the mode of the
+            // deployment is taken
+            livemode: core_utils::get_default_livemode(),
             ..Default::default()
         }
     }
@@ -365,6 +371,7 @@ impl PaymentMethodValidate {
             off_session: request.off_session,
             active_attempt_id,
             attempt_count: 1,
+            livemode: core_utils::get_default_livemode(),
             ..Default::default()
         }
     }
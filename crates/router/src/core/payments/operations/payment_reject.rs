@@ -155,6 +155,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, PaymentsRejectRequest> for P
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: frm_response.ok(),
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data: None,
             },
             None,
         ))
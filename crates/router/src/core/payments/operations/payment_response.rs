@@ -4,14 +4,16 @@ use async_trait::async_trait;
 use error_stack::ResultExt;
 use futures::FutureExt;
 use router_derive;
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
 
 use super::{Operation, PostUpdateTracker};
 use crate::{
+    connector,
     core::{
+        connector_response_capture,
         errors::{self, RouterResult, StorageErrorExt},
         mandate,
-        payments::{types::MultipleCaptureData, PaymentData},
+        payments::{self, helpers, types::MultipleCaptureData, PaymentData},
         utils as core_utils,
     },
     db::StorageInterface,
@@ -286,9 +288,35 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     db: &dyn StorageInterface,
     _payment_id: &api::PaymentIdType,
     mut payment_data: PaymentData<F>,
-    router_data: types::RouterData<F, T, types::PaymentsResponseData>,
+    mut router_data: types::RouterData<F, T, types::PaymentsResponseData>,
     storage_scheme: enums::MerchantStorageScheme,
 ) -> RouterResult<PaymentData<F>> {
+    connector_response_capture::capture_connector_response(
+        db,
+        connector_response_capture::CapturedResource {
+            merchant_id: &payment_data.payment_attempt.merchant_id,
+            payment_id: &payment_data.payment_attempt.payment_id,
+            attempt_id: &payment_data.payment_attempt.attempt_id,
+            refund_id: None,
+            connector: &router_data.connector,
+        },
+        router_data.raw_connector_response.as_ref(),
+    )
+    .await;
+
+    // An authorization of less than the amount of the attempt can only be captured up to the
+    // authorized amount, it is never recorded as an authorization of the full amount
+    let partially_authorized_amount = helpers::get_partially_authorized_amount(
+        router_data.status,
+        payment_data.payment_attempt.amount,
+        router_data.authorized_amount,
+    );
+    if partially_authorized_amount.is_some() {
+        router_data.status = enums::AttemptStatus::PartiallyAuthorized;
+    }
+    let was_partially_authorized =
+        payment_data.payment_attempt.status == enums::AttemptStatus::PartiallyAuthorized;
+
     let (capture_update, mut payment_attempt_update, connector_response_update) = match router_data
         .response
         .clone()
@@ -391,6 +419,10 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 redirection_data,
                 connector_metadata,
                 connector_response_reference_id,
+                card_verification_results,
+                connector_fee,
+                auth_code,
+                acquirer_reference_number,
                 ..
             } => {
                 let connector_transaction_id = match resource_id {
@@ -408,6 +440,14 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                     .change_context(errors::ApiErrorResponse::InternalServerError)
                     .attach_printable("Could not parse the connector response")?;
 
+                let card_verification_results = card_verification_results
+                    .map(|results| {
+                        utils::Encode::<api::CardVerificationResults>::encode_to_value(&results)
+                    })
+                    .transpose()
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Could not encode the card verification results")?;
+
                 // incase of success, update error code and error message
                 let error_status = if router_data.status == enums::AttemptStatus::Charged {
                     Some(None)
@@ -419,6 +459,13 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                     metrics::SUCCESSFUL_PAYMENT.add(&metrics::CONTEXT, 1, &[]);
                 }
 
+                #[cfg(debug_assertions)]
+                connector::utils::flag_impossible_status_transition(
+                    &router_data.connector,
+                    payment_data.payment_attempt.status,
+                    router_data.status,
+                );
+
                 utils::add_apple_pay_payment_status_metrics(
                     router_data.status,
                     router_data.apple_pay_flow,
@@ -465,8 +512,18 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                                 {
                                     Some(0)
                                 } else {
-                                    None
+                                    partially_authorized_amount
                                 },
+                                card_verification_results,
+                                connector_fee_amount: connector_fee
+                                    .map(|connector_fee| connector_fee.fee_amount),
+                                fee_currency: connector_fee
+                                    .map(|connector_fee| connector_fee.fee_currency),
+                                net_amount: connector_fee
+                                    .and_then(|connector_fee| connector_fee.net_amount),
+                                auth_code,
+                                acquirer_reference_number,
+                                authorized_amount: partially_authorized_amount,
                             }),
                         ),
                     };
@@ -542,11 +599,14 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 multiple_capture_data.update_capture(updated_capture);
             }
 
-            let authorized_amount = payment_data.payment_attempt.amount;
+            let authorized_amount = payment_data
+                .payment_attempt
+                .authorized_amount
+                .unwrap_or(payment_data.payment_attempt.amount);
 
             payment_attempt_update = Some(storage::PaymentAttemptUpdate::AmountToCaptureUpdate {
                 status: multiple_capture_data.get_attempt_status(authorized_amount),
-                amount_capturable: payment_data.payment_attempt.amount
+                amount_capturable: authorized_amount
                     - multiple_capture_data.get_total_blocked_amount(),
             });
             Some(multiple_capture_data)
@@ -632,6 +692,17 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     let (payment_intent, _) = futures::try_join!(payment_intent_fut, mandate_update_fut)?;
     payment_data.payment_intent = payment_intent;
 
+    // The merchant is notified of the partial authorization, or the authorization is voided, once
+    // the attempt becomes partially authorized, whichever flow the response of the connector is for
+    if !was_partially_authorized
+        && payment_data.payment_attempt.status == enums::AttemptStatus::PartiallyAuthorized
+    {
+        payments::add_partial_authorization_task(db, &payment_data.payment_attempt)
+            .await
+            .map_err(|error| logger::error!(process_tracker_error=?error))
+            .ok();
+    }
+
     Ok(payment_data)
 }
 
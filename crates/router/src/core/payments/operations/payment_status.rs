@@ -383,6 +383,9 @@ async fn get_tracker_for_sync<
             multiple_capture_data,
             redirect_response: None,
             frm_message: frm_response.ok(),
+            maintenance_fallback: None,
+            decrypted_wallet_card: None,
+            external_authentication_data: None,
         },
         None,
     ))
@@ -11,6 +11,7 @@ use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, Valida
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        metadata,
         payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils as core_utils,
     },
@@ -46,6 +47,14 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         PaymentData<F>,
         Option<CustomerDetails>,
     )> {
+        request
+            .metadata
+            .as_ref()
+            .map(|metadata| {
+                metadata::validate_metadata(&state.conf.merchant_metadata, "metadata", metadata)
+            })
+            .transpose()?;
+
         let (mut payment_intent, mut payment_attempt, currency): (_, _, storage_enums::Currency);
 
         let payment_id = payment_id
@@ -69,6 +78,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         helpers::validate_card_data(request.payment_method_data.clone())?;
 
+        if request.confirm.unwrap_or(false) {
+            helpers::validate_session_expiry_not_reached(&payment_intent)?;
+        }
+
         helpers::validate_payment_status_against_not_allowed_statuses(
             &payment_intent.status,
             &[
@@ -96,6 +109,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             request,
             mandate_type.clone(),
             merchant_account,
+            false,
         )
         .await?;
 
@@ -196,6 +210,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .attach_printable("Error converting feature_metadata to Value")?
             .or(payment_intent.feature_metadata);
         payment_intent.metadata = request.metadata.clone().or(payment_intent.metadata);
+        payment_intent.device_session_id = request
+            .device_session_id
+            .clone()
+            .or(payment_intent.device_session_id);
         Self::populate_payment_intent_with_request(&mut payment_intent, request);
 
         let token = token.or_else(|| payment_attempt.payment_token.clone());
@@ -288,6 +306,24 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         Self::populate_payment_attempt_with_request(&mut payment_attempt, request);
 
+        // The authentication data passed on update takes the place of the one stored before, it is
+        // passed to the connector when the payment is confirmed
+        let external_authentication_data = match request.authentication_data.as_ref() {
+            Some(authentication_data) => {
+                helpers::validate_external_authentication_data(authentication_data)?;
+                payment_attempt.external_authentication_data =
+                    helpers::encrypt_external_authentication_data(
+                        Some(authentication_data),
+                        key_store,
+                    )
+                    .await?;
+                Some(authentication_data.clone())
+            }
+            None => {
+                helpers::decrypt_external_authentication_data(&payment_attempt, key_store).await?
+            }
+        };
+
         let creds_identifier = request
             .merchant_connector_details
             .as_ref()
@@ -343,6 +379,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                maintenance_fallback: None,
+                decrypted_wallet_card: None,
+                external_authentication_data,
             },
             Some(customer_details),
         ))
@@ -463,6 +502,10 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
         let payment_experience = payment_data.payment_attempt.payment_experience;
         let amount_to_capture = payment_data.payment_attempt.amount_to_capture;
         let capture_method = payment_data.payment_attempt.capture_method;
+        let external_authentication_data = payment_data
+            .payment_attempt
+            .external_authentication_data
+            .clone();
         payment_data.payment_attempt = db
             .update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt,
@@ -479,6 +522,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     business_sub_label,
                     amount_to_capture,
                     capture_method,
+                    external_authentication_data,
                 },
                 storage_scheme,
             )
@@ -520,6 +564,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .clone();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
+        let device_session_id = payment_data.payment_intent.device_session_id.clone();
 
         payment_data.payment_intent = db
             .update_payment_intent(
@@ -541,6 +586,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     order_details,
                     metadata,
                     payment_confirm_source: None,
+                    device_session_id,
                 },
                 storage_scheme,
             )
@@ -0,0 +1,470 @@
+//! Identification of the payment which the customer is redirected back for from the page of the
+//! connector, such as after a 3DS challenge or a bank redirect. The payment is identified from the
+//! payment attempt which the router appends to its return URLs, then from the payment in the path
+//! of the return URL, and then from the identifier which the connector appends to the return URL.
+//!
+//! The attempt appended by the router must belong to the merchant, to the payment and to the
+//! connector of the return URL, a return URL whose payment was replaced is not redirected for the
+//! payment it names. When the payment cannot be identified, the customer is redirected to the return
+//! URL of the merchant, or to the configured failure page, with the error code of the failure
+//! appended, instead of being shown an error.
+
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::helpers;
+use crate::{
+    configs::settings::RedirectResponseConfig,
+    consts,
+    core::{
+        errors::{self, RouterResult, StorageErrorExt},
+        webhooks,
+    },
+    routes::AppState,
+    types::{api, domain},
+};
+
+/// Reason for which the payment of a redirect response could not be identified
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedirectIdentificationError {
+    /// Neither the router nor the connector appended an identifier of the payment
+    PaymentNotIdentified,
+    /// The identified payment or attempt does not exist for the merchant of the return URL
+    PaymentNotFound,
+    /// The attempt appended by the router belongs to another payment or connector than the ones of
+    /// the return URL
+    PaymentMismatch,
+}
+
+impl RedirectIdentificationError {
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::PaymentNotIdentified => "payment_not_identified",
+            Self::PaymentNotFound => "payment_not_found",
+            Self::PaymentMismatch => "payment_mismatch",
+        }
+    }
+
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Self::PaymentNotIdentified => "The payment could not be identified from the redirect",
+            Self::PaymentNotFound => "The payment of the redirect does not exist",
+            Self::PaymentMismatch => "The redirect does not belong to the payment",
+        }
+    }
+}
+
+/// Splits the attempt appended by the router off the query parameters of the return URL, the
+/// remaining parameters are the ones appended by the connector, in the order they were appended
+pub fn split_redirect_attempt_id(query_params: &str) -> (Option<String>, String) {
+    let mut attempt_id = None;
+    let mut connector_params = Vec::new();
+
+    for param in query_params.split('&') {
+        match param.split_once('=') {
+            Some((consts::REDIRECT_ATTEMPT_ID_QUERY_PARAM, value)) => {
+                // Connectors which append their parameters to the return URL with a `?` leave them
+                // in the value of the attempt
+                let (value, appended_params) = value
+                    .split_once('?')
+                    .map_or((value, None), |(value, appended)| (value, Some(appended)));
+                attempt_id = Some(value.to_string()).filter(|value| !value.is_empty());
+                connector_params.extend(appended_params);
+            }
+            _ => connector_params.push(param),
+        }
+    }
+
+    (attempt_id, connector_params.join("&"))
+}
+
+/// Identifies the payment of the redirect response of the merchant. Errors are returned only when
+/// the payment could not be looked up, a payment which is not identified, not found or does not
+/// match the return URL is returned as the reason of the failure.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+pub async fn identify_redirect_payment(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector: &api::ConnectorData,
+    connector_name: &str,
+    path_payment_id: Option<&str>,
+    attempt_id: Option<&str>,
+    connector_params: &str,
+    json_payload: Option<&serde_json::Value>,
+) -> RouterResult<Result<String, RedirectIdentificationError>> {
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let storage_scheme = merchant_account.storage_scheme;
+
+    if let Some(attempt_id) = attempt_id {
+        let payment_attempt = match db
+            .find_payment_attempt_by_attempt_id_merchant_id(attempt_id, merchant_id, storage_scheme)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+        {
+            Ok(payment_attempt) => payment_attempt,
+            Err(error) if is_payment_not_found(&error) => {
+                logger::warn!("Payment attempt {attempt_id} of the redirect response not found");
+                return Ok(Err(RedirectIdentificationError::PaymentNotFound));
+            }
+            Err(error) => {
+                return Err(error).attach_printable(
+                    "Failed to find the payment attempt of the redirect response",
+                )
+            }
+        };
+
+        let is_payment_mismatched =
+            path_payment_id.map_or(false, |payment_id| payment_id != payment_attempt.payment_id);
+        let is_connector_mismatched = payment_attempt.connector.as_deref() != Some(connector_name);
+        if is_payment_mismatched || is_connector_mismatched {
+            logger::warn!(
+                ?path_payment_id,
+                attempt_connector = ?payment_attempt.connector,
+                "Payment attempt {attempt_id} does not belong to the payment and connector of the redirect response"
+            );
+            return Ok(Err(RedirectIdentificationError::PaymentMismatch));
+        }
+
+        return find_payment_of_merchant(state, merchant_account, &payment_attempt.payment_id)
+            .await
+            .map(|payment_id| payment_id.ok_or(RedirectIdentificationError::PaymentNotFound));
+    }
+
+    if let Some(payment_id) = path_payment_id {
+        if let Some(payment_id) =
+            find_payment_of_merchant(state, merchant_account, payment_id).await?
+        {
+            return Ok(Ok(payment_id));
+        }
+    }
+
+    let connector_payment_id = connector
+        .connector
+        .get_payment_identifier_from_redirect(connector_params, json_payload)
+        .map_err(|error| logger::warn!(?error, "Failed to read the payment from the redirect"))
+        .ok()
+        .flatten();
+    let Some(connector_payment_id) = connector_payment_id else {
+        logger::warn!(
+            ?path_payment_id,
+            "Payment of the redirect response not identified"
+        );
+        return Ok(Err(if path_payment_id.is_some() {
+            RedirectIdentificationError::PaymentNotFound
+        } else {
+            RedirectIdentificationError::PaymentNotIdentified
+        }));
+    };
+
+    let payment_id = match webhooks::get_payment_id(
+        db,
+        &connector_payment_id,
+        merchant_id,
+        storage_scheme,
+    )
+    .await
+    {
+        Ok(payment_id) => payment_id,
+        Err(error) if is_payment_not_found(&error) => {
+            logger::warn!(
+                ?connector_payment_id,
+                "Payment of the connector identifier of the redirect response not found"
+            );
+            return Ok(Err(RedirectIdentificationError::PaymentNotFound));
+        }
+        Err(error) => return Err(error),
+    };
+
+    find_payment_of_merchant(state, merchant_account, &payment_id)
+        .await
+        .map(|payment_id| payment_id.ok_or(RedirectIdentificationError::PaymentNotFound))
+}
+
+async fn find_payment_of_merchant(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+) -> RouterResult<Option<String>> {
+    match state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+    {
+        Ok(payment_intent) => Ok(Some(payment_intent.payment_id)),
+        Err(error) if is_payment_not_found(&error) => Ok(None),
+        Err(error) => {
+            Err(error).attach_printable("Failed to find the payment of the redirect response")
+        }
+    }
+}
+
+fn is_payment_not_found(error: &error_stack::Report<errors::ApiErrorResponse>) -> bool {
+    matches!(
+        error.current_context(),
+        errors::ApiErrorResponse::PaymentNotFound
+    )
+}
+
+/// Redirects the customer to the return URL of the merchant, or to the configured failure page,
+/// with the error code and message of the failure appended. The payment is reported as not found
+/// when there is no page to redirect the customer to.
+pub fn get_failure_redirection_response(
+    config: &RedirectResponseConfig,
+    merchant_account: &domain::MerchantAccount,
+    error: RedirectIdentificationError,
+) -> RouterResult<api::RedirectionResponse> {
+    let url = merchant_account
+        .return_url
+        .as_ref()
+        .or(config.failure_url.as_ref())
+        .ok_or_else(|| report!(errors::ApiErrorResponse::PaymentNotFound))
+        .attach_printable("No return URL to redirect the unidentified payment to")?;
+
+    let url = url::Url::parse_with_params(
+        url,
+        &[
+            ("status", "failed"),
+            ("error_code", error.error_code()),
+            ("error_message", error.error_message()),
+        ],
+    )
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Unable to parse the url with param")?;
+
+    helpers::make_url_with_signature(url.as_str(), merchant_account)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::types::storage::{self, enums};
+
+    async fn get_state() -> AppState {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(crate::services::MockApiClient),
+        )
+        .await;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        for (payment_id, connector_transaction_id) in [("pay_1", "pi_1"), ("pay_2", "pi_2")] {
+            state
+                .store
+                .insert_payment_intent(
+                    storage::PaymentIntentNew {
+                        payment_id: payment_id.to_string(),
+                        merchant_id: "merchant_1".to_string(),
+                        status: enums::IntentStatus::RequiresCustomerAction,
+                        amount: 1000,
+                        currency: Some(enums::Currency::USD),
+                        active_attempt_id: format!("{payment_id}_1"),
+                        ..Default::default()
+                    },
+                    storage_scheme,
+                )
+                .await
+                .unwrap();
+            state
+                .store
+                .insert_payment_attempt(
+                    storage::PaymentAttemptNew {
+                        payment_id: payment_id.to_string(),
+                        merchant_id: "merchant_1".to_string(),
+                        attempt_id: format!("{payment_id}_1"),
+                        status: enums::AttemptStatus::AuthenticationPending,
+                        amount: 1000,
+                        connector: Some("stripe".to_string()),
+                        connector_transaction_id: Some(connector_transaction_id.to_string()),
+                        ..Default::default()
+                    },
+                    storage_scheme,
+                )
+                .await
+                .unwrap();
+        }
+
+        state
+    }
+
+    fn get_merchant_account(merchant_id: &str) -> domain::MerchantAccount {
+        domain::MerchantAccount {
+            id: None,
+            merchant_id: merchant_id.to_string(),
+            return_url: Some("https://merchant.test/checkout/return".to_string()),
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::Value::Null,
+            frm_routing_algorithm: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            intent_fulfillment_time: None,
+            payout_routing_algorithm: None,
+            organization_id: None,
+            is_recon_enabled: false,
+            default_profile: None,
+            recon_status: enums::ReconStatus::NotRequested,
+            webhook_auth_details: None,
+            default_currency: None,
+            default_country: None,
+            pii_policy: None,
+        }
+    }
+
+    async fn identify(
+        state: &AppState,
+        merchant_id: &str,
+        path_payment_id: Option<&str>,
+        query_params: &str,
+    ) -> Result<String, RedirectIdentificationError> {
+        let connector = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            "stripe",
+            api::GetToken::Connector,
+        )
+        .unwrap();
+        let (attempt_id, connector_params) = split_redirect_attempt_id(query_params);
+
+        identify_redirect_payment(
+            state,
+            &get_merchant_account(merchant_id),
+            &connector,
+            "stripe",
+            path_payment_id,
+            attempt_id.as_deref(),
+            &connector_params,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_attempt_id_is_split_off_the_connector_params() {
+        assert_eq!(
+            split_redirect_attempt_id("hs_attempt_id=pay_1_1&payment_intent=pi_1"),
+            (
+                Some("pay_1_1".to_string()),
+                "payment_intent=pi_1".to_string()
+            )
+        );
+        // The parameters of connectors which append them with a `?` are kept for the connector
+        assert_eq!(
+            split_redirect_attempt_id("hs_attempt_id=pay_1_1?status=success"),
+            (Some("pay_1_1".to_string()), "status=success".to_string())
+        );
+        assert_eq!(
+            split_redirect_attempt_id("paymentToken=abc"),
+            (None, "paymentToken=abc".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_is_identified_from_each_source() {
+        let state = get_state().await;
+
+        // The attempt appended by the router
+        assert_eq!(
+            identify(&state, "merchant_1", Some("pay_1"), "hs_attempt_id=pay_1_1").await,
+            Ok("pay_1".to_string())
+        );
+        // The payment in the path of the return URL
+        assert_eq!(
+            identify(
+                &state,
+                "merchant_1",
+                Some("pay_2"),
+                "redirect_status=failed"
+            )
+            .await,
+            Ok("pay_2".to_string())
+        );
+        // The identifier appended by the connector, when the payment in the path is mangled
+        assert_eq!(
+            identify(
+                &state,
+                "merchant_1",
+                Some("pay_"),
+                "payment_intent=pi_1&redirect_status=succeeded"
+            )
+            .await,
+            Ok("pay_1".to_string())
+        );
+        assert_eq!(
+            identify(&state, "merchant_1", None, "redirect_status=succeeded").await,
+            Err(RedirectIdentificationError::PaymentNotIdentified)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spoofed_payment_is_redirected_to_the_failure_page() {
+        let state = get_state().await;
+
+        // The attempt of another payment of the merchant
+        let error = identify(&state, "merchant_1", Some("pay_2"), "hs_attempt_id=pay_1_1")
+            .await
+            .unwrap_err();
+        assert_eq!(error, RedirectIdentificationError::PaymentMismatch);
+        // The payments of other merchants are not found
+        assert_eq!(
+            identify(&state, "merchant_2", Some("pay_1"), "hs_attempt_id=pay_1_1").await,
+            Err(RedirectIdentificationError::PaymentNotFound)
+        );
+        assert_eq!(
+            identify(&state, "merchant_2", Some("pay_1"), "payment_intent=pi_1").await,
+            Err(RedirectIdentificationError::PaymentNotFound)
+        );
+
+        let response = get_failure_redirection_response(
+            &state.conf.redirect_response,
+            &get_merchant_account("merchant_1"),
+            error,
+        )
+        .unwrap();
+        assert_eq!(
+            response.return_url_with_query_params,
+            "https://merchant.test/checkout/return?status=failed&error_code=payment_mismatch&error_message=The+redirect+does+not+belong+to+the+payment"
+        );
+
+        // Without a return URL of the merchant, the customer is redirected to the configured page
+        let merchant_account = domain::MerchantAccount {
+            return_url: None,
+            ..get_merchant_account("merchant_1")
+        };
+        let config = RedirectResponseConfig {
+            failure_url: Some("https://router.test/payment/failed".to_string()),
+        };
+        let response = get_failure_redirection_response(&config, &merchant_account, error).unwrap();
+        assert!(response.return_url_with_query_params.starts_with(
+            "https://router.test/payment/failed?status=failed&error_code=payment_mismatch"
+        ));
+        assert!(get_failure_redirection_response(
+            &RedirectResponseConfig::default(),
+            &merchant_account,
+            error
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,595 @@
+use api_models::{
+    admin::{ThreeDsDecisionConditions, ThreeDsDecisionRule},
+    enums as api_enums,
+    payments::AdditionalPaymentData,
+};
+use common_utils::ext_traits::{Encode, StringExt, ValueExt};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+
+use super::{helpers, PaymentData};
+use crate::{
+    core::errors::{self, RouterResult},
+    db::StorageInterface,
+    routes::AppState,
+    types::domain,
+    utils,
+};
+
+/// Name recorded as the rule of the 3DS decision taken for mail order and telephone order payments
+const MOTO_THREE_DS_DECISION_RULE: &str = "moto";
+
+/// Key of the config holding the 3DS decision rules of the merchant
+pub fn get_three_ds_decision_rules_key(merchant_id: &str) -> String {
+    format!("three_ds_decision_rules_{merchant_id}")
+}
+
+/// Fetches the 3DS decision rules configured by the merchant, in order of priority
+pub async fn get_three_ds_decision_rules(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> Vec<ThreeDsDecisionRule> {
+    let rules = db
+        .find_config_by_key(&get_three_ds_decision_rules_key(merchant_id))
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_struct("ThreeDsDecisionRules")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match rules {
+        Ok(rules) => rules,
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, "Failed to fetch the 3DS decision rules");
+            }
+            vec![]
+        }
+    }
+}
+
+pub fn validate_three_ds_decision_rules(rules: &[ThreeDsDecisionRule]) -> RouterResult<()> {
+    for (index, rule) in rules.iter().enumerate() {
+        let invalid_rule = |message: &str| {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("rules[{index}]: {message}"),
+            })
+            .into_report()
+        };
+
+        utils::when(rule.name.trim().is_empty(), || {
+            invalid_rule("name must not be empty")
+        })?;
+        utils::when(
+            rules[..index]
+                .iter()
+                .any(|previous| previous.name == rule.name),
+            || invalid_rule(&format!("name `{}` is used by another rule", rule.name)),
+        )?;
+
+        let conditions = &rule.conditions;
+        utils::when(
+            conditions
+                .amount_greater_than
+                .map_or(false, |amount| amount < 0)
+                || conditions
+                    .amount_less_than
+                    .map_or(false, |amount| amount <= 0),
+            || invalid_rule("amount thresholds must be positive"),
+        )?;
+        utils::when(
+            conditions
+                .amount_greater_than
+                .zip(conditions.amount_less_than)
+                .map_or(false, |(greater_than, less_than)| greater_than >= less_than),
+            || invalid_rule("amount_greater_than must be less than amount_less_than"),
+        )?;
+        utils::when(
+            conditions
+                .currencies
+                .as_ref()
+                .map_or(false, |currencies| currencies.is_empty()),
+            || invalid_rule("currencies must not be empty when provided"),
+        )?;
+        utils::when(
+            rule.sca_exemption.is_some() && rule.decision != api_enums::ThreeDsDecision::Skip,
+            || invalid_rule("sca_exemption can be requested only with the skip decision"),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The 3DS decision taken for a payment attempt, recorded on the attempt
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ThreeDsDecisionData {
+    pub rule_name: String,
+    pub decision: api_enums::ThreeDsDecision,
+    pub reason: String,
+    pub requested_authentication_type: Option<api_enums::AuthenticationType>,
+    pub authentication_type: api_enums::AuthenticationType,
+    pub sca_exemption: Option<api_enums::ScaExemptionType>,
+}
+
+/// Details of the payment against which the 3DS decision rules are evaluated
+#[derive(Clone, Debug, Default)]
+pub struct ThreeDsDecisionInput {
+    pub amount: i64,
+    pub currency: Option<api_enums::Currency>,
+    pub card_issuing_country: Option<String>,
+    pub billing_country: Option<api_enums::CountryAlpha2>,
+    pub is_saved_card: bool,
+    pub is_merchant_initiated: bool,
+    pub requested_authentication_type: Option<api_enums::AuthenticationType>,
+}
+
+impl ThreeDsDecisionInput {
+    async fn from_payment_data<F: Clone>(
+        db: &dyn StorageInterface,
+        payment_data: &PaymentData<F>,
+    ) -> Self {
+        let card_issuing_country = match &payment_data.payment_method_data {
+            Some(payment_method_data) => {
+                match helpers::get_additional_payment_data(payment_method_data, db).await {
+                    AdditionalPaymentData::Card(card_info) => card_info.card_issuing_country,
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        let is_recurring = payment_data.mandate_id.is_some();
+
+        Self {
+            amount: payment_data.amount.into(),
+            currency: Some(payment_data.currency),
+            card_issuing_country,
+            billing_country: payment_data
+                .address
+                .billing
+                .as_ref()
+                .and_then(|billing| billing.address.as_ref())
+                .and_then(|address| address.country),
+            is_saved_card: payment_data.token.is_some() || is_recurring,
+            is_merchant_initiated: payment_data.payment_intent.off_session == Some(true)
+                || is_recurring,
+            requested_authentication_type: payment_data.payment_attempt.authentication_type,
+        }
+    }
+}
+
+/// The card issuing country obtained from the BIN is the name of the country, while the billing
+/// country is an ISO 3166 code, both the forms are accepted for the issuing country
+fn is_card_issued_in_country(
+    card_issuing_country: &str,
+    country: api_enums::CountryAlpha2,
+) -> bool {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let card_issuing_country = normalize(card_issuing_country);
+    let country = api_enums::Country::from_alpha2(country);
+
+    [
+        country.to_alpha2().to_string(),
+        format!("{:?}", country.to_alpha3()),
+        country.to_string(),
+    ]
+    .iter()
+    .any(|name| normalize(name) == card_issuing_country)
+}
+
+/// Returns the conditions satisfied by the payment if all the conditions of the rule are
+/// satisfied. A condition on a detail which is not known for the payment is not satisfied.
+fn get_matched_conditions(
+    conditions: &ThreeDsDecisionConditions,
+    input: &ThreeDsDecisionInput,
+) -> Option<Vec<String>> {
+    let mut matched = vec![];
+
+    if let Some(amount_greater_than) = conditions.amount_greater_than {
+        if input.amount <= amount_greater_than {
+            return None;
+        }
+        matched.push(format!("amount greater than {amount_greater_than}"));
+    }
+
+    if let Some(amount_less_than) = conditions.amount_less_than {
+        if input.amount >= amount_less_than {
+            return None;
+        }
+        matched.push(format!("amount less than {amount_less_than}"));
+    }
+
+    if let Some(currencies) = &conditions.currencies {
+        let currency = input
+            .currency
+            .filter(|currency| currencies.contains(currency))?;
+        matched.push(format!("currency {currency}"));
+    }
+
+    if let Some(outside_billing_country) = conditions.card_issuer_outside_billing_country {
+        let issued_in_billing_country = is_card_issued_in_country(
+            input.card_issuing_country.as_deref()?,
+            input.billing_country?,
+        );
+        if issued_in_billing_country == outside_billing_country {
+            return None;
+        }
+        matched.push(
+            if outside_billing_country {
+                "card issued outside the billing country"
+            } else {
+                "card issued in the billing country"
+            }
+            .to_string(),
+        );
+    }
+
+    if let Some(saved_card) = conditions.saved_card {
+        if input.is_saved_card != saved_card {
+            return None;
+        }
+        matched.push(if saved_card { "saved card" } else { "new card" }.to_string());
+    }
+
+    Some(matched)
+}
+
+fn get_authentication_type(
+    decision: api_enums::ThreeDsDecision,
+    input: &ThreeDsDecisionInput,
+) -> api_enums::AuthenticationType {
+    match decision {
+        api_enums::ThreeDsDecision::Force => api_enums::AuthenticationType::ThreeDs,
+        // The customer is not present to complete the challenge in merchant initiated payments
+        api_enums::ThreeDsDecision::Prefer if input.is_merchant_initiated => input
+            .requested_authentication_type
+            .unwrap_or(api_enums::AuthenticationType::NoThreeDs),
+        api_enums::ThreeDsDecision::Prefer => api_enums::AuthenticationType::ThreeDs,
+        api_enums::ThreeDsDecision::Skip => api_enums::AuthenticationType::NoThreeDs,
+    }
+}
+
+/// Evaluates the rules in order, the first rule matching the payment decides its authentication type
+pub fn evaluate_three_ds_decision_rules(
+    rules: &[ThreeDsDecisionRule],
+    input: &ThreeDsDecisionInput,
+) -> Option<ThreeDsDecisionData> {
+    rules.iter().find_map(|rule| {
+        let matched_conditions = get_matched_conditions(&rule.conditions, input)?;
+        let authentication_type = get_authentication_type(rule.decision, input);
+        let reason = if matched_conditions.is_empty() {
+            format!("rule `{}` matches all payments", rule.name)
+        } else {
+            format!(
+                "rule `{}` matched on {}",
+                rule.name,
+                matched_conditions.join(", ")
+            )
+        };
+
+        Some(ThreeDsDecisionData {
+            rule_name: rule.name.clone(),
+            decision: rule.decision,
+            reason,
+            requested_authentication_type: input.requested_authentication_type,
+            authentication_type,
+            sca_exemption: rule
+                .sca_exemption
+                .filter(|_| authentication_type == api_enums::AuthenticationType::NoThreeDs),
+        })
+    })
+}
+
+/// Mail order and telephone order payments are made without the customer being present to
+/// complete a challenge, 3DS is skipped for them whatever the rules of the merchant
+fn get_moto_three_ds_decision(
+    requested_authentication_type: Option<api_enums::AuthenticationType>,
+) -> ThreeDsDecisionData {
+    ThreeDsDecisionData {
+        rule_name: MOTO_THREE_DS_DECISION_RULE.to_string(),
+        decision: api_enums::ThreeDsDecision::Skip,
+        reason: "mail order and telephone order payments are not authenticated with 3DS"
+            .to_string(),
+        requested_authentication_type,
+        authentication_type: api_enums::AuthenticationType::NoThreeDs,
+        sca_exemption: None,
+    }
+}
+
+/// Decides the authentication type of a card payment being confirmed from the 3DS decision rules
+/// of the merchant, overriding the authentication type requested for the payment
+#[instrument(skip_all)]
+pub async fn decide_authentication_type<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_data: &mut PaymentData<F>,
+) -> RouterResult<()> {
+    if payment_data.payment_attempt.payment_method != Some(api_enums::PaymentMethod::Card) {
+        return Ok(());
+    }
+
+    let is_moto = payment_data
+        .payment_intent
+        .payment_channel
+        .map_or(false, |payment_channel| payment_channel.is_moto());
+    let three_ds_decision = if is_moto {
+        Some(get_moto_three_ds_decision(
+            payment_data.payment_attempt.authentication_type,
+        ))
+    } else {
+        let db = &*state.store;
+        let rules = get_three_ds_decision_rules(db, &merchant_account.merchant_id).await;
+        if rules.is_empty() {
+            return Ok(());
+        }
+
+        let input = ThreeDsDecisionInput::from_payment_data(db, payment_data).await;
+        evaluate_three_ds_decision_rules(&rules, &input)
+    };
+
+    if let Some(three_ds_decision) = &three_ds_decision {
+        logger::info!(?three_ds_decision, "3DS decision rule matched");
+        payment_data.payment_attempt.authentication_type =
+            Some(three_ds_decision.authentication_type);
+    }
+
+    payment_data.payment_attempt.three_ds_decision_data = three_ds_decision
+        .as_ref()
+        .map(Encode::<ThreeDsDecisionData>::encode_to_value)
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the 3DS decision")?;
+
+    Ok(())
+}
+
+/// The SCA exemption to be requested from the issuer, as decided for the payment attempt
+pub fn get_sca_exemption(
+    three_ds_decision_data: Option<serde_json::Value>,
+) -> RouterResult<Option<api_enums::ScaExemptionType>> {
+    Ok(three_ds_decision_data
+        .map(|data| data.parse_value::<ThreeDsDecisionData>("ThreeDsDecisionData"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the 3DS decision of the payment attempt")?
+        .and_then(|three_ds_decision| three_ds_decision.sca_exemption))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_rule(
+        name: &str,
+        conditions: ThreeDsDecisionConditions,
+        decision: api_enums::ThreeDsDecision,
+        sca_exemption: Option<api_enums::ScaExemptionType>,
+    ) -> ThreeDsDecisionRule {
+        ThreeDsDecisionRule {
+            name: name.to_string(),
+            conditions,
+            decision,
+            sca_exemption,
+        }
+    }
+
+    fn get_input(amount: i64) -> ThreeDsDecisionInput {
+        ThreeDsDecisionInput {
+            amount,
+            currency: Some(api_enums::Currency::EUR),
+            card_issuing_country: Some("UNITED STATES OF AMERICA".to_string()),
+            billing_country: Some(api_enums::CountryAlpha2::FR),
+            is_saved_card: false,
+            is_merchant_initiated: false,
+            requested_authentication_type: Some(api_enums::AuthenticationType::NoThreeDs),
+        }
+    }
+
+    #[test]
+    fn test_force_rule_overrides_no_three_ds_request() {
+        let rules = vec![get_rule(
+            "force_3ds_above_150_eur",
+            ThreeDsDecisionConditions {
+                amount_greater_than: Some(15000),
+                currencies: Some(vec![api_enums::Currency::EUR]),
+                ..Default::default()
+            },
+            api_enums::ThreeDsDecision::Force,
+            None,
+        )];
+
+        let decision = evaluate_three_ds_decision_rules(&rules, &get_input(20000)).unwrap();
+        assert_eq!(
+            decision.authentication_type,
+            api_enums::AuthenticationType::ThreeDs
+        );
+        assert_eq!(
+            decision.requested_authentication_type,
+            Some(api_enums::AuthenticationType::NoThreeDs)
+        );
+        assert_eq!(decision.rule_name, "force_3ds_above_150_eur");
+        assert_eq!(
+            decision.reason,
+            "rule `force_3ds_above_150_eur` matched on amount greater than 15000, currency EUR"
+        );
+
+        assert!(evaluate_three_ds_decision_rules(&rules, &get_input(10000)).is_none());
+    }
+
+    #[test]
+    fn test_first_matching_rule_decides() {
+        let rules = vec![
+            get_rule(
+                "skip_saved_cards_under_30_eur",
+                ThreeDsDecisionConditions {
+                    amount_less_than: Some(3000),
+                    saved_card: Some(true),
+                    ..Default::default()
+                },
+                api_enums::ThreeDsDecision::Skip,
+                Some(api_enums::ScaExemptionType::LowValue),
+            ),
+            get_rule(
+                "force_3ds",
+                ThreeDsDecisionConditions::default(),
+                api_enums::ThreeDsDecision::Force,
+                None,
+            ),
+        ];
+
+        let saved_card = ThreeDsDecisionInput {
+            is_saved_card: true,
+            requested_authentication_type: Some(api_enums::AuthenticationType::ThreeDs),
+            ..get_input(2000)
+        };
+        let decision = evaluate_three_ds_decision_rules(&rules, &saved_card).unwrap();
+        assert_eq!(decision.rule_name, "skip_saved_cards_under_30_eur");
+        assert_eq!(
+            decision.authentication_type,
+            api_enums::AuthenticationType::NoThreeDs
+        );
+        assert_eq!(
+            decision.sca_exemption,
+            Some(api_enums::ScaExemptionType::LowValue)
+        );
+
+        let decision = evaluate_three_ds_decision_rules(&rules, &get_input(2000)).unwrap();
+        assert_eq!(decision.rule_name, "force_3ds");
+        assert_eq!(decision.sca_exemption, None);
+    }
+
+    #[test]
+    fn test_card_issuer_outside_billing_country() {
+        let rules = vec![get_rule(
+            "force_3ds_for_foreign_cards",
+            ThreeDsDecisionConditions {
+                card_issuer_outside_billing_country: Some(true),
+                ..Default::default()
+            },
+            api_enums::ThreeDsDecision::Force,
+            None,
+        )];
+
+        assert!(evaluate_three_ds_decision_rules(&rules, &get_input(1000)).is_some());
+
+        let domestic_card = ThreeDsDecisionInput {
+            card_issuing_country: Some("FRANCE".to_string()),
+            ..get_input(1000)
+        };
+        assert!(evaluate_three_ds_decision_rules(&rules, &domestic_card).is_none());
+
+        let unknown_issuer = ThreeDsDecisionInput {
+            card_issuing_country: None,
+            ..get_input(1000)
+        };
+        assert!(evaluate_three_ds_decision_rules(&rules, &unknown_issuer).is_none());
+
+        assert!(is_card_issued_in_country(
+            "United States of America",
+            api_enums::CountryAlpha2::US
+        ));
+        assert!(is_card_issued_in_country(
+            "US",
+            api_enums::CountryAlpha2::US
+        ));
+        assert!(is_card_issued_in_country(
+            "usa",
+            api_enums::CountryAlpha2::US
+        ));
+        assert!(!is_card_issued_in_country(
+            "GERMANY",
+            api_enums::CountryAlpha2::US
+        ));
+    }
+
+    #[test]
+    fn test_prefer_rule_for_merchant_initiated_payment() {
+        let rules = vec![get_rule(
+            "prefer_3ds",
+            ThreeDsDecisionConditions::default(),
+            api_enums::ThreeDsDecision::Prefer,
+            None,
+        )];
+
+        let decision = evaluate_three_ds_decision_rules(&rules, &get_input(1000)).unwrap();
+        assert_eq!(
+            decision.authentication_type,
+            api_enums::AuthenticationType::ThreeDs
+        );
+
+        let merchant_initiated = ThreeDsDecisionInput {
+            is_merchant_initiated: true,
+            ..get_input(1000)
+        };
+        let decision = evaluate_three_ds_decision_rules(&rules, &merchant_initiated).unwrap();
+        assert_eq!(
+            decision.authentication_type,
+            api_enums::AuthenticationType::NoThreeDs
+        );
+    }
+
+    #[test]
+    fn test_validate_three_ds_decision_rules() {
+        let valid = get_rule(
+            "skip_under_30",
+            ThreeDsDecisionConditions {
+                amount_less_than: Some(3000),
+                ..Default::default()
+            },
+            api_enums::ThreeDsDecision::Skip,
+            Some(api_enums::ScaExemptionType::LowValue),
+        );
+        assert!(validate_three_ds_decision_rules(&[valid.clone()]).is_ok());
+
+        assert!(validate_three_ds_decision_rules(&[valid.clone(), valid.clone()]).is_err());
+
+        let exemption_with_force = ThreeDsDecisionRule {
+            decision: api_enums::ThreeDsDecision::Force,
+            ..valid.clone()
+        };
+        assert!(validate_three_ds_decision_rules(&[exemption_with_force]).is_err());
+
+        let inverted_amount_range = ThreeDsDecisionRule {
+            conditions: ThreeDsDecisionConditions {
+                amount_greater_than: Some(5000),
+                amount_less_than: Some(3000),
+                ..Default::default()
+            },
+            ..valid.clone()
+        };
+        assert!(validate_three_ds_decision_rules(&[inverted_amount_range]).is_err());
+
+        let empty_currencies = ThreeDsDecisionRule {
+            conditions: ThreeDsDecisionConditions {
+                currencies: Some(vec![]),
+                ..Default::default()
+            },
+            ..valid
+        };
+        assert!(validate_three_ds_decision_rules(&[empty_currencies]).is_err());
+    }
+
+    #[test]
+    fn test_moto_payment_skips_three_ds() {
+        let decision = get_moto_three_ds_decision(Some(api_enums::AuthenticationType::ThreeDs));
+
+        assert_eq!(decision.decision, api_enums::ThreeDsDecision::Skip);
+        assert_eq!(
+            decision.authentication_type,
+            api_enums::AuthenticationType::NoThreeDs
+        );
+        assert_eq!(
+            decision.requested_authentication_type,
+            Some(api_enums::AuthenticationType::ThreeDs)
+        );
+        assert_eq!(decision.sca_exemption, None);
+    }
+}
@@ -71,11 +71,25 @@ where
                 )
                 .await?;
                 let merchant_id = &merchant_account.merchant_id;
+                let card_fingerprint = match payment_method_create_request.card.as_ref() {
+                    Some(card) => Some(
+                        payment_methods::cards::get_card_fingerprint(
+                            db,
+                            merchant_id,
+                            key_store,
+                            &card.card_number,
+                        )
+                        .await?,
+                    ),
+                    None => None,
+                };
 
                 let locker_response = save_in_locker(
                     state,
                     merchant_account,
                     payment_method_create_request.to_owned(),
+                    card_fingerprint.as_deref(),
+                    key_store,
                 )
                 .await?;
                 let is_duplicate = locker_response.1;
@@ -126,6 +140,7 @@ where
                                             merchant_id,
                                             pm_metadata,
                                             pm_data_encrypted,
+                                            card_fingerprint,
                                         )
                                         .await
                                         .change_context(
@@ -155,6 +170,7 @@ where
                         merchant_id,
                         pm_metadata,
                         pm_data_encrypted,
+                        card_fingerprint,
                     )
                     .await
                     .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -174,6 +190,8 @@ pub async fn save_in_locker(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
     payment_method_request: api::PaymentMethodCreate,
+    card_fingerprint: Option<&str>,
+    key_store: &domain::MerchantKeyStore,
 ) -> RouterResult<(api_models::payment_methods::PaymentMethodResponse, bool)> {
     payment_method_request.validate()?;
     let merchant_id = &merchant_account.merchant_id;
@@ -181,17 +199,19 @@ pub async fn save_in_locker(
         .customer_id
         .clone()
         .get_required_value("customer_id")?;
-    match payment_method_request.card.clone() {
-        Some(card) => payment_methods::cards::add_card_to_locker(
-            state,
-            payment_method_request,
-            card,
-            customer_id,
-            merchant_account,
-        )
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Add Card Failed"),
+    match payment_method_request.card.clone().zip(card_fingerprint) {
+        Some((card, card_fingerprint)) => {
+            payment_methods::cards::save_card_of_customer(
+                state,
+                payment_method_request,
+                card,
+                customer_id,
+                card_fingerprint,
+                merchant_account,
+                key_store,
+            )
+            .await
+        }
         None => {
             let pm_id = common_utils::generate_id(crate::consts::ID_LENGTH, "pm");
             let payment_method_response = api::PaymentMethodResponse {
@@ -10,10 +10,11 @@ use router_env::{instrument, tracing};
 use super::{flows::Feature, PaymentAddress, PaymentData};
 use crate::{
     configs::settings::{ConnectorRequestReferenceIdConfig, Server},
-    connector::Nexinets,
+    connector::{utils::cancellation_reason, Nexinets},
     core::{
         errors::{self, RouterResponse, RouterResult},
-        payments::{self, helpers},
+        metadata as core_metadata,
+        payments::{self, amounts, helpers, three_ds_decision},
         utils as core_utils,
     },
     routes::{metrics, AppState},
@@ -49,6 +50,11 @@ where
     fp_utils::when(merchant_connector_account.is_disabled(), || {
         Err(errors::ApiErrorResponse::MerchantConnectorAccountDisabled)
     })?;
+    helpers::validate_connector_account_mode(
+        &state.conf.livemode,
+        merchant_connector_account,
+        payment_data.payment_intent.livemode,
+    )?;
 
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
 
@@ -81,6 +87,10 @@ where
         connector_metadata: None,
         network_txn_id: None,
         connector_response_reference_id: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
     });
 
     let additional_data = PaymentAdditionalData {
@@ -151,6 +161,8 @@ where
         connector_request_reference_id: core_utils::get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            connector_id,
+            &payment_data.payment_intent,
             &payment_data.payment_attempt,
         ),
         preprocessing_id: payment_data.payment_attempt.preprocessing_step_id,
@@ -163,6 +175,11 @@ where
         connector_api_version,
         connector_http_status_code: None,
         apple_pay_flow,
+        locale: payment_data.payment_attempt.locale,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: payment_data.payment_attempt.authorized_amount,
     };
 
     Ok(router_data)
@@ -238,6 +255,7 @@ where
             payment_data.setup_mandate,
             connector_request_reference_id_config,
             connector_http_status_code,
+            payment_data.maintenance_fallback,
         )
     }
 }
@@ -354,6 +372,7 @@ pub fn payments_to_payments_response<R, Op>(
     mandate_data: Option<MandateData>,
     connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
     connector_http_status_code: Option<u16>,
+    maintenance_fallback: Option<api_models::payments::MaintenanceFallback>,
 ) -> RouterResponse<api::PaymentsResponse>
 where
     Op: Debug,
@@ -369,6 +388,11 @@ where
             field_name: "amount",
         })?;
     let mandate_id = payment_attempt.mandate_id.clone();
+    let payment_amounts = amounts::get_payment_amounts(
+        &(&payment_attempt).into(),
+        payment_intent.amount_captured,
+        &refunds,
+    );
     let refunds_response = if refunds.is_empty() {
         None
     } else {
@@ -418,6 +442,35 @@ where
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "payment_method_data",
             })?;
+    let card_verification_results: Option<api::CardVerificationResults> = payment_attempt
+        .card_verification_results
+        .clone()
+        .map(|results| results.parse_value("CardVerificationResults"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the card verification results")?;
+    let locale = payment_attempt.locale.clone();
+    let auth_code = payment_attempt.auth_code.clone();
+    let acquirer_reference_number = payment_attempt.acquirer_reference_number.clone();
+    let applied_defaults: Option<Vec<api_models::enums::PaymentDefaultField>> = payment_intent
+        .applied_defaults
+        .clone()
+        .map(|applied_defaults| applied_defaults.parse_value("AppliedDefaults"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the applied payment defaults")?;
+    let split_payment = helpers::get_split_payment_details_of_intent(&payment_intent)?;
+    let amount_details = helpers::get_amount_details_of_intent(&payment_intent)?;
+    let connector_fee = payment_attempt
+        .connector_fee_amount
+        .zip(payment_attempt.fee_currency)
+        .map(
+            |(fee_amount, fee_currency)| api_models::payments::ConnectorFeeDetails {
+                fee_amount,
+                fee_currency,
+                net_amount: payment_attempt.net_amount,
+            },
+        );
     let merchant_decision = payment_intent.merchant_decision.to_owned();
     let frm_message = fraud_check.map(FrmMessage::foreign_from);
 
@@ -454,10 +507,16 @@ where
             } else {
                 let mut next_action_response = None;
 
-                let bank_transfer_next_steps =
-                    bank_transfer_next_steps_check(payment_attempt.clone())?;
+                let connector_next_action = connector_next_action_check(payment_attempt.clone())?;
 
-                let next_action_voucher = voucher_next_steps_check(payment_attempt.clone())?;
+                // The untyped next steps are looked up for connectors not emitting `ConnectorNextActionData` and for older attempts
+                let (bank_transfer_next_steps, next_action_voucher) = match connector_next_action {
+                    Some(_) => (None, None),
+                    None => (
+                        bank_transfer_next_steps_check(payment_attempt.clone())?,
+                        voucher_next_steps_check(payment_attempt.clone())?,
+                    ),
+                };
 
                 let next_action_containing_qr_code_url =
                     qr_code_next_steps_check(payment_attempt.clone())?;
@@ -466,17 +525,22 @@ where
                     wait_screen_next_steps_check(payment_attempt.clone())?;
 
                 if payment_intent.status == enums::IntentStatus::RequiresCustomerAction
+                    || connector_next_action.is_some()
                     || bank_transfer_next_steps.is_some()
                     || next_action_voucher.is_some()
                     || next_action_containing_qr_code_url.is_some()
                     || next_action_containing_wait_screen.is_some()
                 {
-                    next_action_response = bank_transfer_next_steps
-                        .map(|bank_transfer| {
+                    next_action_response = connector_next_action
+                        .map(|next_action| {
+                            set_virtual_account_expiry(next_action, payment_intent.session_expiry)
+                        })
+                        .map(api_models::payments::NextActionData::from)
+                        .or(bank_transfer_next_steps.map(|bank_transfer| {
                             api_models::payments::NextActionData::DisplayBankTransferInformation {
                                 bank_transfer_steps_and_charges_details: bank_transfer,
                             }
-                        })
+                        }))
                         .or(next_action_voucher.map(|voucher_data| {
                             api_models::payments::NextActionData::DisplayVoucherInformation {
                                 voucher_details: voucher_data,
@@ -485,6 +549,7 @@ where
                         .or(next_action_containing_qr_code_url.map(|qr_code_data| {
                             api_models::payments::NextActionData::QrCodeInformation {
                                 image_data_url: qr_code_data.image_data_url,
+                                qr_code_data: qr_code_data.qr_code_data,
                                 display_to_timestamp: qr_code_data.display_to_timestamp,
                             }
                         }))
@@ -492,6 +557,7 @@ where
                             api_models::payments::NextActionData::WaitScreenInformation {
                                 display_from_timestamp: wait_screen_data.display_from_timestamp,
                                 display_to_timestamp: wait_screen_data.display_to_timestamp,
+                                poll_interval_in_secs: None,
                             }
                         }))
                         .or(redirection_data.map(|_| {
@@ -531,8 +597,11 @@ where
                         .set_merchant_id(Some(payment_attempt.merchant_id))
                         .set_status(payment_intent.status)
                         .set_amount(payment_attempt.amount)
-                        .set_amount_capturable(Some(payment_attempt.amount_capturable))
+                        .set_amount_capturable(Some(payment_amounts.amount_capturable))
                         .set_amount_received(payment_intent.amount_captured)
+                        .set_amount_captured(Some(payment_amounts.amount_captured))
+                        .set_amount_refunded(Some(payment_amounts.amount_refunded))
+                        .set_amount_refundable(Some(payment_amounts.amount_refundable))
                         .set_connector(routed_through)
                         .set_client_secret(payment_intent.client_secret.map(masking::Secret::new))
                         .set_created(Some(payment_intent.created_at))
@@ -581,6 +650,8 @@ where
                                             start_date: i.start_date,
                                             end_date: i.end_date,
                                             metadata: i.metadata,
+                                            frequency: i.frequency,
+                                            max_charges_per_period: i.max_charges_per_period,
                                         }))
                                     }
                                     data_models::mandates::MandateDataType::SingleUse(i) => {
@@ -591,6 +662,8 @@ where
                                                 start_date: i.start_date,
                                                 end_date: i.end_date,
                                                 metadata: i.metadata,
+                                                frequency: i.frequency,
+                                                max_charges_per_period: i.max_charges_per_period,
                                             },
                                         )
                                     }
@@ -626,6 +699,7 @@ where
                         .set_next_action(next_action_response)
                         .set_return_url(payment_intent.return_url)
                         .set_cancellation_reason(payment_attempt.cancellation_reason)
+                        .set_cancellation_reason_code(payment_attempt.cancellation_reason_code)
                         .set_authentication_type(payment_attempt.authentication_type)
                         .set_statement_descriptor_name(payment_intent.statement_descriptor_name)
                         .set_statement_descriptor_suffix(payment_intent.statement_descriptor_suffix)
@@ -657,6 +731,24 @@ where
                         .set_reference_id(payment_attempt.connector_response_reference_id)
                         .set_profile_id(payment_intent.profile_id)
                         .set_attempt_count(payment_intent.attempt_count)
+                        .set_expires_on(payment_intent.session_expiry)
+                        .set_card_verification_results(card_verification_results)
+                        .set_locale(locale)
+                        .set_connector_fee(connector_fee)
+                        .set_auth_code(auth_code)
+                        .set_acquirer_reference_number(acquirer_reference_number)
+                        .set_authorization_expires_at(payment_attempt.authorization_expires_at)
+                        .set_applied_defaults(applied_defaults)
+                        .set_merchant_order_reference_id(payment_intent.merchant_order_reference_id)
+                        .set_suspected_duplicate_of(payment_intent.suspected_duplicate_of)
+                        .set_livemode(payment_intent.livemode)
+                        .set_split_payment(split_payment)
+                        .set_amount_details(amount_details)
+                        .set_request_partial_authorization(
+                            payment_intent.request_partial_authorization,
+                        )
+                        .set_authorized_amount(payment_attempt.authorized_amount)
+                        .set_maintenance_fallback(maintenance_fallback)
                         .to_owned(),
                     headers,
                 ))
@@ -668,8 +760,11 @@ where
                 merchant_id: Some(payment_attempt.merchant_id),
                 status: payment_intent.status,
                 amount: payment_attempt.amount,
-                amount_capturable: None,
+                amount_capturable: Some(payment_amounts.amount_capturable),
                 amount_received: payment_intent.amount_captured,
+                amount_captured: Some(payment_amounts.amount_captured),
+                amount_refunded: Some(payment_amounts.amount_refunded),
+                amount_refundable: Some(payment_amounts.amount_refundable),
                 client_secret: payment_intent.client_secret.map(masking::Secret::new),
                 created: Some(payment_intent.created_at),
                 currency: currency.to_string(),
@@ -699,6 +794,7 @@ where
                 shipping: address.shipping,
                 billing: address.billing,
                 cancellation_reason: payment_attempt.cancellation_reason,
+                cancellation_reason_code: payment_attempt.cancellation_reason_code,
                 payment_token: payment_attempt.payment_token,
                 metadata: payment_intent.metadata,
                 manual_retry_allowed: helpers::is_manual_retry_allowed(
@@ -715,12 +811,44 @@ where
                 allowed_payment_method_types: payment_intent.allowed_payment_method_types,
                 reference_id: payment_attempt.connector_response_reference_id,
                 attempt_count: payment_intent.attempt_count,
+                expires_on: payment_intent.session_expiry,
+                card_verification_results,
+                locale,
+                connector_fee,
+                auth_code,
+                acquirer_reference_number,
+                authorization_expires_at: payment_attempt.authorization_expires_at,
+                applied_defaults,
+                merchant_order_reference_id: payment_intent.merchant_order_reference_id,
+                suspected_duplicate_of: payment_intent.suspected_duplicate_of,
+                split_payment,
+                amount_details,
+                request_partial_authorization: payment_intent.request_partial_authorization,
+                authorized_amount: payment_attempt.authorized_amount,
+                livemode: payment_intent.livemode,
+                maintenance_fallback,
                 ..Default::default()
             },
             headers,
         )),
     });
 
+    let output = output.map(|response| match auth_flow {
+        services::AuthFlow::Client => match response {
+            services::ApplicationResponse::Json(response) => {
+                services::ApplicationResponse::Json(trim_payments_response_for_client(response))
+            }
+            services::ApplicationResponse::JsonWithHeaders((response, headers)) => {
+                services::ApplicationResponse::JsonWithHeaders((
+                    trim_payments_response_for_client(response),
+                    headers,
+                ))
+            }
+            response => response,
+        },
+        services::AuthFlow::Merchant => response,
+    });
+
     metrics::PAYMENT_OPS_COUNT.add(
         &metrics::CONTEXT,
         1,
@@ -735,6 +863,36 @@ where
     output
 }
 
+/// Removes the connector details, merchant metadata and customer details from the payments
+/// response, which must not be exposed to clients authenticated with the publishable key
+pub fn trim_payments_response_for_client(response: api::PaymentsResponse) -> api::PaymentsResponse {
+    api::PaymentsResponse {
+        connector: None,
+        connector_label: None,
+        connector_transaction_id: None,
+        connector_metadata: None,
+        reference_id: None,
+        metadata: None,
+        feature_metadata: None,
+        frm_message: None,
+        merchant_decision: None,
+        email: None,
+        name: None,
+        phone: None,
+        shipping: None,
+        billing: None,
+        refunds: None,
+        disputes: None,
+        attempts: None,
+        captures: None,
+        card_verification_results: None,
+        connector_fee: None,
+        auth_code: None,
+        acquirer_reference_number: None,
+        ..response
+    }
+}
+
 pub fn third_party_sdk_session_next_action<Op>(
     payment_attempt: &storage::PaymentAttempt,
     operation: &Op,
@@ -765,6 +923,36 @@ where
     }
 }
 
+pub fn connector_next_action_check(
+    payment_attempt: storage::PaymentAttempt,
+) -> RouterResult<Option<api_models::payments::ConnectorNextActionData>> {
+    let connector_next_action: Option<Result<api_models::payments::ConnectorNextActionData, _>> =
+        payment_attempt
+            .connector_metadata
+            .map(|metadata| metadata.parse_value("ConnectorNextActionData"));
+
+    Ok(connector_next_action.transpose().ok().flatten())
+}
+
+/// Virtual accounts which are not given an expiry by the connector accept transfers only until the payment session expires
+fn set_virtual_account_expiry(
+    mut next_action: api_models::payments::ConnectorNextActionData,
+    session_expiry: Option<time::PrimitiveDateTime>,
+) -> api_models::payments::ConnectorNextActionData {
+    if let api_models::payments::ConnectorNextActionData::DisplayBankTransferInformation {
+        bank_transfer_steps_and_charges_details,
+    } = &mut next_action
+    {
+        if let api_models::payments::BankTransferInstructions::VirtualAccountInstructions(
+            instructions,
+        ) = &mut bank_transfer_steps_and_charges_details.bank_transfer_instructions
+        {
+            instructions.expires_at = instructions.expires_at.or(session_expiry);
+        }
+    }
+    next_action
+}
+
 pub fn qr_code_next_steps_check(
     payment_attempt: storage::PaymentAttempt,
 ) -> RouterResult<Option<api_models::payments::QrCodeNextStepsInstruction>> {
@@ -791,16 +979,33 @@ pub fn wait_screen_next_steps_check(
     Ok(display_info_with_timer_instructions)
 }
 
-impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::PaymentsResponse {
-    fn foreign_from(item: (storage::PaymentIntent, storage::PaymentAttempt)) -> Self {
-        let pi = item.0;
-        let pa = item.1;
+impl
+    ForeignFrom<(
+        storage::PaymentIntent,
+        storage::PaymentAttempt,
+        Vec<storage::Refund>,
+    )> for api::PaymentsResponse
+{
+    fn foreign_from(
+        item: (
+            storage::PaymentIntent,
+            storage::PaymentAttempt,
+            Vec<storage::Refund>,
+        ),
+    ) -> Self {
+        let (pi, pa, refunds) = item;
+        let payment_amounts =
+            amounts::get_payment_amounts(&(&pa).into(), pi.amount_captured, &refunds);
         Self {
             payment_id: Some(pi.payment_id),
             merchant_id: Some(pi.merchant_id),
             status: pi.status,
             amount: pi.amount,
-            amount_capturable: pi.amount_captured,
+            amount_capturable: Some(payment_amounts.amount_capturable),
+            amount_received: pi.amount_captured,
+            amount_captured: Some(payment_amounts.amount_captured),
+            amount_refunded: Some(payment_amounts.amount_refunded),
+            amount_refundable: Some(payment_amounts.amount_refundable),
             client_secret: pi.client_secret.map(|s| s.into()),
             created: Some(pi.created_at),
             currency: pi.currency.map(|c| c.to_string()).unwrap_or_default(),
@@ -819,6 +1024,7 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
             authentication_type: pa.authentication_type,
             connector_transaction_id: pa.connector_transaction_id,
             attempt_count: pi.attempt_count,
+            livemode: pi.livemode,
             ..Default::default()
         }
     }
@@ -888,6 +1094,8 @@ pub fn change_order_details_to_new_type(
         product_name: order_details.product_name,
         quantity: order_details.quantity,
         amount: order_amount,
+        product_type: order_details.product_type,
+        commodity_code: None,
     }])
 }
 
@@ -918,7 +1126,32 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
                 field_name: "browser_info",
             })?;
 
-        let order_category = additional_data
+        let sca_exemption =
+            three_ds_decision::get_sca_exemption(attempt.three_ds_decision_data.clone())?;
+
+        helpers::validate_device_session(connector_name, &payment_data.payment_intent)?;
+
+        let is_sub_merchant_data_present =
+            attempt.merchant_descriptor.is_some() || attempt.sub_merchant_data.is_some();
+        let (merchant_descriptor, sub_merchant_data) = if is_sub_merchant_data_present
+            && helpers::should_pass_sub_merchant_data(
+                &additional_data.state.conf.sub_merchant,
+                connector_name,
+            )? {
+            let sub_merchant_data: Option<api_models::payments::SubMerchantData> = attempt
+                .sub_merchant_data
+                .clone()
+                .map(|data| data.parse_value("SubMerchantData"))
+                .transpose()
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "sub_merchant_data",
+                })?;
+            (attempt.merchant_descriptor.clone(), sub_merchant_data)
+        } else {
+            (None, None)
+        };
+
+        let noon_order_category = additional_data
             .payment_data
             .payment_intent
             .connector_metadata
@@ -929,6 +1162,11 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             })
             .transpose()?
             .and_then(|cm| cm.noon.and_then(|noon| noon.order_category));
+        let order_category = payment_data
+            .payment_intent
+            .order_category
+            .clone()
+            .or(noon_order_category);
 
         let order_details = additional_data
             .payment_data
@@ -949,6 +1187,20 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             })
             .transpose()?;
 
+        let split_payment =
+            helpers::get_split_payment_details_of_intent(&payment_data.payment_intent)?;
+        let amount_details = helpers::get_amount_details_for_connector(
+            &payment_data.payment_intent,
+            payment_data.amount.into(),
+            order_details.as_deref(),
+        );
+
+        let metadata = core_metadata::get_connector_pass_through_metadata(
+            &additional_data.state.conf.merchant_metadata,
+            connector_name,
+            payment_data.payment_intent.metadata.as_ref(),
+        );
+
         let complete_authorize_url = Some(helpers::create_complete_authorize_url(
             router_base_url,
             attempt,
@@ -975,6 +1227,12 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
                 None
             }
         });
+        // The connectors which accept the wallet payments only as cards are sent the card decrypted
+        // from the wallet token in place of the wallet data
+        let payment_method_data = match &payment_data.decrypted_wallet_card {
+            Some(decrypted_wallet_card) => Some(decrypted_wallet_card.get_payment_method_data()),
+            None => payment_method_data,
+        };
         Ok(Self {
             payment_method_data: payment_method_data.get_required_value("payment_method_data")?,
             setup_future_usage: payment_data.payment_intent.setup_future_usage,
@@ -992,6 +1250,8 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             payment_experience: payment_data.payment_attempt.payment_experience,
             order_details,
             order_category,
+            shipping_method: payment_data.payment_intent.shipping_method,
+            estimated_delivery_date: payment_data.payment_intent.estimated_delivery_date,
             session_token: None,
             enrolled_for_3ds: true,
             related_transaction_id: None,
@@ -1000,6 +1260,21 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             webhook_url,
             complete_authorize_url,
             customer_id: None,
+            external_authentication_data: payment_data.external_authentication_data,
+            sca_exemption,
+            merchant_descriptor,
+            sub_merchant_data,
+            card_cvc: payment_data.card_cvc,
+            split_payment,
+            amount_details,
+            request_partial_authorization: payment_data
+                .payment_intent
+                .request_partial_authorization
+                .unwrap_or(false),
+            metadata,
+            payment_channel: payment_data.payment_intent.payment_channel,
+            decrypted_wallet_card: payment_data.decrypted_wallet_card,
+            device_session_id: payment_data.payment_intent.device_session_id,
         })
     }
 }
@@ -1020,6 +1295,14 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
             encoded_data: payment_data.connector_response.encoded_data,
             capture_method: payment_data.payment_attempt.capture_method,
             connector_meta: payment_data.payment_attempt.connector_metadata,
+            capture_id: payment_data.multiple_capture_data.as_ref().and_then(
+                |multiple_capture_data| {
+                    multiple_capture_data
+                        .get_latest_capture()
+                        .connector_capture_id
+                        .clone()
+                },
+            ),
             sync_type: match payment_data.multiple_capture_data {
                 Some(multiple_capture_data) => types::SyncRequestType::MultipleCaptureSync(
                     multiple_capture_data.get_pending_connector_capture_ids(),
@@ -1087,6 +1370,11 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelDa
             &additional_data.connector_name,
             api::GetToken::Connector,
         )?;
+        let webhook_url = Some(helpers::create_webhook_url(
+            &additional_data.router_base_url,
+            &payment_data.payment_attempt.merchant_id,
+            &additional_data.connector_name,
+        ));
         Ok(Self {
             amount: Some(payment_data.amount.into()),
             currency: Some(payment_data.currency),
@@ -1094,8 +1382,16 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelDa
                 .connector
                 .connector_transaction_id(payment_data.payment_attempt.clone())?
                 .ok_or(errors::ApiErrorResponse::ResourceIdNotFound)?,
-            cancellation_reason: payment_data.payment_attempt.cancellation_reason,
+            cancellation_reason: cancellation_reason::map_cancellation_reason(
+                api_models::enums::Connector::from_str(&additional_data.connector_name).map_or(
+                    cancellation_reason::CancellationReasonSupport::FreeText,
+                    cancellation_reason::get_cancellation_reason_support,
+                ),
+                payment_data.payment_attempt.cancellation_reason_code,
+                payment_data.payment_attempt.cancellation_reason.as_deref(),
+            ),
             connector_meta: payment_data.payment_attempt.connector_metadata,
+            webhook_url,
         })
     }
 }
@@ -1156,6 +1452,8 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSessionD
                 billing_address.address.and_then(|address| address.country)
             }),
             order_details,
+            // Set from the merchant connector account once the router data is constructed
+            merchant_connector_id: None,
         })
     }
 }
@@ -1369,3 +1667,143 @@ impl ForeignFrom<payments::FraudCheck> for FrmMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_trim_payments_response_for_client() {
+        let response = api::PaymentsResponse {
+            payment_id: Some("pay_123".to_string()),
+            client_secret: Some(masking::Secret::new("pay_123_secret_456".to_string())),
+            amount: 6540,
+            connector: Some("stripe".to_string()),
+            connector_label: Some("stripe_US_default".to_string()),
+            connector_transaction_id: Some("pi_123".to_string()),
+            metadata: Some(masking::Secret::new(serde_json::json!({ "order": "123" }))),
+            email: Some(common_utils::crypto::Encryptable::new(
+                masking::Secret::new("guest@example.com".to_string()),
+                masking::Secret::new(Vec::new()),
+            )),
+            ..Default::default()
+        };
+
+        let trimmed_response = trim_payments_response_for_client(response);
+
+        assert_eq!(trimmed_response.payment_id.as_deref(), Some("pay_123"));
+        assert!(trimmed_response.client_secret.is_some());
+        assert_eq!(trimmed_response.amount, 6540);
+        assert!(trimmed_response.connector.is_none());
+        assert!(trimmed_response.connector_label.is_none());
+        assert!(trimmed_response.connector_transaction_id.is_none());
+        assert!(trimmed_response.metadata.is_none());
+        assert!(trimmed_response.email.is_none());
+    }
+
+    fn get_next_action_from_connector_metadata(
+        connector_metadata: serde_json::Value,
+    ) -> serde_json::Value {
+        let connector_next_action: api_models::payments::ConnectorNextActionData =
+            serde_json::from_value(connector_metadata).unwrap();
+        serde_json::to_value(api_models::payments::NextActionData::from(
+            connector_next_action,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_wait_screen_next_action_serialization() {
+        let next_action = get_next_action_from_connector_metadata(serde_json::json!({
+            "wait_screen_information": {
+                "display_from_timestamp": 1_696_000_000_000_000_000,
+                "display_to_timestamp": 1_696_000_060_000_000_000,
+                "poll_interval_in_secs": 5
+            }
+        }));
+
+        assert_eq!(
+            next_action,
+            serde_json::json!({
+                "type": "wait_screen_information",
+                "display_from_timestamp": 1_696_000_000_000_000_000,
+                "display_to_timestamp": 1_696_000_060_000_000_000,
+                "poll_interval_in_secs": 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_qr_code_next_action_serialization() {
+        let next_action = get_next_action_from_connector_metadata(serde_json::json!({
+            "qr_code_information": {
+                "image_data_url": "https://example.com/qr.png",
+                "qr_code_data": "upi://pay?pa=merchant@bank",
+                "display_to_timestamp": 1_696_000_300
+            }
+        }));
+
+        assert_eq!(
+            next_action,
+            serde_json::json!({
+                "type": "qr_code_information",
+                "image_data_url": "https://example.com/qr.png",
+                "qr_code_data": "upi://pay?pa=merchant@bank",
+                "display_to_timestamp": 1_696_000_300
+            })
+        );
+    }
+
+    #[test]
+    fn test_collect_otp_next_action_serialization() {
+        let next_action = get_next_action_from_connector_metadata(serde_json::json!({
+            "collect_otp": {
+                "otp_length": 6,
+                "display_to_timestamp": null
+            }
+        }));
+
+        assert_eq!(
+            next_action,
+            serde_json::json!({
+                "type": "collect_otp",
+                "otp_length": 6,
+                "display_to_timestamp": null
+            })
+        );
+    }
+
+    #[test]
+    fn test_redirect_next_action_serialization_is_unchanged() {
+        // SDKs read `redirect_to_url` from the next action, this shape must not change
+        let next_action = api_models::payments::NextActionData::RedirectToUrl {
+            redirect_to_url: "https://example.com/start/pay_123".to_string(),
+        };
+
+        let serialized = serde_json::to_value(next_action).unwrap();
+
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "redirect_to_url",
+                "redirect_to_url": "https://example.com/start/pay_123"
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_connector_metadata_is_not_parsed_as_next_action() {
+        let connector_metadata = serde_json::json!({
+            "display_from_timestamp": 1_696_000_000_000_000_000,
+            "display_to_timestamp": null
+        });
+
+        assert!(
+            serde_json::from_value::<api_models::payments::ConnectorNextActionData>(
+                connector_metadata
+            )
+            .is_err()
+        );
+    }
+}
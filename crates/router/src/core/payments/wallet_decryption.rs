@@ -0,0 +1,965 @@
+//! Decryption of the wallet tokens of the payments routed to the connectors which accept the
+//! wallet payments only as cards. The card decrypted from an Apple Pay or a Google Pay token is a
+//! network token, which the connector is sent along with its cryptogram and ECI in place of the
+//! wallet data.
+
+use api_models::payments::{GooglePayWalletData, PaymentMethodData, WalletData};
+use base64::Engine;
+#[cfg(feature = "kms")]
+use common_utils::ext_traits::ValueExt;
+use error_stack::{report, IntoReport, ResultExt};
+#[cfg(feature = "kms")]
+use external_services::kms;
+#[cfg(feature = "kms")]
+use masking::ExposeInterface;
+use masking::{PeekInterface, Secret};
+use openssl::{
+    bn::BigNumContext,
+    derive::Deriver,
+    ec::{EcGroup, EcKey, EcPoint},
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Private, Public},
+    sign::Verifier,
+    symm::{self, Cipher},
+};
+use ring::{hkdf, hmac};
+use router_env::{instrument, tracing};
+use serde::Deserialize;
+
+use crate::{
+    connector::utils::capabilities::{ConnectorCapabilities, WalletSupport},
+    consts::BASE64_ENGINE,
+    core::errors::{self, CustomResult, RouterResult},
+    routes::AppState,
+    types::{ApplePayPredecryptData, DecryptedWalletCard, WalletIndicator},
+};
+
+const GOOGLE_PAY_SENDER_ID: &str = "Google";
+const GOOGLE_PAY_PROTOCOL_VERSION: &str = "ECv2";
+const GOOGLE_PAY_TOKEN_FIELD: &str =
+    "payment_method_data.wallet.google_pay.tokenization_data.token";
+#[cfg(feature = "kms")]
+const APPLE_PAY_TOKEN_FIELD: &str = "payment_method_data.wallet.apple_pay.payment_data";
+
+/// Decrypts the wallet token of the payment when the connector accepts the wallet payments only
+/// as cards. None if the connector is sent the wallet data, or if the payment is not an Apple Pay
+/// or a Google Pay payment.
+#[instrument(skip_all)]
+pub async fn get_decrypted_wallet_card(
+    state: &AppState,
+    connector_capabilities: Option<ConnectorCapabilities>,
+    payment_method_data: Option<&PaymentMethodData>,
+) -> RouterResult<Option<DecryptedWalletCard>> {
+    let is_card_only_connector = connector_capabilities.map_or(false, |capabilities| {
+        capabilities.wallet_support == WalletSupport::DecryptedCardOnly
+    });
+    if !is_card_only_connector {
+        return Ok(None);
+    }
+
+    match payment_method_data {
+        Some(PaymentMethodData::Wallet(WalletData::GooglePay(wallet_data))) => {
+            decrypt_google_pay_token(state, wallet_data).await.map(Some)
+        }
+        Some(PaymentMethodData::Wallet(WalletData::ApplePay(wallet_data))) => {
+            decrypt_apple_pay_token(state, wallet_data).await.map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn decrypt_google_pay_token(
+    state: &AppState,
+    wallet_data: &GooglePayWalletData,
+) -> RouterResult<DecryptedWalletCard> {
+    let config = &state.conf.googlepay_decrypt_keys;
+    if config.google_pay_root_signing_keys.is_empty()
+        || config.google_pay_recipient_id.is_empty()
+        || config.google_pay_private_key.is_empty()
+    {
+        Err(errors::ApiErrorResponse::NotSupported {
+            message: "Google Pay payments through a connector which accepts only cards require the Google Pay decryption keys, which are not configured".to_string(),
+        })?
+    }
+
+    #[cfg(feature = "kms")]
+    let private_key = kms::get_kms_client(&state.conf.kms)
+        .await
+        .decrypt(&config.google_pay_private_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Google Pay private key decryption failed")?;
+
+    #[cfg(not(feature = "kms"))]
+    let private_key = config.google_pay_private_key.clone();
+
+    let decryptor = GooglePayTokenDecryptor::new(
+        &config.google_pay_root_signing_keys,
+        &config.google_pay_recipient_id,
+        &private_key,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Invalid Google Pay decryption keys")?;
+
+    let decrypted = decryptor
+        .decrypt_token(
+            &wallet_data.tokenization_data.token,
+            get_unix_timestamp_millis(),
+        )
+        .map_err(|error| {
+            if matches!(
+                error.current_context(),
+                errors::GooglePayDecryptionError::TokenExpired
+            ) {
+                error.change_context(errors::ApiErrorResponse::WalletTokenExpired)
+            } else {
+                error.change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: GOOGLE_PAY_TOKEN_FIELD,
+                })
+            }
+        })?;
+
+    let details = decrypted.payment_method_details;
+    let cryptogram = match (details.auth_method, details.cryptogram) {
+        (GooglePayAuthMethod::Cryptogram3ds, Some(cryptogram)) => cryptogram,
+        (GooglePayAuthMethod::Cryptogram3ds, None) => Err(report!(
+            errors::ApiErrorResponse::InvalidDataValue {
+                field_name: GOOGLE_PAY_TOKEN_FIELD,
+            }
+        ))
+        .attach_printable("Google Pay token without a cryptogram")?,
+        // The card of a PAN_ONLY token is not a network token, which is authorized without a
+        // cryptogram after 3DS authentication
+        (GooglePayAuthMethod::PanOnly, _) => Err(errors::ApiErrorResponse::NotSupported {
+            message: "Google Pay payments through a connector which accepts only cards must be authenticated with a cryptogram".to_string(),
+        })?,
+    };
+
+    Ok(DecryptedWalletCard {
+        wallet: WalletIndicator::GooglePay,
+        card_number: details.pan,
+        card_exp_month: Secret::new(format!("{:02}", details.expiration_month)),
+        card_exp_year: Secret::new(details.expiration_year.to_string()),
+        cryptogram,
+        eci_indicator: details.eci_indicator,
+    })
+}
+
+#[cfg(feature = "kms")]
+async fn decrypt_apple_pay_token(
+    state: &AppState,
+    wallet_data: &api_models::payments::ApplePayWalletData,
+) -> RouterResult<DecryptedWalletCard> {
+    let config = &state.conf.applepay_decrypt_keys;
+    if config.apple_pay_ppc.is_empty() || config.apple_pay_ppc_key.is_empty() {
+        Err(apple_pay_keys_not_configured())?
+    }
+
+    let apple_pay_predecrypt =
+        super::helpers::ApplePayData::token_json(WalletData::ApplePay(wallet_data.clone()))
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: APPLE_PAY_TOKEN_FIELD,
+            })?
+            .decrypt(state)
+            .await
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: APPLE_PAY_TOKEN_FIELD,
+            })?
+            .parse_value::<ApplePayPredecryptData>("ApplePayPredecryptData")
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    validate_apple_pay_token_freshness(&apple_pay_predecrypt, common_utils::date_time::now())?;
+
+    // The expiration date is in the YYMMDD format
+    let expiration_date = apple_pay_predecrypt.application_expiration_date.expose();
+    let (card_exp_year, card_exp_month) = expiration_date
+        .get(0..2)
+        .zip(expiration_date.get(2..4))
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Invalid expiration date of the Apple Pay card")?;
+    let card_number = apple_pay_predecrypt
+        .application_primary_account_number
+        .expose()
+        .parse::<cards::CardNumber>()
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Invalid card number of the Apple Pay card")?;
+
+    Ok(DecryptedWalletCard {
+        wallet: WalletIndicator::ApplePay,
+        card_number,
+        card_exp_month: Secret::new(card_exp_month.to_string()),
+        card_exp_year: Secret::new(format!("20{card_exp_year}")),
+        cryptogram: apple_pay_predecrypt.payment_data.online_payment_cryptogram,
+        eci_indicator: apple_pay_predecrypt
+            .payment_data
+            .eci_indicator
+            .map(ExposeInterface::expose),
+    })
+}
+
+/// The Apple Pay payment processing certificate and its key are read only from KMS
+#[cfg(not(feature = "kms"))]
+async fn decrypt_apple_pay_token(
+    _state: &AppState,
+    _wallet_data: &api_models::payments::ApplePayWalletData,
+) -> RouterResult<DecryptedWalletCard> {
+    Err(apple_pay_keys_not_configured().into())
+}
+
+/// Rejects the decrypted Apple Pay payment data whose device account has expired at `now`, so that
+/// the wallet sheet can be invoked again instead of the payment being declined by the connector.
+/// The `applicationExpirationDate` is only carried by the decrypted payment data, in the `YYMMDD`
+/// format, and the account is valid until the end of that day.
+pub fn validate_apple_pay_token_freshness(
+    apple_pay_predecrypt: &ApplePayPredecryptData,
+    now: time::PrimitiveDateTime,
+) -> RouterResult<()> {
+    let expiration_date = apple_pay_predecrypt.application_expiration_date.peek();
+    let expires_at = expiration_date
+        .get(0..2)
+        .zip(expiration_date.get(2..4))
+        .zip(expiration_date.get(4..6))
+        .filter(|_| expiration_date.len() == 6)
+        .and_then(|((year, month), day)| {
+            let month = time::Month::try_from(month.parse::<u8>().ok()?).ok()?;
+            time::Date::from_calendar_date(
+                2000 + year.parse::<i32>().ok()?,
+                month,
+                day.parse().ok()?,
+            )
+            .ok()
+        })
+        .and_then(time::Date::next_day)
+        .map(time::Date::midnight)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Invalid expiration date of the Apple Pay card")?;
+
+    if expires_at <= now {
+        Err(report!(errors::ApiErrorResponse::WalletTokenExpired))
+            .attach_printable(format!("Apple Pay token expired at {expires_at}"))?
+    }
+    Ok(())
+}
+
+fn apple_pay_keys_not_configured() -> errors::ApiErrorResponse {
+    errors::ApiErrorResponse::NotSupported {
+        message: "Apple Pay payments through a connector which accepts only cards require the Apple Pay decryption keys, which are not configured".to_string(),
+    }
+}
+
+fn get_unix_timestamp_millis() -> i64 {
+    time::OffsetDateTime::now_utc().unix_timestamp() * 1000
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePayToken {
+    protocol_version: String,
+    signature: Secret<String>,
+    intermediate_signing_key: GooglePayIntermediateSigningKey,
+    signed_message: Secret<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePayIntermediateSigningKey {
+    signed_key: Secret<String>,
+    signatures: Vec<Secret<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePaySignedKey {
+    key_value: Secret<String>,
+    key_expiration: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePaySignedMessage {
+    encrypted_message: Secret<String>,
+    ephemeral_public_key: Secret<String>,
+    tag: Secret<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GooglePayRootSigningKeys {
+    keys: Vec<GooglePayRootSigningKey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GooglePayRootSigningKey {
+    key_value: String,
+    protocol_version: String,
+    key_expiration: Option<String>,
+}
+
+/// Message of a Google Pay token once decrypted
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayPredecryptData {
+    pub message_expiration: String,
+    pub payment_method_details: GooglePayPaymentMethodDetails,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayPaymentMethodDetails {
+    pub auth_method: GooglePayAuthMethod,
+    pub pan: cards::CardNumber,
+    pub expiration_month: u8,
+    pub expiration_year: u16,
+    pub cryptogram: Option<Secret<String>>,
+    pub eci_indicator: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GooglePayAuthMethod {
+    PanOnly,
+    #[serde(rename = "CRYPTOGRAM_3DS")]
+    Cryptogram3ds,
+}
+
+/// Decrypts the tokens of the `ECv2` protocol of Google Pay, after verifying that they are signed
+/// by Google for the recipient
+pub struct GooglePayTokenDecryptor {
+    root_signing_keys: Vec<(PKey<Public>, Option<i64>)>,
+    recipient_id: String,
+    private_key: PKey<Private>,
+}
+
+struct HkdfKeyLength(usize);
+
+impl hkdf::KeyType for HkdfKeyLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+impl GooglePayTokenDecryptor {
+    pub fn new(
+        root_signing_keys: &str,
+        recipient_id: &str,
+        private_key: &str,
+    ) -> CustomResult<Self, errors::GooglePayDecryptionError> {
+        let root_signing_keys = serde_json::from_str::<GooglePayRootSigningKeys>(root_signing_keys)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)
+            .attach_printable("Failed to parse the root signing keys")?
+            .keys
+            .into_iter()
+            .filter(|key| key.protocol_version == GOOGLE_PAY_PROTOCOL_VERSION)
+            .map(|key| {
+                let key_expiration = key
+                    .key_expiration
+                    .as_deref()
+                    .map(parse_expiration)
+                    .transpose()?;
+                Ok((load_public_key(&key.key_value)?, key_expiration))
+            })
+            .collect::<CustomResult<Vec<_>, errors::GooglePayDecryptionError>>()?;
+
+        let private_key = PKey::private_key_from_pem(private_key.as_bytes())
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)
+            .attach_printable("Failed to deserialize the private key")?;
+
+        Ok(Self {
+            root_signing_keys,
+            recipient_id: recipient_id.to_string(),
+            private_key,
+        })
+    }
+
+    /// Verifies the signatures of the token and decrypts its message, `now` being the current time
+    /// in milliseconds since the Unix epoch
+    pub fn decrypt_token(
+        &self,
+        token: &str,
+        now: i64,
+    ) -> CustomResult<GooglePayPredecryptData, errors::GooglePayDecryptionError> {
+        let token: GooglePayToken = serde_json::from_str(token)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::InvalidToken)?;
+        if token.protocol_version != GOOGLE_PAY_PROTOCOL_VERSION {
+            Err(errors::GooglePayDecryptionError::UnsupportedProtocolVersion)
+                .into_report()
+                .attach_printable(format!("Protocol version {}", token.protocol_version))?
+        }
+
+        let intermediate_signing_key = self.get_intermediate_signing_key(&token, now)?;
+        let signed_string = get_signed_string(&[
+            GOOGLE_PAY_SENDER_ID.as_bytes(),
+            self.recipient_id.as_bytes(),
+            GOOGLE_PAY_PROTOCOL_VERSION.as_bytes(),
+            token.signed_message.peek().as_bytes(),
+        ])?;
+        if !verify_signature(
+            &intermediate_signing_key,
+            &decode_base64(token.signature.peek())?,
+            &signed_string,
+        ) {
+            Err(errors::GooglePayDecryptionError::SignatureVerificationFailed)
+                .into_report()
+                .attach_printable("Invalid signature of the signed message")?
+        }
+
+        let signed_message: GooglePaySignedMessage =
+            serde_json::from_str(token.signed_message.peek())
+                .into_report()
+                .change_context(errors::GooglePayDecryptionError::InvalidToken)?;
+        let decrypted: GooglePayPredecryptData =
+            serde_json::from_slice(&self.decrypt_message(&signed_message)?)
+                .into_report()
+                .change_context(errors::GooglePayDecryptionError::InvalidToken)
+                .attach_printable("Failed to parse the decrypted message")?;
+
+        if parse_expiration(&decrypted.message_expiration)? <= now {
+            Err(errors::GooglePayDecryptionError::TokenExpired)
+                .into_report()
+                .attach_printable("The message has expired")?
+        }
+
+        Ok(decrypted)
+    }
+
+    /// The intermediate signing key of the token, once its signature is verified with one of the
+    /// root signing keys
+    fn get_intermediate_signing_key(
+        &self,
+        token: &GooglePayToken,
+        now: i64,
+    ) -> CustomResult<PKey<Public>, errors::GooglePayDecryptionError> {
+        let signed_key = token.intermediate_signing_key.signed_key.peek();
+        let signed_string = get_signed_string(&[
+            GOOGLE_PAY_SENDER_ID.as_bytes(),
+            GOOGLE_PAY_PROTOCOL_VERSION.as_bytes(),
+            signed_key.as_bytes(),
+        ])?;
+        let signatures = token
+            .intermediate_signing_key
+            .signatures
+            .iter()
+            .map(|signature| decode_base64(signature.peek()))
+            .collect::<CustomResult<Vec<_>, _>>()?;
+
+        let is_signed_by_google = self
+            .root_signing_keys
+            .iter()
+            .filter(|(_, key_expiration)| {
+                key_expiration.map_or(true, |expiration| expiration > now)
+            })
+            .any(|(root_signing_key, _)| {
+                signatures
+                    .iter()
+                    .any(|signature| verify_signature(root_signing_key, signature, &signed_string))
+            });
+        if !is_signed_by_google {
+            Err(errors::GooglePayDecryptionError::SignatureVerificationFailed)
+                .into_report()
+                .attach_printable("Invalid signature of the intermediate signing key")?
+        }
+
+        let signed_key: GooglePaySignedKey = serde_json::from_str(signed_key)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::InvalidToken)?;
+        if parse_expiration(&signed_key.key_expiration)? <= now {
+            Err(errors::GooglePayDecryptionError::TokenExpired)
+                .into_report()
+                .attach_printable("The intermediate signing key has expired")?
+        }
+
+        load_public_key(signed_key.key_value.peek())
+    }
+
+    fn decrypt_message(
+        &self,
+        signed_message: &GooglePaySignedMessage,
+    ) -> CustomResult<Vec<u8>, errors::GooglePayDecryptionError> {
+        let ephemeral_public_key = decode_base64(signed_message.ephemeral_public_key.peek())?;
+        let encrypted_message = decode_base64(signed_message.encrypted_message.peek())?;
+        let tag = decode_base64(signed_message.tag.peek())?;
+
+        let shared_secret = self.derive_shared_secret(&ephemeral_public_key)?;
+
+        // The symmetric encryption key and the MAC key are derived together with HKDF-SHA256
+        let input_keying_material = [ephemeral_public_key.as_slice(), &shared_secret].concat();
+        let mut derived_keys = [0u8; 64];
+        hkdf::Salt::new(hkdf::HKDF_SHA256, &[0u8; 32])
+            .extract(&input_keying_material)
+            .expand(&[GOOGLE_PAY_SENDER_ID.as_bytes()], HkdfKeyLength(64))
+            .and_then(|okm| okm.fill(&mut derived_keys))
+            .map_err(|_| report!(errors::GooglePayDecryptionError::DerivingSharedSecretKeyFailed))
+            .attach_printable("Failed to derive the symmetric keys")?;
+        let (symmetric_encryption_key, mac_key) = derived_keys.split_at(32);
+
+        hmac::verify(
+            &hmac::Key::new(hmac::HMAC_SHA256, mac_key),
+            &encrypted_message,
+            &tag,
+        )
+        .map_err(|_| report!(errors::GooglePayDecryptionError::SignatureVerificationFailed))
+        .attach_printable("Invalid tag of the encrypted message")?;
+
+        symm::decrypt(
+            Cipher::aes_256_ctr(),
+            symmetric_encryption_key,
+            Some(&[0u8; 16]),
+            &encrypted_message,
+        )
+        .into_report()
+        .change_context(errors::GooglePayDecryptionError::DecryptionFailed)
+    }
+
+    fn derive_shared_secret(
+        &self,
+        ephemeral_public_key: &[u8],
+    ) -> CustomResult<Vec<u8>, errors::GooglePayDecryptionError> {
+        // The ephemeral public key is an uncompressed point of the prime256v1 curve
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)?;
+        let mut context = BigNumContext::new()
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)?;
+        let public_key = EcPoint::from_bytes(&group, ephemeral_public_key, &mut context)
+            .and_then(|point| EcKey::from_public_key(&group, &point))
+            .and_then(PKey::from_ec_key)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)
+            .attach_printable("Failed to deserialize the ephemeral public key")?;
+
+        let mut deriver = Deriver::new(&self.private_key)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::DerivingSharedSecretKeyFailed)?;
+        deriver
+            .set_peer(&public_key)
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::DerivingSharedSecretKeyFailed)?;
+        deriver
+            .derive_to_vec()
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::DerivingSharedSecretKeyFailed)
+    }
+}
+
+fn decode_base64(data: &str) -> CustomResult<Vec<u8>, errors::GooglePayDecryptionError> {
+    BASE64_ENGINE
+        .decode(data)
+        .into_report()
+        .change_context(errors::GooglePayDecryptionError::Base64DecodingFailed)
+}
+
+/// Loads a public key which is base64 encoded in the X.509 SubjectPublicKeyInfo format
+fn load_public_key(key: &str) -> CustomResult<PKey<Public>, errors::GooglePayDecryptionError> {
+    PKey::public_key_from_der(&decode_base64(key)?)
+        .into_report()
+        .change_context(errors::GooglePayDecryptionError::KeyDeserializationFailed)
+}
+
+/// Parses an expiration, which Google Pay sends as milliseconds since the Unix epoch
+fn parse_expiration(expiration: &str) -> CustomResult<i64, errors::GooglePayDecryptionError> {
+    expiration
+        .parse()
+        .into_report()
+        .change_context(errors::GooglePayDecryptionError::InvalidToken)
+        .attach_printable("Invalid expiration")
+}
+
+/// The string which Google Pay signs, made of each of the components prefixed by its length in
+/// four little-endian bytes
+fn get_signed_string(
+    components: &[&[u8]],
+) -> CustomResult<Vec<u8>, errors::GooglePayDecryptionError> {
+    let mut signed_string = Vec::new();
+    for component in components {
+        let length = u32::try_from(component.len())
+            .into_report()
+            .change_context(errors::GooglePayDecryptionError::InvalidToken)?;
+        signed_string.extend_from_slice(&length.to_le_bytes());
+        signed_string.extend_from_slice(component);
+    }
+    Ok(signed_string)
+}
+
+/// Verifies an ECDSA signature over SHA-256, in the DER format
+fn verify_signature(public_key: &PKey<Public>, signature: &[u8], data: &[u8]) -> bool {
+    Verifier::new(MessageDigest::sha256(), public_key)
+        .and_then(|mut verifier| {
+            verifier.update(data)?;
+            verifier.verify(signature)
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use openssl::sign::Signer;
+
+    use super::*;
+    use crate::configs::settings::{GooglePayDecryptConfig, Settings};
+
+    const DPAN: &str = "4895370012003478";
+    const RECIPIENT_ID: &str = "gateway:hyperswitch";
+
+    /// Capabilities of a connector which accepts only cards
+    const CARD_ONLY_CONNECTOR: ConnectorCapabilities = ConnectorCapabilities {
+        payment_methods: &[
+            crate::connector::utils::capabilities::SupportedPaymentMethod {
+                payment_method: api_models::enums::PaymentMethod::Card,
+                payment_method_types: &[api_models::enums::PaymentMethodType::Credit],
+            },
+            crate::connector::utils::capabilities::SupportedPaymentMethod {
+                payment_method: api_models::enums::PaymentMethod::Wallet,
+                payment_method_types: &[api_models::enums::PaymentMethodType::GooglePay],
+            },
+        ],
+        currencies: None,
+        countries: None,
+        capture_methods: &[api_models::enums::CaptureMethod::Automatic],
+        refunds_supported: true,
+        mandates_supported: false,
+        split_payments_supported: false,
+        wallet_support: WalletSupport::DecryptedCardOnly,
+        banks: &[],
+    };
+
+    fn generate_key() -> PKey<Private> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+    }
+
+    fn encode_public_key(key: &PKey<Private>) -> String {
+        BASE64_ENGINE.encode(key.public_key_to_der().unwrap())
+    }
+
+    fn sign(key: &PKey<Private>, components: &[&[u8]]) -> String {
+        let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+        signer
+            .update(&get_signed_string(components).unwrap())
+            .unwrap();
+        BASE64_ENGINE.encode(signer.sign_to_vec().unwrap())
+    }
+
+    /// Keys of the fixtures, which stand for the root signing key of Google, the intermediate
+    /// signing key of the tokens and the key pair of the recipient
+    struct GooglePayFixture {
+        root_signing_key: PKey<Private>,
+        intermediate_signing_key: PKey<Private>,
+        recipient_key: PKey<Private>,
+    }
+
+    impl GooglePayFixture {
+        fn new() -> Self {
+            Self {
+                root_signing_key: generate_key(),
+                intermediate_signing_key: generate_key(),
+                recipient_key: generate_key(),
+            }
+        }
+
+        fn get_config(&self) -> GooglePayDecryptConfig {
+            GooglePayDecryptConfig {
+                google_pay_root_signing_keys: serde_json::json!({
+                    "keys": [{
+                        "keyValue": encode_public_key(&self.root_signing_key),
+                        "protocolVersion": "ECv2",
+                    }]
+                })
+                .to_string(),
+                google_pay_recipient_id: RECIPIENT_ID.to_string(),
+                google_pay_private_key: String::from_utf8(
+                    self.recipient_key.private_key_to_pem_pkcs8().unwrap(),
+                )
+                .unwrap(),
+            }
+        }
+
+        /// Encrypts the message for the recipient as Google Pay does, and signs it
+        fn get_token(&self, message: &serde_json::Value, recipient_id: &str) -> String {
+            let ephemeral_key = generate_key();
+            let mut deriver = Deriver::new(&ephemeral_key).unwrap();
+            deriver.set_peer(&self.recipient_key).unwrap();
+            let shared_secret = deriver.derive_to_vec().unwrap();
+
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+            let mut context = BigNumContext::new().unwrap();
+            let ephemeral_public_key = ephemeral_key
+                .ec_key()
+                .unwrap()
+                .public_key()
+                .to_bytes(
+                    &group,
+                    openssl::ec::PointConversionForm::UNCOMPRESSED,
+                    &mut context,
+                )
+                .unwrap();
+
+            let mut derived_keys = [0u8; 64];
+            hkdf::Salt::new(hkdf::HKDF_SHA256, &[0u8; 32])
+                .extract(&[ephemeral_public_key.as_slice(), &shared_secret].concat())
+                .expand(&[GOOGLE_PAY_SENDER_ID.as_bytes()], HkdfKeyLength(64))
+                .unwrap()
+                .fill(&mut derived_keys)
+                .unwrap();
+            let (symmetric_encryption_key, mac_key) = derived_keys.split_at(32);
+            let encrypted_message = symm::encrypt(
+                Cipher::aes_256_ctr(),
+                symmetric_encryption_key,
+                Some(&[0u8; 16]),
+                message.to_string().as_bytes(),
+            )
+            .unwrap();
+            let tag = hmac::sign(
+                &hmac::Key::new(hmac::HMAC_SHA256, mac_key),
+                &encrypted_message,
+            );
+
+            let signed_message = serde_json::json!({
+                "encryptedMessage": BASE64_ENGINE.encode(&encrypted_message),
+                "ephemeralPublicKey": BASE64_ENGINE.encode(&ephemeral_public_key),
+                "tag": BASE64_ENGINE.encode(tag.as_ref()),
+            })
+            .to_string();
+            let signed_key = serde_json::json!({
+                "keyValue": encode_public_key(&self.intermediate_signing_key),
+                "keyExpiration": (get_unix_timestamp_millis() + 3_600_000).to_string(),
+            })
+            .to_string();
+
+            serde_json::json!({
+                "protocolVersion": "ECv2",
+                "signature": sign(
+                    &self.intermediate_signing_key,
+                    &[b"Google", recipient_id.as_bytes(), b"ECv2", signed_message.as_bytes()],
+                ),
+                "intermediateSigningKey": {
+                    "signedKey": signed_key,
+                    "signatures": [sign(
+                        &self.root_signing_key,
+                        &[b"Google", b"ECv2", signed_key.as_bytes()],
+                    )],
+                },
+                "signedMessage": signed_message,
+            })
+            .to_string()
+        }
+    }
+
+    fn get_message(message_expiration: i64) -> serde_json::Value {
+        serde_json::json!({
+            "gatewayMerchantId": "merchant_1",
+            "messageExpiration": message_expiration.to_string(),
+            "messageId": "AH2Ejtc8qBlP_MCAV0jJG7Er",
+            "paymentMethod": "CARD",
+            "paymentMethodDetails": {
+                "expirationYear": 2030,
+                "expirationMonth": 3,
+                "pan": DPAN,
+                "authMethod": "CRYPTOGRAM_3DS",
+                "cryptogram": "AgAAAAAABk4DWZ4C28yUQAAAAAA=",
+                "eciIndicator": "05",
+            }
+        })
+    }
+
+    fn get_google_pay_payment_method_data(token: String) -> PaymentMethodData {
+        PaymentMethodData::Wallet(WalletData::GooglePay(GooglePayWalletData {
+            pm_type: "CARD".to_string(),
+            description: "Visa •••• 3478".to_string(),
+            info: api_models::payments::GooglePayPaymentMethodInfo {
+                card_network: "VISA".to_string(),
+                card_details: "3478".to_string(),
+            },
+            tokenization_data: api_models::payments::GpayTokenizationData {
+                token_type: "PAYMENT_GATEWAY".to_string(),
+                token,
+            },
+        }))
+    }
+
+    async fn get_state(config: GooglePayDecryptConfig) -> AppState {
+        let mut settings = Settings::default();
+        settings.googlepay_decrypt_keys = config;
+        AppState::with_storage(
+            settings,
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(crate::services::MockApiClient),
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "kms"))]
+    #[tokio::test]
+    async fn test_google_pay_payment_is_sent_to_card_only_connector_as_decrypted_card() {
+        let fixture = GooglePayFixture::new();
+        let state = get_state(fixture.get_config()).await;
+        let token = fixture.get_token(
+            &get_message(get_unix_timestamp_millis() + 600_000),
+            RECIPIENT_ID,
+        );
+        let payment_method_data = get_google_pay_payment_method_data(token);
+
+        let decrypted_wallet_card = get_decrypted_wallet_card(
+            &state,
+            Some(CARD_ONLY_CONNECTOR),
+            Some(&payment_method_data),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(decrypted_wallet_card.wallet, WalletIndicator::GooglePay);
+        assert_eq!(
+            decrypted_wallet_card.cryptogram.peek(),
+            "AgAAAAAABk4DWZ4C28yUQAAAAAA="
+        );
+        assert_eq!(decrypted_wallet_card.eci_indicator.as_deref(), Some("05"));
+        assert!(matches!(
+            decrypted_wallet_card.get_payment_method_data(),
+            PaymentMethodData::Card(card)
+                if card.card_number.peek() == DPAN
+                    && card.card_exp_month.peek() == "03"
+                    && card.card_exp_year.peek() == "2030"
+        ));
+
+        // The connectors which decrypt the wallet tokens themselves are sent the wallet data
+        let native_connector = crate::connector::utils::capabilities::get_connector_capabilities(
+            api_models::enums::Connector::Stripe,
+        );
+        assert!(
+            get_decrypted_wallet_card(&state, native_connector, Some(&payment_method_data))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            get_decrypted_wallet_card(&state, None, Some(&payment_method_data))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_is_rejected_without_decryption_keys() {
+        let fixture = GooglePayFixture::new();
+        let state = get_state(GooglePayDecryptConfig::default()).await;
+        let token = fixture.get_token(
+            &get_message(get_unix_timestamp_millis() + 600_000),
+            RECIPIENT_ID,
+        );
+
+        let error = get_decrypted_wallet_card(
+            &state,
+            Some(CARD_ONLY_CONNECTOR),
+            Some(&get_google_pay_payment_method_data(token)),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::NotSupported { .. }
+        ));
+    }
+
+    #[cfg(not(feature = "kms"))]
+    #[tokio::test]
+    async fn test_payment_with_expired_google_pay_token_is_rejected() {
+        let fixture = GooglePayFixture::new();
+        let state = get_state(fixture.get_config()).await;
+        let token = fixture.get_token(&get_message(get_unix_timestamp_millis() - 1), RECIPIENT_ID);
+
+        let error = get_decrypted_wallet_card(
+            &state,
+            Some(CARD_ONLY_CONNECTOR),
+            Some(&get_google_pay_payment_method_data(token)),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::WalletTokenExpired
+        ));
+    }
+
+    #[test]
+    fn test_apple_pay_token_freshness() {
+        let now = time::macros::datetime!(2023-10-31 12:00:00);
+        // The payment data as decrypted with the payment processing certificate
+        let get_apple_pay_predecrypt = |application_expiration_date: &str| {
+            serde_json::from_value::<ApplePayPredecryptData>(serde_json::json!({
+                "applicationPrimaryAccountNumber": "4817499195941011",
+                "applicationExpirationDate": application_expiration_date,
+                "currencyCode": "840",
+                "transactionAmount": 1000,
+                "deviceManufacturerIdentifier": "040010030273",
+                "paymentDataType": "3DSecure",
+                "paymentData": {
+                    "onlinePaymentCryptogram": "Ao/fzpIAFvp1eB9y8WVDMAACAAA=",
+                    "eciIndicator": "7",
+                },
+            }))
+            .unwrap()
+        };
+
+        let error = validate_apple_pay_token_freshness(&get_apple_pay_predecrypt("231030"), now)
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::WalletTokenExpired
+        ));
+        assert!(
+            validate_apple_pay_token_freshness(&get_apple_pay_predecrypt("231031"), now).is_ok()
+        );
+        assert!(
+            validate_apple_pay_token_freshness(&get_apple_pay_predecrypt("2310"), now).is_err()
+        );
+    }
+
+    #[test]
+    fn test_tokens_not_signed_for_the_recipient_or_expired_are_rejected() {
+        let fixture = GooglePayFixture::new();
+        let config = fixture.get_config();
+        let decryptor = GooglePayTokenDecryptor::new(
+            &config.google_pay_root_signing_keys,
+            &config.google_pay_recipient_id,
+            &config.google_pay_private_key,
+        )
+        .unwrap();
+        let now = get_unix_timestamp_millis();
+
+        let token = fixture.get_token(&get_message(now + 600_000), "gateway:another_gateway");
+        let error = decryptor.decrypt_token(&token, now).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::GooglePayDecryptionError::SignatureVerificationFailed
+        ));
+
+        let token = fixture.get_token(&get_message(now - 1), RECIPIENT_ID);
+        let error = decryptor.decrypt_token(&token, now).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::GooglePayDecryptionError::TokenExpired
+        ));
+
+        // A token whose intermediate signing key is not signed by a root signing key
+        let other_fixture = GooglePayFixture {
+            recipient_key: fixture.recipient_key.clone(),
+            ..GooglePayFixture::new()
+        };
+        let token = other_fixture.get_token(&get_message(now + 600_000), RECIPIENT_ID);
+        let error = decryptor.decrypt_token(&token, now).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::GooglePayDecryptionError::SignatureVerificationFailed
+        ));
+    }
+}
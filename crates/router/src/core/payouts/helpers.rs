@@ -138,6 +138,7 @@ pub async fn save_payout_data_to_locker(
             let payload = StoreLockerReq::LockerCard(StoreCardReq {
                 merchant_id: &merchant_account.merchant_id,
                 merchant_customer_id: payout_attempt.customer_id.to_owned(),
+                requestor_card_reference: None,
                 card: transformers::Card {
                     card_number: card.card_number.to_owned(),
                     name_on_card: Some(card.card_holder_name.to_owned()),
@@ -237,6 +238,20 @@ pub async fn save_payout_data_to_locker(
         metadata: None,
         customer_id: Some(payout_attempt.customer_id.to_owned()),
         card_network: None,
+        duplicate_behaviour: None,
+    };
+
+    let card_fingerprint = match payment_method.card.as_ref() {
+        Some(card) => Some(
+            cards::get_card_fingerprint(
+                db,
+                &merchant_account.merchant_id,
+                key_store,
+                &card.card_number,
+            )
+            .await?,
+        ),
+        None => None,
     };
 
     cards::create_payment_method(
@@ -247,6 +262,7 @@ pub async fn save_payout_data_to_locker(
         &merchant_account.merchant_id,
         None,
         card_details_encrypted,
+        card_fingerprint,
     )
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -0,0 +1,326 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use common_utils::{date_time, ext_traits::StringExt};
+use error_stack::ResultExt;
+use once_cell::sync::Lazy;
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+
+use super::errors::{self, RouterResult};
+use crate::{
+    configs::settings::RateLimit,
+    routes::{metrics, AppState},
+};
+
+pub const RATE_LIMIT_PREFIX: &str = "PAYMENT_RATE_LIMIT";
+
+/// Number of buckets held in memory beyond which the expired buckets are removed
+const LOCAL_TOKEN_BUCKETS_CAPACITY: usize = 10_000;
+
+// Buckets used when redis cannot be reached, limiting the requests received by this instance only
+static LOCAL_TOKEN_BUCKETS: Lazy<Mutex<HashMap<String, (TokenBucket, i64)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Key of the token bucket limiting the payment requests of a merchant
+pub fn get_merchant_rate_limit_key(merchant_id: &str) -> String {
+    format!("{RATE_LIMIT_PREFIX}_{merchant_id}")
+}
+
+/// Key of the token bucket limiting the payment requests made with the publishable key of a
+/// merchant from a client IP
+pub fn get_client_ip_rate_limit_key(merchant_id: &str, client_ip: &str) -> String {
+    format!("{RATE_LIMIT_PREFIX}_{merchant_id}_{client_ip}")
+}
+
+/// Key of the config holding the rate limit of a merchant, overriding the globally configured limit
+pub fn get_payment_rate_limit_override_key(merchant_id: &str) -> String {
+    format!("payment_rate_limit_{merchant_id}")
+}
+
+/// Token bucket holding the requests which can be made, refilled continuously at the rate of the
+/// limit. The tokens are counted in units of `1 / window_in_millis` of a request so that the refill
+/// does not need fractional tokens.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TokenBucket {
+    pub available_units: u64,
+    pub refilled_at: i64,
+}
+
+fn window_in_millis(limit: &RateLimit) -> u64 {
+    u64::from(limit.window_in_secs).saturating_mul(1000)
+}
+
+fn capacity_in_units(limit: &RateLimit) -> u64 {
+    u64::from(limit.requests).saturating_mul(window_in_millis(limit))
+}
+
+impl TokenBucket {
+    pub fn new(limit: &RateLimit, now: i64) -> Self {
+        Self {
+            available_units: capacity_in_units(limit),
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: i64) {
+        let elapsed_millis = u64::try_from(now.saturating_sub(self.refilled_at)).unwrap_or(0);
+        self.available_units = self
+            .available_units
+            .saturating_add(elapsed_millis.saturating_mul(u64::from(limit.requests)))
+            .min(capacity_in_units(limit));
+        self.refilled_at = self.refilled_at.max(now);
+    }
+
+    /// Takes a token for the request, returns the number of seconds after which the request can be
+    /// retried if the bucket is empty
+    pub fn try_acquire(&mut self, limit: &RateLimit, now: i64) -> Result<(), u64> {
+        self.refill(limit, now);
+
+        let units_per_request = window_in_millis(limit);
+        if self.available_units >= units_per_request {
+            self.available_units -= units_per_request;
+            Ok(())
+        } else {
+            let missing_units = units_per_request - self.available_units;
+            let wait_millis = div_ceil(missing_units, u64::from(limit.requests).max(1));
+            Err(div_ceil(wait_millis, 1000).max(1))
+        }
+    }
+}
+
+fn div_ceil(dividend: u64, divisor: u64) -> u64 {
+    dividend / divisor + u64::from(dividend % divisor != 0)
+}
+
+fn now_in_millis() -> i64 {
+    i64::try_from(date_time::now().assume_utc().unix_timestamp_nanos() / 1_000_000)
+        .unwrap_or_default()
+}
+
+/// Takes a token from the bucket stored in redis, `None` is returned if redis could not be used
+async fn try_acquire_in_redis(
+    redis_conn: &RedisConnectionPool,
+    key: &str,
+    limit: &RateLimit,
+    now: i64,
+) -> Option<Result<(), u64>> {
+    let mut bucket = match redis_conn
+        .get_and_deserialize_key::<TokenBucket>(key, "TokenBucket")
+        .await
+    {
+        Ok(bucket) => bucket,
+        Err(error) if matches!(error.current_context(), errors::RedisError::NotFound) => {
+            TokenBucket::new(limit, now)
+        }
+        Err(error) => {
+            logger::error!(?error, "Failed to fetch the rate limit token bucket");
+            return None;
+        }
+    };
+
+    let result = bucket.try_acquire(limit, now);
+
+    // A full bucket is the same as a missing one, so the bucket expires once it would be refilled
+    match redis_conn
+        .serialize_and_set_key_with_expiry(key, &bucket, i64::from(limit.window_in_secs))
+        .await
+    {
+        Ok(()) => Some(result),
+        Err(error) => {
+            logger::error!(?error, "Failed to store the rate limit token bucket");
+            None
+        }
+    }
+}
+
+fn try_acquire_locally(key: &str, limit: &RateLimit, now: i64) -> Result<(), u64> {
+    let mut buckets = match LOCAL_TOKEN_BUCKETS.lock() {
+        Ok(buckets) => buckets,
+        Err(error) => {
+            logger::error!(?error, "Failed to lock the local rate limit token buckets");
+            return Ok(());
+        }
+    };
+
+    if buckets.len() >= LOCAL_TOKEN_BUCKETS_CAPACITY && !buckets.contains_key(key) {
+        buckets.retain(|_, (_, expires_at)| *expires_at > now);
+    }
+
+    let expires_at = now.saturating_add(i64::try_from(window_in_millis(limit)).unwrap_or(i64::MAX));
+    let (bucket, bucket_expires_at) = buckets
+        .entry(key.to_string())
+        .or_insert_with(|| (TokenBucket::new(limit, now), expires_at));
+    *bucket_expires_at = expires_at;
+    bucket.try_acquire(limit, now)
+}
+
+/// Takes a token for the request from the bucket identified by the key.
+///
+/// The bucket is read and written back without a lock, concurrent requests may briefly exceed the
+/// limit. When redis cannot be used, the requests are limited with buckets held in memory.
+async fn try_acquire(state: &AppState, key: &str, limit: &RateLimit) -> Result<(), u64> {
+    let now = now_in_millis();
+    match state.store.get_redis_conn() {
+        Ok(redis_conn) => {
+            if let Some(result) = try_acquire_in_redis(&redis_conn, key, limit, now).await {
+                return result;
+            }
+        }
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection for rate limiting");
+        }
+    }
+
+    try_acquire_locally(key, limit, now)
+}
+
+/// Fetches the rate limit configured for the merchant, or the globally configured limit
+pub async fn get_merchant_rate_limit(state: &AppState, merchant_id: &str) -> RateLimit {
+    let rate_limit = state
+        .store
+        .find_config_by_key(&get_payment_rate_limit_override_key(merchant_id))
+        .await
+        .map(|config| config.config)
+        .and_then(|config| {
+            config
+                .parse_struct::<RateLimit>("RateLimit")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+
+    match rate_limit {
+        Ok(rate_limit) if rate_limit.validate("merchant").is_ok() => rate_limit,
+        Ok(rate_limit) => {
+            logger::error!(
+                ?rate_limit,
+                "Invalid payment rate limit configured for merchant"
+            );
+            state.conf.payment_rate_limit.merchant
+        }
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(
+                    ?error,
+                    "Failed to fetch the payment rate limit of the merchant"
+                );
+            }
+            state.conf.payment_rate_limit.merchant
+        }
+    }
+}
+
+/// Rejects the payment request if the merchant, or the client IP for requests made with the
+/// publishable key, has exceeded its rate limit. This must be checked before anything is written
+/// for the request.
+#[instrument(skip_all)]
+pub async fn check_payment_rate_limit(
+    state: &AppState,
+    merchant_id: &str,
+    client_ip: Option<&str>,
+) -> RouterResult<()> {
+    let config = &state.conf.payment_rate_limit;
+    if !config.enabled {
+        return Ok(());
+    }
+
+    if let Some(client_ip) = client_ip {
+        let key = get_client_ip_rate_limit_key(merchant_id, client_ip);
+        try_acquire(state, &key, &config.client_ip)
+            .await
+            .map_err(|retry_after_secs| rate_limited(merchant_id, "client_ip", retry_after_secs))?;
+    }
+
+    let merchant_limit = get_merchant_rate_limit(state, merchant_id).await;
+    try_acquire(
+        state,
+        &get_merchant_rate_limit_key(merchant_id),
+        &merchant_limit,
+    )
+    .await
+    .map_err(|retry_after_secs| rate_limited(merchant_id, "merchant", retry_after_secs))
+}
+
+fn rate_limited(
+    merchant_id: &str,
+    limit: &'static str,
+    retry_after_secs: u64,
+) -> error_stack::Report<errors::ApiErrorResponse> {
+    logger::warn!(merchant_id, limit, "Payment request rate limited");
+    metrics::PAYMENT_RATE_LIMITED_REQUEST_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes("merchant_id", merchant_id.to_string()),
+            metrics::request::add_attributes("limit", limit),
+        ],
+    );
+    error_stack::report!(errors::ApiErrorResponse::TooManyRequests { retry_after_secs })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const LIMIT: RateLimit = RateLimit {
+        requests: 5,
+        window_in_secs: 10,
+    };
+
+    #[test]
+    fn test_requests_past_the_limit_are_rejected_until_refilled() {
+        let mut bucket = TokenBucket::new(&LIMIT, 0);
+
+        for _ in 0..LIMIT.requests {
+            assert_eq!(bucket.try_acquire(&LIMIT, 0), Ok(()));
+        }
+        // A token is refilled every 2 seconds
+        assert_eq!(bucket.try_acquire(&LIMIT, 0), Err(2));
+        assert_eq!(bucket.try_acquire(&LIMIT, 1500), Err(1));
+        assert_eq!(bucket.try_acquire(&LIMIT, 2000), Ok(()));
+        assert_eq!(bucket.try_acquire(&LIMIT, 2000), Err(2));
+
+        // The bucket is full again after the window, and does not hold more than the limit
+        let recovered_at = 2000 + 60_000;
+        for _ in 0..LIMIT.requests {
+            assert_eq!(bucket.try_acquire(&LIMIT, recovered_at), Ok(()));
+        }
+        assert!(bucket.try_acquire(&LIMIT, recovered_at).is_err());
+    }
+
+    #[test]
+    fn test_local_buckets_are_kept_per_key() {
+        let limit = RateLimit {
+            requests: 1,
+            window_in_secs: 1,
+        };
+
+        assert_eq!(try_acquire_locally("test_merchant_1", &limit, 0), Ok(()));
+        assert_eq!(try_acquire_locally("test_merchant_1", &limit, 0), Err(1));
+        assert_eq!(try_acquire_locally("test_merchant_2", &limit, 0), Ok(()));
+        assert_eq!(try_acquire_locally("test_merchant_1", &limit, 1000), Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limited_response_has_retry_after() {
+        use actix_web::ResponseError;
+
+        let response = errors::ApiErrorResponse::TooManyRequests {
+            retry_after_secs: 7,
+        }
+        .error_response();
+
+        assert_eq!(
+            response.status(),
+            actix_web::http::StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(
+            response
+                .headers()
+                .get(actix_web::http::header::RETRY_AFTER)
+                .unwrap(),
+            "7"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod bulk;
 pub mod validator;
 
 use common_utils::ext_traits::AsyncExt;
@@ -8,6 +9,7 @@ use scheduler::{consumer::types::process_data, utils as process_tracker_utils};
 use crate::{
     consts,
     core::{
+        amount_limits, connector_response_capture,
         errors::{self, ConnectorErrorExt, RouterResponse, RouterResult, StorageErrorExt},
         payments::{self, access_token},
         utils as core_utils,
@@ -35,6 +37,18 @@ pub async fn refund_create_core(
     key_store: domain::MerchantKeyStore,
     req: refunds::RefundRequest,
 ) -> RouterResponse<refunds::RefundResponse> {
+    create_refund(&state, &merchant_account, &key_store, req)
+        .await
+        .map(services::ApplicationResponse::Json)
+}
+
+#[instrument(skip_all)]
+pub async fn create_refund(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    req: refunds::RefundRequest,
+) -> RouterResult<refunds::RefundResponse> {
     let db = &*state.store;
     let (merchant_id, payment_intent, payment_attempt, amount);
 
@@ -101,18 +115,159 @@ pub async fn refund_create_core(
         .await
         .transpose()?;
 
+    // Refunds disabled for the connector account are rejected before the refund is created
+    let (connector, merchant_connector_account) = get_refund_connector_details(
+        state,
+        merchant_account,
+        key_store,
+        &payment_intent,
+        &payment_attempt,
+        creds_identifier.clone(),
+    )
+    .await?;
+    payments::helpers::validate_connector_flow_enabled(
+        &merchant_connector_account,
+        &connector,
+        api::enums::ConnectorFlow::Refund,
+    )?;
+
     validate_and_create_refund(
-        &state,
-        &merchant_account,
-        &key_store,
+        state,
+        merchant_account,
+        key_store,
         &payment_attempt,
         &payment_intent,
         amount,
         req,
         creds_identifier,
+        merchant_connector_account.get_mca_id(),
+    )
+    .await
+}
+
+/// Returns the amount received for a partially funded bank transfer to the customer, before the
+/// payment is cancelled on expiry. The payment never succeeded, so the received amount is refunded
+/// as the captured amount of the payment. A retried expiry task returns the refund created by the
+/// earlier run instead of refunding again.
+#[instrument(skip_all)]
+pub async fn refund_partially_received_amount(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    amount_received: i64,
+) -> RouterResult<refunds::RefundResponse> {
+    let db = &*state.store;
+    let connector_transaction_id = payment_attempt
+        .connector_transaction_id
+        .as_ref()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Missing connector transaction id of the partially funded payment")?;
+    let existing_refund = db
+        .find_refund_by_merchant_id_connector_transaction_id(
+            &merchant_account.merchant_id,
+            connector_transaction_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while finding refunds of the payment attempt")?
+        .into_iter()
+        .find(|refund| {
+            refund.refund_reason.as_deref() == Some(consts::PARTIAL_BANK_TRANSFER_REFUND_REASON)
+        });
+    if let Some(refund) = existing_refund {
+        return Ok(refund.foreign_into());
+    }
+
+    let payment_intent = storage::PaymentIntent {
+        amount_captured: Some(amount_received),
+        ..payment_intent.clone()
+    };
+
+    let (connector, merchant_connector_account) = get_refund_connector_details(
+        state,
+        merchant_account,
+        key_store,
+        &payment_intent,
+        payment_attempt,
+        None,
+    )
+    .await?;
+    payments::helpers::validate_connector_flow_enabled(
+        &merchant_connector_account,
+        &connector,
+        api::enums::ConnectorFlow::Refund,
+    )?;
+
+    validate_and_create_refund(
+        state,
+        merchant_account,
+        key_store,
+        payment_attempt,
+        &payment_intent,
+        amount_received,
+        refunds::RefundRequest {
+            payment_id: payment_intent.payment_id.clone(),
+            amount: Some(amount_received),
+            reason: Some(consts::PARTIAL_BANK_TRANSFER_REFUND_REASON.to_string()),
+            refund_type: Some(api_models::refunds::RefundType::Instant),
+            ..Default::default()
+        },
+        None,
+        merchant_connector_account.get_mca_id(),
     )
     .await
-    .map(services::ApplicationResponse::Json)
+}
+
+/// Resolves the connector and the merchant connector account of the refund from the successful
+/// attempt of the payment, which may have been made through another connector than the earlier
+/// attempts of the payment
+#[instrument(skip_all)]
+pub async fn get_refund_connector_details(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    creds_identifier: Option<String>,
+) -> RouterResult<(String, payments::helpers::MerchantConnectorAccountType)> {
+    let connector = payment_attempt
+        .connector
+        .clone()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Failed to retrieve connector from payment attempt")?;
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &connector,
+        api::GetToken::Connector,
+    )?;
+
+    if let Some(connector_transaction_id) = payment_attempt.connector_transaction_id.as_deref() {
+        validator::validate_connector_transaction_id(
+            &connector,
+            connector_data
+                .connector
+                .get_connector_transaction_id_pattern(),
+            connector_transaction_id,
+        )?;
+    }
+
+    let merchant_connector_account = core_utils::get_merchant_connector_account_of_payment(
+        state,
+        &connector,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        creds_identifier,
+    )
+    .await?;
+
+    Ok((connector, merchant_connector_account))
 }
 
 #[instrument(skip_all)]
@@ -201,6 +356,52 @@ pub async fn trigger_refund_to_gateway(
         router_data
     };
 
+    // The refunds which the connector accepted without processing them are polled, a sync task
+    // resumes them when they are not processed within the polling budget
+    let router_data_res = match router_data_res.poll_config.clone() {
+        Some(poll_config) => {
+            let schedule_time = common_utils::date_time::now().saturating_add(
+                time::Duration::try_from(poll_config.get_polling_budget()).unwrap_or_default(),
+            );
+            add_refund_sync_task(
+                &*state.store,
+                refund,
+                "REFUND_WORKFLOW_ROUTER",
+                schedule_time,
+            )
+            .await
+            .map_err(|error| logger::error!(process_tracker_error=?error))
+            .ok();
+
+            let connector_integration: services::BoxedConnectorIntegration<
+                '_,
+                api::Execute,
+                types::RefundsData,
+                types::RefundsResponseData,
+            > = connector.connector.get_connector_integration();
+            services::api::polling::execute_connector_polling(
+                state,
+                &connector_integration,
+                router_data_res,
+            )
+            .await
+        }
+        None => router_data_res,
+    };
+
+    connector_response_capture::capture_connector_response(
+        state.store.as_ref(),
+        connector_response_capture::CapturedResource {
+            merchant_id: &refund.merchant_id,
+            payment_id: &refund.payment_id,
+            attempt_id: &payment_attempt.attempt_id,
+            refund_id: Some(&refund.refund_id),
+            connector: &router_data_res.connector,
+        },
+        router_data_res.raw_connector_response.as_ref(),
+    )
+    .await;
+
     let refund_update = match router_data_res.response {
         Err(err) => storage::RefundUpdate::ErrorUpdate {
             refund_status: Some(enums::RefundStatus::Failure),
@@ -223,7 +424,7 @@ pub async fn trigger_refund_to_gateway(
                 refund_status: response.refund_status,
                 sent_to_gateway: true,
                 refund_error_message: None,
-                refund_arn: "".to_string(),
+                refund_arn: response.acquirer_reference_number,
             }
         }
     };
@@ -429,6 +630,19 @@ pub async fn sync_refund_with_gateway(
         router_data
     };
 
+    connector_response_capture::capture_connector_response(
+        state.store.as_ref(),
+        connector_response_capture::CapturedResource {
+            merchant_id: &refund.merchant_id,
+            payment_id: &refund.payment_id,
+            attempt_id: &payment_attempt.attempt_id,
+            refund_id: Some(&refund.refund_id),
+            connector: &router_data_res.connector,
+        },
+        router_data_res.raw_connector_response.as_ref(),
+    )
+    .await;
+
     let refund_update = match router_data_res.response {
         Err(error_message) => storage::RefundUpdate::ErrorUpdate {
             refund_status: None,
@@ -440,7 +654,7 @@ pub async fn sync_refund_with_gateway(
             refund_status: response.refund_status,
             sent_to_gateway: true,
             refund_error_message: None,
-            refund_arn: "".to_string(),
+            refund_arn: response.acquirer_reference_number,
         },
     };
 
@@ -509,6 +723,7 @@ pub async fn validate_and_create_refund(
     refund_amount: i64,
     req: refunds::RefundRequest,
     creds_identifier: Option<String>,
+    merchant_connector_id: Option<String>,
 ) -> RouterResult<refunds::RefundResponse> {
     let db = &*state.store;
     let (refund_id, all_refunds, currency, refund_create_req, refund);
@@ -564,6 +779,11 @@ pub async fn validate_and_create_refund(
                 .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
 
             currency = payment_attempt.currency.get_required_value("currency")?;
+            req.currency
+                .map(|refund_currency| {
+                    validator::validate_refund_currency(refund_currency, currency)
+                })
+                .transpose()?;
 
             //[#249]: Add Connector Based Validation here.
             validator::validate_payment_order_age(
@@ -578,8 +798,22 @@ pub async fn validate_and_create_refund(
                 ),
             })?;
 
-            validator::validate_refund_amount(payment_attempt.amount, &all_refunds, refund_amount)
-                .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount)?;
+            validator::validate_refund_amount(
+                &payment_attempt.into(),
+                payment_intent.amount_captured,
+                &all_refunds,
+                refund_amount,
+            )
+            .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount)?;
+
+            amount_limits::validate_amount_limits(
+                state,
+                &merchant_account.merchant_id,
+                amount_limits::LimitedAmount::Refund,
+                refund_amount,
+                currency,
+            )
+            .await?;
 
             validator::validate_maximum_refund_against_payment_attempt(
                 &all_refunds,
@@ -594,6 +828,21 @@ pub async fn validate_and_create_refund(
                 .into_report()
                 .attach_printable("No connector populated in payment attempt")?;
 
+            let is_refund_reason_required = db
+                .find_config_by_key(&format!(
+                    "{}_requires_refund_reason",
+                    merchant_account.merchant_id
+                ))
+                .await
+                .map(|config| config.config == "true")
+                .unwrap_or(false);
+            validator::validate_refund_reason(
+                &connector,
+                is_refund_reason_required,
+                req.reason_code,
+                req.reason.as_deref(),
+            )?;
+
             refund_create_req = storage::RefundNew::default()
                 .set_refund_id(refund_id.to_string())
                 .set_internal_reference_id(utils::generate_id(consts::ID_LENGTH, "refid"))
@@ -613,6 +862,8 @@ pub async fn validate_and_create_refund(
                 .set_description(req.reason.clone())
                 .set_attempt_id(payment_attempt.attempt_id.clone())
                 .set_refund_reason(req.reason)
+                .set_refund_reason_code(req.reason_code)
+                .set_merchant_connector_id(merchant_connector_id)
                 .to_owned();
 
             refund = db
@@ -620,6 +871,41 @@ pub async fn validate_and_create_refund(
                 .await
                 .to_duplicate_response(errors::ApiErrorResponse::DuplicateRefundRequest)?;
 
+            // Refunds of the same payment created concurrently have all been validated against
+            // the refunds which existed before them, validate again now that they are inserted
+            let refunds_after_insert = db
+                .find_refund_by_merchant_id_connector_transaction_id(
+                    &merchant_account.merchant_id,
+                    &connecter_transaction_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed while finding refunds of the payment attempt")?;
+            if let Err(error) = validator::validate_inserted_refund_amount(
+                &payment_attempt.into(),
+                payment_intent.amount_captured,
+                &refunds_after_insert,
+                &refund,
+            ) {
+                db.update_refund(
+                    refund,
+                    storage::RefundUpdate::ErrorUpdate {
+                        refund_status: Some(enums::RefundStatus::TransactionFailure),
+                        refund_error_message: Some(error.current_context().to_string()),
+                        refund_error_code: None,
+                    },
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable(
+                    "Failed while updating the refund which exceeds the payment amount",
+                )?;
+                return Err(error
+                    .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount));
+            }
+
             schedule_refund_execution(
                 state,
                 refund,
@@ -716,6 +1002,7 @@ impl ForeignFrom<storage::Refund> for api::RefundResponse {
             amount: refund.refund_amount,
             currency: refund.currency.to_string(),
             reason: refund.refund_reason,
+            reason_code: refund.refund_reason_code,
             status: refund.refund_status.foreign_into(),
             metadata: refund.metadata,
             error_message: refund.refund_error_message,
@@ -723,6 +1010,10 @@ impl ForeignFrom<storage::Refund> for api::RefundResponse {
             created_at: Some(refund.created_at),
             updated_at: Some(refund.updated_at),
             connector: refund.connector,
+            merchant_connector_id: refund.merchant_connector_id,
+            // Refunds created earlier have an empty reference number stored
+            acquirer_reference_number: refund.refund_arn.filter(|arn| !arn.is_empty()),
+            connector_response: None,
         }
     }
 }
@@ -786,7 +1077,7 @@ pub async fn schedule_refund_execution(
                     //[#300]: return refund status response
                     match refund_type {
                         api_models::refunds::RefundType::Scheduled => {
-                            add_refund_sync_task(db, &refund, runner)
+                            add_refund_sync_task(db, &refund, runner, common_utils::date_time::now())
                                 .await
                                 .change_context(errors::ApiErrorResponse::InternalServerError)
                                 .attach_printable_lazy(|| format!("Failed while pushing refund sync task in scheduler: refund_id: {}", refund.refund_id))?;
@@ -865,6 +1156,7 @@ pub async fn sync_refund_with_gateway_workflow(
                 &*state.store,
                 response.connector,
                 response.merchant_id,
+                None,
                 refund_tracker.to_owned(),
             )
             .await?
@@ -961,11 +1253,34 @@ pub async fn trigger_refund_execute_workflow(
                 None,
             )
             .await?;
-            add_refund_sync_task(db, &updated_refund, "REFUND_WORKFLOW_ROUTER").await?;
+            // The refunds which are polled already have their sync task
+            match add_refund_sync_task(
+                db,
+                &updated_refund,
+                "REFUND_WORKFLOW_ROUTER",
+                common_utils::date_time::now(),
+            )
+            .await
+            {
+                Err(error)
+                    if matches!(
+                        error.current_context(),
+                        errors::ApiErrorResponse::DuplicateRefundRequest
+                    ) => {}
+                result => {
+                    result?;
+                }
+            }
         }
         (true, enums::RefundStatus::Pending) => {
             // create sync task
-            add_refund_sync_task(db, &refund, "REFUND_WORKFLOW_ROUTER").await?;
+            add_refund_sync_task(
+                db,
+                &refund,
+                "REFUND_WORKFLOW_ROUTER",
+                common_utils::date_time::now(),
+            )
+            .await?;
         }
         (_, _) => {
             //mark task as finished
@@ -996,6 +1311,7 @@ pub async fn add_refund_sync_task(
     db: &dyn db::StorageInterface,
     refund: &storage::Refund,
     runner: &str,
+    schedule_time: time::PrimitiveDateTime,
 ) -> RouterResult<storage::ProcessTracker> {
     let current_time = common_utils::date_time::now();
     let refund_workflow_model = serde_json::to_value(refund_to_refund_core_workflow_model(refund))
@@ -1009,7 +1325,7 @@ pub async fn add_refund_sync_task(
         tag: vec![String::from("REFUND")],
         runner: Some(String::from(runner)),
         retry_count: 0,
-        schedule_time: Some(common_utils::date_time::now()),
+        schedule_time: Some(schedule_time),
         rule: String::new(),
         tracking_data: refund_workflow_model,
         business_status: String::from("Pending"),
@@ -1118,3 +1434,312 @@ pub async fn retry_refund_sync_task(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use masking::PeekInterface;
+
+    use super::*;
+
+    async fn get_state_with_connector_accounts(
+    ) -> (AppState, domain::MerchantAccount, domain::MerchantKeyStore) {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+
+        let merchant_account = db
+            .insert_merchant(
+                domain::MerchantAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    return_url: None,
+                    enable_payment_response_hash: false,
+                    payment_response_hash_key: None,
+                    redirect_to_merchant_with_http_post: false,
+                    merchant_name: None,
+                    merchant_details: None,
+                    webhook_details: None,
+                    sub_merchants_enabled: None,
+                    parent_merchant_id: None,
+                    publishable_key: None,
+                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+                    locker_id: None,
+                    metadata: None,
+                    routing_algorithm: None,
+                    primary_business_details: serde_json::Value::Null,
+                    frm_routing_algorithm: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    intent_fulfillment_time: None,
+                    payout_routing_algorithm: None,
+                    organization_id: None,
+                    is_recon_enabled: false,
+                    default_profile: None,
+                    recon_status: enums::ReconStatus::NotRequested,
+                    webhook_auth_details: None,
+                    default_currency: None,
+                    default_country: None,
+                    pii_policy: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+
+        for connector_name in ["adyen", "stripe"] {
+            db.insert_merchant_connector_account(
+                domain::MerchantConnectorAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    connector_name: connector_name.to_string(),
+                    connector_account_details: domain::types::encrypt(
+                        masking::Secret::new(serde_json::json!({
+                            "auth_type": "HeaderKey",
+                            "api_key": format!("{connector_name}_key"),
+                        })),
+                        key_store.key.get_inner().peek(),
+                    )
+                    .await
+                    .unwrap(),
+                    test_mode: None,
+                    disabled: None,
+                    merchant_connector_id: format!("mca_{connector_name}"),
+                    payment_methods_enabled: None,
+                    connector_type: enums::ConnectorType::PaymentProcessor,
+                    metadata: None,
+                    frm_configs: None,
+                    connector_label: None,
+                    business_country: None,
+                    business_label: None,
+                    business_sub_label: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    connector_webhook_details: None,
+                    profile_id: Some("pro_default".to_string()),
+                    applepay_verified_domains: None,
+                    disabled_flows: None,
+                    previous_credentials: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+        }
+
+        (state, merchant_account, key_store)
+    }
+
+    async fn insert_retried_payment(
+        db: &dyn db::StorageInterface,
+    ) -> (storage::PaymentIntent, storage::PaymentAttempt) {
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let now = common_utils::date_time::now();
+        let payment_intent = db
+            .insert_payment_intent(
+                storage::PaymentIntentNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    status: enums::IntentStatus::Succeeded,
+                    amount: 1000,
+                    currency: Some(enums::Currency::USD),
+                    amount_captured: Some(1000),
+                    active_attempt_id: "pay_1_2".to_string(),
+                    profile_id: Some("pro_default".to_string()),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        // The first attempt failed on adyen, the retry succeeded on stripe
+        for (attempt_id, connector, status, modified_at) in [
+            (
+                "pay_1_1",
+                "adyen",
+                enums::AttemptStatus::Failure,
+                now.saturating_sub(time::Duration::minutes(5)),
+            ),
+            ("pay_1_2", "stripe", enums::AttemptStatus::Charged, now),
+        ] {
+            db.insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: attempt_id.to_string(),
+                    status,
+                    amount: 1000,
+                    currency: Some(enums::Currency::USD),
+                    connector: Some(connector.to_string()),
+                    merchant_connector_id: Some(format!("mca_{connector}")),
+                    created_at: Some(modified_at),
+                    modified_at: Some(modified_at),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        }
+
+        let mut payment_attempt = db
+            .find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        payment_attempt.connector_transaction_id = Some("pi_3NqLdY2eZvKYlo2C".to_string());
+
+        (payment_intent, payment_attempt)
+    }
+
+    #[tokio::test]
+    async fn test_refund_of_retried_payment_is_made_through_the_successful_attempt() {
+        let (state, merchant_account, key_store) = get_state_with_connector_accounts().await;
+        let (payment_intent, payment_attempt) = insert_retried_payment(&*state.store).await;
+
+        let (connector, merchant_connector_account) = get_refund_connector_details(
+            &state,
+            &merchant_account,
+            &key_store,
+            &payment_intent,
+            &payment_attempt,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(payment_attempt.attempt_id, "pay_1_2");
+        assert_eq!(connector, "stripe");
+        assert_eq!(
+            merchant_connector_account.get_mca_id().as_deref(),
+            Some("mca_stripe")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refund_of_attempt_with_unknown_connector_account_is_rejected() {
+        let (state, merchant_account, key_store) = get_state_with_connector_accounts().await;
+        let (payment_intent, payment_attempt) = insert_retried_payment(&*state.store).await;
+
+        for merchant_connector_id in ["mca_deleted", "mca_adyen"] {
+            let tampered_attempt = storage::PaymentAttempt {
+                merchant_connector_id: Some(merchant_connector_id.to_string()),
+                ..payment_attempt.clone()
+            };
+            let error = get_refund_connector_details(
+                &state,
+                &merchant_account,
+                &key_store,
+                &payment_intent,
+                &tampered_attempt,
+                None,
+            )
+            .await
+            .err()
+            .unwrap();
+
+            assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::MerchantConnectorAccountNotFound { id }
+                    if id == merchant_connector_id
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refund_in_another_currency_than_the_payment_is_rejected() {
+        let (state, merchant_account, key_store) = get_state_with_connector_accounts().await;
+        let (payment_intent, payment_attempt) = insert_retried_payment(&*state.store).await;
+
+        let error = validate_and_create_refund(
+            &state,
+            &merchant_account,
+            &key_store,
+            &payment_attempt,
+            &payment_intent,
+            500,
+            refunds::RefundRequest {
+                payment_id: "pay_1".to_string(),
+                amount: Some(500),
+                currency: Some(enums::Currency::EUR),
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::RefundCurrencyMismatch {
+                refund_currency,
+                payment_currency,
+            } if refund_currency == "EUR" && payment_currency == "USD"
+        ));
+        // The refund was not stored
+        assert!(state
+            .store
+            .find_refund_by_merchant_id_connector_transaction_id(
+                "merchant_1",
+                "pi_3NqLdY2eZvKYlo2C",
+                merchant_account.storage_scheme,
+            )
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_transaction_id_of_another_connector_is_rejected() {
+        let connector = api::ConnectorData::get_connector_by_name(
+            &crate::configs::settings::Connectors::default(),
+            "stripe",
+            api::GetToken::Connector,
+        )
+        .unwrap();
+        let pattern = connector.connector.get_connector_transaction_id_pattern();
+
+        assert!(validator::validate_connector_transaction_id(
+            "stripe",
+            pattern,
+            "pi_3NqLdY2eZvKYlo2C"
+        )
+        .is_ok());
+        assert!(validator::validate_connector_transaction_id(
+            "stripe",
+            pattern,
+            "pay_mbabizu24mvu3mela5njyhpit4"
+        )
+        .is_err());
+        // Connectors which do not declare a pattern accept any transaction id
+        assert!(validator::validate_connector_transaction_id("adyen", None, "8835").is_ok());
+    }
+}
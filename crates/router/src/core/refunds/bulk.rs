@@ -0,0 +1,336 @@
+//! Refunds initiated in bulk. Every refund of the request is validated and created when the
+//! request is received, the refunds which fail validation are rejected without affecting the
+//! others. The accepted refunds are executed asynchronously by the scheduler, and their status
+//! can be retrieved with the identifier of the bulk refund.
+
+use std::collections::HashMap;
+
+use error_stack::{report, ResultExt};
+use futures::Future;
+use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
+
+use super::create_refund;
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    logger,
+    routes::AppState,
+    services,
+    types::{
+        api::refunds,
+        domain,
+        storage::{self, enums},
+        transformers::ForeignInto,
+    },
+    utils::{self, Encode, ValueExt},
+};
+
+#[instrument(skip_all)]
+pub async fn bulk_refund_create_core(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: refunds::BulkRefundRequest,
+) -> RouterResponse<refunds::BulkRefundResponse> {
+    validate_bulk_refund_request(&req)?;
+
+    let (items, created_refunds) = create_bulk_refund_items(req.refunds, |refund_request| {
+        create_refund(&state, &merchant_account, &key_store, refund_request)
+    })
+    .await;
+
+    let bulk_refund = storage::BulkRefundNew {
+        bulk_refund_id: utils::generate_id(consts::ID_LENGTH, "bulk_ref"),
+        merchant_id: merchant_account.merchant_id.clone(),
+        items: Encode::<Vec<storage::BulkRefundItem>>::encode_to_value(&items)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the refunds of the bulk refund")?,
+        created_at: common_utils::date_time::now(),
+    };
+    let bulk_refund = state
+        .store
+        .insert_bulk_refund(bulk_refund)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while inserting the bulk refund")?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_bulk_refund_response(
+            bulk_refund.bulk_refund_id,
+            bulk_refund.created_at,
+            items,
+            &created_refunds,
+        ),
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn bulk_refund_retrieve_core(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    bulk_refund_id: String,
+) -> RouterResponse<refunds::BulkRefundResponse> {
+    let db = &*state.store;
+    let bulk_refund = db
+        .find_bulk_refund_by_merchant_id_bulk_refund_id(
+            &merchant_account.merchant_id,
+            &bulk_refund_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Bulk refund with id {bulk_refund_id} does not exist"),
+        })?;
+    let items = bulk_refund
+        .items
+        .parse_value::<Vec<storage::BulkRefundItem>>("BulkRefundItem")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the refunds of the bulk refund")?;
+
+    let mut refunds = HashMap::new();
+    for refund_id in items.iter().filter_map(|item| item.refund_id.as_ref()) {
+        let refund = db
+            .find_refund_by_merchant_id_refund_id(
+                &merchant_account.merchant_id,
+                refund_id,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+        refunds.insert(refund_id.clone(), refund.foreign_into());
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        get_bulk_refund_response(
+            bulk_refund.bulk_refund_id,
+            bulk_refund.created_at,
+            items,
+            &refunds,
+        ),
+    ))
+}
+
+pub fn validate_bulk_refund_request(req: &refunds::BulkRefundRequest) -> RouterResult<()> {
+    utils::when(
+        req.refunds.is_empty() || req.refunds.len() > consts::MAX_BULK_REFUND_ITEMS,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "refunds must contain between 1 and {} refunds",
+                    consts::MAX_BULK_REFUND_ITEMS
+                ),
+            }))
+        },
+    )
+}
+
+/// Creates the refunds one after the other, so that the refunds of the same payment are validated
+/// against each other. A refund which fails is recorded as rejected and the remaining refunds are
+/// still created.
+pub async fn create_bulk_refund_items<F, Fut>(
+    refund_requests: Vec<refunds::BulkRefundItemRequest>,
+    create_refund: F,
+) -> (
+    Vec<storage::BulkRefundItem>,
+    HashMap<String, refunds::RefundResponse>,
+)
+where
+    F: Fn(refunds::RefundRequest) -> Fut,
+    Fut: Future<Output = RouterResult<refunds::RefundResponse>>,
+{
+    let mut items = Vec::with_capacity(refund_requests.len());
+    let mut created_refunds = HashMap::new();
+
+    for refund_request in refund_requests {
+        let payment_id = refund_request.payment_id.clone();
+        match create_refund(refund_request.into()).await {
+            Ok(refund) => {
+                items.push(storage::BulkRefundItem {
+                    payment_id,
+                    refund_id: Some(refund.refund_id.clone()),
+                    amount: Some(refund.amount),
+                    status: enums::BulkRefundItemStatus::Accepted,
+                    error_code: None,
+                    error_message: None,
+                });
+                created_refunds.insert(refund.refund_id.clone(), refund);
+            }
+            Err(error) => {
+                logger::info!(?error, %payment_id, "refund of the bulk refund was rejected");
+                let error = error.current_context();
+                items.push(storage::BulkRefundItem {
+                    payment_id,
+                    refund_id: None,
+                    amount: None,
+                    status: enums::BulkRefundItemStatus::Rejected,
+                    error_code: Some(error.error_code()),
+                    error_message: Some(error.error_message()),
+                });
+            }
+        }
+    }
+
+    (items, created_refunds)
+}
+
+pub fn get_bulk_refund_response(
+    bulk_refund_id: String,
+    created_at: PrimitiveDateTime,
+    items: Vec<storage::BulkRefundItem>,
+    refunds: &HashMap<String, refunds::RefundResponse>,
+) -> refunds::BulkRefundResponse {
+    let refunds = items
+        .into_iter()
+        .map(|item| {
+            let refund = item
+                .refund_id
+                .as_ref()
+                .and_then(|refund_id| refunds.get(refund_id));
+            refunds::BulkRefundItemResponse {
+                payment_id: item.payment_id,
+                refund_id: item.refund_id,
+                amount: item.amount,
+                status: item.status,
+                refund_status: refund.map(|refund| refund.status),
+                error_code: item
+                    .error_code
+                    .or_else(|| refund.and_then(|refund| refund.error_code.clone())),
+                error_message: item
+                    .error_message
+                    .or_else(|| refund.and_then(|refund| refund.error_message.clone())),
+            }
+        })
+        .collect::<Vec<_>>();
+    let accepted_count = refunds
+        .iter()
+        .filter(|refund| refund.status == enums::BulkRefundItemStatus::Accepted)
+        .count();
+
+    refunds::BulkRefundResponse {
+        bulk_refund_id,
+        accepted_count,
+        rejected_count: refunds.len() - accepted_count,
+        refunds,
+        created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn get_refund_response(refund_request: refunds::RefundRequest) -> refunds::RefundResponse {
+        refunds::RefundResponse {
+            refund_id: format!("ref_{}", refund_request.payment_id),
+            payment_id: refund_request.payment_id,
+            amount: refund_request.amount.unwrap_or(1000),
+            currency: "USD".to_string(),
+            reason: refund_request.reason,
+            reason_code: refund_request.reason_code,
+            status: refunds::RefundStatus::Pending,
+            metadata: None,
+            error_message: None,
+            error_code: None,
+            created_at: None,
+            updated_at: None,
+            connector: "stripe".to_string(),
+            merchant_connector_id: None,
+            acquirer_reference_number: None,
+            connector_response: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_refund_does_not_abort_bulk_refund() {
+        let refund_requests = ["pay_1", "pay_unknown", "pay_3"]
+            .into_iter()
+            .map(|payment_id| refunds::BulkRefundItemRequest {
+                payment_id: payment_id.to_string(),
+                amount: Some(500),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        validate_bulk_refund_request(&refunds::BulkRefundRequest {
+            refunds: refund_requests.clone(),
+        })
+        .unwrap();
+
+        let (items, mut created_refunds) =
+            create_bulk_refund_items(refund_requests, |refund_request| async move {
+                if refund_request.payment_id == "pay_unknown" {
+                    Err(report!(errors::ApiErrorResponse::PaymentNotFound))
+                } else {
+                    assert_eq!(
+                        refund_request.refund_type,
+                        Some(refunds::RefundType::Scheduled)
+                    );
+                    Ok(get_refund_response(refund_request))
+                }
+            })
+            .await;
+        assert_eq!(created_refunds.len(), 2);
+
+        // The items are stored with the bulk refund and read back when its status is retrieved
+        let stored_items = Encode::<Vec<storage::BulkRefundItem>>::encode_to_value(&items)
+            .unwrap()
+            .parse_value::<Vec<storage::BulkRefundItem>>("BulkRefundItem")
+            .unwrap();
+        assert_eq!(stored_items, items);
+
+        // The first refund has been executed by the scheduler in the meantime
+        if let Some(refund) = created_refunds.get_mut("ref_pay_1") {
+            refund.status = refunds::RefundStatus::Succeeded;
+        }
+        let response = get_bulk_refund_response(
+            "bulk_ref_1".to_string(),
+            common_utils::date_time::now(),
+            stored_items,
+            &created_refunds,
+        );
+
+        assert_eq!(response.accepted_count, 2);
+        assert_eq!(response.rejected_count, 1);
+        let statuses = response
+            .refunds
+            .iter()
+            .map(|refund| {
+                (
+                    refund.payment_id.as_str(),
+                    refund.status,
+                    refund.refund_status,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                (
+                    "pay_1",
+                    enums::BulkRefundItemStatus::Accepted,
+                    Some(refunds::RefundStatus::Succeeded)
+                ),
+                ("pay_unknown", enums::BulkRefundItemStatus::Rejected, None),
+                (
+                    "pay_3",
+                    enums::BulkRefundItemStatus::Accepted,
+                    Some(refunds::RefundStatus::Pending)
+                ),
+            ]
+        );
+        assert_eq!(response.refunds[1].error_code.as_deref(), Some("HE_02"));
+        assert_eq!(response.refunds[1].refund_id, None);
+    }
+
+    #[test]
+    fn test_bulk_refund_size_is_limited() {
+        let refunds = |count| refunds::BulkRefundRequest {
+            refunds: vec![refunds::BulkRefundItemRequest::default(); count],
+        };
+
+        assert!(validate_bulk_refund_request(&refunds(0)).is_err());
+        assert!(validate_bulk_refund_request(&refunds(consts::MAX_BULK_REFUND_ITEMS)).is_ok());
+        assert!(validate_bulk_refund_request(&refunds(consts::MAX_BULK_REFUND_ITEMS + 1)).is_err());
+    }
+}
@@ -1,9 +1,15 @@
-use error_stack::{report, IntoReport};
+use std::str::FromStr;
+
+use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 
 use crate::{
-    core::errors::{self, CustomResult, RouterResult},
+    connector::utils::refund_reason,
+    core::{
+        errors::{self, CustomResult, RouterResult},
+        payments::amounts,
+    },
     db::StorageInterface,
     logger,
     types::storage::{self, enums},
@@ -40,32 +46,54 @@ pub fn validate_success_transaction(
     Ok(())
 }
 
+/// Validates the refund amount against the amount of the payment which is still refundable, as
+/// returned in the payments response
 #[instrument(skip_all)]
 pub fn validate_refund_amount(
-    payment_attempt_amount: i64, // &storage::PaymentAttempt,
+    payment_attempt: &amounts::AttemptAmounts,
+    amount_captured: Option<i64>,
     all_refunds: &[storage::Refund],
     refund_amount: i64,
 ) -> CustomResult<(), RefundValidationError> {
-    let total_refunded_amount: i64 = all_refunds
+    let payment_amounts =
+        amounts::get_payment_amounts(payment_attempt, amount_captured, all_refunds);
+
+    utils::when(refund_amount > payment_amounts.amount_refundable, || {
+        Err(report!(
+            RefundValidationError::RefundAmountExceedsPaymentAmount
+        ))
+    })
+}
+
+/// Validates the amount of a refund which has already been inserted. Refunds of the same payment
+/// which are created concurrently all pass `validate_refund_amount`, so only the refunds created
+/// before the inserted refund are counted against the payment amount, and the refund created last
+/// is the one to fail.
+#[instrument(skip_all)]
+pub fn validate_inserted_refund_amount(
+    payment_attempt: &amounts::AttemptAmounts,
+    amount_captured: Option<i64>,
+    all_refunds: &[storage::Refund],
+    refund: &storage::Refund,
+) -> CustomResult<(), RefundValidationError> {
+    let refund_order = |refund: &storage::Refund| {
+        (
+            refund.created_at,
+            refund.id,
+            refund.internal_reference_id.clone(),
+        )
+    };
+    let prior_refunds = all_refunds
         .iter()
-        .filter_map(|refund| {
-            if refund.refund_status != enums::RefundStatus::Failure
-                && refund.refund_status != enums::RefundStatus::TransactionFailure
-            {
-                Some(refund.refund_amount)
-            } else {
-                None
-            }
-        })
-        .sum();
+        .filter(|prior_refund| refund_order(prior_refund) < refund_order(refund))
+        .cloned()
+        .collect::<Vec<_>>();
 
-    utils::when(
-        refund_amount > (payment_attempt_amount - total_refunded_amount),
-        || {
-            Err(report!(
-                RefundValidationError::RefundAmountExceedsPaymentAmount
-            ))
-        },
+    validate_refund_amount(
+        payment_attempt,
+        amount_captured,
+        &prior_refunds,
+        refund.refund_amount,
     )
 }
 
@@ -181,3 +209,215 @@ pub fn validate_for_valid_refunds(
         _ => Ok(()),
     }
 }
+
+/// Connectors refund in the currency of the payment, so a refund requested in any other currency
+/// is rejected before it is created
+pub fn validate_refund_currency(
+    refund_currency: enums::Currency,
+    payment_currency: enums::Currency,
+) -> RouterResult<()> {
+    utils::when(refund_currency != payment_currency, || {
+        Err(report!(errors::ApiErrorResponse::RefundCurrencyMismatch {
+            refund_currency: refund_currency.to_string(),
+            payment_currency: payment_currency.to_string(),
+        }))
+    })
+}
+
+/// When the merchant requires a reason for every refund, the refund is rejected if it has no
+/// reason, or if the connector would not receive the reason
+pub fn validate_refund_reason(
+    connector: &str,
+    is_refund_reason_required: bool,
+    reason_code: Option<enums::RefundReason>,
+    reason: Option<&str>,
+) -> RouterResult<()> {
+    if !is_refund_reason_required {
+        return Ok(());
+    }
+
+    utils::when(reason_code.is_none() && reason.is_none(), || {
+        Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "reason_code"
+        }))
+    })?;
+
+    let support = api_models::enums::Connector::from_str(connector)
+        .map(refund_reason::get_refund_reason_support)
+        .unwrap_or(refund_reason::RefundReasonSupport::FreeText { max_length: None });
+    let message = match support {
+        refund_reason::RefundReasonSupport::NotSupported => {
+            format!("A refund reason is required by the merchant, but {connector} does not accept a refund reason")
+        }
+        _ => format!("A refund reason is required by the merchant, but {connector} does not accept the reason of the refund"),
+    };
+    utils::when(
+        refund_reason::map_refund_reason(support, reason_code, reason).is_none(),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message
+            }))
+        },
+    )
+}
+
+/// The refund is sent to the connector of the successful attempt with its transaction id, so a
+/// transaction id which does not match the pattern declared by the connector means the attempt
+/// was recorded against another connector
+pub fn validate_connector_transaction_id(
+    connector: &str,
+    connector_transaction_id_pattern: Option<&str>,
+    connector_transaction_id: &str,
+) -> RouterResult<()> {
+    let Some(pattern) = connector_transaction_id_pattern else {
+        return Ok(());
+    };
+    let is_match = regex::Regex::new(pattern)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Invalid connector transaction id pattern configured for {connector}")
+        })?
+        .is_match(connector_transaction_id);
+
+    utils::when(!is_match, || {
+        Err(report!(errors::ApiErrorResponse::InternalServerError)).attach_printable(format!(
+            "Connector transaction id {connector_transaction_id} of the payment attempt was not issued by {connector}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_refund(
+        id: i32,
+        refund_amount: i64,
+        refund_status: enums::RefundStatus,
+    ) -> storage::Refund {
+        let created_at = common_utils::date_time::now();
+        storage::Refund {
+            id,
+            internal_reference_id: format!("refid_{id}"),
+            refund_id: format!("ref_{id}"),
+            payment_id: "pay_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            connector_transaction_id: "txn_1".to_string(),
+            connector: "stripe".to_string(),
+            connector_refund_id: None,
+            external_reference_id: None,
+            refund_type: enums::RefundType::RegularRefund,
+            total_amount: 1000,
+            currency: enums::Currency::USD,
+            refund_amount,
+            refund_status,
+            sent_to_gateway: false,
+            refund_error_message: None,
+            metadata: None,
+            refund_arn: None,
+            created_at,
+            updated_at: created_at,
+            description: None,
+            attempt_id: "pay_1_1".to_string(),
+            refund_reason: None,
+            refund_error_code: None,
+            profile_id: None,
+            refund_reason_code: None,
+            merchant_connector_id: None,
+        }
+    }
+
+    #[test]
+    fn test_concurrent_refund_created_last_exceeds_payment_amount() {
+        let mut first = get_refund(1, 600, enums::RefundStatus::Pending);
+        let mut second = get_refund(2, 600, enums::RefundStatus::Pending);
+        second.created_at = first.created_at;
+        let failed = get_refund(0, 900, enums::RefundStatus::Failure);
+
+        let payment_attempt = amounts::AttemptAmounts {
+            status: enums::AttemptStatus::Charged,
+            amount: 1000,
+            ..Default::default()
+        };
+
+        // Both refunds passed the validation before being inserted
+        let all_refunds = vec![failed.clone(), first.clone(), second.clone()];
+
+        assert!(validate_inserted_refund_amount(
+            &payment_attempt,
+            Some(1000),
+            &all_refunds,
+            &first
+        )
+        .is_ok());
+        assert!(validate_inserted_refund_amount(
+            &payment_attempt,
+            Some(1000),
+            &all_refunds,
+            &second
+        )
+        .is_err());
+
+        first.refund_status = enums::RefundStatus::TransactionFailure;
+        let all_refunds = vec![failed, first, second.clone()];
+        assert!(validate_inserted_refund_amount(
+            &payment_attempt,
+            Some(1000),
+            &all_refunds,
+            &second
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_refund_amount_is_limited_to_captured_amount() {
+        let payment_attempt = amounts::AttemptAmounts {
+            status: enums::AttemptStatus::PartialCharged,
+            amount: 1000,
+            ..Default::default()
+        };
+        let all_refunds = vec![get_refund(1, 300, enums::RefundStatus::Success)];
+
+        // Only 600 of the 1000 authorized were captured, of which 300 were already refunded
+        assert!(validate_refund_amount(&payment_attempt, Some(600), &all_refunds, 300).is_ok());
+        assert!(validate_refund_amount(&payment_attempt, Some(600), &all_refunds, 301).is_err());
+    }
+
+    #[test]
+    fn test_refund_currency_mismatch_is_rejected() {
+        assert!(validate_refund_currency(enums::Currency::USD, enums::Currency::USD).is_ok());
+
+        let error = validate_refund_currency(enums::Currency::EUR, enums::Currency::USD)
+            .expect_err("refund in a different currency should be rejected");
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::RefundCurrencyMismatch {
+                refund_currency,
+                payment_currency,
+            } if refund_currency == "EUR" && payment_currency == "USD"
+        ));
+    }
+
+    #[test]
+    fn test_required_refund_reason_is_enforced_per_connector() {
+        assert!(validate_refund_reason("checkout", false, None, None).is_ok());
+        assert!(validate_refund_reason("stripe", true, None, None).is_err());
+
+        assert!(
+            validate_refund_reason("stripe", true, Some(enums::RefundReason::Duplicate), None)
+                .is_ok()
+        );
+        // Stripe accepts only a fixed set of reasons, a free text reason is not sent to it
+        assert!(validate_refund_reason("stripe", true, None, Some("Charged twice")).is_err());
+        assert!(validate_refund_reason("mollie", true, None, Some("Charged twice")).is_ok());
+
+        let error =
+            validate_refund_reason("checkout", true, Some(enums::RefundReason::Duplicate), None)
+                .expect_err("checkout does not accept a refund reason");
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+    }
+}
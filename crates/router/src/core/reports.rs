@@ -0,0 +1,855 @@
+use api_models::reports;
+use bytes::Bytes;
+use common_utils::date_time;
+use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
+use error_stack::{IntoReport, ResultExt};
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use scheduler::utils as pt_utils;
+use time::{format_description::well_known::Iso8601, PrimitiveDateTime};
+
+use super::errors::{self, RouterResponse, RouterResult};
+use crate::{
+    consts,
+    db::StorageInterface,
+    routes::AppState,
+    services::{self, ApplicationResponse},
+    types::{domain, storage, storage::enums},
+    utils,
+};
+
+/// The columns of the reconciliation report, in the order in which they are written.
+///
+/// This set is stable, new columns are only ever appended so that parsers of the report written
+/// against older versions keep working.
+///
+/// - `record_type`: `payment` or `refund`
+/// - `payment_id`: the identifier of the payment, for refunds the payment that was refunded
+/// - `refund_id`: the identifier of the refund, empty for payments
+/// - `connector`: the connector that processed the payment or refund
+/// - `connector_transaction_id`: the reference of the payment at the connector
+/// - `connector_refund_id`: the reference of the refund at the connector, empty for payments
+/// - `status`: the status of the payment or refund
+/// - `currency`: the ISO 4217 code of the currency of the amount
+/// - `amount`: the captured or refunded amount, in the major unit of the currency
+/// - `fee_amount`: the fee charged by the connector, in the major unit of `fee_currency`, empty
+///   when the connector does not report it and for refunds
+/// - `payment_method`: the payment method used for the payment
+/// - `created_at`: the time at which the payment or refund was created, in ISO 8601 format
+/// - `modified_at`: the time at which the payment or refund was last modified, in ISO 8601 format
+/// - `fee_currency`: the ISO 4217 code of the currency in which the connector charged the fee,
+///   which can differ from `currency`
+/// - `net_amount`: the amount settled to the merchant after deducting the fee, in the major unit
+///   of `fee_currency`, empty when the connector does not report it
+pub const RECONCILIATION_REPORT_COLUMNS: [&str; 15] = [
+    "record_type",
+    "payment_id",
+    "refund_id",
+    "connector",
+    "connector_transaction_id",
+    "connector_refund_id",
+    "status",
+    "currency",
+    "amount",
+    "fee_amount",
+    "payment_method",
+    "created_at",
+    "modified_at",
+    "fee_currency",
+    "net_amount",
+];
+
+/// Number of payments or refunds fetched from the database at a time while generating a report
+pub const RECONCILIATION_REPORT_PAGE_SIZE: u32 = 100;
+
+const RECONCILIATION_REPORT_FILE_TYPE: &str = "text/csv";
+
+// Tracking data by process_tracker
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReconciliationReportTrackingData {
+    pub merchant_id: String,
+    /// The file to which the report is written
+    pub file_id: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+    /// Schedule the report of the following day once this report is generated
+    pub daily: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ReconciliationRecordType {
+    Payment,
+    Refund,
+}
+
+/// A single row of the reconciliation report
+#[derive(Debug, Clone)]
+pub struct ReconciliationRecord {
+    pub record_type: ReconciliationRecordType,
+    pub payment_id: String,
+    pub refund_id: Option<String>,
+    pub connector: Option<String>,
+    pub connector_transaction_id: Option<String>,
+    pub connector_refund_id: Option<String>,
+    pub status: String,
+    pub currency: Option<enums::Currency>,
+    /// Amount in the minor unit of the currency
+    pub amount: i64,
+    pub payment_method: Option<enums::PaymentMethod>,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+    /// Fee in the minor unit of `fee_currency`
+    pub fee_amount: Option<i64>,
+    pub fee_currency: Option<enums::Currency>,
+    /// Net amount in the minor unit of `fee_currency`
+    pub net_amount: Option<i64>,
+}
+
+impl ReconciliationRecord {
+    fn to_csv_row(&self) -> RouterResult<String> {
+        let amount = format_report_amount(self.amount, self.currency);
+        let fee_amount = self
+            .fee_amount
+            .map(|fee_amount| format_report_amount(fee_amount, self.fee_currency))
+            .unwrap_or_default();
+        let net_amount = self
+            .net_amount
+            .map(|net_amount| format_report_amount(net_amount, self.fee_currency))
+            .unwrap_or_default();
+        let fields = [
+            self.record_type.to_string(),
+            self.payment_id.clone(),
+            self.refund_id.clone().unwrap_or_default(),
+            self.connector.clone().unwrap_or_default(),
+            self.connector_transaction_id.clone().unwrap_or_default(),
+            self.connector_refund_id.clone().unwrap_or_default(),
+            self.status.clone(),
+            self.currency
+                .map(|currency| currency.to_string())
+                .unwrap_or_default(),
+            amount,
+            fee_amount,
+            self.payment_method
+                .map(|payment_method| payment_method.to_string())
+                .unwrap_or_default(),
+            format_report_date(self.created_at)?,
+            format_report_date(self.modified_at)?,
+            self.fee_currency
+                .map(|currency| currency.to_string())
+                .unwrap_or_default(),
+            net_amount,
+        ];
+        Ok(to_csv_line(fields.iter().map(String::as_str)))
+    }
+}
+
+impl From<(storage::PaymentIntent, storage::PaymentAttempt)> for ReconciliationRecord {
+    fn from(
+        (payment_intent, payment_attempt): (storage::PaymentIntent, storage::PaymentAttempt),
+    ) -> Self {
+        Self {
+            record_type: ReconciliationRecordType::Payment,
+            payment_id: payment_intent.payment_id,
+            refund_id: None,
+            connector: payment_attempt.connector,
+            connector_transaction_id: payment_attempt.connector_transaction_id,
+            connector_refund_id: None,
+            status: payment_intent.status.to_string(),
+            currency: payment_intent.currency,
+            amount: payment_intent
+                .amount_captured
+                .unwrap_or(payment_intent.amount),
+            payment_method: payment_attempt.payment_method,
+            created_at: payment_intent.created_at,
+            modified_at: payment_intent.modified_at,
+            fee_amount: payment_attempt.connector_fee_amount,
+            fee_currency: payment_attempt.fee_currency,
+            net_amount: payment_attempt.net_amount,
+        }
+    }
+}
+
+impl From<storage::Refund> for ReconciliationRecord {
+    fn from(refund: storage::Refund) -> Self {
+        Self {
+            record_type: ReconciliationRecordType::Refund,
+            payment_id: refund.payment_id,
+            refund_id: Some(refund.refund_id),
+            connector: Some(refund.connector),
+            connector_transaction_id: Some(refund.connector_transaction_id),
+            connector_refund_id: refund.connector_refund_id,
+            status: refund.refund_status.to_string(),
+            currency: Some(refund.currency),
+            amount: refund.refund_amount,
+            payment_method: None,
+            created_at: refund.created_at,
+            modified_at: refund.updated_at,
+            fee_amount: None,
+            fee_currency: None,
+            net_amount: None,
+        }
+    }
+}
+
+fn format_report_amount(amount: i64, currency: Option<enums::Currency>) -> String {
+    match currency {
+        Some(currency) => currency.to_currency_base_unit_exact(amount),
+        None => amount.to_string(),
+    }
+}
+
+fn format_report_date(date: PrimitiveDateTime) -> RouterResult<String> {
+    date.assume_utc()
+        .format(&Iso8601::DEFAULT)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to format date in reconciliation report")
+}
+
+fn escape_csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        std::borrow::Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        std::borrow::Cow::Borrowed(field)
+    }
+}
+
+fn to_csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let mut line = fields.map(escape_csv_field).collect::<Vec<_>>().join(",");
+    line.push('\n');
+    line
+}
+
+/// Source of the payments and refunds included in a reconciliation report, fetched a page at a
+/// time so that a report never needs all of the records in memory at once
+#[async_trait::async_trait]
+pub trait ReconciliationRecordSource: Send + Sync {
+    async fn fetch_payments(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> RouterResult<Vec<ReconciliationRecord>>;
+
+    async fn fetch_refunds(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> RouterResult<Vec<ReconciliationRecord>>;
+}
+
+/// Fetches the succeeded payments and refunds of a merchant created in a time range
+pub struct StoreReconciliationRecordSource {
+    store: Box<dyn StorageInterface>,
+    merchant_id: String,
+    storage_scheme: enums::MerchantStorageScheme,
+    start_time: PrimitiveDateTime,
+    end_time: PrimitiveDateTime,
+}
+
+impl StoreReconciliationRecordSource {
+    pub fn new(
+        store: Box<dyn StorageInterface>,
+        merchant_account: &domain::MerchantAccount,
+        start_time: PrimitiveDateTime,
+        end_time: PrimitiveDateTime,
+    ) -> Self {
+        Self {
+            store,
+            merchant_id: merchant_account.merchant_id.clone(),
+            storage_scheme: merchant_account.storage_scheme,
+            start_time,
+            end_time,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReconciliationRecordSource for StoreReconciliationRecordSource {
+    async fn fetch_payments(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> RouterResult<Vec<ReconciliationRecord>> {
+        let constraints = PaymentIntentFetchConstraints::List {
+            offset,
+            starting_at: Some(self.start_time),
+            ending_at: Some(self.end_time),
+            connector: None,
+            currency: None,
+            status: Some(vec![
+                enums::IntentStatus::Succeeded,
+                enums::IntentStatus::PartiallyCaptured,
+            ]),
+            payment_methods: None,
+            customer_id: None,
+            starting_after_id: None,
+            ending_before_id: None,
+            limit: Some(limit),
+            livemode: None,
+            // The payments of the period are reconciled whether they are archived or not
+            include_archived: true,
+        };
+        let payments = self
+            .store
+            .get_filtered_payment_intents_attempt(
+                &self.merchant_id,
+                &constraints,
+                self.storage_scheme,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch payments for reconciliation report")?;
+        Ok(payments
+            .into_iter()
+            .map(ReconciliationRecord::from)
+            .collect())
+    }
+
+    async fn fetch_refunds(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> RouterResult<Vec<ReconciliationRecord>> {
+        let refund_list_request = api_models::refunds::RefundListRequest {
+            payment_id: None,
+            refund_id: None,
+            limit: None,
+            offset: None,
+            time_range: Some(api_models::refunds::TimeRange {
+                start_time: self.start_time,
+                end_time: Some(self.end_time),
+            }),
+            connector: None,
+            currency: None,
+            refund_status: Some(vec![enums::RefundStatus::Success]),
+        };
+        let refunds = self
+            .store
+            .filter_refund_by_constraints(
+                &self.merchant_id,
+                &refund_list_request,
+                self.storage_scheme,
+                limit.into(),
+                offset.into(),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch refunds for reconciliation report")?;
+        Ok(refunds
+            .into_iter()
+            .map(ReconciliationRecord::from)
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ReportCursor {
+    Header,
+    Payments { offset: u32 },
+    Refunds { offset: u32 },
+    Done,
+}
+
+/// Generate the reconciliation report as a stream of CSV chunks, the header followed by one
+/// chunk per page of payments or refunds. A page is only fetched once the previous chunk has been
+/// consumed.
+pub fn reconciliation_report_stream<S>(
+    source: S,
+    page_size: u32,
+) -> BoxStream<'static, RouterResult<Bytes>>
+where
+    S: ReconciliationRecordSource + 'static,
+{
+    let page_length = usize::try_from(page_size).unwrap_or(usize::MAX);
+    futures::stream::try_unfold(
+        (source, ReportCursor::Header),
+        move |(source, mut cursor)| async move {
+            loop {
+                let (records, next_cursor) = match cursor {
+                    ReportCursor::Header => {
+                        let header = to_csv_line(RECONCILIATION_REPORT_COLUMNS.into_iter());
+                        return Ok(Some((
+                            Bytes::from(header),
+                            (source, ReportCursor::Payments { offset: 0 }),
+                        )));
+                    }
+                    ReportCursor::Payments { offset } => {
+                        let records = source.fetch_payments(offset, page_size).await?;
+                        let next_cursor = if records.len() < page_length {
+                            ReportCursor::Refunds { offset: 0 }
+                        } else {
+                            ReportCursor::Payments {
+                                offset: offset.saturating_add(page_size),
+                            }
+                        };
+                        (records, next_cursor)
+                    }
+                    ReportCursor::Refunds { offset } => {
+                        let records = source.fetch_refunds(offset, page_size).await?;
+                        let next_cursor = if records.len() < page_length {
+                            ReportCursor::Done
+                        } else {
+                            ReportCursor::Refunds {
+                                offset: offset.saturating_add(page_size),
+                            }
+                        };
+                        (records, next_cursor)
+                    }
+                    ReportCursor::Done => return Ok(None),
+                };
+
+                if records.is_empty() {
+                    cursor = next_cursor;
+                    continue;
+                }
+
+                let chunk = records
+                    .iter()
+                    .map(ReconciliationRecord::to_csv_row)
+                    .collect::<RouterResult<String>>()?;
+                return Ok(Some((Bytes::from(chunk), (source, next_cursor))));
+            }
+        },
+    )
+    .boxed()
+}
+
+fn validate_time_range(
+    start_time: PrimitiveDateTime,
+    end_time: PrimitiveDateTime,
+) -> RouterResult<()> {
+    utils::when(start_time > end_time, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must not be later than `end_time`".to_string(),
+        })
+        .into_report()
+    })
+}
+
+fn get_report_file_name(start_time: PrimitiveDateTime, end_time: PrimitiveDateTime) -> String {
+    format!(
+        "reconciliation_report_{}_{}.csv",
+        start_time.date(),
+        end_time.date()
+    )
+}
+
+pub async fn retrieve_reconciliation_report(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: reports::ReconciliationReportRequest,
+) -> RouterResponse<()> {
+    let end_time = request.end_time.unwrap_or_else(date_time::now);
+    validate_time_range(request.start_time, end_time)?;
+
+    let source = StoreReconciliationRecordSource::new(
+        state.store.clone(),
+        &merchant_account,
+        request.start_time,
+        end_time,
+    );
+
+    Ok(ApplicationResponse::FileStream(services::api::FileStream {
+        content_type: mime::TEXT_CSV,
+        file_name: get_report_file_name(request.start_time, end_time),
+        stream: reconciliation_report_stream(source, RECONCILIATION_REPORT_PAGE_SIZE),
+    }))
+}
+
+pub async fn schedule_reconciliation_report(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: reports::ReconciliationReportScheduleRequest,
+) -> RouterResponse<reports::ReconciliationReportScheduleResponse> {
+    validate_time_range(request.start_time, request.end_time)?;
+
+    let tracking_data = ReconciliationReportTrackingData {
+        merchant_id: merchant_account.merchant_id.clone(),
+        file_id: common_utils::generate_id(consts::ID_LENGTH, "file"),
+        start_time: request.start_time,
+        end_time: request.end_time,
+        daily: request.daily,
+    };
+    let file_id = tracking_data.file_id.clone();
+
+    add_reconciliation_report_task(&*state.store, tracking_data, date_time::now()).await?;
+
+    Ok(ApplicationResponse::Json(
+        reports::ReconciliationReportScheduleResponse { file_id },
+    ))
+}
+
+pub async fn add_reconciliation_report_task(
+    db: &dyn StorageInterface,
+    tracking_data: ReconciliationReportTrackingData,
+    schedule_time: PrimitiveDateTime,
+) -> RouterResult<()> {
+    let runner = "RECONCILIATION_REPORT_WORKFLOW";
+    let task = "GENERATE_RECONCILIATION_REPORT";
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        &tracking_data.file_id,
+        &tracking_data.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        task,
+        runner,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct reconciliation report process tracker task")?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert reconciliation report process tracker task")?;
+    Ok(())
+}
+
+/// Generate the report for the given tracking data and store it as a file of the merchant, so
+/// that it can be retrieved with the files API
+pub async fn generate_reconciliation_report_file(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    tracking_data: &ReconciliationReportTrackingData,
+) -> RouterResult<()> {
+    let source = StoreReconciliationRecordSource::new(
+        state.store.clone(),
+        merchant_account,
+        tracking_data.start_time,
+        tracking_data.end_time,
+    );
+    // The file storage backends only accept a complete file, the stream still keeps the number
+    // of records fetched from the database at a time bounded
+    let file_data = reconciliation_report_stream(source, RECONCILIATION_REPORT_PAGE_SIZE)
+        .try_fold(Vec::new(), |mut file_data, chunk| async move {
+            file_data.extend_from_slice(&chunk);
+            Ok(file_data)
+        })
+        .await?;
+    let file_size = i32::try_from(file_data.len())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Reconciliation report is too large to be stored")?;
+
+    #[cfg(feature = "s3")]
+    let file_key = format!("{}/{}", merchant_account.merchant_id, tracking_data.file_id);
+    #[cfg(not(feature = "s3"))]
+    let file_key = format!("{}_{}", merchant_account.merchant_id, tracking_data.file_id);
+
+    super::files::helpers::upload_file(
+        #[cfg(feature = "s3")]
+        state,
+        file_key.clone(),
+        file_data,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to upload reconciliation report")?;
+
+    let file_new = diesel_models::file::FileMetadataNew {
+        file_id: tracking_data.file_id.clone(),
+        merchant_id: merchant_account.merchant_id.clone(),
+        file_name: Some(get_report_file_name(
+            tracking_data.start_time,
+            tracking_data.end_time,
+        )),
+        file_size,
+        file_type: RECONCILIATION_REPORT_FILE_TYPE.to_string(),
+        provider_file_id: Some(file_key),
+        file_upload_provider: Some(enums::FileUploadProvider::Router),
+        available: true,
+        connector_label: None,
+        profile_id: None,
+    };
+    state
+        .store
+        .insert_file_metadata(file_new)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert reconciliation report file metadata")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use futures::StreamExt;
+
+    use super::*;
+
+    struct InMemoryRecordSource {
+        payments: Vec<ReconciliationRecord>,
+        refunds: Vec<ReconciliationRecord>,
+        fetched_records: Arc<AtomicUsize>,
+    }
+
+    impl InMemoryRecordSource {
+        fn page(
+            &self,
+            records: &[ReconciliationRecord],
+            offset: u32,
+            limit: u32,
+        ) -> Vec<ReconciliationRecord> {
+            let page = records
+                .iter()
+                .skip(usize::try_from(offset).unwrap())
+                .take(usize::try_from(limit).unwrap())
+                .cloned()
+                .collect::<Vec<_>>();
+            self.fetched_records.fetch_add(page.len(), Ordering::SeqCst);
+            page
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ReconciliationRecordSource for InMemoryRecordSource {
+        async fn fetch_payments(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> RouterResult<Vec<ReconciliationRecord>> {
+            Ok(self.page(&self.payments, offset, limit))
+        }
+
+        async fn fetch_refunds(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> RouterResult<Vec<ReconciliationRecord>> {
+            Ok(self.page(&self.refunds, offset, limit))
+        }
+    }
+
+    fn record(
+        record_type: ReconciliationRecordType,
+        index: usize,
+        currency: enums::Currency,
+        amount: i64,
+    ) -> ReconciliationRecord {
+        let created_at = time::macros::datetime!(2023-10-05 10:11:12);
+        ReconciliationRecord {
+            record_type,
+            payment_id: format!("pay_{index}"),
+            refund_id: (record_type == ReconciliationRecordType::Refund)
+                .then(|| format!("ref_{index}")),
+            connector: Some("stripe".to_string()),
+            connector_transaction_id: Some(format!("ch_{index}")),
+            connector_refund_id: (record_type == ReconciliationRecordType::Refund)
+                .then(|| format!("re_{index}")),
+            status: "succeeded".to_string(),
+            currency: Some(currency),
+            amount,
+            payment_method: Some(enums::PaymentMethod::Card),
+            created_at,
+            modified_at: created_at,
+            fee_amount: None,
+            fee_currency: None,
+            net_amount: None,
+        }
+    }
+
+    fn seeded_source(payments: usize, refunds: usize) -> InMemoryRecordSource {
+        InMemoryRecordSource {
+            payments: (0..payments)
+                .map(|index| {
+                    record(
+                        ReconciliationRecordType::Payment,
+                        index,
+                        enums::Currency::USD,
+                        1050,
+                    )
+                })
+                .collect(),
+            refunds: (0..refunds)
+                .map(|index| {
+                    record(
+                        ReconciliationRecordType::Refund,
+                        index,
+                        enums::Currency::USD,
+                        500,
+                    )
+                })
+                .collect(),
+            fetched_records: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    async fn collect_report(stream: BoxStream<'static, RouterResult<Bytes>>) -> Vec<String> {
+        stream
+            .map(|chunk| String::from_utf8(chunk.unwrap().to_vec()).unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_report_row_counts() {
+        let chunks = collect_report(reconciliation_report_stream(seeded_source(25, 7), 10)).await;
+        // Header, three pages of payments and one page of refunds
+        assert_eq!(chunks.len(), 5);
+
+        let report = chunks.concat();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1 + 25 + 7);
+        assert_eq!(lines[0], RECONCILIATION_REPORT_COLUMNS.join(","));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("payment,"))
+                .count(),
+            25
+        );
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("refund,"))
+                .count(),
+            7
+        );
+        assert!(lines[1..]
+            .iter()
+            .all(|line| line.split(',').count() == RECONCILIATION_REPORT_COLUMNS.len()));
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_report_without_records() {
+        let chunks = collect_report(reconciliation_report_stream(seeded_source(0, 0), 10)).await;
+        assert_eq!(
+            chunks,
+            vec![format!("{}\n", RECONCILIATION_REPORT_COLUMNS.join(","))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_report_amount_formatting() {
+        let source = InMemoryRecordSource {
+            payments: vec![
+                record(
+                    ReconciliationRecordType::Payment,
+                    0,
+                    enums::Currency::JPY,
+                    1000,
+                ),
+                record(
+                    ReconciliationRecordType::Payment,
+                    1,
+                    enums::Currency::USD,
+                    1050,
+                ),
+                record(
+                    ReconciliationRecordType::Payment,
+                    2,
+                    enums::Currency::KWD,
+                    1500,
+                ),
+            ],
+            refunds: vec![record(
+                ReconciliationRecordType::Refund,
+                3,
+                enums::Currency::USD,
+                5,
+            )],
+            fetched_records: Arc::new(AtomicUsize::new(0)),
+        };
+        let report = collect_report(reconciliation_report_stream(source, 10))
+            .await
+            .concat();
+        let amounts = report
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(8).unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec!["1000", "10.50", "1.500", "0.05"]);
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_report_connector_fee() {
+        let source = InMemoryRecordSource {
+            payments: vec![
+                ReconciliationRecord {
+                    fee_amount: Some(280),
+                    fee_currency: Some(enums::Currency::CAD),
+                    net_amount: None,
+                    ..record(
+                        ReconciliationRecordType::Payment,
+                        0,
+                        enums::Currency::USD,
+                        10000,
+                    )
+                },
+                ReconciliationRecord {
+                    fee_amount: Some(59),
+                    fee_currency: Some(enums::Currency::USD),
+                    net_amount: Some(941),
+                    ..record(
+                        ReconciliationRecordType::Payment,
+                        1,
+                        enums::Currency::USD,
+                        1000,
+                    )
+                },
+                record(
+                    ReconciliationRecordType::Payment,
+                    2,
+                    enums::Currency::USD,
+                    1000,
+                ),
+            ],
+            refunds: vec![],
+            fetched_records: Arc::new(AtomicUsize::new(0)),
+        };
+        let report = collect_report(reconciliation_report_stream(source, 10))
+            .await
+            .concat();
+        let fees = report
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let fields = line.split(',').collect::<Vec<_>>();
+                (fields[7], fields[8], fields[9], fields[13], fields[14])
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fees,
+            vec![
+                ("USD", "100.00", "2.80", "CAD", ""),
+                ("USD", "10.00", "0.59", "USD", "9.41"),
+                ("USD", "10.00", "", "", ""),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_report_does_not_buffer_records() {
+        let source = seeded_source(25, 7);
+        let fetched_records = source.fetched_records.clone();
+        let mut stream = reconciliation_report_stream(source, 10);
+
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(fetched_records.load(Ordering::SeqCst), 0);
+
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(fetched_records.load(Ordering::SeqCst), 10);
+
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(fetched_records.load(Ordering::SeqCst), 20);
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(
+            to_csv_line(["plain", "with,comma", "with \"quote\""].into_iter()),
+            "plain,\"with,comma\",\"with \"\"quote\"\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,542 @@
+//! Decisions taken in routing the payment attempts to the connectors, so that the merchants can see
+//! why a payment was slow or was processed by a connector other than the one of their routing: the
+//! connector chosen by the routing rule, the connectors routed around because they were ineligible,
+//! unavailable or under maintenance, and the outcome and latency of each request sent to the
+//! connector. The decisions are recorded when they are taken and are never updated.
+//!
+//! The decisions of a payment are returned by retrieve with `expand=routing_decisions`, and the
+//! decisions of all the payments of a merchant are summarized for each day.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use api_models::payments::{
+    ConnectorRoutingSummary, RoutingDecision, RoutingDecisionsSummaryRequest,
+    RoutingDecisionsSummaryResponse,
+};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use time::{format_description::well_known::Iso8601, PrimitiveDateTime};
+
+use super::{
+    circuit_breaker,
+    errors::{self, RouterResponse, RouterResult},
+};
+use crate::{
+    db::StorageInterface,
+    routes::{metrics::request as metrics_request, AppState},
+    services::ApplicationResponse,
+    types::{self, api, domain, storage, storage::enums},
+};
+
+/// Records a decision of the attempt. A decision which cannot be recorded is only logged, it never
+/// fails the payment.
+#[instrument(skip(db))]
+pub async fn record_decision(
+    db: &dyn StorageInterface,
+    decision: storage::PaymentRoutingDecisionNew,
+) {
+    if let Err(error) = db.insert_payment_routing_decision(decision).await {
+        logger::error!(
+            ?error,
+            "Failed to record the routing decision of the payment attempt"
+        );
+    }
+}
+
+/// The attempt which a decision is recorded for
+#[derive(Clone, Copy, Debug)]
+pub struct DecisionSource<'a> {
+    pub merchant_id: &'a str,
+    pub payment_id: &'a str,
+    pub attempt_id: &'a str,
+}
+
+impl<'a> From<&'a storage::PaymentAttempt> for DecisionSource<'a> {
+    fn from(payment_attempt: &'a storage::PaymentAttempt) -> Self {
+        Self {
+            merchant_id: &payment_attempt.merchant_id,
+            payment_id: &payment_attempt.payment_id,
+            attempt_id: &payment_attempt.attempt_id,
+        }
+    }
+}
+
+impl<'a, F, Req, Res> From<&'a types::RouterData<F, Req, Res>> for DecisionSource<'a> {
+    fn from(router_data: &'a types::RouterData<F, Req, Res>) -> Self {
+        Self {
+            merchant_id: &router_data.merchant_id,
+            payment_id: &router_data.payment_id,
+            attempt_id: &router_data.attempt_id,
+        }
+    }
+}
+
+fn get_decision(
+    source: DecisionSource<'_>,
+    decision: enums::RoutingDecisionType,
+    connector: String,
+) -> storage::PaymentRoutingDecisionNew {
+    storage::PaymentRoutingDecisionNew {
+        merchant_id: source.merchant_id.to_string(),
+        payment_id: source.payment_id.to_string(),
+        attempt_id: source.attempt_id.to_string(),
+        decision,
+        connector,
+        routing_rule: None,
+        fallback_reason: None,
+        outcome: None,
+        latency_ms: None,
+        created_at: date_time::now(),
+    }
+}
+
+/// Records the connectors which the attempt was routed around, followed by the connector which it
+/// was routed to. Nothing is recorded for the attempts whose connector was not chosen by routing.
+pub async fn record_routing_decisions(
+    db: &dyn StorageInterface,
+    source: DecisionSource<'_>,
+    routing_data: &storage::RoutingData,
+) {
+    let (Some(connector), Some(routing_rule)) = (
+        routing_data.routed_through.as_ref(),
+        routing_data.routing_rule,
+    ) else {
+        return;
+    };
+
+    for skipped_connector in &routing_data.skipped_connectors {
+        record_decision(
+            db,
+            storage::PaymentRoutingDecisionNew {
+                fallback_reason: Some(skipped_connector.reason),
+                ..get_decision(
+                    source,
+                    enums::RoutingDecisionType::ConnectorSkipped,
+                    skipped_connector.connector.to_string(),
+                )
+            },
+        )
+        .await;
+    }
+    record_decision(
+        db,
+        storage::PaymentRoutingDecisionNew {
+            routing_rule: Some(routing_rule),
+            fallback_reason: routing_data
+                .skipped_connectors
+                .last()
+                .map(|skipped_connector| skipped_connector.reason),
+            ..get_decision(
+                source,
+                enums::RoutingDecisionType::ConnectorRouted,
+                connector.clone(),
+            )
+        },
+    )
+    .await;
+}
+
+/// Whether the requests of the flow are recorded as the attempts of the connector, which are the
+/// requests authorizing the payment
+pub fn is_routed_flow(flow: &str) -> bool {
+    flow == circuit_breaker::get_flow_name::<api::Authorize>()
+        || flow == circuit_breaker::get_flow_name::<api::CompleteAuthorize>()
+}
+
+pub fn get_attempt_outcome(
+    outcome: metrics_request::ConnectorCallOutcome,
+) -> enums::ConnectorAttemptOutcome {
+    match outcome {
+        metrics_request::ConnectorCallOutcome::Success => enums::ConnectorAttemptOutcome::Success,
+        metrics_request::ConnectorCallOutcome::BusinessDecline => {
+            enums::ConnectorAttemptOutcome::BusinessDecline
+        }
+        metrics_request::ConnectorCallOutcome::ConnectorError => {
+            enums::ConnectorAttemptOutcome::ConnectorError
+        }
+        metrics_request::ConnectorCallOutcome::Timeout => enums::ConnectorAttemptOutcome::Timeout,
+    }
+}
+
+/// Records a request of the attempt sent to the connector, or blocked before it was sent in which
+/// case it has no latency
+pub async fn record_connector_attempt<F, Req, Res>(
+    db: &dyn StorageInterface,
+    router_data: &types::RouterData<F, Req, Res>,
+    flow: &str,
+    outcome: enums::ConnectorAttemptOutcome,
+    latency: Option<std::time::Duration>,
+) {
+    if !is_routed_flow(flow) {
+        return;
+    }
+    record_decision(
+        db,
+        storage::PaymentRoutingDecisionNew {
+            outcome: Some(outcome),
+            latency_ms: latency.and_then(|latency| i64::try_from(latency.as_millis()).ok()),
+            ..get_decision(
+                DecisionSource::from(router_data),
+                enums::RoutingDecisionType::ConnectorAttempted,
+                router_data.connector.clone(),
+            )
+        },
+    )
+    .await;
+}
+
+/// Returns the decisions of the attempts of the payment, in the order they were taken
+pub async fn get_routing_decisions(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    payment_id: &str,
+) -> RouterResult<Vec<RoutingDecision>> {
+    let decisions = db
+        .find_payment_routing_decisions_by_merchant_id_payment_id(merchant_id, payment_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the routing decisions of the payment")?;
+
+    Ok(decisions
+        .into_iter()
+        .map(|decision| RoutingDecision {
+            decision: decision.decision,
+            attempt_id: decision.attempt_id,
+            connector: decision.connector,
+            routing_rule: decision.routing_rule,
+            fallback_reason: decision.fallback_reason,
+            outcome: decision.outcome,
+            latency_ms: decision.latency_ms,
+            created_at: decision.created_at,
+        })
+        .collect())
+}
+
+pub async fn add_routing_decisions_to_payments_response(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    response: ApplicationResponse<api::PaymentsResponse>,
+) -> RouterResult<ApplicationResponse<api::PaymentsResponse>> {
+    Ok(match response {
+        ApplicationResponse::Json(payments_response) => ApplicationResponse::Json(
+            with_routing_decisions(db, merchant_id, payments_response).await?,
+        ),
+        ApplicationResponse::JsonWithHeaders((payments_response, headers)) => {
+            ApplicationResponse::JsonWithHeaders((
+                with_routing_decisions(db, merchant_id, payments_response).await?,
+                headers,
+            ))
+        }
+        response => response,
+    })
+}
+
+async fn with_routing_decisions(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    mut payments_response: api::PaymentsResponse,
+) -> RouterResult<api::PaymentsResponse> {
+    let payment_id = payments_response.payment_id.clone().unwrap_or_default();
+    payments_response.routing_decisions =
+        Some(get_routing_decisions(db, merchant_id, &payment_id).await?);
+    Ok(payments_response)
+}
+
+/// Counts the decisions for each connector. An attempt is counted as a retry when an earlier
+/// attempt of its payment was sent to a connector on the same day.
+pub fn summarize_routing_decisions(
+    decisions: &[storage::PaymentRoutingDecision],
+) -> Vec<ConnectorRoutingSummary> {
+    let mut summaries: BTreeMap<&str, ConnectorRoutingSummary> = BTreeMap::new();
+    let mut first_attempts: HashMap<&str, &str> = HashMap::new();
+    let mut retried_attempts: HashSet<(&str, &str)> = HashSet::new();
+
+    for decision in decisions {
+        let summary = summaries
+            .entry(decision.connector.as_str())
+            .or_insert_with(|| ConnectorRoutingSummary {
+                connector: decision.connector.clone(),
+                ..Default::default()
+            });
+        match decision.decision {
+            enums::RoutingDecisionType::ConnectorSkipped => summary.skipped += 1,
+            enums::RoutingDecisionType::ConnectorRouted => {
+                summary.routed += 1;
+                if decision.fallback_reason.is_some() {
+                    summary.fallbacks += 1;
+                }
+            }
+            enums::RoutingDecisionType::ConnectorAttempted => {
+                summary.attempts += 1;
+                if decision
+                    .outcome
+                    .map_or(false, |outcome| outcome.is_failure())
+                {
+                    summary.failed_attempts += 1;
+                }
+                let first_attempt = *first_attempts
+                    .entry(decision.payment_id.as_str())
+                    .or_insert(decision.attempt_id.as_str());
+                if first_attempt != decision.attempt_id
+                    && retried_attempts
+                        .insert((decision.payment_id.as_str(), decision.attempt_id.as_str()))
+                {
+                    summary.retries += 1;
+                }
+            }
+        }
+    }
+
+    summaries.into_values().collect()
+}
+
+fn get_summary_range(
+    date: Option<&str>,
+) -> RouterResult<(time::Date, PrimitiveDateTime, PrimitiveDateTime)> {
+    let date = match date {
+        Some(date) => time::Date::parse(date, &Iso8601::DEFAULT)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`date` must be a date in the YYYY-MM-DD format".to_string(),
+            })?,
+        None => date_time::now().date(),
+    };
+    let next_day = date
+        .next_day()
+        .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`date` is out of range".to_string(),
+        })
+        .into_report()?;
+
+    Ok((date, date.midnight(), next_day.midnight()))
+}
+
+/// Summarizes the routing decisions of the payments of the merchant taken during a day
+#[instrument(skip(state))]
+pub async fn get_routing_decisions_summary(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: RoutingDecisionsSummaryRequest,
+) -> RouterResponse<RoutingDecisionsSummaryResponse> {
+    let (date, created_after, created_before) = get_summary_range(request.date.as_deref())?;
+    let decisions = state
+        .store
+        .find_payment_routing_decisions_by_merchant_id_created_at_range(
+            &merchant_account.merchant_id,
+            created_after,
+            created_before,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the routing decisions of the merchant")?;
+
+    Ok(ApplicationResponse::Json(RoutingDecisionsSummaryResponse {
+        date: date.to_string(),
+        connectors: summarize_routing_decisions(&decisions),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use api_models::enums::RoutableConnectors;
+
+    use super::*;
+    use crate::{db::MockDb, types::test_utils::get_authorize_router_data};
+
+    fn get_source(attempt_id: &str) -> DecisionSource<'_> {
+        DecisionSource {
+            merchant_id: "merchant_1",
+            payment_id: "pay_1",
+            attempt_id,
+        }
+    }
+
+    fn get_routing_data(
+        connector: &str,
+        skipped_connectors: Vec<circuit_breaker::SkippedConnector>,
+    ) -> storage::RoutingData {
+        storage::RoutingData {
+            routed_through: Some(connector.to_string()),
+            algorithm: None,
+            maintenance_fallback: None,
+            routing_rule: Some(enums::RoutingRule::MerchantRoutingAlgorithm),
+            skipped_connectors,
+        }
+    }
+
+    fn get_router_data(attempt_id: &str, connector: &str) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(None);
+        router_data.merchant_id = "merchant_1".to_string();
+        router_data.payment_id = "pay_1".to_string();
+        router_data.attempt_id = attempt_id.to_string();
+        router_data.connector = connector.to_string();
+        router_data
+    }
+
+    /// The first attempt is routed to stripe and times out, the circuit of stripe opens and the
+    /// retried attempt falls back to adyen, which authorizes the payment
+    #[tokio::test]
+    async fn test_decisions_of_a_payment_which_failed_and_fell_back_are_recorded_in_order() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let flow = circuit_breaker::get_flow_name::<api::Authorize>();
+
+        record_routing_decisions(
+            &db,
+            get_source("pay_1_1"),
+            &get_routing_data("stripe", vec![]),
+        )
+        .await;
+        record_connector_attempt(
+            &db,
+            &get_router_data("pay_1_1", "stripe"),
+            &flow,
+            enums::ConnectorAttemptOutcome::Timeout,
+            Some(std::time::Duration::from_millis(8000)),
+        )
+        .await;
+        record_routing_decisions(
+            &db,
+            get_source("pay_1_2"),
+            &get_routing_data(
+                "adyen",
+                vec![circuit_breaker::SkippedConnector {
+                    connector: RoutableConnectors::Stripe,
+                    reason: enums::RoutingFallbackReason::CircuitOpen,
+                }],
+            ),
+        )
+        .await;
+        record_connector_attempt(
+            &db,
+            &get_router_data("pay_1_2", "adyen"),
+            &flow,
+            enums::ConnectorAttemptOutcome::Success,
+            Some(std::time::Duration::from_millis(900)),
+        )
+        .await;
+        // The syncs of the payment are not attempts of the connector
+        record_connector_attempt(
+            &db,
+            &get_router_data("pay_1_2", "adyen"),
+            &circuit_breaker::get_flow_name::<api::PSync>(),
+            enums::ConnectorAttemptOutcome::Success,
+            Some(std::time::Duration::from_millis(100)),
+        )
+        .await;
+
+        let decisions = get_routing_decisions(&db, "merchant_1", "pay_1")
+            .await
+            .unwrap();
+        let trail: Vec<_> = decisions
+            .iter()
+            .map(|decision| {
+                (
+                    decision.decision,
+                    decision.attempt_id.as_str(),
+                    decision.connector.as_str(),
+                    decision.fallback_reason,
+                    decision.outcome,
+                    decision.latency_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            trail,
+            vec![
+                (
+                    enums::RoutingDecisionType::ConnectorRouted,
+                    "pay_1_1",
+                    "stripe",
+                    None,
+                    None,
+                    None
+                ),
+                (
+                    enums::RoutingDecisionType::ConnectorAttempted,
+                    "pay_1_1",
+                    "stripe",
+                    None,
+                    Some(enums::ConnectorAttemptOutcome::Timeout),
+                    Some(8000)
+                ),
+                (
+                    enums::RoutingDecisionType::ConnectorSkipped,
+                    "pay_1_2",
+                    "stripe",
+                    Some(enums::RoutingFallbackReason::CircuitOpen),
+                    None,
+                    None
+                ),
+                (
+                    enums::RoutingDecisionType::ConnectorRouted,
+                    "pay_1_2",
+                    "adyen",
+                    Some(enums::RoutingFallbackReason::CircuitOpen),
+                    None,
+                    None
+                ),
+                (
+                    enums::RoutingDecisionType::ConnectorAttempted,
+                    "pay_1_2",
+                    "adyen",
+                    None,
+                    Some(enums::ConnectorAttemptOutcome::Success),
+                    Some(900)
+                ),
+            ]
+        );
+        assert_eq!(
+            decisions[0].routing_rule,
+            Some(enums::RoutingRule::MerchantRoutingAlgorithm)
+        );
+
+        let (_, created_after, created_before) = get_summary_range(None).unwrap();
+        let summary = summarize_routing_decisions(
+            &db.find_payment_routing_decisions_by_merchant_id_created_at_range(
+                "merchant_1",
+                created_after,
+                created_before,
+            )
+            .await
+            .unwrap(),
+        );
+        assert_eq!(
+            summary,
+            vec![
+                ConnectorRoutingSummary {
+                    connector: "adyen".to_string(),
+                    routed: 1,
+                    fallbacks: 1,
+                    skipped: 0,
+                    attempts: 1,
+                    retries: 1,
+                    failed_attempts: 0,
+                },
+                ConnectorRoutingSummary {
+                    connector: "stripe".to_string(),
+                    routed: 1,
+                    fallbacks: 0,
+                    skipped: 1,
+                    attempts: 1,
+                    retries: 0,
+                    failed_attempts: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_date_is_validated() {
+        let (date, created_after, created_before) = get_summary_range(Some("2023-11-14")).unwrap();
+        assert_eq!(date.to_string(), "2023-11-14");
+        assert_eq!(created_after, time::macros::datetime!(2023-11-14 00:00));
+        assert_eq!(created_before, time::macros::datetime!(2023-11-15 00:00));
+
+        assert!(get_summary_range(Some("14/11/2023")).is_err());
+    }
+}
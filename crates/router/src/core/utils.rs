@@ -8,13 +8,17 @@ use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use uuid::Uuid;
 
-use super::payments::{helpers, PaymentAddress};
+use super::payments::{
+    connector_account_selection::{self, PaymentMethodDetails},
+    helpers, PaymentAddress,
+};
 #[cfg(feature = "payouts")]
 use super::payouts::PayoutData;
 #[cfg(feature = "payouts")]
 use crate::core::payments;
 use crate::{
     configs::settings,
+    connector::utils::{connector_reference, refund_reason},
     consts,
     core::errors::{self, RouterResult, StorageErrorExt},
     db::StorageInterface,
@@ -24,7 +28,7 @@ use crate::{
         storage::{self, enums},
         ErrorResponse,
     },
-    utils::{generate_id, generate_uuid, OptionExt, ValueExt},
+    utils::{self, generate_id, generate_uuid, OptionExt, ValueExt},
 };
 
 pub const IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_DISPUTE_FLOW: &str =
@@ -62,6 +66,7 @@ pub async fn get_mca_for_payout<'a>(
                 key_store,
                 profile_id,
                 connector_id,
+                None,
             )
             .await?;
             Ok(merchant_connector_account)
@@ -188,24 +193,47 @@ pub async fn construct_payout_router_data<'a, F>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
 
     Ok(router_data)
 }
 
+/// Merchant connector account of the business profile of the payment, through which the payment
+/// was processed
 #[instrument(skip_all)]
-#[allow(clippy::too_many_arguments)]
-pub async fn construct_refund_router_data<'a, F>(
-    state: &'a AppState,
+pub async fn get_merchant_connector_account_of_payment(
+    state: &AppState,
     connector_id: &str,
     merchant_account: &domain::MerchantAccount,
     key_store: &domain::MerchantKeyStore,
-    money: (i64, enums::Currency),
-    payment_intent: &'a storage::PaymentIntent,
+    payment_intent: &storage::PaymentIntent,
     payment_attempt: &storage::PaymentAttempt,
-    refund: &'a storage::Refund,
     creds_identifier: Option<String>,
-) -> RouterResult<types::RefundsRouterData<F>> {
+) -> RouterResult<helpers::MerchantConnectorAccountType> {
+    // The attempts which record the merchant connector account they were made or routed through
+    // are resolved to that account, the earlier attempts to the account of the connector in the
+    // business profile of the payment
+    let merchant_connector_id = payment_attempt.merchant_connector_id.clone().or_else(|| {
+        connector_account_selection::get_routed_merchant_connector_id(payment_attempt, connector_id)
+    });
+    if let (Some(merchant_connector_id), None) =
+        (merchant_connector_id.as_ref(), creds_identifier.as_ref())
+    {
+        return get_merchant_connector_account_of_attempt(
+            state,
+            connector_id,
+            merchant_account,
+            key_store,
+            merchant_connector_id,
+        )
+        .await;
+    }
+
     let profile_id = get_profile_id_from_business_details(
         payment_intent.business_country,
         payment_intent.business_label.as_ref(),
@@ -218,13 +246,81 @@ pub async fn construct_refund_router_data<'a, F>(
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("profile_id is not set in payment_intent")?;
 
-    let merchant_connector_account = helpers::get_merchant_connector_account(
+    helpers::get_merchant_connector_account(
         state,
         merchant_account.merchant_id.as_str(),
         creds_identifier,
         key_store,
         &profile_id,
         connector_id,
+        Some(&PaymentMethodDetails::from(payment_attempt)),
+    )
+    .await
+}
+
+/// Fetches the merchant connector account recorded on the payment attempt, which must be an account
+/// of the connector of the attempt
+pub async fn get_merchant_connector_account_of_attempt(
+    state: &AppState,
+    connector_id: &str,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    merchant_connector_id: &str,
+) -> RouterResult<helpers::MerchantConnectorAccountType> {
+    let merchant_connector_account = state
+        .store
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            &merchant_account.merchant_id,
+            merchant_connector_id,
+            key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.to_string(),
+        })
+        .attach_printable("Merchant connector account of the payment attempt not found")?;
+
+    utils::when(
+        merchant_connector_account.connector_name != connector_id,
+        || {
+            Err(report!(
+            errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                id: merchant_connector_id.to_string(),
+            }
+        ))
+        .attach_printable(format!(
+            "Merchant connector account of the payment attempt belongs to {} instead of {connector_id}",
+            merchant_connector_account.connector_name
+        ))
+        },
+    )?;
+
+    Ok(helpers::MerchantConnectorAccountType::DbVal(
+        merchant_connector_account,
+    ))
+}
+
+#[instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+pub async fn construct_refund_router_data<'a, F>(
+    state: &'a AppState,
+    connector_id: &str,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    money: (i64, enums::Currency),
+    payment_intent: &'a storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    refund: &'a storage::Refund,
+    creds_identifier: Option<String>,
+) -> RouterResult<types::RefundsRouterData<F>> {
+    let merchant_connector_account = get_merchant_connector_account_of_payment(
+        state,
+        connector_id,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        creds_identifier,
     )
     .await?;
 
@@ -249,6 +345,18 @@ pub async fn construct_refund_router_data<'a, F>(
     ));
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
 
+    let split_refund =
+        helpers::get_split_payment_details_of_intent(payment_intent)?.map(|split_payment| {
+            types::SplitRefundData {
+                platform_fee_refund_amount: helpers::get_platform_fee_refund_amount(
+                    &split_payment,
+                    payment_amount,
+                    refund.refund_amount,
+                ),
+                fee_refund_behaviour: split_payment.fee_refund_behaviour,
+            }
+        });
+
     let supported_connector = &state
         .conf
         .multiple_api_version_supported_connectors
@@ -298,13 +406,19 @@ pub async fn construct_refund_router_data<'a, F>(
             payment_amount,
             webhook_url,
             connector_metadata: payment_attempt.connector_metadata.clone(),
-            reason: refund.refund_reason.clone(),
+            reason: refund_reason::map_refund_reason(
+                refund_reason::get_refund_reason_support(connector_enum),
+                refund.refund_reason_code,
+                refund.refund_reason.as_deref(),
+            ),
             connector_refund_id: refund.connector_refund_id.clone(),
+            split_refund,
         },
 
         response: Ok(types::RefundsResponseData {
             connector_refund_id: refund.connector_refund_id.clone().unwrap_or_default(),
             refund_status: refund.refund_status,
+            acquirer_reference_number: None,
         }),
         access_token: None,
         session_token: None,
@@ -316,6 +430,8 @@ pub async fn construct_refund_router_data<'a, F>(
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            connector_id,
+            payment_intent,
             payment_attempt,
         ),
         #[cfg(feature = "payouts")]
@@ -327,6 +443,11 @@ pub async fn construct_refund_router_data<'a, F>(
         connector_api_version,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
 
     Ok(router_data)
@@ -481,25 +602,14 @@ pub async fn construct_accept_dispute_router_data<'a>(
     key_store: &domain::MerchantKeyStore,
     dispute: &storage::Dispute,
 ) -> RouterResult<types::AcceptDisputeRouterData> {
-    let profile_id = get_profile_id_from_business_details(
-        payment_intent.business_country,
-        payment_intent.business_label.as_ref(),
-        merchant_account,
-        payment_intent.profile_id.as_ref(),
-        &*state.store,
-        false,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("profile_id is not set in payment_intent")?;
-
-    let merchant_connector_account = helpers::get_merchant_connector_account(
+    let merchant_connector_account = get_merchant_connector_account_of_payment(
         state,
-        merchant_account.merchant_id.as_str(),
-        None,
-        key_store,
-        &profile_id,
         &dispute.connector,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        None,
     )
     .await?;
 
@@ -543,6 +653,8 @@ pub async fn construct_accept_dispute_router_data<'a>(
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            &dispute.connector,
+            payment_intent,
             payment_attempt,
         ),
         #[cfg(feature = "payouts")]
@@ -554,6 +666,11 @@ pub async fn construct_accept_dispute_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -569,25 +686,14 @@ pub async fn construct_submit_evidence_router_data<'a>(
     submit_evidence_request_data: types::SubmitEvidenceRequestData,
 ) -> RouterResult<types::SubmitEvidenceRouterData> {
     let connector_id = &dispute.connector;
-    let profile_id = get_profile_id_from_business_details(
-        payment_intent.business_country,
-        payment_intent.business_label.as_ref(),
-        merchant_account,
-        payment_intent.profile_id.as_ref(),
-        &*state.store,
-        false,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("profile_id is not set in payment_intent")?;
-
-    let merchant_connector_account = helpers::get_merchant_connector_account(
+    let merchant_connector_account = get_merchant_connector_account_of_payment(
         state,
-        merchant_account.merchant_id.as_str(),
-        None,
-        key_store,
-        &profile_id,
         connector_id,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        None,
     )
     .await?;
 
@@ -629,6 +735,8 @@ pub async fn construct_submit_evidence_router_data<'a>(
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            connector_id,
+            payment_intent,
             payment_attempt,
         ),
         #[cfg(feature = "payouts")]
@@ -639,6 +747,11 @@ pub async fn construct_submit_evidence_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -655,25 +768,14 @@ pub async fn construct_upload_file_router_data<'a>(
     connector_id: &str,
     file_key: String,
 ) -> RouterResult<types::UploadFileRouterData> {
-    let profile_id = get_profile_id_from_business_details(
-        payment_intent.business_country,
-        payment_intent.business_label.as_ref(),
-        merchant_account,
-        payment_intent.profile_id.as_ref(),
-        &*state.store,
-        false,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("profile_id is not set in payment_intent")?;
-
-    let merchant_connector_account = helpers::get_merchant_connector_account(
+    let merchant_connector_account = get_merchant_connector_account_of_payment(
         state,
-        merchant_account.merchant_id.as_str(),
-        None,
-        key_store,
-        &profile_id,
         connector_id,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        None,
     )
     .await?;
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
@@ -719,6 +821,8 @@ pub async fn construct_upload_file_router_data<'a>(
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            connector_id,
+            payment_intent,
             payment_attempt,
         ),
         #[cfg(feature = "payouts")]
@@ -729,6 +833,11 @@ pub async fn construct_upload_file_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -744,25 +853,14 @@ pub async fn construct_defend_dispute_router_data<'a>(
 ) -> RouterResult<types::DefendDisputeRouterData> {
     let _db = &*state.store;
     let connector_id = &dispute.connector;
-    let profile_id = get_profile_id_from_business_details(
-        payment_intent.business_country,
-        payment_intent.business_label.as_ref(),
-        merchant_account,
-        payment_intent.profile_id.as_ref(),
-        &*state.store,
-        false,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("profile_id is not set in payment_intent")?;
-
-    let merchant_connector_account = helpers::get_merchant_connector_account(
+    let merchant_connector_account = get_merchant_connector_account_of_payment(
         state,
-        merchant_account.merchant_id.as_str(),
-        None,
-        key_store,
-        &profile_id,
         connector_id,
+        merchant_account,
+        key_store,
+        payment_intent,
+        payment_attempt,
+        None,
     )
     .await?;
 
@@ -807,6 +905,8 @@ pub async fn construct_defend_dispute_router_data<'a>(
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
+            connector_id,
+            payment_intent,
             payment_attempt,
         ),
         #[cfg(feature = "payouts")]
@@ -817,6 +917,11 @@ pub async fn construct_defend_dispute_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -846,6 +951,7 @@ pub async fn construct_retrieve_file_router_data<'a>(
         key_store,
         profile_id,
         connector_id,
+        None,
     )
     .await?;
 
@@ -898,6 +1004,11 @@ pub async fn construct_retrieve_file_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -912,11 +1023,38 @@ pub fn is_merchant_enabled_for_payment_id_as_connector_request_id(
     config_map.contains(merchant_id)
 }
 
+/// Reference of the attempt sent to the connector. The reference is rendered from the connector
+/// request reference format of the business profile, if one was configured when the payment was
+/// created, falling back to the payment id or the attempt id
 pub fn get_connector_request_reference_id(
     conf: &settings::Settings,
     merchant_id: &str,
+    connector_name: &str,
+    payment_intent: &data_models::payments::payment_intent::PaymentIntent,
     payment_attempt: &data_models::payments::payment_attempt::PaymentAttempt,
 ) -> String {
+    let formatted_reference = payment_intent
+        .connector_request_reference_format
+        .as_deref()
+        .and_then(|format| {
+            let details = connector_reference::ReferenceDetails {
+                payment_id: &payment_attempt.payment_id,
+                merchant_order_reference_id: payment_intent.merchant_order_reference_id.as_deref(),
+                attempt_ordinal: utils::get_payment_attempt_ordinal(
+                    &payment_attempt.payment_id,
+                    &payment_attempt.attempt_id,
+                )
+                .unwrap_or(1),
+            };
+            let max_length = api_models::enums::Connector::from_str(connector_name)
+                .ok()
+                .and_then(connector_reference::get_max_reference_length);
+            connector_reference::render_reference(format, details, max_length)
+        });
+    if let Some(reference) = formatted_reference {
+        return reference;
+    }
+
     let is_config_enabled_for_merchant =
         is_merchant_enabled_for_payment_id_as_connector_request_id(conf, merchant_id);
     // Send payment_id if config is enabled for a merchant, else send attempt_id
@@ -1052,3 +1190,9 @@ pub fn get_flow_name<F>() -> RouterResult<String> {
         .attach_printable("Flow stringify failed")?
         .to_string())
 }
+
+/// Mode of the API keys and the payments for which none is given, only the production deployments
+/// default to live so that a missing mode is never treated as live elsewhere
+pub fn get_default_livemode() -> bool {
+    matches!(router_env::env::which(), router_env::env::Env::Production)
+}
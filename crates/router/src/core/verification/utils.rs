@@ -58,6 +58,8 @@ pub async fn check_existence_and_add_domain_to_db(
         frm_configs: None,
         connector_webhook_details: None,
         applepay_verified_domains: Some(already_verified_domains.clone()),
+        disabled_flows: None,
+        previous_credentials: None,
     };
     state
         .store
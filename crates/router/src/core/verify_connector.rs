@@ -0,0 +1,683 @@
+pub mod test_data;
+
+use std::{marker::PhantomData, str::FromStr, time::Instant};
+
+use async_trait::async_trait;
+use common_utils::{date_time, ext_traits::Encode, generate_id};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface};
+use router_env::{instrument, tracing};
+
+use self::test_data::{ConnectorTestData, TestFlow};
+use crate::{
+    consts,
+    core::{
+        errors::{self, ConnectorErrorExt, RouterResponse, RouterResult, StorageErrorExt},
+        payments::{self, access_token, helpers, PaymentAddress},
+    },
+    logger,
+    routes::AppState,
+    services,
+    types::{
+        self,
+        api::{self, admin, enums as api_enums},
+        domain, storage,
+    },
+    utils::{self, ValueExt},
+};
+
+/// Key under which the result of the test payment is stored in the merchant connector metadata
+pub const CONNECTOR_VERIFICATION_METADATA_KEY: &str = "connector_verification";
+
+/// Runs the steps of the test payment against the connector
+#[async_trait]
+pub trait ConnectorTestExecutor {
+    async fn authorize(
+        &self,
+        router_data: types::PaymentsAuthorizeRouterData,
+    ) -> RouterResult<types::PaymentsAuthorizeRouterData>;
+
+    async fn void(
+        &self,
+        router_data: types::PaymentsCancelRouterData,
+    ) -> RouterResult<types::PaymentsCancelRouterData>;
+}
+
+/// Runs the steps of the test payment using the connector integration
+pub struct ConnectorIntegrationExecutor<'a> {
+    pub state: &'a AppState,
+    pub merchant_account: &'a domain::MerchantAccount,
+    pub connector: api::ConnectorData,
+}
+
+#[async_trait]
+impl<'a> ConnectorTestExecutor for ConnectorIntegrationExecutor<'a> {
+    async fn authorize(
+        &self,
+        mut router_data: types::PaymentsAuthorizeRouterData,
+    ) -> RouterResult<types::PaymentsAuthorizeRouterData> {
+        let add_access_token_result = access_token::add_access_token(
+            self.state,
+            &self.connector,
+            self.merchant_account,
+            &router_data,
+        )
+        .await?;
+
+        let should_continue = access_token::update_router_data_with_access_token_result(
+            &add_access_token_result,
+            &mut router_data,
+            &payments::CallConnectorAction::Trigger,
+        );
+
+        if !should_continue {
+            return Ok(router_data);
+        }
+
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        > = self.connector.connector.get_connector_integration();
+
+        services::execute_connector_processing_step(
+            self.state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+        .to_payment_failed_response()
+    }
+
+    async fn void(
+        &self,
+        router_data: types::PaymentsCancelRouterData,
+    ) -> RouterResult<types::PaymentsCancelRouterData> {
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::Void,
+            types::PaymentsCancelData,
+            types::PaymentsResponseData,
+        > = self.connector.connector.get_connector_integration();
+
+        services::execute_connector_processing_step(
+            self.state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+        .to_payment_failed_response()
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn verify_merchant_connector(
+    state: AppState,
+    merchant_id: String,
+    merchant_connector_id: String,
+    req: admin::MerchantConnectorVerifyRequest,
+) -> RouterResponse<admin::MerchantConnectorVerifyResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mca = db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            &merchant_id,
+            &merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.clone(),
+        })?;
+
+    utils::when(!req.allow_live && mca.test_mode != Some(true), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Test payments can only be run against connectors in test mode, set `allow_live` to run it with live credentials".to_string(),
+        })
+        .into_report()
+    })?;
+
+    let connector_name = api_enums::Connector::from_str(&mca.connector_name)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "connector_name",
+        })
+        .attach_printable_lazy(|| {
+            format!("unable to parse connector name {:?}", mca.connector_name)
+        })?;
+
+    let test_data = test_data::get_connector_test_data(connector_name)
+        .ok_or(errors::ApiErrorResponse::NotSupported {
+            message: format!("Test payments are not supported for {connector_name}"),
+        })
+        .into_report()?;
+
+    let connector_auth_type: types::ConnectorAuthType = mca
+        .connector_account_details
+        .get_inner()
+        .peek()
+        .clone()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let router_data = types::RouterData {
+        connector_proxy: services::connector_proxy::get_merchant_connector_proxy_or_default(
+            mca.connector_account_details.get_inner().peek(),
+        ),
+        ..construct_test_payment_router_data(
+            &merchant_id,
+            &mca.connector_name,
+            connector_auth_type,
+            mca.metadata.clone(),
+            mca.test_mode,
+            &test_data,
+            &req,
+        )?
+    };
+
+    let executor = ConnectorIntegrationExecutor {
+        state: &state,
+        merchant_account: &merchant_account,
+        connector: api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            &mca.connector_name,
+            api::GetToken::Connector,
+        )?,
+    };
+
+    let (authorization, void) = run_test_payment(&executor, router_data, test_data.flow).await?;
+
+    let response = admin::MerchantConnectorVerifyResponse {
+        merchant_connector_id: merchant_connector_id.clone(),
+        connector_name: mca.connector_name.clone(),
+        test_mode: mca.test_mode,
+        verified: authorization.success && void.as_ref().map_or(true, |void| void.success),
+        authorization,
+        void,
+        verified_at: date_time::now(),
+    };
+
+    if req.store_result_in_metadata {
+        let metadata = add_verification_result_to_metadata(
+            mca.metadata.clone().map(ExposeInterface::expose),
+            &response,
+        )?;
+
+        let mca_update = storage::MerchantConnectorAccountUpdate::Update {
+            merchant_id: None,
+            connector_type: None,
+            connector_name: None,
+            connector_account_details: None,
+            test_mode: None,
+            disabled: None,
+            merchant_connector_id: None,
+            payment_methods_enabled: None,
+            metadata: Some(masking::Secret::new(metadata)),
+            frm_configs: None,
+            connector_webhook_details: None,
+            applepay_verified_domains: None,
+            disabled_flows: None,
+            previous_credentials: None,
+        };
+
+        db.update_merchant_connector_account(mca, mca_update.into(), &key_store)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}"
+                )
+            })?;
+    }
+
+    Ok(services::ApplicationResponse::Json(response))
+}
+
+pub fn construct_test_payment_router_data(
+    merchant_id: &str,
+    connector_name: &str,
+    connector_auth_type: types::ConnectorAuthType,
+    connector_meta_data: Option<masking::Secret<serde_json::Value>>,
+    test_mode: Option<bool>,
+    test_data: &ConnectorTestData,
+    req: &admin::MerchantConnectorVerifyRequest,
+) -> RouterResult<types::PaymentsAuthorizeRouterData> {
+    let payment_id = generate_id(consts::ID_LENGTH, "verify");
+    let attempt_id = format!("{payment_id}_1");
+    let capture_method = match test_data.flow {
+        TestFlow::AuthorizeAndVoid => api_enums::CaptureMethod::Manual,
+        TestFlow::AuthorizeOnly => api_enums::CaptureMethod::Automatic,
+    };
+
+    Ok(types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_id.to_string(),
+        customer_id: None,
+        connector_customer: None,
+        connector: connector_name.to_string(),
+        payment_id: payment_id.clone(),
+        attempt_id,
+        status: api_enums::AttemptStatus::Started,
+        payment_method: api_enums::PaymentMethod::Card,
+        connector_auth_type,
+        description: Some("Connector verification test payment".to_string()),
+        return_url: None,
+        address: PaymentAddress::default(),
+        auth_type: req.authentication_type.unwrap_or_default(),
+        connector_meta_data,
+        amount_captured: None,
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        connector_api_version: None,
+        request: types::PaymentsAuthorizeData {
+            payment_method_data: test_data.card.get_payment_method_data()?,
+            amount: test_data.amount,
+            email: None,
+            currency: req.currency.unwrap_or(test_data.currency),
+            confirm: true,
+            statement_descriptor_suffix: None,
+            statement_descriptor: None,
+            capture_method: Some(capture_method),
+            router_return_url: None,
+            webhook_url: None,
+            complete_authorize_url: None,
+            setup_future_usage: None,
+            mandate_id: None,
+            off_session: None,
+            setup_mandate_details: None,
+            browser_info: None,
+            order_details: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            session_token: None,
+            enrolled_for_3ds: req.authentication_type
+                == Some(api_enums::AuthenticationType::ThreeDs),
+            related_transaction_id: None,
+            payment_experience: None,
+            payment_method_type: Some(api_enums::PaymentMethodType::Credit),
+            customer_id: None,
+            external_authentication_data: None,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
+        },
+        response: Err(types::ErrorResponse::default()),
+        payment_method_id: None,
+        connector_request_reference_id: payment_id,
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode,
+        connector_http_status_code: None,
+        apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: None,
+        authorized_amount: None,
+    })
+}
+
+/// Authorizes the test card and voids the authorization, if supported by the connector
+pub async fn run_test_payment(
+    executor: &(impl ConnectorTestExecutor + Sync),
+    router_data: types::PaymentsAuthorizeRouterData,
+    flow: TestFlow,
+) -> RouterResult<(
+    admin::ConnectorTestStepResult,
+    Option<admin::ConnectorTestStepResult>,
+)> {
+    let start_instant = Instant::now();
+    let authorize_router_data = executor.authorize(router_data).await?;
+    let authorization = get_test_step_result(
+        &authorize_router_data,
+        start_instant,
+        &[
+            api_enums::AttemptStatus::Authorized,
+            api_enums::AttemptStatus::Charged,
+            api_enums::AttemptStatus::AuthenticationPending,
+        ],
+        api_enums::AttemptStatus::AuthorizationFailed,
+    );
+
+    logger::info!(connector_verification_authorization=?authorization);
+
+    // Any authorization left open by the test payment is voided, whether or not it passed the
+    // check, so that no funds stay held on the test card
+    let void = match (flow, &authorization.connector_transaction_id) {
+        (TestFlow::AuthorizeAndVoid, Some(connector_transaction_id))
+            if matches!(
+                authorize_router_data.status,
+                api_enums::AttemptStatus::Authorized
+                    | api_enums::AttemptStatus::Authorizing
+                    | api_enums::AttemptStatus::AuthenticationPending
+                    | api_enums::AttemptStatus::Pending
+            ) =>
+        {
+            let void_request = types::PaymentsCancelData {
+                amount: Some(authorize_router_data.request.amount),
+                currency: Some(authorize_router_data.request.currency),
+                connector_transaction_id: connector_transaction_id.clone(),
+                cancellation_reason: Some("Connector verification test payment".to_string()),
+                connector_meta: match &authorize_router_data.response {
+                    Ok(types::PaymentsResponseData::TransactionResponse {
+                        connector_metadata,
+                        ..
+                    }) => connector_metadata.clone(),
+                    _ => None,
+                },
+                webhook_url: authorize_router_data.request.webhook_url.clone(),
+            };
+            let void_router_data = helpers::router_data_type_conversion::<_, api::Void, _, _, _, _>(
+                authorize_router_data,
+                void_request,
+                Err(types::ErrorResponse::default()),
+            );
+
+            let start_instant = Instant::now();
+            let void_router_data = executor.void(void_router_data).await?;
+            let void = get_test_step_result(
+                &void_router_data,
+                start_instant,
+                &[api_enums::AttemptStatus::Voided],
+                api_enums::AttemptStatus::VoidFailed,
+            );
+
+            logger::info!(connector_verification_void=?void);
+            Some(void)
+        }
+        _ => None,
+    };
+
+    Ok((authorization, void))
+}
+
+fn get_test_step_result<F, Req>(
+    router_data: &types::RouterData<F, Req, types::PaymentsResponseData>,
+    start_instant: Instant,
+    success_statuses: &[api_enums::AttemptStatus],
+    failure_status: api_enums::AttemptStatus,
+) -> admin::ConnectorTestStepResult {
+    let latency_ms = u64::try_from(start_instant.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    match &router_data.response {
+        Ok(response) => admin::ConnectorTestStepResult {
+            success: success_statuses.contains(&router_data.status),
+            status: router_data.status,
+            latency_ms,
+            connector_http_status_code: router_data.connector_http_status_code,
+            connector_transaction_id: match response {
+                types::PaymentsResponseData::TransactionResponse { resource_id, .. } => {
+                    resource_id.get_connector_transaction_id().ok()
+                }
+                _ => None,
+            },
+            error_code: None,
+            error_message: None,
+        },
+        Err(error) => admin::ConnectorTestStepResult {
+            success: false,
+            status: failure_status,
+            latency_ms,
+            connector_http_status_code: Some(error.status_code),
+            connector_transaction_id: None,
+            error_code: Some(error.code.clone()),
+            error_message: Some(error.reason.clone().unwrap_or(error.message.clone())),
+        },
+    }
+}
+
+fn add_verification_result_to_metadata(
+    metadata: Option<serde_json::Value>,
+    response: &admin::MerchantConnectorVerifyResponse,
+) -> RouterResult<serde_json::Value> {
+    let verification_result =
+        Encode::<admin::MerchantConnectorVerifyResponse>::encode_to_value(response)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize connector verification result")?;
+
+    match metadata {
+        Some(serde_json::Value::Object(mut metadata)) => {
+            metadata.insert(
+                CONNECTOR_VERIFICATION_METADATA_KEY.to_string(),
+                verification_result,
+            );
+            Ok(serde_json::Value::Object(metadata))
+        }
+        None | Some(serde_json::Value::Null) => Ok(serde_json::json!({
+            CONNECTOR_VERIFICATION_METADATA_KEY: verification_result
+        })),
+        Some(_) => Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The metadata of the merchant connector is not an object, the test payment result cannot be stored in it".to_string(),
+        })
+        .into_report(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+
+    use super::*;
+
+    struct MockConnector {
+        valid_credentials: bool,
+        requires_authentication: bool,
+    }
+
+    #[async_trait]
+    impl ConnectorTestExecutor for MockConnector {
+        async fn authorize(
+            &self,
+            mut router_data: types::PaymentsAuthorizeRouterData,
+        ) -> RouterResult<types::PaymentsAuthorizeRouterData> {
+            if self.valid_credentials {
+                router_data.status = if self.requires_authentication {
+                    api_enums::AttemptStatus::AuthenticationPending
+                } else {
+                    api_enums::AttemptStatus::Authorized
+                };
+                router_data.connector_http_status_code = Some(200);
+                router_data.response = Ok(types::PaymentsResponseData::TransactionResponse {
+                    resource_id: types::ResponseId::ConnectorTransactionId("txn_123".to_string()),
+                    redirection_data: None,
+                    mandate_reference: None,
+                    connector_metadata: None,
+                    network_txn_id: None,
+                    connector_response_reference_id: None,
+                    card_verification_results: None,
+                    connector_fee: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
+                });
+            } else {
+                router_data.response = Err(types::ErrorResponse {
+                    code: "invalid_api_key".to_string(),
+                    message: "Invalid API Key provided".to_string(),
+                    reason: None,
+                    status_code: 401,
+                });
+            }
+            Ok(router_data)
+        }
+
+        async fn void(
+            &self,
+            mut router_data: types::PaymentsCancelRouterData,
+        ) -> RouterResult<types::PaymentsCancelRouterData> {
+            assert_eq!(router_data.request.connector_transaction_id, "txn_123");
+            router_data.status = api_enums::AttemptStatus::Voided;
+            router_data.response = Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId("txn_123".to_string()),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                card_verification_results: None,
+                connector_fee: None,
+                auth_code: None,
+                acquirer_reference_number: None,
+            });
+            Ok(router_data)
+        }
+    }
+
+    fn get_test_router_data() -> types::PaymentsAuthorizeRouterData {
+        let test_data = test_data::get_connector_test_data(api_enums::Connector::Stripe).unwrap();
+        construct_test_payment_router_data(
+            "merchant_123",
+            "stripe",
+            types::ConnectorAuthType::HeaderKey {
+                api_key: Secret::new("sk_test_123".to_string()),
+            },
+            None,
+            Some(true),
+            &test_data,
+            &admin::MerchantConnectorVerifyRequest::default(),
+        )
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_with_valid_credentials() {
+        let connector = MockConnector {
+            valid_credentials: true,
+            requires_authentication: false,
+        };
+
+        let (authorization, void) = run_test_payment(
+            &connector,
+            get_test_router_data(),
+            TestFlow::AuthorizeAndVoid,
+        )
+        .await
+        .unwrap();
+
+        assert!(authorization.success);
+        assert_eq!(authorization.status, api_enums::AttemptStatus::Authorized);
+        assert_eq!(
+            authorization.connector_transaction_id.as_deref(),
+            Some("txn_123")
+        );
+
+        let void = void.unwrap();
+        assert!(void.success);
+        assert_eq!(void.status, api_enums::AttemptStatus::Voided);
+    }
+
+    #[actix_rt::test]
+    async fn test_pending_authorization_is_voided() {
+        let connector = MockConnector {
+            valid_credentials: true,
+            requires_authentication: true,
+        };
+
+        let (authorization, void) = run_test_payment(
+            &connector,
+            get_test_router_data(),
+            TestFlow::AuthorizeAndVoid,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            authorization.status,
+            api_enums::AttemptStatus::AuthenticationPending
+        );
+        let void = void.unwrap();
+        assert!(void.success);
+        assert_eq!(void.status, api_enums::AttemptStatus::Voided);
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_with_invalid_credentials() {
+        let connector = MockConnector {
+            valid_credentials: false,
+            requires_authentication: false,
+        };
+
+        let (authorization, void) = run_test_payment(
+            &connector,
+            get_test_router_data(),
+            TestFlow::AuthorizeAndVoid,
+        )
+        .await
+        .unwrap();
+
+        assert!(!authorization.success);
+        assert_eq!(
+            authorization.status,
+            api_enums::AttemptStatus::AuthorizationFailed
+        );
+        assert_eq!(authorization.connector_http_status_code, Some(401));
+        assert_eq!(authorization.error_code.as_deref(), Some("invalid_api_key"));
+        assert!(void.is_none());
+    }
+
+    #[test]
+    fn test_verification_result_is_added_to_metadata() {
+        let response = admin::MerchantConnectorVerifyResponse {
+            merchant_connector_id: "mca_123".to_string(),
+            connector_name: "stripe".to_string(),
+            test_mode: Some(true),
+            verified: false,
+            authorization: admin::ConnectorTestStepResult {
+                success: false,
+                status: api_enums::AttemptStatus::AuthorizationFailed,
+                latency_ms: 10,
+                connector_http_status_code: Some(401),
+                connector_transaction_id: None,
+                error_code: Some("invalid_api_key".to_string()),
+                error_message: None,
+            },
+            void: None,
+            verified_at: date_time::now(),
+        };
+
+        let metadata = add_verification_result_to_metadata(
+            Some(serde_json::json!({ "city": "NY" })),
+            &response,
+        )
+        .unwrap();
+
+        assert_eq!(metadata["city"], "NY");
+        assert_eq!(
+            metadata[CONNECTOR_VERIFICATION_METADATA_KEY]["merchant_connector_id"],
+            "mca_123"
+        );
+        assert!(
+            add_verification_result_to_metadata(Some(serde_json::json!([])), &response).is_err()
+        );
+    }
+}
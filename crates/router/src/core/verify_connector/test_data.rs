@@ -0,0 +1,150 @@
+//! Registry of the test data used to run synthetic test payments against connectors.
+
+use std::str::FromStr;
+
+use error_stack::{IntoReport, ResultExt};
+use masking::Secret;
+
+use crate::{
+    core::errors::{self, RouterResult},
+    types::api::{self, enums as api_enums},
+};
+
+/// The steps run against the connector as a part of the test payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestFlow {
+    /// Authorize the test card with manual capture, and void the authorization
+    AuthorizeAndVoid,
+    /// Only authorize the test card, for connectors which do not support voiding a payment
+    AuthorizeOnly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCard {
+    pub card_number: &'static str,
+    pub card_exp_month: &'static str,
+    pub card_exp_year: &'static str,
+    pub card_cvc: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorTestData {
+    pub card: TestCard,
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    pub flow: TestFlow,
+}
+
+const VISA_TEST_CARD: TestCard = TestCard {
+    card_number: "4111111111111111",
+    card_exp_month: "03",
+    card_exp_year: "2030",
+    card_cvc: "737",
+};
+
+const STRIPE_TEST_CARD: TestCard = TestCard {
+    card_number: "4242424242424242",
+    card_exp_month: "12",
+    card_exp_year: "2030",
+    card_cvc: "123",
+};
+
+const ADYEN_TEST_CARD: TestCard = TestCard {
+    card_number: "4111111145551142",
+    card_exp_month: "03",
+    card_exp_year: "2030",
+    card_cvc: "737",
+};
+
+const CHECKOUT_TEST_CARD: TestCard = TestCard {
+    card_number: "4242424242424242",
+    card_exp_month: "12",
+    card_exp_year: "2030",
+    card_cvc: "100",
+};
+
+const WORLDPAY_TEST_CARD: TestCard = TestCard {
+    card_number: "4444333322221111",
+    card_exp_month: "10",
+    card_exp_year: "2030",
+    card_cvc: "123",
+};
+
+/// Returns the test data to be used for the test payment against the connector, `None` if
+/// the connector does not support card payments
+pub fn get_connector_test_data(connector: api_enums::Connector) -> Option<ConnectorTestData> {
+    let (card, currency, flow) = match connector {
+        api_enums::Connector::Stripe => (
+            STRIPE_TEST_CARD,
+            api_enums::Currency::USD,
+            TestFlow::AuthorizeAndVoid,
+        ),
+        api_enums::Connector::Adyen => (
+            ADYEN_TEST_CARD,
+            api_enums::Currency::EUR,
+            TestFlow::AuthorizeAndVoid,
+        ),
+        api_enums::Connector::Checkout => (
+            CHECKOUT_TEST_CARD,
+            api_enums::Currency::USD,
+            TestFlow::AuthorizeAndVoid,
+        ),
+        api_enums::Connector::Worldpay => (
+            WORLDPAY_TEST_CARD,
+            api_enums::Currency::GBP,
+            TestFlow::AuthorizeAndVoid,
+        ),
+        api_enums::Connector::Globepay | api_enums::Connector::Payme => (
+            VISA_TEST_CARD,
+            api_enums::Currency::USD,
+            TestFlow::AuthorizeOnly,
+        ),
+        api_enums::Connector::Bitpay
+        | api_enums::Connector::Boku
+        | api_enums::Connector::Cashtocode
+        | api_enums::Connector::Coinbase
+        | api_enums::Connector::Cryptopay
+        | api_enums::Connector::Gocardless
+        | api_enums::Connector::Iatapay
+        | api_enums::Connector::Klarna
+        | api_enums::Connector::Opennode
+        | api_enums::Connector::Wise
+        | api_enums::Connector::Signifyd
+        | api_enums::Connector::Plaid => return None,
+        _ => (
+            VISA_TEST_CARD,
+            api_enums::Currency::USD,
+            TestFlow::AuthorizeAndVoid,
+        ),
+    };
+
+    Some(ConnectorTestData {
+        card,
+        amount: 100,
+        currency,
+        flow,
+    })
+}
+
+impl TestCard {
+    pub fn get_payment_method_data(&self) -> RouterResult<api::PaymentMethodData> {
+        let card_number = cards::CardNumber::from_str(self.card_number)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid card number in connector test data")?;
+
+        Ok(api::PaymentMethodData::Card(api::Card {
+            card_number,
+            card_exp_month: Secret::new(self.card_exp_month.to_string()),
+            card_exp_year: Secret::new(self.card_exp_year.to_string()),
+            card_holder_name: Secret::new("Test Payment".to_string()),
+            card_cvc: Secret::new(self.card_cvc.to_string()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        }))
+    }
+}
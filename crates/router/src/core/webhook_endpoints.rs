@@ -0,0 +1,1124 @@
+//! Registration of the webhook endpoint of a merchant connector account with the connector, for
+//! the connectors which allow managing webhook endpoints through their API. The endpoint is
+//! registered, updated and deleted along with the merchant connector account, the connector
+//! failures are retried by the scheduler without failing the merchant connector account operation.
+
+use std::marker::PhantomData;
+
+use api_models::admin::{
+    MerchantConnectorWebhookDetails, WebhookEndpointRegistration, WebhookEndpointRegistrationStatus,
+};
+use async_trait::async_trait;
+use common_utils::ext_traits::{Encode, ValueExt};
+use diesel_models::encryption::Encryption;
+use error_stack::ResultExt;
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::logger;
+use scheduler::{
+    consumer::types::process_data, db::process_tracker::ProcessTrackerExt, errors as sch_errors,
+    utils as pt_utils,
+};
+
+use crate::{
+    configs::settings::ConnectorProxy,
+    consts,
+    core::{
+        errors::{self, CustomResult, RouterResult},
+        payments::{self, helpers},
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{
+        self, api,
+        domain::{self, types as domain_types},
+        storage,
+    },
+    utils::{self, OptionExt},
+};
+
+pub const WEBHOOK_ENDPOINT_WORKFLOW_RUNNER: &str = "WEBHOOK_ENDPOINT_WORKFLOW";
+const WEBHOOK_ENDPOINT_WORKFLOW_TASK: &str = "WEBHOOK_ENDPOINT_SYNC";
+
+const IRRELEVANT_PAYMENT_ID_IN_WEBHOOK_ENDPOINT_FLOW: &str =
+    "irrelevant_payment_id_in_webhook_endpoint_flow";
+const IRRELEVANT_ATTEMPT_ID_IN_WEBHOOK_ENDPOINT_FLOW: &str =
+    "irrelevant_attempt_id_in_webhook_endpoint_flow";
+const IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_WEBHOOK_ENDPOINT_FLOW: &str =
+    "irrelevant_connector_request_reference_id_in_webhook_endpoint_flow";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEndpointAction {
+    Register,
+    Update,
+    Delete,
+}
+
+/// Tracking data of the task retrying a failed webhook endpoint action
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WebhookEndpointTrackingData {
+    pub merchant_id: String,
+    pub merchant_connector_id: String,
+    pub action: WebhookEndpointAction,
+    /// Details of the merchant connector account for deleting the endpoint, as the account may no
+    /// longer exist when the task is run
+    pub deleted_endpoint: Option<DeletedWebhookEndpoint>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct DeletedWebhookEndpoint {
+    pub connector_name: String,
+    pub connector_webhook_endpoint_id: String,
+    /// The connector account details, encrypted with the key of the merchant
+    pub connector_account_details: Encryption,
+}
+
+/// The merchant connector account whose webhook endpoint is managed
+#[derive(Clone, Debug)]
+pub struct WebhookEndpointAccount {
+    pub merchant_id: String,
+    pub merchant_connector_id: String,
+    pub connector_name: String,
+    pub connector_auth_type: types::ConnectorAuthType,
+    pub connector_proxy: Option<ConnectorProxy>,
+    pub connector_webhook_endpoint_id: Option<String>,
+}
+
+impl WebhookEndpointAccount {
+    pub fn from_merchant_connector_account(
+        merchant_connector_account: &domain::MerchantConnectorAccount,
+    ) -> RouterResult<Self> {
+        let account =
+            helpers::MerchantConnectorAccountType::DbVal(merchant_connector_account.clone());
+        let connector_auth_type = account
+            .get_connector_account_details()
+            .parse_value("ConnectorAuthType")
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+        Ok(Self {
+            merchant_id: merchant_connector_account.merchant_id.clone(),
+            merchant_connector_id: merchant_connector_account.merchant_connector_id.clone(),
+            connector_name: merchant_connector_account.connector_name.clone(),
+            connector_auth_type,
+            connector_proxy: account.get_connector_proxy(),
+            connector_webhook_endpoint_id: get_connector_webhook_details(
+                merchant_connector_account,
+            )?
+            .and_then(|webhook_details| webhook_details.connector_webhook_endpoint_id),
+        })
+    }
+}
+
+/// Outcome of calling the connector for a webhook endpoint action
+#[derive(Debug)]
+pub enum WebhookEndpointActionOutcome {
+    Completed {
+        /// The merchant connector account with the endpoint stored in its webhook details, if the
+        /// account was passed
+        merchant_connector_account: Option<domain::MerchantConnectorAccount>,
+        registration: WebhookEndpointRegistration,
+    },
+    Failed {
+        error_message: String,
+    },
+}
+
+/// Calls the connector for managing the webhook endpoint
+#[async_trait]
+pub trait WebhookEndpointExecutor {
+    async fn create(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        errors::ConnectorError,
+    >;
+
+    async fn update(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        errors::ConnectorError,
+    >;
+
+    async fn delete(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        errors::ConnectorError,
+    >;
+}
+
+/// Calls the connector using its integration
+pub struct ConnectorIntegrationExecutor<'a> {
+    pub state: &'a AppState,
+    pub connector: api::ConnectorData,
+}
+
+impl<'a> ConnectorIntegrationExecutor<'a> {
+    pub fn new(state: &'a AppState, connector_name: &str) -> RouterResult<Self> {
+        let connector = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            connector_name,
+            api::GetToken::Connector,
+        )?;
+        Ok(Self { state, connector })
+    }
+}
+
+#[async_trait]
+impl<'a> WebhookEndpointExecutor for ConnectorIntegrationExecutor<'a> {
+    async fn create(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::CreateWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > = self.connector.connector.get_connector_integration();
+
+        services::execute_connector_processing_step(
+            self.state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+    }
+
+    async fn update(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::UpdateWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > = self.connector.connector.get_connector_integration();
+
+        services::execute_connector_processing_step(
+            self.state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+    }
+
+    async fn delete(
+        &self,
+        router_data: types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+    ) -> CustomResult<
+        types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        errors::ConnectorError,
+    > {
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::DeleteWebhookEndpoint,
+            types::WebhookEndpointRequestData,
+            types::WebhookEndpointResponseData,
+        > = self.connector.connector.get_connector_integration();
+
+        services::execute_connector_processing_step(
+            self.state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+    }
+}
+
+pub fn is_webhook_endpoint_management_supported(
+    state: &AppState,
+    connector: api_models::enums::Connector,
+) -> bool {
+    state
+        .conf
+        .webhook_endpoint_management
+        .connectors_with_webhook_endpoint_management
+        .contains(&connector)
+}
+
+pub fn get_connector_webhook_details(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<Option<MerchantConnectorWebhookDetails>> {
+    merchant_connector_account
+        .connector_webhook_details
+        .clone()
+        .map(|webhook_details| {
+            webhook_details
+                .expose()
+                .parse_value("MerchantConnectorWebhookDetails")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to deserialize connector_webhook_details")
+        })
+        .transpose()
+}
+
+fn construct_webhook_endpoint_router_data<F>(
+    state: &AppState,
+    account: &WebhookEndpointAccount,
+) -> types::WebhookEndpointRouterData<F> {
+    types::RouterData {
+        flow: PhantomData,
+        merchant_id: account.merchant_id.clone(),
+        connector: account.connector_name.clone(),
+        customer_id: None,
+        payment_id: IRRELEVANT_PAYMENT_ID_IN_WEBHOOK_ENDPOINT_FLOW.to_string(),
+        attempt_id: IRRELEVANT_ATTEMPT_ID_IN_WEBHOOK_ENDPOINT_FLOW.to_string(),
+        status: diesel_models::enums::AttemptStatus::default(),
+        payment_method: diesel_models::enums::PaymentMethod::default(),
+        connector_auth_type: account.connector_auth_type.clone(),
+        description: None,
+        return_url: None,
+        payment_method_id: None,
+        address: types::PaymentAddress::default(),
+        auth_type: diesel_models::enums::AuthenticationType::default(),
+        connector_meta_data: None,
+        amount_captured: None,
+        request: types::WebhookEndpointRequestData {
+            // The merchant connector id identifies the account whose webhook secret is used for
+            // verifying the webhooks
+            webhook_url: helpers::create_webhook_url(
+                &state.conf.server.base_url,
+                &account.merchant_id,
+                &account.merchant_connector_id,
+            ),
+            connector_webhook_endpoint_id: account.connector_webhook_endpoint_id.clone(),
+        },
+        response: Err(types::ErrorResponse::default()),
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        connector_customer: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        connector_request_reference_id:
+            IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_WEBHOOK_ENDPOINT_FLOW.to_string(),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode: None,
+        payment_method_balance: None,
+        connector_api_version: None,
+        connector_http_status_code: None,
+        apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: account.connector_proxy.clone(),
+        authorized_amount: None,
+    }
+}
+
+fn get_webhook_endpoint_response<F>(
+    result: CustomResult<types::WebhookEndpointRouterData<F>, errors::ConnectorError>,
+) -> Result<types::WebhookEndpointResponseData, String> {
+    match result {
+        Ok(router_data) => router_data
+            .response
+            .map_err(|error| error.reason.unwrap_or(error.message)),
+        Err(error) => Err(error.current_context().to_string()),
+    }
+}
+
+/// Calls the connector for the action, and stores the endpoint in the webhook details of the
+/// merchant connector account if the account is passed
+pub async fn perform_webhook_endpoint_action<E: WebhookEndpointExecutor + Sync>(
+    state: &AppState,
+    executor: &E,
+    key_store: &domain::MerchantKeyStore,
+    account: &WebhookEndpointAccount,
+    merchant_connector_account: Option<domain::MerchantConnectorAccount>,
+    action: WebhookEndpointAction,
+) -> RouterResult<WebhookEndpointActionOutcome> {
+    let router_data = construct_webhook_endpoint_router_data(state, account);
+    let response = match action {
+        WebhookEndpointAction::Register => {
+            get_webhook_endpoint_response(executor.create(router_data).await)
+        }
+        WebhookEndpointAction::Update => {
+            get_webhook_endpoint_response(executor.update(router_data).await)
+        }
+        WebhookEndpointAction::Delete => {
+            get_webhook_endpoint_response(executor.delete(router_data).await)
+        }
+    };
+    let response = match response {
+        Ok(response) => response,
+        Err(error_message) => {
+            return Ok(WebhookEndpointActionOutcome::Failed { error_message });
+        }
+    };
+
+    let registration = WebhookEndpointRegistration {
+        status: match action {
+            WebhookEndpointAction::Register => WebhookEndpointRegistrationStatus::Registered,
+            WebhookEndpointAction::Update => WebhookEndpointRegistrationStatus::Updated,
+            WebhookEndpointAction::Delete => WebhookEndpointRegistrationStatus::Deleted,
+        },
+        connector_webhook_endpoint_id: Some(response.connector_webhook_endpoint_id.clone()),
+        error_message: None,
+    };
+    let merchant_connector_account = match merchant_connector_account {
+        Some(merchant_connector_account) => Some(
+            store_webhook_endpoint(
+                &*state.store,
+                key_store,
+                merchant_connector_account,
+                action,
+                response,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    Ok(WebhookEndpointActionOutcome::Completed {
+        merchant_connector_account,
+        registration,
+    })
+}
+
+async fn store_webhook_endpoint(
+    db: &dyn StorageInterface,
+    key_store: &domain::MerchantKeyStore,
+    merchant_connector_account: domain::MerchantConnectorAccount,
+    action: WebhookEndpointAction,
+    response: types::WebhookEndpointResponseData,
+) -> RouterResult<domain::MerchantConnectorAccount> {
+    let webhook_details = get_connector_webhook_details(&merchant_connector_account)?;
+    let webhook_details = match action {
+        WebhookEndpointAction::Register | WebhookEndpointAction::Update => {
+            let (merchant_secret, additional_secret) = webhook_details
+                .map(|webhook_details| {
+                    (
+                        Some(webhook_details.merchant_secret),
+                        webhook_details.additional_secret,
+                    )
+                })
+                .unwrap_or_default();
+            MerchantConnectorWebhookDetails {
+                // The connector returns the secret only when the endpoint is created
+                merchant_secret: response
+                    .webhook_secret
+                    .or(merchant_secret)
+                    .unwrap_or_else(|| Secret::new(String::new())),
+                additional_secret,
+                connector_webhook_endpoint_id: Some(response.connector_webhook_endpoint_id),
+            }
+        }
+        WebhookEndpointAction::Delete => match webhook_details {
+            Some(webhook_details)
+                if webhook_details.connector_webhook_endpoint_id.as_ref()
+                    == Some(&response.connector_webhook_endpoint_id) =>
+            {
+                MerchantConnectorWebhookDetails {
+                    connector_webhook_endpoint_id: None,
+                    ..webhook_details
+                }
+            }
+            // Another endpoint has been registered since
+            _ => return Ok(merchant_connector_account),
+        },
+    };
+
+    let connector_webhook_details =
+        Encode::<MerchantConnectorWebhookDetails>::encode_to_value(&webhook_details)
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let merchant_connector_account_update = storage::MerchantConnectorAccountUpdate::Update {
+        merchant_id: None,
+        connector_type: None,
+        connector_name: None,
+        merchant_connector_id: None,
+        connector_account_details: None,
+        test_mode: merchant_connector_account.test_mode,
+        disabled: merchant_connector_account.disabled,
+        payment_methods_enabled: merchant_connector_account.payment_methods_enabled.clone(),
+        metadata: None,
+        frm_configs: merchant_connector_account.frm_configs.clone(),
+        connector_webhook_details: Some(Secret::new(connector_webhook_details)),
+        applepay_verified_domains: None,
+        disabled_flows: None,
+        previous_credentials: None,
+    };
+    let merchant_connector_id = merchant_connector_account.merchant_connector_id.clone();
+
+    db.update_merchant_connector_account(
+        merchant_connector_account,
+        merchant_connector_account_update.into(),
+        key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable_lazy(|| {
+        format!("Failed while storing the webhook endpoint of MerchantConnectorAccount: id: {merchant_connector_id}")
+    })
+}
+
+/// Registers, updates or deletes the webhook endpoint of the merchant connector account, a
+/// failure of the connector is retried by the scheduler
+pub async fn sync_webhook_endpoint<E: WebhookEndpointExecutor + Sync>(
+    state: &AppState,
+    executor: &E,
+    key_store: &domain::MerchantKeyStore,
+    merchant_connector_account: domain::MerchantConnectorAccount,
+    action: WebhookEndpointAction,
+) -> RouterResult<(
+    domain::MerchantConnectorAccount,
+    WebhookEndpointRegistration,
+)> {
+    let account =
+        WebhookEndpointAccount::from_merchant_connector_account(&merchant_connector_account)?;
+    match perform_webhook_endpoint_action(
+        state,
+        executor,
+        key_store,
+        &account,
+        Some(merchant_connector_account.clone()),
+        action,
+    )
+    .await?
+    {
+        WebhookEndpointActionOutcome::Completed {
+            merchant_connector_account: updated_merchant_connector_account,
+            registration,
+        } => Ok((
+            updated_merchant_connector_account.unwrap_or(merchant_connector_account),
+            registration,
+        )),
+        WebhookEndpointActionOutcome::Failed { error_message } => {
+            let registration = schedule_webhook_endpoint_retry(
+                &*state.store,
+                &account,
+                &merchant_connector_account,
+                action,
+                error_message,
+            )
+            .await;
+            Ok((merchant_connector_account, registration))
+        }
+    }
+}
+
+/// Deletes the webhook endpoint of a merchant connector account which is being deleted
+pub async fn delete_webhook_endpoint<E: WebhookEndpointExecutor + Sync>(
+    state: &AppState,
+    executor: &E,
+    key_store: &domain::MerchantKeyStore,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<WebhookEndpointRegistration> {
+    let account =
+        WebhookEndpointAccount::from_merchant_connector_account(merchant_connector_account)?;
+    match perform_webhook_endpoint_action(
+        state,
+        executor,
+        key_store,
+        &account,
+        None,
+        WebhookEndpointAction::Delete,
+    )
+    .await?
+    {
+        WebhookEndpointActionOutcome::Completed { registration, .. } => Ok(registration),
+        WebhookEndpointActionOutcome::Failed { error_message } => {
+            Ok(schedule_webhook_endpoint_retry(
+                &*state.store,
+                &account,
+                merchant_connector_account,
+                WebhookEndpointAction::Delete,
+                error_message,
+            )
+            .await)
+        }
+    }
+}
+
+async fn schedule_webhook_endpoint_retry(
+    db: &dyn StorageInterface,
+    account: &WebhookEndpointAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    action: WebhookEndpointAction,
+    error_message: String,
+) -> WebhookEndpointRegistration {
+    logger::warn!(
+        "Failed to {action:?} the webhook endpoint of merchant connector account {}: {error_message}",
+        account.merchant_connector_id
+    );
+    let status =
+        match add_webhook_endpoint_task(db, account, merchant_connector_account, action).await {
+            Ok(()) => WebhookEndpointRegistrationStatus::RetryScheduled,
+            Err(error) => {
+                logger::error!(process_tracker_error=?error);
+                WebhookEndpointRegistrationStatus::Failed
+            }
+        };
+    WebhookEndpointRegistration {
+        status,
+        connector_webhook_endpoint_id: account.connector_webhook_endpoint_id.clone(),
+        error_message: Some(error_message),
+    }
+}
+
+async fn add_webhook_endpoint_task(
+    db: &dyn StorageInterface,
+    account: &WebhookEndpointAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    action: WebhookEndpointAction,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let deleted_endpoint = match (action, account.connector_webhook_endpoint_id.as_ref()) {
+        (WebhookEndpointAction::Delete, Some(connector_webhook_endpoint_id)) => {
+            Some(DeletedWebhookEndpoint {
+                connector_name: account.connector_name.clone(),
+                connector_webhook_endpoint_id: connector_webhook_endpoint_id.clone(),
+                connector_account_details: merchant_connector_account
+                    .connector_account_details
+                    .clone()
+                    .into(),
+            })
+        }
+        _ => None,
+    };
+    let tracking_data = WebhookEndpointTrackingData {
+        merchant_id: account.merchant_id.clone(),
+        merchant_connector_id: account.merchant_connector_id.clone(),
+        action,
+        deleted_endpoint,
+    };
+    // Each failed action is retried by its own task, as the earlier tasks of the account remain
+    // in the process tracker once finished
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        WEBHOOK_ENDPOINT_WORKFLOW_RUNNER,
+        WEBHOOK_ENDPOINT_WORKFLOW_TASK,
+        &utils::generate_id(consts::ID_LENGTH, &account.merchant_connector_id),
+        &account.merchant_id,
+    );
+    let schedule_time = get_webhook_endpoint_retry_schedule_time(&account.merchant_id, 0)
+        .unwrap_or_else(common_utils::date_time::now);
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        WEBHOOK_ENDPOINT_WORKFLOW_TASK,
+        WEBHOOK_ENDPOINT_WORKFLOW_RUNNER,
+        tracking_data,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+pub fn get_webhook_endpoint_retry_schedule_time(
+    merchant_id: &str,
+    retry_count: i32,
+) -> Option<time::PrimitiveDateTime> {
+    let time_delta = pt_utils::get_schedule_time(
+        process_data::ConnectorPTMapping::default(),
+        merchant_id,
+        retry_count + 1,
+    );
+    pt_utils::get_time_from_delta(time_delta)
+}
+
+/// Finds the account whose webhook endpoint is to be synced by a retry task, returns `None` if the
+/// action is no longer needed
+pub async fn get_webhook_endpoint_account_for_retry(
+    db: &dyn StorageInterface,
+    key_store: &domain::MerchantKeyStore,
+    tracking_data: &WebhookEndpointTrackingData,
+) -> RouterResult<
+    Option<(
+        WebhookEndpointAccount,
+        Option<domain::MerchantConnectorAccount>,
+    )>,
+> {
+    let merchant_connector_account = match db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            &tracking_data.merchant_id,
+            &tracking_data.merchant_connector_id,
+            key_store,
+        )
+        .await
+    {
+        Ok(merchant_connector_account) => Some(merchant_connector_account),
+        Err(error)
+            if error.current_context().is_db_not_found()
+                || matches!(
+                    error.current_context(),
+                    errors::StorageError::ValueNotFound(_)
+                ) =>
+        {
+            None
+        }
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError))?,
+    };
+
+    match (tracking_data.action, merchant_connector_account) {
+        (WebhookEndpointAction::Delete, merchant_connector_account) => {
+            let deleted_endpoint = tracking_data
+                .deleted_endpoint
+                .clone()
+                .get_required_value("deleted_endpoint")?;
+            let connector_account_details =
+                domain_types::decrypt::<serde_json::Value, masking::WithType>(
+                    Some(deleted_endpoint.connector_account_details),
+                    key_store.key.get_inner().peek(),
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)?
+                .get_required_value("connector_account_details")?;
+            let connector_account_details = connector_account_details.into_inner().expose();
+            let connector_proxy =
+                services::connector_proxy::get_merchant_connector_proxy_or_default(
+                    &connector_account_details,
+                );
+            let connector_auth_type = connector_account_details
+                .parse_value("ConnectorAuthType")
+                .change_context(errors::ApiErrorResponse::InternalServerError)?;
+            let account = WebhookEndpointAccount {
+                merchant_id: tracking_data.merchant_id.clone(),
+                merchant_connector_id: tracking_data.merchant_connector_id.clone(),
+                connector_name: deleted_endpoint.connector_name,
+                connector_auth_type,
+                connector_proxy,
+                connector_webhook_endpoint_id: Some(deleted_endpoint.connector_webhook_endpoint_id),
+            };
+            Ok(Some((account, merchant_connector_account)))
+        }
+        (action, Some(merchant_connector_account)) => {
+            let account = WebhookEndpointAccount::from_merchant_connector_account(
+                &merchant_connector_account,
+            )?;
+            // The endpoint could have been registered or deleted by a later request
+            let is_registered = account.connector_webhook_endpoint_id.is_some();
+            let is_needed = match action {
+                WebhookEndpointAction::Register => !is_registered,
+                WebhookEndpointAction::Update | WebhookEndpointAction::Delete => is_registered,
+            };
+            Ok(is_needed.then_some((account, Some(merchant_connector_account))))
+        }
+        (_, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+    use crate::{configs::settings, routes, types::api::enums as api_enums};
+
+    /// A connector whose webhook endpoints are kept in memory
+    struct MockConnector {
+        is_available: AtomicBool,
+    }
+
+    impl MockConnector {
+        fn new() -> Self {
+            Self {
+                is_available: AtomicBool::new(true),
+            }
+        }
+
+        fn get_response<F>(
+            &self,
+            mut router_data: types::WebhookEndpointRouterData<F>,
+            connector_webhook_endpoint_id: Option<String>,
+            webhook_secret: Option<&str>,
+        ) -> CustomResult<types::WebhookEndpointRouterData<F>, errors::ConnectorError> {
+            assert!(router_data
+                .request
+                .webhook_url
+                .ends_with(&format!("/webhooks/merchant_1/{}", "mca_webhooks")));
+            router_data.response = match connector_webhook_endpoint_id {
+                Some(connector_webhook_endpoint_id)
+                    if self.is_available.load(Ordering::Relaxed) =>
+                {
+                    Ok(types::WebhookEndpointResponseData {
+                        connector_webhook_endpoint_id,
+                        webhook_secret: webhook_secret
+                            .map(|webhook_secret| Secret::new(webhook_secret.to_string())),
+                    })
+                }
+                _ => Err(types::ErrorResponse {
+                    code: "api_error".to_string(),
+                    message: "api_error".to_string(),
+                    reason: Some("The connector is unavailable".to_string()),
+                    status_code: 503,
+                }),
+            };
+            Ok(router_data)
+        }
+    }
+
+    #[async_trait]
+    impl WebhookEndpointExecutor for MockConnector {
+        async fn create(
+            &self,
+            router_data: types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+        ) -> CustomResult<
+            types::WebhookEndpointRouterData<api::CreateWebhookEndpoint>,
+            errors::ConnectorError,
+        > {
+            assert!(router_data.request.connector_webhook_endpoint_id.is_none());
+            self.get_response(router_data, Some("we_123".to_string()), Some("whsec_123"))
+        }
+
+        async fn update(
+            &self,
+            router_data: types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+        ) -> CustomResult<
+            types::WebhookEndpointRouterData<api::UpdateWebhookEndpoint>,
+            errors::ConnectorError,
+        > {
+            let connector_webhook_endpoint_id =
+                router_data.request.connector_webhook_endpoint_id.clone();
+            self.get_response(router_data, connector_webhook_endpoint_id, None)
+        }
+
+        async fn delete(
+            &self,
+            router_data: types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+        ) -> CustomResult<
+            types::WebhookEndpointRouterData<api::DeleteWebhookEndpoint>,
+            errors::ConnectorError,
+        > {
+            let connector_webhook_endpoint_id =
+                router_data.request.connector_webhook_endpoint_id.clone();
+            self.get_response(router_data, connector_webhook_endpoint_id, None)
+        }
+    }
+
+    async fn get_app_state() -> AppState {
+        routes::AppState::with_storage(
+            settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await
+    }
+
+    async fn create_merchant_connector_account(
+        state: &AppState,
+    ) -> (domain::MerchantKeyStore, domain::MerchantConnectorAccount) {
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain_types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+        let merchant_connector_account = db
+            .insert_merchant_connector_account(
+                domain::MerchantConnectorAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    connector_name: "stripe".to_string(),
+                    connector_account_details: domain_types::encrypt(
+                        serde_json::json!({
+                            "auth_type": "HeaderKey",
+                            "api_key": "sk_test_123"
+                        })
+                        .into(),
+                        key_store.key.get_inner().peek(),
+                    )
+                    .await
+                    .unwrap(),
+                    test_mode: Some(true),
+                    disabled: Some(false),
+                    merchant_connector_id: "mca_webhooks".to_string(),
+                    payment_methods_enabled: None,
+                    connector_type: api_enums::ConnectorType::PaymentProcessor,
+                    metadata: None,
+                    frm_configs: None,
+                    connector_label: None,
+                    business_country: None,
+                    business_label: None,
+                    business_sub_label: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    connector_webhook_details: None,
+                    profile_id: Some("pro_default".to_string()),
+                    applepay_verified_domains: None,
+                    disabled_flows: None,
+                    previous_credentials: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+        (key_store, merchant_connector_account)
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_endpoint_round_trip() {
+        let state = get_app_state().await;
+        let (key_store, merchant_connector_account) =
+            create_merchant_connector_account(&state).await;
+        let connector = MockConnector::new();
+
+        let (merchant_connector_account, registration) = sync_webhook_endpoint(
+            &state,
+            &connector,
+            &key_store,
+            merchant_connector_account,
+            WebhookEndpointAction::Register,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            registration.status,
+            WebhookEndpointRegistrationStatus::Registered
+        );
+        let webhook_details = get_connector_webhook_details(&merchant_connector_account)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            webhook_details.connector_webhook_endpoint_id.as_deref(),
+            Some("we_123")
+        );
+        assert_eq!(webhook_details.merchant_secret.peek(), "whsec_123");
+
+        // The secret is kept when the endpoint is updated
+        let (merchant_connector_account, registration) = sync_webhook_endpoint(
+            &state,
+            &connector,
+            &key_store,
+            merchant_connector_account,
+            WebhookEndpointAction::Update,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            registration.status,
+            WebhookEndpointRegistrationStatus::Updated
+        );
+        let webhook_details = get_connector_webhook_details(&merchant_connector_account)
+            .unwrap()
+            .unwrap();
+        assert_eq!(webhook_details.merchant_secret.peek(), "whsec_123");
+
+        let (merchant_connector_account, registration) = sync_webhook_endpoint(
+            &state,
+            &connector,
+            &key_store,
+            merchant_connector_account,
+            WebhookEndpointAction::Delete,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            registration.status,
+            WebhookEndpointRegistrationStatus::Deleted
+        );
+        assert_eq!(
+            registration.connector_webhook_endpoint_id.as_deref(),
+            Some("we_123")
+        );
+        let stored_merchant_connector_account = state
+            .store
+            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                "merchant_1",
+                "mca_webhooks",
+                &key_store,
+            )
+            .await
+            .unwrap();
+        assert!(
+            get_connector_webhook_details(&stored_merchant_connector_account)
+                .unwrap()
+                .unwrap()
+                .connector_webhook_endpoint_id
+                .is_none()
+        );
+        assert!(WebhookEndpointAccount::from_merchant_connector_account(
+            &merchant_connector_account
+        )
+        .unwrap()
+        .connector_webhook_endpoint_id
+        .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_failed_registration_is_retried() {
+        let state = get_app_state().await;
+        let (key_store, merchant_connector_account) =
+            create_merchant_connector_account(&state).await;
+        let connector = MockConnector::new();
+        connector.is_available.store(false, Ordering::Relaxed);
+
+        let (merchant_connector_account, registration) = sync_webhook_endpoint(
+            &state,
+            &connector,
+            &key_store,
+            merchant_connector_account,
+            WebhookEndpointAction::Register,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            registration.status,
+            WebhookEndpointRegistrationStatus::RetryScheduled
+        );
+        assert_eq!(
+            registration.error_message.as_deref(),
+            Some("The connector is unavailable")
+        );
+        assert!(merchant_connector_account
+            .connector_webhook_details
+            .is_none());
+
+        // The task registers the endpoint once the connector is available again
+        let tracking_data = WebhookEndpointTrackingData {
+            merchant_id: "merchant_1".to_string(),
+            merchant_connector_id: "mca_webhooks".to_string(),
+            action: WebhookEndpointAction::Register,
+            deleted_endpoint: None,
+        };
+        let (account, merchant_connector_account) =
+            get_webhook_endpoint_account_for_retry(&*state.store, &key_store, &tracking_data)
+                .await
+                .unwrap()
+                .unwrap();
+        connector.is_available.store(true, Ordering::Relaxed);
+        let outcome = perform_webhook_endpoint_action(
+            &state,
+            &connector,
+            &key_store,
+            &account,
+            merchant_connector_account,
+            tracking_data.action,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            outcome,
+            WebhookEndpointActionOutcome::Completed {
+                merchant_connector_account: Some(_),
+                ..
+            }
+        ));
+
+        // A registration which is no longer needed is not retried again
+        assert!(
+            get_webhook_endpoint_account_for_retry(&*state.store, &key_store, &tracking_data)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_endpoint_of_deleted_account_is_deleted_on_retry() {
+        let state = get_app_state().await;
+        let (key_store, merchant_connector_account) =
+            create_merchant_connector_account(&state).await;
+        let connector = MockConnector::new();
+        let (merchant_connector_account, _) = sync_webhook_endpoint(
+            &state,
+            &connector,
+            &key_store,
+            merchant_connector_account,
+            WebhookEndpointAction::Register,
+        )
+        .await
+        .unwrap();
+
+        connector.is_available.store(false, Ordering::Relaxed);
+        let registration =
+            delete_webhook_endpoint(&state, &connector, &key_store, &merchant_connector_account)
+                .await
+                .unwrap();
+        assert_eq!(
+            registration.status,
+            WebhookEndpointRegistrationStatus::RetryScheduled
+        );
+        let account =
+            WebhookEndpointAccount::from_merchant_connector_account(&merchant_connector_account)
+                .unwrap();
+        state
+            .store
+            .delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
+                "merchant_1",
+                "mca_webhooks",
+            )
+            .await
+            .unwrap();
+
+        let tracking_data = WebhookEndpointTrackingData {
+            merchant_id: "merchant_1".to_string(),
+            merchant_connector_id: "mca_webhooks".to_string(),
+            action: WebhookEndpointAction::Delete,
+            deleted_endpoint: Some(DeletedWebhookEndpoint {
+                connector_name: account.connector_name,
+                connector_webhook_endpoint_id: "we_123".to_string(),
+                connector_account_details: merchant_connector_account
+                    .connector_account_details
+                    .clone()
+                    .into(),
+            }),
+        };
+        let (account, merchant_connector_account) =
+            get_webhook_endpoint_account_for_retry(&*state.store, &key_store, &tracking_data)
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(merchant_connector_account.is_none());
+        assert!(matches!(
+            account.connector_auth_type,
+            types::ConnectorAuthType::HeaderKey { ref api_key } if api_key.peek() == "sk_test_123"
+        ));
+
+        connector.is_available.store(true, Ordering::Relaxed);
+        let outcome = perform_webhook_endpoint_action(
+            &state,
+            &connector,
+            &key_store,
+            &account,
+            None,
+            WebhookEndpointAction::Delete,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            outcome,
+            WebhookEndpointActionOutcome::Completed {
+                merchant_connector_account: None,
+                registration: WebhookEndpointRegistration {
+                    status: WebhookEndpointRegistrationStatus::Deleted,
+                    ..
+                },
+            }
+        ));
+    }
+}
@@ -1,13 +1,15 @@
 pub mod types;
 pub mod utils;
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
-use api_models::payments::HeaderPayload;
+use api_models::{admin::WebhookAuthentication, enums as api_enums, payments::HeaderPayload};
+use base64::Engine;
 use common_utils::errors::ReportSwitchExt;
 use error_stack::{report, IntoReport, ResultExt};
-use masking::ExposeInterface;
+use masking::{ExposeInterface, Mask, PeekInterface};
 use router_env::{instrument, tracing};
+use strum::IntoEnumIterator;
 
 use super::{errors::StorageErrorExt, metrics};
 #[cfg(feature = "stripe")]
@@ -15,12 +17,12 @@ use crate::compatibility::stripe::webhooks as stripe_webhooks;
 use crate::{
     consts,
     core::{
-        api_locking,
-        errors::{self, ConnectorErrorExt, CustomResult, RouterResponse},
-        payments, refunds,
+        admin, alerts, api_locking,
+        errors::{self, user_messages, ConnectorErrorExt, CustomResult, RouterResponse},
+        payment_timeline, payments, refunds,
     },
     db::StorageInterface,
-    logger,
+    headers, logger,
     routes::{lock_utils, metrics::request::add_attributes, AppState},
     services,
     types::{
@@ -39,6 +41,8 @@ pub async fn payments_incoming_webhook_flow<W: types::OutgoingWebhookType>(
     merchant_account: domain::MerchantAccount,
     key_store: domain::MerchantKeyStore,
     webhook_details: api::IncomingWebhookDetails,
+    connector_fee: Option<router_types::ConnectorFee>,
+    acquirer_reference_number: Option<String>,
     source_verified: bool,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     let consume_or_trigger_flow = if source_verified {
@@ -58,7 +62,7 @@ pub async fn payments_incoming_webhook_flow<W: types::OutgoingWebhookType>(
 
             let lock_action = api_locking::LockAction::Hold {
                 input: super::api_locking::LockingInput {
-                    unique_locking_key: payment_id,
+                    unique_locking_key: payment_id.clone(),
                     api_identifier: lock_utils::ApiIdentifier::Payments,
                     override_lock_retries: None,
                 },
@@ -91,10 +95,30 @@ pub async fn payments_incoming_webhook_flow<W: types::OutgoingWebhookType>(
             )
             .await;
 
+            // The payment is not synced once it has succeeded, so a fee or an acquirer reference
+            // number which the connector reports only after the payment has succeeded is stored
+            // separately
+            let active_attempt_update = if response.is_ok()
+                && (connector_fee.is_some() || acquirer_reference_number.is_some())
+            {
+                update_active_attempt_from_webhook(
+                    &*state.store,
+                    &merchant_account,
+                    &payment_id,
+                    connector_fee,
+                    acquirer_reference_number,
+                )
+                .await
+            } else {
+                Ok(())
+            };
+
             lock_action
                 .free_lock_action(&state, merchant_account.merchant_id.to_owned())
                 .await?;
 
+            active_attempt_update?;
+
             match response {
                 Ok(value) => value,
                 Err(err)
@@ -437,6 +461,46 @@ pub async fn disputes_incoming_webhook_flow<W: types::OutgoingWebhookType>(
     }
 }
 
+pub async fn alerts_incoming_webhook_flow<W: types::OutgoingWebhookType>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    source_verified: bool,
+    connector: &(dyn api::Connector + Sync),
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
+    event_object: serde_json::Value,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    if !source_verified {
+        return Err(errors::ApiErrorResponse::WebhookAuthenticationFailed).into_report();
+    }
+
+    let alert_details = connector.get_alert_details(request_details).switch()?;
+    let Some(alert) = alerts::process_alert(
+        &state,
+        &merchant_account,
+        &key_store,
+        connector.id(),
+        alert_details,
+        event_object,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    create_event_and_trigger_outgoing_webhook::<W>(
+        state,
+        merchant_account,
+        enums::EventType::AlertReceived,
+        enums::EventClass::Alerts,
+        None,
+        alert.alert_id.clone(),
+        enums::EventObjectType::AlertDetails,
+        api::OutgoingWebhookContent::AlertDetails(Box::new(alert.foreign_into())),
+    )
+    .await
+}
+
 async fn bank_transfer_webhook_flow<W: types::OutgoingWebhookType>(
     state: AppState,
     merchant_account: domain::MerchantAccount,
@@ -595,27 +659,40 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
     }?;
 
     if state.conf.webhooks.outgoing_enabled {
-        let arbiter = actix::Arbiter::try_current()
-            .ok_or(errors::ApiErrorResponse::WebhookProcessingFailure)
-            .into_report()
-            .attach_printable("arbiter retrieval failure")?;
-
+        let timeline = payment_timeline::get_outgoing_webhook_timeline(
+            &*state.store,
+            &merchant_account.merchant_id,
+            &content,
+        )
+        .await;
         let outgoing_webhook = api::OutgoingWebhook {
             merchant_id: merchant_account.merchant_id.clone(),
             event_id: event.event_id,
             event_type: event.event_type,
             content,
             timestamp: event.created_at,
+            webhook_version: None,
+            error: None,
+            attempts: None,
+            timeline,
         };
 
-        arbiter.spawn(async move {
+        let webhook_future = async move {
             let result =
                 trigger_webhook_to_merchant::<W>(merchant_account, outgoing_webhook, &state).await;
 
             if let Err(e) = result {
                 logger::error!(?e);
             }
-        });
+        };
+
+        match actix::Arbiter::try_current() {
+            Some(arbiter) => {
+                arbiter.spawn(webhook_future);
+            }
+            // Outside of the actix runtime, as in the scheduler, the webhook is sent inline
+            None => webhook_future.await,
+        }
     }
 
     Ok(())
@@ -636,258 +713,946 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
             .parse_value("WebhookDetails")
             .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
 
+    let webhook_auth_details = merchant_account
+        .webhook_auth_details
+        .clone()
+        .map(|auth_details| {
+            auth_details
+                .into_inner()
+                .expose()
+                .parse_value::<api_models::admin::WebhookAuthDetails>("WebhookAuthDetails")
+        })
+        .transpose()
+        .change_context(errors::WebhooksFlowError::WebhookAuthDetailsParsingFailed)?
+        .unwrap_or_default();
+
+    let business_profile =
+        get_webhook_business_profile(state, &merchant_account, &webhook.content).await;
+    let subscriptions = get_webhook_subscriptions(
+        state,
+        &merchant_account.merchant_id,
+        business_profile.as_ref(),
+        webhook_details.subscriptions.clone(),
+        webhook_auth_details.subscription_secrets.clone(),
+    )
+    .await;
+    let pii_redaction = services::api::pii_redaction::get_outgoing_webhook_redaction(
+        merchant_account.pii_policy.as_ref(),
+        business_profile
+            .as_ref()
+            .and_then(|business_profile| business_profile.pii_policy.as_ref()),
+    );
+
+    let outgoing_webhook_event_id = webhook.event_id.clone();
+    let outgoing_webhook_event_type = webhook.event_type;
+
+    // The webhooks configured before the payloads were versioned are sent in the first version
+    let payload_version = webhook_details
+        .payload_version
+        .unwrap_or(api_enums::WebhookPayloadVersion::V1);
+    let mut renderer =
+        OutgoingWebhookRenderer::new(state, &merchant_account, webhook, pii_redaction);
+
+    if let Some(subscriptions) = subscriptions {
+        return deliver_webhook_to_subscriptions(
+            state,
+            &merchant_account.merchant_id,
+            &outgoing_webhook_event_id,
+            outgoing_webhook_event_type,
+            &mut renderer,
+            payload_version,
+            subscriptions,
+        )
+        .await;
+    }
+
+    let (transformed_outgoing_webhook, transformed_outgoing_webhook_string) =
+        renderer.render::<W>(payload_version).await?;
+
     let webhook_url = webhook_details
         .webhook_url
         .get_required_value("webhook_url")
         .change_context(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured)
         .map(ExposeInterface::expose)?;
 
-    let outgoing_webhook_event_id = webhook.event_id.clone();
-
-    let transformed_outgoing_webhook = W::from(webhook);
-
     let outgoing_webhooks_signature = transformed_outgoing_webhook
-        .get_outgoing_webhooks_signature(merchant_account.payment_response_hash_key.clone())?;
+        .get_outgoing_webhooks_signature(
+            &transformed_outgoing_webhook_string,
+            merchant_account.payment_response_hash_key.clone(),
+        )?;
 
-    let transformed_outgoing_webhook_string = router_types::RequestBody::log_and_get_request_body(
-        &transformed_outgoing_webhook,
-        Encode::<serde_json::Value>::encode_to_string_of_json,
+    let status_code = send_webhook_to_endpoint::<W>(
+        state,
+        &merchant_account.merchant_id,
+        &webhook_url,
+        outgoing_webhooks_signature,
+        transformed_outgoing_webhook.get_payload_version(),
+        transformed_outgoing_webhook_string,
+        webhook_auth_details,
     )
-    .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
-    .attach_printable("There was an issue when encoding the outgoing webhook body")?;
-
-    let mut header = vec![(
-        reqwest::header::CONTENT_TYPE.to_string(),
-        "application/json".into(),
-    )];
+    .await?;
 
-    if let Some(signature) = outgoing_webhooks_signature {
-        W::add_webhook_header(&mut header, signature)
+    if !is_success_status_code(status_code) {
+        // [#217]: Schedule webhook for retry.
+        Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
     }
 
-    let request = services::RequestBuilder::new()
-        .method(services::Method::Post)
-        .url(&webhook_url)
-        .attach_default_headers()
-        .headers(header)
-        .body(Some(transformed_outgoing_webhook_string))
-        .build();
+    let update_event = storage::EventUpdate::UpdateWebhookNotified {
+        is_webhook_notified: Some(true),
+    };
+    state
+        .store
+        .update_event(outgoing_webhook_event_id, update_event)
+        .await
+        .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
 
-    let response = state
-        .api_client
-        .send_request(state, request, Some(OUTGOING_WEBHOOK_TIMEOUT_SECS), false)
-        .await;
+    Ok(())
+}
 
-    metrics::WEBHOOK_OUTGOING_COUNT.add(
-        &metrics::CONTEXT,
-        1,
-        &[metrics::KeyValue::new(
-            MERCHANT_ID,
-            merchant_account.merchant_id.clone(),
-        )],
-    );
-    logger::debug!(outgoing_webhook_response=?response);
+/// Renders the webhook in the versions of the payloads of the endpoints it is sent to, fetching the
+/// attempts of its payment only once, and only if a version sending them is used. The encoded
+/// payloads are redacted as configured in the PII policy of the merchant or the business profile.
+struct OutgoingWebhookRenderer<'a> {
+    state: &'a AppState,
+    merchant_account: &'a domain::MerchantAccount,
+    webhook: api::OutgoingWebhook,
+    attempts: Option<Option<Vec<api_models::payments::PaymentAttemptResponse>>>,
+    pii_redaction: Option<api_models::admin::PiiRedaction>,
+}
 
-    match response {
-        Err(e) => {
-            // [#217]: Schedule webhook for retry.
-            Err(e).change_context(errors::WebhooksFlowError::CallToMerchantFailed)?;
+impl<'a> OutgoingWebhookRenderer<'a> {
+    fn new(
+        state: &'a AppState,
+        merchant_account: &'a domain::MerchantAccount,
+        webhook: api::OutgoingWebhook,
+        pii_redaction: Option<api_models::admin::PiiRedaction>,
+    ) -> Self {
+        Self {
+            state,
+            merchant_account,
+            webhook,
+            attempts: None,
+            pii_redaction,
         }
-        Ok(res) => {
-            if res.status().is_success() {
-                metrics::WEBHOOK_OUTGOING_RECEIVED_COUNT.add(
-                    &metrics::CONTEXT,
-                    1,
-                    &[metrics::KeyValue::new(
-                        MERCHANT_ID,
-                        merchant_account.merchant_id.clone(),
-                    )],
-                );
-                let update_event = storage::EventUpdate::UpdateWebhookNotified {
-                    is_webhook_notified: Some(true),
-                };
-                state
-                    .store
-                    .update_event(outgoing_webhook_event_id, update_event)
-                    .await
-                    .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
-            } else {
-                metrics::WEBHOOK_OUTGOING_NOT_RECEIVED_COUNT.add(
-                    &metrics::CONTEXT,
-                    1,
-                    &[metrics::KeyValue::new(
-                        MERCHANT_ID,
-                        merchant_account.merchant_id.clone(),
-                    )],
-                );
-                // [#217]: Schedule webhook for retry.
-                Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
+    }
+
+    async fn render_webhook(
+        &mut self,
+        payload_version: api_enums::WebhookPayloadVersion,
+    ) -> api::OutgoingWebhook {
+        let attempts = match payload_version {
+            api_enums::WebhookPayloadVersion::V1 => None,
+            api_enums::WebhookPayloadVersion::V2 => {
+                if self.attempts.is_none() {
+                    self.attempts = Some(
+                        get_outgoing_webhook_attempts(
+                            self.state,
+                            self.merchant_account,
+                            &self.webhook.content,
+                        )
+                        .await,
+                    );
+                }
+                self.attempts.clone().flatten()
             }
-        }
+        };
+        render_outgoing_webhook(self.webhook.clone(), payload_version, attempts)
     }
 
-    Ok(())
+    /// Renders the webhook in the version and transforms it into the payload sent to the
+    /// endpoint, returning the payload along with its redacted encoded body
+    async fn render<W: types::OutgoingWebhookType>(
+        &mut self,
+        payload_version: api_enums::WebhookPayloadVersion,
+    ) -> CustomResult<(W, String), errors::WebhooksFlowError> {
+        let transformed_outgoing_webhook = W::from(self.render_webhook(payload_version).await);
+        let transformed_outgoing_webhook_string = services::api::pii_redaction::redact_and_encode(
+            &transformed_outgoing_webhook,
+            self.pii_redaction.as_ref(),
+        )
+        .into_report()
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
+        .attach_printable("There was an issue when encoding the outgoing webhook body")?;
+        Ok((
+            transformed_outgoing_webhook,
+            transformed_outgoing_webhook_string,
+        ))
+    }
 }
 
-#[instrument(skip_all)]
-pub async fn webhooks_core<W: types::OutgoingWebhookType>(
-    state: AppState,
-    req: &actix_web::HttpRequest,
-    merchant_account: domain::MerchantAccount,
-    key_store: domain::MerchantKeyStore,
-    connector_name_or_mca_id: &str,
-    body: actix_web::web::Bytes,
-) -> RouterResponse<serde_json::Value> {
-    metrics::WEBHOOK_INCOMING_COUNT.add(
-        &metrics::CONTEXT,
-        1,
-        &[metrics::KeyValue::new(
-            MERCHANT_ID,
-            merchant_account.merchant_id.clone(),
-        )],
-    );
-    let mut request_details = api::IncomingWebhookRequestDetails {
-        method: req.method().clone(),
-        uri: req.uri().clone(),
-        headers: req.headers(),
-        query_params: req.query_string().to_string(),
-        body: &body,
-    };
+/// Renders the webhook in the version of the payloads. The payloads of `v1` carry the event type
+/// and the object of the event, the ones of `v2` also carry the unified error of the object and the
+/// attempts of the payment
+pub fn render_outgoing_webhook(
+    mut webhook: api::OutgoingWebhook,
+    payload_version: api_enums::WebhookPayloadVersion,
+    attempts: Option<Vec<api_models::payments::PaymentAttemptResponse>>,
+) -> api::OutgoingWebhook {
+    webhook.webhook_version = Some(payload_version);
+    match payload_version {
+        api_enums::WebhookPayloadVersion::V1 => {
+            webhook.error = None;
+            webhook.attempts = None;
+        }
+        api_enums::WebhookPayloadVersion::V2 => {
+            webhook.error = get_outgoing_webhook_error(&webhook.content);
+            webhook.attempts = attempts;
+        }
+    }
+    webhook
+}
 
-    let (merchant_connector_account, connector) = fetch_mca_and_connector(
-        state.clone(),
-        &merchant_account,
-        connector_name_or_mca_id,
-        &key_store,
-        &request_details,
-    )
-    .await?;
+fn get_outgoing_webhook_error(
+    content: &api::OutgoingWebhookContent,
+) -> Option<api_models::webhooks::OutgoingWebhookError> {
+    let (code, message) = match content {
+        api::OutgoingWebhookContent::PaymentDetails(payments_response) => (
+            payments_response.error_code.clone(),
+            payments_response.error_message.clone(),
+        ),
+        api::OutgoingWebhookContent::RefundDetails(refund_response) => (
+            refund_response.error_code.clone(),
+            refund_response.error_message.clone(),
+        ),
+        api::OutgoingWebhookContent::DisputeDetails(_)
+        | api::OutgoingWebhookContent::AlertDetails(_) => (None, None),
+    };
+    if code.is_none() && message.is_none() {
+        return None;
+    }
 
-    let connector_name = merchant_connector_account.clone().connector_name;
+    // The errors reported by the connector for the payment or the refund are declines, as are the
+    // errors of the connectors returned by the API
+    let unified_code = user_messages::UnifiedCode::PaymentDeclined;
+    Some(api_models::webhooks::OutgoingWebhookError {
+        code,
+        message,
+        unified_code: unified_code.to_string(),
+        unified_message: user_messages::get_message(unified_code, None, &[]),
+    })
+}
 
-    let connector = connector.connector;
+/// The attempts of the payment of the webhook, the latest first, `None` for the webhooks of the
+/// other objects
+async fn get_outgoing_webhook_attempts(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    content: &api::OutgoingWebhookContent,
+) -> Option<Vec<api_models::payments::PaymentAttemptResponse>> {
+    let api::OutgoingWebhookContent::PaymentDetails(payments_response) = content else {
+        return None;
+    };
+    if payments_response.attempts.is_some() {
+        return payments_response.attempts.clone();
+    }
 
-    let decoded_body = connector
-        .decode_webhook_body(
-            &*state.clone().store,
-            &request_details,
+    let payment_id = payments_response.payment_id.as_deref()?;
+    let mut attempts = state
+        .store
+        .find_attempts_by_merchant_id_payment_id(
             &merchant_account.merchant_id,
+            payment_id,
+            merchant_account.storage_scheme,
         )
         .await
-        .switch()
-        .attach_printable("There was an error in incoming webhook body decoding")?;
-
-    request_details.body = &decoded_body;
-
-    let event_type = match connector
-        .get_webhook_event_type(&request_details)
-        .allow_webhook_event_type_not_found(
-            state
-                .clone()
-                .conf
-                .webhooks
-                .ignore_error
-                .event_type
-                .unwrap_or(true),
-        )
-        .switch()
-        .attach_printable("Could not find event type in incoming webhook body")?
-    {
-        Some(event_type) => event_type,
-        // Early return allows us to acknowledge the webhooks that we do not support
-        None => {
-            logger::error!(
-                webhook_payload =? request_details.body,
-                "Failed while identifying the event type",
-            );
-
-            metrics::WEBHOOK_EVENT_TYPE_IDENTIFICATION_FAILURE_COUNT.add(
-                &metrics::CONTEXT,
-                1,
-                &[
-                    metrics::KeyValue::new(MERCHANT_ID, merchant_account.merchant_id.clone()),
-                    metrics::KeyValue::new("connector", connector_name.to_string()),
-                ],
-            );
-
-            return connector
-                .get_webhook_api_response(&request_details)
-                .switch()
-                .attach_printable("Failed while early return in case of event type parsing");
-        }
-    };
+        .map_err(|error| {
+            logger::warn!(
+                ?error,
+                "Failed to fetch the attempts of the payment of the webhook"
+            )
+        })
+        .ok()?;
+    attempts.sort_by(|attempt, other| other.created_at.cmp(&attempt.created_at));
+    Some(
+        attempts
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect(),
+    )
+}
 
-    let process_webhook_further = utils::lookup_webhook_event(
-        &*state.clone().store,
-        connector_name.as_str(),
+/// Renders the event in each version of the payloads, for the merchant to see how its webhooks
+/// would change on moving to another version. The object of the event is read in its current
+/// state, which may differ from the state in which the event was sent.
+pub async fn preview_webhook_event(
+    state: AppState,
+    merchant_id: String,
+    event_id: String,
+) -> RouterResponse<api_models::webhooks::WebhookEventPreviewResponse> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let event = db
+        .find_event_by_event_id(&event_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+            message: "Webhook event not found".to_string(),
+        })?;
+
+    // The events are not stored with the merchant, the object of the event is looked up among the
+    // objects of the merchant instead
+    let content =
+        get_outgoing_webhook_content(&state, &merchant_account, key_store, &event).await?;
+    let timeline = payment_timeline::get_outgoing_webhook_timeline(
+        db,
         &merchant_account.merchant_id,
-        &event_type,
+        &content,
     )
     .await;
+    let webhook = api::OutgoingWebhook {
+        merchant_id: merchant_account.merchant_id.clone(),
+        event_id: event.event_id.clone(),
+        event_type: event.event_type,
+        content,
+        timestamp: event.created_at,
+        webhook_version: None,
+        error: None,
+        attempts: None,
+        timeline,
+    };
 
-    logger::info!(process_webhook=?process_webhook_further);
-    logger::info!(event_type=?event_type);
+    // The preview is shown to the merchant, so the payloads are not redacted
+    let mut renderer = OutgoingWebhookRenderer::new(&state, &merchant_account, webhook, None);
+    let mut payloads = Vec::new();
+    for payload_version in api_enums::WebhookPayloadVersion::iter() {
+        payloads.push(renderer.render_webhook(payload_version).await);
+    }
 
-    let flow_type: api::WebhookFlow = event_type.to_owned().into();
-    if process_webhook_further && !matches!(flow_type, api::WebhookFlow::ReturnResponse) {
-        let object_ref_id = connector
-            .get_webhook_object_reference_id(&request_details)
-            .switch()
-            .attach_printable("Could not find object reference id in incoming webhook body")?;
-        let connector_enum = api_models::enums::Connector::from_str(&connector_name)
-            .into_report()
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "connector",
-            })
-            .attach_printable_lazy(|| {
-                format!("unable to parse connector name {connector_name:?}")
-            })?;
-        let connectors_with_source_verification_call = &state.conf.webhook_source_verification_call;
-
-        let source_verified = if connectors_with_source_verification_call
-            .connectors_with_webhook_source_verification_call
-            .contains(&connector_enum)
-        {
-            connector
-                .verify_webhook_source_verification_call(
-                    &state,
-                    &merchant_account,
-                    merchant_connector_account.clone(),
-                    &connector_name,
-                    &request_details,
+    Ok(services::ApplicationResponse::Json(
+        api_models::webhooks::WebhookEventPreviewResponse {
+            event_id: event.event_id,
+            event_type: event.event_type,
+            payloads,
+        },
+    ))
+}
+
+/// The current state of the object of the event, as sent in the webhooks
+async fn get_outgoing_webhook_content(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    event: &storage::Event,
+) -> CustomResult<api::OutgoingWebhookContent, errors::ApiErrorResponse> {
+    let db = &*state.store;
+    match event.primary_object_type {
+        enums::EventObjectType::PaymentDetails => {
+            let payments_response =
+                payments::payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+                    state.clone(),
+                    merchant_account.clone(),
+                    key_store,
+                    payments::operations::PaymentStatus,
+                    api::PaymentsRetrieveRequest {
+                        resource_id: api::PaymentIdType::PaymentIntentId(
+                            event.primary_object_id.clone(),
+                        ),
+                        merchant_id: Some(merchant_account.merchant_id.clone()),
+                        force_sync: false,
+                        connector: None,
+                        param: None,
+                        merchant_connector_details: None,
+                        client_secret: None,
+                        expand_attempts: None,
+                        expand_captures: None,
+                    },
+                    services::AuthFlow::Merchant,
+                    payments::CallConnectorAction::Avoid,
+                    HeaderPayload::default(),
+                )
+                .await?;
+            match payments_response {
+                services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => Ok(
+                    api::OutgoingWebhookContent::PaymentDetails(payments_response),
+                ),
+                _ => Err(errors::ApiErrorResponse::InternalServerError)
+                    .into_report()
+                    .attach_printable("received non-json response from payments core"),
+            }
+        }
+        enums::EventObjectType::RefundDetails => {
+            let refund = db
+                .find_refund_by_merchant_id_refund_id(
+                    &merchant_account.merchant_id,
+                    &event.primary_object_id,
+                    merchant_account.storage_scheme,
                 )
                 .await
-                .or_else(|error| match error.current_context() {
-                    errors::ConnectorError::WebhookSourceVerificationFailed => {
-                        logger::error!(?error, "Source Verification Failed");
-                        Ok(false)
-                    }
-                    _ => Err(error),
-                })
-                .switch()
-                .attach_printable("There was an issue in incoming webhook source verification")?
-        } else {
-            connector
-                .verify_webhook_source(
-                    &request_details,
-                    &merchant_account,
-                    merchant_connector_account.clone(),
-                    connector_name.as_str(),
+                .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+            Ok(api::OutgoingWebhookContent::RefundDetails(
+                refund.foreign_into(),
+            ))
+        }
+        enums::EventObjectType::DisputeDetails => {
+            let dispute = db
+                .find_dispute_by_merchant_id_dispute_id(
+                    &merchant_account.merchant_id,
+                    &event.primary_object_id,
                 )
                 .await
-                .or_else(|error| match error.current_context() {
-                    errors::ConnectorError::WebhookSourceVerificationFailed => {
-                        logger::error!(?error, "Source Verification Failed");
-                        Ok(false)
-                    }
-                    _ => Err(error),
-                })
-                .switch()
-                .attach_printable("There was an issue in incoming webhook source verification")?
-        };
+                .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound {
+                    dispute_id: event.primary_object_id.clone(),
+                })?;
+            Ok(api::OutgoingWebhookContent::DisputeDetails(Box::new(
+                dispute.foreign_into(),
+            )))
+        }
+        // The alerts are stored by the identifier of the connector, they cannot be looked up by
+        // the object id of their events
+        enums::EventObjectType::AlertDetails => Err(errors::ApiErrorResponse::NotSupported {
+            message: "Previewing the webhook events of alerts".to_string(),
+        })
+        .into_report(),
+    }
+}
 
-        if source_verified {
-            metrics::WEBHOOK_SOURCE_VERIFIED_COUNT.add(
+/// The business profile of the payment, refund or dispute of the webhook, `None` for the webhooks
+/// of the other objects
+async fn get_webhook_business_profile(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    content: &api::OutgoingWebhookContent,
+) -> Option<storage::business_profile::BusinessProfile> {
+    let profile_id = match content {
+        api::OutgoingWebhookContent::PaymentDetails(payments_response) => {
+            payments_response.profile_id.clone()
+        }
+        api::OutgoingWebhookContent::RefundDetails(refund_response) => state
+            .store
+            .find_refund_by_merchant_id_refund_id(
+                &merchant_account.merchant_id,
+                &refund_response.refund_id,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .map_err(|error| logger::warn!(?error, "Failed to fetch the refund of the webhook"))
+            .ok()
+            .and_then(|refund| refund.profile_id),
+        api::OutgoingWebhookContent::DisputeDetails(dispute_response) => state
+            .store
+            .find_dispute_by_merchant_id_dispute_id(
+                &merchant_account.merchant_id,
+                &dispute_response.dispute_id,
+            )
+            .await
+            .map_err(|error| logger::warn!(?error, "Failed to fetch the dispute of the webhook"))
+            .ok()
+            .and_then(|dispute| dispute.profile_id),
+        api::OutgoingWebhookContent::AlertDetails(_) => None,
+    }?;
+
+    state
+        .store
+        .find_business_profile_by_profile_id(&profile_id)
+        .await
+        .map_err(|error| {
+            logger::warn!(
+                ?error,
+                "Failed to fetch the business profile of the webhook"
+            )
+        })
+        .ok()
+}
+
+/// Returns the webhook subscriptions the event is delivered to, `None` when no subscriptions are
+/// configured and the event is delivered to the webhook url. The subscriptions of the business
+/// profile of the webhook take precedence over the ones of the merchant account. The secret of each
+/// subscription is set from the encrypted keys of the profile, the subscriptions which the profile
+/// inherited from the merchant account using the keys of the merchant account.
+async fn get_webhook_subscriptions(
+    state: &AppState,
+    merchant_id: &str,
+    business_profile: Option<&storage::business_profile::BusinessProfile>,
+    merchant_subscriptions: Option<Vec<api_models::admin::WebhookSubscription>>,
+    merchant_subscription_secrets: Option<HashMap<String, masking::Secret<String>>>,
+) -> Option<Vec<api_models::admin::WebhookSubscription>> {
+    let profile_subscriptions = business_profile
+        .and_then(|business_profile| business_profile.webhook_details.clone())
+        .and_then(|webhook_details| {
+            webhook_details
+                .parse_value::<api::WebhookDetails>("WebhookDetails")
+                .map_err(|error| {
+                    logger::warn!(?error, "Failed to parse the webhook details of the profile")
+                })
+                .ok()
+        })
+        .and_then(|webhook_details| webhook_details.subscriptions);
+
+    let mut subscription_secrets = merchant_subscription_secrets.unwrap_or_default();
+    let subscriptions = match (profile_subscriptions, business_profile) {
+        (Some(profile_subscriptions), Some(business_profile)) => {
+            let profile_subscription_secrets =
+                get_business_profile_subscription_secrets(state, merchant_id, business_profile)
+                    .await;
+            subscription_secrets.extend(profile_subscription_secrets);
+            profile_subscriptions
+        }
+        _ => merchant_subscriptions?,
+    };
+
+    Some(
+        subscriptions
+            .into_iter()
+            .map(|mut subscription| {
+                subscription.secret = subscription_secrets
+                    .get(&subscription.subscription_id)
+                    .cloned();
+                subscription
+            })
+            .collect(),
+    )
+}
+
+/// The keys of the subscriptions of the business profile, none when they cannot be decrypted
+async fn get_business_profile_subscription_secrets(
+    state: &AppState,
+    merchant_id: &str,
+    business_profile: &storage::business_profile::BusinessProfile,
+) -> HashMap<String, masking::Secret<String>> {
+    let db = &*state.store;
+    let webhook_auth_details = match db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+    {
+        Ok(key_store) => {
+            admin::get_business_profile_webhook_auth_details(business_profile, &key_store).await
+        }
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError)),
+    };
+
+    webhook_auth_details
+        .map_err(|error| {
+            logger::error!(
+                ?error,
+                "Failed to fetch the webhook authentication details of the profile"
+            )
+        })
+        .ok()
+        .flatten()
+        .and_then(|webhook_auth_details| webhook_auth_details.subscription_secrets)
+        .unwrap_or_default()
+}
+
+/// Delivers the event to each of the subscriptions matching its event type, in the payload version
+/// of the subscription and signed with its secret, and records the outcome of each delivery. An
+/// event matching none of the subscriptions is recorded as skipped.
+async fn deliver_webhook_to_subscriptions<W: types::OutgoingWebhookType>(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: &str,
+    event_type: enums::EventType,
+    renderer: &mut OutgoingWebhookRenderer<'_>,
+    payload_version: api_enums::WebhookPayloadVersion,
+    subscriptions: Vec<api_models::admin::WebhookSubscription>,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let matching_subscriptions = subscriptions
+        .into_iter()
+        .filter(|subscription| subscription.matches(event_type))
+        .collect::<Vec<_>>();
+
+    if matching_subscriptions.is_empty() {
+        logger::info!(
+            event_id,
+            %event_type,
+            "Webhook event matched none of the subscriptions"
+        );
+        return record_webhook_delivery(
+            state,
+            merchant_id,
+            event_id,
+            None,
+            enums::WebhookDeliveryStatus::Skipped,
+            None,
+            None,
+        )
+        .await;
+    }
+
+    let previous_payload_versions =
+        get_previous_payload_versions(state, merchant_id, event_id).await;
+
+    let mut is_delivered_to_all = true;
+    for subscription in matching_subscriptions {
+        // A redelivery of the event uses the version of the earlier delivery, even if the version
+        // of the subscription has been changed since
+        let subscription_payload_version = previous_payload_versions
+            .get(&subscription.subscription_id)
+            .copied()
+            .or(subscription.payload_version)
+            .unwrap_or(payload_version);
+        let (outgoing_webhook, outgoing_webhook_string) =
+            renderer.render::<W>(subscription_payload_version).await?;
+
+        // The events are never delivered unsigned
+        let Some(secret) = subscription.secret.as_ref() else {
+            logger::error!(
+                subscription_id = %subscription.subscription_id,
+                "Webhook subscription has no secret, the event is not delivered to it"
+            );
+            is_delivered_to_all = false;
+            record_webhook_delivery(
+                state,
+                merchant_id,
+                event_id,
+                Some(subscription.subscription_id),
+                enums::WebhookDeliveryStatus::Failed,
+                None,
+                outgoing_webhook.get_payload_version(),
+            )
+            .await?;
+            continue;
+        };
+        let signature = outgoing_webhook.get_outgoing_webhooks_signature(
+            &outgoing_webhook_string,
+            Some(secret.peek().to_owned()),
+        )?;
+
+        // The authentication details of the merchant are meant for the webhook url, they are not
+        // sent to the endpoints of the subscriptions
+        let response = send_webhook_to_endpoint::<W>(
+            state,
+            merchant_id,
+            subscription.url.peek(),
+            signature,
+            outgoing_webhook.get_payload_version(),
+            outgoing_webhook_string,
+            api_models::admin::WebhookAuthDetails::default(),
+        )
+        .await;
+
+        let (status, response_status_code) = match response {
+            Ok(status_code) if is_success_status_code(status_code) => {
+                (enums::WebhookDeliveryStatus::Delivered, Some(status_code))
+            }
+            Ok(status_code) => (enums::WebhookDeliveryStatus::Failed, Some(status_code)),
+            Err(error) => {
+                logger::warn!(
+                    ?error,
+                    subscription_id = %subscription.subscription_id,
+                    "Failed to deliver the webhook to the subscription"
+                );
+                (enums::WebhookDeliveryStatus::Failed, None)
+            }
+        };
+        is_delivered_to_all &= status == enums::WebhookDeliveryStatus::Delivered;
+
+        record_webhook_delivery(
+            state,
+            merchant_id,
+            event_id,
+            Some(subscription.subscription_id),
+            status,
+            response_status_code,
+            outgoing_webhook.get_payload_version(),
+        )
+        .await?;
+    }
+
+    if !is_delivered_to_all {
+        // [#217]: Schedule webhook for retry.
+        Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
+    }
+
+    let update_event = storage::EventUpdate::UpdateWebhookNotified {
+        is_webhook_notified: Some(true),
+    };
+    state
+        .store
+        .update_event(event_id.to_owned(), update_event)
+        .await
+        .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
+
+    Ok(())
+}
+
+/// The payload versions of the earlier deliveries of the event, by the subscription they were
+/// delivered to
+async fn get_previous_payload_versions(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: &str,
+) -> HashMap<String, api_enums::WebhookPayloadVersion> {
+    state
+        .store
+        .find_webhook_deliveries_by_merchant_id_event_id(merchant_id, event_id)
+        .await
+        .map_err(|error| {
+            logger::warn!(
+                ?error,
+                "Failed to fetch the earlier deliveries of the webhook event"
+            )
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|webhook_delivery| {
+            webhook_delivery
+                .subscription_id
+                .zip(webhook_delivery.payload_version)
+        })
+        .collect()
+}
+
+async fn record_webhook_delivery(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: &str,
+    subscription_id: Option<String>,
+    status: enums::WebhookDeliveryStatus,
+    response_status_code: Option<u16>,
+    payload_version: Option<api_enums::WebhookPayloadVersion>,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let webhook_delivery = storage::WebhookDeliveryNew {
+        delivery_id: generate_id(consts::ID_LENGTH, "whd"),
+        event_id: event_id.to_owned(),
+        merchant_id: merchant_id.to_owned(),
+        subscription_id,
+        status,
+        response_status_code: response_status_code.map(i32::from),
+        payload_version,
+    };
+    state
+        .store
+        .insert_webhook_delivery(webhook_delivery)
+        .await
+        .change_context(errors::WebhooksFlowError::WebhookDeliveryRecordingFailed)?;
+    Ok(())
+}
+
+fn is_success_status_code(status_code: u16) -> bool {
+    (200..300).contains(&status_code)
+}
+
+/// Sends the outgoing webhook to the endpoint, returning the status code of the response
+async fn send_webhook_to_endpoint<W: types::OutgoingWebhookType>(
+    state: &AppState,
+    merchant_id: &str,
+    url: &str,
+    signature: Option<String>,
+    payload_version: Option<api_enums::WebhookPayloadVersion>,
+    outgoing_webhook_string: String,
+    webhook_auth_details: api_models::admin::WebhookAuthDetails,
+) -> CustomResult<u16, errors::WebhooksFlowError> {
+    let mut header = vec![(
+        reqwest::header::CONTENT_TYPE.to_string(),
+        "application/json".into(),
+    )];
+
+    if let Some(signature) = signature {
+        W::add_webhook_header(&mut header, signature)
+    }
+
+    if let Some(payload_version) = payload_version {
+        header.push((
+            headers::X_WEBHOOK_VERSION.to_string(),
+            payload_version.to_string().into(),
+        ));
+    }
+
+    header.extend(get_webhook_auth_headers(&webhook_auth_details));
+
+    let request = services::RequestBuilder::new()
+        .method(services::Method::Post)
+        .url(url)
+        .attach_default_headers()
+        .headers(header)
+        .add_certificate(
+            webhook_auth_details
+                .client_certificate
+                .map(|certificate| consts::BASE64_ENGINE.encode(certificate.peek())),
+        )
+        .add_certificate_key(
+            webhook_auth_details
+                .client_certificate_key
+                .map(|certificate_key| consts::BASE64_ENGINE.encode(certificate_key.peek())),
+        )
+        .body(Some(
+            router_types::RequestBody::log_and_get_request_body(outgoing_webhook_string, Ok)
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)?,
+        ))
+        .build();
+
+    let response = state
+        .api_client
+        .send_request(state, request, Some(OUTGOING_WEBHOOK_TIMEOUT_SECS), false)
+        .await;
+
+    metrics::WEBHOOK_OUTGOING_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::KeyValue::new(MERCHANT_ID, merchant_id.to_owned())],
+    );
+    logger::debug!(outgoing_webhook_response=?response);
+
+    let status_code = response
+        .change_context(errors::WebhooksFlowError::CallToMerchantFailed)?
+        .status()
+        .as_u16();
+
+    if is_success_status_code(status_code) {
+        metrics::WEBHOOK_OUTGOING_RECEIVED_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new(MERCHANT_ID, merchant_id.to_owned())],
+        );
+    } else {
+        metrics::WEBHOOK_OUTGOING_NOT_RECEIVED_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new(MERCHANT_ID, merchant_id.to_owned())],
+        );
+    }
+
+    Ok(status_code)
+}
+
+/// Builds the custom headers and the authorization header configured by the merchant for the
+/// outgoing webhooks
+fn get_webhook_auth_headers(
+    webhook_auth_details: &api_models::admin::WebhookAuthDetails,
+) -> Vec<(String, services::request::Maskable<String>)> {
+    let mut headers = webhook_auth_details
+        .custom_headers
+        .iter()
+        .flatten()
+        .map(|(name, value)| (name.to_owned(), value.peek().to_owned().into_masked()))
+        .collect::<Vec<_>>();
+
+    if let Some(authentication) = webhook_auth_details.authentication.as_ref() {
+        let authorization = match authentication {
+            WebhookAuthentication::Basic { username, password } => format!(
+                "Basic {}",
+                consts::BASE64_ENGINE.encode(format!("{username}:{}", password.peek()))
+            ),
+            WebhookAuthentication::Bearer { token } => format!("Bearer {}", token.peek()),
+        };
+        headers.push((
+            reqwest::header::AUTHORIZATION.to_string(),
+            authorization.into_masked(),
+        ));
+    }
+
+    headers
+}
+
+#[instrument(skip_all)]
+pub async fn webhooks_core<W: types::OutgoingWebhookType>(
+    state: AppState,
+    req: &actix_web::HttpRequest,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    connector_name_or_mca_id: &str,
+    body: actix_web::web::Bytes,
+) -> RouterResponse<serde_json::Value> {
+    metrics::WEBHOOK_INCOMING_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::KeyValue::new(
+            MERCHANT_ID,
+            merchant_account.merchant_id.clone(),
+        )],
+    );
+    let mut request_details = api::IncomingWebhookRequestDetails {
+        method: req.method().clone(),
+        uri: req.uri().clone(),
+        headers: req.headers(),
+        query_params: req.query_string().to_string(),
+        body: &body,
+    };
+
+    let (merchant_connector_account, connector) = fetch_mca_and_connector(
+        state.clone(),
+        &merchant_account,
+        connector_name_or_mca_id,
+        &key_store,
+        &request_details,
+    )
+    .await?;
+
+    let connector_name = merchant_connector_account.clone().connector_name;
+
+    let connector = connector.connector;
+
+    let decoded_body = connector
+        .decode_webhook_body(
+            &*state.clone().store,
+            &request_details,
+            &merchant_account.merchant_id,
+        )
+        .await
+        .switch()
+        .attach_printable("There was an error in incoming webhook body decoding")?;
+
+    request_details.body = &decoded_body;
+
+    let event_type = match connector
+        .get_webhook_event_type(&request_details)
+        .allow_webhook_event_type_not_found(
+            state
+                .clone()
+                .conf
+                .webhooks
+                .ignore_error
+                .event_type
+                .unwrap_or(true),
+        )
+        .switch()
+        .attach_printable("Could not find event type in incoming webhook body")?
+    {
+        Some(event_type) => event_type,
+        // Early return allows us to acknowledge the webhooks that we do not support
+        None => {
+            logger::error!(
+                webhook_payload =? request_details.body,
+                "Failed while identifying the event type",
+            );
+
+            metrics::WEBHOOK_EVENT_TYPE_IDENTIFICATION_FAILURE_COUNT.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::KeyValue::new(MERCHANT_ID, merchant_account.merchant_id.clone()),
+                    metrics::KeyValue::new("connector", connector_name.to_string()),
+                ],
+            );
+
+            return connector
+                .get_webhook_api_response(&request_details)
+                .switch()
+                .attach_printable("Failed while early return in case of event type parsing");
+        }
+    };
+
+    let process_webhook_further = utils::lookup_webhook_event(
+        &*state.clone().store,
+        connector_name.as_str(),
+        &merchant_account.merchant_id,
+        &event_type,
+    )
+    .await;
+
+    logger::info!(process_webhook=?process_webhook_further);
+    logger::info!(event_type=?event_type);
+
+    let flow_type: api::WebhookFlow = event_type.to_owned().into();
+    if process_webhook_further && !matches!(flow_type, api::WebhookFlow::ReturnResponse) {
+        let source_verified = verify_webhook_source_with_previous_credentials(
+            &state,
+            *connector,
+            &merchant_account,
+            &merchant_connector_account,
+            &connector_name,
+            &request_details,
+        )
+        .await?;
+
+        if source_verified {
+            metrics::WEBHOOK_SOURCE_VERIFIED_COUNT.add(
                 &metrics::CONTEXT,
                 1,
                 &[metrics::KeyValue::new(
@@ -895,214 +1660,1824 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
                     merchant_account.merchant_id.clone(),
                 )],
             );
-        } else if connector.is_webhook_source_verification_mandatory() {
-            // if webhook consumption is mandatory for connector, fail webhook
-            // so that merchant can retrigger it after updating merchant_secret
-            return Err(errors::ApiErrorResponse::WebhookAuthenticationFailed.into());
+        } else {
+            metrics::WEBHOOK_SOURCE_VERIFICATION_FAILED_COUNT.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::KeyValue::new(MERCHANT_ID, merchant_account.merchant_id.clone()),
+                    metrics::KeyValue::new("connector", connector_name.to_string()),
+                ],
+            );
+
+            if connector.is_webhook_source_verification_mandatory() {
+                // if webhook consumption is mandatory for connector, fail webhook
+                // so that merchant can retrigger it after updating merchant_secret
+                return Err(errors::ApiErrorResponse::WebhookAuthenticationFailed.into());
+            }
+        }
+
+        logger::info!(source_verified=?source_verified);
+
+        let event_object = connector
+            .get_webhook_resource_object(&request_details)
+            .switch()
+            .attach_printable("Could not find resource object in incoming webhook body")?;
+
+        // The alerts are matched to the payment from their details, as they may carry only the
+        // acquirer reference number of the payment
+        if matches!(flow_type, api::WebhookFlow::Alert) {
+            alerts_incoming_webhook_flow::<W>(
+                state.clone(),
+                merchant_account,
+                key_store,
+                source_verified,
+                *connector,
+                &request_details,
+                event_object,
+            )
+            .await
+            .attach_printable("Incoming webhook flow for alerts failed")?;
+
+            return connector
+                .get_webhook_api_response(&request_details)
+                .switch()
+                .attach_printable("Could not get incoming webhook api response from connector");
+        }
+
+        let object_ref_id = connector
+            .get_webhook_object_reference_id(&request_details)
+            .switch()
+            .attach_printable("Could not find object reference id in incoming webhook body")?;
+        let webhook_details = api::IncomingWebhookDetails {
+            object_reference_id: object_ref_id,
+            resource_object: Encode::<serde_json::Value>::encode_to_vec(&event_object)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable(
+                    "There was an issue when encoding the incoming webhook body to bytes",
+                )?,
+        };
+
+        match flow_type {
+            api::WebhookFlow::Payment => {
+                // The fee and the acquirer reference number are stored as reported, so they are read
+                // only from verified webhooks
+                let connector_fee = if source_verified {
+                    connector
+                        .get_webhook_connector_fee(&request_details)
+                        .switch()
+                        .attach_printable(
+                            "Could not get connector fee from incoming webhook body",
+                        )?
+                } else {
+                    None
+                };
+                let acquirer_reference_number = if source_verified {
+                    connector
+                        .get_webhook_acquirer_reference_number(&request_details)
+                        .switch()
+                        .attach_printable(
+                            "Could not get acquirer reference number from incoming webhook body",
+                        )?
+                } else {
+                    None
+                };
+
+                payments_incoming_webhook_flow::<W>(
+                    state.clone(),
+                    merchant_account,
+                    key_store,
+                    webhook_details,
+                    connector_fee,
+                    acquirer_reference_number,
+                    source_verified,
+                )
+                .await
+                .attach_printable("Incoming webhook flow for payments failed")?
+            }
+
+            api::WebhookFlow::Refund => refunds_incoming_webhook_flow::<W>(
+                state.clone(),
+                merchant_account,
+                key_store,
+                webhook_details,
+                connector_name.as_str(),
+                source_verified,
+                event_type,
+            )
+            .await
+            .attach_printable("Incoming webhook flow for refunds failed")?,
+
+            api::WebhookFlow::Dispute => disputes_incoming_webhook_flow::<W>(
+                state.clone(),
+                merchant_account,
+                webhook_details,
+                source_verified,
+                *connector,
+                &request_details,
+                event_type,
+            )
+            .await
+            .attach_printable("Incoming webhook flow for disputes failed")?,
+
+            api::WebhookFlow::BankTransfer => bank_transfer_webhook_flow::<W>(
+                state.clone(),
+                merchant_account,
+                key_store,
+                webhook_details,
+                source_verified,
+            )
+            .await
+            .attach_printable("Incoming bank-transfer webhook flow failed")?,
+
+            api::WebhookFlow::ReturnResponse => {}
+
+            _ => Err(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Unsupported Flow Type received in incoming webhooks")?,
+        }
+    } else {
+        metrics::WEBHOOK_INCOMING_FILTERED_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new(
+                MERCHANT_ID,
+                merchant_account.merchant_id.clone(),
+            )],
+        );
+    }
+
+    let response = connector
+        .get_webhook_api_response(&request_details)
+        .switch()
+        .attach_printable("Could not get incoming webhook api response from connector")?;
+
+    Ok(response)
+}
+
+async fn update_active_attempt_from_webhook(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+    connector_fee: Option<router_types::ConnectorFee>,
+    acquirer_reference_number: Option<String>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_attempt = match connector_fee {
+        Some(connector_fee) => {
+            update_connector_fee(
+                db,
+                payment_attempt,
+                connector_fee,
+                merchant_account.storage_scheme,
+            )
+            .await?
+        }
+        None => payment_attempt,
+    };
+
+    if let Some(acquirer_reference_number) = acquirer_reference_number {
+        update_acquirer_reference_number(
+            db,
+            payment_attempt,
+            acquirer_reference_number,
+            merchant_account.storage_scheme,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Store the fee reported by the connector on the payment attempt, irrespective of the status of
+/// the attempt
+pub async fn update_connector_fee(
+    db: &dyn StorageInterface,
+    payment_attempt: storage::PaymentAttempt,
+    connector_fee: router_types::ConnectorFee,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> CustomResult<storage::PaymentAttempt, errors::ApiErrorResponse> {
+    let is_fee_stored = payment_attempt.connector_fee_amount == Some(connector_fee.fee_amount)
+        && payment_attempt.fee_currency == Some(connector_fee.fee_currency)
+        && payment_attempt.net_amount == connector_fee.net_amount;
+    if is_fee_stored {
+        return Ok(payment_attempt);
+    }
+
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::ConnectorFeeUpdate {
+            connector_fee_amount: connector_fee.fee_amount,
+            fee_currency: connector_fee.fee_currency,
+            net_amount: connector_fee.net_amount,
+        },
+        storage_scheme,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+}
+
+/// Store the acquirer reference number reported by the connector on the payment attempt, which is
+/// usually assigned only once the payment is captured
+pub async fn update_acquirer_reference_number(
+    db: &dyn StorageInterface,
+    payment_attempt: storage::PaymentAttempt,
+    acquirer_reference_number: String,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> CustomResult<storage::PaymentAttempt, errors::ApiErrorResponse> {
+    if payment_attempt.acquirer_reference_number.as_ref() == Some(&acquirer_reference_number) {
+        return Ok(payment_attempt);
+    }
+
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::AcquirerReferenceNumberUpdate {
+            acquirer_reference_number,
+        },
+        storage_scheme,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+}
+
+#[inline]
+pub async fn get_payment_id(
+    db: &dyn StorageInterface,
+    payment_id: &api::PaymentIdType,
+    merchant_id: &str,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> errors::RouterResult<String> {
+    let pay_id = || async {
+        match payment_id {
+            api_models::payments::PaymentIdType::PaymentIntentId(ref id) => Ok(id.to_string()),
+            api_models::payments::PaymentIdType::ConnectorTransactionId(ref id) => db
+                .find_payment_attempt_by_merchant_id_connector_txn_id(
+                    merchant_id,
+                    id,
+                    storage_scheme,
+                )
+                .await
+                .map(|p| p.payment_id),
+            api_models::payments::PaymentIdType::PaymentAttemptId(ref id) => db
+                .find_payment_attempt_by_attempt_id_merchant_id(id, merchant_id, storage_scheme)
+                .await
+                .map(|p| p.payment_id),
+            api_models::payments::PaymentIdType::PreprocessingId(ref id) => db
+                .find_payment_attempt_by_preprocessing_id_merchant_id(
+                    id,
+                    merchant_id,
+                    storage_scheme,
+                )
+                .await
+                .map(|p| p.payment_id),
+        }
+    };
+
+    pay_id()
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+}
+
+/// Verifies the source of the webhook with the current credentials of the merchant connector
+/// account, and with the credentials replaced in the last rotation until they expire, as the
+/// connector keeps signing the webhooks with them until the rotation is completed on its side
+async fn verify_webhook_source_with_previous_credentials(
+    state: &AppState,
+    connector: &(dyn api::Connector + Sync),
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    connector_name: &str,
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
+) -> CustomResult<bool, errors::ApiErrorResponse> {
+    let source_verified = verify_webhook_source(
+        state,
+        connector,
+        merchant_account,
+        merchant_connector_account.clone(),
+        connector_name,
+        request_details,
+    )
+    .await?;
+    let previous_merchant_connector_account = merchant_connector_account
+        .with_previous_credentials(common_utils::date_time::now())
+        .filter(|_| !source_verified);
+
+    match previous_merchant_connector_account {
+        Some(previous_merchant_connector_account) => {
+            let source_verified = verify_webhook_source(
+                state,
+                connector,
+                merchant_account,
+                previous_merchant_connector_account,
+                connector_name,
+                request_details,
+            )
+            .await?;
+            if source_verified {
+                logger::info!(
+                    audit_event = "webhook_verified_with_previous_credentials",
+                    merchant_id = %merchant_account.merchant_id,
+                    merchant_connector_id = %merchant_connector_account.merchant_connector_id,
+                    "Verified the webhook with the credentials replaced in the last rotation"
+                );
+            }
+            Ok(source_verified)
+        }
+        None => Ok(source_verified),
+    }
+}
+
+async fn verify_webhook_source(
+    state: &AppState,
+    connector: &(dyn api::Connector + Sync),
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: domain::MerchantConnectorAccount,
+    connector_name: &str,
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
+) -> CustomResult<bool, errors::ApiErrorResponse> {
+    let connector_enum = api_models::enums::Connector::from_str(connector_name)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "connector",
+        })
+        .attach_printable_lazy(|| format!("unable to parse connector name {connector_name:?}"))?;
+    let connectors_with_source_verification_call = &state.conf.webhook_source_verification_call;
+
+    if connectors_with_source_verification_call
+        .connectors_with_webhook_source_verification_call
+        .contains(&connector_enum)
+    {
+        connector
+            .verify_webhook_source_verification_call(
+                state,
+                merchant_account,
+                merchant_connector_account,
+                connector_name,
+                request_details,
+            )
+            .await
+            .or_else(|error| match error.current_context() {
+                errors::ConnectorError::WebhookSourceVerificationFailed => {
+                    logger::error!(?error, "Source Verification Failed");
+                    Ok(false)
+                }
+                _ => Err(error),
+            })
+            .switch()
+            .attach_printable("There was an issue in incoming webhook source verification")
+    } else {
+        connector
+            .verify_webhook_source(
+                request_details,
+                merchant_account,
+                merchant_connector_account,
+                connector_name,
+            )
+            .await
+            .or_else(|error| match error.current_context() {
+                errors::ConnectorError::WebhookSourceVerificationFailed => {
+                    logger::error!(?error, "Source Verification Failed");
+                    Ok(false)
+                }
+                _ => Err(error),
+            })
+            .switch()
+            .attach_printable("There was an issue in incoming webhook source verification")
+    }
+}
+
+/// Merchant connector account through which the payment or the refund of the webhook was
+/// processed, when it is recorded on the payment attempt or on the refund
+async fn get_merchant_connector_id_of_webhook_object(
+    state: &AppState,
+    object_reference_id: &api::ObjectReferenceId,
+    merchant_account: &domain::MerchantAccount,
+    connector_name: &str,
+) -> Option<String> {
+    match object_reference_id {
+        api::ObjectReferenceId::PaymentId(_) => {
+            let payment_attempt = get_payment_attempt_from_object_reference_id(
+                state,
+                object_reference_id.clone(),
+                merchant_account,
+            )
+            .await
+            .ok()?;
+            payment_attempt.merchant_connector_id.clone().or_else(|| {
+                payments::connector_account_selection::get_routed_merchant_connector_id(
+                    &payment_attempt,
+                    connector_name,
+                )
+            })
+        }
+        api::ObjectReferenceId::RefundId(refund_id_type) => {
+            let db = &*state.store;
+            let refund = match refund_id_type {
+                api_models::webhooks::RefundIdType::RefundId(id) => {
+                    db.find_refund_by_merchant_id_refund_id(
+                        &merchant_account.merchant_id,
+                        id,
+                        merchant_account.storage_scheme,
+                    )
+                    .await
+                }
+                api_models::webhooks::RefundIdType::ConnectorRefundId(id) => {
+                    db.find_refund_by_merchant_id_connector_refund_id_connector(
+                        &merchant_account.merchant_id,
+                        id,
+                        connector_name,
+                        merchant_account.storage_scheme,
+                    )
+                    .await
+                }
+            }
+            .ok()?;
+            refund.merchant_connector_id
         }
+    }
+}
 
-        logger::info!(source_verified=?source_verified);
+async fn fetch_mca_and_connector(
+    state: AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector_name_or_mca_id: &str,
+    key_store: &domain::MerchantKeyStore,
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
+) -> CustomResult<(domain::MerchantConnectorAccount, api::ConnectorData), errors::ApiErrorResponse>
+{
+    let db = &state.store;
+    if connector_name_or_mca_id.starts_with("mca_") {
+        let mca = db
+            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                &merchant_account.merchant_id,
+                connector_name_or_mca_id,
+                key_store,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                id: connector_name_or_mca_id.to_string(),
+            })
+            .attach_printable(
+                "error while fetching merchant_connector_account from connector_id",
+            )?;
 
-        let event_object = connector
-            .get_webhook_resource_object(&request_details)
+        let connector = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            &mca.connector_name,
+            api::GetToken::Connector,
+        )
+        .change_context(errors::ApiErrorResponse::InvalidRequestData {
+            message: "invalid connector name received".to_string(),
+        })
+        .attach_printable("Failed construction of ConnectorData")?;
+
+        Ok((mca, connector))
+    } else {
+        let connector = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            connector_name_or_mca_id,
+            api::GetToken::Connector,
+        )
+        .change_context(errors::ApiErrorResponse::InvalidRequestData {
+            message: "invalid connector name received".to_string(),
+        })
+        .attach_printable("Failed construction of ConnectorData")?;
+
+        let object_ref_id = connector
+            .connector
+            .get_webhook_object_reference_id(request_details)
             .switch()
-            .attach_printable("Could not find resource object in incoming webhook body")?;
+            .attach_printable("Could not find object reference id in incoming webhook body")?;
 
-        let webhook_details = api::IncomingWebhookDetails {
-            object_reference_id: object_ref_id,
-            resource_object: Encode::<serde_json::Value>::encode_to_vec(&event_object)
-                .change_context(errors::ApiErrorResponse::InternalServerError)
+        // The payments and refunds which record the merchant connector account they were processed
+        // through are resolved to that account, as the business profile may have several accounts
+        // of the connector
+        if let Some(merchant_connector_id) = get_merchant_connector_id_of_webhook_object(
+            &state,
+            &object_ref_id,
+            merchant_account,
+            connector_name_or_mca_id,
+        )
+        .await
+        {
+            let mca = db
+                .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                    &merchant_account.merchant_id,
+                    &merchant_connector_id,
+                    key_store,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                    id: merchant_connector_id.clone(),
+                })
                 .attach_printable(
-                    "There was an issue when encoding the incoming webhook body to bytes",
-                )?,
-        };
+                    "error while fetching merchant_connector_account of the webhook object",
+                )?;
+            if mca.connector_name == connector_name_or_mca_id {
+                return Ok((mca, connector));
+            }
+        }
 
-        match flow_type {
-            api::WebhookFlow::Payment => payments_incoming_webhook_flow::<W>(
-                state.clone(),
-                merchant_account,
+        let profile_id = helper_utils::get_profile_id_using_object_reference_id(
+            &*state.store,
+            object_ref_id,
+            merchant_account,
+            connector_name_or_mca_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "object reference id",
+        })
+        .attach_printable("Could not find profile id from object reference id")?;
+
+        let mca = db
+            .find_merchant_connector_account_by_profile_id_connector_name(
+                &profile_id,
+                connector_name_or_mca_id,
                 key_store,
-                webhook_details,
-                source_verified,
             )
             .await
-            .attach_printable("Incoming webhook flow for payments failed")?,
+            .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                id: format!(
+                    "profile_id {profile_id} and connector name {connector_name_or_mca_id}"
+                ),
+            })
+            .attach_printable("error while fetching merchant_connector_account from profile_id")?;
 
-            api::WebhookFlow::Refund => refunds_incoming_webhook_flow::<W>(
-                state.clone(),
-                merchant_account,
-                key_store,
-                webhook_details,
-                connector_name.as_str(),
-                source_verified,
-                event_type,
+        Ok((mca, connector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connector_fee_enriches_succeeded_payment() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    status: enums::AttemptStatus::Charged,
+                    amount: 10000,
+                    currency: Some(enums::Currency::USD),
+                    ..Default::default()
+                },
+                storage_scheme,
             )
             .await
-            .attach_printable("Incoming webhook flow for refunds failed")?,
+            .unwrap();
+        assert_eq!(payment_attempt.connector_fee_amount, None);
+
+        // Fee reported in a webhook received after the payment has succeeded, in a currency other
+        // than that of the payment
+        let connector_fee = router_types::ConnectorFee {
+            fee_amount: 280,
+            fee_currency: enums::Currency::CAD,
+            net_amount: None,
+        };
+        let payment_attempt =
+            update_connector_fee(db, payment_attempt, connector_fee, storage_scheme)
+                .await
+                .unwrap();
+
+        assert_eq!(payment_attempt.status, enums::AttemptStatus::Charged);
+        assert_eq!(payment_attempt.amount, 10000);
+        assert_eq!(payment_attempt.currency, Some(enums::Currency::USD));
+        assert_eq!(payment_attempt.connector_fee_amount, Some(280));
+        assert_eq!(payment_attempt.fee_currency, Some(enums::Currency::CAD));
+        assert_eq!(payment_attempt.net_amount, None);
+
+        // A retried webhook with the same fee leaves the attempt as is
+        let modified_at = payment_attempt.modified_at;
+        let payment_attempt =
+            update_connector_fee(db, payment_attempt, connector_fee, storage_scheme)
+                .await
+                .unwrap();
+        assert_eq!(payment_attempt.modified_at, modified_at);
+    }
+
+    #[tokio::test]
+    async fn test_acquirer_reference_number_enriches_captured_payment() {
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let db: &dyn StorageInterface = &mockdb;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        // The authorization code is returned by the connector when the payment is authorized,
+        // whereas the acquirer reference number is assigned only once the payment is captured
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_2".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_2_1".to_string(),
+                    status: enums::AttemptStatus::Charged,
+                    amount: 10000,
+                    currency: Some(enums::Currency::USD),
+                    auth_code: Some("A1B2C3".to_string()),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.acquirer_reference_number, None);
+
+        let payment_attempt = update_acquirer_reference_number(
+            db,
+            payment_attempt,
+            "74537603289200123456789".to_string(),
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(payment_attempt.status, enums::AttemptStatus::Charged);
+        assert_eq!(payment_attempt.auth_code.as_deref(), Some("A1B2C3"));
+        assert_eq!(
+            payment_attempt.acquirer_reference_number.as_deref(),
+            Some("74537603289200123456789")
+        );
+
+        // A retried webhook with the same acquirer reference number leaves the attempt as is
+        let modified_at = payment_attempt.modified_at;
+        let payment_attempt = update_acquirer_reference_number(
+            db,
+            payment_attempt,
+            "74537603289200123456789".to_string(),
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+        assert_eq!(payment_attempt.modified_at, modified_at);
+
+        // The acquirer reference number is kept when the payment is synced afterwards and the
+        // connector does not return it
+        let payment_attempt = db
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::ResponseUpdate {
+                    status: enums::AttemptStatus::Charged,
+                    connector: None,
+                    connector_transaction_id: None,
+                    authentication_type: None,
+                    payment_method_id: None,
+                    mandate_id: None,
+                    connector_metadata: None,
+                    payment_token: None,
+                    error_code: None,
+                    error_message: None,
+                    error_reason: None,
+                    connector_response_reference_id: None,
+                    amount_capturable: Some(0),
+                    card_verification_results: None,
+                    connector_fee_amount: None,
+                    fee_currency: None,
+                    net_amount: None,
+                    auth_code: None,
+                    acquirer_reference_number: None,
+                    authorized_amount: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.auth_code.as_deref(), Some("A1B2C3"));
+        assert_eq!(
+            payment_attempt.acquirer_reference_number.as_deref(),
+            Some("74537603289200123456789")
+        );
+    }
+
+    #[test]
+    fn test_webhook_auth_headers() {
+        let webhook_auth_details = api_models::admin::WebhookAuthDetails {
+            custom_headers: Some(std::collections::HashMap::from([(
+                "x-webhook-key".to_string(),
+                masking::Secret::new("static_key".to_string()),
+            )])),
+            authentication: Some(WebhookAuthentication::Basic {
+                username: "user".to_string(),
+                password: masking::Secret::new("pass".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let headers = get_webhook_auth_headers(&webhook_auth_details)
+            .into_iter()
+            .map(|(name, value)| (name, value.into_inner()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            headers,
+            vec![
+                ("x-webhook-key".to_string(), "static_key".to_string()),
+                (
+                    "authorization".to_string(),
+                    "Basic dXNlcjpwYXNz".to_string()
+                ),
+            ]
+        );
+        assert!(get_webhook_auth_headers(&Default::default()).is_empty());
+    }
+
+    async fn get_state_with_stripe_connector_account() -> (AppState, domain::MerchantAccount) {
+        let state = AppState::with_storage(
+            crate::configs::settings::Settings::default(),
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
+            )
+            .await
+            .unwrap();
+
+        let merchant_account = db
+            .insert_merchant(
+                domain::MerchantAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    return_url: None,
+                    enable_payment_response_hash: false,
+                    payment_response_hash_key: None,
+                    redirect_to_merchant_with_http_post: false,
+                    merchant_name: None,
+                    merchant_details: None,
+                    webhook_details: None,
+                    sub_merchants_enabled: None,
+                    parent_merchant_id: None,
+                    publishable_key: None,
+                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+                    locker_id: None,
+                    metadata: None,
+                    routing_algorithm: None,
+                    primary_business_details: serde_json::Value::Null,
+                    frm_routing_algorithm: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    intent_fulfillment_time: None,
+                    payout_routing_algorithm: None,
+                    organization_id: None,
+                    is_recon_enabled: false,
+                    default_profile: None,
+                    recon_status: enums::ReconStatus::NotRequested,
+                    webhook_auth_details: None,
+                    default_currency: None,
+                    default_country: None,
+                    pii_policy: None,
+                },
+                &key_store,
+            )
+            .await
+            .unwrap();
+
+        db.insert_merchant_connector_account(
+            domain::MerchantConnectorAccount {
+                id: None,
+                merchant_id: "merchant_1".to_string(),
+                connector_name: "stripe".to_string(),
+                connector_account_details: domain::types::encrypt(
+                    masking::Secret::new(serde_json::json!({
+                        "auth_type": "HeaderKey",
+                        "api_key": "sk_old",
+                    })),
+                    key_store.key.get_inner().peek(),
+                )
+                .await
+                .unwrap(),
+                test_mode: None,
+                disabled: None,
+                merchant_connector_id: "mca_1".to_string(),
+                payment_methods_enabled: None,
+                connector_type: enums::ConnectorType::PaymentProcessor,
+                metadata: None,
+                frm_configs: None,
+                connector_label: None,
+                business_country: None,
+                business_label: None,
+                business_sub_label: None,
+                created_at: common_utils::date_time::now(),
+                modified_at: common_utils::date_time::now(),
+                connector_webhook_details: Some(masking::Secret::new(
+                    serde_json::json!({ "merchant_secret": "whsec_old" }),
+                )),
+                profile_id: Some("pro_default".to_string()),
+                applepay_verified_domains: None,
+                disabled_flows: None,
+                previous_credentials: None,
+            },
+            &key_store,
+        )
+        .await
+        .unwrap();
+
+        (state, merchant_account)
+    }
+
+    async fn is_stripe_webhook_verified(
+        state: &AppState,
+        merchant_account: &domain::MerchantAccount,
+        secret: &str,
+    ) -> bool {
+        use common_utils::crypto::SignMessage;
+
+        let db = &*state.store;
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                "merchant_1",
+                &db.get_master_key().to_vec().into(),
+            )
+            .await
+            .unwrap();
+        let merchant_connector_account = db
+            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                "merchant_1",
+                "mca_1",
+                &key_store,
+            )
+            .await
+            .unwrap();
+
+        let body = r#"{"id":"evt_1","type":"payment_intent.succeeded"}"#;
+        let signature = common_utils::crypto::HmacSha256
+            .sign_message(secret.as_bytes(), format!("1700000000.{body}").as_bytes())
+            .unwrap();
+        let mut headers = actix_web::http::header::HeaderMap::new();
+        headers.insert(
+            actix_web::http::header::HeaderName::from_static("stripe-signature"),
+            actix_web::http::header::HeaderValue::from_str(&format!(
+                "t=1700000000,v1={}",
+                hex::encode(signature)
+            ))
+            .unwrap(),
+        );
+        let request_details = api::IncomingWebhookRequestDetails {
+            method: actix_web::http::Method::POST,
+            uri: actix_web::http::Uri::from_static("/webhooks/merchant_1/stripe"),
+            headers: &headers,
+            query_params: String::new(),
+            body: body.as_bytes(),
+        };
+
+        verify_webhook_source_with_previous_credentials(
+            state,
+            &crate::connector::Stripe,
+            merchant_account,
+            &merchant_connector_account,
+            "stripe",
+            &request_details,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_signed_with_rotated_credentials_are_verified_until_finalized() {
+        let (state, merchant_account) = get_state_with_stripe_connector_account().await;
+        let update_request = serde_json::from_value(serde_json::json!({
+            "connector_type": "payment_processor",
+            "connector_account_details_new": { "auth_type": "HeaderKey", "api_key": "sk_new" },
+            "connector_webhook_details": { "merchant_secret": "whsec_new" },
+        }))
+        .unwrap();
+
+        let response = match crate::core::admin::update_payment_connector(
+            state.clone(),
+            "merchant_1",
+            "mca_1",
+            update_request,
+        )
+        .await
+        .unwrap()
+        {
+            services::ApplicationResponse::Json(response) => response,
+            _ => panic!("Unexpected response"),
+        };
+
+        // The requests to the connector are made with the new credentials right away
+        assert_eq!(
+            response.connector_account_details.peek(),
+            &serde_json::json!({ "auth_type": "HeaderKey", "api_key": "sk_new" })
+        );
+        let previous_credentials_expire_at = response.previous_credentials_expire_at.unwrap();
+        assert!(previous_credentials_expire_at > common_utils::date_time::now());
+
+        assert!(is_stripe_webhook_verified(&state, &merchant_account, "whsec_new").await);
+        assert!(is_stripe_webhook_verified(&state, &merchant_account, "whsec_old").await);
+        assert!(!is_stripe_webhook_verified(&state, &merchant_account, "whsec_other").await);
+
+        let response = match crate::core::admin::finalize_connector_credential_rotation(
+            state.clone(),
+            "merchant_1",
+            "mca_1",
+        )
+        .await
+        .unwrap()
+        {
+            services::ApplicationResponse::Json(response) => response,
+            _ => panic!("Unexpected response"),
+        };
+
+        assert!(response.previous_credentials_expire_at.is_none());
+        assert!(is_stripe_webhook_verified(&state, &merchant_account, "whsec_new").await);
+        assert!(!is_stripe_webhook_verified(&state, &merchant_account, "whsec_old").await);
+        assert!(crate::core::admin::finalize_connector_credential_rotation(
+            state.clone(),
+            "merchant_1",
+            "mca_1",
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rotated_credentials_are_not_used_after_the_grace_period() {
+        let (state, _) = get_state_with_stripe_connector_account().await;
+        let db = &*state.store;
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                "merchant_1",
+                &db.get_master_key().to_vec().into(),
+            )
+            .await
+            .unwrap();
+        let merchant_connector_account = db
+            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                "merchant_1",
+                "mca_1",
+                &key_store,
+            )
+            .await
+            .unwrap();
+        let now = common_utils::date_time::now();
+        let merchant_connector_account = domain::MerchantConnectorAccount {
+            previous_credentials: Some(domain::PreviousConnectorCredentials {
+                connector_account_details: merchant_connector_account
+                    .connector_account_details
+                    .clone(),
+                connector_webhook_details: Some(masking::Secret::new(
+                    serde_json::json!({ "merchant_secret": "whsec_older" }),
+                )),
+                expire_at: now + time::Duration::hours(1),
+            }),
+            ..merchant_connector_account
+        };
+
+        let previous_merchant_connector_account = merchant_connector_account
+            .with_previous_credentials(now)
+            .unwrap();
+        assert_eq!(
+            previous_merchant_connector_account
+                .connector_webhook_details
+                .unwrap()
+                .peek(),
+            &serde_json::json!({ "merchant_secret": "whsec_older" })
+        );
+        assert!(merchant_connector_account
+            .with_previous_credentials(now + time::Duration::hours(2))
+            .is_none());
+    }
+
+    async fn get_state_with_webhook_subscriptions(
+        mock_server: &wiremock::MockServer,
+    ) -> (AppState, domain::MerchantAccount) {
+        let webhook_details = serde_json::json!({
+            "webhook_url": format!("{}/webhooks", mock_server.uri()),
+            "subscriptions": [
+                {
+                    "subscription_id": "refunds",
+                    "event_type": "refund_*",
+                    "url": format!("{}/refunds", mock_server.uri()),
+                    "secret": "whsec_refunds",
+                },
+                {
+                    "subscription_id": "ledger",
+                    "event_type": "refund_succeeded",
+                    "url": format!("{}/ledger", mock_server.uri()),
+                    "secret": "whsec_ledger",
+                },
+            ],
+        });
+        get_state_with_webhook_details(webhook_details).await
+    }
 
-            api::WebhookFlow::Dispute => disputes_incoming_webhook_flow::<W>(
-                state.clone(),
-                merchant_account,
-                webhook_details,
-                source_verified,
-                *connector,
-                &request_details,
-                event_type,
+    async fn get_state_with_webhook_details(
+        webhook_details: serde_json::Value,
+    ) -> (AppState, domain::MerchantAccount) {
+        let mut settings = crate::configs::settings::Settings::default();
+        settings.webhooks.outgoing_enabled = true;
+        let state = AppState::with_storage(
+            settings,
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::ProxyClient::new(Default::default(), vec![]).unwrap()),
+        )
+        .await;
+        let db = &*state.store;
+        let master_key = db.get_master_key().to_vec();
+        let key_store = db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant_1".to_string(),
+                    key: domain::types::encrypt(
+                        services::generate_aes256_key().unwrap().to_vec().into(),
+                        &master_key,
+                    )
+                    .await
+                    .unwrap(),
+                    created_at: common_utils::date_time::now(),
+                },
+                &master_key.into(),
             )
             .await
-            .attach_printable("Incoming webhook flow for disputes failed")?,
-
-            api::WebhookFlow::BankTransfer => bank_transfer_webhook_flow::<W>(
-                state.clone(),
-                merchant_account,
-                key_store,
-                webhook_details,
-                source_verified,
+            .unwrap();
+
+        // The secrets of the subscriptions are stored encrypted, apart from the webhook details
+        let mut webhook_details = webhook_details
+            .parse_value::<api::WebhookDetails>("WebhookDetails")
+            .unwrap();
+        let webhook_auth_details = webhook_details
+            .take_auth_details()
+            .map(|auth_details| masking::Secret::new(serde_json::to_value(auth_details).unwrap()));
+        let webhook_auth_details =
+            domain::types::encrypt_optional(webhook_auth_details, key_store.key.get_inner().peek())
+                .await
+                .unwrap();
+
+        let merchant_account = db
+            .insert_merchant(
+                domain::MerchantAccount {
+                    id: None,
+                    merchant_id: "merchant_1".to_string(),
+                    return_url: None,
+                    enable_payment_response_hash: true,
+                    payment_response_hash_key: Some("merchant_hash_key".to_string()),
+                    redirect_to_merchant_with_http_post: false,
+                    merchant_name: None,
+                    merchant_details: None,
+                    webhook_details: Some(serde_json::to_value(webhook_details).unwrap()),
+                    sub_merchants_enabled: None,
+                    parent_merchant_id: None,
+                    publishable_key: None,
+                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+                    locker_id: None,
+                    metadata: None,
+                    routing_algorithm: None,
+                    primary_business_details: serde_json::Value::Null,
+                    frm_routing_algorithm: None,
+                    created_at: common_utils::date_time::now(),
+                    modified_at: common_utils::date_time::now(),
+                    intent_fulfillment_time: None,
+                    payout_routing_algorithm: None,
+                    organization_id: None,
+                    is_recon_enabled: false,
+                    default_profile: None,
+                    recon_status: enums::ReconStatus::NotRequested,
+                    webhook_auth_details,
+                    default_currency: None,
+                    default_country: None,
+                    pii_policy: None,
+                },
+                &key_store,
             )
             .await
-            .attach_printable("Incoming bank-transfer webhook flow failed")?,
+            .unwrap();
 
-            api::WebhookFlow::ReturnResponse => {}
+        (state, merchant_account)
+    }
 
-            _ => Err(errors::ApiErrorResponse::InternalServerError)
-                .into_report()
-                .attach_printable("Unsupported Flow Type received in incoming webhooks")?,
+    #[tokio::test]
+    async fn test_event_is_delivered_to_each_matching_subscription() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        let (state, merchant_account) = get_state_with_webhook_subscriptions(&mock_server).await;
+
+        let refund_response = api_models::refunds::RefundResponse {
+            refund_id: "ref_1".to_string(),
+            payment_id: "pay_1".to_string(),
+            amount: 1000,
+            currency: "USD".to_string(),
+            reason: None,
+            reason_code: None,
+            status: api_models::refunds::RefundStatus::Succeeded,
+            metadata: None,
+            error_message: None,
+            error_code: None,
+            created_at: None,
+            updated_at: None,
+            connector: "stripe".to_string(),
+            merchant_connector_id: None,
+            acquirer_reference_number: None,
+            connector_response: None,
+        };
+        create_event_and_trigger_outgoing_webhook::<api_models::webhooks::OutgoingWebhook>(
+            state.clone(),
+            merchant_account,
+            enums::EventType::RefundSucceeded,
+            enums::EventClass::Refunds,
+            None,
+            "ref_1".to_string(),
+            enums::EventObjectType::RefundDetails,
+            api::OutgoingWebhookContent::RefundDetails(refund_response),
+        )
+        .await
+        .unwrap();
+
+        // The event is delivered to the endpoint of each subscription, not to the webhook url
+        let requests = mock_server.received_requests().await.unwrap();
+        let mut paths = requests
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, vec!["/ledger", "/refunds"]);
+
+        // Each delivery is signed with the secret of its subscription
+        let signatures = requests
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get(crate::headers::X_WEBHOOK_SIGNATURE)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(signatures[0], signatures[1]);
+        assert_eq!(requests[0].body, requests[1].body);
+
+        let mut deliveries = state
+            .store
+            .find_webhook_deliveries_by_merchant_id_event_id("merchant_1", "ref_1_refund_succeeded")
+            .await
+            .unwrap();
+        deliveries.sort_by(|a, b| a.subscription_id.cmp(&b.subscription_id));
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].subscription_id.as_deref(), Some("ledger"));
+        assert_eq!(deliveries[1].subscription_id.as_deref(), Some("refunds"));
+        for delivery in deliveries {
+            assert_eq!(delivery.status, enums::WebhookDeliveryStatus::Delivered);
+            assert_eq!(delivery.response_status_code, Some(200));
         }
-    } else {
-        metrics::WEBHOOK_INCOMING_FILTERED_COUNT.add(
-            &metrics::CONTEXT,
-            1,
-            &[metrics::KeyValue::new(
-                MERCHANT_ID,
-                merchant_account.merchant_id.clone(),
-            )],
+    }
+
+    #[tokio::test]
+    async fn test_event_matching_no_subscription_is_recorded_as_skipped() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        let (state, merchant_account) = get_state_with_webhook_subscriptions(&mock_server).await;
+
+        create_event_and_trigger_outgoing_webhook::<api_models::webhooks::OutgoingWebhook>(
+            state.clone(),
+            merchant_account,
+            enums::EventType::PaymentSucceeded,
+            enums::EventClass::Payments,
+            None,
+            "pay_1".to_string(),
+            enums::EventObjectType::PaymentDetails,
+            api::OutgoingWebhookContent::PaymentDetails(Default::default()),
+        )
+        .await
+        .unwrap();
+
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+        let deliveries = state
+            .store
+            .find_webhook_deliveries_by_merchant_id_event_id(
+                "merchant_1",
+                "pay_1_payment_succeeded",
+            )
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].subscription_id, None);
+        assert_eq!(deliveries[0].status, enums::WebhookDeliveryStatus::Skipped);
+        assert_eq!(deliveries[0].response_status_code, None);
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_of_merchant_with_pii_policy_omit_phone() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        let (state, mut merchant_account) = get_state_with_webhook_details(serde_json::json!({
+            "webhook_url": format!("{}/webhooks", mock_server.uri()),
+        }))
+        .await;
+        merchant_account.pii_policy = Some(serde_json::json!({
+            "outgoing_webhook": {
+                "removed_fields": ["/content/object/phone"],
+            },
+        }));
+
+        let payments_response = api_models::payments::PaymentsResponse {
+            payment_id: Some("pay_1".to_string()),
+            amount: 1000,
+            email: Some(common_utils::crypto::Encryptable::new(
+                masking::Secret::new("guest@example.com".to_string()),
+                masking::Secret::new(Vec::new()),
+            )),
+            phone: Some(common_utils::crypto::Encryptable::new(
+                masking::Secret::new("9123456789".to_string()),
+                masking::Secret::new(Vec::new()),
+            )),
+            ..Default::default()
+        };
+        create_event_and_trigger_outgoing_webhook::<api_models::webhooks::OutgoingWebhook>(
+            state,
+            merchant_account,
+            enums::EventType::PaymentSucceeded,
+            enums::EventClass::Payments,
+            Some("pay_1".to_string()),
+            "pay_1".to_string(),
+            enums::EventObjectType::PaymentDetails,
+            api::OutgoingWebhookContent::PaymentDetails(payments_response),
+        )
+        .await
+        .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body = serde_json::from_slice::<serde_json::Value>(&requests[0].body).unwrap();
+        assert!(body["content"]["object"].get("phone").is_none());
+        assert_eq!(body["content"]["object"]["email"], "guest@example.com");
+        assert_eq!(body["content"]["object"]["payment_id"], "pay_1");
+
+        // The signature is computed over the redacted body, as the merchant receives it
+        let signature = requests[0]
+            .headers
+            .get(crate::headers::X_WEBHOOK_SIGNATURE)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let expected_signature = hex::encode(
+            common_utils::crypto::SignMessage::sign_message(
+                &common_utils::crypto::HmacSha512,
+                b"merchant_hash_key",
+                &requests[0].body,
+            )
+            .unwrap(),
         );
+        assert_eq!(signature, expected_signature);
     }
 
-    let response = connector
-        .get_webhook_api_response(&request_details)
-        .switch()
-        .attach_printable("Could not get incoming webhook api response from connector")?;
+    #[tokio::test]
+    async fn test_encoded_outgoing_webhook_body_omits_phone_of_policy() {
+        let (state, mut merchant_account) = get_state_with_webhook_details(serde_json::json!({
+            "webhook_url": "https://merchant.example/webhooks",
+        }))
+        .await;
+        merchant_account.pii_policy = Some(serde_json::json!({
+            "outgoing_webhook": {
+                "removed_fields": ["/content/object/phone"],
+            },
+        }));
+        let webhook = api::OutgoingWebhook {
+            merchant_id: "merchant_1".to_string(),
+            event_id: "pay_1_payment_succeeded".to_string(),
+            event_type: enums::EventType::PaymentSucceeded,
+            content: api::OutgoingWebhookContent::PaymentDetails(
+                api_models::payments::PaymentsResponse {
+                    payment_id: Some("pay_1".to_string()),
+                    amount: 1000,
+                    email: Some(common_utils::crypto::Encryptable::new(
+                        masking::Secret::new("guest@example.com".to_string()),
+                        masking::Secret::new(Vec::new()),
+                    )),
+                    phone: Some(common_utils::crypto::Encryptable::new(
+                        masking::Secret::new("9123456789".to_string()),
+                        masking::Secret::new(Vec::new()),
+                    )),
+                    ..Default::default()
+                },
+            ),
+            timestamp: common_utils::date_time::now(),
+            webhook_version: None,
+            error: None,
+            attempts: None,
+            timeline: None,
+        };
+        let pii_redaction = services::api::pii_redaction::get_outgoing_webhook_redaction(
+            merchant_account.pii_policy.as_ref(),
+            None,
+        );
 
-    Ok(response)
-}
+        let mut renderer =
+            OutgoingWebhookRenderer::new(&state, &merchant_account, webhook, pii_redaction);
+        let (outgoing_webhook, outgoing_webhook_string) = renderer
+            .render::<api_models::webhooks::OutgoingWebhook>(api_enums::WebhookPayloadVersion::V1)
+            .await
+            .unwrap();
+
+        let body = serde_json::from_str::<serde_json::Value>(&outgoing_webhook_string).unwrap();
+        assert_eq!(body["content"]["type"], "payment_details");
+        assert!(body["content"]["object"].get("phone").is_none());
+        assert_eq!(body["content"]["object"]["email"], "guest@example.com");
+        assert_eq!(body["content"]["object"]["payment_id"], "pay_1");
+
+        // Only the encoded body is redacted, the rendered webhook keeps the phone
+        assert!(
+            serde_json::to_value(&outgoing_webhook).unwrap()["content"]["object"]
+                .get("phone")
+                .is_some()
+        );
+    }
 
-#[inline]
-pub async fn get_payment_id(
-    db: &dyn StorageInterface,
-    payment_id: &api::PaymentIdType,
-    merchant_id: &str,
-    storage_scheme: enums::MerchantStorageScheme,
-) -> errors::RouterResult<String> {
-    let pay_id = || async {
-        match payment_id {
-            api_models::payments::PaymentIdType::PaymentIntentId(ref id) => Ok(id.to_string()),
-            api_models::payments::PaymentIdType::ConnectorTransactionId(ref id) => db
-                .find_payment_attempt_by_merchant_id_connector_txn_id(
-                    merchant_id,
-                    id,
-                    storage_scheme,
-                )
-                .await
-                .map(|p| p.payment_id),
-            api_models::payments::PaymentIdType::PaymentAttemptId(ref id) => db
-                .find_payment_attempt_by_attempt_id_merchant_id(id, merchant_id, storage_scheme)
-                .await
-                .map(|p| p.payment_id),
-            api_models::payments::PaymentIdType::PreprocessingId(ref id) => db
-                .find_payment_attempt_by_preprocessing_id_merchant_id(
-                    id,
-                    merchant_id,
-                    storage_scheme,
-                )
-                .await
-                .map(|p| p.payment_id),
+    fn get_refund_response(error_code: Option<&str>) -> api_models::refunds::RefundResponse {
+        api_models::refunds::RefundResponse {
+            refund_id: "ref_1".to_string(),
+            payment_id: "pay_1".to_string(),
+            amount: 1000,
+            currency: "USD".to_string(),
+            reason: None,
+            reason_code: None,
+            status: api_models::refunds::RefundStatus::Failed,
+            metadata: None,
+            error_message: error_code.map(|_| "Refund declined by the issuer".to_string()),
+            error_code: error_code.map(str::to_string),
+            created_at: None,
+            updated_at: None,
+            connector: "stripe".to_string(),
+            merchant_connector_id: None,
+            acquirer_reference_number: None,
+            connector_response: None,
         }
-    };
-
-    pay_id()
-        .await
-        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
-}
+    }
 
-async fn fetch_mca_and_connector(
-    state: AppState,
-    merchant_account: &domain::MerchantAccount,
-    connector_name_or_mca_id: &str,
-    key_store: &domain::MerchantKeyStore,
-    request_details: &api::IncomingWebhookRequestDetails<'_>,
-) -> CustomResult<(domain::MerchantConnectorAccount, api::ConnectorData), errors::ApiErrorResponse>
-{
-    let db = &state.store;
-    if connector_name_or_mca_id.starts_with("mca_") {
-        let mca = db
-            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
-                &merchant_account.merchant_id,
-                connector_name_or_mca_id,
-                key_store,
+    #[tokio::test]
+    async fn test_refund_event_is_delivered_to_the_subscriptions_of_its_profile() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        let (state, merchant_account) = get_state_with_webhook_subscriptions(&mock_server).await;
+        let db = &*state.store;
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                "merchant_1",
+                &db.get_master_key().to_vec().into(),
             )
             .await
-            .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
-                id: connector_name_or_mca_id.to_string(),
-            })
-            .attach_printable(
-                "error while fetching merchant_connector_account from connector_id",
-            )?;
-
-        let connector = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &mca.connector_name,
-            api::GetToken::Connector,
+            .unwrap();
+        let webhook_details = serde_json::from_value(serde_json::json!({
+            "subscriptions": [
+                {
+                    "subscription_id": "profile_refunds",
+                    "event_type": "refund_*",
+                    "url": format!("{}/profile_refunds", mock_server.uri()),
+                    "secret": "whsec_profile_refunds",
+                },
+            ],
+        }))
+        .unwrap();
+        let business_profile = admin::create_and_insert_business_profile(
+            db,
+            api_models::admin::BusinessProfileCreate {
+                profile_name: Some("refunds".to_string()),
+                webhook_details: Some(webhook_details),
+                ..Default::default()
+            },
+            merchant_account.clone(),
+            &key_store,
         )
-        .change_context(errors::ApiErrorResponse::InvalidRequestData {
-            message: "invalid connector name received".to_string(),
-        })
-        .attach_printable("Failed construction of ConnectorData")?;
+        .await
+        .unwrap();
+        // The secret of the subscription is stored encrypted, apart from the webhook details
+        assert!(business_profile.webhook_auth_details.is_some());
+        assert!(!business_profile
+            .webhook_details
+            .clone()
+            .unwrap()
+            .to_string()
+            .contains("whsec_profile_refunds"));
+        db.insert_refund(
+            storage::RefundNew {
+                refund_id: "ref_1".to_string(),
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                profile_id: Some(business_profile.profile_id),
+                ..Default::default()
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .unwrap();
 
-        Ok((mca, connector))
-    } else {
-        let connector = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            connector_name_or_mca_id,
-            api::GetToken::Connector,
+        create_event_and_trigger_outgoing_webhook::<api_models::webhooks::OutgoingWebhook>(
+            state.clone(),
+            merchant_account,
+            enums::EventType::RefundFailed,
+            enums::EventClass::Refunds,
+            None,
+            "ref_1".to_string(),
+            enums::EventObjectType::RefundDetails,
+            api::OutgoingWebhookContent::RefundDetails(get_refund_response(None)),
         )
-        .change_context(errors::ApiErrorResponse::InvalidRequestData {
-            message: "invalid connector name received".to_string(),
-        })
-        .attach_printable("Failed construction of ConnectorData")?;
+        .await
+        .unwrap();
+
+        // The subscriptions of the profile take precedence over the ones of the merchant account
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url.path(), "/profile_refunds");
+        assert!(requests[0]
+            .headers
+            .get(crate::headers::X_WEBHOOK_SIGNATURE)
+            .is_some());
+    }
 
-        let object_ref_id = connector
-            .connector
-            .get_webhook_object_reference_id(request_details)
-            .switch()
-            .attach_printable("Could not find object reference id in incoming webhook body")?;
+    #[test]
+    fn test_same_event_is_rendered_in_each_payload_version() {
+        let webhook = api::OutgoingWebhook {
+            merchant_id: "merchant_1".to_string(),
+            event_id: "pay_1_payment_failed".to_string(),
+            event_type: enums::EventType::PaymentFailed,
+            content: api::OutgoingWebhookContent::PaymentDetails(
+                api_models::payments::PaymentsResponse {
+                    payment_id: Some("pay_1".to_string()),
+                    error_code: Some("card_declined".to_string()),
+                    error_message: Some("Your card was declined".to_string()),
+                    ..Default::default()
+                },
+            ),
+            timestamp: common_utils::date_time::now(),
+            webhook_version: None,
+            error: None,
+            attempts: None,
+            timeline: None,
+        };
+        let attempts = vec![api_models::payments::PaymentAttemptResponse {
+            attempt_id: "pay_1_1".to_string(),
+            status: enums::AttemptStatus::Failure,
+            ..Default::default()
+        }];
 
-        let profile_id = helper_utils::get_profile_id_using_object_reference_id(
-            &*state.store,
-            object_ref_id,
+        let v1 = serde_json::to_value(render_outgoing_webhook(
+            webhook.clone(),
+            api_enums::WebhookPayloadVersion::V1,
+            Some(attempts.clone()),
+        ))
+        .unwrap();
+        let v2 = serde_json::to_value(render_outgoing_webhook(
+            webhook,
+            api_enums::WebhookPayloadVersion::V2,
+            Some(attempts),
+        ))
+        .unwrap();
+
+        // Both versions carry the envelope of the event, with their version
+        for (payload, version) in [(&v1, "v1"), (&v2, "v2")] {
+            assert_eq!(payload["webhook_version"], version);
+            assert_eq!(payload["merchant_id"], "merchant_1");
+            assert_eq!(payload["event_id"], "pay_1_payment_failed");
+            assert_eq!(payload["event_type"], "payment_failed");
+            assert_eq!(payload["content"]["type"], "payment_details");
+            assert_eq!(payload["content"]["object"]["payment_id"], "pay_1");
+        }
+
+        // Only the second version carries the unified error and the attempts
+        assert!(v1.get("error").is_none());
+        assert!(v1.get("attempts").is_none());
+        assert_eq!(v2["error"]["code"], "card_declined");
+        assert_eq!(v2["error"]["unified_code"], "payment_declined");
+        assert_eq!(
+            v2["error"]["unified_message"],
+            user_messages::get_message(user_messages::UnifiedCode::PaymentDeclined, None, &[])
+        );
+        assert_eq!(v2["attempts"][0]["attempt_id"], "pay_1_1");
+    }
+
+    #[tokio::test]
+    async fn test_event_is_delivered_in_the_payload_version_of_each_subscription() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        // The subscription without a version of its own uses the version of the webhook details
+        let webhook_details = serde_json::json!({
+            "payload_version": "v2",
+            "subscriptions": [
+                {
+                    "subscription_id": "refunds",
+                    "event_type": "refund_*",
+                    "url": format!("{}/refunds", mock_server.uri()),
+                    "secret": "whsec_refunds",
+                },
+                {
+                    "subscription_id": "ledger",
+                    "event_type": "refund_failed",
+                    "url": format!("{}/ledger", mock_server.uri()),
+                    "secret": "whsec_ledger",
+                    "payload_version": "v1",
+                },
+            ],
+        });
+        let (state, merchant_account) = get_state_with_webhook_details(webhook_details).await;
+
+        create_event_and_trigger_outgoing_webhook::<api_models::webhooks::OutgoingWebhook>(
+            state.clone(),
             merchant_account,
-            connector_name_or_mca_id,
+            enums::EventType::RefundFailed,
+            enums::EventClass::Refunds,
+            None,
+            "ref_1".to_string(),
+            enums::EventObjectType::RefundDetails,
+            api::OutgoingWebhookContent::RefundDetails(get_refund_response(Some(
+                "insufficient_funds",
+            ))),
         )
         .await
-        .change_context(errors::ApiErrorResponse::InvalidDataValue {
-            field_name: "object reference id",
-        })
-        .attach_printable("Could not find profile id from object reference id")?;
+        .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            let expected_version = match request.url.path() {
+                "/ledger" => "v1",
+                _ => "v2",
+            };
+            let version_header = request
+                .headers
+                .get(crate::headers::X_WEBHOOK_VERSION)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert_eq!(version_header, expected_version);
+
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(body["webhook_version"], expected_version);
+            assert_eq!(body.get("error").is_some(), expected_version == "v2");
+        }
 
-        let mca = db
-            .find_merchant_connector_account_by_profile_id_connector_name(
-                &profile_id,
-                connector_name_or_mca_id,
-                key_store,
-            )
+        // The version of each delivery is recorded, for the redeliveries to use the same version
+        let mut deliveries = state
+            .store
+            .find_webhook_deliveries_by_merchant_id_event_id("merchant_1", "ref_1_refund_failed")
             .await
-            .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
-                id: format!(
-                    "profile_id {profile_id} and connector name {connector_name_or_mca_id}"
-                ),
-            })
-            .attach_printable("error while fetching merchant_connector_account from profile_id")?;
+            .unwrap();
+        deliveries.sort_by(|a, b| a.subscription_id.cmp(&b.subscription_id));
+        assert_eq!(
+            deliveries
+                .iter()
+                .map(|delivery| delivery.payload_version)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(api_enums::WebhookPayloadVersion::V1),
+                Some(api_enums::WebhookPayloadVersion::V2)
+            ]
+        );
+    }
 
-        Ok((mca, connector))
+    mod mutual_tls {
+        use std::{
+            io::{Read, Write},
+            net::TcpListener,
+        };
+
+        use openssl::{
+            asn1::Asn1Time,
+            bn::{BigNum, MsbOption},
+            hash::MessageDigest,
+            nid::Nid,
+            pkey::{PKey, Private},
+            rsa::Rsa,
+            ssl::{SslAcceptor, SslMethod, SslVerifyMode},
+            x509::{
+                extension::{BasicConstraints, KeyUsage, SubjectAlternativeName},
+                X509Builder, X509NameBuilder, X509,
+            },
+        };
+
+        use super::*;
+
+        fn generate_certificate(
+            common_name: &str,
+            issuer: Option<(&X509, &PKey<Private>)>,
+        ) -> (X509, PKey<Private>) {
+            let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+            let mut name = X509NameBuilder::new().unwrap();
+            name.append_entry_by_nid(Nid::COMMONNAME, common_name)
+                .unwrap();
+            let name = name.build();
+
+            let mut serial_number = BigNum::new().unwrap();
+            serial_number
+                .rand(64, MsbOption::MAYBE_ZERO, false)
+                .unwrap();
+
+            let mut builder = X509Builder::new().unwrap();
+            builder.set_version(2).unwrap();
+            builder
+                .set_serial_number(&serial_number.to_asn1_integer().unwrap())
+                .unwrap();
+            builder.set_subject_name(&name).unwrap();
+            builder.set_pubkey(&key).unwrap();
+            builder
+                .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+                .unwrap();
+            builder
+                .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+                .unwrap();
+
+            match issuer {
+                Some((issuer_certificate, issuer_key)) => {
+                    builder
+                        .set_issuer_name(issuer_certificate.subject_name())
+                        .unwrap();
+                    let subject_alternative_name = SubjectAlternativeName::new()
+                        .ip("127.0.0.1")
+                        .build(&builder.x509v3_context(Some(issuer_certificate), None))
+                        .unwrap();
+                    builder.append_extension(subject_alternative_name).unwrap();
+                    builder.sign(issuer_key, MessageDigest::sha256()).unwrap();
+                }
+                None => {
+                    builder.set_issuer_name(&name).unwrap();
+                    builder
+                        .append_extension(BasicConstraints::new().critical().ca().build().unwrap())
+                        .unwrap();
+                    builder
+                        .append_extension(
+                            KeyUsage::new()
+                                .critical()
+                                .key_cert_sign()
+                                .crl_sign()
+                                .build()
+                                .unwrap(),
+                        )
+                        .unwrap();
+                    builder.sign(&key, MessageDigest::sha256()).unwrap();
+                }
+            }
+
+            (builder.build(), key)
+        }
+
+        #[tokio::test]
+        async fn test_webhook_delivered_with_client_certificate_and_custom_header() {
+            let (ca_certificate, ca_key) = generate_certificate("Webhook Test CA", None);
+            let (server_certificate, server_key) =
+                generate_certificate("127.0.0.1", Some((&ca_certificate, &ca_key)));
+            let (client_certificate, client_key) =
+                generate_certificate("merchant", Some((&ca_certificate, &ca_key)));
+
+            let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+            acceptor.set_private_key(&server_key).unwrap();
+            acceptor.set_certificate(&server_certificate).unwrap();
+            acceptor
+                .cert_store_mut()
+                .add_cert(ca_certificate.clone())
+                .unwrap();
+            acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+            let acceptor = acceptor.build();
+
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let webhook_url = format!(
+                "https://127.0.0.1:{}/webhooks",
+                listener.local_addr().unwrap().port()
+            );
+
+            // Accepts two connections, returning the request received on each connection whose
+            // TLS handshake succeeded
+            let server = std::thread::spawn(move || {
+                listener
+                    .incoming()
+                    .take(2)
+                    .map(|stream| {
+                        let mut stream = acceptor.accept(stream.unwrap()).ok()?;
+                        let mut request = Vec::new();
+                        let mut buffer = [0; 1024];
+                        while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                            let read = stream.read(&mut buffer).ok()?;
+                            if read == 0 {
+                                break;
+                            }
+                            request.extend_from_slice(&buffer[..read]);
+                        }
+                        stream
+                            .write_all(
+                                b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                            )
+                            .ok()?;
+                        Some(String::from_utf8_lossy(&request).to_lowercase())
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            let webhook_auth_details = api_models::admin::WebhookAuthDetails {
+                custom_headers: Some(std::collections::HashMap::from([(
+                    "x-webhook-key".to_string(),
+                    masking::Secret::new("static_key".to_string()),
+                )])),
+                ..Default::default()
+            };
+            let headers = get_webhook_auth_headers(&webhook_auth_details)
+                .into_iter()
+                .map(|(name, value)| {
+                    (
+                        reqwest::header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                        reqwest::header::HeaderValue::from_str(&value.into_inner()).unwrap(),
+                    )
+                })
+                .collect::<reqwest::header::HeaderMap>();
+
+            let root_certificate =
+                reqwest::Certificate::from_pem(&ca_certificate.to_pem().unwrap()).unwrap();
+
+            let client = services::api::client::build_client_with_identity(
+                reqwest::Client::builder().add_root_certificate(root_certificate.clone()),
+                consts::BASE64_ENGINE.encode(client_certificate.to_pem().unwrap()),
+                consts::BASE64_ENGINE.encode(client_key.private_key_to_pem_pkcs8().unwrap()),
+            )
+            .unwrap();
+            let response = client
+                .post(&webhook_url)
+                .headers(headers.clone())
+                .body("{}")
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+
+            let client_without_certificate = reqwest::Client::builder()
+                .add_root_certificate(root_certificate)
+                .build()
+                .unwrap();
+            let response = client_without_certificate
+                .post(&webhook_url)
+                .headers(headers)
+                .body("{}")
+                .send()
+                .await;
+            assert!(response.is_err());
+
+            let requests = server.join().unwrap();
+            assert_eq!(requests.len(), 2);
+            assert!(requests[0]
+                .as_ref()
+                .unwrap()
+                .contains("x-webhook-key: static_key"));
+            assert!(requests[1].is_none());
+        }
     }
 }
@@ -1,5 +1,5 @@
-use api_models::webhooks;
-use common_utils::{crypto::SignMessage, ext_traits};
+use api_models::{enums as api_enums, webhooks};
+use common_utils::crypto::SignMessage;
 use error_stack::ResultExt;
 use serde::Serialize;
 
@@ -8,30 +8,33 @@ use crate::{core::errors, headers, services::request::Maskable};
 pub trait OutgoingWebhookType:
     Serialize + From<webhooks::OutgoingWebhook> + Sync + Send + std::fmt::Debug
 {
+    /// Signs the payload as it is encoded in the body of the webhook
     fn get_outgoing_webhooks_signature(
         &self,
+        outgoing_webhook_string: &str,
         payment_response_hash_key: Option<String>,
     ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError>;
 
     fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String);
+
+    /// The version of the payload, `None` for the payloads which are not versioned
+    fn get_payload_version(&self) -> Option<api_enums::WebhookPayloadVersion> {
+        None
+    }
 }
 
 impl OutgoingWebhookType for webhooks::OutgoingWebhook {
     fn get_outgoing_webhooks_signature(
         &self,
+        outgoing_webhook_string: &str,
         payment_response_hash_key: Option<String>,
     ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError> {
-        let webhook_signature_payload =
-            ext_traits::Encode::<serde_json::Value>::encode_to_string_of_json(self)
-                .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
-                .attach_printable("failed encoding outgoing webhook payload")?;
-
         Ok(payment_response_hash_key
             .map(|key| {
                 common_utils::crypto::HmacSha512::sign_message(
                     &common_utils::crypto::HmacSha512,
                     key.as_bytes(),
-                    webhook_signature_payload.as_bytes(),
+                    outgoing_webhook_string.as_bytes(),
                 )
             })
             .transpose()
@@ -42,4 +45,7 @@ impl OutgoingWebhookType for webhooks::OutgoingWebhook {
     fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
         header.push((headers::X_WEBHOOK_SIGNATURE.to_string(), signature.into()))
     }
+    fn get_payload_version(&self) -> Option<api_enums::WebhookPayloadVersion> {
+        self.webhook_version
+    }
 }
@@ -69,11 +69,12 @@ pub async fn construct_webhook_router_data<'a>(
     connector_wh_secrets: &api_models::webhooks::ConnectorWebhookSecrets,
     request_details: &api::IncomingWebhookRequestDetails<'_>,
 ) -> CustomResult<types::VerifyWebhookSourceRouterData, errors::ApiErrorResponse> {
-    let auth_type: types::ConnectorAuthType =
-        helpers::MerchantConnectorAccountType::DbVal(merchant_connector_account.clone())
-            .get_connector_account_details()
-            .parse_value("ConnectorAuthType")
-            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let merchant_connector_account =
+        helpers::MerchantConnectorAccountType::DbVal(merchant_connector_account);
+    let auth_type: types::ConnectorAuthType = merchant_connector_account
+        .get_connector_account_details()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
     let router_data = types::RouterData {
         flow: PhantomData,
@@ -116,6 +117,11 @@ pub async fn construct_webhook_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: merchant_connector_account.get_connector_proxy(),
+        authorized_amount: None,
     };
     Ok(router_data)
 }
@@ -1,27 +1,37 @@
 pub mod address;
+pub mod alert;
 pub mod api_keys;
+pub mod audit_event;
+pub mod blocklist;
+pub mod bulk_refund;
 pub mod business_profile;
 pub mod cache;
 pub mod capture;
 pub mod cards_info;
 pub mod configs;
 pub mod connector_response;
+pub mod connector_response_capture;
 pub mod customers;
 pub mod dispute;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
 pub mod fraud_check;
+pub mod idempotency_key;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_migration;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
+pub mod payment_attempt_step_event;
 pub mod payment_method;
+pub mod payment_routing_decision;
 pub mod payout_attempt;
 pub mod payouts;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod webhook_delivery;
 
 use data_models::payments::{
     payment_attempt::PaymentAttemptInterface, payment_intent::PaymentIntentInterface,
@@ -44,24 +54,33 @@ pub trait StorageInterface:
     + Sync
     + dyn_clone::DynClone
     + address::AddressInterface
+    + alert::AlertInterface
     + api_keys::ApiKeyInterface
+    + audit_event::AuditEventInterface
+    + blocklist::BlocklistInterface
+    + bulk_refund::BulkRefundInterface
     + configs::ConfigInterface
     + capture::CaptureInterface
     + connector_response::ConnectorResponseInterface
+    + connector_response_capture::ConnectorResponseCaptureInterface
     + customers::CustomerInterface
     + dispute::DisputeInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
     + file::FileMetadataInterface
     + fraud_check::FraudCheckInterface
+    + idempotency_key::IdempotencyKeyInterface
     + locker_mock_up::LockerMockUpInterface
     + mandate::MandateInterface
+    + mandate_migration::MandateMigrationInterface
     + merchant_account::MerchantAccountInterface
     + merchant_connector_account::ConnectorAccessToken
     + merchant_connector_account::MerchantConnectorAccountInterface
     + PaymentAttemptInterface
+    + payment_attempt_step_event::PaymentAttemptStepEventInterface
     + PaymentIntentInterface
     + payment_method::PaymentMethodInterface
+    + payment_routing_decision::PaymentRoutingDecisionInterface
     + scheduler::SchedulerInterface
     + payout_attempt::PayoutAttemptInterface
     + payouts::PayoutsInterface
@@ -72,6 +91,7 @@ pub trait StorageInterface:
     + MasterKeyInterface
     + RedisConnInterface
     + business_profile::BusinessProfileInterface
+    + webhook_delivery::WebhookDeliveryInterface
     + 'static
 {
     fn get_scheduler_db(&self) -> Box<dyn scheduler::SchedulerInterface>;
@@ -0,0 +1,136 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait AlertInterface {
+    async fn insert_alert(
+        &self,
+        alert: storage::AlertNew,
+    ) -> CustomResult<storage::Alert, errors::StorageError>;
+
+    async fn find_alert_by_merchant_id_connector_connector_alert_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_alert_id: &str,
+    ) -> CustomResult<Option<storage::Alert>, errors::StorageError>;
+
+    async fn update_alert(
+        &self,
+        this: storage::Alert,
+        alert: storage::AlertUpdate,
+    ) -> CustomResult<storage::Alert, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AlertInterface for Store {
+    async fn insert_alert(
+        &self,
+        alert: storage::AlertNew,
+    ) -> CustomResult<storage::Alert, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        alert.insert(&conn).await.map_err(Into::into).into_report()
+    }
+
+    async fn find_alert_by_merchant_id_connector_connector_alert_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_alert_id: &str,
+    ) -> CustomResult<Option<storage::Alert>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Alert::find_optional_by_merchant_id_connector_connector_alert_id(
+            &conn,
+            merchant_id,
+            connector,
+            connector_alert_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_alert(
+        &self,
+        this: storage::Alert,
+        alert: storage::AlertUpdate,
+    ) -> CustomResult<storage::Alert, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        this.update(&conn, alert)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertInterface for MockDb {
+    async fn insert_alert(
+        &self,
+        alert: storage::AlertNew,
+    ) -> CustomResult<storage::Alert, errors::StorageError> {
+        let now = common_utils::date_time::now();
+        let alert = storage::Alert {
+            alert_id: alert.alert_id,
+            merchant_id: alert.merchant_id,
+            payment_id: alert.payment_id,
+            attempt_id: alert.attempt_id,
+            connector: alert.connector,
+            connector_alert_id: alert.connector_alert_id,
+            alert_type: alert.alert_type,
+            initiated_by: alert.initiated_by,
+            connector_reason: alert.connector_reason,
+            status: alert.status,
+            action: alert.action,
+            refund_id: alert.refund_id,
+            raw_payload: alert.raw_payload,
+            created_at: now,
+            modified_at: now,
+        };
+        self.alerts.lock().await.push(alert.clone());
+        Ok(alert)
+    }
+
+    async fn find_alert_by_merchant_id_connector_connector_alert_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_alert_id: &str,
+    ) -> CustomResult<Option<storage::Alert>, errors::StorageError> {
+        Ok(self
+            .alerts
+            .lock()
+            .await
+            .iter()
+            .find(|alert| {
+                alert.merchant_id == merchant_id
+                    && alert.connector == connector
+                    && alert.connector_alert_id == connector_alert_id
+            })
+            .cloned())
+    }
+
+    async fn update_alert(
+        &self,
+        this: storage::Alert,
+        alert: storage::AlertUpdate,
+    ) -> CustomResult<storage::Alert, errors::StorageError> {
+        let mut alerts = self.alerts.lock().await;
+        let stored_alert = alerts
+            .iter_mut()
+            .find(|stored_alert| stored_alert.alert_id == this.alert_id)
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No alert found for alert id = {}",
+                this.alert_id
+            )))
+            .into_report()?;
+        *stored_alert = storage::AlertUpdateInternal::from(alert).apply_changeset(this);
+        Ok(stored_alert.clone())
+    }
+}
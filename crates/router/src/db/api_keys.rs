@@ -234,6 +234,10 @@ impl ApiKeyInterface for MockDb {
             created_at: api_key.created_at,
             expires_at: api_key.expires_at,
             last_used: api_key.last_used,
+            permissions: api_key.permissions,
+            livemode: api_key.livemode,
+            strict_request_validation: api_key.strict_request_validation,
+            pii_restricted: api_key.pii_restricted,
         };
         locked_api_keys.push(stored_key.clone());
 
@@ -259,6 +263,7 @@ impl ApiKeyInterface for MockDb {
                 description,
                 expires_at,
                 last_used,
+                permissions,
             } => {
                 if let Some(name) = name {
                     key_to_update.name = name;
@@ -273,6 +278,9 @@ impl ApiKeyInterface for MockDb {
                 if last_used.is_some() {
                     key_to_update.last_used = last_used
                 }
+                if let Some(permissions) = permissions {
+                    key_to_update.permissions = permissions;
+                }
             }
             storage::ApiKeyUpdate::LastUsedUpdate { last_used } => {
                 key_to_update.last_used = Some(last_used);
@@ -406,6 +414,10 @@ mod tests {
                 created_at: datetime!(2023-02-01 0:00),
                 expires_at: Some(datetime!(2023-03-01 0:00)),
                 last_used: None,
+                permissions: vec![storage::enums::ApiKeyPermission::PaymentsRead],
+                livemode: true,
+                strict_request_validation: false,
+                pii_restricted: false,
             })
             .await
             .unwrap();
@@ -421,6 +433,10 @@ mod tests {
                 created_at: datetime!(2023-03-01 0:00),
                 expires_at: None,
                 last_used: None,
+                permissions: vec![storage::enums::ApiKeyPermission::PaymentsRead],
+                livemode: true,
+                strict_request_validation: false,
+                pii_restricted: false,
             })
             .await
             .unwrap();
@@ -498,6 +514,10 @@ mod tests {
             created_at: datetime!(2023-06-01 0:00),
             expires_at: None,
             last_used: None,
+            permissions: vec![storage::enums::ApiKeyPermission::PaymentsRead],
+            livemode: true,
+            strict_request_validation: false,
+            pii_restricted: false,
         };
 
         let api = db.insert_api_key(api).await.unwrap();
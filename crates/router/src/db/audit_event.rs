@@ -0,0 +1,195 @@
+use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, enums},
+};
+
+#[async_trait::async_trait]
+pub trait AuditEventInterface {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn list_audit_events_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError>;
+
+    async fn get_total_count_of_audit_events(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+    ) -> CustomResult<i64, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for Store {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        audit_event
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_audit_events_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::AuditEvent::list_by_merchant_id(
+            &conn,
+            merchant_id,
+            resource_type,
+            resource_id,
+            created_after,
+            created_before,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn get_total_count_of_audit_events(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+    ) -> CustomResult<i64, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::AuditEvent::count_by_merchant_id(
+            &conn,
+            merchant_id,
+            resource_type,
+            resource_id,
+            created_after,
+            created_before,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for MockDb {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        let mut audit_events = self.audit_events.lock().await;
+        let event = storage::AuditEvent {
+            audit_event_id: audit_event.audit_event_id,
+            merchant_id: audit_event.merchant_id,
+            actor_key_id: audit_event.actor_key_id,
+            resource_type: audit_event.resource_type,
+            resource_id: audit_event.resource_id,
+            action: audit_event.action,
+            diff: audit_event.diff,
+            request_id: audit_event.request_id,
+            created_at: audit_event.created_at,
+        };
+        audit_events.push(event.clone());
+        Ok(event)
+    }
+
+    async fn list_audit_events_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        let offset: usize = offset
+            .unwrap_or(0)
+            .try_into()
+            .into_report()
+            .change_context(errors::StorageError::MockDbError)?;
+        let limit: usize = limit
+            .map(TryInto::try_into)
+            .transpose()
+            .into_report()
+            .change_context(errors::StorageError::MockDbError)?
+            .unwrap_or(usize::MAX);
+
+        let mut audit_events = self
+            .audit_events
+            .lock()
+            .await
+            .iter()
+            .filter(|event| {
+                event.merchant_id == merchant_id
+                    && resource_type
+                        .map_or(true, |resource_type| event.resource_type == resource_type)
+                    && resource_id.map_or(true, |resource_id| event.resource_id == resource_id)
+                    && created_after.map_or(true, |created_after| event.created_at >= created_after)
+                    && created_before
+                        .map_or(true, |created_before| event.created_at < created_before)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        // The events recorded in the same instant are listed in the reverse order of insertion
+        audit_events.reverse();
+        audit_events.sort_by(|event, other_event| other_event.created_at.cmp(&event.created_at));
+
+        Ok(audit_events.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn get_total_count_of_audit_events(
+        &self,
+        merchant_id: &str,
+        resource_type: Option<enums::AuditResourceType>,
+        resource_id: Option<&str>,
+        created_after: Option<PrimitiveDateTime>,
+        created_before: Option<PrimitiveDateTime>,
+    ) -> CustomResult<i64, errors::StorageError> {
+        let audit_events = self
+            .list_audit_events_by_merchant_id(
+                merchant_id,
+                resource_type,
+                resource_id,
+                created_after,
+                created_before,
+                None,
+                None,
+            )
+            .await?;
+        audit_events
+            .len()
+            .try_into()
+            .into_report()
+            .change_context(errors::StorageError::MockDbError)
+    }
+}
@@ -0,0 +1,287 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, enums},
+};
+
+#[async_trait::async_trait]
+pub trait BlocklistInterface {
+    async fn insert_blocklist_entry(
+        &self,
+        blocklist_entry: storage::BlocklistNew,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError>;
+
+    async fn find_blocklist_entry_by_merchant_id_blocklist_id(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError>;
+
+    async fn find_blocklist_entry_by_merchant_id_data_kind_fingerprint_optional(
+        &self,
+        merchant_id: &str,
+        data_kind: enums::BlocklistDataKind,
+        fingerprint: &str,
+    ) -> CustomResult<Option<storage::Blocklist>, errors::StorageError>;
+
+    async fn list_blocklist_entries_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        data_kind: Option<enums::BlocklistDataKind>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Blocklist>, errors::StorageError>;
+
+    async fn update_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+        blocklist_update: storage::BlocklistUpdate,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError>;
+
+    async fn delete_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl BlocklistInterface for Store {
+    async fn insert_blocklist_entry(
+        &self,
+        blocklist_entry: storage::BlocklistNew,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        blocklist_entry
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_blocklist_entry_by_merchant_id_blocklist_id(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Blocklist::find_by_merchant_id_blocklist_id(&conn, merchant_id, blocklist_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_blocklist_entry_by_merchant_id_data_kind_fingerprint_optional(
+        &self,
+        merchant_id: &str,
+        data_kind: enums::BlocklistDataKind,
+        fingerprint: &str,
+    ) -> CustomResult<Option<storage::Blocklist>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Blocklist::find_optional_by_merchant_id_data_kind_fingerprint(
+            &conn,
+            merchant_id,
+            data_kind,
+            fingerprint,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn list_blocklist_entries_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        data_kind: Option<enums::BlocklistDataKind>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Blocklist>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Blocklist::list_by_merchant_id(&conn, merchant_id, data_kind, limit, offset)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+        blocklist_update: storage::BlocklistUpdate,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Blocklist::update_by_merchant_id_blocklist_id(
+            &conn,
+            merchant_id,
+            blocklist_id,
+            blocklist_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn delete_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Blocklist::delete_by_merchant_id_blocklist_id(&conn, merchant_id, blocklist_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl BlocklistInterface for MockDb {
+    async fn insert_blocklist_entry(
+        &self,
+        blocklist_entry: storage::BlocklistNew,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let mut blocklists = self.blocklists.lock().await;
+        if blocklists.iter().any(|entry| {
+            entry.merchant_id == blocklist_entry.merchant_id
+                && entry.data_kind == blocklist_entry.data_kind
+                && entry.fingerprint == blocklist_entry.fingerprint
+        }) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "blocklist",
+                key: Some(blocklist_entry.blocklist_id.clone()),
+            })?
+        }
+
+        let entry = storage::Blocklist {
+            blocklist_id: blocklist_entry.blocklist_id,
+            merchant_id: blocklist_entry.merchant_id,
+            data_kind: blocklist_entry.data_kind,
+            fingerprint: blocklist_entry.fingerprint,
+            reason: blocklist_entry.reason,
+            expires_at: blocklist_entry.expires_at,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: blocklist_entry.created_at,
+            modified_at: blocklist_entry.modified_at,
+        };
+        blocklists.push(entry.clone());
+        Ok(entry)
+    }
+
+    async fn find_blocklist_entry_by_merchant_id_blocklist_id(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        self.blocklists
+            .lock()
+            .await
+            .iter()
+            .find(|entry| entry.merchant_id == merchant_id && entry.blocklist_id == blocklist_id)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No blocklist entry found for blocklist_id = {blocklist_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn find_blocklist_entry_by_merchant_id_data_kind_fingerprint_optional(
+        &self,
+        merchant_id: &str,
+        data_kind: enums::BlocklistDataKind,
+        fingerprint: &str,
+    ) -> CustomResult<Option<storage::Blocklist>, errors::StorageError> {
+        Ok(self
+            .blocklists
+            .lock()
+            .await
+            .iter()
+            .find(|entry| {
+                entry.merchant_id == merchant_id
+                    && entry.data_kind == data_kind
+                    && entry.fingerprint == fingerprint
+            })
+            .cloned())
+    }
+
+    async fn list_blocklist_entries_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        data_kind: Option<enums::BlocklistDataKind>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Blocklist>, errors::StorageError> {
+        let offset: usize = offset
+            .unwrap_or(0)
+            .try_into()
+            .into_report()
+            .change_context(errors::StorageError::MockDbError)?;
+        let limit: usize = limit
+            .map(TryInto::try_into)
+            .transpose()
+            .into_report()
+            .change_context(errors::StorageError::MockDbError)?
+            .unwrap_or(usize::MAX);
+
+        Ok(self
+            .blocklists
+            .lock()
+            .await
+            .iter()
+            .filter(|entry| {
+                entry.merchant_id == merchant_id
+                    && data_kind.map_or(true, |data_kind| entry.data_kind == data_kind)
+            })
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+        blocklist_update: storage::BlocklistUpdate,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        self.blocklists
+            .lock()
+            .await
+            .iter_mut()
+            .find(|entry| entry.merchant_id == merchant_id && entry.blocklist_id == blocklist_id)
+            .map(|entry| {
+                *entry = storage::BlocklistUpdateInternal::from(blocklist_update)
+                    .apply_changeset(entry.clone());
+                entry.clone()
+            })
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No blocklist entry found for blocklist_id = {blocklist_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn delete_blocklist_entry(
+        &self,
+        merchant_id: &str,
+        blocklist_id: &str,
+    ) -> CustomResult<storage::Blocklist, errors::StorageError> {
+        let mut blocklists = self.blocklists.lock().await;
+        let index = blocklists
+            .iter()
+            .position(|entry| {
+                entry.merchant_id == merchant_id && entry.blocklist_id == blocklist_id
+            })
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No blocklist entry found for blocklist_id = {blocklist_id}"
+            )))?;
+        Ok(blocklists.remove(index))
+    }
+}
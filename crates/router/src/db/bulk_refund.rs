@@ -0,0 +1,99 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait BulkRefundInterface {
+    async fn insert_bulk_refund(
+        &self,
+        bulk_refund: storage::BulkRefundNew,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError>;
+
+    async fn find_bulk_refund_by_merchant_id_bulk_refund_id(
+        &self,
+        merchant_id: &str,
+        bulk_refund_id: &str,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl BulkRefundInterface for Store {
+    async fn insert_bulk_refund(
+        &self,
+        bulk_refund: storage::BulkRefundNew,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        bulk_refund
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_bulk_refund_by_merchant_id_bulk_refund_id(
+        &self,
+        merchant_id: &str,
+        bulk_refund_id: &str,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::BulkRefund::find_by_merchant_id_bulk_refund_id(&conn, merchant_id, bulk_refund_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl BulkRefundInterface for MockDb {
+    async fn insert_bulk_refund(
+        &self,
+        bulk_refund: storage::BulkRefundNew,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError> {
+        let mut bulk_refunds = self.bulk_refunds.lock().await;
+        if bulk_refunds
+            .iter()
+            .any(|existing| existing.bulk_refund_id == bulk_refund.bulk_refund_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "bulk_refund",
+                key: Some(bulk_refund.bulk_refund_id.clone()),
+            })?
+        }
+
+        let bulk_refund = storage::BulkRefund {
+            bulk_refund_id: bulk_refund.bulk_refund_id,
+            merchant_id: bulk_refund.merchant_id,
+            items: bulk_refund.items,
+            created_at: bulk_refund.created_at,
+        };
+        bulk_refunds.push(bulk_refund.clone());
+        Ok(bulk_refund)
+    }
+
+    async fn find_bulk_refund_by_merchant_id_bulk_refund_id(
+        &self,
+        merchant_id: &str,
+        bulk_refund_id: &str,
+    ) -> CustomResult<storage::BulkRefund, errors::StorageError> {
+        self.bulk_refunds
+            .lock()
+            .await
+            .iter()
+            .find(|bulk_refund| {
+                bulk_refund.merchant_id == merchant_id
+                    && bulk_refund.bulk_refund_id == bulk_refund_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No bulk refund found for bulk_refund_id = {bulk_refund_id}"
+                ))
+                .into(),
+            )
+    }
+}
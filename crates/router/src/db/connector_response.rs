@@ -322,13 +322,25 @@ impl ConnectorResponseInterface for MockDb {
     #[instrument(skip_all)]
     async fn find_connector_response_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_type::ConnectorResponse, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.connector_response
+            .lock()
+            .await
+            .iter()
+            .find(|connector_response| {
+                connector_response.payment_id == payment_id
+                    && connector_response.merchant_id == merchant_id
+                    && connector_response.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No connector response found for attempt id = {attempt_id}"
+            )))
+            .into_report()
     }
 
     // safety: interface only used for testing
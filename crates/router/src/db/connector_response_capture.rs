@@ -0,0 +1,140 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait ConnectorResponseCaptureInterface {
+    async fn insert_connector_response_capture(
+        &self,
+        capture: storage::ConnectorResponseCaptureNew,
+    ) -> CustomResult<storage::ConnectorResponseCapture, errors::StorageError>;
+
+    async fn find_latest_connector_response_capture_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError>;
+
+    async fn find_latest_connector_response_capture_by_merchant_id_refund_id(
+        &self,
+        merchant_id: &str,
+        refund_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl ConnectorResponseCaptureInterface for Store {
+    async fn insert_connector_response_capture(
+        &self,
+        capture: storage::ConnectorResponseCaptureNew,
+    ) -> CustomResult<storage::ConnectorResponseCapture, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        capture
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_latest_connector_response_capture_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ConnectorResponseCapture::find_latest_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn find_latest_connector_response_capture_by_merchant_id_refund_id(
+        &self,
+        merchant_id: &str,
+        refund_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ConnectorResponseCapture::find_latest_by_merchant_id_refund_id(
+            &conn,
+            merchant_id,
+            refund_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl ConnectorResponseCaptureInterface for MockDb {
+    async fn insert_connector_response_capture(
+        &self,
+        capture: storage::ConnectorResponseCaptureNew,
+    ) -> CustomResult<storage::ConnectorResponseCapture, errors::StorageError> {
+        let capture = storage::ConnectorResponseCapture {
+            capture_id: capture.capture_id,
+            merchant_id: capture.merchant_id,
+            payment_id: capture.payment_id,
+            attempt_id: capture.attempt_id,
+            refund_id: capture.refund_id,
+            connector: capture.connector,
+            flow: capture.flow,
+            status_code: capture.status_code,
+            response_body: capture.response_body,
+            is_truncated: capture.is_truncated,
+            created_at: capture.created_at,
+        };
+        self.connector_response_captures
+            .lock()
+            .await
+            .push(capture.clone());
+        Ok(capture)
+    }
+
+    async fn find_latest_connector_response_capture_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError> {
+        // The captures are stored in the order they are made, the last one is the latest
+        Ok(self
+            .connector_response_captures
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|capture| {
+                capture.merchant_id == merchant_id
+                    && capture.payment_id == payment_id
+                    && capture.refund_id.is_none()
+            })
+            .cloned())
+    }
+
+    async fn find_latest_connector_response_capture_by_merchant_id_refund_id(
+        &self,
+        merchant_id: &str,
+        refund_id: &str,
+    ) -> CustomResult<Option<storage::ConnectorResponseCapture>, errors::StorageError> {
+        Ok(self
+            .connector_response_captures
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|capture| {
+                capture.merchant_id == merchant_id
+                    && capture.refund_id.as_deref() == Some(refund_id)
+            })
+            .cloned())
+    }
+}
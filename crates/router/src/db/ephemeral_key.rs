@@ -17,6 +17,11 @@ pub trait EphemeralKeyInterface {
         &self,
         _key: &str,
     ) -> CustomResult<EphemeralKey, errors::StorageError>;
+    async fn find_latest_ephemeral_key_by_merchant_id_customer_id(
+        &self,
+        _merchant_id: &str,
+        _customer_id: &str,
+    ) -> CustomResult<EphemeralKey, errors::StorageError>;
     async fn delete_ephemeral_key(
         &self,
         _id: &str,
@@ -46,6 +51,7 @@ mod storage {
         ) -> CustomResult<EphemeralKey, errors::StorageError> {
             let secret_key = format!("epkey_{}", &new.secret);
             let id_key = format!("epkey_{}", &new.id);
+            let customer_key = format!("epkey_customer_{}_{}", &new.merchant_id, &new.customer_id);
 
             let created_at = date_time::now();
             let expires = created_at.saturating_add(validity.hours());
@@ -86,6 +92,16 @@ mod storage {
                         .set_expire_at(&id_key, expire_at)
                         .await
                         .change_context(errors::StorageError::KVError)?;
+                    // Only the latest key of the customer is tracked, to invalidate it when a new key is created
+                    self.get_redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .serialize_and_set_key_with_expiry(
+                            &customer_key,
+                            &created_ek,
+                            validity.hours().whole_seconds(),
+                        )
+                        .await
+                        .change_context(errors::StorageError::KVError)?;
                     Ok(created_ek)
                 }
                 Err(er) => Err(er).change_context(errors::StorageError::KVError),
@@ -102,6 +118,25 @@ mod storage {
                 .await
                 .change_context(errors::StorageError::KVError)
         }
+        async fn find_latest_ephemeral_key_by_merchant_id_customer_id(
+            &self,
+            merchant_id: &str,
+            customer_id: &str,
+        ) -> CustomResult<EphemeralKey, errors::StorageError> {
+            let key = format!("epkey_customer_{merchant_id}_{customer_id}");
+            self.get_redis_conn()
+                .map_err(Into::<errors::StorageError>::into)?
+                .get_and_deserialize_key(&key, "EphemeralKey")
+                .await
+                .map_err(|error| match error.current_context() {
+                    redis_interface::errors::RedisError::NotFound => {
+                        error.change_context(errors::StorageError::ValueNotFound(format!(
+                            "No ephemeral key found for customer {customer_id}"
+                        )))
+                    }
+                    _ => error.change_context(errors::StorageError::KVError),
+                })
+        }
         async fn delete_ephemeral_key(
             &self,
             id: &str,
@@ -163,6 +198,24 @@ impl EphemeralKeyInterface for MockDb {
             ),
         }
     }
+    async fn find_latest_ephemeral_key_by_merchant_id_customer_id(
+        &self,
+        merchant_id: &str,
+        customer_id: &str,
+    ) -> CustomResult<EphemeralKey, errors::StorageError> {
+        self.ephemeral_keys
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|ephemeral_key| {
+                ephemeral_key.merchant_id == merchant_id && ephemeral_key.customer_id == customer_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound("ephemeral key not found".to_string()).into(),
+            )
+    }
     async fn delete_ephemeral_key(
         &self,
         id: &str,
@@ -13,6 +13,10 @@ pub trait EventInterface {
         &self,
         event: storage::EventNew,
     ) -> CustomResult<storage::Event, errors::StorageError>;
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError>;
     async fn update_event(
         &self,
         event_id: String,
@@ -29,6 +33,16 @@ impl EventInterface for Store {
         let conn = connection::pg_connection_write(self).await?;
         event.insert(&conn).await.map_err(Into::into).into_report()
     }
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Event::find_by_event_id(&conn, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
     async fn update_event(
         &self,
         event_id: String,
@@ -71,6 +85,21 @@ impl EventInterface for MockDb {
 
         Ok(stored_event)
     }
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        self.events
+            .lock()
+            .await
+            .iter()
+            .find(|event| event.event_id == event_id)
+            .cloned()
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No event available for event_id = {event_id}"
+            )))
+            .into_report()
+    }
     async fn update_event(
         &self,
         event_id: String,
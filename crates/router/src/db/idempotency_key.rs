@@ -0,0 +1,246 @@
+use diesel_models::errors as storage_errors;
+use error_stack::IntoReport;
+use router_env::logger;
+use storage_impl::redis::kv_store::RedisConnInterface;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+/// The idempotency keys are stored in the database, the completed ones are also cached in redis
+/// until they expire so that the retries of a request are replayed without a database read. The
+/// cache is only an optimization, the keys are read from the database when redis cannot be used.
+#[async_trait::async_trait]
+pub trait IdempotencyKeyInterface {
+    /// Fails with a unique violation if the key is already stored for the merchant
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError>;
+
+    async fn find_idempotency_key_by_merchant_id_idempotency_key_optional(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<Option<storage::IdempotencyKey>, errors::StorageError>;
+
+    async fn update_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError>;
+
+    async fn delete_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+fn get_idempotency_key_cache_key(merchant_id: &str, idempotency_key: &str) -> String {
+    format!("idempotency_key_{merchant_id}_{idempotency_key}")
+}
+
+impl Store {
+    async fn cache_completed_idempotency_key(&self, idempotency_key: &storage::IdempotencyKey) {
+        if idempotency_key.response_status_code.is_none() {
+            return;
+        }
+        let expiry_in_secs = (idempotency_key.expires_at - common_utils::date_time::now())
+            .whole_seconds()
+            .max(1);
+        let key = get_idempotency_key_cache_key(
+            &idempotency_key.merchant_id,
+            &idempotency_key.idempotency_key,
+        );
+        let result = match self.get_redis_conn() {
+            Ok(redis_conn) => {
+                redis_conn
+                    .serialize_and_set_key_with_expiry(&key, idempotency_key, expiry_in_secs)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            logger::error!(?error, "Failed to cache the idempotency key in redis");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeyInterface for Store {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        idempotency_key
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_idempotency_key_by_merchant_id_idempotency_key_optional(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<Option<storage::IdempotencyKey>, errors::StorageError> {
+        let key = get_idempotency_key_cache_key(merchant_id, idempotency_key);
+        if let Ok(redis_conn) = self.get_redis_conn() {
+            match redis_conn
+                .get_and_deserialize_key::<storage::IdempotencyKey>(&key, "IdempotencyKey")
+                .await
+            {
+                Ok(cached) => return Ok(Some(cached)),
+                Err(error) if matches!(error.current_context(), errors::RedisError::NotFound) => {}
+                Err(error) => {
+                    logger::error!(?error, "Failed to read the idempotency key from redis");
+                }
+            }
+        }
+
+        let conn = connection::pg_connection_read(self).await?;
+        let stored = storage::IdempotencyKey::find_optional_by_merchant_id_idempotency_key(
+            &conn,
+            merchant_id,
+            idempotency_key,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()?;
+        if let Some(stored) = stored.as_ref() {
+            self.cache_completed_idempotency_key(stored).await;
+        }
+        Ok(stored)
+    }
+
+    async fn update_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        let updated = storage::IdempotencyKey::update_by_merchant_id_idempotency_key(
+            &conn,
+            merchant_id,
+            idempotency_key,
+            idempotency_key_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()?;
+        self.cache_completed_idempotency_key(&updated).await;
+        Ok(updated)
+    }
+
+    async fn delete_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        if let Ok(redis_conn) = self.get_redis_conn() {
+            let key = get_idempotency_key_cache_key(merchant_id, idempotency_key);
+            if let Err(error) = redis_conn.delete_key(&key).await {
+                logger::error!(?error, "Failed to delete the idempotency key from redis");
+            }
+        }
+
+        let conn = connection::pg_connection_write(self).await?;
+        storage::IdempotencyKey::delete_by_merchant_id_idempotency_key(
+            &conn,
+            merchant_id,
+            idempotency_key,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeyInterface for MockDb {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        // Mirrors the primary key on the merchant id and the idempotency key
+        if idempotency_keys.iter().any(|stored| {
+            stored.merchant_id == idempotency_key.merchant_id
+                && stored.idempotency_key == idempotency_key.idempotency_key
+        }) {
+            Err(errors::StorageError::DatabaseError(
+                storage_errors::DatabaseError::UniqueViolation.into(),
+            ))
+            .into_report()?
+        }
+
+        let idempotency_key = storage::IdempotencyKey {
+            merchant_id: idempotency_key.merchant_id,
+            idempotency_key: idempotency_key.idempotency_key,
+            request_hash: idempotency_key.request_hash,
+            response_status_code: None,
+            response_headers: None,
+            response_body: None,
+            created_at: common_utils::date_time::now(),
+            expires_at: idempotency_key.expires_at,
+        };
+        idempotency_keys.push(idempotency_key.clone());
+        Ok(idempotency_key)
+    }
+
+    async fn find_idempotency_key_by_merchant_id_idempotency_key_optional(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<Option<storage::IdempotencyKey>, errors::StorageError> {
+        Ok(self
+            .idempotency_keys
+            .lock()
+            .await
+            .iter()
+            .find(|stored| {
+                stored.merchant_id == merchant_id && stored.idempotency_key == idempotency_key
+            })
+            .cloned())
+    }
+
+    async fn update_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        let stored = idempotency_keys
+            .iter_mut()
+            .find(|stored| {
+                stored.merchant_id == merchant_id && stored.idempotency_key == idempotency_key
+            })
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No idempotency key {idempotency_key} found for merchant {merchant_id}"
+            )))
+            .into_report()?;
+        *stored = idempotency_key_update.apply_changeset(stored.clone());
+        Ok(stored.clone())
+    }
+
+    async fn delete_idempotency_key_by_merchant_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        let count = idempotency_keys.len();
+        idempotency_keys.retain(|stored| {
+            stored.merchant_id != merchant_id || stored.idempotency_key != idempotency_key
+        });
+        Ok(idempotency_keys.len() < count)
+    }
+}
@@ -1,4 +1,5 @@
 use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
 
 use super::{MockDb, Store};
 use crate::{
@@ -38,6 +39,24 @@ pub trait MandateInterface {
         &self,
         mandate: storage::MandateNew,
     ) -> CustomResult<storage::Mandate, errors::StorageError>;
+
+    /// Counts a charge against the mandate before it is made, `false` if the mandate was already
+    /// charged the maximum number of times in the period and the limit is enforced
+    async fn reserve_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+        enforce_period_limit: bool,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    /// Releases a charge counted by `reserve_mandate_charge` which was not made
+    async fn release_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+    ) -> CustomResult<(), errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -102,6 +121,44 @@ impl MandateInterface for Store {
             .map_err(Into::into)
             .into_report()
     }
+
+    async fn reserve_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+        enforce_period_limit: bool,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Mandate::reserve_charge_by_merchant_id_mandate_id(
+            &conn,
+            merchant_id,
+            mandate_id,
+            period_started_at,
+            enforce_period_limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn release_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+    ) -> CustomResult<(), errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Mandate::release_charge_by_merchant_id_mandate_id(
+            &conn,
+            merchant_id,
+            mandate_id,
+            period_started_at,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -156,12 +213,41 @@ impl MandateInterface for MockDb {
                     }
                     storage::MandateUpdate::CaptureAmountUpdate { amount_captured } => {
                         mandate.amount_captured = amount_captured;
+                        mandate.consecutive_failure_count = 0;
+                    }
+                    storage::MandateUpdate::CaptureAmountAndStatusUpdate {
+                        amount_captured,
+                        mandate_status,
+                    } => {
+                        mandate.amount_captured = amount_captured;
+                        mandate.mandate_status = mandate_status;
+                        mandate.consecutive_failure_count = 0;
                     }
                     storage::MandateUpdate::ConnectorReferenceUpdate {
                         connector_mandate_ids,
                     } => {
                         mandate.connector_mandate_ids = connector_mandate_ids;
                     }
+                    storage::MandateUpdate::FailureCountUpdate {
+                        consecutive_failure_count,
+                        mandate_status,
+                    } => {
+                        mandate.consecutive_failure_count = consecutive_failure_count;
+                        if let Some(mandate_status) = mandate_status {
+                            mandate.mandate_status = mandate_status;
+                        }
+                    }
+                    storage::MandateUpdate::ConnectorMigrationUpdate {
+                        connector,
+                        merchant_connector_id,
+                        connector_mandate_ids,
+                        network_transaction_id,
+                    } => {
+                        mandate.connector = connector;
+                        mandate.merchant_connector_id = merchant_connector_id;
+                        mandate.connector_mandate_ids = connector_mandate_ids;
+                        mandate.network_transaction_id = network_transaction_id;
+                    }
                 }
                 Ok(mandate.clone())
             }
@@ -249,8 +335,71 @@ impl MandateInterface for MockDb {
             end_date: mandate_new.end_date,
             metadata: mandate_new.metadata,
             connector_mandate_ids: mandate_new.connector_mandate_ids,
+            consecutive_failure_count: 0,
+            frequency: mandate_new.frequency,
+            max_charges_per_period: mandate_new.max_charges_per_period,
+            charge_count: 0,
+            period_charge_count: 0,
+            period_started_at: None,
+            merchant_connector_id: mandate_new.merchant_connector_id,
         };
         mandates.push(mandate.clone());
         Ok(mandate)
     }
+
+    async fn reserve_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+        enforce_period_limit: bool,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let mut mandates = self.mandates.lock().await;
+        let Some(mandate) = mandates
+            .iter_mut()
+            .find(|mandate| mandate.merchant_id == merchant_id && mandate.mandate_id == mandate_id)
+        else {
+            return Ok(false);
+        };
+        if let Some(period_started_at) = period_started_at {
+            if mandate.period_started_at == Some(period_started_at) {
+                let limit_reached = mandate
+                    .max_charges_per_period
+                    .map_or(false, |max_charges_per_period| {
+                        mandate.period_charge_count >= max_charges_per_period
+                    });
+                if enforce_period_limit && limit_reached {
+                    return Ok(false);
+                }
+                mandate.period_charge_count += 1;
+            } else {
+                mandate.period_charge_count = 1;
+                mandate.period_started_at = Some(period_started_at);
+            }
+        }
+        mandate.charge_count += 1;
+        Ok(true)
+    }
+
+    async fn release_mandate_charge(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        period_started_at: Option<PrimitiveDateTime>,
+    ) -> CustomResult<(), errors::StorageError> {
+        let mut mandates = self.mandates.lock().await;
+        if let Some(mandate) = mandates
+            .iter_mut()
+            .find(|mandate| mandate.merchant_id == merchant_id && mandate.mandate_id == mandate_id)
+        {
+            if period_started_at.is_some()
+                && mandate.period_started_at == period_started_at
+                && mandate.period_charge_count > 0
+            {
+                mandate.period_charge_count -= 1;
+            }
+            mandate.charge_count = (mandate.charge_count - 1).max(0);
+        }
+        Ok(())
+    }
 }
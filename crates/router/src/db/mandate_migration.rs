@@ -0,0 +1,145 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait MandateMigrationInterface {
+    async fn insert_mandate_migration(
+        &self,
+        mandate_migration: storage::MandateMigrationNew,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError>;
+
+    async fn find_mandate_migration_by_merchant_id_migration_id(
+        &self,
+        merchant_id: &str,
+        migration_id: &str,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError>;
+
+    async fn update_mandate_migration(
+        &self,
+        this: storage::MandateMigration,
+        mandate_migration: storage::MandateMigrationUpdate,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl MandateMigrationInterface for Store {
+    async fn insert_mandate_migration(
+        &self,
+        mandate_migration: storage::MandateMigrationNew,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        mandate_migration
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_mandate_migration_by_merchant_id_migration_id(
+        &self,
+        merchant_id: &str,
+        migration_id: &str,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::MandateMigration::find_by_merchant_id_migration_id(
+            &conn,
+            merchant_id,
+            migration_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_mandate_migration(
+        &self,
+        this: storage::MandateMigration,
+        mandate_migration: storage::MandateMigrationUpdate,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        this.update(&conn, mandate_migration)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl MandateMigrationInterface for MockDb {
+    async fn insert_mandate_migration(
+        &self,
+        mandate_migration: storage::MandateMigrationNew,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        let mut mandate_migrations = self.mandate_migrations.lock().await;
+        if mandate_migrations
+            .iter()
+            .any(|existing| existing.migration_id == mandate_migration.migration_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "mandate_migration",
+                key: Some(mandate_migration.migration_id.clone()),
+            })?
+        }
+
+        let mandate_migration = storage::MandateMigration {
+            migration_id: mandate_migration.migration_id,
+            merchant_id: mandate_migration.merchant_id,
+            source_merchant_connector_id: mandate_migration.source_merchant_connector_id,
+            target_merchant_connector_id: mandate_migration.target_merchant_connector_id,
+            verify: mandate_migration.verify,
+            status: mandate_migration.status,
+            items: mandate_migration.items,
+            created_at: mandate_migration.created_at,
+            modified_at: mandate_migration.modified_at,
+        };
+        mandate_migrations.push(mandate_migration.clone());
+        Ok(mandate_migration)
+    }
+
+    async fn find_mandate_migration_by_merchant_id_migration_id(
+        &self,
+        merchant_id: &str,
+        migration_id: &str,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        self.mandate_migrations
+            .lock()
+            .await
+            .iter()
+            .find(|mandate_migration| {
+                mandate_migration.merchant_id == merchant_id
+                    && mandate_migration.migration_id == migration_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No mandate migration found for migration_id = {migration_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn update_mandate_migration(
+        &self,
+        this: storage::MandateMigration,
+        mandate_migration: storage::MandateMigrationUpdate,
+    ) -> CustomResult<storage::MandateMigration, errors::StorageError> {
+        let mut mandate_migrations = self.mandate_migrations.lock().await;
+        let stored_mandate_migration = mandate_migrations
+            .iter_mut()
+            .find(|stored| stored.migration_id == this.migration_id)
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No mandate migration found for migration_id = {}",
+                this.migration_id
+            )))
+            .into_report()?;
+        *stored_mandate_migration =
+            storage::MandateMigrationUpdateInternal::from(mandate_migration).apply_changeset(this);
+        Ok(stored_mandate_migration.clone())
+    }
+}
@@ -209,6 +209,10 @@ impl MerchantAccountInterface for Store {
                 .change_context(errors::StorageError::DecryptionError)?,
 
             key_store,
+            api_key_permissions: None,
+            api_key_livemode: None,
+            api_key_strict_request_validation: None,
+            api_key_pii_restricted: None,
         })
     }
 
@@ -324,10 +328,38 @@ impl MerchantAccountInterface for MockDb {
 
     async fn find_merchant_account_by_publishable_key(
         &self,
-        _publishable_key: &str,
+        publishable_key: &str,
     ) -> CustomResult<authentication::AuthenticationData, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let merchant_account = self
+            .merchant_accounts
+            .lock()
+            .await
+            .iter()
+            .find(|account| account.publishable_key.as_deref() == Some(publishable_key))
+            .cloned()
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No merchant account found for the publishable key {publishable_key}"
+            )))
+            .into_report()?;
+        let key_store = self
+            .get_merchant_key_store_by_merchant_id(
+                &merchant_account.merchant_id,
+                &self.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        Ok(authentication::AuthenticationData {
+            merchant_account: merchant_account
+                .convert(key_store.key.get_inner())
+                .await
+                .change_context(errors::StorageError::DecryptionError)?,
+
+            key_store,
+            api_key_permissions: None,
+            api_key_livemode: None,
+            api_key_strict_request_validation: None,
+            api_key_pii_restricted: None,
+        })
     }
 
     async fn delete_merchant_account_by_merchant_id(
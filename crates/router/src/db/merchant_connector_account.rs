@@ -125,6 +125,15 @@ where
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::MerchantConnectorAccount, errors::StorageError>;
 
+    /// All the merchant connector accounts of the connector in the business profile, such as the
+    /// accounts of a processor which requires different merchant IDs for cards and wallets
+    async fn find_merchant_connector_accounts_by_profile_id_connector_name(
+        &self,
+        profile_id: &str,
+        connector_name: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::MerchantConnectorAccount>, errors::StorageError>;
+
     async fn find_merchant_connector_account_by_merchant_id_connector_name(
         &self,
         merchant_id: &str,
@@ -258,6 +267,35 @@ impl MerchantConnectorAccountInterface for Store {
         }
     }
 
+    async fn find_merchant_connector_accounts_by_profile_id_connector_name(
+        &self,
+        profile_id: &str,
+        connector_name: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::MerchantConnectorAccount>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::MerchantConnectorAccount::filter_by_profile_id_connector_name(
+            &conn,
+            profile_id,
+            connector_name,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+        .async_and_then(|items| async {
+            let mut output = Vec::with_capacity(items.len());
+            for item in items.into_iter() {
+                output.push(
+                    item.convert(key_store.key.get_inner())
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)?,
+                )
+            }
+            Ok(output)
+        })
+        .await
+    }
+
     async fn find_merchant_connector_account_by_merchant_id_connector_name(
         &self,
         merchant_id: &str,
@@ -509,6 +547,36 @@ impl MerchantConnectorAccountInterface for MockDb {
         }
     }
 
+    async fn find_merchant_connector_accounts_by_profile_id_connector_name(
+        &self,
+        profile_id: &str,
+        connector_name: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::MerchantConnectorAccount>, errors::StorageError> {
+        let accounts = self
+            .merchant_connector_accounts
+            .lock()
+            .await
+            .iter()
+            .filter(|account| {
+                account.profile_id.as_deref() == Some(profile_id)
+                    && account.connector_name == connector_name
+            })
+            .cloned()
+            .collect::<Vec<storage::MerchantConnectorAccount>>();
+
+        let mut output = Vec::with_capacity(accounts.len());
+        for account in accounts.into_iter() {
+            output.push(
+                account
+                    .convert(key_store.key.get_inner())
+                    .await
+                    .change_context(errors::StorageError::DecryptionError)?,
+            )
+        }
+        Ok(output)
+    }
+
     async fn find_merchant_connector_account_by_merchant_id_connector_name(
         &self,
         merchant_id: &str,
@@ -624,6 +692,11 @@ impl MerchantConnectorAccountInterface for MockDb {
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::MerchantConnectorAccount, errors::StorageError> {
         let mut accounts = self.merchant_connector_accounts.lock().await;
+        let (
+            previous_connector_account_details,
+            previous_connector_webhook_details,
+            previous_credentials_expire_at,
+        ) = domain::previous_credentials_to_storage(t.previous_credentials);
         let account = storage::MerchantConnectorAccount {
             id: accounts
                 .len()
@@ -650,6 +723,10 @@ impl MerchantConnectorAccountInterface for MockDb {
             connector_webhook_details: t.connector_webhook_details,
             profile_id: t.profile_id,
             applepay_verified_domains: t.applepay_verified_domains,
+            disabled_flows: t.disabled_flows.map(domain::connector_flows_to_storage),
+            previous_connector_account_details,
+            previous_connector_webhook_details,
+            previous_credentials_expire_at,
         };
         accounts.push(account.clone());
         account
@@ -845,6 +922,8 @@ mod merchant_connector_account_cache_tests {
             connector_webhook_details: None,
             profile_id: Some(profile_id.to_string()),
             applepay_verified_domains: None,
+            disabled_flows: None,
+            previous_credentials: None,
         };
 
         db.insert_merchant_connector_account(mca.clone(), &merchant_key)
@@ -0,0 +1,90 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait PaymentAttemptStepEventInterface {
+    async fn insert_payment_attempt_step_event(
+        &self,
+        event: storage::PaymentAttemptStepEventNew,
+    ) -> CustomResult<storage::PaymentAttemptStepEvent, errors::StorageError>;
+
+    async fn find_payment_attempt_step_events_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStepEvent>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentAttemptStepEventInterface for Store {
+    async fn insert_payment_attempt_step_event(
+        &self,
+        event: storage::PaymentAttemptStepEventNew,
+    ) -> CustomResult<storage::PaymentAttemptStepEvent, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        event.insert(&conn).await.map_err(Into::into).into_report()
+    }
+
+    async fn find_payment_attempt_step_events_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStepEvent>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentAttemptStepEvent::find_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentAttemptStepEventInterface for MockDb {
+    async fn insert_payment_attempt_step_event(
+        &self,
+        event: storage::PaymentAttemptStepEventNew,
+    ) -> CustomResult<storage::PaymentAttemptStepEvent, errors::StorageError> {
+        let mut events = self.payment_attempt_step_events.lock().await;
+        let event = storage::PaymentAttemptStepEvent {
+            id: events
+                .len()
+                .try_into()
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            merchant_id: event.merchant_id,
+            payment_id: event.payment_id,
+            attempt_id: event.attempt_id,
+            step: event.step,
+            connector: event.connector,
+            created_at: event.created_at,
+        };
+        events.push(event.clone());
+        Ok(event)
+    }
+
+    async fn find_payment_attempt_step_events_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStepEvent>, errors::StorageError> {
+        // The events are stored in the order they are recorded
+        Ok(self
+            .payment_attempt_step_events
+            .lock()
+            .await
+            .iter()
+            .filter(|event| event.merchant_id == merchant_id && event.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+}
@@ -1,4 +1,4 @@
-use diesel_models::payment_method::PaymentMethodUpdateInternal;
+use diesel_models::{enums as storage_enums, payment_method::PaymentMethodUpdateInternal};
 use error_stack::{IntoReport, ResultExt};
 
 use super::{MockDb, Store};
@@ -21,6 +21,13 @@ pub trait PaymentMethodInterface {
         merchant_id: &str,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
 
+    async fn find_payment_method_by_customer_id_merchant_id_card_fingerprint_list(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        card_fingerprint: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
+
     async fn insert_payment_method(
         &self,
         payment_method_new: storage::PaymentMethodNew,
@@ -37,6 +44,12 @@ pub trait PaymentMethodInterface {
         merchant_id: &str,
         payment_method_id: &str,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
+
+    async fn find_card_payment_methods_without_fingerprint(
+        &self,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -89,6 +102,24 @@ impl PaymentMethodInterface for Store {
             .into_report()
     }
 
+    async fn find_payment_method_by_customer_id_merchant_id_card_fingerprint_list(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        card_fingerprint: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentMethod::find_by_customer_id_merchant_id_card_fingerprint(
+            &conn,
+            customer_id,
+            merchant_id,
+            card_fingerprint,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
     async fn delete_payment_method_by_merchant_id_payment_method_id(
         &self,
         merchant_id: &str,
@@ -104,6 +135,18 @@ impl PaymentMethodInterface for Store {
         .map_err(Into::into)
         .into_report()
     }
+
+    async fn find_card_payment_methods_without_fingerprint(
+        &self,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentMethod::find_cards_without_fingerprint(&conn, after_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -160,6 +203,8 @@ impl PaymentMethodInterface for MockDb {
             payment_method_issuer_code: payment_method_new.payment_method_issuer_code,
             metadata: payment_method_new.metadata,
             payment_method_data: payment_method_new.payment_method_data,
+            card_fingerprint: payment_method_new.card_fingerprint,
+            locker_id: payment_method_new.locker_id,
         };
         payment_methods.push(payment_method.clone());
         Ok(payment_method)
@@ -187,6 +232,24 @@ impl PaymentMethodInterface for MockDb {
         }
     }
 
+    async fn find_payment_method_by_customer_id_merchant_id_card_fingerprint_list(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        card_fingerprint: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let payment_methods = self.payment_methods.lock().await;
+        Ok(payment_methods
+            .iter()
+            .filter(|pm| {
+                pm.customer_id == customer_id
+                    && pm.merchant_id == merchant_id
+                    && pm.card_fingerprint.as_deref() == Some(card_fingerprint)
+            })
+            .cloned()
+            .collect())
+    }
+
     async fn delete_payment_method_by_merchant_id_payment_method_id(
         &self,
         merchant_id: &str,
@@ -232,4 +295,24 @@ impl PaymentMethodInterface for MockDb {
             .into()),
         }
     }
+
+    async fn find_card_payment_methods_without_fingerprint(
+        &self,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let payment_methods = self.payment_methods.lock().await;
+        let mut payment_methods_found: Vec<storage::PaymentMethod> = payment_methods
+            .iter()
+            .filter(|pm| {
+                pm.id > after_id
+                    && pm.payment_method == storage_enums::PaymentMethod::Card
+                    && pm.card_fingerprint.is_none()
+            })
+            .cloned()
+            .collect();
+        payment_methods_found.sort_by_key(|pm| pm.id);
+        payment_methods_found.truncate(usize::try_from(limit).unwrap_or_default());
+        Ok(payment_methods_found)
+    }
 }
@@ -0,0 +1,146 @@
+use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait PaymentRoutingDecisionInterface {
+    async fn insert_payment_routing_decision(
+        &self,
+        decision: storage::PaymentRoutingDecisionNew,
+    ) -> CustomResult<storage::PaymentRoutingDecision, errors::StorageError>;
+
+    async fn find_payment_routing_decisions_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError>;
+
+    async fn find_payment_routing_decisions_by_merchant_id_created_at_range(
+        &self,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        created_before: PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentRoutingDecisionInterface for Store {
+    async fn insert_payment_routing_decision(
+        &self,
+        decision: storage::PaymentRoutingDecisionNew,
+    ) -> CustomResult<storage::PaymentRoutingDecision, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        decision
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_payment_routing_decisions_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentRoutingDecision::find_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn find_payment_routing_decisions_by_merchant_id_created_at_range(
+        &self,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        created_before: PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentRoutingDecision::find_by_merchant_id_created_at_range(
+            &conn,
+            merchant_id,
+            created_after,
+            created_before,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentRoutingDecisionInterface for MockDb {
+    async fn insert_payment_routing_decision(
+        &self,
+        decision: storage::PaymentRoutingDecisionNew,
+    ) -> CustomResult<storage::PaymentRoutingDecision, errors::StorageError> {
+        let mut decisions = self.payment_routing_decisions.lock().await;
+        let decision = storage::PaymentRoutingDecision {
+            id: decisions
+                .len()
+                .try_into()
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            merchant_id: decision.merchant_id,
+            payment_id: decision.payment_id,
+            attempt_id: decision.attempt_id,
+            decision: decision.decision,
+            connector: decision.connector,
+            routing_rule: decision.routing_rule,
+            fallback_reason: decision.fallback_reason,
+            outcome: decision.outcome,
+            latency_ms: decision.latency_ms,
+            created_at: decision.created_at,
+        };
+        decisions.push(decision.clone());
+        Ok(decision)
+    }
+
+    async fn find_payment_routing_decisions_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError> {
+        // The decisions are stored in the order they are taken
+        Ok(self
+            .payment_routing_decisions
+            .lock()
+            .await
+            .iter()
+            .filter(|decision| {
+                decision.merchant_id == merchant_id && decision.payment_id == payment_id
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_payment_routing_decisions_by_merchant_id_created_at_range(
+        &self,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        created_before: PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PaymentRoutingDecision>, errors::StorageError> {
+        Ok(self
+            .payment_routing_decisions
+            .lock()
+            .await
+            .iter()
+            .filter(|decision| {
+                decision.merchant_id == merchant_id
+                    && decision.created_at >= created_after
+                    && decision.created_at < created_before
+            })
+            .cloned()
+            .collect())
+    }
+}
@@ -64,6 +64,14 @@ pub trait RefundInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_types::Refund, errors::StorageError>;
 
+    #[cfg(feature = "olap")]
+    async fn find_refunds_by_merchant_id_payment_ids(
+        &self,
+        merchant_id: &str,
+        payment_ids: &[String],
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
@@ -206,6 +214,20 @@ mod storage {
                 .into_report()
         }
 
+        #[cfg(feature = "olap")]
+        async fn find_refunds_by_merchant_id_payment_ids(
+            &self,
+            merchant_id: &str,
+            payment_ids: &[String],
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage_types::Refund::find_by_merchant_id_payment_ids(&conn, merchant_id, payment_ids)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
@@ -359,6 +381,8 @@ mod storage {
                         description: new.description.clone(),
                         refund_reason: new.refund_reason.clone(),
                         profile_id: new.profile_id.clone(),
+                        refund_reason_code: new.refund_reason_code,
+                        merchant_connector_id: new.merchant_connector_id.clone(),
                     };
 
                     let field = format!(
@@ -638,6 +662,30 @@ mod storage {
             }
         }
 
+        #[cfg(feature = "olap")]
+        async fn find_refunds_by_merchant_id_payment_ids(
+            &self,
+            merchant_id: &str,
+            payment_ids: &[String],
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_read(self).await?;
+                    storage_types::Refund::find_by_merchant_id_payment_ids(
+                        &conn,
+                        merchant_id,
+                        payment_ids,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                }
+
+                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+            }
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
@@ -761,6 +809,8 @@ impl RefundInterface for MockDb {
             description: new.description,
             refund_reason: new.refund_reason.clone(),
             profile_id: new.profile_id,
+            refund_reason_code: new.refund_reason_code,
+            merchant_connector_id: new.merchant_connector_id,
         };
         refunds.push(refund.clone());
         Ok(refund)
@@ -859,6 +909,24 @@ impl RefundInterface for MockDb {
             .collect::<Vec<_>>())
     }
 
+    #[cfg(feature = "olap")]
+    async fn find_refunds_by_merchant_id_payment_ids(
+        &self,
+        merchant_id: &str,
+        payment_ids: &[String],
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+
+        Ok(refunds
+            .iter()
+            .filter(|refund| {
+                refund.merchant_id == merchant_id && payment_ids.contains(&refund.payment_id)
+            })
+            .cloned()
+            .collect::<Vec<_>>())
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
@@ -0,0 +1,90 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryInterface {
+    async fn insert_webhook_delivery(
+        &self,
+        webhook_delivery: storage::WebhookDeliveryNew,
+    ) -> CustomResult<storage::WebhookDelivery, errors::StorageError>;
+
+    async fn find_webhook_deliveries_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDelivery>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryInterface for Store {
+    async fn insert_webhook_delivery(
+        &self,
+        webhook_delivery: storage::WebhookDeliveryNew,
+    ) -> CustomResult<storage::WebhookDelivery, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        webhook_delivery
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_deliveries_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDelivery>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::WebhookDelivery::find_by_merchant_id_event_id(&conn, merchant_id, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryInterface for MockDb {
+    async fn insert_webhook_delivery(
+        &self,
+        webhook_delivery: storage::WebhookDeliveryNew,
+    ) -> CustomResult<storage::WebhookDelivery, errors::StorageError> {
+        let webhook_delivery = storage::WebhookDelivery {
+            delivery_id: webhook_delivery.delivery_id,
+            event_id: webhook_delivery.event_id,
+            merchant_id: webhook_delivery.merchant_id,
+            subscription_id: webhook_delivery.subscription_id,
+            status: webhook_delivery.status,
+            response_status_code: webhook_delivery.response_status_code,
+            created_at: common_utils::date_time::now(),
+            payload_version: webhook_delivery.payload_version,
+        };
+        self.webhook_deliveries
+            .lock()
+            .await
+            .push(webhook_delivery.clone());
+        Ok(webhook_delivery)
+    }
+
+    async fn find_webhook_deliveries_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDelivery>, errors::StorageError> {
+        Ok(self
+            .webhook_deliveries
+            .lock()
+            .await
+            .iter()
+            .filter(|webhook_delivery| {
+                webhook_delivery.merchant_id == merchant_id && webhook_delivery.event_id == event_id
+            })
+            .cloned()
+            .collect())
+    }
+}
@@ -48,8 +48,11 @@ pub mod headers {
     pub const APIKEY: &str = "apikey";
     pub const X_CC_API_KEY: &str = "X-CC-Api-Key";
     pub const AUTHORIZATION: &str = "Authorization";
+    pub const CACHE_CONTROL: &str = "Cache-Control";
     pub const CONTENT_TYPE: &str = "Content-Type";
     pub const DATE: &str = "Date";
+    pub const ETAG: &str = "ETag";
+    pub const IF_NONE_MATCH: &str = "If-None-Match";
     pub const NONCE: &str = "nonce";
     pub const TIMESTAMP: &str = "Timestamp";
     pub const TOKEN: &str = "token";
@@ -64,6 +67,7 @@ pub mod headers {
     pub const X_ACCEPT_VERSION: &str = "X-Accept-Version";
     pub const X_DATE: &str = "X-Date";
     pub const X_WEBHOOK_SIGNATURE: &str = "X-Webhook-Signature-512";
+    pub const X_WEBHOOK_VERSION: &str = "X-Webhook-Version";
     pub const X_REQUEST_ID: &str = "X-Request-Id";
     pub const STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE: &str = "Stripe-Signature";
 }
@@ -135,8 +139,11 @@ pub fn mk_app(
         server_app = server_app
             .service(routes::MerchantAccount::server(state.clone()))
             .service(routes::ApiKeys::server(state.clone()))
+            .service(routes::Blocklist::server(state.clone()))
             .service(routes::Files::server(state.clone()))
+            .service(routes::Reports::server(state.clone()))
             .service(routes::Disputes::server(state.clone()))
+            .service(routes::Connectors::server(state.clone()))
     }
 
     #[cfg(all(feature = "olap", feature = "kms"))]
@@ -179,6 +186,13 @@ pub async fn start_server(conf: settings::Settings) -> ApplicationResult<Server>
             errors::ApplicationError::ApiClientError(error.current_context().clone())
         })?,
     );
+    services::connector_proxy::check_connector_proxies(&conf.connector_proxies)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Proxy of a connector is not reachable");
+            errors::ApplicationError::ApiClientError(error.current_context().clone())
+        })?;
+    core::bin_database::load_bin_database(&conf.bin_database);
     let state = routes::AppState::new(conf, tx, api_client).await;
     let request_body_limit = server.request_body_limit;
     let server = actix_web::HttpServer::new(move || mk_app(state.clone(), request_body_limit))
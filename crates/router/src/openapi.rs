@@ -60,13 +60,17 @@ Never share your secret api keys. Keep them guarded and secure.
         (name = "Payment Methods", description = "Create and manage payment methods of customers"),
         (name = "Disputes", description = "Manage disputes"),
         // (name = "API Key", description = "Create and manage API Keys"),
+        // (name = "Blocklist", description = "Manage the blocklist of a merchant account"),
         (name = "Payouts", description = "Create and manage payouts"),
+        (name = "Reports", description = "Export reports of payments and refunds"),
     ),
     paths(
         crate::routes::refunds::refunds_create,
         crate::routes::refunds::refunds_retrieve,
         crate::routes::refunds::refunds_update,
         crate::routes::refunds::refunds_list,
+        crate::routes::refunds::refunds_bulk_create,
+        crate::routes::refunds::refunds_bulk_retrieve,
         // Commenting this out as these are admin apis and not to be used by the merchant
         // crate::routes::admin::merchant_account_create,
         // crate::routes::admin::retrieve_merchant_account,
@@ -77,6 +81,23 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::admin::payment_connector_list,
         // crate::routes::admin::payment_connector_update,
         // crate::routes::admin::payment_connector_delete,
+        // crate::routes::admin::payment_connector_verify,
+        // crate::routes::admin::payment_connector_credentials_finalize,
+        // crate::routes::admin::payment_connector_circuit_breakers,
+        // crate::routes::admin::payment_connector_template_export,
+        // crate::routes::admin::payment_connector_template_import,
+        // crate::routes::admin::connector_metadata_schema_retrieve,
+        // crate::routes::admin::connector_capabilities_retrieve,
+        // crate::routes::admin::connector_maintenance_windows_list,
+        // crate::routes::admin::connector_maintenance_window_create,
+        // crate::routes::admin::connector_maintenance_window_delete,
+        // crate::routes::admin::three_ds_decision_rules_retrieve,
+        // crate::routes::admin::three_ds_decision_rules_update,
+        // crate::routes::admin::three_ds_decision_rules_delete,
+        // crate::routes::admin::audit_events_list,
+        // crate::routes::admin::mandate_migration_create,
+        // crate::routes::admin::mandate_migration_retrieve,
+        // crate::routes::admin::webhook_event_preview,
         crate::routes::mandates::get_mandate,
         crate::routes::mandates::revoke_mandate,
         crate::routes::payments::payments_create,
@@ -86,11 +107,19 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payments::payments_confirm,
         crate::routes::payments::payments_capture,
         crate::routes::payments::payments_connector_session,
+        crate::routes::payments::payments_device_data,
     // crate::routes::payments::payments_redirect_response,
         crate::routes::payments::payments_cancel,
         crate::routes::payments::payments_list,
+        crate::routes::payments::payments_routing_decisions_summary,
+        crate::routes::payments::payments_eligible_connectors,
+        crate::routes::payments::payments_attempts_list,
+        crate::routes::payments::payments_recurring_charge,
         crate::routes::payment_methods::create_payment_method_api,
         crate::routes::payment_methods::list_payment_method_api,
+        crate::routes::payment_methods::list_payment_methods_for_payment_api,
+        crate::routes::payment_methods::list_required_fields_api,
+        crate::routes::payment_methods::list_banks_api,
         crate::routes::payment_methods::list_customer_payment_method_api,
         crate::routes::payment_methods::list_customer_payment_method_api_client,
         crate::routes::payment_methods::payment_method_retrieve_api,
@@ -105,6 +134,11 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::api_keys::api_key_update,
         // crate::routes::api_keys::api_key_revoke,
         // crate::routes::api_keys::api_key_list,
+        // crate::routes::blocklist::blocklist_create,
+        // crate::routes::blocklist::blocklist_retrieve,
+        // crate::routes::blocklist::blocklist_update,
+        // crate::routes::blocklist::blocklist_delete,
+        // crate::routes::blocklist::blocklist_list,
         crate::routes::disputes::retrieve_disputes_list,
         crate::routes::disputes::retrieve_dispute,
         crate::routes::payouts::payouts_create,
@@ -112,13 +146,33 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payouts::payouts_fulfill,
         crate::routes::payouts::payouts_retrieve,
         crate::routes::payouts::payouts_update,
+        crate::routes::reports::reconciliation_report_retrieve,
+        crate::routes::reports::reconciliation_report_schedule,
     ),
     components(schemas(
         crate::types::api::refunds::RefundRequest,
         crate::types::api::refunds::RefundType,
+        api_models::enums::RefundReason,
+        api_models::enums::ShippingMethod,
+        api_models::enums::PaymentChannel,
+        api_models::enums::ConnectorFlow,
+        api_models::enums::ProductType,
+        api_models::enums::PlatformFeeRefundBehaviour,
+        api_models::enums::DuplicatePaymentMethodBehaviour,
+        api_models::enums::DuplicateOrderReferenceBehaviour,
+        api_models::enums::AlertType,
+        api_models::enums::AlertStatus,
+        api_models::enums::AlertAction,
         crate::types::api::refunds::RefundResponse,
         crate::types::api::refunds::RefundStatus,
         crate::types::api::refunds::RefundUpdateRequest,
+        crate::types::api::refunds::BulkRefundRequest,
+        crate::types::api::refunds::BulkRefundItemRequest,
+        crate::types::api::refunds::BulkRefundResponse,
+        crate::types::api::refunds::BulkRefundItemResponse,
+        api_models::enums::BulkRefundItemStatus,
+        api_models::enums::MandateMigrationStatus,
+        api_models::enums::MandateMigrationItemStatus,
         crate::types::api::admin::MerchantAccountCreate,
         crate::types::api::admin::MerchantAccountUpdate,
         crate::types::api::admin::MerchantAccountDeleteResponse,
@@ -139,6 +193,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::customers::CustomerResponse,
         api_models::admin::AcceptedCountries,
         api_models::admin::AcceptedCurrencies,
+        api_models::enums::ApiKeyPermission,
+        api_models::enums::BlocklistDataKind,
         api_models::enums::RoutingAlgorithm,
         api_models::enums::PaymentMethod,
         api_models::enums::PaymentMethodType,
@@ -153,6 +209,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::PaymentMethod,
         api_models::enums::PaymentMethodIssuerCode,
         api_models::enums::MandateStatus,
+        api_models::enums::MandateFrequency,
         api_models::enums::PaymentExperience,
         api_models::enums::BankNames,
         api_models::enums::CardNetwork,
@@ -163,8 +220,11 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::FrmAction,
         api_models::enums::FrmPreferredFlowTypes,
         api_models::enums::RetryAction,
+        api_models::enums::PaymentDefaultField,
         api_models::enums::AttemptStatus,
         api_models::enums::CaptureStatus,
+        api_models::enums::AvsResult,
+        api_models::enums::CvcResult,
         api_models::enums::ReconStatus,
         api_models::admin::MerchantConnectorCreate,
         api_models::admin::MerchantConnectorUpdate,
@@ -176,8 +236,11 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::MerchantConnectorDetailsWrap,
         api_models::admin::MerchantConnectorDetails,
         api_models::admin::MerchantConnectorWebhookDetails,
+        api_models::admin::WebhookEndpointRegistration,
+        api_models::admin::WebhookEndpointRegistrationStatus,
         api_models::disputes::DisputeResponse,
         api_models::disputes::DisputeResponsePaymentsRetrieve,
+        api_models::alerts::AlertResponse,
         api_models::payments::AddressDetails,
         api_models::payments::BankDebitData,
         api_models::payments::AliPayQr,
@@ -229,13 +292,37 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::MandateAmountData,
         api_models::payments::OnlineMandate,
         api_models::payments::Card,
+        api_models::payments::CardVerificationResults,
+        api_models::payments::ConnectorFeeDetails,
+        api_models::payments::DisplayAmount,
+        api_models::payments::MaintenanceFallback,
         api_models::payments::CardRedirectData,
         api_models::payments::CustomerAcceptance,
         api_models::payments::PaymentsRequest,
         api_models::payments::PaymentsCreateRequest,
+        api_models::payments::RecurringPaymentsRequest,
+        api_models::payments::ExternalAuthenticationData,
+        api_models::payments::SubMerchantData,
+        api_models::payments::SplitPaymentRequest,
+        api_models::payments::PlatformFee,
+        api_models::payments::SplitPaymentDetails,
+        api_models::payments::AmountDetails,
         api_models::payments::PaymentsResponse,
         api_models::payments::PaymentsStartRequest,
         api_models::payments::PaymentRetrieveBody,
+        api_models::payments::RetrieveExpandField,
+        api_models::payments::ConnectorResponseCaptureStatus,
+        api_models::payments::ConnectorResponseDetails,
+        api_models::payments::PaymentTimelineEvent,
+        api_models::enums::PaymentAttemptStep,
+        api_models::payments::RoutingDecision,
+        api_models::enums::RoutingDecisionType,
+        api_models::enums::RoutingRule,
+        api_models::enums::RoutingFallbackReason,
+        api_models::enums::ConnectorAttemptOutcome,
+        api_models::payments::RoutingDecisionsSummaryRequest,
+        api_models::payments::RoutingDecisionsSummaryResponse,
+        api_models::payments::ConnectorRoutingSummary,
         api_models::payments::PaymentsRetrieveRequest,
         api_models::payments::PaymentIdType,
         api_models::payments::PaymentsCaptureRequest,
@@ -271,12 +358,20 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::ApplePayWalletData,
         api_models::payments::ApplepayPaymentMethod,
         api_models::payments::PaymentsCancelRequest,
+        api_models::enums::CancellationReason,
         api_models::payments::PaymentListConstraints,
         api_models::payments::PaymentListResponse,
+        api_models::payments::PaymentsDeviceDataRequest,
+        api_models::payments::PaymentsDeviceDataResponse,
+        api_models::payments::DeviceDataCollectionParams,
+        api_models::payments::EligibleConnectorsResponse,
+        api_models::payments::ConnectorEligibility,
+        api_models::payments::EligibilityCriterion,
         api_models::payments::CashappQr,
         api_models::payments::BankTransferData,
         api_models::payments::BankTransferNextStepsData,
         api_models::payments::SepaAndBacsBillingDetails,
+        api_models::payments::VirtualAccountBillingDetails,
         api_models::payments::AchBillingDetails,
         api_models::payments::MultibancoBillingDetails,
         api_models::payments::DokuBillingDetails,
@@ -285,6 +380,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::AchTransfer,
         api_models::payments::MultibancoTransferInstructions,
         api_models::payments::DokuBankTransferInstructions,
+        api_models::payments::VirtualAccountTransferInstructions,
         api_models::payments::ApplePayRedirectData,
         api_models::payments::ApplePayThirdPartySdkData,
         api_models::payments::GooglePayRedirectData,
@@ -294,14 +390,27 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::BacsBankTransferInstructions,
         api_models::payments::RedirectResponse,
         api_models::payments::PaymentAttemptResponse,
+        api_models::payments::PaymentAttemptListResponse,
         api_models::payments::CaptureResponse,
         api_models::payment_methods::RequiredFieldInfo,
+        api_models::payment_methods::RequiredFieldsRequest,
+        api_models::payment_methods::RequiredFieldsResponse,
+        api_models::payment_methods::BankListRequest,
+        api_models::payment_methods::BankListResponse,
+        api_models::payment_methods::ConnectorBanks,
         api_models::refunds::RefundListRequest,
+        api_models::reports::ReconciliationReportScheduleRequest,
+        api_models::reports::ReconciliationReportScheduleResponse,
         api_models::refunds::RefundListResponse,
         api_models::refunds::TimeRange,
         api_models::mandates::MandateRevokedResponse,
+        api_models::mandates::MandateMigrationRequest,
+        api_models::mandates::MandateMigrationItemRequest,
+        api_models::mandates::MandateMigrationResponse,
+        api_models::mandates::MandateMigrationItemResponse,
         api_models::mandates::MandateResponse,
         api_models::mandates::MandateCardDetails,
+        api_models::mandates::MandateUsage,
         api_models::ephemeral_key::EphemeralKeyCreateResponse,
         api_models::payments::CustomerDetails,
         api_models::payments::GiftCardData,
@@ -325,17 +434,28 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::FrmMessage,
         api_models::webhooks::OutgoingWebhook,
         api_models::webhooks::OutgoingWebhookContent,
+        api_models::webhooks::OutgoingWebhookError,
+        api_models::webhooks::WebhookEventPreviewResponse,
+        api_models::enums::WebhookPayloadVersion,
         api_models::enums::EventType,
         crate::types::api::admin::MerchantAccountResponse,
         crate::types::api::admin::MerchantConnectorId,
         crate::types::api::admin::MerchantDetails,
         crate::types::api::admin::WebhookDetails,
+        crate::types::api::admin::WebhookAuthentication,
+        crate::types::api::admin::WebhookSubscription,
+        crate::types::api::admin::PiiPolicy,
+        crate::types::api::admin::PiiRedaction,
         crate::types::api::api_keys::ApiKeyExpiration,
         crate::types::api::api_keys::CreateApiKeyRequest,
         crate::types::api::api_keys::CreateApiKeyResponse,
         crate::types::api::api_keys::RetrieveApiKeyResponse,
         crate::types::api::api_keys::RevokeApiKeyResponse,
-        crate::types::api::api_keys::UpdateApiKeyRequest
+        crate::types::api::api_keys::UpdateApiKeyRequest,
+        api_models::blocklist::CreateBlocklistRequest,
+        api_models::blocklist::UpdateBlocklistRequest,
+        api_models::blocklist::BlocklistResponse,
+        api_models::blocklist::DeleteBlocklistResponse
     )),
     modifiers(&SecurityAddon)
 )]
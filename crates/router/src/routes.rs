@@ -1,6 +1,7 @@
 pub mod admin;
 pub mod api_keys;
 pub mod app;
+pub mod blocklist;
 pub mod cache;
 pub mod cards_info;
 pub mod configs;
@@ -19,6 +20,8 @@ pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
+#[cfg(feature = "olap")]
+pub mod reports;
 #[cfg(all(feature = "olap", feature = "kms"))]
 pub mod verification;
 pub mod webhooks;
@@ -30,9 +33,9 @@ pub use self::app::Payouts;
 #[cfg(all(feature = "olap", feature = "kms"))]
 pub use self::app::Verify;
 pub use self::app::{
-    ApiKeys, AppState, BusinessProfile, Cache, Cards, Configs, Customers, Disputes, EphemeralKey,
-    Files, Health, Mandates, MerchantAccount, MerchantConnectorAccount, PaymentMethods, Payments,
-    Refunds, Webhooks,
+    ApiKeys, AppState, Blocklist, BusinessProfile, Cache, Cards, Configs, Connectors, Customers,
+    Disputes, EphemeralKey, Files, Health, Mandates, MerchantAccount, MerchantConnectorAccount,
+    PaymentMethods, Payments, Refunds, Reports, Webhooks,
 };
 #[cfg(feature = "stripe")]
 pub use super::compatibility::stripe::StripeApis;
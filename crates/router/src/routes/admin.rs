@@ -3,7 +3,10 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::{admin::*, api_locking},
+    core::{
+        admin::*, api_locking, audit_events, connector_maintenance, connector_templates,
+        mandate::migration, verify_connector, webhooks,
+    },
     services::{api, authentication as auth},
     types::api::admin,
 };
@@ -231,6 +234,55 @@ pub async fn payment_connector_retrieve(
     )
     .await
 }
+/// Merchant Connector - Verify
+///
+/// Run a test payment against the Merchant Connector, to verify its credentials and configuration
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/verify",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = String, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    request_body = MerchantConnectorVerifyRequest,
+    responses(
+        (status = 200, description = "Test payment run against the Merchant Connector", body = MerchantConnectorVerifyResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 412, description = "Merchant Connector is not in test mode"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Verify a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsVerify))]
+pub async fn payment_connector_verify(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<admin::MerchantConnectorVerifyRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsVerify;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| {
+            verify_connector::verify_merchant_connector(
+                state,
+                merchant_id.clone(),
+                merchant_connector_id.clone(),
+                req,
+            )
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// Merchant Connector - List
 ///
 /// List Merchant Connector Details for the merchant
@@ -269,6 +321,320 @@ pub async fn payment_connector_list(
     )
     .await
 }
+/// Merchant Connector - Circuit Breakers
+///
+/// Retrieve the circuit breaker status of the connectors configured for the merchant
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/connectors/circuit_breakers",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker status retrieved successfully", body = ConnectorCircuitBreakerStatusResponse),
+        (status = 404, description = "Merchant Account does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Retrieve the circuit breaker status of Merchant Connectors",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsCircuitBreakerRetrieve))]
+pub async fn payment_connector_circuit_breakers(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsCircuitBreakerRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| retrieve_connector_circuit_breakers(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Connector - Template Export
+///
+/// Export the Merchant Connectors of the merchant as a template, with their credentials replaced by placeholders, to be imported into other merchants
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/connectors/template",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+    ),
+    responses(
+        (status = 200, description = "Merchant Connectors exported successfully", body = MerchantConnectorTemplate),
+        (status = 404, description = "Merchant Account does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Export the Merchant Connectors as a template",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsTemplateExport))]
+pub async fn payment_connector_template_export(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsTemplateExport;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| connector_templates::export_connector_template(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Connector - Template Import
+///
+/// Apply a template of Merchant Connectors to the merchant, with the values of the credential placeholders of the merchant. The Merchant Connectors are identified by their labels, the ones with a label already used by the merchant are updated instead of being created again
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/template",
+    request_body = MerchantConnectorTemplateImportRequest,
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+    ),
+    responses(
+        (status = 200, description = "Template imported, with the outcome of each entry", body = MerchantConnectorTemplateImportResponse),
+        (status = 404, description = "Merchant Account does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Import a template of Merchant Connectors",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsTemplateImport))]
+pub async fn payment_connector_template_import(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::admin::MerchantConnectorTemplateImportRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsTemplateImport;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| {
+            connector_templates::import_connector_template(state, merchant_id.clone(), req)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Connector - Metadata Schema
+///
+/// Retrieve the fields expected by a connector in the metadata of the merchant connector account
+#[utoipa::path(
+    get,
+    path = "/accounts/connectors/metadata_schema/{connector}",
+    params(
+        ("connector" = Connector, Path, description = "The name of the connector"),
+    ),
+    responses(
+        (status = 200, description = "Connector metadata schema retrieved successfully", body = ConnectorMetadataSchemaResponse),
+        (status = 400, description = "Invalid connector name"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Retrieve the metadata schema of a Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorMetadataSchemaRetrieve))]
+pub async fn connector_metadata_schema_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<api_models::enums::Connector>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorMetadataSchemaRetrieve;
+    let connector = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        connector,
+        |_, _, connector| retrieve_connector_metadata_schema(connector),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Connector - Capabilities
+///
+/// Retrieve the payment methods, currencies and countries which a connector can process, and the flows which it supports
+#[utoipa::path(
+    get,
+    path = "/connectors/{connector}/capabilities",
+    params(
+        ("connector" = Connector, Path, description = "The name of the connector"),
+    ),
+    responses(
+        (status = 200, description = "Connector capabilities retrieved successfully", body = ConnectorCapabilitiesResponse),
+        (status = 400, description = "Invalid connector name"),
+        (status = 401, description = "Unauthorized request"),
+        (status = 404, description = "Connector does not declare its capabilities")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Retrieve the capabilities of a Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorCapabilitiesRetrieve))]
+pub async fn connector_capabilities_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<api_models::enums::Connector>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorCapabilitiesRetrieve;
+    let connector = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        connector,
+        |_, _, connector| retrieve_connector_capabilities(connector),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Connector - Maintenance Windows List
+///
+/// List the maintenance windows of a connector which have not ended
+#[utoipa::path(
+    get,
+    path = "/connectors/{connector}/maintenance_windows",
+    params(
+        ("connector" = Connector, Path, description = "The name of the connector"),
+    ),
+    responses(
+        (status = 200, description = "Maintenance windows retrieved successfully", body = ConnectorMaintenanceWindowsResponse),
+        (status = 400, description = "Invalid connector name"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "List the maintenance windows of a Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorMaintenanceWindowsList))]
+pub async fn connector_maintenance_windows_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<api_models::enums::Connector>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorMaintenanceWindowsList;
+    let connector = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        connector,
+        |state, _, connector| connector_maintenance::list_maintenance_windows(state, connector),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Connector - Maintenance Window Create
+///
+/// Create a maintenance window of a connector. During the window, new payments are routed to the fallback connectors of the merchants, and the other affected flows are not sent to the connector. The syncs and the webhooks of the connector are not affected
+#[utoipa::path(
+    post,
+    path = "/connectors/{connector}/maintenance_windows",
+    request_body = ConnectorMaintenanceWindowRequest,
+    params(
+        ("connector" = Connector, Path, description = "The name of the connector"),
+    ),
+    responses(
+        (status = 200, description = "Maintenance window created successfully", body = ConnectorMaintenanceWindowsResponse),
+        (status = 400, description = "Invalid maintenance window"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Create a maintenance window of a Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorMaintenanceWindowCreate))]
+pub async fn connector_maintenance_window_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<api_models::enums::Connector>,
+    json_payload: web::Json<api_models::admin::ConnectorMaintenanceWindowRequest>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorMaintenanceWindowCreate;
+    let connector = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| connector_maintenance::create_maintenance_window(state, connector, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Connector - Maintenance Window Delete
+///
+/// Delete a maintenance window of a connector
+#[utoipa::path(
+    delete,
+    path = "/connectors/{connector}/maintenance_windows/{window_id}",
+    params(
+        ("connector" = Connector, Path, description = "The name of the connector"),
+        ("window_id" = String, Path, description = "The identifier of the maintenance window"),
+    ),
+    responses(
+        (status = 200, description = "Maintenance window deleted successfully", body = ConnectorMaintenanceWindowsResponse),
+        (status = 404, description = "Maintenance window does not exist"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Delete a maintenance window of a Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorMaintenanceWindowDelete))]
+pub async fn connector_maintenance_window_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(api_models::enums::Connector, String)>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorMaintenanceWindowDelete;
+    let (connector, window_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        window_id,
+        |state, _, window_id| {
+            connector_maintenance::delete_maintenance_window(state, connector, window_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// Merchant Connector - Update
 ///
 /// To update an existing Merchant Connector. Helpful in enabling / disabling different payment methods and other settings for the connector etc.
@@ -310,6 +676,48 @@ pub async fn payment_connector_update(
     )
     .await
 }
+/// Merchant Connector - Finalize Credential Rotation
+///
+/// Drop the credentials replaced in the last rotation of the credentials of a Merchant Connector, after which the webhooks signed with them are not accepted
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/credentials/finalize",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = String, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Credential rotation finalized", body = MerchantConnectorResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 412, description = "The credentials of the Merchant Connector are not being rotated"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Finalize the credential rotation of a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsCredentialsFinalize))]
+pub async fn payment_connector_credentials_finalize(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsCredentialsFinalize;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, _, _| {
+            finalize_connector_credential_rotation(state, &merchant_id, &merchant_connector_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// Merchant Connector - Delete
 ///
 /// Delete or Detach a Merchant Connector from Merchant Account
@@ -408,14 +816,14 @@ pub async fn business_profile_retrieve(
     path: web::Path<(String, String)>,
 ) -> HttpResponse {
     let flow = Flow::BusinessProfileRetrieve;
-    let (_, profile_id) = path.into_inner();
+    let (merchant_id, profile_id) = path.into_inner();
 
     api::server_wrap(
         flow,
         state,
         &req,
         profile_id,
-        |state, _, profile_id| retrieve_business_profile(state, profile_id),
+        |state, _, profile_id| retrieve_business_profile(state, profile_id, &merchant_id),
         &auth::AdminApiAuth,
         api_locking::LockAction::NotApplicable,
     )
@@ -505,3 +913,281 @@ pub async fn merchant_account_kv_status(
     )
     .await
 }
+/// Merchant Account - Retrieve 3DS Decision Rules
+///
+/// Retrieve the rules deciding whether the card payments of the merchant are authenticated with 3DS
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/three_ds_decision_rules",
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "3DS Decision Rules Retrieved", body = ThreeDsDecisionRulesResponse),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Retrieve the 3DS Decision Rules of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ThreeDsDecisionRulesRetrieve))]
+pub async fn three_ds_decision_rules_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::ThreeDsDecisionRulesRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| retrieve_three_ds_decision_rules(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Account - Update 3DS Decision Rules
+///
+/// Replace the rules deciding whether the card payments of the merchant are authenticated with 3DS. The rules are evaluated in order on confirm, and the first matching rule overrides the requested authentication type.
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/three_ds_decision_rules",
+    request_body = ThreeDsDecisionRulesRequest,
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "3DS Decision Rules Updated", body = ThreeDsDecisionRulesResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Update the 3DS Decision Rules of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ThreeDsDecisionRulesUpdate))]
+pub async fn three_ds_decision_rules_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<admin::ThreeDsDecisionRulesRequest>,
+) -> HttpResponse {
+    let flow = Flow::ThreeDsDecisionRulesUpdate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| update_three_ds_decision_rules(state, merchant_id.clone(), req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Account - Delete 3DS Decision Rules
+///
+/// Delete the 3DS decision rules of the merchant, the requested authentication type is used for the payments thereafter
+#[utoipa::path(
+    delete,
+    path = "/accounts/{account_id}/three_ds_decision_rules",
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "3DS Decision Rules Deleted", body = ThreeDsDecisionRulesResponse),
+        (status = 404, description = "3DS decision rules are not configured for the merchant")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Delete the 3DS Decision Rules of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ThreeDsDecisionRulesDelete))]
+pub async fn three_ds_decision_rules_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::ThreeDsDecisionRulesDelete;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| delete_three_ds_decision_rules(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Account - List Audit Events
+///
+/// List the changes made to the merchant account, its merchant connector accounts and its API keys through the admin API, the latest first. The values of the secret fields are masked in the recorded changes.
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/audit_events",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("resource_type" = Option<AuditResourceType>, Query, description = "Only list the events of this kind of resource"),
+        ("resource_id" = Option<String>, Query, description = "Only list the events of the resource with this identifier"),
+        ("created_after" = Option<PrimitiveDateTime>, Query, description = "Only list the events recorded at or after this time"),
+        ("created_before" = Option<PrimitiveDateTime>, Query, description = "Only list the events recorded before this time"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of events to include in the response, between 1 and 100. Defaults to 10"),
+        ("skip" = Option<i64>, Query, description = "The number of events to skip when retrieving the list of events"),
+    ),
+    responses(
+        (status = 200, description = "Audit Events Retrieved", body = AuditEventListResponse),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "List the Audit Events of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::AuditEventsList))]
+pub async fn audit_events_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<api_models::audit_events::ListAuditEventsConstraints>,
+) -> HttpResponse {
+    let flow = Flow::AuditEventsList;
+    let merchant_id = path.into_inner();
+    let constraints = query.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, constraints),
+        |state, _, (merchant_id, constraints)| {
+            audit_events::list_audit_events(state, merchant_id, constraints)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Create Mandate Migration
+///
+/// Migrate the mandates of a merchant connector account to another merchant connector account, with the mapping of their references produced by the token migration program of the connectors. The mandates are migrated in the background, each independently of the others.
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/mandate_migrations",
+    params(("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    request_body = MandateMigrationRequest,
+    responses(
+        (status = 200, description = "Mandate Migration Created", body = MandateMigrationResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Merchant account or merchant connector account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Create a Mandate Migration",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MandateMigrationCreate))]
+pub async fn mandate_migration_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::mandates::MandateMigrationRequest>,
+) -> HttpResponse {
+    let flow = Flow::MandateMigrationCreate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, json_payload.into_inner()),
+        |state, _, (merchant_id, payload)| {
+            migration::mandate_migration_create_core(state, merchant_id, payload)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Retrieve Mandate Migration
+///
+/// Retrieve the progress of a mandate migration, with the outcome of the migration of each of its mandates.
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/mandate_migrations/{migration_id}",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("migration_id" = String, Path, description = "The identifier for the mandate migration")
+    ),
+    responses(
+        (status = 200, description = "Mandate Migration Retrieved", body = MandateMigrationResponse),
+        (status = 404, description = "Mandate migration not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Retrieve a Mandate Migration",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MandateMigrationRetrieve))]
+pub async fn mandate_migration_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::MandateMigrationRetrieve;
+    let (merchant_id, migration_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, migration_id),
+        |state, _, (merchant_id, migration_id)| {
+            migration::mandate_migration_retrieve_core(state, merchant_id, migration_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Preview Webhook Event
+///
+/// Render a webhook event in each version of the payloads of the webhooks, to see how the webhooks would change on moving to another version. The object of the event is rendered in its current state.
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/webhook_events/{event_id}/preview",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("event_id" = String, Path, description = "The unique identifier for the webhook event")
+    ),
+    responses(
+        (status = 200, description = "Webhook Event Previewed", body = WebhookEventPreviewResponse),
+        (status = 404, description = "Webhook event not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Preview a Webhook Event",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventPreview))]
+pub async fn webhook_event_preview(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::WebhookEventPreview;
+    let (merchant_id, event_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, event_id),
+        |state, _, (merchant_id, event_id)| {
+            webhooks::preview_webhook_event(state, merchant_id, event_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
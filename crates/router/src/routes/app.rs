@@ -16,7 +16,7 @@ use super::payouts::*;
 #[cfg(all(feature = "olap", feature = "kms"))]
 use super::verification::{apple_pay_merchant_registration, retrieve_apple_pay_verified_domains};
 #[cfg(feature = "olap")]
-use super::{admin::*, api_keys::*, disputes::*, files::*};
+use super::{admin::*, api_keys::*, blocklist::*, disputes::*, files::*, reports::*};
 use super::{cache::*, health::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
 use super::{configs::*, customers::*, mandates::*, payments::*, refunds::*};
@@ -214,15 +214,25 @@ impl Payments {
                         .route(web::post().to(payments_list_by_filter)),
                 )
                 .service(web::resource("/filter").route(web::post().to(get_filters_for_payments)))
+                .service(
+                    web::resource("/routing_decisions/summary")
+                        .route(web::get().to(payments_routing_decisions_summary)),
+                )
         }
         #[cfg(feature = "oltp")]
         {
             route = route
                 .service(web::resource("").route(web::post().to(payments_create)))
+                .service(
+                    web::resource("/recurring").route(web::post().to(payments_recurring_charge)),
+                )
                 .service(
                     web::resource("/session_tokens")
                         .route(web::post().to(payments_connector_session)),
                 )
+                .service(
+                    web::resource("/device_data").route(web::post().to(payments_device_data)),
+                )
                 .service(
                     web::resource("/sync")
                         .route(web::post().to(payments_retrieve_with_gateway_creds)),
@@ -235,6 +245,18 @@ impl Payments {
                 .service(
                     web::resource("/{payment_id}/confirm").route(web::post().to(payments_confirm)),
                 )
+                .service(
+                    web::resource("/{payment_id}/payment_methods")
+                        .route(web::get().to(list_payment_methods_for_payment_api)),
+                )
+                .service(
+                    web::resource("/{payment_id}/eligible_connectors")
+                        .route(web::get().to(payments_eligible_connectors)),
+                )
+                .service(
+                    web::resource("/{payment_id}/attempts")
+                        .route(web::get().to(payments_attempts_list)),
+                )
                 .service(
                     web::resource("/{payment_id}/cancel").route(web::post().to(payments_cancel)),
                 )
@@ -322,6 +344,11 @@ impl Refunds {
             route = route
                 .service(web::resource("").route(web::post().to(refunds_create)))
                 .service(web::resource("/sync").route(web::post().to(refunds_retrieve_with_body)))
+                .service(web::resource("/bulk").route(web::post().to(refunds_bulk_create)))
+                .service(
+                    web::resource("/bulk/{bulk_refund_id}")
+                        .route(web::get().to(refunds_bulk_retrieve)),
+                )
                 .service(
                     web::resource("/{id}")
                         .route(web::get().to(refunds_retrieve))
@@ -363,6 +390,12 @@ impl PaymentMethods {
                     .route(web::post().to(create_payment_method_api))
                     .route(web::get().to(list_payment_method_api)), // TODO : added for sdk compatibility for now, need to deprecate this later
             )
+            .service(
+                web::resource("/required_fields").route(web::get().to(list_required_fields_api)),
+            )
+            .service(
+                web::resource("/{payment_method_type}/banks").route(web::get().to(list_banks_api)),
+            )
             .service(
                 web::resource("/{payment_method_id}")
                     .route(web::get().to(payment_method_retrieve_api))
@@ -385,6 +418,25 @@ impl MerchantAccount {
                     .route(web::post().to(merchant_account_toggle_kv))
                     .route(web::get().to(merchant_account_kv_status)),
             )
+            .service(
+                web::resource("/{id}/three_ds_decision_rules")
+                    .route(web::get().to(three_ds_decision_rules_retrieve))
+                    .route(web::post().to(three_ds_decision_rules_update))
+                    .route(web::delete().to(three_ds_decision_rules_delete)),
+            )
+            .service(web::resource("/{id}/audit_events").route(web::get().to(audit_events_list)))
+            .service(
+                web::resource("/{id}/mandate_migrations")
+                    .route(web::post().to(mandate_migration_create)),
+            )
+            .service(
+                web::resource("/{id}/mandate_migrations/{migration_id}")
+                    .route(web::get().to(mandate_migration_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/webhook_events/{event_id}/preview")
+                    .route(web::get().to(webhook_event_preview)),
+            )
             .service(
                 web::resource("/{id}")
                     .route(web::get().to(retrieve_merchant_account))
@@ -411,11 +463,34 @@ impl MerchantConnectorAccount {
                         .route(web::post().to(payment_connector_create))
                         .route(web::get().to(payment_connector_list)),
                 )
+                .service(
+                    web::resource("/{merchant_id}/connectors/circuit_breakers")
+                        .route(web::get().to(payment_connector_circuit_breakers)),
+                )
+                .service(
+                    web::resource("/{merchant_id}/connectors/template")
+                        .route(web::get().to(payment_connector_template_export))
+                        .route(web::post().to(payment_connector_template_import)),
+                )
+                .service(
+                    web::resource("/connectors/metadata_schema/{connector}")
+                        .route(web::get().to(connector_metadata_schema_retrieve)),
+                )
                 .service(
                     web::resource("/{merchant_id}/connectors/{merchant_connector_id}")
                         .route(web::get().to(payment_connector_retrieve))
                         .route(web::post().to(payment_connector_update))
                         .route(web::delete().to(payment_connector_delete)),
+                )
+                .service(
+                    web::resource("/{merchant_id}/connectors/{merchant_connector_id}/verify")
+                        .route(web::post().to(payment_connector_verify)),
+                )
+                .service(
+                    web::resource(
+                        "/{merchant_id}/connectors/{merchant_connector_id}/credentials/finalize",
+                    )
+                    .route(web::post().to(payment_connector_credentials_finalize)),
                 );
         }
         #[cfg(feature = "oltp")]
@@ -428,6 +503,29 @@ impl MerchantConnectorAccount {
     }
 }
 
+pub struct Connectors;
+
+#[cfg(feature = "olap")]
+impl Connectors {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/connectors")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/{connector}/capabilities")
+                    .route(web::get().to(connector_capabilities_retrieve)),
+            )
+            .service(
+                web::resource("/{connector}/maintenance_windows")
+                    .route(web::get().to(connector_maintenance_windows_list))
+                    .route(web::post().to(connector_maintenance_window_create)),
+            )
+            .service(
+                web::resource("/{connector}/maintenance_windows/{window_id}")
+                    .route(web::delete().to(connector_maintenance_window_delete)),
+            )
+    }
+}
+
 pub struct EphemeralKey;
 
 #[cfg(feature = "oltp")]
@@ -518,6 +616,24 @@ impl ApiKeys {
     }
 }
 
+pub struct Blocklist;
+
+#[cfg(feature = "olap")]
+impl Blocklist {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/blocklist/{merchant_id}")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(blocklist_create)))
+            .service(web::resource("/list").route(web::get().to(blocklist_list)))
+            .service(
+                web::resource("/{blocklist_id}")
+                    .route(web::get().to(blocklist_retrieve))
+                    .route(web::post().to(blocklist_update))
+                    .route(web::delete().to(blocklist_delete)),
+            )
+    }
+}
+
 pub struct Disputes;
 
 #[cfg(feature = "olap")]
@@ -566,6 +682,21 @@ impl Files {
     }
 }
 
+pub struct Reports;
+
+#[cfg(feature = "olap")]
+impl Reports {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/reports")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/reconciliation")
+                    .route(web::get().to(reconciliation_report_retrieve))
+                    .route(web::post().to(reconciliation_report_schedule)),
+            )
+    }
+}
+
 pub struct Cache;
 
 impl Cache {
@@ -0,0 +1,215 @@
+use actix_web::{web, HttpRequest, Responder};
+use api_models::blocklist as blocklist_api;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, blocklist},
+    services::{api, authentication as auth},
+};
+
+/// Blocklist - Create
+///
+/// Add an entry to the blocklist of the merchant. Payments whose card, email or IP address match
+/// the entry are failed before they are routed to a connector.
+#[utoipa::path(
+    post,
+    path = "/blocklist/{merchant_id}",
+    params(("merchant_id" = String, Path, description = "The unique identifier for the merchant account")),
+    request_body = CreateBlocklistRequest,
+    responses(
+        (status = 200, description = "Blocklist entry created", body = BlocklistResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Blocklist",
+    operation_id = "Create a Blocklist entry",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::BlocklistCreate))]
+pub async fn blocklist_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<blocklist_api::CreateBlocklistRequest>,
+) -> impl Responder {
+    let flow = Flow::BlocklistCreate;
+    let payload = json_payload.into_inner();
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, payload| blocklist::create_blocklist_entry(state, merchant_id.clone(), payload),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Blocklist - Retrieve
+///
+/// Retrieve the specified blocklist entry.
+#[utoipa::path(
+    get,
+    path = "/blocklist/{merchant_id}/{blocklist_id}",
+    params (
+        ("merchant_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("blocklist_id" = String, Path, description = "The unique identifier for the blocklist entry")
+    ),
+    responses(
+        (status = 200, description = "Blocklist entry retrieved", body = BlocklistResponse),
+        (status = 404, description = "Blocklist entry not found")
+    ),
+    tag = "Blocklist",
+    operation_id = "Retrieve a Blocklist entry",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::BlocklistRetrieve))]
+pub async fn blocklist_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::BlocklistRetrieve;
+    let (merchant_id, blocklist_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (&merchant_id, &blocklist_id),
+        |state, _, (merchant_id, blocklist_id)| {
+            blocklist::retrieve_blocklist_entry(state, merchant_id, blocklist_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Blocklist - Update
+///
+/// Update the reason or the expiry of the specified blocklist entry.
+#[utoipa::path(
+    post,
+    path = "/blocklist/{merchant_id}/{blocklist_id}",
+    request_body = UpdateBlocklistRequest,
+    params (
+        ("merchant_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("blocklist_id" = String, Path, description = "The unique identifier for the blocklist entry")
+    ),
+    responses(
+        (status = 200, description = "Blocklist entry updated", body = BlocklistResponse),
+        (status = 404, description = "Blocklist entry not found")
+    ),
+    tag = "Blocklist",
+    operation_id = "Update a Blocklist entry",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::BlocklistUpdate))]
+pub async fn blocklist_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<blocklist_api::UpdateBlocklistRequest>,
+) -> impl Responder {
+    let flow = Flow::BlocklistUpdate;
+    let (merchant_id, blocklist_id) = path.into_inner();
+    let payload = json_payload.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (&merchant_id, &blocklist_id, payload),
+        |state, _, (merchant_id, blocklist_id, payload)| {
+            blocklist::update_blocklist_entry(state, merchant_id, blocklist_id, payload)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Blocklist - Delete
+///
+/// Delete the specified blocklist entry. Once deleted, the entry no longer blocks payments.
+#[utoipa::path(
+    delete,
+    path = "/blocklist/{merchant_id}/{blocklist_id}",
+    params (
+        ("merchant_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("blocklist_id" = String, Path, description = "The unique identifier for the blocklist entry")
+    ),
+    responses(
+        (status = 200, description = "Blocklist entry deleted", body = DeleteBlocklistResponse),
+        (status = 404, description = "Blocklist entry not found")
+    ),
+    tag = "Blocklist",
+    operation_id = "Delete a Blocklist entry",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::BlocklistDelete))]
+pub async fn blocklist_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::BlocklistDelete;
+    let (merchant_id, blocklist_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (&merchant_id, &blocklist_id),
+        |state, _, (merchant_id, blocklist_id)| {
+            blocklist::delete_blocklist_entry(state, merchant_id, blocklist_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Blocklist - List
+///
+/// List the entries of the blocklist of the merchant.
+#[utoipa::path(
+    get,
+    path = "/blocklist/{merchant_id}/list",
+    params(
+        ("merchant_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("data_kind" = Option<BlocklistDataKind>, Query, description = "Only list the entries which block this kind of data"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of entries to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of entries to skip when retrieving the list of entries"),
+    ),
+    responses(
+        (status = 200, description = "List of blocklist entries retrieved successfully", body = Vec<BlocklistResponse>),
+    ),
+    tag = "Blocklist",
+    operation_id = "List all Blocklist entries of a merchant account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::BlocklistList))]
+pub async fn blocklist_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<blocklist_api::ListBlocklistConstraints>,
+) -> impl Responder {
+    let flow = Flow::BlocklistList;
+    let constraints = query.into_inner();
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, constraints),
+        |state, _, (merchant_id, constraints)| async move {
+            blocklist::list_blocklist_entries(state, merchant_id, constraints).await
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -1,4 +1,7 @@
 pub const PAYMENT_ID_PREFIX: &str = "dummy_pay";
 pub const ATTEMPT_ID_PREFIX: &str = "dummy_attempt";
 pub const REFUND_ID_PREFIX: &str = "dummy_ref";
+/// Payments whose amount ends with these two digits are declined for insufficient funds.
+pub const DECLINED_AMOUNT_SUFFIX: i64 = 5;
+pub const WEBHOOK_DELIVERY_TIMEOUT_SECS: u64 = 5;
 pub const THREE_DS_CSS: &str = include_str!("threeds_page.css");
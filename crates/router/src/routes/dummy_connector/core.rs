@@ -121,6 +121,14 @@ pub async fn payment_complete(
             state.conf.dummy_connector.payment_ttl,
         )
         .await?;
+
+        let webhook = if req.confirm {
+            types::DummyConnectorWebhook::PaymentSucceeded(updated_payment_data.clone().into())
+        } else {
+            types::DummyConnectorWebhook::PaymentFailed(updated_payment_data.clone().into())
+        };
+        utils::deliver_webhook(&state, updated_payment_data.webhook_url.clone(), webhook).await;
+
         return Ok(api::ApplicationResponse::JsonForRedirection(
             api_models::payments::RedirectionResponse {
                 return_url: String::new(),
@@ -177,9 +185,11 @@ pub async fn refund_payment(
     )
     .await?;
 
+    // Refunds are processed asynchronously, they succeed once they are retrieved for the first time.
     let refund_data = types::DummyConnectorRefundResponse::new(
-        types::DummyConnectorStatus::Succeeded,
+        types::DummyConnectorStatus::Processing,
         refund_id.to_owned(),
+        payment_data.payment_id.to_owned(),
         payment_data.currency,
         common_utils::date_time::now(),
         payment_data.amount,
@@ -212,12 +222,37 @@ pub async fn refund_data(
         .get_redis_conn()
         .change_context(errors::DummyConnectorErrors::InternalServerError)
         .attach_printable("Failed to get redis connection")?;
-    let refund_data = redis_conn
+    let mut refund_data = redis_conn
         .get_and_deserialize_key::<types::DummyConnectorRefundResponse>(
             refund_id.as_str(),
             "DummyConnectorRefundResponse",
         )
         .await
         .change_context(errors::DummyConnectorErrors::RefundNotFound)?;
+
+    if refund_data.status == types::DummyConnectorStatus::Processing {
+        refund_data.status = types::DummyConnectorStatus::Succeeded;
+        utils::store_data_in_redis(
+            &state,
+            refund_id,
+            refund_data.to_owned(),
+            state.conf.dummy_connector.refund_ttl,
+        )
+        .await?;
+
+        let webhook_url = match refund_data.payment_id.clone() {
+            Some(payment_id) => utils::get_payment_data_from_payment_id(&state, payment_id)
+                .await
+                .ok()
+                .and_then(|payment_data| payment_data.webhook_url),
+            None => None,
+        };
+        utils::deliver_webhook(
+            &state,
+            webhook_url,
+            types::DummyConnectorWebhook::RefundSucceeded(refund_data.clone()),
+        )
+        .await;
+    }
     Ok(api::ApplicationResponse::Json(refund_data))
 }
@@ -107,6 +107,7 @@ impl DummyConnectorPaymentAttempt {
             payment_method_type: self.payment_request.payment_method_data.into(),
             next_action,
             return_url,
+            webhook_url: self.payment_request.webhook_url,
         }
     }
 }
@@ -118,6 +119,7 @@ pub struct DummyConnectorPaymentRequest {
     pub payment_method_data: DummyConnectorPaymentMethodData,
     pub return_url: Option<String>,
     pub connector: DummyConnectors,
+    pub webhook_url: Option<String>,
 }
 
 pub trait GetPaymentMethodDetails {
@@ -259,6 +261,7 @@ pub struct DummyConnectorPaymentData {
     pub connector: DummyConnectors,
     pub next_action: Option<DummyConnectorNextAction>,
     pub return_url: Option<String>,
+    pub webhook_url: Option<String>,
 }
 
 impl DummyConnectorPaymentData {
@@ -340,6 +343,7 @@ pub struct DummyConnectorRefundRequest {
 pub struct DummyConnectorRefundResponse {
     pub status: DummyConnectorStatus,
     pub id: String,
+    pub payment_id: Option<String>,
     pub currency: Currency,
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub created: PrimitiveDateTime,
@@ -351,6 +355,7 @@ impl DummyConnectorRefundResponse {
     pub fn new(
         status: DummyConnectorStatus,
         id: String,
+        payment_id: String,
         currency: Currency,
         created: PrimitiveDateTime,
         payment_amount: i64,
@@ -359,6 +364,7 @@ impl DummyConnectorRefundResponse {
         Self {
             status,
             id,
+            payment_id: Some(payment_id),
             currency,
             created,
             payment_amount,
@@ -372,6 +378,16 @@ pub struct DummyConnectorRefundRetrieveRequest {
     pub refund_id: String,
 }
 
+/// Webhook delivered to the router when a payment or a refund reaches a terminal status
+/// asynchronously.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event_type", content = "data", rename_all = "snake_case")]
+pub enum DummyConnectorWebhook {
+    PaymentSucceeded(DummyConnectorPaymentResponse),
+    PaymentFailed(DummyConnectorPaymentResponse),
+    RefundSucceeded(DummyConnectorRefundResponse),
+}
+
 pub type DummyConnectorResponse<T> =
     CustomResult<services::ApplicationResponse<T>, DummyConnectorErrors>;
 
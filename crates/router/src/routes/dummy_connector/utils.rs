@@ -5,13 +5,14 @@ use error_stack::{report, IntoReport, ResultExt};
 use masking::PeekInterface;
 use maud::html;
 use rand::{distributions::Uniform, prelude::Distribution};
+use router_env::logger;
 use tokio::time as tokio;
 
 use super::{
     consts, errors,
     types::{self, GetPaymentMethodDetails},
 };
-use crate::{configs::settings, routes::AppState};
+use crate::{configs::settings, routes::AppState, services, types as router_types, utils::Encode};
 
 pub async fn tokio_mock_sleep(delay: u64, tolerance: u64) {
     let mut rng = rand::thread_rng();
@@ -89,6 +90,49 @@ pub async fn get_payment_data_by_attempt_id(
         .change_context(errors::DummyConnectorErrors::PaymentNotFound)
 }
 
+/// Delivers the webhook to the router which created the payment. Webhooks are only delivered to
+/// the router itself, since the dummy connector endpoints do not authenticate their callers.
+pub async fn deliver_webhook(
+    state: &AppState,
+    webhook_url: Option<String>,
+    webhook: types::DummyConnectorWebhook,
+) {
+    let webhook_url = match webhook_url {
+        Some(webhook_url) if webhook_url.starts_with(&state.conf.server.base_url) => webhook_url,
+        _ => return,
+    };
+
+    let webhook_body = match router_types::RequestBody::log_and_get_request_body(
+        &webhook,
+        Encode::<types::DummyConnectorWebhook>::encode_to_string_of_json,
+    ) {
+        Ok(webhook_body) => webhook_body,
+        Err(error) => {
+            logger::error!(?error, "Failed to encode the dummy connector webhook");
+            return;
+        }
+    };
+
+    let request = services::RequestBuilder::new()
+        .method(services::Method::Post)
+        .url(&webhook_url)
+        .attach_default_headers()
+        .header(reqwest::header::CONTENT_TYPE.as_str(), "application/json")
+        .body(Some(webhook_body))
+        .build();
+
+    let response = state
+        .api_client
+        .send_request(
+            state,
+            request,
+            Some(consts::WEBHOOK_DELIVERY_TIMEOUT_SECS),
+            false,
+        )
+        .await;
+    logger::debug!(dummy_connector_webhook_response=?response);
+}
+
 pub fn get_authorize_page(
     payment_data: types::DummyConnectorPaymentData,
     return_url: String,
@@ -339,6 +383,13 @@ impl types::DummyConnectorPaymentData {
         state: &AppState,
         payment_attempt: types::DummyConnectorPaymentAttempt,
     ) -> types::DummyConnectorResult<Self> {
+        // Amounts are checked before the payment method, so that declines can be simulated for
+        // payment methods which have no test credentials of their own.
+        if payment_attempt.payment_request.amount % 100 == consts::DECLINED_AMOUNT_SUFFIX {
+            return Err(report!(errors::DummyConnectorErrors::PaymentDeclined {
+                message: "Insufficient funds",
+            }));
+        }
         let redirect_url = format!(
             "{}/dummy-connector/authorize/{}",
             state.conf.server.base_url, payment_attempt.attempt_id
@@ -5,14 +5,14 @@ use super::AppState;
 use crate::{
     core::{api_locking, payments::helpers},
     services::{api, authentication as auth},
-    types::api::customers,
+    types::api::ephemeral_key,
 };
 
 #[instrument(skip_all, fields(flow = ?Flow::EphemeralKeyCreate))]
 pub async fn ephemeral_key_create(
     state: web::Data<AppState>,
     req: HttpRequest,
-    json_payload: web::Json<customers::CustomerId>,
+    json_payload: web::Json<ephemeral_key::EphemeralKeyCreateRequest>,
 ) -> HttpResponse {
     let flow = Flow::EphemeralKeyCreate;
     let payload = json_payload.into_inner();
@@ -22,7 +22,12 @@ pub async fn ephemeral_key_create(
         &req,
         payload,
         |state, auth, req| {
-            helpers::make_ephemeral_key(state, req.customer_id, auth.merchant_account.merchant_id)
+            helpers::make_ephemeral_key(
+                state,
+                req.customer_id,
+                auth.merchant_account.merchant_id,
+                req.invalidate_existing_keys,
+            )
         },
         &auth::ApiKeyAuth,
         api_locking::LockAction::NotApplicable,
@@ -21,6 +21,8 @@ pub enum ApiIdentifier {
     Business,
     Verification,
     ApiKeys,
+    Blocklist,
+    Reports,
 }
 
 impl From<Flow> for ApiIdentifier {
@@ -29,13 +31,30 @@ impl From<Flow> for ApiIdentifier {
             Flow::MerchantsAccountCreate
             | Flow::MerchantsAccountRetrieve
             | Flow::MerchantsAccountUpdate
-            | Flow::MerchantsAccountDelete => Self::MerchantAccount,
+            | Flow::MerchantsAccountDelete
+            | Flow::ThreeDsDecisionRulesRetrieve
+            | Flow::ThreeDsDecisionRulesUpdate
+            | Flow::ThreeDsDecisionRulesDelete
+            | Flow::AuditEventsList
+            | Flow::MandateMigrationCreate
+            | Flow::MandateMigrationRetrieve
+            | Flow::WebhookEventPreview => Self::MerchantAccount,
 
             Flow::MerchantConnectorsCreate
             | Flow::MerchantConnectorsRetrieve
             | Flow::MerchantConnectorsUpdate
             | Flow::MerchantConnectorsDelete
-            | Flow::MerchantConnectorsList => Self::MerchantConnector,
+            | Flow::MerchantConnectorsList
+            | Flow::MerchantConnectorsVerify
+            | Flow::MerchantConnectorsCredentialsFinalize
+            | Flow::MerchantConnectorsCircuitBreakerRetrieve
+            | Flow::MerchantConnectorsTemplateExport
+            | Flow::MerchantConnectorsTemplateImport
+            | Flow::ConnectorMetadataSchemaRetrieve
+            | Flow::ConnectorCapabilitiesRetrieve
+            | Flow::ConnectorMaintenanceWindowsList
+            | Flow::ConnectorMaintenanceWindowCreate
+            | Flow::ConnectorMaintenanceWindowDelete => Self::MerchantConnector,
 
             Flow::ConfigKeyCreate
             | Flow::ConfigKeyFetch
@@ -54,6 +73,8 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::PaymentMethodsCreate
             | Flow::PaymentMethodsList
+            | Flow::PaymentMethodsRequiredFields
+            | Flow::PaymentMethodsBankList
             | Flow::CustomerPaymentMethodsList
             | Flow::PaymentMethodsRetrieve
             | Flow::PaymentMethodsUpdate
@@ -69,8 +90,13 @@ impl From<Flow> for ApiIdentifier {
             | Flow::PaymentsApprove
             | Flow::PaymentsReject
             | Flow::PaymentsSessionToken
+            | Flow::PaymentsDeviceData
             | Flow::PaymentsStart
             | Flow::PaymentsList
+            | Flow::PaymentsRoutingDecisionsSummary
+            | Flow::PaymentsEligibleConnectors
+            | Flow::PaymentAttemptsList
+            | Flow::PaymentsRecurringCharge
             | Flow::PaymentsRedirect => Self::Payments,
 
             Flow::PayoutsCreate
@@ -83,7 +109,9 @@ impl From<Flow> for ApiIdentifier {
             Flow::RefundsCreate
             | Flow::RefundsRetrieve
             | Flow::RefundsUpdate
-            | Flow::RefundsList => Self::Refunds,
+            | Flow::RefundsList
+            | Flow::RefundsBulkCreate
+            | Flow::RefundsBulkRetrieve => Self::Refunds,
 
             Flow::IncomingWebhookReceive => Self::Webhooks,
 
@@ -93,6 +121,12 @@ impl From<Flow> for ApiIdentifier {
             | Flow::ApiKeyRevoke
             | Flow::ApiKeyList => Self::ApiKeys,
 
+            Flow::BlocklistCreate
+            | Flow::BlocklistRetrieve
+            | Flow::BlocklistUpdate
+            | Flow::BlocklistDelete
+            | Flow::BlocklistList => Self::Blocklist,
+
             Flow::DisputesRetrieve
             | Flow::DisputesList
             | Flow::DisputesEvidenceSubmit
@@ -112,6 +146,10 @@ impl From<Flow> for ApiIdentifier {
             | Flow::BusinessProfileList => Self::Business,
 
             Flow::Verification => Self::Verification,
+
+            Flow::ReconciliationReportRetrieve | Flow::ReconciliationReportSchedule => {
+                Self::Reports
+            }
         }
     }
 }
@@ -1,4 +1,6 @@
-use router_env::{counter_metric, global_meter, histogram_metric, metrics_context};
+use router_env::{
+    counter_metric, global_meter, histogram_metric, metrics_context, up_down_counter_metric,
+};
 
 metrics_context!(CONTEXT);
 global_meter!(GLOBAL_METER, "ROUTER_API");
@@ -52,20 +54,35 @@ counter_metric!(API_KEY_REVOKED, GLOBAL_METER);
 
 counter_metric!(MCA_CREATE, GLOBAL_METER);
 
+counter_metric!(BLOCKLIST_ENTRY_CREATED, GLOBAL_METER);
+counter_metric!(BLOCKED_PAYMENT_COUNT, GLOBAL_METER);
+
 // Flow Specific Metrics
 
 counter_metric!(ACCESS_TOKEN_CREATION, GLOBAL_METER);
-histogram_metric!(CONNECTOR_REQUEST_TIME, GLOBAL_METER);
+histogram_metric!(CONNECTOR_REQUEST_TIME, GLOBAL_METER); // Attributes needed
+counter_metric!(CONNECTOR_CALL_OUTCOME, GLOBAL_METER); // Attributes needed
+up_down_counter_metric!(CONNECTOR_IN_FLIGHT_REQUESTS, GLOBAL_METER); // Attributes needed
 counter_metric!(SESSION_TOKEN_CREATED, GLOBAL_METER);
 
 counter_metric!(CONNECTOR_CALL_COUNT, GLOBAL_METER); // Attributes needed
+counter_metric!(CONNECTOR_POLL_COUNT, GLOBAL_METER); // Attributes needed
+counter_metric!(CONNECTOR_POLLING_HANDED_OFF_COUNT, GLOBAL_METER); // Attributes needed
 
 counter_metric!(THREE_DS_PAYMENT_COUNT, GLOBAL_METER);
 counter_metric!(THREE_DS_DOWNGRADE_COUNT, GLOBAL_METER);
 
 counter_metric!(RESPONSE_DESERIALIZATION_FAILURE, GLOBAL_METER);
+counter_metric!(RESPONSE_PARTIAL_DESERIALIZATION_FAILURE, GLOBAL_METER);
 counter_metric!(CONNECTOR_ERROR_RESPONSE_COUNT, GLOBAL_METER);
 counter_metric!(REQUEST_TIMEOUT_COUNT, GLOBAL_METER);
+counter_metric!(REQUEST_UNKNOWN_FIELD_COUNT, GLOBAL_METER);
+
+counter_metric!(CIRCUIT_BREAKER_OPENED, GLOBAL_METER);
+counter_metric!(CIRCUIT_BREAKER_CLOSED, GLOBAL_METER);
+counter_metric!(CIRCUIT_BREAKER_REJECTED_REQUEST_COUNT, GLOBAL_METER);
+
+counter_metric!(PAYMENT_RATE_LIMITED_REQUEST_COUNT, GLOBAL_METER);
 
 counter_metric!(EXECUTE_PRETASK_COUNT, GLOBAL_METER);
 counter_metric!(CONNECTOR_PAYMENT_METHOD_TOKENIZATION, GLOBAL_METER);
@@ -1,7 +1,7 @@
 use router_env::opentelemetry;
 
 use super::utils as metric_utils;
-use crate::services::ApplicationResponse;
+use crate::{consts, services::ApplicationResponse, types::ErrorResponse};
 
 pub async fn record_request_time_metric<F, R>(
     future: F,
@@ -61,7 +61,181 @@ pub fn track_response_status_code<Q>(response: &ApplicationResponse<Q>) -> i64 {
         | ApplicationResponse::TextPlain(_)
         | ApplicationResponse::Form(_)
         | ApplicationResponse::FileData(_)
+        | ApplicationResponse::FileStream(_)
         | ApplicationResponse::JsonWithHeaders(_) => 200,
         ApplicationResponse::JsonForRedirection(_) => 302,
+        ApplicationResponse::NotModified(_) => 304,
+        ApplicationResponse::IdempotentReplay(response) => response.status_code.into(),
+    }
+}
+
+/// Outcome of a connector call, as reported in the connector metrics
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorCallOutcome {
+    Success,
+    // The connector processed the request and returned an error, like a decline
+    BusinessDecline,
+    // The connector failed to process the request, or its response could not be handled
+    ConnectorError,
+    Timeout,
+}
+
+impl ConnectorCallOutcome {
+    /// Derives the outcome from the error response the connector response was mapped to
+    pub fn from_connector_response<R>(response: &Result<R, ErrorResponse>) -> Self {
+        match response {
+            Ok(_) => Self::Success,
+            Err(error)
+                if error.code == consts::REQUEST_TIMEOUT_ERROR_CODE
+                    || error.code == consts::CONNECT_TIMEOUT_ERROR_CODE =>
+            {
+                Self::Timeout
+            }
+            Err(error) if error.status_code >= 500 => Self::ConnectorError,
+            Err(_) => Self::BusinessDecline,
+        }
+    }
+}
+
+pub fn get_status_class(status_code: Option<u16>) -> &'static str {
+    match status_code {
+        Some(100..=199) => "1xx",
+        Some(200..=299) => "2xx",
+        Some(300..=399) => "3xx",
+        Some(400..=499) => "4xx",
+        Some(500..=599) => "5xx",
+        Some(_) | None => "unknown",
+    }
+}
+
+pub fn connector_call_attributes(
+    connector: &str,
+    flow: &str,
+    status_code: Option<u16>,
+) -> Vec<opentelemetry::KeyValue> {
+    vec![
+        add_attributes("connector", connector.to_string()),
+        add_attributes("flow", flow.to_string()),
+        add_attributes("status_class", get_status_class(status_code)),
+    ]
+}
+
+pub fn record_connector_call_metrics(
+    connector: &str,
+    flow: &str,
+    status_code: Option<u16>,
+    outcome: ConnectorCallOutcome,
+    latency: std::time::Duration,
+) {
+    let mut attributes = connector_call_attributes(connector, flow, status_code);
+    super::CONNECTOR_REQUEST_TIME.record(&super::CONTEXT, latency.as_secs_f64(), &attributes);
+
+    attributes.push(add_attributes("outcome", outcome.to_string()));
+    super::CONNECTOR_CALL_OUTCOME.add(&super::CONTEXT, 1, &attributes);
+}
+
+/// Tracks a connector request as in-flight until dropped, so that requests whose futures are
+/// cancelled are accounted for as well
+pub struct InFlightConnectorRequest {
+    attributes: [opentelemetry::KeyValue; 2],
+}
+
+impl InFlightConnectorRequest {
+    pub fn start(connector: &str, flow: &str) -> Self {
+        let attributes = [
+            add_attributes("connector", connector.to_string()),
+            add_attributes("flow", flow.to_string()),
+        ];
+        super::CONNECTOR_IN_FLIGHT_REQUESTS.add(&super::CONTEXT, 1, &attributes);
+        Self { attributes }
+    }
+}
+
+impl Drop for InFlightConnectorRequest {
+    fn drop(&mut self) {
+        super::CONNECTOR_IN_FLIGHT_REQUESTS.add(&super::CONTEXT, -1, &self.attributes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_attribute(attributes: &[opentelemetry::KeyValue], key: &'static str) -> Option<String> {
+        attributes
+            .iter()
+            .find(|attribute| attribute.key.as_str() == key)
+            .map(|attribute| attribute.value.to_string())
+    }
+
+    #[test]
+    fn test_connector_call_attributes_for_success() {
+        let response: Result<(), ErrorResponse> = Ok(());
+        let outcome = ConnectorCallOutcome::from_connector_response(&response);
+        let attributes = connector_call_attributes("stripe", "Authorize", Some(200));
+
+        assert_eq!(outcome, ConnectorCallOutcome::Success);
+        assert_eq!(outcome.to_string(), "success");
+        assert_eq!(
+            get_attribute(&attributes, "connector"),
+            Some("stripe".to_string())
+        );
+        assert_eq!(
+            get_attribute(&attributes, "flow"),
+            Some("Authorize".to_string())
+        );
+        assert_eq!(
+            get_attribute(&attributes, "status_class"),
+            Some("2xx".to_string())
+        );
+    }
+
+    #[test]
+    fn test_connector_call_attributes_for_timeout() {
+        let response: Result<(), ErrorResponse> = Err(ErrorResponse {
+            code: consts::REQUEST_TIMEOUT_ERROR_CODE.to_string(),
+            message: consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string(),
+            reason: None,
+            status_code: 504,
+        });
+        let outcome = ConnectorCallOutcome::from_connector_response(&response);
+        let attributes = connector_call_attributes("adyen", "PSync", Some(504));
+
+        assert_eq!(outcome, ConnectorCallOutcome::Timeout);
+        assert_eq!(outcome.to_string(), "timeout");
+        assert_eq!(
+            get_attribute(&attributes, "connector"),
+            Some("adyen".to_string())
+        );
+        assert_eq!(
+            get_attribute(&attributes, "status_class"),
+            Some("5xx".to_string())
+        );
+    }
+
+    #[test]
+    fn test_declines_are_not_connector_errors() {
+        let decline: Result<(), ErrorResponse> = Err(ErrorResponse {
+            code: "card_declined".to_string(),
+            message: "Your card was declined".to_string(),
+            reason: None,
+            status_code: 402,
+        });
+        let server_error: Result<(), ErrorResponse> = Err(ErrorResponse {
+            code: "internal_error".to_string(),
+            message: "Internal error".to_string(),
+            reason: None,
+            status_code: 500,
+        });
+
+        assert_eq!(
+            ConnectorCallOutcome::from_connector_response(&decline),
+            ConnectorCallOutcome::BusinessDecline
+        );
+        assert_eq!(
+            ConnectorCallOutcome::from_connector_response(&server_error),
+            ConnectorCallOutcome::ConnectorError
+        );
     }
 }
@@ -7,7 +7,12 @@ use time::PrimitiveDateTime;
 
 use super::app::AppState;
 use crate::{
-    core::{api_locking, errors, payment_methods::cards},
+    core::{
+        api_locking,
+        errors::{self, StorageErrorExt},
+        payment_methods::cards,
+    },
+    headers,
     services::{api, authentication as auth},
     types::api::payment_methods::{self, PaymentMethodId},
 };
@@ -61,9 +66,11 @@ pub async fn create_payment_method_api(
         ("maximum_amount" = i64, Query, description = "The maximum amount amount accepted for processing by the particular payment method."),
         ("recurring_payment_enabled" = bool, Query, description = "Indicates whether the payment method is eligible for recurring payments"),
         ("installment_payment_enabled" = bool, Query, description = "Indicates whether the payment method is eligible for installment payments"),
+        ("If-None-Match" = Option<String>, Header, description = "The ETag of the payment methods previously retrieved"),
     ),
     responses(
         (status = 200, description = "Payment Methods retrieved", body = PaymentMethodListResponse),
+        (status = 304, description = "Payment Methods not modified since they were retrieved with the ETag of the If-None-Match header"),
         (status = 400, description = "Invalid Data"),
         (status = 404, description = "Payment Methods does not exist in records")
     ),
@@ -79,6 +86,125 @@ pub async fn list_payment_method_api(
 ) -> HttpResponse {
     let flow = Flow::PaymentMethodsList;
     let payload = json_payload.into_inner();
+    let (auth, auth_flow) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
+        Ok((auth, auth_flow)) => (auth, auth_flow),
+        Err(e) => return api::log_and_return_error_response(e),
+    };
+    let if_none_match = get_if_none_match(&req);
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            cards::list_payment_methods(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+                if_none_match.clone(),
+                auth_flow,
+            )
+        },
+        &*auth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// The ETag of the payment methods which the client retrieved before, if it sent one
+fn get_if_none_match(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(headers::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+/// List banks for a Payment Method Type
+///
+/// To list the banks which can be selected to pay with a bank redirect payment method type, for
+/// each of the connectors eligible for a payment
+#[utoipa::path(
+    get,
+    path = "/payment_methods/{payment_method_type}/banks",
+    params (
+        ("payment_method_type" = PaymentMethodType, Path, description = "The bank redirect payment method type for which the banks are listed"),
+        ("client_secret" = Option<String>, Query, description = "The client secret of the payment"),
+    ),
+    responses(
+        (status = 200, description = "Banks retrieved", body = BankListResponse),
+        (status = 400, description = "Invalid Data"),
+        (status = 404, description = "Payment does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "List the banks for a Payment Method Type",
+    security(("api_key" = []), ("publishable_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsBankList))]
+pub async fn list_banks_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<api_models::enums::PaymentMethodType>,
+    query_payload: web::Query<payment_methods::BankListRequest>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodsBankList;
+    let payment_method_type = path.into_inner();
+    let payload = query_payload.into_inner();
+    let (auth, _) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
+        Ok((auth, _auth_flow)) => (auth, _auth_flow),
+        Err(e) => return api::log_and_return_error_response(e),
+    };
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            cards::list_banks_for_payment_method_type(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_method_type,
+                req,
+            )
+        },
+        &*auth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// List required fields for a Payment Method Type
+///
+/// To list the fields required to pay with a payment method type through any of the connectors
+/// eligible for a payment, so that they can be collected before the payment is confirmed
+#[utoipa::path(
+    get,
+    path = "/payment_methods/required_fields",
+    params (
+        ("client_secret" = Option<String>, Query, description = "The client secret of the payment"),
+        ("payment_method" = PaymentMethod, Query, description = "The payment method for which the required fields are listed"),
+        ("payment_method_type" = PaymentMethodType, Query, description = "The payment method type for which the required fields are listed"),
+    ),
+    responses(
+        (status = 200, description = "Required fields retrieved", body = RequiredFieldsResponse),
+        (status = 400, description = "Invalid Data"),
+        (status = 404, description = "Payment does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "List the fields required for a Payment Method Type",
+    security(("api_key" = []), ("publishable_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsRequiredFields))]
+pub async fn list_required_fields_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query_payload: web::Query<payment_methods::RequiredFieldsRequest>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodsRequiredFields;
+    let payload = query_payload.into_inner();
     let (auth, _) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
         Ok((auth, _auth_flow)) => (auth, _auth_flow),
         Err(e) => return api::log_and_return_error_response(e),
@@ -90,13 +216,66 @@ pub async fn list_payment_method_api(
         &req,
         payload,
         |state, auth, req| {
-            cards::list_payment_methods(state, auth.merchant_account, auth.key_store, req)
+            cards::list_required_fields(state, auth.merchant_account, auth.key_store, req)
         },
         &*auth,
         api_locking::LockAction::NotApplicable,
     )
     .await
 }
+/// List payment methods for a Payment
+///
+/// To filter and list the payment methods applicable to a particular payment, based on its amount, currency and country
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/payment_methods",
+    params (
+        ("payment_id" = String, Path, description = "The identifier for the payment"),
+        ("client_secret" = String, Query, description = "The client secret of the payment"),
+        ("If-None-Match" = Option<String>, Header, description = "The ETag of the payment methods previously retrieved"),
+    ),
+    responses(
+        (status = 200, description = "Payment Methods retrieved", body = PaymentMethodListResponse),
+        (status = 304, description = "Payment Methods not modified since they were retrieved with the ETag of the If-None-Match header"),
+        (status = 400, description = "Invalid Data"),
+        (status = 401, description = "Unauthorized request"),
+        (status = 404, description = "Payment does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "List all Payment Methods for a Payment",
+    security(("publishable_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsList))]
+pub async fn list_payment_methods_for_payment_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<payment_methods::PaymentMethodListRequest>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodsList;
+    let payment_id = path.into_inner();
+    let if_none_match = get_if_none_match(&req);
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        query_payload.into_inner(),
+        |state, auth, req| {
+            cards::list_payment_methods_for_payment(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_id.clone(),
+                req,
+                if_none_match.clone(),
+            )
+        },
+        &auth::PublishableKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// List payment methods for a Customer
 ///
 /// To filter and list the applicable payment methods for a particular Customer ID
@@ -119,7 +298,7 @@ pub async fn list_payment_method_api(
     ),
     tag = "Payment Methods",
     operation_id = "List all Payment Methods for a Customer",
-    security(("api_key" = []))
+    security(("api_key" = []), ("ephemeral_key" = []))
 )]
 #[instrument(skip_all, fields(flow = ?Flow::CustomerPaymentMethodsList))]
 pub async fn list_customer_payment_method_api(
@@ -130,11 +309,18 @@ pub async fn list_customer_payment_method_api(
 ) -> HttpResponse {
     let flow = Flow::CustomerPaymentMethodsList;
     let payload = query_payload.into_inner();
-    let (auth, _) = match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
-        Ok((auth, _auth_flow)) => (auth, _auth_flow),
-        Err(e) => return api::log_and_return_error_response(e),
-    };
     let customer_id = customer_id.into_inner().0;
+    let auth = if auth::is_ephemeral_key(req.headers()) {
+        match auth::is_ephemeral_auth(req.headers(), &*state.store, &customer_id, &flow).await {
+            Ok(auth) => auth,
+            Err(err) => return api::log_and_return_error_response(err),
+        }
+    } else {
+        match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
+            Ok((auth, _auth_flow)) => auth,
+            Err(e) => return api::log_and_return_error_response(e),
+        }
+    };
     api::server_wrap(
         flow,
         state,
@@ -312,7 +498,7 @@ pub async fn payment_method_update_api(
     ),
     tag = "Payment Methods",
     operation_id = "Delete a Payment method",
-    security(("api_key" = []))
+    security(("api_key" = []), ("ephemeral_key" = []))
 )]
 #[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsDelete))]
 pub async fn payment_method_delete_api(
@@ -324,13 +510,32 @@ pub async fn payment_method_delete_api(
     let pm = PaymentMethodId {
         payment_method_id: payment_method_id.into_inner().0,
     };
+    let auth: Box<dyn auth::AuthenticateAndFetch<auth::AuthenticationData, AppState>> =
+        if auth::is_ephemeral_key(req.headers()) {
+            // The ephemeral key is bound to a customer, which is known only from the payment method
+            let customer_id = match state
+                .store
+                .find_payment_method(&pm.payment_method_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)
+            {
+                Ok(payment_method) => payment_method.customer_id,
+                Err(err) => return api::log_and_return_error_response(err),
+            };
+            match auth::is_ephemeral_auth(req.headers(), &*state.store, &customer_id, &flow).await {
+                Ok(auth) => auth,
+                Err(err) => return api::log_and_return_error_response(err),
+            }
+        } else {
+            Box::new(auth::ApiKeyAuth)
+        };
     api::server_wrap(
         flow,
         state,
         &req,
         pm,
         |state, auth, req| cards::delete_payment_method(state, auth.merchant_account, req),
-        &auth::ApiKeyAuth,
+        &*auth,
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -9,8 +9,11 @@ use router_env::{instrument, tracing, types, Flow};
 use crate::{
     self as app,
     core::{
+        connector_response_capture,
         errors::http_not_implemented,
+        forex, mandate, payment_timeline,
         payments::{self, PaymentRedirectFlow},
+        rate_limiter, routing_decisions,
     },
     openapi::examples::{
         PAYMENTS_CREATE, PAYMENTS_CREATE_MINIMUM_FIELDS, PAYMENTS_CREATE_WITH_ADDRESS,
@@ -19,7 +22,10 @@ use crate::{
         PAYMENTS_CREATE_WITH_ORDER_DETAILS,
     },
     routes::lock_utils,
-    services::{api, authentication as auth},
+    services::{
+        api::{self, unknown_fields::JsonWithUnknownFields},
+        authentication as auth,
+    },
     types::{
         api::{self as api_types, enums as api_enums, payments as payment_types},
         domain,
@@ -33,6 +39,9 @@ use crate::{
 #[utoipa::path(
     post,
     path = "/payments",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Key identifying the request, a retry made with the same key gets the response of the first request instead of creating another payment")
+    ),
     request_body(
         content = PaymentsCreateRequest,
         examples(
@@ -79,7 +88,8 @@ use crate::{
         )),
     responses(
         (status = 200, description = "Payment created", body = PaymentsResponse),
-        (status = 400, description = "Missing Mandatory fields")
+        (status = 400, description = "Missing Mandatory fields"),
+        (status = 409, description = "The idempotency key was used with a different request, or a request with the idempotency key is in progress")
     ),
     tag = "Payments",
     operation_id = "Create a Payment",
@@ -89,10 +99,10 @@ use crate::{
 pub async fn payments_create(
     state: web::Data<app::AppState>,
     req: actix_web::HttpRequest,
-    json_payload: web::Json<payment_types::PaymentsRequest>,
+    json_payload: JsonWithUnknownFields<payment_types::PaymentsRequest>,
 ) -> impl Responder {
     let flow = Flow::PaymentsCreate;
-    let payload = json_payload.into_inner();
+    let (payload, unknown_fields) = json_payload.into_inner();
 
     if let Some(api_enums::CaptureMethod::Scheduled) = payload.capture_method {
         return http_not_implemented();
@@ -100,21 +110,47 @@ pub async fn payments_create(
 
     let locking_action = payload.get_locking_input(flow.clone());
 
-    api::server_wrap(
+    api::idempotent_server_wrap(
         flow,
         state,
         &req,
         payload,
         |state, auth, req| {
-            authorize_verify_select(
-                payments::PaymentCreate,
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payment_types::HeaderPayload::default(),
-                req,
-                api::AuthFlow::Merchant,
-            )
+            let validation = unknown_fields.validate(auth.api_key_strict_request_validation);
+            async move {
+                validation?;
+                rate_limiter::check_payment_rate_limit(
+                    &state,
+                    &auth.merchant_account.merchant_id,
+                    None,
+                )
+                .await?;
+                let display_currency = req.display_currency;
+                let response =
+                    payments::duplicate_order_reference::payments_create_with_duplicate_detection(
+                        &state,
+                        &auth.merchant_account,
+                        &auth.key_store,
+                        req,
+                        |req, suspected_duplicate_of| {
+                            authorize_verify_select(
+                                payments::PaymentCreate {
+                                    suspected_duplicate_of,
+                                    livemode: auth.api_key_livemode,
+                                    ..Default::default()
+                                },
+                                state.clone(),
+                                auth.merchant_account.clone(),
+                                auth.key_store.clone(),
+                                payment_types::HeaderPayload::default(),
+                                req,
+                                api::AuthFlow::Merchant,
+                            )
+                        },
+                    )
+                    .await?;
+                Ok(forex::add_display_amount(&state, response, display_currency).await)
+            }
         },
         &auth::ApiKeyAuth,
         locking_action,
@@ -189,6 +225,7 @@ pub async fn payments_start(
     request_body=PaymentRetrieveBody,
     responses(
         (status = 200, description = "Gets the payment with final status", body = PaymentsResponse),
+        (status = 400, description = "The API key does not have the permission to expand the connector response"),
         (status = 404, description = "No payment found")
     ),
     tag = "Payments",
@@ -213,6 +250,7 @@ pub async fn payments_retrieve(
         expand_captures: json_payload.expand_captures,
         ..Default::default()
     };
+    let expand = json_payload.expand;
     let (auth_type, auth_flow) =
         match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
             Ok(auth) => auth,
@@ -226,9 +264,22 @@ pub async fn payments_retrieve(
         state,
         &req,
         payload,
-        |state, auth, req| {
-            payments::payments_core::<api_types::PSync, payment_types::PaymentsResponse, _, _, _>(
-                state,
+        |state, auth, req| async move {
+            // The access is checked before the payment is synced with the connector
+            if expand == Some(payment_types::RetrieveExpandField::ConnectorResponse) {
+                connector_response_capture::check_connector_response_access(
+                    auth.api_key_permissions.as_deref(),
+                )?;
+            }
+            let merchant_id = auth.merchant_account.merchant_id.clone();
+            let response = payments::payments_core::<
+                api_types::PSync,
+                payment_types::PaymentsResponse,
+                _,
+                _,
+                _,
+            >(
+                state.clone(),
                 auth.merchant_account,
                 auth.key_store,
                 payments::PaymentStatus,
@@ -237,6 +288,34 @@ pub async fn payments_retrieve(
                 payments::CallConnectorAction::Trigger,
                 HeaderPayload::default(),
             )
+            .await?;
+            match expand {
+                Some(payment_types::RetrieveExpandField::ConnectorResponse) => {
+                    connector_response_capture::add_connector_response_to_payments_response(
+                        state.store.as_ref(),
+                        &merchant_id,
+                        response,
+                    )
+                    .await
+                }
+                Some(payment_types::RetrieveExpandField::Timeline) => {
+                    payment_timeline::add_timeline_to_payments_response(
+                        state.store.as_ref(),
+                        &merchant_id,
+                        response,
+                    )
+                    .await
+                }
+                Some(payment_types::RetrieveExpandField::RoutingDecisions) => {
+                    routing_decisions::add_routing_decisions_to_payments_response(
+                        state.store.as_ref(),
+                        &merchant_id,
+                        response,
+                    )
+                    .await
+                }
+                None => Ok(response),
+            }
         },
         &*auth_type,
         locking_action,
@@ -327,11 +406,11 @@ pub async fn payments_retrieve_with_gateway_creds(
 pub async fn payments_update(
     state: web::Data<app::AppState>,
     req: actix_web::HttpRequest,
-    json_payload: web::Json<payment_types::PaymentsRequest>,
+    json_payload: JsonWithUnknownFields<payment_types::PaymentsRequest>,
     path: web::Path<String>,
 ) -> impl Responder {
     let flow = Flow::PaymentsUpdate;
-    let mut payload = json_payload.into_inner();
+    let (mut payload, unknown_fields) = json_payload.into_inner();
 
     if let Some(api_enums::CaptureMethod::Scheduled) = payload.capture_method {
         return http_not_implemented();
@@ -354,15 +433,20 @@ pub async fn payments_update(
         &req,
         payload,
         |state, auth, req| {
-            authorize_verify_select(
-                payments::PaymentUpdate,
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payment_types::HeaderPayload::default(),
-                req,
-                auth_flow,
-            )
+            let validation = unknown_fields.validate(auth.api_key_strict_request_validation);
+            async move {
+                validation?;
+                authorize_verify_select(
+                    payments::PaymentUpdate,
+                    state,
+                    auth.merchant_account,
+                    auth.key_store,
+                    payment_types::HeaderPayload::default(),
+                    req,
+                    auth_flow,
+                )
+                .await
+            }
         },
         &*auth_type,
         locking_action,
@@ -392,11 +476,11 @@ pub async fn payments_update(
 pub async fn payments_confirm(
     state: web::Data<app::AppState>,
     req: actix_web::HttpRequest,
-    json_payload: web::Json<payment_types::PaymentsRequest>,
+    json_payload: JsonWithUnknownFields<payment_types::PaymentsRequest>,
     path: web::Path<String>,
 ) -> impl Responder {
     let flow = Flow::PaymentsConfirm;
-    let mut payload = json_payload.into_inner();
+    let (mut payload, unknown_fields) = json_payload.into_inner();
 
     if let Some(api_enums::CaptureMethod::Scheduled) = payload.capture_method {
         return http_not_implemented();
@@ -407,7 +491,9 @@ pub async fn payments_confirm(
     }
 
     let payment_id = path.into_inner();
-    payload.payment_id = Some(payment_types::PaymentIdType::PaymentIntentId(payment_id));
+    payload.payment_id = Some(payment_types::PaymentIdType::PaymentIntentId(
+        payment_id.clone(),
+    ));
     payload.confirm = Some(true);
     let header_payload = match payment_types::HeaderPayload::foreign_try_from(req.headers()) {
         Ok(headers) => headers,
@@ -422,26 +508,52 @@ pub async fn payments_confirm(
             Err(e) => return api::log_and_return_error_response(e),
         };
 
-    let locking_action = payload.get_locking_input(flow.clone());
+    let client_ip = helpers::get_rate_limited_client_ip(&req, auth_flow);
 
+    // The payment is locked across the connector call by the confirm flow itself, which handles
+    // concurrent confirm requests
     api::server_wrap(
         flow,
         state,
         &req,
         payload,
         |state, auth, req| {
-            authorize_verify_select(
-                payments::PaymentConfirm,
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                header_payload,
-                req,
-                auth_flow,
-            )
+            let client_ip = client_ip.clone();
+            let payment_id = payment_id.clone();
+            let validation = unknown_fields.validate(auth.api_key_strict_request_validation);
+            async move {
+                validation?;
+                rate_limiter::check_payment_rate_limit(
+                    &state,
+                    &auth.merchant_account.merchant_id,
+                    client_ip.as_deref(),
+                )
+                .await?;
+                let client_secret = req.client_secret.clone();
+                payments::confirm_lock::payments_confirm_with_lock(
+                    &state,
+                    &auth.merchant_account,
+                    &auth.key_store,
+                    &payment_id,
+                    client_secret,
+                    auth_flow,
+                    || {
+                        authorize_verify_select(
+                            payments::PaymentConfirm,
+                            state.clone(),
+                            auth.merchant_account.clone(),
+                            auth.key_store.clone(),
+                            header_payload,
+                            req,
+                            auth_flow,
+                        )
+                    },
+                )
+                .await
+            }
         },
         &*auth_type,
-        locking_action,
+        api_locking::LockAction::NotApplicable,
     )
     .await
 }
@@ -479,7 +591,7 @@ pub async fn payments_capture(
 
     let locking_action = payload.get_locking_input(flow.clone());
 
-    api::server_wrap(
+    api::idempotent_server_wrap(
         flow,
         state,
         &req,
@@ -555,6 +667,50 @@ pub async fn payments_connector_session(
     )
     .await
 }
+
+/// Payments - Device data
+///
+/// To create the device data session of a payment, along with the parameters of the device data collection of the connectors which require it before the authorization. The returned `device_session_id` is stored on the payment and sent to these connectors when the payment is confirmed
+#[utoipa::path(
+    post,
+    path = "/payments/device_data",
+    request_body=PaymentsDeviceDataRequest,
+    responses(
+        (status = 200, description = "Device data session created for the payment", body = PaymentsDeviceDataResponse),
+        (status = 400, description = "Missing mandatory fields"),
+        (status = 404, description = "No payment found")
+    ),
+    tag = "Payments",
+    operation_id = "Create the device data session for a Payment",
+    security(("publishable_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsDeviceData))]
+pub async fn payments_device_data(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsDeviceDataRequest>,
+) -> impl Responder {
+    let flow = Flow::PaymentsDeviceData;
+    let payload = json_payload.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, payload| {
+            payments::device_data::create_device_data_session(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payload,
+            )
+        },
+        &auth::PublishableKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 // /// Payments - Redirect response
 // ///
 // /// To get the payment response for redirect flows
@@ -739,7 +895,7 @@ pub async fn payments_cancel(
     let payment_id = path.into_inner();
     payload.payment_id = payment_id;
     let locking_action = payload.get_locking_input(flow.clone());
-    api::server_wrap(
+    api::idempotent_server_wrap(
         flow,
         state,
         &req,
@@ -846,6 +1002,180 @@ pub async fn get_filters_for_payments(
     )
     .await
 }
+/// Payments - Routing Decisions Summary
+///
+/// To count, for each connector, the routing decisions taken for the payments of the merchant during a day: the attempts routed to the connector, the fallbacks to and from it, and the requests and retries sent to it
+#[utoipa::path(
+    get,
+    path = "/payments/routing_decisions/summary",
+    params(
+        ("date" = Option<String>, Query, description = "The day to summarize in UTC, in the YYYY-MM-DD format. Defaults to the current day")
+    ),
+    responses(
+        (status = 200, description = "Routing decisions summarized", body = RoutingDecisionsSummaryResponse),
+        (status = 400, description = "Invalid date")
+    ),
+    tag = "Payments",
+    operation_id = "Summarize the Routing Decisions of the Payments",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsRoutingDecisionsSummary))]
+#[cfg(feature = "olap")]
+pub async fn payments_routing_decisions_summary(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    payload: web::Query<payment_types::RoutingDecisionsSummaryRequest>,
+) -> impl Responder {
+    let flow = Flow::PaymentsRoutingDecisionsSummary;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            routing_decisions::get_routing_decisions_summary(state, auth.merchant_account, req)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Payments - Eligible Connectors
+///
+/// To validate a payment against the payment methods enabled for each of the connectors configured for its business profile, useful to debug connector selection
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/eligible_connectors",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "Eligibility of the connectors for the payment", body = EligibleConnectorsResponse),
+        (status = 404, description = "No payment found")
+    ),
+    tag = "Payments",
+    operation_id = "Retrieve the eligible connectors for a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsEligibleConnectors))]
+pub async fn payments_eligible_connectors(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsEligibleConnectors;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payment_id,
+        |state, auth, payment_id| {
+            payments::eligibility::list_eligible_connectors(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_id,
+            )
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Payments - Attempts List
+///
+/// To list all the attempts made for a Payment, including the failed attempts which were retried, ordered by the time at which they were created
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/attempts",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "List of the attempts of the payment", body = PaymentAttemptListResponse),
+        (status = 404, description = "No payment found")
+    ),
+    tag = "Payments",
+    operation_id = "List the attempts of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentAttemptsList))]
+pub async fn payments_attempts_list(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentAttemptsList;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payment_id,
+        |state, auth, payment_id| {
+            payments::list_payment_attempts(state, auth.merchant_account, payment_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Payments - Recurring Charge
+///
+/// To charge a customer off session against a mandate which was set up earlier, the payment is created and confirmed in a single call
+#[utoipa::path(
+    post,
+    path = "/payments/recurring",
+    request_body = RecurringPaymentsRequest,
+    responses(
+        (status = 200, description = "Payment created", body = PaymentsResponse),
+        (status = 400, description = "Missing Mandatory fields or mandate validation failed"),
+        (status = 404, description = "No mandate found")
+    ),
+    tag = "Payments",
+    operation_id = "Charge a Mandate",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsRecurringCharge))]
+pub async fn payments_recurring_charge(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::RecurringPaymentsRequest>,
+) -> impl Responder {
+    let flow = Flow::PaymentsRecurringCharge;
+    let payload = json_payload.into_inner();
+
+    if let Some(api_enums::CaptureMethod::Scheduled) = payload.capture_method {
+        return http_not_implemented();
+    };
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            mandate::charge_recurring_mandate(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                auth.api_key_permissions,
+                auth.api_key_livemode,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
 async fn authorize_verify_select<Op>(
     operation: Op,
     state: app::AppState,
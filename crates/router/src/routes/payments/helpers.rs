@@ -2,7 +2,7 @@ use error_stack::ResultExt;
 
 use crate::{
     core::errors::{self, RouterResult},
-    headers, logger,
+    headers, logger, services,
     types::{self, api},
     utils::{Encode, ValueExt},
 };
@@ -82,3 +82,62 @@ pub fn populate_ip_into_browser_info(
     payload.browser_info = Some(encoded);
     Ok(())
 }
+
+/// IP of the client by which the payment requests made with the publishable key are rate limited,
+/// the requests made with the API key are rate limited by the merchant only
+pub fn get_rate_limited_client_ip(
+    req: &actix_web::HttpRequest,
+    auth_flow: services::AuthFlow,
+) -> Option<String> {
+    (auth_flow == services::AuthFlow::Client)
+        .then(|| get_trusted_client_ip(req))
+        .flatten()
+}
+
+/// IP of the client as seen by the load balancer in front of the application. The load balancer
+/// appends the address it received the request from to the "X-Forwarded-For" header, so only the
+/// last entry of the header can be trusted, the entries before it are set by the client. The
+/// address of the peer is used when the request did not go through the load balancer.
+fn get_trusted_client_ip(req: &actix_web::HttpRequest) -> Option<String> {
+    let forwarded_ip = req
+        .headers()
+        .get(headers::X_FORWARDED_FOR)
+        .and_then(|val| val.to_str().ok())
+        .and_then(|ips| ips.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse::<std::net::IpAddr>().ok());
+
+    forwarded_ip
+        .or_else(|| req.peer_addr().map(|addr| addr.ip()))
+        .map(|ip| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_client_ip_set_by_client_is_not_trusted() {
+        let req = actix_web::test::TestRequest::default()
+            .insert_header((headers::X_FORWARDED_FOR, "1.1.1.1, 10.0.0.1"))
+            .peer_addr("192.168.0.1:443".parse().unwrap())
+            .to_http_request();
+        assert_eq!(
+            get_rate_limited_client_ip(&req, services::AuthFlow::Client).as_deref(),
+            Some("10.0.0.1")
+        );
+        assert_eq!(
+            get_rate_limited_client_ip(&req, services::AuthFlow::Merchant),
+            None
+        );
+
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr("192.168.0.1:443".parse().unwrap())
+            .to_http_request();
+        assert_eq!(
+            get_rate_limited_client_ip(&req, services::AuthFlow::Client).as_deref(),
+            Some("192.168.0.1")
+        );
+    }
+}
@@ -3,8 +3,11 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::{api_locking, refunds::*},
-    services::{api, authentication as auth},
+    core::{api_locking, connector_response_capture, refunds::*},
+    services::{
+        api::{self, unknown_fields::JsonWithUnknownFields},
+        authentication as auth,
+    },
     types::api::refunds,
 };
 
@@ -28,15 +31,96 @@ use crate::{
 pub async fn refunds_create(
     state: web::Data<AppState>,
     req: HttpRequest,
-    json_payload: web::Json<refunds::RefundRequest>,
+    json_payload: JsonWithUnknownFields<refunds::RefundRequest>,
 ) -> HttpResponse {
     let flow = Flow::RefundsCreate;
+    let (payload, unknown_fields) = json_payload.into_inner();
+    api::idempotent_server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            let validation = unknown_fields.validate(auth.api_key_strict_request_validation);
+            async move {
+                validation?;
+                refund_create_core(state, auth.merchant_account, auth.key_store, req).await
+            }
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Refunds - Bulk Create
+///
+/// To create refunds against multiple already processed payments. Each refund is validated when the request is received and is either accepted or rejected, the accepted refunds are processed asynchronously
+#[utoipa::path(
+    post,
+    path = "/refunds/bulk",
+    request_body=BulkRefundRequest,
+    responses(
+        (status = 200, description = "Bulk refund created", body = BulkRefundResponse),
+        (status = 400, description = "Missing Mandatory fields or too many refunds")
+    ),
+    tag = "Refunds",
+    operation_id = "Create Refunds in Bulk",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsBulkCreate))]
+// #[post("/bulk")]
+pub async fn refunds_bulk_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<refunds::BulkRefundRequest>,
+) -> HttpResponse {
+    let flow = Flow::RefundsBulkCreate;
     api::server_wrap(
         flow,
         state,
         &req,
         json_payload.into_inner(),
-        |state, auth, req| refund_create_core(state, auth.merchant_account, auth.key_store, req),
+        |state, auth, req| {
+            bulk::bulk_refund_create_core(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Refunds - Bulk Retrieve
+///
+/// To retrieve the outcome of the refunds of a bulk refund, along with the current status of the accepted refunds
+#[utoipa::path(
+    get,
+    path = "/refunds/bulk/{bulk_refund_id}",
+    params(
+        ("bulk_refund_id" = String, Path, description = "The identifier for the bulk refund")
+    ),
+    responses(
+        (status = 200, description = "Bulk refund retrieved", body = BulkRefundResponse),
+        (status = 404, description = "Bulk refund does not exist in our records")
+    ),
+    tag = "Refunds",
+    operation_id = "Retrieve a Bulk Refund",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsBulkRetrieve))]
+// #[get("/bulk/{bulk_refund_id}")]
+pub async fn refunds_bulk_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::RefundsBulkRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, bulk_refund_id| {
+            bulk::bulk_refund_retrieve_core(state, auth.merchant_account, bulk_refund_id)
+        },
         &auth::ApiKeyAuth,
         api_locking::LockAction::NotApplicable,
     )
@@ -49,10 +133,12 @@ pub async fn refunds_create(
     get,
     path = "/refunds/{refund_id}",
     params(
-        ("refund_id" = String, Path, description = "The identifier for refund")
+        ("refund_id" = String, Path, description = "The identifier for refund"),
+        ("expand" = Option<RetrieveExpandField>, Query, description = "Field of the refund which is only returned when expanded, `connector_response` requires an API key with the `connector_response_read` permission")
     ),
     responses(
         (status = 200, description = "Refund retrieved", body = RefundResponse),
+        (status = 400, description = "The API key does not have the permission to expand the connector response"),
         (status = 404, description = "Refund does not exist in our records")
     ),
     tag = "Refunds",
@@ -72,6 +158,8 @@ pub async fn refunds_retrieve(
         force_sync: query_params.force_sync,
         merchant_connector_details: None,
     };
+    let expand_connector_response =
+        query_params.expand == Some(api_models::payments::RetrieveExpandField::ConnectorResponse);
     let flow = Flow::RefundsRetrieve;
 
     api::server_wrap(
@@ -79,14 +167,31 @@ pub async fn refunds_retrieve(
         state,
         &req,
         refund_request,
-        |state, auth, refund_request| {
-            refund_response_wrapper(
-                state,
+        |state, auth, refund_request| async move {
+            // The access is checked before the refund is synced with the connector
+            if expand_connector_response {
+                connector_response_capture::check_connector_response_access(
+                    auth.api_key_permissions.as_deref(),
+                )?;
+            }
+            let merchant_id = auth.merchant_account.merchant_id.clone();
+            let response = refund_response_wrapper(
+                state.clone(),
                 auth.merchant_account,
                 auth.key_store,
                 refund_request,
                 refund_retrieve_core,
             )
+            .await?;
+            if !expand_connector_response {
+                return Ok(response);
+            }
+            connector_response_capture::add_connector_response_to_refund_response(
+                state.store.as_ref(),
+                &merchant_id,
+                response,
+            )
+            .await
         },
         &auth::ApiKeyAuth,
         api_locking::LockAction::NotApplicable,
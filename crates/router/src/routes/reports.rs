@@ -0,0 +1,86 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::reports as report_types;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, reports},
+    services::{api, authentication as auth},
+};
+
+/// Reports - Reconciliation Report
+///
+/// To retrieve the succeeded payments and refunds created in a time range as a CSV file, with the
+/// connector references needed to reconcile them against the settlement reports of the connectors
+#[utoipa::path(
+    get,
+    path = "/reports/reconciliation",
+    params(
+        ("start_time" = PrimitiveDateTime, Query, description = "Payments and refunds created at or after this time are reported"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "Payments and refunds created at or before this time are reported, the current time if not passed")
+    ),
+    responses(
+        (status = 200, description = "Reconciliation report generated as a CSV file"),
+        (status = 400, description = "Invalid time range")
+    ),
+    tag = "Reports",
+    operation_id = "Retrieve a Reconciliation Report",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReconciliationReportRetrieve))]
+pub async fn reconciliation_report_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<report_types::ReconciliationReportRequest>,
+) -> HttpResponse {
+    let flow = Flow::ReconciliationReportRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload.into_inner(),
+        |state, auth, req| {
+            reports::retrieve_reconciliation_report(state, auth.merchant_account, req)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Reports - Schedule Reconciliation Report
+///
+/// To generate the reconciliation report of a time range in the background, optionally every day.
+/// The report can be retrieved with the files API once it is generated
+#[utoipa::path(
+    post,
+    path = "/reports/reconciliation",
+    request_body = ReconciliationReportScheduleRequest,
+    responses(
+        (status = 200, description = "Reconciliation report scheduled", body = ReconciliationReportScheduleResponse),
+        (status = 400, description = "Invalid time range")
+    ),
+    tag = "Reports",
+    operation_id = "Schedule a Reconciliation Report",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReconciliationReportSchedule))]
+pub async fn reconciliation_report_schedule(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<report_types::ReconciliationReportScheduleRequest>,
+) -> HttpResponse {
+    let flow = Flow::ReconciliationReportSchedule;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            reports::schedule_reconciliation_report(state, auth.merchant_account, req)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -1,5 +1,12 @@
 pub mod client;
+pub mod connector_proxy;
+pub mod idempotency;
+pub mod masked_logging;
+pub mod pii_redaction;
+pub mod polling;
+pub mod redirect_form;
 pub mod request;
+pub mod unknown_fields;
 
 use std::{
     collections::HashMap,
@@ -11,12 +18,15 @@ use std::{
 };
 
 use actix_web::{body, web, FromRequest, HttpRequest, HttpResponse, Responder, ResponseError};
-use api_models::enums::CaptureMethod;
+use api_models::enums::{CaptureMethod, ConnectorAttemptOutcome};
+use bytes::Bytes;
 pub use client::{proxy_bypass_urls, ApiClient, MockApiClient, ProxyClient};
 use common_utils::errors::ReportSwitchExt;
 pub use common_utils::request::{ContentType, Method, Request, RequestBuilder};
 use error_stack::{report, IntoReport, Report, ResultExt};
+use futures::{stream::BoxStream, TryStreamExt};
 use masking::{ExposeOptionInterface, PeekInterface};
+pub use redirect_form::{RedirectFormBuilder, ThreeDsOneFields};
 use router_env::{instrument, tracing, tracing_actix_web::RequestId, Tag};
 use serde::Serialize;
 use serde_json::json;
@@ -26,9 +36,9 @@ use crate::{
     configs::settings::{Connectors, Settings},
     consts,
     core::{
-        api_locking,
+        api_locking, circuit_breaker, connector_maintenance,
         errors::{self, CustomResult},
-        payments,
+        payments, routing_decisions,
     },
     logger,
     routes::{
@@ -92,6 +102,12 @@ pub trait ConnectorValidation: ConnectorCommon {
     fn is_webhook_source_verification_mandatory(&self) -> bool {
         false
     }
+
+    /// Pattern of the transaction identifiers of the connector, a transaction identifier which does
+    /// not match it was issued by another connector
+    fn get_connector_transaction_id_pattern(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -191,6 +207,39 @@ pub trait ConnectorIntegration<T, Req, Resp>: ConnectorIntegrationAny<T, Req, Re
         Ok(ErrorResponse::get_not_implemented())
     }
 
+    /// Request to poll the status of an operation which the connector accepted without completing
+    /// it, `url` is the URL of the `PollConfig` returned in the response of the operation
+    fn build_poll_request(
+        &self,
+        req: &types::RouterData<T, Req, Resp>,
+        url: &str,
+        connectors: &Connectors,
+    ) -> CustomResult<Option<Request>, errors::ConnectorError> {
+        Ok(Some(
+            RequestBuilder::new()
+                .method(Method::Get)
+                .url(url)
+                .attach_default_headers()
+                .headers(self.get_headers(req, connectors)?)
+                .build(),
+        ))
+    }
+
+    /// Handle the response of a poll of the status of an operation. The `PollConfig` is to be kept
+    /// in the returned data for as long as the operation is not complete
+    fn handle_poll_response(
+        &self,
+        data: &types::RouterData<T, Req, Resp>,
+        res: types::Response,
+    ) -> CustomResult<types::RouterData<T, Req, Resp>, errors::ConnectorError>
+    where
+        T: Clone,
+        Req: Clone,
+        Resp: Clone,
+    {
+        self.handle_response(data, res)
+    }
+
     fn get_5xx_error_response(
         &self,
         res: types::Response,
@@ -217,6 +266,13 @@ pub trait ConnectorIntegration<T, Req, Resp>: ConnectorIntegrationAny<T, Req, Re
         })
     }
 
+    /// Error reported in the body of a successful HTTP response, for the connectors which respond
+    /// to declined or failed requests with a 2xx status code. The error is handled in the same way
+    /// as the error responses, and the response is not passed to `handle_response`.
+    fn get_error_from_success_body(&self, _res: &types::Response) -> Option<ErrorResponse> {
+        None
+    }
+
     // whenever capture sync is implemented at the connector side, this method should be overridden
     fn get_multiple_capture_sync_method(
         &self,
@@ -299,19 +355,13 @@ where
             Ok(router_data)
         }
         payments::CallConnectorAction::Trigger => {
+            let flow = circuit_breaker::get_flow_name::<T>();
             metrics::CONNECTOR_CALL_COUNT.add(
                 &metrics::CONTEXT,
                 1,
                 &[
                     metrics::request::add_attributes("connector", req.connector.to_string()),
-                    metrics::request::add_attributes(
-                        "flow",
-                        std::any::type_name::<T>()
-                            .split("::")
-                            .last()
-                            .unwrap_or_default()
-                            .to_string(),
-                    ),
+                    metrics::request::add_attributes("flow", flow.clone()),
                 ],
             );
 
@@ -336,81 +386,344 @@ where
                 })?);
 
             match connector_request {
-                Some(request) => {
-                    logger::debug!(connector_request=?request);
+                Some(mut request) => {
+                    request.add_proxy(connector_proxy::get_connector_proxy(
+                        &state.conf.connector_proxies,
+                        &req.connector,
+                        req.connector_proxy.as_ref(),
+                        &request.url,
+                    ));
+                    // The flows affected by an ongoing maintenance of the connector are not sent
+                    // to the connector, the payments were routed around it when possible
+                    if let Some(maintenance_window) =
+                        connector_maintenance::get_ongoing_maintenance_window(
+                            state.store.as_ref(),
+                            &req.connector,
+                            &flow,
+                        )
+                        .await
+                    {
+                        logger::warn!(
+                            connector = %req.connector,
+                            %flow,
+                            window_id = %maintenance_window.window_id,
+                            "Connector is under maintenance, request not sent to the connector"
+                        );
+                        router_data.response = Err(ErrorResponse {
+                            code: consts::CONNECTOR_UNAVAILABLE_ERROR_CODE.to_string(),
+                            message: consts::CONNECTOR_UNDER_MAINTENANCE_ERROR_MESSAGE.to_string(),
+                            reason: maintenance_window.message,
+                            status_code: 503,
+                        });
+                        routing_decisions::record_connector_attempt(
+                            state.store.as_ref(),
+                            req,
+                            &flow,
+                            ConnectorAttemptOutcome::UnderMaintenance,
+                            None,
+                        )
+                        .await;
+                        return Ok(router_data);
+                    }
+                    let circuit_decision = circuit_breaker::check_circuit(
+                        state,
+                        &req.merchant_id,
+                        &req.connector,
+                        &flow,
+                    )
+                    .await;
+                    if circuit_decision == circuit_breaker::CircuitDecision::Reject {
+                        logger::warn!(
+                            connector = %req.connector,
+                            %flow,
+                            "Circuit breaker is open, request not sent to the connector"
+                        );
+                        routing_decisions::record_connector_attempt(
+                            state.store.as_ref(),
+                            req,
+                            &flow,
+                            ConnectorAttemptOutcome::CircuitOpen,
+                            None,
+                        )
+                        .await;
+                        metrics::CIRCUIT_BREAKER_REJECTED_REQUEST_COUNT.add(
+                            &metrics::CONTEXT,
+                            1,
+                            &[
+                                metrics::request::add_attributes(
+                                    "connector",
+                                    req.connector.to_string(),
+                                ),
+                                metrics::request::add_attributes("flow", flow),
+                            ],
+                        );
+                        router_data.response = Err(ErrorResponse {
+                            code: consts::CONNECTOR_UNAVAILABLE_ERROR_CODE.to_string(),
+                            message: consts::CONNECTOR_UNAVAILABLE_ERROR_MESSAGE.to_string(),
+                            reason: Some(consts::CONNECTOR_UNAVAILABLE_ERROR_MESSAGE.to_string()),
+                            status_code: 503,
+                        });
+                        return Ok(router_data);
+                    }
+
+                    let masked_request_body = request.payload.as_ref().map(|payload| {
+                        masked_logging::get_masked_body(
+                            &req.connector,
+                            payload.peek().as_bytes(),
+                            matches!(request.content_type, Some(ContentType::FormUrlEncoded)),
+                        )
+                    });
+                    logger::debug!(connector_request=?request, connector_request_body=?masked_request_body);
+                    let in_flight_request =
+                        metrics_request::InFlightConnectorRequest::start(&req.connector, &flow);
+                    let request_started_at = Instant::now();
                     let response = call_connector_api(state, request).await;
-                    logger::debug!(connector_response=?response);
-                    match response {
-                        Ok(body) => {
-                            let response = match body {
-                                Ok(body) => {
-                                    let connector_http_status_code = Some(body.status_code);
-                                    let mut data = connector_integration
-                                        .handle_response(req, body)
-                                        .map_err(|error| {
-                                            if error.current_context()
-                                            == &errors::ConnectorError::ResponseDeserializationFailed
-                                        {
-                                            metrics::RESPONSE_DESERIALIZATION_FAILURE.add(
-                                                &metrics::CONTEXT,
-                                                1,
-                                                &[metrics::request::add_attributes(
-                                                    "connector",
-                                                    req.connector.to_string(),
-                                                )],
-                                            )
-                                        }
-                                            error
-                                        })?;
-                                    data.connector_http_status_code = connector_http_status_code;
-                                    data
-                                }
-                                Err(body) => {
-                                    router_data.connector_http_status_code = Some(body.status_code);
-                                    metrics::CONNECTOR_ERROR_RESPONSE_COUNT.add(
+                    let latency = request_started_at.elapsed();
+                    drop(in_flight_request);
+                    let raw_connector_response = match &response {
+                        Ok(Ok(body)) | Ok(Err(body)) => {
+                            let masked_response_body = masked_logging::get_masked_body(
+                                &req.connector,
+                                &body.response,
+                                false,
+                            );
+                            logger::debug!(connector_response_status_code=?body.status_code, connector_response_body=%masked_response_body);
+                            let (captured_body, is_truncated) = masked_logging::get_captured_body(
+                                &masked_response_body,
+                                consts::CONNECTOR_RESPONSE_CAPTURE_MAX_LENGTH,
+                            );
+                            Some(types::RawConnectorResponse {
+                                flow: flow.clone(),
+                                status_code: body.status_code,
+                                body: captured_body,
+                                is_truncated,
+                            })
+                        }
+                        Err(error) => {
+                            logger::debug!(connector_response=?error);
+                            None
+                        }
+                    };
+                    circuit_breaker::record_connector_call_outcome(
+                        state,
+                        &req.merchant_id,
+                        &req.connector,
+                        &flow,
+                        get_connector_call_outcome(&response),
+                    )
+                    .await;
+
+                    let raw_status_code = response.as_ref().ok().map(|body| match body {
+                        Ok(body) | Err(body) => body.status_code,
+                    });
+                    let result = handle_connector_api_response(
+                        &connector_integration,
+                        req,
+                        router_data,
+                        response,
+                    )
+                    .map(|mut data| {
+                        data.raw_connector_response = raw_connector_response;
+                        data
+                    });
+                    let (status_code, outcome) = match &result {
+                        Ok(data) => (
+                            data.connector_http_status_code.or(raw_status_code),
+                            metrics_request::ConnectorCallOutcome::from_connector_response(
+                                &data.response,
+                            ),
+                        ),
+                        Err(_) => (
+                            raw_status_code,
+                            metrics_request::ConnectorCallOutcome::ConnectorError,
+                        ),
+                    };
+                    metrics_request::record_connector_call_metrics(
+                        &req.connector,
+                        &flow,
+                        status_code,
+                        outcome,
+                        latency,
+                    );
+                    routing_decisions::record_connector_attempt(
+                        state.store.as_ref(),
+                        req,
+                        &flow,
+                        routing_decisions::get_attempt_outcome(outcome),
+                        Some(latency),
+                    )
+                    .await;
+                    result
+                }
+                None => Ok(router_data),
+            }
+        }
+    }
+}
+
+fn handle_connector_api_response<T, Req, Resp>(
+    connector_integration: &BoxedConnectorIntegration<'_, T, Req, Resp>,
+    req: &types::RouterData<T, Req, Resp>,
+    mut router_data: types::RouterData<T, Req, Resp>,
+    response: CustomResult<Result<types::Response, types::Response>, errors::ApiClientError>,
+) -> CustomResult<types::RouterData<T, Req, Resp>, errors::ConnectorError>
+where
+    T: Clone + Debug + 'static,
+    Req: Debug + Clone + 'static,
+    Resp: Debug + Clone + 'static,
+{
+    match response {
+        Ok(body) => {
+            let response = match body {
+                Ok(body) => {
+                    if let Some(error) = connector_integration.get_error_from_success_body(&body) {
+                        router_data.connector_http_status_code = Some(body.status_code);
+                        metrics::CONNECTOR_ERROR_RESPONSE_COUNT.add(
+                            &metrics::CONTEXT,
+                            1,
+                            &[metrics::request::add_attributes(
+                                "connector",
+                                req.connector.clone(),
+                            )],
+                        );
+                        router_data.response = Err(error);
+                        return Ok(router_data);
+                    }
+                    let connector_http_status_code = Some(body.status_code);
+                    let headers = body.headers.clone();
+                    let mut data =
+                        connector_integration
+                            .handle_response(req, body)
+                            .map_err(|error| {
+                                if error.current_context()
+                                    == &errors::ConnectorError::ResponseDeserializationFailed
+                                {
+                                    metrics::RESPONSE_DESERIALIZATION_FAILURE.add(
                                         &metrics::CONTEXT,
                                         1,
                                         &[metrics::request::add_attributes(
                                             "connector",
-                                            req.connector.clone(),
+                                            req.connector.to_string(),
                                         )],
-                                    );
-                                    let error = match body.status_code {
-                                        500..=511 => {
-                                            connector_integration.get_5xx_error_response(body)?
-                                        }
-                                        _ => connector_integration.get_error_response(body)?,
-                                    };
-
-                                    router_data.response = Err(error);
-
-                                    router_data
+                                    )
                                 }
-                            };
-                            Ok(response)
-                        }
-                        Err(error) => {
-                            if error.current_context().is_upstream_timeout() {
-                                let error_response = ErrorResponse {
-                                    code: consts::REQUEST_TIMEOUT_ERROR_CODE.to_string(),
-                                    message: consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string(),
-                                    reason: Some(consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string()),
-                                    status_code: 504,
-                                };
-                                router_data.response = Err(error_response);
-                                router_data.connector_http_status_code = Some(504);
-                                Ok(router_data)
-                            } else {
-                                Err(error.change_context(
-                                    errors::ConnectorError::ProcessingStepFailed(None),
-                                ))
-                            }
-                        }
+                                error
+                            })?;
+                    data.connector_http_status_code = connector_http_status_code;
+                    data.poll_config = data.poll_config.take().and_then(|poll_config| {
+                        polling::resolve_poll_url(poll_config, headers.as_ref())
+                    });
+                    data
+                }
+                Err(body) => {
+                    router_data.connector_http_status_code = Some(body.status_code);
+                    metrics::CONNECTOR_ERROR_RESPONSE_COUNT.add(
+                        &metrics::CONTEXT,
+                        1,
+                        &[metrics::request::add_attributes(
+                            "connector",
+                            req.connector.clone(),
+                        )],
+                    );
+                    let status_code = body.status_code;
+                    let response_body = body.response.clone();
+                    let error = match status_code {
+                        500..=511 => connector_integration.get_5xx_error_response(body),
+                        _ => connector_integration.get_error_response(body),
                     }
+                    .or_else(|error| match error.current_context() {
+                        // Error responses which the connector could not parse, like the error
+                        // pages of a gateway in front of the connector, are reported with the
+                        // status code and an excerpt of the body
+                        errors::ConnectorError::ResponseDeserializationFailed => {
+                            logger::warn!(?error, "Failed to parse the connector error response");
+                            Ok(get_transport_error_response(
+                                &errors::ConnectorError::ConnectorHttpError {
+                                    status: status_code,
+                                    body_excerpt: masked_logging::get_body_excerpt(
+                                        &req.connector,
+                                        &response_body,
+                                        consts::CONNECTOR_ERROR_BODY_EXCERPT_LENGTH,
+                                    ),
+                                },
+                                status_code,
+                            ))
+                        }
+                        _ => Err(error),
+                    })?;
+
+                    router_data.response = Err(error);
+
+                    router_data
                 }
-                None => Ok(router_data),
+            };
+            Ok(response)
+        }
+        Err(error) => match errors::ConnectorError::from_transport_error(error.current_context()) {
+            // Failures in reaching the connector are stored in the payment attempt, so that the
+            // cause of the failure is visible to the merchant
+            Some(connector_error) => {
+                logger::warn!(?error, retryable = connector_error.is_retryable());
+                let status_code = if connector_error.is_connector_timeout() {
+                    504
+                } else {
+                    503
+                };
+                router_data.response =
+                    Err(get_transport_error_response(&connector_error, status_code));
+                router_data.connector_http_status_code = Some(status_code);
+                Ok(router_data)
             }
+            None => Err(error.change_context(errors::ConnectorError::ProcessingStepFailed(None))),
+        },
+    }
+}
+
+fn get_transport_error_response(
+    connector_error: &errors::ConnectorError,
+    status_code: u16,
+) -> ErrorResponse {
+    let code = connector_error
+        .get_error_code()
+        .unwrap_or(consts::NO_ERROR_CODE)
+        .to_string();
+    match connector_error {
+        errors::ConnectorError::RequestTimedOut {
+            stage: errors::TimeoutStage::Read,
+        } => ErrorResponse {
+            code,
+            message: consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string(),
+            reason: Some(consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string()),
+            status_code,
+        },
+        errors::ConnectorError::ConnectorHttpError { body_excerpt, .. } => ErrorResponse {
+            code,
+            message: connector_error.to_string(),
+            reason: Some(body_excerpt.clone()),
+            status_code,
+        },
+        _ => ErrorResponse {
+            code,
+            message: connector_error.to_string(),
+            reason: Some(connector_error.to_string()),
+            status_code,
+        },
+    }
+}
+
+fn get_connector_call_outcome(
+    response: &CustomResult<Result<types::Response, types::Response>, errors::ApiClientError>,
+) -> circuit_breaker::ConnectorCallOutcome {
+    match response {
+        Ok(Ok(_)) => circuit_breaker::ConnectorCallOutcome::Success,
+        Ok(Err(body)) if (500..=599).contains(&body.status_code) => {
+            circuit_breaker::ConnectorCallOutcome::Failure
+        }
+        Ok(Err(_)) => circuit_breaker::ConnectorCallOutcome::Success,
+        Err(error) if error.current_context().is_connector_unavailable() => {
+            circuit_breaker::ConnectorCallOutcome::Failure
         }
+        Err(_) => circuit_breaker::ConnectorCallOutcome::Ignored,
     }
 }
 
@@ -454,6 +767,7 @@ pub async fn send_request(
     let client = client::create_client(
         &state.conf.proxy,
         should_bypass_proxy,
+        request.proxy.as_ref(),
         request.certificate,
         request.certificate_key,
     )?;
@@ -516,17 +830,7 @@ pub async fn send_request(
         ))
         .send()
         .await
-        .map_err(|error| match error {
-            error if error.is_timeout() => {
-                metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
-                errors::ApiClientError::RequestTimeoutReceived
-            }
-            error if is_connection_closed(&error) => {
-                metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
-                errors::ApiClientError::ConnectionClosed
-            }
-            _ => errors::ApiClientError::RequestNotSent(error.to_string()),
-        })
+        .map_err(get_request_error)
         .into_report()
         .attach_printable("Unable to send request to connector")
     };
@@ -539,6 +843,52 @@ pub async fn send_request(
     .await
 }
 
+/// Classifies the failure in sending the request to the connector by the stage of the connector
+/// call at which it failed
+fn get_request_error(error: reqwest::Error) -> errors::ApiClientError {
+    match error {
+        error if error.is_timeout() => {
+            metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
+            errors::ApiClientError::RequestTimedOut {
+                stage: if error.is_connect() {
+                    errors::TimeoutStage::Connect
+                } else {
+                    errors::TimeoutStage::Read
+                },
+            }
+        }
+        error if is_connection_closed(&error) => {
+            metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
+            errors::ApiClientError::ConnectionClosed
+        }
+        error if error.is_connect() => get_connect_error(&error),
+        error => errors::ApiClientError::RequestNotSent(error.to_string()),
+    }
+}
+
+/// Classifies the failure in establishing the connection to the connector
+fn get_connect_error(error: &reqwest::Error) -> errors::ApiClientError {
+    let mut source = error.source();
+    while let Some(err) = source {
+        if let Some(io_error) = err.downcast_ref::<std::io::Error>() {
+            if io_error.kind() == std::io::ErrorKind::ConnectionRefused {
+                return errors::ApiClientError::ConnectionRefused(err.to_string());
+            }
+        }
+        if err.downcast_ref::<openssl::ssl::Error>().is_some()
+            || err.downcast_ref::<openssl::error::ErrorStack>().is_some()
+        {
+            return errors::ApiClientError::TlsHandshakeFailed(err.to_string());
+        }
+        // The resolver errors are not exposed by hyper, and are identified by their message
+        if err.to_string().starts_with("dns error") {
+            return errors::ApiClientError::DnsResolutionFailed(err.to_string());
+        }
+        source = err.source();
+    }
+    errors::ApiClientError::RequestNotSent(error.to_string())
+}
+
 fn is_connection_closed(error: &reqwest::Error) -> bool {
     let mut source = error.source();
     while let Some(err) = source {
@@ -640,9 +990,40 @@ pub enum ApplicationResponse<R> {
     JsonForRedirection(api::RedirectionResponse),
     Form(Box<RedirectionFormData>),
     FileData((Vec<u8>, mime::Mime)),
+    FileStream(FileStream),
     JsonWithHeaders((R, Vec<(String, String)>)),
+    /// The resource did not change since the version the client has, sent with the headers
+    /// identifying that version
+    NotModified(Vec<(String, String)>),
+    /// The response recorded for the idempotency key of the request, sent as it was recorded
+    IdempotentReplay(idempotency::RecordedResponse),
+}
+
+/// A file sent to the client as it is generated, without being held in memory in full
+pub struct FileStream {
+    pub content_type: mime::Mime,
+    pub file_name: String,
+    pub stream: BoxStream<'static, errors::RouterResult<Bytes>>,
+}
+
+impl Debug for FileStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileStream")
+            .field("content_type", &self.content_type)
+            .field("file_name", &self.file_name)
+            .finish_non_exhaustive()
+    }
 }
 
+// Streams cannot be compared by content, two file streams are equal only if they are the same stream
+impl PartialEq for FileStream {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for FileStream {}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct RedirectionFormData {
     pub redirect_form: RedirectForm,
@@ -718,6 +1099,8 @@ pub async fn server_wrap_util<'a, 'b, A, U, T, Q, F, Fut, E, OErr>(
     func: F,
     api_auth: &dyn auth::AuthenticateAndFetch<U, A>,
     lock_action: api_locking::LockAction,
+    mut idempotency: Option<&mut idempotency::IdempotentRequest>,
+    response_pii_redaction: &mut Option<api_models::admin::PiiRedaction>,
 ) -> CustomResult<ApplicationResponse<Q>, OErr>
 where
     F: Fn(A, U, T) -> Fut,
@@ -730,6 +1113,8 @@ where
     CustomResult<ApplicationResponse<Q>, E>: ReportSwitchExt<ApplicationResponse<Q>, OErr>,
     CustomResult<U, errors::ApiErrorResponse>: ReportSwitchExt<U, OErr>,
     CustomResult<(), errors::ApiErrorResponse>: ReportSwitchExt<(), OErr>,
+    CustomResult<Option<idempotency::RecordedResponse>, errors::ApiErrorResponse>:
+        ReportSwitchExt<Option<idempotency::RecordedResponse>, OErr>,
     OErr: ResponseError + Sync + Send + 'static,
 {
     let request_id = RequestId::extract(request)
@@ -741,11 +1126,16 @@ where
 
     request_state.add_request_id(request_id);
 
+    auth::check_publishable_key_access(flow, request.headers()).switch()?;
+
     let auth_out = api_auth
         .authenticate_and_fetch(request.headers(), &request_state)
         .await
         .switch()?;
 
+    auth::check_api_key_permission(flow, &auth_out).switch()?;
+    *response_pii_redaction = auth_out.get_response_pii_redaction();
+
     let merchant_id = auth_out
         .get_merchant_id()
         .unwrap_or("MERCHANT_ID_NOT_FOUND")
@@ -757,12 +1147,26 @@ where
 
     tracing::Span::current().record("merchant_id", &merchant_id);
 
-    let output = {
-        lock_action
+    let replayed_response = match idempotency.as_deref_mut() {
+        Some(idempotency) => idempotency
+            .begin(&request_state, &merchant_id)
+            .await
+            .switch()?,
+        None => None,
+    };
+
+    let output = if let Some(response) = replayed_response {
+        Ok(ApplicationResponse::IdempotentReplay(response))
+    } else {
+        let locking_result = lock_action
             .clone()
             .perform_locking_action(&request_state, merchant_id.to_owned())
-            .await
-            .switch()?;
+            .await;
+        if let (Err(_), Some(idempotency)) = (&locking_result, idempotency) {
+            // The request is not processed, so it can be retried with its idempotency key
+            idempotency.abandon(&request_state).await;
+        }
+        locking_result.switch()?;
         let res = func(request_state.clone(), auth_out, payload)
             .await
             .switch();
@@ -796,6 +1200,90 @@ pub async fn server_wrap<'a, A, T, U, Q, F, Fut, E>(
     api_auth: &dyn auth::AuthenticateAndFetch<U, A>,
     lock_action: api_locking::LockAction,
 ) -> HttpResponse
+where
+    F: Fn(A, U, T) -> Fut,
+    Fut: Future<Output = CustomResult<ApplicationResponse<Q>, E>>,
+    Q: Serialize + Debug + 'a,
+    T: Debug,
+    U: auth::AuthInfo,
+    A: AppStateInfo + Clone,
+    ApplicationResponse<Q>: Debug,
+    CustomResult<ApplicationResponse<Q>, E>:
+        ReportSwitchExt<ApplicationResponse<Q>, api_models::errors::types::ApiErrorResponse>,
+{
+    server_wrap_with_idempotency(
+        flow,
+        state,
+        request,
+        payload,
+        func,
+        api_auth,
+        lock_action,
+        None,
+    )
+    .await
+}
+
+/// Wraps a mutating endpoint, whose requests made with an `Idempotency-Key` header get the
+/// response of the first request made with their key instead of being processed again
+#[instrument(
+    skip(request, state, func, api_auth, payload),
+    fields(request_method, request_url_path)
+)]
+pub async fn idempotent_server_wrap<'a, A, T, U, Q, F, Fut, E>(
+    flow: impl router_env::types::FlowMetric,
+    state: web::Data<A>,
+    request: &'a HttpRequest,
+    payload: T,
+    func: F,
+    api_auth: &dyn auth::AuthenticateAndFetch<U, A>,
+    lock_action: api_locking::LockAction,
+) -> HttpResponse
+where
+    F: Fn(A, U, T) -> Fut,
+    Fut: Future<Output = CustomResult<ApplicationResponse<Q>, E>>,
+    Q: Serialize + Debug + 'a,
+    T: Serialize + Debug,
+    U: auth::AuthInfo,
+    A: AppStateInfo + Clone,
+    ApplicationResponse<Q>: Debug,
+    CustomResult<ApplicationResponse<Q>, E>:
+        ReportSwitchExt<ApplicationResponse<Q>, api_models::errors::types::ApiErrorResponse>,
+{
+    let idempotency = match idempotency::IdempotentRequest::from_request(request, &payload).switch()
+    {
+        Ok(idempotency) => idempotency,
+        Err(error) => {
+            return log_and_return_error_response(errors::user_messages::attach_request_locale(
+                error, request,
+            ))
+        }
+    };
+
+    server_wrap_with_idempotency(
+        flow,
+        state,
+        request,
+        payload,
+        func,
+        api_auth,
+        lock_action,
+        idempotency,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn server_wrap_with_idempotency<'a, A, T, U, Q, F, Fut, E>(
+    flow: impl router_env::types::FlowMetric,
+    state: web::Data<A>,
+    request: &'a HttpRequest,
+    payload: T,
+    func: F,
+    api_auth: &dyn auth::AuthenticateAndFetch<U, A>,
+    lock_action: api_locking::LockAction,
+    mut idempotency: Option<idempotency::IdempotentRequest>,
+) -> HttpResponse
 where
     F: Fn(A, U, T) -> Fut,
     Fut: Future<Output = CustomResult<ApplicationResponse<Q>, E>>,
@@ -815,6 +1303,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut response_pii_redaction = None;
     let res = match metrics::request::record_request_time_metric(
         server_wrap_util(
             &flow,
@@ -824,6 +1313,8 @@ where
             func,
             api_auth,
             lock_action,
+            idempotency.as_mut(),
+            &mut response_pii_redaction,
         ),
         &flow,
     )
@@ -832,21 +1323,24 @@ where
         logger::info!(api_response =? response);
         response
     }) {
-        Ok(ApplicationResponse::Json(response)) => match serde_json::to_string(&response) {
-            Ok(res) => http_response_json(res),
-            Err(_) => http_response_err(
-                r#"{
+        Ok(ApplicationResponse::Json(response)) => {
+            match pii_redaction::redact_and_encode(&response, response_pii_redaction.as_ref()) {
+                Ok(res) => http_response_json(res),
+                Err(_) => http_response_err(
+                    r#"{
                     "error": {
                         "message": "Error serializing response from connector"
                     }
                 }"#,
-            ),
-        },
+                ),
+            }
+        }
         Ok(ApplicationResponse::StatusOk) => http_response_ok(),
         Ok(ApplicationResponse::TextPlain(text)) => http_response_plaintext(text),
         Ok(ApplicationResponse::FileData((file_data, content_type))) => {
             http_response_file_data(file_data, content_type)
         }
+        Ok(ApplicationResponse::FileStream(file_stream)) => http_response_file_stream(file_stream),
         Ok(ApplicationResponse::JsonForRedirection(response)) => {
             match serde_json::to_string(&response) {
                 Ok(res) => http_redirect_response(res, response),
@@ -872,7 +1366,7 @@ where
             .map_into_boxed_body()
         }
         Ok(ApplicationResponse::JsonWithHeaders((response, headers))) => {
-            match serde_json::to_string(&response) {
+            match pii_redaction::redact_and_encode(&response, response_pii_redaction.as_ref()) {
                 Ok(res) => http_response_json_with_headers(res, headers),
                 Err(_) => http_response_err(
                     r#"{
@@ -883,7 +1377,16 @@ where
                 ),
             }
         }
-        Err(error) => log_and_return_error_response(error),
+        Ok(ApplicationResponse::NotModified(headers)) => http_response_not_modified(headers),
+        Ok(ApplicationResponse::IdempotentReplay(response)) => response.into_http_response(),
+        Err(error) => log_and_return_error_response(errors::user_messages::attach_request_locale(
+            error, request,
+        )),
+    };
+
+    let res = match idempotency.as_ref() {
+        Some(idempotency) => idempotency.record_response(state.get_ref(), res).await,
+        None => res,
     };
 
     let response_code = res.status().as_u16();
@@ -915,9 +1418,11 @@ pub trait EmbedError: Sized {
 
 impl EmbedError for Report<api_models::errors::types::ApiErrorResponse> {
     fn embed(self) -> Self {
+        let report = errors::user_messages::embed_user_message(self);
+
         #[cfg(feature = "detailed_errors")]
         {
-            let mut report = self;
+            let mut report = report;
             let error_trace = serde_json::to_value(&report).ok().and_then(|inner| {
                 serde_json::from_value::<Vec<errors::NestedErrorStack<'_>>>(inner)
                     .ok()
@@ -938,7 +1443,7 @@ impl EmbedError for Report<api_models::errors::types::ApiErrorResponse> {
         }
 
         #[cfg(not(feature = "detailed_errors"))]
-        self
+        report
     }
 }
 
@@ -961,6 +1466,14 @@ pub fn http_response_json_with_headers<T: body::MessageBody + 'static>(
         .body(response)
 }
 
+pub fn http_response_not_modified(headers: Vec<(String, String)>) -> HttpResponse {
+    let mut response_builder = HttpResponse::NotModified();
+    for (name, value) in headers {
+        response_builder.append_header((name, value));
+    }
+    response_builder.finish()
+}
+
 pub fn http_response_plaintext<T: body::MessageBody + 'static>(res: T) -> HttpResponse {
     HttpResponse::Ok().content_type(mime::TEXT_PLAIN).body(res)
 }
@@ -972,6 +1485,20 @@ pub fn http_response_file_data<T: body::MessageBody + 'static>(
     HttpResponse::Ok().content_type(content_type).body(res)
 }
 
+pub fn http_response_file_stream(file_stream: FileStream) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(file_stream.content_type)
+        .insert_header((
+            actix_web::http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_stream.file_name),
+        ))
+        .streaming(file_stream.stream.map_err(|error| {
+            // The status code has already been sent, the client only sees a truncated body
+            logger::error!(file_stream_error=?error);
+            std::io::Error::new(std::io::ErrorKind::Other, "Failed to generate file")
+        }))
+}
+
 pub fn http_response_ok() -> HttpResponse {
     HttpResponse::Ok().finish()
 }
@@ -1005,6 +1532,26 @@ pub trait ConnectorRedirectResponse {
     ) -> CustomResult<payments::CallConnectorAction, errors::ConnectorError> {
         Ok(payments::CallConnectorAction::Avoid)
     }
+
+    /// Whether the customer cancelled the payment on the page of the connector before being
+    /// redirected back, in which case the payment is voided if it was authorized
+    fn is_cancelled_by_customer(
+        &self,
+        _query_params: &str,
+        _json_payload: Option<&serde_json::Value>,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        Ok(false)
+    }
+
+    /// Identifier of the payment which the connector appends to the return URL, used to identify
+    /// the payment when the return URL does not carry the identifiers appended by the router
+    fn get_payment_identifier_from_redirect(
+        &self,
+        _query_params: &str,
+        _json_payload: Option<&serde_json::Value>,
+    ) -> CustomResult<Option<api_models::payments::PaymentIdType>, errors::ConnectorError> {
+        Ok(None)
+    }
 }
 
 pub trait Authenticate {
@@ -1095,7 +1642,7 @@ pub fn build_redirection_form(
 
 
                 h3 style="text-align: center;" { "Please wait while we process your payment..." }
-                    form action=(PreEscaped(endpoint)) method=(method.to_string()) #payment_form {
+                    form action=(endpoint) method=(method.to_string()) #payment_form {
                         @for (field, value) in form_fields {
                         input type="hidden" name=(field) value=(value);
                     }
@@ -1290,8 +1837,223 @@ pub fn build_redirection_form(
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
     #[test]
     fn test_mime_essence() {
         assert_eq!(mime::APPLICATION_JSON.essence_str(), "application/json");
     }
+
+    async fn get_send_error(client: reqwest::Client, url: &str) -> errors::ApiClientError {
+        get_request_error(client.get(url).send().await.unwrap_err())
+    }
+
+    #[tokio::test]
+    async fn test_connection_failures_are_classified() {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        assert!(matches!(
+            get_send_error(client.clone(), &format!("http://127.0.0.1:{closed_port}")).await,
+            errors::ApiClientError::ConnectionRefused(_)
+        ));
+
+        // The `.invalid` top level domain is reserved and never resolves
+        assert!(matches!(
+            get_send_error(client.clone(), "http://connector.invalid").await,
+            errors::ApiClientError::DnsResolutionFailed(_)
+        ));
+
+        // Packets to this non routable address are dropped, so the connection never completes
+        assert_eq!(
+            get_send_error(client, "http://10.255.255.1").await,
+            errors::ApiClientError::RequestTimedOut {
+                stage: errors::TimeoutStage::Connect
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_after_connecting_are_classified() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&mock_server)
+            .await;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            get_send_error(client.clone(), &mock_server.uri()).await,
+            errors::ApiClientError::RequestTimedOut {
+                stage: errors::TimeoutStage::Read
+            }
+        );
+
+        // The mock server does not speak TLS, so the handshake fails
+        let https_url = mock_server.uri().replacen("http://", "https://", 1);
+        assert!(matches!(
+            get_send_error(client, &https_url).await,
+            errors::ApiClientError::TlsHandshakeFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_transport_errors_are_stored_with_their_error_code() {
+        let cases = [
+            (
+                errors::ApiClientError::DnsResolutionFailed("dns error".to_string()),
+                consts::DNS_RESOLUTION_FAILED_ERROR_CODE,
+                true,
+            ),
+            (
+                errors::ApiClientError::ConnectionRefused("connection refused".to_string()),
+                consts::CONNECTION_REFUSED_ERROR_CODE,
+                true,
+            ),
+            (
+                errors::ApiClientError::TlsHandshakeFailed("wrong version number".to_string()),
+                consts::TLS_HANDSHAKE_FAILED_ERROR_CODE,
+                true,
+            ),
+            (
+                errors::ApiClientError::RequestTimedOut {
+                    stage: errors::TimeoutStage::Connect,
+                },
+                consts::CONNECT_TIMEOUT_ERROR_CODE,
+                true,
+            ),
+            (
+                errors::ApiClientError::RequestTimedOut {
+                    stage: errors::TimeoutStage::Read,
+                },
+                consts::REQUEST_TIMEOUT_ERROR_CODE,
+                false,
+            ),
+        ];
+        for (api_client_error, error_code, is_retryable) in cases {
+            let connector_error =
+                errors::ConnectorError::from_transport_error(&api_client_error).unwrap();
+            assert_eq!(connector_error.is_retryable(), is_retryable);
+            assert_eq!(
+                get_transport_error_response(&connector_error, 503).code,
+                error_code
+            );
+        }
+        assert!(errors::ConnectorError::from_transport_error(
+            &errors::ApiClientError::UrlEncodingFailed
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_unparsable_connector_error_response_is_stored_with_status_and_excerpt() {
+        let connector_error = errors::ConnectorError::ConnectorHttpError {
+            status: 502,
+            body_excerpt: "<html><title>502 Bad Gateway</title></html>".to_string(),
+        };
+        let error_response = get_transport_error_response(&connector_error, 502);
+
+        assert!(connector_error.is_retryable());
+        assert_eq!(error_response.code, consts::CONNECTOR_HTTP_ERROR_ERROR_CODE);
+        assert_eq!(
+            error_response.message,
+            "Connector responded with HTTP status 502"
+        );
+        assert_eq!(
+            error_response.reason.as_deref(),
+            Some("<html><title>502 Bad Gateway</title></html>")
+        );
+        assert!(!errors::ConnectorError::ConnectorHttpError {
+            status: 500,
+            body_excerpt: String::new(),
+        }
+        .is_retryable());
+    }
+
+    fn handle_authorize_response<C>(
+        connector: &C,
+        body: serde_json::Value,
+    ) -> types::PaymentsAuthorizeRouterData
+    where
+        C: ConnectorIntegration<
+                api::Authorize,
+                types::PaymentsAuthorizeData,
+                types::PaymentsResponseData,
+            > + Send
+            + Sync,
+    {
+        let router_data = crate::types::test_utils::get_authorize_router_data(None);
+        let response = types::Response {
+            headers: None,
+            response: body.to_string().into(),
+            status_code: 200,
+        };
+        handle_connector_api_response(
+            &connector.get_connector_integration(),
+            &router_data,
+            router_data.clone(),
+            Ok(Ok(response)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_errors_in_successful_responses_are_handled_as_error_responses() {
+        // Return codes which are not known to the connector integration do not fail the
+        // deserialization of the response
+        let router_data = handle_authorize_response(
+            &crate::connector::Globepay,
+            json!({ "return_code": "INVALID_CURRENCY", "return_msg": "Currency is not supported" }),
+        );
+        let error_response = router_data.response.unwrap_err();
+        assert_eq!(error_response.code, "INVALID_CURRENCY");
+        assert_eq!(
+            error_response.reason.as_deref(),
+            Some("Currency is not supported")
+        );
+        // Errors with a 2xx status code mark the payment attempt as failed
+        assert_eq!(error_response.status_code, 200);
+        assert_eq!(router_data.connector_http_status_code, Some(200));
+
+        let router_data = handle_authorize_response(
+            &crate::connector::Noon,
+            json!({
+                "resultCode": 19001,
+                "message": "Invalid card number",
+                "classDescription": "Invalid BadRequest",
+            }),
+        );
+        let error_response = router_data.response.unwrap_err();
+        assert_eq!(error_response.code, "19001");
+        assert_eq!(error_response.message, "Invalid card number");
+        assert_eq!(error_response.status_code, 200);
+    }
+
+    #[test]
+    fn test_successful_responses_are_passed_to_the_connector() {
+        let router_data = handle_authorize_response(
+            &crate::connector::Globepay,
+            json!({
+                "return_code": "SUCCESS",
+                "result_code": "SUCCESS",
+                "order_id": "order_1",
+                "qrcode_img": "https://pay.globepay.co/qrcode/order_1.png",
+            }),
+        );
+
+        assert!(router_data.response.is_ok());
+        assert_eq!(router_data.connector_http_status_code, Some(200));
+    }
 }
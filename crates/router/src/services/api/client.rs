@@ -1,14 +1,19 @@
-use std::time::Duration;
+use std::{collections::HashMap, sync::RwLock, time::Duration};
 
+use common_utils::{
+    crypto::{self, GenerateDigest},
+    request::OutboundProxy,
+};
 use error_stack::{IntoReport, ResultExt};
 use http::{HeaderValue, Method};
 use masking::PeekInterface;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use reqwest::multipart::Form;
 
 use super::{request::Maskable, Request};
 use crate::{
     configs::settings::{Locker, Proxy},
+    consts,
     core::{
         errors::{ApiClientError, CustomResult},
         payments,
@@ -18,19 +23,54 @@ use crate::{
 
 static NON_PROXIED_CLIENT: OnceCell<reqwest::Client> = OnceCell::new();
 static PROXIED_CLIENT: OnceCell<reqwest::Client> = OnceCell::new();
+// Clients presenting a client certificate, keyed by the digest of the certificate, the
+// certificate key and whether the proxy is bypassed, so that the TLS identity is parsed and the
+// connection pool is set up only once per certificate
+static CERTIFICATE_CLIENTS: Lazy<RwLock<ClientCache>> = Lazy::new(|| RwLock::new(HashMap::new()));
+// Clients sending the requests of a connector through its proxy, keyed by the digest of the proxy
+static CONNECTOR_PROXY_CLIENTS: Lazy<RwLock<ClientCache>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Maximum number of clients held by each client cache, the client cached first is evicted to
+/// make room for a new one. Certificates and proxies which are rotated out are not used again.
+const MAX_CACHED_CLIENTS: usize = 100;
+
+/// Cached clients along with the order they were cached in
+type ClientCache = HashMap<Vec<u8>, (reqwest::Client, u64)>;
 
 fn get_client_builder(
     proxy_config: &Proxy,
     should_bypass_proxy: bool,
+    connector_proxy: Option<&OutboundProxy>,
 ) -> CustomResult<reqwest::ClientBuilder, ApiClientError> {
     let mut client_builder = reqwest::Client::builder()
         .redirect(reqwest::redirect::Policy::none())
+        .connect_timeout(Duration::from_secs(consts::REQUEST_CONNECT_TIME_OUT))
         .pool_idle_timeout(std::time::Duration::from_secs(
             proxy_config
                 .idle_pool_connection_timeout
                 .unwrap_or_default(),
         ));
 
+    // Proxy all the traffic of the connector through its proxy, in place of the configured proxy
+    if let Some(connector_proxy) = connector_proxy {
+        let mut proxy = reqwest::Proxy::all(&connector_proxy.url)
+            .into_report()
+            .change_context(ApiClientError::InvalidProxyConfiguration)
+            .attach_printable("Connector proxy configuration error")?;
+        if let Some(username) = connector_proxy.username.as_ref() {
+            proxy = proxy.basic_auth(
+                username,
+                connector_proxy
+                    .password
+                    .as_ref()
+                    .map(|password| password.peek().as_str())
+                    .unwrap_or_default(),
+            );
+        }
+        return Ok(client_builder.proxy(proxy));
+    }
+
     if should_bypass_proxy {
         return Ok(client_builder);
     }
@@ -70,7 +110,7 @@ fn get_base_client(
         &PROXIED_CLIENT
     }
     .get_or_try_init(|| {
-        get_client_builder(proxy_config, should_bypass_proxy)?
+        get_client_builder(proxy_config, should_bypass_proxy, None)?
             .build()
             .into_report()
             .change_context(ApiClientError::ClientConstructionFailed)
@@ -79,34 +119,131 @@ fn get_base_client(
     .clone())
 }
 
+fn get_client_cache_key(key: String) -> CustomResult<Vec<u8>, ApiClientError> {
+    crypto::Sha256
+        .generate_digest(key.as_bytes())
+        .change_context(ApiClientError::ClientConstructionFailed)
+        .attach_printable("Failed to generate the cache key of the client")
+}
+
+fn get_or_build_cached_client(
+    clients: &RwLock<ClientCache>,
+    cache_key: Vec<u8>,
+    build_client: impl FnOnce() -> CustomResult<reqwest::Client, ApiClientError>,
+) -> CustomResult<reqwest::Client, ApiClientError> {
+    if let Some(client) = clients
+        .read()
+        .ok()
+        .and_then(|clients| clients.get(&cache_key).map(|(client, _)| client.clone()))
+    {
+        return Ok(client);
+    }
+
+    let client = build_client()?;
+    if let Ok(mut clients) = clients.write() {
+        insert_cached_client(&mut clients, cache_key, client.clone());
+    }
+
+    Ok(client)
+}
+
+fn insert_cached_client(clients: &mut ClientCache, cache_key: Vec<u8>, client: reqwest::Client) {
+    if clients.len() >= MAX_CACHED_CLIENTS && !clients.contains_key(&cache_key) {
+        let oldest_cache_key = clients
+            .iter()
+            .min_by_key(|(_, (_, order))| *order)
+            .map(|(cache_key, _)| cache_key.clone());
+        if let Some(oldest_cache_key) = oldest_cache_key {
+            clients.remove(&oldest_cache_key);
+        }
+    }
+    let order = clients
+        .values()
+        .map(|(_, order)| order + 1)
+        .max()
+        .unwrap_or_default();
+    clients.insert(cache_key, (client, order));
+}
+
+fn get_connector_proxy_cache_key(connector_proxy: Option<&OutboundProxy>) -> String {
+    connector_proxy
+        .map(|proxy| {
+            format!(
+                "{}:{}:{}",
+                proxy.url,
+                proxy.username.as_deref().unwrap_or_default(),
+                proxy
+                    .password
+                    .as_ref()
+                    .map(|password| password.peek().as_str())
+                    .unwrap_or_default()
+            )
+        })
+        .unwrap_or_default()
+}
+
 // We may need to use outbound proxy to connect to external world.
 // Precedence will be the environment variables, followed by the config.
+// The proxy of a connector takes precedence over both, for the requests to that connector.
 pub(super) fn create_client(
     proxy_config: &Proxy,
     should_bypass_proxy: bool,
+    connector_proxy: Option<&OutboundProxy>,
     client_certificate: Option<String>,
     client_certificate_key: Option<String>,
 ) -> CustomResult<reqwest::Client, ApiClientError> {
     match (client_certificate, client_certificate_key) {
         (Some(encoded_certificate), Some(encoded_certificate_key)) => {
-            let client_builder = get_client_builder(proxy_config, should_bypass_proxy)?;
-
-            let identity = payments::helpers::create_identity_from_certificate_and_key(
-                encoded_certificate,
-                encoded_certificate_key,
-            )?;
-
-            client_builder
-                .identity(identity)
-                .build()
-                .into_report()
-                .change_context(ApiClientError::ClientConstructionFailed)
-                .attach_printable("Failed to construct client with certificate and certificate key")
+            let cache_key = get_client_cache_key(format!(
+                "{encoded_certificate}:{encoded_certificate_key}:{should_bypass_proxy}:{}",
+                get_connector_proxy_cache_key(connector_proxy)
+            ))?;
+
+            get_or_build_cached_client(&CERTIFICATE_CLIENTS, cache_key, || {
+                build_client_with_identity(
+                    get_client_builder(proxy_config, should_bypass_proxy, connector_proxy)?,
+                    encoded_certificate,
+                    encoded_certificate_key,
+                )
+            })
         }
-        _ => get_base_client(proxy_config, should_bypass_proxy),
+        _ => match connector_proxy {
+            Some(connector_proxy) => {
+                let cache_key =
+                    get_client_cache_key(get_connector_proxy_cache_key(Some(connector_proxy)))?;
+
+                get_or_build_cached_client(&CONNECTOR_PROXY_CLIENTS, cache_key, || {
+                    get_client_builder(proxy_config, should_bypass_proxy, Some(connector_proxy))?
+                        .build()
+                        .into_report()
+                        .change_context(ApiClientError::ClientConstructionFailed)
+                        .attach_printable("Failed to construct the client of the connector proxy")
+                })
+            }
+            None => get_base_client(proxy_config, should_bypass_proxy),
+        },
     }
 }
 
+/// Builds a client presenting the given base64 encoded PEM certificate and certificate key
+pub(crate) fn build_client_with_identity(
+    client_builder: reqwest::ClientBuilder,
+    encoded_certificate: String,
+    encoded_certificate_key: String,
+) -> CustomResult<reqwest::Client, ApiClientError> {
+    let identity = payments::helpers::create_identity_from_certificate_and_key(
+        encoded_certificate,
+        encoded_certificate_key,
+    )?;
+
+    client_builder
+        .identity(identity)
+        .build()
+        .into_report()
+        .change_context(ApiClientError::ClientConstructionFailed)
+        .attach_printable("Failed to construct client with certificate and certificate key")
+}
+
 pub fn proxy_bypass_urls(locker: &Locker) -> Vec<String> {
     let locker_host = locker.host.to_owned();
     let basilisk_host = locker.basilisk_host.to_owned();
@@ -415,3 +552,24 @@ impl ApiClient for MockApiClient {
 
     fn add_flow_name(&mut self, _flow_name: String) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_cached_first_is_evicted_when_cache_is_full() {
+        let mut clients = ClientCache::new();
+        for index in 0..=MAX_CACHED_CLIENTS {
+            insert_cached_client(
+                &mut clients,
+                index.to_be_bytes().to_vec(),
+                reqwest::Client::new(),
+            );
+        }
+
+        assert_eq!(clients.len(), MAX_CACHED_CLIENTS);
+        assert!(!clients.contains_key(&0_usize.to_be_bytes().to_vec()));
+        assert!(clients.contains_key(&MAX_CACHED_CLIENTS.to_be_bytes().to_vec()));
+    }
+}
@@ -0,0 +1,398 @@
+//! Outbound proxies of the connectors, through which the requests to a connector are sent so that
+//! they reach the connector from the IPs it has allow-listed. The proxy of a connector is
+//! configured in `connector_proxies`, and can be overridden for a merchant connector account in the
+//! `outbound_proxy` field of its connector account details, which are stored encrypted. The proxy
+//! of an account is only used when the operator allowed its URL in `allowed_proxy_urls`, so that
+//! an account cannot have the requests of the application sent to any host.
+//!
+//! Only the requests to the connectors are sent through these proxies. The other outgoing requests,
+//! like the webhooks sent to the merchants, are sent as configured in `proxy`.
+
+use std::time::Duration;
+
+use common_utils::request::OutboundProxy;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    configs::settings::{ConnectorProxy, ConnectorProxyConfig},
+    core::errors::{ApiClientError, CustomResult},
+};
+
+/// Field of the connector account details of a merchant connector account holding the proxy of
+/// the account
+pub const OUTBOUND_PROXY_ACCOUNT_DETAILS_FIELD: &str = "outbound_proxy";
+
+/// Fields of the proxy of an account which are masked in the responses
+const MASKED_OUTBOUND_PROXY_FIELDS: [&str; 2] =
+    ["/outbound_proxy/username", "/outbound_proxy/password"];
+
+/// Reads the proxy of the merchant connector account from its connector account details, none if
+/// the details do not configure one
+pub fn get_merchant_connector_proxy(
+    connector_account_details: &serde_json::Value,
+) -> Result<Option<ConnectorProxy>, serde_json::Error> {
+    connector_account_details
+        .get(OUTBOUND_PROXY_ACCOUNT_DETAILS_FIELD)
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+}
+
+/// Reads the proxy of the merchant connector account like [`get_merchant_connector_proxy`], an
+/// invalid proxy is logged and ignored so that the proxy of the connector is used instead
+pub fn get_merchant_connector_proxy_or_default(
+    connector_account_details: &serde_json::Value,
+) -> Option<ConnectorProxy> {
+    get_merchant_connector_proxy(connector_account_details).unwrap_or_else(|error| {
+        logger::error!(
+            ?error,
+            "Invalid outbound proxy in the connector account details, the proxy of the connector is used"
+        );
+        None
+    })
+}
+
+/// Masks the credentials of the proxy in the connector account details returned to the merchant
+pub fn mask_merchant_connector_proxy(connector_account_details: &mut serde_json::Value) {
+    super::masked_logging::mask_value(connector_account_details, &MASKED_OUTBOUND_PROXY_FIELDS);
+}
+
+/// Whether the operator allowed the proxy to be configured for a merchant connector account. The
+/// URLs are compared by their scheme, host and port.
+pub fn is_allowed_merchant_connector_proxy(
+    config: &ConnectorProxyConfig,
+    proxy: &ConnectorProxy,
+) -> bool {
+    let Ok(proxy_url) = url::Url::parse(&proxy.url) else {
+        return false;
+    };
+
+    config.allowed_proxy_urls.iter().any(|allowed_url| {
+        url::Url::parse(allowed_url)
+            .map(|allowed_url| allowed_url.origin() == proxy_url.origin())
+            .unwrap_or(false)
+    })
+}
+
+/// The proxy through which the request to the URL is sent, which is the proxy of the merchant
+/// connector account if it configures an allowed one, else the proxy configured for the
+/// connector. None if neither is configured, or if the host of the URL is bypassed by the proxy.
+pub fn get_connector_proxy(
+    config: &ConnectorProxyConfig,
+    connector: &str,
+    merchant_connector_proxy: Option<&ConnectorProxy>,
+    url: &str,
+) -> Option<OutboundProxy> {
+    let merchant_connector_proxy = merchant_connector_proxy.filter(|proxy| {
+        let is_allowed = is_allowed_merchant_connector_proxy(config, proxy);
+        if !is_allowed {
+            logger::error!(
+                %connector,
+                "Outbound proxy of the merchant connector account is not allowed, the proxy of the connector is used"
+            );
+        }
+        is_allowed
+    });
+
+    merchant_connector_proxy
+        .or_else(|| config.connectors.get(connector))
+        .and_then(|proxy| proxy.get_outbound_proxy(url))
+}
+
+fn get_proxy_address(proxy: &ConnectorProxy) -> CustomResult<String, ApiClientError> {
+    let url = url::Url::parse(&proxy.url)
+        .into_report()
+        .change_context(ApiClientError::InvalidProxyConfiguration)?;
+    let host = url
+        .host_str()
+        .ok_or(ApiClientError::InvalidProxyConfiguration)
+        .into_report()
+        .attach_printable("Proxy URL without a host")?;
+    let port = url
+        .port_or_known_default()
+        .ok_or(ApiClientError::InvalidProxyConfiguration)
+        .into_report()
+        .attach_printable("Proxy URL without a port")?;
+
+    Ok(format!("{host}:{port}"))
+}
+
+/// Connects to the proxy of each connector, so that a proxy which cannot be reached fails the
+/// startup of the application instead of the payments routed to its connector
+#[instrument(skip_all)]
+pub async fn check_connector_proxies(
+    config: &ConnectorProxyConfig,
+) -> CustomResult<(), ApiClientError> {
+    let connect_timeout = Duration::from_secs(config.connect_timeout_in_secs);
+
+    for (connector, proxy) in &config.connectors {
+        let address = get_proxy_address(proxy)
+            .attach_printable_lazy(|| format!("Invalid proxy of connector {connector}"))?;
+        match tokio::time::timeout(
+            connect_timeout,
+            tokio::net::TcpStream::connect(address.as_str()),
+        )
+        .await
+        {
+            Ok(Ok(_)) => logger::info!(%connector, "Proxy of the connector is reachable"),
+            Ok(Err(error)) => {
+                return Err(report!(ApiClientError::ProxyUnreachable(
+                    connector.to_string()
+                )))
+                .attach_printable(format!("Failed to connect to {address}: {error}"));
+            }
+            Err(_) => {
+                return Err(report!(ApiClientError::ProxyUnreachable(
+                    connector.to_string()
+                )))
+                .attach_printable(format!("Connection to {address} timed out"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{configs::settings::Proxy, services::api::client};
+
+    /// Server answering every request with `200 OK`, which records the request line and the
+    /// proxy authorization of the requests it receives. It acts both as a proxy, which receives
+    /// the absolute URL of the request, and as a connector, which receives its path.
+    async fn start_recording_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded_requests = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = vec![0; 4096];
+                let length = stream.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..length]).to_string();
+                let request_line = request.lines().next().unwrap_or_default().to_string();
+                let proxy_authorization = request
+                    .lines()
+                    .find(|line| line.to_lowercase().starts_with("proxy-authorization"))
+                    .map(|_| " with proxy authorization")
+                    .unwrap_or_default();
+                recorded_requests
+                    .lock()
+                    .unwrap()
+                    .push(format!("{request_line}{proxy_authorization}"));
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await
+                    .unwrap();
+            }
+        });
+
+        (format!("http://{address}"), requests)
+    }
+
+    fn get_connector_proxy_config(proxy_url: &str) -> ConnectorProxyConfig {
+        ConnectorProxyConfig {
+            connectors: HashMap::from([(
+                "adyen".to_string(),
+                ConnectorProxy {
+                    url: proxy_url.to_string(),
+                    username: Some("egress".to_string()),
+                    password: Some("password".to_string().into()),
+                    bypass_hosts: vec![".bypassed.test".to_string()],
+                },
+            )]),
+            allowed_proxy_urls: vec!["http://merchant-egress.internal:8080".to_string()],
+            ..Default::default()
+        }
+    }
+
+    async fn send(proxy: Option<OutboundProxy>, url: &str) {
+        let client =
+            client::create_client(&Proxy::default(), false, proxy.as_ref(), None, None).unwrap();
+        client.get(url).send().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_only_the_requests_of_the_configured_connector_traverse_its_proxy() {
+        let (proxy_url, proxied_requests) = start_recording_server().await;
+        let (connector_url, direct_requests) = start_recording_server().await;
+        let config = get_connector_proxy_config(&proxy_url);
+
+        let adyen_url = "http://checkout.adyen.test/payments";
+        send(
+            get_connector_proxy(&config, "adyen", None, adyen_url),
+            adyen_url,
+        )
+        .await;
+        let stripe_url = format!("{connector_url}/v1/payment_intents");
+        send(
+            get_connector_proxy(&config, "stripe", None, &stripe_url),
+            &stripe_url,
+        )
+        .await;
+
+        assert_eq!(
+            *proxied_requests.lock().unwrap(),
+            vec![
+                "GET http://checkout.adyen.test/payments HTTP/1.1 with proxy authorization"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            *direct_requests.lock().unwrap(),
+            vec!["GET /v1/payment_intents HTTP/1.1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_bypassed_hosts_are_connected_to_directly() {
+        let config = get_connector_proxy_config("http://egress.internal:3128");
+
+        assert!(
+            get_connector_proxy(&config, "adyen", None, "https://bypassed.test/token").is_none()
+        );
+        assert!(
+            get_connector_proxy(&config, "adyen", None, "https://auth.Bypassed.test/token")
+                .is_none()
+        );
+        assert!(
+            get_connector_proxy(&config, "adyen", None, "https://notbypassed.test/token").is_some()
+        );
+    }
+
+    #[test]
+    fn test_merchant_connector_account_proxy_overrides_the_connector_proxy() {
+        let config = get_connector_proxy_config("http://egress.internal:3128");
+        let connector_account_details = serde_json::json!({
+            "auth_type": "HeaderKey",
+            "api_key": "key",
+            "outbound_proxy": { "url": "http://merchant-egress.internal:8080" }
+        });
+        let merchant_connector_proxy =
+            get_merchant_connector_proxy_or_default(&connector_account_details);
+
+        let proxy = get_connector_proxy(
+            &config,
+            "adyen",
+            merchant_connector_proxy.as_ref(),
+            "https://adyen.test",
+        )
+        .unwrap();
+        assert_eq!(proxy.url, "http://merchant-egress.internal:8080");
+        assert!(proxy.username.is_none());
+        // The account configures its proxy for any connector
+        assert!(get_connector_proxy(
+            &config,
+            "stripe",
+            merchant_connector_proxy.as_ref(),
+            "https://stripe.test"
+        )
+        .is_some());
+
+        // An invalid proxy in the details falls back to the proxy of the connector
+        let merchant_connector_proxy = get_merchant_connector_proxy_or_default(
+            &serde_json::json!({ "outbound_proxy": "egress" }),
+        );
+        assert!(merchant_connector_proxy.is_none());
+        let proxy = get_connector_proxy(
+            &config,
+            "adyen",
+            merchant_connector_proxy.as_ref(),
+            "https://adyen.test",
+        )
+        .unwrap();
+        assert_eq!(proxy.url, "http://egress.internal:3128");
+    }
+
+    #[test]
+    fn test_merchant_connector_account_proxy_which_is_not_allowed_is_not_used() {
+        let config = get_connector_proxy_config("http://egress.internal:3128");
+        let merchant_connector_proxy =
+            get_merchant_connector_proxy_or_default(&serde_json::json!({
+                "outbound_proxy": { "url": "http://169.254.169.254:80" }
+            }))
+            .unwrap();
+
+        assert!(!is_allowed_merchant_connector_proxy(
+            &config,
+            &merchant_connector_proxy
+        ));
+        let proxy = get_connector_proxy(
+            &config,
+            "adyen",
+            Some(&merchant_connector_proxy),
+            "https://adyen.test",
+        )
+        .unwrap();
+        assert_eq!(proxy.url, "http://egress.internal:3128");
+        assert!(get_connector_proxy(
+            &config,
+            "stripe",
+            Some(&merchant_connector_proxy),
+            "https://stripe.test"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_credentials_of_the_merchant_connector_account_proxy_are_masked() {
+        let mut connector_account_details = serde_json::json!({
+            "auth_type": "HeaderKey",
+            "api_key": "key",
+            "outbound_proxy": {
+                "url": "http://merchant-egress.internal:8080",
+                "username": "egress",
+                "password": "password"
+            }
+        });
+
+        mask_merchant_connector_proxy(&mut connector_account_details);
+
+        assert_eq!(
+            connector_account_details["outbound_proxy"],
+            serde_json::json!({
+                "url": "http://merchant-egress.internal:8080",
+                "username": "***",
+                "password": "***"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_proxy_fails_the_check() {
+        let (proxy_url, _) = start_recording_server().await;
+        assert!(
+            check_connector_proxies(&get_connector_proxy_config(&proxy_url))
+                .await
+                .is_ok()
+        );
+
+        // The port of a listener which is closed again is not listened on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let error =
+            check_connector_proxies(&get_connector_proxy_config(&format!("http://{address}")))
+                .await
+                .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            ApiClientError::ProxyUnreachable(connector) if connector == "adyen"
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+//! Idempotency of the mutating API requests made with an `Idempotency-Key` header. The first
+//! request made with a key records a hash of the request, and its response once it completes, so
+//! that a retry made with the same key gets the recorded response, with the same status code,
+//! headers and body, instead of being processed again. The error responses are recorded as well,
+//! except the conflicts and the rate limits, which are returned before the request is processed.
+//!
+//! The keys are scoped to the merchant, and a key reused for a different request is rejected. A
+//! request made while the request with its key is in progress waits for the response of that
+//! request for the configured wait time, and is rejected with the number of seconds after which it
+//! can be retried if the response is not recorded by then.
+
+use actix_web::{
+    body::{self, BodySize, MessageBody},
+    http::StatusCode,
+    HttpRequest, HttpResponse,
+};
+use bytes::Bytes;
+use common_utils::{
+    crypto::{self, GenerateDigest},
+    date_time,
+};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde::Serialize;
+use time::ext::NumericalDuration;
+
+use crate::{
+    configs::settings::IdempotencyConfig,
+    core::errors::{self, RouterResult},
+    routes::app::AppStateInfo,
+    types::storage,
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Header set on the responses replayed from the request which first used the idempotency key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// Response recorded for an idempotency key, replayed as it was sent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Bytes,
+}
+
+impl RecordedResponse {
+    fn from_idempotency_key(idempotency_key: storage::IdempotencyKey) -> Option<Self> {
+        let status_code = u16::try_from(idempotency_key.response_status_code?).ok()?;
+        let headers = idempotency_key
+            .response_headers
+            .map(serde_json::from_value)
+            .transpose()
+            .unwrap_or_else(|error| {
+                logger::error!(?error, "Invalid headers recorded for the idempotency key");
+                None
+            })
+            .unwrap_or_default();
+        Some(Self {
+            status_code,
+            headers,
+            body: idempotency_key.response_body.unwrap_or_default().into(),
+        })
+    }
+
+    pub fn into_http_response(self) -> HttpResponse {
+        let mut response_builder = HttpResponse::build(
+            StatusCode::from_u16(self.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        for header in self.headers {
+            response_builder.append_header(header);
+        }
+        response_builder
+            .insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"))
+            .body(self.body)
+    }
+}
+
+/// A request made with an idempotency key
+#[derive(Debug)]
+pub struct IdempotentRequest {
+    idempotency_key: String,
+    request_hash: String,
+    /// The merchant of the request, set once the request holds its key, which is only the case
+    /// for the request which records the response
+    merchant_id: Option<String>,
+    config: Option<IdempotencyConfig>,
+}
+
+impl IdempotentRequest {
+    /// Reads the idempotency key of the request, none if the request has no key. The hash of the
+    /// request covers its method and path, so that a key cannot be reused across endpoints.
+    pub fn from_request<T: Serialize>(
+        request: &HttpRequest,
+        payload: &T,
+    ) -> RouterResult<Option<Self>> {
+        let Some(header) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+            return Ok(None);
+        };
+        let idempotency_key = header
+            .to_str()
+            .ok()
+            .filter(|key| {
+                !key.is_empty()
+                    && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH
+                    && key.chars().all(|c| c.is_ascii_graphic())
+            })
+            .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The {IDEMPOTENCY_KEY_HEADER} header must be made of 1 to {MAX_IDEMPOTENCY_KEY_LENGTH} printable ASCII characters"
+                ),
+            })
+            .into_report()?
+            .to_string();
+
+        let mut hashed_request = format!("{} {}\n", request.method(), request.path()).into_bytes();
+        serde_json::to_writer(&mut hashed_request, payload)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the request to hash it")?;
+        let request_hash = crypto::Sha256
+            .generate_digest(&hashed_request)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to hash the request")?;
+
+        Ok(Some(Self {
+            idempotency_key,
+            request_hash: hex::encode(request_hash),
+            merchant_id: None,
+            config: None,
+        }))
+    }
+
+    /// Takes the idempotency key for the request, or returns the response recorded for the key.
+    /// Waits for the response if the request with the key is in progress.
+    #[instrument(skip_all)]
+    pub async fn begin<A: AppStateInfo>(
+        &mut self,
+        state: &A,
+        merchant_id: &str,
+    ) -> RouterResult<Option<RecordedResponse>> {
+        let config = state.conf().idempotency;
+        let store = state.store();
+        let retries = config
+            .wait_time_in_millis
+            .checked_div(config.delay_between_retries_in_millis)
+            .unwrap_or(0);
+
+        let mut attempt = 0;
+        loop {
+            let now = date_time::now();
+            let insert_result = store
+                .insert_idempotency_key(storage::IdempotencyKeyNew {
+                    merchant_id: merchant_id.to_string(),
+                    idempotency_key: self.idempotency_key.clone(),
+                    request_hash: self.request_hash.clone(),
+                    expires_at: now
+                        .saturating_add(i64::from(config.in_progress_ttl_in_secs).seconds()),
+                })
+                .await;
+            match insert_result {
+                Ok(_) => {
+                    self.merchant_id = Some(merchant_id.to_string());
+                    self.config = Some(config);
+                    return Ok(None);
+                }
+                Err(error) if error.current_context().is_db_unique_violation() => {}
+                Err(error) => {
+                    return Err(error.change_context(errors::ApiErrorResponse::InternalServerError))
+                        .attach_printable("Failed to insert the idempotency key");
+                }
+            }
+
+            let stored = store
+                .find_idempotency_key_by_merchant_id_idempotency_key_optional(
+                    merchant_id,
+                    &self.idempotency_key,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to fetch the idempotency key")?;
+            match stored {
+                // The key was released or replaced since the insert, it is taken again
+                None => continue,
+                Some(stored) if stored.expires_at <= now => {
+                    store
+                        .delete_idempotency_key_by_merchant_id_idempotency_key(
+                            merchant_id,
+                            &self.idempotency_key,
+                        )
+                        .await
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to delete the expired idempotency key")?;
+                    continue;
+                }
+                Some(stored) if stored.request_hash != self.request_hash => {
+                    return Err(report!(errors::ApiErrorResponse::IdempotencyKeyMismatch {
+                        idempotency_key: self.idempotency_key.clone(),
+                    }));
+                }
+                Some(stored) => {
+                    if let Some(response) = RecordedResponse::from_idempotency_key(stored) {
+                        logger::info!(
+                            idempotency_key = %self.idempotency_key,
+                            "Replaying the response recorded for the idempotency key"
+                        );
+                        return Ok(Some(response));
+                    }
+                }
+            }
+
+            if attempt >= retries {
+                break;
+            }
+            attempt += 1;
+            tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
+                config.delay_between_retries_in_millis,
+            )))
+            .await;
+        }
+
+        Err(report!(errors::ApiErrorResponse::IdempotencyKeyInUse {
+            idempotency_key: self.idempotency_key.clone(),
+            retry_after_secs: u64::from(config.wait_time_in_millis / 1000).max(1),
+        }))
+    }
+
+    /// Records the response of the request holding the idempotency key, and returns it to be
+    /// sent. The key is released instead when the response is not replayable.
+    #[instrument(skip_all)]
+    pub async fn record_response<A: AppStateInfo>(
+        &self,
+        state: &A,
+        response: HttpResponse,
+    ) -> HttpResponse {
+        let (Some(merchant_id), Some(config)) = (self.merchant_id.as_deref(), self.config.as_ref())
+        else {
+            return response;
+        };
+
+        let (response, response_body) = response.into_parts();
+        let replayable = !matches!(
+            response.status(),
+            StatusCode::CONFLICT | StatusCode::TOO_MANY_REQUESTS
+        ) && !matches!(response_body.size(), BodySize::Stream);
+        if !replayable {
+            self.release(state, merchant_id).await;
+            return response.set_body(response_body);
+        }
+
+        let response_body = match body::to_bytes(response_body).await {
+            Ok(response_body) => response_body,
+            Err(error) => {
+                logger::error!(?error, "Failed to read the response body to record it");
+                self.release(state, merchant_id).await;
+                return super::http_response_err(
+                    r#"{
+                        "error": {
+                            "message": "Error reading the response"
+                        }
+                    }"#,
+                );
+            }
+        };
+        let response_headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect::<Vec<_>>();
+
+        let update = storage::IdempotencyKeyUpdate::ResponseUpdate {
+            response_status_code: i32::from(response.status().as_u16()),
+            response_headers: serde_json::json!(response_headers),
+            response_body: response_body.to_vec(),
+            expires_at: date_time::now().saturating_add(i64::from(config.ttl_in_secs).seconds()),
+        };
+        // The key is kept in progress if the response cannot be recorded, as the request has been
+        // processed and must not be processed again by a retry
+        if let Err(error) = state
+            .store()
+            .update_idempotency_key_by_merchant_id_idempotency_key(
+                merchant_id,
+                &self.idempotency_key,
+                update,
+            )
+            .await
+        {
+            logger::error!(
+                ?error,
+                "Failed to record the response of the idempotency key"
+            );
+        }
+
+        response.set_body(response_body.boxed())
+    }
+
+    /// Releases the idempotency key held by the request without recording a response, for a
+    /// request which fails before it is processed
+    pub async fn abandon<A: AppStateInfo>(&mut self, state: &A) {
+        if let Some(merchant_id) = self.merchant_id.take() {
+            self.release(state, &merchant_id).await;
+        }
+    }
+
+    async fn release<A: AppStateInfo>(&self, state: &A, merchant_id: &str) {
+        if let Err(error) = state
+            .store()
+            .delete_idempotency_key_by_merchant_id_idempotency_key(
+                merchant_id,
+                &self.idempotency_key,
+            )
+            .await
+        {
+            logger::error!(?error, "Failed to release the idempotency key");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use actix_web::{test::TestRequest, web};
+    use router_env::Flow;
+
+    use super::*;
+    use crate::{
+        core::api_locking,
+        routes::AppState,
+        services::{self, api, authentication as auth},
+    };
+
+    async fn get_state(wait_time_in_millis: u32) -> AppState {
+        let mut conf = crate::configs::settings::Settings::default();
+        conf.idempotency.wait_time_in_millis = wait_time_in_millis;
+        AppState::with_storage(
+            conf,
+            crate::db::StorageImpl::Mock,
+            tokio::sync::oneshot::channel().0,
+            Box::new(services::MockApiClient),
+        )
+        .await
+    }
+
+    fn get_request(idempotency_key: &str) -> HttpRequest {
+        TestRequest::post()
+            .uri("/payments")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key))
+            .to_http_request()
+    }
+
+    async fn begin(
+        state: &AppState,
+        idempotency_key: &str,
+        amount: i64,
+    ) -> (IdempotentRequest, RouterResult<Option<RecordedResponse>>) {
+        let payload = serde_json::json!({ "amount": amount });
+        let mut request = IdempotentRequest::from_request(&get_request(idempotency_key), &payload)
+            .unwrap()
+            .unwrap();
+        let result = request.begin(state, "merchant_1").await;
+        (request, result)
+    }
+
+    #[tokio::test]
+    async fn test_same_key_and_request_replays_the_response() {
+        let state = get_state(3000).await;
+        let (request, result) = begin(&state, "key_1", 100).await;
+        assert!(result.unwrap().is_none());
+
+        let response = HttpResponse::BadRequest()
+            .content_type(mime::APPLICATION_JSON)
+            .insert_header(("x-custom", "value"))
+            .body(r#"{"error":{"code":"IR_06"}}"#);
+        let response = request.record_response(&state, response).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let (_, result) = begin(&state, "key_1", 100).await;
+        let replayed = result.unwrap().unwrap();
+        assert_eq!(replayed.status_code, 400);
+        assert_eq!(replayed.body, Bytes::from(r#"{"error":{"code":"IR_06"}}"#));
+
+        let replayed = replayed.into_http_response();
+        assert_eq!(replayed.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(replayed.headers().get("x-custom").unwrap(), "value");
+        assert_eq!(
+            replayed.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            replayed.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_same_key_and_different_request_conflicts() {
+        let state = get_state(3000).await;
+        let (request, result) = begin(&state, "key_1", 100).await;
+        assert!(result.unwrap().is_none());
+        request
+            .record_response(&state, HttpResponse::Ok().body("{}"))
+            .await;
+
+        let (_, result) = begin(&state, "key_1", 200).await;
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::IdempotencyKeyMismatch { idempotency_key } if idempotency_key == "key_1"
+        ));
+
+        // The key of another merchant is not shared
+        let payload = serde_json::json!({ "amount": 200 });
+        let mut request = IdempotentRequest::from_request(&get_request("key_1"), &payload)
+            .unwrap()
+            .unwrap();
+        assert!(request.begin(&state, "merchant_2").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_in_progress_is_not_processed_again() {
+        let state = get_state(0).await;
+
+        let (request, result) = begin(&state, "key_1", 100).await;
+        assert!(result.unwrap().is_none());
+        let (_, result) = begin(&state, "key_1", 100).await;
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::IdempotencyKeyInUse { .. }
+        ));
+
+        // A conflict is not recorded, the key is released for the request to be retried
+        request
+            .record_response(&state, HttpResponse::Conflict().finish())
+            .await;
+        let (_, result) = begin(&state, "key_1", 100).await;
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_requests_create_one_payment() {
+        let state = web::Data::new(get_state(3000).await);
+        let payments_created = Arc::new(AtomicUsize::new(0));
+        let request = get_request("key_1");
+        let payload = serde_json::json!({ "amount": 100 });
+
+        let create_payment = || {
+            let payments_created = payments_created.clone();
+            api::idempotent_server_wrap(
+                Flow::PaymentsCreate,
+                state.clone(),
+                &request,
+                payload.clone(),
+                move |_, _, _| {
+                    let payments_created = payments_created.clone();
+                    async move {
+                        let payment_number = payments_created.fetch_add(1, Ordering::SeqCst) + 1;
+                        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+                        Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(
+                            api::ApplicationResponse::Json(
+                                serde_json::json!({ "payment_id": format!("pay_{payment_number}") }),
+                            ),
+                        )
+                    }
+                },
+                &auth::NoAuth,
+                api_locking::LockAction::NotApplicable,
+            )
+        };
+
+        let (first, second) = futures::join!(create_payment(), create_payment());
+        assert_eq!(payments_created.load(Ordering::SeqCst), 1);
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(
+            [&first, &second]
+                .iter()
+                .filter(|response| response.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER))
+                .count(),
+            1
+        );
+
+        let first = body::to_bytes(first.into_body()).await.unwrap();
+        let second = body::to_bytes(second.into_body()).await.unwrap();
+        assert_eq!(first, Bytes::from(r#"{"payment_id":"pay_1"}"#));
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,1054 @@
+//! Masking of sensitive fields in connector request and response bodies before they are logged.
+//!
+//! Every connector registers the [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901)
+//! of the fields that must not be logged in [`get_sensitive_paths`]. A `*` segment in a pointer
+//! matches every element of an array or every member of an object. Form encoded bodies are
+//! flat, so their pointers have a single segment, which is the name of the form field.
+
+use std::str::FromStr;
+
+use api_models::enums as api_enums;
+
+/// Value logged in place of a sensitive field, or of a whole body which cannot be masked
+pub const MASKED_VALUE: &str = "***";
+
+/// Serializes a value to JSON with the values at the sensitive paths masked
+pub trait MaskedSerialize: serde::Serialize {
+    fn masked_serialize(&self, sensitive_paths: &[&str]) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        mask_value(&mut value, sensitive_paths);
+        serde_json::to_string(&value)
+    }
+}
+
+impl<T: serde::Serialize + ?Sized> MaskedSerialize for T {}
+
+/// Replaces the values at the given JSON pointers with [`MASKED_VALUE`]
+pub fn mask_value(value: &mut serde_json::Value, sensitive_paths: &[&str]) {
+    for path in sensitive_paths {
+        mask_segments(value, &get_pointer_segments(path));
+    }
+}
+
+/// Splits a JSON pointer into its unescaped segments
+pub(super) fn get_pointer_segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+fn mask_segments(value: &mut serde_json::Value, segments: &[String]) {
+    let (segment, remaining) = match segments.split_first() {
+        Some(split) => split,
+        None => {
+            if !value.is_null() {
+                *value = serde_json::Value::String(MASKED_VALUE.to_string());
+            }
+            return;
+        }
+    };
+
+    match value {
+        serde_json::Value::Object(map) if segment == "*" => map
+            .values_mut()
+            .for_each(|member| mask_segments(member, remaining)),
+        serde_json::Value::Object(map) => {
+            if let Some(member) = map.get_mut(segment) {
+                mask_segments(member, remaining);
+            }
+        }
+        serde_json::Value::Array(elements) if segment == "*" => elements
+            .iter_mut()
+            .for_each(|element| mask_segments(element, remaining)),
+        serde_json::Value::Array(elements) => {
+            if let Some(element) = segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| elements.get_mut(index))
+            {
+                mask_segments(element, remaining);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn mask_form_urlencoded(body: &[u8], sensitive_paths: &[&str]) -> Option<String> {
+    let fields = serde_urlencoded::from_bytes::<Vec<(String, String)>>(body)
+        .ok()?
+        .into_iter()
+        .map(|(name, value)| {
+            let is_sensitive = sensitive_paths.iter().any(|path| {
+                path.strip_prefix('/')
+                    .map(|field| field == "*" || field == name)
+                    .unwrap_or(false)
+            });
+            if is_sensitive {
+                (name, MASKED_VALUE.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect::<Vec<_>>();
+    serde_urlencoded::to_string(fields).ok()
+}
+
+/// Returns the body of a request sent to or a response received from the connector, with its
+/// sensitive fields masked.
+///
+/// The whole body is masked when the connector has not registered its sensitive paths, or when
+/// the body is neither JSON nor form encoded, as the sensitive fields cannot be located.
+pub fn get_masked_body(connector_name: &str, body: &[u8], is_form_urlencoded: bool) -> String {
+    let sensitive_paths = match api_enums::Connector::from_str(connector_name)
+        .ok()
+        .and_then(get_sensitive_paths)
+    {
+        Some(sensitive_paths) => sensitive_paths,
+        None => return MASKED_VALUE.to_string(),
+    };
+
+    if is_form_urlencoded {
+        return mask_form_urlencoded(body, sensitive_paths)
+            .unwrap_or_else(|| MASKED_VALUE.to_string());
+    }
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(mut value) => {
+            mask_value(&mut value, sensitive_paths);
+            value.to_string()
+        }
+        Err(_) => MASKED_VALUE.to_string(),
+    }
+}
+
+/// Returns at most `max_length` characters of the error response of the connector, to be stored
+/// with the error. The body is masked as in the logs, except for HTML error pages, which are sent
+/// by the gateways in front of the connector and do not contain the fields of the payment.
+pub fn get_body_excerpt(connector_name: &str, body: &[u8], max_length: usize) -> String {
+    let body_text = String::from_utf8_lossy(body);
+    let trimmed_body = body_text.trim_start().to_ascii_lowercase();
+    let body = if trimmed_body.starts_with("<!doctype html") || trimmed_body.starts_with("<html") {
+        body_text.into_owned()
+    } else {
+        get_masked_body(connector_name, body, false)
+    };
+    body.chars().take(max_length).collect()
+}
+
+/// Masks the card numbers appearing anywhere in the text, like in the messages of the connector
+/// or in the fields which the connector has not registered as sensitive. A card number is a run of
+/// 13 to 19 digits, which may be grouped with spaces or dashes, that passes the Luhn check.
+pub fn mask_card_numbers(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut masked = String::with_capacity(text.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        if !chars[index].is_ascii_digit() {
+            masked.push(chars[index]);
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        let mut digits = Vec::new();
+        while index < chars.len() {
+            match chars[index] {
+                digit if digit.is_ascii_digit() => digits.push(digit),
+                ' ' | '-'
+                    if chars
+                        .get(index + 1)
+                        .map(char::is_ascii_digit)
+                        .unwrap_or(false) => {}
+                _ => break,
+            }
+            index += 1;
+        }
+
+        if (13..=19).contains(&digits.len()) && is_luhn_valid(&digits) {
+            masked.push_str(MASKED_VALUE);
+        } else {
+            masked.extend(&chars[start..index]);
+        }
+    }
+
+    masked
+}
+
+fn is_luhn_valid(digits: &[char]) -> bool {
+    let sum = digits
+        .iter()
+        .rev()
+        .filter_map(|digit| digit.to_digit(10))
+        .enumerate()
+        .map(|(position, digit)| match position % 2 {
+            0 => digit,
+            _ if digit > 4 => digit * 2 - 9,
+            _ => digit * 2,
+        })
+        .sum::<u32>();
+    sum % 10 == 0
+}
+
+/// Returns the response of the connector to be captured from its body as it is logged, with the
+/// card numbers in it masked as well, truncated to at most `max_length` characters. The returned
+/// flag is set when the body was truncated.
+pub fn get_captured_body(masked_body: &str, max_length: usize) -> (String, bool) {
+    let masked_body = mask_card_numbers(masked_body);
+    match masked_body.char_indices().nth(max_length) {
+        Some((end, _)) => (masked_body[..end].to_string(), true),
+        None => (masked_body, false),
+    }
+}
+
+/// Paths of the fields in the request and response bodies of the connector that must not be
+/// logged. `None` is returned only for connectors which do not have an integration.
+pub fn get_sensitive_paths(connector: api_enums::Connector) -> Option<&'static [&'static str]> {
+    match connector {
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector1 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector2 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector3 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector4 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector5 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector6 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        #[cfg(feature = "dummy_connector")]
+        api_enums::Connector::DummyConnector7 => Some(DUMMYCONNECTOR_SENSITIVE_PATHS),
+        api_enums::Connector::Aci => Some(ACI_SENSITIVE_PATHS),
+        api_enums::Connector::Adyen => Some(ADYEN_SENSITIVE_PATHS),
+        api_enums::Connector::Airwallex => Some(AIRWALLEX_SENSITIVE_PATHS),
+        api_enums::Connector::Authorizedotnet => Some(AUTHORIZEDOTNET_SENSITIVE_PATHS),
+        api_enums::Connector::Bambora => Some(BAMBORA_SENSITIVE_PATHS),
+        api_enums::Connector::Bitpay => Some(BITPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Bluesnap => Some(BLUESNAP_SENSITIVE_PATHS),
+        api_enums::Connector::Boku => Some(BOKU_SENSITIVE_PATHS),
+        api_enums::Connector::Braintree => Some(BRAINTREE_SENSITIVE_PATHS),
+        api_enums::Connector::Cashtocode => Some(CASHTOCODE_SENSITIVE_PATHS),
+        api_enums::Connector::Checkout => Some(CHECKOUT_SENSITIVE_PATHS),
+        api_enums::Connector::Coinbase => Some(COINBASE_SENSITIVE_PATHS),
+        api_enums::Connector::Cryptopay => Some(CRYPTOPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Cybersource => Some(CYBERSOURCE_SENSITIVE_PATHS),
+        api_enums::Connector::Dlocal => Some(DLOCAL_SENSITIVE_PATHS),
+        api_enums::Connector::Fiserv => Some(FISERV_SENSITIVE_PATHS),
+        api_enums::Connector::Forte => Some(FORTE_SENSITIVE_PATHS),
+        api_enums::Connector::Globalpay => Some(GLOBALPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Globepay => Some(GLOBEPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Gocardless => Some(GOCARDLESS_SENSITIVE_PATHS),
+        api_enums::Connector::Iatapay => Some(IATAPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Klarna => Some(KLARNA_SENSITIVE_PATHS),
+        api_enums::Connector::Mollie => Some(MOLLIE_SENSITIVE_PATHS),
+        api_enums::Connector::Multisafepay => Some(MULTISAFEPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Nexinets => Some(NEXINETS_SENSITIVE_PATHS),
+        api_enums::Connector::Nmi => Some(NMI_SENSITIVE_PATHS),
+        api_enums::Connector::Noon => Some(NOON_SENSITIVE_PATHS),
+        api_enums::Connector::Nuvei => Some(NUVEI_SENSITIVE_PATHS),
+        api_enums::Connector::Opennode => Some(OPENNODE_SENSITIVE_PATHS),
+        api_enums::Connector::Payme => Some(PAYME_SENSITIVE_PATHS),
+        api_enums::Connector::Paypal => Some(PAYPAL_SENSITIVE_PATHS),
+        api_enums::Connector::Payu => Some(PAYU_SENSITIVE_PATHS),
+        api_enums::Connector::Powertranz => Some(POWERTRANZ_SENSITIVE_PATHS),
+        api_enums::Connector::Rapyd => Some(RAPYD_SENSITIVE_PATHS),
+        api_enums::Connector::Shift4 => Some(SHIFT4_SENSITIVE_PATHS),
+        api_enums::Connector::Square => Some(SQUARE_SENSITIVE_PATHS),
+        api_enums::Connector::Stax => Some(STAX_SENSITIVE_PATHS),
+        api_enums::Connector::Stripe => Some(STRIPE_SENSITIVE_PATHS),
+        api_enums::Connector::Trustpay => Some(TRUSTPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Tsys => Some(TSYS_SENSITIVE_PATHS),
+        api_enums::Connector::Wise => Some(WISE_SENSITIVE_PATHS),
+        api_enums::Connector::Worldline => Some(WORLDLINE_SENSITIVE_PATHS),
+        api_enums::Connector::Worldpay => Some(WORLDPAY_SENSITIVE_PATHS),
+        api_enums::Connector::Zen => Some(ZEN_SENSITIVE_PATHS),
+        api_enums::Connector::Signifyd | api_enums::Connector::Plaid => None,
+    }
+}
+
+const ACI_SENSITIVE_PATHS: &[&str] = &[
+    "/bankAccount.bic",
+    "/bankAccount.iban",
+    "/card.cvv",
+    "/card.expiryMonth",
+    "/card.expiryYear",
+    "/card.holder",
+    "/card.number",
+    "/customer.email",
+    "/customer.merchantCustomerId",
+    "/entityId",
+    "/merchantTransactionId",
+    "/virtualAccount.accountId",
+];
+
+const ADYEN_SENSITIVE_PATHS: &[&str] = &[
+    "/billingAddress/houseNumberOrName",
+    "/billingAddress/postalCode",
+    "/billingAddress/stateOrProvince",
+    "/billingAddress/street",
+    "/dateOfBirth",
+    "/deliveryAddress/houseNumberOrName",
+    "/deliveryAddress/postalCode",
+    "/deliveryAddress/stateOrProvince",
+    "/deliveryAddress/street",
+    "/merchantAccount",
+    "/paymentMethod/applePayToken",
+    "/paymentMethod/bankAccountNumber",
+    "/paymentMethod/bankLocationId",
+    "/paymentMethod/cvc",
+    "/paymentMethod/expiryMonth",
+    "/paymentMethod/expiryYear",
+    "/paymentMethod/firstName",
+    "/paymentMethod/googlePayToken",
+    "/paymentMethod/holderName",
+    "/paymentMethod/lastName",
+    "/paymentMethod/number",
+    "/paymentMethod/ownerName",
+    "/paymentMethod/samsungPayToken",
+    "/paymentMethod/sepa.ibanNumber",
+    "/paymentMethod/sepa.ownerName",
+    "/paymentMethod/shopperEmail",
+    "/paymentMethod/telephoneNumber",
+    "/shopperEmail",
+    "/shopperName/firstName",
+    "/shopperName/lastName",
+    "/socialSecurityNumber",
+    "/telephoneNumber",
+];
+
+const AIRWALLEX_SENSITIVE_PATHS: &[&str] = &[
+    "/client_secret",
+    "/payment_method/card/cvc",
+    "/payment_method/card/expiry_month",
+    "/payment_method/card/expiry_year",
+    "/payment_method/card/number",
+    "/payment_method/googlepay/encrypted_payment_token",
+    "/three_ds/acs_response",
+    "/token",
+];
+
+const AUTHORIZEDOTNET_SENSITIVE_PATHS: &[&str] = &[
+    "/createTransactionRequest/merchantAuthentication/name",
+    "/createTransactionRequest/merchantAuthentication/transactionKey",
+    "/createTransactionRequest/transactionRequest/payment/payPal/payerID",
+    "/getTransactionDetailsRequest/merchantAuthentication/name",
+    "/getTransactionDetailsRequest/merchantAuthentication/transactionKey",
+];
+
+const BAMBORA_SENSITIVE_PATHS: &[&str] = &[
+    "/card/cvd",
+    "/card/expiry_month",
+    "/card/expiry_year",
+    "/card/name",
+    "/card/number",
+    "/card_response/cres",
+];
+
+const BITPAY_SENSITIVE_PATHS: &[&str] = &["/token"];
+
+const BLUESNAP_SENSITIVE_PATHS: &[&str] = &[
+    "/cardHolderInfo/email",
+    "/cardHolderInfo/firstName",
+    "/cardHolderInfo/lastName",
+    "/ccNumber",
+    "/creditCard/cardNumber",
+    "/creditCard/expirationMonth",
+    "/creditCard/expirationYear",
+    "/creditCard/securityCode",
+    "/expDate",
+    "/validationUrl",
+    "/wallet/encodedPaymentToken",
+];
+
+const BOKU_SENSITIVE_PATHS: &[&str] = &[
+    "/BeginSingleCharge/merchant_id",
+    "/BeginSingleCharge/merchant_transaction_id",
+    "/merchant_id",
+    "/merchant_refund_id",
+    "/merchant_transaction_id",
+];
+
+const BRAINTREE_SENSITIVE_PATHS: &[&str] = &[
+    "/transaction/creditCard/cvv",
+    "/transaction/creditCard/expirationMonth",
+    "/transaction/creditCard/expirationYear",
+    "/transaction/creditCard/number",
+    "/transaction/merchantAccountId",
+];
+
+const CASHTOCODE_SENSITIVE_PATHS: &[&str] = &[
+    "/email",
+    "/firstName",
+    "/lastName",
+    "/mid",
+    "/userAlias",
+    "/userId",
+];
+
+const CHECKOUT_SENSITIVE_PATHS: &[&str] = &[
+    "/data",
+    "/header/ephemeralPublicKey",
+    "/header/publicKeyHash",
+    "/header/transactionId",
+    "/processing_channel_id",
+    "/protocolVersion",
+    "/signature",
+    "/signedMessage",
+    "/source/cryptogram",
+    "/source/cvv",
+    "/source/eci",
+    "/source/expiry_month",
+    "/source/expiry_year",
+    "/source/number",
+    "/source/token",
+    "/token",
+    "/version",
+];
+
+const COINBASE_SENSITIVE_PATHS: &[&str] = &["/name"];
+
+const CRYPTOPAY_SENSITIVE_PATHS: &[&str] = &[];
+
+const CYBERSOURCE_SENSITIVE_PATHS: &[&str] = &[
+    "/orderInformation/billTo/address1",
+    "/orderInformation/billTo/administrativeArea",
+    "/orderInformation/billTo/email",
+    "/orderInformation/billTo/firstName",
+    "/orderInformation/billTo/lastName",
+    "/orderInformation/billTo/phoneNumber",
+    "/orderInformation/billTo/postalCode",
+    "/paymentInformation/card/expirationMonth",
+    "/paymentInformation/card/expirationYear",
+    "/paymentInformation/card/number",
+    "/paymentInformation/card/securityCode",
+];
+
+const DLOCAL_SENSITIVE_PATHS: &[&str] = &[
+    "/card/cvv",
+    "/card/expiration_month",
+    "/card/expiration_year",
+    "/card/holder_name",
+    "/card/number",
+    "/payer/document",
+    "/payer/email",
+    "/payer/name",
+];
+
+#[cfg(feature = "dummy_connector")]
+const DUMMYCONNECTOR_SENSITIVE_PATHS: &[&str] = &[
+    "/payment_method_data/card/cvc",
+    "/payment_method_data/card/expiry_month",
+    "/payment_method_data/card/expiry_year",
+    "/payment_method_data/card/name",
+    "/payment_method_data/card/number",
+];
+
+const FISERV_SENSITIVE_PATHS: &[&str] = &[
+    "/merchantDetails/merchantId",
+    "/source/card/cardData",
+    "/source/card/expirationMonth",
+    "/source/card/expirationYear",
+    "/source/card/securityCode",
+];
+
+const FORTE_SENSITIVE_PATHS: &[&str] = &[
+    "/billing_address/first_name",
+    "/billing_address/last_name",
+    "/card/account_number",
+    "/card/card_verification_value",
+    "/card/expire_month",
+    "/card/expire_year",
+    "/card/name_on_card",
+];
+
+const GLOBALPAY_SENSITIVE_PATHS: &[&str] = &[
+    "/account_name",
+    "/app_id",
+    "/payment_method/card/cvv",
+    "/payment_method/card/expiry_month",
+    "/payment_method/card/expiry_year",
+    "/payment_method/card/number",
+    "/secret",
+    "/token",
+];
+
+const GLOBEPAY_SENSITIVE_PATHS: &[&str] = &[];
+
+const GOCARDLESS_SENSITIVE_PATHS: &[&str] = &[
+    "/customer_bank_accounts/account_holder_name",
+    "/customer_bank_accounts/account_number",
+    "/customer_bank_accounts/bank_code",
+    "/customer_bank_accounts/branch_code",
+    "/customer_bank_accounts/iban",
+    "/customer_bank_accounts/links/customer",
+    "/customers/address_line1",
+    "/customers/address_line2",
+    "/customers/address_line3",
+    "/customers/city",
+    "/customers/danish_identity_number",
+    "/customers/email",
+    "/customers/family_name",
+    "/customers/given_name",
+    "/customers/metadata/crm_id",
+    "/customers/postal_code",
+    "/customers/region",
+    "/customers/swedish_identity_number",
+    "/mandates/links/customer_bank_account",
+    "/mandates/payer_ip_address",
+    "/payments/links/mandate",
+];
+
+const IATAPAY_SENSITIVE_PATHS: &[&str] = &["/access_token", "/merchantId", "/payerInfo/tokenId"];
+
+const KLARNA_SENSITIVE_PATHS: &[&str] = &["/client_token"];
+
+const MOLLIE_SENSITIVE_PATHS: &[&str] = &[
+    "/billingAddress/postalCode",
+    "/billingAddress/region",
+    "/billingAddress/streetAndNumber",
+    "/billingEmail",
+    "/cardCvv",
+    "/cardExpiryDate",
+    "/cardHolder",
+    "/cardNumber",
+    "/cardToken",
+    "/consumerAccount",
+    "/consumerName",
+    "/issuer",
+    "/profileToken",
+    "/shippingAddress/postalCode",
+    "/shippingAddress/region",
+    "/shippingAddress/streetAndNumber",
+];
+
+const MULTISAFEPAY_SENSITIVE_PATHS: &[&str] = &[
+    "/customer/email",
+    "/delivery/address1",
+    "/delivery/first_name",
+    "/delivery/house_number",
+    "/delivery/last_name",
+    "/delivery/zip_code",
+    "/gateway_info/card_cvc",
+    "/gateway_info/card_holder_name",
+    "/gateway_info/card_number",
+    "/gateway_info/email",
+];
+
+const NEXINETS_SENSITIVE_PATHS: &[&str] = &[
+    "/payment/cardNumber",
+    "/payment/expiryMonth",
+    "/payment/expiryYear",
+    "/payment/verification",
+];
+
+const NMI_SENSITIVE_PATHS: &[&str] = &[
+    "/applepay_payment_data",
+    "/ccexp",
+    "/ccnumber",
+    "/cvv",
+    "/googlepay_payment_data",
+    "/security_key",
+];
+
+const NOON_SENSITIVE_PATHS: &[&str] = &[
+    "/paymentData/cvv",
+    "/paymentData/expiryMonth",
+    "/paymentData/expiryYear",
+    "/paymentData/nameOnCard",
+    "/paymentData/numberPlain",
+    "/paymentData/paymentInfo",
+];
+
+const NUVEI_SENSITIVE_PATHS: &[&str] = &[
+    "/billingAddress/email",
+    "/billingAddress/firstName",
+    "/billingAddress/lastName",
+    "/clientRequestId",
+    "/merchantId",
+    "/merchantSiteId",
+    "/paymentOption/billingAddress/email",
+    "/paymentOption/billingAddress/firstName",
+    "/paymentOption/billingAddress/lastName",
+    "/paymentOption/card/CVV",
+    "/paymentOption/card/cardHolderName",
+    "/paymentOption/card/cardNumber",
+    "/paymentOption/card/expirationMonth",
+    "/paymentOption/card/expirationYear",
+    "/paymentOption/card/externalToken/mobileToken",
+    "/paymentOption/card/threeD/browserDetails/ip",
+    "/userTokenId",
+];
+
+const OPENNODE_SENSITIVE_PATHS: &[&str] = &[];
+
+const PAYME_SENSITIVE_PATHS: &[&str] = &[
+    "/buyer_email",
+    "/buyer_key",
+    "/buyer_name",
+    "/credit_card_cvv",
+    "/credit_card_exp",
+    "/credit_card_number",
+    "/seller_payme_id",
+];
+
+const PAYPAL_SENSITIVE_PATHS: &[&str] = &[
+    "/access_token",
+    "/client_id",
+    "/client_secret",
+    "/payment_source/card/billing_address/address_line_1",
+    "/payment_source/card/billing_address/postal_code",
+    "/payment_source/card/expiry",
+    "/payment_source/card/name",
+    "/payment_source/card/number",
+    "/payment_source/card/security_code",
+    "/payment_source/eps/name",
+    "/payment_source/giropay/name",
+    "/payment_source/ideal/name",
+    "/payment_source/sofort/name",
+];
+
+const PAYU_SENSITIVE_PATHS: &[&str] = &[
+    "/access_token",
+    "/client_id",
+    "/client_secret",
+    "/merchantPosId",
+    "/payMethods/payMethod/cvv",
+    "/payMethods/payMethod/expirationMonth",
+    "/payMethods/payMethod/expirationYear",
+    "/payMethods/payMethod/number",
+];
+
+const POWERTRANZ_SENSITIVE_PATHS: &[&str] = &[
+    "/BillingAddress/EmailAddress",
+    "/BillingAddress/FirstName",
+    "/BillingAddress/LastName",
+    "/BillingAddress/Line1",
+    "/BillingAddress/Line2",
+    "/BillingAddress/PhoneNumber",
+    "/BillingAddress/PostalCode",
+    "/BillingAddress/State",
+    "/ShippingAddress/EmailAddress",
+    "/ShippingAddress/FirstName",
+    "/ShippingAddress/LastName",
+    "/ShippingAddress/Line1",
+    "/ShippingAddress/Line2",
+    "/ShippingAddress/PhoneNumber",
+    "/ShippingAddress/PostalCode",
+    "/ShippingAddress/State",
+    "/Source/CardCvv",
+    "/Source/CardExpiration",
+    "/Source/CardPan",
+    "/Source/CardholderName",
+];
+
+const RAPYD_SENSITIVE_PATHS: &[&str] = &[
+    "/payment_method/address/line_1",
+    "/payment_method/address/line_2",
+    "/payment_method/address/line_3",
+    "/payment_method/address/name",
+    "/payment_method/address/phone_number",
+    "/payment_method/address/state",
+    "/payment_method/fields/cvv",
+    "/payment_method/fields/expiration_month",
+    "/payment_method/fields/expiration_year",
+    "/payment_method/fields/name",
+    "/payment_method/fields/number",
+];
+
+const SHIFT4_SENSITIVE_PATHS: &[&str] = &[
+    "/card/cardholderName",
+    "/card/expMonth",
+    "/card/expYear",
+    "/card/number",
+    "/card[expMonth]",
+    "/card[expYear]",
+    "/card[number]",
+    "/paymentMethod/billing/address/line1",
+    "/paymentMethod/billing/address/line2",
+    "/paymentMethod/billing/address/state",
+    "/paymentMethod/billing/address/zip",
+    "/paymentMethod/billing/email",
+    "/paymentMethod/billing/name",
+];
+
+const SQUARE_SENSITIVE_PATHS: &[&str] = &[
+    "/card_data/cvv",
+    "/card_data/exp_month",
+    "/card_data/exp_year",
+    "/card_data/number",
+    "/client_id",
+    "/idempotency_key",
+    "/payment_id",
+    "/session_id",
+    "/source_id",
+];
+
+const STAX_SENSITIVE_PATHS: &[&str] = &[
+    "/bank_account",
+    "/bank_routing",
+    "/card_cvv",
+    "/card_exp",
+    "/card_number",
+    "/customer_id",
+    "/email",
+    "/payment_method_id",
+    "/person_name",
+];
+
+const STRIPE_SENSITIVE_PATHS: &[&str] = &[
+    "/card[cryptogram]",
+    "/card[eci]",
+    "/card[exp_month]",
+    "/card[exp_year]",
+    "/card[number]",
+    "/client_secret",
+    "/customer",
+    "/email",
+    "/mandate",
+    "/mandate_data[customer_acceptance][online][ip_address]",
+    "/name",
+    "/owner[email]",
+    "/payment_method_data[au_becs_debit][account_number]",
+    "/payment_method_data[au_becs_debit][bsb_number]",
+    "/payment_method_data[bacs_debit][account_number]",
+    "/payment_method_data[bacs_debit][sort_code]",
+    "/payment_method_data[billing_details][address][line1]",
+    "/payment_method_data[billing_details][address][line2]",
+    "/payment_method_data[billing_details][address][postal_code]",
+    "/payment_method_data[billing_details][email]",
+    "/payment_method_data[billing_details][name]",
+    "/payment_method_data[card][cvc]",
+    "/payment_method_data[card][exp_month]",
+    "/payment_method_data[card][exp_year]",
+    "/payment_method_data[card][number]",
+    "/payment_method_data[card][token]",
+    "/payment_method_data[sepa_debit][iban]",
+    "/payment_method_data[us_bank_account][account_number]",
+    "/payment_method_data[us_bank_account][routing_number]",
+    "/payment_method_options[card][three_d_secure][cryptogram]",
+    "/phone",
+    "/pk_token",
+    "/shipping[address][line1]",
+    "/shipping[address][line2]",
+    "/shipping[address][postal_code]",
+    "/shipping[address][state]",
+    "/shipping[name]",
+    "/shipping[phone]",
+];
+
+const TRUSTPAY_SENSITIVE_PATHS: &[&str] = &[
+    "/access_token",
+    "/billing[postcode]",
+    "/billing[street1]",
+    "/cardholder",
+    "/customer[email]",
+    "/customer[ipAddress]",
+    "/cvv",
+    "/exp",
+    "/merchantIdentification/ProjectId",
+    "/pan",
+    "/paymentInformation/Debtor/Email",
+    "/paymentInformation/Debtor/Name",
+];
+
+const TSYS_SENSITIVE_PATHS: &[&str] = &[
+    "/Auth/cardNumber",
+    "/Auth/cvv2",
+    "/Auth/developerID",
+    "/Auth/deviceID",
+    "/Auth/expirationDate",
+    "/Auth/transactionKey",
+    "/Capture/developerID",
+    "/Capture/deviceID",
+    "/Capture/transactionKey",
+    "/Return/deviceID",
+    "/Return/transactionKey",
+    "/Sale/cardNumber",
+    "/Sale/cvv2",
+    "/Sale/developerID",
+    "/Sale/deviceID",
+    "/Sale/expirationDate",
+    "/Sale/transactionKey",
+    "/SearchTransaction/developerID",
+    "/SearchTransaction/deviceID",
+    "/SearchTransaction/transactionKey",
+    "/Void/developerID",
+    "/Void/deviceID",
+    "/Void/transactionKey",
+];
+
+const WISE_SENSITIVE_PATHS: &[&str] = &[
+    "/accountHolderName",
+    "/details/abartn",
+    "/details/accountHolderName",
+    "/details/accountNumber",
+    "/details/address/firstLine",
+    "/details/address/postCode",
+    "/details/address/state",
+    "/details/bic",
+    "/details/email",
+    "/details/iban",
+    "/details/routingNumber",
+    "/details/sortCode",
+    "/details/swiftCode",
+    "/details/transitNumber",
+    "/profile",
+];
+
+const WORLDLINE_SENSITIVE_PATHS: &[&str] = &[
+    "/cardPaymentMethodSpecificInput/card/cardNumber",
+    "/cardPaymentMethodSpecificInput/card/cardholderName",
+    "/cardPaymentMethodSpecificInput/card/cvv",
+    "/cardPaymentMethodSpecificInput/card/expiryDate",
+    "/order/customer/billingAddress/houseNumber",
+    "/order/customer/billingAddress/state",
+    "/order/customer/billingAddress/stateCode",
+    "/order/customer/billingAddress/street",
+    "/order/customer/billingAddress/zip",
+    "/order/customer/contactDetails/emailAddress",
+    "/order/customer/contactDetails/mobilePhoneNumber",
+    "/redirectPaymentMethodSpecificInput/paymentProduct816SpecificInput/bankAccountIban/accountHolderName",
+    "/redirectPaymentMethodSpecificInput/paymentProduct816SpecificInput/bankAccountIban/iban",
+    "/shipping/name/firstName",
+    "/shipping/name/surname",
+    "/shipping/name/surnamePrefix",
+    "/shipping/name/title",
+    "/shipping/state",
+    "/shipping/zip",
+];
+
+const WORLDPAY_SENSITIVE_PATHS: &[&str] = &[
+    "/instruction/paymentInstrument/cardExpiryDate/month",
+    "/instruction/paymentInstrument/cardExpiryDate/year",
+    "/instruction/paymentInstrument/cardHolderName",
+    "/instruction/paymentInstrument/cardNumber",
+    "/instruction/paymentInstrument/cvc",
+];
+
+const ZEN_SENSITIVE_PATHS: &[&str] = &[
+    "/customer/email",
+    "/customer/ip",
+    "/paymentSpecificData/card/cvv",
+    "/paymentSpecificData/card/expiryDate",
+    "/paymentSpecificData/card/number",
+    "/paymentSpecificData/token",
+    "/signature",
+    "/terminalUuid",
+];
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::consts;
+
+    const CARD_NUMBER: &str = "4111111111111111";
+    const CARD_CVC: &str = "737";
+    const SHOPPER_EMAIL: &str = "john.doe@example.com";
+
+    fn assert_fully_masked(masked_body: &str, sensitive_values: &[&str]) {
+        for sensitive_value in sensitive_values {
+            assert!(
+                !masked_body.contains(sensitive_value),
+                "{sensitive_value} was not masked in {masked_body}"
+            );
+        }
+        assert!(masked_body.contains(MASKED_VALUE));
+    }
+
+    #[test]
+    fn test_card_numbers_are_masked_in_captured_bodies() {
+        let body = serde_json::json!({
+            "id": "8835511210681341",
+            "status": "Refused",
+            "refusalReason": "Card 4111 1111 1111 1111 is blocked, card 5555-5555-5555-4444 too",
+            "additionalData": { "cardSummary": "1111", "unregisteredField": CARD_NUMBER },
+            "created": 1699999999999_i64,
+        })
+        .to_string();
+
+        let masked_body = get_masked_body("adyen", body.as_bytes(), false);
+
+        let (captured_body, is_truncated) =
+            get_captured_body(&masked_body, consts::CONNECTOR_RESPONSE_CAPTURE_MAX_LENGTH);
+        assert!(!is_truncated);
+        assert_fully_masked(
+            &captured_body,
+            &[CARD_NUMBER, "4111 1111 1111 1111", "5555-5555-5555-4444"],
+        );
+        // The numbers which are not card numbers are kept
+        assert!(captured_body.contains("1699999999999"));
+        assert!(captured_body.contains("\"cardSummary\":\"1111\""));
+
+        let (captured_body, is_truncated) = get_captured_body(&masked_body, 16);
+        assert!(is_truncated);
+        assert_eq!(captured_body.chars().count(), 16);
+    }
+
+    #[test]
+    fn test_every_connector_registers_sensitive_paths() {
+        for connector in api_enums::Connector::iter() {
+            if matches!(
+                connector,
+                api_enums::Connector::Signifyd | api_enums::Connector::Plaid
+            ) {
+                continue;
+            }
+            let sensitive_paths = get_sensitive_paths(connector);
+            assert!(
+                sensitive_paths.is_some(),
+                "{connector} has not registered its sensitive paths"
+            );
+            for path in sensitive_paths.unwrap_or_default() {
+                assert!(
+                    path.starts_with('/') && !path.contains("//"),
+                    "{path} of {connector} is not a valid JSON pointer"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_adyen_payment_request_is_masked() {
+        let body = serde_json::json!({
+            "amount": { "currency": "EUR", "value": 1000 },
+            "merchantAccount": "HyperswitchECOM",
+            "paymentMethod": {
+                "type": "scheme",
+                "number": CARD_NUMBER,
+                "expiryMonth": "03",
+                "expiryYear": "2030",
+                "cvc": CARD_CVC,
+                "holderName": "John Doe"
+            },
+            "reference": "pay_12345",
+            "shopperEmail": SHOPPER_EMAIL,
+            "telephoneNumber": "9123456789"
+        });
+
+        let masked_body = get_masked_body("adyen", body.to_string().as_bytes(), false);
+
+        assert_fully_masked(
+            &masked_body,
+            &[
+                CARD_NUMBER,
+                CARD_CVC,
+                SHOPPER_EMAIL,
+                "HyperswitchECOM",
+                "John Doe",
+                "2030",
+                "9123456789",
+            ],
+        );
+        let masked_value: serde_json::Value = serde_json::from_str(&masked_body).unwrap();
+        assert_eq!(masked_value["reference"], "pay_12345");
+        assert_eq!(masked_value["paymentMethod"]["type"], "scheme");
+    }
+
+    #[test]
+    fn test_stripe_form_urlencoded_request_is_masked() {
+        let body = serde_urlencoded::to_string([
+            ("amount", "1000"),
+            ("currency", "usd"),
+            ("payment_method_data[card][number]", CARD_NUMBER),
+            ("payment_method_data[card][exp_month]", "03"),
+            ("payment_method_data[card][exp_year]", "2030"),
+            ("payment_method_data[card][cvc]", CARD_CVC),
+            ("payment_method_data[billing_details][email]", SHOPPER_EMAIL),
+            ("shipping[name]", "John Doe"),
+        ])
+        .unwrap();
+
+        let masked_body = get_masked_body("stripe", body.as_bytes(), true);
+
+        assert_fully_masked(
+            &masked_body,
+            &[CARD_NUMBER, CARD_CVC, SHOPPER_EMAIL, "2030", "John+Doe"],
+        );
+        assert!(masked_body.contains("currency=usd"));
+    }
+
+    #[test]
+    fn test_checkout_payment_request_is_masked() {
+        let body = serde_json::json!({
+            "source": {
+                "type": "card",
+                "number": CARD_NUMBER,
+                "expiry_month": "03",
+                "expiry_year": "2030",
+                "cvv": CARD_CVC
+            },
+            "amount": 1000,
+            "currency": "USD",
+            "processing_channel_id": "pc_abcdefgh",
+            "reference": "pay_12345"
+        });
+
+        let masked_body = get_masked_body("checkout", body.to_string().as_bytes(), false);
+
+        assert_fully_masked(
+            &masked_body,
+            &[CARD_NUMBER, CARD_CVC, "2030", "pc_abcdefgh"],
+        );
+        let masked_value: serde_json::Value = serde_json::from_str(&masked_body).unwrap();
+        assert_eq!(masked_value["source"]["type"], "card");
+        assert_eq!(masked_value["amount"], 1000);
+    }
+
+    #[test]
+    fn test_unmaskable_bodies_are_masked_entirely() {
+        let xml_body = format!("<card><number>{CARD_NUMBER}</number></card>");
+        assert_eq!(
+            get_masked_body("adyen", xml_body.as_bytes(), false),
+            MASKED_VALUE
+        );
+
+        let json_body = serde_json::json!({ "number": CARD_NUMBER }).to_string();
+        assert_eq!(
+            get_masked_body("unknown_connector", json_body.as_bytes(), false),
+            MASKED_VALUE
+        );
+    }
+
+    #[test]
+    fn test_body_excerpt_keeps_only_html_error_pages_unmasked() {
+        let error_page = format!(
+            "<html><head><title>502 Bad Gateway</title></head><body>{}</body></html>",
+            "nginx ".repeat(100)
+        );
+        let excerpt = get_body_excerpt("adyen", error_page.as_bytes(), 32);
+        assert_eq!(excerpt, "<html><head><title>502 Bad Gatew");
+
+        let json_body = serde_json::json!({ "cardNumber": CARD_NUMBER }).to_string();
+        assert_fully_masked(
+            &get_body_excerpt("unknown_connector", json_body.as_bytes(), 256),
+            &[CARD_NUMBER],
+        );
+
+        let xml_body = format!("<card><number>{CARD_NUMBER}</number></card>");
+        assert_eq!(
+            get_body_excerpt("adyen", xml_body.as_bytes(), 256),
+            MASKED_VALUE
+        );
+    }
+
+    #[test]
+    fn test_masked_serialize_with_wildcard_path() {
+        let body = serde_json::json!({
+            "cards": [
+                { "number": CARD_NUMBER, "brand": "visa" },
+                { "number": "5555555555554444", "brand": "mastercard" }
+            ]
+        });
+
+        let masked_body = body.masked_serialize(&["/cards/*/number"]).unwrap();
+
+        assert_fully_masked(&masked_body, &[CARD_NUMBER, "5555555555554444"]);
+        assert!(masked_body.contains("mastercard"));
+    }
+}
@@ -0,0 +1,233 @@
+//! Redaction of the personal data of the customers from the API responses and the outgoing
+//! webhooks, as configured in the PII policy of the merchant account or the business profile.
+//!
+//! The bodies are redacted as JSON values before they are encoded, the fields of the policy being
+//! located by JSON pointers as in [`masked_logging`]. A masked field keeps its place in the body
+//! with its value replaced by [`masked_logging::MASKED_VALUE`], while a removed field is taken
+//! out of its object. The responses are redacted only for the API keys marked as
+//! `pii_restricted`, so that the dashboards and the other keys with full access still see the
+//! data.
+
+use api_models::admin::{PiiPolicy, PiiRedaction};
+use common_utils::ext_traits::ValueExt;
+use router_env::logger;
+
+use super::masked_logging;
+
+/// Parses the PII policy stored on the merchant account or the business profile. A policy which
+/// cannot be parsed is logged and ignored.
+pub fn get_pii_policy(pii_policy: Option<&serde_json::Value>) -> Option<PiiPolicy> {
+    pii_policy.and_then(|pii_policy| {
+        pii_policy
+            .clone()
+            .parse_value::<PiiPolicy>("PiiPolicy")
+            .map_err(|error| logger::error!(?error, "Failed to parse the stored PII policy"))
+            .ok()
+    })
+}
+
+/// The redaction of the responses to a request, `None` unless the request was made with an API
+/// key marked as `pii_restricted`
+pub fn get_api_response_redaction(
+    merchant_pii_policy: Option<&serde_json::Value>,
+    api_key_pii_restricted: Option<bool>,
+) -> Option<PiiRedaction> {
+    if api_key_pii_restricted != Some(true) {
+        return None;
+    }
+    get_pii_policy(merchant_pii_policy).and_then(|pii_policy| pii_policy.api_response)
+}
+
+/// The redaction of the outgoing webhooks, the policy of the business profile of the object of the
+/// webhook taking precedence over the one of the merchant account
+pub fn get_outgoing_webhook_redaction(
+    merchant_pii_policy: Option<&serde_json::Value>,
+    profile_pii_policy: Option<&serde_json::Value>,
+) -> Option<PiiRedaction> {
+    get_pii_policy(profile_pii_policy)
+        .or_else(|| get_pii_policy(merchant_pii_policy))
+        .and_then(|pii_policy| pii_policy.outgoing_webhook)
+}
+
+/// Encodes the body to JSON, with the fields of the redaction masked or removed. The body is
+/// encoded as is when there is nothing to redact.
+pub fn redact_and_encode<T: serde::Serialize + ?Sized>(
+    body: &T,
+    redaction: Option<&PiiRedaction>,
+) -> Result<String, serde_json::Error> {
+    match redaction {
+        Some(redaction) => {
+            let mut value = serde_json::to_value(body)?;
+            redact_value(&mut value, redaction);
+            serde_json::to_string(&value)
+        }
+        None => serde_json::to_string(body),
+    }
+}
+
+/// Masks the masked fields of the redaction and removes its removed fields from the value
+pub fn redact_value(value: &mut serde_json::Value, redaction: &PiiRedaction) {
+    let masked_fields = redaction
+        .masked_fields
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    masked_logging::mask_value(value, &masked_fields);
+
+    for field in &redaction.removed_fields {
+        remove_segments(value, &masked_logging::get_pointer_segments(field));
+    }
+}
+
+fn remove_segments(value: &mut serde_json::Value, segments: &[String]) {
+    let Some((segment, remaining)) = segments.split_first() else {
+        return;
+    };
+
+    match value {
+        serde_json::Value::Object(map) if remaining.is_empty() => {
+            if segment == "*" {
+                map.clear();
+            } else {
+                map.remove(segment);
+            }
+        }
+        serde_json::Value::Object(map) if segment == "*" => map
+            .values_mut()
+            .for_each(|member| remove_segments(member, remaining)),
+        serde_json::Value::Object(map) => {
+            if let Some(member) = map.get_mut(segment) {
+                remove_segments(member, remaining);
+            }
+        }
+        // The elements of an array are not removed, as the elements after them would move to
+        // their indices
+        serde_json::Value::Array(_) if remaining.is_empty() => (),
+        serde_json::Value::Array(elements) if segment == "*" => elements
+            .iter_mut()
+            .for_each(|element| remove_segments(element, remaining)),
+        serde_json::Value::Array(elements) => {
+            if let Some(element) = segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| elements.get_mut(index))
+            {
+                remove_segments(element, remaining);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn get_payments_response() -> api_models::payments::PaymentsResponse {
+        api_models::payments::PaymentsResponse {
+            payment_id: Some("pay_123".to_string()),
+            amount: 6540,
+            email: Some(common_utils::crypto::Encryptable::new(
+                masking::Secret::new("guest@example.com".to_string()),
+                masking::Secret::new(Vec::new()),
+            )),
+            phone: Some(common_utils::crypto::Encryptable::new(
+                masking::Secret::new("9123456789".to_string()),
+                masking::Secret::new(Vec::new()),
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn get_merchant_pii_policy() -> serde_json::Value {
+        serde_json::json!({
+            "api_response": {
+                "masked_fields": ["/email"],
+                "removed_fields": ["/phone"],
+            },
+            "outgoing_webhook": {
+                "removed_fields": ["/content/object/phone"],
+            },
+        })
+    }
+
+    #[test]
+    fn test_restricted_key_sees_masked_email() {
+        let merchant_pii_policy = get_merchant_pii_policy();
+        let redaction = get_api_response_redaction(Some(&merchant_pii_policy), Some(true));
+
+        let body = redact_and_encode(&get_payments_response(), redaction.as_ref()).unwrap();
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+
+        assert_eq!(body["email"], masked_logging::MASKED_VALUE);
+        assert!(body.get("phone").is_none());
+        assert_eq!(body["payment_id"], "pay_123");
+        assert_eq!(body["amount"], 6540);
+    }
+
+    #[test]
+    fn test_full_access_keys_are_not_redacted() {
+        let merchant_pii_policy = get_merchant_pii_policy();
+        let response = get_payments_response();
+
+        for api_key_pii_restricted in [Some(false), None] {
+            let redaction =
+                get_api_response_redaction(Some(&merchant_pii_policy), api_key_pii_restricted);
+            assert!(redaction.is_none());
+            assert_eq!(
+                redact_and_encode(&response, redaction.as_ref()).unwrap(),
+                serde_json::to_string(&response).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_profile_policy_takes_precedence_for_webhooks() {
+        let merchant_pii_policy = get_merchant_pii_policy();
+        let profile_pii_policy = serde_json::json!({
+            "outgoing_webhook": {
+                "masked_fields": ["/content/object/email"],
+            },
+        });
+
+        let redaction =
+            get_outgoing_webhook_redaction(Some(&merchant_pii_policy), Some(&profile_pii_policy))
+                .unwrap();
+        assert_eq!(redaction.masked_fields, vec!["/content/object/email"]);
+        assert!(redaction.removed_fields.is_empty());
+
+        let redaction = get_outgoing_webhook_redaction(Some(&merchant_pii_policy), None).unwrap();
+        assert_eq!(redaction.removed_fields, vec!["/content/object/phone"]);
+    }
+
+    #[test]
+    fn test_redaction_is_structural() {
+        let mut value = serde_json::json!({
+            "phone": "9123456789",
+            "description": "Call 9123456789",
+            "attempts": [
+                { "phone": "9123456789", "email": "guest@example.com" },
+                { "phone": null },
+            ],
+        });
+        let redaction = PiiRedaction {
+            masked_fields: vec!["/attempts/*/email".to_string()],
+            removed_fields: vec!["/phone".to_string(), "/attempts/*/phone".to_string()],
+        };
+
+        redact_value(&mut value, &redaction);
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "description": "Call 9123456789",
+                "attempts": [
+                    { "email": masked_logging::MASKED_VALUE },
+                    {},
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Polling of the operations which a connector accepted without completing them, such as the
+//! operations answered with `202 Accepted` and the URL of their status. The connector returns a
+//! `PollConfig` in the router data of the operation, and the status is polled at the interval of
+//! the configuration until the connector reports the operation complete.
+//!
+//! The polling is bounded by the attempts of the configuration. An operation which is not complete
+//! within them is left pending, and is resolved later by a sync of its status, the same as any other
+//! pending operation.
+
+use std::fmt::Debug;
+
+use router_env::{instrument, logger, tracing};
+
+use super::{call_connector_api, connector_proxy, BoxedConnectorIntegration};
+use crate::{
+    routes::{metrics, AppState},
+    types::{self, PollConfig, PollUrlSource},
+};
+
+/// Reads the URL of the configuration from the headers of the response, when the connector returns
+/// it in a header. The configuration is dropped if the header is missing, which leaves the operation
+/// to be synced later.
+pub fn resolve_poll_url(
+    poll_config: PollConfig,
+    headers: Option<&http::HeaderMap>,
+) -> Option<PollConfig> {
+    match &poll_config.url_source {
+        PollUrlSource::Url(_) => Some(poll_config),
+        PollUrlSource::Header(header_name) => {
+            let url = headers
+                .and_then(|headers| headers.get(header_name.as_str()))
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string);
+            match url {
+                Some(url) => Some(PollConfig {
+                    url_source: PollUrlSource::Url(url),
+                    ..poll_config
+                }),
+                None => {
+                    logger::warn!(
+                        %header_name,
+                        "Poll URL header missing in the connector response, the operation is left to be synced"
+                    );
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Polls the status of the operation until the connector reports it complete, or until the attempts
+/// of the `PollConfig` are exhausted. The `PollConfig` is kept in the returned data when the
+/// operation is not complete, in which case the operation is left to be synced later. Failures of
+/// the polls do not fail the operation, which the connector has already accepted, they only stop
+/// the polling.
+#[instrument(skip_all)]
+pub async fn execute_connector_polling<T, Req, Resp>(
+    state: &AppState,
+    connector_integration: &BoxedConnectorIntegration<'_, T, Req, Resp>,
+    mut router_data: types::RouterData<T, Req, Resp>,
+) -> types::RouterData<T, Req, Resp>
+where
+    T: Clone + Debug + 'static,
+    Req: Debug + Clone + 'static,
+    Resp: Debug + Clone + 'static,
+{
+    let Some(poll_config) = router_data.poll_config.clone() else {
+        return router_data;
+    };
+    let PollUrlSource::Url(url) = &poll_config.url_source else {
+        logger::warn!(
+            ?poll_config,
+            "Poll URL not resolved, the operation is left to be synced"
+        );
+        return router_data;
+    };
+
+    for attempt in 1..=poll_config.max_attempts {
+        tokio::time::sleep(poll_config.interval).await;
+        metrics::CONNECTOR_POLL_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes(
+                "connector",
+                router_data.connector.clone(),
+            )],
+        );
+
+        let mut request = match connector_integration.build_poll_request(
+            &router_data,
+            url,
+            &state.conf.connectors,
+        ) {
+            Ok(Some(request)) => request,
+            Ok(None) => break,
+            Err(error) => {
+                logger::error!(?error, "Failed to build the poll request");
+                break;
+            }
+        };
+        request.add_proxy(connector_proxy::get_connector_proxy(
+            &state.conf.connector_proxies,
+            &router_data.connector,
+            router_data.connector_proxy.as_ref(),
+            &request.url,
+        ));
+
+        router_data = match call_connector_api(state, request).await {
+            Ok(Ok(body)) => match connector_integration.handle_poll_response(&router_data, body) {
+                Ok(mut data) => {
+                    data.connector_http_status_code = router_data.connector_http_status_code;
+                    data
+                }
+                Err(error) => {
+                    logger::error!(?error, "Failed to handle the poll response");
+                    break;
+                }
+            },
+            Ok(Err(body)) => {
+                logger::warn!(
+                    status_code = body.status_code,
+                    "Error response received for the poll of the operation"
+                );
+                break;
+            }
+            Err(error) => {
+                logger::warn!(?error, "Failed to poll the operation");
+                break;
+            }
+        };
+
+        if router_data.poll_config.is_none() {
+            logger::info!(attempt, "Operation completed while polling the connector");
+            return router_data;
+        }
+    }
+
+    metrics::CONNECTOR_POLLING_HANDED_OFF_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::request::add_attributes(
+            "connector",
+            router_data.connector.clone(),
+        )],
+    );
+    logger::info!(
+        status = ?router_data.status,
+        "Operation not completed while polling the connector, it is left to be synced"
+    );
+    router_data
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::time::Duration;
+
+    use super::*;
+
+    fn get_poll_config(url_source: PollUrlSource) -> PollConfig {
+        PollConfig {
+            url_source,
+            interval: Duration::from_millis(500),
+            max_attempts: 4,
+        }
+    }
+
+    #[test]
+    fn test_poll_url_is_read_from_the_header() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            "operation-location",
+            http::HeaderValue::from_static("https://connector.test/operations/1"),
+        );
+        let poll_config = resolve_poll_url(
+            get_poll_config(PollUrlSource::Header("Operation-Location".to_string())),
+            Some(&headers),
+        )
+        .unwrap();
+        assert_eq!(
+            poll_config.url_source,
+            PollUrlSource::Url("https://connector.test/operations/1".to_string())
+        );
+        assert_eq!(poll_config.max_attempts, 4);
+        assert_eq!(poll_config.get_polling_budget(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_poll_config_is_dropped_without_the_header() {
+        let poll_config = get_poll_config(PollUrlSource::Header("Location".to_string()));
+        assert!(resolve_poll_url(poll_config.clone(), Some(&http::HeaderMap::new())).is_none());
+        assert!(resolve_poll_url(poll_config, None).is_none());
+    }
+
+    #[test]
+    fn test_poll_url_from_the_body_is_kept() {
+        let poll_config = get_poll_config(PollUrlSource::Url("/operations/1".to_string()));
+        assert_eq!(
+            resolve_poll_url(poll_config.clone(), None),
+            Some(poll_config)
+        );
+    }
+}
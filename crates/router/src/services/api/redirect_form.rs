@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use error_stack::{IntoReport, ResultExt};
+
+use super::{Method, RedirectForm};
+use crate::core::errors::{self, CustomResult};
+
+/// The fields posted to the ACS in a 3DS1 challenge
+#[derive(Debug, Clone)]
+pub struct ThreeDsOneFields {
+    /// The merchant data, echoed back by the ACS along with the `PaRes`
+    pub md: String,
+    /// The payer authentication request
+    pub pa_req: String,
+    /// The url to which the ACS posts the result of the challenge
+    pub term_url: String,
+}
+
+/// Builds the form which redirects the customer's browser to the connector, either as the
+/// structured representation consumed by the SDKs or as an auto submitting HTML page
+#[derive(Debug, Clone)]
+pub struct RedirectFormBuilder {
+    endpoint: url::Url,
+    method: Method,
+    form_fields: HashMap<String, String>,
+    target: Option<String>,
+}
+
+impl RedirectFormBuilder {
+    /// Only `http` and `https` urls are accepted as the endpoint of the form
+    pub fn new(endpoint: &str, method: Method) -> CustomResult<Self, errors::ConnectorError> {
+        let endpoint = url::Url::parse(endpoint)
+            .into_report()
+            .change_context(errors::ConnectorError::InvalidDataFormat {
+                field_name: "redirect_url",
+            })
+            .attach_printable("Failed to parse the redirect url")?;
+
+        if !matches!(endpoint.scheme(), "http" | "https") {
+            Err(errors::ConnectorError::InvalidDataFormat {
+                field_name: "redirect_url",
+            })
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "Unsupported scheme in the redirect url: {}",
+                    endpoint.scheme()
+                )
+            })?
+        }
+
+        Ok(Self {
+            endpoint,
+            method,
+            form_fields: HashMap::new(),
+            target: None,
+        })
+    }
+
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.form_fields.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn fields(mut self, fields: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.form_fields.extend(fields);
+        self
+    }
+
+    /// Adds the standard `MD`, `PaReq` and `TermUrl` fields of a 3DS1 challenge
+    pub fn three_ds_one_fields(self, three_ds_fields: ThreeDsOneFields) -> Self {
+        self.field("MD", three_ds_fields.md)
+            .field("PaReq", three_ds_fields.pa_req)
+            .field("TermUrl", three_ds_fields.term_url)
+    }
+
+    /// The browsing context in which the form is submitted, e.g. `_top` to break out of an iframe.
+    /// This is only used by the HTML page
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn build(self) -> RedirectForm {
+        RedirectForm::Form {
+            endpoint: self.endpoint.to_string(),
+            method: self.method,
+            form_fields: self.form_fields,
+        }
+    }
+
+    /// Builds an HTML page which submits the form as soon as it is loaded. The endpoint and the
+    /// fields are HTML escaped.
+    pub fn build_html(self) -> RedirectForm {
+        let mut form_fields = self.form_fields.into_iter().collect::<Vec<_>>();
+        // Keep the generated page stable across calls
+        form_fields.sort();
+
+        let html = maud::html! {
+            (maud::DOCTYPE)
+            html {
+                body {
+                    form action=(self.endpoint.as_str()) method=(self.method.to_string()) target=[self.target] #redirect_form {
+                        @for (name, value) in &form_fields {
+                            input type="hidden" name=(name) value=(value);
+                        }
+                    }
+                    script { (maud::PreEscaped(r#"document.getElementById("redirect_form").submit();"#)) }
+                }
+            }
+        };
+
+        RedirectForm::Html {
+            html_data: html.into_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_field_values_are_html_escaped() {
+        let form = RedirectFormBuilder::new("https://acs.example.com/challenge", Method::Post)
+            .unwrap()
+            .field("MD", r#""><script>alert(1)</script>"#)
+            .build_html();
+
+        let RedirectForm::Html { html_data } = form else {
+            panic!("expected an html redirect form")
+        };
+        assert!(!html_data.contains("<script>alert(1)</script>"));
+        assert!(html_data.contains("&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+
+    #[test]
+    fn test_non_http_url_is_rejected() {
+        assert!(RedirectFormBuilder::new("javascript:alert(1)", Method::Post).is_err());
+        assert!(RedirectFormBuilder::new("ftp://acs.example.com", Method::Post).is_err());
+        assert!(RedirectFormBuilder::new("not a url", Method::Post).is_err());
+    }
+
+    #[test]
+    fn test_three_ds_one_fields_are_merged_into_the_form() {
+        let form = RedirectFormBuilder::new("https://acs.example.com/challenge", Method::Post)
+            .unwrap()
+            .field("merchant_field", "value")
+            .three_ds_one_fields(ThreeDsOneFields {
+                md: "merchant_data".to_string(),
+                pa_req: "pa_req".to_string(),
+                term_url: "https://merchant.example.com/complete".to_string(),
+            })
+            .build();
+
+        let RedirectForm::Form {
+            endpoint,
+            method,
+            form_fields,
+        } = form
+        else {
+            panic!("expected a structured redirect form")
+        };
+        assert_eq!(endpoint, "https://acs.example.com/challenge");
+        assert_eq!(method, Method::Post);
+        assert_eq!(form_fields.len(), 4);
+        assert_eq!(form_fields.get("MD").unwrap(), "merchant_data");
+        assert_eq!(form_fields.get("PaReq").unwrap(), "pa_req");
+        assert_eq!(
+            form_fields.get("TermUrl").unwrap(),
+            "https://merchant.example.com/complete"
+        );
+    }
+
+    #[test]
+    fn test_target_is_set_on_the_html_form() {
+        let form = RedirectFormBuilder::new("https://acs.example.com/challenge", Method::Get)
+            .unwrap()
+            .target("_top")
+            .build_html();
+
+        let RedirectForm::Html { html_data } = form else {
+            panic!("expected an html redirect form")
+        };
+        assert!(html_data.contains(r#"target="_top""#));
+        assert!(html_data.contains(r#"method="GET""#));
+    }
+}
@@ -0,0 +1,627 @@
+//! Detection of the fields of a JSON request body which are not part of the request type, such as
+//! misspelled fields which would otherwise be silently ignored. The body is deserialized through a
+//! wrapper of the JSON value which compares the keys of every object deserialized into a struct
+//! with the fields of the struct, so that the request types do not need to be duplicated. Free form
+//! fields such as `metadata` are deserialized as JSON values, and their keys are never reported.
+//!
+//! The unknown fields are rejected for the API keys with strict request validation, for the others
+//! they are only counted in a metric, so that the drift of the integrations can be monitored.
+
+use std::{cell::RefCell, collections::HashSet, sync::Mutex};
+
+use actix_web::{dev::Payload, error::JsonPayloadError, web, FromRequest, HttpRequest};
+use error_stack::report;
+use futures::future::LocalBoxFuture;
+use once_cell::sync::Lazy;
+use router_env::logger;
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Deserialize,
+};
+use serde_json::{Map, Value};
+
+use crate::{
+    core::errors::{self, RouterResult},
+    routes::metrics,
+    utils::error_parser,
+};
+
+/// The maximum number of distinct fields labelled in the metric. The names of the unknown fields
+/// are chosen by the clients, the fields seen once the limit is reached are counted together under
+/// [`OTHER_FIELD_LABEL`]
+const MAX_FIELD_LABELS: usize = 500;
+/// The longest field path labelled in the metric, the longer ones are counted under
+/// [`OTHER_FIELD_LABEL`]
+const MAX_FIELD_LABEL_LENGTH: usize = 128;
+const OTHER_FIELD_LABEL: &str = "other";
+
+static FIELD_LABELS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// The paths of the unknown fields found while deserializing a value, such as
+/// `shipping.address.lin1` or `order_details[0].product_nme`
+#[derive(Debug, Default)]
+struct UnknownFieldTracker {
+    strip: bool,
+    paths: RefCell<Vec<String>>,
+}
+
+/// Deserializes the value, returning it with the paths of its unknown fields. When `strip` is set,
+/// the unknown fields are removed before the structs are deserialized, so that the structs which
+/// deny unknown fields do not fail on the first of them.
+pub fn from_value_with_unknown_fields<T: DeserializeOwned>(
+    value: Value,
+    strip: bool,
+) -> Result<(T, Vec<String>), serde_json::Error> {
+    let tracker = UnknownFieldTracker {
+        strip,
+        ..Default::default()
+    };
+    let deserialized = T::deserialize(TrackedValue {
+        value,
+        path: String::new(),
+        tracker: &tracker,
+    })?;
+    Ok((deserialized, tracker.paths.into_inner()))
+}
+
+struct TrackedValue<'a> {
+    value: Value,
+    path: String,
+    tracker: &'a UnknownFieldTracker,
+}
+
+impl<'a> TrackedValue<'a> {
+    fn child(&self, value: Value, key: &str) -> Self {
+        Self {
+            value,
+            path: get_child_path(&self.path, key),
+            tracker: self.tracker,
+        }
+    }
+
+    fn element(&self, value: Value, index: usize) -> Self {
+        Self {
+            value,
+            path: format!("{}[{index}]", self.path),
+            tracker: self.tracker,
+        }
+    }
+
+    fn track_unknown_fields(&mut self, fields: &'static [&'static str]) {
+        let Value::Object(object) = &mut self.value else {
+            return;
+        };
+        let unknown_keys = object
+            .keys()
+            .filter(|key| !fields.contains(&key.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in unknown_keys {
+            self.tracker
+                .paths
+                .borrow_mut()
+                .push(get_child_path(&self.path, &key));
+            if self.tracker.strip {
+                object.remove(&key);
+            }
+        }
+    }
+}
+
+fn get_child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for TrackedValue<'a> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::Number(number) => {
+                if let Some(value) = number.as_u64() {
+                    visitor.visit_u64(value)
+                } else if let Some(value) = number.as_i64() {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = number.as_f64() {
+                    visitor.visit_f64(value)
+                } else {
+                    Err(de::Error::custom(format!("invalid number {number}")))
+                }
+            }
+            Value::String(value) => visitor.visit_string(value),
+            Value::Array(values) => {
+                let length = values.len();
+                let mut seq = TrackedSeq {
+                    values: values.into_iter().enumerate(),
+                    parent: TrackedValue {
+                        value: Value::Null,
+                        path: self.path,
+                        tracker: self.tracker,
+                    },
+                };
+                let deserialized = visitor.visit_seq(&mut seq)?;
+                if seq.values.len() == 0 {
+                    Ok(deserialized)
+                } else {
+                    Err(de::Error::invalid_length(
+                        length,
+                        &"fewer elements in array",
+                    ))
+                }
+            }
+            Value::Object(object) => visitor.visit_map(TrackedMap {
+                entries: object.into_iter(),
+                next_value: None,
+                parent: TrackedValue {
+                    value: Value::Null,
+                    path: self.path,
+                    tracker: self.tracker,
+                },
+            }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.track_unknown_fields(fields);
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Object(object) if object.len() == 1 => {
+                let parent = TrackedValue {
+                    value: Value::Null,
+                    path: self.path,
+                    tracker: self.tracker,
+                };
+                let Some((variant, value)) = object.into_iter().next() else {
+                    return Err(de::Error::custom("expected an enum variant"));
+                };
+                let value = parent.child(value, &variant);
+                visitor.visit_enum(TrackedEnum { variant, value })
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or an object with a single key for an enum",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+struct TrackedSeq<'a> {
+    values: std::iter::Enumerate<std::vec::IntoIter<Value>>,
+    parent: TrackedValue<'a>,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for TrackedSeq<'a> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        self.values
+            .next()
+            .map(|(index, value)| seed.deserialize(self.parent.element(value, index)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct TrackedMap<'a> {
+    entries: <Map<String, Value> as IntoIterator>::IntoIter,
+    next_value: Option<(String, Value)>,
+    parent: TrackedValue<'a>,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for TrackedMap<'a> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let deserialized = seed.deserialize(IntoDeserializer::<Self::Error>::into_deserializer(
+            key.clone(),
+        ))?;
+        self.next_value = Some((key, value));
+        Ok(Some(deserialized))
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        let (key, value) = self
+            .next_value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(self.parent.child(value, &key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct TrackedEnum<'a> {
+    variant: String,
+    value: TrackedValue<'a>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for TrackedEnum<'a> {
+    type Error = serde_json::Error;
+    type Variant = TrackedValue<'a>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(IntoDeserializer::<Self::Error>::into_deserializer(
+            self.variant,
+        ))?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for TrackedValue<'a> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::IgnoredAny::deserialize(self).map(|_| ())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+/// The unknown fields of a request body
+#[derive(Debug, Clone)]
+pub struct UnknownFields {
+    request_type: &'static str,
+    paths: Vec<String>,
+}
+
+impl UnknownFields {
+    /// Rejects the request when it has unknown fields and the API key has strict request
+    /// validation, otherwise the unknown fields are counted in a metric
+    pub fn validate(&self, strict_request_validation: Option<bool>) -> RouterResult<()> {
+        if self.paths.is_empty() {
+            return Ok(());
+        }
+        if strict_request_validation.unwrap_or(false) {
+            return Err(report!(errors::ApiErrorResponse::UnknownFields {
+                fields: self.paths.clone(),
+            }));
+        }
+
+        logger::info!(
+            request_type = self.request_type,
+            unknown_fields = ?self.paths,
+            "Request has unknown fields"
+        );
+        for path in &self.paths {
+            metrics::REQUEST_UNKNOWN_FIELD_COUNT.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::request::add_attributes("request_type", self.request_type),
+                    metrics::request::add_attributes("field", get_field_label(path)),
+                ],
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The label of the field in the metric, with the indices of the arrays left out of its path, such
+/// as `order_details[].product_nme`
+fn get_field_label(path: &str) -> String {
+    let mut label = String::with_capacity(path.len());
+    let mut is_index = false;
+    for character in path.chars() {
+        match character {
+            '[' => {
+                is_index = true;
+                label.push_str("[]");
+            }
+            ']' if is_index => is_index = false,
+            _ if is_index => {}
+            _ => label.push(character),
+        }
+    }
+    if label.len() > MAX_FIELD_LABEL_LENGTH {
+        return OTHER_FIELD_LABEL.to_owned();
+    }
+
+    let mut labels = match FIELD_LABELS.lock() {
+        Ok(labels) => labels,
+        Err(error) => {
+            logger::error!(?error, "Failed to lock the labels of the unknown fields");
+            return OTHER_FIELD_LABEL.to_owned();
+        }
+    };
+    if labels.contains(&label) {
+        label
+    } else if labels.len() < MAX_FIELD_LABELS {
+        labels.insert(label.clone());
+        label
+    } else {
+        OTHER_FIELD_LABEL.to_owned()
+    }
+}
+
+/// JSON extractor which keeps the unknown fields of the request body, to be validated once the
+/// request is authenticated
+#[derive(Debug)]
+pub struct JsonWithUnknownFields<T> {
+    pub payload: T,
+    pub unknown_fields: UnknownFields,
+}
+
+impl<T> JsonWithUnknownFields<T> {
+    pub fn into_inner(self) -> (T, UnknownFields) {
+        (self.payload, self.unknown_fields)
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for JsonWithUnknownFields<T> {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<Value>::from_request(req, payload);
+        let req = req.clone();
+
+        Box::pin(async move {
+            let value = json.await?.into_inner();
+            let request_type = get_request_type::<T>();
+
+            match from_value_with_unknown_fields::<T>(value.clone(), false) {
+                Ok((payload, paths)) => Ok(Self {
+                    payload,
+                    unknown_fields: UnknownFields {
+                        request_type,
+                        paths,
+                    },
+                }),
+                Err(error) => {
+                    // The request types which deny unknown fields fail on the first of them,
+                    // all of them are reported when they are the only reason of the failure
+                    if let Ok((_, fields)) = from_value_with_unknown_fields::<T>(value, true) {
+                        if !fields.is_empty() {
+                            return Err(errors::ApiErrorResponse::UnknownFields { fields }.into());
+                        }
+                    }
+                    Err(error_parser::custom_json_error_handler(
+                        JsonPayloadError::Deserialize(error),
+                        &req,
+                    ))
+                }
+            }
+        })
+    }
+}
+
+fn get_request_type<T>() -> &'static str {
+    let type_name = std::any::type_name::<T>();
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Address {
+        line1: Option<String>,
+        city: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct OrderDetails {
+        product_name: String,
+        #[serde(alias = "qty")]
+        quantity: u16,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    #[allow(dead_code)]
+    enum PaymentMethodData {
+        Card { card_number: String },
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct PaymentsRequest {
+        amount: i64,
+        capture_method: Option<String>,
+        shipping: Option<Address>,
+        order_details: Option<Vec<OrderDetails>>,
+        payment_method_data: Option<PaymentMethodData>,
+        metadata: Option<Value>,
+    }
+
+    #[test]
+    fn test_nested_unknown_fields_are_reported() {
+        let value = serde_json::json!({
+            "amount": 6540,
+            "shipping": { "lin1": "1467 Harrison Street", "city": "San Fransico" },
+            "payment_method_data": { "card": { "card_number": "4242", "card_cvv": "123" } },
+        });
+
+        let (request, mut paths) =
+            from_value_with_unknown_fields::<PaymentsRequest>(value, false).unwrap();
+        paths.sort();
+
+        assert_eq!(request.amount, 6540);
+        assert_eq!(
+            request.shipping.unwrap().city.as_deref(),
+            Some("San Fransico")
+        );
+        assert_eq!(
+            paths,
+            vec![
+                "payment_method_data.card.card_cvv".to_string(),
+                "shipping.lin1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_of_arrays_of_objects_are_reported() {
+        let value = serde_json::json!({
+            "amount": 6540,
+            "order_details": [
+                { "product_name": "Apple iPhone 15", "quantity": 1 },
+                { "product_nme": "Apple iPhone 15 case", "product_name": "case", "qty": 2 },
+            ],
+        });
+
+        let (request, paths) =
+            from_value_with_unknown_fields::<PaymentsRequest>(value, false).unwrap();
+
+        assert_eq!(request.order_details.unwrap()[1].quantity, 2);
+        assert_eq!(paths, vec!["order_details[1].product_nme".to_string()]);
+    }
+
+    #[test]
+    fn test_keys_of_free_form_fields_are_not_reported() {
+        let value = serde_json::json!({
+            "amount": 6540,
+            "metadata": { "order_source": "web", "nested": { "any_key": true } },
+        });
+
+        let (_, paths) = from_value_with_unknown_fields::<PaymentsRequest>(value, false).unwrap();
+
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_all_unknown_fields_are_reported_for_structs_denying_them() {
+        let value = serde_json::json!({
+            "amount": 6540,
+            "capture_methd": "manual",
+            "shipping": { "lin1": "1467 Harrison Street" },
+        });
+
+        assert!(from_value_with_unknown_fields::<PaymentsRequest>(value.clone(), false).is_err());
+
+        let (request, paths) =
+            from_value_with_unknown_fields::<PaymentsRequest>(value, true).unwrap();
+        assert!(request.capture_method.is_none());
+        assert_eq!(
+            paths,
+            vec!["capture_methd".to_string(), "shipping.lin1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_strict_validation_rejects_unknown_fields() {
+        let unknown_fields = UnknownFields {
+            request_type: "PaymentsRequest",
+            paths: vec!["shipping.lin1".to_string()],
+        };
+
+        assert!(unknown_fields.validate(None).is_ok());
+        assert!(unknown_fields.validate(Some(false)).is_ok());
+        assert!(matches!(
+            unknown_fields.validate(Some(true)).unwrap_err().current_context(),
+            errors::ApiErrorResponse::UnknownFields { fields } if fields == &unknown_fields.paths
+        ));
+
+        let no_unknown_fields = UnknownFields {
+            request_type: "PaymentsRequest",
+            paths: Vec::new(),
+        };
+        assert!(no_unknown_fields.validate(Some(true)).is_ok());
+    }
+
+    #[test]
+    fn test_field_labels_leave_out_array_indices() {
+        assert_eq!(
+            get_field_label("order_details[1].product_nme"),
+            "order_details[].product_nme"
+        );
+        assert_eq!(
+            get_field_label("order_details[12].sub_items[0].nme"),
+            "order_details[].sub_items[].nme"
+        );
+        assert_eq!(get_field_label("shipping.lin1"), "shipping.lin1");
+        assert_eq!(get_field_label(&"a".repeat(129)), OTHER_FIELD_LABEL);
+    }
+}
@@ -1,5 +1,7 @@
+use std::str::FromStr;
+
 use actix_web::http::header::HeaderMap;
-use api_models::{payment_methods::PaymentMethodListRequest, payments};
+use api_models::{enums::ApiKeyPermission, payment_methods::PaymentMethodListRequest, payments};
 use async_trait::async_trait;
 use common_utils::date_time;
 use error_stack::{report, IntoReport, ResultExt};
@@ -7,6 +9,7 @@ use error_stack::{report, IntoReport, ResultExt};
 use external_services::kms::{self, decrypt::KmsDecrypt};
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use masking::{PeekInterface, StrongSecret};
+use router_env::{types::FlowMetric, Flow};
 
 use crate::{
     configs::settings,
@@ -17,17 +20,35 @@ use crate::{
     db::StorageInterface,
     routes::app::AppStateInfo,
     services::api,
-    types::domain,
+    types::{domain, storage},
     utils::OptionExt,
 };
 
 pub struct AuthenticationData {
     pub merchant_account: domain::MerchantAccount,
     pub key_store: domain::MerchantKeyStore,
+    /// The permissions of the API key used, `None` if the request was not authenticated with an API key
+    pub api_key_permissions: Option<Vec<ApiKeyPermission>>,
+    /// Whether the API key used is a live key, `None` if the request was not authenticated with an
+    /// API key
+    pub api_key_livemode: Option<bool>,
+    pub api_key_strict_request_validation: Option<bool>,
+    /// Whether the personal data of the customers is redacted from the responses, `None` if the
+    /// request was not authenticated with an API key
+    pub api_key_pii_restricted: Option<bool>,
 }
 
 pub trait AuthInfo {
     fn get_merchant_id(&self) -> Option<&str>;
+
+    fn get_api_key_permissions(&self) -> Option<&[ApiKeyPermission]> {
+        None
+    }
+
+    /// The redaction of the personal data of the customers from the responses to the request
+    fn get_response_pii_redaction(&self) -> Option<api_models::admin::PiiRedaction> {
+        None
+    }
 }
 
 impl AuthInfo for () {
@@ -40,6 +61,17 @@ impl AuthInfo for AuthenticationData {
     fn get_merchant_id(&self) -> Option<&str> {
         Some(&self.merchant_account.merchant_id)
     }
+
+    fn get_api_key_permissions(&self) -> Option<&[ApiKeyPermission]> {
+        self.api_key_permissions.as_deref()
+    }
+
+    fn get_response_pii_redaction(&self) -> Option<api_models::admin::PiiRedaction> {
+        api::pii_redaction::get_api_response_redaction(
+            self.merchant_account.pii_policy.as_ref(),
+            self.api_key_pii_restricted,
+        )
+    }
 }
 
 #[async_trait]
@@ -142,6 +174,10 @@ where
         Ok(AuthenticationData {
             merchant_account: merchant,
             key_store,
+            api_key_permissions: Some(stored_api_key.permissions),
+            api_key_livemode: Some(stored_api_key.livemode),
+            api_key_strict_request_validation: Some(stored_api_key.strict_request_validation),
+            api_key_pii_restricted: Some(stored_api_key.pii_restricted),
         })
     }
 }
@@ -248,6 +284,10 @@ where
         Ok(AuthenticationData {
             merchant_account: merchant,
             key_store,
+            api_key_permissions: None,
+            api_key_livemode: None,
+            api_key_strict_request_validation: None,
+            api_key_pii_restricted: None,
         })
     }
 }
@@ -346,6 +386,10 @@ where
         Ok(AuthenticationData {
             merchant_account: merchant,
             key_store,
+            api_key_permissions: None,
+            api_key_livemode: None,
+            api_key_strict_request_validation: None,
+            api_key_pii_restricted: None,
         })
     }
 }
@@ -366,6 +410,18 @@ impl ClientSecretFetch for PaymentMethodListRequest {
     }
 }
 
+impl ClientSecretFetch for api_models::payment_methods::RequiredFieldsRequest {
+    fn get_client_secret(&self) -> Option<&String> {
+        self.client_secret.as_ref()
+    }
+}
+
+impl ClientSecretFetch for api_models::payment_methods::BankListRequest {
+    fn get_client_secret(&self) -> Option<&String> {
+        self.client_secret.as_ref()
+    }
+}
+
 impl ClientSecretFetch for api_models::cards_info::CardsInfoRequest {
     fn get_client_secret(&self) -> Option<&String> {
         self.client_secret.as_ref()
@@ -425,10 +481,46 @@ where
     Ok((Box::new(ApiKeyAuth), api::AuthFlow::Merchant))
 }
 
+/// Flows which accept an ephemeral key, all of them operate on a single customer
+pub fn is_ephemeral_key_flow(flow: &Flow) -> bool {
+    matches!(
+        flow,
+        Flow::CustomersRetrieve | Flow::CustomerPaymentMethodsList | Flow::PaymentMethodsDelete
+    )
+}
+
+pub fn is_ephemeral_key(headers: &HeaderMap) -> bool {
+    get_api_key(headers)
+        .map(|api_key| api_key.starts_with("epk"))
+        .unwrap_or(false)
+}
+
+pub async fn validate_ephemeral_key(
+    db: &dyn StorageInterface,
+    secret: &str,
+    customer_id: &str,
+) -> RouterResult<storage::EphemeralKey> {
+    let ephemeral_key = db
+        .get_ephemeral_key(secret)
+        .await
+        .change_context(errors::ApiErrorResponse::Unauthorized)?;
+
+    if ephemeral_key.expires <= date_time::now_unix_timestamp() {
+        return Err(report!(errors::ApiErrorResponse::EphemeralKeyExpired));
+    }
+
+    if ephemeral_key.customer_id.ne(customer_id) {
+        return Err(report!(errors::ApiErrorResponse::InvalidEphemeralKey));
+    }
+
+    Ok(ephemeral_key)
+}
+
 pub async fn is_ephemeral_auth<A: AppStateInfo + Sync>(
     headers: &HeaderMap,
     db: &dyn StorageInterface,
     customer_id: &str,
+    flow: &Flow,
 ) -> RouterResult<Box<dyn AuthenticateAndFetch<AuthenticationData, A>>> {
     let api_key = get_api_key(headers)?;
 
@@ -436,15 +528,13 @@ pub async fn is_ephemeral_auth<A: AppStateInfo + Sync>(
         return Ok(Box::new(ApiKeyAuth));
     }
 
-    let ephemeral_key = db
-        .get_ephemeral_key(api_key)
-        .await
-        .change_context(errors::ApiErrorResponse::Unauthorized)?;
-
-    if ephemeral_key.customer_id.ne(customer_id) {
-        return Err(report!(errors::ApiErrorResponse::InvalidEphemeralKey));
+    if !is_ephemeral_key_flow(flow) {
+        return Err(report!(errors::ApiErrorResponse::Unauthorized))
+            .attach_printable_lazy(|| format!("Ephemeral keys are not accepted for {flow}"));
     }
 
+    let ephemeral_key = validate_ephemeral_key(db, api_key, customer_id).await?;
+
     Ok(Box::new(MerchantIdAuth(ephemeral_key.merchant_id)))
 }
 
@@ -497,6 +587,138 @@ where
         .change_context(errors::ApiErrorResponse::InvalidJwtToken)
 }
 
+/// Returns the API key permission required to perform the given flow
+pub fn get_required_api_key_permission(flow: &Flow) -> ApiKeyPermission {
+    match flow {
+        Flow::PaymentsRetrieve
+        | Flow::PaymentsList
+        | Flow::PaymentsRoutingDecisionsSummary
+        | Flow::PaymentsEligibleConnectors
+        | Flow::PaymentAttemptsList
+        | Flow::RefundsRetrieve
+        | Flow::RefundsList
+        | Flow::RefundsBulkRetrieve
+        | Flow::CustomersRetrieve
+        | Flow::CustomersGetMandates
+        | Flow::CustomerPaymentMethodsList
+        | Flow::PaymentMethodsList
+        | Flow::PaymentMethodsRequiredFields
+        | Flow::PaymentMethodsBankList
+        | Flow::PaymentMethodsRetrieve
+        | Flow::MandatesRetrieve
+        | Flow::MandatesList
+        | Flow::DisputesRetrieve
+        | Flow::DisputesList
+        | Flow::CardsInfo
+        | Flow::ReconciliationReportRetrieve => ApiKeyPermission::PaymentsRead,
+        Flow::PaymentsCreate
+        | Flow::PaymentsRecurringCharge
+        | Flow::PaymentsUpdate
+        | Flow::PaymentsConfirm
+        | Flow::PaymentsCapture
+        | Flow::PaymentsCancel
+        | Flow::PaymentsApprove
+        | Flow::PaymentsReject
+        | Flow::PaymentsSessionToken
+        | Flow::PaymentsDeviceData
+        | Flow::PaymentsStart
+        | Flow::ValidatePaymentMethod => ApiKeyPermission::PaymentsWrite,
+        Flow::RefundsCreate | Flow::RefundsUpdate | Flow::RefundsBulkCreate => {
+            ApiKeyPermission::RefundsWrite
+        }
+        Flow::CustomersCreate
+        | Flow::CustomersUpdate
+        | Flow::CustomersDelete
+        | Flow::EphemeralKeyCreate
+        | Flow::EphemeralKeyDelete
+        | Flow::MandatesRevoke
+        | Flow::PaymentMethodsCreate
+        | Flow::PaymentMethodsUpdate
+        | Flow::PaymentMethodsDelete => ApiKeyPermission::CustomersWrite,
+        _ => ApiKeyPermission::Admin,
+    }
+}
+
+/// Verifies that the API key used to authenticate the request, if any, is permitted to perform the flow
+pub fn check_api_key_permission(
+    flow: &impl FlowMetric,
+    auth_info: &impl AuthInfo,
+) -> RouterResult<()> {
+    // Flows which cannot be identified are restricted to keys with admin access
+    let required_permission = Flow::from_str(&flow.to_string())
+        .map(|flow| get_required_api_key_permission(&flow))
+        .unwrap_or(ApiKeyPermission::Admin);
+
+    check_api_key_has_permission(auth_info.get_api_key_permissions(), required_permission)
+        .attach_printable_lazy(|| {
+            format!(
+                "API key does not have the permission required for the flow {}",
+                flow.to_string()
+            )
+        })
+}
+
+/// Checks that the API key has the required permission. Keys with admin access have every
+/// permission, requests which were not authenticated with an API key are not restricted
+pub fn check_api_key_has_permission(
+    permissions: Option<&[ApiKeyPermission]>,
+    required_permission: ApiKeyPermission,
+) -> RouterResult<()> {
+    match permissions {
+        Some(permissions)
+            if !permissions.contains(&ApiKeyPermission::Admin)
+                && !permissions.contains(&required_permission) =>
+        {
+            Err(report!(errors::ApiErrorResponse::MissingApiKeyPermission {
+                permission: required_permission.to_string(),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns whether the flow can be performed by a client authenticated with the publishable key
+pub fn is_publishable_key_flow(flow: &Flow) -> bool {
+    matches!(
+        flow,
+        Flow::PaymentsRetrieve
+            | Flow::PaymentsUpdate
+            | Flow::PaymentsConfirm
+            | Flow::PaymentsSessionToken
+            | Flow::PaymentsDeviceData
+            | Flow::PaymentMethodsList
+            | Flow::PaymentMethodsRequiredFields
+            | Flow::PaymentMethodsBankList
+            | Flow::CustomerPaymentMethodsList
+            | Flow::CardsInfo
+    )
+}
+
+/// Rejects requests made with a publishable key to flows which are not allowed for clients
+pub fn check_publishable_key_access(
+    flow: &impl FlowMetric,
+    headers: &HeaderMap,
+) -> RouterResult<()> {
+    let is_publishable_key = get_header_value_by_key("api-key".into(), headers)?
+        .map(|api_key| api_key.starts_with("pk_"))
+        .unwrap_or(false);
+
+    let is_allowed = Flow::from_str(&flow.to_string())
+        .map(|flow| is_publishable_key_flow(&flow))
+        .unwrap_or(false);
+
+    if is_publishable_key && !is_allowed {
+        Err(
+            report!(errors::ApiErrorResponse::Unauthorized).attach_printable(format!(
+                "Publishable key is not allowed for the flow {}",
+                flow.to_string()
+            )),
+        )
+    } else {
+        Ok(())
+    }
+}
+
 pub fn get_api_key(headers: &HeaderMap) -> RouterResult<&str> {
     get_header_value_by_key("api-key".into(), headers)?.get_required_value("api_key")
 }
@@ -545,3 +767,179 @@ where
     }
     default_auth
 }
+
+#[cfg(test)]
+mod api_key_permission_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    struct ApiKeyAuthInfo(Vec<ApiKeyPermission>);
+
+    impl AuthInfo for ApiKeyAuthInfo {
+        fn get_merchant_id(&self) -> Option<&str> {
+            Some("merchant_id")
+        }
+
+        fn get_api_key_permissions(&self) -> Option<&[ApiKeyPermission]> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_read_only_api_key_permissions() {
+        let read_only_key = ApiKeyAuthInfo(vec![ApiKeyPermission::PaymentsRead]);
+
+        assert!(check_api_key_permission(&Flow::PaymentsRetrieve, &read_only_key).is_ok());
+        assert!(
+            check_api_key_permission(&Flow::PaymentsRoutingDecisionsSummary, &read_only_key)
+                .is_ok()
+        );
+
+        let error = check_api_key_permission(&Flow::PaymentsConfirm, &read_only_key).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::MissingApiKeyPermission { permission }
+                if permission == "payments_write"
+        ));
+    }
+
+    #[test]
+    fn test_admin_api_key_permissions() {
+        let admin_key = ApiKeyAuthInfo(vec![ApiKeyPermission::Admin]);
+
+        assert!(check_api_key_permission(&Flow::PaymentsConfirm, &admin_key).is_ok());
+        assert!(check_api_key_permission(&Flow::MerchantConnectorsCreate, &admin_key).is_ok());
+        assert!(check_api_key_permission(&Flow::RefundsCreate, &()).is_ok());
+    }
+
+    fn headers_with_api_key(api_key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            actix_web::http::header::HeaderName::from_static("api-key"),
+            actix_web::http::header::HeaderValue::from_str(api_key).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_publishable_key_access() {
+        let publishable_key = headers_with_api_key("pk_dev_0123456789");
+
+        assert!(check_publishable_key_access(&Flow::PaymentsConfirm, &publishable_key).is_ok());
+        assert!(check_publishable_key_access(&Flow::PaymentMethodsList, &publishable_key).is_ok());
+        assert!(check_publishable_key_access(&Flow::PaymentsDeviceData, &publishable_key).is_ok());
+
+        for flow in [
+            Flow::PaymentsCreate,
+            Flow::RefundsCreate,
+            Flow::MerchantConnectorsCreate,
+        ] {
+            let error = check_publishable_key_access(&flow, &publishable_key).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::Unauthorized
+            ));
+        }
+    }
+
+    #[test]
+    fn test_secret_key_access_is_not_restricted() {
+        let secret_key = headers_with_api_key("snd_0123456789");
+
+        assert!(check_publishable_key_access(&Flow::PaymentsCreate, &secret_key).is_ok());
+        assert!(check_publishable_key_access(&Flow::RefundsCreate, &HeaderMap::new()).is_ok());
+    }
+
+    async fn mock_db_with_ephemeral_key(validity: i64) -> (crate::db::MockDb, String) {
+        use crate::db::ephemeral_key::EphemeralKeyInterface;
+
+        #[allow(clippy::expect_used)]
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let ephemeral_key = mockdb
+            .create_ephemeral_key(
+                storage::EphemeralKeyNew {
+                    id: "eki_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    customer_id: "cus_1".to_string(),
+                    secret: "epk_1".to_string(),
+                },
+                validity,
+            )
+            .await
+            .unwrap();
+        (mockdb, ephemeral_key.secret)
+    }
+
+    #[test]
+    fn test_ephemeral_key_flows() {
+        for flow in [
+            Flow::CustomersRetrieve,
+            Flow::CustomerPaymentMethodsList,
+            Flow::PaymentMethodsDelete,
+        ] {
+            assert!(is_ephemeral_key_flow(&flow));
+        }
+        for flow in [
+            Flow::PaymentsCreate,
+            Flow::CustomersUpdate,
+            Flow::PaymentMethodsCreate,
+        ] {
+            assert!(!is_ephemeral_key_flow(&flow));
+        }
+
+        assert!(is_ephemeral_key(&headers_with_api_key("epk_1")));
+        assert!(!is_ephemeral_key(&headers_with_api_key("snd_0123456789")));
+        assert!(!is_ephemeral_key(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_key_is_valid_for_its_customer() {
+        let (mockdb, secret) = mock_db_with_ephemeral_key(1).await;
+
+        let ephemeral_key = validate_ephemeral_key(&mockdb, &secret, "cus_1")
+            .await
+            .unwrap();
+        assert_eq!(ephemeral_key.merchant_id, "merchant_1");
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_key_is_invalid_for_another_customer() {
+        let (mockdb, secret) = mock_db_with_ephemeral_key(1).await;
+
+        let error = validate_ephemeral_key(&mockdb, &secret, "cus_2")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidEphemeralKey
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_ephemeral_key() {
+        let (mockdb, secret) = mock_db_with_ephemeral_key(0).await;
+
+        let error = validate_ephemeral_key(&mockdb, &secret, "cus_1")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::EphemeralKeyExpired
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_ephemeral_key() {
+        let (mockdb, _) = mock_db_with_ephemeral_key(1).await;
+
+        let error = validate_ephemeral_key(&mockdb, "epk_unknown", "cus_1")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::Unauthorized
+        ));
+    }
+}
@@ -9,6 +9,8 @@
 pub mod api;
 pub mod domain;
 pub mod storage;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod transformers;
 
 use std::{collections::HashMap, marker::PhantomData};
@@ -21,14 +23,16 @@ pub use common_utils::request::RequestBody;
 use common_utils::{pii, pii::Email};
 use data_models::mandates::MandateData;
 use error_stack::{IntoReport, ResultExt};
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 
 use self::{api::payments, storage::enums as storage_enums};
 pub use crate::core::payments::{CustomerDetails, PaymentAddress};
 #[cfg(feature = "payouts")]
 use crate::core::utils::IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_DISPUTE_FLOW;
 use crate::{
+    configs::settings::ConnectorProxy,
     core::{
+        bin_database,
         errors::{self, RouterResult},
         payments::RecurringMandatePaymentData,
     },
@@ -183,6 +187,24 @@ pub type VerifyWebhookSourceType = dyn services::ConnectorIntegration<
     VerifyWebhookSourceResponseData,
 >;
 
+pub type CreateWebhookEndpointType = dyn services::ConnectorIntegration<
+    api::CreateWebhookEndpoint,
+    WebhookEndpointRequestData,
+    WebhookEndpointResponseData,
+>;
+
+pub type UpdateWebhookEndpointType = dyn services::ConnectorIntegration<
+    api::UpdateWebhookEndpoint,
+    WebhookEndpointRequestData,
+    WebhookEndpointResponseData,
+>;
+
+pub type DeleteWebhookEndpointType = dyn services::ConnectorIntegration<
+    api::DeleteWebhookEndpoint,
+    WebhookEndpointRequestData,
+    WebhookEndpointResponseData,
+>;
+
 pub type SubmitEvidenceType = dyn services::ConnectorIntegration<
     api::Evidence,
     SubmitEvidenceRequestData,
@@ -215,6 +237,9 @@ pub type VerifyWebhookSourceRouterData = RouterData<
     VerifyWebhookSourceResponseData,
 >;
 
+pub type WebhookEndpointRouterData<F> =
+    RouterData<F, WebhookEndpointRequestData, WebhookEndpointResponseData>;
+
 pub type SubmitEvidenceRouterData =
     RouterData<api::Evidence, SubmitEvidenceRequestData, SubmitEvidenceResponse>;
 
@@ -288,6 +313,23 @@ pub struct RouterData<Flow, Request, Response> {
 
     /// Contains apple pay flow type simplified or manual
     pub apple_pay_flow: Option<storage_enums::ApplePayFlow>,
+
+    /// Shopper locale resolved for this payment, used for connector language fields
+    pub locale: Option<String>,
+
+    /// Set by the connector when it accepted the operation without completing it, the status of
+    /// the operation is then polled until it is complete
+    pub poll_config: Option<PollConfig>,
+
+    /// Set by the connector when it authorized less than the amount of the payment
+    pub authorized_amount: Option<i64>,
+
+    /// The response received from the connector, with its sensitive fields masked
+    pub raw_connector_response: Option<RawConnectorResponse>,
+
+    /// Outbound proxy configured in the connector account details of the merchant connector
+    /// account, used instead of the proxy of the connector when the operator allows it
+    pub connector_proxy: Option<ConnectorProxy>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -315,6 +357,46 @@ pub struct ApplePayCryptogramData {
     pub eci_indicator: Option<Secret<String>>,
 }
 
+/// Card decrypted from the token of an Apple Pay or a Google Pay payment, for the connectors which
+/// accept the wallet payments only as card payments. The card number is a network token, which is
+/// authorized with its cryptogram.
+#[derive(Debug, Clone)]
+pub struct DecryptedWalletCard {
+    /// Wallet which the card was decrypted from, for the connector to flag the payment with
+    pub wallet: WalletIndicator,
+    pub card_number: cards::CardNumber,
+    pub card_exp_month: Secret<String>,
+    pub card_exp_year: Secret<String>,
+    pub cryptogram: Secret<String>,
+    pub eci_indicator: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum WalletIndicator {
+    ApplePay,
+    GooglePay,
+}
+
+impl DecryptedWalletCard {
+    /// The card payment method data which the connector is sent in place of the wallet data
+    pub fn get_payment_method_data(&self) -> payments::PaymentMethodData {
+        payments::PaymentMethodData::Card(payments::Card {
+            card_number: self.card_number.clone(),
+            card_exp_month: self.card_exp_month.clone(),
+            card_exp_year: self.card_exp_year.clone(),
+            card_holder_name: Secret::new(String::new()),
+            card_cvc: Secret::new(String::new()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PaymentMethodBalance {
     pub amount: i64,
@@ -370,12 +452,37 @@ pub struct PaymentsAuthorizeData {
     pub browser_info: Option<BrowserInformation>,
     pub order_details: Option<Vec<api_models::payments::OrderDetailsWithAmount>>,
     pub order_category: Option<String>,
+    pub shipping_method: Option<storage_enums::ShippingMethod>,
+    pub estimated_delivery_date: Option<time::PrimitiveDateTime>,
     pub session_token: Option<String>,
     pub enrolled_for_3ds: bool,
     pub related_transaction_id: Option<String>,
     pub payment_experience: Option<storage_enums::PaymentExperience>,
     pub payment_method_type: Option<storage_enums::PaymentMethodType>,
     pub customer_id: Option<String>,
+    // 3DS authentication performed by the merchant outside of the router
+    pub external_authentication_data: Option<api_models::payments::ExternalAuthenticationData>,
+    // SCA exemption requested when 3DS is skipped by the merchant's 3DS decision rules
+    pub sca_exemption: Option<api_models::enums::ScaExemptionType>,
+    // Payment facilitator details, only present if the connector supports them
+    pub merchant_descriptor: Option<String>,
+    pub sub_merchant_data: Option<api_models::payments::SubMerchantData>,
+    // Card verification code collected for a payment with a saved card, it is never persisted
+    pub card_cvc: Option<Secret<String>>,
+    // Split of a marketplace payment, only present if the connector supports split payments
+    pub split_payment: Option<api_models::payments::SplitPaymentDetails>,
+    // Breakdown of the amount for Level 2 and Level 3 data, only present if it adds up to the amount
+    pub amount_details: Option<api_models::payments::AmountDetails>,
+    // Whether the merchant accepts an authorization of less than the amount of the payment
+    pub request_partial_authorization: bool,
+    // Payment metadata forwarded to the connector, made of only the keys allowed for the connector
+    pub metadata: Option<pii::SecretSerdeValue>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    // Card decrypted from the wallet token, only present if the connector accepts the wallet
+    // payments only as cards, in which case `payment_method_data` is the decrypted card
+    pub decrypted_wallet_card: Option<DecryptedWalletCard>,
+    // Session of the device data collection run by the SDK, required by some connectors
+    pub device_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -471,6 +578,8 @@ pub struct PaymentsSyncData {
     pub connector_meta: Option<serde_json::Value>,
     pub sync_type: SyncRequestType,
     pub mandate_id: Option<api_models::payments::MandateIds>,
+    /// Connector reference of the latest capture, present for payments with multiple captures
+    pub capture_id: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -487,6 +596,7 @@ pub struct PaymentsCancelData {
     pub connector_transaction_id: String,
     pub cancellation_reason: Option<String>,
     pub connector_meta: Option<serde_json::Value>,
+    pub webhook_url: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -507,6 +617,9 @@ pub struct PaymentsSessionData {
     pub currency: storage_enums::Currency,
     pub country: Option<api::enums::CountryAlpha2>,
     pub order_details: Option<Vec<api_models::payments::OrderDetailsWithAmount>>,
+    /// Merchant connector account holding the wallet configurations in its metadata, absent when
+    /// the account details were passed in the request
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -564,6 +677,19 @@ impl Capturable for PaymentsRejectData {}
 impl Capturable for PaymentsSessionData {}
 impl Capturable for PaymentsSyncData {}
 
+impl<F, Res> RouterData<F, PaymentsAuthorizeData, Res> {
+    /// Network, card type, issuing country and bank of the card being charged, available when
+    /// the BIN of the card is found in the BIN database
+    pub fn get_card_bin_info(&self) -> Option<&'static bin_database::BinInfo> {
+        match &self.request.payment_method_data {
+            payments::PaymentMethodData::Card(card) => {
+                bin_database::lookup_bin(card.card_number.peek())
+            }
+            _ => None,
+        }
+    }
+}
+
 pub struct AddAccessTokenResult {
     pub access_token_result: Result<Option<AccessToken>, ErrorResponse>,
     pub connector_supports_access_token: bool,
@@ -624,6 +750,12 @@ pub enum PaymentsResponseData {
         connector_metadata: Option<serde_json::Value>,
         network_txn_id: Option<String>,
         connector_response_reference_id: Option<String>,
+        card_verification_results: Option<api_models::payments::CardVerificationResults>,
+        connector_fee: Option<ConnectorFee>,
+        /// Authorization code returned by the issuer for the payment
+        auth_code: Option<String>,
+        /// Acquirer reference number (ARN) used to trace the payment across the card network
+        acquirer_reference_number: Option<String>,
     },
     MultipleCaptureResponse {
         // pending_capture_id_list: Vec<String>,
@@ -661,6 +793,17 @@ pub enum PaymentsResponseData {
     },
 }
 
+/// Fee charged by the connector for processing a payment, as reported by the connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectorFee {
+    /// Fee in the minor unit of `fee_currency`
+    pub fee_amount: i64,
+    /// Currency in which the connector charged the fee, which need not be the payment currency
+    pub fee_currency: storage_enums::Currency,
+    /// Amount settled to the merchant after deducting the fee, in the minor unit of `fee_currency`
+    pub net_amount: Option<i64>,
+}
+
 #[derive(Debug, Clone)]
 pub enum PreprocessingResponseId {
     PreProcessingId(String),
@@ -699,12 +842,22 @@ pub struct RefundsData {
     pub currency: storage_enums::Currency,
     /// Amount for the payment against which this refund is issued
     pub payment_amount: i64,
+    /// Reason for the refund, mapped to the value accepted by the connector
     pub reason: Option<String>,
     pub webhook_url: Option<String>,
     /// Amount to be refunded
     pub refund_amount: i64,
     /// Arbitrary metadata required for refund
     pub connector_metadata: Option<serde_json::Value>,
+    /// Platform fee reversed with the refund of a split payment
+    pub split_refund: Option<SplitRefundData>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitRefundData {
+    /// Part of the platform fee of the payment which is returned to the customer
+    pub platform_fee_refund_amount: i64,
+    pub fee_refund_behaviour: storage_enums::PlatformFeeRefundBehaviour,
 }
 
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -726,6 +879,9 @@ pub struct RefundsResponseData {
     pub connector_refund_id: String,
     pub refund_status: storage_enums::RefundStatus,
     // pub amount_received: Option<i32>, // Calculation for amount received not in place yet
+    /// Acquirer reference number (ARN) of the refund, used by the customer to trace the refund
+    /// with their bank
+    pub acquirer_reference_number: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -746,6 +902,21 @@ pub struct VerifyWebhookSourceResponseData {
     pub verify_webhook_status: VerifyWebhookStatus,
 }
 
+#[derive(Debug, Clone)]
+pub struct WebhookEndpointRequestData {
+    /// The URL at which we receive the webhooks of the merchant connector account
+    pub webhook_url: String,
+    /// Identifier of the registered endpoint, required for updating and deleting it
+    pub connector_webhook_endpoint_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookEndpointResponseData {
+    pub connector_webhook_endpoint_id: String,
+    /// The secret used by the connector to sign the webhooks, returned only when the endpoint is created
+    pub webhook_secret: Option<Secret<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub enum VerifyWebhookStatus {
     SourceVerified,
@@ -905,6 +1076,44 @@ pub struct Response {
     pub status_code: u16,
 }
 
+/// Where the URL to poll the status of an operation is read from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PollUrlSource {
+    /// URL returned in the body of the response, either absolute or relative to the connector
+    Url(String),
+    /// Header of the response carrying the URL, like `Location` or `Operation-Location`
+    Header(String),
+}
+
+/// Response of the connector to a request, with its sensitive fields and the card numbers in it
+/// masked, captured for the merchants which enabled the capture of the connector responses
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawConnectorResponse {
+    pub flow: String,
+    pub status_code: u16,
+    pub body: String,
+    /// Whether the body was truncated to the maximum length of a captured response
+    pub is_truncated: bool,
+}
+
+/// Polling of an operation which the connector accepted without completing it, like the
+/// operations answered with `202 Accepted` and a URL of their status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PollConfig {
+    pub url_source: PollUrlSource,
+    /// Wait before each poll of the status
+    pub interval: std::time::Duration,
+    /// Polls made before the operation is left to be synced later
+    pub max_attempts: u16,
+}
+
+impl PollConfig {
+    /// Time the operation is polled for before it is left to be synced later
+    pub fn get_polling_budget(&self) -> std::time::Duration {
+        self.interval * u32::from(self.max_attempts)
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct ErrorResponse {
     pub code: String,
@@ -1060,12 +1269,26 @@ impl From<&VerifyRouterData> for PaymentsAuthorizeData {
             browser_info: data.request.browser_info.clone(),
             order_details: None,
             order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
             session_token: None,
             enrolled_for_3ds: true,
             related_transaction_id: None,
             payment_experience: None,
             payment_method_type: None,
             customer_id: None,
+            external_authentication_data: None,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
         }
     }
 }
@@ -1112,6 +1335,11 @@ impl<F1, F2, T1, T2> From<(&RouterData<F1, T1, PaymentsResponseData>, T2)>
             connector_api_version: data.connector_api_version.clone(),
             connector_http_status_code: data.connector_http_status_code,
             apple_pay_flow: data.apple_pay_flow.clone(),
+            locale: data.locale.clone(),
+            poll_config: None,
+            raw_connector_response: None,
+            connector_proxy: data.connector_proxy.clone(),
+            authorized_amount: data.authorized_amount,
         }
     }
 }
@@ -1166,6 +1394,11 @@ impl<F1, F2>
             connector_api_version: None,
             connector_http_status_code: data.connector_http_status_code,
             apple_pay_flow: None,
+            locale: None,
+            poll_config: None,
+            raw_connector_response: None,
+            connector_proxy: data.connector_proxy.clone(),
+            authorized_amount: None,
         }
     }
 }
@@ -1,4 +1,5 @@
 pub mod admin;
+pub mod alerts;
 pub mod api_keys;
 pub mod configs;
 pub mod customers;
@@ -18,8 +19,8 @@ use std::{fmt::Debug, str::FromStr};
 use error_stack::{report, IntoReport, ResultExt};
 
 pub use self::{
-    admin::*, api_keys::*, configs::*, customers::*, disputes::*, files::*, payment_methods::*,
-    payments::*, payouts::*, refunds::*, webhooks::*,
+    admin::*, alerts::*, api_keys::*, configs::*, customers::*, disputes::*, files::*,
+    payment_methods::*, payments::*, payouts::*, refunds::*, webhooks::*,
 };
 use super::ErrorResponse;
 use crate::{
@@ -50,6 +51,33 @@ pub trait ConnectorVerifyWebhookSource:
 {
 }
 
+#[derive(Clone, Debug)]
+pub struct CreateWebhookEndpoint;
+
+#[derive(Clone, Debug)]
+pub struct UpdateWebhookEndpoint;
+
+#[derive(Clone, Debug)]
+pub struct DeleteWebhookEndpoint;
+
+/// Management of the webhook endpoints registered with the connector, through its API
+pub trait ConnectorWebhookEndpoint:
+    ConnectorIntegration<
+        CreateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > + ConnectorIntegration<
+        UpdateWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    > + ConnectorIntegration<
+        DeleteWebhookEndpoint,
+        types::WebhookEndpointRequestData,
+        types::WebhookEndpointResponseData,
+    >
+{
+}
+
 pub trait ConnectorTransactionId: ConnectorCommon + Sync {
     fn connector_transaction_id(
         &self,
@@ -136,6 +164,7 @@ pub trait Connector:
     + ConnectorTransactionId
     + Payouts
     + ConnectorVerifyWebhookSource
+    + ConnectorWebhookEndpoint
 {
 }
 
@@ -155,7 +184,8 @@ impl<
             + FileUpload
             + ConnectorTransactionId
             + Payouts
-            + ConnectorVerifyWebhookSource,
+            + ConnectorVerifyWebhookSource
+            + ConnectorWebhookEndpoint,
     > Connector for T
 {
 }
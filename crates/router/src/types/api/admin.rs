@@ -1,11 +1,13 @@
 pub use api_models::admin::{
     payout_routing_algorithm, BusinessProfileCreate, BusinessProfileResponse,
-    BusinessProfileUpdate, MerchantAccountCreate, MerchantAccountDeleteResponse,
-    MerchantAccountResponse, MerchantAccountUpdate, MerchantConnectorCreate,
-    MerchantConnectorDeleteResponse, MerchantConnectorDetails, MerchantConnectorDetailsWrap,
-    MerchantConnectorId, MerchantConnectorResponse, MerchantDetails, MerchantId,
-    PaymentMethodsEnabled, PayoutRoutingAlgorithm, PayoutStraightThroughAlgorithm,
-    RoutingAlgorithm, StraightThroughAlgorithm, ToggleKVRequest, ToggleKVResponse, WebhookDetails,
+    BusinessProfileUpdate, ConnectorTestStepResult, MerchantAccountCreate,
+    MerchantAccountDeleteResponse, MerchantAccountResponse, MerchantAccountUpdate,
+    MerchantConnectorCreate, MerchantConnectorDeleteResponse, MerchantConnectorDetails,
+    MerchantConnectorDetailsWrap, MerchantConnectorId, MerchantConnectorResponse,
+    MerchantConnectorVerifyRequest, MerchantConnectorVerifyResponse, MerchantDetails, MerchantId,
+    PaymentMethodsEnabled, PayoutRoutingAlgorithm, PayoutStraightThroughAlgorithm, PiiPolicy,
+    PiiRedaction, RoutingAlgorithm, StraightThroughAlgorithm, ThreeDsDecisionRulesRequest,
+    ToggleKVRequest, ToggleKVResponse, WebhookAuthentication, WebhookDetails, WebhookSubscription,
 };
 use common_utils::ext_traits::ValueExt;
 use error_stack::ResultExt;
@@ -46,6 +48,12 @@ impl TryFrom<domain::MerchantAccount> for MerchantAccountResponse {
             is_recon_enabled: item.is_recon_enabled,
             default_profile: item.default_profile,
             recon_status: item.recon_status,
+            default_currency: item.default_currency,
+            default_country: item.default_country,
+            pii_policy: item
+                .pii_policy
+                .map(|pii_policy| pii_policy.parse_value("PiiPolicy"))
+                .transpose()?,
         })
     }
 }
@@ -71,6 +79,21 @@ impl ForeignTryFrom<storage::business_profile::BusinessProfile> for BusinessProf
             frm_routing_algorithm: item.frm_routing_algorithm,
             payout_routing_algorithm: item.payout_routing_algorithm,
             applepay_verified_domains: item.applepay_verified_domains,
+            statement_descriptor_name: item.statement_descriptor_name,
+            statement_descriptor_suffix: item.statement_descriptor_suffix,
+            session_expiry: item.session_expiry,
+            default_locale: item.default_locale,
+            default_payment_description: item.default_payment_description,
+            connector_request_reference_format: item.connector_request_reference_format,
+            duplicate_order_reference_behaviour: item.duplicate_order_reference_behaviour,
+            duplicate_order_reference_window: item.duplicate_order_reference_window,
+            alert_auto_refund_max_amount: item.alert_auto_refund_max_amount,
+            default_currency: item.default_currency,
+            default_country: item.default_country,
+            pii_policy: item
+                .pii_policy
+                .map(|pii_policy| pii_policy.parse_value("PiiPolicy"))
+                .transpose()?,
         })
     }
 }
@@ -90,6 +113,10 @@ impl ForeignTryFrom<(domain::MerchantAccount, BusinessProfileCreate)>
 
         let webhook_details = request
             .webhook_details
+            .map(|mut webhook_details| {
+                webhook_details.pin_payload_versions(None);
+                webhook_details
+            })
             .as_ref()
             .map(|webhook_details| {
                 common_utils::ext_traits::Encode::<WebhookDetails>::encode_to_value(webhook_details)
@@ -99,6 +126,17 @@ impl ForeignTryFrom<(domain::MerchantAccount, BusinessProfileCreate)>
             })
             .transpose()?;
 
+        let pii_policy = request
+            .pii_policy
+            .as_ref()
+            .map(|pii_policy| {
+                common_utils::ext_traits::Encode::<PiiPolicy>::encode_to_value(pii_policy)
+                    .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                        field_name: "pii_policy",
+                    })
+            })
+            .transpose()?;
+
         let payment_response_hash_key = request
             .payment_response_hash_key
             .or(merchant_account.payment_response_hash_key)
@@ -138,6 +176,21 @@ impl ForeignTryFrom<(domain::MerchantAccount, BusinessProfileCreate)>
                 .or(merchant_account.payout_routing_algorithm),
             is_recon_enabled: merchant_account.is_recon_enabled,
             applepay_verified_domains: request.applepay_verified_domains,
+            statement_descriptor_name: request.statement_descriptor_name,
+            statement_descriptor_suffix: request.statement_descriptor_suffix,
+            session_expiry: request.session_expiry.map(i64::from),
+            default_locale: request.default_locale,
+            default_payment_description: request.default_payment_description,
+            connector_request_reference_format: request.connector_request_reference_format,
+            duplicate_order_reference_behaviour: request.duplicate_order_reference_behaviour,
+            duplicate_order_reference_window: request
+                .duplicate_order_reference_window
+                .map(i64::from),
+            alert_auto_refund_max_amount: request.alert_auto_refund_max_amount,
+            default_currency: request.default_currency,
+            default_country: request.default_country,
+            pii_policy,
+            webhook_auth_details: None,
         })
     }
 }
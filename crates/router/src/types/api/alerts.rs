@@ -0,0 +1,12 @@
+/// A chargeback prevention alert as sent by the connector. The alert is matched to the payment by
+/// the connector transaction id, or by the acquirer reference number when the alert does not
+/// identify the payment at the connector.
+#[derive(Debug)]
+pub struct AlertPayload {
+    pub connector_alert_id: String,
+    pub alert_type: api_models::enums::AlertType,
+    pub initiated_by: Option<String>,
+    pub connector_reason: Option<String>,
+    pub connector_transaction_id: Option<String>,
+    pub acquirer_reference_number: Option<String>,
+}
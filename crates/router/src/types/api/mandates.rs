@@ -1,12 +1,13 @@
 use api_models::mandates;
 pub use api_models::mandates::{MandateId, MandateResponse, MandateRevokedResponse};
+use common_utils::date_time;
 use error_stack::ResultExt;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
-        payment_methods,
+        mandate, payment_methods,
     },
     newtype,
     routes::AppState,
@@ -40,7 +41,7 @@ impl MandateResponseExt for MandateResponse {
                 state,
                 &payment_method.customer_id,
                 &payment_method.merchant_id,
-                &payment_method.payment_method_id,
+                payment_methods::cards::get_locker_id(&payment_method),
             )
             .await?;
             let card_detail = payment_methods::transformers::get_card_detail(&payment_method, card)
@@ -51,6 +52,19 @@ impl MandateResponseExt for MandateResponse {
             None
         };
 
+        let now = date_time::now();
+        let usage = mandates::MandateUsage {
+            charge_count: mandate.charge_count,
+            amount_charged: mandate.amount_captured.unwrap_or(0),
+            frequency: mandate.frequency,
+            max_charges_per_period: mandate.max_charges_per_period,
+            period_charge_count: mandate::usage::get_period_charge_count(&mandate, now),
+            period_started_at: mandate
+                .frequency
+                .map(|frequency| mandate::usage::get_period_start(frequency, now)),
+            next_charge_allowed_at: mandate::usage::get_next_charge_allowed_at(&mandate, now),
+        };
+
         Ok(Self {
             mandate_id: mandate.mandate_id,
             customer_acceptance: Some(api::payments::CustomerAcceptance {
@@ -69,6 +83,7 @@ impl MandateResponseExt for MandateResponse {
             status: mandate.mandate_status,
             payment_method: payment_method.payment_method.to_string(),
             payment_method_id: mandate.payment_method_id,
+            usage,
         })
     }
 }
@@ -1,4 +1,5 @@
 pub use api_models::refunds::{
+    BulkRefundItemRequest, BulkRefundItemResponse, BulkRefundRequest, BulkRefundResponse,
     RefundRequest, RefundResponse, RefundStatus, RefundType, RefundUpdateRequest,
     RefundsRetrieveRequest,
 };
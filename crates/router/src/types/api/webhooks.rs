@@ -260,10 +260,31 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         Ok(services::api::ApplicationResponse::StatusOk)
     }
 
+    fn get_webhook_connector_fee(
+        &self,
+        _request: &IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<types::ConnectorFee>, errors::ConnectorError> {
+        Ok(None)
+    }
+
+    fn get_webhook_acquirer_reference_number(
+        &self,
+        _request: &IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        Ok(None)
+    }
+
     fn get_dispute_details(
         &self,
         _request: &IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<super::disputes::DisputePayload, errors::ConnectorError> {
         Err(errors::ConnectorError::NotImplemented("get_dispute_details method".to_string()).into())
     }
+
+    fn get_alert_details(
+        &self,
+        _request: &IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<super::alerts::AlertPayload, errors::ConnectorError> {
+        Err(errors::ConnectorError::NotImplemented("get_alert_details method".to_string()).into())
+    }
 }
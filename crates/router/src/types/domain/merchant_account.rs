@@ -44,6 +44,10 @@ pub struct MerchantAccount {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: diesel_models::enums::ReconStatus,
+    pub webhook_auth_details: OptionalEncryptableValue,
+    pub default_currency: Option<diesel_models::enums::Currency>,
+    pub default_country: Option<diesel_models::enums::CountryAlpha2>,
+    pub pii_policy: Option<serde_json::Value>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -68,6 +72,10 @@ pub enum MerchantAccountUpdate {
         frm_routing_algorithm: Option<serde_json::Value>,
         payout_routing_algorithm: Option<serde_json::Value>,
         default_profile: Option<Option<String>>,
+        webhook_auth_details: OptionalEncryptableValue,
+        default_currency: Option<diesel_models::enums::Currency>,
+        default_country: Option<diesel_models::enums::CountryAlpha2>,
+        pii_policy: Option<serde_json::Value>,
     },
     StorageSchemeUpdate {
         storage_scheme: MerchantStorageScheme,
@@ -100,6 +108,10 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 frm_routing_algorithm,
                 payout_routing_algorithm,
                 default_profile,
+                webhook_auth_details,
+                default_currency,
+                default_country,
+                pii_policy,
             } => Self {
                 merchant_name: merchant_name.map(Encryption::from),
                 merchant_details: merchant_details.map(Encryption::from),
@@ -120,6 +132,10 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 intent_fulfillment_time,
                 payout_routing_algorithm,
                 default_profile,
+                webhook_auth_details: webhook_auth_details.map(Encryption::from),
+                default_currency,
+                default_country,
+                pii_policy,
                 ..Default::default()
             },
             MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme } => Self {
@@ -173,6 +189,10 @@ impl super::behaviour::Conversion for MerchantAccount {
             is_recon_enabled: self.is_recon_enabled,
             default_profile: self.default_profile,
             recon_status: self.recon_status,
+            webhook_auth_details: self.webhook_auth_details.map(Encryption::from),
+            default_currency: self.default_currency,
+            default_country: self.default_country,
+            pii_policy: self.pii_policy,
         })
     }
 
@@ -217,6 +237,13 @@ impl super::behaviour::Conversion for MerchantAccount {
                 is_recon_enabled: item.is_recon_enabled,
                 default_profile: item.default_profile,
                 recon_status: item.recon_status,
+                webhook_auth_details: item
+                    .webhook_auth_details
+                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .await?,
+                default_currency: item.default_currency,
+                default_country: item.default_country,
+                pii_policy: item.pii_policy,
             })
         }
         .await
@@ -252,6 +279,10 @@ impl super::behaviour::Conversion for MerchantAccount {
             is_recon_enabled: self.is_recon_enabled,
             default_profile: self.default_profile,
             recon_status: self.recon_status,
+            webhook_auth_details: self.webhook_auth_details.map(Encryption::from),
+            default_currency: self.default_currency,
+            default_country: self.default_country,
+            pii_policy: self.pii_policy,
         })
     }
 }
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use common_utils::{
     crypto::{Encryptable, GcmAes256},
     date_time,
@@ -34,6 +36,36 @@ pub struct MerchantConnectorAccount {
     pub connector_webhook_details: Option<pii::SecretSerdeValue>,
     pub profile_id: Option<String>,
     pub applepay_verified_domains: Option<Vec<String>>,
+    /// Flows which are rejected for the payments processed through the account
+    pub disabled_flows: Option<Vec<enums::ConnectorFlow>>,
+    /// Credentials replaced in the last rotation, until the rotation is finalized
+    pub previous_credentials: Option<PreviousConnectorCredentials>,
+}
+
+/// Account details and webhook details replaced in a rotation, which are used only to verify the
+/// webhooks signed with them until they expire
+#[derive(Clone, Debug)]
+pub struct PreviousConnectorCredentials {
+    pub connector_account_details: Encryptable<Secret<serde_json::Value>>,
+    pub connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub expire_at: time::PrimitiveDateTime,
+}
+
+impl MerchantConnectorAccount {
+    /// The account with the credentials replaced in the last rotation in place of the current
+    /// credentials, if they have not expired
+    pub fn with_previous_credentials(&self, now: time::PrimitiveDateTime) -> Option<Self> {
+        let previous_credentials = self
+            .previous_credentials
+            .as_ref()
+            .filter(|previous_credentials| previous_credentials.expire_at > now)?;
+        Some(Self {
+            connector_account_details: previous_credentials.connector_account_details.clone(),
+            connector_webhook_details: previous_credentials.connector_webhook_details.clone(),
+            previous_credentials: None,
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -51,7 +83,11 @@ pub enum MerchantConnectorAccountUpdate {
         frm_configs: Option<Vec<Secret<serde_json::Value>>>,
         connector_webhook_details: Option<pii::SecretSerdeValue>,
         applepay_verified_domains: Option<Vec<String>>,
+        disabled_flows: Option<Vec<enums::ConnectorFlow>>,
+        /// Set when the credentials are rotated
+        previous_credentials: Option<PreviousConnectorCredentials>,
     },
+    FinalizeCredentialRotation,
 }
 
 #[async_trait::async_trait]
@@ -60,6 +96,11 @@ impl behaviour::Conversion for MerchantConnectorAccount {
     type NewDstType = diesel_models::merchant_connector_account::MerchantConnectorAccountNew;
 
     async fn convert(self) -> CustomResult<Self::DstType, ValidationError> {
+        let (
+            previous_connector_account_details,
+            previous_connector_webhook_details,
+            previous_credentials_expire_at,
+        ) = previous_credentials_to_storage(self.previous_credentials);
         Ok(
             diesel_models::merchant_connector_account::MerchantConnectorAccount {
                 id: self.id.ok_or(ValidationError::MissingRequiredField {
@@ -85,6 +126,10 @@ impl behaviour::Conversion for MerchantConnectorAccount {
                 connector_webhook_details: self.connector_webhook_details,
                 profile_id: self.profile_id,
                 applepay_verified_domains: self.applepay_verified_domains,
+                disabled_flows: self.disabled_flows.map(connector_flows_to_storage),
+                previous_connector_account_details,
+                previous_connector_webhook_details,
+                previous_credentials_expire_at,
             },
         )
     }
@@ -93,6 +138,22 @@ impl behaviour::Conversion for MerchantConnectorAccount {
         other: Self::DstType,
         key: &Secret<Vec<u8>>,
     ) -> CustomResult<Self, ValidationError> {
+        let previous_connector_account_details =
+            super::types::decrypt(other.previous_connector_account_details, key.peek())
+                .await
+                .change_context(ValidationError::InvalidValue {
+                    message: "Failed while decrypting previous connector account details"
+                        .to_string(),
+                })?;
+        let previous_credentials = previous_connector_account_details
+            .zip(other.previous_credentials_expire_at)
+            .map(
+                |(connector_account_details, expire_at)| PreviousConnectorCredentials {
+                    connector_account_details,
+                    connector_webhook_details: other.previous_connector_webhook_details,
+                    expire_at,
+                },
+            );
         Ok(Self {
             id: Some(other.id),
             merchant_id: other.merchant_id,
@@ -123,11 +184,18 @@ impl behaviour::Conversion for MerchantConnectorAccount {
             connector_webhook_details: other.connector_webhook_details,
             profile_id: other.profile_id,
             applepay_verified_domains: other.applepay_verified_domains,
+            disabled_flows: other.disabled_flows.map(connector_flows_from_storage),
+            previous_credentials,
         })
     }
 
     async fn construct_new(self) -> CustomResult<Self::NewDstType, ValidationError> {
         let now = date_time::now();
+        let (
+            previous_connector_account_details,
+            previous_connector_webhook_details,
+            previous_credentials_expire_at,
+        ) = previous_credentials_to_storage(self.previous_credentials);
         Ok(Self::NewDstType {
             merchant_id: Some(self.merchant_id),
             connector_name: Some(self.connector_name),
@@ -149,6 +217,10 @@ impl behaviour::Conversion for MerchantConnectorAccount {
             connector_webhook_details: self.connector_webhook_details,
             profile_id: self.profile_id,
             applepay_verified_domains: self.applepay_verified_domains,
+            disabled_flows: self.disabled_flows.map(connector_flows_to_storage),
+            previous_connector_account_details,
+            previous_connector_webhook_details,
+            previous_credentials_expire_at,
         })
     }
 }
@@ -169,22 +241,90 @@ impl From<MerchantConnectorAccountUpdate> for MerchantConnectorAccountUpdateInte
                 frm_configs,
                 connector_webhook_details,
                 applepay_verified_domains,
-            } => Self {
-                merchant_id,
-                connector_type,
-                connector_name,
-                connector_account_details: connector_account_details.map(Encryption::from),
-                test_mode,
-                disabled,
-                merchant_connector_id,
-                payment_methods_enabled,
-                metadata,
+                disabled_flows,
+                previous_credentials,
+            } => {
+                let is_rotated = previous_credentials.is_some();
+                let (
+                    previous_connector_account_details,
+                    previous_connector_webhook_details,
+                    previous_credentials_expire_at,
+                ) = previous_credentials_to_storage(previous_credentials);
+                Self {
+                    merchant_id,
+                    connector_type,
+                    connector_name,
+                    connector_account_details: connector_account_details.map(Encryption::from),
+                    test_mode,
+                    disabled,
+                    merchant_connector_id,
+                    payment_methods_enabled,
+                    metadata,
+                    frm_configs: None,
+                    frm_config: frm_configs,
+                    modified_at: Some(common_utils::date_time::now()),
+                    connector_webhook_details,
+                    applepay_verified_domains,
+                    disabled_flows: disabled_flows.map(connector_flows_to_storage),
+                    // The previous credentials are kept when the credentials are not rotated
+                    previous_connector_account_details: is_rotated
+                        .then_some(previous_connector_account_details),
+                    previous_connector_webhook_details: is_rotated
+                        .then_some(previous_connector_webhook_details),
+                    previous_credentials_expire_at: is_rotated
+                        .then_some(previous_credentials_expire_at),
+                }
+            }
+            MerchantConnectorAccountUpdate::FinalizeCredentialRotation => Self {
+                merchant_id: None,
+                connector_type: None,
+                connector_name: None,
+                connector_account_details: None,
+                test_mode: None,
+                disabled: None,
+                merchant_connector_id: None,
+                payment_methods_enabled: None,
+                metadata: None,
                 frm_configs: None,
-                frm_config: frm_configs,
+                frm_config: None,
                 modified_at: Some(common_utils::date_time::now()),
-                connector_webhook_details,
-                applepay_verified_domains,
+                connector_webhook_details: None,
+                applepay_verified_domains: None,
+                disabled_flows: None,
+                previous_connector_account_details: Some(None),
+                previous_connector_webhook_details: Some(None),
+                previous_credentials_expire_at: Some(None),
             },
         }
     }
 }
+
+pub fn connector_flows_to_storage(flows: Vec<enums::ConnectorFlow>) -> Vec<String> {
+    flows.iter().map(ToString::to_string).collect()
+}
+
+/// Flows which are not known are ignored, as they can only have been stored by a newer version
+fn connector_flows_from_storage(flows: Vec<String>) -> Vec<enums::ConnectorFlow> {
+    flows
+        .iter()
+        .filter_map(|flow| enums::ConnectorFlow::from_str(flow).ok())
+        .collect()
+}
+
+/// Columns in which the previous credentials are stored
+pub fn previous_credentials_to_storage(
+    previous_credentials: Option<PreviousConnectorCredentials>,
+) -> (
+    Option<Encryption>,
+    Option<pii::SecretSerdeValue>,
+    Option<time::PrimitiveDateTime>,
+) {
+    match previous_credentials {
+        Some(previous_credentials) => (
+            Some(previous_credentials.connector_account_details.into()),
+            previous_credentials.connector_webhook_details,
+            Some(previous_credentials.expire_at),
+        ),
+        None => (None, None, None),
+    }
+}
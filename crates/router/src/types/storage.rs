@@ -1,25 +1,34 @@
 pub mod address;
+pub mod alert;
 pub mod api_keys;
+pub mod audit_event;
+pub mod blocklist;
+pub mod bulk_refund;
 pub mod business_profile;
 pub mod capture;
 pub mod cards_info;
 pub mod configs;
 pub mod connector_response;
+pub mod connector_response_capture;
 pub mod customers;
 pub mod dispute;
 pub mod enums;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
+pub mod idempotency_key;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_migration;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
+pub mod payment_attempt_step_event;
 pub mod payment_method;
+pub mod payment_routing_decision;
 pub use diesel_models::{ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate};
 pub use scheduler::db::process_tracker;
 pub mod reverse_lookup;
@@ -28,6 +37,7 @@ pub mod payout_attempt;
 pub mod payouts;
 mod query;
 pub mod refund;
+pub mod webhook_delivery;
 
 pub use data_models::payments::{
     payment_attempt::{PaymentAttempt, PaymentAttemptNew, PaymentAttemptUpdate},
@@ -35,14 +45,23 @@ pub use data_models::payments::{
 };
 
 pub use self::{
-    address::*, api_keys::*, capture::*, cards_info::*, configs::*, connector_response::*,
-    customers::*, dispute::*, ephemeral_key::*, events::*, file::*, locker_mock_up::*, mandate::*,
-    merchant_account::*, merchant_connector_account::*, merchant_key_store::*, payment_method::*,
-    payout_attempt::*, payouts::*, process_tracker::*, refund::*, reverse_lookup::*,
+    address::*, alert::*, api_keys::*, audit_event::*, blocklist::*, bulk_refund::*, capture::*,
+    cards_info::*, configs::*, connector_response::*, connector_response_capture::*, customers::*,
+    dispute::*, ephemeral_key::*, events::*, file::*, idempotency_key::*, locker_mock_up::*,
+    mandate::*, mandate_migration::*, merchant_account::*, merchant_connector_account::*,
+    merchant_key_store::*, payment_attempt_step_event::*, payment_method::*,
+    payment_routing_decision::*, payout_attempt::*, payouts::*, process_tracker::*, refund::*,
+    reverse_lookup::*, webhook_delivery::*,
 };
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RoutingData {
     pub routed_through: Option<String>,
     pub algorithm: Option<api_models::admin::StraightThroughAlgorithm>,
+    /// The connector which the payment was routed around because of its maintenance window
+    pub maintenance_fallback: Option<api_models::payments::MaintenanceFallback>,
+    /// The routing rule which chose the connector, when the connector was chosen by the routing
+    pub routing_rule: Option<enums::RoutingRule>,
+    /// The connectors which the payment was routed around, in the order of preference
+    pub skipped_connectors: Vec<crate::core::circuit_breaker::SkippedConnector>,
 }
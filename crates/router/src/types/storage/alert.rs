@@ -0,0 +1 @@
+pub use diesel_models::alert::{Alert, AlertNew, AlertUpdate, AlertUpdateInternal};
@@ -0,0 +1 @@
+pub use diesel_models::bulk_refund::{BulkRefund, BulkRefundItem, BulkRefundNew};
@@ -0,0 +1,3 @@
+pub use diesel_models::connector_response_capture::{
+    ConnectorResponseCapture, ConnectorResponseCaptureNew,
+};
@@ -0,0 +1,4 @@
+pub use diesel_models::mandate_migration::{
+    MandateMigration, MandateMigrationItem, MandateMigrationNew, MandateMigrationUpdate,
+    MandateMigrationUpdateInternal,
+};
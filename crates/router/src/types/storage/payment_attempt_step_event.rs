@@ -0,0 +1,3 @@
+pub use diesel_models::payment_attempt_step_event::{
+    PaymentAttemptStepEvent, PaymentAttemptStepEventNew,
+};
@@ -0,0 +1,3 @@
+pub use diesel_models::payment_routing_decision::{
+    PaymentRoutingDecision, PaymentRoutingDecisionNew,
+};
@@ -0,0 +1,110 @@
+//! Fixtures shared by the tests of the connectors and of the core flows
+#![allow(clippy::unwrap_used)]
+
+use std::marker::PhantomData;
+
+use masking::Secret;
+
+use crate::types::{self, api, storage::enums, PaymentAddress};
+
+/// Router data of a card payment authorized with stripe, with the external authentication data
+/// of the payment
+pub fn get_authorize_router_data(
+    external_authentication_data: Option<api_models::payments::ExternalAuthenticationData>,
+) -> types::PaymentsAuthorizeRouterData {
+    types::RouterData {
+        flow: PhantomData,
+        merchant_id: "merchant_1".to_string(),
+        customer_id: None,
+        connector_customer: None,
+        connector: "stripe".to_string(),
+        payment_id: "pay_1".to_string(),
+        attempt_id: "pay_1_1".to_string(),
+        status: enums::AttemptStatus::Started,
+        payment_method: enums::PaymentMethod::Card,
+        connector_auth_type: types::ConnectorAuthType::HeaderKey {
+            api_key: Secret::new("sk_test".to_string()),
+        },
+        description: None,
+        return_url: None,
+        address: PaymentAddress::default(),
+        auth_type: enums::AuthenticationType::ThreeDs,
+        connector_meta_data: None,
+        amount_captured: None,
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        connector_api_version: None,
+        request: types::PaymentsAuthorizeData {
+            payment_method_data: api::PaymentMethodData::Card(api::Card {
+                card_number: cards::CardNumber::try_from("4000000000003220".to_string()).unwrap(),
+                card_exp_month: Secret::new("10".to_string()),
+                card_exp_year: Secret::new("2035".to_string()),
+                card_holder_name: Secret::new("John Doe".to_string()),
+                card_cvc: Secret::new("123".to_string()),
+                card_issuer: None,
+                card_network: None,
+                card_type: None,
+                card_issuing_country: None,
+                bank_code: None,
+                nick_name: None,
+            }),
+            amount: 1000,
+            email: None,
+            currency: enums::Currency::USD,
+            confirm: true,
+            statement_descriptor_suffix: None,
+            statement_descriptor: None,
+            capture_method: None,
+            router_return_url: None,
+            webhook_url: None,
+            complete_authorize_url: None,
+            setup_future_usage: None,
+            mandate_id: None,
+            off_session: None,
+            setup_mandate_details: None,
+            browser_info: None,
+            order_details: None,
+            order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
+            session_token: None,
+            enrolled_for_3ds: true,
+            related_transaction_id: None,
+            payment_experience: None,
+            payment_method_type: None,
+            customer_id: None,
+            external_authentication_data,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
+        },
+        response: Err(types::ErrorResponse::default()),
+        payment_method_id: None,
+        connector_request_reference_id: "pay_1_1".to_string(),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode: None,
+        connector_http_status_code: None,
+        apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: None,
+        authorized_amount: None,
+    }
+}
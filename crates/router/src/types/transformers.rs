@@ -13,7 +13,7 @@ use masking::{ExposeInterface, PeekInterface};
 use super::domain;
 use crate::{
     core::errors,
-    services::authentication::get_header_value_by_key,
+    services::{authentication::get_header_value_by_key, connector_proxy},
     types::{api as api_types, storage},
 };
 
@@ -76,7 +76,8 @@ impl ForeignFrom<storage_enums::AttemptStatus> for storage_enums::IntentStatus {
             storage_enums::AttemptStatus::ConfirmationAwaited => Self::RequiresConfirmation,
             storage_enums::AttemptStatus::PaymentMethodAwaited => Self::RequiresPaymentMethod,
 
-            storage_enums::AttemptStatus::Authorized => Self::RequiresCapture,
+            storage_enums::AttemptStatus::Authorized
+            | storage_enums::AttemptStatus::PartiallyAuthorized => Self::RequiresCapture,
             storage_enums::AttemptStatus::AuthenticationPending
             | storage_enums::AttemptStatus::DeviceDataCollectionPending => {
                 Self::RequiresCustomerAction
@@ -98,7 +99,8 @@ impl ForeignFrom<storage_enums::AttemptStatus> for storage_enums::IntentStatus {
             | storage_enums::AttemptStatus::RouterDeclined
             | storage_enums::AttemptStatus::CaptureFailed
             | storage_enums::AttemptStatus::Failure => Self::Failed,
-            storage_enums::AttemptStatus::Voided => Self::Cancelled,
+            storage_enums::AttemptStatus::Voided
+            | storage_enums::AttemptStatus::AuthorizationExpired => Self::Cancelled,
         }
     }
 }
@@ -133,7 +135,9 @@ impl ForeignTryFrom<storage_enums::AttemptStatus> for storage_enums::CaptureStat
             | storage_enums::AttemptStatus::Unresolved
             | storage_enums::AttemptStatus::PaymentMethodAwaited
             | storage_enums::AttemptStatus::ConfirmationAwaited
-            | storage_enums::AttemptStatus::DeviceDataCollectionPending => {
+            | storage_enums::AttemptStatus::DeviceDataCollectionPending
+            | storage_enums::AttemptStatus::AuthorizationExpired
+            | storage_enums::AttemptStatus::PartiallyAuthorized => {
                 Err(errors::ApiErrorResponse::PreconditionFailed {
                     message: "AttemptStatus must be one of these for multiple partial captures [Charged, PartialCharged, Pending, CaptureInitiated, Failure, CaptureFailed]".into(),
                 }.into())
@@ -176,6 +180,8 @@ impl ForeignFrom<storage_enums::MandateAmountData> for api_models::payments::Man
             start_date: from.start_date,
             end_date: from.end_date,
             metadata: from.metadata,
+            frequency: from.frequency,
+            max_charges_per_period: from.max_charges_per_period,
         }
     }
 }
@@ -210,6 +216,8 @@ impl ForeignFrom<api_models::payments::MandateData> for data_models::mandates::M
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency: i.frequency,
+                            max_charges_per_period: i.max_charges_per_period,
                         },
                     ))
                 }
@@ -221,6 +229,8 @@ impl ForeignFrom<api_models::payments::MandateData> for data_models::mandates::M
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency: i.frequency,
+                            max_charges_per_period: i.max_charges_per_period,
                         },
                     )
                 }
@@ -240,6 +250,8 @@ impl ForeignFrom<api_models::payments::MandateAmountData> for storage_enums::Man
             start_date: from.start_date,
             end_date: from.end_date,
             metadata: from.metadata,
+            frequency: from.frequency,
+            max_charges_per_period: from.max_charges_per_period,
         }
     }
 }
@@ -256,6 +268,8 @@ impl ForeignFrom<api_enums::IntentStatus> for Option<storage_enums::EventType> {
             | api_enums::IntentStatus::RequiresCustomerAction => {
                 Some(storage_enums::EventType::ActionRequired)
             }
+            // Cancellations are notified by the flows which cancel the payment, such as the session
+            // expiry workflow, rather than on every cancelled status
             api_enums::IntentStatus::Cancelled
             | api_enums::IntentStatus::RequiresPaymentMethod
             | api_enums::IntentStatus::RequiresConfirmation
@@ -345,6 +359,7 @@ impl ForeignFrom<api_enums::PaymentMethodType> for api_enums::PaymentMethod {
             | api_enums::PaymentMethodType::CimbVa
             | api_enums::PaymentMethodType::DanamonVa
             | api_enums::PaymentMethodType::MandiriVa
+            | api_enums::PaymentMethodType::VirtualAccount
             | api_enums::PaymentMethodType::Pix => Self::BankTransfer,
             api_enums::PaymentMethodType::Givex | api_enums::PaymentMethodType::PaySafeCard => {
                 Self::GiftCard
@@ -490,6 +505,10 @@ impl
             api_key: StrongSecret::from(plaintext_api_key.peek().to_owned()),
             created: api_key.created_at,
             expiration: api_key.expires_at.into(),
+            permissions: api_key.permissions,
+            livemode: api_key.livemode,
+            strict_request_validation: api_key.strict_request_validation,
+            pii_restricted: api_key.pii_restricted,
         }
     }
 }
@@ -504,6 +523,75 @@ impl ForeignFrom<diesel_models::api_keys::ApiKey> for api_models::api_keys::Retr
             prefix: api_key.prefix.into(),
             created: api_key.created_at,
             expiration: api_key.expires_at.into(),
+            permissions: api_key.permissions,
+            livemode: api_key.livemode,
+            strict_request_validation: api_key.strict_request_validation,
+            pii_restricted: api_key.pii_restricted,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::blocklist::Blocklist> for api_models::blocklist::BlocklistResponse {
+    fn foreign_from(blocklist_entry: diesel_models::blocklist::Blocklist) -> Self {
+        Self {
+            blocklist_id: blocklist_entry.blocklist_id,
+            merchant_id: blocklist_entry.merchant_id,
+            data_kind: blocklist_entry.data_kind,
+            fingerprint: blocklist_entry.fingerprint,
+            reason: blocklist_entry.reason,
+            expires_at: blocklist_entry.expires_at,
+            hit_count: blocklist_entry.hit_count,
+            last_hit_at: blocklist_entry.last_hit_at,
+            created_at: blocklist_entry.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::audit_event::AuditEvent>
+    for api_models::audit_events::AuditEventResponse
+{
+    fn foreign_from(audit_event: diesel_models::audit_event::AuditEvent) -> Self {
+        Self {
+            audit_event_id: audit_event.audit_event_id,
+            merchant_id: audit_event.merchant_id,
+            actor_key_id: audit_event.actor_key_id,
+            resource_type: audit_event.resource_type,
+            resource_id: audit_event.resource_id,
+            action: audit_event.action,
+            diff: audit_event.diff,
+            request_id: audit_event.request_id,
+            created_at: audit_event.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::alert::Alert> for api_models::alerts::AlertResponse {
+    fn foreign_from(alert: diesel_models::alert::Alert) -> Self {
+        Self {
+            alert_id: alert.alert_id,
+            merchant_id: alert.merchant_id,
+            payment_id: alert.payment_id,
+            attempt_id: alert.attempt_id,
+            connector: alert.connector,
+            connector_alert_id: alert.connector_alert_id,
+            alert_type: alert.alert_type,
+            initiated_by: alert.initiated_by,
+            connector_reason: alert.connector_reason,
+            status: alert.status,
+            action: alert.action,
+            refund_id: alert.refund_id,
+            created_at: alert.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<api_models::blocklist::UpdateBlocklistRequest>
+    for diesel_models::blocklist::BlocklistUpdate
+{
+    fn foreign_from(request: api_models::blocklist::UpdateBlocklistRequest) -> Self {
+        Self::Update {
+            reason: request.reason.map(Some),
+            expires_at: request.expires_at.map(Some),
         }
     }
 }
@@ -517,6 +605,7 @@ impl ForeignFrom<api_models::api_keys::UpdateApiKeyRequest>
             description: api_key.description,
             expires_at: api_key.expiration.map(Into::into),
             last_used: None,
+            permissions: api_key.permissions,
         }
     }
 }
@@ -641,12 +730,14 @@ impl TryFrom<domain::MerchantConnectorAccount> for api_models::admin::MerchantCo
             }
             None => None,
         };
+        let mut connector_account_details = item.connector_account_details.into_inner().expose();
+        connector_proxy::mask_merchant_connector_proxy(&mut connector_account_details);
         Ok(Self {
             connector_type: item.connector_type,
             connector_name: item.connector_name,
             connector_label: item.connector_label,
             merchant_connector_id: item.merchant_connector_id,
-            connector_account_details: item.connector_account_details.into_inner(),
+            connector_account_details: connector_account_details.into(),
             test_mode: item.test_mode,
             disabled: item.disabled,
             payment_methods_enabled,
@@ -668,6 +759,11 @@ impl TryFrom<domain::MerchantConnectorAccount> for api_models::admin::MerchantCo
                 .transpose()?,
             profile_id: item.profile_id,
             applepay_verified_domains: item.applepay_verified_domains,
+            disabled_flows: item.disabled_flows,
+            previous_credentials_expire_at: item
+                .previous_credentials
+                .map(|previous_credentials| previous_credentials.expire_at),
+            webhook_endpoint_registration: None,
         })
     }
 }
@@ -680,12 +776,14 @@ impl ForeignFrom<storage::PaymentAttempt> for api_models::payments::PaymentAttem
             amount: payment_attempt.amount,
             currency: payment_attempt.currency,
             connector: payment_attempt.connector,
-            error_message: payment_attempt.error_reason,
+            error_message: payment_attempt.error_message,
+            error_reason: payment_attempt.error_reason,
             payment_method: payment_attempt.payment_method,
             connector_transaction_id: payment_attempt.connector_transaction_id,
             capture_method: payment_attempt.capture_method,
             authentication_type: payment_attempt.authentication_type,
             cancellation_reason: payment_attempt.cancellation_reason,
+            cancellation_reason_code: payment_attempt.cancellation_reason_code,
             mandate_id: payment_attempt.mandate_id,
             error_code: payment_attempt.error_code,
             payment_token: payment_attempt.payment_token,
@@ -693,6 +791,10 @@ impl ForeignFrom<storage::PaymentAttempt> for api_models::payments::PaymentAttem
             payment_experience: payment_attempt.payment_experience,
             payment_method_type: payment_attempt.payment_method_type,
             reference_id: payment_attempt.connector_response_reference_id,
+            amount_to_capture: payment_attempt.amount_to_capture,
+            amount_capturable: payment_attempt.amount_capturable,
+            created_at: Some(payment_attempt.created_at),
+            modified_at: Some(payment_attempt.modified_at),
         }
     }
 }
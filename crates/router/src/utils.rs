@@ -150,6 +150,15 @@ pub fn get_payment_attempt_id(payment_id: impl std::fmt::Display, attempt_count:
     format!("{payment_id}_{attempt_count}")
 }
 
+/// Ordinal of the attempt generated by [`get_payment_attempt_id`], `None` if the attempt id was not
+/// generated from the payment id
+pub fn get_payment_attempt_ordinal(payment_id: &str, attempt_id: &str) -> Option<i16> {
+    attempt_id
+        .strip_prefix(payment_id)
+        .and_then(|suffix| suffix.strip_prefix('_'))
+        .and_then(|ordinal| ordinal.parse().ok())
+}
+
 #[derive(Debug)]
 pub struct QrImage {
     pub data: String,
@@ -158,13 +167,27 @@ pub struct QrImage {
 impl QrImage {
     pub fn new_from_data(
         data: String,
+    ) -> Result<Self, error_stack::Report<common_utils::errors::QrCodeError>> {
+        Self::new_from_data_with_size(data, None)
+    }
+
+    /// Renders the data as a QR code image, optionally bounded to `size` x `size` pixels
+    pub fn new_from_data_with_size(
+        data: String,
+        size: Option<u32>,
     ) -> Result<Self, error_stack::Report<common_utils::errors::QrCodeError>> {
         let qr_code = qrcode::QrCode::new(data.as_bytes())
             .into_report()
             .change_context(common_utils::errors::QrCodeError::FailedToCreateQrCode)?;
 
         // Renders the QR code into an image.
-        let qrcode_image_buffer = qr_code.render::<Luma<u8>>().build();
+        let mut qrcode_renderer = qr_code.render::<Luma<u8>>();
+        if let Some(size) = size {
+            qrcode_renderer
+                .min_dimensions(size, size)
+                .max_dimensions(size, size);
+        }
+        let qrcode_image_buffer = qrcode_renderer.build();
         let qrcode_dynamic_image = image::DynamicImage::ImageLuma8(qrcode_image_buffer);
 
         let mut image_bytes = Vec::new();
@@ -191,6 +214,20 @@ mod tests {
         let qr_image_data_source_url = utils::QrImage::new_from_data("Hyperswitch".to_string());
         assert!(qr_image_data_source_url.is_ok());
     }
+
+    #[test]
+    fn test_payment_attempt_ordinal() {
+        let attempt_id = utils::get_payment_attempt_id("pay_123", 3);
+        assert_eq!(
+            utils::get_payment_attempt_ordinal("pay_123", &attempt_id),
+            Some(3)
+        );
+        assert_eq!(
+            utils::get_payment_attempt_ordinal("pay_12", &attempt_id),
+            None
+        );
+        assert_eq!(utils::get_payment_attempt_ordinal("pay_123", "att_3"), None);
+    }
 }
 
 pub async fn find_payment_intent_from_payment_id_type(
@@ -1,3 +1,13 @@
+pub mod audit_event;
+pub mod authorization_expiry;
+pub mod card_fingerprint_backfill;
+pub mod data_retention;
+pub mod mandate_migration;
+pub mod partial_authorization;
+pub mod payment_expiry;
 pub mod payment_sync;
+#[cfg(feature = "olap")]
+pub mod reconciliation_report;
 pub mod refund_router;
 pub mod tokenized_data;
+pub mod webhook_endpoint;
@@ -0,0 +1,71 @@
+use common_utils::ext_traits::ValueExt;
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{core::audit_events, db::StorageInterface, errors, routes::AppState, types::storage};
+
+pub struct AuditEventWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for AuditEventWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let audit_event: storage::AuditEventNew =
+            process.tracking_data.clone().parse_value("AuditEventNew")?;
+        let audit_event_id = audit_event.audit_event_id.clone();
+        let merchant_id = audit_event.merchant_id.clone();
+
+        match db.insert_audit_event(audit_event).await {
+            Ok(_) => {}
+            // The event was stored by an earlier attempt whose completion was not recorded
+            Err(error) if error.current_context().is_db_unique_violation() => {}
+            Err(error) => {
+                logger::warn!(
+                    ?error,
+                    "Retry to record the audit event {audit_event_id} failed"
+                );
+                return retry_audit_event_task(db, &merchant_id, process).await;
+            }
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+async fn retry_audit_event_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    pt: storage::ProcessTracker,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    match audit_events::get_audit_event_retry_schedule_time(merchant_id, pt.retry_count) {
+        Some(schedule_time) => pt.retry(db.as_scheduler(), schedule_time).await,
+        None => {
+            pt.finish_with_status(db.as_scheduler(), "RETRIES_EXCEEDED".to_string())
+                .await
+        }
+    }
+}
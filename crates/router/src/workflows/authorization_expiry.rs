@@ -0,0 +1,527 @@
+use common_utils::ext_traits::{OptionExt, ValueExt};
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+use time::PrimitiveDateTime;
+
+use crate::{
+    consts,
+    core::{
+        api_locking,
+        payments::{self as payment_flows, helpers, operations},
+        webhooks,
+    },
+    db::StorageInterface,
+    errors,
+    routes::{lock_utils, AppState},
+    services,
+    types::{
+        api::{self, PaymentIdTypeExt},
+        domain,
+        storage::{self, enums},
+        transformers::ForeignFrom,
+    },
+};
+
+pub struct PaymentsAuthorizationExpiryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for PaymentsAuthorizationExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: api::PaymentsRetrieveRequest = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentsRetrieveRequest")?;
+        let merchant_id = tracking_data
+            .merchant_id
+            .clone()
+            .get_required_value("merchant_id")?;
+        let payment_id = tracking_data.resource_id.get_payment_intent_id()?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+            .await?;
+
+        // The payment could have been captured or voided directly at the connector, it is synced
+        // so that only payments which are still awaiting a capture are acted upon
+        let payments_response = get_payments_response(
+            payment_flows::payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+                state.clone(),
+                merchant_account.clone(),
+                key_store.clone(),
+                operations::PaymentStatus,
+                tracking_data,
+                services::AuthFlow::Merchant,
+                payment_flows::CallConnectorAction::Trigger,
+                api::HeaderPayload::default(),
+            )
+            .await?,
+        )?;
+
+        let auto_capture =
+            helpers::is_auto_capture_before_authorization_expiry_enabled(db, &merchant_id).await;
+
+        match get_authorization_expiry_action(
+            payments_response.status,
+            payments_response.authorization_expires_at,
+            common_utils::date_time::now(),
+            auto_capture,
+        ) {
+            AuthorizationExpiryAction::WarnMerchant {
+                authorization_expires_at,
+            } => {
+                trigger_capture_deadline_webhook(
+                    state,
+                    merchant_account,
+                    payment_id,
+                    payments_response,
+                )
+                .await?;
+                // The task is run again once the authorization expires
+                process
+                    .reset(db.as_scheduler(), authorization_expires_at)
+                    .await?;
+                return Ok(());
+            }
+            AuthorizationExpiryAction::Capture {
+                authorization_expires_at,
+            } => {
+                let response = capture_payment(
+                    state,
+                    merchant_account.clone(),
+                    key_store,
+                    payment_id.clone(),
+                )
+                .await
+                .and_then(get_payments_response);
+
+                match response {
+                    Ok(captured_payments_response) => {
+                        trigger_payment_status_webhook(
+                            state,
+                            merchant_account,
+                            payment_id,
+                            captured_payments_response,
+                        )
+                        .await?;
+                    }
+                    // The merchant is warned so that the payment can still be captured manually
+                    // before the authorization expires
+                    Err(error) => {
+                        logger::error!(
+                            ?error,
+                            "Failed to capture payment {payment_id} before its authorization expiry"
+                        );
+                        trigger_capture_deadline_webhook(
+                            state,
+                            merchant_account,
+                            payment_id,
+                            payments_response,
+                        )
+                        .await?;
+                        process
+                            .reset(db.as_scheduler(), authorization_expires_at)
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            AuthorizationExpiryAction::Expire => {
+                expire_authorization(state, merchant_account, key_store, payment_id).await?;
+            }
+            AuthorizationExpiryAction::NoAction => {}
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuthorizationExpiryAction {
+    /// The merchant is warned that the authorization is about to expire
+    WarnMerchant {
+        authorization_expires_at: PrimitiveDateTime,
+    },
+    /// The payment is captured as the merchant has opted in to automatic capture
+    Capture {
+        authorization_expires_at: PrimitiveDateTime,
+    },
+    /// The authorization has expired without the payment being captured
+    Expire,
+    /// The payment is no longer awaiting a capture
+    NoAction,
+}
+
+fn get_authorization_expiry_action(
+    status: enums::IntentStatus,
+    authorization_expires_at: Option<PrimitiveDateTime>,
+    current_time: PrimitiveDateTime,
+    auto_capture: bool,
+) -> AuthorizationExpiryAction {
+    match authorization_expires_at {
+        Some(authorization_expires_at) if status == enums::IntentStatus::RequiresCapture => {
+            if current_time >= authorization_expires_at {
+                AuthorizationExpiryAction::Expire
+            } else if auto_capture {
+                AuthorizationExpiryAction::Capture {
+                    authorization_expires_at,
+                }
+            } else {
+                AuthorizationExpiryAction::WarnMerchant {
+                    authorization_expires_at,
+                }
+            }
+        }
+        _ => AuthorizationExpiryAction::NoAction,
+    }
+}
+
+fn get_payments_response(
+    response: services::ApplicationResponse<api::PaymentsResponse>,
+) -> errors::RouterResult<api::PaymentsResponse> {
+    match response {
+        services::ApplicationResponse::JsonWithHeaders((payments_response, _))
+        | services::ApplicationResponse::Json(payments_response) => Ok(payments_response),
+        _ => Err(errors::ApiErrorResponse::InternalServerError.into()),
+    }
+}
+
+async fn capture_payment(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+) -> errors::RouterResponse<api::PaymentsResponse> {
+    let merchant_id = merchant_account.merchant_id.clone();
+    let lock_action = get_lock_action(payment_id.clone());
+    lock_action
+        .clone()
+        .perform_locking_action(state, merchant_id.clone())
+        .await?;
+
+    let response = payment_flows::payments_core::<api::Capture, api::PaymentsResponse, _, _, _>(
+        state.clone(),
+        merchant_account,
+        key_store,
+        operations::PaymentCapture,
+        api::PaymentsCaptureRequest {
+            payment_id,
+            merchant_id: Some(merchant_id.clone()),
+            ..Default::default()
+        },
+        services::AuthFlow::Merchant,
+        payment_flows::CallConnectorAction::Trigger,
+        api::HeaderPayload::default(),
+    )
+    .await;
+
+    lock_action.free_lock_action(state, merchant_id).await?;
+    response
+}
+
+/// Voids the authorization at the connector if configured, and marks the payment as cancelled as
+/// its authorization can no longer be captured
+async fn expire_authorization(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let db = &*state.store;
+    let merchant_id = merchant_account.merchant_id.clone();
+
+    if state.conf.authorization_validity.void_on_expiry {
+        let lock_action = get_lock_action(payment_id.clone());
+        lock_action
+            .clone()
+            .perform_locking_action(state, merchant_id.clone())
+            .await?;
+
+        let response = payment_flows::payments_core::<api::Void, api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            operations::PaymentCancel,
+            api::PaymentsCancelRequest {
+                payment_id: payment_id.clone(),
+                cancellation_reason: Some(
+                    consts::AUTHORIZATION_EXPIRED_CANCELLATION_REASON.to_string(),
+                ),
+                cancellation_reason_code: Some(enums::CancellationReason::Expired),
+                merchant_connector_details: None,
+            },
+            services::AuthFlow::Merchant,
+            payment_flows::CallConnectorAction::Trigger,
+            api::HeaderPayload::default(),
+        )
+        .await;
+
+        lock_action
+            .free_lock_action(state, merchant_id.clone())
+            .await?;
+
+        // The authorization lapses at the connector even if it could not be voided
+        if let Err(error) = response {
+            logger::error!(
+                ?error,
+                "Failed to void the expired authorization of payment {payment_id}"
+            );
+        }
+    }
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await?;
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_id,
+            &merchant_id,
+            &payment_intent.active_attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await?;
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::StatusUpdate {
+            status: enums::AttemptStatus::AuthorizationExpired,
+        },
+        merchant_account.storage_scheme,
+    )
+    .await?;
+    db.update_payment_intent(
+        payment_intent,
+        storage::PaymentIntentUpdate::PGStatusUpdate {
+            status: enums::IntentStatus::Cancelled,
+        },
+        merchant_account.storage_scheme,
+    )
+    .await?;
+
+    let payments_response = get_payments_response(
+        payment_flows::payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store,
+            operations::PaymentStatus,
+            api::PaymentsRetrieveRequest {
+                merchant_id: Some(merchant_id),
+                resource_id: api::PaymentIdType::PaymentIntentId(payment_id.clone()),
+                ..Default::default()
+            },
+            services::AuthFlow::Merchant,
+            payment_flows::CallConnectorAction::Avoid,
+            api::HeaderPayload::default(),
+        )
+        .await?,
+    )?;
+    trigger_payment_status_webhook(state, merchant_account, payment_id, payments_response).await?;
+    Ok(())
+}
+
+fn get_lock_action(payment_id: String) -> api_locking::LockAction {
+    api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: payment_id,
+            api_identifier: lock_utils::ApiIdentifier::Payments,
+            override_lock_retries: None,
+        },
+    }
+}
+
+async fn trigger_capture_deadline_webhook(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+    payments_response: api::PaymentsResponse,
+) -> errors::RouterResult<()> {
+    webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+        state.clone(),
+        merchant_account,
+        enums::EventType::PaymentCaptureDeadlineApproaching,
+        enums::EventClass::Payments,
+        None,
+        payment_id,
+        enums::EventObjectType::PaymentDetails,
+        api::OutgoingWebhookContent::PaymentDetails(payments_response),
+    )
+    .await
+}
+
+async fn trigger_payment_status_webhook(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+    payments_response: api::PaymentsResponse,
+) -> errors::RouterResult<()> {
+    match Option::<enums::EventType>::foreign_from(payments_response.status) {
+        Some(event_type) => {
+            webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+                state.clone(),
+                merchant_account,
+                event_type,
+                enums::EventClass::Payments,
+                None,
+                payment_id,
+                enums::EventObjectType::PaymentDetails,
+                api::OutgoingWebhookContent::PaymentDetails(payments_response),
+            )
+            .await
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::settings::AuthorizationValidityConfig;
+
+    /// Validity shortened to a minute, with the merchant warned ten seconds before the expiry
+    fn get_shortened_validity() -> AuthorizationValidityConfig {
+        AuthorizationValidityConfig {
+            default_validity_in_secs: 60,
+            lead_time_in_secs: 10,
+            ..Default::default()
+        }
+    }
+
+    fn get_schedule(authorized_at: PrimitiveDateTime) -> (PrimitiveDateTime, PrimitiveDateTime) {
+        let authorization_validity = get_shortened_validity();
+        let authorization_expires_at = authorized_at
+            + time::Duration::seconds(authorization_validity.get_validity_in_secs("stripe").into());
+        let schedule_time = authorization_expires_at
+            - time::Duration::seconds(authorization_validity.lead_time_in_secs.into());
+        (schedule_time, authorization_expires_at)
+    }
+
+    #[test]
+    fn test_merchant_is_warned_before_authorization_expiry() {
+        let authorized_at = common_utils::date_time::now();
+        let (schedule_time, authorization_expires_at) = get_schedule(authorized_at);
+        assert_eq!(schedule_time, authorized_at + time::Duration::seconds(50));
+
+        assert_eq!(
+            get_authorization_expiry_action(
+                enums::IntentStatus::RequiresCapture,
+                Some(authorization_expires_at),
+                schedule_time,
+                false,
+            ),
+            AuthorizationExpiryAction::WarnMerchant {
+                authorization_expires_at
+            }
+        );
+    }
+
+    #[test]
+    fn test_payment_is_captured_before_authorization_expiry_if_opted_in() {
+        let (schedule_time, authorization_expires_at) =
+            get_schedule(common_utils::date_time::now());
+
+        assert_eq!(
+            get_authorization_expiry_action(
+                enums::IntentStatus::RequiresCapture,
+                Some(authorization_expires_at),
+                schedule_time,
+                true,
+            ),
+            AuthorizationExpiryAction::Capture {
+                authorization_expires_at
+            }
+        );
+    }
+
+    #[test]
+    fn test_authorization_expires_when_not_captured() {
+        let (_, authorization_expires_at) = get_schedule(common_utils::date_time::now());
+
+        for auto_capture in [false, true] {
+            assert_eq!(
+                get_authorization_expiry_action(
+                    enums::IntentStatus::RequiresCapture,
+                    Some(authorization_expires_at),
+                    authorization_expires_at,
+                    auto_capture,
+                ),
+                AuthorizationExpiryAction::Expire
+            );
+        }
+    }
+
+    #[test]
+    fn test_captured_or_cancelled_payment_is_not_acted_upon() {
+        let (schedule_time, authorization_expires_at) =
+            get_schedule(common_utils::date_time::now());
+
+        for status in [
+            enums::IntentStatus::Succeeded,
+            enums::IntentStatus::PartiallyCaptured,
+            enums::IntentStatus::Cancelled,
+            enums::IntentStatus::Processing,
+        ] {
+            assert_eq!(
+                get_authorization_expiry_action(
+                    status,
+                    Some(authorization_expires_at),
+                    schedule_time,
+                    true,
+                ),
+                AuthorizationExpiryAction::NoAction
+            );
+        }
+        assert_eq!(
+            get_authorization_expiry_action(
+                enums::IntentStatus::RequiresCapture,
+                None,
+                schedule_time,
+                true,
+            ),
+            AuthorizationExpiryAction::NoAction
+        );
+    }
+
+    #[test]
+    fn test_connector_validity_overrides_default_validity() {
+        let authorization_validity = AuthorizationValidityConfig {
+            connector_validity_in_secs: [("adyen".to_string(), 120)].into_iter().collect(),
+            ..get_shortened_validity()
+        };
+        assert_eq!(authorization_validity.get_validity_in_secs("adyen"), 120);
+        assert_eq!(authorization_validity.get_validity_in_secs("stripe"), 60);
+    }
+}
@@ -0,0 +1,57 @@
+use common_utils::ext_traits::ValueExt;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{
+    core::payment_methods::card_fingerprint_backfill, errors, routes::AppState, types::storage,
+};
+
+pub struct CardFingerprintBackfillWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for CardFingerprintBackfillWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let mut tracking_data: card_fingerprint_backfill::CardFingerprintBackfillTrackingData =
+            process
+                .tracking_data
+                .clone()
+                .parse_value("CardFingerprintBackfillTrackingData")?;
+
+        let is_job_complete = card_fingerprint_backfill::execute_card_fingerprint_backfill_task(
+            state,
+            &mut tracking_data,
+        )
+        .await?;
+        card_fingerprint_backfill::add_next_card_fingerprint_backfill_task(
+            state,
+            tracking_data,
+            is_job_complete,
+        )
+        .await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
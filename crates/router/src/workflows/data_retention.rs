@@ -0,0 +1,57 @@
+use common_utils::ext_traits::ValueExt;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{core::data_retention, db::StorageInterface, errors, routes::AppState, types::storage};
+
+pub struct DataRetentionWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for DataRetentionWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let config = &state.conf.data_retention;
+        let mut tracking_data: data_retention::DataRetentionTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("DataRetentionTrackingData")?;
+
+        // The runs stop being scheduled once the job is disabled
+        if config.enabled {
+            let is_run_complete =
+                data_retention::execute_data_retention_task(db, config, &mut tracking_data).await?;
+            data_retention::add_next_data_retention_task(
+                db,
+                config,
+                tracking_data,
+                is_run_complete,
+            )
+            .await?;
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -0,0 +1,116 @@
+use common_utils::ext_traits::{OptionExt, ValueExt};
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{
+    core::{
+        api_locking,
+        payments::{self as payment_flows, operations},
+    },
+    db::StorageInterface,
+    errors,
+    routes::{lock_utils, AppState},
+    services,
+    types::{
+        api::{self, PaymentIdTypeExt},
+        storage,
+    },
+};
+
+pub struct PaymentsPartialAuthorizationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for PaymentsPartialAuthorizationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: api::PaymentsRetrieveRequest = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentsRetrieveRequest")?;
+        let merchant_id = tracking_data
+            .merchant_id
+            .clone()
+            .get_required_value("merchant_id")?;
+        let payment_id = tracking_data.resource_id.get_payment_intent_id()?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+            .await?;
+
+        let lock_action = api_locking::LockAction::Hold {
+            input: api_locking::LockingInput {
+                unique_locking_key: payment_id,
+                api_identifier: lock_utils::ApiIdentifier::Payments,
+                override_lock_retries: None,
+            },
+        };
+        lock_action
+            .clone()
+            .perform_locking_action(state, merchant_id.clone())
+            .await?;
+
+        // The payment is read as it is stored, it could have been captured or voided since the
+        // task was scheduled, in which case it is no longer partially authorized
+        let response = payment_flows::payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            operations::PaymentStatus,
+            tracking_data,
+            services::AuthFlow::Merchant,
+            payment_flows::CallConnectorAction::Avoid,
+            api::HeaderPayload::default(),
+        )
+        .await;
+        let result = match response {
+            Ok(
+                services::ApplicationResponse::Json(payments_response)
+                | services::ApplicationResponse::JsonWithHeaders((payments_response, _)),
+            ) => payment_flows::handle_partial_authorization(
+                state,
+                merchant_account,
+                key_store,
+                payments_response,
+            )
+            .await
+            .map(|_| ()),
+            Ok(_) => Err(errors::ApiErrorResponse::InternalServerError.into()),
+            Err(error) => Err(error),
+        };
+
+        lock_action.free_lock_action(state, merchant_id).await?;
+        result?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -0,0 +1,505 @@
+use std::future::Future;
+
+use common_utils::ext_traits::{OptionExt, ValueExt};
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{
+    consts,
+    core::{
+        api_locking,
+        payments::{self as payment_flows, helpers, operations},
+        refunds, webhooks,
+    },
+    db::StorageInterface,
+    errors,
+    routes::{lock_utils, AppState},
+    services,
+    types::{
+        api::{self, PaymentIdTypeExt},
+        storage::{self, enums},
+    },
+};
+
+pub struct PaymentsExpiryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for PaymentsExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: api::PaymentsRetrieveRequest = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentsRetrieveRequest")?;
+        let merchant_id = tracking_data
+            .merchant_id
+            .clone()
+            .get_required_value("merchant_id")?;
+        let payment_id = tracking_data.resource_id.get_payment_intent_id()?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+            .await?;
+
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                &payment_id,
+                &merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        // The customer could have completed the authentication at the connector after the expiry,
+        // the status is synced so that such an authorization is voided instead of being dropped
+        let status_at_expiry = payment_intent.status;
+        let (payment_intent, synced_payment_attempt) =
+            if status_at_expiry == enums::IntentStatus::RequiresCustomerAction {
+                let (payment_data, _, _, _) =
+                    payment_flows::payments_operation_core::<api::PSync, _, _, _>(
+                        state,
+                        merchant_account.clone(),
+                        key_store.clone(),
+                        operations::PaymentStatus,
+                        tracking_data,
+                        payment_flows::CallConnectorAction::Trigger,
+                        services::AuthFlow::Merchant,
+                        api::HeaderPayload::default(),
+                    )
+                    .await?;
+                (
+                    payment_data.payment_intent,
+                    Some(payment_data.payment_attempt),
+                )
+            } else {
+                (payment_intent, None)
+            };
+        let current_status = payment_intent.status;
+        let partially_received_amount = synced_payment_attempt
+            .as_ref()
+            .and_then(helpers::get_partially_received_amount);
+
+        let partial_bank_transfer_handling = match partially_received_amount {
+            Some(_) => Some(helpers::get_partial_bank_transfer_handling(db, &merchant_id).await),
+            None => None,
+        };
+
+        if should_accept_partial_bank_transfer(current_status, partial_bank_transfer_handling) {
+            // The received amount stays with the connector until the merchant accepts or cancels the payment
+            db.update_payment_intent(
+                payment_intent,
+                storage::PaymentIntentUpdate::PGStatusUpdate {
+                    status: enums::IntentStatus::RequiresMerchantAction,
+                },
+                merchant_account.storage_scheme,
+            )
+            .await?;
+        } else if should_cancel_expired_payment(status_at_expiry, current_status) {
+            // The received amount is returned to the customer before the payment is cancelled
+            if let (Some(amount_received), Some(payment_attempt)) = (
+                get_amount_to_refund_on_expiry(
+                    current_status,
+                    partial_bank_transfer_handling,
+                    partially_received_amount,
+                ),
+                synced_payment_attempt.as_ref(),
+            ) {
+                refunds::refund_partially_received_amount(
+                    state,
+                    &merchant_account,
+                    &key_store,
+                    &payment_intent,
+                    payment_attempt,
+                    amount_received,
+                )
+                .await?;
+            }
+
+            let lock_action = api_locking::LockAction::Hold {
+                input: api_locking::LockingInput {
+                    unique_locking_key: payment_id.clone(),
+                    api_identifier: lock_utils::ApiIdentifier::Payments,
+                    override_lock_retries: None,
+                },
+            };
+
+            cancel_expired_payment(
+                &payment_id,
+                |cancel_request| async {
+                    lock_action
+                        .clone()
+                        .perform_locking_action(state, merchant_id.clone())
+                        .await?;
+
+                    // Payments which are authorized are voided at the connector by the cancel operation
+                    let response =
+                        payment_flows::payments_core::<api::Void, api::PaymentsResponse, _, _, _>(
+                            state.clone(),
+                            merchant_account.clone(),
+                            key_store,
+                            operations::PaymentCancel,
+                            cancel_request,
+                            services::AuthFlow::Merchant,
+                            payment_flows::CallConnectorAction::Trigger,
+                            api::HeaderPayload::default(),
+                        )
+                        .await;
+
+                    lock_action
+                        .free_lock_action(state, merchant_id.clone())
+                        .await?;
+                    response
+                },
+                |payments_response| {
+                    webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+                        state.clone(),
+                        merchant_account.clone(),
+                        enums::EventType::PaymentCancelled,
+                        enums::EventClass::Payments,
+                        None,
+                        payment_id.clone(),
+                        enums::EventObjectType::PaymentDetails,
+                        api::OutgoingWebhookContent::PaymentDetails(payments_response),
+                    )
+                },
+            )
+            .await?;
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+/// Cancels the payment whose session expired, the merchant is notified of the cancellation by a
+/// single webhook, sent only when the payment is cancelled
+async fn cancel_expired_payment<C, CFut, W, WFut>(
+    payment_id: &str,
+    cancel_payment: C,
+    send_webhook: W,
+) -> errors::RouterResult<()>
+where
+    C: FnOnce(api::PaymentsCancelRequest) -> CFut,
+    CFut: Future<Output = errors::RouterResponse<api::PaymentsResponse>>,
+    W: FnOnce(api::PaymentsResponse) -> WFut,
+    WFut: Future<Output = errors::RouterResult<()>>,
+{
+    let response = cancel_payment(api::PaymentsCancelRequest {
+        payment_id: payment_id.to_owned(),
+        cancellation_reason: Some(consts::SESSION_EXPIRED_CANCELLATION_REASON.to_string()),
+        cancellation_reason_code: Some(enums::CancellationReason::Expired),
+        merchant_connector_details: None,
+    })
+    .await?;
+
+    match response {
+        services::ApplicationResponse::JsonWithHeaders((payments_response, _))
+            if payments_response.status == enums::IntentStatus::Cancelled =>
+        {
+            send_webhook(payments_response).await
+        }
+        _ => {
+            logger::info!("Payment {payment_id} was not cancelled on session expiry");
+            Ok(())
+        }
+    }
+}
+
+/// Only payments which were not authorized when their session expired are cancelled, a payment
+/// authorized by the customer after the expiry is cancelled only after voiding it at the connector
+fn should_cancel_expired_payment(
+    status_at_expiry: enums::IntentStatus,
+    current_status: enums::IntentStatus,
+) -> bool {
+    match status_at_expiry {
+        enums::IntentStatus::RequiresPaymentMethod | enums::IntentStatus::RequiresConfirmation => {
+            true
+        }
+        enums::IntentStatus::RequiresCustomerAction => matches!(
+            current_status,
+            enums::IntentStatus::RequiresPaymentMethod
+                | enums::IntentStatus::RequiresConfirmation
+                | enums::IntentStatus::RequiresCustomerAction
+                | enums::IntentStatus::RequiresCapture
+        ),
+        enums::IntentStatus::Succeeded
+        | enums::IntentStatus::Failed
+        | enums::IntentStatus::Cancelled
+        | enums::IntentStatus::Processing
+        | enums::IntentStatus::RequiresMerchantAction
+        | enums::IntentStatus::RequiresCapture
+        | enums::IntentStatus::PartiallyCaptured => false,
+    }
+}
+
+/// A bank transfer which is partially funded when its session expires is cancelled, returning the
+/// received amount to the customer, unless the merchant has chosen to accept partial payments
+fn should_accept_partial_bank_transfer(
+    current_status: enums::IntentStatus,
+    partial_bank_transfer_handling: Option<api::enums::PartialBankTransferHandling>,
+) -> bool {
+    current_status == enums::IntentStatus::RequiresCustomerAction
+        && partial_bank_transfer_handling == Some(api::enums::PartialBankTransferHandling::Accept)
+}
+
+/// Amount of a partially funded bank transfer which is refunded when the payment is cancelled on
+/// expiry, if the merchant has chosen to refund partial payments
+fn get_amount_to_refund_on_expiry(
+    current_status: enums::IntentStatus,
+    partial_bank_transfer_handling: Option<api::enums::PartialBankTransferHandling>,
+    partially_received_amount: Option<i64>,
+) -> Option<i64> {
+    partially_received_amount.filter(|_| {
+        current_status == enums::IntentStatus::RequiresCustomerAction
+            && partial_bank_transfer_handling
+                == Some(api::enums::PartialBankTransferHandling::Refund)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Cancels the expired payment with a cancel step returning the given status, and returns the
+    /// cancel requests and the webhooks which were sent
+    async fn cancel_expired_payment_with_status(
+        status: enums::IntentStatus,
+    ) -> (Vec<api::PaymentsCancelRequest>, Vec<api::PaymentsResponse>) {
+        let cancel_requests = Mutex::new(Vec::new());
+        let webhooks = Mutex::new(Vec::new());
+
+        cancel_expired_payment(
+            "pay_expired",
+            |cancel_request| async {
+                cancel_requests.lock().unwrap().push(cancel_request);
+                Ok(services::ApplicationResponse::JsonWithHeaders((
+                    api::PaymentsResponse {
+                        payment_id: Some("pay_expired".to_string()),
+                        status,
+                        ..Default::default()
+                    },
+                    vec![],
+                )))
+            },
+            |payments_response| async {
+                webhooks.lock().unwrap().push(payments_response);
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        (
+            cancel_requests.into_inner().unwrap(),
+            webhooks.into_inner().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_expired_payment_is_cancelled_with_expiry_reason() {
+        let (cancel_requests, _) =
+            cancel_expired_payment_with_status(enums::IntentStatus::Cancelled).await;
+
+        assert_eq!(cancel_requests.len(), 1);
+        assert_eq!(cancel_requests[0].payment_id, "pay_expired");
+        assert_eq!(
+            cancel_requests[0].cancellation_reason.as_deref(),
+            Some(consts::SESSION_EXPIRED_CANCELLATION_REASON)
+        );
+        assert_eq!(
+            cancel_requests[0].cancellation_reason_code,
+            Some(enums::CancellationReason::Expired)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_webhook_is_sent_once() {
+        let (_, webhooks) =
+            cancel_expired_payment_with_status(enums::IntentStatus::Cancelled).await;
+
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0].payment_id.as_deref(), Some("pay_expired"));
+        assert_eq!(webhooks[0].status, enums::IntentStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_no_webhook_is_sent_when_payment_is_not_cancelled() {
+        let (_, webhooks) =
+            cancel_expired_payment_with_status(enums::IntentStatus::Processing).await;
+
+        assert!(webhooks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expiry_task_is_scheduled_at_session_expiry() {
+        let mockdb = crate::db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let db: &dyn StorageInterface = &mockdb;
+        let session_expiry = common_utils::date_time::now()
+            .saturating_add(time::Duration::seconds(consts::MIN_SESSION_EXPIRY.into()));
+
+        let payment_intent = db
+            .insert_payment_intent(
+                storage::PaymentIntentNew {
+                    payment_id: "pay_expiring".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    status: enums::IntentStatus::RequiresPaymentMethod,
+                    active_attempt_id: "pay_expiring_1".to_string(),
+                    session_expiry: Some(session_expiry),
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        payment_flows::add_payment_expiry_task(db, &payment_intent)
+            .await
+            .unwrap();
+
+        let process = db
+            .find_process_by_id(&scheduler::utils::get_process_tracker_id(
+                "PAYMENTS_EXPIRY_WORKFLOW",
+                "PAYMENTS_EXPIRY",
+                "pay_expiring",
+                "merchant_1",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.runner.as_deref(), Some("PAYMENTS_EXPIRY_WORKFLOW"));
+        assert_eq!(process.schedule_time, Some(session_expiry));
+    }
+
+    #[test]
+    fn test_unconfirmed_payment_is_cancelled_on_expiry() {
+        assert!(should_cancel_expired_payment(
+            enums::IntentStatus::RequiresPaymentMethod,
+            enums::IntentStatus::RequiresPaymentMethod,
+        ));
+        assert!(should_cancel_expired_payment(
+            enums::IntentStatus::RequiresConfirmation,
+            enums::IntentStatus::RequiresConfirmation,
+        ));
+    }
+
+    #[test]
+    fn test_payment_authorized_after_expiry_is_cancelled() {
+        assert!(should_cancel_expired_payment(
+            enums::IntentStatus::RequiresCustomerAction,
+            enums::IntentStatus::RequiresCapture,
+        ));
+        assert!(!should_cancel_expired_payment(
+            enums::IntentStatus::RequiresCustomerAction,
+            enums::IntentStatus::Succeeded,
+        ));
+    }
+
+    #[test]
+    fn test_pending_bank_transfer_is_cancelled_on_expiry() {
+        assert!(should_cancel_expired_payment(
+            enums::IntentStatus::RequiresCustomerAction,
+            enums::IntentStatus::RequiresCustomerAction,
+        ));
+        assert!(!should_cancel_expired_payment(
+            enums::IntentStatus::RequiresCustomerAction,
+            enums::IntentStatus::Succeeded,
+        ));
+    }
+
+    #[test]
+    fn test_partial_bank_transfer_handling_on_expiry() {
+        assert!(should_accept_partial_bank_transfer(
+            enums::IntentStatus::RequiresCustomerAction,
+            Some(api::enums::PartialBankTransferHandling::Accept),
+        ));
+        assert!(!should_accept_partial_bank_transfer(
+            enums::IntentStatus::RequiresCustomerAction,
+            Some(api::enums::PartialBankTransferHandling::Refund),
+        ));
+        assert!(!should_accept_partial_bank_transfer(
+            enums::IntentStatus::RequiresCustomerAction,
+            None,
+        ));
+        assert!(!should_accept_partial_bank_transfer(
+            enums::IntentStatus::Succeeded,
+            Some(api::enums::PartialBankTransferHandling::Accept),
+        ));
+    }
+
+    #[test]
+    fn test_partially_received_amount_is_refunded_on_expiry() {
+        assert_eq!(
+            get_amount_to_refund_on_expiry(
+                enums::IntentStatus::RequiresCustomerAction,
+                Some(api::enums::PartialBankTransferHandling::Refund),
+                Some(600),
+            ),
+            Some(600)
+        );
+        assert_eq!(
+            get_amount_to_refund_on_expiry(
+                enums::IntentStatus::RequiresCustomerAction,
+                Some(api::enums::PartialBankTransferHandling::Accept),
+                Some(600),
+            ),
+            None
+        );
+        assert_eq!(
+            get_amount_to_refund_on_expiry(enums::IntentStatus::RequiresCustomerAction, None, None,),
+            None
+        );
+        assert_eq!(
+            get_amount_to_refund_on_expiry(
+                enums::IntentStatus::Succeeded,
+                Some(api::enums::PartialBankTransferHandling::Refund),
+                Some(600),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_confirmed_payment_is_not_cancelled_on_expiry() {
+        for status in [
+            enums::IntentStatus::Succeeded,
+            enums::IntentStatus::Processing,
+            enums::IntentStatus::RequiresCapture,
+            enums::IntentStatus::Cancelled,
+        ] {
+            assert!(!should_cancel_expired_payment(status, status));
+        }
+    }
+}
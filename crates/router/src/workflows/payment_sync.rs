@@ -96,6 +96,7 @@ impl ProcessTrackerWorkflow<AppState> for PaymentsSyncWorkflow {
                     db,
                     connector,
                     payment_data.payment_attempt.merchant_id,
+                    payment_data.payment_attempt.payment_method,
                     process,
                 )
                 .await?
@@ -118,13 +119,25 @@ pub async fn get_sync_process_schedule_time(
     db: &dyn StorageInterface,
     connector: &str,
     merchant_id: &str,
+    payment_method: Option<enums::PaymentMethod>,
     retry_count: i32,
 ) -> Result<Option<time::PrimitiveDateTime>, errors::ProcessTrackerError> {
+    // Bank transfers are completed by the customer outside the payment session, possibly days later
+    let (mapping_key, default_mapping) = match payment_method {
+        Some(enums::PaymentMethod::BankTransfer) => (
+            format!("pt_mapping_{connector}_bank_transfer"),
+            process_data::ConnectorPTMapping::long_horizon_default(),
+        ),
+        _ => (
+            format!("pt_mapping_{connector}"),
+            process_data::ConnectorPTMapping::default(),
+        ),
+    };
     let mapping: common_utils::errors::CustomResult<
         process_data::ConnectorPTMapping,
         errors::StorageError,
     > = db
-        .find_config_by_key(&format!("pt_mapping_{connector}"))
+        .find_config_by_key(&mapping_key)
         .await
         .map(|value| value.config)
         .and_then(|config| {
@@ -136,7 +149,7 @@ pub async fn get_sync_process_schedule_time(
         Ok(x) => x,
         Err(err) => {
             logger::info!("Redis Mapping Error: {}", err);
-            process_data::ConnectorPTMapping::default()
+            default_mapping
         }
     };
     let time_delta = utils::get_schedule_time(mapping, merchant_id, retry_count + 1);
@@ -148,10 +161,17 @@ pub async fn retry_sync_task(
     db: &dyn StorageInterface,
     connector: String,
     merchant_id: String,
+    payment_method: Option<enums::PaymentMethod>,
     pt: storage::ProcessTracker,
 ) -> Result<(), sch_errors::ProcessTrackerError> {
-    let schedule_time =
-        get_sync_process_schedule_time(db, &connector, &merchant_id, pt.retry_count).await?;
+    let schedule_time = get_sync_process_schedule_time(
+        db,
+        &connector,
+        &merchant_id,
+        payment_method,
+        pt.retry_count,
+    )
+    .await?;
 
     match schedule_time {
         Some(s_time) => pt.retry(db.as_scheduler(), s_time).await,
@@ -179,4 +199,16 @@ mod tests {
             vec![cpt_default.start_after, cpt_default.frequency[0]]
         );
     }
+
+    #[test]
+    fn test_long_horizon_schedule_spans_days() {
+        let mapping = process_data::ConnectorPTMapping::long_horizon_default;
+        let total_retries: i32 = mapping().default_mapping.count.iter().sum();
+        let total_delay: i32 = (1..=total_retries)
+            .map(|retry_count| utils::get_schedule_time(mapping(), "-", retry_count).unwrap())
+            .sum();
+
+        assert!(total_delay >= 7 * 24 * 60 * 60);
+        assert!(utils::get_schedule_time(mapping(), "-", total_retries + 1).is_none());
+    }
 }
@@ -0,0 +1,73 @@
+use common_utils::ext_traits::ValueExt;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{
+    consts, core::reports, db::StorageInterface, errors, routes::AppState, types::storage,
+};
+
+pub struct ReconciliationReportWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for ReconciliationReportWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: reports::ReconciliationReportTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("ReconciliationReportTrackingData")?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+            .await?;
+
+        reports::generate_reconciliation_report_file(state, &merchant_account, &tracking_data)
+            .await?;
+
+        if tracking_data.daily {
+            let next_tracking_data = reports::ReconciliationReportTrackingData {
+                file_id: common_utils::generate_id(consts::ID_LENGTH, "file"),
+                start_time: tracking_data.start_time.saturating_add(time::Duration::DAY),
+                end_time: tracking_data.end_time.saturating_add(time::Duration::DAY),
+                ..tracking_data
+            };
+            // The report of the following day is generated once that day has ended
+            let schedule_time = next_tracking_data
+                .end_time
+                .max(common_utils::date_time::now());
+            reports::add_reconciliation_report_task(db, next_tracking_data, schedule_time).await?;
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
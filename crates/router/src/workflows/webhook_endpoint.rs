@@ -0,0 +1,103 @@
+use common_utils::ext_traits::ValueExt;
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, SchedulerAppState,
+};
+
+use crate::{
+    core::webhook_endpoints::{self, WebhookEndpointActionOutcome},
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    types::storage,
+};
+
+pub struct WebhookEndpointWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for WebhookEndpointWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: webhook_endpoints::WebhookEndpointTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("WebhookEndpointTrackingData")?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let account = webhook_endpoints::get_webhook_endpoint_account_for_retry(
+            db,
+            &key_store,
+            &tracking_data,
+        )
+        .await?;
+
+        if let Some((account, merchant_connector_account)) = account {
+            let executor = webhook_endpoints::ConnectorIntegrationExecutor::new(
+                state,
+                &account.connector_name,
+            )?;
+            let outcome = webhook_endpoints::perform_webhook_endpoint_action(
+                state,
+                &executor,
+                &key_store,
+                &account,
+                merchant_connector_account,
+                tracking_data.action,
+            )
+            .await?;
+
+            if let WebhookEndpointActionOutcome::Failed { error_message } = outcome {
+                logger::warn!(
+                    "Retry to {:?} the webhook endpoint of merchant connector account {} failed: {error_message}",
+                    tracking_data.action,
+                    tracking_data.merchant_connector_id
+                );
+                return retry_webhook_endpoint_task(db, &tracking_data.merchant_id, process).await;
+            }
+        }
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(
+                state.get_db().as_scheduler(),
+                format!("COMPLETED_BY_PT_{id}"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+async fn retry_webhook_endpoint_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    pt: storage::ProcessTracker,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    match webhook_endpoints::get_webhook_endpoint_retry_schedule_time(merchant_id, pt.retry_count) {
+        Some(schedule_time) => pt.retry(db.as_scheduler(), schedule_time).await,
+        None => {
+            pt.finish_with_status(db.as_scheduler(), "RETRIES_EXCEEDED".to_string())
+                .await
+        }
+    }
+}
@@ -58,6 +58,8 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             browser_info: None,
             order_details: None,
             order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
@@ -68,6 +70,18 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: None,
+            external_authentication_data: None,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
@@ -92,6 +106,11 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: None,
+        authorized_amount: None,
     }
 }
 
@@ -124,6 +143,7 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
             connector_metadata: None,
             reason: None,
             connector_refund_id: None,
+            split_refund: None,
         },
         payment_method_id: None,
         response: Err(types::ErrorResponse::default()),
@@ -148,6 +168,11 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        locale: None,
+        poll_config: None,
+        raw_connector_response: None,
+        connector_proxy: None,
+        authorized_amount: None,
     }
 }
 
@@ -107,6 +107,7 @@ async fn should_sync_authorized_payment() {
                 encoded_data: None,
                 capture_method: Some(diesel_models::enums::CaptureMethod::Manual),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta: None,
             }),
             None,
@@ -221,6 +222,7 @@ async fn should_sync_auto_captured_payment() {
                 encoded_data: None,
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta: None,
             }),
             None,
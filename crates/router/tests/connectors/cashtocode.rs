@@ -55,6 +55,8 @@ impl CashtocodeTest {
             browser_info: None,
             order_details: None,
             order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
             email: None,
             payment_experience: None,
             payment_method_type,
@@ -65,6 +67,18 @@ impl CashtocodeTest {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: Some("John Doe".to_owned()),
+            external_authentication_data: None,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
         })
     }
 
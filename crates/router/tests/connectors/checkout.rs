@@ -3,6 +3,7 @@ use router::types::{self, api, storage::enums};
 
 use crate::{
     connector_auth,
+    replay::{ReplayApiClient, RequestMatcher},
     utils::{self, ConnectorActions},
 };
 #[derive(Clone, Copy)]
@@ -30,6 +31,14 @@ impl utils::Connector for CheckoutTest {
     fn get_name(&self) -> String {
         "checkout".to_string()
     }
+
+    fn get_api_client(&self) -> Box<dyn router::services::ApiClient> {
+        // The references of the payments and of the refunds are generated for every run
+        Box::new(ReplayApiClient::new(
+            self.get_name(),
+            RequestMatcher::new().ignore_uuids(),
+        ))
+    }
 }
 
 static CONNECTOR: CheckoutTest = CheckoutTest {};
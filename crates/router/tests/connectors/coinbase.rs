@@ -81,6 +81,8 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         browser_info: None,
         order_details: None,
         order_category: None,
+        shipping_method: None,
+        estimated_delivery_date: None,
         email: None,
         payment_experience: None,
         payment_method_type: None,
@@ -92,6 +94,18 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         complete_authorize_url: None,
         capture_method: None,
         customer_id: None,
+        external_authentication_data: None,
+        sca_exemption: None,
+        merchant_descriptor: None,
+        sub_merchant_data: None,
+        card_cvc: None,
+        split_payment: None,
+        amount_details: None,
+        request_partial_authorization: false,
+        metadata: None,
+        payment_channel: None,
+        decrypted_wallet_card: None,
+        device_session_id: None,
     })
 }
 
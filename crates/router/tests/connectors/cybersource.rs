@@ -63,10 +63,24 @@ fn get_default_payment_authorize_data() -> Option<types::PaymentsAuthorizeData>
     Some(types::PaymentsAuthorizeData {
         currency: storage::enums::Currency::USD,
         email: Some(Email::from_str("abc@gmail.com").unwrap()),
+        device_session_id: Some("dds_cybersource_test".to_string()),
         ..PaymentAuthorizeType::default().0
     })
 }
 #[actix_web::test]
+async fn should_fail_payment_without_device_session() {
+    let response = Cybersource {}
+        .make_payment(
+            Some(types::PaymentsAuthorizeData {
+                device_session_id: None,
+                ..get_default_payment_authorize_data().unwrap()
+            }),
+            get_default_payment_info(),
+        )
+        .await;
+    assert!(response.is_err());
+}
+#[actix_web::test]
 async fn should_only_authorize_payment() {
     let response = Cybersource {}
         .authorize_payment(
@@ -138,7 +138,7 @@ async fn should_refund_manually_captured_payment() {
         .unwrap();
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
@@ -159,7 +159,7 @@ async fn should_partially_refund_manually_captured_payment() {
         .unwrap();
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
@@ -236,7 +236,7 @@ async fn should_refund_auto_captured_payment() {
         .unwrap();
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
@@ -256,7 +256,7 @@ async fn should_partially_refund_succeeded_payment() {
         .unwrap();
     assert_eq!(
         refund_response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
@@ -441,4 +441,80 @@ async fn should_fail_for_refund_amount_higher_than_payment_amount() {
 
 // Connector dependent test cases goes here
 
+fn card_payment_details(card_number: &str, amount: i64) -> Option<types::PaymentsAuthorizeData> {
+    Some(types::PaymentsAuthorizeData {
+        amount,
+        payment_method_data: types::api::PaymentMethodData::Card(api::Card {
+            card_number: CardNumber::from_str(card_number).unwrap(),
+            ..utils::CCardType::default().0
+        }),
+        ..utils::PaymentAuthorizeType::default().0
+    })
+}
+
+// Creates a payment whose amount ends with 05, which is declined for insufficient funds.
+#[actix_web::test]
+async fn should_decline_payment_for_amount_ending_with_05() {
+    let response = CONNECTOR
+        .make_payment(
+            card_payment_details("4242424242424242", 1005),
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap_err().message,
+        "Payment declined: Insufficient funds".to_string(),
+    );
+}
+
+// Creates a payment with a 3DS card, which is redirected to the simulated authentication page.
+#[actix_web::test]
+async fn should_redirect_payment_for_3ds_card() {
+    let response = CONNECTOR
+        .make_payment(
+            card_payment_details("4000003800000446", 100),
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::AuthenticationPending);
+    let redirection_data = match response.response.unwrap() {
+        types::PaymentsResponseData::TransactionResponse {
+            redirection_data, ..
+        } => redirection_data,
+        _ => None,
+    };
+    assert!(redirection_data.is_some());
+}
+
+// Refunds a payment, the refund is pending until it is synchronized for the first time.
+#[actix_web::test]
+async fn should_succeed_refund_on_first_sync() {
+    let refund_response = CONNECTOR
+        .make_payment_and_refund(
+            card_payment_details("4242424242424242", 100),
+            None,
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap()
+        .response
+        .unwrap();
+    assert_eq!(refund_response.refund_status, enums::RefundStatus::Pending);
+
+    let response = CONNECTOR
+        .sync_refund(
+            refund_response.connector_refund_id,
+            None,
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Success,
+    );
+}
+
 // [#478]: add unit tests for non 3DS, wallets & webhooks in connector tests
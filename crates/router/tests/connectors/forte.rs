@@ -151,6 +151,7 @@ async fn should_sync_authorized_payment() {
                 encoded_data: None,
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta: None,
                 mandate_id: None,
             }),
@@ -43,6 +43,7 @@ mod paypal;
 mod payu;
 mod powertranz;
 mod rapyd;
+mod replay;
 mod shift4;
 mod square;
 mod stax;
@@ -76,6 +76,8 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
             product_name: "iphone 13".to_string(),
             quantity: 1,
             amount: 1000,
+            product_type: None,
+            commodity_code: None,
         }]),
         router_return_url: Some("https://hyperswitch.io".to_string()),
         webhook_url: Some("https://hyperswitch.io".to_string()),
@@ -370,6 +372,8 @@ async fn should_fail_payment_for_incorrect_cvc() {
                     product_name: "iphone 13".to_string(),
                     quantity: 1,
                     amount: 100,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 router_return_url: Some("https://hyperswitch.io".to_string()),
                 webhook_url: Some("https://hyperswitch.io".to_string()),
@@ -402,6 +406,8 @@ async fn should_fail_payment_for_invalid_exp_month() {
                     product_name: "iphone 13".to_string(),
                     quantity: 1,
                     amount: 100,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 router_return_url: Some("https://hyperswitch.io".to_string()),
                 webhook_url: Some("https://hyperswitch.io".to_string()),
@@ -434,6 +440,8 @@ async fn should_fail_payment_for_incorrect_expiry_year() {
                     product_name: "iphone 13".to_string(),
                     quantity: 1,
                     amount: 100,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 router_return_url: Some("https://hyperswitch.io".to_string()),
                 webhook_url: Some("https://hyperswitch.io".to_string()),
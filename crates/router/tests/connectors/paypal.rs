@@ -139,6 +139,7 @@ async fn should_sync_authorized_payment() {
                 encoded_data: None,
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta,
             }),
             get_default_payment_info(),
@@ -335,6 +336,7 @@ async fn should_sync_auto_captured_payment() {
                 encoded_data: None,
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta,
             }),
             get_default_payment_info(),
@@ -0,0 +1,541 @@
+//! Record and replay of the HTTP calls made to the connector sandboxes.
+//!
+//! The mode is selected with the `CONNECTOR_REPLAY` environment variable:
+//!
+//! - `record`: the requests are sent to the sandbox, and every request (with its body masked)
+//!   and its response are recorded to a fixture file of the test.
+//! - `replay`: no request is sent, the responses are replayed from the fixture file of the test
+//!   in the order they were recorded. Every request must match the recorded request, a request
+//!   which diverges fails the test with a diff of the recorded and the replayed request.
+//! - unset: the requests are sent to the sandbox, nothing is recorded.
+//!
+//! The fixture file of a test is `fixtures/<module>/<test>.json`, next to this file. Fields which
+//! change from one run to another, such as generated references or timestamps, are excluded from
+//! the comparison with a [`RequestMatcher`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use error_stack::{IntoReport, ResultExt};
+use masking::PeekInterface;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use router::{
+    core::errors::{ApiClientError, CustomResult},
+    routes::AppState,
+    services::{self, api::masked_logging, ApiClient, ContentType, Request},
+};
+use serde::{Deserialize, Serialize};
+
+const MODE_ENV: &str = "CONNECTOR_REPLAY";
+const PLACEHOLDER: &str = "<ignored>";
+
+static UUID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+        .unwrap()
+});
+static TIMESTAMP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:?\d{2})?").unwrap()
+});
+
+// Fixtures of the tests run in this process, keyed by the name of the test
+static CASSETTES: Lazy<Mutex<HashMap<String, Cassette>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplayMode {
+    Live,
+    Record,
+    Replay,
+}
+
+impl ReplayMode {
+    pub fn from_env() -> Self {
+        match std::env::var(MODE_ENV).as_deref() {
+            Ok("record") => Self::Record,
+            Ok("replay") => Self::Replay,
+            Ok("") | Err(_) => Self::Live,
+            Ok(mode) => panic!("Unknown {MODE_ENV} mode `{mode}`, expected `record` or `replay`"),
+        }
+    }
+}
+
+/// Excludes the parts of a request which change from one run to another from the comparison of
+/// a replayed request with the recorded request. Headers are never compared.
+#[derive(Clone, Debug, Default)]
+pub struct RequestMatcher {
+    ignored_fields: Vec<String>,
+    ignored_query_params: Vec<String>,
+    ignored_patterns: Vec<Regex>,
+}
+
+impl RequestMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignores the body fields with this name, at any depth of the body
+    pub fn ignore_field(mut self, name: impl Into<String>) -> Self {
+        self.ignored_fields.push(name.into());
+        self
+    }
+
+    /// Ignores the query parameter of the URL with this name
+    pub fn ignore_query_param(mut self, name: impl Into<String>) -> Self {
+        self.ignored_query_params.push(name.into());
+        self
+    }
+
+    /// Ignores the parts of the URL and of the body values which match the pattern
+    pub fn ignore_pattern(mut self, pattern: Regex) -> Self {
+        self.ignored_patterns.push(pattern);
+        self
+    }
+
+    /// Ignores UUIDs, such as the generated payment and attempt ids
+    pub fn ignore_uuids(self) -> Self {
+        self.ignore_pattern(UUID_REGEX.clone())
+    }
+
+    /// Ignores RFC 3339 and ISO 8601 timestamps
+    pub fn ignore_timestamps(self) -> Self {
+        self.ignore_pattern(TIMESTAMP_REGEX.clone())
+    }
+
+    fn redact(&self, value: &str) -> String {
+        self.ignored_patterns
+            .iter()
+            .fold(value.to_string(), |value, pattern| {
+                pattern.replace_all(&value, PLACEHOLDER).into_owned()
+            })
+    }
+
+    /// Flattens the request to the values it is compared on, keyed by their location in the
+    /// request: `method`, `url`, `url?<param>` and `body<JSON pointer>`
+    pub fn normalize(&self, request: &RecordedRequest) -> BTreeMap<String, String> {
+        let mut values = BTreeMap::new();
+        values.insert("method".to_string(), request.method.clone());
+
+        match reqwest::Url::parse(&request.url) {
+            Ok(mut url) => {
+                for (name, value) in url.query_pairs() {
+                    if !self
+                        .ignored_query_params
+                        .iter()
+                        .any(|ignored| *ignored == name)
+                    {
+                        values.insert(format!("url?{name}"), self.redact(&value));
+                    }
+                }
+                url.set_query(None);
+                values.insert("url".to_string(), self.redact(url.as_str()));
+            }
+            Err(_) => {
+                values.insert("url".to_string(), self.redact(&request.url));
+            }
+        }
+
+        if let Some(body) = request.body.as_deref() {
+            let parsed = if request.is_form_urlencoded {
+                serde_urlencoded::from_str::<Vec<(String, String)>>(body)
+                    .ok()
+                    .map(|fields| {
+                        serde_json::Value::Object(
+                            fields
+                                .into_iter()
+                                .map(|(name, value)| (name, serde_json::Value::String(value)))
+                                .collect(),
+                        )
+                    })
+            } else {
+                serde_json::from_str::<serde_json::Value>(body).ok()
+            };
+            match parsed {
+                Some(parsed) => self.flatten("body".to_string(), &parsed, &mut values),
+                None => {
+                    values.insert("body".to_string(), self.redact(body));
+                }
+            }
+        }
+
+        values
+    }
+
+    fn flatten(
+        &self,
+        path: String,
+        value: &serde_json::Value,
+        values: &mut BTreeMap<String, String>,
+    ) {
+        match value {
+            serde_json::Value::Object(members) => {
+                for (name, member) in members {
+                    if self.ignored_fields.iter().any(|ignored| ignored == name) {
+                        continue;
+                    }
+                    let name = name.replace('~', "~0").replace('/', "~1");
+                    self.flatten(format!("{path}/{name}"), member, values);
+                }
+            }
+            serde_json::Value::Array(elements) => {
+                for (index, element) in elements.iter().enumerate() {
+                    self.flatten(format!("{path}/{index}"), element, values);
+                }
+            }
+            serde_json::Value::String(value) => {
+                values.insert(path, self.redact(&format!("{value:?}")));
+            }
+            value => {
+                values.insert(path, value.to_string());
+            }
+        }
+    }
+
+    /// Lists the differences of the replayed request from the recorded request, `None` when the
+    /// requests match
+    pub fn diff(&self, recorded: &RecordedRequest, replayed: &RecordedRequest) -> Option<String> {
+        let recorded = self.normalize(recorded);
+        let replayed = self.normalize(replayed);
+
+        let mut paths = recorded.keys().chain(replayed.keys()).collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+
+        let differences = paths
+            .into_iter()
+            .filter_map(|path| match (recorded.get(path), replayed.get(path)) {
+                (Some(recorded), Some(replayed)) if recorded == replayed => None,
+                (recorded, replayed) => Some(format!(
+                    "  {path}:\n    - recorded: {}\n    + replayed: {}",
+                    recorded.map(String::as_str).unwrap_or("<missing>"),
+                    replayed.map(String::as_str).unwrap_or("<missing>"),
+                )),
+            })
+            .collect::<Vec<_>>();
+
+        (!differences.is_empty()).then(|| differences.join("\n"))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    /// The body with the sensitive fields of the connector masked
+    pub body: Option<String>,
+    #[serde(default)]
+    pub is_form_urlencoded: bool,
+}
+
+impl RecordedRequest {
+    fn from_request(connector_name: &str, request: &Request) -> Self {
+        let is_form_urlencoded = matches!(request.content_type, Some(ContentType::FormUrlEncoded));
+        Self {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            body: request.payload.as_ref().map(|payload| {
+                masked_logging::get_masked_body(
+                    connector_name,
+                    payload.peek().as_bytes(),
+                    is_form_urlencoded,
+                )
+            }),
+            is_form_urlencoded,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+impl RecordedResponse {
+    async fn from_response(response: reqwest::Response) -> CustomResult<Self, ApiClientError> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = response
+            .text()
+            .await
+            .into_report()
+            .change_context(ApiClientError::ResponseDecodingFailed)?;
+        Ok(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn to_response(&self) -> CustomResult<reqwest::Response, ApiClientError> {
+        let builder = self.headers.iter().fold(
+            http::Response::builder().status(self.status),
+            |builder, (name, value)| builder.header(name, value),
+        );
+        builder
+            .body(self.body.clone())
+            .into_report()
+            .change_context(ApiClientError::ResponseDecodingFailed)
+            .map(reqwest::Response::from)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+    #[serde(skip)]
+    cursor: usize,
+}
+
+fn get_test_name() -> String {
+    std::thread::current()
+        .name()
+        .map(str::to_string)
+        .expect("Connector calls must be made from the thread of the test")
+}
+
+fn get_fixture_path(test_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/connectors/fixtures")
+        .join(format!("{}.json", test_name.replace("::", "/")))
+}
+
+fn lock_cassettes() -> std::sync::MutexGuard<'static, HashMap<String, Cassette>> {
+    // A test failing on a diverged request must not fail the tests which run after it
+    CASSETTES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn record(test_name: &str, interaction: Interaction) {
+    let mut cassettes = lock_cassettes();
+    let cassette = cassettes.entry(test_name.to_string()).or_default();
+    cassette.interactions.push(interaction);
+
+    let path = get_fixture_path(test_name);
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).unwrap();
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&*cassette).unwrap()).unwrap();
+}
+
+fn next_interaction(test_name: &str) -> (usize, Option<Interaction>) {
+    let mut cassettes = lock_cassettes();
+    let cassette = cassettes.entry(test_name.to_string()).or_insert_with(|| {
+        let path = get_fixture_path(test_name);
+        let fixture = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "No fixture for the test `{test_name}` at {}, record it with {MODE_ENV}=record",
+                path.display()
+            )
+        });
+        serde_json::from_str(&fixture)
+            .unwrap_or_else(|error| panic!("Invalid fixture {}: {error}", path.display()))
+    });
+    let index = cassette.cursor;
+    cassette.cursor += 1;
+    (index, cassette.interactions.get(index).cloned())
+}
+
+/// Api client recording the connector calls to, or replaying them from, the fixture of the test
+/// in the [`ReplayMode`] set in the environment
+#[derive(Clone)]
+pub struct ReplayApiClient {
+    connector_name: String,
+    matcher: RequestMatcher,
+    request_id: Option<String>,
+}
+
+impl ReplayApiClient {
+    pub fn new(connector_name: impl Into<String>, matcher: RequestMatcher) -> Self {
+        Self {
+            connector_name: connector_name.into(),
+            matcher,
+            request_id: None,
+        }
+    }
+
+    fn replay(&self, request: &Request) -> CustomResult<reqwest::Response, ApiClientError> {
+        let test_name = get_test_name();
+        let replayed = RecordedRequest::from_request(&self.connector_name, request);
+        let (index, interaction) = next_interaction(&test_name);
+        let interaction = interaction.unwrap_or_else(|| {
+            panic!(
+                "The test `{test_name}` made request {} ({} {}), which is not in its fixture, \
+                 record it again with {MODE_ENV}=record",
+                index + 1,
+                replayed.method,
+                replayed.url
+            )
+        });
+        if let Some(diff) = self.matcher.diff(&interaction.request, &replayed) {
+            panic!(
+                "Request {} of the test `{test_name}` diverges from the recorded request in {}:\n{diff}",
+                index + 1,
+                get_fixture_path(&test_name).display()
+            );
+        }
+        interaction.response.to_response()
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiClient for ReplayApiClient {
+    fn request(
+        &self,
+        _method: reqwest::Method,
+        _url: String,
+    ) -> CustomResult<Box<dyn services::api::client::RequestBuilder>, ApiClientError> {
+        // Connector calls are made through `send_request` only
+        Err(ApiClientError::UnexpectedState.into())
+    }
+
+    fn request_with_certificate(
+        &self,
+        _method: reqwest::Method,
+        _url: String,
+        _certificate: Option<String>,
+        _certificate_key: Option<String>,
+    ) -> CustomResult<Box<dyn services::api::client::RequestBuilder>, ApiClientError> {
+        Err(ApiClientError::UnexpectedState.into())
+    }
+
+    async fn send_request(
+        &self,
+        state: &AppState,
+        request: Request,
+        option_timeout_secs: Option<u64>,
+        _forward_to_kafka: bool,
+    ) -> CustomResult<reqwest::Response, ApiClientError> {
+        match ReplayMode::from_env() {
+            ReplayMode::Live => services::send_request(state, request, option_timeout_secs).await,
+            ReplayMode::Record => {
+                let recorded = RecordedRequest::from_request(&self.connector_name, &request);
+                let response = RecordedResponse::from_response(
+                    services::send_request(state, request, option_timeout_secs).await?,
+                )
+                .await?;
+                record(
+                    &get_test_name(),
+                    Interaction {
+                        request: recorded,
+                        response: response.clone(),
+                    },
+                );
+                response.to_response()
+            }
+            ReplayMode::Replay => self.replay(&request),
+        }
+    }
+
+    fn add_request_id(&mut self, request_id: Option<String>) {
+        self.request_id = request_id;
+    }
+
+    fn get_request_id(&self) -> Option<String> {
+        self.request_id.clone()
+    }
+
+    fn add_merchant_id(&mut self, _merchant_id: Option<String>) {}
+
+    fn add_flow_name(&mut self, _flow_name: String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_request(url: &str, body: &str) -> RecordedRequest {
+        RecordedRequest {
+            method: "POST".to_string(),
+            url: url.to_string(),
+            body: Some(body.to_string()),
+            is_form_urlencoded: false,
+        }
+    }
+
+    #[test]
+    fn test_matcher_ignores_volatile_fields() {
+        let matcher = RequestMatcher::new()
+            .ignore_field("idempotency_key")
+            .ignore_query_param("timestamp")
+            .ignore_uuids();
+        let recorded = get_request(
+            "https://sandbox.example.com/payments?timestamp=1&mode=test",
+            r#"{"amount":100,"reference":"6b4f8a8e-4bd4-4d3a-9f0e-3ab9e2f0a1c7","idempotency_key":"a"}"#,
+        );
+        let replayed = get_request(
+            "https://sandbox.example.com/payments?mode=test&timestamp=2",
+            r#"{"idempotency_key":"b","reference":"0d2c3f1e-8a77-4e59-b1c4-5d6e7f809a1b","amount":100}"#,
+        );
+
+        assert_eq!(matcher.diff(&recorded, &replayed), None);
+    }
+
+    #[test]
+    fn test_diff_lists_the_diverged_values() {
+        let matcher = RequestMatcher::new();
+        let recorded = get_request(
+            "https://sandbox.example.com/payments",
+            r#"{"amount":100,"source":{"type":"card"}}"#,
+        );
+        let replayed = get_request(
+            "https://sandbox.example.com/payments",
+            r#"{"amount":50,"capture":true,"source":{}}"#,
+        );
+
+        assert_eq!(
+            matcher.diff(&recorded, &replayed).unwrap(),
+            [
+                "  body/amount:\n    - recorded: 100\n    + replayed: 50",
+                "  body/capture:\n    - recorded: <missing>\n    + replayed: true",
+                "  body/source/type:\n    - recorded: \"card\"\n    + replayed: <missing>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_matcher_compares_form_fields() {
+        let matcher = RequestMatcher::new().ignore_field("created");
+        let form_request = |body: &str| RecordedRequest {
+            is_form_urlencoded: true,
+            ..get_request("https://sandbox.example.com/charges", body)
+        };
+
+        assert_eq!(
+            matcher.diff(
+                &form_request("amount=100&created=1699999999"),
+                &form_request("created=1700000000&amount=100")
+            ),
+            None
+        );
+        assert_eq!(
+            matcher
+                .diff(
+                    &form_request("amount=100&currency=EUR"),
+                    &form_request("amount=100&currency=USD")
+                )
+                .unwrap(),
+            "  body/currency:\n    - recorded: \"EUR\"\n    + replayed: \"USD\""
+        );
+    }
+}
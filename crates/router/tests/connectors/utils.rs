@@ -2,6 +2,7 @@ use std::{fmt::Debug, marker::PhantomData, str::FromStr, time::Duration};
 
 use async_trait::async_trait;
 use common_utils::pii::Email;
+use data_models::payments::payment_attempt::PaymentAttemptInterface;
 use error_stack::Report;
 use masking::Secret;
 use router::{
@@ -9,7 +10,11 @@ use router::{
     core::{errors, errors::ConnectorError, payments, utils as core_utils},
     db::StorageImpl,
     routes, services,
-    types::{self, api, storage::enums, AccessToken, PaymentAddress, RouterData},
+    types::{
+        self, api,
+        storage::{self, enums},
+        AccessToken, PaymentAddress, RouterData,
+    },
 };
 use test_utils::connector_auth::ConnectorAuthType;
 use tokio::sync::oneshot;
@@ -29,6 +34,11 @@ pub trait Connector {
     fn get_payout_data(&self) -> Option<types::api::PayoutConnectorData> {
         None
     }
+    /// Api client the requests to the connector are sent with. The connectors whose tests are
+    /// recorded and replayed return a [`ReplayApiClient`](crate::replay::ReplayApiClient)
+    fn get_api_client(&self) -> Box<dyn services::ApiClient> {
+        Box::new(services::MockApiClient)
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -68,11 +78,85 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         integration.execute_pretasks(&mut request, &state).await?;
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
+    }
+
+    /// Polls the status of an authorization which the connector accepted without completing it
+    async fn poll_authorization(
+        &self,
+        router_data: types::PaymentsAuthorizeRouterData,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let integration = self.get_data().connector.get_connector_integration();
+        poll_connector(router_data, integration, self.get_api_client()).await
+    }
+
+    /// Polls the status of an authorization which the connector accepted without completing it, the
+    /// same as the payments do, returning the sync task which is scheduled for the attempt
+    async fn poll_authorization_with_sync_task(
+        &self,
+        router_data: types::PaymentsAuthorizeRouterData,
+    ) -> (
+        types::PaymentsAuthorizeRouterData,
+        Option<storage::ProcessTracker>,
+    ) {
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let state = routes::AppState::with_storage(
+            Settings::new().unwrap(),
+            StorageImpl::PostgresqlTest,
+            tx,
+            self.get_api_client(),
+        )
+        .await;
+        let payment_attempt = state
+            .store
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: router_data.payment_id.clone(),
+                    merchant_id: router_data.merchant_id.clone(),
+                    attempt_id: router_data.attempt_id.clone(),
+                    status: router_data.status,
+                    amount: router_data.request.amount,
+                    connector: Some(self.get_name()),
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let router_data = payments::poll_connector_operation(
+            &state,
+            &self.get_data(),
+            &payment_attempt,
+            router_data,
+            None,
+        )
+        .await;
+        let process_tracker_id = scheduler::utils::get_process_tracker_id(
+            "PAYMENTS_SYNC_WORKFLOW",
+            "PAYMENTS_SYNC",
+            &payment_attempt.attempt_id,
+            &payment_attempt.merchant_id,
+        );
+        let process = state
+            .store
+            .find_process_by_id(&process_tracker_id)
+            .await
+            .unwrap();
+        (router_data, process)
+    }
+
+    /// Polls the status of a refund which the connector accepted without processing it
+    async fn poll_refund(
+        &self,
+        router_data: types::RefundExecuteRouterData,
+    ) -> types::RefundExecuteRouterData {
+        let integration = self.get_data().connector.get_connector_integration();
+        poll_connector(router_data, integration, self.get_api_client()).await
     }
 
     async fn create_connector_customer(
@@ -92,11 +176,11 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         integration.execute_pretasks(&mut request, &state).await?;
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     async fn create_connector_pm_token(
@@ -116,11 +200,11 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         integration.execute_pretasks(&mut request, &state).await?;
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     /// For initiating payments when `CaptureMethod` is set to `Automatic`
@@ -144,11 +228,11 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         integration.execute_pretasks(&mut request, &state).await?;
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     async fn sync_payment(
@@ -161,7 +245,7 @@ pub trait ConnectorActions: Connector {
             payment_data.unwrap_or_else(|| PaymentSyncType::default().0),
             payment_info,
         );
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     /// will retry the psync till the given status matches or retry max 3 times
@@ -199,7 +283,7 @@ pub trait ConnectorActions: Connector {
             },
             payment_info,
         );
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     async fn authorize_and_capture_payment(
@@ -235,7 +319,7 @@ pub trait ConnectorActions: Connector {
             },
             payment_info,
         );
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     async fn authorize_and_void_payment(
@@ -272,7 +356,7 @@ pub trait ConnectorActions: Connector {
             },
             payment_info,
         );
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     async fn capture_payment_and_refund(
@@ -388,10 +472,11 @@ pub trait ConnectorActions: Connector {
                 connector_metadata: None,
                 reason: None,
                 connector_refund_id: Some(refund_id),
+                split_refund: None,
             }),
             payment_info,
         );
-        call_connector(request, integration).await
+        call_connector(request, integration, self.get_api_client()).await
     }
 
     /// will retry the rsync till the given status matches or retry max 3 times
@@ -514,6 +599,11 @@ pub trait ConnectorActions: Connector {
             connector_api_version: None,
             connector_http_status_code: None,
             apple_pay_flow: None,
+            locale: None,
+            poll_config: None,
+            raw_connector_response: None,
+            connector_proxy: None,
+            authorized_amount: None,
         }
     }
 
@@ -558,7 +648,7 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         connector_integration
@@ -597,7 +687,7 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         connector_integration
@@ -637,7 +727,7 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         connector_integration
@@ -677,7 +767,7 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         connector_integration
@@ -761,7 +851,7 @@ pub trait ConnectorActions: Connector {
             Settings::new().unwrap(),
             StorageImpl::PostgresqlTest,
             tx,
-            Box::new(services::MockApiClient),
+            self.get_api_client(),
         )
         .await;
         connector_integration
@@ -786,16 +876,12 @@ async fn call_connector<
 >(
     request: RouterData<T, Req, Resp>,
     integration: services::BoxedConnectorIntegration<'_, T, Req, Resp>,
+    api_client: Box<dyn services::ApiClient>,
 ) -> Result<RouterData<T, Req, Resp>, Report<ConnectorError>> {
     let conf = Settings::new().unwrap();
     let tx: oneshot::Sender<()> = oneshot::channel().0;
-    let state = routes::AppState::with_storage(
-        conf,
-        StorageImpl::PostgresqlTest,
-        tx,
-        Box::new(services::MockApiClient),
-    )
-    .await;
+    let state =
+        routes::AppState::with_storage(conf, StorageImpl::PostgresqlTest, tx, api_client).await;
     services::api::execute_connector_processing_step(
         &state,
         integration,
@@ -806,6 +892,22 @@ async fn call_connector<
     .await
 }
 
+async fn poll_connector<
+    T: Debug + Clone + 'static,
+    Req: Debug + Clone + 'static,
+    Resp: Debug + Clone + 'static,
+>(
+    request: RouterData<T, Req, Resp>,
+    integration: services::BoxedConnectorIntegration<'_, T, Req, Resp>,
+    api_client: Box<dyn services::ApiClient>,
+) -> RouterData<T, Req, Resp> {
+    let conf = Settings::new().unwrap();
+    let tx: oneshot::Sender<()> = oneshot::channel().0;
+    let state =
+        routes::AppState::with_storage(conf, StorageImpl::PostgresqlTest, tx, api_client).await;
+    services::api::polling::execute_connector_polling(&state, &integration, request).await
+}
+
 pub struct MockConfig {
     pub address: Option<String>,
     pub mocks: Vec<Mock>,
@@ -875,6 +977,8 @@ impl Default for PaymentAuthorizeType {
             browser_info: Some(BrowserInfoType::default().0),
             order_details: None,
             order_category: None,
+            shipping_method: None,
+            estimated_delivery_date: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
@@ -885,6 +989,18 @@ impl Default for PaymentAuthorizeType {
             complete_authorize_url: None,
             webhook_url: None,
             customer_id: None,
+            external_authentication_data: None,
+            sca_exemption: None,
+            merchant_descriptor: None,
+            sub_merchant_data: None,
+            card_cvc: None,
+            split_payment: None,
+            amount_details: None,
+            request_partial_authorization: false,
+            metadata: None,
+            payment_channel: None,
+            decrypted_wallet_card: None,
+            device_session_id: None,
         };
         Self(data)
     }
@@ -940,6 +1056,7 @@ impl Default for PaymentSyncType {
             encoded_data: None,
             capture_method: None,
             sync_type: types::SyncRequestType::SinglePaymentSync,
+            capture_id: None,
             connector_meta: None,
         };
         Self(data)
@@ -958,6 +1075,7 @@ impl Default for PaymentRefundType {
             connector_metadata: None,
             reason: Some("Customer returned product".to_string()),
             connector_refund_id: None,
+            split_refund: None,
         };
         Self(data)
     }
@@ -1018,6 +1136,10 @@ pub fn get_connector_metadata(
             connector_metadata,
             network_txn_id: _,
             connector_response_reference_id: _,
+            card_verification_results: None,
+            connector_fee: _,
+            auth_code: _,
+            acquirer_reference_number: _,
         }) => connector_metadata,
         _ => None,
     }
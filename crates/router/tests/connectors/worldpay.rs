@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use futures::future::OptionFuture;
 use router::types::{self, api, storage::enums};
 use serde_json::json;
@@ -213,10 +215,30 @@ async fn should_refund_succeeded_payment() {
         .refund_payment(transaction_id, None, None)
         .await
         .unwrap();
+
+    // The refund is accepted before it is processed, its events are polled for its outcome
     assert_eq!(
-        response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        response.response.clone().unwrap().refund_status,
+        enums::RefundStatus::Pending,
+    );
+    let poll_config = response.poll_config.clone().unwrap();
+    assert_eq!(
+        poll_config.url_source,
+        types::PollUrlSource::Url("/payments/events/654321".to_string())
     );
+    let response = connector
+        .poll_refund(types::RouterData {
+            poll_config: Some(types::PollConfig {
+                interval: Duration::from_millis(10),
+                ..poll_config
+            }),
+            ..response
+        })
+        .await;
+    assert!(response.poll_config.is_none());
+    let response = response.response.unwrap();
+    assert_eq!(response.refund_status, enums::RefundStatus::Success);
+    assert_eq!(response.connector_refund_id, "654321");
 }
 
 #[actix_web::test]
@@ -234,6 +256,125 @@ async fn should_sync_refund() {
     );
 }
 
+#[actix_web::test]
+#[serial]
+async fn should_poll_accepted_authorization_until_it_is_complete() {
+    let conn = Worldpay {};
+    let _mock = conn.start_server(get_polling_mock_config()).await;
+    let response = conn.authorize_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Pending);
+    assert_eq!(
+        utils::get_connector_transaction_id(response.response.clone()),
+        Some("778899".to_string())
+    );
+    let poll_config = response.poll_config.clone().unwrap();
+    assert_eq!(
+        poll_config.url_source,
+        types::PollUrlSource::Url("/payments/events/778899".to_string())
+    );
+
+    // The events are pending twice before the authorization is complete
+    let response = conn
+        .poll_authorization(types::RouterData {
+            poll_config: Some(types::PollConfig {
+                interval: Duration::from_millis(10),
+                ..poll_config
+            }),
+            ..response
+        })
+        .await;
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+    assert!(response.poll_config.is_none());
+    assert_eq!(
+        utils::get_connector_transaction_id(response.response),
+        Some("778899".to_string())
+    );
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_leave_authorization_pending_to_be_synced_after_the_polling_budget() {
+    let conn = Worldpay {};
+    let _mock = conn.start_server(get_polling_mock_config()).await;
+    let response = conn.authorize_payment(None, None).await.unwrap();
+    let poll_config = response.poll_config.clone().unwrap();
+    let (response, sync_task) = conn
+        .poll_authorization_with_sync_task(types::RouterData {
+            poll_config: Some(types::PollConfig {
+                interval: Duration::from_millis(10),
+                max_attempts: 2,
+                ..poll_config
+            }),
+            ..response
+        })
+        .await;
+
+    // The operation is handed off to the scheduler, which syncs it once the polling budget is over
+    assert_eq!(response.status, enums::AttemptStatus::Pending);
+    assert!(response.poll_config.is_some());
+    let sync_task = sync_task.unwrap();
+    assert_eq!(sync_task.runner.as_deref(), Some("PAYMENTS_SYNC_WORKFLOW"));
+    assert_eq!(sync_task.name.as_deref(), Some("PAYMENTS_SYNC"));
+    assert_eq!(sync_task.status, enums::ProcessTrackerStatus::New);
+    assert!(sync_task.schedule_time.unwrap() > sync_task.created_at);
+    assert_eq!(
+        sync_task.tracking_data["resource_id"]["PaymentAttemptId"],
+        json!(response.attempt_id)
+    );
+    let txn_id = utils::get_connector_transaction_id(response.response).unwrap();
+    let response = conn
+        .sync_payment(
+            Some(types::PaymentsSyncData {
+                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(txn_id),
+                ..Default::default()
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+}
+
+fn get_polling_mock_config() -> MockConfig {
+    let accepted = json!({
+        "_links": {
+            "payments:events": {
+                "href": "/payments/events/778899"
+            },
+            "curies": [
+                {
+                    "name": "payments",
+                    "href": "/rels/payments/{rel}",
+                    "templated": true
+                }
+            ]
+        }
+    });
+    let pending = json!({
+        "lastEvent": "sentForAuthorization"
+    });
+    let authorized = json!({
+        "lastEvent": "authorized"
+    });
+
+    MockConfig {
+        address: Some("127.0.0.1:9090".to_string()),
+        mocks: vec![
+            Mock::given(method("POST"))
+                .and(path("/payments/authorizations".to_string()))
+                .respond_with(ResponseTemplate::new(202).set_body_json(accepted)),
+            // The first mock which matches is used, until it is exhausted
+            Mock::given(method("GET"))
+                .and(path("/payments/events/778899".to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(pending))
+                .up_to_n_times(2),
+            Mock::given(method("GET"))
+                .and(path("/payments/events/778899".to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(authorized)),
+        ],
+    }
+}
+
 fn get_mock_config() -> MockConfig {
     let authorized = json!({
         "outcome": "authorized",
@@ -286,11 +427,11 @@ fn get_mock_config() -> MockConfig {
     let partial_refund = json!({
         "_links": {
             "payments:events": {
-                "href": "https://try.access.worldpay.com/payments/events/eyJrIjoiazNhYjYzMiJ9"
+                "href": "/payments/events/654321"
             },
             "curies": [{
                 "name": "payments",
-                "href": "https://try.access.worldpay.com/rels/payments/{rel}",
+                "href": "/rels/payments/{rel}",
                 "templated": true
             }]
         }
@@ -100,6 +100,7 @@ async fn should_sync_authorized_payment() {
                 encoded_data: None,
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta: None,
                 mandate_id: None,
             }),
@@ -214,6 +215,7 @@ async fn should_sync_auto_captured_payment() {
                 encoded_data: None,
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
+                capture_id: None,
                 connector_meta: None,
                 mandate_id: None,
             }),
@@ -313,6 +315,8 @@ async fn should_fail_payment_for_incorrect_card_number() {
                     product_name: "test".to_string(),
                     quantity: 1,
                     amount: 1000,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 email: Some(Email::from_str("test@gmail.com").unwrap()),
                 webhook_url: Some("https://1635-116-74-253-164.ngrok-free.app".to_string()),
@@ -348,6 +352,8 @@ async fn should_fail_payment_for_incorrect_cvc() {
                     product_name: "test".to_string(),
                     quantity: 1,
                     amount: 1000,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 email: Some(Email::from_str("test@gmail.com").unwrap()),
                 webhook_url: Some("https://1635-116-74-253-164.ngrok-free.app".to_string()),
@@ -383,6 +389,8 @@ async fn should_fail_payment_for_invalid_exp_month() {
                     product_name: "test".to_string(),
                     quantity: 1,
                     amount: 1000,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 email: Some(Email::from_str("test@gmail.com").unwrap()),
                 webhook_url: Some("https://1635-116-74-253-164.ngrok-free.app".to_string()),
@@ -418,6 +426,8 @@ async fn should_fail_payment_for_incorrect_expiry_year() {
                     product_name: "test".to_string(),
                     quantity: 1,
                     amount: 1000,
+                    product_type: None,
+                    commodity_code: None,
                 }]),
                 email: Some(Email::from_str("test@gmail.com").unwrap()),
                 webhook_url: Some("https://1635-116-74-253-164.ngrok-free.app".to_string()),
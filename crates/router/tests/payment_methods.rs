@@ -0,0 +1,101 @@
+#![allow(clippy::expect_used, clippy::unwrap_in_result, clippy::unwrap_used)]
+
+mod utils;
+
+use actix_web::test::{call_and_read_body_json, call_service, read_body_json, TestRequest};
+use serde_json::{json, Value};
+use utils::{mk_service, ApiKey, AppClient, MerchantId, PublishableKey};
+
+/// Whether any `eligible_connectors` list of the response is non empty
+fn has_eligible_connectors(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.iter().any(|(key, value)| {
+            (key == "eligible_connectors"
+                && value.as_array().map_or(false, |list| !list.is_empty()))
+                || has_eligible_connectors(value)
+        }),
+        Value::Array(list) => list.iter().any(has_eligible_connectors),
+        _ => false,
+    }
+}
+
+/// 1) Create a merchant account, an API key and a connector
+/// 2) Create a payment with the API key
+/// 3) List the payment methods with the publishable key and the client secret of the payment
+/// 4) No connector of the merchant is returned to the client
+#[actix_web::test]
+async fn list_payment_methods_with_publishable_key_leaves_out_connectors() {
+    let server = mk_service().await;
+    let client = AppClient::guest();
+    let admin_client = client.admin("test_admin");
+
+    let hlist_pat![merchant_id, publishable_key]: HList![MerchantId, PublishableKey] =
+        admin_client.create_merchant_account(&server, None).await;
+
+    let request = TestRequest::post()
+        .uri(&format!("/api_keys/{}", *merchant_id))
+        .append_header(("api-key", "test_admin"))
+        .set_json(json!({
+            "name": "Payment methods list test key",
+            "expiration": "never"
+        }))
+        .to_request();
+    let api_key: ApiKey = call_and_read_body_json(&server, request).await;
+
+    let request = TestRequest::post()
+        .uri(&format!("/account/{}/connectors", *merchant_id))
+        .append_header(("api-key", "test_admin"))
+        .set_json(json!({
+            "connector_type": "fiz_operations",
+            "connector_name": "stripe",
+            "connector_account_details": {
+                "auth_type": "HeaderKey",
+                "api_key": "sk_test_key"
+            },
+            "payment_methods_enabled": [
+                {
+                    "payment_method": "card",
+                    "payment_method_types": [
+                        {
+                            "payment_method_type": "credit",
+                            "card_networks": ["Visa"],
+                            "minimum_amount": 1,
+                            "maximum_amount": 68607706,
+                            "recurring_enabled": true,
+                            "installment_payment_enabled": true
+                        }
+                    ]
+                }
+            ]
+        }))
+        .to_request();
+    let _connector: Value = call_and_read_body_json(&server, request).await;
+
+    let request = TestRequest::post()
+        .uri("/payments")
+        .append_header(("api-key", api_key.as_str()))
+        .set_json(json!({
+            "amount": 100,
+            "currency": "USD",
+            "confirm": false
+        }))
+        .to_request();
+    let payment: Value = call_and_read_body_json(&server, request).await;
+    let client_secret = payment["client_secret"].as_str().unwrap();
+
+    let request = TestRequest::get()
+        .uri(&format!(
+            "/account/payment_methods?client_secret={client_secret}"
+        ))
+        .append_header(("api-key", publishable_key.as_str()))
+        .to_request();
+    let response = call_service(&server, request).await;
+
+    assert!(response.status().is_success());
+    let payment_methods: Value = read_body_json(response).await;
+    assert!(!payment_methods["payment_methods"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+    assert!(!has_eligible_connectors(&payment_methods));
+}
@@ -365,7 +365,7 @@ async fn payments_create_core() {
             state,
             merchant_account,
             key_store,
-            payments::PaymentCreate,
+            payments::PaymentCreate::default(),
             req,
             services::AuthFlow::Merchant,
             payments::CallConnectorAction::Trigger,
@@ -535,7 +535,7 @@ async fn payments_create_core_adyen_no_redirect() {
             state,
             merchant_account,
             key_store,
-            payments::PaymentCreate,
+            payments::PaymentCreate::default(),
             req,
             services::AuthFlow::Merchant,
             payments::CallConnectorAction::Trigger,
@@ -545,3 +545,27 @@ async fn payments_create_core_adyen_no_redirect() {
         .unwrap();
     assert_eq!(expected_response, actual_response);
 }
+
+#[actix_web::test]
+async fn payments_device_data_with_publishable_key() {
+    use actix_web::{http::StatusCode, test};
+    use utils::{AppClient, MerchantId, PublishableKey};
+
+    let server = utils::mk_service().await;
+    let admin_client = AppClient::guest().admin("test_admin");
+
+    let hlist_pat![_merchant_id, publishable_key]: HList![MerchantId, PublishableKey] =
+        admin_client.create_merchant_account(&server, None).await;
+
+    let request = test::TestRequest::post()
+        .uri("/payments/device_data")
+        .append_header(("api-key", publishable_key.as_str()))
+        .set_json(serde_json::json!({
+            "client_secret": "pay_unknown_secret_0123456789",
+        }))
+        .to_request();
+    let response = test::call_service(&server, request).await;
+
+    // The publishable key is accepted for the flow, the payment of the client secret not existing
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -125,7 +125,7 @@ async fn payments_create_core() {
             state,
             merchant_account,
             key_store,
-            payments::PaymentCreate,
+            payments::PaymentCreate::default(),
             req,
             services::AuthFlow::Merchant,
             payments::CallConnectorAction::Trigger,
@@ -297,7 +297,7 @@ async fn payments_create_core_adyen_no_redirect() {
             state,
             merchant_account,
             key_store,
-            payments::PaymentCreate,
+            payments::PaymentCreate::default(),
             req,
             services::AuthFlow::Merchant,
             payments::CallConnectorAction::Trigger,
@@ -400,6 +400,11 @@ pub struct Message {
     message: String,
 }
 
+#[derive(Deserialize, Deref)]
+pub struct PublishableKey {
+    publishable_key: String,
+}
+
 #[derive(Deserialize, Deref)]
 pub struct PaymentId {
     payment_id: String,
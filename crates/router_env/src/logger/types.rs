@@ -52,7 +52,7 @@ pub enum Tag {
 }
 
 /// API Flow
-#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[derive(Debug, Display, Clone, PartialEq, Eq, EnumString)]
 pub enum Flow {
     /// Merchants account create flow.
     MerchantsAccountCreate,
@@ -72,6 +72,40 @@ pub enum Flow {
     MerchantConnectorsDelete,
     /// Merchant Connectors list flow.
     MerchantConnectorsList,
+    /// Merchant Connectors verify flow.
+    MerchantConnectorsVerify,
+    /// Merchant Connectors credential rotation finalize flow.
+    MerchantConnectorsCredentialsFinalize,
+    /// Merchant Connectors circuit breaker status retrieve flow.
+    MerchantConnectorsCircuitBreakerRetrieve,
+    /// Merchant Connectors template export flow.
+    MerchantConnectorsTemplateExport,
+    /// Merchant Connectors template import flow.
+    MerchantConnectorsTemplateImport,
+    /// Connector metadata schema retrieve flow.
+    ConnectorMetadataSchemaRetrieve,
+    /// Connector capabilities retrieve flow.
+    ConnectorCapabilitiesRetrieve,
+    /// Connector maintenance windows list flow.
+    ConnectorMaintenanceWindowsList,
+    /// Connector maintenance window create flow.
+    ConnectorMaintenanceWindowCreate,
+    /// Connector maintenance window delete flow.
+    ConnectorMaintenanceWindowDelete,
+    /// 3DS decision rules retrieve flow.
+    ThreeDsDecisionRulesRetrieve,
+    /// 3DS decision rules update flow.
+    ThreeDsDecisionRulesUpdate,
+    /// 3DS decision rules delete flow.
+    ThreeDsDecisionRulesDelete,
+    /// Audit events list flow.
+    AuditEventsList,
+    /// Mandate migration create flow.
+    MandateMigrationCreate,
+    /// Mandate migration retrieve flow.
+    MandateMigrationRetrieve,
+    /// Webhook event preview flow.
+    WebhookEventPreview,
     /// ConfigKey create flow.
     ConfigKeyCreate,
     /// ConfigKey fetch flow.
@@ -102,6 +136,10 @@ pub enum Flow {
     PaymentMethodsCreate,
     /// Payment methods list flow.
     PaymentMethodsList,
+    /// Payment methods required fields list flow.
+    PaymentMethodsRequiredFields,
+    /// Payment methods bank list flow.
+    PaymentMethodsBankList,
     /// Customer payment methods list flow.
     CustomerPaymentMethodsList,
     /// Payment methods retrieve flow.
@@ -128,10 +166,20 @@ pub enum Flow {
     PaymentsReject,
     /// Payments Session Token flow
     PaymentsSessionToken,
+    /// Payments device data collection flow.
+    PaymentsDeviceData,
     /// Payments start flow.
     PaymentsStart,
     /// Payments list flow.
     PaymentsList,
+    /// Payments routing decisions summary flow.
+    PaymentsRoutingDecisionsSummary,
+    /// Payments eligible connectors flow.
+    PaymentsEligibleConnectors,
+    /// Payment attempts list flow.
+    PaymentAttemptsList,
+    /// Payments recurring mandate charge flow.
+    PaymentsRecurringCharge,
     #[cfg(feature = "payouts")]
     /// Payouts create flow
     PayoutsCreate,
@@ -159,6 +207,10 @@ pub enum Flow {
     RefundsUpdate,
     /// Refunds list flow.
     RefundsList,
+    /// Refunds bulk create flow.
+    RefundsBulkCreate,
+    /// Refunds bulk retrieve flow.
+    RefundsBulkRetrieve,
     /// Incoming Webhook Receive
     IncomingWebhookReceive,
     /// Validate payment method flow
@@ -173,6 +225,16 @@ pub enum Flow {
     ApiKeyRevoke,
     /// API Key list flow
     ApiKeyList,
+    /// Blocklist entry create flow.
+    BlocklistCreate,
+    /// Blocklist entry retrieve flow.
+    BlocklistRetrieve,
+    /// Blocklist entry update flow.
+    BlocklistUpdate,
+    /// Blocklist entry delete flow.
+    BlocklistDelete,
+    /// Blocklist entry list flow.
+    BlocklistList,
     /// Dispute Retrieve flow
     DisputesRetrieve,
     /// Dispute List flow
@@ -207,6 +269,10 @@ pub enum Flow {
     BusinessProfileList,
     /// Different verification flows
     Verification,
+    /// Retrieve the reconciliation report of a time range
+    ReconciliationReportRetrieve,
+    /// Schedule the generation of a reconciliation report into a file
+    ReconciliationReportSchedule,
 }
 
 ///
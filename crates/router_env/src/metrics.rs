@@ -63,3 +63,23 @@ macro_rules! histogram_metric {
         > = once_cell::sync::Lazy::new(|| $meter.f64_histogram($description).init());
     };
 }
+
+/// Create an [`UpDownCounter`][UpDownCounter] metric with the specified name and an optional
+/// description, associated with the specified meter. Note that the meter must be to a valid
+/// [`Meter`][Meter]. Can be used as a gauge for values which go up and down, like in-flight requests.
+///
+/// [UpDownCounter]: opentelemetry::metrics::UpDownCounter
+/// [Meter]: opentelemetry::metrics::Meter
+#[macro_export]
+macro_rules! up_down_counter_metric {
+    ($name:ident, $meter:ident) => {
+        pub(crate) static $name: once_cell::sync::Lazy<
+            $crate::opentelemetry::metrics::UpDownCounter<i64>,
+        > = once_cell::sync::Lazy::new(|| $meter.i64_up_down_counter(stringify!($name)).init());
+    };
+    ($name:ident, $meter:ident, $description:literal) => {
+        pub(crate) static $name: once_cell::sync::Lazy<
+            $crate::opentelemetry::metrics::UpDownCounter<i64>,
+        > = once_cell::sync::Lazy::new(|| $meter.i64_up_down_counter($description).init());
+    };
+}
@@ -32,6 +32,22 @@ impl Default for ConnectorPTMapping {
     }
 }
 
+impl ConnectorPTMapping {
+    /// Mapping for tasks which could take days to complete, retried hourly for the first day
+    /// and every six hours for the next six days
+    pub fn long_horizon_default() -> Self {
+        Self {
+            custom_merchant_mapping: HashMap::new(),
+            default_mapping: RetryMapping {
+                start_after: 3600,
+                frequency: vec![3600, 21600],
+                count: vec![24, 24],
+            },
+            max_retries_count: 48,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentMethodsPTMapping {
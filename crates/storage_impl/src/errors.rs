@@ -232,6 +232,8 @@ pub enum ApiClientError {
     HeaderMapConstructionFailed,
     #[error("Invalid proxy configuration")]
     InvalidProxyConfiguration,
+    #[error("Proxy of connector {0} is not reachable")]
+    ProxyUnreachable(String),
     #[error("Client construction failed")]
     ClientConstructionFailed,
     #[error("Certificate decode failed")]
@@ -250,9 +252,17 @@ pub enum ApiClientError {
 
     #[error("Server responded with Request Timeout")]
     RequestTimeoutReceived,
+    #[error("Request to the connector timed out at the {stage:?} stage")]
+    RequestTimedOut { stage: TimeoutStage },
 
     #[error("connection closed before a message could complete")]
     ConnectionClosed,
+    #[error("Failed to resolve the address of the connector: {0}")]
+    DnsResolutionFailed(String),
+    #[error("Connection refused by the connector: {0}")]
+    ConnectionRefused(String),
+    #[error("TLS handshake with the connector failed: {0}")]
+    TlsHandshakeFailed(String),
 
     #[error("Server responded with Internal Server Error")]
     InternalServerErrorReceived,
@@ -266,13 +276,43 @@ pub enum ApiClientError {
     UnexpectedServerResponse,
 }
 
+/// Stage of the connector call at which the request timed out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutStage {
+    /// The connection could not be established in time, the request was not sent to the connector
+    Connect,
+    /// The connector did not respond in time, the request may have been processed by the connector
+    Read,
+}
+
 impl ApiClientError {
     pub fn is_upstream_timeout(&self) -> bool {
-        self == &Self::RequestTimeoutReceived
+        matches!(
+            self,
+            Self::RequestTimeoutReceived | Self::RequestTimedOut { .. }
+        )
     }
     pub fn is_connection_closed(&self) -> bool {
         self == &Self::ConnectionClosed
     }
+    /// Errors which indicate that the connector could not be reached or did not process the request,
+    /// as opposed to errors in building the request or handling the response
+    pub fn is_connector_unavailable(&self) -> bool {
+        matches!(
+            self,
+            Self::RequestNotSent(_)
+                | Self::RequestTimeoutReceived
+                | Self::RequestTimedOut { .. }
+                | Self::ConnectionClosed
+                | Self::DnsResolutionFailed(_)
+                | Self::ConnectionRefused(_)
+                | Self::TlsHandshakeFailed(_)
+                | Self::InternalServerErrorReceived
+                | Self::BadGatewayReceived
+                | Self::ServiceUnavailableReceived
+                | Self::GatewayTimeoutReceived
+        )
+    }
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
@@ -40,6 +40,16 @@ pub struct MockDb {
     pub merchant_key_store: Arc<Mutex<Vec<crate::store::merchant_key_store::MerchantKeyStore>>>,
     pub business_profiles: Arc<Mutex<Vec<crate::store::business_profile::BusinessProfile>>>,
     pub reverse_lookups: Arc<Mutex<Vec<store::ReverseLookup>>>,
+    pub blocklists: Arc<Mutex<Vec<store::Blocklist>>>,
+    pub bulk_refunds: Arc<Mutex<Vec<store::BulkRefund>>>,
+    pub audit_events: Arc<Mutex<Vec<store::AuditEvent>>>,
+    pub alerts: Arc<Mutex<Vec<store::Alert>>>,
+    pub mandate_migrations: Arc<Mutex<Vec<store::MandateMigration>>>,
+    pub webhook_deliveries: Arc<Mutex<Vec<store::WebhookDelivery>>>,
+    pub idempotency_keys: Arc<Mutex<Vec<store::IdempotencyKey>>>,
+    pub connector_response_captures: Arc<Mutex<Vec<store::ConnectorResponseCapture>>>,
+    pub payment_attempt_step_events: Arc<Mutex<Vec<store::PaymentAttemptStepEvent>>>,
+    pub payment_routing_decisions: Arc<Mutex<Vec<store::PaymentRoutingDecision>>>,
 }
 
 impl MockDb {
@@ -72,6 +82,16 @@ impl MockDb {
             merchant_key_store: Default::default(),
             business_profiles: Default::default(),
             reverse_lookups: Default::default(),
+            blocklists: Default::default(),
+            bulk_refunds: Default::default(),
+            audit_events: Default::default(),
+            alerts: Default::default(),
+            mandate_migrations: Default::default(),
+            webhook_deliveries: Default::default(),
+            idempotency_keys: Default::default(),
+            connector_response_captures: Default::default(),
+            payment_attempt_step_events: Default::default(),
+            payment_routing_decisions: Default::default(),
         })
     }
 }
@@ -1,4 +1,4 @@
-use api_models::enums::{Connector, PaymentMethod};
+use api_models::enums::{AttemptStatus, Connector, PaymentMethod};
 use common_utils::errors::CustomResult;
 use data_models::{
     errors::StorageError,
@@ -7,6 +7,7 @@ use data_models::{
     },
     MerchantStorageScheme,
 };
+use error_stack::IntoReport;
 
 use super::MockDb;
 use crate::DataModelExt;
@@ -15,13 +16,25 @@ use crate::DataModelExt;
 impl PaymentAttemptInterface for MockDb {
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        self.payment_attempts
+            .lock()
+            .await
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.payment_id == payment_id
+                    && payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No payment attempt found for attempt id = {attempt_id}"
+            )))
+            .into_report()
     }
 
     async fn get_filters_for_payments(
@@ -47,12 +60,23 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn find_payment_attempt_by_attempt_id_merchant_id(
         &self,
-        _attempt_id: &str,
-        _merchant_id: &str,
+        attempt_id: &str,
+        merchant_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        self.payment_attempts
+            .lock()
+            .await
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.attempt_id == attempt_id
+                    && payment_attempt.merchant_id == merchant_id
+            })
+            .cloned()
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No payment attempt found for attempt id = {attempt_id}"
+            )))
+            .into_report()
     }
 
     async fn find_payment_attempt_by_preprocessing_id_merchant_id(
@@ -67,22 +91,66 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn find_payment_attempt_by_merchant_id_connector_txn_id(
         &self,
-        _merchant_id: &str,
-        _connector_txn_id: &str,
+        merchant_id: &str,
+        connector_txn_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        self.payment_attempts
+            .lock()
+            .await
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.connector_transaction_id.as_deref() == Some(connector_txn_id)
+            })
+            .cloned()
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No payment attempt found for connector transaction id = {connector_txn_id}"
+            )))
+            .into_report()
+    }
+
+    async fn find_payment_attempt_by_merchant_id_acquirer_reference_number(
+        &self,
+        merchant_id: &str,
+        acquirer_reference_number: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, StorageError> {
+        self.payment_attempts
+            .lock()
+            .await
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.acquirer_reference_number.as_deref()
+                        == Some(acquirer_reference_number)
+            })
+            .cloned()
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No payment attempt found for acquirer reference number = {acquirer_reference_number}"
+            )))
+            .into_report()
     }
 
     async fn find_attempts_by_merchant_id_payment_id(
         &self,
-        _merchant_id: &str,
-        _payment_id: &str,
+        merchant_id: &str,
+        payment_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<PaymentAttempt>, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let mut payment_attempts: Vec<PaymentAttempt> = self
+            .payment_attempts
+            .lock()
+            .await
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.payment_id == payment_id
+            })
+            .cloned()
+            .collect();
+        payment_attempts.sort_by_key(|payment_attempt| payment_attempt.created_at);
+        Ok(payment_attempts)
     }
 
     #[allow(clippy::panic)]
@@ -123,7 +191,7 @@ impl PaymentAttemptInterface for MockDb {
             cancellation_reason: payment_attempt.cancellation_reason,
             amount_to_capture: payment_attempt.amount_to_capture,
             mandate_id: None,
-            browser_info: None,
+            browser_info: payment_attempt.browser_info,
             payment_token: None,
             error_code: payment_attempt.error_code,
             connector_metadata: None,
@@ -139,6 +207,25 @@ impl PaymentAttemptInterface for MockDb {
             connector_response_reference_id: None,
             amount_capturable: payment_attempt.amount_capturable,
             surcharge_metadata: payment_attempt.surcharge_metadata,
+            external_authentication_data: payment_attempt.external_authentication_data,
+            card_verification_results: payment_attempt.card_verification_results,
+            three_ds_decision_data: payment_attempt.three_ds_decision_data,
+            routing_metadata: payment_attempt.routing_metadata,
+            merchant_connector_id: payment_attempt.merchant_connector_id,
+            authorized_amount: payment_attempt.authorized_amount,
+            locale: payment_attempt.locale,
+            connector_fee_amount: payment_attempt.connector_fee_amount,
+            fee_currency: payment_attempt.fee_currency,
+            net_amount: payment_attempt.net_amount,
+            merchant_descriptor: payment_attempt.merchant_descriptor,
+            sub_merchant_data: payment_attempt.sub_merchant_data,
+            auth_code: payment_attempt.auth_code,
+            acquirer_reference_number: payment_attempt.acquirer_reference_number,
+            authorized_at: payment_attempt.authorized_at,
+            authorization_expires_at: payment_attempt.authorization_expires_at,
+            cancellation_reason_code: payment_attempt.cancellation_reason_code,
+            livemode: payment_attempt.livemode,
+            redacted_at: payment_attempt.redacted_at,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -179,8 +266,6 @@ impl PaymentAttemptInterface for MockDb {
         Err(StorageError::MockDbError)?
     }
 
-    // safety: only used for testing
-    #[allow(clippy::unwrap_used)]
     async fn find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
         &self,
         payment_id: &str,
@@ -189,13 +274,46 @@ impl PaymentAttemptInterface for MockDb {
     ) -> CustomResult<PaymentAttempt, StorageError> {
         let payment_attempts = self.payment_attempts.lock().await;
 
-        Ok(payment_attempts
+        payment_attempts
             .iter()
-            .find(|payment_attempt| {
+            .filter(|payment_attempt| {
                 payment_attempt.payment_id == payment_id
                     && payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.status == AttemptStatus::Charged
             })
+            .max_by_key(|payment_attempt| payment_attempt.modified_at)
             .cloned()
-            .unwrap())
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No successful payment attempt found for payment id = {payment_id}"
+            )))
+            .into_report()
+    }
+
+    async fn redact_payment_attempts_created_before(
+        &self,
+        created_before: time::PrimitiveDateTime,
+        limit: i64,
+    ) -> CustomResult<usize, StorageError> {
+        let mut payment_attempts = self.payment_attempts.lock().await;
+        let redacted_at = common_utils::date_time::now();
+        let mut redactable_attempts = payment_attempts
+            .iter_mut()
+            .filter(|payment_attempt| {
+                payment_attempt.created_at < created_before && payment_attempt.redacted_at.is_none()
+            })
+            .collect::<Vec<_>>();
+        redactable_attempts.sort_by_key(|payment_attempt| payment_attempt.created_at);
+
+        let mut redacted_count = 0;
+        for payment_attempt in redactable_attempts
+            .into_iter()
+            .take(usize::try_from(limit).unwrap_or_default())
+        {
+            payment_attempt.browser_info = None;
+            payment_attempt.payment_method_data = None;
+            payment_attempt.redacted_at = Some(redacted_at);
+            redacted_count += 1;
+        }
+        Ok(redacted_count)
     }
 }
@@ -1,4 +1,6 @@
 use common_utils::errors::CustomResult;
+#[cfg(feature = "olap")]
+use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
 use data_models::{
     errors::StorageError,
     payments::payment_intent::{
@@ -6,6 +8,7 @@ use data_models::{
     },
     MerchantStorageScheme,
 };
+use diesel_models::enums::IntentStatus;
 use error_stack::{IntoReport, ResultExt};
 
 use super::MockDb;
@@ -15,12 +18,66 @@ impl PaymentIntentInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
-        _merchant_id: &str,
-        _filters: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
+        merchant_id: &str,
+        filters: &PaymentIntentFetchConstraints,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<PaymentIntent>, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let payment_intents = self.payment_intents.lock().await;
+        let mut payment_intents = payment_intents
+            .iter()
+            .filter(|payment_intent| payment_intent.merchant_id == merchant_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        payment_intents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        match filters {
+            PaymentIntentFetchConstraints::Single { payment_intent_id } => {
+                payment_intents
+                    .retain(|payment_intent| &payment_intent.payment_id == payment_intent_id);
+                Ok(payment_intents)
+            }
+            PaymentIntentFetchConstraints::List {
+                offset,
+                starting_at,
+                ending_at,
+                currency,
+                status,
+                customer_id,
+                limit,
+                livemode,
+                include_archived,
+                ..
+            } => {
+                // The pagination by the identifiers of the payments is not supported by `MockDb`
+                let payment_intents = payment_intents
+                    .into_iter()
+                    .filter(|payment_intent| {
+                        customer_id
+                            .as_ref()
+                            .map_or(true, |id| payment_intent.customer_id.as_ref() == Some(id))
+                            && starting_at.map_or(true, |time| payment_intent.created_at >= time)
+                            && ending_at.map_or(true, |time| payment_intent.created_at <= time)
+                            && currency.as_ref().map_or(true, |currencies| {
+                                payment_intent
+                                    .currency
+                                    .map_or(false, |currency| currencies.contains(&currency))
+                            })
+                            && status
+                                .as_ref()
+                                .map_or(true, |statuses| statuses.contains(&payment_intent.status))
+                            && livemode.map_or(true, |livemode| payment_intent.livemode == livemode)
+                            && (*include_archived || payment_intent.archived_at.is_none())
+                    })
+                    .skip(usize::try_from(*offset).unwrap_or(usize::MAX))
+                    .take(
+                        limit
+                            .and_then(|limit| usize::try_from(limit).ok())
+                            .unwrap_or(usize::MAX),
+                    )
+                    .collect();
+                Ok(payment_intents)
+            }
+        }
     }
     #[cfg(feature = "olap")]
     async fn filter_payment_intents_by_time_range_constraints(
@@ -36,7 +93,7 @@ impl PaymentIntentInterface for MockDb {
     async fn get_filtered_active_attempt_ids_for_total_count(
         &self,
         _merchant_id: &str,
-        _constraints: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
+        _constraints: &PaymentIntentFetchConstraints,
         _storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<Vec<String>, StorageError> {
         // [#172]: Implement function for `MockDb`
@@ -46,7 +103,7 @@ impl PaymentIntentInterface for MockDb {
     async fn get_filtered_payment_intents_attempt(
         &self,
         _merchant_id: &str,
-        _constraints: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
+        _constraints: &PaymentIntentFetchConstraints,
         _storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<
         Vec<(
@@ -106,6 +163,21 @@ impl PaymentIntentInterface for MockDb {
             profile_id: new.profile_id,
             merchant_decision: new.merchant_decision,
             payment_confirm_source: new.payment_confirm_source,
+            session_expiry: new.session_expiry,
+            applied_defaults: new.applied_defaults,
+            order_category: new.order_category,
+            shipping_method: new.shipping_method,
+            estimated_delivery_date: new.estimated_delivery_date,
+            merchant_order_reference_id: new.merchant_order_reference_id,
+            connector_request_reference_format: new.connector_request_reference_format,
+            split_payment: new.split_payment,
+            amount_details: new.amount_details,
+            request_partial_authorization: new.request_partial_authorization,
+            archived_at: new.archived_at,
+            device_session_id: new.device_session_id,
+            payment_channel: new.payment_channel,
+            suspected_duplicate_of: new.suspected_duplicate_of,
+            livemode: new.livemode,
         };
         payment_intents.push(payment_intent.clone());
         Ok(payment_intent)
@@ -128,8 +200,6 @@ impl PaymentIntentInterface for MockDb {
         Ok(payment_intent.clone())
     }
 
-    // safety: only used for testing
-    #[allow(clippy::unwrap_used)]
     async fn find_payment_intent_by_payment_id_merchant_id(
         &self,
         payment_id: &str,
@@ -138,12 +208,69 @@ impl PaymentIntentInterface for MockDb {
     ) -> CustomResult<PaymentIntent, StorageError> {
         let payment_intents = self.payment_intents.lock().await;
 
-        Ok(payment_intents
+        payment_intents
             .iter()
             .find(|payment_intent| {
                 payment_intent.payment_id == payment_id && payment_intent.merchant_id == merchant_id
             })
             .cloned()
-            .unwrap())
+            .ok_or(StorageError::ValueNotFound(format!(
+                "No payment intent found for payment id = {payment_id}"
+            )))
+            .into_report()
+    }
+
+    async fn find_payment_intent_by_merchant_order_reference_id(
+        &self,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+        created_after: time::PrimitiveDateTime,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Option<PaymentIntent>, StorageError> {
+        let payment_intents = self.payment_intents.lock().await;
+
+        Ok(payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                payment_intent.merchant_id == merchant_id
+                    && payment_intent.merchant_order_reference_id.as_deref()
+                        == Some(merchant_order_reference_id)
+                    && payment_intent.created_at >= created_after
+                    && !matches!(
+                        payment_intent.status,
+                        IntentStatus::Failed | IntentStatus::Cancelled
+                    )
+            })
+            .min_by_key(|payment_intent| payment_intent.created_at)
+            .cloned())
+    }
+
+    async fn archive_payment_intents_created_before(
+        &self,
+        created_before: time::PrimitiveDateTime,
+        statuses: Vec<IntentStatus>,
+        limit: i64,
+    ) -> CustomResult<usize, StorageError> {
+        let mut payment_intents = self.payment_intents.lock().await;
+        let archived_at = common_utils::date_time::now();
+        let mut archivable_intents = payment_intents
+            .iter_mut()
+            .filter(|payment_intent| {
+                payment_intent.created_at < created_before
+                    && payment_intent.archived_at.is_none()
+                    && statuses.contains(&payment_intent.status)
+            })
+            .collect::<Vec<_>>();
+        archivable_intents.sort_by_key(|payment_intent| payment_intent.created_at);
+
+        let mut archived_count = 0;
+        for payment_intent in archivable_intents
+            .into_iter()
+            .take(usize::try_from(limit).unwrap_or_default())
+        {
+            payment_intent.archived_at = Some(archived_at);
+            archived_count += 1;
+        }
+        Ok(archived_count)
     }
 }
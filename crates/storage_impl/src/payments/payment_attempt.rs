@@ -13,6 +13,7 @@ use data_models::{
     MerchantStorageScheme,
 };
 use diesel_models::{
+    encryption::Encryption,
     enums::{MandateAmountData as DieselMandateAmountData, MandateDataType as DieselMandateType},
     kv,
     payment_attempt::{
@@ -133,6 +134,26 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
         .map(PaymentAttempt::from_storage_model)
     }
 
+    async fn find_payment_attempt_by_merchant_id_acquirer_reference_number(
+        &self,
+        merchant_id: &str,
+        acquirer_reference_number: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, errors::StorageError> {
+        let conn = pg_connection_read(self).await?;
+        DieselPaymentAttempt::find_by_merchant_id_acquirer_reference_number(
+            &conn,
+            merchant_id,
+            acquirer_reference_number,
+        )
+        .await
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+        .map(PaymentAttempt::from_storage_model)
+    }
+
     #[instrument(skip_all)]
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
@@ -277,6 +298,21 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
             er.change_context(new_err)
         })
     }
+
+    #[instrument(skip_all)]
+    async fn redact_payment_attempts_created_before(
+        &self,
+        created_before: time::PrimitiveDateTime,
+        limit: i64,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let conn = pg_connection_write(self).await?;
+        DieselPaymentAttempt::redact_by_created_before(&conn, created_before, limit)
+            .await
+            .map_err(|er| {
+                let new_err = diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+    }
 }
 
 #[async_trait::async_trait]
@@ -346,6 +382,27 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     connector_response_reference_id: None,
                     amount_capturable: payment_attempt.amount_capturable,
                     surcharge_metadata: payment_attempt.surcharge_metadata.clone(),
+                    external_authentication_data: payment_attempt
+                        .external_authentication_data
+                        .clone(),
+                    card_verification_results: payment_attempt.card_verification_results.clone(),
+                    three_ds_decision_data: payment_attempt.three_ds_decision_data.clone(),
+                    routing_metadata: payment_attempt.routing_metadata.clone(),
+                    merchant_connector_id: payment_attempt.merchant_connector_id.clone(),
+                    authorized_amount: payment_attempt.authorized_amount,
+                    locale: payment_attempt.locale.clone(),
+                    connector_fee_amount: payment_attempt.connector_fee_amount,
+                    fee_currency: payment_attempt.fee_currency,
+                    net_amount: payment_attempt.net_amount,
+                    merchant_descriptor: payment_attempt.merchant_descriptor.clone(),
+                    sub_merchant_data: payment_attempt.sub_merchant_data.clone(),
+                    auth_code: payment_attempt.auth_code.clone(),
+                    acquirer_reference_number: payment_attempt.acquirer_reference_number.clone(),
+                    authorized_at: payment_attempt.authorized_at,
+                    authorization_expires_at: payment_attempt.authorization_expires_at,
+                    cancellation_reason_code: payment_attempt.cancellation_reason_code,
+                    livemode: payment_attempt.livemode,
+                    redacted_at: payment_attempt.redacted_at,
                 };
 
                 let field = format!("pa_{}", created_attempt.attempt_id);
@@ -649,6 +706,23 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         }
     }
 
+    async fn find_payment_attempt_by_merchant_id_acquirer_reference_number(
+        &self,
+        merchant_id: &str,
+        acquirer_reference_number: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
+        // The acquirer reference number has no reverse lookup in the KV store, as it is only known
+        // after the payment is processed, so the attempt is looked up in the database
+        self.router_store
+            .find_payment_attempt_by_merchant_id_acquirer_reference_number(
+                merchant_id,
+                acquirer_reference_number,
+                storage_scheme,
+            )
+            .await
+    }
+
     #[instrument(skip_all)]
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
@@ -807,6 +881,10 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     .hscan_and_deserialize(&key, "pa_*", None)
                     .await
                     .change_context(errors::StorageError::KVError)
+                    .map(|mut payment_attempts: Vec<PaymentAttempt>| {
+                        payment_attempts.sort_by_key(|payment_attempt| payment_attempt.created_at);
+                        payment_attempts
+                    })
             }
         }
     }
@@ -840,6 +918,18 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
             )
             .await
     }
+
+    async fn redact_payment_attempts_created_before(
+        &self,
+        created_before: time::PrimitiveDateTime,
+        limit: i64,
+    ) -> CustomResult<usize, errors::StorageError> {
+        // The attempts old enough to be redacted have expired from redis, they are only redacted
+        // in the database
+        self.router_store
+            .redact_payment_attempts_created_before(created_before, limit)
+            .await
+    }
 }
 
 impl DataModelExt for MandateAmountData {
@@ -852,6 +942,8 @@ impl DataModelExt for MandateAmountData {
             start_date: self.start_date,
             end_date: self.end_date,
             metadata: self.metadata,
+            frequency: self.frequency,
+            max_charges_per_period: self.max_charges_per_period,
         }
     }
 
@@ -862,6 +954,8 @@ impl DataModelExt for MandateAmountData {
             start_date: storage_model.start_date,
             end_date: storage_model.end_date,
             metadata: storage_model.metadata,
+            frequency: storage_model.frequency,
+            max_charges_per_period: storage_model.max_charges_per_period,
         }
     }
 }
@@ -939,6 +1033,25 @@ impl DataModelExt for PaymentAttempt {
             connector_response_reference_id: self.connector_response_reference_id,
             amount_capturable: self.amount_capturable,
             surcharge_metadata: self.surcharge_metadata,
+            external_authentication_data: self.external_authentication_data.map(Encryption::new),
+            card_verification_results: self.card_verification_results,
+            three_ds_decision_data: self.three_ds_decision_data,
+            routing_metadata: self.routing_metadata,
+            merchant_connector_id: self.merchant_connector_id,
+            authorized_amount: self.authorized_amount,
+            locale: self.locale,
+            connector_fee_amount: self.connector_fee_amount,
+            fee_currency: self.fee_currency,
+            net_amount: self.net_amount,
+            merchant_descriptor: self.merchant_descriptor,
+            sub_merchant_data: self.sub_merchant_data,
+            auth_code: self.auth_code,
+            acquirer_reference_number: self.acquirer_reference_number,
+            authorized_at: self.authorized_at,
+            authorization_expires_at: self.authorization_expires_at,
+            cancellation_reason_code: self.cancellation_reason_code,
+            livemode: self.livemode,
+            redacted_at: self.redacted_at,
         }
     }
 
@@ -988,6 +1101,27 @@ impl DataModelExt for PaymentAttempt {
             connector_response_reference_id: storage_model.connector_response_reference_id,
             amount_capturable: storage_model.amount_capturable,
             surcharge_metadata: storage_model.surcharge_metadata,
+            external_authentication_data: storage_model
+                .external_authentication_data
+                .map(Encryption::into_inner),
+            card_verification_results: storage_model.card_verification_results,
+            three_ds_decision_data: storage_model.three_ds_decision_data,
+            routing_metadata: storage_model.routing_metadata,
+            merchant_connector_id: storage_model.merchant_connector_id,
+            authorized_amount: storage_model.authorized_amount,
+            locale: storage_model.locale,
+            connector_fee_amount: storage_model.connector_fee_amount,
+            fee_currency: storage_model.fee_currency,
+            net_amount: storage_model.net_amount,
+            merchant_descriptor: storage_model.merchant_descriptor,
+            sub_merchant_data: storage_model.sub_merchant_data,
+            auth_code: storage_model.auth_code,
+            acquirer_reference_number: storage_model.acquirer_reference_number,
+            authorized_at: storage_model.authorized_at,
+            authorization_expires_at: storage_model.authorization_expires_at,
+            cancellation_reason_code: storage_model.cancellation_reason_code,
+            livemode: storage_model.livemode,
+            redacted_at: storage_model.redacted_at,
         }
     }
 }
@@ -1037,6 +1171,25 @@ impl DataModelExt for PaymentAttemptNew {
             multiple_capture_count: self.multiple_capture_count,
             amount_capturable: self.amount_capturable,
             surcharge_metadata: self.surcharge_metadata,
+            external_authentication_data: self.external_authentication_data.map(Encryption::new),
+            card_verification_results: self.card_verification_results,
+            three_ds_decision_data: self.three_ds_decision_data,
+            routing_metadata: self.routing_metadata,
+            merchant_connector_id: self.merchant_connector_id,
+            authorized_amount: self.authorized_amount,
+            locale: self.locale,
+            connector_fee_amount: self.connector_fee_amount,
+            fee_currency: self.fee_currency,
+            net_amount: self.net_amount,
+            merchant_descriptor: self.merchant_descriptor,
+            sub_merchant_data: self.sub_merchant_data,
+            auth_code: self.auth_code,
+            acquirer_reference_number: self.acquirer_reference_number,
+            authorized_at: self.authorized_at,
+            authorization_expires_at: self.authorization_expires_at,
+            cancellation_reason_code: self.cancellation_reason_code,
+            livemode: self.livemode,
+            redacted_at: self.redacted_at,
         }
     }
 
@@ -1084,6 +1237,27 @@ impl DataModelExt for PaymentAttemptNew {
             multiple_capture_count: storage_model.multiple_capture_count,
             amount_capturable: storage_model.amount_capturable,
             surcharge_metadata: storage_model.surcharge_metadata,
+            external_authentication_data: storage_model
+                .external_authentication_data
+                .map(Encryption::into_inner),
+            card_verification_results: storage_model.card_verification_results,
+            three_ds_decision_data: storage_model.three_ds_decision_data,
+            routing_metadata: storage_model.routing_metadata,
+            merchant_connector_id: storage_model.merchant_connector_id,
+            authorized_amount: storage_model.authorized_amount,
+            locale: storage_model.locale,
+            connector_fee_amount: storage_model.connector_fee_amount,
+            fee_currency: storage_model.fee_currency,
+            net_amount: storage_model.net_amount,
+            merchant_descriptor: storage_model.merchant_descriptor,
+            sub_merchant_data: storage_model.sub_merchant_data,
+            auth_code: storage_model.auth_code,
+            acquirer_reference_number: storage_model.acquirer_reference_number,
+            authorized_at: storage_model.authorized_at,
+            authorization_expires_at: storage_model.authorization_expires_at,
+            cancellation_reason_code: storage_model.cancellation_reason_code,
+            livemode: storage_model.livemode,
+            redacted_at: storage_model.redacted_at,
         }
     }
 }
@@ -1106,6 +1280,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data,
             } => DieselPaymentAttemptUpdate::Update {
                 amount,
                 currency,
@@ -1119,17 +1294,24 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data: external_authentication_data.map(Encryption::new),
             },
             Self::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
             } => DieselPaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
             },
             Self::AuthenticationTypeUpdate {
                 authentication_type,
@@ -1153,6 +1335,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data,
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
             } => DieselPaymentAttemptUpdate::ConfirmUpdate {
                 amount,
                 currency,
@@ -1170,13 +1359,22 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data: external_authentication_data.map(Encryption::new),
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
             },
             Self::VoidUpdate {
                 status,
                 cancellation_reason,
+                cancellation_reason_code,
             } => DieselPaymentAttemptUpdate::VoidUpdate {
                 status,
                 cancellation_reason,
+                cancellation_reason_code,
             },
             Self::ResponseUpdate {
                 status,
@@ -1192,6 +1390,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
             } => DieselPaymentAttemptUpdate::ResponseUpdate {
                 status,
                 connector,
@@ -1206,6 +1411,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
             },
             Self::UnresolvedResponseUpdate {
                 status,
@@ -1281,6 +1493,27 @@ impl DataModelExt for PaymentAttemptUpdate {
             Self::SurchargeMetadataUpdate { surcharge_metadata } => {
                 DieselPaymentAttemptUpdate::SurchargeMetadataUpdate { surcharge_metadata }
             }
+            Self::ConnectorFeeUpdate {
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+            } => DieselPaymentAttemptUpdate::ConnectorFeeUpdate {
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+            },
+            Self::AcquirerReferenceNumberUpdate {
+                acquirer_reference_number,
+            } => DieselPaymentAttemptUpdate::AcquirerReferenceNumberUpdate {
+                acquirer_reference_number,
+            },
+            Self::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            } => DieselPaymentAttemptUpdate::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            },
         }
     }
 
@@ -1299,6 +1532,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data,
             } => Self::Update {
                 amount,
                 currency,
@@ -1312,17 +1546,25 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                external_authentication_data: external_authentication_data
+                    .map(Encryption::into_inner),
             },
             DieselPaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
             } => Self::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                locale,
+                routing_metadata,
+                merchant_connector_id,
             },
             DieselPaymentAttemptUpdate::AuthenticationTypeUpdate {
                 authentication_type,
@@ -1346,6 +1588,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data,
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
             } => Self::ConfirmUpdate {
                 amount,
                 currency,
@@ -1363,13 +1612,23 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                external_authentication_data: external_authentication_data
+                    .map(Encryption::into_inner),
+                three_ds_decision_data,
+                routing_metadata,
+                locale,
+                merchant_descriptor,
+                sub_merchant_data,
+                merchant_connector_id,
             },
             DieselPaymentAttemptUpdate::VoidUpdate {
                 status,
                 cancellation_reason,
+                cancellation_reason_code,
             } => Self::VoidUpdate {
                 status,
                 cancellation_reason,
+                cancellation_reason_code,
             },
             DieselPaymentAttemptUpdate::ResponseUpdate {
                 status,
@@ -1385,6 +1644,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
             } => Self::ResponseUpdate {
                 status,
                 connector,
@@ -1399,6 +1665,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                card_verification_results,
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+                auth_code,
+                acquirer_reference_number,
+                authorized_amount,
             },
             DieselPaymentAttemptUpdate::UnresolvedResponseUpdate {
                 status,
@@ -1474,6 +1747,27 @@ impl DataModelExt for PaymentAttemptUpdate {
             DieselPaymentAttemptUpdate::SurchargeMetadataUpdate { surcharge_metadata } => {
                 Self::SurchargeMetadataUpdate { surcharge_metadata }
             }
+            DieselPaymentAttemptUpdate::ConnectorFeeUpdate {
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+            } => Self::ConnectorFeeUpdate {
+                connector_fee_amount,
+                fee_currency,
+                net_amount,
+            },
+            DieselPaymentAttemptUpdate::AcquirerReferenceNumberUpdate {
+                acquirer_reference_number,
+            } => Self::AcquirerReferenceNumberUpdate {
+                acquirer_reference_number,
+            },
+            DieselPaymentAttemptUpdate::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            } => Self::AuthorizationExpiryUpdate {
+                authorized_at,
+                authorization_expires_at,
+            },
         }
     }
 }
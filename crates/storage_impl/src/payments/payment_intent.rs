@@ -17,11 +17,13 @@ use diesel::{associations::HasTable, ExpressionMethods, JoinOnDsl, QueryDsl};
 #[cfg(feature = "olap")]
 use diesel_models::query::generics::db_metrics;
 use diesel_models::{
+    enums::IntentStatus,
     kv,
     payment_intent::{
         PaymentIntent as DieselPaymentIntent, PaymentIntentNew as DieselPaymentIntentNew,
         PaymentIntentUpdate as DieselPaymentIntentUpdate,
     },
+    reverse_lookup::ReverseLookupNew,
 };
 #[cfg(feature = "olap")]
 use diesel_models::{
@@ -33,8 +35,10 @@ use redis_interface::HsetnxReply;
 #[cfg(feature = "olap")]
 use router_env::logger;
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use crate::{
+    lookup::ReverseLookupInterface,
     redis::kv_store::{PartitionKey, RedisConnInterface},
     utils::{pg_connection_read, pg_connection_write},
     DataModelExt, DatabaseStore, KVRouterStore,
@@ -91,6 +95,23 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     profile_id: new.profile_id.clone(),
                     merchant_decision: new.merchant_decision.clone(),
                     payment_confirm_source: new.payment_confirm_source,
+                    session_expiry: new.session_expiry,
+                    applied_defaults: new.applied_defaults.clone(),
+                    order_category: new.order_category.clone(),
+                    shipping_method: new.shipping_method,
+                    estimated_delivery_date: new.estimated_delivery_date,
+                    merchant_order_reference_id: new.merchant_order_reference_id.clone(),
+                    connector_request_reference_format: new
+                        .connector_request_reference_format
+                        .clone(),
+                    split_payment: new.split_payment.clone(),
+                    amount_details: new.amount_details.clone(),
+                    request_partial_authorization: new.request_partial_authorization,
+                    archived_at: new.archived_at,
+                    device_session_id: new.device_session_id.clone(),
+                    payment_channel: new.payment_channel,
+                    suspected_duplicate_of: new.suspected_duplicate_of.clone(),
+                    livemode: new.livemode,
                 };
 
                 match self
@@ -105,6 +126,34 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     })
                     .into_report(),
                     Ok(HsetnxReply::KeySet) => {
+                        if let Some(merchant_order_reference_id) =
+                            created_intent.merchant_order_reference_id.as_deref()
+                        {
+                            // Only the first payment created with the merchant order reference is
+                            // looked up in redis, the later ones are found once they are drained
+                            // to the database
+                            match self
+                                .insert_reverse_lookup(ReverseLookupNew {
+                                    lookup_id: get_merchant_order_reference_lookup_id(
+                                        &created_intent.merchant_id,
+                                        merchant_order_reference_id,
+                                    ),
+                                    pk_id: key.clone(),
+                                    sk_id: field.clone(),
+                                    source: "payment_intent".to_string(),
+                                })
+                                .await
+                            {
+                                Ok(_) => (),
+                                Err(error)
+                                    if matches!(
+                                        error.current_context(),
+                                        StorageError::DuplicateValue { .. }
+                                    ) => {}
+                                Err(error) => Err(error)?,
+                            }
+                        }
+
                         let redis_entry = kv::TypedSql {
                             op: kv::DBOperation::Insert {
                                 insertable: kv::Insertable::PaymentIntent(new.to_storage_model()),
@@ -217,6 +266,79 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         }
     }
 
+    async fn find_payment_intent_by_merchant_order_reference_id(
+        &self,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+        created_after: PrimitiveDateTime,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Option<PaymentIntent>, StorageError> {
+        let database_call = || async {
+            self.router_store
+                .find_payment_intent_by_merchant_order_reference_id(
+                    merchant_id,
+                    merchant_order_reference_id,
+                    created_after,
+                    storage_scheme,
+                )
+                .await
+        };
+        match storage_scheme {
+            MerchantStorageScheme::PostgresOnly => database_call().await,
+
+            MerchantStorageScheme::RedisKv => {
+                if let Some(payment_intent) = database_call().await? {
+                    return Ok(Some(payment_intent));
+                }
+
+                // The payments which have not been drained to the database yet are found through
+                // the reverse lookup of the merchant order reference
+                let lookup_id = get_merchant_order_reference_lookup_id(
+                    merchant_id,
+                    merchant_order_reference_id,
+                );
+                let lookup = match self.get_lookup_by_lookup_id(&lookup_id).await {
+                    Ok(lookup) => lookup,
+                    Err(error)
+                        if matches!(error.current_context(), StorageError::ValueNotFound(_)) =>
+                    {
+                        return Ok(None)
+                    }
+                    Err(error) => return Err(error),
+                };
+                match self
+                    .get_redis_conn()
+                    .change_context(StorageError::DatabaseConnectionError)?
+                    .get_hash_field_and_deserialize::<PaymentIntent>(
+                        &lookup.pk_id,
+                        &lookup.sk_id,
+                        "PaymentIntent",
+                    )
+                    .await
+                {
+                    Ok(payment_intent) => Ok(Some(payment_intent).filter(|payment_intent| {
+                        payment_intent.created_at >= created_after
+                            && !matches!(
+                                payment_intent.status,
+                                IntentStatus::Failed | IntentStatus::Cancelled
+                            )
+                    })),
+                    // The payment has expired from redis after it was drained, it was then looked
+                    // up in the database
+                    Err(error)
+                        if matches!(
+                            error.current_context(),
+                            redis_interface::errors::RedisError::NotFound
+                        ) =>
+                    {
+                        Ok(None)
+                    }
+                    Err(error) => Err(error.change_context(StorageError::KVError)),
+                }
+            }
+        }
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -292,6 +414,28 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
             MerchantStorageScheme::RedisKv => Err(StorageError::KVError.into()),
         }
     }
+
+    async fn archive_payment_intents_created_before(
+        &self,
+        created_before: PrimitiveDateTime,
+        statuses: Vec<IntentStatus>,
+        limit: i64,
+    ) -> error_stack::Result<usize, StorageError> {
+        // The payments old enough to be archived have expired from redis, they are only archived
+        // in the database
+        self.router_store
+            .archive_payment_intents_created_before(created_before, statuses, limit)
+            .await
+    }
+}
+
+/// Identifier of the reverse lookup of the first payment created with the merchant order reference
+#[inline]
+fn get_merchant_order_reference_lookup_id(
+    merchant_id: &str,
+    merchant_order_reference_id: &str,
+) -> String {
+    format!("{merchant_id}_order_{merchant_order_reference_id}")
 }
 
 #[async_trait::async_trait]
@@ -346,6 +490,30 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
             })
     }
 
+    async fn find_payment_intent_by_merchant_order_reference_id(
+        &self,
+        merchant_id: &str,
+        merchant_order_reference_id: &str,
+        created_after: PrimitiveDateTime,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Option<PaymentIntent>, StorageError> {
+        // The primary is read, as the payment created by a concurrent request may not have been
+        // replicated yet
+        let conn = pg_connection_write(self).await?;
+        DieselPaymentIntent::find_optional_by_merchant_id_merchant_order_reference_id(
+            &conn,
+            merchant_id,
+            merchant_order_reference_id,
+            created_after,
+        )
+        .await
+        .map(|payment_intent| payment_intent.map(PaymentIntent::from_storage_model))
+        .map_err(|er| {
+            let new_err = crate::diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -378,6 +546,8 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                 starting_after_id,
                 ending_before_id,
                 limit,
+                livemode,
+                include_archived,
             } => {
                 if let Some(limit) = limit {
                     query = query.limit((*limit).into());
@@ -431,6 +601,15 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                     Some(status) => query.filter(pi_dsl::status.eq_any(status.clone())),
                     None => query,
                 };
+
+                query = match livemode {
+                    Some(livemode) => query.filter(pi_dsl::livemode.eq(*livemode)),
+                    None => query,
+                };
+
+                if !include_archived {
+                    query = query.filter(pi_dsl::archived_at.is_null());
+                }
             }
         }
 
@@ -502,6 +681,8 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                 starting_after_id,
                 ending_before_id,
                 limit,
+                livemode,
+                include_archived,
             } => {
                 if let Some(limit) = limit {
                     query = query.limit((*limit).into());
@@ -566,6 +747,15 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                     None => query,
                 };
 
+                query = match livemode {
+                    Some(livemode) => query.filter(pi_dsl::livemode.eq(*livemode)),
+                    None => query,
+                };
+
+                if !include_archived {
+                    query = query.filter(pi_dsl::archived_at.is_null());
+                }
+
                 query = match payment_methods {
                     Some(payment_methods) => {
                         query.filter(pa_dsl::payment_method.eq_any(payment_methods.clone()))
@@ -626,6 +816,8 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                 currency,
                 status,
                 customer_id,
+                livemode,
+                include_archived,
                 ..
             } => {
                 if let Some(customer_id) = customer_id {
@@ -652,6 +844,15 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                     None => query,
                 };
 
+                query = match livemode {
+                    Some(livemode) => query.filter(pi_dsl::livemode.eq(*livemode)),
+                    None => query,
+                };
+
+                if !include_archived {
+                    query = query.filter(pi_dsl::archived_at.is_null());
+                }
+
                 query
             }
         };
@@ -668,6 +869,22 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         })
         .attach_printable_lazy(|| "Error filtering records by predicate")
     }
+
+    #[instrument(skip_all)]
+    async fn archive_payment_intents_created_before(
+        &self,
+        created_before: PrimitiveDateTime,
+        statuses: Vec<IntentStatus>,
+        limit: i64,
+    ) -> error_stack::Result<usize, StorageError> {
+        let conn = pg_connection_write(self).await?;
+        DieselPaymentIntent::archive_by_created_before(&conn, created_before, statuses, limit)
+            .await
+            .map_err(|er| {
+                let new_err = crate::diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+    }
 }
 
 impl DataModelExt for PaymentIntentNew {
@@ -707,6 +924,21 @@ impl DataModelExt for PaymentIntentNew {
             profile_id: self.profile_id,
             merchant_decision: self.merchant_decision,
             payment_confirm_source: self.payment_confirm_source,
+            session_expiry: self.session_expiry,
+            applied_defaults: self.applied_defaults,
+            order_category: self.order_category,
+            shipping_method: self.shipping_method,
+            estimated_delivery_date: self.estimated_delivery_date,
+            merchant_order_reference_id: self.merchant_order_reference_id,
+            connector_request_reference_format: self.connector_request_reference_format,
+            split_payment: self.split_payment,
+            amount_details: self.amount_details,
+            request_partial_authorization: self.request_partial_authorization,
+            archived_at: self.archived_at,
+            device_session_id: self.device_session_id,
+            payment_channel: self.payment_channel,
+            suspected_duplicate_of: self.suspected_duplicate_of,
+            livemode: self.livemode,
         }
     }
 
@@ -744,6 +976,21 @@ impl DataModelExt for PaymentIntentNew {
             profile_id: storage_model.profile_id,
             merchant_decision: storage_model.merchant_decision,
             payment_confirm_source: storage_model.payment_confirm_source,
+            session_expiry: storage_model.session_expiry,
+            applied_defaults: storage_model.applied_defaults,
+            order_category: storage_model.order_category,
+            shipping_method: storage_model.shipping_method,
+            estimated_delivery_date: storage_model.estimated_delivery_date,
+            merchant_order_reference_id: storage_model.merchant_order_reference_id,
+            connector_request_reference_format: storage_model.connector_request_reference_format,
+            split_payment: storage_model.split_payment,
+            amount_details: storage_model.amount_details,
+            request_partial_authorization: storage_model.request_partial_authorization,
+            archived_at: storage_model.archived_at,
+            device_session_id: storage_model.device_session_id,
+            payment_channel: storage_model.payment_channel,
+            suspected_duplicate_of: storage_model.suspected_duplicate_of,
+            livemode: storage_model.livemode,
         }
     }
 }
@@ -786,6 +1033,21 @@ impl DataModelExt for PaymentIntent {
             profile_id: self.profile_id,
             merchant_decision: self.merchant_decision,
             payment_confirm_source: self.payment_confirm_source,
+            session_expiry: self.session_expiry,
+            applied_defaults: self.applied_defaults,
+            order_category: self.order_category,
+            shipping_method: self.shipping_method,
+            estimated_delivery_date: self.estimated_delivery_date,
+            merchant_order_reference_id: self.merchant_order_reference_id,
+            connector_request_reference_format: self.connector_request_reference_format,
+            split_payment: self.split_payment,
+            amount_details: self.amount_details,
+            request_partial_authorization: self.request_partial_authorization,
+            archived_at: self.archived_at,
+            device_session_id: self.device_session_id,
+            payment_channel: self.payment_channel,
+            suspected_duplicate_of: self.suspected_duplicate_of,
+            livemode: self.livemode,
         }
     }
 
@@ -824,6 +1086,21 @@ impl DataModelExt for PaymentIntent {
             profile_id: storage_model.profile_id,
             merchant_decision: storage_model.merchant_decision,
             payment_confirm_source: storage_model.payment_confirm_source,
+            session_expiry: storage_model.session_expiry,
+            applied_defaults: storage_model.applied_defaults,
+            order_category: storage_model.order_category,
+            shipping_method: storage_model.shipping_method,
+            estimated_delivery_date: storage_model.estimated_delivery_date,
+            merchant_order_reference_id: storage_model.merchant_order_reference_id,
+            connector_request_reference_format: storage_model.connector_request_reference_format,
+            split_payment: storage_model.split_payment,
+            amount_details: storage_model.amount_details,
+            request_partial_authorization: storage_model.request_partial_authorization,
+            archived_at: storage_model.archived_at,
+            device_session_id: storage_model.device_session_id,
+            payment_channel: storage_model.payment_channel,
+            suspected_duplicate_of: storage_model.suspected_duplicate_of,
+            livemode: storage_model.livemode,
         }
     }
 }
@@ -885,6 +1162,7 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             } => DieselPaymentIntentUpdate::Update {
                 amount,
                 currency,
@@ -902,6 +1180,7 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             },
             Self::PaymentAttemptAndAttemptCountUpdate {
                 active_attempt_id,
@@ -929,6 +1208,9 @@ impl DataModelExt for PaymentIntentUpdate {
                 status,
                 merchant_decision,
             },
+            Self::DeviceSessionUpdate { device_session_id } => {
+                DieselPaymentIntentUpdate::DeviceSessionUpdate { device_session_id }
+            }
         }
     }
 
@@ -986,6 +1268,7 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             } => Self::Update {
                 amount,
                 currency,
@@ -1003,6 +1286,7 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                device_session_id,
             },
             DieselPaymentIntentUpdate::PaymentAttemptAndAttemptCountUpdate {
                 active_attempt_id,
@@ -1030,6 +1314,9 @@ impl DataModelExt for PaymentIntentUpdate {
                 status,
                 merchant_decision,
             },
+            DieselPaymentIntentUpdate::DeviceSessionUpdate { device_session_id } => {
+                Self::DeviceSessionUpdate { device_session_id }
+            }
         }
     }
 }
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS statement_descriptor_name,
+DROP COLUMN IF EXISTS statement_descriptor_suffix;
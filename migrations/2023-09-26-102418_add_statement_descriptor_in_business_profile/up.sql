@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS statement_descriptor_name VARCHAR(255) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS statement_descriptor_suffix VARCHAR(255) DEFAULT NULL;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Existing API keys are granted all the permissions, to retain their current access
+ALTER TABLE api_keys
+ADD COLUMN IF NOT EXISTS permissions TEXT[] NOT NULL DEFAULT ARRAY['payments_read', 'payments_write', 'refunds_write', 'customers_write', 'admin']::TEXT[];
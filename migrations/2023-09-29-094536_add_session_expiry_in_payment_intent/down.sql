@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS session_expiry;
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS session_expiry;
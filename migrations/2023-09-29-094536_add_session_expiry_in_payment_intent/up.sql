@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS session_expiry TIMESTAMP DEFAULT NULL;
+
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS session_expiry BIGINT DEFAULT NULL;
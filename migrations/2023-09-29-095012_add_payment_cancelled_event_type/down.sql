@@ -0,0 +1,5 @@
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_cancelled'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE mandate DROP COLUMN IF EXISTS consecutive_failure_count;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'requires_attention'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'MandateStatus'
+);
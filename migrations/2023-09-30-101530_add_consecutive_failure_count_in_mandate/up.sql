@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TYPE "MandateStatus" ADD VALUE IF NOT EXISTS 'requires_attention';
+
+ALTER TABLE mandate ADD COLUMN IF NOT EXISTS consecutive_failure_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS locale;
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS default_locale;
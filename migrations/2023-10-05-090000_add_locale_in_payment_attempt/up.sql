@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS locale VARCHAR(35) DEFAULT NULL;
+
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS default_locale VARCHAR(35) DEFAULT NULL;
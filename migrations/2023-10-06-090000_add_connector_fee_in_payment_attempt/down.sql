@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS connector_fee_amount,
+DROP COLUMN IF EXISTS fee_currency,
+DROP COLUMN IF EXISTS net_amount;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS blocklist_merchant_id_data_kind_fingerprint_index;
+
+DROP TABLE IF EXISTS blocklist;
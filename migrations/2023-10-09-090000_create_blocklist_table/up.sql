@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE blocklist (
+    blocklist_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    data_kind VARCHAR(32) NOT NULL,
+    fingerprint VARCHAR(128) NOT NULL,
+    reason VARCHAR(255),
+    expires_at TIMESTAMP,
+    hit_count BIGINT NOT NULL DEFAULT 0,
+    last_hit_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE UNIQUE INDEX blocklist_merchant_id_data_kind_fingerprint_index ON blocklist (
+    merchant_id,
+    data_kind,
+    fingerprint
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS merchant_descriptor,
+DROP COLUMN IF EXISTS sub_merchant_data;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS merchant_descriptor VARCHAR(255) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS sub_merchant_data JSONB DEFAULT NULL;
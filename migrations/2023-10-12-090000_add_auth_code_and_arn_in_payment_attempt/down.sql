@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS auth_code,
+DROP COLUMN IF EXISTS acquirer_reference_number;
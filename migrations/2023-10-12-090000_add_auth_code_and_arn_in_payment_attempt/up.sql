@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS auth_code VARCHAR(64) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS acquirer_reference_number VARCHAR(128) DEFAULT NULL;
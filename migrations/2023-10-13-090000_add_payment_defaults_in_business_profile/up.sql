@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS default_payment_description VARCHAR(255) DEFAULT NULL;
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS applied_defaults JSON DEFAULT NULL;
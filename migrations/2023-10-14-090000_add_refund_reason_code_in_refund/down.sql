@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE refund
+DROP COLUMN IF EXISTS refund_reason_code;
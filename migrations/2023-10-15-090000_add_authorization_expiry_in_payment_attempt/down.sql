@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS authorized_at,
+DROP COLUMN IF EXISTS authorization_expires_at;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_capture_deadline_approaching'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'authorization_expired'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'AttemptStatus'
+);
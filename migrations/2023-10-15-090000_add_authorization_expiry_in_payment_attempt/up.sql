@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TYPE "AttemptStatus" ADD VALUE IF NOT EXISTS 'authorization_expired';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payment_capture_deadline_approaching';
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS authorized_at TIMESTAMP DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS authorization_expires_at TIMESTAMP DEFAULT NULL;
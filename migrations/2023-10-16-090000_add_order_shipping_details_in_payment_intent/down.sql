@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS order_category,
+DROP COLUMN IF EXISTS shipping_method,
+DROP COLUMN IF EXISTS estimated_delivery_date;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS order_category VARCHAR(255) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS shipping_method VARCHAR(32) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS estimated_delivery_date TIMESTAMP DEFAULT NULL;
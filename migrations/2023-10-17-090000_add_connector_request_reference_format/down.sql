@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS merchant_order_reference_id,
+DROP COLUMN IF EXISTS connector_request_reference_format;
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS connector_request_reference_format;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS connector_request_reference_format VARCHAR(255) DEFAULT NULL;
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS merchant_order_reference_id VARCHAR(255) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS connector_request_reference_format VARCHAR(255) DEFAULT NULL;
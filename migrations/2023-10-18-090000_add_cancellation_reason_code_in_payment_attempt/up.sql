@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS cancellation_reason_code VARCHAR(32) DEFAULT NULL;
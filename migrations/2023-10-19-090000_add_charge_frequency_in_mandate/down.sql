@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE mandate
+DROP COLUMN IF EXISTS frequency,
+DROP COLUMN IF EXISTS max_charges_per_period,
+DROP COLUMN IF EXISTS charge_count,
+DROP COLUMN IF EXISTS period_charge_count,
+DROP COLUMN IF EXISTS period_started_at;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE mandate
+ADD COLUMN IF NOT EXISTS frequency VARCHAR(16) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS max_charges_per_period INTEGER DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS charge_count INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS period_charge_count INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS period_started_at TIMESTAMP DEFAULT NULL;
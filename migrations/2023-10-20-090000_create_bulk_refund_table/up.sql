@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE bulk_refund (
+    bulk_refund_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    items JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
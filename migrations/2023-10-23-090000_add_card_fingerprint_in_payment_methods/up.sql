@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE payment_methods
+ADD COLUMN IF NOT EXISTS card_fingerprint VARCHAR(128) DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS payment_methods_merchant_id_customer_id_card_fingerprint_index ON payment_methods (merchant_id, customer_id, card_fingerprint);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE merchant_connector_account
+ADD COLUMN IF NOT EXISTS disabled_flows TEXT[] DEFAULT NULL;
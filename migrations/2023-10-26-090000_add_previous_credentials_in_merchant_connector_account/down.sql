@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_connector_account
+DROP COLUMN IF EXISTS previous_connector_account_details,
+DROP COLUMN IF EXISTS previous_connector_webhook_details,
+DROP COLUMN IF EXISTS previous_credentials_expire_at;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS audit_event_merchant_id_resource_id_index;
+
+DROP INDEX IF EXISTS audit_event_merchant_id_created_at_index;
+
+DROP TABLE IF EXISTS audit_event;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE audit_event (
+    audit_event_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    actor_key_id VARCHAR(64) NOT NULL,
+    resource_type VARCHAR(32) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    action VARCHAR(32) NOT NULL,
+    diff JSONB NOT NULL,
+    request_id VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX audit_event_merchant_id_created_at_index ON audit_event (merchant_id, created_at);
+
+CREATE INDEX audit_event_merchant_id_resource_id_index ON audit_event (merchant_id, resource_id);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_intent_merchant_id_merchant_order_reference_id_index;
+
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS suspected_duplicate_of;
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS duplicate_order_reference_behaviour,
+DROP COLUMN IF EXISTS duplicate_order_reference_window;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS duplicate_order_reference_behaviour VARCHAR(32) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS duplicate_order_reference_window BIGINT DEFAULT NULL;
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS suspected_duplicate_of VARCHAR(64) DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS payment_intent_merchant_id_merchant_order_reference_id_index ON payment_intent (merchant_id, merchant_order_reference_id, created_at);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS livemode;
+
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS livemode;
+
+ALTER TABLE api_keys
+DROP COLUMN IF EXISTS livemode;
@@ -0,0 +1,82 @@
+-- Your SQL goes here
+-- The mode of the existing rows is backfilled from the merchant connector accounts before the
+-- columns are made mandatory, so that the payments are still accepted by the accounts which
+-- process them. A payment takes the mode of the account it was routed to. The API keys, and the
+-- payments which were not routed yet, are test ones only when all the accounts of the merchant are
+-- in test mode.
+ALTER TABLE api_keys
+ADD COLUMN IF NOT EXISTS livemode BOOLEAN;
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS livemode BOOLEAN;
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS livemode BOOLEAN;
+
+UPDATE payment_attempt
+SET livemode = NOT COALESCE(merchant_connector_account.test_mode, FALSE)
+FROM merchant_connector_account
+WHERE payment_attempt.livemode IS NULL
+    AND payment_attempt.merchant_connector_id = merchant_connector_account.merchant_connector_id;
+
+UPDATE payment_attempt
+SET livemode = EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = payment_attempt.merchant_id
+            AND NOT COALESCE(merchant_connector_account.test_mode, FALSE)
+    )
+    OR NOT EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = payment_attempt.merchant_id
+    )
+WHERE livemode IS NULL;
+
+UPDATE payment_intent
+SET livemode = payment_attempt.livemode
+FROM payment_attempt
+WHERE payment_intent.livemode IS NULL
+    AND payment_attempt.merchant_id = payment_intent.merchant_id
+    AND payment_attempt.attempt_id = payment_intent.active_attempt_id;
+
+UPDATE payment_intent
+SET livemode = EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = payment_intent.merchant_id
+            AND NOT COALESCE(merchant_connector_account.test_mode, FALSE)
+    )
+    OR NOT EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = payment_intent.merchant_id
+    )
+WHERE livemode IS NULL;
+
+UPDATE api_keys
+SET livemode = EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = api_keys.merchant_id
+            AND NOT COALESCE(merchant_connector_account.test_mode, FALSE)
+    )
+    OR NOT EXISTS (
+        SELECT 1
+        FROM merchant_connector_account
+        WHERE merchant_connector_account.merchant_id = api_keys.merchant_id
+    )
+WHERE livemode IS NULL;
+
+-- The rows inserted without a mode are test ones
+ALTER TABLE api_keys
+ALTER COLUMN livemode SET NOT NULL,
+ALTER COLUMN livemode SET DEFAULT FALSE;
+
+ALTER TABLE payment_intent
+ALTER COLUMN livemode SET NOT NULL,
+ALTER COLUMN livemode SET DEFAULT FALSE;
+
+ALTER TABLE payment_attempt
+ALTER COLUMN livemode SET NOT NULL,
+ALTER COLUMN livemode SET DEFAULT FALSE;
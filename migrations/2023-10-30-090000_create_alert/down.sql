@@ -0,0 +1,25 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel = 'alert_received'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'alert_details'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventObjectType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'alerts'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventClass'
+);
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS alert_auto_refund_max_amount;
+
+DROP INDEX IF EXISTS payment_attempt_merchant_id_acquirer_reference_number_index;
+
+DROP TABLE IF EXISTS alert;
@@ -0,0 +1,33 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS alert (
+    alert_id VARCHAR(64) PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    connector VARCHAR(64) NOT NULL,
+    connector_alert_id VARCHAR(255) NOT NULL,
+    alert_type VARCHAR(32) NOT NULL,
+    initiated_by VARCHAR(64),
+    connector_reason VARCHAR(255),
+    status VARCHAR(32) NOT NULL,
+    action VARCHAR(32),
+    refund_id VARCHAR(64),
+    raw_payload JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS alert_merchant_id_connector_connector_alert_id_index ON alert (merchant_id, connector, connector_alert_id);
+
+CREATE INDEX IF NOT EXISTS alert_merchant_id_payment_id_index ON alert (merchant_id, payment_id);
+
+CREATE INDEX IF NOT EXISTS payment_attempt_merchant_id_acquirer_reference_number_index ON payment_attempt (merchant_id, acquirer_reference_number);
+
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS alert_auto_refund_max_amount BIGINT DEFAULT NULL;
+
+ALTER TYPE "EventClass" ADD VALUE IF NOT EXISTS 'alerts';
+
+ALTER TYPE "EventObjectType" ADD VALUE IF NOT EXISTS 'alert_details';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'alert_received';
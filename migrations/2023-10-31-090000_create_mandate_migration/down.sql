@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE mandate DROP COLUMN IF EXISTS merchant_connector_id;
+
+DROP TABLE IF EXISTS mandate_migration;
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE mandate_migration (
+    migration_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    source_merchant_connector_id VARCHAR(64) NOT NULL,
+    target_merchant_connector_id VARCHAR(64) NOT NULL,
+    verify BOOLEAN NOT NULL DEFAULT FALSE,
+    status VARCHAR(32) NOT NULL,
+    items JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+ALTER TABLE mandate ADD COLUMN IF NOT EXISTS merchant_connector_id VARCHAR(64);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE api_keys
+ADD COLUMN IF NOT EXISTS strict_request_validation BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS webhook_delivery (
+    delivery_id VARCHAR(64) PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    subscription_id VARCHAR(64),
+    status VARCHAR(32) NOT NULL,
+    response_status_code INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_merchant_id_event_id_index ON webhook_delivery (merchant_id, event_id);
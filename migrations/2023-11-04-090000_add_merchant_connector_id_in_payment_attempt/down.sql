@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS merchant_connector_id;
+
+ALTER TABLE refund
+DROP COLUMN IF EXISTS merchant_connector_id;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS merchant_connector_id VARCHAR(64);
+
+ALTER TABLE refund
+ADD COLUMN IF NOT EXISTS merchant_connector_id VARCHAR(64);
@@ -0,0 +1,18 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS authorized_amount;
+
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS request_partial_authorization;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_partially_authorized'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'partially_authorized'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'AttemptStatus'
+);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+ALTER TYPE "AttemptStatus" ADD VALUE IF NOT EXISTS 'partially_authorized';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payment_partially_authorized';
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS request_partial_authorization BOOLEAN DEFAULT NULL;
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS authorized_amount BIGINT DEFAULT NULL;
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_attempt_created_at_not_redacted_index;
+
+DROP INDEX IF EXISTS payment_intent_created_at_not_archived_index;
+
+DROP INDEX IF EXISTS payment_intent_merchant_id_created_at_not_archived_index;
+
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS redacted_at;
+
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS archived_at;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS archived_at TIMESTAMP DEFAULT NULL;
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS redacted_at TIMESTAMP DEFAULT NULL;
+
+-- The listing of payments only reads the payments which are not archived
+CREATE INDEX IF NOT EXISTS payment_intent_merchant_id_created_at_not_archived_index
+ON payment_intent (merchant_id, created_at)
+WHERE archived_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS payment_intent_created_at_not_archived_index
+ON payment_intent (created_at)
+WHERE archived_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS payment_attempt_created_at_not_redacted_index
+ON payment_attempt (created_at)
+WHERE redacted_at IS NULL;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS default_currency,
+DROP COLUMN IF EXISTS default_country;
+
+ALTER TABLE merchant_account
+DROP COLUMN IF EXISTS default_currency,
+DROP COLUMN IF EXISTS default_country;
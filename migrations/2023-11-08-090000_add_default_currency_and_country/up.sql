@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE merchant_account
+ADD COLUMN IF NOT EXISTS default_currency "Currency" DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS default_country "CountryAlpha2" DEFAULT NULL;
+
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS default_currency "Currency" DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS default_country "CountryAlpha2" DEFAULT NULL;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE connector_response_capture (
+    capture_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    refund_id VARCHAR(64),
+    connector VARCHAR(64) NOT NULL,
+    flow VARCHAR(64) NOT NULL,
+    status_code INTEGER NOT NULL,
+    response_body TEXT NOT NULL,
+    is_truncated BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX connector_response_capture_merchant_id_payment_id_index ON connector_response_capture (merchant_id, payment_id, created_at);
+
+CREATE INDEX connector_response_capture_merchant_id_refund_id_index ON connector_response_capture (merchant_id, refund_id, created_at);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE webhook_delivery
+ADD COLUMN IF NOT EXISTS payload_version VARCHAR(16) DEFAULT NULL;
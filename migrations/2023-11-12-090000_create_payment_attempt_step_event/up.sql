@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE payment_attempt_step_event (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    step VARCHAR(32) NOT NULL,
+    connector VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX payment_attempt_step_event_merchant_id_payment_id_index ON payment_attempt_step_event (merchant_id, payment_id);
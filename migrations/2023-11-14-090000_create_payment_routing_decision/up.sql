@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE payment_routing_decision (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    decision VARCHAR(32) NOT NULL,
+    connector VARCHAR(64) NOT NULL,
+    routing_rule VARCHAR(32),
+    fallback_reason VARCHAR(32),
+    outcome VARCHAR(32),
+    latency_ms BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX payment_routing_decision_merchant_id_payment_id_index ON payment_routing_decision (merchant_id, payment_id);
+
+CREATE INDEX payment_routing_decision_merchant_id_created_at_index ON payment_routing_decision (merchant_id, created_at);
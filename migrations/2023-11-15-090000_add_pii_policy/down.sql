@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_account
+DROP COLUMN IF EXISTS pii_policy;
+
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS pii_policy;
+
+ALTER TABLE api_keys
+DROP COLUMN IF EXISTS pii_restricted;
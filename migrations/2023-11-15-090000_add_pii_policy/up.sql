@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE merchant_account
+ADD COLUMN IF NOT EXISTS pii_policy JSONB DEFAULT NULL;
+
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS pii_policy JSONB DEFAULT NULL;
+
+ALTER TABLE api_keys
+ADD COLUMN IF NOT EXISTS pii_restricted BOOLEAN NOT NULL DEFAULT FALSE;
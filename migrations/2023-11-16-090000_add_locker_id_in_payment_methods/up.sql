@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- The reference of the card in the locker, when it differs from the id of the payment method
+ALTER TABLE payment_methods
+ADD COLUMN IF NOT EXISTS locker_id VARCHAR(64) DEFAULT NULL;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- The keys signing the webhooks delivered to the subscriptions of the profile, stored encrypted
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS webhook_auth_details BYTEA DEFAULT NULL;